@@ -0,0 +1,298 @@
+//! Benchmarks of the hot signalling paths of `Medea` media server.
+
+use std::{
+    convert::{TryFrom as _, TryInto as _},
+    rc::Rc,
+    time::Duration,
+};
+
+use criterion::{
+    criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion,
+    Throughput,
+};
+use medea::{
+    api::control::{
+        endpoints::webrtc_publish_endpoint::{
+            AudioSettings, P2pMode, VideoSettings,
+        },
+        member::Credential,
+        refs::SrcUri,
+    },
+    conf,
+    media::{
+        peer::{PeerStateMachine, PeerUpdatesSubscriber},
+        Peer, Stable, WaitLocalSdp, WaitRemoteSdp,
+    },
+    signalling::{
+        elements::{
+            endpoints::webrtc::{WebRtcPlayEndpoint, WebRtcPublishEndpoint},
+            Member,
+        },
+        peers::{build_peers_traffic_watcher, PeersService},
+    },
+    turn::new_turn_auth_service_mock,
+};
+use medea_client_api_proto::{
+    stats::{
+        HighResTimeStamp, RtcOutboundRtpStreamMediaType,
+        RtcOutboundRtpStreamStats, RtcStat, RtcStatsType, StatId,
+    },
+    AudioSettings as ProtoAudioSettings, Direction, Event, MediaSourceKind,
+    MediaType, NegotiationRole, PeerId, PeerUpdate, ServerMsg, Track, TrackId,
+    TrackPatchEvent, VideoSettings as ProtoVideoSettings,
+};
+
+/// Numbers of [`Track`]s (or [`RtcStat`]s) the benchmarks are run with.
+const SIZES: [u32; 3] = [2, 16, 128];
+
+/// [`PeerUpdatesSubscriber`] ignoring all the updates.
+#[derive(Debug)]
+struct NoopPeerUpdatesSubscriber;
+
+impl PeerUpdatesSubscriber for NoopPeerUpdatesSubscriber {
+    fn negotiation_needed(&self, _: PeerId) {}
+
+    fn force_update(&self, _: PeerId, _: Vec<PeerUpdate>) {}
+}
+
+/// Returns the provided number of audio and video [`Track`]s sent to a
+/// single `Member`.
+fn tracks(count: u32) -> Vec<Track> {
+    (0..count)
+        .map(|id| Track {
+            id: TrackId(id),
+            direction: Direction::Send {
+                receivers: vec!["receiver".into()],
+                mid: Some(id.to_string()),
+            },
+            media_type: if id % 2 == 0 {
+                MediaType::Audio(ProtoAudioSettings {
+                    required: true,
+                    opus: None,
+                })
+            } else {
+                MediaType::Video(ProtoVideoSettings {
+                    required: true,
+                    source_kind: MediaSourceKind::Device,
+                })
+            },
+        })
+        .collect()
+}
+
+/// Benchmarks serialization of [`ServerMsg::Event`]s sent to `Client`s.
+fn event_serialization(c: &mut Criterion) {
+    let mut group = c.benchmark_group("event_serialization");
+    for size in SIZES.iter().copied() {
+        let peer_created = ServerMsg::Event {
+            room_id: "room".into(),
+            event: Event::PeerCreated {
+                peer_id: PeerId(1),
+                negotiation_role: NegotiationRole::Offerer,
+                tracks: tracks(size),
+                ice_servers: Vec::new(),
+                force_relay: false,
+            },
+            seq: None,
+        };
+        let peer_updated = ServerMsg::Event {
+            room_id: "room".into(),
+            event: Event::PeerUpdated {
+                peer_id: PeerId(1),
+                updates: (0..size)
+                    .map(|id| {
+                        PeerUpdate::Updated(TrackPatchEvent {
+                            muted: Some(id % 2 == 0),
+                            ..TrackPatchEvent::new(TrackId(id))
+                        })
+                    })
+                    .collect(),
+                negotiation_role: None,
+            },
+            seq: None,
+        };
+
+        group.throughput(Throughput::Elements(u64::from(size)));
+        group.bench_with_input(
+            BenchmarkId::new("PeerCreated", size),
+            &peer_created,
+            |b, msg| b.iter(|| serde_json::to_string(msg).unwrap()),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("PeerUpdated", size),
+            &peer_updated,
+            |b, msg| b.iter(|| serde_json::to_string(msg).unwrap()),
+        );
+    }
+    group.finish();
+}
+
+/// Benchmarks full negotiation cycles of a [`PeerStateMachine`].
+fn peer_transitions(c: &mut Criterion) {
+    /// Negotiates the provided [`Peer`] as an offerer, converting it from
+    /// and back to a [`PeerStateMachine`] on each transition.
+    fn negotiate(peer: PeerStateMachine) -> PeerStateMachine {
+        let peer: Peer<Stable> = peer.try_into().map_err(|(e, _)| e).unwrap();
+        let peer = PeerStateMachine::from(peer.start_as_offerer());
+        let peer: Peer<WaitLocalSdp> =
+            peer.try_into().map_err(|(e, _)| e).unwrap();
+        let peer = PeerStateMachine::from(peer.set_local_offer("offer".into()));
+        let peer: Peer<WaitRemoteSdp> =
+            peer.try_into().map_err(|(e, _)| e).unwrap();
+        PeerStateMachine::from(peer.set_remote_answer("answer".into()))
+    }
+
+    let subscriber: Rc<dyn PeerUpdatesSubscriber> =
+        Rc::new(NoopPeerUpdatesSubscriber);
+    c.bench_function("peer_transitions/negotiation", |b| {
+        b.iter_batched(
+            || {
+                PeerStateMachine::from(Peer::new(
+                    PeerId(1),
+                    "publisher".into(),
+                    PeerId(2),
+                    "receiver".into(),
+                    false,
+                    Rc::clone(&subscriber),
+                ))
+            },
+            negotiate,
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+/// Benchmarks bulk application of [`TrackPatchEvent`]s via
+/// [`TrackPatchEvent::merge()`].
+fn track_patches_merge(c: &mut Criterion) {
+    let mut group = c.benchmark_group("track_patches_merge");
+    for size in SIZES.iter().copied() {
+        let patches: Vec<_> = (0..size)
+            .map(|i| TrackPatchEvent {
+                enabled_individual: Some(i % 2 == 0),
+                enabled_general: Some(i % 3 == 0),
+                muted: Some(i % 5 == 0),
+                ..TrackPatchEvent::new(TrackId(i % 4))
+            })
+            .collect();
+
+        group.throughput(Throughput::Elements(u64::from(size)));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &patches,
+            |b, patches| {
+                b.iter(|| {
+                    let mut merged: Vec<_> = (0..4)
+                        .map(|id| TrackPatchEvent::new(TrackId(id)))
+                        .collect();
+                    for patch in patches {
+                        for target in &mut merged {
+                            target.merge(patch);
+                        }
+                    }
+                    merged
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Benchmarks ingestion of [`RtcStat`]s reported by `Client`s.
+fn stats_ingestion(c: &mut Criterion) {
+    /// Returns [`PeersService`] with a single pair of interconnected `Peer`s.
+    async fn peers_service() -> (Rc<PeersService>, PeerId) {
+        let media_conf = conf::Media::default();
+        let peers = PeersService::new(
+            "test".into(),
+            new_turn_auth_service_mock(),
+            build_peers_traffic_watcher(&media_conf),
+            &media_conf,
+            Rc::new(NoopPeerUpdatesSubscriber),
+        );
+
+        let member = |id: &str| {
+            Member::new(
+                id.into(),
+                Credential::Plain("test".into()),
+                "test".into(),
+                Duration::from_secs(10),
+                Duration::from_secs(10),
+                Duration::from_secs(5),
+            )
+        };
+        let publisher = member("publisher");
+        let receiver = member("receiver");
+        let publish = WebRtcPublishEndpoint::new(
+            "publish".to_string().into(),
+            P2pMode::Always,
+            publisher.downgrade(),
+            false,
+            AudioSettings::default(),
+            VideoSettings::default(),
+        );
+        let play = WebRtcPlayEndpoint::new(
+            "play-publisher".to_string().into(),
+            SrcUri::try_from("local://test/publisher/publish".to_string())
+                .unwrap(),
+            publish.downgrade(),
+            receiver.downgrade(),
+            false,
+        );
+
+        let (src_peer_id, _) = Rc::clone(&peers)
+            .connect_endpoints(publish, play)
+            .await
+            .unwrap()
+            .unwrap();
+        (peers, src_peer_id)
+    }
+
+    let mut group = c.benchmark_group("stats_ingestion");
+    let (peers, peer_id) = actix_rt::System::new().block_on(peers_service());
+    for size in SIZES.iter().copied() {
+        let stats: Vec<_> = (0..size)
+            .map(|i| RtcStat {
+                id: StatId(format!("{}-send", i)),
+                timestamp: HighResTimeStamp(0.),
+                stats: RtcStatsType::OutboundRtp(Box::new(
+                    RtcOutboundRtpStreamStats {
+                        track_id: None,
+                        media_type: if i % 2 == 0 {
+                            RtcOutboundRtpStreamMediaType::Audio {
+                                total_samples_sent: None,
+                                voice_activity_flag: None,
+                            }
+                        } else {
+                            RtcOutboundRtpStreamMediaType::Video {
+                                frame_width: None,
+                                frame_height: None,
+                                frames_per_second: None,
+                            }
+                        },
+                        packets_sent: u64::from(i) * 10,
+                        bytes_sent: u64::from(i) * 1000,
+                        media_source_id: None,
+                    },
+                )),
+            })
+            .collect();
+
+        group.throughput(Throughput::Elements(u64::from(size)));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &stats,
+            |b, stats| b.iter(|| peers.add_stats(peer_id, stats)),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    event_serialization,
+    peer_transitions,
+    track_patches_merge,
+    stats_ingestion,
+);
+criterion_main!(benches);
@@ -0,0 +1,393 @@
+//! Command-line tool for operating [Medea] media server via its gRPC
+//! [Control API].
+//!
+//! [Medea]: https://github.com/instrumentisto/medea
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+#![forbid(non_ascii_idents, unsafe_code)]
+
+use std::{
+    collections::HashMap, error::Error as StdError, fmt, fs, io, io::Read as _,
+    time::Duration,
+};
+
+use clap::{
+    app_from_crate, crate_authors, crate_description, crate_name,
+    crate_version, AppSettings, Arg, ArgMatches, SubCommand,
+};
+use medea_control_api_mock::{callback::CallbackItem, proto::Element};
+use medea_control_api_proto::grpc::{
+    api::{self as proto, control_api_client::ControlApiClient},
+    callback::{
+        self as callback_proto,
+        callback_server::{Callback, CallbackServer},
+    },
+};
+use tonic::transport::{Channel, Server};
+
+/// Result of running a single command.
+type Result<T = ()> = std::result::Result<T, Box<dyn StdError>>;
+
+/// Error returned by [Medea] in a [Control API] response.
+///
+/// [Medea]: https://github.com/instrumentisto/medea
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Debug)]
+struct ControlApiError(proto::Error);
+
+impl ControlApiError {
+    /// Fails if the provided [`proto::Error`] is returned.
+    fn check(err: Option<proto::Error>) -> std::result::Result<(), Self> {
+        err.map_or(Ok(()), |e| Err(Self(e)))
+    }
+}
+
+impl fmt::Display for ControlApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Control API error {}: {}", self.0.code, self.0.text)?;
+        if !self.0.element.is_empty() {
+            write!(f, " [element = {}]", self.0.element)?;
+        }
+        Ok(())
+    }
+}
+
+impl StdError for ControlApiError {}
+
+#[tokio::main]
+async fn main() {
+    let opts = app_from_crate!()
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(
+            Arg::with_name("medea_addr")
+                .help("Address of Medea's gRPC Control API.")
+                .default_value("http://127.0.0.1:6565")
+                .long("medea-addr")
+                .short("m"),
+        )
+        .subcommand(
+            SubCommand::with_name("rooms").about("Lists IDs of all the Rooms."),
+        )
+        .subcommand(
+            SubCommand::with_name("get")
+                .about("Prints specs of the Elements with the given FIDs.")
+                .arg(
+                    Arg::with_name("fid")
+                        .help("FIDs of the Elements.")
+                        .multiple(true)
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("create")
+                .about("Creates an Element with the given FID from YAML spec.")
+                .arg(
+                    Arg::with_name("fid")
+                        .help("FID of the Element to create.")
+                        .required(true),
+                )
+                .arg(
+                    Arg::with_name("file")
+                        .help("YAML spec file to read, or `-` for stdin.")
+                        .default_value("-")
+                        .long("file")
+                        .short("f"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("export")
+                .about("Prints versioned YAML spec of the given Room.")
+                .arg(
+                    Arg::with_name("fid")
+                        .help("FID of the Room.")
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("import")
+                .about("Applies versioned YAML spec of a Room.")
+                .arg(
+                    Arg::with_name("file")
+                        .help("YAML spec file to read, or `-` for stdin.")
+                        .default_value("-")
+                        .long("file")
+                        .short("f"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("delete")
+                .about("Deletes the Elements with the given FIDs.")
+                .arg(
+                    Arg::with_name("fid")
+                        .help("FIDs of the Elements.")
+                        .multiple(true)
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("callbacks")
+                .about("Serves Control API callbacks printing them as JSON.")
+                .arg(
+                    Arg::with_name("addr")
+                        .help("Address to serve gRPC Callback service on.")
+                        .default_value("0.0.0.0:9099")
+                        .long("addr")
+                        .short("a"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("drain")
+                .about("Puts Medea into drain mode printing its progress.")
+                .arg(
+                    Arg::with_name("shutdown")
+                        .help("Shut down Medea gracefully once it's drained.")
+                        .long("shutdown"),
+                )
+                .arg(
+                    Arg::with_name("wait")
+                        .help("Wait until all the Members disconnect.")
+                        .long("wait")
+                        .short("w"),
+                ),
+        )
+        .get_matches();
+
+    if let Err(e) = run(&opts).await {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Runs the subcommand specified in the provided [`ArgMatches`].
+async fn run(opts: &ArgMatches<'static>) -> Result {
+    let (name, args) = opts.subcommand();
+    let args = args.unwrap();
+    if name == "callbacks" {
+        return tail_callbacks(args.value_of("addr").unwrap()).await;
+    }
+
+    let addr = opts.value_of("medea_addr").unwrap().to_owned();
+    let mut client = ControlApiClient::connect(addr).await?;
+    match name {
+        "rooms" => list_rooms(&mut client).await,
+        "get" => get(&mut client, fids(args)).await,
+        "create" => {
+            create(
+                &mut client,
+                args.value_of("fid").unwrap(),
+                args.value_of("file").unwrap(),
+            )
+            .await
+        }
+        "export" => export(&mut client, args.value_of("fid").unwrap()).await,
+        "import" => import(&mut client, args.value_of("file").unwrap()).await,
+        "delete" => delete(&mut client, fids(args)).await,
+        "drain" => {
+            drain(
+                &mut client,
+                args.is_present("shutdown"),
+                args.is_present("wait"),
+            )
+            .await
+        }
+        _ => unreachable!("unknown subcommand: {}", name),
+    }
+}
+
+/// Returns all the FIDs provided in the [`ArgMatches`].
+fn fids(args: &ArgMatches<'_>) -> Vec<String> {
+    args.values_of("fid")
+        .unwrap()
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+/// Requests specs of the Elements with the provided FIDs (or all the `Room`s
+/// if none provided).
+async fn get_elements(
+    client: &mut ControlApiClient<Channel>,
+    fid: Vec<String>,
+) -> Result<HashMap<String, proto::Element>> {
+    let resp = client.get(proto::IdRequest { fid }).await?.into_inner();
+    ControlApiError::check(resp.error)?;
+    Ok(resp.elements)
+}
+
+/// Prints IDs of all the `Room`s, one per line.
+async fn list_rooms(client: &mut ControlApiClient<Channel>) -> Result {
+    let mut ids: Vec<_> = get_elements(client, Vec::new())
+        .await?
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    ids.sort();
+    for id in ids {
+        println!("{}", id);
+    }
+    Ok(())
+}
+
+/// Prints specs of the Elements with the provided FIDs as YAML.
+async fn get(
+    client: &mut ControlApiClient<Channel>,
+    fids: Vec<String>,
+) -> Result {
+    let elements: HashMap<_, _> = get_elements(client, fids)
+        .await?
+        .into_iter()
+        .map(|(fid, el)| (fid, Element::from(el)))
+        .collect();
+    print!("{}", serde_yaml::to_string(&elements)?);
+    Ok(())
+}
+
+/// Creates an Element with the provided FID from its YAML spec read from the
+/// provided file (or stdin if `-`), and prints SIDs of the created `Member`s.
+async fn create(
+    client: &mut ControlApiClient<Channel>,
+    fid: &str,
+    file: &str,
+) -> Result {
+    use proto::create_request::El;
+
+    let spec = read_spec(file)?;
+    let (parent_fid, id) = fid.rsplit_once('/').unwrap_or(("", fid));
+    let id = id.to_owned();
+    let el = match serde_yaml::from_str(&spec)? {
+        Element::Room(room) => El::Room(room.into_proto(id)),
+        Element::Member(member) => El::Member(member.into_proto(id)),
+        Element::WebRtcPlayEndpoint(play) => {
+            El::WebrtcPlay(play.into_proto(id))
+        }
+        Element::WebRtcPublishEndpoint(publish) => {
+            El::WebrtcPub(publish.into_proto(id))
+        }
+    };
+
+    let resp = client
+        .create(proto::CreateRequest {
+            parent_fid: parent_fid.to_owned(),
+            el: Some(el),
+            on_progress: String::new(),
+        })
+        .await?
+        .into_inner();
+    ControlApiError::check(resp.error)?;
+
+    print_sids(resp.sid);
+    Ok(())
+}
+
+/// Prints versioned YAML spec of the `Room` with the provided FID.
+async fn export(client: &mut ControlApiClient<Channel>, fid: &str) -> Result {
+    let resp = client
+        .export_spec(proto::ExportSpecRequest {
+            room_fid: fid.to_owned(),
+        })
+        .await?
+        .into_inner();
+    ControlApiError::check(resp.error)?;
+    print!("{}", resp.spec);
+    Ok(())
+}
+
+/// Applies versioned YAML spec of a `Room` read from the provided file (or
+/// stdin if `-`), and prints SIDs of its `Member`s.
+async fn import(client: &mut ControlApiClient<Channel>, file: &str) -> Result {
+    let resp = client
+        .import_spec(proto::ImportSpecRequest {
+            spec: read_spec(file)?,
+        })
+        .await?
+        .into_inner();
+    ControlApiError::check(resp.error)?;
+    print_sids(resp.sid);
+    Ok(())
+}
+
+/// Reads YAML spec from the provided file (or stdin if `-`).
+fn read_spec(file: &str) -> io::Result<String> {
+    if file == "-" {
+        let mut spec = String::new();
+        io::stdin().read_to_string(&mut spec)?;
+        Ok(spec)
+    } else {
+        fs::read_to_string(file)
+    }
+}
+
+/// Prints the provided SIDs of `Member`s ordered by their IDs.
+fn print_sids(sids: HashMap<String, String>) {
+    let mut sids: Vec<_> = sids.into_iter().collect();
+    sids.sort();
+    for (member_id, sid) in sids {
+        println!("{}: {}", member_id, sid);
+    }
+}
+
+/// Deletes the Elements with the provided FIDs.
+async fn delete(
+    client: &mut ControlApiClient<Channel>,
+    fid: Vec<String>,
+) -> Result {
+    let resp = client.delete(proto::IdRequest { fid }).await?.into_inner();
+    ControlApiError::check(resp.error)?;
+    Ok(())
+}
+
+/// Puts [Medea] into drain mode and prints its progress, polling it each
+/// second until all the `Member`s disconnect, if `wait` is `true`.
+///
+/// [Medea]: https://github.com/instrumentisto/medea
+async fn drain(
+    client: &mut ControlApiClient<Channel>,
+    shutdown: bool,
+    wait: bool,
+) -> Result {
+    loop {
+        let resp = client
+            .drain(proto::DrainRequest { shutdown })
+            .await?
+            .into_inner();
+        ControlApiError::check(resp.error)?;
+        println!("rooms: {}, members: {}", resp.rooms, resp.members);
+
+        if !wait || resp.members == 0 {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+/// [`Callback`] gRPC service printing all the received callbacks to stdout
+/// as JSON lines.
+#[derive(Clone, Copy, Debug)]
+struct CallbackPrinter;
+
+#[tonic::async_trait]
+impl Callback for CallbackPrinter {
+    async fn on_event(
+        &self,
+        req: tonic::Request<callback_proto::Request>,
+    ) -> std::result::Result<
+        tonic::Response<callback_proto::Response>,
+        tonic::Status,
+    > {
+        let item = CallbackItem::from(req.into_inner());
+        match serde_json::to_string(&item) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("Failed to serialize callback: {}", e),
+        }
+        Ok(tonic::Response::new(callback_proto::Response {}))
+    }
+}
+
+/// Serves gRPC [`Callback`] service on the provided address, printing all the
+/// received callbacks until terminated.
+async fn tail_callbacks(addr: &str) -> Result {
+    Server::builder()
+        .add_service(CallbackServer::new(CallbackPrinter))
+        .serve(addr.parse()?)
+        .await?;
+    Ok(())
+}
@@ -7,17 +7,20 @@ use synstructure::{BindStyle, Structure};
 ///
 /// # Algorithm
 ///
-/// 1. Generate body for trait method `name()` as enum variant name "as is".
-/// 2. Generate body for trait method `js_cause()`:
+/// 1. Generate body for trait method `code()` as enum name "as is".
+/// 2. Generate body for trait method `name()` as enum variant name "as is".
+/// 3. Generate body for trait method `js_cause()`:
 ///     - if `enum` variant contains associated error, returns this error;
 ///     - if `enum` variant contains `JsCaused`, invoke its trait method;
 ///     - otherwise returns `None`.
-/// 3. Generate implementation of `JsCaused` trait for this enum with generated
-///    methods from step 1 and 2.
+/// 4. Generate implementation of `JsCaused` trait for this enum with generated
+///    methods from steps 1, 2 and 3.
 #[allow(clippy::needless_pass_by_value)]
 pub fn derive(mut s: Structure) -> Result<TokenStream> {
     let error_type = error_type(&s)?;
 
+    let code = &s.ast().ident;
+
     let name_body = s.each_variant(|v| {
         let name = &v.ast().ident;
         quote!(stringify!(#name))
@@ -42,6 +45,10 @@ pub fn derive(mut s: Structure) -> Result<TokenStream> {
         gen impl JsCaused for @Self {
             type Error = #error_type;
 
+            fn code(&self) -> &'static str {
+                stringify!(#code)
+            }
+
             fn name(&self) -> &'static str {
                 match self { #name_body }
             }
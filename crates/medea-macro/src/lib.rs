@@ -397,6 +397,7 @@ decl_derive!([JsCaused, attributes(js)] =>
 /// }
 ///
 /// let err = FooError::Internal;
+/// assert_eq!(err.code(), "FooError");
 /// assert_eq!(err.name(), "Internal");
 /// assert!(err.js_cause().is_none());
 ///
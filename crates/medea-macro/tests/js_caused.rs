@@ -10,6 +10,7 @@ fn derives_for_structure() {
     struct TestError;
 
     let err = TestError;
+    assert_eq!(err.code(), "TestError");
     assert_eq!(err.name(), "TestError");
     assert!(err.js_cause().is_none());
 }
@@ -23,6 +24,7 @@ fn derives_for_enum_with_js_error() {
     }
 
     let err = TestError::Foo;
+    assert_eq!(err.code(), "TestError");
     assert_eq!(err.name(), "Foo");
     assert!(err.js_cause().is_none());
 
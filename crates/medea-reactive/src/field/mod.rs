@@ -4,6 +4,7 @@
 
 pub mod cell;
 pub mod progressable_cell;
+pub mod rw_lock;
 
 use std::{
     cell::RefCell,
@@ -22,7 +23,11 @@ use crate::subscribers_store::{
 };
 
 #[doc(inline)]
-pub use self::{cell::ObservableCell, progressable_cell::ProgressableCell};
+pub use self::{
+    cell::ObservableCell,
+    progressable_cell::ProgressableCell,
+    rw_lock::{ObservableRwLock, ObservableRwLockWriteGuard},
+};
 
 /// Default type of [`ObservableField`] subscribers.
 type DefaultSubscribers<D> = RefCell<Vec<UniversalSubscriber<D>>>;
@@ -0,0 +1,417 @@
+//! Implementation of the observable analogue of the asynchronous [`RwLock`],
+//! which can be shared between threads.
+//!
+//! Subscription to changes works the same way as [`ObservableField`], but
+//! returned [`Stream`]s and [`Future`]s are [`Send`].
+//!
+//! [`Future`]: std::future::Future
+//! [`ObservableField`]: crate::ObservableField
+//! [`Stream`]: futures::Stream
+
+#![allow(clippy::module_name_repetitions)]
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use futures::{
+    channel::{mpsc, oneshot},
+    future::{self, BoxFuture},
+    stream::{self, BoxStream, StreamExt as _},
+};
+use tokio::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use super::DroppedError;
+
+/// Subscriber of an [`ObservableRwLock`].
+enum Subscriber<D> {
+    /// Subscriber for [`ObservableRwLock::when`].
+    When {
+        /// [`oneshot::Sender`] with which [`ObservableRwLock::when`]'s
+        /// [`Future`] will resolve.
+        ///
+        /// [`Future`]: std::future::Future
+        sender: oneshot::Sender<()>,
+
+        /// Function with which will be checked that
+        /// [`ObservableRwLock::when`]'s [`Future`] should resolve.
+        ///
+        /// [`Future`]: std::future::Future
+        assert_fn: Box<dyn Fn(&D) -> bool + Send>,
+    },
+
+    /// Subscriber for data updates.
+    Subscribe(mpsc::UnboundedSender<D>),
+}
+
+impl<D> fmt::Debug for Subscriber<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Subscriber::When { .. } => write!(f, "Subscriber::When"),
+            Subscriber::Subscribe(_) => write!(f, "Subscriber::Subscribe"),
+        }
+    }
+}
+
+/// Observable analogue of asynchronous [`RwLock`], which can be shared between
+/// threads.
+///
+/// Acquiring the lock never blocks the current thread, but yields to the
+/// executor instead.
+///
+/// All the subscribers are notified when an [`ObservableRwLockWriteGuard`] is
+/// dropped, if the underlying data __really__ changed. This is checked with
+/// [`PartialEq`] implementation of the underlying data.
+///
+/// # Usage
+///
+/// ```
+/// # use std::{sync::Arc, thread};
+/// # use futures::{executor::block_on, StreamExt as _};
+/// use medea_reactive::ObservableRwLock;
+///
+/// let lock = Arc::new(ObservableRwLock::new(0i32));
+/// let mut updates = block_on(lock.subscribe());
+/// assert_eq!(block_on(updates.next()), Some(0));
+///
+/// let writer = Arc::clone(&lock);
+/// thread::spawn(move || block_on(writer.set(100))).join().unwrap();
+///
+/// assert_eq!(block_on(updates.next()), Some(100));
+/// assert_eq!(*block_on(lock.read()), 100);
+/// ```
+pub struct ObservableRwLock<D> {
+    /// Data stored by this [`ObservableRwLock`].
+    data: RwLock<D>,
+
+    /// Subscribers to [`ObservableRwLock`]'s data mutations.
+    ///
+    /// Always locked after the `data`.
+    subs: Mutex<Vec<Subscriber<D>>>,
+}
+
+impl<D: fmt::Debug> fmt::Debug for ObservableRwLock<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservableRwLock")
+            .field("data", &self.data)
+            .field("subs", &*self.subs())
+            .finish()
+    }
+}
+
+impl<D> ObservableRwLock<D> {
+    /// Returns new [`ObservableRwLock`] with subscribable mutations.
+    #[inline]
+    pub fn new(data: D) -> Self {
+        Self {
+            data: RwLock::new(data),
+            subs: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Locks this [`ObservableRwLock`] with shared read access, waiting until
+    /// it can be acquired.
+    #[inline]
+    pub async fn read(&self) -> RwLockReadGuard<'_, D> {
+        self.data.read().await
+    }
+
+    /// Returns locked subscribers of this [`ObservableRwLock`].
+    ///
+    /// Never held across `.await` points, so doesn't block for long.
+    #[inline]
+    fn subs(&self) -> MutexGuard<'_, Vec<Subscriber<D>>> {
+        self.subs.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns [`Future`] which will resolve only on modifications that
+    /// the given `assert_fn` returns `true` on.
+    ///
+    /// Resolves immediately if the current data satisfies `assert_fn`
+    /// already.
+    ///
+    /// [`Future`]: std::future::Future
+    pub async fn when<F>(
+        &self,
+        assert_fn: F,
+    ) -> BoxFuture<'static, Result<(), DroppedError>>
+    where
+        F: Fn(&D) -> bool + Send + 'static,
+    {
+        let data = self.read().await;
+        if (assert_fn)(&data) {
+            return Box::pin(future::ok(()));
+        }
+        let (sender, rx) = oneshot::channel();
+        self.subs().push(Subscriber::When {
+            sender,
+            assert_fn: Box::new(assert_fn),
+        });
+        drop(data);
+
+        Box::pin(async move { Ok(rx.await?) })
+    }
+}
+
+impl<D> ObservableRwLock<D>
+where
+    D: PartialEq + Send + 'static,
+{
+    /// Returns [`Future`] which will resolve only when data of this
+    /// [`ObservableRwLock`] will become equal to the provided `should_be`
+    /// value.
+    ///
+    /// [`Future`]: std::future::Future
+    #[inline]
+    pub async fn when_eq(
+        &self,
+        should_be: D,
+    ) -> BoxFuture<'static, Result<(), DroppedError>> {
+        self.when(move |data| data == &should_be).await
+    }
+}
+
+impl<D> ObservableRwLock<D>
+where
+    D: Clone + Send + 'static,
+{
+    /// Returns copy of an underlying data.
+    #[inline]
+    pub async fn get(&self) -> D {
+        self.read().await.clone()
+    }
+
+    /// Returns [`Stream`] into which the current data and all its further
+    /// updates will be emitted.
+    ///
+    /// [`Stream`]: futures::Stream
+    pub async fn subscribe(&self) -> BoxStream<'static, D> {
+        let data = self.read().await;
+        let (tx, rx) = mpsc::unbounded();
+        self.subs().push(Subscriber::Subscribe(tx));
+        let current = data.clone();
+        drop(data);
+
+        Box::pin(stream::once(async move { current }).chain(rx))
+    }
+}
+
+impl<D> ObservableRwLock<D>
+where
+    D: Clone + PartialEq,
+{
+    /// Locks this [`ObservableRwLock`] with exclusive write access, waiting
+    /// until it can be acquired.
+    ///
+    /// Subscribers are notified when the returned
+    /// [`ObservableRwLockWriteGuard`] is dropped, if the data was changed.
+    #[inline]
+    pub async fn write(&self) -> ObservableRwLockWriteGuard<'_, D> {
+        let data = self.data.write().await;
+        ObservableRwLockWriteGuard {
+            value_before_mutation: data.clone(),
+            data,
+            subs: &self.subs,
+        }
+    }
+
+    /// Sets the `new_data` value as an underlying data.
+    #[inline]
+    pub async fn set(&self, new_data: D) {
+        *self.write().await = new_data;
+    }
+
+    /// Replaces the contained underlying data with the given `new_data` value,
+    /// and returns the old one.
+    #[inline]
+    pub async fn replace(&self, mut new_data: D) -> D {
+        std::mem::swap(&mut *self.write().await, &mut new_data);
+        new_data
+    }
+}
+
+/// Mutable [`ObservableRwLock`] reference returned by
+/// [`ObservableRwLock::write`].
+///
+/// When this guard is [`Drop`]ped, a check for modifications will be performed.
+/// If data was changed, then all the subscribers of the [`ObservableRwLock`]
+/// are notified before the lock is released.
+pub struct ObservableRwLockWriteGuard<'a, D: Clone + PartialEq> {
+    /// Data stored by the [`ObservableRwLock`].
+    data: RwLockWriteGuard<'a, D>,
+
+    /// Subscribers to [`ObservableRwLock`]'s data mutations.
+    subs: &'a Mutex<Vec<Subscriber<D>>>,
+
+    /// Data stored by the [`ObservableRwLock`] before mutation.
+    value_before_mutation: D,
+}
+
+impl<'a, D> fmt::Debug for ObservableRwLockWriteGuard<'a, D>
+where
+    D: Clone + fmt::Debug + PartialEq,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ObservableRwLockWriteGuard")
+            .field("data", &*self.data)
+            .field("value_before_mutation", &self.value_before_mutation)
+            .finish()
+    }
+}
+
+impl<'a, D: Clone + PartialEq> Deref for ObservableRwLockWriteGuard<'a, D> {
+    type Target = D;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl<'a, D: Clone + PartialEq> DerefMut for ObservableRwLockWriteGuard<'a, D> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.data
+    }
+}
+
+impl<'a, D: Clone + PartialEq> Drop for ObservableRwLockWriteGuard<'a, D> {
+    fn drop(&mut self) {
+        if *self.data == self.value_before_mutation {
+            return;
+        }
+        let data = &*self.data;
+        let mut subs = self.subs.lock().unwrap_or_else(PoisonError::into_inner);
+        *subs = subs
+            .drain(..)
+            .filter_map(|sub| match sub {
+                Subscriber::When { sender, assert_fn } => {
+                    if (assert_fn)(data) {
+                        let _ = sender.send(());
+                        None
+                    } else {
+                        Some(Subscriber::When { sender, assert_fn })
+                    }
+                }
+                Subscriber::Subscribe(sender) => sender
+                    .unbounded_send(data.clone())
+                    .ok()
+                    .map(|()| Subscriber::Subscribe(sender)),
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod observable_rw_lock {
+    use std::{sync::Arc, thread, time::Duration};
+
+    use futures::{executor::block_on, StreamExt as _};
+    use tokio::time::timeout;
+
+    use crate::ObservableRwLock;
+
+    #[tokio::test]
+    async fn subscription() {
+        let lock = ObservableRwLock::new(0);
+        let subscription = lock.subscribe().await;
+
+        lock.set(100).await;
+        assert_eq!(subscription.skip(1).next().await.unwrap(), 100);
+    }
+
+    #[tokio::test]
+    async fn notifies_once_write_guard_dropped() {
+        let lock = ObservableRwLock::new(0);
+        let mut subscription = lock.subscribe().await.skip(1);
+
+        let mut guard = lock.write().await;
+        *guard = 1;
+        *guard = 2;
+        let _ =
+            timeout(Duration::from_millis(10), Box::pin(subscription.next()))
+                .await
+                .unwrap_err();
+
+        drop(guard);
+        assert_eq!(subscription.next().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn no_update_if_data_unchanged() {
+        let lock = ObservableRwLock::new(0);
+        let mut subscription = lock.subscribe().await.skip(1);
+
+        *lock.write().await = 0;
+        let _ =
+            timeout(Duration::from_millis(10), Box::pin(subscription.next()))
+                .await
+                .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn when_eq() {
+        let lock = ObservableRwLock::new(0);
+        let when_will_be_5 = lock.when_eq(5).await;
+
+        lock.set(5).await;
+        timeout(Duration::from_millis(50), when_will_be_5)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn when_resolves_on_current_data() {
+        let lock = ObservableRwLock::new(6);
+
+        timeout(Duration::from_millis(50), lock.when(|data| *data > 5).await)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn when_errors_on_drop() {
+        let lock = ObservableRwLock::new(0);
+        let when_will_be_5 = lock.when_eq(5).await;
+
+        drop(lock);
+        assert!(when_will_be_5.await.is_err());
+    }
+
+    #[tokio::test]
+    async fn read_waits_for_write_guard() {
+        let lock = ObservableRwLock::new(0);
+
+        let guard = lock.write().await;
+        let _ = timeout(Duration::from_millis(10), lock.read())
+            .await
+            .unwrap_err();
+
+        drop(guard);
+        assert_eq!(*lock.read().await, 0);
+    }
+
+    #[tokio::test]
+    async fn updates_from_other_threads() {
+        let lock = Arc::new(ObservableRwLock::new(0));
+        let subscription = lock.subscribe().await;
+
+        let writers: Vec<_> = (1..=4)
+            .map(|i| {
+                let lock = Arc::clone(&lock);
+                thread::spawn(move || *block_on(lock.write()) += i)
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        assert_eq!(lock.get().await, 10);
+        let updates: Vec<_> = subscription.take(5).collect().await;
+        assert_eq!(updates.last(), Some(&10));
+    }
+}
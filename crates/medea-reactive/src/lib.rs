@@ -34,7 +34,8 @@ pub use crate::{
     },
     field::{
         cell::ObservableCell, DroppedError, MutObservableFieldGuard,
-        Observable, ObservableField, OnObservableFieldModification,
+        Observable, ObservableField, ObservableRwLock,
+        ObservableRwLockWriteGuard, OnObservableFieldModification,
         Progressable, ProgressableCell, UniversalSubscriber, Whenable,
     },
     subscribers_store::progressable::processed::{
@@ -1,8 +1,13 @@
 //! `Connection` JS object's representation.
 
+use serde::Deserialize;
+
 use crate::{
     browser::Statement,
-    object::{tracks_store, Object},
+    object::{
+        debug_dump::{PeerDump, RoomDump},
+        tracks_store, Object,
+    },
 };
 
 use super::Error;
@@ -27,6 +32,51 @@ impl Object<Connection> {
         .await
     }
 
+    /// Returns [`PeerDump`]s of the `PeerConnection`s exchanging media with
+    /// the remote `Member` of this [`Connection`], exposing their current
+    /// SDPs, selected ICE candidate pairs and stats.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to parse the `Room.export_debug_dump()` result.
+    pub async fn peers(&self) -> Result<Vec<PeerDump>, Error> {
+        /// Debug dump of the `Room` along with the ID of the remote `Member`
+        /// of a [`Connection`].
+        #[derive(Deserialize)]
+        struct Dump {
+            /// ID of the remote `Member` of the [`Connection`].
+            member_id: String,
+
+            /// `Room.export_debug_dump()` result.
+            dump: String,
+        }
+
+        let dump: Dump = serde_json::from_value(
+            self.execute(Statement::new(
+                // language=JavaScript
+                r#"
+                    async (conn) => {
+                        return {
+                            member_id: conn.conn.get_remote_member_id(),
+                            dump: await conn.room.export_debug_dump(),
+                        };
+                    }
+                "#,
+                [],
+            ))
+            .await?,
+        )
+        .map_err(|_| Error::TypeCast)?;
+        let room = serde_json::from_str::<RoomDump>(&dump.dump)
+            .map_err(|_| Error::TypeCast)?;
+        Ok(room
+            .peers
+            .into_iter()
+            .filter(|peer| peer.is_connected_with(&dump.member_id))
+            .collect())
+    }
+
     /// Returns a [`Future`] resolving when `Connection.on_close()` callback is
     /// fired.
     ///
@@ -0,0 +1,175 @@
+//! Parsed `RoomHandle.export_debug_dump()` results, exposing the negotiated
+//! SDPs and stats of `PeerConnection`s to tests.
+
+use std::collections::HashMap;
+
+use medea_client_api_proto::stats::{
+    KnownCandidateType, KnownIceCandidatePairState, NonExhaustive,
+    RtcIceCandidateStats, RtcStat, RtcStatsType, StatId,
+};
+use serde::Deserialize;
+
+/// Debug dump of a whole `Room`.
+#[derive(Clone, Debug, Deserialize)]
+pub(super) struct RoomDump {
+    /// [`PeerDump`]s of all the `PeerConnection`s of the `Room`.
+    pub(super) peers: Vec<PeerDump>,
+}
+
+/// Debug dump of a single `PeerConnection`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct PeerDump {
+    /// ID of the `PeerConnection`.
+    pub id: u32,
+
+    /// State of the `PeerConnection`, if it's known already.
+    state: Option<PeerState>,
+
+    /// All the latest [`RtcStat`]s of the `PeerConnection`.
+    pub stats: Vec<RtcStat>,
+}
+
+/// State of a `PeerConnection` as it's present in its [`PeerDump`].
+#[derive(Clone, Debug, Deserialize)]
+struct PeerState {
+    /// Tracks sent by the `PeerConnection`.
+    senders: HashMap<String, SenderState>,
+
+    /// Tracks received by the `PeerConnection`.
+    receivers: HashMap<String, ReceiverState>,
+
+    /// Current local SDP of the `PeerConnection`.
+    local_sdp: Option<String>,
+
+    /// Current remote SDP of the `PeerConnection`.
+    remote_sdp: Option<String>,
+}
+
+/// State of a track sent by a `PeerConnection`.
+#[derive(Clone, Debug, Deserialize)]
+struct SenderState {
+    /// IDs of the `Member`s receiving this track.
+    receivers: Vec<String>,
+}
+
+/// State of a track received by a `PeerConnection`.
+#[derive(Clone, Debug, Deserialize)]
+struct ReceiverState {
+    /// ID of the `Member` sending this track.
+    sender_id: String,
+}
+
+/// ICE candidate pair selected by a `PeerConnection` for media transmission.
+#[derive(Clone, Copy, Debug)]
+pub struct SelectedCandidatePair<'a> {
+    /// Stats of the local ICE candidate of this pair.
+    pub local: &'a RtcIceCandidateStats,
+
+    /// Stats of the remote ICE candidate of this pair.
+    pub remote: &'a RtcIceCandidateStats,
+}
+
+impl SelectedCandidatePair<'_> {
+    /// Indicates whether media transmitted via this [`SelectedCandidatePair`]
+    /// is relayed through a TURN server.
+    #[must_use]
+    pub fn is_relayed(&self) -> bool {
+        let relay = NonExhaustive::Known(KnownCandidateType::Relay);
+        self.local.candidate_type == relay
+            || self.remote.candidate_type == relay
+    }
+}
+
+impl PeerDump {
+    /// Returns the current local SDP of this `PeerConnection`, if any.
+    #[inline]
+    #[must_use]
+    pub fn local_sdp(&self) -> Option<&str> {
+        self.state.as_ref()?.local_sdp.as_deref()
+    }
+
+    /// Returns the current remote SDP of this `PeerConnection`, if any.
+    #[inline]
+    #[must_use]
+    pub fn remote_sdp(&self) -> Option<&str> {
+        self.state.as_ref()?.remote_sdp.as_deref()
+    }
+
+    /// Returns names of the codecs present in both the local and the remote
+    /// SDPs of this `PeerConnection` (`opus`, `VP8`, etc.).
+    #[must_use]
+    pub fn negotiated_codecs(&self) -> Vec<String> {
+        let (local, remote) = match (self.local_sdp(), self.remote_sdp()) {
+            (Some(local), Some(remote)) => (codecs(local), codecs(remote)),
+            _ => return Vec::new(),
+        };
+        let mut negotiated: Vec<_> = local
+            .into_iter()
+            .filter(|codec| {
+                remote.iter().any(|c| c.eq_ignore_ascii_case(codec))
+            })
+            .collect();
+        negotiated.sort_unstable();
+        negotiated.dedup();
+        negotiated
+    }
+
+    /// Returns the [`SelectedCandidatePair`] of this `PeerConnection`, if
+    /// there is any.
+    #[must_use]
+    pub fn selected_candidate_pair(&self) -> Option<SelectedCandidatePair<'_>> {
+        let pair = self.stats.iter().find_map(|stat| match &stat.stats {
+            RtcStatsType::CandidatePair(pair)
+                if pair.nominated
+                    && pair.state
+                        == NonExhaustive::Known(
+                            KnownIceCandidatePairState::Succeeded,
+                        ) =>
+            {
+                Some(pair)
+            }
+            _ => None,
+        })?;
+
+        Some(SelectedCandidatePair {
+            local: self.candidate(pair.local_candidate_id.as_ref()?)?,
+            remote: self.candidate(pair.remote_candidate_id.as_ref()?)?,
+        })
+    }
+
+    /// Indicates whether this `PeerConnection` sends media to or receives
+    /// media from the `Member` with the provided ID.
+    pub(super) fn is_connected_with(&self, member_id: &str) -> bool {
+        self.state.as_ref().map_or(false, |state| {
+            state
+                .senders
+                .values()
+                .any(|s| s.receivers.iter().any(|id| id == member_id))
+                || state.receivers.values().any(|r| r.sender_id == member_id)
+        })
+    }
+
+    /// Returns the [`RtcIceCandidateStats`] with the provided [`StatId`].
+    fn candidate(&self, id: &StatId) -> Option<&RtcIceCandidateStats> {
+        self.stats
+            .iter()
+            .find(|stat| &stat.id == id)
+            .and_then(|stat| match &stat.stats {
+                RtcStatsType::LocalCandidate(candidate)
+                | RtcStatsType::RemoteCandidate(candidate) => {
+                    Some(&**candidate)
+                }
+                _ => None,
+            })
+    }
+}
+
+/// Parses names of the codecs from `a=rtpmap` attributes of the provided SDP.
+fn codecs(sdp: &str) -> Vec<String> {
+    sdp.lines()
+        .filter_map(|line| line.strip_prefix("a=rtpmap:"))
+        .filter_map(|rtpmap| rtpmap.split_whitespace().nth(1))
+        .filter_map(|encoding| encoding.split('/').next())
+        .map(ToOwned::to_owned)
+        .collect()
+}
@@ -2,6 +2,7 @@
 
 pub mod connection;
 pub mod connections_store;
+pub mod debug_dump;
 pub mod jason;
 pub mod local_track;
 pub mod remote_track;
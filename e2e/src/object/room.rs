@@ -4,7 +4,11 @@ use std::{borrow::Cow, str::FromStr};
 
 use crate::{
     browser::Statement,
-    object::{connections_store::ConnectionStore, tracks_store, Object},
+    object::{
+        connections_store::ConnectionStore,
+        debug_dump::{PeerDump, RoomDump},
+        tracks_store, Object,
+    },
 };
 
 use super::{AwaitCompletion, Error};
@@ -362,6 +366,7 @@ impl Object<Room> {
                         };
                         let connection = {
                             conn: conn,
+                            room: r.room,
                             tracksStore: tracksStore,
                             closeListener: closeListener,
                         };
@@ -453,6 +458,27 @@ impl Object<Room> {
         .await
     }
 
+    /// Returns [`PeerDump`]s of all the `PeerConnection`s of this [`Room`],
+    /// exposing their current SDPs, selected ICE candidate pairs and stats.
+    ///
+    /// # Errors
+    ///
+    /// - If failed to execute JS statement.
+    /// - If failed to parse the `Room.export_debug_dump()` result.
+    pub async fn peers(&self) -> Result<Vec<PeerDump>, Error> {
+        let dump = self
+            .execute(Statement::new(
+                // language=JavaScript
+                r#"async (r) => await r.room.export_debug_dump()"#,
+                [],
+            ))
+            .await?;
+        let dump = dump.as_str().ok_or(Error::TypeCast)?;
+        serde_json::from_str::<RoomDump>(dump)
+            .map(|dump| dump.peers)
+            .map_err(|_| Error::TypeCast)
+    }
+
     /// Waits for the `Room.on_close()` callback to fire.
     ///
     /// # Errors
@@ -540,6 +566,7 @@ impl Object<Room> {
     pub async fn set_local_media_settings(
         &self,
         video: bool,
+        display_video: bool,
         audio: bool,
     ) -> Result<(), Error> {
         self.forget_local_tracks().await;
@@ -547,13 +574,18 @@ impl Object<Room> {
             // language=JavaScript
             r#"
                 async (room) => {
-                    const [video, audio] = args;
+                    const [video, displayVideo, audio] = args;
                     let constraints = new rust.MediaStreamSettings();
                     if (video) {
                         let video =
                             new window.rust.DeviceVideoTrackConstraints();
                         constraints.device_video(video);
                     }
+                    if (displayVideo) {
+                        let video =
+                            new window.rust.DisplayVideoTrackConstraints();
+                        constraints.display_video(video);
+                    }
                     if (audio) {
                         let audio = new window.rust.AudioTrackConstraints();
                         constraints.audio(audio);
@@ -565,7 +597,7 @@ impl Object<Room> {
                     );
                 }
             "#,
-            [video.into(), audio.into()],
+            [video.into(), display_video.into(), audio.into()],
         ))
         .await
         .map(drop)
@@ -0,0 +1,9 @@
+Feature: Device and display video publishing
+
+  Scenario: Member publishes device and display video simultaneously
+    Given room with joined members Alice and Bob
+    When Alice enables device and display video in local media settings
+    Then Alice has local device video
+    And Alice has local display video
+    And Bob's device video remote track from Alice is enabled
+    And Bob's display video remote track from Alice is enabled
@@ -7,6 +7,13 @@ Feature: `on_new_connection` callback
     Then Alice receives connection with Bob
     And Bob receives connection with Alice
 
+  Scenario: Member joined with enabled media negotiates codecs
+    Given room with joined member Alice
+    And member Bob
+    When Bob joins the room
+    Then Alice's connection with Bob negotiates opus codec
+    And Bob's connection with Alice negotiates VP8 codec
+
   Scenario: Member joined with disabled media
     Given room with member Alice with disabled media publishing
     And joined member Bob
@@ -1,4 +1,7 @@
+use std::time::Duration;
+
 use cucumber_rust::then;
+use tokio::time::sleep;
 
 use crate::world::World;
 
@@ -42,3 +45,32 @@ async fn then_connection_closes(
         member.connections().get(partner_id).await.unwrap().unwrap();
     assert!(connection.wait_for_close().await.is_ok());
 }
+
+#[then(regex = r"^(\S+)'s connection with (\S+) negotiates (\S+) codec$")]
+async fn then_connection_negotiates_codec(
+    world: &mut World,
+    id: String,
+    partner_id: String,
+    codec: String,
+) {
+    let member = world.get_member(&id).unwrap();
+    let connection =
+        member.connections().get(partner_id).await.unwrap().unwrap();
+
+    let mut codecs = Vec::new();
+    for _ in 0..10 {
+        codecs = connection
+            .peers()
+            .await
+            .unwrap()
+            .iter()
+            .flat_map(|peer| peer.negotiated_codecs())
+            .collect();
+        if codecs.iter().any(|c| c.eq_ignore_ascii_case(&codec)) {
+            return;
+        }
+        sleep(Duration::from_millis(300)).await;
+    }
+
+    panic!("{} codec is not negotiated, only {:?} are", codec, codecs);
+}
@@ -27,9 +27,11 @@ async fn when_interconnects_kind(
 ) {
     let send_video = kind.contains("video").then(|| VideoSettings {
         publish_policy: proto::PublishPolicy::Optional,
+        display_publish_policy: proto::PublishPolicy::Optional,
     });
     let send_audio = kind.contains("audio").then(|| AudioSettings {
         publish_policy: proto::PublishPolicy::Optional,
+        opus: None,
     });
 
     world
@@ -44,8 +44,8 @@ async fn given_member_gum_will_error(
     media_devices.mock_gum(video, audio).await;
 }
 
-#[when(regex = "^(\\S+) enables (video|audio|video and audio) in local \
-                 media settings$")]
+#[when(regex = "^(\\S+) enables (video|audio|video and audio|device and \
+                 display video) in local media settings$")]
 async fn when_member_enables_via_local_media_settings(
     world: &mut World,
     id: String,
@@ -53,10 +53,11 @@ async fn when_member_enables_via_local_media_settings(
 ) {
     let member = world.get_member(&id).unwrap();
     let video = kind.contains("video");
+    let display_video = kind.contains("display");
     let audio = kind.contains("audio");
     member
         .room()
-        .set_local_media_settings(video, audio)
+        .set_local_media_settings(video, display_video, audio)
         .await
         .unwrap();
 }
@@ -135,6 +135,8 @@ impl World {
                         id: "publish".to_owned(),
                         p2p: proto::P2pMode::Always,
                         force_relay: false,
+                        on_start: None,
+                        on_stop: None,
                         audio_settings: proto::AudioSettings::default(),
                         video_settings: proto::VideoSettings::default(),
                     },
@@ -159,6 +161,8 @@ impl World {
                                 m.id(),
                             ),
                             force_relay: false,
+                            on_start: None,
+                            on_stop: None,
                         },
                     ),
                 );
@@ -176,9 +180,12 @@ impl World {
                     )),
                     on_join: Some("grpc://127.0.0.1:9099".to_owned()),
                     on_leave: Some("grpc://127.0.0.1:9099".to_owned()),
+                    on_negotiation_timeout: None,
                     idle_timeout: None,
                     reconnect_timeout: None,
                     ping_interval: None,
+                    metadata: HashMap::new(),
+                    features: HashMap::new(),
                 }),
             )
             .await?;
@@ -204,6 +211,8 @@ impl World {
                                     self.room_id, builder.id,
                                 ),
                                 force_relay: false,
+                                on_start: None,
+                                on_stop: None,
                             },
                         );
                         (id, elem)
@@ -664,14 +673,18 @@ impl PairedMember {
             id: "publish".to_owned(),
             p2p: proto::P2pMode::Always,
             force_relay: false,
+            on_start: None,
+            on_stop: None,
             audio_settings: self.send_audio.clone().unwrap_or(
                 proto::AudioSettings {
                     publish_policy: PublishPolicy::Disabled,
+                    opus: None,
                 },
             ),
             video_settings: self.send_video.clone().unwrap_or(
                 proto::VideoSettings {
                     publish_policy: PublishPolicy::Disabled,
+                    display_publish_policy: PublishPolicy::Disabled,
                 },
             ),
         })
@@ -690,6 +703,8 @@ impl PairedMember {
             id: format!("play-{}", publisher.id),
             src: format!("local://{}/{}/{}", room_id, publisher.id, "publish"),
             force_relay: false,
+            on_start: None,
+            on_stop: None,
         })
     }
 }
@@ -1,10 +1,29 @@
-//! Compiles `trampoline.c` and links it into the final library.
+//! Compiles `trampoline.c` and links it into the final library, and generates
+//! TypeScript definitions of the JS API which [`wasm_bindgen`] is unable to
+//! describe on its own.
+//!
+//! [`wasm_bindgen`]: https://docs.rs/wasm-bindgen
 
 #![forbid(non_ascii_idents, unsafe_code)]
 
-use std::env;
+use std::{collections::HashMap, env, fmt::Write as _, fs, path::Path};
+
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Attribute, Fields, Ident, Item, Lit, Meta, Token, Type, UseTree,
+    Visibility,
+};
+
+/// Directory containing sources of the JS API.
+const WASM_API_DIR: &str = "src/api/wasm";
 
 fn main() {
+    println!("cargo:rerun-if-changed={}", WASM_API_DIR);
+    let out_dir = env::var("OUT_DIR").expect("`OUT_DIR` is always set");
+    fs::write(Path::new(&out_dir).join("typings.rs"), generate_typings())
+        .expect("Failed to write generated TypeScript definitions");
+
     println!("cargo:rerun-if-env-changed=CLIPPY_ARGS");
     if env::var("CLIPPY_ARGS").is_ok() {
         return;
@@ -19,3 +38,219 @@ fn main() {
         .file("src/platform/dart/api_dl/trampoline.c")
         .compile("trampoline");
 }
+
+/// Generates a `typescript_custom_section` with TypeScript definitions of:
+/// - union types of all the `#[wasm_bindgen]` exported enums' variant names;
+/// - signatures of all the JS callbacks declared in `typings.rs` via
+///   `impl_callback!` macro.
+///
+/// JSDoc of the generated definitions is taken from the Rust docs.
+fn generate_typings() -> String {
+    let mut files = fs::read_dir(WASM_API_DIR)
+        .expect("Failed to read JS API sources")
+        .map(|e| e.expect("Failed to read JS API sources").path())
+        .filter(|p| p.extension().map_or(false, |ext| ext == "rs"))
+        .collect::<Vec<_>>();
+    files.sort();
+
+    let mut ts = String::new();
+    let mut renames = HashMap::new();
+    let mut callbacks = Vec::new();
+    let mut callback_docs = HashMap::new();
+    for path in files {
+        let src = fs::read_to_string(&path).expect("Failed to read JS API");
+        let file = syn::parse_file(&src).expect("Failed to parse JS API");
+        for item in file.items {
+            match item {
+                Item::Enum(e) if is_exported_c_like(&e) => {
+                    let variants = e
+                        .variants
+                        .iter()
+                        .map(|v| format!("\"{}\"", v.ident))
+                        .collect::<Vec<_>>()
+                        .join(" | ");
+                    write_jsdoc(&mut ts, &e.attrs);
+                    writeln!(
+                        ts,
+                        "export type {}Name = {};\n",
+                        e.ident, variants
+                    )
+                    .unwrap();
+                }
+                Item::Use(u) if matches!(u.vis, Visibility::Public(_)) => {
+                    collect_renames(&u.tree, &mut renames);
+                }
+                Item::ForeignMod(m) => {
+                    for item in m.items {
+                        if let syn::ForeignItem::Type(t) = item {
+                            callback_docs.insert(t.ident.to_string(), t.attrs);
+                        }
+                    }
+                }
+                Item::Macro(m) if m.mac.path.is_ident("impl_callback") => {
+                    callbacks.extend(
+                        m.mac
+                            .parse_body_with(Callbacks::parse_terminated)
+                            .expect("Failed to parse `impl_callback!`"),
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    for cb in callbacks {
+        if let Some(attrs) = callback_docs.get(&cb.name.to_string()) {
+            write_jsdoc(&mut ts, attrs);
+        }
+        let args = ts_type(&cb.arg, &renames).map_or_else(String::new, |ty| {
+            let mut name = ty.clone();
+            if ty == "number" {
+                name = "value".into();
+            } else if let Some(first) = name.get_mut(0..1) {
+                first.make_ascii_lowercase();
+            }
+            format!("{}: {}", name, ty)
+        });
+        writeln!(ts, "export type {} = ({}) => void;\n", cb.name, args)
+            .unwrap();
+    }
+
+    format!(
+        "#[wasm_bindgen(typescript_custom_section)]\n\
+         const TS_DEFINITIONS: &'static str = r#\"\n{}\"#;\n",
+        ts,
+    )
+}
+
+/// Entries of an `impl_callback!` macro invocation.
+type Callbacks = Punctuated<Callback, Token![,]>;
+
+/// Entry of an `impl_callback!` macro invocation: `Callback => Arg`.
+struct Callback {
+    /// Name of the JS callback type.
+    name: Ident,
+
+    /// Type of the argument the JS callback accepts.
+    arg: Type,
+}
+
+impl Parse for Callback {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        Ok(Self {
+            name,
+            arg: input.parse()?,
+        })
+    }
+}
+
+/// Checks whether the provided enum is exported via `#[wasm_bindgen]` and has
+/// only unit variants.
+fn is_exported_c_like(e: &syn::ItemEnum) -> bool {
+    e.attrs.iter().any(|a| a.path.is_ident("wasm_bindgen"))
+        && e.variants.iter().all(|v| matches!(v.fields, Fields::Unit))
+}
+
+/// Collects `pub use original as Renamed` imports into `renames`, mapping
+/// Rust names onto the ones exported to JS.
+fn collect_renames(tree: &UseTree, renames: &mut HashMap<String, String>) {
+    match tree {
+        UseTree::Path(p) => collect_renames(&p.tree, renames),
+        UseTree::Group(g) => {
+            for tree in &g.items {
+                collect_renames(tree, renames);
+            }
+        }
+        UseTree::Rename(r) => {
+            drop(renames.insert(r.rename.to_string(), r.ident.to_string()));
+        }
+        UseTree::Name(_) | UseTree::Glob(_) => {}
+    }
+}
+
+/// Returns TypeScript type of the provided Rust type, or [`None`] if it's a
+/// unit type.
+fn ts_type(ty: &Type, renames: &HashMap<String, String>) -> Option<String> {
+    let ident = match ty {
+        Type::Tuple(t) if t.elems.is_empty() => return None,
+        Type::Path(p) => p.path.segments.last().unwrap().ident.to_string(),
+        _ => panic!("Unsupported JS callback argument type"),
+    };
+    Some(match ident.as_str() {
+        "u8" | "u16" | "u32" | "u64" | "i8" | "i16" | "i32" | "i64" | "f32"
+        | "f64" => "number".into(),
+        "bool" => "boolean".into(),
+        "String" => "string".into(),
+        _ => renames.get(&ident).cloned().unwrap_or(ident),
+    })
+}
+
+/// Writes the provided Rust docs as JSDoc, omitting intra-doc links and
+/// turning external ones into `@see` tags.
+fn write_jsdoc(ts: &mut String, attrs: &[Attribute]) {
+    let mut lines = Vec::new();
+    let mut links = Vec::new();
+    for attr in attrs {
+        if let Ok(Meta::NameValue(nv)) = attr.parse_meta() {
+            let doc = match nv.lit {
+                Lit::Str(s) if nv.path.is_ident("doc") => s.value(),
+                _ => continue,
+            };
+            let line = doc.strip_prefix(' ').unwrap_or(&doc);
+            match line.split_once("]: ") {
+                Some((_, url)) if line.starts_with('[') => {
+                    if url.starts_with("http") {
+                        links.push(url.to_owned());
+                    }
+                }
+                _ => lines.push(strip_links(line)),
+            }
+        }
+    }
+    while lines.last().map_or(false, String::is_empty) {
+        drop(lines.pop());
+    }
+    if lines.is_empty() && links.is_empty() {
+        return;
+    }
+
+    ts.push_str("/**\n");
+    for line in lines {
+        writeln!(ts, " *{}{}", if line.is_empty() { "" } else { " " }, line)
+            .unwrap();
+    }
+    for link in links {
+        writeln!(ts, " * @see {}", link).unwrap();
+    }
+    ts.push_str(" */\n");
+}
+
+/// Replaces Markdown links in the provided `line` with their text.
+fn strip_links(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find('[') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let end = match after.find(']') {
+            Some(end) => end,
+            None => {
+                rest = &rest[start..];
+                break;
+            }
+        };
+        out.push_str(&after[..end]);
+        rest = &after[end + 1..];
+        for (open, close) in &[('[', ']'), ('(', ')')] {
+            if rest.starts_with(*open) {
+                if let Some(end) = rest.find(*close) {
+                    rest = &rest[end + 1..];
+                }
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
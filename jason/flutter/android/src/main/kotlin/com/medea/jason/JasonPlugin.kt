@@ -1,17 +1,82 @@
 package com.medea.jason
 
+import android.content.BroadcastReceiver
+import android.content.Context
+import android.content.Intent
+import android.content.IntentFilter
+import android.media.AudioManager
 import androidx.annotation.NonNull
 
 import io.flutter.embedding.engine.plugins.FlutterPlugin
+import io.flutter.plugin.common.MethodChannel
 
 class JasonPlugin: FlutterPlugin {
+    /**
+     * Channel reporting audio output route changes to the Dart side.
+     */
+    private var audioRouteChannel: MethodChannel? = null
+
+    /**
+     * Application context the [audioRouteReceiver] is registered in.
+     */
+    private var context: Context? = null
+
+    /**
+     * Receiver of the `AudioManager` audio output route notifications.
+     */
+    private val audioRouteReceiver = object : BroadcastReceiver() {
+        override fun onReceive(context: Context, intent: Intent) {
+            // Sticky `ACTION_HEADSET_PLUG` is delivered on registration,
+            // which is not a route change.
+            if (isInitialStickyBroadcast) {
+                return
+            }
+            val reason = when (intent.action) {
+                AudioManager.ACTION_AUDIO_BECOMING_NOISY ->
+                    AudioRouteChangeReason.OLD_DEVICE_UNAVAILABLE
+                Intent.ACTION_HEADSET_PLUG ->
+                    if (intent.getIntExtra("state", 0) == 1) {
+                        AudioRouteChangeReason.NEW_DEVICE_AVAILABLE
+                    } else {
+                        // Unplugging is reported via
+                        // `ACTION_AUDIO_BECOMING_NOISY` already.
+                        return
+                    }
+                else -> AudioRouteChangeReason.OTHER
+            }
+            audioRouteChannel?.invokeMethod("onAudioRouteChange", reason.value)
+        }
+    }
+
     override fun onAttachedToEngine(
         @NonNull flutterPluginBinding: FlutterPlugin.FlutterPluginBinding,
     ) {
+        audioRouteChannel = MethodChannel(
+            flutterPluginBinding.binaryMessenger,
+            "com.medea.jason/audio_route",
+        )
+        context = flutterPluginBinding.applicationContext
+        val filter = IntentFilter()
+        filter.addAction(AudioManager.ACTION_AUDIO_BECOMING_NOISY)
+        filter.addAction(Intent.ACTION_HEADSET_PLUG)
+        context?.registerReceiver(audioRouteReceiver, filter)
     }
 
     override fun onDetachedFromEngine(
         @NonNull binding: FlutterPlugin.FlutterPluginBinding,
     ) {
+        context?.unregisterReceiver(audioRouteReceiver)
+        context = null
+        audioRouteChannel = null
     }
 }
+
+/**
+ * Reason of an audio output route change, matching the Dart
+ * `AudioRouteChangeReason`.
+ */
+enum class AudioRouteChangeReason(val value: Int) {
+    NEW_DEVICE_AVAILABLE(0),
+    OLD_DEVICE_UNAVAILABLE(1),
+    OTHER(2),
+}
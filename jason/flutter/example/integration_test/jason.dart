@@ -3,6 +3,9 @@ import 'dart:ffi';
 
 import 'package:flutter_test/flutter_test.dart';
 import 'package:integration_test/integration_test.dart';
+import 'package:medea_jason/abort_signal.dart';
+import 'package:medea_jason/analytics_event.dart';
+import 'package:medea_jason/audio_route.dart';
 import 'package:medea_jason/audio_track_constraints.dart';
 import 'package:medea_jason/connection_handle.dart';
 import 'package:medea_jason/device_video_track_constraints.dart';
@@ -12,10 +15,13 @@ import 'package:medea_jason/ffi/foreign_value.dart';
 import 'package:medea_jason/ffi/result.dart';
 import 'package:medea_jason/input_device_info.dart';
 import 'package:medea_jason/jason.dart';
+import 'package:medea_jason/local_media_track.dart';
+import 'package:medea_jason/media_permissions.dart';
 import 'package:medea_jason/media_stream_settings.dart';
 import 'package:medea_jason/reconnect_handle.dart';
 import 'package:medea_jason/remote_media_track.dart';
 import 'package:medea_jason/room_close_reason.dart';
+import 'package:medea_jason/room_handle.dart';
 import 'package:medea_jason/track_kinds.dart';
 import 'package:medea_jason/util/nullable_pointer.dart';
 
@@ -31,6 +37,20 @@ void main() {
     expect(() => jason.closeRoom(room), throwsStateError);
   });
 
+  testWidgets('AnalyticsEvent', (WidgetTester tester) async {
+    var jason = Jason();
+
+    var events = <AnalyticsEvent>[];
+    jason.setAnalyticsSink((event) => events.add(event));
+
+    expect(events.length, equals(1));
+    var event = events.first;
+    expect(event.kind(), equals(AnalyticsEventKind.NegotiationFinished));
+    expect(event.peerId(), equals(1));
+    expect(event.duration(), equals(Duration(milliseconds: 150)));
+    event.free();
+  });
+
   testWidgets('MediaManager', (WidgetTester tester) async {
     final returnsLocalMediaInitException =
         dl.lookupFunction<Result Function(Handle), Result Function(Object)>(
@@ -64,15 +84,40 @@ void main() {
     expect(devices.first.kind(), equals(MediaKind.Audio));
     expect(devices.first.label(), equals('InputDeviceInfo.label'));
 
+    var caps = devices.first.capabilities()!;
+    expect(caps.minWidth(), equals(1));
+    expect(caps.maxHeight(), equals(1080));
+    expect(caps.maxFrameRate(), equals(30));
+    expect(caps.minSampleRate(), equals(null));
+    expect(caps.voiceIsolation(), isFalse);
+    expect(caps.facingModes(), equals([FacingMode.User]));
+    caps.free();
+
     devices.first.free();
     expect(() => devices.first.label(), throwsStateError);
 
     expect(tracks.first.kind(), equals(MediaKind.Video));
     expect(tracks.first.mediaSourceKind(), equals(MediaSourceKind.Display));
+    expect(tracks.first.reason(), equals(LocalTrackReason.DeviceSwitch));
+
+    var advanced = DeviceVideoTrackConstraints();
+    advanced.torch(true);
+    await tracks.first.applyAdvanced(advanced);
+    advanced.free();
+
+    var previous = tracks.first.previous()!;
+    expect(previous.kind(), equals(MediaKind.Video));
+    previous.free();
 
     tracks.first.free();
     expect(() => tracks.first.kind(), throwsStateError);
 
+    var permissions = await mediaManager.queryPermissions();
+    expect(permissions.camera(), equals(PermissionState.Granted));
+    expect(permissions.microphone(), equals(PermissionState.Denied));
+    expect(permissions.screen(), equals(PermissionState.Unknown));
+    permissions.free();
+
     expect(
         () => returnsLocalMediaInitException('Dart err cause1').unwrap(),
         throwsA(predicate((e) =>
@@ -129,6 +174,14 @@ void main() {
     constraints.exactWidth(444);
     constraints.idealWidth(111);
     constraints.widthInRange(55, 66);
+    constraints.contentHint(ContentHint.Motion);
+    constraints.degradationPreference(DegradationPreference.Balanced);
+    constraints.torch(true);
+    constraints.exactZoom(200);
+    constraints.idealZoom(150);
+    constraints.zoomInRange(100, 300);
+    constraints.exactFocusMode(FocusMode.Manual);
+    constraints.idealFocusMode(FocusMode.Continuous);
 
     expect(() => constraints.exactHeight(-1), throwsArgumentError);
     expect(() => constraints.idealHeight(-1), throwsArgumentError);
@@ -142,6 +195,11 @@ void main() {
     expect(() => constraints.widthInRange(-1, 200), throwsArgumentError);
     expect(() => constraints.widthInRange(200, -1), throwsArgumentError);
 
+    expect(() => constraints.exactZoom(-1), throwsArgumentError);
+    expect(() => constraints.idealZoom(-1), throwsArgumentError);
+    expect(() => constraints.zoomInRange(-1, 200), throwsArgumentError);
+    expect(() => constraints.zoomInRange(200, -1), throwsArgumentError);
+
     constraints.free();
     expect(() => constraints.deviceId('deviceId'), throwsStateError);
 
@@ -154,6 +212,9 @@ void main() {
 
   testWidgets('DisplayVideoTrackConstraints', (WidgetTester tester) async {
     var constraints = DisplayVideoTrackConstraints();
+    constraints.contentHint(ContentHint.Text);
+    constraints
+        .degradationPreference(DegradationPreference.MaintainResolution);
     constraints.free();
     expect(() => constraints.free(), throwsStateError);
 
@@ -166,6 +227,20 @@ void main() {
   testWidgets('AudioTrackConstraints', (WidgetTester tester) async {
     var constraints = AudioTrackConstraints();
     constraints.deviceId('deviceId');
+    constraints.exactSampleRate(48000);
+    constraints.idealSampleRate(44100);
+    constraints.sampleRateInRange(8000, 48000);
+    constraints.exactChannelCount(2);
+    constraints.idealChannelCount(1);
+    constraints.exactLatency(10);
+    constraints.idealLatency(20);
+    constraints.voiceIsolation(true);
+    constraints.routePolicy(AudioRoutePolicy.MuteOnDeviceUnavailable);
+
+    expect(() => constraints.exactSampleRate(-1), throwsArgumentError);
+    expect(() => constraints.idealChannelCount(-1), throwsArgumentError);
+    expect(() => constraints.sampleRateInRange(-1, 200), throwsArgumentError);
+
     constraints.free();
     expect(() => constraints.deviceId('deviceId'), throwsStateError);
 
@@ -180,7 +255,7 @@ void main() {
     var jason = Jason();
     var room = jason.initRoom();
 
-    var allFired = List<Completer>.generate(4, (_) => Completer());
+    var allFired = List<Completer>.generate(5, (_) => Completer());
 
     room.onClose((reason) {
       allFired[0].complete();
@@ -198,8 +273,13 @@ void main() {
       allFired[3].complete();
     });
 
-    await Future.wait(allFired.map((e) => e.future))
+    room.onAudioRouteChange((reason) {
+      allFired[4].complete(reason);
+    });
+
+    var res = await Future.wait(allFired.map((e) => e.future))
         .timeout(Duration(seconds: 1));
+    expect(res[4], equals(AudioRouteChangeReason.OldDeviceUnavailable));
 
     room.free();
 
@@ -218,8 +298,11 @@ void main() {
     var reason = await reasonFut.future.timeout(Duration(seconds: 1));
 
     expect(reason.reason(), equals('RpcClientUnexpectedlyDropped'));
+    expect(reason.kind(), equals(RoomCloseKind.RpcClientUnexpectedlyDropped));
     expect(reason.isClosedByServer(), equals(false));
     expect(reason.isErr(), equals(true));
+    expect(reason.isRetryable(), equals(false));
+    expect(reason.retryAfter(), isNull);
     reason.free();
     expect(() => reason.isErr(), throwsStateError);
   });
@@ -270,6 +353,14 @@ void main() {
 
     var track = await trackFut.future;
 
+    var removedTrackFut = Completer<RemoteMediaTrack>();
+    conn.onRemoteTrackRemoved((remoteTrack) {
+      removedTrackFut.complete(remoteTrack);
+    });
+    var removedTrack = await removedTrackFut.future;
+    expect(removedTrack.kind(), equals(MediaKind.Video));
+    removedTrack.free();
+
     expect(track.enabled(), equals(true));
     expect(track.muted(), equals(false));
     expect(track.kind(), equals(MediaKind.Video));
@@ -299,11 +390,47 @@ void main() {
     expect(() => track.kind(), throwsStateError);
   });
 
+  testWidgets('AbortSignal', (WidgetTester tester) async {
+    var jason = Jason();
+    var room = jason.initRoom();
+
+    var signal = AbortSignal();
+    await room.join('wss://example.com/room/Alice?token=777', signal: signal);
+
+    signal.abort();
+    var errors = [];
+    for (var op in [
+      () => room.join('wss://example.com/room/Alice?token=777',
+          signal: signal),
+      () => room.joinWithoutMedia('wss://example.com/room/Alice?token=777',
+          signal: signal),
+      () => room.setLocalMediaSettings(MediaStreamSettings(), true, false,
+          signal: signal),
+      () => jason.mediaManager().enumerateDevices(signal: signal),
+    ]) {
+      try {
+        await op();
+      } catch (e) {
+        errors.add(e);
+      }
+    }
+    expect(errors.length, equals(4));
+    expect(
+        errors,
+        everyElement(allOf(isStateError,
+            predicate((e) => e.message == 'Operation was aborted'))));
+
+    signal.free();
+    expect(() => signal.abort(), throwsStateError);
+  });
+
   testWidgets('RoomHandle', (WidgetTester tester) async {
     var jason = Jason();
     var room = jason.initRoom();
 
     await room.join('wss://example.com/room/Alice?token=777');
+    await room.joinWithoutMedia('wss://example.com/room/Alice?token=777');
+    await room.publish();
     await room.setLocalMediaSettings(MediaStreamSettings(), true, false);
     await room.muteAudio();
     await room.unmuteAudio();
@@ -316,6 +443,9 @@ void main() {
     await room.disableRemoteAudio();
     await room.enableRemoteAudio();
     await room.disableRemoteVideo();
+    room.setRecvMediaPolicy(RecvMediaPolicy.Manual);
+    await room.enableRemoteMediaFrom('bob');
+    await room.disableRemoteMediaFrom('bob');
 
     var stateErr;
     try {
@@ -472,6 +602,47 @@ void main() {
     expect(returnsInt().toDart(), equals(333));
   });
 
+  testWidgets('ForeignValue collections Rust => Dart',
+      (WidgetTester tester) async {
+    final returnsNestedList =
+        dl.lookupFunction<ForeignValue Function(), ForeignValue Function()>(
+            'returns_nested_list');
+    final returnsMap =
+        dl.lookupFunction<ForeignValue Function(), ForeignValue Function()>(
+            'returns_map');
+    final returnsSizedString =
+        dl.lookupFunction<ForeignValue Function(), ForeignValue Function()>(
+            'returns_sized_string');
+    final valueArraysAlive =
+        dl.lookupFunction<Uint64 Function(), int Function()>(
+            'value_arrays_alive');
+
+    expect(valueArraysAlive(), equals(0));
+
+    expect(
+        returnsNestedList().toDart(),
+        equals([
+          [1, 2],
+          [],
+          'str',
+          5,
+          null
+        ]));
+    expect(valueArraysAlive(), equals(0));
+
+    expect(
+        returnsMap().toDart(),
+        equals({
+          'int': 1,
+          'list': ['a'],
+          'map': {'key': 'value'}
+        }));
+    expect(valueArraysAlive(), equals(0));
+
+    expect(returnsSizedString().toDart(), equals('null\u0000byte'));
+    expect(valueArraysAlive(), equals(0));
+  });
+
   testWidgets('ForeignValue Dart => Rust', (WidgetTester tester) async {
     final acceptsNone = dl.lookupFunction<Void Function(ForeignValue),
         void Function(ForeignValue)>('accepts_none');
@@ -0,0 +1,43 @@
+import 'dart:ffi';
+
+import 'jason.dart';
+import 'util/move_semantic.dart';
+import 'util/nullable_pointer.dart';
+
+typedef _new_C = Pointer Function();
+typedef _new_Dart = Pointer Function();
+
+typedef _abort_C = Void Function(Pointer);
+typedef _abort_Dart = void Function(Pointer);
+
+typedef _free_C = Void Function(Pointer);
+typedef _free_Dart = void Function(Pointer);
+
+final _new = dl.lookupFunction<_new_C, _new_Dart>('AbortSignal__new');
+
+final _abort = dl.lookupFunction<_abort_C, _abort_Dart>('AbortSignal__abort');
+
+final _free = dl.lookupFunction<_free_C, _free_Dart>('AbortSignal__free');
+
+/// Signal aborting the asynchronous operations it's passed to.
+///
+/// Once aborted, these operations are canceled and throw a [StateError].
+class AbortSignal {
+  /// [Pointer] to the Rust struct backing this object.
+  final NullablePointer ptr = NullablePointer(_new());
+
+  /// Aborts all the operations this [AbortSignal] has been passed to, and all
+  /// the further ones.
+  void abort() {
+    _abort(ptr.getInnerPtr());
+  }
+
+  /// Drops the associated Rust struct and nulls the local [Pointer] to it.
+  ///
+  /// Doesn't abort the operations this [AbortSignal] has been passed to.
+  @moveSemantics
+  void free() {
+    _free(ptr.getInnerPtr());
+    ptr.free();
+  }
+}
@@ -0,0 +1,86 @@
+import 'dart:ffi';
+
+import 'ffi/foreign_value.dart';
+import 'jason.dart';
+import 'util/move_semantic.dart';
+import 'util/nullable_pointer.dart';
+
+typedef _kind_C = Uint8 Function(Pointer);
+typedef _kind_Dart = int Function(Pointer);
+
+typedef _peerId_C = ForeignValue Function(Pointer);
+typedef _peerId_Dart = ForeignValue Function(Pointer);
+
+typedef _duration_C = ForeignValue Function(Pointer);
+typedef _duration_Dart = ForeignValue Function(Pointer);
+
+typedef _free_C = Void Function(Pointer);
+typedef _free_Dart = void Function(Pointer);
+
+final _kind = dl.lookupFunction<_kind_C, _kind_Dart>('AnalyticsEvent__kind');
+
+final _peerId =
+    dl.lookupFunction<_peerId_C, _peerId_Dart>('AnalyticsEvent__peer_id');
+
+final _duration =
+    dl.lookupFunction<_duration_C, _duration_Dart>('AnalyticsEvent__duration');
+
+final _free = dl.lookupFunction<_free_C, _free_Dart>('AnalyticsEvent__free');
+
+/// Kind of an [AnalyticsEvent].
+enum AnalyticsEventKind {
+  /// Joining a `Room` has been started.
+  JoinStarted,
+
+  /// `Room` has been joined successfully.
+  JoinSucceeded,
+
+  /// Connection with a media server has been restored after being lost.
+  Reconnected,
+
+  /// SDP negotiation of a `PeerConnection` has been finished.
+  NegotiationFinished,
+
+  /// Local media has been acquired successfully.
+  LocalMediaAcquired,
+
+  /// Local media has failed to be acquired.
+  LocalMediaFailed,
+}
+
+/// Structured event of a key [Jason] lifecycle stage.
+///
+/// This struct is passed into the `Jason.setAnalyticsSink()` callback.
+class AnalyticsEvent {
+  /// [Pointer] to the Rust struct that backing this object.
+  late NullablePointer ptr;
+
+  /// Constructs a new [AnalyticsEvent] backed by the Rust struct behind the
+  /// provided [Pointer].
+  AnalyticsEvent(this.ptr);
+
+  /// Returns the [AnalyticsEventKind] of this [AnalyticsEvent].
+  AnalyticsEventKind kind() {
+    var index = _kind(ptr.getInnerPtr());
+    return AnalyticsEventKind.values[index];
+  }
+
+  /// Returns ID of the `PeerConnection` this [AnalyticsEvent] relates to, if
+  /// any.
+  int? peerId() {
+    return _peerId(ptr.getInnerPtr()).toDart();
+  }
+
+  /// Returns duration of the stage this [AnalyticsEvent] finishes, if any.
+  Duration? duration() {
+    int? ms = _duration(ptr.getInnerPtr()).toDart();
+    return ms == null ? null : Duration(milliseconds: ms);
+  }
+
+  /// Drops the associated Rust struct and nulls the local [Pointer] to it.
+  @moveSemantics
+  void free() {
+    _free(ptr.getInnerPtr());
+    ptr.free();
+  }
+}
@@ -0,0 +1,38 @@
+import 'dart:ffi';
+
+import 'package:flutter/services.dart';
+
+typedef _audioRouteChanged_C = Void Function(Uint8);
+typedef _audioRouteChanged_Dart = void Function(int);
+
+/// [MethodChannel] which audio output route changes are reported to by the
+/// Android side of this plugin.
+const _channel = MethodChannel('com.medea.jason/audio_route');
+
+/// Reason of an audio output route change.
+enum AudioRouteChangeReason {
+  /// New audio output device became available (e.g. headphones are plugged
+  /// in).
+  NewDeviceAvailable,
+
+  /// Previously used audio output device became unavailable (e.g. headphones
+  /// are unplugged), so the audio is routed to the speaker.
+  OldDeviceUnavailable,
+
+  /// Audio output route changed for any other reason.
+  Other,
+}
+
+/// Forwards the audio output route changes reported by the Android side of
+/// this plugin to Rust.
+void registerFunctions(DynamicLibrary dl) {
+  final audioRouteChanged =
+      dl.lookupFunction<_audioRouteChanged_C, _audioRouteChanged_Dart>(
+          'audio_route_changed');
+
+  _channel.setMethodCallHandler((call) async {
+    if (call.method == 'onAudioRouteChange') {
+      audioRouteChanged(call.arguments as int);
+    }
+  });
+}
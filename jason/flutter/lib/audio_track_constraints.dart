@@ -2,6 +2,7 @@ import 'dart:ffi';
 
 import 'package:ffi/ffi.dart';
 
+import 'ffi/result.dart';
 import 'jason.dart';
 import 'util/move_semantic.dart';
 import 'util/nullable_pointer.dart';
@@ -14,6 +15,36 @@ typedef _new_Dart = Pointer Function();
 typedef _deviceId_C = Void Function(Pointer, Pointer<Utf8>);
 typedef _deviceId_Dart = void Function(Pointer, Pointer<Utf8>);
 
+typedef _exactSampleRate_C = Result Function(Pointer, Int64);
+typedef _exactSampleRate_Dart = Result Function(Pointer, int);
+
+typedef _idealSampleRate_C = Result Function(Pointer, Int64);
+typedef _idealSampleRate_Dart = Result Function(Pointer, int);
+
+typedef _exactChannelCount_C = Result Function(Pointer, Int64);
+typedef _exactChannelCount_Dart = Result Function(Pointer, int);
+
+typedef _idealChannelCount_C = Result Function(Pointer, Int64);
+typedef _idealChannelCount_Dart = Result Function(Pointer, int);
+
+typedef _exactLatency_C = Result Function(Pointer, Int64);
+typedef _exactLatency_Dart = Result Function(Pointer, int);
+
+typedef _idealLatency_C = Result Function(Pointer, Int64);
+typedef _idealLatency_Dart = Result Function(Pointer, int);
+
+typedef _sampleRateInRange_C = Result Function(Pointer, Int64, Int64);
+typedef _sampleRateInRange_Dart = Result Function(Pointer, int, int);
+
+typedef _voiceIsolation_C = Void Function(Pointer, Uint8);
+typedef _voiceIsolation_Dart = void Function(Pointer, int);
+
+typedef _muteStrategy_C = Void Function(Pointer, Uint8);
+typedef _muteStrategy_Dart = void Function(Pointer, int);
+
+typedef _routePolicy_C = Void Function(Pointer, Uint8);
+typedef _routePolicy_Dart = void Function(Pointer, int);
+
 typedef _free_C = Void Function(Pointer);
 typedef _free_Dart = void Function(Pointer);
 
@@ -22,9 +53,80 @@ final _new = dl.lookupFunction<_new_C, _new_Dart>('AudioTrackConstraints__new');
 final _deviceId = dl.lookupFunction<_deviceId_C, _deviceId_Dart>(
     'AudioTrackConstraints__device_id');
 
+final _exactSampleRate =
+    dl.lookupFunction<_exactSampleRate_C, _exactSampleRate_Dart>(
+        'AudioTrackConstraints__exact_sample_rate');
+
+final _idealSampleRate =
+    dl.lookupFunction<_idealSampleRate_C, _idealSampleRate_Dart>(
+        'AudioTrackConstraints__ideal_sample_rate');
+
+final _exactChannelCount =
+    dl.lookupFunction<_exactChannelCount_C, _exactChannelCount_Dart>(
+        'AudioTrackConstraints__exact_channel_count');
+
+final _idealChannelCount =
+    dl.lookupFunction<_idealChannelCount_C, _idealChannelCount_Dart>(
+        'AudioTrackConstraints__ideal_channel_count');
+
+final _exactLatency = dl.lookupFunction<_exactLatency_C, _exactLatency_Dart>(
+    'AudioTrackConstraints__exact_latency');
+
+final _idealLatency = dl.lookupFunction<_idealLatency_C, _idealLatency_Dart>(
+    'AudioTrackConstraints__ideal_latency');
+
+final _sampleRateInRange =
+    dl.lookupFunction<_sampleRateInRange_C, _sampleRateInRange_Dart>(
+        'AudioTrackConstraints__sample_rate_in_range');
+
+final _voiceIsolation =
+    dl.lookupFunction<_voiceIsolation_C, _voiceIsolation_Dart>(
+        'AudioTrackConstraints__voice_isolation');
+
+final _muteStrategy = dl.lookupFunction<_muteStrategy_C, _muteStrategy_Dart>(
+    'AudioTrackConstraints__mute_strategy');
+
+final _routePolicy = dl.lookupFunction<_routePolicy_C, _routePolicy_Dart>(
+    'AudioTrackConstraints__route_policy');
+
 final _free =
     dl.lookupFunction<_free_C, _free_Dart>('AudioTrackConstraints__free');
 
+/// Strategy of muting a sent audio track.
+///
+/// Regardless of the chosen [MuteStrategy], the remote side is notified about
+/// muting in the same way, so the choice only affects what happens to the
+/// captured media locally.
+enum MuteStrategy {
+  /// Disable the sent track, so silence is sent while the microphone keeps
+  /// capturing.
+  DisableTrack,
+
+  /// Replace the sent track with nothing, while the microphone keeps
+  /// capturing, allowing to unmute without re-acquiring media.
+  ReplaceWithSilence,
+
+  /// Stop capturing entirely, releasing the microphone (and turning off its
+  /// privacy indicator), and re-acquire it on unmuting.
+  ///
+  /// The microphone is released only once all the `LocalMediaTrack`s of it
+  /// are freed as well.
+  StopCapture,
+}
+
+/// Policy of reacting to audio output route changes (e.g. headphones being
+/// unplugged) in a `Room`.
+enum AudioRoutePolicy {
+  /// Audio output route changes are only reported, without touching any
+  /// media.
+  Ignore,
+
+  /// Outbound audio is muted once the used audio output device becomes
+  /// unavailable, so the microphone doesn't start capturing the speaker
+  /// unexpectedly.
+  MuteOnDeviceUnavailable,
+}
+
 /// Constraints applicable to audio tracks.
 class AudioTrackConstraints {
   /// [Pointer] to the Rust struct backing this object.
@@ -42,6 +144,93 @@ class AudioTrackConstraints {
     }
   }
 
+  /// Sets an exact [`sampleRate`][1] constraint.
+  ///
+  /// Converts the provided [sampleRate] into an `u32`. Throws an
+  /// [ArgumentError] if conversion fails.
+  ///
+  /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-sampleRate
+  void exactSampleRate(int sampleRate) {
+    _exactSampleRate(ptr.getInnerPtr(), sampleRate).unwrap();
+  }
+
+  /// Sets an ideal [`sampleRate`][1] constraint.
+  ///
+  /// Converts the provided [sampleRate] into an `u32`. Throws an
+  /// [ArgumentError] if conversion fails.
+  ///
+  /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-sampleRate
+  void idealSampleRate(int sampleRate) {
+    _idealSampleRate(ptr.getInnerPtr(), sampleRate).unwrap();
+  }
+
+  /// Sets a range of a [`sampleRate`][1] constraint.
+  ///
+  /// Converts the provided [min] and [max] into an `u32`. Throws an
+  /// [ArgumentError] if conversion fails.
+  ///
+  /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-sampleRate
+  void sampleRateInRange(int min, int max) {
+    _sampleRateInRange(ptr.getInnerPtr(), min, max).unwrap();
+  }
+
+  /// Sets an exact [`channelCount`][1] constraint.
+  ///
+  /// Converts the provided [channelCount] into an `u32`. Throws an
+  /// [ArgumentError] if conversion fails.
+  ///
+  /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-channelCount
+  void exactChannelCount(int channelCount) {
+    _exactChannelCount(ptr.getInnerPtr(), channelCount).unwrap();
+  }
+
+  /// Sets an ideal [`channelCount`][1] constraint.
+  ///
+  /// Converts the provided [channelCount] into an `u32`. Throws an
+  /// [ArgumentError] if conversion fails.
+  ///
+  /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-channelCount
+  void idealChannelCount(int channelCount) {
+    _idealChannelCount(ptr.getInnerPtr(), channelCount).unwrap();
+  }
+
+  /// Sets an exact [`latency`][1] constraint in milliseconds.
+  ///
+  /// Converts the provided [latency] into an `u32`. Throws an [ArgumentError]
+  /// if conversion fails.
+  ///
+  /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-latency
+  void exactLatency(int latency) {
+    _exactLatency(ptr.getInnerPtr(), latency).unwrap();
+  }
+
+  /// Sets an ideal [`latency`][1] constraint in milliseconds.
+  ///
+  /// Converts the provided [latency] into an `u32`. Throws an [ArgumentError]
+  /// if conversion fails.
+  ///
+  /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-latency
+  void idealLatency(int latency) {
+    _idealLatency(ptr.getInnerPtr(), latency).unwrap();
+  }
+
+  /// Sets a [`voiceIsolation`][1] constraint.
+  ///
+  /// [1]: https://w3.org/TR/mediacapture-streams#dfn-voiceisolation
+  void voiceIsolation(bool enabled) {
+    _voiceIsolation(ptr.getInnerPtr(), enabled ? 1 : 0);
+  }
+
+  /// Sets a [MuteStrategy] of the sent audio track.
+  void muteStrategy(MuteStrategy strategy) {
+    _muteStrategy(ptr.getInnerPtr(), strategy.index);
+  }
+
+  /// Sets an [AudioRoutePolicy] of the sent audio track.
+  void routePolicy(AudioRoutePolicy policy) {
+    _routePolicy(ptr.getInnerPtr(), policy.index);
+  }
+
   /// Drops the associated Rust struct and nulls the local [Pointer] to it.
   @moveSemantics
   void free() {
@@ -19,6 +19,10 @@ typedef _onRemoteTrackAdded_C = Result Function(Pointer, Handle);
 typedef _onRemoteTrackAdded_Dart = Result Function(
     Pointer, void Function(Pointer));
 
+typedef _onRemoteTrackRemoved_C = Result Function(Pointer, Handle);
+typedef _onRemoteTrackRemoved_Dart = Result Function(
+    Pointer, void Function(Pointer));
+
 typedef _onQualityScoreUpdate_C = Result Function(Pointer, Handle);
 typedef _onQualityScoreUpdate_Dart = Result Function(
     Pointer, void Function(int));
@@ -36,6 +40,10 @@ final _onRemoteTrackAdded =
     dl.lookupFunction<_onRemoteTrackAdded_C, _onRemoteTrackAdded_Dart>(
         'ConnectionHandle__on_remote_track_added');
 
+final _onRemoteTrackRemoved =
+    dl.lookupFunction<_onRemoteTrackRemoved_C, _onRemoteTrackRemoved_Dart>(
+        'ConnectionHandle__on_remote_track_removed');
+
 final _onQualityScoreUpdate =
     dl.lookupFunction<_onQualityScoreUpdate_C, _onQualityScoreUpdate_Dart>(
         'ConnectionHandle__on_quality_score_update');
@@ -82,6 +90,19 @@ class ConnectionHandle {
     }).unwrap();
   }
 
+  /// Sets callback, invoked when a [RemoteMediaTrack] is removed from this
+  /// `Connection` by a server.
+  ///
+  /// Throws a [StateError] if an underlying object has been disposed, e.g.
+  /// [free] was called on this [ConnectionHandle], or on a [Jason], or on a
+  /// `RoomHandle` that implicitly owns native object behind this
+  /// [ConnectionHandle].
+  void onRemoteTrackRemoved(void Function(RemoteMediaTrack) f) {
+    _onRemoteTrackRemoved(ptr.getInnerPtr(), (t) {
+      f(RemoteMediaTrack(NullablePointer(t)));
+    }).unwrap();
+  }
+
   /// Sets callback, invoked when a connection quality score is updated by a
   /// server.
   ///
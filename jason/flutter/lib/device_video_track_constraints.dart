@@ -19,6 +19,12 @@ typedef _exactFacingMode_Dart = void Function(Pointer, int);
 typedef _idealFacingMode_C = Void Function(Pointer, Uint8);
 typedef _idealFacingMode_Dart = void Function(Pointer, int);
 
+typedef _contentHint_C = Void Function(Pointer, Uint8);
+typedef _contentHint_Dart = void Function(Pointer, int);
+
+typedef _degradationPreference_C = Void Function(Pointer, Uint8);
+typedef _degradationPreference_Dart = void Function(Pointer, int);
+
 typedef _exactHeight_C = Result Function(Pointer, Int64);
 typedef _exactHeight_Dart = Result Function(Pointer, int);
 
@@ -37,6 +43,24 @@ typedef _idealWidth_Dart = Result Function(Pointer, int);
 typedef _widthInRange_C = Result Function(Pointer, Int64, Int64);
 typedef _widthInRange_Dart = Result Function(Pointer, int, int);
 
+typedef _torch_C = Void Function(Pointer, Uint8);
+typedef _torch_Dart = void Function(Pointer, int);
+
+typedef _exactZoom_C = Result Function(Pointer, Int64);
+typedef _exactZoom_Dart = Result Function(Pointer, int);
+
+typedef _idealZoom_C = Result Function(Pointer, Int64);
+typedef _idealZoom_Dart = Result Function(Pointer, int);
+
+typedef _zoomInRange_C = Result Function(Pointer, Int64, Int64);
+typedef _zoomInRange_Dart = Result Function(Pointer, int, int);
+
+typedef _exactFocusMode_C = Void Function(Pointer, Uint8);
+typedef _exactFocusMode_Dart = void Function(Pointer, int);
+
+typedef _idealFocusMode_C = Void Function(Pointer, Uint8);
+typedef _idealFocusMode_Dart = void Function(Pointer, int);
+
 typedef _free_C = Void Function(Pointer);
 typedef _free_Dart = void Function(Pointer);
 
@@ -72,6 +96,33 @@ final _idealWidth = dl.lookupFunction<_idealWidth_C, _idealWidth_Dart>(
 final _widthInRange = dl.lookupFunction<_widthInRange_C, _widthInRange_Dart>(
     'DeviceVideoTrackConstraints__width_in_range');
 
+final _contentHint = dl.lookupFunction<_contentHint_C, _contentHint_Dart>(
+    'DeviceVideoTrackConstraints__content_hint');
+
+final _degradationPreference =
+    dl.lookupFunction<_degradationPreference_C, _degradationPreference_Dart>(
+        'DeviceVideoTrackConstraints__degradation_preference');
+
+final _torch = dl.lookupFunction<_torch_C, _torch_Dart>(
+    'DeviceVideoTrackConstraints__torch');
+
+final _exactZoom = dl.lookupFunction<_exactZoom_C, _exactZoom_Dart>(
+    'DeviceVideoTrackConstraints__exact_zoom');
+
+final _idealZoom = dl.lookupFunction<_idealZoom_C, _idealZoom_Dart>(
+    'DeviceVideoTrackConstraints__ideal_zoom');
+
+final _zoomInRange = dl.lookupFunction<_zoomInRange_C, _zoomInRange_Dart>(
+    'DeviceVideoTrackConstraints__zoom_in_range');
+
+final _exactFocusMode =
+    dl.lookupFunction<_exactFocusMode_C, _exactFocusMode_Dart>(
+        'DeviceVideoTrackConstraints__exact_focus_mode');
+
+final _idealFocusMode =
+    dl.lookupFunction<_idealFocusMode_C, _idealFocusMode_Dart>(
+        'DeviceVideoTrackConstraints__ideal_focus_mode');
+
 final _free =
     dl.lookupFunction<_free_C, _free_Dart>('DeviceVideoTrackConstraints__free');
 
@@ -95,6 +146,58 @@ enum FacingMode {
   Right,
 }
 
+/// Describes focus modes of a camera.
+///
+/// Representation of the [`MeteringMode`][1].
+///
+/// [1]: https://w3.org/TR/image-capture#dom-meteringmode
+enum FocusMode {
+  /// No focus is applied.
+  None,
+
+  /// Focus is fixed at the distance set manually.
+  Manual,
+
+  /// Focus is adjusted once and then fixed.
+  SingleShot,
+
+  /// Focus is adjusted continuously.
+  Continuous,
+}
+
+/// Hint of a video track contents, letting a browser choose how to encode it.
+///
+/// Representation of a [video content hint][1].
+///
+/// [1]: https://w3.org/TR/mst-content-hint#video-content-hints
+enum ContentHint {
+  /// Video where motion is important (webcam or a video playback).
+  Motion,
+
+  /// Video where details are important (presentation slides or web pages).
+  Detail,
+
+  /// Video where details are extra important, containing text content.
+  Text,
+}
+
+/// Preference of what a browser should sacrifice when sending a video track
+/// under constrained bandwidth.
+///
+/// Representation of a [`RTCDegradationPreference`][1].
+///
+/// [1]: https://w3.org/TR/webrtc/#dom-rtcdegradationpreference
+enum DegradationPreference {
+  /// Degrade resolution in order to maintain framerate.
+  MaintainFramerate,
+
+  /// Degrade framerate in order to maintain resolution.
+  MaintainResolution,
+
+  /// Degrade both framerate and resolution in a balanced way.
+  Balanced,
+}
+
 /// Constraints applicable to video tracks sourced from some media device.
 class DeviceVideoTrackConstraints {
   /// [Pointer] to the Rust struct backing this object.
@@ -186,6 +289,71 @@ class DeviceVideoTrackConstraints {
     _widthInRange(ptr.getInnerPtr(), min, max).unwrap();
   }
 
+  /// Sets a [`contentHint`][1] of the sent video track.
+  ///
+  /// [1]: https://w3.org/TR/mst-content-hint#dom-mediastreamtrack-contenthint
+  void contentHint(ContentHint hint) {
+    _contentHint(ptr.getInnerPtr(), hint.index);
+  }
+
+  /// Sets a [`degradationPreference`][1] of the video track sender.
+  ///
+  /// [1]: https://w3.org/TR/webrtc/#dom-rtcdegradationpreference
+  void degradationPreference(DegradationPreference preference) {
+    _degradationPreference(ptr.getInnerPtr(), preference.index);
+  }
+
+  /// Sets a [`torch`][1] constraint.
+  ///
+  /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-torch
+  void torch(bool enabled) {
+    _torch(ptr.getInnerPtr(), enabled ? 1 : 0);
+  }
+
+  /// Sets an exact [`zoom`][1] constraint in percents.
+  ///
+  /// Converts the provided [zoom] into an `u32`. Throws an [ArgumentError] if
+  /// conversion fails.
+  ///
+  /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-zoom
+  void exactZoom(int zoom) {
+    _exactZoom(ptr.getInnerPtr(), zoom).unwrap();
+  }
+
+  /// Sets an ideal [`zoom`][1] constraint in percents.
+  ///
+  /// Converts the provided [zoom] into an `u32`. Throws an [ArgumentError] if
+  /// conversion fails.
+  ///
+  /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-zoom
+  void idealZoom(int zoom) {
+    _idealZoom(ptr.getInnerPtr(), zoom).unwrap();
+  }
+
+  /// Sets a range of a [`zoom`][1] constraint in percents.
+  ///
+  /// Converts the provided [min] and [max] into an `u32`. Throws an
+  /// [ArgumentError] if conversion fails.
+  ///
+  /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-zoom
+  void zoomInRange(int min, int max) {
+    _zoomInRange(ptr.getInnerPtr(), min, max).unwrap();
+  }
+
+  /// Sets an exact [`focusMode`][1] constraint.
+  ///
+  /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-focusmode
+  void exactFocusMode(FocusMode focusMode) {
+    _exactFocusMode(ptr.getInnerPtr(), focusMode.index);
+  }
+
+  /// Sets an ideal [`focusMode`][1] constraint.
+  ///
+  /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-focusmode
+  void idealFocusMode(FocusMode focusMode) {
+    _idealFocusMode(ptr.getInnerPtr(), focusMode.index);
+  }
+
   /// Drops the associated Rust struct and nulls the local [Pointer] to it.
   @moveSemantics
   void free() {
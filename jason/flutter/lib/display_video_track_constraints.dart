@@ -1,5 +1,6 @@
 import 'dart:ffi';
 
+import 'device_video_track_constraints.dart';
 import 'jason.dart';
 import 'util/move_semantic.dart';
 import 'util/nullable_pointer.dart';
@@ -7,12 +8,25 @@ import 'util/nullable_pointer.dart';
 typedef _new_C = Pointer Function();
 typedef _new_Dart = Pointer Function();
 
+typedef _contentHint_C = Void Function(Pointer, Uint8);
+typedef _contentHint_Dart = void Function(Pointer, int);
+
+typedef _degradationPreference_C = Void Function(Pointer, Uint8);
+typedef _degradationPreference_Dart = void Function(Pointer, int);
+
 typedef _free_C = Void Function(Pointer);
 typedef _free_Dart = void Function(Pointer);
 
 final _new =
     dl.lookupFunction<_new_C, _new_Dart>('DisplayVideoTrackConstraints__new');
 
+final _contentHint = dl.lookupFunction<_contentHint_C, _contentHint_Dart>(
+    'DisplayVideoTrackConstraints__content_hint');
+
+final _degradationPreference =
+    dl.lookupFunction<_degradationPreference_C, _degradationPreference_Dart>(
+        'DisplayVideoTrackConstraints__degradation_preference');
+
 final _free_Dart _free = dl
     .lookupFunction<_free_C, _free_Dart>('DisplayVideoTrackConstraints__free');
 
@@ -21,6 +35,20 @@ class DisplayVideoTrackConstraints {
   /// [Pointer] to the Rust struct backing this object.
   final NullablePointer ptr = NullablePointer(_new());
 
+  /// Sets a [`contentHint`][1] of the sent video track.
+  ///
+  /// [1]: https://w3.org/TR/mst-content-hint#dom-mediastreamtrack-contenthint
+  void contentHint(ContentHint hint) {
+    _contentHint(ptr.getInnerPtr(), hint.index);
+  }
+
+  /// Sets a [`degradationPreference`][1] of the video track sender.
+  ///
+  /// [1]: https://w3.org/TR/webrtc/#dom-rtcdegradationpreference
+  void degradationPreference(DegradationPreference preference) {
+    _degradationPreference(ptr.getInnerPtr(), preference.index);
+  }
+
   /// Drops the associated Rust struct and nulls the local [Pointer] to it.
   @moveSemantics
   void free() {
@@ -208,6 +208,9 @@ enum RpcClientExceptionKind {
 
   /// RPC session has been finished. This is a terminal state.
   SessionFinished,
+
+  /// Reconnection with a backoff was cancelled.
+  ReconnectionCancelled,
 }
 
 /// Exception thrown when the requested media state transition could not be
@@ -1,12 +1,36 @@
+import 'dart:convert';
 import 'dart:ffi';
 
 import 'package:ffi/ffi.dart';
 
+import '../jason.dart';
 import '../util/move_semantic.dart';
 import '../util/nullable_pointer.dart';
 import 'native_string.dart';
 import 'unbox_handle.dart';
 
+typedef _freeArray_C = Void Function(Pointer<_ValueArray>);
+typedef _freeArray_Dart = void Function(Pointer<_ValueArray>);
+
+typedef _freeMap_C = Void Function(Pointer<_MapArray>);
+typedef _freeMap_Dart = void Function(Pointer<_MapArray>);
+
+typedef _freeSizedString_C = Void Function(Pointer<_SizedString>);
+typedef _freeSizedString_Dart = void Function(Pointer<_SizedString>);
+
+/// Frees an array of [ForeignValue]s returned from Rust.
+final _freeArray = dl.lookupFunction<_freeArray_C, _freeArray_Dart>(
+    'DartValueArray_free');
+
+/// Frees a map of [ForeignValue]s returned from Rust.
+final _freeMap =
+    dl.lookupFunction<_freeMap_C, _freeMap_Dart>('DartMap_free');
+
+/// Frees a not null-terminated string returned from Rust.
+final _freeSizedString =
+    dl.lookupFunction<_freeSizedString_C, _freeSizedString_Dart>(
+        'SizedString_free');
+
 /// Type-erased value that can be transferred via FFI boundaries to/from Rust.
 class ForeignValue extends Struct {
   /// Index of the used [_ForeignValueFields] union field.
@@ -39,6 +63,12 @@ class ForeignValue extends Struct {
         return _payload.string.nativeStringToDartString();
       case 4:
         return _payload.number;
+      case 5:
+        return _payload.array.intoList();
+      case 6:
+        return _payload.map.intoMap();
+      case 7:
+        return _payload.sizedString.intoDartString();
       default:
         throw TypeError();
     }
@@ -102,4 +132,99 @@ class _ForeignValueFields extends Union {
   /// Integer value.
   @Int64()
   external int number;
+
+  /// [Pointer] to an array of [ForeignValue]s.
+  external Pointer<_ValueArray> array;
+
+  /// [Pointer] to a map of [ForeignValue]s.
+  external Pointer<_MapArray> map;
+
+  /// [Pointer] to a not null-terminated UTF-8 string.
+  external Pointer<_SizedString> sizedString;
+}
+
+/// Array of [ForeignValue]s allocated by Rust.
+class _ValueArray extends Struct {
+  /// [Pointer] to the first array element.
+  external Pointer<ForeignValue> _ptr;
+
+  /// Length of this [_ValueArray].
+  @Uint64()
+  external int _len;
+}
+
+extension _ValueArrayToList on Pointer<_ValueArray> {
+  /// Converts this [_ValueArray] to a Dart's [List], converting all its
+  /// elements via [ForeignValue.toDart].
+  @moveSemantics
+  List<dynamic> intoList() {
+    try {
+      var out = List<dynamic>.empty(growable: true);
+      for (var i = 0; i < ref._len; i++) {
+        out.add(ref._ptr.elementAt(i).ref.toDart());
+      }
+      return out;
+    } finally {
+      _freeArray(this);
+    }
+  }
+}
+
+/// Entry of a [_MapArray].
+class _MapEntry extends Struct {
+  /// Key of this [_MapEntry].
+  external ForeignValue key;
+
+  /// Value of this [_MapEntry].
+  external ForeignValue value;
+}
+
+/// Map of [ForeignValue]s allocated by Rust.
+class _MapArray extends Struct {
+  /// [Pointer] to the first map entry.
+  external Pointer<_MapEntry> _ptr;
+
+  /// Number of entries in this [_MapArray].
+  @Uint64()
+  external int _len;
+}
+
+extension _MapArrayToMap on Pointer<_MapArray> {
+  /// Converts this [_MapArray] to a Dart's [Map], converting all its keys and
+  /// values via [ForeignValue.toDart].
+  @moveSemantics
+  Map<dynamic, dynamic> intoMap() {
+    try {
+      var out = <dynamic, dynamic>{};
+      for (var i = 0; i < ref._len; i++) {
+        var entry = ref._ptr.elementAt(i).ref;
+        out[entry.key.toDart()] = entry.value.toDart();
+      }
+      return out;
+    } finally {
+      _freeMap(this);
+    }
+  }
+}
+
+/// Not null-terminated UTF-8 string allocated by Rust.
+class _SizedString extends Struct {
+  /// [Pointer] to the first byte of the string.
+  external Pointer<Uint8> _ptr;
+
+  /// Length of this [_SizedString] in bytes.
+  @Uint64()
+  external int _len;
+}
+
+extension _SizedStringToString on Pointer<_SizedString> {
+  /// Converts this [_SizedString] to a Dart's [String].
+  @moveSemantics
+  String intoDartString() {
+    try {
+      return utf8.decode(ref._ptr.asTypedList(ref._len));
+    } finally {
+      _freeSizedString(this);
+    }
+  }
 }
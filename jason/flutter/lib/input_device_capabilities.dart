@@ -0,0 +1,166 @@
+import 'dart:ffi';
+
+import 'device_video_track_constraints.dart';
+import 'ffi/foreign_value.dart';
+import 'jason.dart';
+import 'util/move_semantic.dart';
+import 'util/nullable_pointer.dart';
+
+typedef _bound_C = ForeignValue Function(Pointer);
+typedef _bound_Dart = ForeignValue Function(Pointer);
+
+typedef _voiceIsolation_C = Uint8 Function(Pointer);
+typedef _voiceIsolation_Dart = int Function(Pointer);
+
+typedef _facingModes_C = ForeignValue Function(Pointer);
+typedef _facingModes_Dart = ForeignValue Function(Pointer);
+
+typedef _free_C = Void Function(Pointer);
+typedef _free_Dart = void Function(Pointer);
+
+
+final _minSampleRate = dl.lookupFunction<_bound_C, _bound_Dart>(
+    'InputDeviceCapabilities__min_sample_rate');
+
+final _maxSampleRate = dl.lookupFunction<_bound_C, _bound_Dart>(
+    'InputDeviceCapabilities__max_sample_rate');
+
+final _minChannelCount = dl.lookupFunction<_bound_C, _bound_Dart>(
+    'InputDeviceCapabilities__min_channel_count');
+
+final _maxChannelCount = dl.lookupFunction<_bound_C, _bound_Dart>(
+    'InputDeviceCapabilities__max_channel_count');
+
+final _minLatency = dl.lookupFunction<_bound_C, _bound_Dart>(
+    'InputDeviceCapabilities__min_latency');
+
+final _maxLatency = dl.lookupFunction<_bound_C, _bound_Dart>(
+    'InputDeviceCapabilities__max_latency');
+
+final _minWidth = dl.lookupFunction<_bound_C, _bound_Dart>(
+    'InputDeviceCapabilities__min_width');
+
+final _maxWidth = dl.lookupFunction<_bound_C, _bound_Dart>(
+    'InputDeviceCapabilities__max_width');
+
+final _minHeight = dl.lookupFunction<_bound_C, _bound_Dart>(
+    'InputDeviceCapabilities__min_height');
+
+final _maxHeight = dl.lookupFunction<_bound_C, _bound_Dart>(
+    'InputDeviceCapabilities__max_height');
+
+final _minFrameRate = dl.lookupFunction<_bound_C, _bound_Dart>(
+    'InputDeviceCapabilities__min_frame_rate');
+
+final _maxFrameRate = dl.lookupFunction<_bound_C, _bound_Dart>(
+    'InputDeviceCapabilities__max_frame_rate');
+
+final _voiceIsolation =
+    dl.lookupFunction<_voiceIsolation_C, _voiceIsolation_Dart>(
+        'InputDeviceCapabilities__voice_isolation');
+
+final _facingModes = dl.lookupFunction<_facingModes_C, _facingModes_Dart>(
+    'InputDeviceCapabilities__facing_modes');
+
+final _free =
+    dl.lookupFunction<_free_C, _free_Dart>('InputDeviceCapabilities__free');
+
+/// Capabilities of a media input device, describing the constraints values it
+/// supports.
+///
+/// Representation of a [`MediaTrackCapabilities`][1].
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#dom-mediatrackcapabilities
+class InputDeviceCapabilities {
+  /// [Pointer] to the Rust struct backing this object.
+  late NullablePointer ptr;
+
+  /// Constructs a new [InputDeviceCapabilities] backed by a Rust struct behind
+  /// the provided [Pointer].
+  InputDeviceCapabilities(this.ptr);
+
+  /// Returns the minimum supported sample rate of the audio in Hz, if known.
+  int? minSampleRate() {
+    return _minSampleRate(ptr.getInnerPtr()).toDart();
+  }
+
+  /// Returns the maximum supported sample rate of the audio in Hz, if known.
+  int? maxSampleRate() {
+    return _maxSampleRate(ptr.getInnerPtr()).toDart();
+  }
+
+  /// Returns the minimum supported number of independent audio channels, if
+  /// known.
+  int? minChannelCount() {
+    return _minChannelCount(ptr.getInnerPtr()).toDart();
+  }
+
+  /// Returns the maximum supported number of independent audio channels, if
+  /// known.
+  int? maxChannelCount() {
+    return _maxChannelCount(ptr.getInnerPtr()).toDart();
+  }
+
+  /// Returns the minimum supported latency of the audio in milliseconds, if
+  /// known.
+  int? minLatency() {
+    return _minLatency(ptr.getInnerPtr()).toDart();
+  }
+
+  /// Returns the maximum supported latency of the audio in milliseconds, if
+  /// known.
+  int? maxLatency() {
+    return _maxLatency(ptr.getInnerPtr()).toDart();
+  }
+
+  /// Returns the minimum supported width of the video in pixels, if known.
+  int? minWidth() {
+    return _minWidth(ptr.getInnerPtr()).toDart();
+  }
+
+  /// Returns the maximum supported width of the video in pixels, if known.
+  int? maxWidth() {
+    return _maxWidth(ptr.getInnerPtr()).toDart();
+  }
+
+  /// Returns the minimum supported height of the video in pixels, if known.
+  int? minHeight() {
+    return _minHeight(ptr.getInnerPtr()).toDart();
+  }
+
+  /// Returns the maximum supported height of the video in pixels, if known.
+  int? maxHeight() {
+    return _maxHeight(ptr.getInnerPtr()).toDart();
+  }
+
+  /// Returns the minimum supported frame rate of the video in frames per
+  /// second, if known.
+  int? minFrameRate() {
+    return _minFrameRate(ptr.getInnerPtr()).toDart();
+  }
+
+  /// Returns the maximum supported frame rate of the video in frames per
+  /// second, if known.
+  int? maxFrameRate() {
+    return _maxFrameRate(ptr.getInnerPtr()).toDart();
+  }
+
+  /// Indicates whether the device supports isolating a voice from a background
+  /// noise.
+  bool voiceIsolation() {
+    return _voiceIsolation(ptr.getInnerPtr()) > 0;
+  }
+
+  /// Returns [FacingMode]s the device may face.
+  List<FacingMode> facingModes() {
+    List<dynamic> modes = _facingModes(ptr.getInnerPtr()).toDart();
+    return modes.map((mode) => FacingMode.values[mode as int]).toList();
+  }
+
+  /// Drops the associated Rust struct and nulls the local [Pointer] to it.
+  @moveSemantics
+  void free() {
+    _free(ptr.getInnerPtr());
+    ptr.free();
+  }
+}
@@ -3,6 +3,7 @@ import 'dart:ffi';
 import 'package:ffi/ffi.dart';
 
 import 'ffi/native_string.dart';
+import 'input_device_capabilities.dart';
 import 'jason.dart';
 import 'track_kinds.dart';
 import 'util/move_semantic.dart';
@@ -20,6 +21,9 @@ typedef _kind_Dart = int Function(Pointer);
 typedef _nativeGroupId_C = Pointer<Utf8> Function(Pointer);
 typedef _nativeGroupId_Dart = Pointer<Utf8> Function(Pointer);
 
+typedef _capabilities_C = Pointer Function(Pointer);
+typedef _capabilities_Dart = Pointer Function(Pointer);
+
 typedef _free_C = Void Function(Pointer);
 typedef _free_Dart = void Function(Pointer);
 
@@ -34,6 +38,9 @@ final _label =
 final _deviceId = dl
     .lookupFunction<_deviceId_C, _deviceId_Dart>('InputDeviceInfo__device_id');
 
+final _capabilities = dl.lookupFunction<_capabilities_C, _capabilities_Dart>(
+    'InputDeviceInfo__capabilities');
+
 final _free = dl.lookupFunction<_free_C, _free_Dart>('InputDeviceInfo__free');
 
 /// [`MediaDeviceInfo`][1] interface.
@@ -82,6 +89,16 @@ class InputDeviceInfo {
     return _nativeGroupId(ptr.getInnerPtr()).nativeStringToDartString();
   }
 
+  /// Returns [InputDeviceCapabilities] of the represented device, or `null` if
+  /// the platform doesn't provide them.
+  InputDeviceCapabilities? capabilities() {
+    var capabilities = _capabilities(ptr.getInnerPtr());
+    if (capabilities.address == 0) {
+      return null;
+    }
+    return InputDeviceCapabilities(NullablePointer(capabilities));
+  }
+
   /// Drops the associated Rust struct and nulls the local [Pointer] to it.
   @moveSemantics
   void free() {
@@ -3,6 +3,8 @@ library jason;
 import 'dart:ffi';
 import 'dart:io';
 
+import 'analytics_event.dart';
+import 'audio_route.dart' as audio_route;
 import 'ffi/callback.dart' as callback;
 import 'ffi/completer.dart' as completer;
 import 'ffi/exceptions.dart' as exceptions;
@@ -24,6 +26,10 @@ typedef _closeRoom_Dart = void Function(Pointer, Pointer);
 typedef _initRoom_C = Pointer Function(Pointer);
 typedef _initRoom_Dart = Pointer Function(Pointer);
 
+typedef _setAnalyticsSink_C = Void Function(Pointer, Handle);
+typedef _setAnalyticsSink_Dart = void Function(
+    Pointer, void Function(Pointer));
+
 typedef _free_C = Void Function(Pointer);
 typedef _free_Dart = void Function(Pointer);
 
@@ -43,6 +49,10 @@ final _media_manager = dl.lookupFunction<_mediaManager_C, _mediaManager_Dart>(
 final _initRoom =
     dl.lookupFunction<_initRoom_C, _initRoom_Dart>('Jason__init_room');
 
+final _setAnalyticsSink =
+    dl.lookupFunction<_setAnalyticsSink_C, _setAnalyticsSink_Dart>(
+        'Jason__set_analytics_sink');
+
 final _close_room =
     dl.lookupFunction<_closeRoom_C, _closeRoom_Dart>('Jason__close_room');
 
@@ -72,6 +82,7 @@ DynamicLibrary _dl_load() {
   callback.registerFunctions(dl);
   completer.registerFunctions(dl);
   exceptions.registerFunctions(dl);
+  audio_route.registerFunctions(dl);
 
   executor = Executor(dl);
 
@@ -97,6 +108,16 @@ class Jason {
     return RoomHandle(NullablePointer(_initRoom(ptr.getInnerPtr())));
   }
 
+  /// Sets callback, invoked with [AnalyticsEvent]s of all the `Room`s of this
+  /// [Jason], replacing the previously set one (if any).
+  ///
+  /// May be used to pipe metrics into an application telemetry.
+  void setAnalyticsSink(void Function(AnalyticsEvent) f) {
+    _setAnalyticsSink(ptr.getInnerPtr(), (t) {
+      f(AnalyticsEvent(NullablePointer(t)));
+    });
+  }
+
   /// Closes the `Room` by the provided [RoomHandle].
   void closeRoom(@moveSemantics RoomHandle room) {
     _close_room(ptr.getInnerPtr(), room.ptr.getInnerPtr());
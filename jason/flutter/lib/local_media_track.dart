@@ -1,5 +1,6 @@
 import 'dart:ffi';
 
+import 'device_video_track_constraints.dart';
 import 'jason.dart';
 import 'track_kinds.dart';
 import 'util/move_semantic.dart';
@@ -11,6 +12,15 @@ typedef _kind_Dart = int Function(Pointer);
 typedef _mediaSourceKind_C = Uint8 Function(Pointer);
 typedef _mediaSourceKind_Dart = int Function(Pointer);
 
+typedef _reason_C = Uint8 Function(Pointer);
+typedef _reason_Dart = int Function(Pointer);
+
+typedef _previous_C = Pointer Function(Pointer);
+typedef _previous_Dart = Pointer Function(Pointer);
+
+typedef _applyAdvanced_C = Handle Function(Pointer, Pointer);
+typedef _applyAdvanced_Dart = Object Function(Pointer, Pointer);
+
 typedef _free_C = Void Function(Pointer);
 typedef _free_Dart = void Function(Pointer);
 
@@ -20,8 +30,36 @@ final _sourceKind =
     dl.lookupFunction<_mediaSourceKind_C, _mediaSourceKind_Dart>(
         'LocalMediaTrack__media_source_kind');
 
+final _reason =
+    dl.lookupFunction<_reason_C, _reason_Dart>('LocalMediaTrack__reason');
+
+final _previous = dl.lookupFunction<_previous_C, _previous_Dart>(
+    'LocalMediaTrack__previous');
+
+final _applyAdvanced =
+    dl.lookupFunction<_applyAdvanced_C, _applyAdvanced_Dart>(
+        'LocalMediaTrack__apply_advanced');
+
 final _free = dl.lookupFunction<_free_C, _free_Dart>('LocalMediaTrack__free');
 
+/// Reason of a [LocalMediaTrack] being passed to the
+/// `RoomHandle.onLocalTrack()` callback.
+enum LocalTrackReason {
+  /// Track is published for the first time.
+  Initial,
+
+  /// Track replaces the previous one, captured from another device.
+  DeviceSwitch,
+
+  /// Track replaces the previous one, captured from the same device with other
+  /// constraints.
+  ConstraintsChange,
+
+  /// Track replaces the previous one, which was ended by the UA or the
+  /// hardware.
+  Ended,
+}
+
 /// Strongly referenced media track received from a
 /// [`getUserMedia()`][1]/[`getDisplayMedia()`][2] request.
 ///
@@ -52,6 +90,36 @@ class LocalMediaTrack {
     return MediaSourceKind.values[index];
   }
 
+  /// Returns the [LocalTrackReason] of this [LocalMediaTrack] being passed to
+  /// the `RoomHandle.onLocalTrack()` callback.
+  LocalTrackReason reason() {
+    var index = _reason(ptr.getInnerPtr());
+    return LocalTrackReason.values[index];
+  }
+
+  /// Returns the [LocalMediaTrack] replaced by this one, if any.
+  LocalMediaTrack? previous() {
+    var previous = _previous(ptr.getInnerPtr());
+    if (previous.address == 0) {
+      return null;
+    }
+    return LocalMediaTrack(NullablePointer(previous));
+  }
+
+  /// Applies the torch, zoom and focus mode of the provided
+  /// [DeviceVideoTrackConstraints] to this [LocalMediaTrack] as advanced
+  /// constraints, so the ones unsupported by the device are ignored.
+  ///
+  /// Other constraints of the provided [DeviceVideoTrackConstraints] are not
+  /// applied.
+  ///
+  /// Throws an exception if this [LocalMediaTrack] is not a device video track
+  /// or the constraints cannot be applied.
+  Future<void> applyAdvanced(DeviceVideoTrackConstraints constraints) async {
+    await (_applyAdvanced(ptr.getInnerPtr(), constraints.ptr.getInnerPtr())
+        as Future);
+  }
+
   /// Drops the associated Rust struct and nulls the local [Pointer] to it.
   ///
   /// Note, that this is a strong reference, so freeing it will stop underlying
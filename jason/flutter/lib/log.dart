@@ -0,0 +1,95 @@
+import 'dart:ffi';
+
+import 'ffi/foreign_value.dart';
+import 'ffi/result.dart';
+import 'jason.dart';
+
+typedef _setLevel_C = Result Function(Int64);
+typedef _setLevel_Dart = Result Function(int);
+
+typedef _setScopeLevel_C = Result Function(Int64, ForeignValue);
+typedef _setScopeLevel_Dart = Result Function(int, ForeignValue);
+
+typedef _onLog_C = Void Function(Handle);
+typedef _onLog_Dart = void Function(void Function(String));
+
+typedef _clearCallbacks_C = Void Function();
+typedef _clearCallbacks_Dart = void Function();
+
+final _setLevel =
+    dl.lookupFunction<_setLevel_C, _setLevel_Dart>('log__set_level');
+
+final _setScopeLevel = dl.lookupFunction<_setScopeLevel_C, _setScopeLevel_Dart>(
+    'log__set_scope_level');
+
+final _onLog = dl.lookupFunction<_onLog_C, _onLog_Dart>('log__on_log');
+
+final _clearCallbacks =
+    dl.lookupFunction<_clearCallbacks_C, _clearCallbacks_Dart>(
+        'log__clear_callbacks');
+
+/// Level of `Jason` logging.
+enum LogLevel {
+  /// Logging is disabled.
+  off,
+
+  /// Only errors are logged.
+  error,
+
+  /// Warnings and more severe records are logged.
+  warn,
+
+  /// Informational and more severe records are logged.
+  info,
+
+  /// Debug and more severe records are logged.
+  debug,
+
+  /// All the records are logged.
+  trace,
+}
+
+/// Part of `Jason`, logging of which can be configured independently.
+enum LogScope {
+  /// Signalling with a media server.
+  rpc,
+
+  /// `PeerConnection`s management.
+  peer,
+
+  /// Local and remote media management.
+  media,
+}
+
+/// Sets the global [LogLevel] of `Jason` logging.
+///
+/// Defaults to [LogLevel.debug].
+void setLogLevel(LogLevel level) {
+  _setLevel(level.index).unwrap();
+}
+
+/// Sets [LogLevel] of the provided [LogScope], overriding the global one.
+///
+/// `null` makes the [LogScope] use the global [LogLevel] again.
+void setLogScopeLevel(LogScope scope, LogLevel? level) {
+  var level_arg =
+      level == null ? ForeignValue.none() : ForeignValue.fromInt(level.index);
+  try {
+    _setScopeLevel(scope.index, level_arg.ref).unwrap();
+  } finally {
+    level_arg.free();
+  }
+}
+
+/// Registers a callback, invoked with every log record passing the configured
+/// [LogLevel]s.
+///
+/// May be used to collect logs for bug reports.
+void onLog(void Function(String) f) {
+  _onLog(f);
+}
+
+/// Removes all the callbacks registered via [onLog].
+void clearLogCallbacks() {
+  _clearCallbacks();
+}
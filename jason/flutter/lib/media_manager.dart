@@ -1,9 +1,11 @@
 import 'dart:ffi';
 
+import 'abort_signal.dart';
 import 'ffi/ptrarray.dart';
 import 'input_device_info.dart';
 import 'jason.dart';
 import 'local_media_track.dart';
+import 'media_permissions.dart';
 import 'media_stream_settings.dart';
 import 'util/move_semantic.dart';
 import 'util/nullable_pointer.dart';
@@ -11,8 +13,11 @@ import 'util/nullable_pointer.dart';
 typedef _initLocalTracks_C = Handle Function(Pointer, Pointer);
 typedef _initLocalTracks_Dart = Object Function(Pointer, Pointer);
 
-typedef _enumerateDevices_C = Handle Function(Pointer);
-typedef _enumerateDevices_Dart = Object Function(Pointer);
+typedef _enumerateDevices_C = Handle Function(Pointer, Pointer);
+typedef _enumerateDevices_Dart = Object Function(Pointer, Pointer);
+
+typedef _queryPermissions_C = Handle Function(Pointer);
+typedef _queryPermissions_Dart = Object Function(Pointer);
 
 typedef _free_C = Void Function(Pointer);
 typedef _free_Dart = void Function(Pointer);
@@ -25,6 +30,10 @@ final _enumerateDevices =
     dl.lookupFunction<_enumerateDevices_C, _enumerateDevices_Dart>(
         'MediaManagerHandle__enumerate_devices');
 
+final _queryPermissions =
+    dl.lookupFunction<_queryPermissions_C, _queryPermissions_Dart>(
+        'MediaManagerHandle__query_permissions');
+
 final _free =
     dl.lookupFunction<_free_C, _free_Dart>('MediaManagerHandle__free');
 
@@ -74,8 +83,11 @@ class MediaManagerHandle {
   ///
   /// Throws a [EnumerateDevicesException] if a request of platform media
   /// devices access failed.
-  Future<List<InputDeviceInfo>> enumerateDevices() async {
-    Pointer pointer = await (_enumerateDevices(ptr.getInnerPtr()) as Future);
+  ///
+  /// Throws a [StateError] once the provided [signal] is aborted.
+  Future<List<InputDeviceInfo>> enumerateDevices({AbortSignal? signal}) async {
+    Pointer pointer = await (_enumerateDevices(
+        ptr.getInnerPtr(), signal?.ptr.getInnerPtr() ?? nullptr) as Future);
     return pointer
         .cast<PtrArray>()
         .intoPointerList()
@@ -83,6 +95,16 @@ class MediaManagerHandle {
         .toList();
   }
 
+  /// Returns [MediaPermissions] of using a camera, a microphone and capturing
+  /// a screen, without prompting a user.
+  ///
+  /// Permissions which can't be determined on the current platform are
+  /// reported as [PermissionState.Unknown].
+  Future<MediaPermissions> queryPermissions() async {
+    Pointer pointer = await (_queryPermissions(ptr.getInnerPtr()) as Future);
+    return MediaPermissions(NullablePointer(pointer));
+  }
+
   /// Drops the associated Rust struct and nulls the local [Pointer] to it.
   @moveSemantics
   void free() {
@@ -0,0 +1,76 @@
+import 'dart:ffi';
+
+import 'jason.dart';
+import 'util/move_semantic.dart';
+import 'util/nullable_pointer.dart';
+
+typedef _state_C = Uint8 Function(Pointer);
+typedef _state_Dart = int Function(Pointer);
+
+typedef _free_C = Void Function(Pointer);
+typedef _free_Dart = void Function(Pointer);
+
+final _camera =
+    dl.lookupFunction<_state_C, _state_Dart>('MediaPermissions__camera');
+
+final _microphone =
+    dl.lookupFunction<_state_C, _state_Dart>('MediaPermissions__microphone');
+
+final _screen =
+    dl.lookupFunction<_state_C, _state_Dart>('MediaPermissions__screen');
+
+final _free = dl.lookupFunction<_free_C, _free_Dart>('MediaPermissions__free');
+
+/// State of a permission to use some media.
+///
+/// Representation of a [`PermissionState`][1].
+///
+/// [1]: https://w3.org/TR/permissions#dom-permissionstate
+enum PermissionState {
+  /// Permission is granted, so media can be acquired without prompting a user.
+  Granted,
+
+  /// Permission is denied, so media acquisition will fail without prompting a
+  /// user.
+  Denied,
+
+  /// User will be prompted for a permission on media acquisition.
+  Prompt,
+
+  /// Permission state can't be determined on the current platform without
+  /// prompting a user.
+  Unknown,
+}
+
+/// States of permissions to use media devices, queried without prompting a
+/// user.
+class MediaPermissions {
+  /// [Pointer] to the Rust struct backing this object.
+  late NullablePointer ptr;
+
+  /// Constructs a new [MediaPermissions] backed by a Rust struct behind the
+  /// provided [Pointer].
+  MediaPermissions(this.ptr);
+
+  /// Returns [PermissionState] of using a camera.
+  PermissionState camera() {
+    return PermissionState.values[_camera(ptr.getInnerPtr())];
+  }
+
+  /// Returns [PermissionState] of using a microphone.
+  PermissionState microphone() {
+    return PermissionState.values[_microphone(ptr.getInnerPtr())];
+  }
+
+  /// Returns [PermissionState] of capturing a screen.
+  PermissionState screen() {
+    return PermissionState.values[_screen(ptr.getInnerPtr())];
+  }
+
+  /// Drops the associated Rust struct and nulls the local [Pointer] to it.
+  @moveSemantics
+  void free() {
+    _free(ptr.getInnerPtr());
+    ptr.free();
+  }
+}
@@ -12,9 +12,15 @@ typedef _reconnect_with_delay_C = Handle Function(Pointer, Int64);
 typedef _reconnect_with_delay_Dart = Object Function(Pointer, int);
 
 typedef _reconnect_with_backoff_C = Handle Function(
-    Pointer, Int64, Double, Int64, ForeignValue);
+    Pointer, Int64, Double, Int64, ForeignValue, Double, ForeignValue);
 typedef _reconnect_with_backoff_Dart = Object Function(
-    Pointer, int, double, int, ForeignValue);
+    Pointer, int, double, int, ForeignValue, double, ForeignValue);
+
+typedef _onGiveUp_C = Void Function(Pointer, Handle);
+typedef _onGiveUp_Dart = void Function(Pointer, void Function());
+
+typedef _cancelBackoff_C = Void Function(Pointer);
+typedef _cancelBackoff_Dart = void Function(Pointer);
 
 final _free = dl.lookupFunction<_free_C, _free_Dart>('ReconnectHandle__free');
 
@@ -26,6 +32,13 @@ final _reconnect_with_backoff =
     dl.lookupFunction<_reconnect_with_backoff_C, _reconnect_with_backoff_Dart>(
         'ReconnectHandle__reconnect_with_backoff');
 
+final _onGiveUp = dl.lookupFunction<_onGiveUp_C, _onGiveUp_Dart>(
+    'ReconnectHandle__on_give_up');
+
+final _cancelBackoff =
+    dl.lookupFunction<_cancelBackoff_C, _cancelBackoff_Dart>(
+        'ReconnectHandle__cancel_backoff');
+
 /// External handle used to reconnect to a media server when connection is lost.
 ///
 /// This handle is passed to the `RoomHandle.onConnectionLoss()` callback.
@@ -67,7 +80,12 @@ class ReconnectHandle {
   /// This might cause a busy loop, so it's not recommended.
   ///
   /// Max elapsed time can be limited with an optional [maxElapsedTimeMs]
-  /// argument.
+  /// argument, and the number of attempts with an optional [maxAttempts]
+  /// argument. Once any of them is exceeded, the [onGiveUp] callback is
+  /// invoked.
+  ///
+  /// Each delay is randomized by the given [jitter] factor (clamped into
+  /// `[0.0, 1.0]` range).
   ///
   /// If the `Room` is already reconnecting then new reconnection attempt won't
   /// be performed. Instead, it will wait for the first reconnection attempt
@@ -77,17 +95,43 @@ class ReconnectHandle {
   ///
   /// Throws [StateError] if the underlying [Pointer] has been freed.
   ///
-  /// Converts the provided [startingDelayMs], [maxDelay] and [maxElapsedTimeMs]
-  /// into an `u32`s. Throws an [ArgumentError] if any conversion fails.
+  /// Converts the provided [startingDelayMs], [maxDelay], [maxElapsedTimeMs]
+  /// and [maxAttempts] into an `u32`s. Throws an [ArgumentError] if any
+  /// conversion fails.
   Future<void> reconnectWithBackoff(
       int startingDelayMs, double multiplier, int maxDelay,
-      [int? maxElapsedTimeMs]) async {
+      [int? maxElapsedTimeMs, double jitter = 0, int? maxAttempts]) async {
     var maxElapsedTimeMs_arg = maxElapsedTimeMs == null
         ? ForeignValue.none()
         : ForeignValue.fromInt(maxElapsedTimeMs);
+    var maxAttempts_arg = maxAttempts == null
+        ? ForeignValue.none()
+        : ForeignValue.fromInt(maxAttempts);
+
+    await (_reconnect_with_backoff(
+        ptr.getInnerPtr(),
+        startingDelayMs,
+        multiplier,
+        maxDelay,
+        maxElapsedTimeMs_arg.ref,
+        jitter,
+        maxAttempts_arg.ref) as Future);
+  }
 
-    await (_reconnect_with_backoff(ptr.getInnerPtr(), startingDelayMs,
-        multiplier, maxDelay, maxElapsedTimeMs_arg.ref) as Future);
+  /// Sets callback, invoked when [reconnectWithBackoff] gives up, because its
+  /// max attempts count or max elapsed time is exceeded.
+  ///
+  /// Throws [StateError] if the underlying [Pointer] has been freed.
+  void onGiveUp(void Function() f) {
+    _onGiveUp(ptr.getInnerPtr(), f);
+  }
+
+  /// Cancels all the ongoing [reconnectWithBackoff] calls, making them throw a
+  /// `RpcClientException` of `ReconnectionCancelled` kind.
+  ///
+  /// Throws [StateError] if the underlying [Pointer] has been freed.
+  void cancelBackoff() {
+    _cancelBackoff(ptr.getInnerPtr());
   }
 
   /// Drops the associated Rust struct and nulls the local [Pointer] to it.
@@ -1,6 +1,7 @@
 import 'dart:ffi';
 import 'package:ffi/ffi.dart';
 
+import 'ffi/foreign_value.dart';
 import 'ffi/native_string.dart';
 import 'jason.dart';
 import 'util/move_semantic.dart';
@@ -9,18 +10,29 @@ import 'util/nullable_pointer.dart';
 typedef _reason_C = Pointer<Utf8> Function(Pointer);
 typedef _reason_Dart = Pointer<Utf8> Function(Pointer);
 
+typedef _kind_C = Uint8 Function(Pointer);
+typedef _kind_Dart = int Function(Pointer);
+
 typedef _isClosedByServer_C = Int8 Function(Pointer);
 typedef _isClosedByServer_Dart = int Function(Pointer);
 
 typedef _isErr_C = Int8 Function(Pointer);
 typedef _isErr_Dart = int Function(Pointer);
 
+typedef _isRetryable_C = Int8 Function(Pointer);
+typedef _isRetryable_Dart = int Function(Pointer);
+
+typedef _retryAfter_C = ForeignValue Function(Pointer);
+typedef _retryAfter_Dart = ForeignValue Function(Pointer);
+
 typedef _free_C = Void Function(Pointer);
 typedef _free_Dart = void Function(Pointer);
 
 final _reason =
     dl.lookupFunction<_reason_C, _reason_Dart>('RoomCloseReason__reason');
 
+final _kind = dl.lookupFunction<_kind_C, _kind_Dart>('RoomCloseReason__kind');
+
 final _isClosedByServer =
     dl.lookupFunction<_isClosedByServer_C, _isClosedByServer_Dart>(
         'RoomCloseReason__is_closed_by_server');
@@ -28,8 +40,66 @@ final _isClosedByServer =
 final _isErr =
     dl.lookupFunction<_isErr_C, _isErr_Dart>('RoomCloseReason__is_err');
 
+final _isRetryable = dl.lookupFunction<_isRetryable_C, _isRetryable_Dart>(
+    'RoomCloseReason__is_retryable');
+
+final _retryAfter = dl.lookupFunction<_retryAfter_C, _retryAfter_Dart>(
+    'RoomCloseReason__retry_after');
+
 final _free = dl.lookupFunction<_free_C, _free_Dart>('RoomCloseReason__free');
 
+/// Kind of a [RoomCloseReason], allowing to distinguish close reasons without
+/// comparing their string representations.
+enum RoomCloseKind {
+  /// Client session was finished on a server side.
+  Finished,
+
+  /// Old connection was closed due to a client reconnection.
+  Reconnected,
+
+  /// Connection has been inactive for a while and thus considered idle by a
+  /// server.
+  Idle,
+
+  /// Establishing of connection with a server was rejected on server side.
+  Rejected,
+
+  /// Server internal error has occurred while connecting.
+  InternalError,
+
+  /// Client was evicted on the server side.
+  Evicted,
+
+  /// Media server is draining, so doesn't accept new clients.
+  Draining,
+
+  /// Media server admits too many clients at the moment, so doesn't accept
+  /// new ones.
+  Overloaded,
+
+  /// Media server has been drained and is shutting down.
+  Drained,
+
+  /// Client has sent a message exceeding the maximum size allowed by media
+  /// server.
+  MessageTooLarge,
+
+  /// `Room` was dropped without any close reason.
+  RoomUnexpectedlyDropped,
+
+  /// `Room` was normally closed by client.
+  RoomClosed,
+
+  /// RPC client was unexpectedly dropped.
+  RpcClientUnexpectedlyDropped,
+
+  /// RPC transport was unexpectedly dropped.
+  RpcTransportUnexpectedlyDropped,
+
+  /// RPC session was unexpectedly dropped.
+  SessionUnexpectedlyDropped,
+}
+
 /// Reason of why a `Room` has been closed.
 ///
 /// This struct is passed into the `RoomHandle.onClose()` callback.
@@ -46,6 +116,13 @@ class RoomCloseReason {
     return _reason(ptr.getInnerPtr()).nativeStringToDartString();
   }
 
+  /// Returns the [RoomCloseKind] of the `Room`'s close reason, which
+  /// [reason()] is a string representation of.
+  RoomCloseKind kind() {
+    var index = _kind(ptr.getInnerPtr());
+    return RoomCloseKind.values[index];
+  }
+
   /// Indicates whether the `Room` was closed by server.
   bool isClosedByServer() {
     return _isClosedByServer(ptr.getInnerPtr()) > 0;
@@ -56,6 +133,19 @@ class RoomCloseReason {
     return _isErr(ptr.getInnerPtr()) > 0;
   }
 
+  /// Indicates whether the `Room` may be rejoined, since it was closed by
+  /// server for a temporary reason.
+  bool isRetryable() {
+    return _isRetryable(ptr.getInnerPtr()) > 0;
+  }
+
+  /// Returns the delay after which the `Room` should be rejoined, if server has
+  /// hinted it.
+  Duration? retryAfter() {
+    int? ms = _retryAfter(ptr.getInnerPtr()).toDart();
+    return ms == null ? null : Duration(milliseconds: ms);
+  }
+
   /// Drops the associated Rust struct and nulls the local [Pointer] to it.
   @moveSemantics
   void free() {
@@ -2,6 +2,8 @@ import 'dart:ffi';
 
 import 'package:ffi/ffi.dart';
 
+import 'abort_signal.dart';
+import 'audio_route.dart';
 import 'connection_handle.dart';
 import 'ffi/foreign_value.dart';
 import 'ffi/result.dart';
@@ -31,17 +33,28 @@ typedef _onConnectionLoss_C = Result Function(Pointer, Handle);
 typedef _onConnectionLoss_Dart = Result Function(
     Pointer, void Function(Pointer));
 
+typedef _onAudioRouteChange_C = Result Function(Pointer, Handle);
+typedef _onAudioRouteChange_Dart = Result Function(
+    Pointer, void Function(int));
+
 typedef _onFailedLocalMedia_C = Result Function(Pointer, Handle);
 typedef _onFailedLocalMedia_Dart = Result Function(
     Pointer, void Function(Pointer<Handle>));
 
-typedef _join_C = Handle Function(Pointer, Pointer<Utf8>);
-typedef _join_Dart = Object Function(Pointer, Pointer<Utf8>);
+typedef _join_C = Handle Function(Pointer, Pointer<Utf8>, Pointer);
+typedef _join_Dart = Object Function(Pointer, Pointer<Utf8>, Pointer);
+
+typedef _joinWithoutMedia_C = Handle Function(Pointer, Pointer<Utf8>, Pointer);
+typedef _joinWithoutMedia_Dart = Object Function(
+    Pointer, Pointer<Utf8>, Pointer);
+
+typedef _publish_C = Handle Function(Pointer);
+typedef _publish_Dart = Object Function(Pointer);
 
 typedef _setLocalMediaSettings_C = Handle Function(
-    Pointer, Pointer, Uint8, Uint8);
+    Pointer, Pointer, Uint8, Uint8, Pointer);
 typedef _setLocalMediaSettings_Dart = Object Function(
-    Pointer, Pointer, int, int);
+    Pointer, Pointer, int, int, Pointer);
 
 typedef _muteAudio_C = Handle Function(Pointer);
 typedef _muteAudio_Dart = Object Function(Pointer);
@@ -64,6 +77,24 @@ typedef _enableVideo_Dart = Object Function(Pointer, ForeignValue);
 typedef _disableAudio_C = Handle Function(Pointer);
 typedef _disableAudio_Dart = Object Function(Pointer);
 
+typedef _updateToken_C = Result Function(Pointer, Pointer<Utf8>);
+typedef _updateToken_Dart = Result Function(Pointer, Pointer<Utf8>);
+
+typedef _exportDebugDump_C = Handle Function(Pointer);
+typedef _exportDebugDump_Dart = Object Function(Pointer);
+
+typedef _setStatsInterval_C = Result Function(Pointer, Int64);
+typedef _setStatsInterval_Dart = Result Function(Pointer, int);
+
+typedef _pollStatsNow_C = Handle Function(Pointer);
+typedef _pollStatsNow_Dart = Object Function(Pointer);
+
+typedef _retryLocalMedia_C = Handle Function(Pointer);
+typedef _retryLocalMedia_Dart = Object Function(Pointer);
+
+typedef _setLocalMediaRetryInterval_C = Result Function(Pointer, Int64);
+typedef _setLocalMediaRetryInterval_Dart = Result Function(Pointer, int);
+
 typedef _enableAudio_C = Handle Function(Pointer);
 typedef _enableAudio_Dart = Object Function(Pointer);
 
@@ -79,6 +110,15 @@ typedef _disableRemoteVideo_Dart = Object Function(Pointer);
 typedef _enableRemoteVideo_C = Handle Function(Pointer);
 typedef _enableRemoteVideo_Dart = Object Function(Pointer);
 
+typedef _setRecvMediaPolicy_C = Result Function(Pointer, Uint8);
+typedef _setRecvMediaPolicy_Dart = Result Function(Pointer, int);
+
+typedef _enableRemoteMediaFrom_C = Handle Function(Pointer, Pointer<Utf8>);
+typedef _enableRemoteMediaFrom_Dart = Object Function(Pointer, Pointer<Utf8>);
+
+typedef _disableRemoteMediaFrom_C = Handle Function(Pointer, Pointer<Utf8>);
+typedef _disableRemoteMediaFrom_Dart = Object Function(Pointer, Pointer<Utf8>);
+
 final _free = dl.lookupFunction<_free_C, _free_Dart>('RoomHandle__free');
 
 final _onNewConnection =
@@ -95,12 +135,23 @@ final _onConnectionLoss =
     dl.lookupFunction<_onConnectionLoss_C, _onConnectionLoss_Dart>(
         'RoomHandle__on_connection_loss');
 
+final _onAudioRouteChange =
+    dl.lookupFunction<_onAudioRouteChange_C, _onAudioRouteChange_Dart>(
+        'RoomHandle__on_audio_route_change');
+
 final _onFailedLocalMedia =
     dl.lookupFunction<_onFailedLocalMedia_C, _onFailedLocalMedia_Dart>(
         'RoomHandle__on_failed_local_media');
 
 final _join = dl.lookupFunction<_join_C, _join_Dart>('RoomHandle__join');
 
+final _joinWithoutMedia =
+    dl.lookupFunction<_joinWithoutMedia_C, _joinWithoutMedia_Dart>(
+        'RoomHandle__join_without_media');
+
+final _publish =
+    dl.lookupFunction<_publish_C, _publish_Dart>('RoomHandle__publish');
+
 final _setLocalMediaSettings =
     dl.lookupFunction<_setLocalMediaSettings_C, _setLocalMediaSettings_Dart>(
         'RoomHandle__set_local_media_settings');
@@ -126,6 +177,28 @@ final _enableVideo = dl.lookupFunction<_enableVideo_C, _enableVideo_Dart>(
 final _disableAudio = dl.lookupFunction<_disableAudio_C, _disableAudio_Dart>(
     'RoomHandle__disable_audio');
 
+final _updateToken = dl.lookupFunction<_updateToken_C, _updateToken_Dart>(
+    'RoomHandle__update_token');
+
+final _exportDebugDump =
+    dl.lookupFunction<_exportDebugDump_C, _exportDebugDump_Dart>(
+        'RoomHandle__export_debug_dump');
+
+final _setStatsInterval =
+    dl.lookupFunction<_setStatsInterval_C, _setStatsInterval_Dart>(
+        'RoomHandle__set_stats_interval');
+
+final _pollStatsNow = dl.lookupFunction<_pollStatsNow_C, _pollStatsNow_Dart>(
+    'RoomHandle__poll_stats_now');
+
+final _retryLocalMedia =
+    dl.lookupFunction<_retryLocalMedia_C, _retryLocalMedia_Dart>(
+        'RoomHandle__retry_local_media');
+
+final _setLocalMediaRetryInterval = dl.lookupFunction<
+        _setLocalMediaRetryInterval_C, _setLocalMediaRetryInterval_Dart>(
+    'RoomHandle__set_local_media_retry_interval');
+
 final _enableAudio = dl.lookupFunction<_enableAudio_C, _enableAudio_Dart>(
     'RoomHandle__enable_audio');
 
@@ -145,6 +218,28 @@ final _enableRemoteVideo =
     dl.lookupFunction<_enableRemoteVideo_C, _enableRemoteVideo_Dart>(
         'RoomHandle__enable_remote_video');
 
+final _setRecvMediaPolicy =
+    dl.lookupFunction<_setRecvMediaPolicy_C, _setRecvMediaPolicy_Dart>(
+        'RoomHandle__set_recv_media_policy');
+
+final _enableRemoteMediaFrom =
+    dl.lookupFunction<_enableRemoteMediaFrom_C, _enableRemoteMediaFrom_Dart>(
+        'RoomHandle__enable_remote_media_from');
+
+final _disableRemoteMediaFrom = dl.lookupFunction<_disableRemoteMediaFrom_C,
+    _disableRemoteMediaFrom_Dart>('RoomHandle__disable_remote_media_from');
+
+/// Policy of enabling the media received from remote `Member`s in a `Room`.
+enum RecvMediaPolicy {
+  /// All the received media is enabled, unless it's disabled explicitly.
+  Auto,
+
+  /// No received media is enabled, unless it's enabled explicitly for its
+  /// sending `Member`, so it's negotiated as disabled with a media server from
+  /// the very start.
+  Manual,
+}
+
 /// External handle to a `Room`.
 class RoomHandle {
   /// [Pointer] to the Rust struct that backing this object.
@@ -168,15 +263,45 @@ class RoomHandle {
   /// Throws [FormatException] if the provided [token] string has bad format.
   ///
   /// Throws `RpcClientException` if could not connect to media server.
-  Future<void> join(String token) async {
+  ///
+  /// Once the provided [signal] is aborted, the connection establishment is
+  /// canceled and a [StateError] is thrown.
+  Future<void> join(String token, {AbortSignal? signal}) async {
     var tokenPtr = token.toNativeUtf8();
     try {
-      await (_join(ptr.getInnerPtr(), tokenPtr) as Future);
+      await (_join(ptr.getInnerPtr(), tokenPtr,
+          signal?.ptr.getInnerPtr() ?? nullptr) as Future);
     } finally {
       calloc.free(tokenPtr);
     }
   }
 
+  /// Connects to a media server and joins the `Room` with the provided
+  /// authorization [token], without acquiring any local media.
+  ///
+  /// Remote media is received right away, while local media is acquired and
+  /// published only on a [RoomHandle.publish] call.
+  ///
+  /// Throws the same exceptions as [RoomHandle.join], and can be aborted via
+  /// the provided [signal] the same way.
+  Future<void> joinWithoutMedia(String token, {AbortSignal? signal}) async {
+    var tokenPtr = token.toNativeUtf8();
+    try {
+      await (_joinWithoutMedia(ptr.getInnerPtr(), tokenPtr,
+          signal?.ptr.getInnerPtr() ?? nullptr) as Future);
+    } finally {
+      calloc.free(tokenPtr);
+    }
+  }
+
+  /// Acquires local media and starts publishing it in this `Room`.
+  ///
+  /// Throws the same exceptions as [RoomHandle.enableAudio] and
+  /// [RoomHandle.enableVideo].
+  Future<void> publish() async {
+    await (_publish(ptr.getInnerPtr()) as Future);
+  }
+
   /// Updates this `Room`'s [MediaStreamSettings]. This affects all the
   /// `PeerConnection`s in this `Room`. If [MediaStreamSettings] are configured
   /// for some `Room`, then this `Room` can only send media tracks that
@@ -202,11 +327,19 @@ class RoomHandle {
   ///
   /// Throws a [MediaSettingsUpdateException] if settings could not be updated.
   ///
+  /// Once the provided [signal] is aborted, the update is canceled, the
+  /// previous [MediaStreamSettings] are restored and a [StateError] is thrown.
+  ///
   /// [1]: https://w3.org/TR/mediacapture-streams#dom-mediadevices-getusermedia
   Future<void> setLocalMediaSettings(
-      MediaStreamSettings settings, bool stopFirst, bool rollbackOnFail) async {
-    await (_setLocalMediaSettings(ptr.getInnerPtr(), settings.ptr.getInnerPtr(),
-        stopFirst ? 1 : 0, rollbackOnFail ? 1 : 0) as Future);
+      MediaStreamSettings settings, bool stopFirst, bool rollbackOnFail,
+      {AbortSignal? signal}) async {
+    await (_setLocalMediaSettings(
+        ptr.getInnerPtr(),
+        settings.ptr.getInnerPtr(),
+        stopFirst ? 1 : 0,
+        rollbackOnFail ? 1 : 0,
+        signal?.ptr.getInnerPtr() ?? nullptr) as Future);
   }
 
   /// Mutes outbound audio in this `Room`.
@@ -379,6 +512,52 @@ class RoomHandle {
     await (_disableRemoteVideo(ptr.getInnerPtr()) as Future);
   }
 
+  /// Sets the [RecvMediaPolicy] of this `Room`.
+  ///
+  /// With [RecvMediaPolicy.Manual] the media received from a remote `Member`
+  /// stays disabled until it's enabled via [RoomHandle.enableRemoteMediaFrom].
+  /// Affects only the media received after this call, so should be called
+  /// before [RoomHandle.join].
+  ///
+  /// Throws a [StateError] if the underlying [Pointer] has been freed.
+  void setRecvMediaPolicy(RecvMediaPolicy policy) {
+    _setRecvMediaPolicy(ptr.getInnerPtr(), policy.index).unwrap();
+  }
+
+  /// Enables inbound media from the remote `Member` with the provided ID in
+  /// this `Room`.
+  ///
+  /// Throws a [StateError] if the underlying [Pointer] has been freed.
+  ///
+  /// Throws a `MediaStateTransitionException` if
+  /// [RoomHandle.disableRemoteMediaFrom] was called while enabling or a media
+  /// server didn't approve this state transition.
+  Future<void> enableRemoteMediaFrom(String memberId) async {
+    var memberIdPtr = memberId.toNativeUtf8();
+    try {
+      await (_enableRemoteMediaFrom(ptr.getInnerPtr(), memberIdPtr) as Future);
+    } finally {
+      calloc.free(memberIdPtr);
+    }
+  }
+
+  /// Disables inbound media from the remote `Member` with the provided ID in
+  /// this `Room`.
+  ///
+  /// Throws a [StateError] if the underlying [Pointer] has been freed.
+  ///
+  /// Throws a `MediaStateTransitionException` if
+  /// [RoomHandle.enableRemoteMediaFrom] was called while disabling or a media
+  /// server didn't approve this state transition.
+  Future<void> disableRemoteMediaFrom(String memberId) async {
+    var memberIdPtr = memberId.toNativeUtf8();
+    try {
+      await (_disableRemoteMediaFrom(ptr.getInnerPtr(), memberIdPtr) as Future);
+    } finally {
+      calloc.free(memberIdPtr);
+    }
+  }
+
   /// Sets callback, invoked when a new `Connection` with some remote `Peer`
   /// is established.
   ///
@@ -426,6 +605,19 @@ class RoomHandle {
     }).unwrap();
   }
 
+  /// Sets callback, invoked when an audio output route changes (e.g.
+  /// headphones are unplugged), providing an [AudioRouteChangeReason].
+  ///
+  /// If the configured `AudioRoutePolicy` requires outbound audio to be muted
+  /// on this change, then the callback is invoked once it's muted.
+  ///
+  /// Throws [StateError] if the underlying [Pointer] has been freed.
+  void onAudioRouteChange(void Function(AudioRouteChangeReason) f) {
+    _onAudioRouteChange(ptr.getInnerPtr(), (reason) {
+      f(AudioRouteChangeReason.values[reason]);
+    }).unwrap();
+  }
+
   /// Sets callback, invoked on a local media acquisition failures.
   ///
   /// Throws [StateError] if the underlying [Pointer] has been freed.
@@ -435,6 +627,71 @@ class RoomHandle {
     }).unwrap();
   }
 
+  /// Renews the credential of this `Room`'s `Member` with the provided
+  /// authorization [token], without rejoining the `Room`.
+  ///
+  /// Media server closes this `Room` if the provided credential is invalid.
+  ///
+  /// Throws a [StateError] if the underlying [Pointer] has been freed.
+  void updateToken(String token) {
+    var tokenPtr = token.toNativeUtf8();
+    try {
+      _updateToken(ptr.getInnerPtr(), tokenPtr).unwrap();
+    } finally {
+      calloc.free(tokenPtr);
+    }
+  }
+
+  /// Returns a JSON document describing the current state of all the
+  /// `PeerConnection`s of this `Room` (their SDPs, selected ICE candidate pairs
+  /// and latest stats) along with a history of this `Room` state
+  /// synchronization with a media server.
+  ///
+  /// Intended to be attached to bug reports.
+  ///
+  /// Throws a [StateError] if the underlying [Pointer] has been freed.
+  Future<String> exportDebugDump() async {
+    return await (_exportDebugDump(ptr.getInnerPtr()) as Future) as String;
+  }
+
+  /// Sets the interval (in milliseconds) of collecting stats of all the
+  /// `PeerConnection`s of this `Room` and sending them to a media server.
+  ///
+  /// `0` stops periodic stats collecting, so it's done only via
+  /// [RoomHandle.pollStatsNow].
+  ///
+  /// Throws an [ArgumentError] if the provided [intervalMs] is not an `u32`.
+  void setStatsInterval(int intervalMs) {
+    _setStatsInterval(ptr.getInnerPtr(), intervalMs).unwrap();
+  }
+
+  /// Collects stats of all the `PeerConnection`s of this `Room` right away and
+  /// sends them to a media server.
+  Future<void> pollStatsNow() async {
+    await (_pollStatsNow(ptr.getInnerPtr()) as Future);
+  }
+
+  /// Re-acquires the local media, which has failed to be acquired previously
+  /// (and so has been reported to [RoomHandle.onFailedLocalMedia] callback),
+  /// and re-enables the affected outbound media in this `Room`.
+  ///
+  /// Does nothing if there is no failed local media.
+  ///
+  /// Throws the same exceptions as [RoomHandle.enableAudio].
+  Future<void> retryLocalMedia() async {
+    await (_retryLocalMedia(ptr.getInnerPtr()) as Future);
+  }
+
+  /// Sets the interval (in milliseconds) of automatic
+  /// [RoomHandle.retryLocalMedia] attempts.
+  ///
+  /// `0` stops automatic retrying (default).
+  ///
+  /// Throws an [ArgumentError] if the provided [intervalMs] is not an `u32`.
+  void setLocalMediaRetryInterval(int intervalMs) {
+    _setLocalMediaRetryInterval(ptr.getInnerPtr(), intervalMs).unwrap();
+  }
+
   /// Drops the associated Rust struct and nulls the local [Pointer] to it.
   @moveSemantics
   void free() {
@@ -0,0 +1,106 @@
+//! Signal aborting asynchronous operations started from Dart side.
+
+use std::{cell::RefCell, future::Future, ptr};
+
+use futures::future::{self, AbortHandle};
+
+use super::{
+    utils::{DartError, StateError},
+    ForeignClass,
+};
+
+/// Signal aborting the asynchronous operations it's passed to.
+///
+/// Dart `Future`s cannot be cancelled, so this is an analogue of a JS
+/// [`AbortSignal`][1] for Dart side.
+///
+/// [1]: https://dom.spec.whatwg.org/#interface-AbortSignal
+#[derive(Debug, Default)]
+pub struct AbortSignal(RefCell<Inner>);
+
+/// Inner state of an [`AbortSignal`].
+#[derive(Debug, Default)]
+struct Inner {
+    /// Indicator whether the [`AbortSignal`] has been triggered already.
+    aborted: bool,
+
+    /// [`AbortHandle`]s of the operations to be aborted by the
+    /// [`AbortSignal`].
+    handles: Vec<AbortHandle>,
+}
+
+impl ForeignClass for AbortSignal {}
+
+impl AbortSignal {
+    /// Triggers this [`AbortSignal`], aborting all the operations it has been
+    /// passed to, and all the further ones.
+    pub fn abort(&self) {
+        let mut inner = self.0.borrow_mut();
+        inner.aborted = true;
+        for handle in inner.handles.drain(..) {
+            handle.abort();
+        }
+    }
+
+    /// Makes the provided [`Future`] abortable via the provided
+    /// [`AbortSignal`], if any.
+    ///
+    /// Once aborted, the returned [`Future`] resolves with a Dart
+    /// [`StateError`].
+    ///
+    /// # Safety
+    ///
+    /// The provided `signal` must point to a live [`AbortSignal`].
+    pub unsafe fn abortable<F, T, E>(
+        fut: F,
+        signal: Option<ptr::NonNull<Self>>,
+    ) -> impl Future<Output = Result<T, DartError>>
+    where
+        F: Future<Output = Result<T, E>> + 'static,
+        T: 'static,
+        E: Into<DartError> + 'static,
+    {
+        let (fut, handle) = future::abortable(fut);
+        if let Some(signal) = signal {
+            let mut inner = signal.as_ref().0.borrow_mut();
+            if inner.aborted {
+                handle.abort();
+            } else {
+                inner.handles.push(handle);
+            }
+        }
+
+        async move {
+            match fut.await {
+                Ok(res) => res.map_err(Into::into),
+                Err(_) => Err(StateError::new("Operation was aborted").into()),
+            }
+        }
+    }
+}
+
+/// Creates a new [`AbortSignal`].
+#[no_mangle]
+pub extern "C" fn AbortSignal__new() -> ptr::NonNull<AbortSignal> {
+    AbortSignal::default().into_ptr()
+}
+
+/// Triggers the provided [`AbortSignal`], aborting all the operations it has
+/// been passed to.
+#[no_mangle]
+pub unsafe extern "C" fn AbortSignal__abort(this: ptr::NonNull<AbortSignal>) {
+    this.as_ref().abort();
+}
+
+/// Frees the data behind the provided pointer.
+///
+/// Doesn't abort the operations the [`AbortSignal`] has been passed to.
+///
+/// # Safety
+///
+/// Should be called when object is no longer needed. Calling this more than
+/// once for the same pointer is equivalent to double free.
+#[no_mangle]
+pub unsafe extern "C" fn AbortSignal__free(this: ptr::NonNull<AbortSignal>) {
+    drop(AbortSignal::from_ptr(this));
+}
@@ -0,0 +1,49 @@
+use std::ptr;
+
+use super::{DartValueArg, ForeignClass};
+
+pub use crate::jason::{AnalyticsEvent, AnalyticsEventKind};
+
+impl ForeignClass for AnalyticsEvent {}
+
+/// Returns an [`AnalyticsEventKind`] of an [`AnalyticsEvent`].
+#[no_mangle]
+pub unsafe extern "C" fn AnalyticsEvent__kind(
+    this: ptr::NonNull<AnalyticsEvent>,
+) -> AnalyticsEventKind {
+    this.as_ref().kind()
+}
+
+/// Returns ID of the `PeerConnection` an [`AnalyticsEvent`] relates to, if
+/// any.
+#[no_mangle]
+pub unsafe extern "C" fn AnalyticsEvent__peer_id(
+    this: ptr::NonNull<AnalyticsEvent>,
+) -> DartValueArg<Option<u32>> {
+    DartValueArg::from(this.as_ref().peer_id().map(|id| id.0))
+}
+
+/// Returns duration (in milliseconds) of the stage an [`AnalyticsEvent`]
+/// finishes, if any.
+#[allow(clippy::cast_possible_truncation)]
+#[no_mangle]
+pub unsafe extern "C" fn AnalyticsEvent__duration(
+    this: ptr::NonNull<AnalyticsEvent>,
+) -> DartValueArg<Option<u32>> {
+    DartValueArg::from(
+        this.as_ref().duration().map(|d| d.as_millis() as u32),
+    )
+}
+
+/// Frees the data behind the provided pointer.
+///
+/// # Safety
+///
+/// Should be called when object is no longer needed. Calling this more than
+/// once for the same pointer is equivalent to double free.
+#[no_mangle]
+pub unsafe extern "C" fn AnalyticsEvent__free(
+    this: ptr::NonNull<AnalyticsEvent>,
+) {
+    drop(AnalyticsEvent::from_ptr(this));
+}
@@ -0,0 +1,34 @@
+use std::{os::raw::c_char, ptr};
+
+use super::{utils::string_into_c_str, ForeignClass};
+
+pub use crate::room::AppData;
+
+impl ForeignClass for AppData {}
+
+/// Returns ID of the remote `Member` which sent an [`AppData`].
+#[no_mangle]
+pub unsafe extern "C" fn AppData__from(
+    this: ptr::NonNull<AppData>,
+) -> ptr::NonNull<c_char> {
+    string_into_c_str(this.as_ref().from())
+}
+
+/// Returns JSON-encoded payload of an [`AppData`].
+#[no_mangle]
+pub unsafe extern "C" fn AppData__data(
+    this: ptr::NonNull<AppData>,
+) -> ptr::NonNull<c_char> {
+    string_into_c_str(this.as_ref().data().to_string())
+}
+
+/// Frees the data behind the provided pointer.
+///
+/// # Safety
+///
+/// Should be called when object is no longer needed. Calling this more than
+/// once for the same pointer is equivalent to double free.
+#[no_mangle]
+pub unsafe extern "C" fn AppData__free(this: ptr::NonNull<AppData>) {
+    drop(AppData::from_ptr(this));
+}
@@ -1,10 +1,13 @@
 //! Constraints applicable to audio tracks.
 
-use std::{os::raw::c_char, ptr};
+use std::{convert::TryFrom as _, os::raw::c_char, ptr};
 
-use super::{utils::c_str_into_string, ForeignClass};
+use super::{
+    utils::{c_str_into_string, ArgumentError, DartResult},
+    ForeignClass,
+};
 
-pub use crate::media::AudioTrackConstraints;
+pub use crate::media::{AudioRoutePolicy, AudioTrackConstraints, MuteStrategy};
 
 impl ForeignClass for AudioTrackConstraints {}
 
@@ -26,6 +29,180 @@ pub unsafe extern "C" fn AudioTrackConstraints__device_id(
     this.as_mut().device_id(c_str_into_string(device_id))
 }
 
+/// Sets an exact [sampleRate][1] constraint.
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-sampleRate
+#[no_mangle]
+pub unsafe extern "C" fn AudioTrackConstraints__exact_sample_rate(
+    mut this: ptr::NonNull<AudioTrackConstraints>,
+    sample_rate: i64,
+) -> DartResult {
+    match u32::try_from(sample_rate) {
+        Ok(v) => this.as_mut().exact_sample_rate(v),
+        Err(_) => {
+            return ArgumentError::new(
+                sample_rate,
+                "sample_rate",
+                "Expected u32",
+            )
+            .into();
+        }
+    };
+    Ok(()).into()
+}
+
+/// Sets an ideal [sampleRate][1] constraint.
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-sampleRate
+#[no_mangle]
+pub unsafe extern "C" fn AudioTrackConstraints__ideal_sample_rate(
+    mut this: ptr::NonNull<AudioTrackConstraints>,
+    sample_rate: i64,
+) -> DartResult {
+    match u32::try_from(sample_rate) {
+        Ok(v) => this.as_mut().ideal_sample_rate(v),
+        Err(_) => {
+            return ArgumentError::new(
+                sample_rate,
+                "sample_rate",
+                "Expected u32",
+            )
+            .into();
+        }
+    };
+    Ok(()).into()
+}
+
+/// Sets an exact [channelCount][1] constraint.
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-channelCount
+#[no_mangle]
+pub unsafe extern "C" fn AudioTrackConstraints__exact_channel_count(
+    mut this: ptr::NonNull<AudioTrackConstraints>,
+    channel_count: i64,
+) -> DartResult {
+    match u32::try_from(channel_count) {
+        Ok(v) => this.as_mut().exact_channel_count(v),
+        Err(_) => {
+            return ArgumentError::new(
+                channel_count,
+                "channel_count",
+                "Expected u32",
+            )
+            .into();
+        }
+    };
+    Ok(()).into()
+}
+
+/// Sets an ideal [channelCount][1] constraint.
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-channelCount
+#[no_mangle]
+pub unsafe extern "C" fn AudioTrackConstraints__ideal_channel_count(
+    mut this: ptr::NonNull<AudioTrackConstraints>,
+    channel_count: i64,
+) -> DartResult {
+    match u32::try_from(channel_count) {
+        Ok(v) => this.as_mut().ideal_channel_count(v),
+        Err(_) => {
+            return ArgumentError::new(
+                channel_count,
+                "channel_count",
+                "Expected u32",
+            )
+            .into();
+        }
+    };
+    Ok(()).into()
+}
+
+/// Sets an exact [latency][1] constraint in milliseconds.
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-latency
+#[no_mangle]
+pub unsafe extern "C" fn AudioTrackConstraints__exact_latency(
+    mut this: ptr::NonNull<AudioTrackConstraints>,
+    latency: i64,
+) -> DartResult {
+    match u32::try_from(latency) {
+        Ok(v) => this.as_mut().exact_latency(v),
+        Err(_) => {
+            return ArgumentError::new(latency, "latency", "Expected u32")
+                .into();
+        }
+    };
+    Ok(()).into()
+}
+
+/// Sets an ideal [latency][1] constraint in milliseconds.
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-latency
+#[no_mangle]
+pub unsafe extern "C" fn AudioTrackConstraints__ideal_latency(
+    mut this: ptr::NonNull<AudioTrackConstraints>,
+    latency: i64,
+) -> DartResult {
+    match u32::try_from(latency) {
+        Ok(v) => this.as_mut().ideal_latency(v),
+        Err(_) => {
+            return ArgumentError::new(latency, "latency", "Expected u32")
+                .into();
+        }
+    };
+    Ok(()).into()
+}
+
+/// Sets a range of a [sampleRate][1] constraint.
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-sampleRate
+#[no_mangle]
+pub unsafe extern "C" fn AudioTrackConstraints__sample_rate_in_range(
+    mut this: ptr::NonNull<AudioTrackConstraints>,
+    min: i64,
+    max: i64,
+) -> DartResult {
+    match (u32::try_from(min), u32::try_from(max)) {
+        (Ok(min), Ok(max)) => this.as_mut().sample_rate_in_range(min, max),
+        (Err(_), _) => {
+            return ArgumentError::new(min, "min", "Expected u32").into();
+        }
+        (_, Err(_)) => {
+            return ArgumentError::new(max, "max", "Expected u32").into();
+        }
+    };
+    Ok(()).into()
+}
+
+/// Sets a [voiceIsolation][1] constraint.
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#dfn-voiceisolation
+#[no_mangle]
+pub unsafe extern "C" fn AudioTrackConstraints__voice_isolation(
+    mut this: ptr::NonNull<AudioTrackConstraints>,
+    enabled: bool,
+) {
+    this.as_mut().voice_isolation(enabled);
+}
+
+/// Sets a [`MuteStrategy`] of the sent audio track.
+#[no_mangle]
+pub unsafe extern "C" fn AudioTrackConstraints__mute_strategy(
+    mut this: ptr::NonNull<AudioTrackConstraints>,
+    strategy: MuteStrategy,
+) {
+    this.as_mut().mute_strategy(strategy);
+}
+
+/// Sets an [`AudioRoutePolicy`] of the sent audio track.
+#[no_mangle]
+pub unsafe extern "C" fn AudioTrackConstraints__route_policy(
+    mut this: ptr::NonNull<AudioTrackConstraints>,
+    policy: AudioRoutePolicy,
+) {
+    this.as_mut().route_policy(policy);
+}
+
 /// Frees the data behind the provided pointer.
 ///
 /// # Safety
@@ -53,6 +53,22 @@ pub unsafe extern "C" fn ConnectionHandle__on_remote_track_added(
         .into()
 }
 
+/// Sets callback, invoked when a [`remote::Track`] is removed from this
+/// [`Connection`] by a server.
+///
+/// [`remote::Track`]: crate::media::track::remote::Track
+/// [`Connection`]: crate::connection::Connection
+#[no_mangle]
+pub unsafe extern "C" fn ConnectionHandle__on_remote_track_removed(
+    this: ptr::NonNull<ConnectionHandle>,
+    f: Dart_Handle,
+) -> DartResult {
+    this.as_ref()
+        .on_remote_track_removed(platform::Function::new(f))
+        .map_err(DartError::from)
+        .into()
+}
+
 /// Sets callback, invoked when a connection quality score is updated by
 /// a server.
 #[no_mangle]
@@ -133,6 +149,14 @@ mod mock {
             Ok(())
         }
 
+        pub fn on_remote_track_removed(
+            &self,
+            f: platform::Function<RemoteMediaTrack>,
+        ) -> Result<(), Traced<HandlerDetachedError>> {
+            f.call1(RemoteMediaTrack);
+            Ok(())
+        }
+
         pub fn on_quality_score_update(
             &self,
             f: platform::Function<u8>,
@@ -1,6 +1,8 @@
 use std::{convert::TryFrom as _, os::raw::c_char, ptr};
 
-use crate::media::FacingMode;
+use crate::media::{
+    ContentHint, DegradationPreference, FacingMode, FocusMode,
+};
 
 use super::{
     utils::{c_str_into_string, ArgumentError, DartResult},
@@ -162,6 +164,116 @@ pub unsafe extern "C" fn DeviceVideoTrackConstraints__width_in_range(
     Ok(()).into()
 }
 
+/// Sets a [contentHint][1] of the sent video track.
+///
+/// [1]: https://w3.org/TR/mst-content-hint#dom-mediastreamtrack-contenthint
+#[no_mangle]
+pub unsafe extern "C" fn DeviceVideoTrackConstraints__content_hint(
+    mut this: ptr::NonNull<DeviceVideoTrackConstraints>,
+    hint: ContentHint,
+) {
+    this.as_mut().content_hint(hint);
+}
+
+/// Sets a [degradationPreference][1] of the video track sender.
+///
+/// [1]: https://w3.org/TR/webrtc/#dom-rtcdegradationpreference
+#[no_mangle]
+pub unsafe extern "C" fn DeviceVideoTrackConstraints__degradation_preference(
+    mut this: ptr::NonNull<DeviceVideoTrackConstraints>,
+    preference: DegradationPreference,
+) {
+    this.as_mut().degradation_preference(preference);
+}
+
+/// Sets a [torch][1] constraint.
+///
+/// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-torch
+#[no_mangle]
+pub unsafe extern "C" fn DeviceVideoTrackConstraints__torch(
+    mut this: ptr::NonNull<DeviceVideoTrackConstraints>,
+    enabled: bool,
+) {
+    this.as_mut().torch(enabled);
+}
+
+/// Sets an exact [zoom][1] constraint in percents.
+///
+/// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-zoom
+#[no_mangle]
+pub unsafe extern "C" fn DeviceVideoTrackConstraints__exact_zoom(
+    mut this: ptr::NonNull<DeviceVideoTrackConstraints>,
+    zoom: i64,
+) -> DartResult {
+    match u32::try_from(zoom) {
+        Ok(z) => this.as_mut().exact_zoom(z),
+        Err(_) => {
+            return ArgumentError::new(zoom, "zoom", "Expected u32").into();
+        }
+    };
+    Ok(()).into()
+}
+
+/// Sets an ideal [zoom][1] constraint in percents.
+///
+/// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-zoom
+#[no_mangle]
+pub unsafe extern "C" fn DeviceVideoTrackConstraints__ideal_zoom(
+    mut this: ptr::NonNull<DeviceVideoTrackConstraints>,
+    zoom: i64,
+) -> DartResult {
+    match u32::try_from(zoom) {
+        Ok(z) => this.as_mut().ideal_zoom(z),
+        Err(_) => {
+            return ArgumentError::new(zoom, "zoom", "Expected u32").into();
+        }
+    };
+    Ok(()).into()
+}
+
+/// Sets a range of a [zoom][1] constraint in percents.
+///
+/// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-zoom
+#[no_mangle]
+pub unsafe extern "C" fn DeviceVideoTrackConstraints__zoom_in_range(
+    mut this: ptr::NonNull<DeviceVideoTrackConstraints>,
+    min: i64,
+    max: i64,
+) -> DartResult {
+    match (u32::try_from(min), u32::try_from(max)) {
+        (Ok(min), Ok(max)) => this.as_mut().zoom_in_range(min, max),
+        (Err(_), _) => {
+            return ArgumentError::new(min, "min", "Expected u32").into();
+        }
+        (_, Err(_)) => {
+            return ArgumentError::new(max, "max", "Expected u32").into();
+        }
+    };
+    Ok(()).into()
+}
+
+/// Sets an exact [focusMode][1] constraint.
+///
+/// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-focusmode
+#[no_mangle]
+pub unsafe extern "C" fn DeviceVideoTrackConstraints__exact_focus_mode(
+    mut this: ptr::NonNull<DeviceVideoTrackConstraints>,
+    focus_mode: FocusMode,
+) {
+    this.as_mut().exact_focus_mode(focus_mode);
+}
+
+/// Sets an ideal [focusMode][1] constraint.
+///
+/// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-focusmode
+#[no_mangle]
+pub unsafe extern "C" fn DeviceVideoTrackConstraints__ideal_focus_mode(
+    mut this: ptr::NonNull<DeviceVideoTrackConstraints>,
+    focus_mode: FocusMode,
+) {
+    this.as_mut().ideal_focus_mode(focus_mode);
+}
+
 /// Frees the data behind the provided pointer.
 ///
 /// # Safety
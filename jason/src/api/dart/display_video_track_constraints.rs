@@ -1,5 +1,7 @@
 use std::ptr;
 
+use crate::media::{ContentHint, DegradationPreference};
+
 use super::ForeignClass;
 
 pub use crate::media::DisplayVideoTrackConstraints;
@@ -14,6 +16,28 @@ pub extern "C" fn DisplayVideoTrackConstraints__new(
     DisplayVideoTrackConstraints::new().into_ptr()
 }
 
+/// Sets a [contentHint][1] of the sent video track.
+///
+/// [1]: https://w3.org/TR/mst-content-hint#dom-mediastreamtrack-contenthint
+#[no_mangle]
+pub unsafe extern "C" fn DisplayVideoTrackConstraints__content_hint(
+    mut this: ptr::NonNull<DisplayVideoTrackConstraints>,
+    hint: ContentHint,
+) {
+    this.as_mut().content_hint(hint);
+}
+
+/// Sets a [degradationPreference][1] of the video track sender.
+///
+/// [1]: https://w3.org/TR/webrtc/#dom-rtcdegradationpreference
+#[no_mangle]
+pub unsafe extern "C" fn DisplayVideoTrackConstraints__degradation_preference(
+    mut this: ptr::NonNull<DisplayVideoTrackConstraints>,
+    preference: DegradationPreference,
+) {
+    this.as_mut().degradation_preference(preference);
+}
+
 /// Frees the data behind the provided pointer.
 ///
 /// # Safety
@@ -0,0 +1,147 @@
+use std::ptr;
+
+use super::{DartValueArg, ForeignClass};
+
+pub use crate::media::InputDeviceCapabilities;
+
+impl ForeignClass for InputDeviceCapabilities {}
+
+/// Returns the minimum supported sample rate of the audio in Hz, if known.
+#[no_mangle]
+pub unsafe extern "C" fn InputDeviceCapabilities__min_sample_rate(
+    this: ptr::NonNull<InputDeviceCapabilities>,
+) -> DartValueArg<Option<u32>> {
+    DartValueArg::from(this.as_ref().sample_rate.map(|r| r.min))
+}
+
+/// Returns the maximum supported sample rate of the audio in Hz, if known.
+#[no_mangle]
+pub unsafe extern "C" fn InputDeviceCapabilities__max_sample_rate(
+    this: ptr::NonNull<InputDeviceCapabilities>,
+) -> DartValueArg<Option<u32>> {
+    DartValueArg::from(this.as_ref().sample_rate.map(|r| r.max))
+}
+
+/// Returns the minimum supported number of independent audio channels, if
+/// known.
+#[no_mangle]
+pub unsafe extern "C" fn InputDeviceCapabilities__min_channel_count(
+    this: ptr::NonNull<InputDeviceCapabilities>,
+) -> DartValueArg<Option<u32>> {
+    DartValueArg::from(this.as_ref().channel_count.map(|r| r.min))
+}
+
+/// Returns the maximum supported number of independent audio channels, if
+/// known.
+#[no_mangle]
+pub unsafe extern "C" fn InputDeviceCapabilities__max_channel_count(
+    this: ptr::NonNull<InputDeviceCapabilities>,
+) -> DartValueArg<Option<u32>> {
+    DartValueArg::from(this.as_ref().channel_count.map(|r| r.max))
+}
+
+/// Returns the minimum supported latency of the audio in milliseconds, if
+/// known.
+#[no_mangle]
+pub unsafe extern "C" fn InputDeviceCapabilities__min_latency(
+    this: ptr::NonNull<InputDeviceCapabilities>,
+) -> DartValueArg<Option<u32>> {
+    DartValueArg::from(this.as_ref().latency.map(|r| r.min))
+}
+
+/// Returns the maximum supported latency of the audio in milliseconds, if
+/// known.
+#[no_mangle]
+pub unsafe extern "C" fn InputDeviceCapabilities__max_latency(
+    this: ptr::NonNull<InputDeviceCapabilities>,
+) -> DartValueArg<Option<u32>> {
+    DartValueArg::from(this.as_ref().latency.map(|r| r.max))
+}
+
+/// Returns the minimum supported width of the video in pixels, if known.
+#[no_mangle]
+pub unsafe extern "C" fn InputDeviceCapabilities__min_width(
+    this: ptr::NonNull<InputDeviceCapabilities>,
+) -> DartValueArg<Option<u32>> {
+    DartValueArg::from(this.as_ref().width.map(|r| r.min))
+}
+
+/// Returns the maximum supported width of the video in pixels, if known.
+#[no_mangle]
+pub unsafe extern "C" fn InputDeviceCapabilities__max_width(
+    this: ptr::NonNull<InputDeviceCapabilities>,
+) -> DartValueArg<Option<u32>> {
+    DartValueArg::from(this.as_ref().width.map(|r| r.max))
+}
+
+/// Returns the minimum supported height of the video in pixels, if known.
+#[no_mangle]
+pub unsafe extern "C" fn InputDeviceCapabilities__min_height(
+    this: ptr::NonNull<InputDeviceCapabilities>,
+) -> DartValueArg<Option<u32>> {
+    DartValueArg::from(this.as_ref().height.map(|r| r.min))
+}
+
+/// Returns the maximum supported height of the video in pixels, if known.
+#[no_mangle]
+pub unsafe extern "C" fn InputDeviceCapabilities__max_height(
+    this: ptr::NonNull<InputDeviceCapabilities>,
+) -> DartValueArg<Option<u32>> {
+    DartValueArg::from(this.as_ref().height.map(|r| r.max))
+}
+
+/// Returns the minimum supported frame rate of the video in frames per second,
+/// if known.
+#[no_mangle]
+pub unsafe extern "C" fn InputDeviceCapabilities__min_frame_rate(
+    this: ptr::NonNull<InputDeviceCapabilities>,
+) -> DartValueArg<Option<u32>> {
+    DartValueArg::from(this.as_ref().frame_rate.map(|r| r.min))
+}
+
+/// Returns the maximum supported frame rate of the video in frames per second,
+/// if known.
+#[no_mangle]
+pub unsafe extern "C" fn InputDeviceCapabilities__max_frame_rate(
+    this: ptr::NonNull<InputDeviceCapabilities>,
+) -> DartValueArg<Option<u32>> {
+    DartValueArg::from(this.as_ref().frame_rate.map(|r| r.max))
+}
+
+/// Indicates whether the device supports isolating a voice from a background
+/// noise.
+#[no_mangle]
+pub unsafe extern "C" fn InputDeviceCapabilities__voice_isolation(
+    this: ptr::NonNull<InputDeviceCapabilities>,
+) -> u8 {
+    this.as_ref().voice_isolation as u8
+}
+
+/// Returns [`FacingMode`]s the device may face.
+///
+/// [`FacingMode`]: crate::media::FacingMode
+#[no_mangle]
+pub unsafe extern "C" fn InputDeviceCapabilities__facing_modes(
+    this: ptr::NonNull<InputDeviceCapabilities>,
+) -> DartValueArg<Vec<u8>> {
+    DartValueArg::from(
+        this.as_ref()
+            .facing_mode
+            .iter()
+            .map(|mode| *mode as u8)
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Frees the data behind the provided pointer.
+///
+/// # Safety
+///
+/// Should be called when object is no longer needed. Calling this more than
+/// once for the same pointer is equivalent to double free.
+#[no_mangle]
+pub unsafe extern "C" fn InputDeviceCapabilities__free(
+    this: ptr::NonNull<InputDeviceCapabilities>,
+) {
+    let _ = InputDeviceCapabilities::from_ptr(this);
+}
@@ -1,6 +1,6 @@
 use std::{os::raw::c_char, ptr};
 
-use super::{utils::string_into_c_str, ForeignClass};
+use super::{utils::string_into_c_str, ForeignClass, InputDeviceCapabilities};
 
 use crate::media::MediaKind;
 
@@ -57,6 +57,17 @@ pub unsafe extern "C" fn InputDeviceInfo__group_id(
     string_into_c_str(this.as_ref().group_id())
 }
 
+/// Returns capabilities of the represented device, or a null pointer if the
+/// platform doesn't provide them.
+#[no_mangle]
+pub unsafe extern "C" fn InputDeviceInfo__capabilities(
+    this: ptr::NonNull<InputDeviceInfo>,
+) -> *mut InputDeviceCapabilities {
+    this.as_ref()
+        .capabilities()
+        .map_or(ptr::null_mut(), |caps| caps.into_ptr().as_ptr())
+}
+
 /// Frees the data behind the provided pointer.
 ///
 /// # Safety
@@ -72,7 +83,9 @@ pub unsafe extern "C" fn InputDeviceInfo__free(
 
 #[cfg(feature = "mockable")]
 mod mock {
-    use crate::media::MediaKind;
+    use crate::media::{
+        CapabilityRange, FacingMode, InputDeviceCapabilities, MediaKind,
+    };
 
     pub struct InputDeviceInfo;
 
@@ -92,5 +105,15 @@ mod mock {
         pub fn group_id(&self) -> String {
             String::from("InputDeviceInfo.group_id")
         }
+
+        pub fn capabilities(&self) -> Option<InputDeviceCapabilities> {
+            Some(InputDeviceCapabilities {
+                width: Some(CapabilityRange { min: 1, max: 1920 }),
+                height: Some(CapabilityRange { min: 1, max: 1080 }),
+                frame_rate: Some(CapabilityRange { min: 0, max: 30 }),
+                facing_mode: vec![FacingMode::User],
+                ..InputDeviceCapabilities::default()
+            })
+        }
     }
 }
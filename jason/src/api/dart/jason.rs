@@ -1,6 +1,11 @@
 use std::ptr;
 
+use dart_sys::Dart_Handle;
+
+use crate::platform;
+
 use super::{
+    analytics_event::AnalyticsEvent,
     media_manager_handle::MediaManagerHandle, room_handle::RoomHandle,
     ForeignClass,
 };
@@ -15,6 +20,7 @@ impl ForeignClass for Jason {}
 /// Instantiates a new [`Jason`] interface to interact with this library.
 #[no_mangle]
 pub extern "C" fn Jason__new() -> ptr::NonNull<Jason> {
+    crate::log::init();
     Jason::new().into_ptr()
 }
 
@@ -36,6 +42,19 @@ pub unsafe extern "C" fn Jason__media_manager(
     this.as_ref().media_manager().into_ptr()
 }
 
+/// Sets callback, invoked with [`AnalyticsEvent`]s of all the [`Room`]s of
+/// this [`Jason`], replacing the previously set one (if any).
+///
+/// [`Room`]: crate::room::Room
+#[no_mangle]
+pub unsafe extern "C" fn Jason__set_analytics_sink(
+    this: ptr::NonNull<Jason>,
+    cb: Dart_Handle,
+) {
+    let cb = platform::Function::<AnalyticsEvent>::new(cb);
+    this.as_ref().set_analytics_sink(move |event| cb.call1(event));
+}
+
 /// Closes the provided [`RoomHandle`].
 #[no_mangle]
 pub unsafe extern "C" fn Jason__close_room(
@@ -46,26 +65,32 @@ pub unsafe extern "C" fn Jason__close_room(
         .close_room(RoomHandle::from_ptr(room_to_delete));
 }
 
-/// Frees the data behind the provided pointer.
+/// Disposes the [`Jason`] behind the provided pointer, closing all its
+/// [`Room`]s and stopping all the local media tracks, and frees its data.
 ///
 /// # Safety
 ///
 /// Should be called when object is no longer needed. Calling this more than
 /// once for the same pointer is equivalent to double free.
+///
+/// [`Room`]: crate::room::Room
 #[no_mangle]
 pub unsafe extern "C" fn Jason__free(this: ptr::NonNull<Jason>) {
-    drop(Jason::from_ptr(this));
+    Jason::from_ptr(this).dispose();
 }
 
 #[cfg(feature = "mockable")]
 mod mock {
-    use crate::api::{MediaManagerHandle, RoomHandle};
+    use std::time::Duration;
+
+    use medea_client_api_proto::PeerId;
+
+    use crate::api::{AnalyticsEvent, MediaManagerHandle, RoomHandle};
 
     pub struct Jason;
 
     impl Jason {
         pub fn new() -> Self {
-            crate::platform::init_logger();
             Self
         }
 
@@ -77,6 +102,18 @@ mod mock {
             MediaManagerHandle
         }
 
+        pub fn set_analytics_sink<F: Fn(AnalyticsEvent) + 'static>(
+            &self,
+            sink: F,
+        ) {
+            sink(AnalyticsEvent::NegotiationFinished {
+                peer_id: PeerId(1),
+                duration: Duration::from_millis(150),
+            });
+        }
+
         pub fn close_room(&self, _: RoomHandle) {}
+
+        pub fn dispose(self) {}
     }
 }
@@ -1,8 +1,16 @@
 use std::ptr;
 
-use super::ForeignClass;
+use tracerr::Traced;
 
-use crate::media::{MediaKind, MediaSourceKind};
+use super::{
+    utils::{DartError, DartFuture, InternalException, IntoDartFuture},
+    ForeignClass,
+};
+
+use crate::media::{
+    track::local::{ApplyAdvancedError, LocalTrackReason},
+    DeviceVideoTrackConstraints, MediaKind, MediaSourceKind,
+};
 
 #[cfg(feature = "mockable")]
 pub use self::mock::LocalMediaTrack;
@@ -11,6 +19,19 @@ pub use crate::media::track::local::LocalMediaTrack;
 
 impl ForeignClass for LocalMediaTrack {}
 
+impl From<Traced<ApplyAdvancedError>> for DartError {
+    fn from(err: Traced<ApplyAdvancedError>) -> Self {
+        let (err, trace) = err.into_parts();
+        let message = err.to_string();
+
+        let cause = match err {
+            ApplyAdvancedError::NotDeviceVideo => None,
+            ApplyAdvancedError::ApplyConstraintsFailed(cause) => Some(cause),
+        };
+        InternalException::new(message, cause, trace).into()
+    }
+}
+
 /// Returns a [`MediaKind::Audio`] if this [`LocalMediaTrack`] represents an
 /// audio track, or a [`MediaKind::Video`] if it represents a video track.
 ///
@@ -38,6 +59,40 @@ pub unsafe extern "C" fn LocalMediaTrack__media_source_kind(
     this.as_ref().media_source_kind()
 }
 
+/// Returns a [`LocalTrackReason`] of this [`LocalMediaTrack`] being passed to
+/// the `on_local_track` callback.
+#[no_mangle]
+pub unsafe extern "C" fn LocalMediaTrack__reason(
+    this: ptr::NonNull<LocalMediaTrack>,
+) -> LocalTrackReason {
+    this.as_ref().reason()
+}
+
+/// Returns a [`LocalMediaTrack`] replaced by this one, or a null pointer if
+/// there is no such [`LocalMediaTrack`].
+#[no_mangle]
+pub unsafe extern "C" fn LocalMediaTrack__previous(
+    this: ptr::NonNull<LocalMediaTrack>,
+) -> *mut LocalMediaTrack {
+    this.as_ref()
+        .previous()
+        .map_or(ptr::null_mut(), |track| track.into_ptr().as_ptr())
+}
+
+/// Applies the provided [`DeviceVideoTrackConstraints`] to this
+/// [`LocalMediaTrack`] at runtime, adjusting the camera capture controls
+/// (torch, zoom, focus mode) without re-acquiring the track.
+#[no_mangle]
+pub unsafe extern "C" fn LocalMediaTrack__apply_advanced(
+    this: ptr::NonNull<LocalMediaTrack>,
+    constraints: ptr::NonNull<DeviceVideoTrackConstraints>,
+) -> DartFuture<Result<(), Traced<ApplyAdvancedError>>> {
+    let this = this.as_ref().clone();
+    let constraints = constraints.as_ref().clone();
+
+    async move { this.apply_advanced(constraints).await }.into_dart_future()
+}
+
 /// Frees the data behind the provided pointer.
 ///
 /// # Safety
@@ -53,11 +108,17 @@ pub unsafe extern "C" fn LocalMediaTrack__free(
 
 #[cfg(feature = "mockable")]
 mod mock {
+    use tracerr::Traced;
+
     use crate::media::{
-        track::local::LocalMediaTrack as CoreLocalMediaTrack, MediaKind,
-        MediaSourceKind,
+        track::local::{
+            ApplyAdvancedError, LocalMediaTrack as CoreLocalMediaTrack,
+            LocalTrackReason,
+        },
+        DeviceVideoTrackConstraints, MediaKind, MediaSourceKind,
     };
 
+    #[derive(Clone)]
     pub struct LocalMediaTrack;
 
     impl From<CoreLocalMediaTrack> for LocalMediaTrack {
@@ -75,6 +136,22 @@ mod mock {
             MediaSourceKind::Display
         }
 
+        pub fn reason(&self) -> LocalTrackReason {
+            LocalTrackReason::DeviceSwitch
+        }
+
+        pub fn previous(&self) -> Option<Self> {
+            Some(Self)
+        }
+
+        #[allow(clippy::missing_errors_doc)]
+        pub async fn apply_advanced(
+            &self,
+            _constraints: DeviceVideoTrackConstraints,
+        ) -> Result<(), Traced<ApplyAdvancedError>> {
+            Ok(())
+        }
+
         // pub fn get_track(&self) -> sys::MediaStreamTrack
     }
 }
@@ -0,0 +1,124 @@
+//! Runtime configuration of [`Jason`] logging.
+//!
+//! [`Jason`]: crate::api::Jason
+
+use std::convert::TryFrom;
+
+use ::log::LevelFilter;
+use dart_sys::Dart_Handle;
+
+use crate::{
+    api::dart::{
+        utils::{ArgumentError, DartError, DartResult},
+        DartValueArg,
+    },
+    log::{self, LogScope},
+    platform,
+};
+
+/// Sets the global level of [`Jason`] logging.
+///
+/// Levels are encoded as `0` (off), `1` (error), `2` (warn), `3` (info),
+/// `4` (debug) and `5` (trace). Defaults to `4` (debug).
+///
+/// [`Jason`]: crate::api::Jason
+#[no_mangle]
+pub extern "C" fn log__set_level(level: i64) -> DartResult {
+    level_filter(level)
+        .map(|level| {
+            log::init();
+            log::set_level(level);
+        })
+        .into()
+}
+
+/// Sets level of the provided [`LogScope`], overriding the global one.
+///
+/// Scopes are encoded as `0` (rpc), `1` (peer) and `2` (media). Levels are
+/// encoded the same way as in the [`log__set_level()`]. `null` level makes the
+/// [`LogScope`] use the global level again.
+#[no_mangle]
+pub extern "C" fn log__set_scope_level(
+    scope: i64,
+    level: DartValueArg<Option<LevelFilter>>,
+) -> DartResult {
+    let scope = match scope {
+        0 => LogScope::Rpc,
+        1 => LogScope::Peer,
+        2 => LogScope::Media,
+        _ => {
+            return ArgumentError::new(scope, "scope", "unknown `LogScope`")
+                .into();
+        }
+    };
+    Option::<LevelFilter>::try_from(level)
+        .map(|level| {
+            log::init();
+            log::set_scope_level(scope, level);
+        })
+        .into()
+}
+
+/// Registers a callback, invoked with every log record passing the configured
+/// levels.
+///
+/// Records are passed as `<level> <target>: <message>` strings.
+#[no_mangle]
+pub unsafe extern "C" fn log__on_log(cb: Dart_Handle) {
+    log::init();
+    let cb = platform::Function::<String>::new(cb);
+    log::add_sink(move |record| {
+        cb.call1(format!(
+            "{} {}: {}",
+            record.level(),
+            record.target(),
+            record.message(),
+        ));
+    });
+}
+
+/// Removes all the callbacks registered via [`log__on_log()`].
+#[no_mangle]
+pub extern "C" fn log__clear_callbacks() {
+    log::clear_sinks();
+}
+
+impl TryFrom<DartValueArg<Option<LevelFilter>>> for Option<LevelFilter> {
+    type Error = DartError;
+
+    fn try_from(
+        level: DartValueArg<Option<LevelFilter>>,
+    ) -> Result<Self, Self::Error> {
+        Option::<i64>::try_from(level)
+            .map_err(|err| {
+                let message = err.to_string();
+                ArgumentError::new(err.into_value(), "level", message)
+            })?
+            .map(level_filter)
+            .transpose()
+    }
+}
+
+/// Decodes a [`LevelFilter`] from the provided Dart value.
+///
+/// # Errors
+///
+/// With an [`ArgumentError`] if the provided value is not a valid level.
+fn level_filter(level: i64) -> Result<LevelFilter, DartError> {
+    Ok(match level {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Error,
+        2 => LevelFilter::Warn,
+        3 => LevelFilter::Info,
+        4 => LevelFilter::Debug,
+        5 => LevelFilter::Trace,
+        _ => {
+            return Err(ArgumentError::new(
+                level,
+                "level",
+                "unknown log level",
+            )
+            .into());
+        }
+    })
+}
@@ -14,7 +14,8 @@ use super::{
         LocalMediaInitException, LocalMediaInitExceptionKind, PtrArray,
         StateError,
     },
-    ForeignClass, InputDeviceInfo, LocalMediaTrack,
+    AbortSignal, ForeignClass, InputDeviceInfo, LocalMediaTrack,
+    MediaPermissions,
 };
 
 #[cfg(feature = "mockable")]
@@ -85,18 +86,38 @@ pub unsafe extern "C" fn MediaManagerHandle__init_local_tracks(
 /// Returns a list of [`InputDeviceInfo`] objects representing available media
 /// input and devices, such as microphones, cameras, and so forth.
 ///
+/// Once the provided [`AbortSignal`] (if any) is triggered, the returned
+/// `Future` resolves with a [`StateError`].
+///
 /// [`InputDeviceInfo`]: super::input_device_info::InputDeviceInfo
-#[rustfmt::skip]
 #[no_mangle]
 pub unsafe extern "C" fn MediaManagerHandle__enumerate_devices(
     this: ptr::NonNull<MediaManagerHandle>,
-) -> DartFuture<
-    Result<PtrArray<InputDeviceInfo>, Traced<EnumerateDevicesError>>,
-> {
+    signal: Option<ptr::NonNull<AbortSignal>>,
+) -> DartFuture<Result<PtrArray<InputDeviceInfo>, DartError>> {
     let this = this.as_ref().clone();
 
-    async move { Ok(PtrArray::new(this.enumerate_devices().await?)) }
-        .into_dart_future()
+    AbortSignal::abortable(
+        async move {
+            let devices = this.enumerate_devices().await?;
+            Ok::<_, Traced<EnumerateDevicesError>>(PtrArray::new(devices))
+        },
+        signal,
+    )
+    .into_dart_future()
+}
+
+/// Returns [`MediaPermissions`] of using a camera, a microphone and capturing a
+/// screen, without prompting a user.
+///
+/// [`MediaPermissions`]: super::media_permissions::MediaPermissions
+#[no_mangle]
+pub unsafe extern "C" fn MediaManagerHandle__query_permissions(
+    this: ptr::NonNull<MediaManagerHandle>,
+) -> DartFuture<Result<MediaPermissions, DartError>> {
+    let this = this.as_ref().clone();
+
+    async move { Ok(this.query_permissions().await) }.into_dart_future()
 }
 
 /// Frees the data behind the provided pointer.
@@ -125,7 +146,10 @@ mod mock {
             },
             InputDeviceInfo, LocalMediaTrack, MediaStreamSettings,
         },
-        media::{EnumerateDevicesError, InitLocalTracksError},
+        media::{
+            EnumerateDevicesError, InitLocalTracksError, MediaPermissions,
+            PermissionState,
+        },
         platform,
     };
 
@@ -156,6 +180,14 @@ mod mock {
                 LocalMediaTrack {},
             ])
         }
+
+        pub async fn query_permissions(&self) -> MediaPermissions {
+            MediaPermissions {
+                camera: PermissionState::Granted,
+                microphone: PermissionState::Denied,
+                screen: PermissionState::Unknown,
+            }
+        }
     }
 
     #[no_mangle]
@@ -0,0 +1,44 @@
+use std::ptr;
+
+use super::ForeignClass;
+
+pub use crate::media::{MediaPermissions, PermissionState};
+
+impl ForeignClass for MediaPermissions {}
+
+/// Returns [`PermissionState`] of using a camera.
+#[no_mangle]
+pub unsafe extern "C" fn MediaPermissions__camera(
+    this: ptr::NonNull<MediaPermissions>,
+) -> PermissionState {
+    this.as_ref().camera
+}
+
+/// Returns [`PermissionState`] of using a microphone.
+#[no_mangle]
+pub unsafe extern "C" fn MediaPermissions__microphone(
+    this: ptr::NonNull<MediaPermissions>,
+) -> PermissionState {
+    this.as_ref().microphone
+}
+
+/// Returns [`PermissionState`] of capturing a screen.
+#[no_mangle]
+pub unsafe extern "C" fn MediaPermissions__screen(
+    this: ptr::NonNull<MediaPermissions>,
+) -> PermissionState {
+    this.as_ref().screen
+}
+
+/// Frees the data behind the provided pointer.
+///
+/// # Safety
+///
+/// Should be called when object is no longer needed. Calling this more than
+/// once for the same pointer is equivalent to double free.
+#[no_mangle]
+pub unsafe extern "C" fn MediaPermissions__free(
+    this: ptr::NonNull<MediaPermissions>,
+) {
+    let _ = MediaPermissions::from_ptr(this);
+}
@@ -7,14 +7,20 @@
 // TODO: Improve documentation in this module.
 #![allow(clippy::missing_safety_doc, clippy::missing_panics_doc, missing_docs)]
 
+pub mod abort_signal;
+pub mod analytics_event;
+pub mod app_data;
 pub mod audio_track_constraints;
 pub mod connection_handle;
 pub mod device_video_track_constraints;
 pub mod display_video_track_constraints;
+pub mod input_device_capabilities;
 pub mod input_device_info;
 pub mod jason;
 pub mod local_media_track;
+pub mod logging;
 pub mod media_manager_handle;
+pub mod media_permissions;
 pub mod media_stream_settings;
 pub mod reconnect_handle;
 pub mod remote_media_track;
@@ -22,7 +28,10 @@ pub mod room_close_reason;
 pub mod room_handle;
 pub mod utils;
 
-use std::{convert::TryFrom, ffi::c_void, marker::PhantomData, ptr};
+use std::{
+    collections::HashMap, convert::TryFrom, ffi::c_void, marker::PhantomData,
+    ptr,
+};
 
 use dart_sys::Dart_Handle;
 use derive_more::Display;
@@ -30,22 +39,31 @@ use libc::c_char;
 
 use crate::{
     api::dart::utils::{
-        c_str_into_string, string_into_c_str, DartError, PtrArray,
+        c_str_into_string, sized_str_into_string, string_into_c_str, DartError,
+        DartMapEntry, PtrArray, ValueArray,
     },
     media::MediaSourceKind,
 };
 
 pub use self::{
+    abort_signal::AbortSignal,
+    analytics_event::{AnalyticsEvent, AnalyticsEventKind},
+    app_data::AppData,
     audio_track_constraints::AudioTrackConstraints,
     connection_handle::ConnectionHandle,
     device_video_track_constraints::DeviceVideoTrackConstraints,
     display_video_track_constraints::DisplayVideoTrackConstraints,
-    input_device_info::InputDeviceInfo, jason::Jason,
+    input_device_capabilities::InputDeviceCapabilities,
+    input_device_info::InputDeviceInfo,
+    jason::Jason,
     local_media_track::LocalMediaTrack,
     media_manager_handle::MediaManagerHandle,
+    media_permissions::MediaPermissions,
     media_stream_settings::MediaStreamSettings,
-    reconnect_handle::ReconnectHandle, remote_media_track::RemoteMediaTrack,
-    room_close_reason::RoomCloseReason, room_handle::RoomHandle,
+    reconnect_handle::ReconnectHandle,
+    remote_media_track::RemoteMediaTrack,
+    room_close_reason::{RoomCloseKind, RoomCloseReason},
+    room_handle::RoomHandle,
     utils::DartError as Error,
 };
 
@@ -95,6 +113,17 @@ pub enum DartValue {
     ///
     /// This can also be used to transfer boolean values and C-like enums.
     Int(i64),
+
+    /// Array of [`DartValue`]s, which may be nested.
+    Array(ptr::NonNull<ValueArray<DartValue>>),
+
+    /// Map of [`DartValue`]s.
+    Map(ptr::NonNull<ValueArray<DartMapEntry>>),
+
+    /// UTF-8 string with an explicit length, which is not null-terminated.
+    ///
+    /// Used for the [`String`]s containing `0x0` bytes.
+    SizedString(ptr::NonNull<ValueArray<u8>>),
 }
 
 impl From<()> for DartValue {
@@ -141,7 +170,11 @@ impl<T> From<Option<PtrArray<T>>> for DartValue {
 impl From<String> for DartValue {
     #[inline]
     fn from(string: String) -> Self {
-        Self::String(string_into_c_str(string))
+        if string.contains('\0') {
+            Self::SizedString(ValueArray::new(string.into_bytes()).into_ptr())
+        } else {
+            Self::String(string_into_c_str(string))
+        }
     }
 }
 
@@ -155,6 +188,48 @@ impl From<Option<String>> for DartValue {
     }
 }
 
+impl<T: Into<DartValue>> From<Vec<T>> for DartValue {
+    #[inline]
+    fn from(vec: Vec<T>) -> Self {
+        Self::Array(ValueArray::new(vec.into_iter().map(Into::into)).into_ptr())
+    }
+}
+
+impl<T: Into<DartValue>> From<Option<Vec<T>>> for DartValue {
+    #[inline]
+    fn from(val: Option<Vec<T>>) -> Self {
+        match val {
+            None => Self::None,
+            Some(vec) => Self::from(vec),
+        }
+    }
+}
+
+impl<K: Into<DartValue>, V: Into<DartValue>> From<HashMap<K, V>> for DartValue {
+    #[inline]
+    fn from(map: HashMap<K, V>) -> Self {
+        Self::Map(
+            ValueArray::new(map.into_iter().map(|(k, v)| DartMapEntry {
+                key: k.into(),
+                value: v.into(),
+            }))
+            .into_ptr(),
+        )
+    }
+}
+
+impl<K: Into<DartValue>, V: Into<DartValue>> From<Option<HashMap<K, V>>>
+    for DartValue
+{
+    #[inline]
+    fn from(val: Option<HashMap<K, V>>) -> Self {
+        match val {
+            None => Self::None,
+            Some(map) => Self::from(map),
+        }
+    }
+}
+
 impl From<Dart_Handle> for DartValue {
     #[inline]
     fn from(handle: Dart_Handle) -> Self {
@@ -199,6 +274,16 @@ macro_rules! impl_from_num_for_dart_value {
                 DartValue::Int(i64::from(val))
             }
         }
+
+        impl From<Option<$arg>> for DartValue {
+            #[inline]
+            fn from(val: Option<$arg>) -> Self {
+                match val {
+                    None => DartValue::None,
+                    Some(num) => DartValue::from(num),
+                }
+            }
+        }
     };
 }
 
@@ -264,6 +349,9 @@ impl TryFrom<DartValueArg<String>> for String {
     fn try_from(value: DartValueArg<String>) -> Result<Self, Self::Error> {
         match value.0 {
             DartValue::String(c_str) => unsafe { Ok(c_str_into_string(c_str)) },
+            DartValue::SizedString(s) => unsafe {
+                Ok(sized_str_into_string(s))
+            },
             _ => Err(DartValueCastError {
                 expectation: "String",
                 value: value.0,
@@ -283,6 +371,9 @@ impl TryFrom<DartValueArg<Option<String>>> for Option<String> {
             DartValue::String(c_str) => unsafe {
                 Ok(Some(c_str_into_string(c_str)))
             },
+            DartValue::SizedString(s) => unsafe {
+                Ok(Some(sized_str_into_string(s)))
+            },
             _ => Err(DartValueCastError {
                 expectation: "Option<String>",
                 value: value.0,
@@ -390,7 +481,7 @@ pub unsafe extern "C" fn unbox_dart_handle(
 
 #[cfg(feature = "mockable")]
 mod dart_value_extern_tests_helpers {
-    use std::convert::TryInto;
+    use std::{convert::TryInto, sync::atomic::Ordering};
 
     use super::*;
 
@@ -422,6 +513,44 @@ mod dart_value_extern_tests_helpers {
         DartValueArg::from(333)
     }
 
+    #[no_mangle]
+    pub unsafe extern "C" fn returns_nested_list(
+    ) -> DartValueArg<Vec<DartValue>> {
+        DartValueArg::from(vec![
+            DartValue::from(vec![1_i64, 2]),
+            DartValue::from(Vec::<i64>::new()),
+            DartValue::from(String::from("str")),
+            DartValue::from(Some(5_i64)),
+            DartValue::from(None::<i64>),
+        ])
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn returns_map(
+    ) -> DartValueArg<HashMap<String, DartValue>> {
+        let mut inner = HashMap::new();
+        inner.insert(String::from("key"), String::from("value"));
+
+        let mut map = HashMap::new();
+        map.insert(String::from("int"), DartValue::from(1_i64));
+        map.insert(
+            String::from("list"),
+            DartValue::from(vec![String::from("a")]),
+        );
+        map.insert(String::from("map"), DartValue::from(inner));
+        DartValueArg::from(map)
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn returns_sized_string() -> DartValueArg<String> {
+        DartValueArg::from(String::from("null\0byte"))
+    }
+
+    #[no_mangle]
+    pub unsafe extern "C" fn value_arrays_alive() -> u64 {
+        utils::VALUE_ARRAYS_ALIVE.load(Ordering::SeqCst) as u64
+    }
+
     #[no_mangle]
     pub unsafe extern "C" fn accepts_none(none: DartValueArg<String>) {
         assert!(matches!(none.0, DartValue::None));
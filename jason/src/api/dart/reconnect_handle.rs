@@ -1,5 +1,6 @@
 use std::{convert::TryFrom as _, ptr};
 
+use dart_sys::Dart_Handle;
 use tracerr::Traced;
 
 use crate::{
@@ -10,6 +11,7 @@ use crate::{
         },
         DartValueArg,
     },
+    platform,
     rpc::{rpc_session::ConnectionLostReason, ReconnectError, SessionError},
     utils::JsCaused as _,
 };
@@ -38,6 +40,13 @@ impl From<Traced<ReconnectError>> for DartError {
             ReconnectError::Session(err) => {
                 Traced::from_parts(err, trace).into()
             }
+            ReconnectError::Cancelled => RpcClientException::new(
+                RpcClientExceptionKind::ReconnectionCancelled,
+                err.to_string(),
+                None,
+                trace,
+            )
+            .into(),
         }
     }
 }
@@ -120,7 +129,12 @@ pub unsafe extern "C" fn ReconnectHandle__reconnect_with_delay(
 /// This might cause a busy loop, so it's not recommended.
 ///
 /// Max elapsed time can be limited with an optional `max_elapsed_time_ms`
-/// argument.
+/// argument, and the number of attempts with an optional `max_attempts`
+/// argument. Once any of them is exceeded, the `on_give_up` callback is
+/// invoked.
+///
+/// Each delay is randomized by the given `jitter` factor (clamped into
+/// `[0.0, 1.0]` range).
 ///
 /// If the [`Room`] is already reconnecting then new reconnection attempt won't
 /// be performed. Instead, it will wait for the first reconnection attempt
@@ -135,6 +149,8 @@ pub unsafe extern "C" fn ReconnectHandle__reconnect_with_backoff(
     multiplier: f64,
     max_delay: i64,
     max_elapsed_time_ms: DartValueArg<Option<i64>>,
+    jitter: f64,
+    max_attempts: DartValueArg<Option<i64>>,
 ) -> DartFuture<Result<(), DartError>> {
     let this = this.as_ref().clone();
 
@@ -164,12 +180,25 @@ pub unsafe extern "C" fn ReconnectHandle__reconnect_with_backoff(
                 })
             })
             .transpose()?;
+        let max_attempts = Option::<i64>::try_from(max_attempts)
+            .map_err(|err| {
+                let message = err.to_string();
+                ArgumentError::new(err.into_value(), "maxAttempts", message)
+            })?
+            .map(|v| {
+                u32::try_from(v).map_err(|_| {
+                    ArgumentError::new(v, "maxAttempts", "Expected u32")
+                })
+            })
+            .transpose()?;
 
         this.reconnect_with_backoff(
             starting_delay,
             multiplier,
             max_delay,
             max_elapsed_time_ms,
+            jitter,
+            max_attempts,
         )
         .await?;
         Ok(())
@@ -177,6 +206,25 @@ pub unsafe extern "C" fn ReconnectHandle__reconnect_with_backoff(
     .into_dart_future()
 }
 
+/// Sets callback, invoked when reconnection with a backoff gives up, because
+/// its max attempts count or max elapsed time is exceeded.
+#[no_mangle]
+pub unsafe extern "C" fn ReconnectHandle__on_give_up(
+    this: ptr::NonNull<ReconnectHandle>,
+    f: Dart_Handle,
+) {
+    this.as_ref().on_give_up(platform::Function::new(f));
+}
+
+/// Cancels all the ongoing reconnections with a backoff, making them fail with
+/// a `ReconnectionCancelled` [`RpcClientException`].
+#[no_mangle]
+pub unsafe extern "C" fn ReconnectHandle__cancel_backoff(
+    this: ptr::NonNull<ReconnectHandle>,
+) {
+    this.as_ref().cancel_backoff();
+}
+
 /// Frees the data behind the provided pointer.
 ///
 /// # Safety
@@ -227,9 +275,17 @@ mod mock {
             _multiplier: f64,
             _max_delay: u32,
             _max_elapsed_time_ms: Option<u32>,
+            _jitter: f64,
+            _max_attempts: Option<u32>,
         ) -> Result<(), Traced<ReconnectError>> {
             Ok(())
         }
+
+        pub fn on_give_up(&self, cb: platform::Function<()>) {
+            cb.call0();
+        }
+
+        pub fn cancel_backoff(&self) {}
     }
 
     #[no_mangle]
@@ -1,8 +1,8 @@
 use std::{os::raw::c_char, ptr};
 
-use super::{utils::string_into_c_str, ForeignClass};
+use super::{utils::string_into_c_str, DartValueArg, ForeignClass};
 
-pub use crate::room::RoomCloseReason;
+pub use crate::room::{RoomCloseKind, RoomCloseReason};
 
 impl ForeignClass for RoomCloseReason {}
 
@@ -16,6 +16,16 @@ pub unsafe extern "C" fn RoomCloseReason__reason(
     string_into_c_str(this.as_ref().reason())
 }
 
+/// Returns a [`RoomCloseKind`] of a [`Room`]'s close reason.
+///
+/// [`Room`]: crate::room::Room
+#[no_mangle]
+pub unsafe extern "C" fn RoomCloseReason__kind(
+    this: ptr::NonNull<RoomCloseReason>,
+) -> RoomCloseKind {
+    this.as_ref().kind()
+}
+
 /// Indicates whether a [`Room`] was closed by server.
 ///
 /// [`Room`]: crate::room::Room
@@ -36,6 +46,31 @@ pub unsafe extern "C" fn RoomCloseReason__is_err(
     this.as_ref().is_err() as u8
 }
 
+/// Indicates whether a [`Room`] may be rejoined, since it was closed by server
+/// for a temporary reason.
+///
+/// [`Room`]: crate::room::Room
+#[no_mangle]
+pub unsafe extern "C" fn RoomCloseReason__is_retryable(
+    this: ptr::NonNull<RoomCloseReason>,
+) -> u8 {
+    this.as_ref().is_retryable() as u8
+}
+
+/// Returns the delay (in milliseconds) after which a [`Room`] should be
+/// rejoined, if server has hinted it.
+///
+/// [`Room`]: crate::room::Room
+#[allow(clippy::cast_possible_truncation)]
+#[no_mangle]
+pub unsafe extern "C" fn RoomCloseReason__retry_after(
+    this: ptr::NonNull<RoomCloseReason>,
+) -> DartValueArg<Option<u32>> {
+    DartValueArg::from(
+        this.as_ref().retry_after().map(|d| d.as_millis() as u32),
+    )
+}
+
 /// Frees the data behind the provided pointer.
 ///
 /// # Safety
@@ -16,7 +16,7 @@ use crate::{
         },
         DartValueArg, ForeignClass,
     },
-    media::MediaSourceKind,
+    media::{MediaSourceKind, RecvMediaPolicy},
     peer::{
         media::sender::CreateError, InsertLocalTracksError, LocalMediaError,
         UpdateLocalStreamError,
@@ -28,7 +28,7 @@ use crate::{
     },
 };
 
-use super::{utils::DartError, MediaStreamSettings};
+use super::{utils::DartError, AbortSignal, MediaStreamSettings};
 
 #[cfg(feature = "mockable")]
 pub use self::mock::RoomHandle;
@@ -145,16 +145,69 @@ impl From<ConstraintsUpdateError> for DartError {
 /// `{{ Host URL }}/{{ Room ID }}/{{ Member ID }}?token={{ Auth Token }}`
 /// (e.g. `wss://medea.com/MyConf1/Alice?token=777`).
 ///
+/// Once the provided [`AbortSignal`] (if any) is triggered, the connection
+/// establishment is canceled and the returned `Future` resolves with a
+/// [`StateError`].
+///
 /// [`Room`]: crate::room::Room
 #[no_mangle]
 pub unsafe extern "C" fn RoomHandle__join(
     this: ptr::NonNull<RoomHandle>,
     token: ptr::NonNull<libc::c_char>,
-) -> DartFuture<Result<(), Traced<RoomJoinError>>> {
+    signal: Option<ptr::NonNull<AbortSignal>>,
+) -> DartFuture<Result<(), DartError>> {
+    let this = this.as_ref().clone();
+
+    AbortSignal::abortable(
+        async move {
+            this.join(c_str_into_string(token)).await?;
+            Ok::<_, Traced<RoomJoinError>>(())
+        },
+        signal,
+    )
+    .into_dart_future()
+}
+
+/// Connects to a media server and joins the [`Room`] with the provided
+/// authorization `token`, without acquiring any local media.
+///
+/// Local media is acquired and published only on a [`RoomHandle__publish()`]
+/// call.
+///
+/// Once the provided [`AbortSignal`] (if any) is triggered, the connection
+/// establishment is canceled and the returned `Future` resolves with a
+/// [`StateError`].
+///
+/// [`Room`]: crate::room::Room
+#[no_mangle]
+pub unsafe extern "C" fn RoomHandle__join_without_media(
+    this: ptr::NonNull<RoomHandle>,
+    token: ptr::NonNull<libc::c_char>,
+    signal: Option<ptr::NonNull<AbortSignal>>,
+) -> DartFuture<Result<(), DartError>> {
+    let this = this.as_ref().clone();
+
+    AbortSignal::abortable(
+        async move {
+            this.join_without_media(c_str_into_string(token)).await?;
+            Ok::<_, Traced<RoomJoinError>>(())
+        },
+        signal,
+    )
+    .into_dart_future()
+}
+
+/// Acquires local media and starts publishing it in this [`Room`].
+///
+/// [`Room`]: crate::room::Room
+#[no_mangle]
+pub unsafe extern "C" fn RoomHandle__publish(
+    this: ptr::NonNull<RoomHandle>,
+) -> DartFuture<Result<(), Traced<ChangeMediaStateError>>> {
     let this = this.as_ref().clone();
 
     async move {
-        this.join(c_str_into_string(token)).await?;
+        this.publish().await?;
         Ok(())
     }
     .into_dart_future()
@@ -183,6 +236,10 @@ pub unsafe extern "C" fn RoomHandle__join(
 /// If recovering from fail state isn't possible then affected media types will
 /// be disabled.
 ///
+/// Once the provided [`AbortSignal`] (if any) is triggered, the update is
+/// canceled, the previous [`MediaStreamSettings`] are restored and the
+/// returned `Future` resolves with a [`StateError`].
+///
 /// [`Room`]: crate::room::Room
 /// [`PeerConnection`]: crate::peer::PeerConnection
 /// [1]: https://w3.org/TR/mediacapture-streams#dom-mediadevices-getusermedia
@@ -192,15 +249,23 @@ pub unsafe extern "C" fn RoomHandle__set_local_media_settings(
     settings: ptr::NonNull<MediaStreamSettings>,
     stop_first: bool,
     rollback_on_fail: bool,
-) -> DartFuture<Result<(), ConstraintsUpdateError>> {
+    signal: Option<ptr::NonNull<AbortSignal>>,
+) -> DartFuture<Result<(), DartError>> {
     let this = this.as_ref().clone();
     let settings = settings.as_ref().clone();
 
-    async move {
-        this.set_local_media_settings(settings, stop_first, rollback_on_fail)
+    AbortSignal::abortable(
+        async move {
+            this.set_local_media_settings(
+                settings,
+                stop_first,
+                rollback_on_fail,
+            )
             .await?;
-        Ok(())
-    }
+            Ok::<_, ConstraintsUpdateError>(())
+        },
+        signal,
+    )
     .into_dart_future()
 }
 
@@ -486,6 +551,246 @@ pub unsafe extern "C" fn RoomHandle__on_failed_local_media(
         .into()
 }
 
+/// Sets callback, invoked when some remote `Member` sends application data,
+/// providing an [`AppData`].
+///
+/// [`AppData`]: crate::room::AppData
+#[no_mangle]
+pub unsafe extern "C" fn RoomHandle__on_app_data(
+    this: ptr::NonNull<RoomHandle>,
+    cb: Dart_Handle,
+) -> DartResult {
+    let this = this.as_ref();
+
+    this.on_app_data(platform::Function::new(cb))
+        .map_err(DartError::from)
+        .into()
+}
+
+/// Sets callback, invoked when an audio output route changes, providing an
+/// [`AudioRouteChangeReason`].
+///
+/// [`AudioRouteChangeReason`]: crate::media::AudioRouteChangeReason
+#[no_mangle]
+pub unsafe extern "C" fn RoomHandle__on_audio_route_change(
+    this: ptr::NonNull<RoomHandle>,
+    cb: Dart_Handle,
+) -> DartResult {
+    let this = this.as_ref();
+
+    this.on_audio_route_change(platform::Function::new(cb))
+        .map_err(DartError::from)
+        .into()
+}
+
+/// Sends JSON-encoded `data` to the remote `Member` with the provided ID, or
+/// to all remote `Member`s of this [`Room`] if `to` is `null`.
+///
+/// [`Room`]: crate::room::Room
+#[no_mangle]
+pub unsafe extern "C" fn RoomHandle__send_app_data(
+    this: ptr::NonNull<RoomHandle>,
+    to: DartValueArg<Option<String>>,
+    data: ptr::NonNull<libc::c_char>,
+) -> DartResult {
+    let this = this.as_ref();
+
+    let to = match Option::<String>::try_from(to) {
+        Ok(to) => to,
+        Err(err) => {
+            let message = err.to_string();
+            return ArgumentError::new(err.into_value(), "to", message).into();
+        }
+    };
+    let data = match serde_json::from_str(&c_str_into_string(data)) {
+        Ok(data) => data,
+        Err(err) => {
+            return FormatException::new(err.to_string()).into();
+        }
+    };
+
+    this.send_app_data(to, data).map_err(DartError::from).into()
+}
+
+/// Renews the credential of this [`Room`]'s `Member` with the provided
+/// `token`, without rejoining the [`Room`].
+///
+/// Media server closes this [`Room`] if the provided credential is invalid.
+///
+/// [`Room`]: crate::room::Room
+#[no_mangle]
+pub unsafe extern "C" fn RoomHandle__update_token(
+    this: ptr::NonNull<RoomHandle>,
+    token: ptr::NonNull<libc::c_char>,
+) -> DartResult {
+    let this = this.as_ref();
+
+    this.update_token(c_str_into_string(token))
+        .map_err(DartError::from)
+        .into()
+}
+
+/// Returns a JSON document describing the current state of all the
+/// [`PeerConnection`]s of this [`Room`] (their SDPs, selected ICE candidate
+/// pairs and latest stats) along with a history of this [`Room`] state
+/// synchronization with a media server.
+///
+/// Intended to be attached to bug reports, similarly to
+/// `chrome://webrtc-internals` dumps.
+///
+/// [`PeerConnection`]: crate::peer::PeerConnection
+/// [`Room`]: crate::room::Room
+#[no_mangle]
+pub unsafe extern "C" fn RoomHandle__export_debug_dump(
+    this: ptr::NonNull<RoomHandle>,
+) -> DartFuture<Result<String, DartError>> {
+    let this = this.as_ref().clone();
+
+    async move { Ok(this.export_debug_dump().await?) }.into_dart_future()
+}
+
+/// Sets the interval (in milliseconds) of collecting stats of all the
+/// [`PeerConnection`]s of this [`Room`] and sending them to a media server.
+///
+/// `0` stops periodic stats collecting, so it's done only via
+/// `RoomHandle__poll_stats_now()`.
+///
+/// [`PeerConnection`]: crate::peer::PeerConnection
+/// [`Room`]: crate::room::Room
+#[no_mangle]
+pub unsafe extern "C" fn RoomHandle__set_stats_interval(
+    this: ptr::NonNull<RoomHandle>,
+    interval_ms: i64,
+) -> DartResult {
+    let this = this.as_ref();
+
+    match u32::try_from(interval_ms) {
+        Ok(interval_ms) => this
+            .set_stats_interval(interval_ms)
+            .map_err(DartError::from)
+            .into(),
+        Err(_) => {
+            ArgumentError::new(interval_ms, "intervalMs", "Expected u32").into()
+        }
+    }
+}
+
+/// Collects stats of all the [`PeerConnection`]s of this [`Room`] right away
+/// and sends them to a media server.
+///
+/// [`PeerConnection`]: crate::peer::PeerConnection
+/// [`Room`]: crate::room::Room
+#[no_mangle]
+pub unsafe extern "C" fn RoomHandle__poll_stats_now(
+    this: ptr::NonNull<RoomHandle>,
+) -> DartFuture<Result<(), DartError>> {
+    let this = this.as_ref().clone();
+
+    async move {
+        this.poll_stats_now().await?;
+        Ok(())
+    }
+    .into_dart_future()
+}
+
+/// Re-acquires the local media, which has failed to be acquired previously
+/// (and so has been reported to `on_failed_local_media` callback), and
+/// re-enables the affected outbound media in this [`Room`].
+///
+/// Does nothing if there is no failed local media.
+///
+/// [`Room`]: crate::room::Room
+#[no_mangle]
+pub unsafe extern "C" fn RoomHandle__retry_local_media(
+    this: ptr::NonNull<RoomHandle>,
+) -> DartFuture<Result<(), Traced<ChangeMediaStateError>>> {
+    let this = this.as_ref().clone();
+
+    async move {
+        this.retry_local_media().await?;
+        Ok(())
+    }
+    .into_dart_future()
+}
+
+/// Sets the interval (in milliseconds) of automatic
+/// `RoomHandle__retry_local_media()` attempts.
+///
+/// `0` stops automatic retrying (default).
+#[no_mangle]
+pub unsafe extern "C" fn RoomHandle__set_local_media_retry_interval(
+    this: ptr::NonNull<RoomHandle>,
+    interval_ms: i64,
+) -> DartResult {
+    let this = this.as_ref();
+
+    match u32::try_from(interval_ms) {
+        Ok(interval_ms) => this
+            .set_local_media_retry_interval(interval_ms)
+            .map_err(DartError::from)
+            .into(),
+        Err(_) => {
+            ArgumentError::new(interval_ms, "intervalMs", "Expected u32").into()
+        }
+    }
+}
+
+/// Sets the [`RecvMediaPolicy`] of this [`Room`].
+///
+/// Affects only the media received after this call, so should be called
+/// before `RoomHandle__join()`.
+///
+/// [`Room`]: crate::room::Room
+#[no_mangle]
+pub unsafe extern "C" fn RoomHandle__set_recv_media_policy(
+    this: ptr::NonNull<RoomHandle>,
+    policy: RecvMediaPolicy,
+) -> DartResult {
+    let this = this.as_ref();
+
+    this.set_recv_media_policy(policy)
+        .map_err(DartError::from)
+        .into()
+}
+
+/// Enables inbound media from the remote `Member` with the provided ID in
+/// this [`Room`].
+///
+/// [`Room`]: crate::room::Room
+#[no_mangle]
+pub unsafe extern "C" fn RoomHandle__enable_remote_media_from(
+    this: ptr::NonNull<RoomHandle>,
+    member_id: ptr::NonNull<libc::c_char>,
+) -> DartFuture<Result<(), Traced<ChangeMediaStateError>>> {
+    let this = this.as_ref().clone();
+    let member_id = c_str_into_string(member_id);
+
+    async move {
+        this.enable_remote_media_from(member_id).await?;
+        Ok(())
+    }
+    .into_dart_future()
+}
+
+/// Disables inbound media from the remote `Member` with the provided ID in
+/// this [`Room`].
+///
+/// [`Room`]: crate::room::Room
+#[no_mangle]
+pub unsafe extern "C" fn RoomHandle__disable_remote_media_from(
+    this: ptr::NonNull<RoomHandle>,
+    member_id: ptr::NonNull<libc::c_char>,
+) -> DartFuture<Result<(), Traced<ChangeMediaStateError>>> {
+    let this = this.as_ref().clone();
+    let member_id = c_str_into_string(member_id);
+
+    async move {
+        this.disable_remote_media_from(member_id).await?;
+        Ok(())
+    }
+    .into_dart_future()
+}
+
 /// Frees the data behind the provided pointer.
 ///
 /// # Safety
@@ -530,10 +835,10 @@ mod mock {
 
     use crate::{
         api::{
-            dart::utils::DartError, ConnectionHandle, LocalMediaTrack,
+            dart::utils::DartError, AppData, ConnectionHandle, LocalMediaTrack,
             MediaStreamSettings, ReconnectHandle,
         },
-        media::MediaSourceKind,
+        media::{MediaSourceKind, RecvMediaPolicy},
         peer::{LocalMediaError, TracksRequestError, UpdateLocalStreamError},
         platform,
         room::{
@@ -583,6 +888,69 @@ mod mock {
             Ok(())
         }
 
+        pub fn on_app_data(
+            &self,
+            _cb: platform::Function<AppData>,
+        ) -> Result<(), Traced<HandleDetachedError>> {
+            Ok(())
+        }
+
+        pub fn on_audio_route_change(
+            &self,
+            cb: platform::Function<u8>,
+        ) -> Result<(), Traced<HandleDetachedError>> {
+            cb.call1(1);
+            Ok(())
+        }
+
+        pub fn send_app_data(
+            &self,
+            to: Option<String>,
+            _data: serde_json::Value,
+        ) -> Result<(), Traced<HandleDetachedError>> {
+            assert_eq!(to, None);
+            Ok(())
+        }
+
+        pub fn update_token(
+            &self,
+            _token: String,
+        ) -> Result<(), Traced<HandleDetachedError>> {
+            Ok(())
+        }
+
+        pub async fn export_debug_dump(
+            &self,
+        ) -> Result<String, Traced<HandleDetachedError>> {
+            Ok(String::from("{}"))
+        }
+
+        pub fn set_stats_interval(
+            &self,
+            _interval_ms: u32,
+        ) -> Result<(), Traced<HandleDetachedError>> {
+            Ok(())
+        }
+
+        pub async fn poll_stats_now(
+            &self,
+        ) -> Result<(), Traced<HandleDetachedError>> {
+            Ok(())
+        }
+
+        pub async fn retry_local_media(
+            &self,
+        ) -> Result<(), Traced<ChangeMediaStateError>> {
+            Ok(())
+        }
+
+        pub fn set_local_media_retry_interval(
+            &self,
+            _interval_ms: u32,
+        ) -> Result<(), Traced<HandleDetachedError>> {
+            Ok(())
+        }
+
         pub async fn join(
             &self,
             token: String,
@@ -593,6 +961,19 @@ mod mock {
                 .map(drop)
         }
 
+        pub async fn join_without_media(
+            &self,
+            token: String,
+        ) -> Result<(), Traced<RoomJoinError>> {
+            self.join(token).await
+        }
+
+        pub async fn publish(
+            &self,
+        ) -> Result<(), Traced<ChangeMediaStateError>> {
+            Ok(())
+        }
+
         pub fn on_failed_local_media(
             &self,
             cb: platform::Function<DartError>,
@@ -696,5 +1077,29 @@ mod mock {
         ) -> Result<(), Traced<ChangeMediaStateError>> {
             Ok(())
         }
+
+        pub fn set_recv_media_policy(
+            &self,
+            policy: RecvMediaPolicy,
+        ) -> Result<(), Traced<HandleDetachedError>> {
+            assert_eq!(policy, RecvMediaPolicy::Manual);
+            Ok(())
+        }
+
+        pub async fn enable_remote_media_from(
+            &self,
+            member_id: String,
+        ) -> Result<(), Traced<ChangeMediaStateError>> {
+            assert_eq!(member_id, "bob");
+            Ok(())
+        }
+
+        pub async fn disable_remote_media_from(
+            &self,
+            member_id: String,
+        ) -> Result<(), Traced<ChangeMediaStateError>> {
+            assert_eq!(member_id, "bob");
+            Ok(())
+        }
     }
 }
@@ -1,8 +1,10 @@
-//! Functionality for passing arrays from Rust to Dart.
+//! Functionality for passing arrays between Rust and Dart.
 
+#[cfg(feature = "mockable")]
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::{ffi::c_void, marker::PhantomData, ptr, slice};
 
-use crate::api::ForeignClass;
+use crate::api::{DartValue, ForeignClass};
 
 /// Array of pointers to [`ForeignClass`] structs.
 ///
@@ -73,3 +75,132 @@ impl<T> Drop for PtrArray<T> {
 pub unsafe extern "C" fn PtrArray_free(arr: ptr::NonNull<PtrArray>) {
     drop(Box::from_raw(arr.as_ptr()));
 }
+
+/// Number of [`ValueArray`]s transferred to the foreign code, but not freed by
+/// it yet.
+///
+/// Used to make sure that foreign code doesn't leak any [`ValueArray`]s.
+#[cfg(feature = "mockable")]
+pub static VALUE_ARRAYS_ALIVE: AtomicUsize = AtomicUsize::new(0);
+
+/// Array of values laid out contiguously.
+///
+/// In opposite to a [`PtrArray`], elements are stored inline, so they are
+/// freed along with this [`ValueArray`]. This allows to pass through FFI
+/// boundaries nested collections of [`DartValue`]s, maps and strings which
+/// are not null-terminated.
+///
+/// Can be safely returned from extern functions. Foreign code must manually
+/// free this array by calling the appropriate `_free()` function:
+/// - [`DartValueArray_free()`] for a [`ValueArray`] of [`DartValue`]s;
+/// - [`DartMap_free()`] for a [`ValueArray`] of [`DartMapEntry`]s;
+/// - [`SizedString_free()`] for a [`ValueArray`] of bytes.
+#[repr(C)]
+#[derive(Debug)]
+pub struct ValueArray<T> {
+    /// Pointer to the first element.
+    ptr: ptr::NonNull<T>,
+
+    /// Array length.
+    len: u64,
+}
+
+impl<T> ValueArray<T> {
+    /// Constructs a new [`ValueArray`] from the provided iterator of values.
+    pub fn new<I: IntoIterator<Item = T>>(arr: I) -> Self {
+        let out: Vec<_> = arr.into_iter().collect();
+        #[cfg(feature = "mockable")]
+        {
+            VALUE_ARRAYS_ALIVE.fetch_add(1, Ordering::SeqCst);
+        }
+        Self {
+            len: out.len() as u64,
+            ptr: ptr::NonNull::from(Box::leak(out.into_boxed_slice())).cast(),
+        }
+    }
+
+    /// Returns elements of this [`ValueArray`] as a slice.
+    #[allow(clippy::cast_possible_truncation)]
+    #[inline]
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len as usize) }
+    }
+
+    /// Leaks this [`ValueArray`] returning a pointer to it, so it can be
+    /// transferred to the foreign code.
+    #[inline]
+    #[must_use]
+    pub fn into_ptr(self) -> ptr::NonNull<Self> {
+        ptr::NonNull::from(Box::leak(Box::new(self)))
+    }
+}
+
+impl<T> Drop for ValueArray<T> {
+    /// Drops this [`ValueArray`] along with its elements.
+    ///
+    /// Note, that [`DartValue`]s don't free the data they're pointing to, so
+    /// it should be freed (or taken ownership over) by the foreign code before
+    /// dropping the [`ValueArray`].
+    #[allow(clippy::cast_possible_truncation)]
+    fn drop(&mut self) {
+        #[cfg(feature = "mockable")]
+        {
+            VALUE_ARRAYS_ALIVE.fetch_sub(1, Ordering::SeqCst);
+        }
+        unsafe {
+            Box::from_raw(slice::from_raw_parts_mut(
+                self.ptr.as_ptr(),
+                self.len as usize,
+            ));
+        };
+    }
+}
+
+/// Entry of the map transferred through FFI boundaries as a [`ValueArray`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct DartMapEntry {
+    /// Key of this [`DartMapEntry`].
+    pub key: DartValue,
+
+    /// Value of this [`DartMapEntry`].
+    pub value: DartValue,
+}
+
+/// Drops the provided [`ValueArray`] of [`DartValue`]s.
+///
+/// # Safety
+///
+/// Doesn't free the data that array elements are pointing to. Foreign code
+/// must convert all the elements before calling this function.
+
+#[no_mangle]
+pub unsafe extern "C" fn DartValueArray_free(
+    arr: ptr::NonNull<ValueArray<DartValue>>,
+) {
+    drop(Box::from_raw(arr.as_ptr()));
+}
+
+/// Drops the provided [`ValueArray`] of [`DartMapEntry`]s.
+///
+/// # Safety
+///
+/// Doesn't free the data that keys and values are pointing to. Foreign code
+/// must convert all the entries before calling this function.
+#[no_mangle]
+pub unsafe extern "C" fn DartMap_free(
+    arr: ptr::NonNull<ValueArray<DartMapEntry>>,
+) {
+    drop(Box::from_raw(arr.as_ptr()));
+}
+
+/// Drops the provided [`ValueArray`] of UTF-8 bytes.
+///
+/// # Safety
+///
+/// Should not be called before foreign code reads the string.
+#[no_mangle]
+pub unsafe extern "C" fn SizedString_free(s: ptr::NonNull<ValueArray<u8>>) {
+    drop(Box::from_raw(s.as_ptr()));
+}
@@ -9,7 +9,7 @@ use tracerr::{Trace, Traced};
 
 use crate::{
     api::dart::{utils::string_into_c_str, DartValue},
-    platform,
+    platform::{self, utils::DartExternFn},
     room::ChangeMediaStateError,
 };
 
@@ -93,177 +93,158 @@ type NewMediaSettingsUpdateExceptionCaller = extern "C" fn(
 /// Stores pointer to the [`NewArgumentErrorCaller`] extern function.
 ///
 /// Must be initialized by Dart during FFI initialization phase.
-static mut NEW_ARGUMENT_ERROR_CALLER: Option<NewArgumentErrorCaller> = None;
+static NEW_ARGUMENT_ERROR_CALLER: DartExternFn<NewArgumentErrorCaller> =
+    DartExternFn::new("NewArgumentErrorCaller");
 
 /// Stores pointer to the [`NewStateErrorCaller`] extern function.
 ///
 /// Must be initialized by Dart during FFI initialization phase.
-static mut NEW_STATE_ERROR_CALLER: Option<NewStateErrorCaller> = None;
+static NEW_STATE_ERROR_CALLER: DartExternFn<NewStateErrorCaller> =
+    DartExternFn::new("NewStateErrorCaller");
 
 /// Stores pointer to the [`NewFormatExceptionCaller`] extern function.
 ///
 /// Must be initialized by Dart during FFI initialization phase.
-static mut NEW_FORMAT_EXCEPTION_CALLER: Option<NewFormatExceptionCaller> = None;
+static NEW_FORMAT_EXCEPTION_CALLER: DartExternFn<NewFormatExceptionCaller> =
+    DartExternFn::new("NewFormatExceptionCaller");
 
 /// Stores pointer to the [`NewLocalMediaInitExceptionCaller`] extern function.
 ///
 /// Must be initialized by Dart during FFI initialization phase.
-static mut NEW_LOCAL_MEDIA_INIT_EXCEPTION_CALLER: Option<
+static NEW_LOCAL_MEDIA_INIT_EXCEPTION_CALLER: DartExternFn<
     NewLocalMediaInitExceptionCaller,
-> = None;
+> = DartExternFn::new("NewLocalMediaInitExceptionCaller");
 
 /// Stores pointer to the [`NewEnumerateDevicesExceptionCaller`] extern
 /// function.
 ///
 /// Must be initialized by Dart during FFI initialization phase.
-static mut NEW_ENUMERATE_DEVICES_EXCEPTION_CALLER: Option<
+static NEW_ENUMERATE_DEVICES_EXCEPTION_CALLER: DartExternFn<
     NewEnumerateDevicesExceptionCaller,
-> = None;
+> = DartExternFn::new("NewEnumerateDevicesExceptionCaller");
 
 /// Stores pointer to the [`NewRpcClientExceptionCaller`] extern function.
 ///
 /// Must be initialized by Dart during FFI initialization phase.
-static mut NEW_RPC_CLIENT_EXCEPTION_CALLER: Option<
+static NEW_RPC_CLIENT_EXCEPTION_CALLER: DartExternFn<
     NewRpcClientExceptionCaller,
-> = None;
+> = DartExternFn::new("NewRpcClientExceptionCaller");
 
 /// Stores pointer to the [`NewMediaStateTransitionExceptionCaller`] extern
 /// function.
 ///
 /// Must be initialized by Dart during FFI initialization phase.
-static mut NEW_MEDIA_STATE_TRANSITION_EXCEPTION_CALLER: Option<
+static NEW_MEDIA_STATE_TRANSITION_EXCEPTION_CALLER: DartExternFn<
     NewMediaStateTransitionExceptionCaller,
-> = None;
+> = DartExternFn::new("NewMediaStateTransitionExceptionCaller");
 
 /// Stores pointer to the [`NewInternalExceptionCaller`] extern function.
 ///
 /// Must be initialized by Dart during FFI initialization phase.
-static mut NEW_INTERNAL_EXCEPTION_CALLER: Option<NewInternalExceptionCaller> =
-    None;
+static NEW_INTERNAL_EXCEPTION_CALLER: DartExternFn<NewInternalExceptionCaller> =
+    DartExternFn::new("NewInternalExceptionCaller");
 
 /// Stores pointer to the [`NewMediaSettingsUpdateExceptionCaller`] extern
 /// function.
 ///
 /// Must be initialized by Dart during FFI initialization phase.
-static mut NEW_MEDIA_SETTINGS_UPDATE_EXCEPTION_CALLER: Option<
+static NEW_MEDIA_SETTINGS_UPDATE_EXCEPTION_CALLER: DartExternFn<
     NewMediaSettingsUpdateExceptionCaller,
-> = None;
+> = DartExternFn::new("NewMediaSettingsUpdateExceptionCaller");
 
 /// Registers the provided [`NewArgumentErrorCaller`] as
 /// [`NEW_ARGUMENT_ERROR_CALLER`].
 ///
-/// # Safety
-///
 /// Must ONLY be called by Dart during FFI initialization.
 #[no_mangle]
-pub unsafe extern "C" fn register_new_argument_error_caller(
+pub extern "C" fn register_new_argument_error_caller(
     f: NewArgumentErrorCaller,
 ) {
-    NEW_ARGUMENT_ERROR_CALLER = Some(f);
+    NEW_ARGUMENT_ERROR_CALLER.register(f);
 }
 
 /// Registers the provided [`NewStateErrorCaller`] as
 /// [`NEW_STATE_ERROR_CALLER`].
 ///
-/// # Safety
-///
 /// Must ONLY be called by Dart during FFI initialization.
 #[no_mangle]
-pub unsafe extern "C" fn register_new_state_error_caller(
-    f: NewStateErrorCaller,
-) {
-    NEW_STATE_ERROR_CALLER = Some(f);
+pub extern "C" fn register_new_state_error_caller(f: NewStateErrorCaller) {
+    NEW_STATE_ERROR_CALLER.register(f);
 }
 
 /// Registers the provided [`NewFormatExceptionCaller`] as
 /// [`NEW_FORMAT_EXCEPTION_CALLER`].
 ///
-/// # Safety
-///
 /// Must ONLY be called by Dart during FFI initialization.
 #[no_mangle]
-pub unsafe extern "C" fn register_new_format_exception_caller(
-    f: NewStateErrorCaller,
-) {
-    NEW_FORMAT_EXCEPTION_CALLER = Some(f);
+pub extern "C" fn register_new_format_exception_caller(f: NewStateErrorCaller) {
+    NEW_FORMAT_EXCEPTION_CALLER.register(f);
 }
 
 /// Registers the provided [`NewLocalMediaInitExceptionCaller`] as
 /// [`NEW_LOCAL_MEDIA_INIT_EXCEPTION_CALLER`].
 ///
-/// # Safety
-///
 /// Must ONLY be called by Dart during FFI initialization.
 #[no_mangle]
-pub unsafe extern "C" fn register_new_local_media_init_exception_caller(
+pub extern "C" fn register_new_local_media_init_exception_caller(
     f: NewLocalMediaInitExceptionCaller,
 ) {
-    NEW_LOCAL_MEDIA_INIT_EXCEPTION_CALLER = Some(f);
+    NEW_LOCAL_MEDIA_INIT_EXCEPTION_CALLER.register(f);
 }
 
 /// Registers the provided [`NewLocalMediaInitExceptionCaller`] as
 /// [`NEW_ENUMERATE_DEVICES_EXCEPTION_CALLER`].
 ///
-/// # Safety
-///
 /// Must ONLY be called by Dart during FFI initialization.
 #[no_mangle]
-pub unsafe extern "C" fn register_new_enumerate_devices_exception_caller(
+pub extern "C" fn register_new_enumerate_devices_exception_caller(
     f: NewEnumerateDevicesExceptionCaller,
 ) {
-    NEW_ENUMERATE_DEVICES_EXCEPTION_CALLER = Some(f);
+    NEW_ENUMERATE_DEVICES_EXCEPTION_CALLER.register(f);
 }
 
 /// Registers the provided [`NewRpcClientExceptionCaller`] as
 /// [`NEW_RPC_CLIENT_EXCEPTION_CALLER`].
 ///
-/// # Safety
-///
 /// Must ONLY be called by Dart during FFI initialization.
 #[no_mangle]
-pub unsafe extern "C" fn register_new_rpc_client_exception_caller(
+pub extern "C" fn register_new_rpc_client_exception_caller(
     f: NewRpcClientExceptionCaller,
 ) {
-    NEW_RPC_CLIENT_EXCEPTION_CALLER = Some(f);
+    NEW_RPC_CLIENT_EXCEPTION_CALLER.register(f);
 }
 
 /// Registers the provided [`NewMediaStateTransitionExceptionCaller`] as
 /// [`NEW_MEDIA_STATE_TRANSITION_EXCEPTION_CALLER`].
 ///
-/// # Safety
-///
 /// Must ONLY be called by Dart during FFI initialization.
 #[no_mangle]
-pub unsafe extern "C" fn register_new_media_state_transition_exception_caller(
+pub extern "C" fn register_new_media_state_transition_exception_caller(
     f: NewMediaStateTransitionExceptionCaller,
 ) {
-    NEW_MEDIA_STATE_TRANSITION_EXCEPTION_CALLER = Some(f);
+    NEW_MEDIA_STATE_TRANSITION_EXCEPTION_CALLER.register(f);
 }
 
 /// Registers the provided [`NewInternalExceptionCaller`] as
 /// [`NEW_INTERNAL_EXCEPTION_CALLER`].
 ///
-/// # Safety
-///
 /// Must ONLY be called by Dart during FFI initialization.
 #[no_mangle]
-pub unsafe extern "C" fn register_new_internal_exception_caller(
+pub extern "C" fn register_new_internal_exception_caller(
     f: NewInternalExceptionCaller,
 ) {
-    NEW_INTERNAL_EXCEPTION_CALLER = Some(f);
+    NEW_INTERNAL_EXCEPTION_CALLER.register(f);
 }
 
 /// Registers the provided [`NewMediaSettingsUpdateExceptionCaller`] as
 /// [`NEW_MEDIA_SETTINGS_UPDATE_EXCEPTION_CALLER`].
 ///
-/// # Safety
-///
 /// Must ONLY be called by Dart during FFI initialization.
 #[no_mangle]
-pub unsafe extern "C" fn register_new_media_settings_update_exception_caller(
+pub extern "C" fn register_new_media_settings_update_exception_caller(
     f: NewMediaSettingsUpdateExceptionCaller,
 ) {
-    NEW_MEDIA_SETTINGS_UPDATE_EXCEPTION_CALLER = Some(f);
+    NEW_MEDIA_SETTINGS_UPDATE_EXCEPTION_CALLER.register(f);
 }
 
 /// An error that can be returned from Rust to Dart.
@@ -322,13 +303,11 @@ impl<T> ArgumentError<T> {
 impl<T: Into<DartValue>> From<ArgumentError<T>> for DartError {
     #[inline]
     fn from(err: ArgumentError<T>) -> Self {
-        unsafe {
-            Self::new(NEW_ARGUMENT_ERROR_CALLER.unwrap()(
-                err.val.into(),
-                string_into_c_str(err.name.to_owned()),
-                string_into_c_str(err.message.into_owned()),
-            ))
-        }
+        Self::new(NEW_ARGUMENT_ERROR_CALLER.get()(
+            err.val.into(),
+            string_into_c_str(err.name.to_owned()),
+            string_into_c_str(err.message.into_owned()),
+        ))
     }
 }
 
@@ -351,11 +330,9 @@ impl StateError {
 impl From<StateError> for DartError {
     #[inline]
     fn from(err: StateError) -> Self {
-        unsafe {
-            Self::new(NEW_STATE_ERROR_CALLER.unwrap()(string_into_c_str(
-                err.0.into_owned(),
-            )))
-        }
+        Self::new(NEW_STATE_ERROR_CALLER.get()(string_into_c_str(
+            err.0.into_owned(),
+        )))
     }
 }
 
@@ -419,14 +396,12 @@ impl LocalMediaInitException {
 impl From<LocalMediaInitException> for DartError {
     #[inline]
     fn from(err: LocalMediaInitException) -> Self {
-        unsafe {
-            Self::new(NEW_LOCAL_MEDIA_INIT_EXCEPTION_CALLER.unwrap()(
-                err.kind,
-                string_into_c_str(err.message.into_owned()),
-                err.cause.map(DartError::from).into(),
-                string_into_c_str(err.trace.to_string()),
-            ))
-        }
+        Self::new(NEW_LOCAL_MEDIA_INIT_EXCEPTION_CALLER.get()(
+            err.kind,
+            string_into_c_str(err.message.into_owned()),
+            err.cause.map(DartError::from).into(),
+            string_into_c_str(err.trace.to_string()),
+        ))
     }
 }
 
@@ -452,12 +427,10 @@ impl EnumerateDevicesException {
 impl From<EnumerateDevicesException> for DartError {
     #[inline]
     fn from(err: EnumerateDevicesException) -> Self {
-        unsafe {
-            Self::new(NEW_ENUMERATE_DEVICES_EXCEPTION_CALLER.unwrap()(
-                err.cause.into(),
-                string_into_c_str(err.trace.to_string()),
-            ))
-        }
+        Self::new(NEW_ENUMERATE_DEVICES_EXCEPTION_CALLER.get()(
+            err.cause.into(),
+            string_into_c_str(err.trace.to_string()),
+        ))
     }
 }
 
@@ -480,11 +453,9 @@ impl FormatException {
 impl From<FormatException> for DartError {
     #[inline]
     fn from(err: FormatException) -> Self {
-        unsafe {
-            Self::new(NEW_FORMAT_EXCEPTION_CALLER.unwrap()(string_into_c_str(
-                err.0.into_owned(),
-            )))
-        }
+        Self::new(NEW_FORMAT_EXCEPTION_CALLER.get()(string_into_c_str(
+            err.0.into_owned(),
+        )))
     }
 }
 
@@ -505,6 +476,9 @@ pub enum RpcClientExceptionKind {
 
     /// RPC session has been finished. This is a terminal state.
     SessionFinished,
+
+    /// Reconnection with a backoff was cancelled.
+    ReconnectionCancelled,
 }
 
 /// Exceptions thrown from an RPC client that implements messaging with media
@@ -546,14 +520,12 @@ impl RpcClientException {
 impl From<RpcClientException> for DartError {
     #[inline]
     fn from(err: RpcClientException) -> Self {
-        unsafe {
-            Self::new(NEW_RPC_CLIENT_EXCEPTION_CALLER.unwrap()(
-                err.kind,
-                string_into_c_str(err.message.into_owned()),
-                err.cause.map(DartError::from).into(),
-                string_into_c_str(err.trace.to_string()),
-            ))
-        }
+        Self::new(NEW_RPC_CLIENT_EXCEPTION_CALLER.get()(
+            err.kind,
+            string_into_c_str(err.message.into_owned()),
+            err.cause.map(DartError::from).into(),
+            string_into_c_str(err.trace.to_string()),
+        ))
     }
 }
 
@@ -583,12 +555,10 @@ impl MediaStateTransitionException {
 impl From<MediaStateTransitionException> for DartError {
     #[inline]
     fn from(err: MediaStateTransitionException) -> Self {
-        unsafe {
-            Self::new(NEW_MEDIA_STATE_TRANSITION_EXCEPTION_CALLER.unwrap()(
-                string_into_c_str(err.message.into_owned()),
-                string_into_c_str(err.trace.to_string()),
-            ))
-        }
+        Self::new(NEW_MEDIA_STATE_TRANSITION_EXCEPTION_CALLER.get()(
+            string_into_c_str(err.message.into_owned()),
+            string_into_c_str(err.trace.to_string()),
+        ))
     }
 }
 
@@ -630,13 +600,11 @@ impl InternalException {
 impl From<InternalException> for DartError {
     #[inline]
     fn from(err: InternalException) -> Self {
-        unsafe {
-            Self::new(NEW_INTERNAL_EXCEPTION_CALLER.unwrap()(
-                string_into_c_str(err.message.into_owned()),
-                err.cause.map(DartError::from).into(),
-                string_into_c_str(err.trace.to_string()),
-            ))
-        }
+        Self::new(NEW_INTERNAL_EXCEPTION_CALLER.get()(
+            string_into_c_str(err.message.into_owned()),
+            err.cause.map(DartError::from).into(),
+            string_into_c_str(err.trace.to_string()),
+        ))
     }
 }
 
@@ -679,12 +647,10 @@ impl MediaSettingsUpdateException {
 impl From<MediaSettingsUpdateException> for DartError {
     #[inline]
     fn from(err: MediaSettingsUpdateException) -> Self {
-        unsafe {
-            Self::new(NEW_MEDIA_SETTINGS_UPDATE_EXCEPTION_CALLER.unwrap()(
-                string_into_c_str(err.message.into_owned()),
-                err.cause.into(),
-                err.rolled_back as u8,
-            ))
-        }
+        Self::new(NEW_MEDIA_SETTINGS_UPDATE_EXCEPTION_CALLER.get()(
+            string_into_c_str(err.message.into_owned()),
+            err.cause.into(),
+            err.rolled_back as u8,
+        ))
     }
 }
@@ -13,7 +13,7 @@ use crate::{
 };
 
 pub use self::{
-    arrays::PtrArray,
+    arrays::{DartMapEntry, PtrArray, ValueArray},
     err::{
         ArgumentError, DartError, EnumerateDevicesException, FormatException,
         InternalException, LocalMediaInitException,
@@ -22,9 +22,12 @@ pub use self::{
         RpcClientExceptionKind, StateError,
     },
     result::DartResult,
-    string::{c_str_into_string, string_into_c_str},
+    string::{c_str_into_string, sized_str_into_string, string_into_c_str},
 };
 
+#[cfg(feature = "mockable")]
+pub use self::arrays::VALUE_ARRAYS_ALIVE;
+
 /// Rust representation of a Dart [`Future`].
 ///
 /// [`Future`]: https://api.dart.dev/dart-async/Future-class.html
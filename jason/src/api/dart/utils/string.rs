@@ -3,9 +3,11 @@
 use std::{
     ffi::{CStr, CString},
     os::raw::c_char,
-    ptr,
+    ptr, str,
 };
 
+use super::ValueArray;
+
 /// Constructs a Rust [`String`] from the provided raw C string.
 ///
 /// # Panics
@@ -21,6 +23,28 @@ pub unsafe fn c_str_into_string(string: ptr::NonNull<c_char>) -> String {
     CStr::from_ptr(string.as_ptr()).to_str().unwrap().to_owned()
 }
 
+/// Constructs a Rust [`String`] from the provided [`ValueArray`] of UTF-8
+/// bytes, which is not null-terminated.
+///
+/// Doesn't take ownership over the provided [`ValueArray`].
+///
+/// # Panics
+///
+/// If the provided bytes UTF-8 validation fails.
+///
+/// # Safety
+///
+/// The provided pointer must point to a valid [`ValueArray`].
+#[inline]
+#[must_use]
+pub unsafe fn sized_str_into_string(
+    string: ptr::NonNull<ValueArray<u8>>,
+) -> String {
+    str::from_utf8(string.as_ref().as_slice())
+        .unwrap()
+        .to_owned()
+}
+
 /// Leaks the given [`String`] returning a raw C string that can be passed
 /// through FFI boundaries.
 ///
@@ -0,0 +1,134 @@
+//! Platform-agnostic representation of an error exposed to the API user.
+
+use std::fmt::Display;
+
+use derive_more::Display;
+use tracerr::{Trace, Traced};
+
+use crate::{platform, utils::JsCaused};
+
+/// Platform-agnostic representation of an error exposed to the API user.
+///
+/// Front-ends map it into their own native error types (JS exception, etc.),
+/// so errors carry the same stable code and name on all the platforms.
+#[derive(Clone, Debug, Display)]
+#[display(fmt = "{}::{}: {}\n{}", code, name, message, trace)]
+pub struct ApiError {
+    /// Code of this [`ApiError`], identifying the type of the original error.
+    code: &'static str,
+
+    /// Name of this [`ApiError`], identifying the concrete kind of the
+    /// original error.
+    name: &'static str,
+
+    /// Message describing this [`ApiError`].
+    message: String,
+
+    /// [`Trace`] information of this [`ApiError`].
+    trace: Trace,
+
+    /// Optional platform error being the cause of this [`ApiError`].
+    platform_cause: Option<platform::Error>,
+}
+
+impl ApiError {
+    /// Returns a code of this [`ApiError`].
+    ///
+    /// Code is a stable name of the original error type, like
+    /// `RoomJoinError`, as returned by [`JsCaused::code()`].
+    #[inline]
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    /// Returns a name of this [`ApiError`].
+    ///
+    /// Name is the concrete kind of the original error, like
+    /// `CallbackNotSet`.
+    #[inline]
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns a message of this [`ApiError`].
+    #[inline]
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns a [`Trace`] information of this [`ApiError`].
+    #[inline]
+    #[must_use]
+    pub fn trace(&self) -> &Trace {
+        &self.trace
+    }
+
+    /// Returns a platform error if it's the cause of this [`ApiError`].
+    #[inline]
+    #[must_use]
+    pub fn platform_cause(&self) -> Option<&platform::Error> {
+        self.platform_cause.as_ref()
+    }
+
+    /// Splits this [`ApiError`] into its message, [`Trace`] and an optional
+    /// platform cause.
+    #[inline]
+    #[must_use]
+    pub fn into_parts(self) -> (String, Trace, Option<platform::Error>) {
+        (self.message, self.trace, self.platform_cause)
+    }
+}
+
+impl<E: JsCaused + Display> From<(E, Trace)> for ApiError
+where
+    E::Error: Into<platform::Error>,
+{
+    fn from((err, trace): (E, Trace)) -> Self {
+        Self {
+            code: err.code(),
+            name: err.name(),
+            message: err.to_string(),
+            trace,
+            platform_cause: err.js_cause().map(Into::into),
+        }
+    }
+}
+
+impl<E: JsCaused + Display> From<Traced<E>> for ApiError
+where
+    E::Error: Into<platform::Error>,
+{
+    #[inline]
+    fn from(traced: Traced<E>) -> Self {
+        Self::from(traced.into_parts())
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use derive_more::Display;
+
+    use crate::{platform, utils::JsCaused};
+
+    use super::ApiError;
+
+    #[derive(Debug, Display, JsCaused)]
+    #[js(error = "platform::Error")]
+    enum SomeError {
+        #[display(fmt = "something went wrong")]
+        SomethingWrong,
+    }
+
+    #[test]
+    fn maps_error_type_and_kind() {
+        let err = ApiError::from(tracerr::new!(SomeError::SomethingWrong));
+
+        assert_eq!(err.code(), "SomeError");
+        assert_eq!(err.name(), "SomethingWrong");
+        assert_eq!(err.message(), "something went wrong");
+        assert!(err.platform_cause().is_none());
+    }
+}
@@ -1,5 +1,9 @@
 //! External [`Jason`] API.
 
+mod error;
+
+pub use self::error::ApiError;
+
 cfg_if::cfg_if! {
     if #[cfg(target_os = "android")] {
         mod dart;
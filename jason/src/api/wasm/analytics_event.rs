@@ -0,0 +1,42 @@
+//! Structured event of a [`Jason`] lifecycle stage.
+//!
+//! [`Jason`]: jason::Jason
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::{api::AnalyticsEventKind, jason};
+
+/// Structured event of a key [`Jason`] lifecycle stage.
+///
+/// This struct is passed to a [`Jason::set_analytics_sink`] JS side callback.
+///
+/// [`Jason`]: jason::Jason
+/// [`Jason::set_analytics_sink`]: crate::api::Jason::set_analytics_sink
+#[wasm_bindgen]
+#[derive(From)]
+pub struct AnalyticsEvent(jason::AnalyticsEvent);
+
+#[wasm_bindgen]
+impl AnalyticsEvent {
+    /// Returns [`AnalyticsEventKind`] of this [`AnalyticsEvent`].
+    #[must_use]
+    pub fn kind(&self) -> AnalyticsEventKind {
+        self.0.kind().into()
+    }
+
+    /// Returns ID of the `PeerConnection` this [`AnalyticsEvent`] relates to,
+    /// if any.
+    #[must_use]
+    pub fn peer_id(&self) -> Option<u32> {
+        self.0.peer_id().map(|id| id.0)
+    }
+
+    /// Returns duration (in milliseconds) of the stage this
+    /// [`AnalyticsEvent`] finishes, if any.
+    #[allow(clippy::cast_possible_truncation)]
+    #[must_use]
+    pub fn duration(&self) -> Option<u32> {
+        self.0.duration().map(|d| d.as_millis() as u32)
+    }
+}
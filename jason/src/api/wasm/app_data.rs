@@ -0,0 +1,35 @@
+//! Application data relayed from a remote `Member`.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::room;
+
+/// Application data sent by some remote `Member` of a [`Room`].
+///
+/// This struct is passed to a [`RoomHandle::on_app_data`] JS side callback.
+///
+/// [`Room`]: room::Room
+/// [`RoomHandle::on_app_data`]: crate::api::RoomHandle::on_app_data
+#[wasm_bindgen]
+#[derive(From)]
+pub struct AppData(room::AppData);
+
+#[wasm_bindgen]
+impl AppData {
+    /// Returns ID of the remote `Member` which sent this [`AppData`].
+    #[must_use]
+    pub fn from(&self) -> String {
+        self.0.from()
+    }
+
+    /// Returns payload of this [`AppData`].
+    ///
+    /// # Errors
+    ///
+    /// If the payload cannot be converted into a JS value.
+    pub fn data(&self) -> Result<JsValue, JsValue> {
+        JsValue::from_serde(self.0.data())
+            .map_err(|e| js_sys::TypeError::new(&e.to_string()).into())
+    }
+}
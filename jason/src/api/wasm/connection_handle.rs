@@ -5,6 +5,8 @@ use wasm_bindgen::prelude::*;
 
 use crate::{api, connection};
 
+use super::typings;
+
 /// Connection with a specific remote `Member`, that is used on JS side.
 ///
 /// Like all the handles it contains a weak reference to the object that is
@@ -19,7 +21,7 @@ impl ConnectionHandle {
     /// Sets callback, invoked when this [`Connection`] is closed.
     ///
     /// [`Connection`]: connection::Connection
-    pub fn on_close(&self, cb: js_sys::Function) -> Result<(), JsValue> {
+    pub fn on_close(&self, cb: typings::VoidCallback) -> Result<(), JsValue> {
         self.0
             .on_close(cb.into())
             .map_err(api::Error::from)
@@ -41,7 +43,7 @@ impl ConnectionHandle {
     /// [`RemoteMediaTrack`]: crate::api::RemoteMediaTrack
     pub fn on_remote_track_added(
         &self,
-        cb: js_sys::Function,
+        cb: typings::RemoteTrackCallback,
     ) -> Result<(), JsValue> {
         self.0
             .on_remote_track_added(cb.into())
@@ -49,15 +51,57 @@ impl ConnectionHandle {
             .map_err(JsValue::from)
     }
 
+    /// Sets callback, invoked when a [`RemoteMediaTrack`] is removed from this
+    /// [`Connection`] by a server.
+    ///
+    /// [`Connection`]: connection::Connection
+    /// [`RemoteMediaTrack`]: crate::api::RemoteMediaTrack
+    pub fn on_remote_track_removed(
+        &self,
+        cb: typings::RemoteTrackCallback,
+    ) -> Result<(), JsValue> {
+        self.0
+            .on_remote_track_removed(cb.into())
+            .map_err(api::Error::from)
+            .map_err(JsValue::from)
+    }
+
     /// Sets callback, invoked when connection quality score is updated by a
     /// server.
     pub fn on_quality_score_update(
         &self,
-        cb: js_sys::Function,
+        cb: typings::QualityScoreCallback,
     ) -> Result<(), JsValue> {
         self.0
             .on_quality_score_update(cb.into())
             .map_err(api::Error::from)
             .map_err(JsValue::from)
     }
+
+    /// Returns current key-value metadata of the remote `Member`.
+    pub fn get_metadata(&self) -> Result<js_sys::Map, JsValue> {
+        self.0
+            .get_metadata()
+            .map(|metadata| {
+                metadata
+                    .into_iter()
+                    .fold(js_sys::Map::new(), |map, (k, v)| {
+                        map.set(&k.into(), &v.into())
+                    })
+            })
+            .map_err(api::Error::from)
+            .map_err(JsValue::from)
+    }
+
+    /// Sets callback, invoked when metadata of the remote `Member` is updated
+    /// by a server.
+    pub fn on_metadata_update(
+        &self,
+        cb: typings::VoidCallback,
+    ) -> Result<(), JsValue> {
+        self.0
+            .on_metadata_update(cb.into())
+            .map_err(api::Error::from)
+            .map_err(JsValue::from)
+    }
 }
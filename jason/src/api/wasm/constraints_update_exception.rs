@@ -7,7 +7,7 @@ use wasm_bindgen::prelude::*;
 
 use crate::room;
 
-use super::Error;
+use super::{typings, Error};
 
 /// Exception returned from [`RoomHandle::set_local_media_settings()`][1].
 ///
@@ -36,7 +36,7 @@ impl ConstraintsUpdateException {
     /// Returns [`js_sys::Array`] with an [`Error`]s if this
     /// [`ConstraintsUpdateException`] represents a `RecoverFailedException`.
     #[must_use]
-    pub fn recover_fail_reasons(&self) -> JsValue {
+    pub fn recover_fail_reasons(&self) -> typings::JasonErrors {
         self.0
             .recover_fail_reasons()
             .into_iter()
@@ -5,7 +5,10 @@
 use derive_more::From;
 use wasm_bindgen::prelude::*;
 
-use crate::{api::MediaKind, platform};
+use crate::{
+    api::{FacingMode, MediaKind},
+    media, platform,
+};
 
 /// Representation of a [MediaDeviceInfo][1].
 ///
@@ -53,4 +56,118 @@ impl InputDeviceInfo {
     pub fn group_id(&self) -> String {
         self.0.group_id()
     }
+
+    /// Returns capabilities of the represented device.
+    ///
+    /// Returns `undefined` if the browser doesn't support
+    /// [getCapabilities()][1].
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dom-inputdeviceinfo
+    #[must_use]
+    pub fn capabilities(&self) -> Option<InputDeviceCapabilities> {
+        self.0.capabilities().map(InputDeviceCapabilities)
+    }
+}
+
+/// Capabilities of a media input device, describing the constraints values it
+/// supports.
+///
+/// Representation of a [MediaTrackCapabilities][1].
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#dom-mediatrackcapabilities
+#[wasm_bindgen]
+pub struct InputDeviceCapabilities(media::InputDeviceCapabilities);
+
+#[wasm_bindgen]
+impl InputDeviceCapabilities {
+    /// Returns the minimum supported sample rate of the audio in Hz.
+    #[must_use]
+    pub fn min_sample_rate(&self) -> Option<u32> {
+        self.0.sample_rate.map(|r| r.min)
+    }
+
+    /// Returns the maximum supported sample rate of the audio in Hz.
+    #[must_use]
+    pub fn max_sample_rate(&self) -> Option<u32> {
+        self.0.sample_rate.map(|r| r.max)
+    }
+
+    /// Returns the minimum supported number of independent audio channels.
+    #[must_use]
+    pub fn min_channel_count(&self) -> Option<u32> {
+        self.0.channel_count.map(|r| r.min)
+    }
+
+    /// Returns the maximum supported number of independent audio channels.
+    #[must_use]
+    pub fn max_channel_count(&self) -> Option<u32> {
+        self.0.channel_count.map(|r| r.max)
+    }
+
+    /// Returns the minimum supported latency of the audio in milliseconds.
+    #[must_use]
+    pub fn min_latency(&self) -> Option<u32> {
+        self.0.latency.map(|r| r.min)
+    }
+
+    /// Returns the maximum supported latency of the audio in milliseconds.
+    #[must_use]
+    pub fn max_latency(&self) -> Option<u32> {
+        self.0.latency.map(|r| r.max)
+    }
+
+    /// Indicates whether the device supports isolating a voice from a
+    /// background noise.
+    #[must_use]
+    pub fn voice_isolation(&self) -> bool {
+        self.0.voice_isolation
+    }
+
+    /// Returns the minimum supported width of the video in pixels.
+    #[must_use]
+    pub fn min_width(&self) -> Option<u32> {
+        self.0.width.map(|r| r.min)
+    }
+
+    /// Returns the maximum supported width of the video in pixels.
+    #[must_use]
+    pub fn max_width(&self) -> Option<u32> {
+        self.0.width.map(|r| r.max)
+    }
+
+    /// Returns the minimum supported height of the video in pixels.
+    #[must_use]
+    pub fn min_height(&self) -> Option<u32> {
+        self.0.height.map(|r| r.min)
+    }
+
+    /// Returns the maximum supported height of the video in pixels.
+    #[must_use]
+    pub fn max_height(&self) -> Option<u32> {
+        self.0.height.map(|r| r.max)
+    }
+
+    /// Returns the minimum supported frame rate of the video in frames per
+    /// second.
+    #[must_use]
+    pub fn min_frame_rate(&self) -> Option<u32> {
+        self.0.frame_rate.map(|r| r.min)
+    }
+
+    /// Returns the maximum supported frame rate of the video in frames per
+    /// second.
+    #[must_use]
+    pub fn max_frame_rate(&self) -> Option<u32> {
+        self.0.frame_rate.map(|r| r.max)
+    }
+
+    /// Returns [`FacingMode`]s the device may face.
+    #[must_use]
+    pub fn facing_modes(&self) -> js_sys::Array {
+        self.0
+            .facing_mode
+            .iter()
+            .map(|mode| JsValue::from(FacingMode::from(*mode) as u32))
+            .collect()
+    }
 }
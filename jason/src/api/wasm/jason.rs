@@ -6,9 +6,9 @@ use derive_more::From;
 use wasm_bindgen::prelude::*;
 
 use crate::{
-    api::{MediaManagerHandle, RoomHandle},
-    jason,
-    platform::{init_logger, set_panic_hook},
+    api::{wasm::typings, AnalyticsEvent, MediaManagerHandle, RoomHandle},
+    jason, log,
+    platform::{self, set_panic_hook},
 };
 
 /// General JS side library interface.
@@ -26,7 +26,7 @@ impl Jason {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
         set_panic_hook();
-        init_logger();
+        log::init();
 
         Self(jason::Jason::new())
     }
@@ -43,6 +43,16 @@ impl Jason {
         self.0.media_manager().into()
     }
 
+    /// Sets callback, invoked with [`AnalyticsEvent`]s of all the `Room`s of
+    /// this [`Jason`], replacing the previously set one (if any).
+    ///
+    /// May be used to pipe metrics into an application telemetry.
+    pub fn set_analytics_sink(&self, cb: typings::AnalyticsCallback) {
+        let cb = platform::Function::<AnalyticsEvent>::from(cb);
+        self.0
+            .set_analytics_sink(move |event| cb.call1(event.into()));
+    }
+
     /// Closes the provided [`RoomHandle`].
     #[allow(clippy::needless_pass_by_value)]
     pub fn close_room(&self, room_to_delete: RoomHandle) {
@@ -1,57 +1,51 @@
 //! App error exported to JS side.
 
-use std::fmt::{Debug, Display};
+use std::fmt::Display;
 
-use derive_more::{Display, From};
+use derive_more::{Display, From, Into};
 use tracerr::{Trace, Traced};
 use wasm_bindgen::prelude::*;
 
-use crate::{platform, utils::JsCaused};
+use crate::{api::ApiError, platform, utils::JsCaused};
 
 /// Representation of an app error exported to JS side.
 ///
 /// Contains JS side error if it's the cause, and a trace information.
 #[wasm_bindgen]
-#[derive(From, Clone, Debug, Display)]
-#[display(fmt = "{}: {}\n{}", name, message, trace)]
-pub struct JasonError {
-    /// Name of this [`JasonError`].
-    name: &'static str,
-
-    /// Message describing this [`JasonError`].
-    message: String,
-
-    /// [`Trace`] information of this [`JasonError`].
-    trace: Trace,
-
-    /// Optional cause of this [`JasonError`] as a JS side error.
-    source: Option<platform::Error>,
-}
+#[derive(From, Into, Clone, Debug, Display)]
+pub struct JasonError(ApiError);
 
 #[wasm_bindgen]
 impl JasonError {
+    /// Returns a code of this error, identifying the type of the original
+    /// error.
+    #[must_use]
+    pub fn code(&self) -> String {
+        self.0.code().to_owned()
+    }
+
     /// Returns a name of this error.
     #[must_use]
     pub fn name(&self) -> String {
-        self.name.to_owned()
+        self.0.name().to_owned()
     }
 
     /// Returns a message of this error.
     #[must_use]
     pub fn message(&self) -> String {
-        self.message.clone()
+        self.0.message().to_owned()
     }
 
     /// Returns a trace information of this error.
     #[must_use]
     pub fn trace(&self) -> String {
-        self.trace.to_string()
+        self.0.trace().to_string()
     }
 
     /// Returns a JS side error if it's the cause.
     #[must_use]
     pub fn source(&self) -> Option<js_sys::Error> {
-        self.source.clone().and_then(|e| e.sys_cause)
+        self.0.platform_cause().and_then(|e| e.sys_cause.clone())
     }
 }
 
@@ -60,13 +54,8 @@ where
     E::Error: Into<platform::Error>,
 {
     #[inline]
-    fn from((err, trace): (E, Trace)) -> Self {
-        Self {
-            name: err.name(),
-            message: err.to_string(),
-            trace,
-            source: err.js_cause().map(Into::into),
-        }
+    fn from(err: (E, Trace)) -> Self {
+        Self(ApiError::from(err))
     }
 }
 
@@ -76,6 +65,6 @@ where
 {
     #[inline]
     fn from(traced: Traced<E>) -> Self {
-        Self::from(traced.into_parts())
+        Self(ApiError::from(traced))
     }
 }
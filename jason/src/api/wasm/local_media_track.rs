@@ -4,12 +4,18 @@
 
 use derive_more::From;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::future_to_promise;
 
 use crate::{
-    api::{MediaKind, MediaSourceKind},
+    api::{
+        DeviceVideoTrackConstraints, LocalTrackReason, MediaKind,
+        MediaSourceKind,
+    },
     media::track::local,
 };
 
+use super::{typings, Error};
+
 /// Wrapper around a local [MediaStreamTrack][1].
 ///
 /// Backed by a strong reference to the actual track implementing auto stop on
@@ -47,4 +53,47 @@ impl LocalMediaTrack {
     pub fn media_source_kind(&self) -> MediaSourceKind {
         self.0.media_source_kind().into()
     }
+    /// Returns a [`LocalTrackReason`] of this [`LocalMediaTrack`] being
+    /// passed to the `on_local_track` callback.
+    #[must_use]
+    pub fn reason(&self) -> LocalTrackReason {
+        self.0.reason().into()
+    }
+
+    /// Returns a [`LocalMediaTrack`] replaced by this one, if any.
+    #[must_use]
+    pub fn previous(&self) -> Option<LocalMediaTrack> {
+        self.0.previous().map(Self)
+    }
+
+    /// Applies the torch, zoom and focus mode of the provided
+    /// [`DeviceVideoTrackConstraints`] to this [`LocalMediaTrack`] as
+    /// [advanced constraints][1], so the ones unsupported by the device are
+    /// ignored.
+    ///
+    /// Other constraints of the provided [`DeviceVideoTrackConstraints`] are
+    /// not applied.
+    ///
+    /// # Errors
+    ///
+    /// - If this [`LocalMediaTrack`] is not a device video track.
+    /// - If the [applyConstraints()][2] call fails.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-advanced
+    /// [2]: https://tinyurl.com/w3-streams#dom-mediastreamtrack-applyconstraints
+    #[must_use]
+    pub fn apply_advanced(
+        &self,
+        constraints: DeviceVideoTrackConstraints,
+    ) -> typings::VoidPromise {
+        let this = self.0.clone();
+
+        future_to_promise(async move {
+            this.apply_advanced(constraints.into())
+                .await
+                .map_err(Error::from)?;
+            Ok(JsValue::UNDEFINED)
+        })
+        .into()
+    }
 }
@@ -0,0 +1,152 @@
+//! Runtime configuration of [`Jason`] logging.
+//!
+//! [`Jason`]: crate::api::Jason
+
+use ::log::{Level, LevelFilter};
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::{api::wasm::typings, log, platform};
+
+/// Level of [`Jason`] logging.
+///
+/// [`Jason`]: crate::api::Jason
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LogLevel {
+    /// Logging is disabled.
+    Off,
+
+    /// Only errors are logged.
+    Error,
+
+    /// Warnings and more severe records are logged.
+    Warn,
+
+    /// Informational and more severe records are logged.
+    Info,
+
+    /// Debug and more severe records are logged.
+    Debug,
+
+    /// All the records are logged.
+    Trace,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Off => Self::Off,
+            LogLevel::Error => Self::Error,
+            LogLevel::Warn => Self::Warn,
+            LogLevel::Info => Self::Info,
+            LogLevel::Debug => Self::Debug,
+            LogLevel::Trace => Self::Trace,
+        }
+    }
+}
+
+impl From<Level> for LogLevel {
+    fn from(level: Level) -> Self {
+        match level {
+            Level::Error => Self::Error,
+            Level::Warn => Self::Warn,
+            Level::Info => Self::Info,
+            Level::Debug => Self::Debug,
+            Level::Trace => Self::Trace,
+        }
+    }
+}
+
+/// Part of [`Jason`], logging of which can be configured independently.
+///
+/// [`Jason`]: crate::api::Jason
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LogScope {
+    /// Signalling with a media server.
+    Rpc,
+
+    /// `PeerConnection`s management.
+    Peer,
+
+    /// Local and remote media management.
+    Media,
+}
+
+impl From<LogScope> for log::LogScope {
+    fn from(scope: LogScope) -> Self {
+        match scope {
+            LogScope::Rpc => Self::Rpc,
+            LogScope::Peer => Self::Peer,
+            LogScope::Media => Self::Media,
+        }
+    }
+}
+
+/// Single log record of [`Jason`].
+///
+/// This struct is passed to an [`on_log()`] JS side callback.
+///
+/// [`Jason`]: crate::api::Jason
+#[wasm_bindgen]
+#[derive(From)]
+pub struct LogRecord(log::LogRecord);
+
+#[wasm_bindgen]
+impl LogRecord {
+    /// Returns [`LogLevel`] of this [`LogRecord`].
+    #[must_use]
+    pub fn level(&self) -> LogLevel {
+        self.0.level().into()
+    }
+
+    /// Returns name of the module this [`LogRecord`] originates from.
+    #[must_use]
+    pub fn target(&self) -> String {
+        self.0.target().to_owned()
+    }
+
+    /// Returns formatted message of this [`LogRecord`].
+    #[must_use]
+    pub fn message(&self) -> String {
+        self.0.message().to_owned()
+    }
+}
+
+/// Sets the global [`LogLevel`] of [`Jason`] logging.
+///
+/// Defaults to [`LogLevel::Debug`].
+///
+/// [`Jason`]: crate::api::Jason
+#[wasm_bindgen]
+pub fn set_log_level(level: LogLevel) {
+    log::init();
+    log::set_level(level.into());
+}
+
+/// Sets [`LogLevel`] of the provided [`LogScope`], overriding the global one.
+///
+/// `undefined` makes the [`LogScope`] use the global [`LogLevel`] again.
+#[wasm_bindgen]
+pub fn set_log_scope_level(scope: LogScope, level: Option<LogLevel>) {
+    log::init();
+    log::set_scope_level(scope.into(), level.map(Into::into));
+}
+
+/// Registers a callback, invoked with every [`LogRecord`] passing the
+/// configured [`LogLevel`]s.
+///
+/// May be used to collect logs for bug reports.
+#[wasm_bindgen]
+pub fn on_log(cb: typings::LogCallback) {
+    log::init();
+    let cb = platform::Function::<LogRecord>::from(cb);
+    log::add_sink(move |record| cb.call1(LogRecord(record.clone())));
+}
+
+/// Removes all the callbacks registered via [`on_log()`].
+#[wasm_bindgen]
+pub fn clear_log_callbacks() {
+    log::clear_sinks();
+}
@@ -3,16 +3,17 @@
 //! [`MediaManager`]: media::MediaManager
 
 use derive_more::From;
-use js_sys::Promise;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::future_to_promise;
 
 use crate::{
-    api::{InputDeviceInfo, LocalMediaTrack, MediaStreamSettings},
+    api::{
+        InputDeviceInfo, LocalMediaTrack, MediaPermissions, MediaStreamSettings,
+    },
     media,
 };
 
-use super::Error;
+use super::{typings, Error};
 
 /// [`MediaManagerHandle`] is a weak reference to a [`MediaManager`].
 ///
@@ -41,31 +42,49 @@ impl MediaManagerHandle {
     /// Returns a list of [`InputDeviceInfo`] objects representing available
     /// media input and output devices, such as microphones, cameras, and so
     /// forth.
-    pub fn enumerate_devices(&self) -> Promise {
+    ///
+    /// If the provided `signal` is aborted, then the returned [`Promise`] is
+    /// rejected with an `AbortError` [`DOMException`][1].
+    ///
+    /// [`Promise`]: js_sys::Promise
+    /// [1]: https://webidl.spec.whatwg.org/#idl-DOMException
+    #[must_use]
+    pub fn enumerate_devices(
+        &self,
+        signal: Option<web_sys::AbortSignal>,
+    ) -> typings::InputDeviceInfosPromise {
         let this = self.0.clone();
 
-        future_to_promise(async move {
-            this.enumerate_devices()
-                .await
-                .map(|devices| {
-                    devices
-                        .into_iter()
-                        .fold(js_sys::Array::new(), |devices_info, info| {
-                            devices_info.push(&JsValue::from(
-                                InputDeviceInfo::from(info),
-                            ));
-                            devices_info
-                        })
-                        .into()
-                })
-                .map_err(Error::from)
-                .map_err(JsValue::from)
-        })
+        future_to_promise(super::abortable(
+            async move {
+                this.enumerate_devices()
+                    .await
+                    .map(|devices| {
+                        devices
+                            .into_iter()
+                            .fold(js_sys::Array::new(), |devices_info, info| {
+                                devices_info.push(&JsValue::from(
+                                    InputDeviceInfo::from(info),
+                                ));
+                                devices_info
+                            })
+                            .into()
+                    })
+                    .map_err(Error::from)
+                    .map_err(JsValue::from)
+            },
+            signal,
+        ))
+        .into()
     }
 
     /// Returns [`LocalMediaTrack`]s objects, built from the provided
     /// [`MediaStreamSettings`].
-    pub fn init_local_tracks(&self, caps: &MediaStreamSettings) -> Promise {
+    #[must_use]
+    pub fn init_local_tracks(
+        &self,
+        caps: &MediaStreamSettings,
+    ) -> typings::LocalMediaTracksPromise {
         let this = self.0.clone();
         let caps = caps.clone();
 
@@ -86,5 +105,25 @@ impl MediaManagerHandle {
                 .map_err(Error::from)
                 .map_err(JsValue::from)
         })
+        .into()
+    }
+
+    /// Returns [`MediaPermissions`] of using a camera, a microphone and
+    /// capturing a screen, without prompting a user.
+    ///
+    /// Uses [Permissions API][1] where available. Permissions which can't be
+    /// determined in the current browser are reported as
+    /// [`PermissionState::Unknown`].
+    ///
+    /// [`PermissionState::Unknown`]: crate::api::PermissionState::Unknown
+    /// [1]: https://w3.org/TR/permissions
+    #[must_use]
+    pub fn query_permissions(&self) -> typings::MediaPermissionsPromise {
+        let this = self.0.clone();
+
+        future_to_promise(async move {
+            Ok(MediaPermissions::from(this.query_permissions().await).into())
+        })
+        .into()
     }
 }
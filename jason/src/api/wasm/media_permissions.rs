@@ -0,0 +1,41 @@
+//! States of permissions to use media devices.
+
+use derive_more::From;
+use wasm_bindgen::prelude::*;
+
+use crate::media;
+
+use super::PermissionState;
+
+/// States of permissions to use a camera, a microphone and to capture a screen,
+/// queried without prompting a user.
+///
+/// This struct is returned from a [`MediaManagerHandle::query_permissions`]
+/// method.
+///
+/// [`MediaManagerHandle::query_permissions`]:
+/// crate::api::MediaManagerHandle::query_permissions
+#[wasm_bindgen]
+#[derive(From)]
+pub struct MediaPermissions(media::MediaPermissions);
+
+#[wasm_bindgen]
+impl MediaPermissions {
+    /// Returns [`PermissionState`] of using a camera.
+    #[must_use]
+    pub fn camera(&self) -> PermissionState {
+        self.0.camera.into()
+    }
+
+    /// Returns [`PermissionState`] of using a microphone.
+    #[must_use]
+    pub fn microphone(&self) -> PermissionState {
+        self.0.microphone.into()
+    }
+
+    /// Returns [`PermissionState`] of capturing a screen.
+    #[must_use]
+    pub fn screen(&self) -> PermissionState {
+        self.0.screen.into()
+    }
+}
@@ -7,7 +7,13 @@
 use derive_more::{From, Into};
 use wasm_bindgen::prelude::*;
 
-use crate::{api::FacingMode, media};
+use crate::{
+    api::{
+        ContentHint, DegradationPreference, FacingMode, FocusMode,
+        MuteStrategy,
+    },
+    media,
+};
 
 /// [MediaStreamConstraints][1] wrapper.
 ///
@@ -65,6 +71,67 @@ impl AudioTrackConstraints {
     pub fn device_id(&mut self, device_id: String) {
         self.0.device_id(device_id);
     }
+
+    /// Sets an exact [`sampleRate`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-sampleRate
+    pub fn exact_sample_rate(&mut self, sample_rate: u32) {
+        self.0.exact_sample_rate(sample_rate);
+    }
+
+    /// Sets an ideal [`sampleRate`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-sampleRate
+    pub fn ideal_sample_rate(&mut self, sample_rate: u32) {
+        self.0.ideal_sample_rate(sample_rate);
+    }
+
+    /// Sets a range of a [`sampleRate`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-sampleRate
+    pub fn sample_rate_in_range(&mut self, min: u32, max: u32) {
+        self.0.sample_rate_in_range(min, max);
+    }
+
+    /// Sets an exact [`channelCount`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-channelCount
+    pub fn exact_channel_count(&mut self, channel_count: u32) {
+        self.0.exact_channel_count(channel_count);
+    }
+
+    /// Sets an ideal [`channelCount`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-channelCount
+    pub fn ideal_channel_count(&mut self, channel_count: u32) {
+        self.0.ideal_channel_count(channel_count);
+    }
+
+    /// Sets an exact [`latency`][1] constraint in milliseconds.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-latency
+    pub fn exact_latency(&mut self, latency: u32) {
+        self.0.exact_latency(latency);
+    }
+
+    /// Sets an ideal [`latency`][1] constraint in milliseconds.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-latency
+    pub fn ideal_latency(&mut self, latency: u32) {
+        self.0.ideal_latency(latency);
+    }
+
+    /// Sets a [`voiceIsolation`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-voiceisolation
+    pub fn voice_isolation(&mut self, enabled: bool) {
+        self.0.voice_isolation(enabled);
+    }
+
+    /// Sets a [`MuteStrategy`] of the sent audio track.
+    pub fn mute_strategy(&mut self, strategy: MuteStrategy) {
+        self.0.mute_strategy(strategy.into());
+    }
 }
 
 /// Constraints applicable to video tracks that are sourced from some media
@@ -147,6 +214,65 @@ impl DeviceVideoTrackConstraints {
     pub fn width_in_range(&mut self, min: u32, max: u32) {
         self.0.width_in_range(min, max);
     }
+
+    /// Sets a [`contentHint`][1] of the sent video track.
+    ///
+    /// [1]: https://w3.org/TR/mst-content-hint#dom-mediastreamtrack-contenthint
+    pub fn content_hint(&mut self, hint: ContentHint) {
+        self.0.content_hint(hint.into());
+    }
+
+    /// Sets a [`degradationPreference`][1] of the video track sender.
+    ///
+    /// [1]: https://w3.org/TR/webrtc/#dom-rtcdegradationpreference
+    pub fn degradation_preference(
+        &mut self,
+        preference: DegradationPreference,
+    ) {
+        self.0.degradation_preference(preference.into());
+    }
+
+    /// Sets a [`torch`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-torch
+    pub fn torch(&mut self, enabled: bool) {
+        self.0.torch(enabled);
+    }
+
+    /// Sets an exact [`zoom`][1] constraint in percents.
+    ///
+    /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-zoom
+    pub fn exact_zoom(&mut self, zoom: u32) {
+        self.0.exact_zoom(zoom);
+    }
+
+    /// Sets an ideal [`zoom`][1] constraint in percents.
+    ///
+    /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-zoom
+    pub fn ideal_zoom(&mut self, zoom: u32) {
+        self.0.ideal_zoom(zoom);
+    }
+
+    /// Sets a range of a [`zoom`][1] constraint in percents.
+    ///
+    /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-zoom
+    pub fn zoom_in_range(&mut self, min: u32, max: u32) {
+        self.0.zoom_in_range(min, max);
+    }
+
+    /// Sets an exact [`focusMode`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-focusmode
+    pub fn exact_focus_mode(&mut self, focus_mode: FocusMode) {
+        self.0.exact_focus_mode(focus_mode.into());
+    }
+
+    /// Sets an ideal [`focusMode`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-focusmode
+    pub fn ideal_focus_mode(&mut self, focus_mode: FocusMode) {
+        self.0.ideal_focus_mode(focus_mode.into());
+    }
 }
 
 /// Constraints applicable to video tracks sourced from a screen capturing.
@@ -163,4 +289,21 @@ impl DisplayVideoTrackConstraints {
     pub fn new() -> Self {
         media::DisplayVideoTrackConstraints::new().into()
     }
+
+    /// Sets a [`contentHint`][1] of the sent video track.
+    ///
+    /// [1]: https://w3.org/TR/mst-content-hint#dom-mediastreamtrack-contenthint
+    pub fn content_hint(&mut self, hint: ContentHint) {
+        self.0.content_hint(hint.into());
+    }
+
+    /// Sets a [`degradationPreference`][1] of the video track sender.
+    ///
+    /// [1]: https://w3.org/TR/webrtc/#dom-rtcdegradationpreference
+    pub fn degradation_preference(
+        &mut self,
+        preference: DegradationPreference,
+    ) {
+        self.0.degradation_preference(preference.into());
+    }
 }
@@ -3,32 +3,44 @@
 //!
 //! [`Jason`]: crate::api::Jason
 
+pub mod analytics_event;
+pub mod app_data;
 pub mod connection_handle;
 pub mod constraints_update_exception;
 pub mod input_device_info;
 pub mod jason;
 pub mod jason_error;
 pub mod local_media_track;
+pub mod logging;
 pub mod media_manager_handle;
+pub mod media_permissions;
 pub mod media_stream_settings;
 pub mod reconnect_handle;
 pub mod remote_media_track;
 pub mod room_close_reason;
 pub mod room_handle;
+pub mod typings;
+
+use std::future::Future;
 
 use derive_more::Display;
-use wasm_bindgen::prelude::*;
+use futures::future;
+use wasm_bindgen::{prelude::*, JsCast as _};
 
-use crate::media;
+use crate::{media, room};
 
 pub use self::{
+    analytics_event::AnalyticsEvent,
+    app_data::AppData,
     connection_handle::ConnectionHandle,
     constraints_update_exception::ConstraintsUpdateException,
-    input_device_info::InputDeviceInfo,
+    input_device_info::{InputDeviceCapabilities, InputDeviceInfo},
     jason::Jason,
     jason_error::JasonError as Error,
     local_media_track::LocalMediaTrack,
+    logging::{LogLevel, LogRecord, LogScope},
     media_manager_handle::MediaManagerHandle,
+    media_permissions::MediaPermissions,
     media_stream_settings::{
         AudioTrackConstraints, DeviceVideoTrackConstraints,
         DisplayVideoTrackConstraints, MediaStreamSettings,
@@ -63,6 +75,26 @@ pub enum MediaSourceKind {
     Display,
 }
 
+/// Reason of a [`LocalMediaTrack`] being passed to the `on_local_track`
+/// callback of a [`RoomHandle`].
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum LocalTrackReason {
+    /// Track is published for the first time.
+    Initial,
+
+    /// Track replaces the previous one, captured from another device.
+    DeviceSwitch,
+
+    /// Track replaces the previous one, captured from the same device with
+    /// other constraints.
+    ConstraintsChange,
+
+    /// Track replaces the previous one, which was ended by the UA or the
+    /// hardware.
+    Ended,
+}
+
 /// Describes directions that a camera can face, as seen from a user's
 /// perspective. Representation of a [VideoFacingModeEnum][1].
 ///
@@ -83,6 +115,253 @@ pub enum FacingMode {
     Right,
 }
 
+/// Describes focus modes of a camera. Representation of a [MeteringMode][1].
+///
+/// [1]: https://w3.org/TR/image-capture#dom-meteringmode
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum FocusMode {
+    /// No focus is applied.
+    None,
+
+    /// Focus is fixed at the distance set manually.
+    Manual,
+
+    /// Focus is adjusted once and then fixed.
+    SingleShot,
+
+    /// Focus is adjusted continuously.
+    Continuous,
+}
+
+/// Strategy of muting a sent audio track.
+///
+/// Regardless of the chosen [`MuteStrategy`], the remote side is notified
+/// about muting in the same way, so the choice only affects what happens to
+/// the captured media locally.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MuteStrategy {
+    /// Disable the sent track, so silence is sent while the microphone keeps
+    /// capturing.
+    DisableTrack,
+
+    /// Replace the sent track with nothing, while the microphone keeps
+    /// capturing, allowing to unmute without re-acquiring media.
+    ReplaceWithSilence,
+
+    /// Stop capturing entirely, releasing the microphone (and turning off its
+    /// privacy indicator), and re-acquire it on unmuting.
+    ///
+    /// The microphone is released only once all the [`LocalMediaTrack`]s of
+    /// it are freed as well.
+    StopCapture,
+}
+
+/// Policy of enabling the media received from remote `Member`s in a `Room`.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecvMediaPolicy {
+    /// All the received media is enabled, unless it's disabled explicitly.
+    Auto,
+
+    /// No received media is enabled, unless it's enabled explicitly for its
+    /// sending `Member`.
+    Manual,
+}
+
+/// State of a permission to use some media. Representation of a
+/// [PermissionState][1].
+///
+/// [1]: https://w3.org/TR/permissions#dom-permissionstate
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PermissionState {
+    /// Permission is granted, so media can be acquired without prompting a
+    /// user.
+    Granted,
+
+    /// Permission is denied, so media acquisition will fail without prompting
+    /// a user.
+    Denied,
+
+    /// User will be prompted for a permission on media acquisition.
+    Prompt,
+
+    /// Permission state can't be determined in the current browser without
+    /// prompting a user.
+    Unknown,
+}
+
+/// Hint of a video track contents, letting a browser choose how to encode it.
+/// Representation of a [video content hint][1].
+///
+/// [1]: https://w3.org/TR/mst-content-hint#video-content-hints
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContentHint {
+    /// Video where motion is important (webcam or a video playback).
+    Motion,
+
+    /// Video where details are important (presentation slides or web pages).
+    Detail,
+
+    /// Video where details are extra important, containing text content.
+    Text,
+}
+
+/// Preference of what a browser should sacrifice when sending a video track
+/// under constrained bandwidth. Representation of a
+/// [RTCDegradationPreference][1].
+///
+/// [1]: https://w3.org/TR/webrtc/#dom-rtcdegradationpreference
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DegradationPreference {
+    /// Degrade resolution in order to maintain framerate.
+    MaintainFramerate,
+
+    /// Degrade framerate in order to maintain resolution.
+    MaintainResolution,
+
+    /// Degrade both framerate and resolution in a balanced way.
+    Balanced,
+}
+
+/// Kind of an [`AnalyticsEvent`].
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum AnalyticsEventKind {
+    /// Joining a `Room` has been started.
+    JoinStarted,
+
+    /// `Room` has been joined successfully.
+    JoinSucceeded,
+
+    /// Connection with a media server has been restored after being lost.
+    Reconnected,
+
+    /// SDP negotiation of a `PeerConnection` has been finished.
+    NegotiationFinished,
+
+    /// Local media has been acquired successfully.
+    LocalMediaAcquired,
+
+    /// Local media has failed to be acquired.
+    LocalMediaFailed,
+}
+
+impl From<crate::jason::AnalyticsEventKind> for AnalyticsEventKind {
+    fn from(that: crate::jason::AnalyticsEventKind) -> Self {
+        use crate::jason::AnalyticsEventKind as K;
+
+        match that {
+            K::JoinStarted => Self::JoinStarted,
+            K::JoinSucceeded => Self::JoinSucceeded,
+            K::Reconnected => Self::Reconnected,
+            K::NegotiationFinished => Self::NegotiationFinished,
+            K::LocalMediaAcquired => Self::LocalMediaAcquired,
+            K::LocalMediaFailed => Self::LocalMediaFailed,
+        }
+    }
+}
+
+/// Kind of a [`RoomCloseReason`], allowing to distinguish close reasons without
+/// comparing their string representations.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum RoomCloseKind {
+    /// Client session was finished on a server side.
+    Finished,
+
+    /// Old connection was closed due to a client reconnection.
+    Reconnected,
+
+    /// Connection has been inactive for a while and thus considered idle by
+    /// a server.
+    Idle,
+
+    /// Establishing of connection with a server was rejected on server side.
+    Rejected,
+
+    /// Server internal error has occurred while connecting.
+    InternalError,
+
+    /// Client was evicted on the server side.
+    Evicted,
+
+    /// Media server is draining, so doesn't accept new clients.
+    Draining,
+
+    /// Media server admits too many clients at the moment, so doesn't accept
+    /// new ones.
+    Overloaded,
+
+    /// Media server has been drained and is shutting down.
+    Drained,
+
+    /// Client has sent a message exceeding the maximum size allowed by media
+    /// server.
+    MessageTooLarge,
+
+    /// `Room` was dropped without any close reason.
+    RoomUnexpectedlyDropped,
+
+    /// `Room` was normally closed by client.
+    RoomClosed,
+
+    /// RPC client was unexpectedly dropped.
+    RpcClientUnexpectedlyDropped,
+
+    /// RPC transport was unexpectedly dropped.
+    RpcTransportUnexpectedlyDropped,
+
+    /// RPC session was unexpectedly dropped.
+    SessionUnexpectedlyDropped,
+}
+
+impl From<room::RoomCloseKind> for RoomCloseKind {
+    fn from(that: room::RoomCloseKind) -> Self {
+        use room::RoomCloseKind as K;
+
+        match that {
+            K::Finished => Self::Finished,
+            K::Reconnected => Self::Reconnected,
+            K::Idle => Self::Idle,
+            K::Rejected => Self::Rejected,
+            K::InternalError => Self::InternalError,
+            K::Evicted => Self::Evicted,
+            K::Draining => Self::Draining,
+            K::Overloaded => Self::Overloaded,
+            K::Drained => Self::Drained,
+            K::MessageTooLarge => Self::MessageTooLarge,
+            K::RoomUnexpectedlyDropped => Self::RoomUnexpectedlyDropped,
+            K::RoomClosed => Self::RoomClosed,
+            K::RpcClientUnexpectedlyDropped => {
+                Self::RpcClientUnexpectedlyDropped
+            }
+            K::RpcTransportUnexpectedlyDropped => {
+                Self::RpcTransportUnexpectedlyDropped
+            }
+            K::SessionUnexpectedlyDropped => Self::SessionUnexpectedlyDropped,
+        }
+    }
+}
+
+impl From<media::track::local::LocalTrackReason> for LocalTrackReason {
+    #[inline]
+    fn from(that: media::track::local::LocalTrackReason) -> Self {
+        use media::track::local::LocalTrackReason as R;
+
+        match that {
+            R::Initial => Self::Initial,
+            R::DeviceSwitch => Self::DeviceSwitch,
+            R::ConstraintsChange => Self::ConstraintsChange,
+            R::Ended => Self::Ended,
+        }
+    }
+}
+
 impl From<media::MediaKind> for MediaKind {
     #[inline]
     fn from(that: media::MediaKind) -> Self {
@@ -146,3 +425,110 @@ impl From<FacingMode> for media::FacingMode {
         }
     }
 }
+
+impl From<FocusMode> for media::FocusMode {
+    #[inline]
+    fn from(val: FocusMode) -> Self {
+        match val {
+            FocusMode::None => Self::None,
+            FocusMode::Manual => Self::Manual,
+            FocusMode::SingleShot => Self::SingleShot,
+            FocusMode::Continuous => Self::Continuous,
+        }
+    }
+}
+
+impl From<MuteStrategy> for media::MuteStrategy {
+    #[inline]
+    fn from(strategy: MuteStrategy) -> Self {
+        match strategy {
+            MuteStrategy::DisableTrack => Self::DisableTrack,
+            MuteStrategy::ReplaceWithSilence => Self::ReplaceWithSilence,
+            MuteStrategy::StopCapture => Self::StopCapture,
+        }
+    }
+}
+
+impl From<RecvMediaPolicy> for media::RecvMediaPolicy {
+    #[inline]
+    fn from(policy: RecvMediaPolicy) -> Self {
+        match policy {
+            RecvMediaPolicy::Auto => Self::Auto,
+            RecvMediaPolicy::Manual => Self::Manual,
+        }
+    }
+}
+
+impl From<media::PermissionState> for PermissionState {
+    #[inline]
+    fn from(that: media::PermissionState) -> Self {
+        match that {
+            media::PermissionState::Granted => Self::Granted,
+            media::PermissionState::Denied => Self::Denied,
+            media::PermissionState::Prompt => Self::Prompt,
+            media::PermissionState::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl From<ContentHint> for media::ContentHint {
+    #[inline]
+    fn from(hint: ContentHint) -> Self {
+        match hint {
+            ContentHint::Motion => Self::Motion,
+            ContentHint::Detail => Self::Detail,
+            ContentHint::Text => Self::Text,
+        }
+    }
+}
+
+impl From<DegradationPreference> for media::DegradationPreference {
+    #[inline]
+    fn from(preference: DegradationPreference) -> Self {
+        use DegradationPreference as P;
+
+        match preference {
+            P::MaintainFramerate => Self::MaintainFramerate,
+            P::MaintainResolution => Self::MaintainResolution,
+            P::Balanced => Self::Balanced,
+        }
+    }
+}
+
+/// Makes the provided [`Future`] abortable via the provided [`AbortSignal`][1].
+///
+/// Once aborted, the returned [`Future`] resolves with an `AbortError`
+/// [`DOMException`][2].
+///
+/// [1]: https://dom.spec.whatwg.org/#interface-AbortSignal
+/// [2]: https://webidl.spec.whatwg.org/#idl-DOMException
+fn abortable<F, T>(
+    fut: F,
+    signal: Option<web_sys::AbortSignal>,
+) -> impl Future<Output = Result<T, JsValue>>
+where
+    F: Future<Output = Result<T, JsValue>>,
+{
+    let (fut, abort) = future::abortable(fut);
+    if let Some(signal) = signal {
+        if signal.aborted() {
+            abort.abort();
+        } else {
+            let on_abort = Closure::once_into_js(move || abort.abort());
+            drop(signal.add_event_listener_with_callback(
+                "abort",
+                on_abort.unchecked_ref(),
+            ));
+        }
+    }
+
+    async move {
+        fut.await.unwrap_or_else(|_| {
+            Err(web_sys::DomException::new_with_message_and_name(
+                "The operation was aborted",
+                "AbortError",
+            )
+            .map_or_else(|e| e, Into::into))
+        })
+    }
+}
@@ -1,13 +1,12 @@
 //! JS side handle for reconnections with a media server.
 
 use derive_more::From;
-use js_sys::Promise;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::future_to_promise;
 
 use crate::rpc;
 
-use super::Error;
+use super::{typings, Error};
 
 /// Handle that JS side can reconnect to a media server with when a connection
 /// is lost.
@@ -32,7 +31,8 @@ impl ReconnectHandle {
     /// reconnection attempt result and use it.
     ///
     /// [`RpcSession`]: rpc::RpcSession
-    pub fn reconnect_with_delay(&self, delay_ms: u32) -> Promise {
+    #[must_use]
+    pub fn reconnect_with_delay(&self, delay_ms: u32) -> typings::VoidPromise {
         let this = self.0.clone();
         future_to_promise(async move {
             this.reconnect_with_delay(delay_ms)
@@ -40,6 +40,7 @@ impl ReconnectHandle {
                 .map_err(Error::from)?;
             Ok(JsValue::UNDEFINED)
         })
+        .into()
     }
 
     /// Tries to reconnect a [`RpcSession`] in a loop with a growing backoff
@@ -59,20 +60,28 @@ impl ReconnectHandle {
     /// `0.0`. This might cause a busy loop, so it's not recommended.
     ///
     /// Max elapsed time can be limited with an optional `max_elapsed_time_ms`
-    /// argument.
+    /// argument, and the number of attempts with an optional `max_attempts`
+    /// argument. Once any of them is exceeded, the `on_give_up` callback is
+    /// invoked.
+    ///
+    /// Each delay is randomized by the given optional `jitter` factor (clamped
+    /// into `[0.0, 1.0]` range). No randomization is applied by default.
     ///
     /// If [`RpcSession`] is already reconnecting then new reconnection attempt
     /// won't be performed. Instead, it will wait for the first reconnection
     /// attempt result and use it here.
     ///
     /// [`RpcSession`]: rpc::RpcSession
+    #[must_use]
     pub fn reconnect_with_backoff(
         &self,
         starting_delay_ms: u32,
         multiplier: f32,
         max_delay: u32,
         max_elapsed_time_ms: Option<u32>,
-    ) -> Promise {
+        jitter: Option<f32>,
+        max_attempts: Option<u32>,
+    ) -> typings::VoidPromise {
         let this = self.0.clone();
         future_to_promise(async move {
             this.reconnect_with_backoff(
@@ -80,10 +89,24 @@ impl ReconnectHandle {
                 multiplier.into(),
                 max_delay,
                 max_elapsed_time_ms,
+                jitter.map_or(0.0, f64::from),
+                max_attempts,
             )
             .await
             .map_err(Error::from)?;
             Ok(JsValue::UNDEFINED)
         })
+        .into()
+    }
+
+    /// Sets callback, invoked when reconnection with a backoff gives up,
+    /// because its max attempts count or max elapsed time is exceeded.
+    pub fn on_give_up(&self, cb: typings::VoidCallback) {
+        self.0.on_give_up(cb.into());
+    }
+
+    /// Cancels all the ongoing reconnections with a backoff, making them fail.
+    pub fn cancel_backoff(&self) {
+        self.0.cancel_backoff();
     }
 }
@@ -10,6 +10,8 @@ use crate::{
     media::track::remote,
 };
 
+use super::typings;
+
 /// Wrapper around a received remote [MediaStreamTrack][1].
 ///
 /// [1]: https://w3.org/TR/mediacapture-streams/#dom-mediastreamtrack
@@ -40,27 +42,27 @@ impl RemoteMediaTrack {
     }
 
     /// Sets callback, invoked when this [`RemoteMediaTrack`] is enabled.
-    pub fn on_enabled(&self, cb: js_sys::Function) {
+    pub fn on_enabled(&self, cb: typings::VoidCallback) {
         self.0.on_enabled(cb.into())
     }
 
     /// Sets callback, invoked when this [`RemoteMediaTrack`] is disabled.
-    pub fn on_disabled(&self, cb: js_sys::Function) {
+    pub fn on_disabled(&self, cb: typings::VoidCallback) {
         self.0.on_disabled(cb.into())
     }
 
     /// Sets callback to invoke when this [`RemoteMediaTrack`] is muted.
-    pub fn on_muted(&self, cb: js_sys::Function) {
+    pub fn on_muted(&self, cb: typings::VoidCallback) {
         self.0.on_muted(cb.into());
     }
 
     /// Sets callback to invoke when this [`RemoteMediaTrack`] is unmuted.
-    pub fn on_unmuted(&self, cb: js_sys::Function) {
+    pub fn on_unmuted(&self, cb: typings::VoidCallback) {
         self.0.on_unmuted(cb.into());
     }
 
     /// Sets callback to invoke when this [`RemoteMediaTrack`] is stopped.
-    pub fn on_stopped(&self, cb: js_sys::Function) {
+    pub fn on_stopped(&self, cb: typings::VoidCallback) {
         self.0.on_stopped(cb.into());
     }
 
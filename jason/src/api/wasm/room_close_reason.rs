@@ -5,7 +5,7 @@
 use derive_more::From;
 use wasm_bindgen::prelude::*;
 
-use crate::room;
+use crate::{api::RoomCloseKind, room};
 
 /// Reason of why a [`Room`] is closed.
 ///
@@ -27,6 +27,15 @@ impl RoomCloseReason {
         self.0.reason()
     }
 
+    /// Returns the [`RoomCloseKind`] of the [`Room`]'s close reason, which
+    /// [`RoomCloseReason::reason()`] is a string representation of.
+    ///
+    /// [`Room`]: room::Room
+    #[must_use]
+    pub fn kind(&self) -> RoomCloseKind {
+        self.0.kind().into()
+    }
+
     /// Indicates whether the [`Room`] was closed by server.
     ///
     /// [`Room`]: room::Room
@@ -42,4 +51,23 @@ impl RoomCloseReason {
     pub fn is_err(&self) -> bool {
         self.0.is_err()
     }
+
+    /// Indicates whether the [`Room`] may be rejoined, since it was closed by
+    /// server for a temporary reason.
+    ///
+    /// [`Room`]: room::Room
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        self.0.is_retryable()
+    }
+
+    /// Returns the delay (in milliseconds) after which the [`Room`] should be
+    /// rejoined, if server has hinted it.
+    ///
+    /// [`Room`]: room::Room
+    #[allow(clippy::cast_possible_truncation)]
+    #[must_use]
+    pub fn retry_after(&self) -> Option<u32> {
+        self.0.retry_after().map(|d| d.as_millis() as u32)
+    }
 }
@@ -3,16 +3,18 @@
 //! [`Room`]: room::Room
 
 use derive_more::{From, Into};
-use js_sys::Promise;
 use wasm_bindgen::{prelude::*, JsValue};
 use wasm_bindgen_futures::future_to_promise;
 
 use crate::{
-    api::{ConstraintsUpdateException, MediaSourceKind, MediaStreamSettings},
+    api::{
+        ConstraintsUpdateException, MediaSourceKind, MediaStreamSettings,
+        RecvMediaPolicy,
+    },
     room,
 };
 
-use super::Error;
+use super::{typings, Error};
 
 /// JS side handle to a [`Room`] where all the media happens.
 ///
@@ -42,15 +44,77 @@ impl RoomHandle {
     /// - When `on_failed_local_media` callback is not set.
     /// - When `on_connection_loss` callback is not set.
     /// - When unable to connect to a media server.
+    /// - With an `AbortError` [`DOMException`][1] when the provided `signal` is
+    ///   aborted. Connection establishment is cancelled in such case, so
+    ///   joining can be retried.
     ///
     /// [`Room`]: room::Room
-    pub fn join(&self, token: String) -> Promise {
+    /// [1]: https://webidl.spec.whatwg.org/#idl-DOMException
+    #[must_use]
+    pub fn join(
+        &self,
+        token: String,
+        signal: Option<web_sys::AbortSignal>,
+    ) -> typings::VoidPromise {
+        let this = self.0.clone();
+
+        future_to_promise(super::abortable(
+            async move {
+                this.join(token).await.map_err(Error::from)?;
+                Ok(JsValue::UNDEFINED)
+            },
+            signal,
+        ))
+        .into()
+    }
+
+    /// Connects to a media server and joins a [`Room`] with the provided
+    /// authorization `token`, without acquiring any local media.
+    ///
+    /// Remote media is received right away, while local media is acquired
+    /// and published only on a `publish` call.
+    ///
+    /// Effectively returns `Result<(), JasonError>`.
+    ///
+    /// # Errors
+    ///
+    /// Same as `join`.
+    ///
+    /// [`Room`]: room::Room
+    #[must_use]
+    pub fn join_without_media(
+        &self,
+        token: String,
+        signal: Option<web_sys::AbortSignal>,
+    ) -> typings::VoidPromise {
+        let this = self.0.clone();
+
+        future_to_promise(super::abortable(
+            async move {
+                this.join_without_media(token).await.map_err(Error::from)?;
+                Ok(JsValue::UNDEFINED)
+            },
+            signal,
+        ))
+        .into()
+    }
+
+    /// Acquires local media and starts publishing it in this [`Room`].
+    ///
+    /// # Errors
+    ///
+    /// Same as `enable_audio` and `enable_video`.
+    ///
+    /// [`Room`]: room::Room
+    #[must_use]
+    pub fn publish(&self) -> typings::VoidPromise {
         let this = self.0.clone();
 
         future_to_promise(async move {
-            this.join(token).await.map_err(Error::from)?;
+            this.publish().await.map_err(Error::from)?;
             Ok(JsValue::UNDEFINED)
         })
+        .into()
     }
 
     /// Sets callback, invoked when a new [`Connection`] with some remote
@@ -59,7 +123,7 @@ impl RoomHandle {
     /// [`Connection`]: crate::connection::Connection
     pub fn on_new_connection(
         &self,
-        cb: js_sys::Function,
+        cb: typings::NewConnectionCallback,
     ) -> Result<(), JsValue> {
         self.0
             .on_new_connection(cb.into())
@@ -72,7 +136,10 @@ impl RoomHandle {
     ///
     /// [`Room`]: room::Room
     /// [`RoomCloseReason`]: room::RoomCloseReason
-    pub fn on_close(&self, cb: js_sys::Function) -> Result<(), JsValue> {
+    pub fn on_close(
+        &self,
+        cb: typings::RoomCloseCallback,
+    ) -> Result<(), JsValue> {
         self.0
             .on_close(cb.into())
             .map_err(Error::from)
@@ -89,7 +156,10 @@ impl RoomHandle {
     ///
     /// [`Room`]: room::Room
     /// [`LocalMediaTrack`]: crate::api::LocalMediaTrack
-    pub fn on_local_track(&self, cb: js_sys::Function) -> Result<(), JsValue> {
+    pub fn on_local_track(
+        &self,
+        cb: typings::LocalTrackCallback,
+    ) -> Result<(), JsValue> {
         self.0
             .on_local_track(cb.into())
             .map_err(Error::from)
@@ -100,7 +170,7 @@ impl RoomHandle {
     /// acquisition failures.
     pub fn on_failed_local_media(
         &self,
-        cb: js_sys::Function,
+        cb: typings::FailedLocalMediaCallback,
     ) -> Result<(), JsValue> {
         self.0
             .on_failed_local_media(cb.into())
@@ -112,7 +182,7 @@ impl RoomHandle {
     /// server is lost.
     pub fn on_connection_loss(
         &self,
-        cb: js_sys::Function,
+        cb: typings::ConnectionLossCallback,
     ) -> Result<(), JsValue> {
         self.0
             .on_connection_loss(cb.into())
@@ -120,6 +190,143 @@ impl RoomHandle {
             .map_err(JsValue::from)
     }
 
+    /// Sets `on_app_data` callback, invoked when some remote `Member` sends
+    /// application data, providing an [`AppData`].
+    ///
+    /// [`AppData`]: crate::api::AppData
+    pub fn on_app_data(
+        &self,
+        cb: typings::AppDataCallback,
+    ) -> Result<(), JsValue> {
+        self.0
+            .on_app_data(cb.into())
+            .map_err(Error::from)
+            .map_err(JsValue::from)
+    }
+
+    /// Sends arbitrary JSON-serializable `data` to the remote `Member` with
+    /// the provided ID, or to all remote `Member`s of this [`Room`] if `to` is
+    /// not provided.
+    ///
+    /// Data is relayed by a media server and may be dropped by it if sent too
+    /// often.
+    ///
+    /// [`Room`]: room::Room
+    pub fn send_app_data(
+        &self,
+        to: Option<String>,
+        data: &JsValue,
+    ) -> Result<(), JsValue> {
+        let data = data.into_serde().map_err(|e| {
+            JsValue::from(js_sys::TypeError::new(&e.to_string()))
+        })?;
+        self.0
+            .send_app_data(to, data)
+            .map_err(Error::from)
+            .map_err(JsValue::from)
+    }
+
+    /// Renews the credential of this [`Room`]'s `Member` with the provided
+    /// `token`, without rejoining the [`Room`].
+    ///
+    /// Media server closes this [`Room`] if the provided credential is
+    /// invalid.
+    ///
+    /// [`Room`]: room::Room
+    pub fn update_token(&self, token: String) -> Result<(), JsValue> {
+        self.0
+            .update_token(token)
+            .map_err(Error::from)
+            .map_err(JsValue::from)
+    }
+
+    /// Returns a JSON document describing the current state of all the
+    /// `PeerConnection`s of this [`Room`] (their SDPs, selected ICE candidate
+    /// pairs and latest stats) along with a history of this [`Room`] state
+    /// synchronization with a media server.
+    ///
+    /// Intended to be attached to bug reports, similarly to
+    /// `chrome://webrtc-internals` dumps.
+    ///
+    /// [`Room`]: room::Room
+    #[must_use]
+    pub fn export_debug_dump(&self) -> typings::StringPromise {
+        let this = self.0.clone();
+
+        future_to_promise(async move {
+            let dump = this.export_debug_dump().await.map_err(Error::from)?;
+            Ok(JsValue::from(dump))
+        })
+        .into()
+    }
+
+    /// Sets the interval (in milliseconds) of collecting stats of all the
+    /// `PeerConnection`s of this [`Room`] and sending them to a media server.
+    ///
+    /// `0` stops periodic stats collecting, so it's done only via
+    /// [`RoomHandle::poll_stats_now()`]. Stats are not collected periodically
+    /// while the page is hidden.
+    ///
+    /// [`Room`]: room::Room
+    pub fn set_stats_interval(&self, interval_ms: u32) -> Result<(), JsValue> {
+        self.0
+            .set_stats_interval(interval_ms)
+            .map_err(Error::from)
+            .map_err(JsValue::from)
+    }
+
+    /// Collects stats of all the `PeerConnection`s of this [`Room`] right away
+    /// and sends them to a media server.
+    ///
+    /// [`Room`]: room::Room
+    #[must_use]
+    pub fn poll_stats_now(&self) -> typings::VoidPromise {
+        let this = self.0.clone();
+
+        future_to_promise(async move {
+            this.poll_stats_now().await.map_err(Error::from)?;
+            Ok(JsValue::UNDEFINED)
+        })
+        .into()
+    }
+
+    /// Re-acquires the local media, which has failed to be acquired previously
+    /// (and so has been reported to `on_failed_local_media` callback), and
+    /// re-enables the affected outbound media in this [`Room`].
+    ///
+    /// Does nothing if there is no failed local media.
+    ///
+    /// # Errors
+    ///
+    /// With `name = 'MediaManagerError'` if media acquisition request to User
+    /// Agent failed again.
+    ///
+    /// [`Room`]: room::Room
+    #[must_use]
+    pub fn retry_local_media(&self) -> typings::VoidPromise {
+        let this = self.0.clone();
+
+        future_to_promise(async move {
+            this.retry_local_media().await.map_err(Error::from)?;
+            Ok(JsValue::UNDEFINED)
+        })
+        .into()
+    }
+
+    /// Sets the interval (in milliseconds) of automatic
+    /// [`RoomHandle::retry_local_media()`] attempts.
+    ///
+    /// `0` stops automatic retrying (default).
+    pub fn set_local_media_retry_interval(
+        &self,
+        interval_ms: u32,
+    ) -> Result<(), JsValue> {
+        self.0
+            .set_local_media_retry_interval(interval_ms)
+            .map_err(Error::from)
+            .map_err(JsValue::from)
+    }
+
     /// Updates this [`Room`]s [`MediaStreamSettings`]. This affects all
     /// [`PeerConnection`]s in this [`Room`]. If [`MediaStreamSettings`] is
     /// configured for some [`Room`], then this [`Room`] can only send media
@@ -143,29 +350,41 @@ impl RoomHandle {
     /// If recovering from fail state isn't possible then affected media types
     /// will be disabled.
     ///
+    /// If the provided `signal` is aborted, then the returned [`Promise`] is
+    /// rejected with an `AbortError` [`DOMException`][2] and the previous
+    /// [`MediaStreamSettings`] are restored.
+    ///
     /// [`Room`]: room::Room
     /// [`PeerConnection`]: crate::peer::PeerConnection
     /// [`LocalMediaTrack`]: crate::api::LocalMediaTrack
+    /// [`Promise`]: js_sys::Promise
     /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    /// [2]: https://webidl.spec.whatwg.org/#idl-DOMException
+    #[must_use]
     pub fn set_local_media_settings(
         &self,
         settings: &MediaStreamSettings,
         stop_first: bool,
         rollback_on_fail: bool,
-    ) -> Promise {
+        signal: Option<web_sys::AbortSignal>,
+    ) -> typings::VoidPromise {
         let this = self.0.clone();
         let settings = settings.clone();
 
-        future_to_promise(async move {
-            this.set_local_media_settings(
-                settings.into(),
-                stop_first,
-                rollback_on_fail,
-            )
-            .await
-            .map_err(ConstraintsUpdateException::from)?;
-            Ok(JsValue::UNDEFINED)
-        })
+        future_to_promise(super::abortable(
+            async move {
+                this.set_local_media_settings(
+                    settings.into(),
+                    stop_first,
+                    rollback_on_fail,
+                )
+                .await
+                .map_err(ConstraintsUpdateException::from)?;
+                Ok(JsValue::UNDEFINED)
+            },
+            signal,
+        ))
+        .into()
     }
 
     /// Mutes outbound audio in this [`Room`].
@@ -177,13 +396,15 @@ impl RoomHandle {
     /// transition.
     ///
     /// [`Room`]: room::Room
-    pub fn mute_audio(&self) -> Promise {
+    #[must_use]
+    pub fn mute_audio(&self) -> typings::VoidPromise {
         let this = self.0.clone();
 
         future_to_promise(async move {
             this.mute_audio().await.map_err(Error::from)?;
             Ok(JsValue::UNDEFINED)
         })
+        .into()
     }
 
     /// Unmutes outbound audio in this [`Room`].
@@ -195,13 +416,15 @@ impl RoomHandle {
     /// transition.
     ///
     /// [`Room`]: room::Room
-    pub fn unmute_audio(&self) -> Promise {
+    #[must_use]
+    pub fn unmute_audio(&self) -> typings::VoidPromise {
         let this = self.0.clone();
 
         future_to_promise(async move {
             this.unmute_audio().await.map_err(Error::from)?;
             Ok(JsValue::UNDEFINED)
         })
+        .into()
     }
 
     /// Mutes outbound video in this [`Room`].
@@ -213,7 +436,11 @@ impl RoomHandle {
     /// transition.
     ///
     /// [`Room`]: room::Room
-    pub fn mute_video(&self, source_kind: Option<MediaSourceKind>) -> Promise {
+    #[must_use]
+    pub fn mute_video(
+        &self,
+        source_kind: Option<MediaSourceKind>,
+    ) -> typings::VoidPromise {
         let this = self.0.clone();
 
         future_to_promise(async move {
@@ -222,6 +449,7 @@ impl RoomHandle {
                 .map_err(Error::from)?;
             Ok(JsValue::UNDEFINED)
         })
+        .into()
     }
 
     /// Unmutes outbound video in this [`Room`].
@@ -233,10 +461,11 @@ impl RoomHandle {
     /// transition.
     ///
     /// [`Room`]: room::Room
+    #[must_use]
     pub fn unmute_video(
         &self,
         source_kind: Option<MediaSourceKind>,
-    ) -> Promise {
+    ) -> typings::VoidPromise {
         let this = self.0.clone();
 
         future_to_promise(async move {
@@ -245,6 +474,7 @@ impl RoomHandle {
                 .map_err(Error::from)?;
             Ok(JsValue::UNDEFINED)
         })
+        .into()
     }
 
     /// Disables outbound audio in this [`Room`].
@@ -257,13 +487,15 @@ impl RoomHandle {
     /// transition.
     ///
     /// [`Room`]: room::Room
-    pub fn disable_audio(&self) -> Promise {
+    #[must_use]
+    pub fn disable_audio(&self) -> typings::VoidPromise {
         let this = self.0.clone();
 
         future_to_promise(async move {
             this.disable_audio().await.map_err(Error::from)?;
             Ok(JsValue::UNDEFINED)
         })
+        .into()
     }
 
     /// Enables outbound audio in this [`Room`].
@@ -278,13 +510,15 @@ impl RoomHandle {
     /// Agent failed.
     ///
     /// [`Room`]: room::Room
-    pub fn enable_audio(&self) -> Promise {
+    #[must_use]
+    pub fn enable_audio(&self) -> typings::VoidPromise {
         let this = self.0.clone();
 
         future_to_promise(async move {
             this.enable_audio().await.map_err(Error::from)?;
             Ok(JsValue::UNDEFINED)
         })
+        .into()
     }
 
     /// Disables outbound video.
@@ -297,10 +531,11 @@ impl RoomHandle {
     /// `required` by a media server or [`RoomHandle::enable_video()`] was
     /// called while disabling or a media server didn't approve this state
     /// transition.
+    #[must_use]
     pub fn disable_video(
         &self,
         source_kind: Option<MediaSourceKind>,
-    ) -> Promise {
+    ) -> typings::VoidPromise {
         let this = self.0.clone();
 
         future_to_promise(async move {
@@ -309,6 +544,7 @@ impl RoomHandle {
                 .map_err(Error::from)?;
             Ok(JsValue::UNDEFINED)
         })
+        .into()
     }
 
     /// Enables outbound video.
@@ -323,10 +559,11 @@ impl RoomHandle {
     ///
     /// With `name = 'MediaManagerError'` if media acquisition request to User
     /// Agent failed.
+    #[must_use]
     pub fn enable_video(
         &self,
         source_kind: Option<MediaSourceKind>,
-    ) -> Promise {
+    ) -> typings::VoidPromise {
         let this = self.0.clone();
 
         future_to_promise(async move {
@@ -335,6 +572,7 @@ impl RoomHandle {
                 .map_err(Error::from)?;
             Ok(JsValue::UNDEFINED)
         })
+        .into()
     }
 
     /// Disables inbound audio in this [`Room`].
@@ -346,13 +584,15 @@ impl RoomHandle {
     /// media server didn't approve this state transition.
     ///
     /// [`Room`]: room::Room
-    pub fn disable_remote_audio(&self) -> Promise {
+    #[must_use]
+    pub fn disable_remote_audio(&self) -> typings::VoidPromise {
         let this = self.0.clone();
 
         future_to_promise(async move {
             this.disable_remote_audio().await.map_err(Error::from)?;
             Ok(JsValue::UNDEFINED)
         })
+        .into()
     }
 
     /// Disables inbound video in this [`Room`].
@@ -364,13 +604,15 @@ impl RoomHandle {
     /// a media server didn't approve this state transition.
     ///
     /// [`Room`]: room::Room
-    pub fn disable_remote_video(&self) -> Promise {
+    #[must_use]
+    pub fn disable_remote_video(&self) -> typings::VoidPromise {
         let this = self.0.clone();
 
         future_to_promise(async move {
             this.disable_remote_video().await.map_err(Error::from)?;
             Ok(JsValue::UNDEFINED)
         })
+        .into()
     }
 
     /// Enables inbound audio in this [`Room`].
@@ -382,13 +624,15 @@ impl RoomHandle {
     /// media server didn't approve this state transition.
     ///
     /// [`Room`]: room::Room
-    pub fn enable_remote_audio(&self) -> Promise {
+    #[must_use]
+    pub fn enable_remote_audio(&self) -> typings::VoidPromise {
         let this = self.0.clone();
 
         future_to_promise(async move {
             this.enable_remote_audio().await.map_err(Error::from)?;
             Ok(JsValue::UNDEFINED)
         })
+        .into()
     }
 
     /// Enables inbound video in this [`Room`].
@@ -400,12 +644,85 @@ impl RoomHandle {
     /// media server didn't approve this state transition.
     ///
     /// [`Room`]: room::Room
-    pub fn enable_remote_video(&self) -> Promise {
+    #[must_use]
+    pub fn enable_remote_video(&self) -> typings::VoidPromise {
         let this = self.0.clone();
 
         future_to_promise(async move {
             this.enable_remote_video().await.map_err(Error::from)?;
             Ok(JsValue::UNDEFINED)
         })
+        .into()
+    }
+
+    /// Sets the [`RecvMediaPolicy`] of this [`Room`].
+    ///
+    /// With [`RecvMediaPolicy::Manual`] the media received from a remote
+    /// `Member` stays disabled until it's enabled via
+    /// [`RoomHandle::enable_remote_media_from()`]. Affects only the media
+    /// received after this call, so should be called before
+    /// [`RoomHandle::join()`].
+    ///
+    /// [`Room`]: room::Room
+    pub fn set_recv_media_policy(
+        &self,
+        policy: RecvMediaPolicy,
+    ) -> Result<(), JsValue> {
+        self.0
+            .set_recv_media_policy(policy.into())
+            .map_err(Error::from)
+            .map_err(JsValue::from)
+    }
+
+    /// Enables inbound media from the remote `Member` with the provided ID in
+    /// this [`Room`].
+    ///
+    /// # Errors
+    ///
+    /// With `name = 'MediaConnections'` if
+    /// [`RoomHandle::disable_remote_media_from()`] was called while enabling
+    /// or a media server didn't approve this state transition.
+    ///
+    /// [`Room`]: room::Room
+    #[must_use]
+    pub fn enable_remote_media_from(
+        &self,
+        member_id: String,
+    ) -> typings::VoidPromise {
+        let this = self.0.clone();
+
+        future_to_promise(async move {
+            this.enable_remote_media_from(member_id)
+                .await
+                .map_err(Error::from)?;
+            Ok(JsValue::UNDEFINED)
+        })
+        .into()
+    }
+
+    /// Disables inbound media from the remote `Member` with the provided ID
+    /// in this [`Room`].
+    ///
+    /// # Errors
+    ///
+    /// With `name = 'MediaConnections'` if
+    /// [`RoomHandle::enable_remote_media_from()`] was called while disabling
+    /// or a media server didn't approve this state transition.
+    ///
+    /// [`Room`]: room::Room
+    #[must_use]
+    pub fn disable_remote_media_from(
+        &self,
+        member_id: String,
+    ) -> typings::VoidPromise {
+        let this = self.0.clone();
+
+        future_to_promise(async move {
+            this.disable_remote_media_from(member_id)
+                .await
+                .map_err(Error::from)?;
+            Ok(JsValue::UNDEFINED)
+        })
+        .into()
     }
 }
@@ -0,0 +1,165 @@
+//! `.d.ts` typings of JS side callbacks and [`Promise`]s, which
+//! [`wasm_bindgen`] is unable to describe on its own.
+//!
+//! TypeScript definitions of the callbacks are generated by the build script
+//! from the `impl_callback!` invocation and the docs of the declared types.
+//!
+//! Every type declared here is a thin wrapper around a [`JsValue`], that only
+//! changes the type emitted into the generated `.d.ts` file. No runtime checks
+//! are performed when converting between these types and their untyped
+//! [`js_sys`] counterparts.
+
+use js_sys::Promise;
+use wasm_bindgen::{prelude::*, JsCast as _};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::{api, platform};
+
+// Definitions of the callbacks declared below and union types of the exported
+// enums, generated by the build script.
+include!(concat!(env!("OUT_DIR"), "/typings.rs"));
+
+#[wasm_bindgen]
+extern "C" {
+    /// Callback invoked when a new connection with a remote `Member` is
+    /// established.
+    #[wasm_bindgen(typescript_type = "NewConnectionCallback")]
+    pub type NewConnectionCallback;
+
+    /// Callback invoked when a `Room` is closed.
+    #[wasm_bindgen(typescript_type = "RoomCloseCallback")]
+    pub type RoomCloseCallback;
+
+    /// Callback invoked when a new `LocalMediaTrack` is added to a `Room`.
+    #[wasm_bindgen(typescript_type = "LocalTrackCallback")]
+    pub type LocalTrackCallback;
+
+    /// Callback invoked on local media acquisition failures.
+    #[wasm_bindgen(typescript_type = "FailedLocalMediaCallback")]
+    pub type FailedLocalMediaCallback;
+
+    /// Callback invoked when a connection with a media server is lost.
+    #[wasm_bindgen(typescript_type = "ConnectionLossCallback")]
+    pub type ConnectionLossCallback;
+
+    /// Callback invoked when some remote `Member` sends application data.
+    #[wasm_bindgen(typescript_type = "AppDataCallback")]
+    pub type AppDataCallback;
+
+    /// Callback invoked when a new `RemoteMediaTrack` is added to a
+    /// connection.
+    #[wasm_bindgen(typescript_type = "RemoteTrackCallback")]
+    pub type RemoteTrackCallback;
+
+    /// Callback invoked with an updated connection quality score in `1..=4`
+    /// range.
+    #[wasm_bindgen(typescript_type = "QualityScoreCallback")]
+    pub type QualityScoreCallback;
+
+    /// Callback invoked with a `LogRecord` of this library.
+    #[wasm_bindgen(typescript_type = "LogCallback")]
+    pub type LogCallback;
+
+    /// Callback invoked with an `AnalyticsEvent` of this library.
+    #[wasm_bindgen(typescript_type = "AnalyticsCallback")]
+    pub type AnalyticsCallback;
+
+    /// Callback invoked without any arguments.
+    #[wasm_bindgen(typescript_type = "VoidCallback")]
+    pub type VoidCallback;
+
+    /// [`Promise`] resolving with nothing.
+    #[wasm_bindgen(typescript_type = "Promise<void>")]
+    pub type VoidPromise;
+
+    /// [`Promise`] resolving with a string.
+    #[wasm_bindgen(typescript_type = "Promise<string>")]
+    pub type StringPromise;
+
+    /// [`Promise`] resolving with a list of [`api::InputDeviceInfo`]s.
+    #[wasm_bindgen(typescript_type = "Promise<InputDeviceInfo[]>")]
+    pub type InputDeviceInfosPromise;
+
+    /// [`Promise`] resolving with a list of [`api::LocalMediaTrack`]s.
+    #[wasm_bindgen(typescript_type = "Promise<LocalMediaTrack[]>")]
+    pub type LocalMediaTracksPromise;
+
+    /// [`Promise`] resolving with [`api::MediaPermissions`].
+    #[wasm_bindgen(typescript_type = "Promise<MediaPermissions>")]
+    pub type MediaPermissionsPromise;
+
+    /// List of [`api::Error`]s.
+    #[wasm_bindgen(typescript_type = "JasonError[]")]
+    pub type JasonErrors;
+}
+
+/// Implements conversions between the typed JS callbacks, untyped
+/// [`js_sys::Function`]s, and [`platform::Function`]s accepting the specified
+/// argument.
+macro_rules! impl_callback {
+    ($($callback:ty => $arg:ty),* $(,)?) => {$(
+        impl From<js_sys::Function> for $callback {
+            #[inline]
+            fn from(cb: js_sys::Function) -> Self {
+                cb.unchecked_into()
+            }
+        }
+
+        impl From<$callback> for platform::Function<$arg> {
+            #[inline]
+            fn from(cb: $callback) -> Self {
+                cb.unchecked_into::<js_sys::Function>().into()
+            }
+        }
+    )*};
+}
+
+impl_callback! {
+    NewConnectionCallback => api::ConnectionHandle,
+    RoomCloseCallback => api::RoomCloseReason,
+    LocalTrackCallback => api::LocalMediaTrack,
+    FailedLocalMediaCallback => api::Error,
+    ConnectionLossCallback => api::ReconnectHandle,
+    AppDataCallback => api::AppData,
+    RemoteTrackCallback => api::RemoteMediaTrack,
+    QualityScoreCallback => u8,
+    LogCallback => api::LogRecord,
+    AnalyticsCallback => api::AnalyticsEvent,
+    VoidCallback => (),
+}
+
+/// Implements conversions of the untyped [`Promise`]s into the typed ones,
+/// and of the typed [`Promise`]s into [`JsFuture`]s, so they still can be
+/// awaited on Rust side.
+macro_rules! impl_promise {
+    ($($promise:ty),* $(,)?) => {$(
+        impl From<Promise> for $promise {
+            #[inline]
+            fn from(promise: Promise) -> Self {
+                promise.unchecked_into()
+            }
+        }
+
+        impl From<$promise> for JsFuture {
+            #[inline]
+            fn from(promise: $promise) -> Self {
+                Self::from(promise.unchecked_into::<Promise>())
+            }
+        }
+    )*};
+}
+
+impl_promise!(
+    VoidPromise,
+    StringPromise,
+    InputDeviceInfosPromise,
+    LocalMediaTracksPromise,
+    MediaPermissionsPromise
+);
+
+impl From<js_sys::Array> for JasonErrors {
+    #[inline]
+    fn from(errors: js_sys::Array) -> Self {
+        errors.unchecked_into()
+    }
+}
@@ -3,6 +3,7 @@
 use std::{
     cell::{Cell, RefCell},
     collections::{HashMap, HashSet},
+    mem,
     rc::{Rc, Weak},
 };
 
@@ -21,6 +22,13 @@ pub struct Connections {
     /// Remote [`MemberId`] to [`Connection`] with that `Member`.
     connections: RefCell<HashMap<MemberId, Connection>>,
 
+    /// Remote [`MemberId`] to the last known key-value metadata of that
+    /// `Member`.
+    ///
+    /// Kept separately from [`Connection`]s, since metadata may be received
+    /// before any [`Connection`] with the `Member` is established.
+    members_metadata: RefCell<HashMap<MemberId, HashMap<String, String>>>,
+
     /// Callback invoked on remote `Member` media arrival.
     on_new_connection: platform::Callback<api::ConnectionHandle>,
 }
@@ -46,6 +54,11 @@ impl Connections {
         let is_new = !self.connections.borrow().contains_key(remote_member_id);
         if is_new {
             let con = Connection::new(remote_member_id.clone());
+            if let Some(metadata) =
+                self.members_metadata.borrow().get(remote_member_id)
+            {
+                con.update_metadata(metadata.clone());
+            }
             self.on_new_connection.call1(con.new_handle());
             self.connections
                 .borrow_mut()
@@ -58,6 +71,23 @@ impl Connections {
         }
     }
 
+    /// Updates key-value metadata of the remote `Member` with the provided
+    /// [`MemberId`].
+    ///
+    /// Updates metadata of the [`Connection`] with this `Member` if it exists.
+    pub fn update_metadata(
+        &self,
+        remote_member_id: MemberId,
+        metadata: HashMap<String, String>,
+    ) {
+        if let Some(conn) = self.connections.borrow().get(&remote_member_id) {
+            conn.update_metadata(metadata.clone());
+        }
+        self.members_metadata
+            .borrow_mut()
+            .insert(remote_member_id, metadata);
+    }
+
     /// Lookups [`Connection`] by the given remote [`PeerId`].
     pub fn get(&self, remote_member_id: &MemberId) -> Option<Connection> {
         self.connections.borrow().get(remote_member_id).cloned()
@@ -82,6 +112,17 @@ impl Connections {
             }
         }
     }
+
+    /// Closes all the [`Connection`]s.
+    ///
+    /// Invokes `on_close` callback of each of them.
+    pub fn close_all(&self) {
+        self.peer_members.borrow_mut().clear();
+        let connections = mem::take(&mut *self.connections.borrow_mut());
+        for connection in connections.values() {
+            connection.0.on_close.call0();
+        }
+    }
 }
 
 /// Error of [`ConnectionHandle`]'s [`Weak`] pointer being detached.
@@ -105,12 +146,21 @@ struct InnerConnection {
     /// Current [`ConnectionQualityScore`] of this [`Connection`].
     quality_score: Cell<Option<ConnectionQualityScore>>,
 
+    /// Current key-value metadata of the remote `Member`.
+    metadata: RefCell<HashMap<String, String>>,
+
     /// Callback invoked when a [`remote::Track`] is received.
     on_remote_track_added: platform::Callback<api::RemoteMediaTrack>,
 
+    /// Callback invoked when a [`remote::Track`] is removed by a server.
+    on_remote_track_removed: platform::Callback<api::RemoteMediaTrack>,
+
     /// Callback invoked when a [`ConnectionQualityScore`] is updated.
     on_quality_score_update: platform::Callback<u8>,
 
+    /// Callback invoked when metadata of the remote `Member` is updated.
+    on_metadata_update: platform::Callback<()>,
+
     /// Callback invoked when this [`Connection`] is closed.
     on_close: platform::Callback<()>,
 }
@@ -161,6 +211,22 @@ impl ConnectionHandle {
             .map(|inner| inner.on_remote_track_added.set_func(f))
     }
 
+    /// Sets callback, invoked when a [`remote::Track`] is removed from this
+    /// [`Connection`] by a server.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandlerDetachedError`] for details.
+    pub fn on_remote_track_removed(
+        &self,
+        f: platform::Function<api::RemoteMediaTrack>,
+    ) -> Result<(), Traced<HandlerDetachedError>> {
+        self.0
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(HandlerDetachedError))
+            .map(|inner| inner.on_remote_track_removed.set_func(f))
+    }
+
     /// Sets callback, invoked when a connection quality score is updated by
     /// a server.
     ///
@@ -176,6 +242,36 @@ impl ConnectionHandle {
             .ok_or_else(|| tracerr::new!(HandlerDetachedError))
             .map(|inner| inner.on_quality_score_update.set_func(f))
     }
+
+    /// Returns current key-value metadata of the remote `Member`.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandlerDetachedError`] for details.
+    pub fn get_metadata(
+        &self,
+    ) -> Result<HashMap<String, String>, Traced<HandlerDetachedError>> {
+        self.0
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(HandlerDetachedError))
+            .map(|inner| inner.metadata.borrow().clone())
+    }
+
+    /// Sets callback, invoked when metadata of the remote `Member` is updated
+    /// by a server.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandlerDetachedError`] for details.
+    pub fn on_metadata_update(
+        &self,
+        f: platform::Function<()>,
+    ) -> Result<(), Traced<HandlerDetachedError>> {
+        self.0
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(HandlerDetachedError))
+            .map(|inner| inner.on_metadata_update.set_func(f))
+    }
 }
 
 /// Connection with a specific remote `Member`, that is used on Rust side.
@@ -190,9 +286,12 @@ impl Connection {
         Self(Rc::new(InnerConnection {
             remote_id,
             quality_score: Cell::default(),
+            metadata: RefCell::default(),
             on_quality_score_update: platform::Callback::default(),
+            on_metadata_update: platform::Callback::default(),
             on_close: platform::Callback::default(),
             on_remote_track_added: platform::Callback::default(),
+            on_remote_track_removed: platform::Callback::default(),
         }))
     }
 
@@ -202,6 +301,12 @@ impl Connection {
         self.0.on_remote_track_added.call1(track);
     }
 
+    /// Invokes `on_remote_track_removed` callback with the provided
+    /// [`remote::Track`].
+    pub fn remove_remote_track(&self, track: remote::Track) {
+        self.0.on_remote_track_removed.call1(track);
+    }
+
     /// Creates a new external handle to this [`Connection`].
     #[inline]
     #[must_use]
@@ -215,4 +320,13 @@ impl Connection {
             self.0.on_quality_score_update.call1(score as u8);
         }
     }
+
+    /// Updates key-value metadata of the remote `Member` of this
+    /// [`Connection`].
+    pub fn update_metadata(&self, metadata: HashMap<String, String>) {
+        if *self.0.metadata.borrow() != metadata {
+            drop(self.0.metadata.replace(metadata));
+            self.0.on_metadata_update.call0();
+        }
+    }
 }
@@ -1,7 +1,9 @@
 //! General library interface.
 
-use futures::FutureExt as _;
-use std::{cell::RefCell, rc::Rc};
+use derive_more::Display;
+use futures::{future, FutureExt as _};
+use medea_client_api_proto::PeerId;
+use std::{cell::RefCell, rc::Rc, time::Duration};
 
 use crate::platform;
 
@@ -11,6 +13,7 @@ use crate::{
     rpc::{
         ClientDisconnect, RpcSession, WebSocketRpcClient, WebSocketRpcSession,
     },
+    utils::TaskHandle,
 };
 
 /// General library interface.
@@ -19,6 +22,144 @@ use crate::{
 /// initialization.
 pub struct Jason(Rc<RefCell<Inner>>);
 
+/// Structured event of a key lifecycle stage of a [`Jason`], delivered to its
+/// sink set via [`Jason::set_analytics_sink()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AnalyticsEvent {
+    /// Joining a [`Room`] has been started.
+    JoinStarted,
+
+    /// [`Room`] has been joined successfully.
+    JoinSucceeded {
+        /// Time taken to join the [`Room`].
+        duration: Duration,
+    },
+
+    /// Connection with a media server has been restored after being lost.
+    Reconnected,
+
+    /// SDP negotiation of a `PeerConnection` has been finished.
+    NegotiationFinished {
+        /// ID of the negotiated `PeerConnection`.
+        peer_id: PeerId,
+
+        /// Time taken by the negotiation.
+        duration: Duration,
+    },
+
+    /// Local media has been acquired successfully.
+    LocalMediaAcquired {
+        /// Time taken to acquire the local media.
+        duration: Duration,
+    },
+
+    /// Local media has failed to be acquired.
+    LocalMediaFailed {
+        /// Time taken until the failure.
+        duration: Duration,
+    },
+}
+
+impl AnalyticsEvent {
+    /// Returns [`AnalyticsEventKind`] of this [`AnalyticsEvent`].
+    #[must_use]
+    pub fn kind(&self) -> AnalyticsEventKind {
+        match self {
+            Self::JoinStarted => AnalyticsEventKind::JoinStarted,
+            Self::JoinSucceeded { .. } => AnalyticsEventKind::JoinSucceeded,
+            Self::Reconnected => AnalyticsEventKind::Reconnected,
+            Self::NegotiationFinished { .. } => {
+                AnalyticsEventKind::NegotiationFinished
+            }
+            Self::LocalMediaAcquired { .. } => {
+                AnalyticsEventKind::LocalMediaAcquired
+            }
+            Self::LocalMediaFailed { .. } => {
+                AnalyticsEventKind::LocalMediaFailed
+            }
+        }
+    }
+
+    /// Returns ID of the `PeerConnection` this [`AnalyticsEvent`] relates to,
+    /// if any.
+    #[must_use]
+    pub fn peer_id(&self) -> Option<PeerId> {
+        match self {
+            Self::NegotiationFinished { peer_id, .. } => Some(*peer_id),
+            _ => None,
+        }
+    }
+
+    /// Returns duration of the stage this [`AnalyticsEvent`] finishes, if
+    /// any.
+    #[must_use]
+    pub fn duration(&self) -> Option<Duration> {
+        match self {
+            Self::JoinSucceeded { duration }
+            | Self::NegotiationFinished { duration, .. }
+            | Self::LocalMediaAcquired { duration }
+            | Self::LocalMediaFailed { duration } => Some(*duration),
+            Self::JoinStarted | Self::Reconnected => None,
+        }
+    }
+}
+
+/// Kind of an [`AnalyticsEvent`].
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+#[repr(u8)]
+pub enum AnalyticsEventKind {
+    /// [`AnalyticsEvent::JoinStarted`].
+    JoinStarted = 0,
+
+    /// [`AnalyticsEvent::JoinSucceeded`].
+    JoinSucceeded = 1,
+
+    /// [`AnalyticsEvent::Reconnected`].
+    Reconnected = 2,
+
+    /// [`AnalyticsEvent::NegotiationFinished`].
+    NegotiationFinished = 3,
+
+    /// [`AnalyticsEvent::LocalMediaAcquired`].
+    LocalMediaAcquired = 4,
+
+    /// [`AnalyticsEvent::LocalMediaFailed`].
+    LocalMediaFailed = 5,
+}
+
+/// Lightweight bus delivering [`AnalyticsEvent`]s emitted by [`Room`]s to the
+/// sink of their [`Jason`].
+#[derive(Default)]
+pub struct Analytics(RefCell<Option<Rc<dyn Fn(AnalyticsEvent)>>>);
+
+impl Analytics {
+    /// Sets the sink receiving all the emitted [`AnalyticsEvent`]s, replacing
+    /// the previously set one (if any).
+    #[inline]
+    pub fn set_sink<F: Fn(AnalyticsEvent) + 'static>(&self, sink: F) {
+        drop(self.0.borrow_mut().replace(Rc::new(sink)));
+    }
+
+    /// Delivers the provided [`AnalyticsEvent`] to the set sink.
+    ///
+    /// Does nothing if no sink is set.
+    pub fn emit(&self, event: AnalyticsEvent) {
+        let sink = self.0.borrow().as_ref().map(Rc::clone);
+        if let Some(sink) = sink {
+            sink(event);
+        }
+    }
+
+    /// Returns time elapsed since the provided `start` time in milliseconds
+    /// since the UNIX epoch.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    #[must_use]
+    pub fn elapsed(start: f64) -> Duration {
+        let elapsed = platform::unix_time_millis() - start;
+        Duration::from_millis(elapsed.max(0.0) as u64)
+    }
+}
+
 /// Inner representation if a [`Jason`].
 struct Inner {
     /// [`Jason`]s [`MediaManager`].
@@ -27,13 +168,18 @@ struct Inner {
     /// tracks that can be used by multiple [`Room`]s.
     media_manager: Rc<MediaManager>,
 
-    /// [`Room`]s maintained by this [`Jason`] instance.
-    rooms: Vec<Room>,
+    /// [`Room`]s maintained by this [`Jason`] instance, along with
+    /// [`TaskHandle`]s of the tasks closing them on a normal [`RpcSession`]
+    /// close.
+    rooms: Vec<(Room, TaskHandle)>,
 
     /// Connection with a media server.
     ///
     /// Only one [`WebSocketRpcClient`] is supported at the moment.
     rpc: Rc<WebSocketRpcClient>,
+
+    /// [`Analytics`] bus shared with all the [`Room`]s of this [`Jason`].
+    analytics: Rc<Analytics>,
 }
 
 impl Jason {
@@ -67,27 +213,50 @@ impl Jason {
         self.0.borrow().media_manager.new_handle()
     }
 
+    /// Sets the sink receiving [`AnalyticsEvent`]s of all the [`Room`]s of
+    /// this [`Jason`], replacing the previously set one (if any).
+    #[inline]
+    pub fn set_analytics_sink<F: Fn(AnalyticsEvent) + 'static>(&self, sink: F) {
+        self.0.borrow().analytics.set_sink(sink);
+    }
+
     /// Closes the provided [`RoomHandle`].
+    ///
+    /// See [`Room::close()`] for details.
     #[allow(clippy::needless_pass_by_value)]
     pub fn close_room(&self, room_to_delete: RoomHandle) {
-        self.0.borrow_mut().rooms.retain(|room| {
-            let should_be_closed = room.inner_ptr_eq(&room_to_delete);
-            if should_be_closed {
-                room.set_close_reason(ClientDisconnect::RoomClosed.into());
-            }
-
-            !should_be_closed
-        });
+        let room = {
+            let mut inner = self.0.borrow_mut();
+            let index = inner
+                .rooms
+                .iter()
+                .position(|(room, _)| room.inner_ptr_eq(&room_to_delete));
+            index.map(|index| inner.rooms.remove(index))
+        };
+        if let Some((room, _)) = room {
+            room.close(ClientDisconnect::RoomClosed.into());
+        }
     }
 
     /// Drops this [`Jason`] API object, so all the related objects (rooms,
     /// connections, streams, etc.) respectively. All objects related to this
     /// [`Jason`] API object will be detached (you will still hold them, but
     /// unable to use).
+    ///
+    /// All the [`Room`]s are closed right away (see [`Room::close()`]), and
+    /// all the local media tracks are stopped.
     pub fn dispose(self) {
-        self.0.borrow_mut().rooms.drain(..).for_each(|room| {
+        let (rooms, media_manager) = {
+            let mut inner = self.0.borrow_mut();
+            (
+                inner.rooms.drain(..).collect::<Vec<_>>(),
+                Rc::clone(&inner.media_manager),
+            )
+        };
+        for (room, _) in rooms {
             room.close(ClientDisconnect::RoomClosed.into());
-        });
+        }
+        media_manager.stop_tracks();
     }
 
     /// Returns a new [`Jason`] with the provided [`WebSocketRpcClient`].
@@ -97,35 +266,50 @@ impl Jason {
             rpc,
             rooms: Vec::new(),
             media_manager: Rc::new(MediaManager::default()),
+            analytics: Rc::default(),
         })))
     }
 
     /// Returns a [`RoomHandle`] for an initialized  [`Room`].
     fn inner_init_room(&self, rpc: Rc<dyn RpcSession>) -> RoomHandle {
         let on_normal_close = rpc.on_normal_close();
-        let room = Room::new(rpc, Rc::clone(&self.0.borrow().media_manager));
+        let room = {
+            let inner = self.0.borrow();
+            Room::new(
+                rpc,
+                Rc::clone(&inner.media_manager),
+                Rc::clone(&inner.analytics),
+            )
+        };
 
         let weak_room = room.downgrade();
         let weak_inner = Rc::downgrade(&self.0);
-        platform::spawn(on_normal_close.map(move |reason| {
-            (|| {
-                let room = weak_room.upgrade()?;
-                let inner = weak_inner.upgrade()?;
-                let mut inner = inner.borrow_mut();
-                let index = inner.rooms.iter().position(|r| r.ptr_eq(&room));
-                if let Some(index) = index {
-                    inner.rooms.remove(index).close(reason);
-                }
-                if inner.rooms.is_empty() {
-                    inner.media_manager = Rc::default();
-                }
-
-                Some(())
-            })();
-        }));
+        let (on_normal_close, abort) =
+            future::abortable(on_normal_close.map(move |reason| {
+                (|| {
+                    let room = weak_room.upgrade()?;
+                    let inner = weak_inner.upgrade()?;
+                    let (room, _) = {
+                        let mut inner = inner.borrow_mut();
+                        let index = inner
+                            .rooms
+                            .iter()
+                            .position(|(r, _)| r.ptr_eq(&room))?;
+                        let removed = inner.rooms.remove(index);
+                        if inner.rooms.is_empty() {
+                            inner.media_manager = Rc::default();
+                        }
+                        removed
+                    };
+                    room.close(reason);
+
+                    Some(())
+                })();
+            }));
+        platform::spawn(on_normal_close.map(drop));
 
         let handle = room.new_handle();
-        self.0.borrow_mut().rooms.push(room);
+        self.0.borrow_mut().rooms.push((room, abort.into()));
         handle
     }
 }
@@ -13,6 +13,7 @@ pub mod utils;
 pub mod api;
 pub mod connection;
 pub mod jason;
+pub mod log;
 pub mod media;
 pub mod peer;
 pub mod platform;
@@ -0,0 +1,253 @@
+//! Logging facade of [`Jason`].
+//!
+//! Routes all the [`log`] records of this library to a platform console and
+//! to the registered sinks, allowing to change the log level at runtime, and
+//! to enable debug logging of separate [`LogScope`]s independently.
+//!
+//! [`Jason`]: crate::api::Jason
+//! [`log`]: https://docs.rs/log
+
+use std::{
+    cell::RefCell,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use ::log::{Level, LevelFilter, Log, Metadata, Record};
+use derive_more::Display;
+
+use crate::platform;
+
+/// Default global [`LevelFilter`] of the [`Logger`].
+const DEFAULT_LEVEL: LevelFilter = LevelFilter::Debug;
+
+/// Value of the [`SCOPE_LEVELS`] meaning that no [`LevelFilter`] is set for a
+/// [`LogScope`].
+const UNSET: usize = usize::MAX;
+
+/// Global [`LevelFilter`] of the [`Logger`].
+static LEVEL: AtomicUsize = AtomicUsize::new(DEFAULT_LEVEL as usize);
+
+/// [`LevelFilter`]s of the [`LogScope`]s, overriding the global [`LEVEL`].
+static SCOPE_LEVELS: [AtomicUsize; 3] = [
+    AtomicUsize::new(UNSET),
+    AtomicUsize::new(UNSET),
+    AtomicUsize::new(UNSET),
+];
+
+thread_local! {
+    /// Sinks registered via [`add_sink()`].
+    static SINKS: RefCell<Vec<Box<dyn Fn(&LogRecord)>>> = RefCell::default();
+}
+
+/// Part of this library, logging of which can be configured independently.
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum LogScope {
+    /// Signalling with a media server.
+    Rpc,
+
+    /// `PeerConnection`s management.
+    Peer,
+
+    /// Local and remote media management.
+    Media,
+}
+
+impl LogScope {
+    /// All the existing [`LogScope`]s.
+    const ALL: [Self; 3] = [Self::Rpc, Self::Peer, Self::Media];
+
+    /// Returns path of the module covered by this [`LogScope`].
+    #[must_use]
+    fn module_path(self) -> &'static str {
+        match self {
+            Self::Rpc => "medea_jason::rpc",
+            Self::Peer => "medea_jason::peer",
+            Self::Media => "medea_jason::media",
+        }
+    }
+
+    /// Returns [`LogScope`] covering the provided log `target`, if any.
+    #[must_use]
+    fn of(target: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|scope| {
+            let path = scope.module_path();
+            target
+                .strip_prefix(path)
+                .map_or(false, |rest| rest.is_empty() || rest.starts_with("::"))
+        })
+    }
+
+    /// Returns [`LevelFilter`] set for this [`LogScope`], if any.
+    #[must_use]
+    fn level(self) -> Option<LevelFilter> {
+        match SCOPE_LEVELS[self as usize].load(Ordering::Relaxed) {
+            UNSET => None,
+            level => Some(level_filter(level)),
+        }
+    }
+}
+
+/// Single log record passed to the registered sinks.
+#[derive(Clone, Debug)]
+pub struct LogRecord {
+    /// [`Level`] of this [`LogRecord`].
+    level: Level,
+
+    /// Name of the module this [`LogRecord`] originates from.
+    target: String,
+
+    /// Formatted message of this [`LogRecord`].
+    message: String,
+}
+
+impl LogRecord {
+    /// Returns [`Level`] of this [`LogRecord`].
+    #[inline]
+    #[must_use]
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// Returns name of the module this [`LogRecord`] originates from.
+    #[inline]
+    #[must_use]
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Returns formatted message of this [`LogRecord`].
+    #[inline]
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl From<&Record<'_>> for LogRecord {
+    #[inline]
+    fn from(record: &Record<'_>) -> Self {
+        Self {
+            level: record.level(),
+            target: record.target().to_owned(),
+            message: record.args().to_string(),
+        }
+    }
+}
+
+/// [`Log`] implementation writing records to a platform console and to the
+/// registered sinks.
+struct Logger;
+
+impl Log for Logger {
+    #[inline]
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= effective_level(metadata.target())
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        platform::console_log(record);
+
+        SINKS.with(|sinks| {
+            // Sinks registered during a sink invocation are skipped.
+            if let Ok(sinks) = sinks.try_borrow() {
+                if !sinks.is_empty() {
+                    let record = LogRecord::from(record);
+                    sinks.iter().for_each(|sink| sink(&record));
+                }
+            }
+        });
+    }
+
+    #[inline]
+    fn flush(&self) {}
+}
+
+/// Installs the [`Logger`] as the global [`log`] logger.
+///
+/// Does nothing if any global [`log`] logger is installed already.
+///
+/// [`log`]: https://docs.rs/log
+pub fn init() {
+    static LOGGER: Logger = Logger;
+
+    if ::log::set_logger(&LOGGER).is_ok() {
+        update_max_level();
+    }
+}
+
+/// Sets the global [`LevelFilter`], applied to all the records not covered by
+/// a [`LogScope`] with its own [`LevelFilter`].
+///
+/// Defaults to [`LevelFilter::Debug`].
+pub fn set_level(level: LevelFilter) {
+    LEVEL.store(level as usize, Ordering::Relaxed);
+    update_max_level();
+}
+
+/// Returns the current global [`LevelFilter`].
+#[inline]
+#[must_use]
+pub fn level() -> LevelFilter {
+    level_filter(LEVEL.load(Ordering::Relaxed))
+}
+
+/// Sets [`LevelFilter`] of the provided [`LogScope`], overriding the global
+/// one.
+///
+/// [`None`] makes the [`LogScope`] use the global [`LevelFilter`] again.
+pub fn set_scope_level(scope: LogScope, level: Option<LevelFilter>) {
+    SCOPE_LEVELS[scope as usize]
+        .store(level.map_or(UNSET, |l| l as usize), Ordering::Relaxed);
+    update_max_level();
+}
+
+/// Registers a new sink receiving every [`LogRecord`] passing the configured
+/// [`LevelFilter`]s.
+///
+/// Sinks are local to the current thread.
+pub fn add_sink<F: Fn(&LogRecord) + 'static>(sink: F) {
+    SINKS.with(|sinks| sinks.borrow_mut().push(Box::new(sink)));
+}
+
+/// Removes all the sinks registered via [`add_sink()`].
+pub fn clear_sinks() {
+    SINKS.with(|sinks| sinks.borrow_mut().clear());
+}
+
+/// Returns [`LevelFilter`] applied to the records of the provided `target`.
+#[must_use]
+fn effective_level(target: &str) -> LevelFilter {
+    LogScope::of(target)
+        .and_then(LogScope::level)
+        .unwrap_or_else(level)
+}
+
+/// Updates [`log::max_level()`] to the most verbose of the configured
+/// [`LevelFilter`]s, so disabled records are filtered out without reaching
+/// the [`Logger`].
+///
+/// [`log::max_level()`]: ::log::max_level
+fn update_max_level() {
+    let max = LogScope::ALL
+        .iter()
+        .filter_map(|scope| scope.level())
+        .fold(level(), Ord::max);
+    ::log::set_max_level(max);
+}
+
+/// Converts the provided [`LevelFilter`] discriminant back into a
+/// [`LevelFilter`].
+#[must_use]
+fn level_filter(level: usize) -> LevelFilter {
+    match level {
+        0 => LevelFilter::Off,
+        1 => LevelFilter::Error,
+        2 => LevelFilter::Warn,
+        3 => LevelFilter::Info,
+        4 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
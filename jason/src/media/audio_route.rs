@@ -0,0 +1,65 @@
+//! Reacting to changes of the audio output route (e.g. headphones being
+//! unplugged) on mobile platforms.
+
+/// Reason of an audio output route change reported by the platform.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum AudioRouteChangeReason {
+    /// New audio output device became available (e.g. headphones are
+    /// plugged in).
+    NewDeviceAvailable = 0,
+
+    /// Previously used audio output device became unavailable (e.g.
+    /// headphones are unplugged), so the audio is routed to the speaker.
+    OldDeviceUnavailable = 1,
+
+    /// Audio output route changed for any other reason.
+    Other = 2,
+}
+
+impl From<u8> for AudioRouteChangeReason {
+    #[inline]
+    fn from(reason: u8) -> Self {
+        match reason {
+            0 => Self::NewDeviceAvailable,
+            1 => Self::OldDeviceUnavailable,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Policy of reacting to audio output route changes in a `Room`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum AudioRoutePolicy {
+    /// Audio output route changes are only reported, without touching any
+    /// media.
+    Ignore = 0,
+
+    /// Outbound audio is muted once the used audio output device becomes
+    /// unavailable, so the microphone doesn't start capturing the speaker
+    /// unexpectedly, as native conferencing applications do.
+    MuteOnDeviceUnavailable = 1,
+}
+
+impl AudioRoutePolicy {
+    /// Indicates whether outbound audio should be muted on an audio output
+    /// route change with the provided [`AudioRouteChangeReason`].
+    #[inline]
+    #[must_use]
+    pub fn should_mute(self, reason: AudioRouteChangeReason) -> bool {
+        match self {
+            Self::Ignore => false,
+            Self::MuteOnDeviceUnavailable => {
+                reason == AudioRouteChangeReason::OldDeviceUnavailable
+            }
+        }
+    }
+}
+
+impl Default for AudioRoutePolicy {
+    #[inline]
+    fn default() -> Self {
+        Self::Ignore
+    }
+}
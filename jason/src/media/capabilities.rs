@@ -0,0 +1,47 @@
+//! Capabilities of media input devices.
+
+use super::FacingMode;
+
+/// Range of values supported by a media input device.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CapabilityRange<T> {
+    /// Minimum supported value.
+    pub min: T,
+
+    /// Maximum supported value.
+    pub max: T,
+}
+
+/// Capabilities of a media input device, describing the constraints values it
+/// supports.
+///
+/// Representation of a [MediaTrackCapabilities][1].
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#dom-mediatrackcapabilities
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct InputDeviceCapabilities {
+    /// Supported sample rates of the audio in Hz.
+    pub sample_rate: Option<CapabilityRange<u32>>,
+
+    /// Supported numbers of independent audio channels.
+    pub channel_count: Option<CapabilityRange<u32>>,
+
+    /// Supported latencies of the audio in milliseconds.
+    pub latency: Option<CapabilityRange<u32>>,
+
+    /// Indicator whether the device supports isolating a voice from a
+    /// background noise.
+    pub voice_isolation: bool,
+
+    /// Supported widths of the video in pixels.
+    pub width: Option<CapabilityRange<u32>>,
+
+    /// Supported heights of the video in pixels.
+    pub height: Option<CapabilityRange<u32>>,
+
+    /// Supported frame rates of the video in frames per second.
+    pub frame_rate: Option<CapabilityRange<u32>>,
+
+    /// [`FacingMode`]s the device may face.
+    pub facing_mode: Vec<FacingMode>,
+}
@@ -2,16 +2,20 @@
 
 use std::{
     cell::{Cell, RefCell},
+    collections::HashMap,
     rc::Rc,
 };
 
 use medea_client_api_proto::{
-    AudioSettings as ProtoAudioConstraints, MediaSourceKind,
-    MediaType as ProtoTrackConstraints, MediaType, VideoSettings,
+    self as proto, AudioSettings as ProtoAudioConstraints, MediaSourceKind,
+    MediaType as ProtoTrackConstraints, MediaType, MemberId, VideoSettings,
 };
 
 use crate::{
-    media::{track::MediaStreamTrackState, MediaKind},
+    media::{
+        track::{local, MediaStreamTrackState},
+        AudioRoutePolicy, MediaKind,
+    },
     peer::{
         media_exchange_state, mute_state, LocalStreamUpdateCriteria, MediaState,
     },
@@ -40,12 +44,183 @@ pub enum FacingMode {
     Right = 3,
 }
 
+/// Describes focus modes of a camera.
+///
+/// Representation of a [MeteringMode][1].
+///
+/// [1]: https://w3.org/TR/image-capture#dom-meteringmode
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum FocusMode {
+    /// No focus is applied.
+    None = 0,
+
+    /// Focus is fixed at the distance set manually.
+    Manual = 1,
+
+    /// Focus is adjusted once and then fixed.
+    SingleShot = 2,
+
+    /// Focus is adjusted continuously.
+    Continuous = 3,
+}
+
+/// Hint of a video track contents, letting a browser choose how to encode it.
+///
+/// Representation of a [video content hint][1].
+///
+/// [1]: https://w3.org/TR/mst-content-hint#video-content-hints
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum ContentHint {
+    /// Video where motion is important (webcam or a video playback).
+    Motion = 0,
+
+    /// Video where details are important (presentation slides or web pages).
+    Detail = 1,
+
+    /// Video where details are extra important, containing text content.
+    Text = 2,
+}
+
+impl ContentHint {
+    /// Returns a [contentHint][1] value of this [`ContentHint`].
+    ///
+    /// [1]: https://w3.org/TR/mst-content-hint#dom-mediastreamtrack-contenthint
+    #[inline]
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Motion => "motion",
+            Self::Detail => "detail",
+            Self::Text => "text",
+        }
+    }
+}
+
+impl From<proto::ContentHint> for ContentHint {
+    #[inline]
+    fn from(hint: proto::ContentHint) -> Self {
+        match hint {
+            proto::ContentHint::Motion => Self::Motion,
+            proto::ContentHint::Detail => Self::Detail,
+            proto::ContentHint::Text => Self::Text,
+        }
+    }
+}
+
+/// Preference of what a browser should sacrifice when sending a video track
+/// under constrained bandwidth.
+///
+/// Representation of a [RTCDegradationPreference][1].
+///
+/// [1]: https://w3.org/TR/webrtc/#dom-rtcdegradationpreference
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum DegradationPreference {
+    /// Degrade resolution in order to maintain framerate.
+    MaintainFramerate = 0,
+
+    /// Degrade framerate in order to maintain resolution.
+    MaintainResolution = 1,
+
+    /// Degrade both framerate and resolution in a balanced way.
+    Balanced = 2,
+}
+
+impl DegradationPreference {
+    /// Returns a [RTCDegradationPreference][1] value of this
+    /// [`DegradationPreference`].
+    ///
+    /// [1]: https://w3.org/TR/webrtc/#dom-rtcdegradationpreference
+    #[inline]
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::MaintainFramerate => "maintain-framerate",
+            Self::MaintainResolution => "maintain-resolution",
+            Self::Balanced => "balanced",
+        }
+    }
+}
+
+impl From<proto::DegradationPreference> for DegradationPreference {
+    #[inline]
+    fn from(preference: proto::DegradationPreference) -> Self {
+        use proto::DegradationPreference as P;
+
+        match preference {
+            P::MaintainFramerate => Self::MaintainFramerate,
+            P::MaintainResolution => Self::MaintainResolution,
+            P::Balanced => Self::Balanced,
+        }
+    }
+}
+
+/// Strategy of muting a sent audio track.
+///
+/// Regardless of the chosen [`MuteStrategy`], the remote side is notified
+/// about muting in the same way, so the choice only affects what happens to
+/// the captured media locally.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum MuteStrategy {
+    /// Disable the sent track via its [`enabled`][1] property, so silence is
+    /// sent while the device keeps capturing.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack-enabled
+    DisableTrack = 0,
+
+    /// Replace the sent track with `null` via [replaceTrack()][2], so nothing
+    /// is sent while the device keeps capturing, allowing to unmute without
+    /// re-acquiring media.
+    ///
+    /// [2]: https://w3.org/TR/webrtc#dom-rtcrtpsender-replacetrack
+    ReplaceWithSilence = 1,
+
+    /// Stop capturing entirely, releasing the device (and turning off its
+    /// privacy indicator), and re-acquire it on unmuting.
+    ///
+    /// The device is released only once all the [`LocalMediaTrack`]s of it
+    /// are dropped by the application as well.
+    ///
+    /// [`LocalMediaTrack`]: crate::media::track::local::LocalMediaTrack
+    StopCapture = 2,
+}
+
+impl Default for MuteStrategy {
+    #[inline]
+    fn default() -> Self {
+        Self::DisableTrack
+    }
+}
+
 /// Local media stream for injecting into new created [`PeerConnection`]s.
 ///
 /// [`PeerConnection`]: crate::peer::PeerConnection
 #[derive(Clone, Debug, Default)]
 pub struct LocalTracksConstraints(Rc<RefCell<MediaStreamSettings>>);
 
+/// Policy of enabling the media received from remote `Member`s in a `Room`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum RecvMediaPolicy {
+    /// All the received media is enabled, unless it's disabled explicitly.
+    Auto = 0,
+
+    /// No received media is enabled, unless it's enabled explicitly for its
+    /// sending `Member`, so it's negotiated as disabled with a media server
+    /// from the very start.
+    Manual = 1,
+}
+
+impl Default for RecvMediaPolicy {
+    #[inline]
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
 /// Constraints to the media received from remote. Used to disable or enable
 /// media receiving.
 pub struct RecvConstraints {
@@ -54,6 +229,13 @@ pub struct RecvConstraints {
 
     /// Is video receiving enabled.
     is_video_enabled: Cell<bool>,
+
+    /// [`RecvMediaPolicy`] applied to the media of the `Member`s not
+    /// mentioned in the [`RecvConstraints::senders`].
+    policy: Cell<RecvMediaPolicy>,
+
+    /// Explicitly enabled or disabled media receiving from the `Member`s.
+    senders: RefCell<HashMap<MemberId, bool>>,
 }
 
 impl Default for RecvConstraints {
@@ -61,11 +243,48 @@ impl Default for RecvConstraints {
         Self {
             is_audio_enabled: Cell::new(true),
             is_video_enabled: Cell::new(true),
+            policy: Cell::new(RecvMediaPolicy::default()),
+            senders: RefCell::new(HashMap::new()),
         }
     }
 }
 
 impl RecvConstraints {
+    /// Sets the [`RecvMediaPolicy`] applied to the media of the `Member`s,
+    /// which receiving wasn't enabled or disabled explicitly.
+    #[inline]
+    pub fn set_policy(&self, policy: RecvMediaPolicy) {
+        self.policy.set(policy);
+    }
+
+    /// Enables or disables media receiving from the `Member` with the provided
+    /// ID.
+    #[inline]
+    pub fn set_enabled_from(&self, enabled: bool, sender_id: MemberId) {
+        drop(self.senders.borrow_mut().insert(sender_id, enabled));
+    }
+
+    /// Indicates whether receiving of the media with the provided
+    /// [`MediaKind`] from the `Member` with the provided ID is enabled.
+    #[must_use]
+    pub fn is_enabled_from(
+        &self,
+        kind: MediaKind,
+        sender_id: &MemberId,
+    ) -> bool {
+        let kind_enabled = match kind {
+            MediaKind::Audio => self.is_audio_enabled(),
+            MediaKind::Video => self.is_video_enabled(),
+        };
+        kind_enabled
+            && self
+                .senders
+                .borrow()
+                .get(sender_id)
+                .copied()
+                .unwrap_or_else(|| self.policy.get() == RecvMediaPolicy::Auto)
+    }
+
     /// Enables or disables audio or video receiving.
     pub fn set_enabled(&self, enabled: bool, kind: MediaKind) {
         match kind {
@@ -153,6 +372,15 @@ impl LocalTracksConstraints {
             .set_media_exchange_state_by_kinds(state, kinds)
     }
 
+    /// Returns [`LocalStreamUpdateCriteria`] with all the [`MediaKind`] and
+    /// [`MediaSourceKind`] pairs enabled in the underlying
+    /// [`MediaStreamSettings`].
+    #[inline]
+    #[must_use]
+    pub fn enabled_kinds(&self) -> LocalStreamUpdateCriteria {
+        self.0.borrow().enabled_kinds()
+    }
+
     /// Indicates whether provided [`MediaType`] is enabled in the underlying
     /// [`MediaStreamSettings`].
     #[inline]
@@ -180,6 +408,35 @@ impl LocalTracksConstraints {
     ) -> bool {
         self.0.borrow().is_track_enabled(kind, source)
     }
+
+    /// Returns [`ContentHint`] and [`DegradationPreference`] configured for
+    /// the provided [`MediaKind`] and [`MediaSourceKind`] in this
+    /// [`LocalTracksConstraints`].
+    #[inline]
+    #[must_use]
+    pub fn video_encoding(
+        &self,
+        kind: MediaKind,
+        source: MediaSourceKind,
+    ) -> (Option<ContentHint>, Option<DegradationPreference>) {
+        self.0.borrow().video_encoding(kind, source)
+    }
+
+    /// Returns [`MuteStrategy`] configured for the provided [`MediaKind`] in
+    /// this [`LocalTracksConstraints`].
+    #[inline]
+    #[must_use]
+    pub fn mute_strategy(&self, kind: MediaKind) -> MuteStrategy {
+        self.0.borrow().mute_strategy(kind)
+    }
+
+    /// Returns [`AudioRoutePolicy`] configured in this
+    /// [`LocalTracksConstraints`].
+    #[inline]
+    #[must_use]
+    pub fn audio_route_policy(&self) -> AudioRoutePolicy {
+        self.0.borrow().audio_route_policy()
+    }
 }
 
 /// [MediaStreamConstraints][1] for the audio media type.
@@ -400,25 +657,36 @@ impl MediaStreamSettings {
 }
 
 impl MediaStreamSettings {
-    /// Indicates whether the provided [`platform::MediaStreamTrack`] satisfies
-    /// some of the [`VideoTrackConstraints`] from this [`MediaStreamSettings`].
+    /// Indicates whether the provided [`local::Track`] satisfies the
+    /// [`VideoTrackConstraints`] of its [`MediaSourceKind`] from this
+    /// [`MediaStreamSettings`].
+    ///
+    /// Unconstrains [`VideoTrackConstraints`] which this [`local::Track`]
+    /// satisfies.
     ///
-    /// Unconstrains [`VideoTrackConstraints`] which this
-    /// [`platform::MediaStreamTrack`] satisfies.
+    /// [`MediaSourceKind`] of the [`local::Track`] is known for sure, so a
+    /// device video [`local::Track`] is never used for display video and vice
+    /// versa, even if both of them are published simultaneously.
     #[must_use]
-    pub fn unconstrain_if_satisfies_video<T>(&mut self, track: T) -> bool
-    where
-        T: AsRef<platform::MediaStreamTrack>,
-    {
-        if self.device_video.satisfies(&track) {
-            self.device_video.unconstrain();
-            true
-        } else if self.display_video.satisfies(&track) {
-            self.display_video.unconstrain();
-            true
-        } else {
-            false
+    pub fn unconstrain_if_satisfies_video(
+        &mut self,
+        track: &local::Track,
+    ) -> bool {
+        match track.media_source_kind() {
+            MediaSourceKind::Device => {
+                if self.device_video.satisfies(track) {
+                    self.device_video.unconstrain();
+                    return true;
+                }
+            }
+            MediaSourceKind::Display => {
+                if self.display_video.satisfies(track) {
+                    self.display_video.unconstrain();
+                    return true;
+                }
+            }
         }
+        false
     }
 
     /// Returns [`LocalStreamUpdateCriteria`] with [`MediaKind`] and
@@ -609,6 +877,23 @@ impl MediaStreamSettings {
         self.display_video.enabled()
     }
 
+    /// Returns [`LocalStreamUpdateCriteria`] with all the [`MediaKind`] and
+    /// [`MediaSourceKind`] pairs enabled in this [`MediaStreamSettings`].
+    #[must_use]
+    pub fn enabled_kinds(&self) -> LocalStreamUpdateCriteria {
+        let mut kinds = LocalStreamUpdateCriteria::empty();
+        if self.is_audio_enabled() {
+            kinds.add(MediaKind::Audio, MediaSourceKind::Device);
+        }
+        if self.is_device_video_enabled() {
+            kinds.add(MediaKind::Video, MediaSourceKind::Device);
+        }
+        if self.is_display_video_enabled() {
+            kinds.add(MediaKind::Video, MediaSourceKind::Display);
+        }
+        kinds
+    }
+
     /// Indicates whether the given [`MediaType`] is enabled and constrained in
     /// this [`MediaStreamSettings`].
     #[inline]
@@ -638,6 +923,52 @@ impl MediaStreamSettings {
         }
     }
 
+    /// Returns [`ContentHint`] and [`DegradationPreference`] configured for
+    /// the provided [`MediaKind`] and [`MediaSourceKind`] in this
+    /// [`MediaStreamSettings`].
+    #[must_use]
+    pub fn video_encoding(
+        &self,
+        kind: MediaKind,
+        source: MediaSourceKind,
+    ) -> (Option<ContentHint>, Option<DegradationPreference>) {
+        match (kind, source) {
+            (MediaKind::Audio, _) => (None, None),
+            (MediaKind::Video, MediaSourceKind::Device) => {
+                self.get_device_video().map_or((None, None), |c| {
+                    (c.content_hint, c.degradation_preference)
+                })
+            }
+            (MediaKind::Video, MediaSourceKind::Display) => {
+                self.get_display_video().map_or((None, None), |c| {
+                    (c.content_hint, c.degradation_preference)
+                })
+            }
+        }
+    }
+
+    /// Returns [`MuteStrategy`] configured for the provided [`MediaKind`] in
+    /// this [`MediaStreamSettings`].
+    ///
+    /// Video tracks are always muted with a [`MuteStrategy::DisableTrack`].
+    #[must_use]
+    pub fn mute_strategy(&self, kind: MediaKind) -> MuteStrategy {
+        match kind {
+            MediaKind::Audio => {
+                self.audio.constraints.mute_strategy.unwrap_or_default()
+            }
+            MediaKind::Video => MuteStrategy::DisableTrack,
+        }
+    }
+
+    /// Returns [`AudioRoutePolicy`] configured in this
+    /// [`MediaStreamSettings`].
+    #[inline]
+    #[must_use]
+    pub fn audio_route_policy(&self) -> AudioRoutePolicy {
+        self.audio.constraints.route_policy.unwrap_or_default()
+    }
+
     /// Indicates whether the given [`MediaKind`] and [`MediaSourceKind`] are
     /// enabled in this [`MediaStreamSettings`].
     #[inline]
@@ -806,11 +1137,18 @@ impl From<VideoSettings> for VideoSource {
                     width: None,
                     height: None,
                     required: settings.required,
+                    content_hint: None,
+                    degradation_preference: None,
+                    torch: None,
+                    zoom: None,
+                    focus_mode: None,
                 })
             }
             MediaSourceKind::Display => {
                 VideoSource::Display(DisplayVideoTrackConstraints {
                     required: settings.required,
+                    content_hint: None,
+                    degradation_preference: None,
                 })
             }
         }
@@ -903,6 +1241,25 @@ pub struct AudioTrackConstraints {
     /// If `true` then without this [`AudioTrackConstraints`] call session
     /// can't be started.
     required: bool,
+
+    /// Sample rate of the audio in Hz.
+    pub sample_rate: Option<ConstrainU32>,
+
+    /// Number of independent audio channels.
+    pub channel_count: Option<ConstrainU32>,
+
+    /// Latency of the audio in milliseconds.
+    pub latency: Option<ConstrainU32>,
+
+    /// Indicator whether the audio should be processed to isolate a voice from
+    /// a background noise.
+    pub voice_isolation: Option<bool>,
+
+    /// [`MuteStrategy`] of the sent audio track.
+    pub mute_strategy: Option<MuteStrategy>,
+
+    /// [`AudioRoutePolicy`] of the sent audio track.
+    pub route_policy: Option<AudioRoutePolicy>,
 }
 
 impl AudioTrackConstraints {
@@ -921,6 +1278,82 @@ impl AudioTrackConstraints {
         self.device_id = Some(ConstrainString::Exact(device_id));
     }
 
+    /// Sets exact [`sampleRate`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-sampleRate
+    #[inline]
+    pub fn exact_sample_rate(&mut self, sample_rate: u32) {
+        self.sample_rate = Some(ConstrainU32::Exact(sample_rate));
+    }
+
+    /// Sets ideal [`sampleRate`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-sampleRate
+    #[inline]
+    pub fn ideal_sample_rate(&mut self, sample_rate: u32) {
+        self.sample_rate = Some(ConstrainU32::Ideal(sample_rate));
+    }
+
+    /// Sets range of [`sampleRate`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-sampleRate
+    #[inline]
+    pub fn sample_rate_in_range(&mut self, min: u32, max: u32) {
+        self.sample_rate = Some(ConstrainU32::Range(min, max));
+    }
+
+    /// Sets exact [`channelCount`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-channelCount
+    #[inline]
+    pub fn exact_channel_count(&mut self, channel_count: u32) {
+        self.channel_count = Some(ConstrainU32::Exact(channel_count));
+    }
+
+    /// Sets ideal [`channelCount`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-channelCount
+    #[inline]
+    pub fn ideal_channel_count(&mut self, channel_count: u32) {
+        self.channel_count = Some(ConstrainU32::Ideal(channel_count));
+    }
+
+    /// Sets exact [`latency`][1] constraint in milliseconds.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-latency
+    #[inline]
+    pub fn exact_latency(&mut self, latency: u32) {
+        self.latency = Some(ConstrainU32::Exact(latency));
+    }
+
+    /// Sets ideal [`latency`][1] constraint in milliseconds.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#def-constraint-latency
+    #[inline]
+    pub fn ideal_latency(&mut self, latency: u32) {
+        self.latency = Some(ConstrainU32::Ideal(latency));
+    }
+
+    /// Sets [`voiceIsolation`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dfn-voiceisolation
+    #[inline]
+    pub fn voice_isolation(&mut self, enabled: bool) {
+        self.voice_isolation = Some(enabled);
+    }
+
+    /// Sets [`MuteStrategy`] of the sent audio track.
+    #[inline]
+    pub fn mute_strategy(&mut self, strategy: MuteStrategy) {
+        self.mute_strategy = Some(strategy);
+    }
+
+    /// Sets [`AudioRoutePolicy`] of the sent audio track.
+    #[inline]
+    pub fn route_policy(&mut self, policy: AudioRoutePolicy) {
+        self.route_policy = Some(policy);
+    }
+
     /// Checks whether the provided [`platform::MediaStreamTrack`] satisfies
     /// contained constraints.
     #[inline]
@@ -932,6 +1365,11 @@ impl AudioTrackConstraints {
         let track = track.as_ref();
         satisfies_track(track, MediaKind::Audio)
             && ConstrainString::satisfies(&self.device_id, &track.device_id())
+            && ConstrainU32::satisfies(self.sample_rate, track.sample_rate())
+            && ConstrainU32::satisfies(
+                self.channel_count,
+                track.channel_count(),
+            )
         // TODO returns Result<bool, Error>
     }
 
@@ -946,6 +1384,24 @@ impl AudioTrackConstraints {
         if !self.required && another.required {
             self.required = another.required;
         }
+        if self.sample_rate.is_none() && another.sample_rate.is_some() {
+            self.sample_rate = another.sample_rate;
+        }
+        if self.channel_count.is_none() && another.channel_count.is_some() {
+            self.channel_count = another.channel_count;
+        }
+        if self.latency.is_none() && another.latency.is_some() {
+            self.latency = another.latency;
+        }
+        if self.voice_isolation.is_none() && another.voice_isolation.is_some() {
+            self.voice_isolation = another.voice_isolation;
+        }
+        if self.mute_strategy.is_none() && another.mute_strategy.is_some() {
+            self.mute_strategy = another.mute_strategy;
+        }
+        if self.route_policy.is_none() && another.route_policy.is_some() {
+            self.route_policy = another.route_policy;
+        }
     }
 
     /// Returns an importance of these [`AudioTrackConstraints`].
@@ -965,6 +1421,17 @@ impl From<ProtoAudioConstraints> for AudioTrackConstraints {
         Self {
             required: caps.required,
             device_id: None,
+            sample_rate: None,
+            // Stereo encoding is pointless for a mono source, so a stereo one
+            // is preferred.
+            channel_count: caps
+                .opus
+                .filter(|opus| opus.stereo)
+                .map(|_| ConstrainU32::Ideal(2)),
+            latency: None,
+            voice_isolation: None,
+            mute_strategy: None,
+            route_policy: None,
         }
     }
 }
@@ -981,6 +1448,18 @@ impl AsRef<str> for FacingMode {
     }
 }
 
+impl AsRef<str> for FocusMode {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        match self {
+            FocusMode::None => "none",
+            FocusMode::Manual => "manual",
+            FocusMode::SingleShot => "single-shot",
+            FocusMode::Continuous => "continuous",
+        }
+    }
+}
+
 /// Representation of a [ConstrainULong][1].
 ///
 /// Underlying value must fit in a `[0, 4294967295]` range.
@@ -1064,6 +1543,21 @@ pub struct DeviceVideoTrackConstraints {
 
     /// Width of the video in pixels.
     pub width: Option<ConstrainU32>,
+
+    /// [`ContentHint`] applied to the sent video track.
+    pub content_hint: Option<ContentHint>,
+
+    /// [`DegradationPreference`] applied to the sender of the video track.
+    pub degradation_preference: Option<DegradationPreference>,
+
+    /// Indicator whether the camera's fill light should be continuously on.
+    pub torch: Option<bool>,
+
+    /// Zoom level of the camera in percents (`100` means no zoom).
+    pub zoom: Option<ConstrainU32>,
+
+    /// [`FocusMode`] of the camera.
+    pub focus_mode: Option<ConstrainString<FocusMode>>,
 }
 
 /// Constraints applicable to video tracks that are sourced from screen-capture.
@@ -1148,6 +1642,69 @@ impl DeviceVideoTrackConstraints {
         self.width = Some(ConstrainU32::Range(min, max));
     }
 
+    /// Sets [`ContentHint`] of the sent video track.
+    #[inline]
+    pub fn content_hint(&mut self, hint: ContentHint) {
+        self.content_hint = Some(hint);
+    }
+
+    /// Sets [`DegradationPreference`] of the sender of the video track.
+    #[inline]
+    pub fn degradation_preference(
+        &mut self,
+        preference: DegradationPreference,
+    ) {
+        self.degradation_preference = Some(preference);
+    }
+
+    /// Sets [`torch`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-torch
+    #[inline]
+    pub fn torch(&mut self, enabled: bool) {
+        self.torch = Some(enabled);
+    }
+
+    /// Sets exact [`zoom`][1] constraint in percents.
+    ///
+    /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-zoom
+    #[inline]
+    pub fn exact_zoom(&mut self, zoom: u32) {
+        self.zoom = Some(ConstrainU32::Exact(zoom));
+    }
+
+    /// Sets ideal [`zoom`][1] constraint in percents.
+    ///
+    /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-zoom
+    #[inline]
+    pub fn ideal_zoom(&mut self, zoom: u32) {
+        self.zoom = Some(ConstrainU32::Ideal(zoom));
+    }
+
+    /// Sets range of [`zoom`][1] constraint in percents.
+    ///
+    /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-zoom
+    #[inline]
+    pub fn zoom_in_range(&mut self, min: u32, max: u32) {
+        self.zoom = Some(ConstrainU32::Range(min, max));
+    }
+
+    /// Sets exact [`focusMode`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-focusmode
+    #[inline]
+    pub fn exact_focus_mode(&mut self, focus_mode: FocusMode) {
+        self.focus_mode = Some(ConstrainString::Exact(focus_mode));
+    }
+
+    /// Sets ideal [`focusMode`][1] constraint.
+    ///
+    /// [1]: https://w3.org/TR/image-capture#dom-mediatracksettings-focusmode
+    #[inline]
+    pub fn ideal_focus_mode(&mut self, focus_mode: FocusMode) {
+        self.focus_mode = Some(ConstrainString::Ideal(focus_mode));
+    }
+
     /// Checks whether the provided [`platform::MediaStreamTrack`] satisfies
     /// contained [`DeviceVideoTrackConstraints`].
     #[must_use]
@@ -1186,6 +1743,21 @@ impl DeviceVideoTrackConstraints {
         if self.width.is_none() && another.width.is_some() {
             self.width = another.width;
         }
+        if self.content_hint.is_none() {
+            self.content_hint = another.content_hint;
+        }
+        if self.degradation_preference.is_none() {
+            self.degradation_preference = another.degradation_preference;
+        }
+        if self.torch.is_none() && another.torch.is_some() {
+            self.torch = another.torch;
+        }
+        if self.zoom.is_none() && another.zoom.is_some() {
+            self.zoom = another.zoom;
+        }
+        if self.focus_mode.is_none() && another.focus_mode.is_some() {
+            self.focus_mode = another.focus_mode;
+        }
     }
 
     /// Returns an importance of these [`DeviceVideoTrackConstraints`].
@@ -1207,6 +1779,12 @@ pub struct DisplayVideoTrackConstraints {
     /// If `true` then without these [`DisplayVideoTrackConstraints`] a session
     /// call can't be started.
     required: bool,
+
+    /// [`ContentHint`] applied to the sent video track.
+    pub content_hint: Option<ContentHint>,
+
+    /// [`DegradationPreference`] applied to the sender of the video track.
+    pub degradation_preference: Option<DegradationPreference>,
 }
 
 impl DisplayVideoTrackConstraints {
@@ -1232,6 +1810,21 @@ impl DisplayVideoTrackConstraints {
             && track.guess_is_from_display()
     }
 
+    /// Sets [`ContentHint`] of the sent video track.
+    #[inline]
+    pub fn content_hint(&mut self, hint: ContentHint) {
+        self.content_hint = Some(hint);
+    }
+
+    /// Sets [`DegradationPreference`] of the sender of the video track.
+    #[inline]
+    pub fn degradation_preference(
+        &mut self,
+        preference: DegradationPreference,
+    ) {
+        self.degradation_preference = Some(preference);
+    }
+
     /// Merges these [`DisplayVideoTrackConstraints`] with `another` ones,
     /// meaning that if some constraints are not set on these ones, then they
     /// will be applied from `another`.
@@ -1240,6 +1833,12 @@ impl DisplayVideoTrackConstraints {
         if !self.required && another.required {
             self.required = another.required;
         }
+        if self.content_hint.is_none() {
+            self.content_hint = another.content_hint;
+        }
+        if self.degradation_preference.is_none() {
+            self.degradation_preference = another.degradation_preference;
+        }
     }
 
     /// Returns an importance of this [`DisplayVideoTrackConstraints`].
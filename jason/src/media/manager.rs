@@ -3,17 +3,19 @@
 use std::{
     cell::RefCell,
     collections::HashMap,
+    mem,
     rc::{Rc, Weak},
 };
 
 use derive_more::{Display, From, Into};
+use futures::future;
 use medea_client_api_proto::MediaSourceKind;
 use tracerr::Traced;
 
 use crate::{
     media::{
-        track::MediaStreamTrackState, MediaKind, MediaStreamSettings,
-        MultiSourceTracksConstraints,
+        track::MediaStreamTrackState, MediaKind, MediaPermissions,
+        MediaStreamSettings, MultiSourceTracksConstraints, PermissionState,
     },
     platform,
     utils::JsCaused,
@@ -146,6 +148,45 @@ impl InnerMediaManager {
             .map_err(tracerr::wrap!())
     }
 
+    /// Queries [`MediaPermissions`] without prompting a user.
+    ///
+    /// If the platform can't report a camera or microphone
+    /// [`PermissionState`], then it's considered [`PermissionState::Granted`]
+    /// if the platform exposes a label of some device of this kind (which
+    /// happens only once the permission is granted), or
+    /// [`PermissionState::Unknown`] otherwise.
+    async fn query_permissions() -> MediaPermissions {
+        let (camera, microphone, screen) = future::join3(
+            platform::query_permission("camera"),
+            platform::query_permission("microphone"),
+            platform::query_permission("display-capture"),
+        )
+        .await;
+
+        let (camera, microphone) = if camera.is_some() && microphone.is_some() {
+            (camera, microphone)
+        } else {
+            let devices =
+                platform::enumerate_devices().await.unwrap_or_default();
+            let labeled = |kind| {
+                devices
+                    .iter()
+                    .any(|d| d.kind() == kind && !d.label().is_empty())
+                    .then(|| PermissionState::Granted)
+            };
+            (
+                camera.or_else(|| labeled(MediaKind::Video)),
+                microphone.or_else(|| labeled(MediaKind::Audio)),
+            )
+        };
+
+        MediaPermissions {
+            camera: camera.unwrap_or(PermissionState::Unknown),
+            microphone: microphone.unwrap_or(PermissionState::Unknown),
+            screen: screen.unwrap_or(PermissionState::Unknown),
+        }
+    }
+
     /// Obtains [`local::Track`]s based on a provided
     /// [`MediaStreamSettings`]. This can be the tracks that were acquired
     /// earlier, or new tracks, acquired via [getUserMedia()][1] or/and
@@ -256,9 +297,7 @@ impl InnerMediaManager {
         tracks.extend(
             storage
                 .iter()
-                .filter(|&track| {
-                    caps.unconstrain_if_satisfies_video(track.as_ref())
-                })
+                .filter(|&track| caps.unconstrain_if_satisfies_video(track))
                 .cloned(),
         );
 
@@ -374,6 +413,17 @@ impl MediaManager {
             .map_err(tracerr::wrap!())
     }
 
+    /// Stops all the [`local::Track`]s obtained by this [`MediaManager`],
+    /// regardless of any references to them still being held, and forgets
+    /// them.
+    pub fn stop_tracks(&self) {
+        let tracks = mem::take(&mut *self.0.tracks.borrow_mut());
+        for track in tracks.values().filter_map(Weak::upgrade) {
+            let platform_track: &platform::MediaStreamTrack = (*track).as_ref();
+            platform_track.stop();
+        }
+    }
+
     /// Instantiates a new [`MediaManagerHandle`] for external usage.
     #[inline]
     #[must_use]
@@ -415,6 +465,17 @@ impl MediaManagerHandle {
             .map_err(tracerr::map_from_and_wrap!())
     }
 
+    /// Returns [`MediaPermissions`] of using a camera, a microphone and
+    /// capturing a screen, without prompting a user.
+    ///
+    /// Uses [Permissions API][1] where available, falling back to
+    /// [`PermissionState::Unknown`] for the permissions it can't report.
+    ///
+    /// [1]: https://w3.org/TR/permissions
+    pub async fn query_permissions(&self) -> MediaPermissions {
+        InnerMediaManager::query_permissions().await
+    }
+
     /// Returns [`local::LocalMediaTrack`]s objects, built from the provided
     /// [`MediaStreamSettings`].
     ///
@@ -2,25 +2,33 @@
 //!
 //! [1]: https://w3.org/TR/mediacapture-streams
 
+mod audio_route;
+mod capabilities;
 pub mod constraints;
 mod manager;
+mod permissions;
 pub mod track;
 
 use derive_more::Display;
 
 #[doc(inline)]
 pub use self::{
+    audio_route::{AudioRouteChangeReason, AudioRoutePolicy},
+    capabilities::{CapabilityRange, InputDeviceCapabilities},
     constraints::{
-        AudioMediaTracksSettings, AudioTrackConstraints,
-        DeviceVideoTrackConstraints, DisplayVideoTrackConstraints, FacingMode,
-        LocalTracksConstraints, MediaStreamSettings,
-        MultiSourceTracksConstraints, RecvConstraints, TrackConstraints,
-        VideoSource, VideoTrackConstraints,
+        AudioMediaTracksSettings, AudioTrackConstraints, ContentHint,
+        DegradationPreference, DeviceVideoTrackConstraints,
+        DisplayVideoTrackConstraints, FacingMode, FocusMode,
+        LocalTracksConstraints,
+        MediaStreamSettings, MultiSourceTracksConstraints, MuteStrategy,
+        RecvConstraints, RecvMediaPolicy, TrackConstraints, VideoSource,
+        VideoTrackConstraints,
     },
     manager::{
         EnumerateDevicesError, GetDisplayMediaError, GetUserMediaError,
         InitLocalTracksError, MediaManager, MediaManagerHandle,
     },
+    permissions::{MediaPermissions, PermissionState},
     track::MediaSourceKind,
 };
 
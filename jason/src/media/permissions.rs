@@ -0,0 +1,55 @@
+//! States of permissions to use media devices.
+
+/// State of a permission to use some media.
+///
+/// Representation of a [PermissionState][1].
+///
+/// [1]: https://w3.org/TR/permissions#dom-permissionstate
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum PermissionState {
+    /// Permission is granted, so media can be acquired without prompting a
+    /// user.
+    Granted = 0,
+
+    /// Permission is denied, so media acquisition will fail without prompting
+    /// a user.
+    Denied = 1,
+
+    /// User will be prompted for a permission on media acquisition.
+    Prompt = 2,
+
+    /// Permission state can't be determined on the current platform without
+    /// prompting a user.
+    Unknown = 3,
+}
+
+impl PermissionState {
+    /// Parses a [`PermissionState`] from the provided [PermissionState][1]
+    /// string.
+    ///
+    /// [1]: https://w3.org/TR/permissions#dom-permissionstate
+    #[must_use]
+    pub fn from_name(state: &str) -> Self {
+        match state {
+            "granted" => Self::Granted,
+            "denied" => Self::Denied,
+            "prompt" => Self::Prompt,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// States of permissions to use media devices, queried without prompting a
+/// user.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MediaPermissions {
+    /// [`PermissionState`] of using a camera.
+    pub camera: PermissionState,
+
+    /// [`PermissionState`] of using a microphone.
+    pub microphone: PermissionState,
+
+    /// [`PermissionState`] of capturing a screen.
+    pub screen: PermissionState,
+}
@@ -6,14 +6,36 @@
 
 use std::rc::Rc;
 
-use derive_more::AsRef;
+use derive_more::{AsRef, Display, From};
 use medea_client_api_proto as proto;
+use tracerr::Traced;
 
 use crate::{
-    media::{MediaKind, MediaSourceKind},
+    media::{
+        track::MediaStreamTrackState, ContentHint, DeviceVideoTrackConstraints,
+        MediaKind, MediaSourceKind,
+    },
     platform,
+    utils::JsCaused,
 };
 
+/// Errors returned from the [`LocalMediaTrack::apply_advanced()`] method.
+#[derive(Clone, Debug, Display, From, JsCaused)]
+#[js(error = "platform::Error")]
+pub enum ApplyAdvancedError {
+    /// [`LocalMediaTrack`] is not a video track captured from a device.
+    #[display(
+        fmt = "Advanced constraints are applicable to device video tracks only"
+    )]
+    NotDeviceVideo,
+
+    /// [applyConstraints()][1] request failed.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediastreamtrack-applyconstraints
+    #[display(fmt = "MediaStreamTrack.applyConstraints() failed: {}", _0)]
+    ApplyConstraintsFailed(platform::Error),
+}
+
 /// Wrapper around a [`platform::MediaStreamTrack`] received from a
 /// [getUserMedia()][1]/[getDisplayMedia()][2] request.
 ///
@@ -36,8 +58,8 @@ pub struct Track {
     /// Parent will be [`None`] if this [`Track`] wasn't forked from another
     /// [`Track`].
     ///
-    /// This field is used only for holding strong reference to the parent.
-    _parent: Option<Rc<Self>>,
+    /// Holds a strong reference to the parent.
+    parent: Option<Rc<Self>>,
 }
 
 impl Track {
@@ -52,7 +74,7 @@ impl Track {
         Self {
             track,
             source_kind,
-            _parent: None,
+            parent: None,
         }
     }
 
@@ -66,6 +88,16 @@ impl Track {
         self.track.set_enabled(enabled);
     }
 
+    /// Changes [`contentHint`][1] attribute on the underlying
+    /// [MediaStreamTrack][2].
+    ///
+    /// [1]: https://w3.org/TR/mst-content-hint#dom-mediastreamtrack-contenthint
+    /// [2]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+    #[inline]
+    pub fn set_content_hint(&self, hint: ContentHint) {
+        self.track.set_content_hint(hint);
+    }
+
     /// Returns [`id`] of underlying [MediaStreamTrack][2].
     ///
     /// [`id`]: https://w3.org/TR/mediacapture-streams#dom-mediastreamtrack-id
@@ -90,6 +122,13 @@ impl Track {
         self.track.kind()
     }
 
+    /// Returns the [`Track`] this [`Track`] was forked from, if any.
+    #[inline]
+    #[must_use]
+    pub fn parent(&self) -> Option<&Rc<Self>> {
+        self.parent.as_ref()
+    }
+
     /// Forks this [`Track`].
     ///
     /// Creates a new [`Track`] from this [`Track`]'s
@@ -105,7 +144,7 @@ impl Track {
         Self {
             track,
             source_kind: self.source_kind,
-            _parent: Some(parent),
+            parent: Some(parent),
         }
     }
 }
@@ -117,19 +156,102 @@ impl Drop for Track {
     }
 }
 
+/// Reason of a [`LocalMediaTrack`] being passed to the `on_local_track`
+/// callback of a `Room`.
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+#[repr(u8)]
+pub enum LocalTrackReason {
+    /// [`LocalMediaTrack`] is published for the first time.
+    Initial = 0,
+
+    /// [`LocalMediaTrack`] replaces the previous one, captured from another
+    /// device.
+    DeviceSwitch = 1,
+
+    /// [`LocalMediaTrack`] replaces the previous one, captured from the same
+    /// device with other constraints.
+    ConstraintsChange = 2,
+
+    /// [`LocalMediaTrack`] replaces the previous one, which was ended by the
+    /// UA or the hardware (device was unplugged or muted physically, for
+    /// example).
+    Ended = 3,
+}
+
+impl LocalTrackReason {
+    /// Determines [`LocalTrackReason`] of the `new` [`Track`] replacing the
+    /// `previous` one.
+    #[must_use]
+    pub fn of(previous: Option<&Track>, new: &Track) -> Self {
+        let previous = if let Some(previous) = previous {
+            &previous.track
+        } else {
+            return Self::Initial;
+        };
+        if previous.ready_state() == MediaStreamTrackState::Ended {
+            Self::Ended
+        } else if previous.device_id() == new.track.device_id() {
+            Self::ConstraintsChange
+        } else {
+            Self::DeviceSwitch
+        }
+    }
+}
+
 /// Strongly referenced [`Track`] received from a
 /// [getUserMedia()][1]/[getDisplayMedia()][2] request.
 ///
 /// [1]: https://w3.org/TR/mediacapture-streams#dom-mediadevices-getusermedia
 /// [2]: https://w3.org/TR/screen-capture/#dom-mediadevices-getdisplaymedia
-pub struct LocalMediaTrack(Rc<Track>);
+#[derive(Clone)]
+pub struct LocalMediaTrack {
+    /// Underlying [`Track`].
+    track: Rc<Track>,
+
+    /// Reason of this [`LocalMediaTrack`] being passed to the
+    /// `on_local_track` callback.
+    reason: LocalTrackReason,
+
+    /// [`Track`] replaced by this [`LocalMediaTrack`], if any.
+    previous: Option<Rc<Track>>,
+}
 
 impl LocalMediaTrack {
     /// Createsa  new [`LocalMediaTrack`] from the provided [`Track`].
     #[inline]
     #[must_use]
     pub fn new(track: Rc<Track>) -> Self {
-        LocalMediaTrack(track)
+        Self {
+            track,
+            reason: LocalTrackReason::Initial,
+            previous: None,
+        }
+    }
+
+    /// Creates a new [`LocalMediaTrack`] from the provided [`Track`], which
+    /// replaces the `previous` one.
+    #[must_use]
+    pub fn replacing(track: Rc<Track>, previous: Option<Rc<Track>>) -> Self {
+        Self {
+            reason: LocalTrackReason::of(previous.as_deref(), &track),
+            track,
+            previous,
+        }
+    }
+
+    /// Returns [`LocalTrackReason`] of this [`LocalMediaTrack`] being passed
+    /// to the `on_local_track` callback.
+    #[inline]
+    #[must_use]
+    pub fn reason(&self) -> LocalTrackReason {
+        self.reason
+    }
+
+    /// Returns [`LocalMediaTrack`] replaced by this one, if any.
+    #[inline]
+    #[must_use]
+    pub fn previous(&self) -> Option<Self> {
+        self.previous.as_ref().map(|t| Self::new(Rc::clone(t)))
     }
 
     /// Returns the underlying [`platform::MediaStreamTrack`] of this
@@ -137,7 +259,7 @@ impl LocalMediaTrack {
     #[inline]
     #[must_use]
     pub fn get_track(&self) -> &platform::MediaStreamTrack {
-        &self.0.track
+        &self.track.track
     }
 
     /// Returns a [`MediaKind::Audio`] if this [`LocalMediaTrack`] represents an
@@ -145,7 +267,7 @@ impl LocalMediaTrack {
     #[inline]
     #[must_use]
     pub fn kind(&self) -> MediaKind {
-        self.0.kind()
+        self.track.kind()
     }
 
     /// Returns a [`MediaSourceKind::Device`] if this [`LocalMediaTrack`] is
@@ -157,6 +279,31 @@ impl LocalMediaTrack {
     #[inline]
     #[must_use]
     pub fn media_source_kind(&self) -> MediaSourceKind {
-        self.0.media_source_kind().into()
+        self.track.media_source_kind().into()
+    }
+
+    /// Applies the provided [`DeviceVideoTrackConstraints`] to this
+    /// [`LocalMediaTrack`] at runtime, adjusting the camera capture controls
+    /// (torch, zoom, focus mode) without re-acquiring the track.
+    ///
+    /// Constraints unsupported by the camera are ignored.
+    ///
+    /// # Errors
+    ///
+    /// See [`ApplyAdvancedError`] for details.
+    pub async fn apply_advanced(
+        &self,
+        constraints: DeviceVideoTrackConstraints,
+    ) -> Result<(), Traced<ApplyAdvancedError>> {
+        if self.kind() != MediaKind::Video
+            || self.media_source_kind() != MediaSourceKind::Device
+        {
+            return Err(tracerr::new!(ApplyAdvancedError::NotDeviceVideo));
+        }
+        self.track
+            .track
+            .apply_advanced(constraints)
+            .await
+            .map_err(tracerr::map_from_and_wrap!())
     }
 }
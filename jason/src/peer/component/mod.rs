@@ -5,7 +5,11 @@ mod local_sdp;
 mod tracks_repository;
 mod watchers;
 
-use std::{cell::Cell, collections::HashSet, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashSet,
+    rc::Rc,
+};
 
 use futures::{future::LocalBoxFuture, TryFutureExt as _};
 use medea_client_api_proto::{
@@ -104,10 +108,14 @@ pub struct State {
 
     /// Indicator whether this [`Component`] should relay all media through a
     /// TURN server forcibly.
-    force_relay: bool,
+    force_relay: Cell<bool>,
 
     /// List of [`IceServer`]s which this [`Component`] should use.
-    ice_servers: Vec<IceServer>,
+    ice_servers: RefCell<Vec<IceServer>>,
+
+    /// Indicator whether [`State::ice_servers`] or [`State::force_relay`] were
+    /// updated and should be applied to the [`PeerConnection`].
+    ice_policy_updated: Cell<bool>,
 
     /// Current [`NegotiationRole`] of this [`Component`].
     negotiation_role: ObservableCell<Option<NegotiationRole>>,
@@ -149,8 +157,9 @@ impl State {
             id,
             senders: TracksRepository::new(),
             receivers: TracksRepository::new(),
-            ice_servers,
-            force_relay,
+            ice_servers: RefCell::new(ice_servers),
+            force_relay: Cell::new(force_relay),
+            ice_policy_updated: Cell::new(false),
             remote_sdp: ProgressableCell::new(None),
             local_sdp: LocalSdp::new(),
             negotiation_role: ObservableCell::new(negotiation_role),
@@ -172,15 +181,15 @@ impl State {
     /// Returns all [`IceServer`]s of this [`State`].
     #[inline]
     #[must_use]
-    pub fn ice_servers(&self) -> &Vec<IceServer> {
-        &self.ice_servers
+    pub fn ice_servers(&self) -> Vec<IceServer> {
+        self.ice_servers.borrow().clone()
     }
 
     /// Indicates whether [`PeerConnection`] should be relayed forcibly.
     #[inline]
     #[must_use]
     pub fn force_relay(&self) -> bool {
-        self.force_relay
+        self.force_relay.get()
     }
 
     /// Inserts a new [`sender::State`] into this [`State`].
@@ -232,6 +241,20 @@ impl State {
         self.restart_ice.set(true);
     }
 
+    /// Updates [`IceServer`]s and ICE transport policy of this [`State`].
+    ///
+    /// They will be applied to the [`PeerConnection`] on the next
+    /// negotiation.
+    pub fn update_ice_policy(
+        &self,
+        ice_servers: Vec<IceServer>,
+        force_relay: bool,
+    ) {
+        self.ice_servers.replace(ice_servers);
+        self.force_relay.set(force_relay);
+        self.ice_policy_updated.set(true);
+    }
+
     /// Removes [`sender::State`] or [`receiver::State`] with the provided
     /// [`TrackId`].
     #[inline]
@@ -374,6 +397,38 @@ impl State {
         }
     }
 
+    /// Indicates whether this [`State`] has no [`sender::State`]s, so only
+    /// receives media.
+    #[inline]
+    #[must_use]
+    pub fn is_recv_only(&self) -> bool {
+        self.senders.is_empty()
+    }
+
+    /// Returns [`receiver::State`]s, which recv [`Transceiver`]s can be
+    /// created at once, along with the [`PeerConnection`] of this [`State`].
+    ///
+    /// These are all the [`receiver::State`]s without `mid` of a receive-only
+    /// [`NegotiationRole::Offerer`], as there are no send [`Transceiver`]s to
+    /// be reused by them.
+    ///
+    /// [`Transceiver`]: crate::platform::Transceiver
+    #[must_use]
+    pub fn receivers_to_precreate(&self) -> Vec<Rc<receiver::State>> {
+        let is_offerer = matches!(
+            self.negotiation_role.get(),
+            Some(NegotiationRole::Offerer)
+        );
+        if !is_offerer || !self.is_recv_only() {
+            return Vec::new();
+        }
+        self.receivers
+            .values()
+            .into_iter()
+            .filter(|r| r.mid().is_none())
+            .collect()
+    }
+
     /// Returns [`Future`] resolving once all [`State::senders`]' inserts and
     /// removes are processed.
     ///
@@ -392,6 +447,18 @@ impl State {
         self.receivers.when_all_processed()
     }
 
+    /// Returns [`Future`] resolving once all [`State::senders`]' and
+    /// [`State::receivers`]' inserts and removes are processed.
+    ///
+    /// [`Future`]: std::future::Future
+    #[inline]
+    pub fn when_all_tracks_processed(&self) -> AllProcessed<'static> {
+        medea_reactive::when_all_processed(vec![
+            self.when_all_senders_processed().into(),
+            self.when_all_receivers_processed().into(),
+        ])
+    }
+
     /// Patches [`sender::State`] or [`receiver::State`] with the provided
     /// [`proto::TrackPatchEvent`].
     ///
@@ -426,8 +493,8 @@ impl AsProtoState for State {
             senders: self.senders.as_proto(),
             receivers: self.receivers.as_proto(),
             ice_candidates: self.ice_candidates.as_proto(),
-            force_relay: self.force_relay,
-            ice_servers: self.ice_servers.clone(),
+            force_relay: self.force_relay.get(),
+            ice_servers: self.ice_servers(),
             negotiation_role: self.negotiation_role.get(),
             local_sdp: self.local_sdp.current(),
             remote_sdp: self.remote_sdp.get(),
@@ -476,6 +543,9 @@ impl SynchronizableState for State {
         if state.restart_ice {
             self.restart_ice.set(true);
         }
+        if state.force_relay != self.force_relay.get() {
+            self.update_ice_policy(state.ice_servers, state.force_relay);
+        }
         if let Some(sdp_offer) = state.local_sdp {
             self.local_sdp.approved_set(sdp_offer);
         }
@@ -84,6 +84,20 @@ impl<S> TracksRepository<S> {
         self.0.borrow().on_remove()
     }
 
+    /// Returns all the tracks of this [`TracksRepository`].
+    #[inline]
+    #[must_use]
+    pub fn values(&self) -> Vec<Rc<S>> {
+        self.0.borrow().values().cloned().collect()
+    }
+
+    /// Indicates whether this [`TracksRepository`] contains no tracks.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.borrow().iter().next().is_none()
+    }
+
     /// Removes a track with the provided [`TrackId`], reporting whether it has
     /// been removed or it hasn't existed at all.
     #[inline]
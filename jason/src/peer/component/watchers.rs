@@ -331,8 +331,8 @@ impl Component {
     /// Resets [`NegotiationRole`] to [`None`] on a
     /// [`NegotiationState::Stable`].
     ///
-    /// Creates and sets local SDP offer on a
-    /// [`NegotiationState::WaitLocalSdp`].
+    /// Applies updated ICE transport policy, and creates and sets local SDP
+    /// offer on a [`NegotiationState::WaitLocalSdp`].
     #[watch(self.negotiation_state.subscribe().skip(1))]
     async fn negotiation_state_changed(
         peer: Rc<PeerConnection>,
@@ -352,6 +352,14 @@ impl Component {
                 state.negotiation_role.set(None);
             }
             NegotiationState::WaitLocalSdp => {
+                if state.ice_policy_updated.take() {
+                    peer.peer
+                        .set_ice_config(
+                            state.ice_servers(),
+                            state.force_relay(),
+                        )
+                        .map_err(tracerr::map_from_and_wrap!())?;
+                }
                 if let Some(negotiation_role) = state.negotiation_role.get() {
                     match negotiation_role {
                         NegotiationRole::Offerer => {
@@ -427,8 +435,11 @@ impl Component {
             }
         }
 
-        state.maybe_update_local_stream.set(true);
-        let _ = state.maybe_update_local_stream.when_eq(false).await;
+        // Receive-only `Peer`s have no local media to be acquired.
+        if !state.is_recv_only() {
+            state.maybe_update_local_stream.set(true);
+            let _ = state.maybe_update_local_stream.when_eq(false).await;
+        }
 
         state.negotiation_state.set(NegotiationState::WaitLocalSdp);
 
@@ -15,14 +15,14 @@ use futures::{
 };
 use medea_client_api_proto as proto;
 #[cfg(feature = "mockable")]
-use medea_client_api_proto::{MediaType, MemberId};
-use proto::{MediaSourceKind, TrackId};
+use medea_client_api_proto::MediaType;
+use proto::{MediaSourceKind, MemberId, TrackId};
 use tracerr::Traced;
 
 #[cfg(feature = "mockable")]
 use crate::media::{LocalTracksConstraints, RecvConstraints};
 use crate::{
-    media::{track::local, MediaKind},
+    media::{track::local, MediaKind, TrackConstraints},
     peer::{LocalStreamUpdateCriteria, PeerEvent},
     platform,
     utils::JsCaused,
@@ -262,6 +262,10 @@ struct InnerMediaConnections {
 
     /// [`TrackId`] to its [`receiver::Component`].
     receivers: HashMap<TrackId, receiver::Component>,
+
+    /// [`TrackId`] to the recv [`platform::Transceiver`] created beforehand
+    /// for its [`Receiver`], which is not created yet.
+    precreated_transceivers: HashMap<TrackId, platform::Transceiver>,
 }
 
 impl InnerMediaConnections {
@@ -353,9 +357,41 @@ impl MediaConnections {
             peer_events_sender,
             senders: HashMap::new(),
             receivers: HashMap::new(),
+            precreated_transceivers: HashMap::new(),
         }))
     }
 
+    /// Creates recv [`platform::Transceiver`]s for all the provided
+    /// [`receiver::State`]s at once, to be taken by their [`Receiver`]s once
+    /// they're created.
+    pub fn precreate_recv_transceivers(
+        &self,
+        receivers: &[Rc<receiver::State>],
+    ) {
+        let mut inner = self.0.borrow_mut();
+        for state in receivers {
+            let kind = MediaKind::from(&TrackConstraints::from(
+                state.media_type().clone(),
+            ));
+            let transceiver = inner
+                .add_transceiver(kind, Receiver::transceiver_direction(state));
+            drop(
+                inner
+                    .precreated_transceivers
+                    .insert(state.track_id(), transceiver),
+            );
+        }
+    }
+
+    /// Takes the recv [`platform::Transceiver`] pre-created for the
+    /// [`Receiver`] with the provided [`TrackId`], if any.
+    fn take_precreated_transceiver(
+        &self,
+        track_id: TrackId,
+    ) -> Option<platform::Transceiver> {
+        self.0.borrow_mut().precreated_transceivers.remove(&track_id)
+    }
+
     /// Returns all [`Sender`]s and [`Receiver`]s from this [`MediaConnections`]
     /// with provided [`MediaKind`], [`TrackDirection`] and
     /// [`MediaSourceKind`].
@@ -372,6 +408,22 @@ impl MediaConnections {
         )
     }
 
+    /// Returns [`TransceiverSide`]s of all the [`Receiver`]s receiving media
+    /// from the `Member` with the provided ID.
+    #[must_use]
+    pub fn get_receivers_sides_from(
+        &self,
+        sender_id: &MemberId,
+    ) -> Vec<Rc<dyn TransceiverSide>> {
+        self.0
+            .borrow()
+            .receivers
+            .values()
+            .filter(|rx| rx.state().sender_id() == sender_id)
+            .map(|rx| rx.state() as Rc<dyn TransceiverSide>)
+            .collect()
+    }
+
     /// Indicates whether all [`TransceiverSide`]s with provided [`MediaKind`],
     /// [`TrackDirection`] and [`MediaSourceKind`] is in the provided
     /// [`MediaExchangeState`].
@@ -508,6 +560,7 @@ impl MediaConnections {
         for sender in self.0.borrow().senders.values() {
             if kinds
                 .has(sender.state().media_kind(), sender.state().media_source())
+                && !sender.is_capture_stopped()
             {
                 stream_request
                     .get_or_insert_with(TracksRequest::default)
@@ -558,6 +611,9 @@ impl MediaConnections {
                         InsertLocalTracksError::InvalidMediaTrack
                     ));
                 }
+            } else if sender.is_capture_stopped() {
+                // No track was requested for the muted `Sender`, which will
+                // acquire it on unmuting.
             } else if sender.caps().required() {
                 return Err(tracerr::new!(
                     InsertLocalTracksError::NotEnoughTracks
@@ -680,9 +736,16 @@ impl MediaConnections {
 
     /// Removes a [`sender::Component`] or a [`receiver::Component`] with the
     /// provided [`TrackId`] from these [`MediaConnections`].
+    ///
+    /// Emits [`PeerEvent::RemoteTrackRemoved`] if the removed
+    /// [`receiver::Component`] has notified about its [`remote::Track`].
+    ///
+    /// [`remote::Track`]: crate::media::track::remote::Track
     pub fn remove_track(&self, track_id: TrackId) {
         let mut inner = self.0.borrow_mut();
-        if inner.receivers.remove(&track_id).is_none() {
+        if let Some(receiver) = inner.receivers.remove(&track_id) {
+            receiver.obj().notify_track_removed();
+        } else {
             inner.senders.remove(&track_id);
         }
     }
@@ -712,6 +775,13 @@ impl MediaConnections {
             .is_none()
     }
 
+    /// Returns number of the recv [`platform::Transceiver`]s created
+    /// beforehand, which are not taken by their [`Receiver`]s yet.
+    #[must_use]
+    pub fn precreated_transceivers_count(&self) -> usize {
+        self.0.borrow().precreated_transceivers.len()
+    }
+
     /// Returns [`Receiver`] with the provided [`TrackId`].
     #[must_use]
     pub fn get_receiver_by_id(
@@ -865,6 +935,23 @@ impl MediaConnections {
         Ok(())
     }
 
+    /// Returns [`local::Track`] currently sent by a [`Sender`] of the same
+    /// kind and source kind as the provided [`local::Track`], if any.
+    ///
+    /// Returns the original [`local::Track`] the sent one was forked from.
+    #[must_use]
+    pub fn get_replaced_track(
+        &self,
+        track: &local::Track,
+    ) -> Option<Rc<local::Track>> {
+        self.0.borrow().senders.values().find_map(|s| {
+            let current = s.obj().transceiver().send_track()?;
+            (current.kind() == track.kind()
+                && current.media_source_kind() == track.media_source_kind())
+            .then(|| current.parent().cloned().unwrap_or(current))
+        })
+    }
+
     /// Returns all underlying [`Sender`]'s.
     pub fn get_senders(&self) -> Vec<Rc<sender::Sender>> {
         self.0
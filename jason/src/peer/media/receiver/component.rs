@@ -1,6 +1,6 @@
 //! [`Component`] for `MediaTrack` with a `Recv` direction.
 
-use std::{convert::Infallible, rc::Rc};
+use std::{cell::Cell, convert::Infallible, rc::Rc};
 
 use futures::StreamExt as _;
 use medea_client_api_proto as proto;
@@ -21,7 +21,6 @@ use crate::{
         MediaExchangeState, MediaExchangeStateController,
         MediaStateControllable, MuteStateController, TransceiverSide,
     },
-    platform,
     utils::{component, AsProtoState, SynchronizableState, Updatable},
 };
 
@@ -36,6 +35,7 @@ pub type Component = component::Component<State, Receiver>;
 pub struct State {
     id: TrackId,
     mid: Option<String>,
+    ssrc: Cell<Option<u32>>,
     media_type: MediaType,
     sender_id: MemberId,
     enabled_individual: Rc<MediaExchangeStateController>,
@@ -52,6 +52,7 @@ impl AsProtoState for State {
         Self::Output {
             id: self.id,
             mid: self.mid.clone(),
+            ssrc: self.ssrc.get(),
             media_type: self.media_type.clone(),
             sender_id: self.sender_id.clone(),
             enabled_individual: self.enabled_individual(),
@@ -69,6 +70,7 @@ impl SynchronizableState for State {
         Self {
             id: input.id,
             mid: input.mid,
+            ssrc: Cell::new(input.ssrc),
             media_type: input.media_type,
             sender_id: input.sender_id,
             enabled_individual: MediaExchangeStateController::new(
@@ -99,6 +101,7 @@ impl SynchronizableState for State {
         let new_general_media_exchange_state =
             media_exchange_state::Stable::from(input.enabled_general);
         self.enabled_general.set(new_general_media_exchange_state);
+        self.ssrc.set(input.ssrc);
 
         self.sync_state.set(SyncState::Synced);
     }
@@ -154,6 +157,7 @@ impl From<&State> for proto::state::Receiver {
         Self {
             id: from.id,
             mid: from.mid.clone(),
+            ssrc: from.ssrc.get(),
             media_type: from.media_type.clone(),
             sender_id: from.sender_id.clone(),
             enabled_individual: from.enabled_individual(),
@@ -176,6 +180,7 @@ impl State {
         Self {
             id,
             mid,
+            ssrc: Cell::new(None),
             media_type,
             sender_id: sender,
             enabled_individual: MediaExchangeStateController::new(
@@ -203,6 +208,14 @@ impl State {
         self.mid.as_deref()
     }
 
+    /// Returns primary SSRC of the RTP stream received by this [`State`], if
+    /// it has been negotiated already.
+    #[inline]
+    #[must_use]
+    pub fn ssrc(&self) -> Option<u32> {
+        self.ssrc.get()
+    }
+
     /// Returns current [`MediaType`] of this [`State`].
     #[inline]
     #[must_use]
@@ -261,10 +274,11 @@ impl Component {
     /// Watcher for the [`State::enabled_general`] updates.
     ///
     /// Updates [`Receiver`]'s general media exchange state. Adds or removes
-    /// [`TransceiverDirection::RECV`] from the [`platform::Transceiver`] of the
-    /// [`Receiver`].
+    /// [`TransceiverDirection::RECV`][1] from the [`platform::Transceiver`] of
+    /// the [`Receiver`].
     ///
-    /// [`TransceiverDirection::RECV`]: platform::TransceiverDirection::RECV
+    /// [`platform::Transceiver`]: crate::platform::Transceiver
+    /// [1]: crate::platform::TransceiverDirection::RECV
     #[watch(self.enabled_general.subscribe())]
     async fn general_media_exchange_state_changed(
         receiver: Rc<Receiver>,
@@ -280,19 +294,14 @@ impl Component {
                 if let Some(track) = receiver.track.borrow().as_ref() {
                     track.set_enabled(false);
                 }
-                if let Some(trnscvr) = receiver.transceiver.borrow().as_ref() {
-                    trnscvr.sub_direction(platform::TransceiverDirection::RECV);
-                }
             }
             media_exchange_state::Stable::Enabled => {
                 if let Some(track) = receiver.track.borrow().as_ref() {
                     track.set_enabled(true);
                 }
-                if let Some(trnscvr) = receiver.transceiver.borrow().as_ref() {
-                    trnscvr.add_direction(platform::TransceiverDirection::RECV);
-                }
             }
         }
+        receiver.update_recv_direction();
         receiver.maybe_notify_track();
 
         Ok(())
@@ -301,7 +310,12 @@ impl Component {
     /// Watcher for [`media_exchange_state::Stable`] media exchange state
     /// updates.
     ///
-    /// Updates [`Receiver::enabled_individual`] to the new state.
+    /// Updates [`Receiver::enabled_individual`] to the new state. Adds or
+    /// removes [`TransceiverDirection::RECV`][1] from the
+    /// [`platform::Transceiver`] of the [`Receiver`].
+    ///
+    /// [`platform::Transceiver`]: crate::platform::Transceiver
+    /// [1]: crate::platform::TransceiverDirection::RECV
     #[inline]
     #[watch(self.enabled_individual.subscribe_stable())]
     async fn enabled_individual_stable_state_changed(
@@ -312,6 +326,7 @@ impl Component {
         receiver
             .enabled_individual
             .set(state == media_exchange_state::Stable::Enabled);
+        receiver.update_recv_direction();
         Ok(())
     }
 
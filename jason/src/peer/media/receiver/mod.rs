@@ -5,7 +5,7 @@ mod component;
 use std::cell::{Cell, RefCell};
 
 use futures::channel::mpsc;
-use medea_client_api_proto::{self as proto, MediaType, MemberId};
+use medea_client_api_proto::{self as proto, MemberId};
 use proto::TrackId;
 
 use crate::{
@@ -43,13 +43,15 @@ pub struct Receiver {
 }
 
 impl Receiver {
-    /// Creates a new [`platform::Transceiver`] if provided `mid` is [`None`],
-    /// otherwise creates a [`Receiver`] without a [`platform::Transceiver`]. It
-    /// will be injected when a [`remote::Track`] will arrive.
+    /// Creates a new [`platform::Transceiver`] (or takes the one pre-created by
+    /// [`MediaConnections::precreate_recv_transceivers()`]) if provided `mid`
+    /// is [`None`], otherwise creates a [`Receiver`] without a
+    /// [`platform::Transceiver`]. It will be injected when a
+    /// [`remote::Track`] will arrive.
     ///
     /// Created [`platform::Transceiver`] direction is set to
-    /// [`TransceiverDirection::INACTIVE`][1] if `enabled_individual` is
-    /// `false`.
+    /// [`TransceiverDirection::INACTIVE`][1] if either `enabled_individual` or
+    /// `enabled_general` is `false`.
     ///
     /// `track` field in the created [`Receiver`] will be `None`, since
     /// [`Receiver`] must be created before the actual [`remote::Track`] data
@@ -62,32 +64,32 @@ impl Receiver {
         track_events_sender: mpsc::UnboundedSender<TrackEvent>,
         recv_constraints: &RecvConstraints,
     ) -> Self {
+        let precreated =
+            media_connections.take_precreated_transceiver(state.track_id());
         let connections = media_connections.0.borrow();
         let caps = TrackConstraints::from(state.media_type().clone());
         let kind = MediaKind::from(&caps);
-        let transceiver_direction = if state.enabled_individual() {
-            platform::TransceiverDirection::RECV
-        } else {
-            platform::TransceiverDirection::INACTIVE
-        };
+        let transceiver_direction = Self::transceiver_direction(state);
 
         let transceiver = if state.mid().is_none() {
-            // Try to find send transceiver that can be used as sendrecv.
-            let mut senders = connections.senders.values();
-            let sender = senders.find(|sndr| {
-                sndr.caps().media_kind() == caps.media_kind()
-                    && sndr.caps().media_source_kind()
-                        == caps.media_source_kind()
-            });
-            Some(sender.map_or_else(
-                || connections.add_transceiver(kind, transceiver_direction),
-                |sender| {
-                    let trnsvr = sender.transceiver();
-                    trnsvr.add_direction(transceiver_direction);
+            Some(precreated.unwrap_or_else(|| {
+                // Try to find send transceiver that can be used as sendrecv.
+                let mut senders = connections.senders.values();
+                let sender = senders.find(|sndr| {
+                    sndr.caps().media_kind() == caps.media_kind()
+                        && sndr.caps().media_source_kind()
+                            == caps.media_source_kind()
+                });
+                sender.map_or_else(
+                    || connections.add_transceiver(kind, transceiver_direction),
+                    |sender| {
+                        let trnsvr = sender.transceiver();
+                        trnsvr.add_direction(transceiver_direction);
 
-                    trnsvr
-                },
-            ))
+                        trnsvr
+                    },
+                )
+            }))
         } else {
             None
         };
@@ -101,16 +103,14 @@ impl Receiver {
             track: RefCell::new(None),
             is_track_notified: Cell::new(false),
             peer_events_sender: connections.peer_events_sender.clone(),
-            enabled_general: Cell::new(state.enabled_individual()),
-            enabled_individual: Cell::new(state.enabled_general()),
+            enabled_general: Cell::new(state.enabled_general()),
+            enabled_individual: Cell::new(state.enabled_individual()),
             muted: Cell::new(state.muted()),
             track_events_sender,
         };
 
-        let enabled_in_cons = match &state.media_type() {
-            MediaType::Audio(_) => recv_constraints.is_audio_enabled(),
-            MediaType::Video(_) => recv_constraints.is_video_enabled(),
-        };
+        let enabled_in_cons =
+            recv_constraints.is_enabled_from(kind, state.sender_id());
         if !enabled_in_cons {
             state
                 .media_exchange_state_controller()
@@ -120,6 +120,24 @@ impl Receiver {
         this
     }
 
+    /// Returns direction of a [`platform::Transceiver`] to be created for the
+    /// [`Receiver`] of the provided [`State`].
+    ///
+    /// It's [`TransceiverDirection::INACTIVE`][1] if either
+    /// `enabled_individual` or `enabled_general` is `false`.
+    ///
+    /// [1]: platform::TransceiverDirection::INACTIVE
+    #[must_use]
+    pub(super) fn transceiver_direction(
+        state: &State,
+    ) -> platform::TransceiverDirection {
+        if state.enabled_individual() && state.enabled_general() {
+            platform::TransceiverDirection::RECV
+        } else {
+            platform::TransceiverDirection::INACTIVE
+        }
+    }
+
     /// Returns [`TrackConstraints`] of this [`Receiver`].
     #[inline]
     #[must_use]
@@ -170,6 +188,25 @@ impl Receiver {
         );
     }
 
+    /// Adds or removes [`TransceiverDirection::RECV`][1] of the
+    /// [`platform::Transceiver`] of this [`Receiver`] (if any), so media is
+    /// received only while it's enabled both individually and generally.
+    ///
+    /// This way a [`platform::Transceiver`], which sends nothing as well,
+    /// becomes `inactive` instead of `recvonly`, so no decoding resources are
+    /// allocated for it.
+    ///
+    /// [1]: platform::TransceiverDirection::RECV
+    fn update_recv_direction(&self) {
+        if let Some(transceiver) = self.transceiver.borrow().as_ref() {
+            if self.enabled_individual.get() && self.enabled_general.get() {
+                transceiver.add_direction(platform::TransceiverDirection::RECV);
+            } else {
+                transceiver.sub_direction(platform::TransceiverDirection::RECV);
+            }
+        }
+    }
+
     /// Adds the provided [`platform::MediaStreamTrack`] and
     /// [`platform::Transceiver`] to this [`Receiver`].
     ///
@@ -193,13 +230,8 @@ impl Receiver {
             self.muted.get(),
         );
 
-        if self.enabled_individual.get() {
-            transceiver.add_direction(platform::TransceiverDirection::RECV);
-        } else {
-            transceiver.sub_direction(platform::TransceiverDirection::RECV);
-        }
-
         self.transceiver.replace(Some(transceiver));
+        self.update_recv_direction();
         if let Some(prev_track) = self.track.replace(Some(new_track)) {
             prev_track.stop();
         };
@@ -248,6 +280,22 @@ impl Receiver {
             self.is_track_notified.set(true);
         }
     }
+
+    /// Emits [`PeerEvent::RemoteTrackRemoved`] if [`PeerEvent::NewRemoteTrack`]
+    /// was emitted for the [`remote::Track`] of this [`Receiver`].
+    pub fn notify_track_removed(&self) {
+        if !self.is_track_notified.replace(false) {
+            return;
+        }
+        if let Some(track) = self.track.borrow().as_ref() {
+            drop(self.peer_events_sender.unbounded_send(
+                PeerEvent::RemoteTrackRemoved {
+                    sender_id: self.sender_id.clone(),
+                    track: track.clone(),
+                },
+            ));
+        }
+    }
 }
 
 #[cfg(feature = "mockable")]
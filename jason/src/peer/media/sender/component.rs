@@ -1,6 +1,6 @@
 //! [`Component`] for `MediaTrack` with a `Send` direction.
 
-use std::{convert::Infallible, rc::Rc};
+use std::{cell::Cell, convert::Infallible, rc::Rc};
 
 use futures::{future::LocalBoxFuture, StreamExt as _};
 use medea_client_api_proto::{
@@ -12,7 +12,10 @@ use medea_reactive::{AllProcessed, Guarded, ObservableCell, ProgressableCell};
 use tracerr::Traced;
 
 use crate::{
-    media::{LocalTracksConstraints, MediaKind, TrackConstraints, VideoSource},
+    media::{
+        ContentHint, DegradationPreference, LocalTracksConstraints, MediaKind,
+        TrackConstraints, VideoSource,
+    },
     peer::{
         component::SyncState,
         media::{
@@ -77,6 +80,7 @@ pub type Component = component::Component<State, Sender>;
 pub struct State {
     id: TrackId,
     mid: Option<String>,
+    ssrc: Cell<Option<u32>>,
     media_type: MediaType,
     receivers: Vec<MemberId>,
     enabled_individual: Rc<MediaExchangeStateController>,
@@ -84,6 +88,8 @@ pub struct State {
     enabled_general: ProgressableCell<media_exchange_state::Stable>,
     send_constraints: LocalTracksConstraints,
     local_track_state: ObservableCell<LocalTrackState>,
+    content_hint: ObservableCell<Option<ContentHint>>,
+    degradation_preference: ObservableCell<Option<DegradationPreference>>,
     sync_state: ObservableCell<SyncState>,
 }
 
@@ -95,6 +101,7 @@ impl AsProtoState for State {
         Self::Output {
             id: self.id,
             mid: self.mid.clone(),
+            ssrc: self.ssrc.get(),
             media_type: self.media_type.clone(),
             receivers: self.receivers.clone(),
             enabled_individual: self.enabled_individual.enabled(),
@@ -116,6 +123,7 @@ impl SynchronizableState for State {
         Self {
             id: input.id,
             mid: input.mid,
+            ssrc: Cell::new(input.ssrc),
             media_type: input.media_type,
             receivers: input.receivers,
             mute_state: MuteStateController::new(mute_state::Stable::from(
@@ -129,6 +137,8 @@ impl SynchronizableState for State {
             ),
             send_constraints: send_constraints.clone(),
             local_track_state: ObservableCell::new(LocalTrackState::Stable),
+            content_hint: ObservableCell::new(None),
+            degradation_preference: ObservableCell::new(None),
             sync_state: ObservableCell::new(SyncState::Synced),
         }
     }
@@ -159,6 +169,7 @@ impl SynchronizableState for State {
         let new_general_media_exchange_state =
             media_exchange_state::Stable::from(input.enabled_general);
         self.enabled_general.set(new_general_media_exchange_state);
+        self.ssrc.set(input.ssrc);
 
         self.sync_state.set(SyncState::Synced);
     }
@@ -209,6 +220,7 @@ impl From<&State> for proto::state::Sender {
         Self {
             id: state.id,
             mid: state.mid.clone(),
+            ssrc: state.ssrc.get(),
             media_type: state.media_type.clone(),
             receivers: state.receivers.clone(),
             enabled_individual: state.enabled_individual.enabled(),
@@ -232,6 +244,7 @@ impl State {
         Self {
             id,
             mid,
+            ssrc: Cell::new(None),
             media_type,
             receivers,
             enabled_individual: MediaExchangeStateController::new(
@@ -246,6 +259,8 @@ impl State {
             sync_state: ObservableCell::new(SyncState::Synced),
             send_constraints,
             local_track_state: ObservableCell::new(LocalTrackState::Stable),
+            content_hint: ObservableCell::new(None),
+            degradation_preference: ObservableCell::new(None),
         }
     }
 
@@ -264,6 +279,14 @@ impl State {
         self.id
     }
 
+    /// Returns primary SSRC of the RTP stream sent by this [`State`], if it
+    /// has been negotiated already.
+    #[inline]
+    #[must_use]
+    pub fn ssrc(&self) -> Option<u32> {
+        self.ssrc.get()
+    }
+
     /// Returns current `mid` of this [`State`].
     #[inline]
     #[must_use]
@@ -307,6 +330,21 @@ impl State {
         self.mute_state.muted()
     }
 
+    /// Returns [`ContentHint`] set by a media server for this [`State`].
+    #[inline]
+    #[must_use]
+    pub fn content_hint(&self) -> Option<ContentHint> {
+        self.content_hint.get()
+    }
+
+    /// Returns [`DegradationPreference`] set by a media server for this
+    /// [`State`].
+    #[inline]
+    #[must_use]
+    pub fn degradation_preference(&self) -> Option<DegradationPreference> {
+        self.degradation_preference.get()
+    }
+
     /// Returns [`Future`] which will be resolved once
     /// [getUserMedia()][1]/[getDisplayMedia()][2] request for this [`State`] is
     /// resolved.
@@ -350,6 +388,12 @@ impl State {
         if let Some(muted) = track_patch.muted {
             self.mute_state.update(mute_state::Stable::from(muted));
         }
+        if let Some(hint) = track_patch.content_hint {
+            self.content_hint.set(Some(hint.into()));
+        }
+        if let Some(preference) = track_patch.degradation_preference {
+            self.degradation_preference.set(Some(preference.into()));
+        }
     }
 
     /// Indicates whether local `MediaStream` update needed for this [`State`].
@@ -471,11 +515,15 @@ impl Component {
     ///
     /// Updates [`Sender::enabled_individual`] to the `new_state`.
     ///
-    /// Removes `MediaTrack` from [`platform::Transceiver`] if `new_state` is
+    /// Removes `MediaTrack` and [`TransceiverDirection::SEND`] from
+    /// [`platform::Transceiver`] if `new_state` is
     /// [`media_exchange_state::Stable::Disabled`].
     ///
-    /// Marks [`State::local_track_state`] as [`LocalTrackState::NeedUpdate`] if
-    /// `new_state` is [`media_exchange_state::Stable::Enabled`].
+    /// Marks [`State::local_track_state`] as [`LocalTrackState::NeedUpdate`]
+    /// and restores [`TransceiverDirection::SEND`] (unless disabled generally)
+    /// if `new_state` is [`media_exchange_state::Stable::Enabled`].
+    ///
+    /// [`TransceiverDirection::SEND`]: platform::TransceiverDirection::SEND
     #[watch(self.enabled_individual.subscribe_stable())]
     async fn enabled_individual_stable_state_changed(
         sender: Rc<Sender>,
@@ -487,9 +535,17 @@ impl Component {
             .set(new_state == media_exchange_state::Stable::Enabled);
         match new_state {
             media_exchange_state::Stable::Enabled => {
+                if sender.enabled_general.get() && sender.enabled_in_cons() {
+                    sender
+                        .transceiver
+                        .add_direction(platform::TransceiverDirection::SEND);
+                }
                 state.local_track_state.set(LocalTrackState::NeedUpdate);
             }
             media_exchange_state::Stable::Disabled => {
+                sender
+                    .transceiver
+                    .sub_direction(platform::TransceiverDirection::SEND);
                 sender.remove_track().await;
             }
         }
@@ -500,26 +556,67 @@ impl Component {
     ///
     /// Updates [`Sender`]'s mute state.
     ///
-    /// Updates [`Sender`]'s [`platform::Transceiver`] `MediaTrack.enabled`
-    /// property.
+    /// Mutes or unmutes the media sent by the [`Sender`] according to its
+    /// [`MuteStrategy`].
+    ///
+    /// Marks [`State::local_track_state`] as [`LocalTrackState::NeedUpdate`] if
+    /// capturing was stopped by muting with a [`MuteStrategy::StopCapture`].
+    ///
+    /// [`MuteStrategy`]: crate::media::MuteStrategy
+    /// [`MuteStrategy::StopCapture`]: crate::media::MuteStrategy::StopCapture
     #[watch(self.mute_state.subscribe_stable())]
     async fn mute_state_stable_watcher(
         sender: Rc<Sender>,
-        _: Rc<State>,
+        state: Rc<State>,
         new_state: mute_state::Stable,
     ) -> Result<(), Infallible> {
         sender.muted.set(new_state == mute_state::Stable::Muted);
         match new_state {
             mute_state::Stable::Muted => {
-                sender.transceiver.set_send_track_enabled(false);
+                sender.mute().await;
             }
             mute_state::Stable::Unmuted => {
-                sender.transceiver.set_send_track_enabled(true);
+                if sender.unmute().await && sender.enabled_individual.get() {
+                    state.local_track_state.set(LocalTrackState::NeedUpdate);
+                }
             }
         }
         Ok(())
     }
 
+    /// Watcher for the [`State::content_hint`] updates.
+    ///
+    /// Applies the new [`ContentHint`] to the [`Sender`]'s [`local::Track`].
+    ///
+    /// [`local::Track`]: crate::media::track::local::Track
+    #[watch(self.content_hint.subscribe().skip(1))]
+    async fn content_hint_changed(
+        sender: Rc<Sender>,
+        _: Rc<State>,
+        hint: Option<ContentHint>,
+    ) -> Result<(), Infallible> {
+        if let Some(hint) = hint {
+            sender.set_content_hint(hint);
+        }
+        Ok(())
+    }
+
+    /// Watcher for the [`State::degradation_preference`] updates.
+    ///
+    /// Applies the new [`DegradationPreference`] to the [`Sender`]'s
+    /// [`platform::Transceiver`].
+    #[watch(self.degradation_preference.subscribe().skip(1))]
+    async fn degradation_preference_changed(
+        sender: Rc<Sender>,
+        _: Rc<State>,
+        preference: Option<DegradationPreference>,
+    ) -> Result<(), Infallible> {
+        if let Some(preference) = preference {
+            sender.set_degradation_preference(preference).await;
+        }
+        Ok(())
+    }
+
     /// Stops transition timeouts on a [`SyncState::Desynced`].
     ///
     /// Sends media state intentions and resets transition timeouts on a
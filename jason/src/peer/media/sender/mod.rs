@@ -2,7 +2,10 @@
 
 mod component;
 
-use std::{cell::Cell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 use derive_more::{Display, From};
 use futures::channel::mpsc;
@@ -11,7 +14,8 @@ use tracerr::Traced;
 
 use crate::{
     media::{
-        track::local, LocalTracksConstraints, MediaKind, TrackConstraints,
+        track::local, ContentHint, DegradationPreference,
+        LocalTracksConstraints, MediaKind, MuteStrategy, TrackConstraints,
     },
     peer::TrackEvent,
     platform,
@@ -53,8 +57,13 @@ pub struct Sender {
     caps: TrackConstraints,
     transceiver: platform::Transceiver,
     muted: Cell<bool>,
+    detached_track: RefCell<Option<Rc<local::Track>>>,
+    capture_stopped: Cell<bool>,
     enabled_individual: Cell<bool>,
     enabled_general: Cell<bool>,
+    content_hint: Cell<Option<ContentHint>>,
+    degradation_preference: Cell<Option<DegradationPreference>>,
+    max_bitrate: Cell<Option<u32>>,
     send_constraints: LocalTracksConstraints,
     track_events_sender: mpsc::UnboundedSender<TrackEvent>,
 }
@@ -127,6 +136,11 @@ impl Sender {
             enabled_general: Cell::new(state.is_enabled_general()),
             enabled_individual: Cell::new(state.is_enabled_individual()),
             muted: Cell::new(state.is_muted()),
+            detached_track: RefCell::new(None),
+            capture_stopped: Cell::new(false),
+            content_hint: Cell::new(state.content_hint()),
+            degradation_preference: Cell::new(state.degradation_preference()),
+            max_bitrate: Cell::new(None),
             track_events_sender,
             send_constraints,
         });
@@ -172,6 +186,7 @@ impl Sender {
     /// [2]: https://w3.org/TR/webrtc/#dom-rtcrtpsender-replacetrack
     #[inline]
     pub async fn remove_track(&self) {
+        drop(self.detached_track.take());
         self.transceiver.drop_send_track().await;
     }
 
@@ -185,6 +200,11 @@ impl Sender {
     /// Inserts provided [`local::Track`] into provided [`Sender`]s
     /// transceiver. No-op if provided track already being used by this
     /// [`Sender`].
+    ///
+    /// If this [`Sender`] is muted, then the provided [`local::Track`] is
+    /// handled according to its [`MuteStrategy`]: kept aside until unmuting
+    /// with a [`MuteStrategy::ReplaceWithSilence`], or dropped with a
+    /// [`MuteStrategy::StopCapture`].
     pub(super) async fn insert_track(
         self: Rc<Self>,
         new_track: Rc<local::Track>,
@@ -199,16 +219,210 @@ impl Sender {
         let new_track = new_track.fork();
 
         new_track.set_enabled(!self.muted.get());
+        if let Some(hint) = self.content_hint() {
+            new_track.set_content_hint(hint);
+        }
 
+        if self.muted.get() {
+            match self.mute_strategy() {
+                MuteStrategy::DisableTrack => (),
+                MuteStrategy::ReplaceWithSilence => {
+                    self.transceiver.drop_send_track().await;
+                    new_track.set_enabled(true);
+                    self.detached_track.replace(Some(Rc::new(new_track)));
+                    return Ok(());
+                }
+                MuteStrategy::StopCapture => {
+                    self.transceiver.drop_send_track().await;
+                    self.capture_stopped.set(true);
+                    return Ok(());
+                }
+            }
+        }
+
+        self.attach_track(Rc::new(new_track)).await
+    }
+
+    /// Sets the provided [`local::Track`] as the one sent by the
+    /// [`platform::Transceiver`] of this [`Sender`], applying the encoding
+    /// parameters of this [`Sender`] to it.
+    async fn attach_track(
+        &self,
+        track: Rc<local::Track>,
+    ) -> Result<(), Traced<InsertTrackError>> {
         self.transceiver
-            .set_send_track(Rc::new(new_track))
+            .set_send_track(track)
             .await
             .map_err(InsertTrackError::from)
             .map_err(tracerr::wrap!())?;
 
+        if let Some(preference) = self.degradation_preference() {
+            self.apply_degradation_preference(preference).await;
+        }
+        if let Some(max_bitrate) = self.max_bitrate.get() {
+            self.apply_max_bitrate(Some(max_bitrate)).await;
+        }
+
         Ok(())
     }
 
+    /// Returns [`MuteStrategy`] of this [`Sender`] configured in the
+    /// [`LocalTracksConstraints`].
+    #[inline]
+    #[must_use]
+    pub fn mute_strategy(&self) -> MuteStrategy {
+        self.send_constraints.mute_strategy(self.caps.media_kind())
+    }
+
+    /// Indicates whether capturing of this [`Sender`]'s media is stopped due to
+    /// muting with a [`MuteStrategy::StopCapture`], so no [`local::Track`]
+    /// should be acquired for it until unmuting.
+    #[inline]
+    #[must_use]
+    pub fn is_capture_stopped(&self) -> bool {
+        self.capture_stopped.get()
+    }
+
+    /// Mutes the media sent by this [`Sender`] according to its
+    /// [`MuteStrategy`].
+    async fn mute(&self) {
+        match self.mute_strategy() {
+            MuteStrategy::DisableTrack => {
+                self.transceiver.set_send_track_enabled(false);
+            }
+            MuteStrategy::ReplaceWithSilence => {
+                if let Some(track) = self.transceiver.send_track() {
+                    self.transceiver.drop_send_track().await;
+                    self.detached_track.replace(Some(track));
+                }
+            }
+            MuteStrategy::StopCapture => {
+                self.remove_track().await;
+                self.capture_stopped.set(true);
+            }
+        }
+    }
+
+    /// Unmutes the media sent by this [`Sender`], reverting whatever was done
+    /// by the [`MuteStrategy`] it was muted with.
+    ///
+    /// Returns `true` if capturing was stopped, so a new [`local::Track`]
+    /// should be acquired.
+    async fn unmute(&self) -> bool {
+        let detached_track = self.detached_track.take();
+        if let Some(track) = detached_track {
+            if let Err(e) = self.attach_track(track).await {
+                log::error!("Failed to restore muted track: {}", e);
+            }
+        }
+        self.transceiver.set_send_track_enabled(true);
+
+        self.capture_stopped.replace(false)
+    }
+
+    /// Returns [`ContentHint`] of the [`local::Track`] sent by this
+    /// [`Sender`].
+    ///
+    /// [`ContentHint`] set by a media server takes precedence over the one
+    /// configured in the [`LocalTracksConstraints`].
+    #[must_use]
+    pub fn content_hint(&self) -> Option<ContentHint> {
+        self.content_hint.get().or_else(|| {
+            self.send_constraints
+                .video_encoding(
+                    self.caps.media_kind(),
+                    self.caps.media_source_kind(),
+                )
+                .0
+        })
+    }
+
+    /// Returns [`DegradationPreference`] of this [`Sender`].
+    ///
+    /// [`DegradationPreference`] set by a media server takes precedence over
+    /// the one configured in the [`LocalTracksConstraints`].
+    #[must_use]
+    pub fn degradation_preference(&self) -> Option<DegradationPreference> {
+        self.degradation_preference.get().or_else(|| {
+            self.send_constraints
+                .video_encoding(
+                    self.caps.media_kind(),
+                    self.caps.media_source_kind(),
+                )
+                .1
+        })
+    }
+
+    /// Sets [`ContentHint`] of this [`Sender`] and applies it to the
+    /// currently sent [`local::Track`], if any.
+    fn set_content_hint(&self, hint: ContentHint) {
+        self.content_hint.set(Some(hint));
+        if let Some(track) = self.transceiver.send_track() {
+            track.set_content_hint(hint);
+        }
+    }
+
+    /// Sets [`DegradationPreference`] of this [`Sender`] and applies it to its
+    /// [`platform::Transceiver`] if it has a [`local::Track`].
+    async fn set_degradation_preference(
+        &self,
+        preference: DegradationPreference,
+    ) {
+        self.degradation_preference.set(Some(preference));
+        if self.has_track() {
+            self.apply_degradation_preference(preference).await;
+        }
+    }
+
+    /// Applies the provided [`DegradationPreference`] to the
+    /// [`platform::Transceiver`] of this [`Sender`].
+    ///
+    /// Failure is only logged, since sending media is still possible.
+    async fn apply_degradation_preference(
+        &self,
+        preference: DegradationPreference,
+    ) {
+        if let Err(e) = self
+            .transceiver
+            .set_degradation_preference(preference)
+            .await
+        {
+            log::error!("Failed to set degradation preference: {}", e);
+        }
+    }
+
+    /// Returns maximum bitrate (in bits per second) of this [`Sender`] advised
+    /// by a media server, if any.
+    #[inline]
+    #[must_use]
+    pub fn max_bitrate(&self) -> Option<u32> {
+        self.max_bitrate.get()
+    }
+
+    /// Sets maximum bitrate (in bits per second) of this [`Sender`] advised by
+    /// a media server and applies it to its [`platform::Transceiver`] if it has
+    /// a [`local::Track`].
+    ///
+    /// [`None`] lifts the previously advised limit.
+    pub async fn set_max_bitrate(&self, max_bitrate: Option<u32>) {
+        if self.max_bitrate.replace(max_bitrate) == max_bitrate {
+            return;
+        }
+        if self.has_track() {
+            self.apply_max_bitrate(max_bitrate).await;
+        }
+    }
+
+    /// Applies the provided maximum bitrate to the [`platform::Transceiver`]
+    /// of this [`Sender`].
+    ///
+    /// Failure is only logged, since sending media is still possible.
+    async fn apply_max_bitrate(&self, max_bitrate: Option<u32>) {
+        if let Err(e) = self.transceiver.set_max_bitrate(max_bitrate).await {
+            log::error!("Failed to set max bitrate: {}", e);
+        }
+    }
+
     /// Returns [`platform::Transceiver`] of this [`Sender`].
     #[inline]
     #[must_use]
@@ -19,10 +19,17 @@ use std::{
 use derive_more::{Display, From};
 use futures::{channel::mpsc, future, StreamExt as _};
 use medea_client_api_proto::{
-    stats::StatId, Command, IceConnectionState, MediaSourceKind, MemberId,
+    self as proto,
+    stats::{
+        CandidateType, KnownCandidateType, KnownIceCandidatePairState,
+        NonExhaustive, Protocol, RtcIceCandidatePairStats, RtcStat,
+        RtcStatsType, StatId,
+    },
+    Command, IceConnectionState, MediaSourceKind, MemberId,
     PeerConnectionState, PeerId as Id, PeerId, TrackId, TrackPatchCommand,
 };
 use medea_macro::dispatchable;
+use serde::Serialize;
 use tracerr::Traced;
 
 use crate::{
@@ -33,7 +40,7 @@ use crate::{
         MediaStreamSettings, RecvConstraints,
     },
     platform,
-    utils::JsCaused,
+    utils::{AsProtoState as _, JsCaused},
 };
 
 #[doc(inline)]
@@ -146,10 +153,23 @@ pub enum PeerEvent {
         track: remote::Track,
     },
 
+    /// [`remote::Track`] received from a remote sender was removed by a
+    /// server.
+    RemoteTrackRemoved {
+        /// Remote `Member` ID.
+        sender_id: MemberId,
+
+        /// Removed [`remote::Track`].
+        track: remote::Track,
+    },
+
     /// [`platform::RtcPeerConnection`] sent new local track to remote members.
     NewLocalTrack {
         /// Local [`local::Track`] that is sent to remote members.
         local_track: Rc<local::Track>,
+
+        /// Local [`local::Track`] replaced by the new one, if any.
+        previous: Option<Rc<local::Track>>,
     },
 
     /// [`platform::RtcPeerConnection`]'s [ICE connection][1] state changed.
@@ -180,6 +200,16 @@ pub enum PeerEvent {
         peer_connection_state: PeerConnectionState,
     },
 
+    /// [`platform::RtcPeerConnection`] has switched to another ICE candidate
+    /// pair for media transmission.
+    SelectedCandidatePairChanged {
+        /// ID of the [`PeerConnection`] which ICE candidate pair has changed.
+        peer_id: Id,
+
+        /// [`SelectedCandidatePair`] the [`PeerConnection`] has switched to.
+        pair: SelectedCandidatePair,
+    },
+
     /// [`platform::RtcPeerConnection`]'s [`platform::RtcStats`] update.
     StatsUpdate {
         /// ID of the [`PeerConnection`] for which [` platform::RtcStats`] was
@@ -236,6 +266,65 @@ pub enum PeerEvent {
     },
 }
 
+/// ICE candidate pair selected by a [`PeerConnection`] for media
+/// transmission.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SelectedCandidatePair {
+    /// Type of the local ICE candidate of this pair.
+    pub local_type: CandidateType,
+
+    /// Type of the remote ICE candidate of this pair.
+    pub remote_type: CandidateType,
+
+    /// Protocol used by the local ICE candidate of this pair to communicate
+    /// with the TURN server, if it's a `relay` one.
+    pub relay_protocol: Option<Protocol>,
+}
+
+impl SelectedCandidatePair {
+    /// Finds the [`SelectedCandidatePair`] in the provided [`RtcStat`]s, if
+    /// any.
+    #[must_use]
+    pub fn from_stats(stats: &[RtcStat]) -> Option<Self> {
+        let pair = stats.iter().find_map(|stat| match &stat.stats {
+            RtcStatsType::CandidatePair(pair) if is_selected(pair) => {
+                Some(pair)
+            }
+            _ => None,
+        })?;
+        let find_candidate = |id: &Option<StatId>| {
+            let id = id.as_ref()?;
+            stats.iter().find(|stat| &stat.id == id).and_then(
+                |stat| match &stat.stats {
+                    RtcStatsType::LocalCandidate(candidate)
+                    | RtcStatsType::RemoteCandidate(candidate) => {
+                        Some(candidate)
+                    }
+                    _ => None,
+                },
+            )
+        };
+        let local = find_candidate(&pair.local_candidate_id)?;
+        let remote = find_candidate(&pair.remote_candidate_id)?;
+
+        let is_relay = local.candidate_type
+            == NonExhaustive::Known(KnownCandidateType::Relay);
+        Some(Self {
+            local_type: local.candidate_type.clone(),
+            remote_type: remote.candidate_type.clone(),
+            relay_protocol: local.relay_protocol.clone().filter(|_| is_relay),
+        })
+    }
+}
+
+/// Indicates whether the provided [`RtcIceCandidatePairStats`] describe the ICE
+/// candidate pair currently selected for media transmission.
+fn is_selected(pair: &RtcIceCandidatePairStats) -> bool {
+    pair.nominated
+        && pair.state
+            == NonExhaustive::Known(KnownIceCandidatePairState::Succeeded)
+}
+
 /// High-level wrapper around a [`platform::RtcPeerConnection`].
 pub struct PeerConnection {
     /// Unique ID of [`PeerConnection`].
@@ -269,6 +358,10 @@ pub struct PeerConnection {
     /// values.
     sent_stats_cache: RefCell<HashMap<StatId, u64>>,
 
+    /// Last [`SelectedCandidatePair`] of this [`PeerConnection`] reported to
+    /// a server.
+    selected_candidate_pair: RefCell<Option<SelectedCandidatePair>>,
+
     /// Local media stream constraints used in this [`PeerConnection`].
     send_constraints: LocalTracksConstraints,
 
@@ -309,7 +402,7 @@ impl PeerConnection {
     ) -> Result<Rc<Self>, Traced<RtcPeerConnectionError>> {
         let peer = Rc::new(
             platform::RtcPeerConnection::new(
-                state.ice_servers().clone(),
+                state.ice_servers(),
                 state.force_relay(),
             )
             .map_err(tracerr::map_from_and_wrap!())?,
@@ -319,6 +412,8 @@ impl PeerConnection {
             Rc::clone(&peer),
             peer_events_sender.clone(),
         ));
+        media_connections
+            .precreate_recv_transceivers(&state.receivers_to_precreate());
 
         platform::spawn({
             let peer_events_sender = peer_events_sender.clone();
@@ -338,6 +433,7 @@ impl PeerConnection {
             media_manager,
             peer_events_sender,
             sent_stats_cache: RefCell::new(HashMap::new()),
+            selected_candidate_pair: RefCell::new(None),
             has_remote_description: Cell::new(false),
             ice_candidates_buffer: RefCell::new(Vec::new()),
             send_constraints,
@@ -456,8 +552,26 @@ impl PeerConnection {
 
     /// Filters out already sent stats, and send new stats from the provided
     /// [`platform::RtcStats`].
+    ///
+    /// Emits [`PeerEvent::SelectedCandidatePairChanged`] if the provided
+    /// [`platform::RtcStats`] contain another [`SelectedCandidatePair`] than
+    /// the last reported one.
     #[allow(clippy::option_if_let_else)]
     pub fn send_peer_stats(&self, stats: platform::RtcStats) {
+        if let Some(pair) = SelectedCandidatePair::from_stats(&stats.0) {
+            let is_changed =
+                self.selected_candidate_pair.borrow().as_ref() != Some(&pair);
+            if is_changed {
+                self.selected_candidate_pair.replace(Some(pair.clone()));
+                drop(self.peer_events_sender.unbounded_send(
+                    PeerEvent::SelectedCandidatePairChanged {
+                        peer_id: self.id,
+                        pair,
+                    },
+                ));
+            }
+        }
+
         let mut stats_cache = self.sent_stats_cache.borrow_mut();
         let stats = platform::RtcStats(
             stats
@@ -615,6 +729,17 @@ impl PeerConnection {
         )
     }
 
+    /// Returns [`TransceiverSide`]s of all the receivers of this
+    /// [`PeerConnection`], receiving media from the `Member` with the provided
+    /// ID.
+    #[inline]
+    pub fn get_receivers_sides_from(
+        &self,
+        sender_id: &MemberId,
+    ) -> Vec<Rc<dyn TransceiverSide>> {
+        self.media_connections.get_receivers_sides_from(sender_id)
+    }
+
     /// Track id to mid relations of all send tracks of this
     /// [`platform::RtcPeerConnection`]. mid is id of [`m= section`][1]. mids
     /// are received directly from registered [`RTCRtpTransceiver`][2]s, and
@@ -781,18 +906,29 @@ impl PeerConnection {
                 )
                 .map_err(tracerr::map_from_and_wrap!())?;
 
+            let new_tracks: Vec<_> = media_tracks
+                .into_iter()
+                .filter_map(|(track, is_new)| is_new.then(|| track))
+                .map(|track| {
+                    let previous =
+                        self.media_connections.get_replaced_track(&track);
+                    (track, previous)
+                })
+                .collect();
+
             let media_exchange_states_updates = self
                 .media_connections
                 .insert_local_tracks(&peer_tracks)
                 .await
                 .map_err(tracerr::map_from_and_wrap!())?;
 
-            for (local_track, is_new) in media_tracks {
-                if is_new {
-                    drop(self.peer_events_sender.unbounded_send(
-                        PeerEvent::NewLocalTrack { local_track },
-                    ));
-                }
+            for (local_track, previous) in new_tracks {
+                drop(self.peer_events_sender.unbounded_send(
+                    PeerEvent::NewLocalTrack {
+                        local_track,
+                        previous,
+                    },
+                ));
             }
 
             Ok(media_exchange_states_updates)
@@ -801,6 +937,17 @@ impl PeerConnection {
         }
     }
 
+    /// Returns [`local::Track`] currently sent by this [`PeerConnection`] and
+    /// going to be replaced with the provided one, if any.
+    #[inline]
+    #[must_use]
+    pub fn get_replaced_track(
+        &self,
+        track: &local::Track,
+    ) -> Option<Rc<local::Track>> {
+        self.media_connections.get_replaced_track(track)
+    }
+
     /// Returns [`Rc`] to [`TransceiverSide`] with a provided [`TrackId`].
     ///
     /// Returns [`None`] if [`TransceiverSide`] with a provided [`TrackId`]
@@ -933,12 +1080,78 @@ impl PeerConnection {
 
     /// Removes a [`sender::Component`] and a [`receiver::Component`] with the
     /// provided [`TrackId`] from this [`PeerConnection`].
-    #[inline]
+    ///
+    /// Acknowledges the removal to Media Server with a
+    /// [`Command::TracksRemovedAck`], once the media resources of the track
+    /// are released.
     pub fn remove_track(&self, track_id: TrackId) {
         self.media_connections.remove_track(track_id);
+        drop(self.peer_events_sender.unbounded_send(
+            PeerEvent::MediaUpdateCommand {
+                command: Command::TracksRemovedAck {
+                    peer_id: self.id,
+                    track_ids: vec![track_id],
+                },
+            },
+        ));
+    }
+
+    /// Returns a [`DebugDump`] of this [`PeerConnection`] along with the
+    /// provided [`State`] of its [`Component`].
+    pub async fn debug_dump(&self, state: Option<&State>) -> DebugDump {
+        let (stats, stats_error) = match self.peer.get_stats().await {
+            Ok(stats) => (stats.0, None),
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+        let selected_candidate_pairs = stats
+            .iter()
+            .filter(|stat| match &stat.stats {
+                RtcStatsType::CandidatePair(pair) => is_selected(pair),
+                _ => false,
+            })
+            .cloned()
+            .collect();
+
+        DebugDump {
+            id: self.id,
+            ice_connection_state: self.peer.ice_connection_state(),
+            connection_state: self.peer.connection_state(),
+            state: state.map(|state| state.as_proto()),
+            selected_candidate_pairs,
+            stats,
+            stats_error,
+        }
     }
 }
 
+/// Snapshot of a [`PeerConnection`] intended to be attached to bug reports.
+#[derive(Debug, Serialize)]
+pub struct DebugDump {
+    /// ID of the [`PeerConnection`].
+    pub id: PeerId,
+
+    /// Current [`IceConnectionState`] of the [`PeerConnection`].
+    pub ice_connection_state: IceConnectionState,
+
+    /// Current [`PeerConnectionState`] of the [`PeerConnection`], if it's
+    /// supported by the platform.
+    pub connection_state: Option<PeerConnectionState>,
+
+    /// [`State`] of the [`PeerConnection`] including its local and remote
+    /// SDPs, tracks and gathered ICE candidates.
+    pub state: Option<proto::state::Peer>,
+
+    /// Stats of the ICE candidate pairs currently selected for media
+    /// transmission.
+    pub selected_candidate_pairs: Vec<RtcStat>,
+
+    /// All the latest [`RtcStat`]s of the [`PeerConnection`].
+    pub stats: Vec<RtcStat>,
+
+    /// Error occurred while obtaining [`RtcStat`]s, if any.
+    pub stats_error: Option<String>,
+}
+
 #[cfg(feature = "mockable")]
 impl PeerConnection {
     /// Returns [`RtcStats`] of this [`PeerConnection`].
@@ -1040,6 +1253,14 @@ impl PeerConnection {
             .collect()
     }
 
+    /// Returns number of the recv [`platform::Transceiver`]s created
+    /// beforehand, which are not taken by their [`Receiver`]s yet.
+    #[inline]
+    #[must_use]
+    pub fn precreated_transceivers_count(&self) -> usize {
+        self.media_connections.precreated_transceivers_count()
+    }
+
     /// Returns [`Rc`] to the [`Receiver`] with the provided [`TrackId`].
     #[inline]
     #[must_use]
@@ -1,14 +1,14 @@
 //! Component responsible for the [`peer::Component`] creating and removing.
 
 use std::{
-    cell::RefCell, collections::HashMap, convert::Infallible, rc::Rc,
+    cell::RefCell, collections::HashMap, convert::Infallible, mem, rc::Rc,
     time::Duration,
 };
 
 use futures::{channel::mpsc, future};
 use medea_client_api_proto::{self as proto, PeerId};
 use medea_macro::watchers;
-use medea_reactive::ObservableHashMap;
+use medea_reactive::{AllProcessed, Guarded, ProgressableHashMap};
 use tracerr::Traced;
 
 use crate::{
@@ -24,6 +24,10 @@ use crate::{
 
 use super::{PeerConnection, PeerEvent};
 
+/// Default interval of scraping [`platform::RtcStats`] of all
+/// [`PeerConnection`]s.
+pub const DEFAULT_STATS_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Component responsible for the [`peer::Component`] creating and removing.
 pub type Component = component::Component<State, Repository>;
 
@@ -46,6 +50,14 @@ impl Component {
             .collect()
     }
 
+    /// Closes all [`PeerConnection`]s stored in the repository, stopping
+    /// their stats scraping.
+    pub fn close_all(&self) {
+        drop(self.stats_scrape_task.borrow_mut().take());
+        let peers = mem::take(&mut *self.peers.borrow_mut());
+        drop(peers);
+    }
+
     /// Notifies all [`peer::Component`]s about a RPC connection loss.
     #[inline]
     pub fn connection_lost(&self) {
@@ -85,7 +97,7 @@ impl Component {
 
 /// State of the [`Component`].
 #[derive(Default)]
-pub struct State(RefCell<ObservableHashMap<PeerId, Rc<peer::State>>>);
+pub struct State(RefCell<ProgressableHashMap<PeerId, Rc<peer::State>>>);
 
 /// Context of the [`Component`].
 pub struct Repository {
@@ -97,11 +109,13 @@ pub struct Repository {
 
     /// [`TaskHandle`] for a task which will call
     /// [`PeerConnection::send_peer_stats`] of all [`PeerConnection`]s
-    /// every second and send updated [`PeerMetrics::RtcStats`] to the server.
+    /// periodically and send updated [`PeerMetrics::RtcStats`] to the server.
+    ///
+    /// [`None`] if periodic stats scraping is stopped.
     ///
     /// [`PeerMetrics::RtcStats`]:
     /// medea_client_api_proto::PeerMetrics::RtcStats
-    _stats_scrape_task: TaskHandle,
+    stats_scrape_task: RefCell<Option<TaskHandle>>,
 
     /// Channel for sending events produced by [`PeerConnection`] to [`Room`].
     ///
@@ -144,8 +158,11 @@ impl Repository {
         let peers = Rc::default();
         Self {
             media_manager,
-            _stats_scrape_task: Self::spawn_peers_stats_scrape_task(Rc::clone(
-                &peers,
+            stats_scrape_task: RefCell::new(Some(
+                Self::spawn_peers_stats_scrape_task(
+                    Rc::clone(&peers),
+                    DEFAULT_STATS_INTERVAL,
+                ),
             )),
             peers,
             peer_event_sender,
@@ -155,27 +172,61 @@ impl Repository {
         }
     }
 
+    /// Sets the interval of scraping [`platform::RtcStats`] of all
+    /// [`PeerConnection`]s and sending them to a server, restarting the
+    /// scraping task.
+    ///
+    /// Zero `interval` stops periodic stats scraping.
+    pub fn set_stats_interval(&self, interval: Duration) {
+        let task = (interval != Duration::from_secs(0)).then(|| {
+            Self::spawn_peers_stats_scrape_task(
+                Rc::clone(&self.peers),
+                interval,
+            )
+        });
+        *self.stats_scrape_task.borrow_mut() = task;
+    }
+
+    /// Scrapes [`platform::RtcStats`] of all [`PeerConnection`]s right away,
+    /// and sends the updated ones to a server.
+    pub async fn poll_stats_now(&self) {
+        Self::scrape_and_send_peers_stats(&self.peers).await;
+    }
+
+    /// Calls [`PeerConnection::scrape_and_send_peer_stats()`] of all the
+    /// provided [`PeerConnection`]s.
+    async fn scrape_and_send_peers_stats(
+        peers: &RefCell<HashMap<PeerId, peer::Component>>,
+    ) {
+        let peers = peers
+            .borrow()
+            .values()
+            .map(component::Component::obj)
+            .collect::<Vec<_>>();
+        future::join_all(peers.iter().map(|p| p.scrape_and_send_peer_stats()))
+            .await;
+    }
+
     /// Spawns a task which will call [`PeerConnection::send_peer_stats()`] of
-    /// all [`PeerConnection`]s every second and send updated
+    /// all [`PeerConnection`]s every provided `interval` and send updated
     /// [`platform::RtcStats`] to a server.
     ///
+    /// Scraping is suspended while the page is hidden (see
+    /// [`platform::is_page_hidden()`]).
+    ///
     /// Returns [`TaskHandle`] which will stop this task on its [`Drop`].
     fn spawn_peers_stats_scrape_task(
         peers: Rc<RefCell<HashMap<PeerId, peer::Component>>>,
+        interval: Duration,
     ) -> TaskHandle {
         let (fut, abort) = future::abortable(async move {
             loop {
-                platform::delay_for(Duration::from_secs(1)).await;
-
-                let peers = peers
-                    .borrow()
-                    .values()
-                    .map(component::Component::obj)
-                    .collect::<Vec<_>>();
-                future::join_all(
-                    peers.iter().map(|p| p.scrape_and_send_peer_stats()),
-                )
-                .await;
+                platform::delay_for(interval).await;
+
+                if platform::is_page_hidden() {
+                    continue;
+                }
+                Self::scrape_and_send_peers_stats(&peers).await;
             }
         });
 
@@ -206,6 +257,15 @@ impl State {
     pub fn remove(&self, peer_id: PeerId) {
         self.0.borrow_mut().remove(&peer_id);
     }
+
+    /// Returns [`Future`] resolving once all [`peer::State`]s' inserts and
+    /// removes are processed.
+    ///
+    /// [`Future`]: std::future::Future
+    #[inline]
+    pub fn when_all_processed(&self) -> AllProcessed<'static> {
+        self.0.borrow().when_all_processed()
+    }
 }
 
 impl AsProtoState for State {
@@ -233,8 +293,9 @@ impl Component {
     async fn peer_added(
         peers: Rc<Repository>,
         _: Rc<State>,
-        (peer_id, new_peer): (PeerId, Rc<peer::State>),
+        val: Guarded<(PeerId, Rc<peer::State>)>,
     ) -> Result<(), Traced<RtcPeerConnectionError>> {
+        let ((peer_id, new_peer), _guard) = val.into_parts();
         let peer = peer::Component::new(
             PeerConnection::new(
                 &new_peer,
@@ -264,8 +325,9 @@ impl Component {
     async fn peer_removed(
         peers: Rc<Repository>,
         _: Rc<State>,
-        (peer_id, _): (PeerId, Rc<peer::State>),
+        val: Guarded<(PeerId, Rc<peer::State>)>,
     ) -> Result<(), Infallible> {
+        let ((peer_id, _), _guard) = val.into_parts();
         peers.peers.borrow_mut().remove(&peer_id);
         peers.connections.close_connection(peer_id);
         Ok(())
@@ -90,6 +90,21 @@ impl LocalStreamUpdateCriteria {
             .bitor_assign(Self::from_kinds(media_kind, Some(source_kind)).0)
     }
 
+    /// Adds all the [`MediaKind`] + [`MediaSourceKind`] pairs of the provided
+    /// [`LocalStreamUpdateCriteria`] to this [`LocalStreamUpdateCriteria`].
+    #[inline]
+    pub fn merge(&mut self, other: Self) {
+        self.0.bitor_assign(other.0)
+    }
+
+    /// Indicates whether this [`LocalStreamUpdateCriteria`] doesn't contain
+    /// any [`MediaKind`] + [`MediaSourceKind`] pair.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(self) -> bool {
+        self.0.is_empty()
+    }
+
     /// Checks whether this [`LocalStreamUpdateCriteria`] contains the provided
     /// [`MediaKind`] + [`MediaSourceKind`] pair.
     #[inline]
@@ -1,17 +1,31 @@
 //! Functionality for calling platform callbacks.
 
-use std::cell::RefCell;
+use std::{cell::RefCell, rc::Rc};
 
 use super::Function;
 
 /// Wrapper for a single argument callback function.
-pub struct Callback<A>(pub RefCell<Option<Function<A>>>);
+///
+/// Exclusively owns the inner [`Function`], so the underlying platform
+/// resources are released exactly once: either when the [`Function`] is
+/// replaced or unset, or when this [`Callback`] is dropped.
+///
+/// Invocation never holds a borrow of this [`Callback`], so the called
+/// [`Function`] is free to set or unset this [`Callback`] again. In such case
+/// the [`Function`] being called is released once the call returns.
+pub struct Callback<A>(RefCell<Option<Rc<Function<A>>>>);
 
 impl<A> Callback<A> {
-    /// Sets the inner [`Function`].
+    /// Sets the inner [`Function`], releasing the previously set one (if any).
     #[inline]
     pub fn set_func(&self, f: Function<A>) {
-        self.0.borrow_mut().replace(f);
+        drop(self.0.borrow_mut().replace(Rc::new(f)));
+    }
+
+    /// Unsets the inner [`Function`] (if any), so it won't be invoked anymore.
+    #[inline]
+    pub fn unset_func(&self) {
+        drop(self.0.borrow_mut().take());
     }
 
     /// Indicates whether this [`Callback`] is set.
@@ -20,13 +34,20 @@ impl<A> Callback<A> {
     pub fn is_set(&self) -> bool {
         self.0.borrow().as_ref().is_some()
     }
+
+    /// Returns the inner [`Function`] (if any) to be invoked without holding a
+    /// borrow of this [`Callback`].
+    #[inline]
+    pub(super) fn func(&self) -> Option<Rc<Function<A>>> {
+        self.0.borrow().as_ref().map(Rc::clone)
+    }
 }
 
 impl Callback<()> {
     /// Invokes the underlying [`Function`] (if any) passing no arguments to it.
     #[inline]
     pub fn call0(&self) {
-        if let Some(f) = self.0.borrow().as_ref() {
+        if let Some(f) = self.func() {
             f.call0()
         };
     }
@@ -4,7 +4,7 @@
 
 use derive_more::Display;
 
-use crate::media::MediaKind;
+use crate::media::{InputDeviceCapabilities, MediaKind};
 
 /// Errors that may occur when parsing [MediaDeviceInfo][1].
 ///
@@ -62,4 +62,16 @@ impl InputDeviceInfo {
     pub fn group_id(&self) -> String {
         unimplemented!()
     }
+
+    /// Returns [`InputDeviceCapabilities`] of the represented device.
+    ///
+    /// Returns [`None`] if the [getCapabilities()][1] method is not supported
+    /// by the platform.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dom-inputdeviceinfo
+    #[inline]
+    #[must_use]
+    pub fn capabilities(&self) -> Option<InputDeviceCapabilities> {
+        unimplemented!()
+    }
 }
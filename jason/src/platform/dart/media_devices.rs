@@ -2,11 +2,20 @@
 //!
 //! [1]: https://w3.org/TR/mediacapture-streams#mediadevices
 
+use std::cell::RefCell;
+
+use futures::{
+    channel::mpsc,
+    stream::{LocalBoxStream, StreamExt as _},
+};
 use tracerr::Traced;
 
-use crate::platform::{
-    DisplayMediaStreamConstraints, Error, InputDeviceInfo,
-    MediaStreamConstraints, MediaStreamTrack,
+use crate::{
+    media::{AudioRouteChangeReason, PermissionState},
+    platform::{
+        DisplayMediaStreamConstraints, Error, InputDeviceInfo,
+        MediaStreamConstraints, MediaStreamTrack,
+    },
 };
 
 /// Collects information about the User Agent's available media input devices.
@@ -25,6 +34,15 @@ pub async fn enumerate_devices() -> Result<Vec<InputDeviceInfo>, Traced<Error>>
     unimplemented!()
 }
 
+/// Queries a state of the permission with the provided `name` without
+/// prompting a user.
+///
+/// Returns [`None`] if the platform doesn't support querying the provided
+/// permission `name`.
+pub async fn query_permission(name: &str) -> Option<PermissionState> {
+    unimplemented!()
+}
+
 /// Prompts a user for a permission to use a media input which produces vector
 /// of [`MediaStreamTrack`]s containing the requested types of media.
 ///
@@ -61,3 +79,34 @@ pub async fn get_display_media(
 ) -> Result<Vec<MediaStreamTrack>, Traced<Error>> {
     unimplemented!()
 }
+
+thread_local! {
+    /// Subscribers to the audio output route changes.
+    static AUDIO_ROUTE_SUBSCRIBERS: RefCell<
+        Vec<mpsc::UnboundedSender<AudioRouteChangeReason>>,
+    > = RefCell::default();
+}
+
+/// Subscribes to the audio output route changes (e.g. headphones being
+/// unplugged), reported by the platform via the [`audio_route_changed()`]
+/// function.
+#[must_use]
+pub fn audio_route_changes() -> LocalBoxStream<'static, AudioRouteChangeReason>
+{
+    let (tx, rx) = mpsc::unbounded();
+    AUDIO_ROUTE_SUBSCRIBERS.with(|subs| subs.borrow_mut().push(tx));
+    rx.boxed_local()
+}
+
+/// Notifies all the [`audio_route_changes()`] subscribers about the audio
+/// output route change with the provided [`AudioRouteChangeReason`].
+///
+/// Called by the Dart side on notifications of the Android `AudioManager`.
+#[no_mangle]
+pub extern "C" fn audio_route_changed(reason: u8) {
+    let reason = AudioRouteChangeReason::from(reason);
+    AUDIO_ROUTE_SUBSCRIBERS.with(|subs| {
+        subs.borrow_mut()
+            .retain(|tx| tx.unbounded_send(reason).is_ok());
+    });
+}
@@ -3,8 +3,15 @@
 //! [1]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
 
 use derive_more::AsRef;
+use tracerr::Traced;
 
-use crate::media::{track::MediaStreamTrackState, FacingMode, MediaKind};
+use crate::{
+    media::{
+        track::MediaStreamTrackState, ContentHint, DeviceVideoTrackConstraints,
+        FacingMode, MediaKind,
+    },
+    platform::Error,
+};
 
 /// Wrapper around [MediaStreamTrack][1] received from a
 /// [getUserMedia()][2]/[getDisplayMedia()][3] request.
@@ -82,6 +89,26 @@ impl MediaStreamTrack {
         unimplemented!()
     }
 
+    /// Returns a [`sampleRate`][1] of the underlying [MediaStreamTrack][2].
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediatracksettings-samplerate
+    /// [2]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+    #[inline]
+    #[must_use]
+    pub fn sample_rate(&self) -> Option<u32> {
+        unimplemented!()
+    }
+
+    /// Returns a [`channelCount`][1] of the underlying [MediaStreamTrack][2].
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediatracksettings-channelcount
+    /// [2]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+    #[inline]
+    #[must_use]
+    pub fn channel_count(&self) -> Option<u32> {
+        unimplemented!()
+    }
+
     /// Changes an [`enabled`][1] attribute in the underlying
     /// [MediaStreamTrack][2].
     ///
@@ -92,6 +119,33 @@ impl MediaStreamTrack {
         unimplemented!()
     }
 
+    /// Changes a [`contentHint`][1] attribute in the underlying
+    /// [MediaStreamTrack][2].
+    ///
+    /// [1]: https://w3.org/TR/mst-content-hint#dom-mediastreamtrack-contenthint
+    /// [2]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+    #[inline]
+    pub fn set_content_hint(&self, hint: ContentHint) {
+        unimplemented!()
+    }
+
+    /// Applies the provided [`DeviceVideoTrackConstraints`] to the underlying
+    /// [MediaStreamTrack][2] as an advanced constraint set via
+    /// [applyConstraints()][1].
+    ///
+    /// # Errors
+    ///
+    /// With [`Error`] if [applyConstraints()][1] fails.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediastreamtrack-applyconstraints
+    /// [2]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+    pub async fn apply_advanced(
+        &self,
+        constraints: DeviceVideoTrackConstraints,
+    ) -> Result<(), Traced<Error>> {
+        unimplemented!()
+    }
+
     /// Changes a [`readyState`][1] attribute in the underlying
     /// [MediaStreamTrack][2] to [`ended`][3].
     ///
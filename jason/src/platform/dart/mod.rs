@@ -21,14 +21,19 @@ pub mod transceiver;
 pub mod transport;
 pub mod utils;
 
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use medea_client_api_proto::ClientCapabilities;
 
 pub use self::{
     constraints::{DisplayMediaStreamConstraints, MediaStreamConstraints},
     error::Error,
     executor::spawn,
     input_device_info::InputDeviceInfo,
-    media_devices::{enumerate_devices, get_display_media, get_user_media},
+    media_devices::{
+        audio_route_changes, enumerate_devices, get_display_media,
+        get_user_media, query_permission,
+    },
     media_track::MediaStreamTrack,
     peer_connection::RtcPeerConnection,
     rtc_stats::RtcStats,
@@ -40,15 +45,50 @@ pub use self::{
 /// TODO: Implement panic hook.
 pub fn set_panic_hook() {}
 
-/// Initialize [`android_logger`] as default application logger with min log
-/// level set to [`log::Level::Debug`].
+/// Writes the provided [`log::Record`] into the Android system log via
+/// [`android_logger`].
 ///
 /// [`android_logger`]: https://docs.rs/android_logger
-pub fn init_logger() {
-    // TODO: `android_logger::init_once()` should be called only once.
-    android_logger::init_once(
-        android_logger::Config::default().with_min_level(log::Level::Debug),
-    );
+pub fn console_log(record: &log::Record<'_>) {
+    use log::Log as _;
+
+    thread_local! {
+        static LOGGER: android_logger::AndroidLogger =
+            android_logger::AndroidLogger::new(
+                android_logger::Config::default()
+                    .with_min_level(log::Level::Trace),
+            );
+    }
+
+    LOGGER.with(|logger| logger.log(record));
+}
+
+/// Returns the current time in milliseconds since the UNIX epoch.
+#[must_use]
+pub fn unix_time_millis() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0.0, |d| d.as_secs_f64() * 1000.0)
+}
+
+/// Indicates whether the application is hidden from a user at the moment.
+///
+/// Always `false`, since application visibility is not tracked on this
+/// platform yet.
+#[inline]
+#[must_use]
+pub fn is_page_hidden() -> bool {
+    false
+}
+
+/// Returns [`ClientCapabilities`] of the application, to be reported to a
+/// media server.
+///
+/// Always empty, since capabilities are not detected on this platform yet.
+#[inline]
+#[must_use]
+pub fn client_capabilities() -> ClientCapabilities {
+    ClientCapabilities::default()
 }
 
 /// [`Future`] which resolves after the provided [`Duration`].
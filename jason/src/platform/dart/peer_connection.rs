@@ -149,6 +149,29 @@ impl RtcPeerConnection {
         unimplemented!()
     }
 
+    /// Updates [`IceServer`]s and ICE transport policy of this
+    /// [`RtcPeerConnection`].
+    ///
+    /// New configuration is applied to the ICE candidates gathered after the
+    /// next ICE restart.
+    ///
+    /// # Errors
+    ///
+    /// With [`RtcPeerConnectionError::SetConfigurationFailed`] if
+    /// [RtcPeerConnection.setConfiguration()][1] fails.
+    ///
+    /// [1]: https://w3.org/TR/webrtc/#dom-rtcpeerconnection-setconfiguration
+    pub fn set_ice_config<I>(
+        &self,
+        ice_servers: I,
+        is_force_relayed: bool,
+    ) -> Result<()>
+    where
+        I: IntoIterator<Item = IceServer>,
+    {
+        unimplemented!()
+    }
+
     /// Sets provided [SDP offer][`SdpType::Offer`] as local description.
     ///
     /// # Errors
@@ -7,7 +7,7 @@ use std::rc::Rc;
 use futures::future::LocalBoxFuture;
 
 use crate::{
-    media::track::local,
+    media::{track::local, DegradationPreference},
     platform::{Error, TransceiverDirection},
 };
 
@@ -91,6 +91,44 @@ impl Transceiver {
         unimplemented!()
     }
 
+    /// Sets a [`degradationPreference`][1] of the underlying
+    /// [RTCRtpSender][2].
+    ///
+    /// # Errors
+    ///
+    /// Errors with platform error if the underlying [`setParameters`][3] call
+    /// fails.
+    ///
+    /// [1]: https://w3.org/TR/webrtc/#dom-rtcrtpsendparameters
+    /// [2]: https://w3.org/TR/webrtc/#dom-rtcrtpsender
+    /// [3]: https://w3.org/TR/webrtc/#dom-rtcrtpsender-setparameters
+    pub async fn set_degradation_preference(
+        &self,
+        preference: DegradationPreference,
+    ) -> Result<(), Error> {
+        unimplemented!()
+    }
+
+    /// Sets a [`maxBitrate`][1] of all the encodings of the underlying
+    /// [RTCRtpSender][2].
+    ///
+    /// [`None`] removes the limit.
+    ///
+    /// # Errors
+    ///
+    /// Errors with platform error if the underlying [`setParameters`][3] call
+    /// fails.
+    ///
+    /// [1]: https://w3.org/TR/webrtc/#dom-rtcrtpencodingparameters-maxbitrate
+    /// [2]: https://w3.org/TR/webrtc/#dom-rtcrtpsender
+    /// [3]: https://w3.org/TR/webrtc/#dom-rtcrtpsender-setparameters
+    pub async fn set_max_bitrate(
+        &self,
+        max_bitrate: Option<u32>,
+    ) -> Result<(), Error> {
+        unimplemented!()
+    }
+
     /// Indicates whether the underlying [RTCRtpTransceiver] is stopped.
     ///
     /// [RTCRtpTransceiver]: https://w3.org/TR/webrtc/#dom-rtcrtptransceiver
@@ -24,9 +24,12 @@ use dart_sys::{Dart_Handle, Dart_PersistentHandle};
 
 use crate::api::{utils::DartError, DartValue};
 
-use super::dart_api::{
-    Dart_HandleFromPersistent_DL_Trampolined,
-    Dart_NewPersistentHandle_DL_Trampolined,
+use super::{
+    dart_api::{
+        Dart_HandleFromPersistent_DL_Trampolined,
+        Dart_NewPersistentHandle_DL_Trampolined,
+    },
+    DartExternFn,
 };
 
 /// Pointer to an extern function that returns a [`Dart_Handle`] to a new Dart
@@ -65,72 +68,65 @@ type CompleterFutureCaller = extern "C" fn(Dart_Handle) -> Dart_Handle;
 /// Stores pointer to the [`CompleterNewCaller`] extern function.
 ///
 /// Must be initialized by Dart during FFI initialization phase.
-static mut COMPLETER_NEW_CALLER: Option<CompleterNewCaller> = None;
+static COMPLETER_NEW_CALLER: DartExternFn<CompleterNewCaller> =
+    DartExternFn::new("CompleterNewCaller");
 
 /// Stores pointer to the [`CompleterCompleteCaller`] extern function.
 ///
 /// Must be initialized by Dart during FFI initialization phase.
-static mut COMPLETER_COMPLETE_CALLER: Option<CompleterCompleteCaller> = None;
+static COMPLETER_COMPLETE_CALLER: DartExternFn<CompleterCompleteCaller> =
+    DartExternFn::new("CompleterCompleteCaller");
 
 /// Stores pointer to the [`CompleterCompleteErrorCaller`] extern function.
 ///
 /// Must be initialized by Dart during FFI initialization phase.
-static mut COMPLETER_COMPLETE_ERROR_CALLER: Option<
+static COMPLETER_COMPLETE_ERROR_CALLER: DartExternFn<
     CompleterCompleteErrorCaller,
-> = None;
+> = DartExternFn::new("CompleterCompleteErrorCaller");
 
 /// Stores pointer to [`CompleterFutureCaller`] extern function.
 ///
 /// Must be initialized by Dart during FFI initialization phase.
-static mut COMPLETER_FUTURE_CALLER: Option<CompleterFutureCaller> = None;
+static COMPLETER_FUTURE_CALLER: DartExternFn<CompleterFutureCaller> =
+    DartExternFn::new("CompleterFutureCaller");
 
 /// Registers the provided [`CompleterNewCaller`] as [`COMPLETER_NEW_CALLER`].
 ///
-/// # Safety
-///
 /// Must ONLY be called by Dart during FFI initialization.
 #[no_mangle]
-pub unsafe extern "C" fn register_new_completer_caller(f: CompleterNewCaller) {
-    COMPLETER_NEW_CALLER = Some(f);
+pub extern "C" fn register_new_completer_caller(f: CompleterNewCaller) {
+    COMPLETER_NEW_CALLER.register(f);
 }
 
 /// Registers the provided [`CompleterCompleteCaller`] as
 /// [`COMPLETER_COMPLETE_CALLER`].
 ///
-/// # Safety
-///
 /// Must ONLY be called by Dart during FFI initialization.
 #[no_mangle]
-pub unsafe extern "C" fn register_completer_complete_caller(
+pub extern "C" fn register_completer_complete_caller(
     f: CompleterCompleteCaller,
 ) {
-    COMPLETER_COMPLETE_CALLER = Some(f);
+    COMPLETER_COMPLETE_CALLER.register(f);
 }
 
 /// Registers the provided [`CompleterCompleteErrorCaller`] as
 /// [`COMPLETER_COMPLETE_ERROR_CALLER`].
 ///
-/// # Safety
-///
 /// Must ONLY be called by Dart during FFI initialization.
 #[no_mangle]
-pub unsafe extern "C" fn register_completer_complete_error_caller(
+pub extern "C" fn register_completer_complete_error_caller(
     f: CompleterCompleteErrorCaller,
 ) {
-    COMPLETER_COMPLETE_ERROR_CALLER = Some(f);
+    COMPLETER_COMPLETE_ERROR_CALLER.register(f);
 }
 
 /// Registers the provided [`CompleterFutureCaller`] as
 /// [`COMPLETER_FUTURE_CALLER`].
 ///
-/// # Safety
-///
 /// Must ONLY be called by Dart during FFI initialization.
 #[no_mangle]
-pub unsafe extern "C" fn register_completer_future_caller(
-    f: CompleterFutureCaller,
-) {
-    COMPLETER_FUTURE_CALLER = Some(f);
+pub extern "C" fn register_completer_future_caller(f: CompleterFutureCaller) {
+    COMPLETER_FUTURE_CALLER.register(f);
 }
 
 /// Dart [Future] which can be resolved from Rust.
@@ -164,7 +160,7 @@ impl<T, E> Completer<T, E> {
     #[must_use]
     pub fn new() -> Self {
         let handle = unsafe {
-            let completer = COMPLETER_NEW_CALLER.unwrap()();
+            let completer = COMPLETER_NEW_CALLER.get()();
             Dart_NewPersistentHandle_DL_Trampolined(completer)
         };
         Self {
@@ -182,7 +178,7 @@ impl<T, E> Completer<T, E> {
     pub fn future(&self) -> Dart_Handle {
         unsafe {
             let handle = Dart_HandleFromPersistent_DL_Trampolined(self.handle);
-            COMPLETER_FUTURE_CALLER.unwrap()(handle)
+            COMPLETER_FUTURE_CALLER.get()(handle)
         }
     }
 }
@@ -202,7 +198,7 @@ impl<T: Into<DartValue>, E> Completer<T, E> {
     pub fn complete(&self, arg: T) {
         unsafe {
             let handle = Dart_HandleFromPersistent_DL_Trampolined(self.handle);
-            COMPLETER_COMPLETE_CALLER.unwrap()(handle, arg.into());
+            COMPLETER_COMPLETE_CALLER.get()(handle, arg.into());
         }
     }
 }
@@ -214,7 +210,7 @@ impl<T> Completer<T, DartError> {
     pub fn complete_error(&self, e: DartError) {
         unsafe {
             let handle = Dart_HandleFromPersistent_DL_Trampolined(self.handle);
-            COMPLETER_COMPLETE_ERROR_CALLER.unwrap()(handle, e);
+            COMPLETER_COMPLETE_ERROR_CALLER.get()(handle, e);
         }
     }
 }
@@ -0,0 +1,61 @@
+//! Registration of the extern functions provided by Dart.
+//!
+//! Dart DL API doesn't allow Rust to call Dart functions directly, so Dart
+//! registers static functions, which Rust calls to manipulate Dart objects,
+//! during FFI initialization phase: after Dart DL API is initialized and before
+//! any other exported Rust function is called.
+//!
+//! Each such function is stored in a typed [`DartExternFn`] slot, which is
+//! filled by the exported `register_*` function, and is read when the
+//! function is called.
+
+use std::cell::Cell;
+
+/// Slot of an extern function of `F` type registered by Dart.
+///
+/// Should be used as a `static` item only.
+pub struct DartExternFn<F> {
+    /// Name of the registered function, used in diagnostics.
+    name: &'static str,
+
+    /// Registered function, if any.
+    f: Cell<Option<F>>,
+}
+
+// SAFETY: Jason runs on a single Dart isolate thread only, so the registered
+//         functions are never accessed concurrently.
+unsafe impl<F> Sync for DartExternFn<F> {}
+
+impl<F> DartExternFn<F> {
+    /// Creates a new empty [`DartExternFn`] slot with the provided `name`.
+    #[inline]
+    #[must_use]
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            f: Cell::new(None),
+        }
+    }
+}
+
+impl<F: Copy> DartExternFn<F> {
+    /// Registers the provided function in this [`DartExternFn`] slot,
+    /// replacing the previously registered one (if any).
+    #[inline]
+    pub fn register(&self, f: F) {
+        self.f.set(Some(f));
+    }
+
+    /// Returns the function registered in this [`DartExternFn`] slot.
+    ///
+    /// # Panics
+    ///
+    /// If no function has been registered by Dart yet.
+    #[inline]
+    #[must_use]
+    pub fn get(&self) -> F {
+        self.f.get().unwrap_or_else(|| {
+            panic!("`{}` function is not registered by Dart", self.name)
+        })
+    }
+}
@@ -14,10 +14,13 @@ use dart_sys::{Dart_Handle, Dart_PersistentHandle};
 
 use crate::{api::DartValue, platform::Callback};
 
-use super::dart_api::{
-    Dart_DeletePersistentHandle_DL_Trampolined,
-    Dart_HandleFromPersistent_DL_Trampolined,
-    Dart_NewPersistentHandle_DL_Trampolined,
+use super::{
+    dart_api::{
+        Dart_DeletePersistentHandle_DL_Trampolined,
+        Dart_HandleFromPersistent_DL_Trampolined,
+        Dart_NewPersistentHandle_DL_Trampolined,
+    },
+    DartExternFn,
 };
 
 /// Pointer to an extern function that accepts a [`Dart_Handle`] and a
@@ -26,16 +29,14 @@ type FnCaller = extern "C" fn(Dart_Handle, DartValue);
 
 /// Dart function used to invoke other Dart closures that accept a [`DartValue`]
 /// argument.
-static mut FN_CALLER: Option<FnCaller> = None;
+static FN_CALLER: DartExternFn<FnCaller> = DartExternFn::new("FnCaller");
 
 /// Registers the provided [`FnCaller`] as [`FN_CALLER`].
 ///
-/// # Safety
-///
 /// Must ONLY be called by Dart during FFI initialization.
 #[no_mangle]
-pub unsafe extern "C" fn register_fn_caller(f: FnCaller) {
-    FN_CALLER = Some(f);
+pub extern "C" fn register_fn_caller(f: FnCaller) {
+    FN_CALLER.register(f);
 }
 
 impl<A: Into<DartValue>> Callback<A> {
@@ -43,7 +44,7 @@ impl<A: Into<DartValue>> Callback<A> {
     /// argument to it.
     #[inline]
     pub fn call1<T: Into<A>>(&self, arg: T) {
-        if let Some(f) = self.0.borrow().as_ref() {
+        if let Some(f) = self.func() {
             f.call1(arg.into());
         }
     }
@@ -88,7 +89,7 @@ impl<T: Into<DartValue>> Function<T> {
         unsafe {
             let fn_handle =
                 Dart_HandleFromPersistent_DL_Trampolined(self.dart_fn);
-            FN_CALLER.unwrap()(fn_handle, arg.into());
+            FN_CALLER.get()(fn_handle, arg.into());
         }
     }
 }
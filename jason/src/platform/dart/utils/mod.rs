@@ -3,11 +3,13 @@
 pub mod completer;
 pub mod dart_api;
 mod event_listener;
+pub mod extern_fn;
 pub mod function;
 
 #[doc(inline)]
 pub use self::{
     completer::Completer,
     event_listener::{EventListener, EventListenerBindError},
+    extern_fn::DartExternFn,
     function::Function,
 };
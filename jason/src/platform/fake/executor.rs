@@ -0,0 +1,54 @@
+//! Single-threaded executor of [`Future`]s on the host.
+
+use std::{cell::RefCell, future::Future, time::Duration};
+
+use futures::{
+    executor::{LocalPool, LocalSpawner},
+    future,
+    task::LocalSpawnExt as _,
+};
+
+thread_local! {
+    /// [`LocalPool`] running all the [`Future`]s spawned on the current
+    /// thread.
+    static POOL: RefCell<LocalPool> = RefCell::new(LocalPool::new());
+
+    /// [`LocalSpawner`] of the current thread's [`POOL`].
+    static SPAWNER: LocalSpawner = POOL.with(|pool| pool.borrow().spawner());
+}
+
+/// Runs a Rust [`Future`] on the current thread.
+///
+/// Spawned [`Future`] is polled only while some [`block_on()`] call is in
+/// progress on the current thread.
+///
+/// # Panics
+///
+/// If the current thread's executor is shut down already.
+#[inline]
+pub fn spawn<F>(task: F)
+where
+    F: Future<Output = ()> + 'static,
+{
+    SPAWNER.with(|spawner| spawner.spawn_local(task).unwrap());
+}
+
+/// Runs the provided [`Future`] to completion on the current thread, polling
+/// all the [`spawn`]ed [`Future`]s meanwhile.
+///
+/// # Panics
+///
+/// If called from inside a [`Future`] executed by another [`block_on()`]
+/// call.
+pub fn block_on<F: Future>(fut: F) -> F::Output {
+    POOL.with(|pool| pool.borrow_mut().run_until(fut))
+}
+
+/// [`Future`] which never resolves.
+///
+/// Time doesn't pass on the fake platform, so timeouts never fire and tests
+/// stay deterministic.
+#[inline]
+pub async fn delay_for(_: Duration) {
+    future::pending::<()>().await;
+}
@@ -0,0 +1,19 @@
+//! Fake platform functionality for running unit tests on the host without a
+//! browser.
+//!
+//! Provides in-memory [`RtcPeerConnection`] and [`Transceiver`]
+//! implementations along with a single-threaded executor, so the state
+//! watchers of `peer` components may be tested with a plain `cargo test`.
+//! Everything else is taken from the `wasm32` platform.
+
+#![allow(clippy::needless_pass_by_value, clippy::unused_self)]
+
+pub mod executor;
+pub mod peer_connection;
+pub mod transceiver;
+
+pub use self::{
+    executor::{block_on, delay_for, spawn},
+    peer_connection::RtcPeerConnection,
+    transceiver::Transceiver,
+};
@@ -0,0 +1,259 @@
+//! In-memory [RTCPeerConnection][1] implementation.
+//!
+//! [1]: https://w3.org/TR/webrtc/#dom-rtcpeerconnection
+
+use std::cell::{Cell, RefCell};
+
+use medea_client_api_proto::{
+    IceConnectionState, IceServer, PeerConnectionState,
+};
+use tracerr::Traced;
+
+use crate::{
+    media::{MediaKind, TrackConstraints},
+    platform::{
+        IceCandidate, MediaStreamTrack, RtcPeerConnectionError, RtcStats,
+        SdpType, TransceiverDirection,
+    },
+};
+
+use super::transceiver::Transceiver;
+
+impl From<&TrackConstraints> for MediaKind {
+    fn from(media_type: &TrackConstraints) -> Self {
+        match media_type {
+            TrackConstraints::Audio(_) => Self::Audio,
+            TrackConstraints::Video(_) => Self::Video,
+        }
+    }
+}
+
+type Result<T> = std::result::Result<T, Traced<RtcPeerConnectionError>>;
+
+/// In-memory [RTCPeerConnection][1] which only keeps its [`Transceiver`]s.
+///
+/// Never connects anywhere, so never fires any events, and its [SDP] is a
+/// meaningless placeholder.
+///
+/// [1]: https://w3.org/TR/webrtc/#dom-rtcpeerconnection
+/// [SDP]: https://tools.ietf.org/html/rfc4566
+#[derive(Default)]
+pub struct RtcPeerConnection {
+    /// [`Transceiver`]s of this [`RtcPeerConnection`] in the order of their
+    /// creation.
+    transceivers: RefCell<Vec<Transceiver>>,
+
+    /// Flag which indicates that ICE restart will be performed on next
+    /// [`RtcPeerConnection::create_offer`] call.
+    ice_restart: Cell<bool>,
+}
+
+impl RtcPeerConnection {
+    /// Instantiates new [`RtcPeerConnection`].
+    ///
+    /// # Errors
+    ///
+    /// Never errors, but keeps the signature of the real implementation.
+    pub fn new<I>(_: I, _: bool) -> Result<Self>
+    where
+        I: IntoIterator<Item = IceServer>,
+    {
+        Ok(Self::default())
+    }
+
+    /// Returns empty [`RtcStats`], since no media is transferred.
+    ///
+    /// # Errors
+    ///
+    /// Never errors, but keeps the signature of the real implementation.
+    pub async fn get_stats(&self) -> Result<RtcStats> {
+        Ok(RtcStats(Vec::new()))
+    }
+
+    /// Does nothing, since no remote tracks are ever received.
+    #[inline]
+    pub fn on_track<F>(&self, _: Option<F>)
+    where
+        F: 'static + FnMut(MediaStreamTrack, Transceiver),
+    {
+    }
+
+    /// Does nothing, since no ICE candidates are ever gathered.
+    #[inline]
+    pub fn on_ice_candidate<F>(&self, _: Option<F>)
+    where
+        F: 'static + FnMut(IceCandidate),
+    {
+    }
+
+    /// Returns [`IceConnectionState::New`], since no connection is ever
+    /// established.
+    #[inline]
+    #[must_use]
+    pub fn ice_connection_state(&self) -> IceConnectionState {
+        IceConnectionState::New
+    }
+
+    /// Returns [`PeerConnectionState::New`], since no connection is ever
+    /// established.
+    #[inline]
+    #[must_use]
+    pub fn connection_state(&self) -> Option<PeerConnectionState> {
+        Some(PeerConnectionState::New)
+    }
+
+    /// Does nothing, since [`IceConnectionState`] never changes.
+    #[inline]
+    pub fn on_ice_connection_state_change<F>(&self, _: Option<F>)
+    where
+        F: 'static + FnMut(IceConnectionState),
+    {
+    }
+
+    /// Does nothing, since [`PeerConnectionState`] never changes.
+    #[inline]
+    pub fn on_connection_state_change<F>(&self, _: Option<F>)
+    where
+        F: 'static + FnMut(PeerConnectionState),
+    {
+    }
+
+    /// Accepts any remote ICE candidate.
+    ///
+    /// # Errors
+    ///
+    /// Never errors, but keeps the signature of the real implementation.
+    pub async fn add_ice_candidate(
+        &self,
+        _: &str,
+        _: Option<u16>,
+        _: &Option<String>,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Marks [`RtcPeerConnection`] to trigger ICE restart.
+    #[inline]
+    pub fn restart_ice(&self) {
+        self.ice_restart.set(true);
+    }
+
+    /// Accepts any [`IceServer`]s and ICE transport policy.
+    ///
+    /// # Errors
+    ///
+    /// Never errors, but keeps the signature of the real implementation.
+    pub fn set_ice_config<I>(&self, _: I, _: bool) -> Result<()>
+    where
+        I: IntoIterator<Item = IceServer>,
+    {
+        Ok(())
+    }
+
+    /// Assigns sequential [`mid`]s to the [`Transceiver`]s which don't have
+    /// them yet, as setting local description does.
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc/#dom-rtptransceiver-mid
+    fn assign_mids(&self) {
+        for (mid, tr) in self.transceivers.borrow().iter().enumerate() {
+            tr.assign_mid(mid.to_string());
+        }
+    }
+
+    /// Sets provided [SDP offer][`SdpType::Offer`] as local description,
+    /// assigning [`mid`]s to the [`Transceiver`]s.
+    ///
+    /// # Errors
+    ///
+    /// Never errors, but keeps the signature of the real implementation.
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc/#dom-rtptransceiver-mid
+    pub async fn set_offer(&self, _: &str) -> Result<()> {
+        self.assign_mids();
+        Ok(())
+    }
+
+    /// Sets provided [SDP answer][`SdpType::Answer`] as local description,
+    /// assigning [`mid`]s to the [`Transceiver`]s.
+    ///
+    /// # Errors
+    ///
+    /// Never errors, but keeps the signature of the real implementation.
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc/#dom-rtptransceiver-mid
+    pub async fn set_answer(&self, _: &str) -> Result<()> {
+        self.assign_mids();
+        Ok(())
+    }
+
+    /// Returns placeholder [SDP answer][`SdpType::Answer`].
+    ///
+    /// # Errors
+    ///
+    /// Never errors, but keeps the signature of the real implementation.
+    pub async fn create_answer(&self) -> Result<String> {
+        Ok(String::from("fake_answer"))
+    }
+
+    /// Does nothing, since the local description is never really applied.
+    ///
+    /// # Errors
+    ///
+    /// Never errors, but keeps the signature of the real implementation.
+    pub async fn rollback(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Returns placeholder [SDP offer][`SdpType::Offer`], resetting the ICE
+    /// restart flag.
+    ///
+    /// # Errors
+    ///
+    /// Never errors, but keeps the signature of the real implementation.
+    pub async fn create_offer(&self) -> Result<String> {
+        Ok(if self.ice_restart.take() {
+            String::from("fake_offer_ice_restart")
+        } else {
+            String::from("fake_offer")
+        })
+    }
+
+    /// Accepts any remote [SDP][`SdpType`].
+    ///
+    /// # Errors
+    ///
+    /// Never errors, but keeps the signature of the real implementation.
+    pub async fn set_remote_description(&self, _: SdpType) -> Result<()> {
+        Ok(())
+    }
+
+    /// Creates new [`Transceiver`] and adds it to this
+    /// [`RtcPeerConnection`].
+    pub fn add_transceiver(
+        &self,
+        kind: MediaKind,
+        direction: TransceiverDirection,
+    ) -> Transceiver {
+        let transceiver = Transceiver::new(kind, direction);
+        self.transceivers.borrow_mut().push(transceiver.clone());
+        transceiver
+    }
+
+    /// Returns [`Transceiver`] of this [`RtcPeerConnection`] by provided
+    /// `mid`.
+    #[must_use]
+    pub fn get_transceiver_by_mid(&self, mid: &str) -> Option<Transceiver> {
+        self.transceivers
+            .borrow()
+            .iter()
+            .find(|tr| tr.mid().as_deref() == Some(mid))
+            .cloned()
+    }
+
+    /// Returns all the [`Transceiver`]s of this [`RtcPeerConnection`] in the
+    /// order of their creation.
+    #[must_use]
+    pub fn transceivers(&self) -> Vec<Transceiver> {
+        self.transceivers.borrow().clone()
+    }
+}
@@ -0,0 +1,204 @@
+//! In-memory [RTCRtpTransceiver] implementation.
+//!
+//! [RTCRtpTransceiver]: https://w3.org/TR/webrtc/#dom-rtcrtptransceiver
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use futures::future::{self, LocalBoxFuture};
+
+use crate::{
+    media::{track::local, DegradationPreference, MediaKind},
+    platform::{Error, TransceiverDirection},
+};
+
+/// In-memory [RTCRtpTransceiver] which only tracks its direction and sent
+/// [`local::Track`].
+///
+/// Clones share the same state, just like clones of a real transceiver do.
+///
+/// [RTCRtpTransceiver]: https://w3.org/TR/webrtc/#dom-rtcrtptransceiver
+#[derive(Clone)]
+pub struct Transceiver(Rc<Inner>);
+
+/// Shared state of a [`Transceiver`].
+struct Inner {
+    /// [`MediaKind`] of this [`Transceiver`].
+    kind: MediaKind,
+
+    /// Current [`TransceiverDirection`] of this [`Transceiver`].
+    direction: Cell<TransceiverDirection>,
+
+    /// [`mid`] of this [`Transceiver`], assigned once a local description is
+    /// set.
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc/#dom-rtptransceiver-mid
+    mid: RefCell<Option<String>>,
+
+    /// [`local::Track`] being sent by this [`Transceiver`], if any.
+    send_track: RefCell<Option<Rc<local::Track>>>,
+
+    /// Maximum bitrate of the media sent by this [`Transceiver`], if any.
+    max_bitrate: Cell<Option<u32>>,
+
+    /// Indicator whether this [`Transceiver`] is stopped.
+    stopped: Cell<bool>,
+}
+
+impl Transceiver {
+    /// Creates a new [`Transceiver`] of the provided [`MediaKind`] with the
+    /// provided [`TransceiverDirection`].
+    #[must_use]
+    pub fn new(kind: MediaKind, direction: TransceiverDirection) -> Self {
+        Self(Rc::new(Inner {
+            kind,
+            direction: Cell::new(direction),
+            mid: RefCell::new(None),
+            send_track: RefCell::new(None),
+            max_bitrate: Cell::new(None),
+            stopped: Cell::new(false),
+        }))
+    }
+
+    /// Returns [`MediaKind`] of this [`Transceiver`].
+    #[inline]
+    #[must_use]
+    pub fn kind(&self) -> MediaKind {
+        self.0.kind
+    }
+
+    /// Returns current [`TransceiverDirection`] of this [`Transceiver`].
+    #[inline]
+    #[must_use]
+    pub fn direction(&self) -> TransceiverDirection {
+        self.0.direction.get()
+    }
+
+    /// Disables provided [`TransceiverDirection`] of this [`Transceiver`].
+    #[inline]
+    pub fn sub_direction(&self, disabled_direction: TransceiverDirection) {
+        self.0.direction.set(self.direction() - disabled_direction);
+    }
+
+    /// Enables provided [`TransceiverDirection`] of this [`Transceiver`].
+    #[inline]
+    pub fn add_direction(&self, enabled_direction: TransceiverDirection) {
+        self.0.direction.set(self.direction() | enabled_direction);
+    }
+
+    /// Indicates whether the provided [`TransceiverDirection`] is enabled for
+    /// this [`Transceiver`].
+    #[inline]
+    #[must_use]
+    pub fn has_direction(&self, direction: TransceiverDirection) -> bool {
+        self.direction().contains(direction)
+    }
+
+    /// Replaces [`TransceiverDirection::SEND`] [`local::Track`] of this
+    /// [`Transceiver`].
+    ///
+    /// # Errors
+    ///
+    /// Never errors, but keeps the signature of the real implementation.
+    pub async fn set_send_track(
+        &self,
+        new_track: Rc<local::Track>,
+    ) -> Result<(), Error> {
+        self.0.send_track.replace(Some(new_track));
+        Ok(())
+    }
+
+    /// Sets a [`TransceiverDirection::SEND`] [`local::Track`] of this
+    /// [`Transceiver`] to [`None`].
+    pub fn drop_send_track(&self) -> LocalBoxFuture<'static, ()> {
+        self.0.send_track.replace(None);
+        Box::pin(future::ready(()))
+    }
+
+    /// Returns [`mid`] of this [`Transceiver`].
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc/#dom-rtptransceiver-mid
+    #[inline]
+    #[must_use]
+    pub fn mid(&self) -> Option<String> {
+        self.0.mid.borrow().clone()
+    }
+
+    /// Assigns the provided [`mid`] to this [`Transceiver`], unless it has one
+    /// already.
+    ///
+    /// [`mid`]: https://w3.org/TR/webrtc/#dom-rtptransceiver-mid
+    pub(super) fn assign_mid(&self, mid: String) {
+        self.0.mid.borrow_mut().get_or_insert(mid);
+    }
+
+    /// Returns [`local::Track`] that is being send to remote, if any.
+    #[inline]
+    #[must_use]
+    pub fn send_track(&self) -> Option<Rc<local::Track>> {
+        self.0.send_track.borrow().clone()
+    }
+
+    /// Indicates whether this [`Transceiver`] has [`local::Track`].
+    #[inline]
+    #[must_use]
+    pub fn has_send_track(&self) -> bool {
+        self.0.send_track.borrow().is_some()
+    }
+
+    /// Sets the underlying [`local::Track`]'s `enabled` field to the provided
+    /// value, if any.
+    #[inline]
+    pub fn set_send_track_enabled(&self, enabled: bool) {
+        if let Some(track) = self.0.send_track.borrow().as_ref() {
+            track.set_enabled(enabled);
+        }
+    }
+
+    /// Does nothing, since there is no media encoding on the fake platform.
+    ///
+    /// # Errors
+    ///
+    /// Never errors, but keeps the signature of the real implementation.
+    pub async fn set_degradation_preference(
+        &self,
+        _: DegradationPreference,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Records the provided maximum bitrate of the sent media.
+    ///
+    /// # Errors
+    ///
+    /// Never errors, but keeps the signature of the real implementation.
+    pub async fn set_max_bitrate(
+        &self,
+        max_bitrate: Option<u32>,
+    ) -> Result<(), Error> {
+        self.0.max_bitrate.set(max_bitrate);
+        Ok(())
+    }
+
+    /// Returns the maximum bitrate of the sent media, if any.
+    #[inline]
+    #[must_use]
+    pub fn max_bitrate(&self) -> Option<u32> {
+        self.0.max_bitrate.get()
+    }
+
+    /// Stops this [`Transceiver`].
+    #[inline]
+    pub fn stop(&self) {
+        self.0.stopped.set(true);
+    }
+
+    /// Indicates whether this [`Transceiver`] is stopped.
+    #[inline]
+    #[must_use]
+    pub fn is_stopped(&self) -> bool {
+        self.0.stopped.get()
+    }
+}
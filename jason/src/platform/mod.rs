@@ -10,6 +10,10 @@ cfg_if::cfg_if! {
     if #[cfg(target_os = "android")] {
         mod dart;
         pub use self::dart::*;
+    } else if #[cfg(all(feature = "mockable", not(target_arch = "wasm32")))] {
+        mod fake;
+        mod wasm;
+        pub use self::{fake::*, wasm::*};
     } else {
         mod wasm;
         pub use self::wasm::*;
@@ -100,4 +100,10 @@ pub enum RtcPeerConnectionError {
     #[display(fmt = "Failed to set remote SDP description: {}", _0)]
     #[from(ignore)]
     SetRemoteDescriptionFailed(platform::Error),
+
+    /// Occurs if the configuration of the [`platform::RtcPeerConnection`]
+    /// cannot be changed.
+    #[display(fmt = "Failed to set PeerConnection configuration: {}", _0)]
+    #[from(ignore)]
+    SetConfigurationFailed(platform::Error),
 }
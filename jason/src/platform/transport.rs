@@ -2,7 +2,7 @@
 
 use derive_more::Display;
 use futures::stream::LocalBoxStream;
-use medea_client_api_proto::{ClientMsg, ServerMsg};
+use medea_client_api_proto::{ClientMsg, MessageTooLarge, ServerMsg};
 use tracerr::Traced;
 
 use crate::{
@@ -91,4 +91,13 @@ pub enum TransportError {
     /// Occurs when message is sent to a closed socket.
     #[display(fmt = "Underlying socket is closed")]
     ClosedSocket,
+
+    /// Occurs when a sent [`ClientMsg`] exceeds the [`MAX_MESSAGE_SIZE`].
+    ///
+    /// Received [`ServerMsg`]s are not limited, since Media Server doesn't
+    /// limit them either.
+    ///
+    /// [`MAX_MESSAGE_SIZE`]: medea_client_api_proto::MAX_MESSAGE_SIZE
+    #[display(fmt = "{}", _0)]
+    MessageTooLarge(MessageTooLarge),
 }
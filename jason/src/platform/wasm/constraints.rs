@@ -6,6 +6,8 @@ use crate::media::{
     DisplayVideoTrackConstraints,
 };
 use derive_more::{AsRef, Into};
+use js_sys::Reflect;
+use wasm_bindgen::JsValue;
 use web_sys::{
     ConstrainDomStringParameters, ConstrainDoubleRange, MediaTrackConstraints,
 };
@@ -56,11 +58,60 @@ impl From<AudioTrackConstraints> for MediaTrackConstraints {
             constraints
                 .device_id(&ConstrainDomStringParameters::from(&device_id));
         }
+        if let Some(sample_rate) = track_constraints.sample_rate {
+            set_constraint(
+                &constraints,
+                "sampleRate",
+                &ConstrainDoubleRange::from(sample_rate),
+            );
+        }
+        if let Some(channel_count) = track_constraints.channel_count {
+            set_constraint(
+                &constraints,
+                "channelCount",
+                &ConstrainDoubleRange::from(channel_count),
+            );
+        }
+        if let Some(latency) = track_constraints.latency {
+            // `latency` is specified in seconds.
+            let mut latency_secs = ConstrainDoubleRange::new();
+            match latency {
+                ConstrainU32::Exact(val) => {
+                    latency_secs.exact(f64::from(val) / 1000.0);
+                }
+                ConstrainU32::Ideal(val) => {
+                    latency_secs.ideal(f64::from(val) / 1000.0);
+                }
+                ConstrainU32::Range(min, max) => {
+                    latency_secs
+                        .min(f64::from(min) / 1000.0)
+                        .max(f64::from(max) / 1000.0);
+                }
+            }
+            set_constraint(&constraints, "latency", &latency_secs);
+        }
+        if let Some(voice_isolation) = track_constraints.voice_isolation {
+            set_constraint(
+                &constraints,
+                "voiceIsolation",
+                &JsValue::from_bool(voice_isolation),
+            );
+        }
 
         constraints
     }
 }
 
+/// Sets the provided constraint of the [`MediaTrackConstraints`] not exposed
+/// by the [`web_sys`] bindings.
+fn set_constraint(
+    constraints: &MediaTrackConstraints,
+    name: &str,
+    val: &JsValue,
+) {
+    let _ = Reflect::set(constraints, &JsValue::from_str(name), val);
+}
+
 impl From<DeviceVideoTrackConstraints> for MediaTrackConstraints {
     fn from(track_constraints: DeviceVideoTrackConstraints) -> Self {
         let mut constraints = Self::new();
@@ -79,11 +130,64 @@ impl From<DeviceVideoTrackConstraints> for MediaTrackConstraints {
         if let Some(height) = track_constraints.height {
             constraints.height(&ConstrainDoubleRange::from(height));
         }
+        if let Some(torch) = track_constraints.torch {
+            set_constraint(&constraints, "torch", &JsValue::from_bool(torch));
+        }
+        if let Some(zoom) = track_constraints.zoom {
+            // `zoom` is specified as a multiplier.
+            let mut zoom_factor = ConstrainDoubleRange::new();
+            match zoom {
+                ConstrainU32::Exact(val) => {
+                    zoom_factor.exact(f64::from(val) / 100.0);
+                }
+                ConstrainU32::Ideal(val) => {
+                    zoom_factor.ideal(f64::from(val) / 100.0);
+                }
+                ConstrainU32::Range(min, max) => {
+                    zoom_factor
+                        .min(f64::from(min) / 100.0)
+                        .max(f64::from(max) / 100.0);
+                }
+            }
+            set_constraint(&constraints, "zoom", &zoom_factor);
+        }
+        if let Some(focus_mode) = track_constraints.focus_mode {
+            set_constraint(
+                &constraints,
+                "focusMode",
+                &ConstrainDomStringParameters::from(&focus_mode),
+            );
+        }
 
         constraints
     }
 }
 
+/// Builds [`MediaTrackConstraints`] applying the torch, zoom and focus mode of
+/// the provided [`DeviceVideoTrackConstraints`] as an [advanced][1] constraint
+/// set, so the ones unsupported by a device are ignored instead of failing the
+/// request.
+///
+/// [1]: https://tinyurl.com/w3-streams#dom-mediatrackconstraints-advanced
+#[must_use]
+pub(super) fn advanced_constraints(
+    track_constraints: DeviceVideoTrackConstraints,
+) -> MediaTrackConstraints {
+    let advanced = DeviceVideoTrackConstraints {
+        torch: track_constraints.torch,
+        zoom: track_constraints.zoom,
+        focus_mode: track_constraints.focus_mode,
+        ..DeviceVideoTrackConstraints::default()
+    };
+    let constraints = MediaTrackConstraints::new();
+    set_constraint(
+        &constraints,
+        "advanced",
+        &js_sys::Array::of1(&MediaTrackConstraints::from(advanced)),
+    );
+    constraints
+}
+
 impl From<ConstrainU32> for ConstrainDoubleRange {
     fn from(from: ConstrainU32) -> Self {
         let mut constraint = ConstrainDoubleRange::new();
@@ -0,0 +1,37 @@
+//! Executor of [`Future`]s in the browser environment.
+
+use std::{convert::TryInto as _, future::Future, time::Duration};
+
+use js_sys::Promise;
+use wasm_bindgen_futures::JsFuture;
+
+use super::window;
+
+/// Runs a Rust [`Future`] on the current thread.
+#[inline]
+pub fn spawn<F>(task: F)
+where
+    F: Future<Output = ()> + 'static,
+{
+    wasm_bindgen_futures::spawn_local(task);
+}
+
+/// [`Future`] which resolves after the provided [`Duration`].
+///
+/// # Panics
+///
+/// If fails to interact with JS side.
+///
+/// [`Future`]: std::future::Future
+pub async fn delay_for(delay: Duration) {
+    let delay_ms = delay.as_millis().try_into().unwrap_or(i32::MAX);
+    JsFuture::from(Promise::new(&mut |yes, _| {
+        window()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(
+                &yes, delay_ms,
+            )
+            .unwrap();
+    }))
+    .await
+    .unwrap();
+}
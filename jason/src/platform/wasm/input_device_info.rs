@@ -5,9 +5,14 @@
 use std::convert::TryFrom;
 
 use derive_more::Display;
+use js_sys::{Array, Function, Reflect};
+use wasm_bindgen::{JsCast as _, JsValue};
 use web_sys as sys;
 
-use crate::media::MediaKind;
+use crate::{
+    media::{CapabilityRange, FacingMode, InputDeviceCapabilities, MediaKind},
+    platform::wasm::get_property_by_name,
+};
 
 /// Errors that may occur when parsing [MediaDeviceInfo][1].
 ///
@@ -85,6 +90,75 @@ impl InputDeviceInfo {
     pub fn group_id(&self) -> String {
         self.info.group_id()
     }
+
+    /// Returns [`InputDeviceCapabilities`] of the represented device.
+    ///
+    /// Returns [`None`] if the [getCapabilities()][1] method is not supported
+    /// by the browser.
+    ///
+    /// [1]: https://w3.org/TR/mediacapture-streams#dom-inputdeviceinfo
+    #[must_use]
+    pub fn capabilities(&self) -> Option<InputDeviceCapabilities> {
+        let get_capabilities =
+            Reflect::get(&self.info, &JsValue::from_str("getCapabilities"))
+                .ok()?
+                .dyn_into::<Function>()
+                .ok()?;
+        let caps = get_capabilities.call0(&self.info).ok()?;
+
+        Some(InputDeviceCapabilities {
+            sample_rate: range_of(&caps, "sampleRate", 1.0),
+            channel_count: range_of(&caps, "channelCount", 1.0),
+            // `latency` is specified in seconds.
+            latency: range_of(&caps, "latency", 1000.0),
+            voice_isolation: get_property_by_name(
+                &caps,
+                "voiceIsolation",
+                |v| Some(Array::from(&v).includes(&JsValue::from(true), 0)),
+            )
+            .unwrap_or_default(),
+            width: range_of(&caps, "width", 1.0),
+            height: range_of(&caps, "height", 1.0),
+            frame_rate: range_of(&caps, "frameRate", 1.0),
+            facing_mode: get_property_by_name(&caps, "facingMode", |v| {
+                Some(
+                    Array::from(&v)
+                        .iter()
+                        .filter_map(|mode| match mode.as_string()?.as_str() {
+                            "user" => Some(FacingMode::User),
+                            "environment" => Some(FacingMode::Environment),
+                            "left" => Some(FacingMode::Left),
+                            "right" => Some(FacingMode::Right),
+                            _ => None,
+                        })
+                        .collect(),
+                )
+            })
+            .unwrap_or_default(),
+        })
+    }
+}
+
+/// Returns a [DoubleRange][1] property with the provided `name` of the provided
+/// [MediaTrackCapabilities][2], multiplying its bounds by the provided `scale`.
+///
+/// [1]: https://w3.org/TR/mediacapture-streams#dom-doublerange
+/// [2]: https://w3.org/TR/mediacapture-streams#dom-mediatrackcapabilities
+fn range_of(
+    caps: &JsValue,
+    name: &str,
+    scale: f64,
+) -> Option<CapabilityRange<u32>> {
+    let range = Reflect::get(caps, &JsValue::from_str(name)).ok()?;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let bound = |name| {
+        get_property_by_name(&range, name, |v| v.as_f64())
+            .map(|v| (v * scale).round() as u32)
+    };
+    Some(CapabilityRange {
+        min: bound("min")?,
+        max: bound("max")?,
+    })
 }
 
 impl TryFrom<sys::MediaDeviceInfo> for InputDeviceInfo {
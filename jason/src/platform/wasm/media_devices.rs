@@ -3,16 +3,21 @@
 //! [1]: https://w3.org/TR/mediacapture-streams#mediadevices
 
 use std::convert::TryFrom as _;
+use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::JsFuture;
 
+use futures::stream::{self, LocalBoxStream, StreamExt as _};
 use tracerr::Traced;
 
-use crate::platform::{
-    DisplayMediaStreamConstraints, Error, InputDeviceInfo,
-    MediaStreamConstraints, MediaStreamTrack,
+use crate::{
+    media::{AudioRouteChangeReason, PermissionState},
+    platform::{
+        DisplayMediaStreamConstraints, Error, InputDeviceInfo,
+        MediaStreamConstraints, MediaStreamTrack,
+    },
 };
 
-use super::window;
+use super::{get_property_by_name, window};
 
 /// Collects information about the User Agent's available media input devices.
 ///
@@ -57,6 +62,40 @@ pub async fn enumerate_devices() -> Result<Vec<InputDeviceInfo>, Traced<Error>>
         .collect())
 }
 
+/// Queries a state of the permission with the provided `name` without
+/// prompting a user.
+///
+/// Adapter for a [Permissions.query()][1] function.
+///
+/// Returns [`None`] if the [Permissions API][2] is not available, or it doesn't
+/// support the provided permission `name`.
+///
+/// [1]: https://w3.org/TR/permissions#dom-permissions-query
+/// [2]: https://w3.org/TR/permissions
+pub async fn query_permission(name: &str) -> Option<PermissionState> {
+    let permissions =
+        get_property_by_name(&window().navigator(), "permissions", |p| {
+            Some(p).filter(JsValue::is_object)
+        })?;
+    let query = get_property_by_name(&permissions, "query", |q| {
+        Some(q).filter(JsValue::is_function)
+    })
+    .map(js_sys::Function::from)?;
+
+    let descriptor = js_sys::Object::new();
+    js_sys::Reflect::set(
+        &descriptor,
+        &JsValue::from_str("name"),
+        &JsValue::from_str(name),
+    )
+    .ok()?;
+    let status = query.call1(&permissions, &descriptor).ok()?;
+    let status = JsFuture::from(js_sys::Promise::from(status)).await.ok()?;
+
+    get_property_by_name(&status, "state", |s| s.as_string())
+        .map(|s| PermissionState::from_name(&s))
+}
+
 /// Prompts a user for a permission to use a media input which produces vector
 /// of [`MediaStreamTrack`]s containing the requested types of media.
 ///
@@ -145,3 +184,14 @@ pub async fn get_display_media(
         .map(|tr| MediaStreamTrack::from(tr.unwrap()))
         .collect())
 }
+
+/// Subscribes to the audio output route changes.
+///
+/// Never yields anything, since browsers don't report audio output route
+/// changes.
+#[inline]
+#[must_use]
+pub fn audio_route_changes() -> LocalBoxStream<'static, AudioRouteChangeReason>
+{
+    stream::empty().boxed_local()
+}
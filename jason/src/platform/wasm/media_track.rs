@@ -5,10 +5,23 @@
 use std::{cell::RefCell, rc::Rc};
 
 use derive_more::AsRef;
+use js_sys::Reflect;
+use tracerr::Traced;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
 
 use crate::{
-    media::{track::MediaStreamTrackState, FacingMode, MediaKind},
-    platform::wasm::{get_property_by_name, utils::EventListener},
+    media::{
+        track::MediaStreamTrackState, ContentHint, DeviceVideoTrackConstraints,
+        FacingMode, MediaKind,
+    },
+    platform::{
+        wasm::{
+            constraints::advanced_constraints, get_property_by_name,
+            utils::EventListener,
+        },
+        Error,
+    },
 };
 
 /// Wrapper around [MediaStreamTrack][1] received from a
@@ -155,6 +168,36 @@ impl MediaStreamTrack {
         })
     }
 
+    /// Returns a [`sampleRate`][1] of the underlying [MediaStreamTrack][2].
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediatracksettings-samplerate
+    /// [2]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+    #[inline]
+    #[must_use]
+    pub fn sample_rate(&self) -> Option<u32> {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        get_property_by_name(
+            &self.sys_track.get_settings(),
+            "sampleRate",
+            |v| v.as_f64().map(|v| v as u32),
+        )
+    }
+
+    /// Returns a [`channelCount`][1] of the underlying [MediaStreamTrack][2].
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediatracksettings-channelcount
+    /// [2]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+    #[inline]
+    #[must_use]
+    pub fn channel_count(&self) -> Option<u32> {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        get_property_by_name(
+            &self.sys_track.get_settings(),
+            "channelCount",
+            |v| v.as_f64().map(|v| v as u32),
+        )
+    }
+
     /// Changes an [`enabled`][1] attribute in the underlying
     /// [MediaStreamTrack][2].
     ///
@@ -165,6 +208,50 @@ impl MediaStreamTrack {
         self.sys_track.set_enabled(enabled);
     }
 
+    /// Changes a [`contentHint`][1] attribute in the underlying
+    /// [MediaStreamTrack][2].
+    ///
+    /// Does nothing if a browser doesn't support [`contentHint`][1].
+    ///
+    /// [1]: https://w3.org/TR/mst-content-hint#dom-mediastreamtrack-contenthint
+    /// [2]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+    #[inline]
+    pub fn set_content_hint(&self, hint: ContentHint) {
+        let _ = Reflect::set(
+            &self.sys_track,
+            &JsValue::from_str("contentHint"),
+            &JsValue::from_str(hint.as_str()),
+        );
+    }
+
+    /// Applies the provided [`DeviceVideoTrackConstraints`] to the underlying
+    /// [MediaStreamTrack][2] as an advanced constraint set via
+    /// [applyConstraints()][1].
+    ///
+    /// # Errors
+    ///
+    /// With [`Error`] if [applyConstraints()][1] fails.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediastreamtrack-applyconstraints
+    /// [2]: https://w3.org/TR/mediacapture-streams#mediastreamtrack
+    pub async fn apply_advanced(
+        &self,
+        constraints: DeviceVideoTrackConstraints,
+    ) -> Result<(), Traced<Error>> {
+        let promise = self
+            .sys_track
+            .apply_constraints_with_constraints(&advanced_constraints(
+                constraints,
+            ))
+            .map_err(Error::from)
+            .map_err(tracerr::wrap!())?;
+        JsFuture::from(promise)
+            .await
+            .map(drop)
+            .map_err(Error::from)
+            .map_err(tracerr::wrap!())
+    }
+
     /// Changes a [`readyState`][1] attribute in the underlying
     /// [MediaStreamTrack][2] to [`ended`][3].
     ///
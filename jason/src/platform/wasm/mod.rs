@@ -2,37 +2,49 @@
 
 pub mod constraints;
 pub mod error;
-pub mod ice_server;
 pub mod input_device_info;
 pub mod media_devices;
 pub mod media_track;
-pub mod peer_connection;
 pub mod rtc_stats;
-pub mod transceiver;
 pub mod transport;
 pub mod utils;
 
-use std::{convert::TryInto as _, time::Duration};
-
-use futures::Future;
-use js_sys::{Promise, Reflect};
+use js_sys::Reflect;
+use medea_client_api_proto::ClientCapabilities;
 use wasm_bindgen::JsValue;
-use wasm_bindgen_futures::JsFuture;
 use web_sys::Window;
 
 pub use self::{
     constraints::{DisplayMediaStreamConstraints, MediaStreamConstraints},
     error::Error,
     input_device_info::InputDeviceInfo,
-    media_devices::{enumerate_devices, get_display_media, get_user_media},
+    media_devices::{
+        audio_route_changes, enumerate_devices, get_display_media,
+        get_user_media, query_permission,
+    },
     media_track::MediaStreamTrack,
-    peer_connection::RtcPeerConnection,
     rtc_stats::RtcStats,
-    transceiver::Transceiver,
     transport::WebSocketRpcTransport,
     utils::Function,
 };
 
+// Host builds with the `mockable` feature use the fake implementations of
+// these instead (see `platform::fake` module).
+cfg_if::cfg_if! {
+    if #[cfg(not(all(feature = "mockable", not(target_arch = "wasm32"))))] {
+        pub mod executor;
+        pub mod ice_server;
+        pub mod peer_connection;
+        pub mod transceiver;
+
+        pub use self::{
+            executor::{delay_for, spawn},
+            peer_connection::RtcPeerConnection,
+            transceiver::Transceiver,
+        };
+    }
+}
+
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
 #[cfg(feature = "wee_alloc")]
@@ -48,40 +60,100 @@ static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 #[cfg(feature = "console_error_panic_hook")]
 pub use console_error_panic_hook::set_once as set_panic_hook;
 
-/// Initialize [`wasm_logger`] as default application logger.
-///
-/// [`wasm_logger`]: https://docs.rs/wasm-logger
-pub fn init_logger() {
-    wasm_logger::init(wasm_logger::Config::default());
+/// Writes the provided [`log::Record`] into the browser console.
+pub fn console_log(record: &log::Record<'_>) {
+    let msg = JsValue::from(format!(
+        "{} {}: {}",
+        record.level(),
+        record.target(),
+        record.args(),
+    ));
+    match record.level() {
+        log::Level::Error => web_sys::console::error_1(&msg),
+        log::Level::Warn => web_sys::console::warn_1(&msg),
+        log::Level::Info => web_sys::console::info_1(&msg),
+        log::Level::Debug | log::Level::Trace => {
+            web_sys::console::debug_1(&msg)
+        }
+    }
 }
 
-/// Runs a Rust [`Future`] on the current thread.
+/// Returns the current time in milliseconds since the UNIX epoch.
 #[inline]
-pub fn spawn<F>(task: F)
-where
-    F: Future<Output = ()> + 'static,
-{
-    wasm_bindgen_futures::spawn_local(task);
+#[must_use]
+pub fn unix_time_millis() -> f64 {
+    js_sys::Date::now()
 }
 
-/// [`Future`] which resolves after the provided [`Duration`].
+/// Indicates whether the page is hidden from a user at the moment (e.g. its
+/// tab is in background or the browser window is minimized).
 ///
-/// # Panics
+/// See [`Document.hidden`][1] for details.
+///
+/// [1]: https://developer.mozilla.org/docs/Web/API/Document/hidden
+#[must_use]
+pub fn is_page_hidden() -> bool {
+    window().document().map_or(false, |doc| doc.hidden())
+}
+
+/// Returns [`ClientCapabilities`] of the browser, to be reported to a media
+/// server.
 ///
-/// If fails to interact with JS side.
+/// Codecs are listed via [`RTCRtpSender.getCapabilities()`][1], and insertable
+/// streams are considered available if
+/// [`RTCRtpSender.createEncodedStreams()`][2] is present.
 ///
-/// [`Future`]: std::future::Future
-pub async fn delay_for(delay: Duration) {
-    let delay_ms = delay.as_millis().try_into().unwrap_or(i32::MAX);
-    JsFuture::from(Promise::new(&mut |yes, _| {
-        window()
-            .set_timeout_with_callback_and_timeout_and_arguments_0(
-                &yes, delay_ms,
-            )
-            .unwrap();
-    }))
-    .await
-    .unwrap();
+/// [1]: https://w3.org/TR/webrtc#dom-rtcrtpsender-getcapabilities
+/// [2]: https://w3.org/TR/webrtc-encoded-transform
+#[must_use]
+pub fn client_capabilities() -> ClientCapabilities {
+    let sender = get_property_by_name(&window(), "RTCRtpSender", Some)
+        .filter(|s| !s.is_undefined());
+    let has_method = |name: &str| {
+        sender
+            .as_ref()
+            .and_then(|s| get_property_by_name(s, "prototype", Some))
+            .and_then(|p| {
+                get_property_by_name(&p, name, |f| Some(f.is_function()))
+            })
+            .unwrap_or_default()
+    };
+
+    let mut codecs = Vec::new();
+    let get_capabilities = sender.as_ref().and_then(|s| {
+        get_property_by_name(s, "getCapabilities", Some)
+            .filter(JsValue::is_function)
+            .map(|f| (s, js_sys::Function::from(f)))
+    });
+    if let Some((sender, get_capabilities)) = get_capabilities {
+        for kind in &["audio", "video"] {
+            let kind_codecs = match get_capabilities
+                .call1(sender, &JsValue::from_str(kind))
+                .ok()
+                .filter(JsValue::is_object)
+                .and_then(|c| get_property_by_name(&c, "codecs", Some))
+            {
+                Some(c) => js_sys::Array::from(&c),
+                None => continue,
+            };
+            for codec in kind_codecs.iter() {
+                let mime =
+                    get_property_by_name(&codec, "mimeType", |m| m.as_string());
+                if let Some(mime) = mime {
+                    if !codecs.contains(&mime) {
+                        codecs.push(mime);
+                    }
+                }
+            }
+        }
+    }
+
+    ClientCapabilities {
+        user_agent: window().navigator().user_agent().ok(),
+        codecs,
+        simulcast: has_method("setParameters"),
+        insertable_streams: has_method("createEncodedStreams"),
+    }
 }
 
 /// Returns property of JS object by name if its defined.
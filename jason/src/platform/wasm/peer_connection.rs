@@ -401,6 +401,41 @@ impl RtcPeerConnection {
         self.ice_restart.set(true);
     }
 
+    /// Updates [`IceServer`]s and ICE transport policy of this
+    /// [`RtcPeerConnection`].
+    ///
+    /// New configuration is applied to the ICE candidates gathered after the
+    /// next ICE restart.
+    ///
+    /// # Errors
+    ///
+    /// With [`RtcPeerConnectionError::SetConfigurationFailed`] if
+    /// [RtcPeerConnection.setConfiguration()][1] fails.
+    ///
+    /// [1]: https://w3.org/TR/webrtc/#dom-rtcpeerconnection-setconfiguration
+    pub fn set_ice_config<I>(
+        &self,
+        ice_servers: I,
+        is_force_relayed: bool,
+    ) -> Result<()>
+    where
+        I: IntoIterator<Item = IceServer>,
+    {
+        let mut peer_conf = self.peer.get_configuration();
+        let policy = if is_force_relayed {
+            RtcIceTransportPolicy::Relay
+        } else {
+            RtcIceTransportPolicy::All
+        };
+        peer_conf.ice_transport_policy(policy);
+        peer_conf.ice_servers(&RtcIceServers::from(ice_servers));
+        self.peer
+            .set_configuration(&peer_conf)
+            .map_err(Into::into)
+            .map_err(RtcPeerConnectionError::SetConfigurationFailed)
+            .map_err(tracerr::wrap!())
+    }
+
     /// Sets local description to the provided one [`RtcSdpType`].
     ///
     /// # Errors
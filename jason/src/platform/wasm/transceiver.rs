@@ -3,12 +3,14 @@
 use std::{cell::RefCell, rc::Rc};
 
 use futures::future::LocalBoxFuture;
+use js_sys::{Array, Object, Reflect};
 use medea_client_api_proto::Direction as DirectionProto;
+use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{RtcRtpTransceiver, RtcRtpTransceiverDirection};
 
 use crate::{
-    media::track::local,
+    media::{track::local, DegradationPreference},
     platform::{transceiver::TransceiverDirection, Error},
 };
 
@@ -124,6 +126,65 @@ impl Transceiver {
         }
     }
 
+    /// Sets a [`degradationPreference`][1] of the underlying
+    /// [RTCRtpSender][2].
+    ///
+    /// # Errors
+    ///
+    /// Errors with JS error if the underlying [`setParameters`][3] call fails.
+    ///
+    /// [1]: https://w3.org/TR/webrtc/#dom-rtcrtpsendparameters
+    /// [2]: https://w3.org/TR/webrtc/#dom-rtcrtpsender
+    /// [3]: https://w3.org/TR/webrtc/#dom-rtcrtpsender-setparameters
+    pub async fn set_degradation_preference(
+        &self,
+        preference: DegradationPreference,
+    ) -> Result<(), Error> {
+        let sender = self.transceiver.sender();
+        let params = sender.get_parameters();
+        Reflect::set(
+            &params,
+            &JsValue::from_str("degradationPreference"),
+            &JsValue::from_str(preference.as_str()),
+        )?;
+        JsFuture::from(sender.set_parameters_with_parameters(&params)).await?;
+        Ok(())
+    }
+
+    /// Sets a [`maxBitrate`][1] of all the encodings of the underlying
+    /// [RTCRtpSender][2].
+    ///
+    /// [`None`] removes the limit.
+    ///
+    /// # Errors
+    ///
+    /// Errors with JS error if the underlying [`setParameters`][3] call fails.
+    ///
+    /// [1]: https://w3.org/TR/webrtc/#dom-rtcrtpencodingparameters-maxbitrate
+    /// [2]: https://w3.org/TR/webrtc/#dom-rtcrtpsender
+    /// [3]: https://w3.org/TR/webrtc/#dom-rtcrtpsender-setparameters
+    pub async fn set_max_bitrate(
+        &self,
+        max_bitrate: Option<u32>,
+    ) -> Result<(), Error> {
+        let sender = self.transceiver.sender();
+        let params = sender.get_parameters();
+        let encodings = Array::from(&Reflect::get(
+            &params,
+            &JsValue::from_str("encodings"),
+        )?);
+        let key = JsValue::from_str("maxBitrate");
+        for encoding in encodings.iter() {
+            if let Some(bitrate) = max_bitrate {
+                Reflect::set(&encoding, &key, &JsValue::from(bitrate))?;
+            } else {
+                Reflect::delete_property(&Object::from(encoding), &key)?;
+            }
+        }
+        JsFuture::from(sender.set_parameters_with_parameters(&params)).await?;
+        Ok(())
+    }
+
     /// Indicates whether the underlying [`RtcRtpTransceiver`] is stopped.
     #[inline]
     #[must_use]
@@ -6,7 +6,9 @@ use std::{cell::RefCell, convert::TryFrom, rc::Rc};
 
 use derive_more::{From, Into};
 use futures::{channel::mpsc, stream::LocalBoxStream, StreamExt};
-use medea_client_api_proto::{ClientMsg, CloseDescription, ServerMsg};
+use medea_client_api_proto::{
+    ClientMsg, MessageTooLarge, ServerMsg, MAX_MESSAGE_SIZE,
+};
 use medea_reactive::ObservableCell;
 use tracerr::Traced;
 use web_sys::{CloseEvent, Event, MessageEvent, WebSocket as SysWebSocket};
@@ -253,6 +255,9 @@ impl RpcTransport for WebSocketRpcTransport {
         let message = serde_json::to_string(msg)
             .map_err(|e| TransportError::SerializeClientMessage(e.into()))
             .map_err(tracerr::wrap!())?;
+        MessageTooLarge::check(message.len(), MAX_MESSAGE_SIZE)
+            .map_err(TransportError::MessageTooLarge)
+            .map_err(tracerr::wrap!())?;
 
         let state = &*inner.socket_state.borrow();
         match state {
@@ -284,19 +289,8 @@ impl Drop for WebSocketRpcTransport {
 }
 
 impl From<&CloseEvent> for CloseMsg {
+    #[inline]
     fn from(event: &CloseEvent) -> Self {
-        let code: u16 = event.code();
-        match code {
-            1000 => {
-                if let Ok(description) =
-                    serde_json::from_str::<CloseDescription>(&event.reason())
-                {
-                    Self::Normal(code, description.reason)
-                } else {
-                    Self::Abnormal(code)
-                }
-            }
-            _ => Self::Abnormal(code),
-        }
+        Self::from_close_frame(event.code(), &event.reason())
     }
 }
@@ -11,7 +11,7 @@ impl<A: Into<wasm_bindgen::JsValue>> Callback<A> {
     /// it.
     #[inline]
     pub fn call1<T: Into<A>>(&self, arg: T) {
-        if let Some(f) = self.0.borrow().as_ref() {
+        if let Some(f) = self.func() {
             f.call1(arg.into())
         };
     }
@@ -0,0 +1,303 @@
+//! Queue of [`Effect`]s, which all the mutations of a [`Room`]'s state caused
+//! by [`RpcEvent`]s pass through.
+//!
+//! [`Effect`]s are applied strictly in the order they were scheduled in, and
+//! the next [`Effect`] is applied only once all the reactive updates caused by
+//! the previous one are processed. So, for example, all the [`Connection`]s,
+//! [`Sender`]s and [`Receiver`]s of a [`PeerConnection`] are guaranteed to be
+//! created before its negotiation (and so local tracks insertion) is started.
+//!
+//! [`Connection`]: crate::connection::Connection
+//! [`PeerConnection`]: crate::peer::PeerConnection
+//! [`Receiver`]: crate::peer::media::Receiver
+//! [`Room`]: super::Room
+//! [`RpcEvent`]: medea_client_api_proto::Event
+//! [`Sender`]: crate::peer::media::Sender
+
+use std::{cell::RefCell, collections::VecDeque};
+
+use medea_client_api_proto::{
+    self as proto, IceCandidate, IceServer, NegotiationRole, PeerId, Track,
+    TrackId, TrackPatchEvent,
+};
+use tracerr::Traced;
+
+use crate::{media::LocalTracksConstraints, peer};
+
+use super::UnknownPeerIdError;
+
+/// Mutation of a [`Room`]'s state.
+///
+/// [`Room`]: super::Room
+pub enum Effect {
+    /// Creation of a new [`peer::State`].
+    CreatePeer(peer::State),
+
+    /// Insertion of new [`Track`]s into the [`peer::State`] with the provided
+    /// [`PeerId`] as a single batch.
+    InsertTracks(PeerId, Vec<Track>),
+
+    /// Patching of a track of the [`peer::State`] with the provided
+    /// [`PeerId`].
+    PatchTrack(PeerId, TrackPatchEvent),
+
+    /// Removal of a track from the [`peer::State`] with the provided
+    /// [`PeerId`].
+    RemoveTrack(PeerId, TrackId),
+
+    /// ICE restart of the [`peer::State`] with the provided [`PeerId`].
+    RestartIce(PeerId),
+
+    /// Update of ICE transport policy of the [`peer::State`] with the
+    /// provided [`PeerId`].
+    UpdateIcePolicy {
+        /// [`PeerId`] of the updated [`peer::State`].
+        peer_id: PeerId,
+
+        /// New [`IceServer`]s of the [`peer::State`].
+        ice_servers: Vec<IceServer>,
+
+        /// Indicator whether the [`peer::State`] should be relayed forcibly.
+        force_relay: bool,
+    },
+
+    /// Start of a negotiation of the [`peer::State`] with the provided
+    /// [`PeerId`] in the provided [`NegotiationRole`].
+    SetNegotiationRole(PeerId, NegotiationRole),
+
+    /// Setting of a remote SDP of the [`peer::State`] with the provided
+    /// [`PeerId`].
+    SetRemoteSdp(PeerId, String),
+
+    /// Approval of a local SDP of the [`peer::State`] with the provided
+    /// [`PeerId`].
+    ApplyLocalSdp(PeerId, String),
+
+    /// Addition of a remote [`IceCandidate`] to the [`peer::State`] with the
+    /// provided [`PeerId`].
+    AddIceCandidate(PeerId, IceCandidate),
+
+    /// Removal of the [`peer::State`] with the provided [`PeerId`].
+    RemovePeer(PeerId),
+
+    /// Synchronization of all the [`peer::State`]s with the provided
+    /// [`proto::state::Room`].
+    SynchronizeState(proto::state::Room),
+}
+
+/// Kind of an [`Effect`] along with the identifiers of the entity it mutates.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EffectKind {
+    /// [`Effect::CreatePeer`].
+    CreatePeer(PeerId),
+
+    /// [`Effect::InsertTracks`].
+    InsertTracks(PeerId, Vec<TrackId>),
+
+    /// [`Effect::PatchTrack`].
+    PatchTrack(PeerId, TrackId),
+
+    /// [`Effect::RemoveTrack`].
+    RemoveTrack(PeerId, TrackId),
+
+    /// [`Effect::RestartIce`].
+    RestartIce(PeerId),
+
+    /// [`Effect::UpdateIcePolicy`].
+    UpdateIcePolicy(PeerId),
+
+    /// [`Effect::SetNegotiationRole`].
+    SetNegotiationRole(PeerId),
+
+    /// [`Effect::SetRemoteSdp`].
+    SetRemoteSdp(PeerId),
+
+    /// [`Effect::ApplyLocalSdp`].
+    ApplyLocalSdp(PeerId),
+
+    /// [`Effect::AddIceCandidate`].
+    AddIceCandidate(PeerId),
+
+    /// [`Effect::RemovePeer`].
+    RemovePeer(PeerId),
+
+    /// [`Effect::SynchronizeState`].
+    SynchronizeState,
+}
+
+impl Effect {
+    /// Returns [`EffectKind`] of this [`Effect`].
+    #[must_use]
+    pub fn kind(&self) -> EffectKind {
+        match self {
+            Self::CreatePeer(state) => EffectKind::CreatePeer(state.id()),
+            Self::InsertTracks(peer_id, tracks) => EffectKind::InsertTracks(
+                *peer_id,
+                tracks.iter().map(|t| t.id).collect(),
+            ),
+            Self::PatchTrack(peer_id, patch) => {
+                EffectKind::PatchTrack(*peer_id, patch.id)
+            }
+            Self::RemoveTrack(peer_id, track_id) => {
+                EffectKind::RemoveTrack(*peer_id, *track_id)
+            }
+            Self::RestartIce(peer_id) => EffectKind::RestartIce(*peer_id),
+            Self::UpdateIcePolicy { peer_id, .. } => {
+                EffectKind::UpdateIcePolicy(*peer_id)
+            }
+            Self::SetNegotiationRole(peer_id, _) => {
+                EffectKind::SetNegotiationRole(*peer_id)
+            }
+            Self::SetRemoteSdp(peer_id, _) => {
+                EffectKind::SetRemoteSdp(*peer_id)
+            }
+            Self::ApplyLocalSdp(peer_id, _) => {
+                EffectKind::ApplyLocalSdp(*peer_id)
+            }
+            Self::AddIceCandidate(peer_id, _) => {
+                EffectKind::AddIceCandidate(*peer_id)
+            }
+            Self::RemovePeer(peer_id) => EffectKind::RemovePeer(*peer_id),
+            Self::SynchronizeState(_) => EffectKind::SynchronizeState,
+        }
+    }
+
+    /// Applies this [`Effect`] to the provided [`peer::repo::Component`],
+    /// waiting until all the reactive updates caused by it are processed.
+    ///
+    /// # Errors
+    ///
+    /// With [`UnknownPeerIdError`] if the mutated [`peer::State`] doesn't
+    /// exist.
+    async fn apply(
+        self,
+        peers: &peer::repo::Component,
+        send_constraints: &LocalTracksConstraints,
+    ) -> Result<(), Traced<UnknownPeerIdError>> {
+        let get_peer = |peer_id| {
+            peers
+                .state()
+                .get(peer_id)
+                .ok_or_else(|| tracerr::new!(UnknownPeerIdError(peer_id)))
+        };
+
+        match self {
+            Self::CreatePeer(peer_state) => {
+                let peer_id = peer_state.id();
+                peers.state().insert(peer_id, peer_state);
+                peers.state().when_all_processed().await;
+                get_peer(peer_id)?.when_all_tracks_processed().await;
+            }
+            Self::InsertTracks(peer_id, tracks) => {
+                let peer_state = get_peer(peer_id)?;
+                for track in &tracks {
+                    peer_state.insert_track(track, send_constraints.clone());
+                }
+                peer_state.when_all_tracks_processed().await;
+            }
+            Self::PatchTrack(peer_id, patch) => {
+                get_peer(peer_id)?.patch_track(&patch);
+            }
+            Self::RemoveTrack(peer_id, track_id) => {
+                let peer_state = get_peer(peer_id)?;
+                peer_state.remove_track(track_id);
+                peer_state.when_all_tracks_processed().await;
+            }
+            Self::RestartIce(peer_id) => {
+                get_peer(peer_id)?.restart_ice();
+            }
+            Self::UpdateIcePolicy {
+                peer_id,
+                ice_servers,
+                force_relay,
+            } => {
+                get_peer(peer_id)?.update_ice_policy(ice_servers, force_relay);
+            }
+            Self::SetNegotiationRole(peer_id, negotiation_role) => {
+                get_peer(peer_id)?
+                    .set_negotiation_role(negotiation_role)
+                    .await;
+            }
+            Self::SetRemoteSdp(peer_id, sdp) => {
+                get_peer(peer_id)?.set_remote_sdp(sdp);
+            }
+            Self::ApplyLocalSdp(peer_id, sdp) => {
+                get_peer(peer_id)?.apply_local_sdp(sdp);
+            }
+            Self::AddIceCandidate(peer_id, candidate) => {
+                get_peer(peer_id)?.add_ice_candidate(candidate);
+            }
+            Self::RemovePeer(peer_id) => {
+                peers.state().remove(peer_id);
+                peers.state().when_all_processed().await;
+            }
+            Self::SynchronizeState(state) => {
+                peers.apply(state);
+                peers.state().when_all_processed().await;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// FIFO queue of [`Effect`]s to be applied to a [`Room`]'s state.
+///
+/// [`Room`]: super::Room
+#[derive(Default)]
+pub struct EffectsQueue {
+    /// Scheduled [`Effect`]s, which are not applied yet.
+    pending: RefCell<VecDeque<Effect>>,
+
+    /// [`EffectKind`]s of all the applied [`Effect`]s, in the order they
+    /// were applied in.
+    #[cfg(feature = "mockable")]
+    applied: RefCell<Vec<EffectKind>>,
+}
+
+impl EffectsQueue {
+    /// Schedules the provided [`Effect`] to be applied after all the already
+    /// scheduled ones.
+    #[inline]
+    pub fn schedule(&self, effect: Effect) {
+        self.pending.borrow_mut().push_back(effect);
+    }
+
+    /// Applies all the scheduled [`Effect`]s one by one to the provided
+    /// [`peer::repo::Component`].
+    ///
+    /// Failed [`Effect`]s are logged and don't prevent the following ones
+    /// from being applied.
+    pub async fn flush(
+        &self,
+        peers: &peer::repo::Component,
+        send_constraints: &LocalTracksConstraints,
+    ) {
+        loop {
+            let effect = self.pending.borrow_mut().pop_front();
+            let effect = if let Some(effect) = effect {
+                effect
+            } else {
+                break;
+            };
+
+            let kind = effect.kind();
+            if let Err(e) = effect.apply(peers, send_constraints).await {
+                log::error!("Failed to apply {:?}: {}", kind, e);
+            }
+            #[cfg(feature = "mockable")]
+            self.applied.borrow_mut().push(kind);
+        }
+    }
+}
+
+#[cfg(feature = "mockable")]
+impl EffectsQueue {
+    /// Returns [`EffectKind`]s of all the applied [`Effect`]s, in the order
+    /// they were applied in.
+    #[inline]
+    #[must_use]
+    pub fn applied(&self) -> Vec<EffectKind> {
+        self.applied.borrow().clone()
+    }
+}
@@ -1,45 +1,58 @@
 //! Medea [`Room`].
 
+pub mod effects;
+
 use std::{
-    cell::RefCell,
-    collections::{HashMap, HashSet},
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet, VecDeque},
+    mem,
     rc::{Rc, Weak},
+    time::Duration,
 };
 
 use async_recursion::async_recursion;
 use async_trait::async_trait;
 use derive_more::{Display, From};
-use futures::{channel::mpsc, future, FutureExt as _, StreamExt as _};
+use futures::{
+    channel::mpsc, future, future::Either, FutureExt as _, StreamExt as _,
+};
 use medea_client_api_proto::{
-    self as proto, Command, ConnectionQualityScore, Event as RpcEvent,
-    EventHandler, IceCandidate, IceConnectionState, IceServer, MemberId,
-    NegotiationRole, PeerConnectionState, PeerId, PeerMetrics, PeerUpdate,
-    Track, TrackId,
+    self as proto, Command, ConnectionQualityScore, Credential,
+    Event as RpcEvent, EventHandler, IceCandidate, IceConnectionState,
+    IceServer, MemberId, NegotiationRole, PeerConnectionState, PeerId,
+    PeerMetrics, PeerUpdate, QualityAdvisoryReason, Track, TrackId,
 };
+use medea_reactive::ObservableCell;
+use serde::Serialize;
 use tracerr::Traced;
 
 use crate::{
     api,
     connection::Connections,
+    jason::{Analytics, AnalyticsEvent},
     media::{
         track::{local, remote},
-        InitLocalTracksError, LocalTracksConstraints, MediaKind, MediaManager,
-        MediaSourceKind, MediaStreamSettings, RecvConstraints,
+        AudioRouteChangeReason, InitLocalTracksError, LocalTracksConstraints,
+        MediaKind, MediaManager, MediaSourceKind, MediaStreamSettings,
+        RecvConstraints, RecvMediaPolicy,
     },
     peer::{
         self, media::ProhibitedStateError, media_exchange_state, mute_state,
         InsertLocalTracksError, LocalMediaError, LocalStreamUpdateCriteria,
         MediaState, PeerConnection, PeerEvent, PeerEventHandler,
-        TrackDirection, TracksRequestError, UpdateLocalStreamError,
+        SelectedCandidatePair, TrackDirection, TracksRequestError,
+        UpdateLocalStreamError,
     },
     platform,
     rpc::{
         ClientDisconnect, CloseReason, ConnectionInfo,
         ConnectionInfoParseError, ReconnectHandle, RpcSession, SessionError,
     },
-    utils::{AsProtoState, JsCaused},
+    utils::{AsProtoState, DropGuard, JsCaused, TaskHandle},
 };
 
+use self::effects::{Effect, EffectsQueue};
+
 /// Reason of why [`Room`] has been closed.
 ///
 /// This struct is passed into [`RoomHandle::on_close`] callback.
@@ -56,6 +69,18 @@ pub struct RoomCloseReason {
     ///
     /// This field may be `true` only on closing by client.
     is_err: bool,
+
+    /// Indicator whether the [`Room`] may be rejoined after closing.
+    ///
+    /// This field may be `true` only on closing by server.
+    is_retryable: bool,
+
+    /// Delay after which the [`Room`] should be rejoined, as hinted by
+    /// server.
+    retry_after: Option<Duration>,
+
+    /// Kind of the reason of closing.
+    kind: RoomCloseKind,
 }
 
 impl RoomCloseReason {
@@ -64,18 +89,31 @@ impl RoomCloseReason {
     /// `is_err` may be `true` only on closing by client.
     ///
     /// `is_closed_by_server` is `true` on [`CloseReason::ByServer`].
+    ///
+    /// `is_retryable` and `retry_after` are derived from the server's
+    /// [`CloseDescription`], so are always unset on closing by client.
+    ///
+    /// [`CloseDescription`]: proto::CloseDescription
     #[must_use]
     pub fn new(reason: CloseReason) -> Self {
         match reason {
-            CloseReason::ByServer(reason) => Self {
-                reason: reason.to_string(),
+            CloseReason::ByServer(desc) => Self {
+                reason: desc.reason.to_string(),
+                kind: desc.reason.into(),
                 is_closed_by_server: true,
                 is_err: false,
+                is_retryable: desc.reason.is_retryable(),
+                retry_after: desc
+                    .retry_after_ms
+                    .map(|ms| Duration::from_millis(ms.into())),
             },
             CloseReason::ByClient { reason, is_err } => Self {
                 reason: reason.to_string(),
+                kind: reason.into(),
                 is_closed_by_server: false,
                 is_err,
+                is_retryable: false,
+                retry_after: None,
             },
         }
     }
@@ -100,6 +138,149 @@ impl RoomCloseReason {
     pub fn is_err(&self) -> bool {
         self.is_err
     }
+
+    /// Indicates whether the [`Room`] may be rejoined, since it was closed by
+    /// server for a temporary reason (media server overload or draining, for
+    /// example).
+    #[inline]
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        self.is_retryable
+    }
+
+    /// Returns the delay after which the [`Room`] should be rejoined, if
+    /// server has hinted it.
+    #[inline]
+    #[must_use]
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after
+    }
+
+    /// Returns a [`RoomCloseKind`] of the [`Room`]'s close reason, which
+    /// [`RoomCloseReason::reason()`] is a string representation of.
+    #[inline]
+    #[must_use]
+    pub fn kind(&self) -> RoomCloseKind {
+        self.kind
+    }
+}
+
+/// Kind of a [`RoomCloseReason`].
+///
+/// Mirrors the [`proto::CloseReason`]s sent by server and the
+/// [`ClientDisconnect`]s of client, so API users can distinguish close
+/// reasons without comparing their string representations.
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+#[repr(u8)]
+pub enum RoomCloseKind {
+    /// [`proto::CloseReason::Finished`].
+    Finished = 0,
+
+    /// [`proto::CloseReason::Reconnected`].
+    Reconnected = 1,
+
+    /// [`proto::CloseReason::Idle`].
+    Idle = 2,
+
+    /// [`proto::CloseReason::Rejected`].
+    Rejected = 3,
+
+    /// [`proto::CloseReason::InternalError`].
+    InternalError = 4,
+
+    /// [`proto::CloseReason::Evicted`].
+    Evicted = 5,
+
+    /// [`proto::CloseReason::Draining`].
+    Draining = 6,
+
+    /// [`proto::CloseReason::Overloaded`].
+    Overloaded = 7,
+
+    /// [`proto::CloseReason::Drained`].
+    Drained = 8,
+
+    /// [`proto::CloseReason::MessageTooLarge`].
+    MessageTooLarge = 9,
+
+    /// [`ClientDisconnect::RoomUnexpectedlyDropped`].
+    RoomUnexpectedlyDropped = 10,
+
+    /// [`ClientDisconnect::RoomClosed`].
+    RoomClosed = 11,
+
+    /// [`ClientDisconnect::RpcClientUnexpectedlyDropped`].
+    RpcClientUnexpectedlyDropped = 12,
+
+    /// [`ClientDisconnect::RpcTransportUnexpectedlyDropped`].
+    RpcTransportUnexpectedlyDropped = 13,
+
+    /// [`ClientDisconnect::SessionUnexpectedlyDropped`].
+    SessionUnexpectedlyDropped = 14,
+}
+
+impl From<proto::CloseReason> for RoomCloseKind {
+    fn from(reason: proto::CloseReason) -> Self {
+        use proto::CloseReason as R;
+
+        match reason {
+            R::Finished => Self::Finished,
+            R::Reconnected => Self::Reconnected,
+            R::Idle => Self::Idle,
+            R::Rejected => Self::Rejected,
+            R::InternalError => Self::InternalError,
+            R::Evicted => Self::Evicted,
+            R::Draining => Self::Draining,
+            R::Overloaded => Self::Overloaded,
+            R::Drained => Self::Drained,
+            R::MessageTooLarge => Self::MessageTooLarge,
+        }
+    }
+}
+
+impl From<ClientDisconnect> for RoomCloseKind {
+    fn from(reason: ClientDisconnect) -> Self {
+        use ClientDisconnect as D;
+
+        match reason {
+            D::RoomUnexpectedlyDropped => Self::RoomUnexpectedlyDropped,
+            D::RoomClosed => Self::RoomClosed,
+            D::RpcClientUnexpectedlyDropped => {
+                Self::RpcClientUnexpectedlyDropped
+            }
+            D::RpcTransportUnexpectedlyDropped => {
+                Self::RpcTransportUnexpectedlyDropped
+            }
+            D::SessionUnexpectedlyDropped => Self::SessionUnexpectedlyDropped,
+        }
+    }
+}
+
+/// Application data relayed from some remote `Member` of the [`Room`].
+///
+/// This struct is passed into [`RoomHandle::on_app_data`] callback.
+pub struct AppData {
+    /// ID of the remote `Member` which sent this [`AppData`].
+    from: MemberId,
+
+    /// Arbitrary JSON payload.
+    data: serde_json::Value,
+}
+
+impl AppData {
+    /// Returns ID of the remote `Member` which sent this [`AppData`].
+    #[inline]
+    #[must_use]
+    pub fn from(&self) -> String {
+        self.from.0.clone()
+    }
+
+    /// Returns JSON payload of this [`AppData`].
+    #[inline]
+    #[must_use]
+    pub fn data(&self) -> &serde_json::Value {
+        &self.data
+    }
 }
 
 /// Errors occurring in [`RoomHandle::join()`] method.
@@ -229,9 +410,15 @@ impl RoomHandle {
     /// Establishes connection with a media server (if it doesn't exist
     /// already ).
     ///
+    /// If the returned [`Future`] is dropped before its completion, then the
+    /// ongoing connection establishment is cancelled, so this [`Room`] can be
+    /// joined again.
+    ///
     /// # Errors
     ///
     /// See [`RoomJoinError`] for details.
+    ///
+    /// [`Future`]: std::future::Future
     pub async fn join(&self, url: String) -> Result<(), Traced<RoomJoinError>> {
         let inner = self
             .0
@@ -253,14 +440,70 @@ impl RoomHandle {
             )));
         }
 
-        Rc::clone(&inner.rpc)
-            .connect(connection_info)
+        inner.analytics.emit(AnalyticsEvent::JoinStarted);
+        let started = platform::unix_time_millis();
+
+        let rpc = Rc::clone(&inner.rpc);
+        let cancel_guard = DropGuard::new(move || rpc.cancel_connect());
+        let res = inner
+            .until_closed(Rc::clone(&inner.rpc).connect(connection_info))
             .await
-            .map_err(tracerr::map_from_and_wrap!( => RoomJoinError))?;
+            .ok_or_else(|| tracerr::new!(RoomJoinError::Detached))?
+            .map_err(tracerr::map_from_and_wrap!( => RoomJoinError));
+        cancel_guard.disarm();
+        res?;
+
+        inner.analytics.emit(AnalyticsEvent::JoinSucceeded {
+            duration: Analytics::elapsed(started),
+        });
 
         Ok(())
     }
 
+    /// Connects to a media server and joins the [`Room`] with the provided
+    /// authorization `token`, without acquiring any local media.
+    ///
+    /// All the outbound media is disabled, so remote media is received
+    /// without prompting a user for a camera or a microphone. Local media is
+    /// acquired and published only on a [`RoomHandle::publish()`] call.
+    ///
+    /// # Errors
+    ///
+    /// See [`RoomJoinError`] for details.
+    pub async fn join_without_media(
+        &self,
+        url: String,
+    ) -> Result<(), Traced<RoomJoinError>> {
+        let inner = self
+            .0
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(RoomJoinError::Detached))?;
+        inner.send_constraints.set_media_exchange_state_by_kinds(
+            media_exchange_state::Stable::Disabled,
+            LocalStreamUpdateCriteria::all(),
+        );
+
+        self.join(url).await
+    }
+
+    /// Acquires local media and starts publishing it in this [`Room`].
+    ///
+    /// Intended to be called after joining the [`Room`] via
+    /// [`RoomHandle::join_without_media()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`RoomHandle::enable_audio()`] and [`RoomHandle::enable_video()`]
+    /// for details.
+    pub async fn publish(&self) -> Result<(), Traced<ChangeMediaStateError>> {
+        self.enable_audio()
+            .await
+            .map_err(tracerr::map_from_and_wrap!())?;
+        self.enable_video(None)
+            .await
+            .map_err(tracerr::map_from_and_wrap!())
+    }
+
     /// Sets callback, invoked when a new [`Connection`] with some remote `Peer`
     /// is established.
     ///
@@ -298,6 +541,9 @@ impl RoomHandle {
     /// 2. `enable_audio`/`enable_video` is called.
     /// 3. [`MediaStreamSettings`] updated via `set_local_media_settings`.
     ///
+    /// Passed [`local::LocalMediaTrack`] describes the reason of its addition
+    /// and the replaced [`local::Track`], if any.
+    ///
     /// # Errors
     ///
     /// See [`HandleDetachedError`] for details.
@@ -335,6 +581,192 @@ impl RoomHandle {
         upgrade_inner!(self.0).map(|inner| inner.on_connection_loss.set_func(f))
     }
 
+    /// Sets `on_app_data` callback, invoked when some remote `Member` sends
+    /// application data to this [`Room`].
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn on_app_data(
+        &self,
+        f: platform::Function<api::AppData>,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| inner.on_app_data.set_func(f))
+    }
+
+    /// Sets `on_audio_route_change` callback, invoked when an audio output
+    /// route changes (e.g. headphones are unplugged), providing an
+    /// [`AudioRouteChangeReason`].
+    ///
+    /// If the configured [`AudioRoutePolicy`] requires outbound audio to be
+    /// muted on this change, then the callback is invoked once it's muted.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    ///
+    /// [`AudioRoutePolicy`]: crate::media::AudioRoutePolicy
+    pub fn on_audio_route_change(
+        &self,
+        f: platform::Function<u8>,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0)
+            .map(|inner| inner.on_audio_route_change.set_func(f))
+    }
+
+    /// Sends arbitrary application data to the remote `Member` with the
+    /// provided ID, or to all remote `Member`s of this [`Room`] if `None` is
+    /// provided.
+    ///
+    /// Data is relayed by media server and may be dropped by it if sent too
+    /// often.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn send_app_data(
+        &self,
+        to: Option<String>,
+        data: serde_json::Value,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| {
+            inner.rpc.send_command(Command::SendAppData {
+                to: to.map(MemberId::from),
+                data: data.to_string(),
+            });
+        })
+    }
+
+    /// Renews the credential of this [`Room`]'s `Member` with the provided
+    /// `token`, without rejoining the [`Room`].
+    ///
+    /// The renewed credential is verified by media server and used for all
+    /// the further reconnections, so short-lived credentials can be rotated
+    /// before they expire. Media server closes this [`Room`] if the provided
+    /// credential is invalid.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn update_token(
+        &self,
+        token: String,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0)
+            .map(|inner| inner.rpc.refresh_credential(Credential::from(token)))
+    }
+
+    /// Returns a JSON document describing the current state of all the
+    /// [`PeerConnection`]s of this [`Room`] (their SDPs, selected ICE
+    /// candidate pairs and latest stats) along with a history of this
+    /// [`Room`] state synchronization with a media server.
+    ///
+    /// Intended to be attached to bug reports, similarly to
+    /// `chrome://webrtc-internals` dumps.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub async fn export_debug_dump(
+        &self,
+    ) -> Result<String, Traced<HandleDetachedError>> {
+        let inner = upgrade_inner!(self.0)?;
+        let dump = inner.debug_dump().await;
+
+        // Serialization of plain data structures into a `String` never fails.
+        Ok(serde_json::to_string_pretty(&dump).unwrap_or_default())
+    }
+
+    /// Sets the interval (in milliseconds) of collecting stats of all the
+    /// [`PeerConnection`]s of this [`Room`] and sending them to a media
+    /// server.
+    ///
+    /// `0` stops periodic stats collecting, so it's done only via
+    /// [`RoomHandle::poll_stats_now()`]. Stats are not collected periodically
+    /// while the page is hidden.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn set_stats_interval(
+        &self,
+        interval_ms: u32,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0).map(|inner| {
+            inner
+                .peers
+                .set_stats_interval(Duration::from_millis(interval_ms.into()));
+        })
+    }
+
+    /// Collects stats of all the [`PeerConnection`]s of this [`Room`] right
+    /// away and sends them to a media server.
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub async fn poll_stats_now(
+        &self,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        let inner = upgrade_inner!(self.0)?;
+        inner.peers.poll_stats_now().await;
+        Ok(())
+    }
+
+    /// Re-acquires the local media, which has failed to be acquired previously
+    /// (and so has been reported to `on_failed_local_media` callback), and
+    /// re-enables the affected outbound media in all [`PeerConnection`]s of
+    /// this [`Room`].
+    ///
+    /// Does nothing if there is no failed local media.
+    ///
+    /// # Errors
+    ///
+    /// With [`ChangeMediaStateError::Detached`] if an inner [`Weak`] pointer
+    /// upgrade fails.
+    ///
+    /// With [`ChangeMediaStateError::CouldNotGetLocalMedia`] if media
+    /// acquisition request failed again. The affected media types are disabled
+    /// in this case, and may be retried later.
+    pub async fn retry_local_media(
+        &self,
+    ) -> Result<(), Traced<ChangeMediaStateError>> {
+        let inner = (self.0)
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(ChangeMediaStateError::Detached))?;
+        inner
+            .until_closed(inner.retry_local_media())
+            .await
+            .ok_or_else(|| tracerr::new!(ChangeMediaStateError::Detached))?
+            .map_err(tracerr::wrap!())
+    }
+
+    /// Sets the interval (in milliseconds) of automatic
+    /// [`RoomHandle::retry_local_media()`] attempts, so the failed local media
+    /// is published again once its device becomes available.
+    ///
+    /// `0` stops automatic retrying (default), so it's done only via
+    /// [`RoomHandle::retry_local_media()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn set_local_media_retry_interval(
+        &self,
+        interval_ms: u32,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        let inner = upgrade_inner!(self.0)?;
+        let interval = Duration::from_millis(interval_ms.into());
+        let task = (interval != Duration::from_secs(0)).then(|| {
+            InnerRoom::spawn_local_media_retry_task(
+                Weak::clone(&self.0),
+                interval,
+            )
+        });
+        *inner.local_media_retry_task.borrow_mut() = task;
+        Ok(())
+    }
+
     /// Updates this [`Room`]s [`MediaStreamSettings`]. This affects all
     /// [`PeerConnection`]s in this [`Room`]. If [`MediaStreamSettings`] is
     /// configured for some [`Room`], then this [`Room`] can only send media
@@ -358,6 +790,9 @@ impl RoomHandle {
     /// If recovering from fail state isn't possible then affected media types
     /// will be disabled.
     ///
+    /// If the returned [`Future`] is dropped before its completion, then the
+    /// previous [`MediaStreamSettings`] are restored in background.
+    ///
     /// # Errors
     ///
     /// With [`ConstraintsUpdateError::Errored`] if and error has occurred while
@@ -369,6 +804,7 @@ impl RoomHandle {
     /// With [`ConstraintsUpdateError::RecoverFailed`] if
     /// [`MediaStreamSettings`] rollback failed.
     ///
+    /// [`Future`]: std::future::Future
     /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
     pub async fn set_local_media_settings(
         &self,
@@ -382,9 +818,32 @@ impl RoomHandle {
             ))
         })?;
 
-        inner
+        // Rollback to the current settings if the update is not completed.
+        let rollback_guard = {
+            let weak_inner = Rc::downgrade(&inner);
+            let current_settings = inner.send_constraints.inner();
+            DropGuard::new(move || {
+                if let Some(inner) = weak_inner.upgrade() {
+                    platform::spawn(async move {
+                        drop(
+                            inner
+                                .set_local_media_settings(
+                                    current_settings,
+                                    stop_first,
+                                    false,
+                                )
+                                .await,
+                        );
+                    });
+                }
+            })
+        };
+        let res = inner
             .set_local_media_settings(settings, stop_first, rollback_on_fail)
-            .await
+            .await;
+        rollback_guard.disarm();
+
+        res
     }
 
     /// Changes [`MediaState`] of the provided [`MediaKind`], [`TrackDirection`]
@@ -408,67 +867,15 @@ impl RoomHandle {
 
         let new_state = new_state.into();
         let source_kind = source_kind.map(Into::into);
-
-        inner.set_constraints_media_state(
-            new_state,
-            kind,
-            direction,
-            source_kind,
-        );
-
-        let direction_send = matches!(direction, TrackDirection::Send);
-        let enabling = matches!(
-            new_state,
-            MediaState::MediaExchange(media_exchange_state::Stable::Enabled)
-        );
-
-        // Perform `getUserMedia()`/`getDisplayMedia()` right away, so we can
-        // fail fast without touching senders states and starting all required
-        // messaging.
-        // Hold tracks through all process, to ensure that they will be reused
-        // without additional requests.
-        let _tracks_handles = if direction_send && enabling {
-            inner
-                .get_local_tracks(kind, source_kind)
-                .await
-                .map_err(tracerr::map_from_and_wrap!())?
-        } else {
-            Vec::new()
-        };
-
-        while !inner.is_all_peers_in_media_state(
-            kind,
-            direction,
-            source_kind,
-            new_state,
-        ) {
-            if let Err(e) = inner
-                .toggle_media_state(new_state, kind, direction, source_kind)
-                .await
-                .map_err(tracerr::map_from_and_wrap!())
-            {
-                if direction_send && enabling {
-                    inner.set_constraints_media_state(
-                        new_state.opposite(),
-                        kind,
-                        direction,
-                        source_kind,
-                    );
-                    inner
-                        .toggle_media_state(
-                            new_state.opposite(),
-                            kind,
-                            direction,
-                            source_kind,
-                        )
-                        .await
-                        .map_err(tracerr::map_from_and_wrap!())?;
-                }
-                return Err(e);
-            }
-        }
-
-        Ok(())
+        inner
+            .until_closed(inner.change_media_state(
+                new_state,
+                kind,
+                direction,
+                source_kind,
+            ))
+            .await
+            .ok_or_else(|| tracerr::new!(ChangeMediaStateError::Detached))?
     }
 
     /// Mutes outbound audio in this [`Room`].
@@ -778,6 +1185,90 @@ impl RoomHandle {
         .await
         .map_err(tracerr::map_from_and_wrap!())
     }
+
+    /// Sets the [`RecvMediaPolicy`] of this [`Room`].
+    ///
+    /// With [`RecvMediaPolicy::Manual`] the media received from a remote
+    /// `Member` is negotiated as disabled with a media server, until it's
+    /// enabled via [`RoomHandle::enable_remote_media_from()`]. Affects only
+    /// the media received after this call, so should be called before
+    /// [`RoomHandle::join()`].
+    ///
+    /// # Errors
+    ///
+    /// See [`HandleDetachedError`] for details.
+    pub fn set_recv_media_policy(
+        &self,
+        policy: RecvMediaPolicy,
+    ) -> Result<(), Traced<HandleDetachedError>> {
+        upgrade_inner!(self.0)
+            .map(|inner| inner.recv_constraints.set_policy(policy))
+    }
+
+    /// Enables inbound media from the remote `Member` with the provided ID in
+    /// this [`Room`], regardless of its [`RecvMediaPolicy`].
+    ///
+    /// Media disabled via [`RoomHandle::disable_remote_audio()`] or
+    /// [`RoomHandle::disable_remote_video()`] is not enabled.
+    ///
+    /// # Errors
+    ///
+    /// With [`ChangeMediaStateError::Detached`] if an inner [`Weak`] pointer
+    /// upgrade fails.
+    ///
+    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if
+    /// [`RoomHandle::disable_remote_media_from()`] was called while enabling
+    /// or a media server didn't approve this state transition.
+    #[inline]
+    pub async fn enable_remote_media_from(
+        &self,
+        member_id: String,
+    ) -> Result<(), Traced<ChangeMediaStateError>> {
+        self.change_media_state_from(true, MemberId::from(member_id))
+            .await
+            .map_err(tracerr::map_from_and_wrap!())
+    }
+
+    /// Disables inbound media from the remote `Member` with the provided ID
+    /// in this [`Room`], regardless of its [`RecvMediaPolicy`].
+    ///
+    /// # Errors
+    ///
+    /// With [`ChangeMediaStateError::Detached`] if an inner [`Weak`] pointer
+    /// upgrade fails.
+    ///
+    /// With [`ChangeMediaStateError::TransitionIntoOppositeState`] if
+    /// [`RoomHandle::enable_remote_media_from()`] was called while disabling
+    /// or a media server didn't approve this state transition.
+    #[inline]
+    pub async fn disable_remote_media_from(
+        &self,
+        member_id: String,
+    ) -> Result<(), Traced<ChangeMediaStateError>> {
+        self.change_media_state_from(false, MemberId::from(member_id))
+            .await
+            .map_err(tracerr::map_from_and_wrap!())
+    }
+
+    /// Enables or disables inbound media from the remote `Member` with the
+    /// provided ID.
+    ///
+    /// Helper function for the exported enable/disable remote media from a
+    /// `Member` methods.
+    async fn change_media_state_from(
+        &self,
+        enabled: bool,
+        sender_id: MemberId,
+    ) -> Result<(), Traced<ChangeMediaStateError>> {
+        let inner = (self.0)
+            .upgrade()
+            .ok_or_else(|| tracerr::new!(ChangeMediaStateError::Detached))?;
+
+        inner
+            .until_closed(inner.change_media_state_from(enabled, sender_id))
+            .await
+            .ok_or_else(|| tracerr::new!(ChangeMediaStateError::Detached))?
+    }
 }
 
 /// [`Weak`] reference upgradeable to the [`Room`].
@@ -800,16 +1291,21 @@ pub struct Room(Rc<InnerRoom>);
 
 impl Room {
     /// Creates new [`Room`] and associates it with the provided [`RpcSession`].
+    ///
+    /// [`AnalyticsEvent`]s of this [`Room`] are emitted into the provided
+    /// [`Analytics`] bus.
     #[allow(clippy::mut_mut)]
     pub fn new(
         rpc: Rc<dyn RpcSession>,
         media_manager: Rc<MediaManager>,
+        analytics: Rc<Analytics>,
     ) -> Self {
         enum RoomEvent {
             RpcEvent(RpcEvent),
             PeerEvent(PeerEvent),
             RpcClientLostConnection,
             RpcClientReconnected,
+            AudioRouteChanged(AudioRouteChangeReason),
         }
 
         let (tx, peer_events_rx) = mpsc::unbounded();
@@ -826,17 +1322,21 @@ impl Room {
             .on_reconnected()
             .map(|_| RoomEvent::RpcClientReconnected)
             .fuse();
+        let mut audio_route_changed = platform::audio_route_changes()
+            .map(RoomEvent::AudioRouteChanged)
+            .fuse();
 
-        let room = Rc::new(InnerRoom::new(rpc, media_manager, tx));
+        let room = Rc::new(InnerRoom::new(rpc, media_manager, analytics, tx));
         let inner = Rc::downgrade(&room);
 
-        platform::spawn(async move {
+        let (event_loop, abort) = future::abortable(async move {
             loop {
                 let event: RoomEvent = futures::select! {
                     event = rpc_events_stream.select_next_some() => event,
                     event = peer_events_stream.select_next_some() => event,
                     event = rpc_connection_lost.select_next_some() => event,
                     event = rpc_client_reconnected.select_next_some() => event,
+                    event = audio_route_changed.select_next_some() => event,
                     complete => break,
                 };
 
@@ -850,6 +1350,10 @@ impl Room {
                             {
                                 log::error!("{}", e);
                             };
+                            inner
+                                .effects
+                                .flush(&inner.peers, &inner.send_constraints)
+                                .await;
                         }
                         RoomEvent::PeerEvent(event) => {
                             if let Err(e) =
@@ -866,6 +1370,9 @@ impl Room {
                         RoomEvent::RpcClientReconnected => {
                             inner.handle_rpc_connection_recovered();
                         }
+                        RoomEvent::AudioRouteChanged(reason) => {
+                            inner.handle_audio_route_change(reason);
+                        }
                     }
                 } else {
                     log::error!("Inner Room dropped unexpectedly");
@@ -873,16 +1380,19 @@ impl Room {
                 }
             }
         });
+        platform::spawn(event_loop.map(drop));
+        room.event_loop_task.replace(Some(abort.into()));
 
         Self(room)
     }
 
-    /// Sets `close_reason` and consumes this [`Room`].
+    /// Sets `close_reason` and closes this [`Room`] right away, regardless of
+    /// any pending operations still referring to it.
     ///
-    /// [`Room`] [`Drop`] triggers `on_close` callback with provided
-    /// [`CloseReason`].
+    /// Triggers `on_close` callback with the provided [`CloseReason`].
     pub fn close(self, reason: CloseReason) {
         self.0.set_close_reason(reason);
+        self.0.close();
     }
 
     /// Sets [`Room`]'s [`CloseReason`] to the provided value.
@@ -959,12 +1469,38 @@ struct InnerRoom {
     /// [`MediaManager`] or failed inject stream into [`PeerConnection`].
     on_failed_local_media: Rc<platform::Callback<api::Error>>,
 
+    /// [`MediaKind`] + [`MediaSourceKind`] pairs of the outbound media, which
+    /// has failed to be acquired, and should be re-acquired on
+    /// [`RoomHandle::retry_local_media()`].
+    failed_local_media: Cell<LocalStreamUpdateCriteria>,
+
+    /// [`TaskHandle`] for a task which will call
+    /// [`InnerRoom::retry_local_media()`] periodically.
+    ///
+    /// [`None`] if automatic local media retrying is stopped.
+    local_media_retry_task: RefCell<Option<TaskHandle>>,
+
     /// Callback invoked when a [`RpcSession`] loses connection.
     on_connection_loss: platform::Callback<api::ReconnectHandle>,
 
     /// Callback invoked when this [`Room`] is closed.
     on_close: Rc<platform::Callback<api::RoomCloseReason>>,
 
+    /// Callback invoked when application data is received from some remote
+    /// `Member`.
+    on_app_data: platform::Callback<api::AppData>,
+
+    /// Callback invoked when an audio output route changes.
+    on_audio_route_change: platform::Callback<u8>,
+
+    /// [`Analytics`] bus to emit [`AnalyticsEvent`]s of this [`Room`] into.
+    analytics: Rc<Analytics>,
+
+    /// Start times (in milliseconds since the UNIX epoch) of the ongoing
+    /// negotiations of [`PeerConnection`]s, reported as
+    /// [`AnalyticsEvent::NegotiationFinished`] once finished.
+    negotiations: RefCell<HashMap<PeerId, f64>>,
+
     /// Reason of [`Room`] closing.
     ///
     /// This [`CloseReason`] will be provided into [`RoomHandle::on_close`]
@@ -973,8 +1509,104 @@ struct InnerRoom {
     /// Note that `None` will be considered as error and `is_err` will be
     /// `true` in [`CloseReason`] provided to callback.
     close_reason: RefCell<CloseReason>,
+
+    /// Number of consecutive [`RpcEvent::StateChecksum`]s not matching the
+    /// local state.
+    state_checksum_mismatches: Cell<u8>,
+
+    /// [`proto::state::Room`] sent in the last [`Command::SynchronizeMe`],
+    /// which [`RpcEvent::StateDeltaSynchronized`] is applied to.
+    sync_base: RefCell<Option<proto::state::Room>>,
+
+    /// Latest [`SyncHistoryEntry`]s of this [`Room`], included into its
+    /// [`DebugDump`].
+    sync_history: RefCell<VecDeque<SyncHistoryEntry>>,
+
+    /// [`EffectsQueue`] which all the mutations of this [`Room`]'s state
+    /// caused by [`RpcEvent`]s pass through.
+    effects: EffectsQueue,
+
+    /// [`TaskHandle`] for a task processing [`RpcEvent`]s and [`PeerEvent`]s
+    /// of this [`Room`].
+    ///
+    /// [`None`] once this [`Room`] is closed.
+    event_loop_task: RefCell<Option<TaskHandle>>,
+
+    /// Indicator whether this [`Room`] is closed already.
+    ///
+    /// Pending [`RoomHandle`] operations resolve with terminal errors once
+    /// it's set.
+    closed: ObservableCell<bool>,
 }
 
+/// Maximum number of [`SyncHistoryEntry`]s kept by a [`Room`].
+const SYNC_HISTORY_LIMIT: usize = 64;
+
+/// Event of a [`Room`] state synchronization with a media server.
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "snake_case", tag = "event")]
+pub enum SyncEvent {
+    /// Connection with a media server has been lost.
+    ConnectionLost,
+
+    /// Connection with a media server has been restored, and
+    /// [`Command::SynchronizeMe`] has been sent.
+    ConnectionRecovered,
+
+    /// [`RpcEvent::StateChecksum`] didn't match the local state.
+    ChecksumMismatch {
+        /// Checksum of a media server state.
+        server: u32,
+
+        /// Checksum of the local state.
+        local: u32,
+    },
+
+    /// State drift has been detected, and [`Command::SynchronizeMe`] has been
+    /// sent.
+    DriftDetected,
+
+    /// [`RpcEvent::StateSynchronized`] or
+    /// [`RpcEvent::StateDeltaSynchronized`] has been applied.
+    StateSynchronized,
+}
+
+/// Timestamped [`SyncEvent`].
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct SyncHistoryEntry {
+    /// Time of the [`SyncEvent`] in milliseconds since the UNIX epoch.
+    pub timestamp: f64,
+
+    /// [`SyncEvent`] itself.
+    #[serde(flatten)]
+    pub event: SyncEvent,
+}
+
+/// Snapshot of a [`Room`] intended to be attached to bug reports.
+///
+/// Produced by the [`RoomHandle::export_debug_dump()`].
+#[derive(Debug, Serialize)]
+pub struct DebugDump {
+    /// Time of this [`DebugDump`] creation in milliseconds since the UNIX
+    /// epoch.
+    pub timestamp: f64,
+
+    /// [`peer::DebugDump`]s of all the [`PeerConnection`]s of the [`Room`].
+    pub peers: Vec<peer::DebugDump>,
+
+    /// Latest [`SyncHistoryEntry`]s of the [`Room`], from the oldest to the
+    /// newest one.
+    pub sync_history: Vec<SyncHistoryEntry>,
+}
+
+/// Number of consecutive [`RpcEvent::StateChecksum`]s not matching the local
+/// state, after which a state drift is considered detected and
+/// [`Command::SynchronizeMe`] is sent.
+///
+/// A single mismatch is tolerated, as the local state may legitimately lag
+/// behind the Media Server state while some [`RpcEvent`]s are being processed.
+const STATE_CHECKSUM_MISMATCHES_LIMIT: u8 = 2;
+
 /// Errors occurring in [`RoomHandle::set_local_media_settings()`] method.
 #[derive(Debug, Display)]
 pub enum ConstraintsUpdateError {
@@ -1043,86 +1675,294 @@ impl ConstraintsUpdateError {
             Self::Errored(error) => Some(error.clone()),
             _ => None,
         }
-    }
+    }
+
+    /// Returns a new [`ConstraintsUpdateError::Recovered`].
+    #[inline]
+    #[must_use]
+    fn recovered(recover_reason: Traced<ChangeMediaStateError>) -> Self {
+        Self::Recovered(recover_reason)
+    }
+
+    /// Converts this [`ChangeMediaStateError`] to the
+    /// [`ConstraintsUpdateError::RecoverFailed`].
+    #[must_use]
+    fn recovery_failed(self, reason: Traced<ChangeMediaStateError>) -> Self {
+        match self {
+            Self::Recovered(recover_reason) => Self::RecoverFailed {
+                recover_reason: reason,
+                recover_fail_reasons: vec![recover_reason],
+            },
+            Self::RecoverFailed {
+                recover_reason,
+                mut recover_fail_reasons,
+            } => {
+                recover_fail_reasons.push(recover_reason);
+
+                Self::RecoverFailed {
+                    recover_reason: reason,
+                    recover_fail_reasons,
+                }
+            }
+            Self::Errored(error) => Self::RecoverFailed {
+                recover_reason: error,
+                recover_fail_reasons: vec![reason],
+            },
+        }
+    }
+
+    /// Returns a [`ConstraintsUpdateError::Errored`] with the provided
+    /// [`ChangeMediaStateError`].
+    #[inline]
+    #[must_use]
+    fn errored(err: Traced<ChangeMediaStateError>) -> Self {
+        Self::Errored(err)
+    }
+}
+
+impl InnerRoom {
+    /// Creates a new [`InnerRoom`].
+    #[inline]
+    fn new(
+        rpc: Rc<dyn RpcSession>,
+        media_manager: Rc<MediaManager>,
+        analytics: Rc<Analytics>,
+        peer_event_sender: mpsc::UnboundedSender<PeerEvent>,
+    ) -> Self {
+        let connections = Rc::new(Connections::default());
+        let send_constraints = LocalTracksConstraints::default();
+        let recv_constraints = Rc::new(RecvConstraints::default());
+        Self {
+            peers: peer::repo::Component::new(
+                Rc::new(peer::repo::Repository::new(
+                    Rc::clone(&media_manager),
+                    peer_event_sender,
+                    send_constraints.clone(),
+                    Rc::clone(&recv_constraints),
+                    Rc::clone(&connections),
+                )),
+                Rc::new(peer::repo::State::default()),
+            ),
+            media_manager,
+            rpc,
+            send_constraints,
+            recv_constraints,
+            connections,
+            on_connection_loss: platform::Callback::default(),
+            on_failed_local_media: Rc::new(platform::Callback::default()),
+            failed_local_media: Cell::new(LocalStreamUpdateCriteria::empty()),
+            local_media_retry_task: RefCell::default(),
+            on_local_track: platform::Callback::default(),
+            on_close: Rc::new(platform::Callback::default()),
+            on_app_data: platform::Callback::default(),
+            on_audio_route_change: platform::Callback::default(),
+            analytics,
+            negotiations: RefCell::default(),
+            close_reason: RefCell::new(CloseReason::ByClient {
+                reason: ClientDisconnect::RoomUnexpectedlyDropped,
+                is_err: true,
+            }),
+            state_checksum_mismatches: Cell::new(0),
+            sync_base: RefCell::default(),
+            sync_history: RefCell::default(),
+            effects: EffectsQueue::default(),
+            event_loop_task: RefCell::default(),
+            closed: ObservableCell::new(false),
+        }
+    }
+
+    /// Tears this [`InnerRoom`] down: stops all its spawned tasks, closes all
+    /// its [`PeerConnection`]s and [`Connection`]s, closes its [`RpcSession`]
+    /// and invokes `on_close` callback.
+    ///
+    /// Pending [`RoomHandle`] operations resolve with terminal errors.
+    ///
+    /// Does nothing if this [`InnerRoom`] is closed already.
+    ///
+    /// [`Connection`]: crate::connection::Connection
+    fn close(&self) {
+        if self.closed.replace(true) {
+            return;
+        }
+
+        drop(self.event_loop_task.borrow_mut().take());
+        drop(self.local_media_retry_task.borrow_mut().take());
+        self.peers.close_all();
+        self.connections.close_all();
+
+        if let CloseReason::ByClient { reason, .. } =
+            *self.close_reason.borrow()
+        {
+            self.rpc.close_with_reason(reason);
+        };
+
+        self.on_close
+            .call1(RoomCloseReason::new(*self.close_reason.borrow()));
+    }
+
+    /// Runs the provided [`Future`] until this [`InnerRoom`] is closed, so
+    /// pending operations don't keep it alive after [`InnerRoom::close()`].
+    ///
+    /// Returns [`None`] if this [`InnerRoom`] is closed before the provided
+    /// [`Future`] resolves.
+    ///
+    /// [`Future`]: std::future::Future
+    async fn until_closed<T>(
+        &self,
+        fut: impl std::future::Future<Output = T>,
+    ) -> Option<T> {
+        if self.closed.get() {
+            return None;
+        }
+        match future::select(Box::pin(fut), self.closed.when_eq(true)).await {
+            Either::Left((output, _)) => Some(output),
+            Either::Right(_) => None,
+        }
+    }
+
+    /// Changes [`MediaState`] of the provided [`MediaKind`],
+    /// [`TrackDirection`] and [`proto::MediaSourceKind`] to the provided
+    /// [`MediaState`] in all [`PeerConnection`]s of this [`Room`].
+    ///
+    /// Performs [getUserMedia()][1]/[getDisplayMedia()][2] requests right
+    /// away when enabling outbound media, and rolls the state back if it
+    /// cannot be applied.
+    ///
+    /// [1]: https://tinyurl.com/w3-streams#dom-mediadevices-getusermedia
+    /// [2]: https://w3.org/TR/screen-capture#dom-mediadevices-getdisplaymedia
+    async fn change_media_state(
+        &self,
+        new_state: MediaState,
+        kind: MediaKind,
+        direction: TrackDirection,
+        source_kind: Option<proto::MediaSourceKind>,
+    ) -> Result<(), Traced<ChangeMediaStateError>> {
+        self.set_constraints_media_state(
+            new_state,
+            kind,
+            direction,
+            source_kind,
+        );
+
+        let direction_send = matches!(direction, TrackDirection::Send);
+        let enabling = matches!(
+            new_state,
+            MediaState::MediaExchange(media_exchange_state::Stable::Enabled)
+        );
+
+        // Perform `getUserMedia()`/`getDisplayMedia()` right away, so we can
+        // fail fast without touching senders states and starting all required
+        // messaging.
+        // Hold tracks through all process, to ensure that they will be reused
+        // without additional requests.
+        let _tracks_handles = if direction_send && enabling {
+            self.get_local_tracks(kind, source_kind)
+                .await
+                .map_err(tracerr::map_from_and_wrap!())?
+        } else {
+            Vec::new()
+        };
+
+        while !self.is_all_peers_in_media_state(
+            kind,
+            direction,
+            source_kind,
+            new_state,
+        ) {
+            if let Err(e) = self
+                .toggle_media_state(new_state, kind, direction, source_kind)
+                .await
+                .map_err(tracerr::map_from_and_wrap!())
+            {
+                if direction_send && enabling {
+                    self.set_constraints_media_state(
+                        new_state.opposite(),
+                        kind,
+                        direction,
+                        source_kind,
+                    );
+                    self.toggle_media_state(
+                        new_state.opposite(),
+                        kind,
+                        direction,
+                        source_kind,
+                    )
+                    .await
+                    .map_err(tracerr::map_from_and_wrap!())?;
+                }
+                return Err(e);
+            }
+        }
 
-    /// Returns a new [`ConstraintsUpdateError::Recovered`].
-    #[inline]
-    #[must_use]
-    fn recovered(recover_reason: Traced<ChangeMediaStateError>) -> Self {
-        Self::Recovered(recover_reason)
+        Ok(())
     }
 
-    /// Converts this [`ChangeMediaStateError`] to the
-    /// [`ConstraintsUpdateError::RecoverFailed`].
-    #[must_use]
-    fn recovery_failed(self, reason: Traced<ChangeMediaStateError>) -> Self {
-        match self {
-            Self::Recovered(recover_reason) => Self::RecoverFailed {
-                recover_reason: reason,
-                recover_fail_reasons: vec![recover_reason],
-            },
-            Self::RecoverFailed {
-                recover_reason,
-                mut recover_fail_reasons,
-            } => {
-                recover_fail_reasons.push(recover_reason);
+    /// Enables or disables inbound media from the remote `Member` with the
+    /// provided ID in all [`PeerConnection`]s of this [`Room`], remembering
+    /// the choice in the [`InnerRoom::recv_constraints`] for the media
+    /// received later.
+    async fn change_media_state_from(
+        &self,
+        enabled: bool,
+        sender_id: MemberId,
+    ) -> Result<(), Traced<ChangeMediaStateError>> {
+        self.recv_constraints
+            .set_enabled_from(enabled, sender_id.clone());
 
-                Self::RecoverFailed {
-                    recover_reason: reason,
-                    recover_fail_reasons,
-                }
-            }
-            Self::Errored(error) => Self::RecoverFailed {
-                recover_reason: error,
-                recover_fail_reasons: vec![reason],
-            },
-        }
+        let state =
+            MediaState::from(media_exchange_state::Stable::from(enabled));
+        let tracks: HashMap<_, _> = self
+            .peers
+            .get_all()
+            .into_iter()
+            .map(|peer| {
+                let new_media_exchange_states = peer
+                    .get_receivers_sides_from(&sender_id)
+                    .into_iter()
+                    .filter(|transceiver| transceiver.is_transitable())
+                    .filter(|transceiver| {
+                        !enabled
+                            || self
+                                .recv_constraints
+                                .is_enabled_from(transceiver.kind(), &sender_id)
+                    })
+                    .map(|transceiver| (transceiver.track_id(), state))
+                    .collect();
+                (peer.id(), new_media_exchange_states)
+            })
+            .collect();
+
+        self.update_media_states(tracks).await
     }
 
-    /// Returns a [`ConstraintsUpdateError::Errored`] with the provided
-    /// [`ChangeMediaStateError`].
-    #[inline]
-    #[must_use]
-    fn errored(err: Traced<ChangeMediaStateError>) -> Self {
-        Self::Errored(err)
+    /// Records the provided [`SyncEvent`] into the
+    /// [`InnerRoom::sync_history`], evicting the oldest entry if the
+    /// [`SYNC_HISTORY_LIMIT`] is reached.
+    fn record_sync_event(&self, event: SyncEvent) {
+        let mut history = self.sync_history.borrow_mut();
+        if history.len() >= SYNC_HISTORY_LIMIT {
+            drop(history.pop_front());
+        }
+        history.push_back(SyncHistoryEntry {
+            timestamp: platform::unix_time_millis(),
+            event,
+        });
     }
-}
 
-impl InnerRoom {
-    /// Creates a new [`InnerRoom`].
-    #[inline]
-    fn new(
-        rpc: Rc<dyn RpcSession>,
-        media_manager: Rc<MediaManager>,
-        peer_event_sender: mpsc::UnboundedSender<PeerEvent>,
-    ) -> Self {
-        let connections = Rc::new(Connections::default());
-        let send_constraints = LocalTracksConstraints::default();
-        let recv_constraints = Rc::new(RecvConstraints::default());
-        Self {
-            peers: peer::repo::Component::new(
-                Rc::new(peer::repo::Repository::new(
-                    Rc::clone(&media_manager),
-                    peer_event_sender,
-                    send_constraints.clone(),
-                    Rc::clone(&recv_constraints),
-                    Rc::clone(&connections),
-                )),
-                Rc::new(peer::repo::State::default()),
-            ),
-            media_manager,
-            rpc,
-            send_constraints,
-            recv_constraints,
-            connections,
-            on_connection_loss: platform::Callback::default(),
-            on_failed_local_media: Rc::new(platform::Callback::default()),
-            on_local_track: platform::Callback::default(),
-            on_close: Rc::new(platform::Callback::default()),
-            close_reason: RefCell::new(CloseReason::ByClient {
-                reason: ClientDisconnect::RoomUnexpectedlyDropped,
-                is_err: true,
-            }),
+    /// Returns a [`DebugDump`] of this [`Room`].
+    async fn debug_dump(&self) -> DebugDump {
+        let peers_state = self.peers.state();
+        let peers =
+            future::join_all(self.peers.get_all().into_iter().map(|peer| {
+                let state = peers_state.get(peer.id());
+                async move { peer.debug_dump(state.as_deref()).await }
+            }))
+            .await;
+
+        DebugDump {
+            timestamp: platform::unix_time_millis(),
+            peers,
+            sync_history: self.sync_history.borrow().iter().copied().collect(),
         }
     }
 
@@ -1157,8 +1997,8 @@ impl InnerRoom {
 
     /// Sets `close_reason` of this [`InnerRoom`].
     ///
-    /// [`Drop`] implementation of [`InnerRoom`] is supposed to be triggered
-    /// after this function call.
+    /// [`InnerRoom::close()`] is supposed to be called after this function
+    /// call.
     fn set_close_reason(&self, reason: CloseReason) {
         self.close_reason.replace(reason);
     }
@@ -1303,11 +2143,22 @@ impl InnerRoom {
 
         let mut result = Vec::new();
         for req in requests {
-            let tracks = self
-                .media_manager
-                .get_tracks(req)
-                .await
+            let started = platform::unix_time_millis();
+            let tracks = self.media_manager.get_tracks(req).await;
+            let duration = Analytics::elapsed(started);
+            self.analytics.emit(if tracks.is_ok() {
+                AnalyticsEvent::LocalMediaAcquired { duration }
+            } else {
+                AnalyticsEvent::LocalMediaFailed { duration }
+            });
+            let tracks = tracks
                 .map_err(|e| {
+                    self.record_failed_local_media(
+                        LocalStreamUpdateCriteria::from_kinds(
+                            kind,
+                            source_kind,
+                        ),
+                    );
                     self.on_failed_local_media.call1(e.clone());
 
                     e
@@ -1315,8 +2166,17 @@ impl InnerRoom {
                 .map_err(tracerr::map_from_and_wrap!())?;
             for (track, is_new) in tracks {
                 if is_new {
-                    self.on_local_track
-                        .call1(local::LocalMediaTrack::new(Rc::clone(&track)));
+                    let previous = self
+                        .peers
+                        .get_all()
+                        .iter()
+                        .find_map(|p| p.get_replaced_track(&track));
+                    self.on_local_track.call1(
+                        local::LocalMediaTrack::replacing(
+                            Rc::clone(&track),
+                            previous,
+                        ),
+                    );
                 }
                 result.push(track);
             }
@@ -1360,6 +2220,7 @@ impl InnerRoom {
     ) -> Result<(), Traced<ChangeMediaStateError>> {
         use media_exchange_state::Stable::Disabled;
 
+        self.record_failed_local_media(kinds);
         self.send_constraints
             .set_media_exchange_state_by_kinds(Disabled, kinds);
         let senders_to_disable = peer.get_senders_without_tracks_ids(kinds);
@@ -1494,23 +2355,193 @@ impl InnerRoom {
             .map_err(|e| E::errored(tracerr::map_from_and_new!(e)))
     }
 
+    /// Adds the provided [`LocalStreamUpdateCriteria`] to the
+    /// [`InnerRoom::failed_local_media`].
+    fn record_failed_local_media(&self, kinds: LocalStreamUpdateCriteria) {
+        let mut failed = self.failed_local_media.get();
+        failed.merge(kinds);
+        self.failed_local_media.set(failed);
+    }
+
+    /// Re-acquires [`local::Track`]s for the
+    /// [`InnerRoom::failed_local_media`], inserts them into [`Sender`]s of all
+    /// [`PeerConnection`]s and enables these [`Sender`]s.
+    ///
+    /// If the [`local::Track`]s cannot be acquired again, then the affected
+    /// media types are disabled and remain in the
+    /// [`InnerRoom::failed_local_media`].
+    ///
+    /// [`Sender`]: peer::media::Sender
+    async fn retry_local_media(
+        &self,
+    ) -> Result<(), Traced<ChangeMediaStateError>> {
+        use media_exchange_state::Stable::Enabled;
+
+        let kinds = self
+            .failed_local_media
+            .replace(LocalStreamUpdateCriteria::empty());
+        if kinds.is_empty() {
+            return Ok(());
+        }
+
+        self.send_constraints
+            .set_media_exchange_state_by_kinds(Enabled, kinds);
+        let mut states_update: HashMap<_, HashMap<_, _>> = HashMap::new();
+        for peer in self.peers.get_all() {
+            match peer.update_local_stream(kinds).await {
+                Ok(states) => {
+                    states_update.entry(peer.id()).or_default().extend(
+                        states.into_iter().map(|(id, s)| (id, s.into())),
+                    );
+                }
+                Err(e) => {
+                    self.disable_senders_without_tracks(
+                        &peer,
+                        kinds,
+                        states_update,
+                    )
+                    .await
+                    .map_err(tracerr::wrap!())?;
+
+                    return Err(tracerr::map_from_and_new!(e));
+                }
+            }
+        }
+
+        self.update_media_states(states_update)
+            .await
+            .map_err(tracerr::wrap!())
+    }
+
+    /// Spawns a task which will call [`InnerRoom::retry_local_media()`] every
+    /// provided `interval` until the provided [`InnerRoom`] is dropped.
+    ///
+    /// Returns [`TaskHandle`] which will stop this task on its [`Drop`].
+    fn spawn_local_media_retry_task(
+        room: Weak<Self>,
+        interval: Duration,
+    ) -> TaskHandle {
+        let (fut, abort) = future::abortable(async move {
+            loop {
+                platform::delay_for(interval).await;
+
+                let inner = if let Some(inner) = room.upgrade() {
+                    inner
+                } else {
+                    break;
+                };
+                // Errors are reported to `on_failed_local_media` callback
+                // already.
+                drop(inner.retry_local_media().await);
+            }
+        });
+
+        platform::spawn(async move {
+            fut.await.ok();
+        });
+
+        abort.into()
+    }
+
+    /// Remembers the start time of the [`PeerConnection`] negotiation, unless
+    /// it's ongoing already.
+    fn start_negotiation(&self, peer_id: PeerId) {
+        self.negotiations
+            .borrow_mut()
+            .entry(peer_id)
+            .or_insert_with(platform::unix_time_millis);
+    }
+
+    /// Emits [`AnalyticsEvent::NegotiationFinished`] if the
+    /// [`PeerConnection`] negotiation is ongoing.
+    fn finish_negotiation(&self, peer_id: PeerId) {
+        let started = self.negotiations.borrow_mut().remove(&peer_id);
+        if let Some(started) = started {
+            self.analytics.emit(AnalyticsEvent::NegotiationFinished {
+                peer_id,
+                duration: Analytics::elapsed(started),
+            });
+        }
+    }
+
     /// Stops state transition timers in all [`PeerConnection`]'s in this
     /// [`Room`].
     fn handle_rpc_connection_lost(&self) {
+        self.record_sync_event(SyncEvent::ConnectionLost);
         self.peers.connection_lost();
         self.on_connection_loss
             .call1(ReconnectHandle::new(Rc::downgrade(&self.rpc)));
     }
 
-    /// Sends [`Command::SynchronizeMe`] with a current Client state to the
-    /// Media Server.
+    /// Sends [`Command::SynchronizeMe`] with a current Client state and the
+    /// sequence number of the last received [`RpcEvent`] to the Media Server,
+    /// so it can replay the [`RpcEvent`]s missed during the connection loss.
     ///
     /// Resets state transition timers in all [`PeerConnection`]'s in this
     /// [`Room`].
     fn handle_rpc_connection_recovered(&self) {
+        self.record_sync_event(SyncEvent::ConnectionRecovered);
+        self.analytics.emit(AnalyticsEvent::Reconnected);
         self.peers.connection_recovered();
+        self.synchronize(
+            self.peers.state().as_proto(),
+            self.rpc.last_event_seq(),
+        );
+    }
+
+    /// Sends [`Command::SynchronizeMe`] with the provided Client `state`,
+    /// remembering it to apply [`RpcEvent::StateDeltaSynchronized`] to.
+    fn synchronize(
+        &self,
+        state: proto::state::Room,
+        last_event_seq: Option<u64>,
+    ) {
+        self.sync_base.replace(Some(state.clone()));
         self.rpc.send_command(Command::SynchronizeMe {
-            state: self.peers.state().as_proto(),
+            state,
+            last_event_seq,
+        });
+    }
+
+    /// Mutes outbound audio in this [`Room`] if its [`AudioRoutePolicy`]
+    /// requires so on the audio output route change with the provided
+    /// [`AudioRouteChangeReason`], and invokes `on_audio_route_change`
+    /// callback afterwards.
+    ///
+    /// [`AudioRoutePolicy`]: crate::media::AudioRoutePolicy
+    fn handle_audio_route_change(
+        self: &Rc<Self>,
+        reason: AudioRouteChangeReason,
+    ) {
+        let policy = self.send_constraints.audio_route_policy();
+        if !policy.should_mute(reason) {
+            self.on_audio_route_change.call1(reason as u8);
+            return;
+        }
+
+        // Muting is approved by a media server, so it cannot be awaited in
+        // the event loop processing its responses.
+        let this = Rc::clone(self);
+        platform::spawn(async move {
+            let muted = this
+                .until_closed(this.change_media_state(
+                    mute_state::Stable::Muted.into(),
+                    MediaKind::Audio,
+                    TrackDirection::Send,
+                    None,
+                ))
+                .await;
+            match muted {
+                Some(Ok(())) => {}
+                Some(Err(e)) => {
+                    log::error!(
+                        "Failed to mute audio on audio route change: {}",
+                        e,
+                    );
+                }
+                None => return,
+            }
+            this.on_audio_route_change.call1(reason as u8);
         });
     }
 }
@@ -1551,7 +2582,8 @@ impl EventHandler for InnerRoom {
             peer_state.insert_track(track, self.send_constraints.clone());
         }
 
-        self.peers.state().insert(peer_id, peer_state);
+        self.start_negotiation(peer_id);
+        self.effects.schedule(Effect::CreatePeer(peer_state));
 
         Ok(())
     }
@@ -1562,12 +2594,9 @@ impl EventHandler for InnerRoom {
         peer_id: PeerId,
         sdp_answer: String,
     ) -> Self::Output {
-        let peer = self
-            .peers
-            .state()
-            .get(peer_id)
-            .ok_or_else(|| tracerr::new!(UnknownPeerIdError(peer_id)))?;
-        peer.set_remote_sdp(sdp_answer);
+        self.finish_negotiation(peer_id);
+        self.effects
+            .schedule(Effect::SetRemoteSdp(peer_id, sdp_answer));
 
         Ok(())
     }
@@ -1578,12 +2607,8 @@ impl EventHandler for InnerRoom {
         peer_id: PeerId,
         local_sdp: String,
     ) -> Self::Output {
-        let peer_state = self
-            .peers
-            .state()
-            .get(peer_id)
-            .ok_or_else(|| tracerr::new!(UnknownPeerIdError(peer_id)))?;
-        peer_state.apply_local_sdp(local_sdp);
+        self.effects
+            .schedule(Effect::ApplyLocalSdp(peer_id, local_sdp));
 
         Ok(())
     }
@@ -1594,12 +2619,8 @@ impl EventHandler for InnerRoom {
         peer_id: PeerId,
         candidate: IceCandidate,
     ) -> Self::Output {
-        let peer = self
-            .peers
-            .state()
-            .get(peer_id)
-            .ok_or_else(|| tracerr::new!(UnknownPeerIdError(peer_id)))?;
-        peer.add_ice_candidate(candidate);
+        self.effects
+            .schedule(Effect::AddIceCandidate(peer_id, candidate));
 
         Ok(())
     }
@@ -1607,7 +2628,8 @@ impl EventHandler for InnerRoom {
     /// Disposes specified [`PeerConnection`]s.
     async fn on_peers_removed(&self, peer_ids: Vec<PeerId>) -> Self::Output {
         for id in peer_ids {
-            self.peers.state().remove(id);
+            drop(self.negotiations.borrow_mut().remove(&id));
+            self.effects.schedule(Effect::RemovePeer(id));
         }
         Ok(())
     }
@@ -1626,27 +2648,50 @@ impl EventHandler for InnerRoom {
         updates: Vec<PeerUpdate>,
         negotiation_role: Option<NegotiationRole>,
     ) -> Self::Output {
-        let peer_state = self
-            .peers
-            .state()
-            .get(peer_id)
-            .ok_or_else(|| tracerr::new!(UnknownPeerIdError(peer_id)))?;
+        if self.peers.state().get(peer_id).is_none() {
+            return Err(tracerr::new!(UnknownPeerIdError(peer_id)));
+        }
 
+        let mut added = Vec::new();
         for update in updates {
-            match update {
-                PeerUpdate::Added(track) => peer_state
-                    .insert_track(&track, self.send_constraints.clone()),
-                PeerUpdate::Updated(patch) => peer_state.patch_track(&patch),
-                PeerUpdate::IceRestart => {
-                    peer_state.restart_ice();
+            let effect = match update {
+                PeerUpdate::Added(track) => {
+                    added.push(track);
+                    continue;
                 }
-                PeerUpdate::Removed(id) => {
-                    peer_state.remove_track(id);
+                PeerUpdate::Updated(patch) => {
+                    Effect::PatchTrack(peer_id, patch)
                 }
+                PeerUpdate::IceRestart => Effect::RestartIce(peer_id),
+                PeerUpdate::IcePolicyUpdated {
+                    ice_servers,
+                    force_relay,
+                } => Effect::UpdateIcePolicy {
+                    peer_id,
+                    ice_servers,
+                    force_relay,
+                },
+                PeerUpdate::Removed(id) => Effect::RemoveTrack(peer_id, id),
+            };
+            // Consecutive additions are inserted as a single batch, so their
+            // `Sender`s and `Receiver`s are created at once.
+            if !added.is_empty() {
+                self.effects.schedule(Effect::InsertTracks(
+                    peer_id,
+                    mem::take(&mut added),
+                ));
             }
+            self.effects.schedule(effect);
+        }
+        if !added.is_empty() {
+            self.effects.schedule(Effect::InsertTracks(peer_id, added));
         }
         if let Some(negotiation_role) = negotiation_role {
-            peer_state.set_negotiation_role(negotiation_role).await;
+            self.start_negotiation(peer_id);
+            self.effects.schedule(Effect::SetNegotiationRole(
+                peer_id,
+                negotiation_role,
+            ));
         }
 
         Ok(())
@@ -1668,6 +2713,35 @@ impl EventHandler for InnerRoom {
         Ok(())
     }
 
+    /// Applies the advised maximum bitrate to the [`Sender`] with the
+    /// provided [`TrackId`].
+    ///
+    /// Does nothing if there is no such [`Sender`].
+    ///
+    /// [`Sender`]: peer::media::Sender
+    async fn on_track_quality_advisory(
+        &self,
+        track_id: TrackId,
+        advised_max_bitrate: Option<u32>,
+        reason: QualityAdvisoryReason,
+    ) -> Self::Output {
+        log::debug!(
+            "Track [id = {}] quality advisory: max bitrate {:?} ({:?})",
+            track_id,
+            advised_max_bitrate,
+            reason,
+        );
+        let sender = self
+            .peers
+            .get_all()
+            .into_iter()
+            .find_map(|peer| peer.get_sender_by_id(track_id));
+        if let Some(sender) = sender {
+            sender.set_max_bitrate(advised_max_bitrate).await;
+        }
+        Ok(())
+    }
+
     #[inline]
     async fn on_room_joined(&self, _: MemberId) -> Self::Output {
         unreachable!("Room can't receive Event::RoomJoined")
@@ -1677,6 +2751,7 @@ impl EventHandler for InnerRoom {
     async fn on_room_left(
         &self,
         _: medea_client_api_proto::CloseReason,
+        _: Option<u32>,
     ) -> Self::Output {
         unreachable!("Room can't receive Event::RoomLeft")
     }
@@ -1687,7 +2762,91 @@ impl EventHandler for InnerRoom {
         &self,
         state: proto::state::Room,
     ) -> Self::Output {
-        self.peers.apply(state);
+        self.sync_base.replace(None);
+        self.effects.schedule(Effect::SynchronizeState(state));
+        self.record_sync_event(SyncEvent::StateSynchronized);
+        Ok(())
+    }
+
+    /// Updates [`peer::repo::State`] with the [`proto::state::Room`] sent in
+    /// the last [`Command::SynchronizeMe`] and the provided
+    /// [`proto::state::RoomStateDelta`] applied to it.
+    ///
+    /// Sends [`Command::SynchronizeMe`] again if there is no such
+    /// [`proto::state::Room`].
+    async fn on_state_delta_synchronized(
+        &self,
+        delta: proto::state::RoomStateDelta,
+    ) -> Self::Output {
+        let base = self.sync_base.borrow_mut().take();
+        if let Some(mut state) = base {
+            state.apply_delta(delta);
+            self.effects.schedule(Effect::SynchronizeState(state));
+            self.record_sync_event(SyncEvent::StateSynchronized);
+        } else {
+            log::warn!("Unexpected Room state delta, synchronizing");
+            self.synchronize(self.peers.state().as_proto(), None);
+        }
+        Ok(())
+    }
+
+    /// Updates metadata of the remote `Member` with the provided
+    /// [`MemberId`].
+    #[inline]
+    async fn on_member_metadata_updated(
+        &self,
+        member_id: MemberId,
+        metadata: HashMap<String, String>,
+    ) -> Self::Output {
+        self.connections.update_metadata(member_id, metadata);
+        Ok(())
+    }
+
+    /// Passes application data received from the remote `Member` to the
+    /// `on_app_data` callback.
+    ///
+    /// Drops the data if it's not a valid JSON.
+    async fn on_app_data_received(
+        &self,
+        from: MemberId,
+        data: String,
+    ) -> Self::Output {
+        match serde_json::from_str(&data) {
+            Ok(data) => self.on_app_data.call1(AppData { from, data }),
+            Err(e) => log::error!(
+                "Dropping malformed application data from Member \
+                 [id = {}]: {}",
+                from,
+                e,
+            ),
+        }
+        Ok(())
+    }
+
+    /// Compares the provided Media Server state `checksum` with a checksum of
+    /// the local state, and sends [`Command::SynchronizeMe`] if they don't
+    /// match for [`STATE_CHECKSUM_MISMATCHES_LIMIT`] times in a row.
+    async fn on_state_checksum(&self, checksum: u32) -> Self::Output {
+        let state = self.peers.state().as_proto();
+        let local = state.checksum();
+        if local == checksum {
+            self.state_checksum_mismatches.set(0);
+            return Ok(());
+        }
+        self.record_sync_event(SyncEvent::ChecksumMismatch {
+            server: checksum,
+            local,
+        });
+
+        let mismatches = self.state_checksum_mismatches.get() + 1;
+        if mismatches < STATE_CHECKSUM_MISMATCHES_LIMIT {
+            self.state_checksum_mismatches.set(mismatches);
+        } else {
+            log::warn!("Room state drift detected, synchronizing");
+            self.record_sync_event(SyncEvent::DriftDetected);
+            self.state_checksum_mismatches.set(0);
+            self.synchronize(state, None);
+        }
         Ok(())
     }
 }
@@ -1741,13 +2900,31 @@ impl PeerEventHandler for InnerRoom {
         Ok(())
     }
 
+    /// Handles [`PeerEvent::RemoteTrackRemoved`] event and passes removed
+    /// [`remote::Track`] to the related [`Connection`].
+    ///
+    /// Does nothing if the related [`Connection`] is closed already.
+    ///
+    /// [`Connection`]: crate::connection::Connection
+    async fn on_remote_track_removed(
+        &self,
+        sender_id: MemberId,
+        track: remote::Track,
+    ) -> Self::Output {
+        if let Some(conn) = self.connections.get(&sender_id) {
+            conn.remove_remote_track(track);
+        }
+        Ok(())
+    }
+
     /// Invokes `on_local_track` [`Room`]'s callback.
     async fn on_new_local_track(
         &self,
         track: Rc<local::Track>,
+        previous: Option<Rc<local::Track>>,
     ) -> Self::Output {
         self.on_local_track
-            .call1(local::LocalMediaTrack::new(track));
+            .call1(local::LocalMediaTrack::replacing(track, previous));
         Ok(())
     }
 
@@ -1799,12 +2976,34 @@ impl PeerEventHandler for InnerRoom {
         Ok(())
     }
 
+    /// Handles [`PeerEvent::SelectedCandidatePairChanged`] event and reports
+    /// the new [`SelectedCandidatePair`] to the RPC server.
+    async fn on_selected_candidate_pair_changed(
+        &self,
+        peer_id: PeerId,
+        pair: SelectedCandidatePair,
+    ) -> Self::Output {
+        self.rpc.send_command(Command::AddPeerConnectionMetrics {
+            peer_id,
+            metrics: PeerMetrics::SelectedCandidatePairChanged {
+                local_type: pair.local_type,
+                remote_type: pair.remote_type,
+                relay_protocol: pair.relay_protocol,
+            },
+        });
+        Ok(())
+    }
+
     /// Handles [`PeerEvent::FailedLocalMedia`] event by invoking
     /// `on_failed_local_media` [`Room`]'s callback.
+    ///
+    /// All the currently enabled outbound media is considered failed, so will
+    /// be re-acquired on [`RoomHandle::retry_local_media()`].
     async fn on_failed_local_media(
         &self,
         error: Traced<LocalMediaError>,
     ) -> Self::Output {
+        self.record_failed_local_media(self.send_constraints.enabled_kinds());
         self.on_failed_local_media.call1(api::Error::from(error));
         Ok(())
     }
@@ -1835,6 +3034,7 @@ impl PeerEventHandler for InnerRoom {
         sdp_answer: String,
         transceivers_statuses: HashMap<TrackId, bool>,
     ) -> Self::Output {
+        self.finish_negotiation(peer_id);
         self.rpc.send_command(Command::MakeSdpAnswer {
             peer_id,
             sdp_answer,
@@ -1855,16 +3055,10 @@ impl PeerEventHandler for InnerRoom {
 }
 
 impl Drop for InnerRoom {
-    /// Unsubscribes [`InnerRoom`] from all its subscriptions.
+    /// Closes this [`InnerRoom`], unless it's closed already.
+    #[inline]
     fn drop(&mut self) {
-        if let CloseReason::ByClient { reason, .. } =
-            *self.close_reason.borrow()
-        {
-            self.rpc.close_with_reason(reason);
-        };
-
-        self.on_close
-            .call1(RoomCloseReason::new(*self.close_reason.borrow()));
+        self.close();
     }
 }
 
@@ -1895,4 +3089,13 @@ impl Room {
     ) -> Option<Rc<peer::State>> {
         self.0.peers.state().get(peer_id)
     }
+
+    /// Returns [`EffectKind`]s of all the [`Effect`]s applied to this
+    /// [`Room`]'s state, in the order they were applied in.
+    ///
+    /// [`EffectKind`]: effects::EffectKind
+    #[inline]
+    pub fn applied_effects(&self) -> Vec<effects::EffectKind> {
+        self.0.effects.applied()
+    }
 }
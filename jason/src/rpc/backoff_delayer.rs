@@ -9,7 +9,16 @@ use futures::{channel::oneshot, future::BoxFuture, Future, FutureExt as _};
 use crate::platform;
 
 /// [`ExponentialBackoff`] adapted for the used async runtime.
-pub struct BackoffDelayer(ExponentialBackoff);
+pub struct BackoffDelayer {
+    /// [`ExponentialBackoff`] policy of delays between attempts.
+    backoff: ExponentialBackoff,
+
+    /// Maximum number of attempts to be performed.
+    ///
+    /// [`None`] means that the number of attempts is limited only by the max
+    /// elapsed time.
+    max_attempts: Option<u32>,
+}
 
 impl BackoffDelayer {
     /// Creates a new [`BackoffDelayer`] out of the provided options.
@@ -26,15 +35,42 @@ impl BackoffDelayer {
         // initial_interval = max_interval if initial_interval > max_delay
         let initial_interval = initial_interval.min(max_interval);
 
-        BackoffDelayer(ExponentialBackoff {
-            current_interval: initial_interval,
-            initial_interval,
-            randomization_factor: 0.0,
-            multiplier,
-            max_interval,
-            max_elapsed_time,
-            ..ExponentialBackoff::default()
-        })
+        BackoffDelayer {
+            backoff: ExponentialBackoff {
+                current_interval: initial_interval,
+                initial_interval,
+                randomization_factor: 0.0,
+                multiplier,
+                max_interval,
+                max_elapsed_time,
+                ..ExponentialBackoff::default()
+            },
+            max_attempts: None,
+        }
+    }
+
+    /// Randomizes each delay of this [`BackoffDelayer`] by the provided
+    /// `jitter` factor, so the actual delay lies in
+    /// `[delay * (1 - jitter), delay * (1 + jitter)]` range.
+    ///
+    /// This prevents multiple clients from retrying simultaneously.
+    ///
+    /// `jitter` is clamped into `[0.0, 1.0]` range.
+    #[inline]
+    #[must_use]
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.backoff.randomization_factor = jitter.max(0.0).min(1.0);
+        self
+    }
+
+    /// Limits the number of attempts performed by this [`BackoffDelayer`].
+    ///
+    /// `0` is considered as `1`.
+    #[inline]
+    #[must_use]
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts.max(1));
+        self
     }
 
     /// Retries the given `operation` according to this [`BackoffDelayer`]'s
@@ -43,13 +79,31 @@ impl BackoffDelayer {
     /// # Errors
     ///
     /// Propagates the error returned by the provided `operation`.
-    #[inline]
-    pub async fn retry<Fn, Fut, I, E>(self, operation: Fn) -> Result<I, E>
+    pub async fn retry<Fn, Fut, I, E>(self, mut operation: Fn) -> Result<I, E>
     where
         Fn: FnMut() -> Fut,
         Fut: Future<Output = Result<I, backoff::Error<E>>>,
     {
-        Retry::new(Sleeper, self.0, |_, _| {}, operation).await
+        let max_attempts = self.max_attempts;
+        let mut attempts = 0_u32;
+        Retry::new(
+            Sleeper,
+            self.backoff,
+            |_, _| {},
+            move || {
+                attempts = attempts.saturating_add(1);
+                let is_last = max_attempts.map_or(false, |max| attempts >= max);
+                operation().map(move |res| {
+                    res.map_err(|e| match e {
+                        backoff::Error::Transient(e) if is_last => {
+                            backoff::Error::Permanent(e)
+                        }
+                        e => e,
+                    })
+                })
+            },
+        )
+        .await
     }
 }
 
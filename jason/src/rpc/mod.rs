@@ -10,7 +10,8 @@ use std::str::FromStr;
 
 use derive_more::{AsRef, Display, From};
 use medea_client_api_proto::{
-    CloseReason as CloseByServerReason, Credential, MemberId, RoomId,
+    CloseDescription, CloseReason as CloseByServerReason, Credential, MemberId,
+    RoomId,
 };
 use tracerr::Traced;
 use url::Url;
@@ -150,8 +151,8 @@ impl FromStr for ConnectionInfo {
 /// Reasons of closing by client side and server side.
 #[derive(Copy, Clone, Display, Debug, Eq, PartialEq)]
 pub enum CloseReason {
-    /// Closed by server.
-    ByServer(CloseByServerReason),
+    /// Closed by server with the provided [`CloseDescription`].
+    ByServer(CloseDescription),
 
     /// Closed by client.
     #[display(fmt = "{}", reason)]
@@ -223,15 +224,34 @@ pub enum RpcClientError {
 pub enum CloseMsg {
     /// Transport was gracefully closed by remote.
     ///
-    /// Determines by close code `1000` and existence of
-    /// [`CloseByServerReason`].
+    /// Determines by a non-retryable [`CloseByServerReason`].
     #[display(fmt = "Normal. Code: {}, Reason: {}", _0, _1)]
     Normal(u16, CloseByServerReason),
 
     /// Connection was unexpectedly closed. Consider reconnecting.
     ///
-    /// Unexpected close determines by non-`1000` close code and for close code
-    /// `1000` without reason.
+    /// Unexpected close determines by a retryable [`CloseByServerReason`] or
+    /// by its absence.
     #[display(fmt = "Abnormal. Code: {}", _0)]
     Abnormal(u16),
 }
+
+impl CloseMsg {
+    /// Creates a new [`CloseMsg`] out of the provided close `code` and
+    /// `description` of a [Close] frame.
+    ///
+    /// [`CloseByServerReason`] is parsed from the provided
+    /// [`CloseDescription`], since non-retryable reasons are sent with the
+    /// same `1000` close code (see [`CloseByServerReason::close_code()`]).
+    ///
+    /// [Close]: https://tools.ietf.org/html/rfc6455#section-5.5.1
+    #[must_use]
+    pub fn from_close_frame(code: u16, description: &str) -> Self {
+        match serde_json::from_str::<CloseDescription>(description) {
+            Ok(desc) if !desc.reason.is_retryable() => {
+                Self::Normal(code, desc.reason)
+            }
+            _ => Self::Abnormal(code),
+        }
+    }
+}
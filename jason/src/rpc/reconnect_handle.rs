@@ -1,8 +1,13 @@
 //! Reconnection for [`RpcSession`].
 
-use std::{rc::Weak, time::Duration};
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+    time::Duration,
+};
 
 use derive_more::{Display, From};
+use futures::future::{self, AbortHandle};
 use tracerr::Traced;
 
 use crate::{
@@ -21,13 +26,27 @@ pub enum ReconnectError {
     /// [`ReconnectHandle`]'s [`Weak`] pointer is detached.
     #[display(fmt = "ReconnectHandle is in detached state")]
     Detached,
+
+    /// Reconnection with a backoff was cancelled via
+    /// [`ReconnectHandle::cancel_backoff()`].
+    #[display(fmt = "Reconnection with backoff was cancelled")]
+    Cancelled,
 }
 
 /// External handle used to reconnect to a media server when connection is lost.
 ///
 /// This handle will be passed to a `Room.on_connection_loss` callback.
 #[derive(Clone)]
-pub struct ReconnectHandle(Weak<dyn RpcSession>);
+pub struct ReconnectHandle {
+    /// [`RpcSession`] to reconnect.
+    rpc: Weak<dyn RpcSession>,
+
+    /// Callback invoked when reconnection with a backoff gives up.
+    on_give_up: Rc<platform::Callback<()>>,
+
+    /// [`AbortHandle`]s of the ongoing reconnections with a backoff.
+    backoffs: Rc<RefCell<Vec<AbortHandle>>>,
+}
 
 impl ReconnectHandle {
     /// Instantiates new [`ReconnectHandle`] from the given [`RpcSession`]
@@ -35,7 +54,32 @@ impl ReconnectHandle {
     #[inline]
     #[must_use]
     pub fn new(rpc: Weak<dyn RpcSession>) -> Self {
-        Self(rpc)
+        Self {
+            rpc,
+            on_give_up: Rc::default(),
+            backoffs: Rc::default(),
+        }
+    }
+
+    /// Sets callback, invoked when
+    /// [`ReconnectHandle::reconnect_with_backoff()`] gives up reconnecting,
+    /// because its max attempts count or max elapsed time is exceeded.
+    #[inline]
+    pub fn on_give_up(&self, f: platform::Function<()>) {
+        self.on_give_up.set_func(f);
+    }
+
+    /// Cancels all the ongoing [`ReconnectHandle::reconnect_with_backoff()`]
+    /// calls, making them fail with a [`ReconnectError::Cancelled`].
+    ///
+    /// Intended to be used when an application detects network recovery by
+    /// its own means, so it can reconnect immediately via
+    /// [`ReconnectHandle::reconnect_with_delay()`] instead of awaiting for the
+    /// next backoff attempt.
+    pub fn cancel_backoff(&self) {
+        for backoff in self.backoffs.borrow_mut().drain(..) {
+            backoff.abort();
+        }
     }
 
     /// Tries to reconnect after the provided delay in milliseconds.
@@ -54,7 +98,7 @@ impl ReconnectHandle {
         platform::delay_for(Duration::from_millis(u64::from(delay_ms))).await;
 
         let rpc = self
-            .0
+            .rpc
             .upgrade()
             .ok_or_else(|| tracerr::new!(ReconnectError::Detached))?;
 
@@ -77,7 +121,17 @@ impl ReconnectHandle {
     /// `0.0`. This might cause a busy loop, so it's not recommended.
     ///
     /// Max elapsed time can be limited with an optional `max_elapsed_time_ms`
-    /// argument.
+    /// argument, and the number of attempts with an optional `max_attempts`
+    /// argument. Once any of them is exceeded, the callback set via
+    /// [`ReconnectHandle::on_give_up()`] is invoked.
+    ///
+    /// Each delay is randomized by the given `jitter` factor (clamped into
+    /// `[0.0, 1.0]` range), so the actual delay lies in
+    /// `[delay * (1 - jitter), delay * (1 + jitter)]` range. This prevents
+    /// all the clients from reconnecting simultaneously after a media server
+    /// restart.
+    ///
+    /// Can be cancelled via [`ReconnectHandle::cancel_backoff()`].
     ///
     /// If [`RpcSession`] is already reconnecting then new reconnection attempt
     /// won't be performed. Instead, it will wait for the first reconnection
@@ -92,15 +146,22 @@ impl ReconnectHandle {
         multiplier: f64,
         max_delay: u32,
         max_elapsed_time_ms: Option<u32>,
+        jitter: f64,
+        max_attempts: Option<u32>,
     ) -> Result<(), Traced<ReconnectError>> {
-        BackoffDelayer::new(
+        let mut delayer = BackoffDelayer::new(
             Duration::from_millis(starting_delay_ms.into()),
             multiplier,
             Duration::from_millis(max_delay.into()),
             max_elapsed_time_ms.map(|val| Duration::from_millis(val.into())),
         )
-        .retry(|| async {
-            self.0
+        .with_jitter(jitter);
+        if let Some(max_attempts) = max_attempts {
+            delayer = delayer.with_max_attempts(max_attempts);
+        }
+
+        let (retry, abort) = future::abortable(delayer.retry(|| async {
+            self.rpc
                 .upgrade()
                 .ok_or_else(|| {
                     backoff::Error::Permanent(tracerr::new!(
@@ -111,7 +172,16 @@ impl ReconnectHandle {
                 .await
                 .map_err(tracerr::map_from_and_wrap!())
                 .map_err(backoff::Error::Transient)
-        })
-        .await
+        }));
+        self.backoffs.borrow_mut().push(abort);
+
+        let res = retry
+            .await
+            .map_err(|_| tracerr::new!(ReconnectError::Cancelled))?;
+        // `RpcSession` is still alive, so reconnection has been exhausted.
+        if res.is_err() && self.rpc.strong_count() > 0 {
+            self.on_give_up.call0();
+        }
+        res
     }
 }
@@ -17,7 +17,7 @@ use futures::{
     stream::LocalBoxStream,
     StreamExt,
 };
-use medea_client_api_proto::{Command, Event, MemberId, RoomId};
+use medea_client_api_proto::{Command, Credential, Event, MemberId, RoomId};
 use medea_reactive::ObservableCell;
 use tracerr::Traced;
 
@@ -82,6 +82,11 @@ pub enum ConnectionLostReason {
 impl JsCaused for ConnectionLostReason {
     type Error = platform::Error;
 
+    #[inline]
+    fn code(&self) -> &'static str {
+        "ConnectionLostReason"
+    }
+
     #[inline]
     fn name(&self) -> &'static str {
         match self {
@@ -136,6 +141,27 @@ pub trait RpcSession {
     /// Sends [`Command`] to server.
     fn send_command(&self, command: Command);
 
+    /// Renews the [`Credential`] this [`RpcSession`] authorizes with, so it's
+    /// used for all the further reconnections.
+    ///
+    /// Sends it to the server if this [`RpcSession`] is opened, so the server
+    /// can verify it.
+    fn refresh_credential(&self, credential: Credential);
+
+    /// Returns sequence number of the last [`Event`] received by this
+    /// [`RpcSession`], if any.
+    ///
+    /// Reported to the server on synchronization, so it can replay the
+    /// [`Event`]s missed during a connection loss.
+    fn last_event_seq(&self) -> Option<u64>;
+
+    /// Returns value of the feature flag with the provided `name`, enabled
+    /// for this [`RpcSession`] by the server, if any.
+    ///
+    /// Allows components to switch to the server-side features rolled out
+    /// without a protocol version bump.
+    fn feature(&self, name: &str) -> Option<String>;
+
     /// [`Future`] which will resolve on normal [`RpcSession`] connection
     /// closing.
     ///
@@ -149,6 +175,12 @@ pub trait RpcSession {
     /// client will be dropped.
     fn close_with_reason(&self, close_reason: ClientDisconnect);
 
+    /// Cancels an ongoing connection establishment or authorization of this
+    /// [`RpcSession`], so it can be [`RpcSession::connect`]ed again later.
+    ///
+    /// Does nothing if this [`RpcSession`] isn't connecting at the moment.
+    fn cancel_connect(&self);
+
     /// Subscribe to connection loss events.
     ///
     /// Connection loss is any unexpected [`platform::RpcTransport`] close. In
@@ -186,6 +218,14 @@ pub struct WebSocketRpcSession {
 
     /// Subscribers of the [`RpcSession::subscribe`].
     event_txs: RefCell<Vec<mpsc::UnboundedSender<Event>>>,
+
+    /// Sequence number of the last [`Event`] sent to the
+    /// [`RpcSession::subscribe`] subscribers.
+    last_event_seq: Cell<Option<u64>>,
+
+    /// [`Credential`] renewed via [`RpcSession::refresh_credential`], which
+    /// supersedes the one from the current [`ConnectionInfo`].
+    refreshed_credential: RefCell<Option<Credential>>,
 }
 
 impl WebSocketRpcSession {
@@ -199,6 +239,8 @@ impl WebSocketRpcSession {
             state: ObservableCell::new(SessionState::Uninitialized),
             can_reconnect: Rc::new(Cell::new(false)),
             event_txs: RefCell::default(),
+            last_event_seq: Cell::default(),
+            refreshed_credential: RefCell::default(),
         });
 
         this.spawn_state_watcher();
@@ -286,10 +328,15 @@ impl WebSocketRpcSession {
                 let this = upgrade_or_break!(weak_this);
                 match state {
                     S::Connecting(info) => {
-                        match Rc::clone(&this.client)
+                        let res = Rc::clone(&this.client)
                             .connect(info.url.clone())
-                            .await
-                        {
+                            .await;
+                        if this.state.get() != S::Connecting(Rc::clone(&info)) {
+                            // Connection establishment has been cancelled or
+                            // superseded while being performed.
+                            continue;
+                        }
+                        match res {
                             Ok(_) => {
                                 this.state.set(S::Authorizing(info));
                             }
@@ -302,10 +349,15 @@ impl WebSocketRpcSession {
                         }
                     }
                     S::Authorizing(info) => {
+                        let credential = this
+                            .refreshed_credential
+                            .borrow()
+                            .clone()
+                            .unwrap_or_else(|| info.credential.clone());
                         this.client.authorize(
                             info.room_id.clone(),
                             info.member_id.clone(),
-                            info.credential.clone(),
+                            credential,
                         );
                     }
                     _ => {}
@@ -395,6 +447,7 @@ impl RpcSession for WebSocketRpcSession {
 
         match self.state.get() {
             S::Uninitialized | S::Initialized(_) | S::Lost(_, _) => {
+                self.refreshed_credential.replace(None);
                 self.state.set(S::Initialized(Rc::new(connection_info)));
             }
             S::Finished(reason) => {
@@ -404,6 +457,7 @@ impl RpcSession for WebSocketRpcSession {
             }
             S::Connecting(info) => {
                 if info.as_ref() != &connection_info {
+                    self.refreshed_credential.replace(None);
                     self.state.set(S::Initialized(Rc::new(connection_info)));
                 }
             }
@@ -448,6 +502,41 @@ impl RpcSession for WebSocketRpcSession {
         }
     }
 
+    /// Stores the provided [`Credential`] to be used for the further
+    /// reconnections, and sends [`Command::RefreshCredentials`] to the server
+    /// if current [`SessionState`] is [`SessionState::Opened`].
+    ///
+    /// Does nothing if current [`SessionState`] is
+    /// [`SessionState::Uninitialized`] or [`SessionState::Finished`].
+    fn refresh_credential(&self, credential: Credential) {
+        match self.state.get() {
+            SessionState::Uninitialized | SessionState::Finished(_) => return,
+            SessionState::Opened(info) => {
+                self.client.send_command(
+                    info.room_id.clone(),
+                    Command::RefreshCredentials {
+                        credential: credential.clone(),
+                    },
+                );
+            }
+            SessionState::Initialized(_)
+            | SessionState::Connecting(_)
+            | SessionState::Authorizing(_)
+            | SessionState::Lost(_, _) => {}
+        }
+        self.refreshed_credential.replace(Some(credential));
+    }
+
+    #[inline]
+    fn last_event_seq(&self) -> Option<u64> {
+        self.last_event_seq.get()
+    }
+
+    #[inline]
+    fn feature(&self, name: &str) -> Option<String> {
+        self.client.feature(name)
+    }
+
     /// Returns [`Future`] which will be resolved when [`SessionState`] will be
     /// transited to the [`SessionState::Finished`] or [`WebSocketRpcSession`]
     /// will be dropped.
@@ -489,6 +578,31 @@ impl RpcSession for WebSocketRpcSession {
         self.state.set(SessionState::Finished(close_reason.into()));
     }
 
+    /// Moves [`SessionState::Connecting`] or [`SessionState::Authorizing`]
+    /// [`WebSocketRpcSession`] back to the [`SessionState::Initialized`].
+    ///
+    /// If authorization request has been already sent, then the [`Room`] is
+    /// left on the server side.
+    ///
+    /// [`Room`]: crate::room::Room
+    fn cancel_connect(&self) {
+        match self.state.get() {
+            SessionState::Connecting(info) => {
+                self.state.set(SessionState::Initialized(info));
+            }
+            SessionState::Authorizing(info) => {
+                self.client
+                    .leave_room(info.room_id.clone(), info.member_id.clone());
+                self.state.set(SessionState::Initialized(info));
+            }
+            SessionState::Uninitialized
+            | SessionState::Initialized(_)
+            | SessionState::Lost(_, _)
+            | SessionState::Opened(_)
+            | SessionState::Finished(_) => {}
+        }
+    }
+
     /// Returns [`Stream`] which will provided `Some(())` every time when
     /// [`SessionState`] goes to the [`SessionState::Lost`].
     ///
@@ -586,9 +700,15 @@ impl RpcEventHandler for WebSocketRpcSession {
     /// subscribers if current [`SessionState`] is [`SessionState::Opened`]
     /// and provided [`RoomId`] is equal to the [`RoomId`] from the
     /// [`ConnectionInfo`].
-    fn on_event(&self, room_id: RoomId, event: Event) {
+    ///
+    /// Remembers the provided sequence number as the
+    /// [`RpcSession::last_event_seq`].
+    fn on_event(&self, room_id: RoomId, event: Event, seq: Option<u64>) {
         if let SessionState::Opened(info) = self.state.get() {
             if info.room_id == room_id {
+                if seq.is_some() {
+                    self.last_event_seq.set(seq);
+                }
                 self.event_txs
                     .borrow_mut()
                     .retain(|tx| tx.unbounded_send(event.clone()).is_ok());
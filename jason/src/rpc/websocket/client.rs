@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc, time::Duration};
+use std::{cell::RefCell, collections::HashMap, rc::Rc, time::Duration};
 
 use derive_more::Display;
 use futures::{
@@ -7,8 +7,8 @@ use futures::{
     stream::{LocalBoxStream, StreamExt as _},
 };
 use medea_client_api_proto::{
-    ClientMsg, CloseReason as CloseByServerReason, Command, Credential, Event,
-    MemberId, RoomId, RpcSettings, ServerMsg,
+    ClientMsg, CloseDescription, CloseReason as CloseByServerReason, Command,
+    Credential, Event, MemberId, RoomId, RpcSettings, ServerMsg,
 };
 use medea_macro::dispatchable;
 use medea_reactive::ObservableCell;
@@ -125,6 +125,9 @@ struct Inner {
 
     /// Current [`ClientState`] of this [`WebSocketRpcClient`].
     state: ObservableCell<ClientState>,
+
+    /// Key-value feature flags received in the last [`RpcSettings`].
+    features: HashMap<String, String>,
 }
 
 /// Factory closure producing a [`platform::RpcTransport`].
@@ -155,13 +158,14 @@ impl Inner {
             state: ObservableCell::new(ClientState::Closed(
                 ClosedStateReason::NeverConnected,
             )),
+            features: HashMap::new(),
         })
     }
 }
 
 /// Events which can be thrown by [`WebSocketRpcClient`].
 #[dispatchable(self: &Self)]
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum RpcEvent {
     /// Notification of the subscribers that [`WebSocketRpcClient`] is joined
     /// [`Room`] on Media Server.
@@ -202,6 +206,9 @@ pub enum RpcEvent {
 
         /// Received [`Event`].
         event: Event,
+
+        /// Sequence number of the received [`Event`], if any.
+        seq: Option<u64>,
     },
 }
 
@@ -231,6 +238,7 @@ impl WebSocketRpcClient {
             Command::JoinRoom {
                 member_id,
                 credential,
+                capabilities: platform::client_capabilities(),
             },
         );
     }
@@ -275,7 +283,9 @@ impl WebSocketRpcClient {
                         .on_close_subscribers
                         .drain(..)
                         .for_each(|sub| {
-                            let _ = sub.send(CloseReason::ByServer(reason));
+                            let _ = sub.send(CloseReason::ByServer(
+                                CloseDescription::new(reason),
+                            ));
                         });
                 }
             },
@@ -290,17 +300,32 @@ impl WebSocketRpcClient {
     /// Handles [`ServerMsg`]s from a remote server.
     fn on_transport_message(&self, msg: ServerMsg) {
         let msg = match msg {
-            ServerMsg::Event { room_id, event } => match event {
+            ServerMsg::Event {
+                room_id,
+                event,
+                seq,
+            } => match event {
                 Event::RoomJoined { member_id } => {
                     Some(RpcEvent::JoinedRoom { room_id, member_id })
                 }
-                Event::RoomLeft { close_reason } => Some(RpcEvent::LeftRoom {
+                Event::RoomLeft {
+                    close_reason,
+                    retry_after_ms,
+                } => Some(RpcEvent::LeftRoom {
+                    room_id,
+                    close_reason: CloseReason::ByServer(CloseDescription {
+                        reason: close_reason,
+                        retry_after_ms,
+                    }),
+                }),
+                _ => Some(RpcEvent::Event {
                     room_id,
-                    close_reason: CloseReason::ByServer(close_reason),
+                    event,
+                    seq,
                 }),
-                _ => Some(RpcEvent::Event { room_id, event }),
             },
             ServerMsg::RpcSettings(settings) => {
+                self.0.borrow_mut().features = settings.features;
                 if let Some(heartbeat) = self.0.borrow_mut().heartbeat.as_ref()
                 {
                     heartbeat.update_settings(
@@ -355,7 +380,9 @@ impl WebSocketRpcClient {
                 }
             }
         });
-        self.0.borrow_mut().heartbeat = Some(heartbeat);
+        let mut inner = self.0.borrow_mut();
+        inner.heartbeat = Some(heartbeat);
+        inner.features = rpc_settings.features;
 
         Ok(())
     }
@@ -506,7 +533,21 @@ impl WebSocketRpcClient {
     }
 
     /// Sends [`Command`] for the provided [`RoomId`] to server.
-    pub fn send_command(&self, room_id: RoomId, command: Command) {
+    ///
+    /// Informational fields of the [`Command`] exceeding their limits are
+    /// truncated (see [`Command::truncate_oversized()`]), while the ones
+    /// exceeding [`Command::check_relayed_size()`] make Media Server to close
+    /// the connection with a [`CloseReason::MessageTooLarge`].
+    ///
+    /// [`CloseReason::MessageTooLarge`]:
+    /// medea_client_api_proto::CloseReason::MessageTooLarge
+    pub fn send_command(&self, room_id: RoomId, mut command: Command) {
+        if let Err(e) = command.check_relayed_size() {
+            log::error!("Oversized Command to Room {}: {}", room_id, e);
+        }
+        if command.truncate_oversized() {
+            log::warn!("Truncated oversized Command to Room {}", room_id);
+        }
         let socket_borrow = &self.0.borrow().sock;
 
         if let Some(socket) = socket_borrow.as_ref() {
@@ -551,6 +592,14 @@ impl WebSocketRpcClient {
         Box::pin(rx)
     }
 
+    /// Returns value of the feature flag with the provided `name` received
+    /// from the server, if any.
+    #[inline]
+    #[must_use]
+    pub fn feature(&self, name: &str) -> Option<String> {
+        self.0.borrow().features.get(name).cloned()
+    }
+
     /// Sets reason being passed to the underlying transport when this client is
     /// dropped.
     #[inline]
@@ -12,6 +12,12 @@ pub trait JsCaused {
     /// Type of wrapper for JS error.
     type Error;
 
+    /// Returns code of error, identifying its type.
+    ///
+    /// Unlike [`std::any::type_name()`], it's stable and doesn't depend on the
+    /// module the error type is declared in.
+    fn code(&self) -> &'static str;
+
     /// Returns name of error.
     fn name(&self) -> &'static str;
 
@@ -31,6 +31,36 @@ impl Drop for TaskHandle {
     }
 }
 
+/// Guard invoking the provided function on [`Drop`], unless it has been
+/// [`DropGuard::disarm()`]ed before.
+///
+/// Intended for cleaning up state of a [`Future`] being dropped before its
+/// completion (e.g. being aborted).
+pub struct DropGuard<F: FnOnce()>(Option<F>);
+
+impl<F: FnOnce()> DropGuard<F> {
+    /// Creates a new [`DropGuard`] invoking the provided function on [`Drop`].
+    #[inline]
+    #[must_use]
+    pub fn new(f: F) -> Self {
+        Self(Some(f))
+    }
+
+    /// Prevents the guarded function from being invoked.
+    #[inline]
+    pub fn disarm(mut self) {
+        drop(self.0.take());
+    }
+}
+
+impl<F: FnOnce()> Drop for DropGuard<F> {
+    fn drop(&mut self) {
+        if let Some(f) = self.0.take() {
+            f();
+        }
+    }
+}
+
 /// Tries to upgrade [`Weak`] reference breaks cycle if upgrade fails.
 macro_rules! upgrade_or_break {
     ($weak:tt) => {
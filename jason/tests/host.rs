@@ -0,0 +1,253 @@
+//! Tests of `peer` components running on the host with the fake platform,
+//! without a browser.
+
+#![cfg(all(feature = "mockable", not(target_arch = "wasm32")))]
+#![forbid(non_ascii_idents, unsafe_code)]
+
+use std::rc::Rc;
+
+use futures::channel::mpsc;
+use medea_client_api_proto::{
+    AudioSettings, MediaType, MemberId, TrackId, TrackPatchEvent,
+};
+use medea_jason::{
+    media::{LocalTracksConstraints, RecvConstraints},
+    peer::{MediaConnections, PeerEvent},
+    platform::{self, RtcPeerConnection, TransceiverDirection},
+    utils::Updatable as _,
+};
+
+const TRACK_ID: TrackId = TrackId(0);
+
+/// Creates new [`MediaConnections`] backed by the fake
+/// [`RtcPeerConnection`].
+fn media_connections() -> (MediaConnections, mpsc::UnboundedReceiver<PeerEvent>)
+{
+    let (tx, rx) = mpsc::unbounded();
+    let media_connections = MediaConnections::new(
+        Rc::new(RtcPeerConnection::new(Vec::new(), false).unwrap()),
+        tx,
+    );
+    (media_connections, rx)
+}
+
+/// Returns [`TrackPatchEvent`] switching only individual media exchange of
+/// the track with the provided [`TrackId`] to the provided state.
+fn individual_patch(id: TrackId, enabled: bool) -> TrackPatchEvent {
+    TrackPatchEvent {
+        enabled_individual: Some(enabled),
+        ..TrackPatchEvent::new(id)
+    }
+}
+
+/// Returns [`TrackPatchEvent`] switching media exchange of the
+/// [`TRACK_ID`] track to the provided state.
+fn enabled_patch(enabled: bool) -> TrackPatchEvent {
+    TrackPatchEvent {
+        enabled_individual: Some(enabled),
+        enabled_general: Some(enabled),
+        ..TrackPatchEvent::new(TRACK_ID)
+    }
+}
+
+mod sender {
+    use super::*;
+
+    #[test]
+    fn updates_send_direction() {
+        let (media_connections, _rx) = media_connections();
+        let sender = media_connections
+            .create_sender(
+                TRACK_ID,
+                MediaType::Audio(AudioSettings {
+                    required: false,
+                    opus: None,
+                }),
+                None,
+                vec!["bob".into()],
+                &LocalTracksConstraints::default(),
+            )
+            .unwrap();
+
+        platform::block_on(async {
+            sender.state().update(&enabled_patch(false));
+            sender.state().when_updated().await;
+            assert!(sender.general_disabled());
+            assert!(!sender.is_publishing());
+
+            sender.state().update(&enabled_patch(true));
+            sender.state().when_updated().await;
+            assert!(!sender.general_disabled());
+            assert!(sender.is_publishing());
+
+            sender.state().update(&enabled_patch(false));
+            sender.state().when_updated().await;
+            assert!(sender.general_disabled());
+            assert!(!sender.is_publishing());
+        });
+    }
+
+    #[test]
+    fn downgrades_send_direction_when_disabled_individually() {
+        let (media_connections, _rx) = media_connections();
+        let sender = media_connections
+            .create_sender(
+                TRACK_ID,
+                MediaType::Audio(AudioSettings {
+                    required: false,
+                    opus: None,
+                }),
+                None,
+                vec!["bob".into()],
+                &LocalTracksConstraints::default(),
+            )
+            .unwrap();
+
+        platform::block_on(async {
+            sender.state().update(&individual_patch(TRACK_ID, false));
+            sender.state().when_updated().await;
+            assert!(!sender.general_disabled());
+            assert!(!sender.is_publishing());
+
+            sender.state().update(&individual_patch(TRACK_ID, true));
+            sender.state().when_updated().await;
+            assert!(sender.is_publishing());
+        });
+    }
+
+    #[test]
+    fn ignores_patch_of_other_track() {
+        let (media_connections, _rx) = media_connections();
+        let sender = media_connections
+            .create_sender(
+                TRACK_ID,
+                MediaType::Audio(AudioSettings {
+                    required: false,
+                    opus: None,
+                }),
+                None,
+                vec!["bob".into()],
+                &LocalTracksConstraints::default(),
+            )
+            .unwrap();
+
+        platform::block_on(async {
+            sender.state().update(&TrackPatchEvent {
+                id: TrackId(TRACK_ID.0 + 100),
+                ..enabled_patch(false)
+            });
+            sender.state().when_updated().await;
+            assert!(!sender.general_disabled());
+        });
+    }
+}
+
+mod receiver {
+    use super::*;
+
+    #[test]
+    fn updates_recv_direction() {
+        let (media_connections, _rx) = media_connections();
+        let receiver = media_connections.create_receiver(
+            TRACK_ID,
+            MediaType::Audio(AudioSettings {
+                required: true,
+                opus: None,
+            }),
+            None,
+            MemberId::from("bob"),
+            &RecvConstraints::default(),
+        );
+        let transceiver = receiver.transceiver().unwrap();
+        assert!(transceiver.has_direction(TransceiverDirection::RECV));
+
+        platform::block_on(async {
+            receiver.state().update(&enabled_patch(false));
+            receiver.state().when_updated().await;
+            assert!(!receiver.enabled_general());
+            assert!(!transceiver.has_direction(TransceiverDirection::RECV));
+
+            receiver.state().update(&enabled_patch(true));
+            receiver.state().when_updated().await;
+            assert!(receiver.enabled_general());
+            assert!(transceiver.has_direction(TransceiverDirection::RECV));
+        });
+    }
+
+    #[test]
+    fn shares_transceiver_with_sender() {
+        let (media_connections, _rx) = media_connections();
+        let sender = media_connections
+            .create_sender(
+                TRACK_ID,
+                MediaType::Audio(AudioSettings {
+                    required: false,
+                    opus: None,
+                }),
+                None,
+                vec!["bob".into()],
+                &LocalTracksConstraints::default(),
+            )
+            .unwrap();
+        let receiver = media_connections.create_receiver(
+            TrackId(1),
+            MediaType::Audio(AudioSettings {
+                required: true,
+                opus: None,
+            }),
+            None,
+            MemberId::from("bob"),
+            &RecvConstraints::default(),
+        );
+
+        let transceiver = receiver.transceiver().unwrap();
+        assert!(transceiver.has_direction(TransceiverDirection::RECV));
+        sender
+            .transceiver()
+            .sub_direction(TransceiverDirection::RECV);
+        assert!(!transceiver.has_direction(TransceiverDirection::RECV));
+    }
+    #[test]
+    fn becomes_inactive_when_neither_sends_nor_receives() {
+        let (media_connections, _rx) = media_connections();
+        let sender = media_connections
+            .create_sender(
+                TRACK_ID,
+                MediaType::Audio(AudioSettings {
+                    required: false,
+                    opus: None,
+                }),
+                None,
+                vec!["bob".into()],
+                &LocalTracksConstraints::default(),
+            )
+            .unwrap();
+        let receiver = media_connections.create_receiver(
+            TrackId(1),
+            MediaType::Audio(AudioSettings {
+                required: true,
+                opus: None,
+            }),
+            None,
+            MemberId::from("bob"),
+            &RecvConstraints::default(),
+        );
+        let transceiver = receiver.transceiver().unwrap();
+
+        platform::block_on(async {
+            sender.state().update(&individual_patch(TRACK_ID, false));
+            sender.state().when_updated().await;
+            receiver
+                .state()
+                .update(&individual_patch(TrackId(1), false));
+            receiver.state().when_updated().await;
+            assert!(!receiver.is_receiving());
+            assert_eq!(transceiver.direction(), TransceiverDirection::INACTIVE);
+
+            receiver.state().update(&individual_patch(TrackId(1), true));
+            receiver.state().when_updated().await;
+            assert!(receiver.is_receiving());
+            assert_eq!(transceiver.direction(), TransceiverDirection::RECV);
+        });
+    }
+}
@@ -0,0 +1,46 @@
+//! Tests for [`medea_jason::log`].
+
+use std::{cell::RefCell, rc::Rc};
+
+use ::log::{Level, LevelFilter};
+use medea_jason::log::{self, LogScope};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Tests that records are filtered by the global and scoped levels, and are
+/// passed to the registered sinks.
+#[wasm_bindgen_test]
+fn filters_records_by_scope_level() {
+    log::init();
+    log::set_level(LevelFilter::Warn);
+
+    let records = Rc::new(RefCell::new(Vec::new()));
+    log::add_sink({
+        let records = Rc::clone(&records);
+        move |record| {
+            records
+                .borrow_mut()
+                .push((record.level(), record.message().to_owned()))
+        }
+    });
+
+    ::log::debug!(target: "medea_jason::rpc::websocket", "rpc 1");
+    ::log::warn!(target: "medea_jason::peer", "peer 1");
+    log::set_scope_level(LogScope::Rpc, Some(LevelFilter::Debug));
+    ::log::debug!(target: "medea_jason::rpc::websocket", "rpc 2");
+    ::log::debug!(target: "medea_jason::rpcs", "rpcs 1");
+    ::log::debug!(target: "medea_jason::peer", "peer 2");
+
+    log::set_scope_level(LogScope::Rpc, None);
+    log::set_level(LevelFilter::Debug);
+    log::clear_sinks();
+
+    assert_eq!(
+        *records.borrow(),
+        vec![
+            (Level::Warn, "peer 1".to_owned()),
+            (Level::Debug, "rpc 2".to_owned()),
+        ],
+    );
+}
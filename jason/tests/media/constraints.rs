@@ -3,6 +3,7 @@
 use medea_client_api_proto::{MediaSourceKind, VideoSettings};
 use medea_jason::{
     media::{
+        constraints::ConstrainU32, AudioRouteChangeReason, AudioRoutePolicy,
         AudioTrackConstraints, DeviceVideoTrackConstraints,
         DisplayVideoTrackConstraints, MediaKind, MediaManager,
         MediaStreamSettings, MultiSourceTracksConstraints, VideoSource,
@@ -462,6 +463,47 @@ async fn multi_source_media_stream_constraints_build8() {
 #[wasm_bindgen_test]
 async fn merge_audio() {}
 
+#[wasm_bindgen_test]
+async fn merge_audio_processing_constraints() {
+    let mut constraints = AudioTrackConstraints::new();
+    constraints.ideal_sample_rate(48000);
+
+    let mut another = AudioTrackConstraints::new();
+    another.exact_sample_rate(44100);
+    another.exact_channel_count(2);
+    another.ideal_latency(10);
+    another.voice_isolation(true);
+    another.route_policy(AudioRoutePolicy::MuteOnDeviceUnavailable);
+    constraints.merge(another);
+
+    assert_eq!(constraints.sample_rate, Some(ConstrainU32::Ideal(48000)));
+    assert_eq!(constraints.channel_count, Some(ConstrainU32::Exact(2)));
+    assert_eq!(constraints.latency, Some(ConstrainU32::Ideal(10)));
+    assert_eq!(constraints.voice_isolation, Some(true));
+    assert_eq!(
+        constraints.route_policy,
+        Some(AudioRoutePolicy::MuteOnDeviceUnavailable),
+    );
+}
+
+#[wasm_bindgen_test]
+async fn audio_route_policy() {
+    let mut settings = MediaStreamSettings::new();
+    assert_eq!(settings.audio_route_policy(), AudioRoutePolicy::Ignore);
+    assert!(!settings
+        .audio_route_policy()
+        .should_mute(AudioRouteChangeReason::OldDeviceUnavailable));
+
+    let mut audio = AudioTrackConstraints::new();
+    audio.route_policy(AudioRoutePolicy::MuteOnDeviceUnavailable);
+    settings.audio(audio);
+    let policy = settings.audio_route_policy();
+
+    assert!(policy.should_mute(AudioRouteChangeReason::OldDeviceUnavailable));
+    assert!(!policy.should_mute(AudioRouteChangeReason::NewDeviceAvailable));
+    assert!(!policy.should_mute(AudioRouteChangeReason::Other));
+}
+
 #[wasm_bindgen_test]
 async fn merge_video() {}
 
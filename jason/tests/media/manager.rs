@@ -3,6 +3,7 @@
 use std::iter::FromIterator;
 
 use js_sys::Array as JsArray;
+use medea_client_api_proto::MediaSourceKind;
 use wasm_bindgen_futures::JsFuture;
 use wasm_bindgen_test::*;
 use web_sys as sys;
@@ -12,7 +13,7 @@ use medea_jason::{
     media::{
         AudioTrackConstraints, DeviceVideoTrackConstraints,
         DisplayVideoTrackConstraints, GetUserMediaError, InitLocalTracksError,
-        MediaKind, MediaManager, MediaStreamSettings,
+        MediaKind, MediaManager, MediaStreamSettings, PermissionState,
     },
 };
 
@@ -25,7 +26,7 @@ async fn get_media_devices_info() {
     let media_manager = MediaManager::default();
     let devices = JsFuture::from(
         api::MediaManagerHandle::from(media_manager.new_handle())
-            .enumerate_devices(),
+            .enumerate_devices(None),
     )
     .await
     .unwrap();
@@ -34,6 +35,23 @@ async fn get_media_devices_info() {
     assert!(devices.length() >= 2);
 }
 
+#[wasm_bindgen_test]
+async fn query_permissions_after_media_acquired() {
+    let media_manager = MediaManager::default();
+    let handle = media_manager.new_handle();
+    let constraints = {
+        let mut constraints = MediaStreamSettings::new();
+        constraints.audio(AudioTrackConstraints::new());
+        constraints.device_video(DeviceVideoTrackConstraints::new());
+        constraints
+    };
+    let _tracks = media_manager.get_tracks(constraints).await.unwrap();
+
+    let permissions = handle.query_permissions().await;
+    assert_eq!(permissions.camera, PermissionState::Granted);
+    assert_eq!(permissions.microphone, PermissionState::Granted);
+}
+
 #[wasm_bindgen_test]
 async fn failed_get_media_devices_info() {
     let mock_navigator = MockNavigator::new();
@@ -42,7 +60,7 @@ async fn failed_get_media_devices_info() {
     let media_manager = MediaManager::default();
     let result = JsFuture::from(
         api::MediaManagerHandle::from(media_manager.new_handle())
-            .enumerate_devices(),
+            .enumerate_devices(None),
     )
     .await;
     mock_navigator.stop();
@@ -316,6 +334,53 @@ async fn display_track_is_cached() {
     mock_navigator.stop();
 }
 
+/// 1. Do `media_manager.get_stream({video:device, video:display}})`;
+/// 2. Do `media_manager.get_stream({video:device, video:display}})`;
+/// 3. Assert that both cached tracks are reused for the sources they were
+///    captured from, and only one getUserMedia and getDisplayMedia requests
+///    were made.
+#[wasm_bindgen_test]
+async fn device_and_display_tracks_are_cached_separately() {
+    if is_firefox() {
+        // getDisplayMedia is not mockable in ff atm
+        return;
+    }
+    let mock_navigator = MockNavigator::new();
+
+    let media_manager = MediaManager::default();
+    let constraints = {
+        let mut constraints = MediaStreamSettings::new();
+        constraints.device_video(DeviceVideoTrackConstraints::new());
+        constraints.display_video(DisplayVideoTrackConstraints::new());
+        constraints
+    };
+
+    let tracks = media_manager.get_tracks(constraints.clone()).await.unwrap();
+    assert_eq!(tracks.len(), 2);
+    assert!(tracks.iter().all(|(_, is_new)| *is_new));
+    let (display, device): (Vec<_>, Vec<_>) =
+        tracks.into_iter().partition(|(track, _)| {
+            matches!(track.media_source_kind(), MediaSourceKind::Display)
+        });
+    assert_eq!(display.len(), 1);
+    assert_eq!(device.len(), 1);
+
+    let tracks = media_manager.get_tracks(constraints).await.unwrap();
+    assert_eq!(tracks.len(), 2);
+    assert!(tracks.iter().all(|(_, is_new)| !*is_new));
+    for (track, _) in tracks {
+        let expected = match track.media_source_kind() {
+            MediaSourceKind::Device => &device[0].0,
+            MediaSourceKind::Display => &display[0].0,
+        };
+        assert_eq!(track.id(), expected.id());
+    }
+
+    assert_eq!(mock_navigator.get_display_media_requests_count(), 1);
+    assert_eq!(mock_navigator.get_user_media_requests_count(), 1);
+    mock_navigator.stop();
+}
+
 /// Check that error is thrown if stream obtained via gUM request contains ended
 /// track.
 #[wasm_bindgen_test]
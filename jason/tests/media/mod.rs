@@ -32,7 +32,10 @@ async fn sendrecv_works() {
             receivers: vec![MemberId::from("bob")],
             mid: None,
         },
-        media_type: MediaType::Audio(AudioSettings { required: false }),
+        media_type: MediaType::Audio(AudioSettings {
+            required: false,
+            opus: None,
+        }),
     };
     let recv_audio_track = Track {
         id: TrackId(2),
@@ -40,7 +43,10 @@ async fn sendrecv_works() {
             mid: None,
             sender: MemberId::from("alice"),
         },
-        media_type: MediaType::Audio(AudioSettings { required: false }),
+        media_type: MediaType::Audio(AudioSettings {
+            required: false,
+            opus: None,
+        }),
     };
     media_connections
         .create_tracks(
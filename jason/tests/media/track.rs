@@ -7,8 +7,12 @@ use futures::{
     StreamExt as _,
 };
 use medea_jason::media::{
-    track::remote, DeviceVideoTrackConstraints, MediaManager,
-    MediaStreamSettings,
+    track::{
+        local::{ApplyAdvancedError, LocalMediaTrack},
+        remote,
+    },
+    AudioTrackConstraints, DeviceVideoTrackConstraints, FocusMode,
+    MediaKind, MediaManager, MediaStreamSettings,
 };
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen_test::*;
@@ -36,6 +40,36 @@ async fn track_autostop() {
     assert_eq!(Weak::strong_count(&weak_track), 0);
 }
 
+/// Assert that advanced constraints are applied to device video tracks only,
+/// ignoring the ones unsupported by the device.
+#[wasm_bindgen_test]
+async fn apply_advanced_to_device_video_only() {
+    let media_manager = MediaManager::default();
+    let mut caps = MediaStreamSettings::new();
+    caps.audio(AudioTrackConstraints::new());
+    caps.device_video(DeviceVideoTrackConstraints::new());
+    let tracks = media_manager.get_tracks(caps).await.unwrap();
+    assert_eq!(tracks.len(), 2);
+
+    let mut advanced = DeviceVideoTrackConstraints::new();
+    advanced.torch(true);
+    advanced.ideal_zoom(200);
+    advanced.exact_focus_mode(FocusMode::Continuous);
+
+    for (track, _) in tracks {
+        let track = LocalMediaTrack::new(track);
+        let res = track.apply_advanced(advanced.clone()).await;
+        if track.kind() == MediaKind::Video {
+            res.unwrap();
+        } else {
+            assert!(matches!(
+                res.unwrap_err().into_inner(),
+                ApplyAdvancedError::NotDeviceVideo,
+            ));
+        }
+    }
+}
+
 #[wasm_bindgen_test]
 async fn on_track_enabled_works() {
     let api_track = get_audio_track().await;
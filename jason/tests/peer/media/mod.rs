@@ -7,7 +7,10 @@ use std::{convert::TryFrom, mem, rc::Rc};
 use futures::channel::mpsc;
 use medea_client_api_proto::{TrackId, TrackPatchEvent};
 use medea_jason::{
-    media::{LocalTracksConstraints, MediaManager, RecvConstraints},
+    media::{
+        LocalTracksConstraints, MediaManager, MediaStreamSettings,
+        RecvConstraints,
+    },
     peer::{
         media_exchange_state, LocalStreamUpdateCriteria, MediaConnections,
         MediaStateControllable, SimpleTracksRequest,
@@ -26,6 +29,19 @@ wasm_bindgen_test_configure!(run_in_browser);
 async fn get_test_media_connections(
     enabled_audio: bool,
     enabled_video: bool,
+) -> (MediaConnections, TrackId, TrackId) {
+    get_test_media_connections_with_settings(
+        get_media_stream_settings(enabled_audio, enabled_video),
+        enabled_audio,
+        enabled_video,
+    )
+    .await
+}
+
+async fn get_test_media_connections_with_settings(
+    settings: MediaStreamSettings,
+    enabled_audio: bool,
+    enabled_video: bool,
 ) -> (MediaConnections, TrackId, TrackId) {
     let (tx, rx) = mpsc::unbounded();
     mem::forget(rx);
@@ -39,7 +55,7 @@ async fn get_test_media_connections(
     media_connections
         .create_tracks(
             vec![audio_track, video_track],
-            &get_media_stream_settings(enabled_audio, enabled_video).into(),
+            &settings.into(),
             &RecvConstraints::default(),
         )
         .unwrap();
@@ -154,8 +170,9 @@ async fn new_media_connections_with_disabled_video_tracks() {
 ///
 /// This tests checks that [`TrackPatch`] works as expected.
 mod sender_patch {
-    use medea_client_api_proto::{AudioSettings, MediaType};
+    use medea_client_api_proto::{self as proto, AudioSettings, MediaType};
     use medea_jason::{
+        media::{ContentHint, DegradationPreference},
         peer::{sender, MediaExchangeState},
         utils::{AsProtoState, SynchronizableState},
     };
@@ -172,7 +189,10 @@ mod sender_patch {
         let sender = media_connections
             .create_sender(
                 TrackId(0),
-                MediaType::Audio(AudioSettings { required: false }),
+                MediaType::Audio(AudioSettings {
+                    required: false,
+                    opus: None,
+                }),
                 None,
                 vec!["bob".into()],
                 &LocalTracksConstraints::default(),
@@ -190,6 +210,8 @@ mod sender_patch {
             enabled_individual: Some(false),
             enabled_general: Some(false),
             muted: None,
+            content_hint: None,
+            degradation_preference: None,
         });
         sender.state().when_updated().await;
 
@@ -204,6 +226,8 @@ mod sender_patch {
             enabled_individual: Some(false),
             enabled_general: Some(false),
             muted: None,
+            content_hint: None,
+            degradation_preference: None,
         });
         sender.state().when_updated().await;
 
@@ -218,6 +242,8 @@ mod sender_patch {
             enabled_individual: Some(true),
             enabled_general: Some(true),
             muted: None,
+            content_hint: None,
+            degradation_preference: None,
         });
         sender.state().when_updated().await;
 
@@ -232,6 +258,8 @@ mod sender_patch {
             enabled_individual: Some(false),
             enabled_general: Some(false),
             muted: None,
+            content_hint: None,
+            degradation_preference: None,
         });
         sender.state().when_updated().await;
         assert!(sender.general_disabled());
@@ -241,6 +269,8 @@ mod sender_patch {
             enabled_individual: Some(false),
             enabled_general: Some(false),
             muted: None,
+            content_hint: None,
+            degradation_preference: None,
         });
         sender.state().when_updated().await;
 
@@ -255,12 +285,33 @@ mod sender_patch {
             enabled_individual: None,
             enabled_general: None,
             muted: None,
+            content_hint: None,
+            degradation_preference: None,
         });
         sender.state().when_updated().await;
 
         assert!(!sender.general_disabled());
     }
 
+    #[wasm_bindgen_test]
+    async fn video_encoding_patch() {
+        let (sender, track_id, _media_connections) = get_sender().await;
+        assert_eq!(sender.state().content_hint(), None);
+
+        let mut patch = TrackPatchEvent::new(track_id);
+        patch.content_hint = Some(proto::ContentHint::Text);
+        patch.degradation_preference =
+            Some(proto::DegradationPreference::MaintainResolution);
+        sender.state().update(&patch);
+
+        assert_eq!(sender.state().content_hint(), Some(ContentHint::Text));
+        assert_eq!(
+            sender.state().degradation_preference(),
+            Some(DegradationPreference::MaintainResolution),
+        );
+        assert!(!sender.general_disabled());
+    }
+
     /// Checks that [`Sender`]'s mute and media exchange states can be changed
     /// by [`SenderState`] update.
     #[wasm_bindgen_test]
@@ -285,6 +336,107 @@ mod sender_patch {
     }
 }
 
+/// Checks that [`MuteStrategy`]s are applied to [`Sender`]s on muting.
+mod mute_strategy {
+    use medea_jason::{
+        media::{
+            AudioTrackConstraints, MediaKind, MediaSourceKind, MuteStrategy,
+        },
+        platform,
+    };
+
+    use super::*;
+
+    async fn get_muted_audio_sender(
+        strategy: MuteStrategy,
+    ) -> (MediaConnections, TrackId) {
+        let mut settings = get_media_stream_settings(true, true);
+        let mut audio = AudioTrackConstraints::new();
+        audio.mute_strategy(strategy);
+        settings.audio(audio);
+        let (media_connections, audio_track_id, _) =
+            get_test_media_connections_with_settings(settings, true, true)
+                .await;
+
+        let mut patch = TrackPatchEvent::new(audio_track_id);
+        patch.muted = Some(true);
+        let state = media_connections
+            .get_sender_state_by_id(audio_track_id)
+            .unwrap();
+        state.update(&patch);
+        state.when_updated().await;
+
+        (media_connections, audio_track_id)
+    }
+
+    #[wasm_bindgen_test]
+    async fn disable_track() {
+        let (media_connections, audio_track_id) =
+            get_muted_audio_sender(MuteStrategy::DisableTrack).await;
+        let sender =
+            media_connections.get_sender_by_id(audio_track_id).unwrap();
+
+        assert!(sender.muted());
+        assert!(sender.has_track());
+        let track = sender.transceiver().send_track().unwrap();
+        let track: &platform::MediaStreamTrack = (*track).as_ref();
+        assert!(!track.enabled());
+    }
+
+    #[wasm_bindgen_test]
+    async fn replace_with_silence() {
+        let (media_connections, audio_track_id) =
+            get_muted_audio_sender(MuteStrategy::ReplaceWithSilence).await;
+        let sender =
+            media_connections.get_sender_by_id(audio_track_id).unwrap();
+
+        assert!(sender.muted());
+        assert!(!sender.has_track());
+        assert!(!sender.is_capture_stopped());
+
+        let mut patch = TrackPatchEvent::new(audio_track_id);
+        patch.muted = Some(false);
+        let state = media_connections
+            .get_sender_state_by_id(audio_track_id)
+            .unwrap();
+        state.update(&patch);
+        state.when_updated().await;
+
+        assert!(!sender.muted());
+        assert!(sender.has_track());
+        let track = sender.transceiver().send_track().unwrap();
+        let track: &platform::MediaStreamTrack = (*track).as_ref();
+        assert!(track.enabled());
+    }
+
+    #[wasm_bindgen_test]
+    async fn stop_capture() {
+        let (media_connections, audio_track_id) =
+            get_muted_audio_sender(MuteStrategy::StopCapture).await;
+        let sender =
+            media_connections.get_sender_by_id(audio_track_id).unwrap();
+
+        assert!(sender.muted());
+        assert!(!sender.has_track());
+        assert!(sender.is_capture_stopped());
+
+        let mut criteria = LocalStreamUpdateCriteria::empty();
+        criteria.add(MediaKind::Audio, MediaSourceKind::Device);
+        assert!(media_connections.get_tracks_request(criteria).is_none());
+
+        let mut patch = TrackPatchEvent::new(audio_track_id);
+        patch.muted = Some(false);
+        let state = media_connections
+            .get_sender_state_by_id(audio_track_id)
+            .unwrap();
+        state.update(&patch);
+        state.when_updated().await;
+
+        assert!(!sender.is_capture_stopped());
+        assert!(state.is_local_stream_update_needed());
+    }
+}
+
 mod receiver_patch {
     use medea_client_api_proto::{AudioSettings, MediaType, MemberId};
     use medea_jason::{
@@ -308,7 +460,11 @@ mod receiver_patch {
         );
         let recv = media_connections.create_receiver(
             TRACK_ID,
-            MediaType::Audio(AudioSettings { required: true }).into(),
+            MediaType::Audio(AudioSettings {
+                required: true,
+                opus: None,
+            })
+            .into(),
             Some(MID.to_string()),
             MemberId(SENDER_ID.to_string()),
             &RecvConstraints::default(),
@@ -325,6 +481,8 @@ mod receiver_patch {
             enabled_individual: Some(false),
             enabled_general: Some(false),
             muted: None,
+            content_hint: None,
+            degradation_preference: None,
         });
         receiver.state().when_updated().await;
 
@@ -339,6 +497,8 @@ mod receiver_patch {
             enabled_individual: Some(false),
             enabled_general: Some(false),
             muted: None,
+            content_hint: None,
+            degradation_preference: None,
         });
         receiver.state().when_updated().await;
 
@@ -353,6 +513,8 @@ mod receiver_patch {
             enabled_individual: Some(true),
             enabled_general: Some(true),
             muted: None,
+            content_hint: None,
+            degradation_preference: None,
         });
         receiver.state().when_updated().await;
 
@@ -367,6 +529,8 @@ mod receiver_patch {
             enabled_individual: Some(false),
             enabled_general: Some(false),
             muted: None,
+            content_hint: None,
+            degradation_preference: None,
         });
         receiver.state().when_updated().await;
         assert!(!receiver.enabled_general());
@@ -376,6 +540,8 @@ mod receiver_patch {
             enabled_individual: Some(false),
             enabled_general: Some(false),
             muted: None,
+            content_hint: None,
+            degradation_preference: None,
         });
         receiver.state().when_updated().await;
 
@@ -390,6 +556,8 @@ mod receiver_patch {
             enabled_individual: None,
             enabled_general: None,
             muted: None,
+            content_hint: None,
+            degradation_preference: None,
         });
         receiver.state().when_updated().await;
 
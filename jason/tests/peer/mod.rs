@@ -11,9 +11,11 @@ use futures::{
 };
 use medea_client_api_proto::{
     stats::{
-        HighResTimeStamp, KnownIceCandidatePairState, NonExhaustive,
-        RtcInboundRtpStreamMediaType, RtcOutboundRtpStreamMediaType, RtcStat,
-        RtcStatsType, StatId, TrackStats, TrackStatsKind,
+        HighResTimeStamp, KnownCandidateType, KnownIceCandidatePairState,
+        KnownProtocol, NonExhaustive, RtcIceCandidatePairStats,
+        RtcIceCandidateStats, RtcInboundRtpStreamMediaType,
+        RtcOutboundRtpStreamMediaType, RtcStat, RtcStatsType, StatId,
+        TrackStats, TrackStatsKind,
     },
     AudioSettings, Direction, IceConnectionState, MediaSourceKind, MediaType,
     MemberId, NegotiationRole, PeerId, Track, TrackId, TrackPatchEvent,
@@ -24,7 +26,7 @@ use medea_jason::{
     media::{LocalTracksConstraints, MediaKind, MediaManager, RecvConstraints},
     peer::{
         self, media_exchange_state, MediaStateControllable, PeerEvent,
-        TrackDirection,
+        SelectedCandidatePair, TrackDirection,
     },
     platform::RtcStats,
     utils::Updatable,
@@ -46,6 +48,8 @@ fn toggle_disable_track_update(id: TrackId, enabled: bool) -> TrackPatchEvent {
         enabled_individual: Some(enabled),
         enabled_general: Some(enabled),
         muted: None,
+        content_hint: None,
+        degradation_preference: None,
     }
 }
 
@@ -719,7 +723,10 @@ impl InterconnectedPeers {
                     receivers: vec![MemberId::from("bob")],
                     mid: None,
                 },
-                media_type: MediaType::Audio(AudioSettings { required: true }),
+                media_type: MediaType::Audio(AudioSettings {
+                    required: true,
+                    opus: None,
+                }),
             },
             Track {
                 id: TrackId(2),
@@ -744,7 +751,10 @@ impl InterconnectedPeers {
                     sender: MemberId::from("alice"),
                     mid: None,
                 },
-                media_type: MediaType::Audio(AudioSettings { required: true }),
+                media_type: MediaType::Audio(AudioSettings {
+                    required: true,
+                    opus: None,
+                }),
             },
             Track {
                 id: TrackId(2),
@@ -993,6 +1003,124 @@ mod peer_stats_caching {
     }
 }
 
+mod selected_candidate_pair {
+    use super::*;
+
+    /// Returns [`RtcStat`]s of the nominated ICE candidate pair with the
+    /// provided local candidate type.
+    fn pair_stats(local_type: KnownCandidateType) -> Vec<RtcStat> {
+        let candidate = |candidate_type, relay_protocol| {
+            Box::new(RtcIceCandidateStats {
+                transport_id: None,
+                address: None,
+                port: 9,
+                protocol: NonExhaustive::Known(KnownProtocol::Udp),
+                candidate_type: NonExhaustive::Known(candidate_type),
+                priority: 0,
+                url: None,
+                relay_protocol,
+            })
+        };
+        let local_id = format!("local-{:?}", local_type);
+        vec![
+            RtcStat {
+                id: StatId("pair".to_string()),
+                timestamp: HighResTimeStamp(1584373509700.0),
+                stats: RtcStatsType::CandidatePair(Box::new(
+                    RtcIceCandidatePairStats {
+                        state: NonExhaustive::Known(
+                            KnownIceCandidatePairState::Succeeded,
+                        ),
+                        local_candidate_id: Some(StatId(local_id.clone())),
+                        remote_candidate_id: Some(StatId("remote".to_string())),
+                        nominated: true,
+                        bytes_sent: 0,
+                        bytes_received: 0,
+                        total_round_trip_time: None,
+                        current_round_trip_time: None,
+                        available_outgoing_bitrate: None,
+                    },
+                )),
+            },
+            RtcStat {
+                id: StatId(local_id),
+                timestamp: HighResTimeStamp(1584373509700.0),
+                stats: RtcStatsType::LocalCandidate(candidate(
+                    local_type,
+                    Some(NonExhaustive::Known(KnownProtocol::Tcp)),
+                )),
+            },
+            RtcStat {
+                id: StatId("remote".to_string()),
+                timestamp: HighResTimeStamp(1584373509700.0),
+                stats: RtcStatsType::RemoteCandidate(candidate(
+                    KnownCandidateType::Host,
+                    None,
+                )),
+            },
+        ]
+    }
+
+    /// Tests that [`PeerConnection::send_peer_stats`] emits
+    /// [`PeerEvent::SelectedCandidatePairChanged`] only when the selected ICE
+    /// candidate pair changes.
+    #[wasm_bindgen_test]
+    async fn emitted_on_change_only() {
+        let (tx, peer_events_stream) = mpsc::unbounded();
+        let manager = Rc::new(MediaManager::default());
+        let peer_state = peer::State::new(PeerId(1), Vec::new(), false, None);
+        let peer = peer::Component::new(
+            peer::PeerConnection::new(
+                &peer_state,
+                tx,
+                manager,
+                LocalTracksConstraints::default(),
+                Rc::new(Connections::default()),
+                Rc::new(RecvConstraints::default()),
+            )
+            .unwrap(),
+            Rc::new(peer_state),
+        );
+
+        let mut peer_events_stream = peer_events_stream.filter_map(|event| {
+            Box::pin(async move {
+                if let PeerEvent::SelectedCandidatePairChanged {
+                    peer_id: _,
+                    pair,
+                } = event
+                {
+                    Some(pair)
+                } else {
+                    None
+                }
+            })
+        });
+
+        peer.send_peer_stats(RtcStats(pair_stats(KnownCandidateType::Host)));
+        assert_eq!(
+            peer_events_stream.next().await.unwrap(),
+            SelectedCandidatePair {
+                local_type: NonExhaustive::Known(KnownCandidateType::Host),
+                remote_type: NonExhaustive::Known(KnownCandidateType::Host),
+                relay_protocol: None,
+            },
+        );
+
+        peer.send_peer_stats(RtcStats(pair_stats(KnownCandidateType::Host)));
+        timeout(100, peer_events_stream.next()).await.unwrap_err();
+
+        peer.send_peer_stats(RtcStats(pair_stats(KnownCandidateType::Relay)));
+        assert_eq!(
+            peer_events_stream.next().await.unwrap(),
+            SelectedCandidatePair {
+                local_type: NonExhaustive::Known(KnownCandidateType::Relay),
+                remote_type: NonExhaustive::Known(KnownCandidateType::Host),
+                relay_protocol: Some(NonExhaustive::Known(KnownProtocol::Tcp)),
+            },
+        );
+    }
+}
+
 #[wasm_bindgen_test]
 async fn reset_transition_timers() {
     let (tx, _) = mpsc::unbounded();
@@ -1152,7 +1280,10 @@ async fn new_remote_track() {
                     sender: MemberId::from("whatever"),
                     mid: Some(String::from("0")),
                 },
-                media_type: MediaType::Audio(AudioSettings { required: true }),
+                media_type: MediaType::Audio(AudioSettings {
+                    required: true,
+                    opus: None,
+                }),
             },
             LocalTracksConstraints::default(),
         );
@@ -1429,6 +1560,8 @@ async fn disable_and_enable_all_tracks() {
         enabled_general: Some(false),
         enabled_individual: Some(false),
         muted: None,
+        content_hint: None,
+        degradation_preference: None,
     });
     pc.state().when_updated().await;
     assert!(audio_track.general_disabled());
@@ -1442,6 +1575,8 @@ async fn disable_and_enable_all_tracks() {
         enabled_general: Some(false),
         enabled_individual: Some(false),
         muted: None,
+        content_hint: None,
+        degradation_preference: None,
     });
     pc.state().when_updated().await;
     assert!(audio_track.general_disabled());
@@ -1455,6 +1590,8 @@ async fn disable_and_enable_all_tracks() {
         enabled_individual: Some(true),
         enabled_general: Some(true),
         muted: None,
+        content_hint: None,
+        degradation_preference: None,
     });
     pc.state().when_updated().await;
     assert!(!audio_track.general_disabled());
@@ -1468,8 +1605,75 @@ async fn disable_and_enable_all_tracks() {
         enabled_individual: Some(true),
         enabled_general: Some(true),
         muted: None,
+        content_hint: None,
+        degradation_preference: None,
     });
     pc.state().when_updated().await;
     assert!(!audio_track.general_disabled());
     assert!(!video_track.general_disabled());
 }
+
+/// Checks that recv transceivers of a receive-only [`NegotiationRole::Offerer`]
+/// are created along with its [`PeerConnection`], before its `Receiver`s and
+/// the SDP offer are, and are taken by its `Receiver`s afterwards.
+#[wasm_bindgen_test]
+async fn precreates_recv_transceivers_of_recv_only_offerer() {
+    let (tx, _rx) = mpsc::unbounded();
+    let manager = Rc::new(MediaManager::default());
+    let peer_state = peer::State::new(
+        PeerId(1),
+        Vec::new(),
+        false,
+        Some(NegotiationRole::Offerer),
+    );
+    let send_constraints = LocalTracksConstraints::default();
+    let recv_track = |id, media_type| Track {
+        id: TrackId(id),
+        direction: Direction::Recv {
+            sender: "bob".into(),
+            mid: None,
+        },
+        media_type,
+    };
+    peer_state.insert_track(
+        &recv_track(
+            1,
+            MediaType::Audio(AudioSettings {
+                required: false,
+                opus: None,
+            }),
+        ),
+        send_constraints.clone(),
+    );
+    peer_state.insert_track(
+        &recv_track(
+            2,
+            MediaType::Video(VideoSettings {
+                required: false,
+                source_kind: MediaSourceKind::Device,
+            }),
+        ),
+        send_constraints.clone(),
+    );
+
+    let pc = peer::PeerConnection::new(
+        &peer_state,
+        tx,
+        manager,
+        send_constraints,
+        Rc::new(Connections::default()),
+        Rc::new(RecvConstraints::default()),
+    )
+    .unwrap();
+    assert_eq!(pc.precreated_transceivers_count(), 2);
+    assert!(pc.get_receiver_by_id(TrackId(1)).is_none());
+
+    let peer = peer::Component::new(pc, Rc::new(peer_state));
+    peer.state().when_all_tracks_processed().await;
+
+    assert_eq!(peer.precreated_transceivers_count(), 0);
+    for id in &[TrackId(1), TrackId(2)] {
+        let receiver = peer.get_receiver_by_id(*id).unwrap();
+        assert!(receiver.transceiver().is_some());
+    }
+}
@@ -56,6 +56,28 @@ async fn on_remote_track_added_fires() {
     wait_and_check_test_result(test_result, || {}).await;
 }
 
+#[wasm_bindgen_test]
+async fn on_remote_track_removed_fires() {
+    let cons = Connections::default();
+
+    cons.create_connection(PeerId(1), &"bob".into());
+
+    let con = cons.get(&"bob".into()).unwrap();
+    let con_handle = con.new_handle();
+    let (cb, test_result) = js_callback!(|track: api::RemoteMediaTrack| {
+        cb_assert_eq!(track.kind(), api::MediaKind::Audio);
+    });
+    con_handle
+        .on_remote_track_removed(platform::Function::from(
+            js_sys::Function::from(cb),
+        ))
+        .unwrap();
+
+    con.remove_remote_track(get_audio_track().await.into());
+
+    wait_and_check_test_result(test_result, || {}).await;
+}
+
 #[wasm_bindgen_test]
 async fn tracks_are_added_to_connection() {
     let cons = Connections::default();
@@ -5,23 +5,23 @@ use std::{cell::RefCell, rc::Rc};
 
 use futures::{
     channel::{mpsc, oneshot},
-    stream, StreamExt,
+    future, stream, StreamExt,
 };
 use medea_client_api_proto::{
     ClientMsg, CloseReason, Command, Event, ServerMsg,
 };
 use medea_jason::{
     api,
-    jason::Jason,
+    jason::{AnalyticsEventKind, Jason},
     platform::{MockRpcTransport, RpcTransport, TransportState},
     rpc::{CloseMsg, WebSocketRpcClient},
 };
 use medea_reactive::ObservableCell;
-use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{closure::Closure, JsCast as _};
 use wasm_bindgen_futures::{spawn_local, JsFuture};
 use wasm_bindgen_test::*;
 
-use crate::{rpc::RPC_SETTINGS, timeout, yield_now, TEST_ROOM_URL};
+use crate::{rpc::rpc_settings, timeout, yield_now, TEST_ROOM_URL};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
@@ -34,12 +34,13 @@ async fn only_one_strong_rpc_rc_exists() {
             transport.expect_on_message().times(3).returning_st({
                 move || {
                     Box::pin(stream::iter(vec![
-                        RPC_SETTINGS,
+                        rpc_settings(),
                         ServerMsg::Event {
                             room_id: "room_id".into(),
                             event: Event::RoomJoined {
                                 member_id: "member_id".into(),
                             },
+                            seq: None,
                         },
                     ]))
                 }
@@ -60,7 +61,7 @@ async fn only_one_strong_rpc_rc_exists() {
         .unwrap();
     room.on_connection_loss(Closure::once_into_js(|| {}).into())
         .unwrap();
-    JsFuture::from(room.join(TEST_ROOM_URL.to_string()))
+    JsFuture::from(room.join(TEST_ROOM_URL.to_string(), None))
         .await
         .unwrap();
 
@@ -69,6 +70,63 @@ async fn only_one_strong_rpc_rc_exists() {
     assert_eq!(Rc::strong_count(&ws), 1);
 }
 
+/// Checks that [`AnalyticsEvent`]s of joining a `Room` are delivered to the
+/// sink set via [`Jason::set_analytics_sink`].
+#[wasm_bindgen_test]
+async fn emits_join_analytics_events() {
+    let ws = Rc::new(WebSocketRpcClient::new(Box::new(move |_| {
+        Box::pin(async move {
+            let mut transport = MockRpcTransport::new();
+            transport.expect_on_message().times(3).returning_st({
+                move || {
+                    Box::pin(stream::iter(vec![
+                        rpc_settings(),
+                        ServerMsg::Event {
+                            room_id: "room_id".into(),
+                            event: Event::RoomJoined {
+                                member_id: "member_id".into(),
+                            },
+                            seq: None,
+                        },
+                    ]))
+                }
+            });
+            transport.expect_send().returning(|_| Ok(()));
+            transport.expect_set_close_reason().return_once(drop);
+            transport.expect_on_state_change().return_once_st(move || {
+                Box::pin(stream::once(async { TransportState::Open }))
+            });
+            let transport = Rc::new(transport);
+            Ok(transport as Rc<dyn RpcTransport>)
+        })
+    })));
+    let jason = Jason::with_rpc_client(ws);
+    let events = Rc::new(RefCell::new(Vec::new()));
+    jason.set_analytics_sink({
+        let events = Rc::clone(&events);
+        move |event| events.borrow_mut().push(event)
+    });
+    let jason = api::Jason::from(jason);
+
+    let room = jason.init_room();
+    room.on_failed_local_media(Closure::once_into_js(|| {}).into())
+        .unwrap();
+    room.on_connection_loss(Closure::once_into_js(|| {}).into())
+        .unwrap();
+    JsFuture::from(room.join(TEST_ROOM_URL.to_string(), None))
+        .await
+        .unwrap();
+
+    let kinds: Vec<_> = events.borrow().iter().map(|e| e.kind()).collect();
+    assert_eq!(
+        kinds,
+        [AnalyticsEventKind::JoinStarted, AnalyticsEventKind::JoinSucceeded],
+    );
+    assert!(events.borrow()[1].duration().is_some());
+
+    jason.dispose();
+}
+
 /// Checks that [`RpcClient`] was dropped on [`JasonHandle::dispose`] call.
 #[wasm_bindgen_test]
 async fn rpc_dropped_on_jason_dispose() {
@@ -80,12 +138,13 @@ async fn rpc_dropped_on_jason_dispose() {
             transport.expect_on_message().times(3).returning_st({
                 move || {
                     Box::pin(stream::iter(vec![
-                        RPC_SETTINGS,
+                        rpc_settings(),
                         ServerMsg::Event {
                             room_id: "room_id".into(),
                             event: Event::RoomJoined {
                                 member_id: "member_id".into(),
                             },
+                            seq: None,
                         },
                     ]))
                 }
@@ -110,7 +169,7 @@ async fn rpc_dropped_on_jason_dispose() {
         .unwrap();
     room.on_connection_loss(Closure::once_into_js(|| {}).into())
         .unwrap();
-    JsFuture::from(room.join(TEST_ROOM_URL.to_string()))
+    JsFuture::from(room.join(TEST_ROOM_URL.to_string(), None))
         .await
         .unwrap();
     jason.dispose();
@@ -136,7 +195,7 @@ async fn room_dispose_works() {
                 transport.expect_on_message().returning_st({
                     move || {
                         let (tx, rx) = mpsc::unbounded();
-                        tx.unbounded_send(RPC_SETTINGS).unwrap();
+                        tx.unbounded_send(rpc_settings()).unwrap();
                         client_msg_txs.borrow_mut().push(tx);
                         Box::pin(rx)
                     }
@@ -175,12 +234,13 @@ async fn room_dispose_works() {
                     event: Event::RoomJoined {
                         member_id: "member_id".into(),
                     },
+                    seq: None,
                 })
                 .ok();
             });
         }
     });
-    JsFuture::from(room.join(TEST_ROOM_URL.to_string()))
+    JsFuture::from(room.join(TEST_ROOM_URL.to_string(), None))
         .await
         .unwrap();
 
@@ -201,6 +261,7 @@ async fn room_dispose_works() {
                     event: Event::RoomJoined {
                         member_id: "member_id".into(),
                     },
+                    seq: None,
                 })
                 .ok();
             });
@@ -208,6 +269,7 @@ async fn room_dispose_works() {
     });
     JsFuture::from(another_room.join(
         "ws://example.com/another_room_id/member_id?token=token".to_string(),
+        None,
     ))
     .await
     .unwrap();
@@ -218,7 +280,8 @@ async fn room_dispose_works() {
             room_id: _,
             command: Command::JoinRoom {
                 member_id: _,
-                credential: _
+                credential: _,
+                capabilities: _,
             }
         }
     ));
@@ -228,7 +291,8 @@ async fn room_dispose_works() {
             room_id: _,
             command: Command::JoinRoom {
                 member_id: _,
-                credential: _
+                credential: _,
+                capabilities: _,
             }
         }
     ));
@@ -271,12 +335,13 @@ async fn room_closes_on_rpc_transport_close() {
                 transport.expect_on_message().times(3).returning_st({
                     move || {
                         Box::pin(stream::iter(vec![
-                            RPC_SETTINGS,
+                            rpc_settings(),
                             ServerMsg::Event {
                                 room_id: "room_id".into(),
                                 event: Event::RoomJoined {
                                     member_id: "member_id".into(),
                                 },
+                                seq: None,
                             },
                         ]))
                     }
@@ -298,7 +363,7 @@ async fn room_closes_on_rpc_transport_close() {
         .unwrap();
     room.on_connection_loss(Closure::once_into_js(|| {}).into())
         .unwrap();
-    JsFuture::from(room.join(TEST_ROOM_URL.to_string()))
+    JsFuture::from(room.join(TEST_ROOM_URL.to_string(), None))
         .await
         .unwrap();
 
@@ -315,3 +380,74 @@ async fn room_closes_on_rpc_transport_close() {
 
     timeout(300, test_rx).await.unwrap().unwrap();
 }
+
+/// Checks that [`Jason::close_room`] closes a [`Room`] deterministically even
+/// while its [`RoomHandle::join`] is still in progress.
+#[wasm_bindgen_test]
+async fn close_room_rejects_pending_join() {
+    let ws = Rc::new(WebSocketRpcClient::new(Box::new(|_| {
+        Box::pin(future::pending())
+    })));
+    let jason = api::Jason::from(Jason::with_rpc_client(ws));
+
+    let room = jason.init_room();
+    room.on_failed_local_media(Closure::once_into_js(|| {}).into())
+        .unwrap();
+    room.on_connection_loss(Closure::once_into_js(|| {}).into())
+        .unwrap();
+    let (test_tx, test_rx) = oneshot::channel();
+    room.on_close(
+        Closure::once_into_js(move || {
+            test_tx.send(()).unwrap();
+        })
+        .into(),
+    )
+    .unwrap();
+
+    let join = JsFuture::from(room.join(TEST_ROOM_URL.to_string(), None));
+    yield_now().await;
+    jason.close_room(room);
+
+    timeout(100, test_rx).await.unwrap().unwrap();
+    assert!(timeout(100, join).await.unwrap().is_err());
+
+    jason.dispose();
+}
+
+/// Checks that repeatedly initialized and closed [`Room`]s don't leak wasm
+/// memory.
+#[wasm_bindgen_test]
+async fn closed_rooms_do_not_leak_memory() {
+    /// Returns current size of the wasm memory in bytes.
+    fn memory_size() -> u32 {
+        wasm_bindgen::memory()
+            .unchecked_into::<js_sys::WebAssembly::Memory>()
+            .buffer()
+            .unchecked_into::<js_sys::ArrayBuffer>()
+            .byte_length()
+    }
+
+    let ws = Rc::new(WebSocketRpcClient::new(Box::new(|_| {
+        Box::pin(future::pending())
+    })));
+    let jason = api::Jason::from(Jason::with_rpc_client(ws));
+    let churn = |count| {
+        for _ in 0..count {
+            let room = jason.init_room();
+            room.on_close(Closure::once_into_js(|| {}).into()).unwrap();
+            jason.close_room(room);
+        }
+    };
+
+    churn(100);
+    yield_now().await;
+    let warmed_up = memory_size();
+
+    churn(1000);
+    yield_now().await;
+
+    // Allows a single wasm page of allocator slack.
+    assert!(memory_size().saturating_sub(warmed_up) <= 65536);
+
+    jason.dispose();
+}
@@ -17,8 +17,12 @@ use medea_client_api_proto::{
     VideoSettings,
 };
 use medea_jason::{
-    api, media::MediaKind, peer::PeerConnection, room::Room,
-    rpc::MockRpcSession, utils::Updatable,
+    api,
+    media::MediaKind,
+    peer::PeerConnection,
+    room::{effects::EffectKind, Room},
+    rpc::MockRpcSession,
+    utils::{AsProtoState as _, Updatable},
 };
 use wasm_bindgen_futures::{spawn_local, JsFuture};
 use wasm_bindgen_test::*;
@@ -51,7 +55,7 @@ fn get_test_room(
         let _ = tx.unbounded_send(command);
     });
 
-    (Room::new(Rc::new(rpc), Rc::default()), rx)
+    (Room::new(Rc::new(rpc), Rc::default(), Rc::default()), rx)
 }
 
 async fn get_test_room_and_exist_peer(
@@ -98,13 +102,14 @@ async fn get_test_room_and_exist_peer(
         }
     });
 
-    let room = Room::new(Rc::new(rpc), Rc::default());
+    let room = Room::new(Rc::new(rpc), Rc::default(), Rc::default());
     if let Some(media_stream_settings) = &media_stream_settings {
         JsFuture::from(
             api::RoomHandle::from(room.new_handle()).set_local_media_settings(
                 &media_stream_settings,
                 false,
                 false,
+                None,
             ),
         )
         .await
@@ -135,6 +140,7 @@ async fn error_get_local_stream_on_new_peer() {
         &media_stream_settings(true, true),
         false,
         false,
+        None,
     ))
     .await
     .unwrap();
@@ -182,11 +188,11 @@ async fn error_join_room_without_on_failed_stream_callback() {
     let room_handle = api::RoomHandle::from(room.new_handle());
 
     room_handle
-        .on_connection_loss(js_sys::Function::new_no_args(""))
+        .on_connection_loss(js_sys::Function::new_no_args("").into())
         .unwrap();
 
     let err = get_jason_error(
-        JsFuture::from(room_handle.join(String::from(TEST_ROOM_URL)))
+        JsFuture::from(room_handle.join(String::from(TEST_ROOM_URL), None))
             .await
             .unwrap_err(),
     );
@@ -212,11 +218,11 @@ async fn error_join_room_without_on_connection_loss_callback() {
     let room_handle = api::RoomHandle::from(room.new_handle());
 
     room_handle
-        .on_failed_local_media(js_sys::Function::new_no_args(""))
+        .on_failed_local_media(js_sys::Function::new_no_args("").into())
         .unwrap();
 
     let err = get_jason_error(
-        JsFuture::from(room_handle.join(String::from(TEST_ROOM_URL)))
+        JsFuture::from(room_handle.join(String::from(TEST_ROOM_URL), None))
             .await
             .unwrap_err(),
     );
@@ -229,6 +235,55 @@ async fn error_join_room_without_on_connection_loss_callback() {
     assert!(!err.trace().is_empty());
 }
 
+/// Tests `Room::join` being aborted via the provided `AbortSignal`.
+/// Setup:
+///     1. Create Room with `RpcSession` never connecting.
+///     2. Try join to Room.
+///     3. Abort join.
+/// Assertions:
+///     1. Room::join fails with `AbortError`.
+///     2. Connection establishment is canceled.
+#[wasm_bindgen_test]
+async fn aborted_join_cancels_connection() {
+    let (cancel_tx, mut cancel_rx) = mpsc::unbounded();
+    let mut rpc = MockRpcSession::new();
+    rpc.expect_subscribe()
+        .return_once(|| stream::pending().boxed_local());
+    rpc.expect_close_with_reason().return_const(());
+    rpc.expect_on_connection_loss()
+        .return_once(|| stream::pending().boxed_local());
+    rpc.expect_on_reconnected()
+        .return_once(|| stream::pending().boxed_local());
+    rpc.expect_connect()
+        .return_once(|_| Box::pin(future::pending()));
+    rpc.expect_cancel_connect().return_once(move || {
+        let _ = cancel_tx.unbounded_send(());
+    });
+    let room = Room::new(Rc::new(rpc), Rc::default(), Rc::default());
+    let room_handle = api::RoomHandle::from(room.new_handle());
+    room_handle
+        .on_failed_local_media(js_sys::Function::new_no_args("").into())
+        .unwrap();
+    room_handle
+        .on_connection_loss(js_sys::Function::new_no_args("").into())
+        .unwrap();
+
+    let controller = web_sys::AbortController::new().unwrap();
+    let join = JsFuture::from(
+        room_handle
+            .join(String::from(TEST_ROOM_URL), Some(controller.signal())),
+    );
+    delay_for(100).await;
+    assert!(cancel_rx.try_next().is_err());
+
+    controller.abort();
+    let err = web_sys::DomException::from(
+        timeout(100, join).await.unwrap().unwrap_err(),
+    );
+    assert_eq!(err.name(), "AbortError");
+    timeout(100, cancel_rx.next()).await.unwrap().unwrap();
+}
+
 mod disable_recv_tracks {
     use medea_client_api_proto::{
         AudioSettings, Direction, MediaSourceKind, MediaType, MemberId,
@@ -260,6 +315,7 @@ mod disable_recv_tracks {
                         },
                         media_type: MediaType::Audio(AudioSettings {
                             required: true,
+                            opus: None,
                         }),
                     },
                     Track {
@@ -281,6 +337,7 @@ mod disable_recv_tracks {
                         },
                         media_type: MediaType::Audio(AudioSettings {
                             required: true,
+                            opus: None,
                         }),
                     },
                 ],
@@ -376,7 +433,10 @@ mod disable_send_tracks {
                 receivers: vec![MemberId::from("bob")],
                 mid: None,
             },
-            media_type: MediaType::Audio(AudioSettings { required }),
+            media_type: MediaType::Audio(AudioSettings {
+                required,
+                opus: None,
+            }),
         }
     }
 
@@ -712,6 +772,7 @@ mod disable_send_tracks {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -754,6 +815,8 @@ mod disable_send_tracks {
                     enabled_individual: Some(false),
                     enabled_general: Some(false),
                     muted: None,
+                    content_hint: None,
+                    degradation_preference: None,
                 })],
                 negotiation_role: None,
             })
@@ -792,6 +855,7 @@ mod disable_send_tracks {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -834,6 +898,8 @@ mod disable_send_tracks {
                     enabled_individual: None,
                     enabled_general: None,
                     muted: Some(true),
+                    content_hint: None,
+                    degradation_preference: None,
                 })],
                 negotiation_role: None,
             })
@@ -873,6 +939,7 @@ mod disable_send_tracks {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -917,6 +984,8 @@ mod disable_send_tracks {
                     enabled_individual: Some(false),
                     enabled_general: Some(false),
                     muted: None,
+                    content_hint: None,
+                    degradation_preference: None,
                 })],
                 negotiation_role: None,
             })
@@ -948,7 +1017,11 @@ mod disable_send_tracks {
 
 /// Tests for `RoomHandle.on_close` JS side callback.
 mod on_close_callback {
-    use medea_client_api_proto::CloseReason as CloseByServerReason;
+    use std::time::Duration;
+
+    use medea_client_api_proto::{
+        CloseDescription, CloseReason as CloseByServerReason,
+    };
     use medea_jason::rpc::{ClientDisconnect, CloseReason};
     use wasm_bindgen::{prelude::*, JsValue};
     use wasm_bindgen_test::*;
@@ -971,6 +1044,21 @@ mod on_close_callback {
     extern "C" {
         fn get_is_err(reason: &JsValue) -> bool;
     }
+    #[wasm_bindgen(inline_js = "export function get_is_retryable(reason) { \
+                                return reason.is_retryable(); }")]
+    extern "C" {
+        fn get_is_retryable(reason: &JsValue) -> bool;
+    }
+    #[wasm_bindgen(inline_js = "export function get_retry_after(reason) { \
+                                return reason.retry_after(); }")]
+    extern "C" {
+        fn get_retry_after(reason: &JsValue) -> Option<u32>;
+    }
+    #[wasm_bindgen(inline_js = "export function get_kind(reason) { return \
+                                reason.kind(); }")]
+    extern "C" {
+        fn get_kind(reason: &JsValue) -> u32;
+    }
 
     /// Tests that JS side [`RoomHandle::on_close`] works.
     ///
@@ -988,12 +1076,39 @@ mod on_close_callback {
 
         let (cb, test_result) = js_callback!(|closed: JsValue| {
             cb_assert_eq!(get_reason(&closed), "Finished");
+            cb_assert_eq!(
+                get_kind(&closed),
+                api::RoomCloseKind::Finished as u32
+            );
             cb_assert_eq!(get_is_closed_by_server(&closed), true);
             cb_assert_eq!(get_is_err(&closed), false);
         });
         room_handle.on_close(cb.into()).unwrap();
 
-        room.close(CloseReason::ByServer(CloseByServerReason::Finished));
+        room.close(CloseReason::ByServer(CloseDescription::new(
+            CloseByServerReason::Finished,
+        )));
+        wait_and_check_test_result(test_result, || {}).await;
+    }
+
+    /// Tests that JS side [`RoomHandle::on_close`] receives retryability info
+    /// from the server's [`CloseDescription`].
+    #[wasm_bindgen_test]
+    async fn closed_by_server_with_retry_hint() {
+        let (room, _) = get_test_room(stream::pending().boxed());
+        let room_handle = api::RoomHandle::from(room.new_handle());
+
+        let (cb, test_result) = js_callback!(|closed: JsValue| {
+            cb_assert_eq!(get_reason(&closed), "Overloaded");
+            cb_assert_eq!(get_is_retryable(&closed), true);
+            cb_assert_eq!(get_retry_after(&closed), Some(1500));
+        });
+        room_handle.on_close(cb.into()).unwrap();
+
+        room.close(CloseReason::ByServer(
+            CloseDescription::new(CloseByServerReason::Overloaded)
+                .with_retry_after(Duration::from_millis(1500)),
+        ));
         wait_and_check_test_result(test_result, || {}).await;
     }
 
@@ -1016,6 +1131,10 @@ mod on_close_callback {
 
         let (cb, test_result) = js_callback!(|closed: JsValue| {
             cb_assert_eq!(get_reason(&closed), "RoomUnexpectedlyDropped");
+            cb_assert_eq!(
+                get_kind(&closed),
+                api::RoomCloseKind::RoomUnexpectedlyDropped as u32
+            );
             cb_assert_eq!(get_is_err(&closed), true);
             cb_assert_eq!(get_is_closed_by_server(&closed), false);
         });
@@ -1080,7 +1199,7 @@ mod rpc_close_reason_on_room_drop {
         rpc.expect_close_with_reason().return_once(move |reason| {
             test_tx.send(reason).unwrap();
         });
-        let room = Room::new(Rc::new(rpc), Rc::default());
+        let room = Room::new(Rc::new(rpc), Rc::default(), Rc::default());
         (room, test_rx)
     }
 
@@ -1157,7 +1276,10 @@ mod patches_generation {
     fn audio_and_device_video_tracks_content() -> Vec<(MediaType, Direction)> {
         vec![
             (
-                MediaType::Audio(AudioSettings { required: false }),
+                MediaType::Audio(AudioSettings {
+                    required: false,
+                    opus: None,
+                }),
                 Direction::Send {
                     receivers: Vec::new(),
                     mid: None,
@@ -1202,7 +1324,7 @@ mod patches_generation {
         rpc.expect_on_reconnected()
             .return_once(|| stream::pending().boxed_local());
 
-        let room = Room::new(Rc::new(rpc), Rc::default());
+        let room = Room::new(Rc::new(rpc), Rc::default(), Rc::default());
 
         for i in 0..peers_count {
             let mut audio_track_id = None;
@@ -1248,6 +1370,8 @@ mod patches_generation {
                                 state,
                                 MediaState::Mute(mute_state::Stable::Muted)
                             )),
+                            content_hint: None,
+                            degradation_preference: None,
                         })],
                         negotiation_role: None,
                     })
@@ -1679,6 +1803,45 @@ async fn remote_disable_enable_video() {
     assert!(peer.is_recv_video_enabled());
 }
 
+/// Tests that disabling and enabling of remote media from a `Member` works.
+#[wasm_bindgen_test]
+async fn remote_disable_enable_media_from_member() {
+    let (audio_track, video_track) = get_test_recv_tracks();
+    let (room, peer, _, _) = get_test_room_and_exist_peer(
+        vec![audio_track, video_track],
+        Some(media_stream_settings(true, true)),
+    )
+    .await;
+
+    let room_handle = api::RoomHandle::from(room.new_handle());
+    room_handle
+        .set_recv_media_policy(api::RecvMediaPolicy::Manual)
+        .unwrap();
+    assert!(JsFuture::from(
+        room_handle.disable_remote_media_from("alice".to_owned())
+    )
+    .await
+    .is_ok());
+    assert!(peer.is_recv_audio_enabled());
+    assert!(peer.is_recv_video_enabled());
+
+    assert!(JsFuture::from(
+        room_handle.disable_remote_media_from("bob".to_owned())
+    )
+    .await
+    .is_ok());
+    assert!(!peer.is_recv_audio_enabled());
+    assert!(!peer.is_recv_video_enabled());
+
+    assert!(JsFuture::from(
+        room_handle.enable_remote_media_from("bob".to_owned())
+    )
+    .await
+    .is_ok());
+    assert!(peer.is_recv_audio_enabled());
+    assert!(peer.is_recv_video_enabled());
+}
+
 /// Checks that server can disable track without client's request.
 #[wasm_bindgen_test]
 async fn disable_by_server() {
@@ -1699,6 +1862,8 @@ async fn disable_by_server() {
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
                 muted: None,
+                content_hint: None,
+                degradation_preference: None,
             })],
         })
         .unwrap();
@@ -1730,6 +1895,8 @@ async fn enable_by_server() {
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
                 muted: None,
+                content_hint: None,
+                degradation_preference: None,
             })],
         })
         .unwrap();
@@ -1749,6 +1916,8 @@ async fn enable_by_server() {
                 enabled_general: Some(true),
                 enabled_individual: Some(true),
                 muted: None,
+                content_hint: None,
+                degradation_preference: None,
             })],
         })
         .unwrap();
@@ -1761,6 +1930,44 @@ async fn enable_by_server() {
     assert!(sender.transceiver().send_track().is_some());
 }
 
+/// Checks that no local media is requested for a receive-only `Peer`.
+#[wasm_bindgen_test]
+async fn recv_only_peer_does_not_request_local_media() {
+    let mock = MockNavigator::new();
+    let (event_tx, event_rx) = mpsc::unbounded();
+    let (_room, mut commands_rx) = get_test_room(Box::pin(event_rx));
+
+    event_tx
+        .unbounded_send(Event::PeerCreated {
+            peer_id: PeerId(1),
+            negotiation_role: NegotiationRole::Offerer,
+            tracks: vec![Track {
+                id: TrackId(1),
+                direction: Direction::Recv {
+                    sender: "bob".into(),
+                    mid: None,
+                },
+                media_type: MediaType::Video(VideoSettings {
+                    required: false,
+                    source_kind: MediaSourceKind::Device,
+                }),
+            }],
+            ice_servers: Vec::new(),
+            force_relay: false,
+        })
+        .unwrap();
+
+    loop {
+        let command = commands_rx.next().await.unwrap();
+        if let Command::MakeSdpOffer { peer_id, .. } = command {
+            assert_eq!(peer_id, PeerId(1));
+            break;
+        }
+    }
+    assert_eq!(mock.get_user_media_requests_count(), 0);
+    mock.stop();
+}
+
 /// Checks that only one get user media request will be performed on
 /// `Room.enable_audio` with a failed get user media.
 #[wasm_bindgen_test]
@@ -1785,6 +1992,8 @@ async fn only_one_gum_performed_on_enable() {
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
                 muted: None,
+                content_hint: None,
+                degradation_preference: None,
             })],
         })
         .unwrap();
@@ -1804,6 +2013,8 @@ async fn only_one_gum_performed_on_enable() {
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
                 muted: None,
+                content_hint: None,
+                degradation_preference: None,
             })],
         })
         .unwrap();
@@ -1920,6 +2131,8 @@ async fn only_one_gum_performed_on_enable_by_server() {
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
                 muted: None,
+                content_hint: None,
+                degradation_preference: None,
             })],
         })
         .unwrap();
@@ -1938,6 +2151,8 @@ async fn only_one_gum_performed_on_enable_by_server() {
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
                 muted: None,
+                content_hint: None,
+                degradation_preference: None,
             })],
         })
         .unwrap();
@@ -1970,12 +2185,13 @@ async fn send_enabling_holds_local_tracks() {
         }
     });
 
-    let room = Room::new(Rc::new(rpc), Rc::default());
+    let room = Room::new(Rc::new(rpc), Rc::default(), Rc::default());
     let room_handle = api::RoomHandle::from(room.new_handle());
     JsFuture::from(room_handle.set_local_media_settings(
         &media_stream_settings(true, true),
         false,
         false,
+        None,
     ))
     .await
     .unwrap();
@@ -1999,6 +2215,8 @@ async fn send_enabling_holds_local_tracks() {
                 enabled_individual: Some(false),
                 enabled_general: Some(false),
                 muted: None,
+                content_hint: None,
+                degradation_preference: None,
             })],
         })
         .unwrap();
@@ -2199,6 +2417,7 @@ mod set_local_media_settings {
             &constraints,
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -2216,6 +2435,54 @@ mod set_local_media_settings {
         wait_and_check_test_result(test_result, || {}).await;
     }
 
+    /// Tests RoomHandle::set_local_media_settings being aborted via the
+    /// provided `AbortSignal`. Setup:
+    ///     1. Create Room.
+    ///     2. Set `on_failed_local_media` callback.
+    ///     3. Invoke `room_handle.set_local_media_settings` with one track.
+    ///     4. Abort it.
+    ///     5. Send `PeerCreated` to room with two tracks.
+    /// Assertions:
+    ///     1. `set_local_media_settings` fails with `AbortError`.
+    ///     2. `on_failed_local_media` callback was not invoked.
+    #[wasm_bindgen_test]
+    async fn aborted_set_local_media_settings_are_not_applied() {
+        let (event_tx, event_rx) = mpsc::unbounded();
+        let (room, _rx) = get_test_room(Box::pin(event_rx));
+        let room_handle = api::RoomHandle::from(room.new_handle());
+
+        let (cb, test_result) = js_callback!(|_err: api::Error| {});
+        room_handle.on_failed_local_media(cb.into()).unwrap();
+
+        let (audio_track, video_track) = get_test_required_tracks();
+
+        let mut constraints = api::MediaStreamSettings::new();
+        constraints.audio(api::AudioTrackConstraints::new());
+
+        let controller = web_sys::AbortController::new().unwrap();
+        let update = JsFuture::from(room_handle.set_local_media_settings(
+            &constraints,
+            false,
+            false,
+            Some(controller.signal()),
+        ));
+        controller.abort();
+        let err = web_sys::DomException::from(update.await.unwrap_err());
+        assert_eq!(err.name(), "AbortError");
+
+        event_tx
+            .unbounded_send(Event::PeerCreated {
+                peer_id: PeerId(1),
+                negotiation_role: NegotiationRole::Offerer,
+                tracks: vec![audio_track, video_track],
+                ice_servers: Vec::new(),
+                force_relay: false,
+            })
+            .unwrap();
+
+        timeout(500, test_result).await.unwrap_err();
+    }
+
     /// Tests RoomHandle::set_local_media_settings for existing PeerConnection.
     /// Setup:
     ///     1. Create Room.
@@ -2248,6 +2515,7 @@ mod set_local_media_settings {
                 &constraints,
                 false,
                 false,
+                None,
             ))
             .await
             .unwrap_err(),
@@ -2301,7 +2569,8 @@ mod set_local_media_settings {
                 JsFuture::from(room_handle.set_local_media_settings(
                     &constraints,
                     false,
-                    false
+                    false,
+                    None
                 ))
                 .await
                 .is_ok(),
@@ -2339,12 +2608,13 @@ mod set_local_media_settings {
         let (room, commands_rx) = get_test_room(Box::pin(event_rx));
         let room_handle = api::RoomHandle::from(room.new_handle());
         room_handle
-            .on_failed_local_media(js_sys::Function::new_no_args(""))
+            .on_failed_local_media(js_sys::Function::new_no_args("").into())
             .unwrap();
         JsFuture::from(room_handle.set_local_media_settings(
             &media_stream_settings(true, false),
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -2366,6 +2636,7 @@ mod set_local_media_settings {
                 &media_stream_settings(true, true),
                 false,
                 false,
+                None,
             ))
             .await
             .unwrap();
@@ -2400,6 +2671,7 @@ mod set_local_media_settings {
                 &media_settings_with_device_id(),
                 true,
                 false,
+                None,
             ))
             .await
             .unwrap_err(),
@@ -2431,6 +2703,7 @@ mod set_local_media_settings {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -2441,6 +2714,7 @@ mod set_local_media_settings {
                 &media_settings_with_device_id(),
                 true,
                 true,
+                None,
             ))
             .await
             .unwrap_err(),
@@ -2468,6 +2742,7 @@ mod set_local_media_settings {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -2479,6 +2754,7 @@ mod set_local_media_settings {
                 &media_settings_with_device_id(),
                 true,
                 true,
+                None,
             ))
             .await
             .unwrap_err(),
@@ -2519,6 +2795,7 @@ mod set_local_media_settings {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ))
         .await
         .unwrap();
@@ -2532,6 +2809,7 @@ mod set_local_media_settings {
                 &media_settings_with_device_id(),
                 false,
                 true,
+                None,
             ))
             .await
             .unwrap_err(),
@@ -2587,8 +2865,11 @@ mod state_synchronization {
         rpc_session.expect_send_command().returning(move |cmd| {
             let _ = command_tx.unbounded_send(cmd);
         });
-        let room =
-            Room::new(Rc::new(rpc_session), Rc::new(MediaManager::default()));
+        let room = Room::new(
+            Rc::new(rpc_session),
+            Rc::new(MediaManager::default()),
+            Rc::default(),
+        );
 
         let mut senders = HashMap::new();
         senders.insert(
@@ -2599,8 +2880,12 @@ mod state_synchronization {
                 enabled_individual: true,
                 enabled_general: true,
                 receivers: Vec::new(),
-                media_type: MediaType::Audio(AudioSettings { required: true }),
+                media_type: MediaType::Audio(AudioSettings {
+                    required: true,
+                    opus: None,
+                }),
                 mid: None,
+                ssrc: None,
             },
         );
         let mut receivers = HashMap::new();
@@ -2612,8 +2897,12 @@ mod state_synchronization {
                 enabled_individual: true,
                 enabled_general: true,
                 sender_id: "".into(),
-                media_type: MediaType::Audio(AudioSettings { required: true }),
+                media_type: MediaType::Audio(AudioSettings {
+                    required: true,
+                    opus: None,
+                }),
                 mid: None,
+                ssrc: None,
             },
         );
         let mut room_proto = room.peers_state().as_proto();
@@ -2656,6 +2945,7 @@ async fn intentions_are_sent_on_reconnect() {
             &media_stream_settings(true, true),
             false,
             false,
+            None,
         ),
     )
     .await
@@ -2721,3 +3011,298 @@ async fn intentions_are_sent_on_reconnect() {
     .await
     .unwrap();
 }
+
+/// Checks that [`Command::SynchronizeMe`] is sent only after consecutive
+/// [`Event::StateChecksum`]s not matching the local state.
+#[wasm_bindgen_test]
+async fn synchronizes_state_on_checksum_mismatch() {
+    let (event_tx, event_rx) = mpsc::unbounded();
+    let (room, mut commands_rx) = get_test_room(Box::pin(event_rx));
+    let checksum = room.peers_state().as_proto().checksum();
+
+    event_tx
+        .unbounded_send(Event::StateChecksum { checksum })
+        .unwrap();
+    event_tx
+        .unbounded_send(Event::StateChecksum {
+            checksum: checksum.wrapping_add(1),
+        })
+        .unwrap();
+    timeout(100, commands_rx.next()).await.unwrap_err();
+
+    event_tx
+        .unbounded_send(Event::StateChecksum {
+            checksum: checksum.wrapping_add(1),
+        })
+        .unwrap();
+    let command = timeout(300, commands_rx.next()).await.unwrap().unwrap();
+    assert!(matches!(command, Command::SynchronizeMe { .. }));
+}
+
+/// Checks that [`Event::StateDeltaSynchronized`] is applied to the state sent
+/// in the last [`Command::SynchronizeMe`].
+#[wasm_bindgen_test]
+async fn applies_state_delta_to_synchronized_state() {
+    let (event_tx, event_rx) = mpsc::unbounded();
+    let (room, mut commands_rx) = get_test_room(Box::pin(event_rx));
+    let checksum = room.peers_state().as_proto().checksum();
+
+    for _ in 0..2 {
+        event_tx
+            .unbounded_send(Event::StateChecksum {
+                checksum: checksum.wrapping_add(1),
+            })
+            .unwrap();
+    }
+    let base = match timeout(300, commands_rx.next()).await.unwrap().unwrap() {
+        Command::SynchronizeMe { state, .. } => state,
+        _ => unreachable!(),
+    };
+
+    let mut senders = HashMap::new();
+    senders.insert(
+        TrackId(0),
+        proto::state::Sender {
+            id: TrackId(0),
+            muted: false,
+            enabled_individual: true,
+            enabled_general: true,
+            receivers: Vec::new(),
+            media_type: MediaType::Audio(proto::AudioSettings {
+                required: true,
+                opus: None,
+            }),
+            mid: None,
+            ssrc: None,
+        },
+    );
+    let mut updated = base.clone();
+    updated.peers.insert(
+        PeerId(0),
+        proto::state::Peer {
+            id: PeerId(0),
+            restart_ice: false,
+            senders,
+            receivers: HashMap::new(),
+            force_relay: false,
+            ice_servers: vec![],
+            negotiation_role: Some(NegotiationRole::Offerer),
+            local_sdp: None,
+            remote_sdp: None,
+            ice_candidates: Default::default(),
+        },
+    );
+    event_tx
+        .unbounded_send(Event::StateDeltaSynchronized {
+            delta: base.diff(&updated),
+        })
+        .unwrap();
+
+    let command = timeout(1000, commands_rx.next()).await.unwrap().unwrap();
+    assert!(matches!(command, Command::MakeSdpOffer { .. }));
+    let peer = room.get_peer_by_id(PeerId(0)).unwrap();
+    assert!(peer.get_sender_by_id(TrackId(0)).is_some());
+}
+
+/// Checks that [`Event::TrackQualityAdvisory`] limits and then lifts the
+/// maximum bitrate of the advised `Sender`.
+#[wasm_bindgen_test]
+async fn applies_track_quality_advisory() {
+    let (audio_track, video_track) = get_test_tracks(false, false);
+    let (room, peer, event_tx, _commands_rx) =
+        get_test_room_and_exist_peer(vec![audio_track, video_track], None)
+            .await;
+    let sender = peer.get_sender_by_id(TrackId(2)).unwrap();
+
+    event_tx
+        .unbounded_send(Event::TrackQualityAdvisory {
+            track_id: TrackId(2),
+            advised_max_bitrate: Some(300_000),
+            reason: proto::QualityAdvisoryReason::PacketLoss,
+        })
+        .unwrap();
+    delay_for(100).await;
+    assert_eq!(sender.max_bitrate(), Some(300_000));
+
+    event_tx
+        .unbounded_send(Event::TrackQualityAdvisory {
+            track_id: TrackId(2),
+            advised_max_bitrate: None,
+            reason: proto::QualityAdvisoryReason::Recovered,
+        })
+        .unwrap();
+    delay_for(100).await;
+    assert_eq!(sender.max_bitrate(), None);
+    drop(room);
+}
+
+/// Checks that [`RoomHandle::export_debug_dump()`] contains all the
+/// [`PeerConnection`]s and the state synchronization history.
+///
+/// [`RoomHandle::export_debug_dump()`]: api::RoomHandle::export_debug_dump
+#[wasm_bindgen_test]
+async fn exports_debug_dump() {
+    let (room, _peer, event_tx, _commands_rx) =
+        get_test_room_and_exist_peer(Vec::new(), None).await;
+    let checksum = room.peers_state().as_proto().checksum();
+
+    event_tx
+        .unbounded_send(Event::StateChecksum {
+            checksum: checksum.wrapping_add(1),
+        })
+        .unwrap();
+    delay_for(100).await;
+
+    let dump = JsFuture::from(
+        api::RoomHandle::from(room.new_handle()).export_debug_dump(),
+    )
+    .await
+    .unwrap()
+    .as_string()
+    .unwrap();
+    let dump: serde_json::Value = serde_json::from_str(&dump).unwrap();
+
+    let peers = dump["peers"].as_array().unwrap();
+    assert_eq!(peers.len(), 1);
+    assert_eq!(peers[0]["id"], 1);
+    assert!(peers[0]["state"].is_object());
+
+    let history = dump["sync_history"].as_array().unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0]["event"], "checksum_mismatch");
+    assert_eq!(history[0]["server"], checksum.wrapping_add(1));
+}
+
+/// Checks that [`RoomHandle::retry_local_media()`] re-enables outbound media,
+/// which has failed to be acquired.
+///
+/// [`RoomHandle::retry_local_media()`]: api::RoomHandle::retry_local_media
+#[wasm_bindgen_test]
+async fn retry_local_media_reenables_failed_media() {
+    let mock = MockNavigator::new();
+    let (audio_track, _) = get_test_tracks(false, false);
+    let (room, peer, _event_tx, _command_rx) = get_test_room_and_exist_peer(
+        vec![audio_track],
+        Some(media_stream_settings(true, false)),
+    )
+    .await;
+    let room_handle = api::RoomHandle::from(room.new_handle());
+    JsFuture::from(room_handle.disable_audio()).await.unwrap();
+
+    mock.error_get_user_media("gum error".into());
+    JsFuture::from(room_handle.enable_audio())
+        .await
+        .unwrap_err();
+    assert!(!peer.is_send_audio_enabled());
+    mock.stop();
+
+    JsFuture::from(room_handle.retry_local_media())
+        .await
+        .unwrap();
+    assert!(peer.is_send_audio_enabled());
+}
+
+/// Checks that [`RoomHandle::set_stats_interval()`] with `0` stops periodic
+/// stats sending, while [`RoomHandle::poll_stats_now()`] still works.
+///
+/// [`RoomHandle::set_stats_interval()`]: api::RoomHandle::set_stats_interval
+/// [`RoomHandle::poll_stats_now()`]: api::RoomHandle::poll_stats_now
+#[wasm_bindgen_test]
+async fn stops_periodic_stats_polling() {
+    let (room, _peer, _event_tx, mut commands_rx) =
+        get_test_room_and_exist_peer(Vec::new(), None).await;
+    let handle = api::RoomHandle::from(room.new_handle());
+    handle.set_stats_interval(0).unwrap();
+
+    let stats_sent = timeout(1500, async {
+        while let Some(command) = commands_rx.next().await {
+            if let Command::AddPeerConnectionMetrics {
+                metrics: PeerMetrics::RtcStats(_),
+                ..
+            } = command
+            {
+                break;
+            }
+        }
+    })
+    .await;
+    assert!(stats_sent.is_err());
+
+    JsFuture::from(handle.poll_stats_now()).await.unwrap();
+}
+
+/// Checks that consecutive [`PeerUpdate::Added`] tracks are inserted as a
+/// single batch.
+#[wasm_bindgen_test]
+async fn batches_added_tracks() {
+    let (audio_track, video_track) = get_test_tracks(false, false);
+    let (room, peer, event_tx, _commands_rx) =
+        get_test_room_and_exist_peer(Vec::new(), None).await;
+
+    event_tx
+        .unbounded_send(Event::PeerUpdated {
+            peer_id: PeerId(1),
+            updates: vec![
+                PeerUpdate::Added(audio_track),
+                PeerUpdate::Added(video_track),
+            ],
+            negotiation_role: None,
+        })
+        .unwrap();
+    delay_for(200).await;
+
+    assert!(peer.get_sender_by_id(TrackId(1)).is_some());
+    assert!(peer.get_sender_by_id(TrackId(2)).is_some());
+    assert_eq!(
+        room.applied_effects(),
+        vec![
+            EffectKind::CreatePeer(PeerId(1)),
+            EffectKind::InsertTracks(PeerId(1), vec![TrackId(1), TrackId(2)]),
+        ],
+    );
+}
+
+/// Checks that mutations caused by [`Event`]s are applied in the order they
+/// were received in, and each [`PeerUpdate::Added`] track is created before
+/// the next mutation is applied.
+#[wasm_bindgen_test]
+async fn applies_effects_in_order() {
+    let (audio_track, video_track) = get_test_tracks(false, false);
+    let (room, peer, event_tx, _commands_rx) =
+        get_test_room_and_exist_peer(vec![audio_track], None).await;
+
+    event_tx
+        .unbounded_send(Event::PeerUpdated {
+            peer_id: PeerId(1),
+            updates: vec![
+                PeerUpdate::Added(video_track),
+                PeerUpdate::Updated(TrackPatchEvent {
+                    id: TrackId(2),
+                    enabled_individual: Some(false),
+                    enabled_general: Some(false),
+                    muted: None,
+                    content_hint: None,
+                    degradation_preference: None,
+                }),
+            ],
+            negotiation_role: None,
+        })
+        .unwrap();
+    event_tx
+        .unbounded_send(Event::PeersRemoved {
+            peer_ids: vec![PeerId(2)],
+        })
+        .unwrap();
+    delay_for(200).await;
+
+    assert!(peer.get_sender_by_id(TrackId(2)).is_some());
+    assert_eq!(
+        room.applied_effects(),
+        vec![
+            EffectKind::CreatePeer(PeerId(1)),
+            EffectKind::InsertTracks(PeerId(1), vec![TrackId(2)]),
+            EffectKind::PatchTrack(PeerId(1), TrackId(2)),
+            EffectKind::RemovePeer(PeerId(2)),
+        ],
+    );
+}
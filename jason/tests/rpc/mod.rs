@@ -15,7 +15,8 @@ use futures::{
     StreamExt as _,
 };
 use medea_client_api_proto::{
-    ClientMsg, CloseReason, Command, Event, PeerId, RpcSettings, ServerMsg,
+    ClientMsg, CloseDescription, CloseReason, Command, Event, PeerId,
+    RpcSettings, ServerMsg,
 };
 use medea_jason::{
     platform::{MockRpcTransport, RpcTransport, TransportState},
@@ -28,11 +29,14 @@ use crate::{delay_for, join_room_url, timeout};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
-/// [`ServerMsg::RpcSettings`] that can be used in tests.
-pub const RPC_SETTINGS: ServerMsg = ServerMsg::RpcSettings(RpcSettings {
-    idle_timeout_ms: 5_000,
-    ping_interval_ms: 2_000,
-});
+/// Returns [`ServerMsg::RpcSettings`] that can be used in tests.
+pub fn rpc_settings() -> ServerMsg {
+    ServerMsg::RpcSettings(RpcSettings {
+        idle_timeout_ms: 5_000,
+        ping_interval_ms: 2_000,
+        features: HashMap::new(),
+    })
+}
 
 /// Creates [`WebSocketRpcClient`] with the provided [`MockRpcTransport`].
 fn new_client(transport: Rc<MockRpcTransport>) -> Rc<WebSocketRpcClient> {
@@ -79,10 +83,12 @@ async fn message_received_from_transport_is_transmitted_to_sub() {
                 ServerMsg::RpcSettings(RpcSettings {
                     idle_timeout_ms: 10_000,
                     ping_interval_ms: 10_000,
+                    features: HashMap::new(),
                 }),
                 ServerMsg::Event {
                     room_id: "".into(),
                     event: SRV_EVENT,
+                    seq: None,
                 },
             ])
             .boxed()
@@ -100,7 +106,8 @@ async fn message_received_from_transport_is_transmitted_to_sub() {
         stream.next().await.unwrap(),
         RpcEvent::Event {
             room_id: "".into(),
-            event: SRV_EVENT
+            event: SRV_EVENT,
+            seq: None,
         }
     );
 }
@@ -128,6 +135,7 @@ async fn transport_is_dropped_when_client_is_dropped() {
         on_message_mock(RpcSettings {
             idle_timeout_ms: 10_000,
             ping_interval_ms: 500,
+            features: HashMap::new(),
         })
     });
     let rpc_transport = Rc::new(transport);
@@ -140,6 +148,40 @@ async fn transport_is_dropped_when_client_is_dropped() {
     assert_eq!(Rc::strong_count(&rpc_transport), 1);
 }
 
+/// Tests that [`WebSocketRpcClient::feature`] returns the feature flags from
+/// the last received [`RpcSettings`].
+#[wasm_bindgen_test]
+async fn features_are_taken_from_last_rpc_settings() {
+    let mut transport = MockRpcTransport::new();
+    transport.expect_send().returning(|_| Ok(()));
+    transport.expect_set_close_reason().return_const(());
+    transport
+        .expect_on_state_change()
+        .return_once(|| stream::once(async { TransportState::Open }).boxed());
+    transport.expect_on_message().returning(|| {
+        let settings = |delta_sync: &str| {
+            let mut features = HashMap::new();
+            features.insert("delta_sync".to_owned(), delta_sync.to_owned());
+            ServerMsg::RpcSettings(RpcSettings {
+                idle_timeout_ms: 10_000,
+                ping_interval_ms: 500,
+                features,
+            })
+        };
+        stream::iter(vec![settings("false"), settings("true")])
+            .chain(stream::pending())
+            .boxed()
+    });
+
+    let ws = new_client(Rc::new(transport));
+    assert_eq!(ws.feature("delta_sync"), None);
+    ws.clone().connect(join_room_url()).await.unwrap();
+    delay_for(100).await;
+
+    assert_eq!(ws.feature("delta_sync").as_deref(), Some("true"));
+    assert_eq!(ws.feature("binary_proto"), None);
+}
+
 /// Tests [`WebSocketRpcClient::send_command`] function.
 ///
 /// # Algorithm
@@ -162,6 +204,7 @@ async fn send_goes_to_transport() {
         on_message_mock(RpcSettings {
             idle_timeout_ms: 10_000,
             ping_interval_ms: 500,
+            features: HashMap::new(),
         })
     });
     transport.expect_send().returning(move |e| {
@@ -229,6 +272,7 @@ mod on_close {
             on_message_mock(RpcSettings {
                 idle_timeout_ms: 10_000,
                 ping_interval_ms: 500,
+                features: HashMap::new(),
             })
         });
         transport.expect_send().returning(|_| Ok(()));
@@ -260,7 +304,9 @@ mod on_close {
 
         assert_eq!(
             ws.on_normal_close().await.unwrap(),
-            medea_jason::rpc::CloseReason::ByServer(CloseReason::Finished)
+            medea_jason::rpc::CloseReason::ByServer(CloseDescription::new(
+                CloseReason::Finished
+            ))
         );
     }
 
@@ -320,6 +366,7 @@ mod transport_close_reason_on_drop {
             on_message_mock(RpcSettings {
                 idle_timeout_ms: 10000,
                 ping_interval_ms: 500,
+                features: HashMap::new(),
             })
         });
         transport.expect_send().return_once(|_| Ok(()));
@@ -422,6 +469,7 @@ mod connect {
                 on_message_mock(RpcSettings {
                     idle_timeout_ms: 3_000,
                     ping_interval_ms: 3_000,
+                    features: HashMap::new(),
                 })
             });
             transport.expect_send().return_once(|_| Ok(()));
@@ -460,6 +508,7 @@ mod connect {
                     on_message_mock(RpcSettings {
                         idle_timeout_ms: 3_000,
                         ping_interval_ms: 3_000,
+                        features: HashMap::new(),
                     })
                 });
                 transport.expect_send().return_once(|_| Ok(()));
@@ -512,6 +561,7 @@ mod connect {
                     on_message_mock(RpcSettings {
                         idle_timeout_ms: 3_000,
                         ping_interval_ms: 3_000,
+                        features: HashMap::new(),
                     })
                 });
                 transport.expect_send().return_once(|_| Ok(()));
@@ -551,6 +601,7 @@ mod on_connection_loss {
                     on_message_mock(RpcSettings {
                         idle_timeout_ms: idle_timeout_ms.unwrap_or(u32::MAX),
                         ping_interval_ms: ping_interval_ms.unwrap_or(u32::MAX),
+                        features: HashMap::new(),
                     })
                 });
                 transport.expect_set_close_reason().return_once(drop);
@@ -722,6 +773,7 @@ mod on_reconnected {
                     on_message_mock(RpcSettings {
                         idle_timeout_ms: 5_000,
                         ping_interval_ms: 2_000,
+                        features: HashMap::new(),
                     })
                 });
                 transport.expect_send().return_once(|_| Ok(()));
@@ -747,6 +799,7 @@ mod on_reconnected {
             Rc::new(ObservableCell::new(ServerMsg::RpcSettings(RpcSettings {
                 idle_timeout_ms: 5_000,
                 ping_interval_ms: 2_000,
+                features: HashMap::new(),
             })));
         let on_state_change_mock =
             Rc::new(ObservableCell::new(TransportState::Open));
@@ -785,6 +838,7 @@ mod on_reconnected {
         on_message_mock.set(ServerMsg::RpcSettings(RpcSettings {
             idle_timeout_ms: 5_000,
             ping_interval_ms: 2_000,
+            features: HashMap::new(),
         }));
 
         ws.connect(join_room_url()).await.unwrap();
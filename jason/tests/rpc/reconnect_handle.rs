@@ -5,7 +5,7 @@ use std::{
     str::FromStr,
 };
 
-use futures::{stream, StreamExt as _};
+use futures::{channel::oneshot, stream, StreamExt as _};
 use medea_client_api_proto::{Event, ServerMsg};
 use medea_jason::{
     platform::{self, MockRpcTransport, RpcTransport, TransportState},
@@ -15,16 +15,18 @@ use medea_jason::{
     },
 };
 use medea_reactive::ObservableCell;
+use wasm_bindgen::closure::Closure;
 use wasm_bindgen_test::*;
 
-use crate::{delay_for, rpc::RPC_SETTINGS, timeout, TEST_ROOM_URL};
+use crate::{delay_for, rpc::rpc_settings, timeout, TEST_ROOM_URL};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
-/// Makes sure that [`ReconnectHandle::reconnect_with_backoff()`] works as
-/// expected.
-#[wasm_bindgen_test]
-async fn reconnect_with_backoff() {
+/// Creates a new [`WebSocketRpcSession`] and makes it lose its connection.
+///
+/// Returned [`ObservableCell`] controls the state of the session's transport.
+async fn lost_session(
+) -> (Rc<WebSocketRpcSession>, Rc<ObservableCell<TransportState>>) {
     let transport_state = Rc::new(ObservableCell::new(TransportState::Open));
 
     let state_clone = Rc::clone(&transport_state);
@@ -35,12 +37,13 @@ async fn reconnect_with_backoff() {
                 let mut transport = MockRpcTransport::new();
                 transport.expect_on_message().returning_st(|| {
                     Box::pin(stream::iter(vec![
-                        RPC_SETTINGS,
+                        rpc_settings(),
                         ServerMsg::Event {
                             room_id: "room_id".into(),
                             event: Event::RoomJoined {
                                 member_id: "member_id".into(),
                             },
+                            seq: None,
                         },
                     ]))
                 });
@@ -64,13 +67,22 @@ async fn reconnect_with_backoff() {
         .await
         .unwrap()
         .unwrap();
+
+    (session, transport_state)
+}
+
+/// Makes sure that [`ReconnectHandle::reconnect_with_backoff()`] works as
+/// expected.
+#[wasm_bindgen_test]
+async fn reconnect_with_backoff() {
+    let (session, transport_state) = lost_session().await;
     let handle =
         ReconnectHandle::new(Rc::downgrade(&session) as Weak<dyn RpcSession>);
 
     // Checks that max_elapsed is not exceeded if starting_delay > max_elapsed.
     let start = instant::Instant::now();
     let err = handle
-        .reconnect_with_backoff(1000, 999.0, 50, Some(200))
+        .reconnect_with_backoff(1000, 999.0, 50, Some(200), 0.0, None)
         .await
         .expect_err("supposed to err since transport state didn't change")
         .into_inner();
@@ -81,7 +93,7 @@ async fn reconnect_with_backoff() {
     // Checks that reconnect attempts are made for an expected period.
     let start = instant::Instant::now();
     let err = handle
-        .reconnect_with_backoff(10, 1.5, 50, Some(444))
+        .reconnect_with_backoff(10, 1.5, 50, Some(444), 0.0, None)
         .await
         .expect_err("supposed to err since transport state didn't change")
         .into_inner();
@@ -99,7 +111,9 @@ async fn reconnect_with_backoff() {
         }
     });
     let start = instant::Instant::now();
-    let err = handle.reconnect_with_backoff(30, 3.0, 9999, None).await;
+    let err = handle
+        .reconnect_with_backoff(30, 3.0, 9999, None, 0.0, None)
+        .await;
     let elapsed = start.elapsed().as_millis();
     assert!(elapsed >= 120 && elapsed < 200); // 30 + 90
     assert!(err.is_ok());
@@ -117,7 +131,7 @@ async fn reconnect_with_backoff() {
     });
     let start = instant::Instant::now();
     let err = handle
-        .reconnect_with_backoff(1, 2.0, 100, None)
+        .reconnect_with_backoff(1, 2.0, 100, None, 0.0, None)
         .await
         .expect_err("should err since we drop RpcSession")
         .into_inner();
@@ -125,3 +139,54 @@ async fn reconnect_with_backoff() {
     assert!(elapsed >= 20 && elapsed < 100);
     assert!(matches!(err, ReconnectError::Detached));
 }
+
+/// Makes sure that [`ReconnectHandle::reconnect_with_backoff()`] gives up after
+/// the provided max attempts count, invoking the `on_give_up` callback.
+#[wasm_bindgen_test]
+async fn reconnect_with_backoff_gives_up_after_max_attempts() {
+    let (session, _transport_state) = lost_session().await;
+    let handle =
+        ReconnectHandle::new(Rc::downgrade(&session) as Weak<dyn RpcSession>);
+    let (give_up_tx, give_up_rx) = oneshot::channel();
+    let cb = Closure::once_into_js(move || {
+        let _ = give_up_tx.send(());
+    });
+    handle.on_give_up(platform::Function::from(js_sys::Function::from(cb)));
+
+    let start = instant::Instant::now();
+    let err = handle
+        .reconnect_with_backoff(10, 1.0, 10, None, 0.5, Some(3))
+        .await
+        .expect_err("supposed to err since transport state didn't change")
+        .into_inner();
+    let elapsed = start.elapsed().as_millis();
+    assert!(elapsed >= 10 && elapsed < 100);
+    assert!(matches!(err, ReconnectError::Session(_)));
+    timeout(100, give_up_rx).await.unwrap().unwrap();
+}
+
+/// Makes sure that [`ReconnectHandle::cancel_backoff()`] cancels an ongoing
+/// [`ReconnectHandle::reconnect_with_backoff()`].
+#[wasm_bindgen_test]
+async fn cancel_backoff() {
+    let (session, _transport_state) = lost_session().await;
+    let handle =
+        ReconnectHandle::new(Rc::downgrade(&session) as Weak<dyn RpcSession>);
+
+    platform::spawn({
+        let handle = handle.clone();
+        async move {
+            delay_for(30).await;
+            handle.cancel_backoff();
+        }
+    });
+    let start = instant::Instant::now();
+    let err = handle
+        .reconnect_with_backoff(1000, 2.0, 1000, None, 0.0, None)
+        .await
+        .expect_err("supposed to err since backoff is cancelled")
+        .into_inner();
+    let elapsed = start.elapsed().as_millis();
+    assert!(elapsed >= 30 && elapsed < 100);
+    assert!(matches!(err, ReconnectError::Cancelled));
+}
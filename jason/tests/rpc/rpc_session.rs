@@ -13,7 +13,8 @@ use medea_client_api_proto::{
 };
 use medea_jason::{
     platform::{
-        MockRpcTransport, RpcTransport, TransportState, WebSocketRpcTransport,
+        self, MockRpcTransport, RpcTransport, TransportState,
+        WebSocketRpcTransport,
     },
     rpc::{
         CloseMsg, ConnectionInfo, RpcSession, SessionError, WebSocketRpcClient,
@@ -22,7 +23,7 @@ use medea_jason::{
 };
 use wasm_bindgen_test::*;
 
-use crate::{delay_for, rpc::RPC_SETTINGS, timeout, TEST_ROOM_URL};
+use crate::{delay_for, rpc::rpc_settings, timeout, TEST_ROOM_URL};
 
 wasm_bindgen_test_configure!(run_in_browser);
 
@@ -36,12 +37,14 @@ async fn could_not_auth_err() {
                 let mut transport = MockRpcTransport::new();
                 transport.expect_on_message().returning_st(|| {
                     Box::pin(stream::iter(vec![
-                        RPC_SETTINGS,
+                        rpc_settings(),
                         ServerMsg::Event {
                             room_id: "room_id".into(),
                             event: Event::RoomLeft {
                                 close_reason: CloseReason::InternalError,
+                                retry_after_ms: None,
                             },
+                            seq: None,
                         },
                     ]))
                 });
@@ -92,12 +95,13 @@ async fn concurrent_connect_requests() {
                 let mut transport = MockRpcTransport::new();
                 transport.expect_on_message().returning_st(|| {
                     Box::pin(stream::iter(vec![
-                        RPC_SETTINGS,
+                        rpc_settings(),
                         ServerMsg::Event {
                             room_id: "room_id".into(),
                             event: Event::RoomJoined {
                                 member_id: "member_id".into(),
                             },
+                            seq: None,
                         },
                     ]))
                 });
@@ -196,12 +200,13 @@ async fn reconnect_after_transport_abnormal_close() {
                 let mut transport = MockRpcTransport::new();
                 transport.expect_on_message().returning_st(|| {
                     Box::pin(stream::iter(vec![
-                        RPC_SETTINGS,
+                        rpc_settings(),
                         ServerMsg::Event {
                             room_id: "room_id".into(),
                             event: Event::RoomJoined {
                                 member_id: "member_id".into(),
                             },
+                            seq: None,
                         },
                     ]))
                 });
@@ -256,6 +261,7 @@ async fn reconnect_after_transport_abnormal_close() {
                 command: Command::JoinRoom {
                     member_id: "member_id".into(),
                     credential: "token".into(),
+                    capabilities: platform::client_capabilities(),
                 }
             },
             // reconnect
@@ -264,8 +270,33 @@ async fn reconnect_after_transport_abnormal_close() {
                 command: Command::JoinRoom {
                     member_id: "member_id".into(),
                     credential: "token".into(),
+                    capabilities: platform::client_capabilities(),
                 }
             }
         ]
     );
 }
+
+/// Makes sure that cancelled `connect` resolves with
+/// [`SessionError::NewConnectionInfo`] and doesn't hang forever.
+#[wasm_bindgen_test]
+async fn cancel_connect_resets_session() {
+    let session = WebSocketRpcSession::new(Rc::new(WebSocketRpcClient::new(
+        Box::new(|_| Box::pin(future::pending())),
+    )));
+
+    let (connect_res, _) = future::join(
+        Rc::clone(&session)
+            .connect(ConnectionInfo::from_str(TEST_ROOM_URL).unwrap()),
+        async {
+            delay_for(10).await;
+            session.cancel_connect();
+        },
+    )
+    .await;
+
+    assert!(matches!(
+        connect_res.unwrap_err().into_inner(),
+        SessionError::NewConnectionInfo
+    ));
+}
@@ -1,6 +1,10 @@
 #![cfg(target_arch = "wasm32")]
 
-use medea_jason::platform::{TransportError, WebSocketRpcTransport};
+use medea_client_api_proto::{CloseDescription, CloseReason};
+use medea_jason::{
+    platform::{TransportError, WebSocketRpcTransport},
+    rpc::CloseMsg,
+};
 use url::Url;
 use wasm_bindgen_test::*;
 
@@ -22,3 +26,48 @@ async fn could_not_init_socket_err() {
         },
     }
 }
+
+/// Makes sure that [`CloseMsg`] is built out of a [`CloseDescription`], and
+/// retryable [`CloseReason`]s are considered abnormal.
+#[wasm_bindgen_test]
+fn close_msg_from_close_frame() {
+    for reason in &[
+        CloseReason::Finished,
+        CloseReason::Evicted,
+        CloseReason::MessageTooLarge,
+    ] {
+        let code = reason.close_code();
+        let description =
+            serde_json::to_string(&CloseDescription::new(*reason)).unwrap();
+        assert_eq!(
+            CloseMsg::from_close_frame(code, &description),
+            CloseMsg::Normal(code, *reason),
+        );
+    }
+    for reason in &[
+        CloseReason::Draining,
+        CloseReason::Overloaded,
+        CloseReason::Drained,
+    ] {
+        let code = reason.close_code();
+        let description =
+            serde_json::to_string(&CloseDescription::new(*reason)).unwrap();
+        assert_eq!(
+            CloseMsg::from_close_frame(code, &description),
+            CloseMsg::Abnormal(code),
+        );
+    }
+
+    assert_eq!(
+        CloseMsg::from_close_frame(4004, ""),
+        CloseMsg::Abnormal(4004),
+    );
+    assert_eq!(
+        CloseMsg::from_close_frame(1000, ""),
+        CloseMsg::Abnormal(1000)
+    );
+    assert_eq!(
+        CloseMsg::from_close_frame(1006, ""),
+        CloseMsg::Abnormal(1006)
+    );
+}
@@ -0,0 +1,82 @@
+//! Tests for the [`medea_jason::platform::Callback`].
+
+use std::{cell::Cell, rc::Rc};
+
+use medea_jason::platform;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Returns [`platform::Function`] incrementing the provided `counter` on each
+/// call.
+fn counting_func(counter: Rc<Cell<u32>>) -> platform::Function<()> {
+    let closure = Closure::wrap(Box::new(move || {
+        counter.set(counter.get() + 1);
+    }) as Box<dyn FnMut()>);
+    platform::Function::from(js_sys::Function::from(closure.into_js_value()))
+}
+
+#[wasm_bindgen_test]
+fn unset_callback_is_not_called() {
+    let counter = Rc::new(Cell::new(0));
+    let cb = platform::Callback::<()>::default();
+    cb.set_func(counting_func(Rc::clone(&counter)));
+    assert!(cb.is_set());
+
+    cb.call0();
+    assert_eq!(counter.get(), 1);
+
+    cb.unset_func();
+    assert!(!cb.is_set());
+    cb.call0();
+    assert_eq!(counter.get(), 1);
+}
+
+#[wasm_bindgen_test]
+fn callback_can_be_unset_from_itself() {
+    let counter = Rc::new(Cell::new(0));
+    let cb = Rc::new(platform::Callback::<()>::default());
+
+    let closure = Closure::wrap(Box::new({
+        let cb = Rc::clone(&cb);
+        let counter = Rc::clone(&counter);
+        move || {
+            counter.set(counter.get() + 1);
+            cb.unset_func();
+        }
+    }) as Box<dyn FnMut()>);
+    cb.set_func(platform::Function::from(js_sys::Function::from(
+        closure.into_js_value(),
+    )));
+
+    cb.call0();
+    cb.call0();
+    assert_eq!(counter.get(), 1);
+    assert!(!cb.is_set());
+}
+
+#[wasm_bindgen_test]
+fn callback_can_be_replaced_from_itself() {
+    let first = Rc::new(Cell::new(0));
+    let second = Rc::new(Cell::new(0));
+    let cb = Rc::new(platform::Callback::<()>::default());
+
+    let closure = Closure::wrap(Box::new({
+        let cb = Rc::clone(&cb);
+        let first = Rc::clone(&first);
+        let second = Rc::clone(&second);
+        move || {
+            first.set(first.get() + 1);
+            cb.set_func(counting_func(Rc::clone(&second)));
+        }
+    }) as Box<dyn FnMut()>);
+    cb.set_func(platform::Function::from(js_sys::Function::from(
+        closure.into_js_value(),
+    )));
+
+    cb.call0();
+    cb.call0();
+    assert_eq!(first.get(), 1);
+    assert_eq!(second.get(), 1);
+}
@@ -1,3 +1,4 @@
 //! Utils functions and structures for the testing purposes.
 
+mod callback;
 mod resettable_delay;
@@ -75,6 +75,7 @@ macro_rules! js_callback {
     }}
 }
 
+mod log;
 mod media;
 mod peer;
 mod room;
@@ -192,6 +193,7 @@ pub fn get_test_tracks(
             },
             media_type: MediaType::Audio(AudioSettings {
                 required: is_audio_required,
+                opus: None,
             }),
         },
         Track {
@@ -220,7 +222,10 @@ pub fn get_test_recv_tracks() -> (Track, Track) {
                 sender: "bob".into(),
                 mid: Some("mid0".to_string()),
             },
-            media_type: MediaType::Audio(AudioSettings { required: false }),
+            media_type: MediaType::Audio(AudioSettings {
+                required: false,
+                opus: None,
+            }),
         },
         Track {
             id: TrackId(1),
@@ -85,6 +85,11 @@ pub struct AudioSettings {
     /// [`WebRtcPublishEndpoint`].
     #[serde(default)]
     pub publish_policy: PublishPolicy,
+
+    /// Parameters of the Opus codec to encode the audio of the
+    /// [`WebRtcPublishEndpoint`] with.
+    #[serde(default)]
+    pub opus: Option<OpusParameters>,
 }
 
 impl From<proto::web_rtc_publish_endpoint::AudioSettings> for AudioSettings {
@@ -96,6 +101,7 @@ impl From<proto::web_rtc_publish_endpoint::AudioSettings> for AudioSettings {
                 )
                 .unwrap_or_default()
                 .into(),
+            opus: proto.opus.map(Into::into),
         }
     }
 }
@@ -105,6 +111,53 @@ impl From<AudioSettings> for proto::web_rtc_publish_endpoint::AudioSettings {
         use proto::web_rtc_publish_endpoint::PublishPolicy;
         Self {
             publish_policy: PublishPolicy::from(from.publish_policy).into(),
+            opus: from.opus.map(Into::into),
+        }
+    }
+}
+
+/// Parameters of the Opus codec negotiated for the audio of the
+/// [`WebRtcPublishEndpoint`].
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct OpusParameters {
+    /// Indicator whether discontinuous transmission (`usedtx`) should be used.
+    #[serde(default)]
+    pub dtx: bool,
+
+    /// Indicator whether in-band forward error correction (`useinbandfec`)
+    /// should be used.
+    #[serde(default)]
+    pub inband_fec: bool,
+
+    /// Indicator whether stereo audio (`stereo`) should be encoded.
+    #[serde(default)]
+    pub stereo: bool,
+
+    /// Maximum average bitrate (`maxaveragebitrate`) of the encoded audio in
+    /// bits per second.
+    #[serde(default)]
+    pub max_average_bitrate: Option<u32>,
+}
+
+impl From<proto::web_rtc_publish_endpoint::OpusParameters> for OpusParameters {
+    fn from(proto: proto::web_rtc_publish_endpoint::OpusParameters) -> Self {
+        Self {
+            dtx: proto.dtx,
+            inband_fec: proto.inband_fec,
+            stereo: proto.stereo,
+            max_average_bitrate: Some(proto.max_average_bitrate)
+                .filter(|bitrate| *bitrate > 0),
+        }
+    }
+}
+
+impl From<OpusParameters> for proto::web_rtc_publish_endpoint::OpusParameters {
+    fn from(from: OpusParameters) -> Self {
+        Self {
+            dtx: from.dtx,
+            inband_fec: from.inband_fec,
+            stereo: from.stereo,
+            max_average_bitrate: from.max_average_bitrate.unwrap_or_default(),
         }
     }
 }
@@ -112,10 +165,15 @@ impl From<AudioSettings> for proto::web_rtc_publish_endpoint::AudioSettings {
 /// Settings for the video media type of the [`WebRtcPublishEndpoint`].
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct VideoSettings {
-    /// Publishing policy of the video media type in the
+    /// Publishing policy of the device video media type in the
     /// [`WebRtcPublishEndpoint`].
     #[serde(default)]
     pub publish_policy: PublishPolicy,
+
+    /// Publishing policy of the display video media type in the
+    /// [`WebRtcPublishEndpoint`].
+    #[serde(default)]
+    pub display_publish_policy: PublishPolicy,
 }
 
 impl From<VideoSettings> for proto::web_rtc_publish_endpoint::VideoSettings {
@@ -123,19 +181,26 @@ impl From<VideoSettings> for proto::web_rtc_publish_endpoint::VideoSettings {
         use proto::web_rtc_publish_endpoint::PublishPolicy;
         Self {
             publish_policy: PublishPolicy::from(from.publish_policy).into(),
+            display_publish_policy: PublishPolicy::from(
+                from.display_publish_policy,
+            )
+            .into(),
         }
     }
 }
 
 impl From<proto::web_rtc_publish_endpoint::VideoSettings> for VideoSettings {
     fn from(proto: proto::web_rtc_publish_endpoint::VideoSettings) -> Self {
+        use proto::web_rtc_publish_endpoint::PublishPolicy;
         Self {
-            publish_policy:
-                proto::web_rtc_publish_endpoint::PublishPolicy::from_i32(
-                    proto.publish_policy,
-                )
+            publish_policy: PublishPolicy::from_i32(proto.publish_policy)
                 .unwrap_or_default()
                 .into(),
+            display_publish_policy: PublishPolicy::from_i32(
+                proto.display_publish_policy,
+            )
+            .unwrap_or_default()
+            .into(),
         }
     }
 }
@@ -163,6 +228,14 @@ pub struct WebRtcPublishEndpoint {
     /// Settings for the video media type of the [`WebRtcPublishEndpoint`].
     #[serde(default)]
     pub video_settings: VideoSettings,
+
+    /// URL to which `OnStart` Control API callback will be sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_start: Option<String>,
+
+    /// URL to which `OnStop` Control API callback will be sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_stop: Option<String>,
 }
 
 impl WebRtcPublishEndpoint {
@@ -175,8 +248,8 @@ impl WebRtcPublishEndpoint {
             id,
             p2p: p2p as i32,
             force_relay: self.force_relay,
-            on_start: String::new(),
-            on_stop: String::new(),
+            on_start: self.on_start.unwrap_or_default(),
+            on_stop: self.on_stop.unwrap_or_default(),
             audio_settings: Some(self.audio_settings.into()),
             video_settings: Some(self.video_settings.into()),
         }
@@ -199,6 +272,8 @@ impl From<proto::WebRtcPublishEndpoint> for WebRtcPublishEndpoint {
                 .video_settings
                 .map(Into::into)
                 .unwrap_or_default(),
+            on_start: Some(proto.on_start).filter(|s| !s.is_empty()),
+            on_stop: Some(proto.on_stop).filter(|s| !s.is_empty()),
         }
     }
 }
@@ -219,6 +294,14 @@ pub struct WebRtcPlayEndpoint {
     /// Option to relay all media through a TURN server forcibly.
     #[serde(default)]
     pub force_relay: bool,
+
+    /// URL to which `OnStart` Control API callback will be sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_start: Option<String>,
+
+    /// URL to which `OnStop` Control API callback will be sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_stop: Option<String>,
 }
 
 impl WebRtcPlayEndpoint {
@@ -230,8 +313,8 @@ impl WebRtcPlayEndpoint {
             id,
             src: self.src,
             force_relay: self.force_relay,
-            on_start: String::new(),
-            on_stop: String::new(),
+            on_start: self.on_start.unwrap_or_default(),
+            on_stop: self.on_stop.unwrap_or_default(),
         }
     }
 }
@@ -242,6 +325,8 @@ impl From<proto::WebRtcPlayEndpoint> for WebRtcPlayEndpoint {
             id: proto.id,
             src: proto.src,
             force_relay: proto.force_relay,
+            on_start: Some(proto.on_start).filter(|s| !s.is_empty()),
+            on_stop: Some(proto.on_stop).filter(|s| !s.is_empty()),
         }
     }
 }
@@ -0,0 +1,202 @@
+//! Simple signalling load generator, provisioning a bunch of `Room`s at once
+//! for capacity smoke tests.
+
+use std::collections::HashMap;
+
+use actix_web::{
+    error::{Error as HttpError, ErrorInternalServerError as InternalError},
+    web::{Data, Json},
+    HttpResponse,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::client::Fid;
+
+use super::{
+    endpoint::{
+        AudioSettings, Endpoint, P2pMode, VideoSettings, WebRtcPlayEndpoint,
+        WebRtcPublishEndpoint,
+    },
+    member::Member,
+    room::{Room, RoomElement},
+    AppContext, Element, ErrorResponse,
+};
+
+/// ID of the [`WebRtcPublishEndpoint`] of each generated [`Member`].
+const PUBLISH_ENDPOINT_ID: &str = "publish";
+
+/// Request for generating a signalling load.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LoadRequest {
+    /// Number of `Room`s to be created.
+    pub rooms: u32,
+
+    /// Number of [`Member`]s to be created in each `Room`.
+    ///
+    /// Each [`Member`] publishes to and plays from all the other [`Member`]s
+    /// of its `Room`.
+    pub members: u32,
+
+    /// Prefix of the created `Room`s IDs.
+    ///
+    /// `Room`s are named as `{prefix}-{index}`.
+    #[serde(default = "LoadRequest::default_prefix")]
+    pub prefix: String,
+}
+
+impl LoadRequest {
+    /// Returns the default prefix of the created `Room`s IDs.
+    #[inline]
+    fn default_prefix() -> String {
+        "load".to_owned()
+    }
+
+    /// Builds the `Room` with the provided `room_id` with pairwise
+    /// interconnected [`Member`]s.
+    fn room(&self, room_id: &str) -> Room {
+        let member_ids: Vec<_> =
+            (0..self.members).map(|i| format!("member-{}", i)).collect();
+        let pipeline = member_ids
+            .iter()
+            .map(|member_id| {
+                let mut pipeline = HashMap::new();
+                pipeline.insert(
+                    PUBLISH_ENDPOINT_ID.to_owned(),
+                    Endpoint::WebRtcPublishEndpoint(WebRtcPublishEndpoint {
+                        id: PUBLISH_ENDPOINT_ID.to_owned(),
+                        p2p: P2pMode::Always,
+                        force_relay: false,
+                        audio_settings: AudioSettings::default(),
+                        video_settings: VideoSettings::default(),
+                        on_start: None,
+                        on_stop: None,
+                    }),
+                );
+                for partner_id in
+                    member_ids.iter().filter(|id| *id != member_id)
+                {
+                    let id = format!("play-{}", partner_id);
+                    pipeline.insert(
+                        id.clone(),
+                        Endpoint::WebRtcPlayEndpoint(WebRtcPlayEndpoint {
+                            id,
+                            src: format!(
+                                "local://{}/{}/{}",
+                                room_id, partner_id, PUBLISH_ENDPOINT_ID,
+                            ),
+                            force_relay: false,
+                            on_start: None,
+                            on_stop: None,
+                        }),
+                    );
+                }
+
+                let member = Member {
+                    id: member_id.clone(),
+                    pipeline,
+                    credentials: None,
+                    on_join: None,
+                    on_leave: None,
+                    on_negotiation_timeout: None,
+                    idle_timeout: None,
+                    reconnect_timeout: None,
+                    ping_interval: None,
+                    metadata: HashMap::new(),
+                    features: HashMap::new(),
+                };
+                (member_id.clone(), RoomElement::Member(member))
+            })
+            .collect();
+
+        Room {
+            id: room_id.to_owned(),
+            pipeline,
+        }
+    }
+}
+
+/// Numbers of the elements created by a [`LoadRequest`].
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct CreatedElements {
+    /// Number of the created `Room`s.
+    pub rooms: u32,
+
+    /// Number of the created [`Member`]s.
+    pub members: u32,
+
+    /// Number of the created `Endpoint`s.
+    pub endpoints: u32,
+}
+
+/// Response on a [`LoadRequest`].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct LoadResponse {
+    /// Numbers of the elements created before an `error` occurred (if any).
+    pub created: CreatedElements,
+
+    /// URIs with which [Jason] can connect the created [`Member`]s.
+    ///
+    /// [Jason]: https://github.com/instrumentisto/medea/tree/master/jason
+    pub sids: HashMap<String, String>,
+
+    /// Error if something happened on [Control API]'s side.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorResponse>,
+}
+
+impl From<LoadResponse> for HttpResponse {
+    fn from(resp: LoadResponse) -> Self {
+        if resp.error.is_some() {
+            Self::BadRequest().json(resp)
+        } else {
+            Self::Ok().json(resp)
+        }
+    }
+}
+
+/// [`actix_web`] REST API endpoint which creates `Room`s with pairwise
+/// interconnected [`Member`]s, as described by the provided [`LoadRequest`].
+///
+/// Stops on the first `Room` failed to be created, reporting the elements
+/// created so far.
+///
+/// # Errors
+///
+/// Errors if gRPC request to [Medea] fails.
+///
+/// [Medea]: https://github.com/instrumentisto/medea
+#[allow(clippy::needless_pass_by_value)]
+pub async fn create_load(
+    state: Data<AppContext>,
+    req: Json<LoadRequest>,
+) -> Result<HttpResponse, HttpError> {
+    let req = req.into_inner();
+    let mut resp = LoadResponse {
+        created: CreatedElements::default(),
+        sids: HashMap::new(),
+        error: None,
+    };
+
+    for i in 0..req.rooms {
+        let room_id = format!("{}-{}", req.prefix, i);
+        let room = req.room(&room_id);
+        let created = state
+            .client
+            .create(room_id, Fid::from(()), Element::Room(room))
+            .await
+            .map_err(|e| InternalError(format!("{:?}", e)))?;
+        if let Some(error) = created.error {
+            resp.error = Some(error.into());
+            break;
+        }
+
+        resp.created.rooms += 1;
+        resp.created.members += req.members;
+        resp.created.endpoints += req.members * req.members;
+        resp.sids.extend(created.sid);
+    }
+
+    Ok(resp.into())
+}
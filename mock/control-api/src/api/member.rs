@@ -34,6 +34,10 @@ pub struct Member {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub on_leave: Option<String>,
 
+    /// URL to which `OnNegotiationTimeout` Control API callback will be sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_negotiation_timeout: Option<String>,
+
     /// Timeout of receiving heartbeat messages from this [`Member`] via Client
     /// API. Once reached, the [`Member`] is considered being idle.
     #[serde(default, with = "humantime_serde")]
@@ -47,6 +51,16 @@ pub struct Member {
     /// Interval of sending pings from Medea to this [`Member`] via Client API.
     #[serde(default, with = "humantime_serde")]
     pub ping_interval: Option<Duration>,
+
+    /// Arbitrary key-value metadata of this [`Member`], which is delivered to
+    /// other `Member`s of the `Room`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+
+    /// Key-value feature flags of this [`Member`], which are delivered to this
+    /// [`Member`] only.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub features: HashMap<String, String>,
 }
 
 impl Member {
@@ -65,9 +79,14 @@ impl Member {
             credentials: self.credentials.map(Into::into),
             on_join: self.on_join.unwrap_or_default(),
             on_leave: self.on_leave.unwrap_or_default(),
+            on_negotiation_timeout: self
+                .on_negotiation_timeout
+                .unwrap_or_default(),
             idle_timeout: self.idle_timeout.map(Into::into),
             reconnect_timeout: self.reconnect_timeout.map(Into::into),
             ping_interval: self.ping_interval.map(Into::into),
+            metadata: self.metadata,
+            features: self.features,
         }
     }
 
@@ -94,6 +113,8 @@ impl From<proto::Member> for Member {
             credentials: proto.credentials.map(Into::into),
             on_join: Some(proto.on_join).filter(|s| !s.is_empty()),
             on_leave: Some(proto.on_leave).filter(|s| !s.is_empty()),
+            on_negotiation_timeout: Some(proto.on_negotiation_timeout)
+                .filter(|s| !s.is_empty()),
             idle_timeout: proto.idle_timeout.map(|dur| dur.try_into().unwrap()),
             reconnect_timeout: proto
                 .reconnect_timeout
@@ -101,6 +122,8 @@ impl From<proto::Member> for Member {
             ping_interval: proto
                 .ping_interval
                 .map(|dur| dur.try_into().unwrap()),
+            metadata: proto.metadata,
+            features: proto.features,
         }
     }
 }
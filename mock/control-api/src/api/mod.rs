@@ -3,6 +3,7 @@
 //! [Control API]: https://tinyurl.com/yxsqplq7
 
 pub mod endpoint;
+pub mod load;
 pub mod member;
 pub mod room;
 pub mod ws;
@@ -112,6 +113,14 @@ pub async fn run(
             .service(
                 web::resource("/callbacks").route(web::get().to(get_callbacks)),
             )
+            .service(web::resource("/spec").route(web::put().to(import_spec)))
+            .service(
+                web::resource("/load").route(web::post().to(load::create_load)),
+            )
+            .service(
+                web::resource("/spec/{room_id}")
+                    .route(web::get().to(export_spec)),
+            )
     })
     .bind(args.value_of("addr").unwrap())
     .unwrap()
@@ -177,6 +186,59 @@ pub async fn get_callbacks(
         .map(|callbacks| HttpResponse::Ok().json(&callbacks.unwrap()))
 }
 
+/// [`actix_web`] REST API endpoint which returns YAML spec of the `Room` with
+/// the provided ID.
+///
+/// # Errors
+///
+/// Errors if gRPC request to [Medea] fails.
+///
+/// [Medea]: https://github.com/instrumentisto/medea
+#[allow(clippy::needless_pass_by_value)]
+pub async fn export_spec(
+    path: Path<String>,
+    state: Data<AppContext>,
+) -> Result<HttpResponse, HttpError> {
+    let resp = state
+        .client
+        .export_spec(path.into_inner())
+        .await
+        .map_err(|e| InternalError(format!("{:?}", e)))?;
+    Ok(resp.error.map_or_else(
+        || {
+            HttpResponse::Ok()
+                .content_type("application/yaml")
+                .body(resp.spec)
+        },
+        |error| {
+            HttpResponse::BadRequest().json(Response {
+                error: Some(error.into()),
+            })
+        },
+    ))
+}
+
+/// [`actix_web`] REST API endpoint which applies the provided YAML spec of a
+/// `Room`.
+///
+/// # Errors
+///
+/// Errors if gRPC request to [Medea] fails.
+///
+/// [Medea]: https://github.com/instrumentisto/medea
+#[allow(clippy::needless_pass_by_value)]
+pub async fn import_spec(
+    state: Data<AppContext>,
+    spec: String,
+) -> Result<HttpResponse, HttpError> {
+    state
+        .client
+        .import_spec(spec)
+        .await
+        .map_err(|e| InternalError(format!("{:?}", e)))
+        .map(|r| CreateResponse::from(r).into())
+}
+
 /// Implementation of `Delete` requests to [Control API] mock.
 ///
 /// [Control API]: https://tinyurl.com/yxsqplq7
@@ -11,6 +11,12 @@ use serde::{Deserialize, Serialize};
 pub enum CallbackEvent {
     OnJoin(join::OnJoin),
     OnLeave(leave::OnLeave),
+    OnStart(traffic::OnStart),
+    OnStop(traffic::OnStop),
+    OnCreateProgress(create::OnCreateProgress),
+    OnCreateComplete(create::OnCreateComplete),
+    OnNegotiationTimeout(negotiation::OnNegotiationTimeout),
+    OnPatchApplied(patch::OnPatchApplied),
 }
 
 impl From<proto::request::Event> for CallbackEvent {
@@ -22,6 +28,24 @@ impl From<proto::request::Event> for CallbackEvent {
             proto::request::Event::OnJoin(on_join) => {
                 Self::OnJoin(on_join.into())
             }
+            proto::request::Event::OnStart(on_start) => {
+                Self::OnStart(on_start.into())
+            }
+            proto::request::Event::OnStop(on_stop) => {
+                Self::OnStop(on_stop.into())
+            }
+            proto::request::Event::OnCreateProgress(progress) => {
+                Self::OnCreateProgress(progress.into())
+            }
+            proto::request::Event::OnCreateComplete(complete) => {
+                Self::OnCreateComplete(complete.into())
+            }
+            proto::request::Event::OnNegotiationTimeout(timeout) => {
+                Self::OnNegotiationTimeout(timeout.into())
+            }
+            proto::request::Event::OnPatchApplied(applied) => {
+                Self::OnPatchApplied(applied.into())
+            }
         }
     }
 }
@@ -54,13 +78,20 @@ mod join {
     use medea_control_api_proto::grpc::callback as proto;
     use serde::{Deserialize, Serialize};
 
+    use super::session::ClientSession;
+
     /// `OnJoin` callback for Control API.
     #[derive(Clone, Deserialize, Serialize)]
-    pub struct OnJoin;
+    pub struct OnJoin {
+        /// Session of the `Client` the `Member` has joined with.
+        pub session: Option<ClientSession>,
+    }
 
     impl From<proto::OnJoin> for OnJoin {
-        fn from(_: proto::OnJoin) -> Self {
-            Self
+        fn from(proto: proto::OnJoin) -> Self {
+            Self {
+                session: proto.session.map(Into::into),
+            }
         }
     }
 }
@@ -71,11 +102,24 @@ mod leave {
     use medea_control_api_proto::grpc::callback as proto;
     use serde::{Deserialize, Serialize};
 
+    use super::session::ClientSession;
+
     /// `OnLeave` callback of Control API.
     #[derive(Clone, Deserialize, Serialize)]
     pub struct OnLeave {
         /// Reason of why `Member` leaves.
         pub reason: OnLeaveReason,
+
+        /// Session of the `Client` the `Member` has left with.
+        pub session: Option<ClientSession>,
+
+        /// Duration (in milliseconds) the `Member` has been in the `Room`
+        /// for.
+        pub duration_ms: u64,
+
+        /// Reason which the `Client`'s connection has been closed by server
+        /// with, if any.
+        pub close_reason: Option<String>,
     }
 
     impl From<proto::OnLeave> for OnLeave {
@@ -84,6 +128,10 @@ mod leave {
                 reason: proto::on_leave::Reason::from_i32(proto.reason)
                     .unwrap_or_default()
                     .into(),
+                session: proto.session.map(Into::into),
+                duration_ms: proto.duration_ms,
+                close_reason: Some(proto.close_reason)
+                    .filter(|r| !r.is_empty()),
             }
         }
     }
@@ -117,3 +165,237 @@ mod leave {
         }
     }
 }
+
+/// Entities describing `Client` sessions of `Member`s, reported in `on_join`
+/// and `on_leave` callbacks.
+mod session {
+    use medea_control_api_proto::grpc::callback as proto;
+    use serde::{Deserialize, Serialize};
+
+    /// Metadata of a `Client` session of a `Member`.
+    #[derive(Clone, Deserialize, Serialize)]
+    pub struct ClientSession {
+        /// Remote address of the `Client`'s connection, if known.
+        pub remote_addr: Option<String>,
+
+        /// User agent of the `Client`, if reported.
+        pub user_agent: Option<String>,
+
+        /// MIME types of the codecs supported by the `Client`.
+        pub codecs: Vec<String>,
+
+        /// Indicator whether the `Client` supports sending simulcast.
+        pub simulcast: bool,
+
+        /// Indicator whether the `Client` supports insertable streams.
+        pub insertable_streams: bool,
+    }
+
+    impl From<proto::ClientSession> for ClientSession {
+        fn from(proto: proto::ClientSession) -> Self {
+            Self {
+                remote_addr: Some(proto.remote_addr).filter(|a| !a.is_empty()),
+                user_agent: Some(proto.user_agent).filter(|a| !a.is_empty()),
+                codecs: proto.codecs,
+                simulcast: proto.simulcast,
+                insertable_streams: proto.insertable_streams,
+            }
+        }
+    }
+}
+
+/// `on_start` and `on_stop` callbacks' related entities and implementations.
+mod traffic {
+    use medea_control_api_proto::grpc::callback as proto;
+    use serde::{Deserialize, Serialize};
+
+    /// `OnStart` callback of Control API.
+    #[derive(Clone, Deserialize, Serialize)]
+    pub struct OnStart {
+        /// Type of the media which traffic started flowing.
+        pub media_type: MediaType,
+
+        /// Direction of the `Endpoint`'s media traffic.
+        pub media_direction: MediaDirection,
+    }
+
+    impl From<proto::OnStart> for OnStart {
+        fn from(proto: proto::OnStart) -> Self {
+            Self {
+                media_type: proto::MediaType::from_i32(proto.media_type)
+                    .unwrap_or_default()
+                    .into(),
+                media_direction: proto::MediaDirection::from_i32(
+                    proto.media_direction,
+                )
+                .unwrap_or_default()
+                .into(),
+            }
+        }
+    }
+
+    /// `OnStop` callback of Control API.
+    #[derive(Clone, Deserialize, Serialize)]
+    pub struct OnStop {
+        /// Type of the media which traffic stopped flowing.
+        pub media_type: MediaType,
+
+        /// Direction of the `Endpoint`'s media traffic.
+        pub media_direction: MediaDirection,
+    }
+
+    impl From<proto::OnStop> for OnStop {
+        fn from(proto: proto::OnStop) -> Self {
+            Self {
+                media_type: proto::MediaType::from_i32(proto.media_type)
+                    .unwrap_or_default()
+                    .into(),
+                media_direction: proto::MediaDirection::from_i32(
+                    proto.media_direction,
+                )
+                .unwrap_or_default()
+                .into(),
+            }
+        }
+    }
+
+    /// Type of the media which traffic starts/stops flowing.
+    #[derive(Clone, Copy, Deserialize, Serialize)]
+    pub enum MediaType {
+        /// Audio traffic.
+        Audio,
+
+        /// Video traffic.
+        Video,
+
+        /// Audio and video traffic.
+        Both,
+    }
+
+    impl From<proto::MediaType> for MediaType {
+        fn from(proto: proto::MediaType) -> Self {
+            match proto {
+                proto::MediaType::Audio => Self::Audio,
+                proto::MediaType::Video => Self::Video,
+                proto::MediaType::Both => Self::Both,
+            }
+        }
+    }
+
+    /// Direction of the `Endpoint`'s media traffic.
+    #[derive(Clone, Copy, Deserialize, Serialize)]
+    pub enum MediaDirection {
+        /// `Endpoint` publishes media.
+        Publish,
+
+        /// `Endpoint` plays media.
+        Play,
+    }
+
+    impl From<proto::MediaDirection> for MediaDirection {
+        fn from(proto: proto::MediaDirection) -> Self {
+            match proto {
+                proto::MediaDirection::Publish => Self::Publish,
+                proto::MediaDirection::Play => Self::Play,
+            }
+        }
+    }
+}
+
+/// `on_progress` callbacks' related entities and implementations.
+mod create {
+    use medea_control_api_proto::grpc::callback as proto;
+    use serde::{Deserialize, Serialize};
+
+    /// `OnCreateProgress` callback of Control API.
+    #[derive(Clone, Deserialize, Serialize)]
+    pub struct OnCreateProgress {
+        /// ID of the creation operation.
+        pub operation_id: String,
+
+        /// Number of the already provisioned elements.
+        pub done: u32,
+
+        /// Total number of the elements to be provisioned.
+        pub total: u32,
+    }
+
+    impl From<proto::OnCreateProgress> for OnCreateProgress {
+        fn from(proto: proto::OnCreateProgress) -> Self {
+            Self {
+                operation_id: proto.operation_id,
+                done: proto.done,
+                total: proto.total,
+            }
+        }
+    }
+
+    /// `OnCreateComplete` callback of Control API.
+    #[derive(Clone, Deserialize, Serialize)]
+    pub struct OnCreateComplete {
+        /// ID of the creation operation.
+        pub operation_id: String,
+
+        /// Error of the creation, if it has failed.
+        pub error: Option<String>,
+    }
+
+    impl From<proto::OnCreateComplete> for OnCreateComplete {
+        fn from(proto: proto::OnCreateComplete) -> Self {
+            Self {
+                operation_id: proto.operation_id,
+                error: Some(proto.error).filter(|e| !e.is_empty()),
+            }
+        }
+    }
+}
+
+/// `on_negotiation_timeout` callback's related entities and implementations.
+mod negotiation {
+    use medea_control_api_proto::grpc::callback as proto;
+    use serde::{Deserialize, Serialize};
+
+    /// `OnNegotiationTimeout` callback of Control API.
+    #[derive(Clone, Deserialize, Serialize)]
+    pub struct OnNegotiationTimeout {
+        /// ID of the removed `Peer`.
+        pub peer_id: u32,
+
+        /// ID of the `Member` owning the removed partner `Peer`.
+        pub partner_member_id: String,
+    }
+
+    impl From<proto::OnNegotiationTimeout> for OnNegotiationTimeout {
+        fn from(proto: proto::OnNegotiationTimeout) -> Self {
+            Self {
+                peer_id: proto.peer_id,
+                partner_member_id: proto.partner_member_id,
+            }
+        }
+    }
+}
+
+/// `on_applied` callbacks' related entities and implementations.
+mod patch {
+    use medea_control_api_proto::grpc::callback as proto;
+    use serde::{Deserialize, Serialize};
+
+    /// `OnPatchApplied` callback of Control API.
+    #[derive(Clone, Deserialize, Serialize)]
+    pub struct OnPatchApplied {
+        /// ID of the patch operation.
+        pub operation_id: String,
+
+        /// Error of the patch operation, if it has failed.
+        pub error: Option<String>,
+    }
+
+    impl From<proto::OnPatchApplied> for OnPatchApplied {
+        fn from(proto: proto::OnPatchApplied) -> Self {
+            Self {
+                operation_id: proto.operation_id,
+                error: Some(proto.error).filter(|e| !e.is_empty()),
+            }
+        }
+    }
+}
@@ -128,6 +128,7 @@ impl ControlClient {
         let req = proto::CreateRequest {
             parent_fid: fid.into(),
             el: Some(el),
+            on_progress: String::new(),
         };
 
         let response = self.get_client().create(tonic::Request::new(req)).await;
@@ -188,6 +189,38 @@ impl ControlClient {
             .map(tonic::Response::into_inner)
     }
 
+    /// Exports YAML spec of the `Room` with the provided ID from Control API.
+    ///
+    /// # Errors
+    ///
+    /// Errors if gRPC request fails.
+    pub async fn export_spec(
+        &self,
+        room_id: String,
+    ) -> Result<proto::ExportSpecResponse, Status> {
+        let req = proto::ExportSpecRequest { room_fid: room_id };
+        self.get_client()
+            .export_spec(tonic::Request::new(req))
+            .await
+            .map(tonic::Response::into_inner)
+    }
+
+    /// Imports the provided YAML spec of a `Room` via Control API.
+    ///
+    /// # Errors
+    ///
+    /// Errors if gRPC request fails.
+    pub async fn import_spec(
+        &self,
+        spec: String,
+    ) -> Result<proto::CreateResponse, Status> {
+        let req = proto::ImportSpecRequest { spec };
+        self.get_client()
+            .import_spec(tonic::Request::new(req))
+            .await
+            .map(tonic::Response::into_inner)
+    }
+
     /// Deletes element from Control API by FID.
     ///
     /// # Errors
@@ -0,0 +1,436 @@
+//! Protocol conformance test vectors, published as the `conformance.json` file
+//! of this crate for verifying third-party `Client` implementations.
+//!
+//! Vectors are built from the protocol types and checked to be parsed (or
+//! rejected) by them, so the published file is verified to be up-to-date.
+//! Run tests with the `MEDEA_UPDATE_CONFORMANCE` environment variable set to
+//! regenerate it.
+
+use std::{
+    collections::{HashMap, HashSet},
+    env, fs,
+    path::PathBuf,
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    state, AudioSettings, ClientCapabilities, ClientMsg, CloseReason, Command,
+    Credential, Direction, Event, IceCandidate, IceServer, MediaSourceKind,
+    MediaType, MemberId, NegotiationRole, PeerId, RoomId, RpcSettings,
+    ServerMsg, Track, TrackId, VideoSettings,
+};
+
+/// Version of the conformance vectors format.
+const VERSION: u32 = 1;
+
+/// Returns path to the published conformance vectors file.
+fn path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("conformance.json")
+}
+
+/// Builds a vector of the valid `msg`, checking it to be parsed back.
+fn valid<T: Serialize + DeserializeOwned>(name: &str, msg: &T) -> Value {
+    let json = serde_json::to_string(msg).unwrap();
+    assert!(
+        serde_json::from_str::<T>(&json).is_ok(),
+        "valid `{}` vector is rejected",
+        name,
+    );
+    json!({
+        "name": name,
+        "valid": true,
+        "json": serde_json::from_str::<Value>(&json).unwrap(),
+    })
+}
+
+/// Builds a vector of the invalid `json` message, checking it to be rejected.
+fn invalid<T: DeserializeOwned>(name: &str, json: &Value) -> Value {
+    assert!(
+        serde_json::from_str::<T>(&json.to_string()).is_err(),
+        "invalid `{}` vector is parsed",
+        name,
+    );
+    json!({
+        "name": name,
+        "valid": false,
+        "json": json,
+    })
+}
+
+/// Builds a vector of the [`state::Room`] transition from the `from` state to
+/// the `to` one, checking the produced delta to transform the states.
+fn transition(name: &str, from: &state::Room, to: &state::Room) -> Value {
+    let delta = from.diff(to);
+    let mut applied = from.clone();
+    applied.apply_delta(delta.clone());
+    assert_eq!(&applied, to, "`{}` delta is not applied", name);
+
+    json!({
+        "name": name,
+        "from": from,
+        "from_checksum": from.checksum(),
+        "delta": delta,
+        "to": to,
+        "to_checksum": to.checksum(),
+    })
+}
+
+/// Wraps the provided [`Command`] into a [`ClientMsg`].
+fn command(command: Command) -> ClientMsg {
+    ClientMsg::Command {
+        room_id: RoomId::from("room"),
+        command,
+    }
+}
+
+/// Wraps the provided [`Event`] into a [`ServerMsg`].
+fn event(event: Event, seq: Option<u64>) -> ServerMsg {
+    ServerMsg::Event {
+        room_id: RoomId::from("room"),
+        event,
+        seq,
+    }
+}
+
+/// Returns an [`state::Peer`] with the provided ID, sending audio to `bob`.
+fn peer(id: u32) -> state::Peer {
+    let sender = state::Sender {
+        id: TrackId(0),
+        mid: None,
+        ssrc: None,
+        media_type: MediaType::Audio(AudioSettings {
+            required: true,
+            opus: None,
+        }),
+        receivers: vec![MemberId::from("bob")],
+        enabled_individual: true,
+        enabled_general: true,
+        muted: false,
+    };
+    let receiver = state::Receiver {
+        id: TrackId(1),
+        mid: None,
+        ssrc: None,
+        media_type: MediaType::Video(VideoSettings {
+            required: false,
+            source_kind: MediaSourceKind::Device,
+        }),
+        sender_id: MemberId::from("bob"),
+        enabled_individual: true,
+        enabled_general: true,
+        muted: false,
+    };
+    state::Peer {
+        id: PeerId(id),
+        senders: vec![(sender.id, sender)].into_iter().collect(),
+        receivers: vec![(receiver.id, receiver)].into_iter().collect(),
+        force_relay: false,
+        ice_servers: Vec::new(),
+        negotiation_role: None,
+        local_sdp: None,
+        remote_sdp: None,
+        restart_ice: false,
+        ice_candidates: HashSet::new(),
+    }
+}
+
+/// Returns a [`state::Room`] with the provided [`state::Peer`]s.
+fn room(peers: Vec<state::Peer>) -> state::Room {
+    state::Room {
+        peers: peers.into_iter().map(|p| (p.id, p)).collect(),
+    }
+}
+
+/// Returns vectors of [`ClientMsg`]s.
+fn client_messages() -> Vec<Value> {
+    vec![
+        valid("pong", &ClientMsg::Pong(1)),
+        valid(
+            "join_room",
+            &command(Command::JoinRoom {
+                member_id: MemberId::from("alice"),
+                credential: Credential::from("token"),
+                capabilities: ClientCapabilities::default(),
+            }),
+        ),
+        valid(
+            "leave_room",
+            &command(Command::LeaveRoom {
+                member_id: MemberId::from("alice"),
+            }),
+        ),
+        valid(
+            "make_sdp_offer",
+            &command(Command::MakeSdpOffer {
+                peer_id: PeerId(1),
+                sdp_offer: String::from("v=0"),
+                mids: vec![(TrackId(0), String::from("0"))]
+                    .into_iter()
+                    .collect(),
+                transceivers_statuses: vec![(TrackId(0), true)]
+                    .into_iter()
+                    .collect(),
+            }),
+        ),
+        invalid::<ClientMsg>("unknown_message", &json!({"Ping": 1})),
+        invalid::<ClientMsg>("negative_pong", &json!({"Pong": -1})),
+        invalid::<ClientMsg>(
+            "missing_room_id",
+            &json!({"Command": {
+                "command": {
+                    "command": "LeaveRoom",
+                    "data": {"member_id": "alice"},
+                },
+            }}),
+        ),
+        invalid::<ClientMsg>(
+            "unknown_command",
+            &json!({"Command": {
+                "room_id": "room",
+                "command": {"command": "Unknown", "data": {}},
+            }}),
+        ),
+    ]
+}
+
+/// Returns vectors of [`ServerMsg`]s.
+fn server_messages() -> Vec<Value> {
+    let synced = room(vec![peer(1)]);
+    vec![
+        valid("ping", &ServerMsg::Ping(1)),
+        valid(
+            "rpc_settings",
+            &ServerMsg::RpcSettings(RpcSettings {
+                idle_timeout_ms: 10_000,
+                ping_interval_ms: 3_000,
+                features: HashMap::new(),
+            }),
+        ),
+        valid(
+            "room_joined",
+            &event(
+                Event::RoomJoined {
+                    member_id: MemberId::from("alice"),
+                },
+                Some(1),
+            ),
+        ),
+        valid(
+            "peer_created",
+            &event(
+                Event::PeerCreated {
+                    peer_id: PeerId(1),
+                    negotiation_role: NegotiationRole::Offerer,
+                    tracks: vec![Track {
+                        id: TrackId(0),
+                        direction: Direction::Send {
+                            receivers: vec![MemberId::from("bob")],
+                            mid: None,
+                        },
+                        media_type: MediaType::Audio(AudioSettings {
+                            required: true,
+                            opus: None,
+                        }),
+                    }],
+                    ice_servers: vec![IceServer {
+                        urls: vec![String::from("stun:stun.example.com")],
+                        username: None,
+                        credential: None,
+                    }],
+                    force_relay: false,
+                },
+                Some(2),
+            ),
+        ),
+        valid(
+            "sdp_answer_made",
+            &event(
+                Event::SdpAnswerMade {
+                    peer_id: PeerId(1),
+                    sdp_answer: String::from("v=0"),
+                },
+                Some(3),
+            ),
+        ),
+        valid(
+            "ice_candidate_discovered",
+            &event(
+                Event::IceCandidateDiscovered {
+                    peer_id: PeerId(1),
+                    candidate: IceCandidate {
+                        candidate: String::from(
+                            "candidate:1 1 udp 2113937151 192.168.0.1 51472 \
+                             typ host",
+                        ),
+                        sdp_m_line_index: Some(0),
+                        sdp_mid: Some(String::from("0")),
+                    },
+                },
+                Some(4),
+            ),
+        ),
+        valid(
+            "state_synchronized",
+            &event(
+                Event::StateSynchronized {
+                    state: synced.clone(),
+                },
+                None,
+            ),
+        ),
+        valid(
+            "state_checksum",
+            &event(
+                Event::StateChecksum {
+                    checksum: synced.checksum(),
+                },
+                None,
+            ),
+        ),
+        valid(
+            "peers_removed",
+            &event(
+                Event::PeersRemoved {
+                    peer_ids: vec![PeerId(1)],
+                },
+                Some(5),
+            ),
+        ),
+        valid(
+            "room_left",
+            &event(
+                Event::RoomLeft {
+                    close_reason: CloseReason::Finished,
+                    retry_after_ms: None,
+                },
+                None,
+            ),
+        ),
+        invalid::<ServerMsg>(
+            "unknown_message",
+            &json!({"msg": "Unknown", "data": null}),
+        ),
+        invalid::<ServerMsg>(
+            "string_ping",
+            &json!({"msg": "Ping", "data": "1"}),
+        ),
+        invalid::<ServerMsg>(
+            "missing_member_id",
+            &json!({"msg": "Event", "data": {
+                "room_id": "room",
+                "event": {"event": "RoomJoined", "data": {}},
+            }}),
+        ),
+        invalid::<ServerMsg>(
+            "unknown_close_reason",
+            &json!({"msg": "Event", "data": {
+                "room_id": "room",
+                "event": {
+                    "event": "RoomLeft",
+                    "data": {"close_reason": "Unknown"},
+                },
+            }}),
+        ),
+    ]
+}
+
+/// Returns vectors of [`CloseReason`]s and their WebSocket close codes.
+fn close_codes() -> Vec<Value> {
+    [
+        CloseReason::Finished,
+        CloseReason::Reconnected,
+        CloseReason::Idle,
+        CloseReason::Rejected,
+        CloseReason::InternalError,
+        CloseReason::Evicted,
+        CloseReason::Draining,
+        CloseReason::Overloaded,
+        CloseReason::Drained,
+        CloseReason::MessageTooLarge,
+    ]
+    .iter()
+    .map(|reason| {
+        json!({
+            "reason": reason,
+            "code": reason.close_code(),
+            "retryable": reason.is_retryable(),
+        })
+    })
+    .collect()
+}
+
+/// Returns vectors of [`state::Room`] transitions.
+fn state_transitions() -> Vec<Value> {
+    let empty = room(Vec::new());
+    let joined = room(vec![peer(1)]);
+
+    let mut muted = joined.clone();
+    let sender = muted
+        .peers
+        .get_mut(&PeerId(1))
+        .unwrap()
+        .senders
+        .get_mut(&TrackId(0))
+        .unwrap();
+    sender.muted = true;
+
+    let mut negotiated = joined.clone();
+    negotiated.peers.get_mut(&PeerId(1)).unwrap().local_sdp =
+        Some(String::from("v=0"));
+
+    let mut unsubscribed = joined.clone();
+    let receivers =
+        &mut unsubscribed.peers.get_mut(&PeerId(1)).unwrap().receivers;
+    drop(receivers.remove(&TrackId(1)));
+
+    vec![
+        transition("add_peer", &empty, &joined),
+        transition("mute_sender", &joined, &muted),
+        transition("set_local_sdp", &joined, &negotiated),
+        transition("unset_local_sdp", &negotiated, &joined),
+        transition("remove_receiver", &joined, &unsubscribed),
+        transition("remove_peer", &joined, &empty),
+    ]
+}
+
+/// Returns all the conformance vectors.
+fn vectors() -> Value {
+    json!({
+        "version": VERSION,
+        "client_messages": client_messages(),
+        "server_messages": server_messages(),
+        "close_codes": close_codes(),
+        "state_transitions": state_transitions(),
+    })
+}
+
+#[test]
+fn published_vectors_are_up_to_date() {
+    let vectors = vectors();
+    if env::var_os("MEDEA_UPDATE_CONFORMANCE").is_some() {
+        let json = serde_json::to_string_pretty(&vectors).unwrap();
+        fs::write(path(), json + "\n").unwrap();
+        return;
+    }
+
+    let published: Value =
+        serde_json::from_str(&fs::read_to_string(path()).unwrap()).unwrap();
+    assert_eq!(
+        published, vectors,
+        "`conformance.json` is outdated, regenerate it with \
+         `MEDEA_UPDATE_CONFORMANCE=1` environment variable set",
+    );
+}
+
+#[test]
+fn checksum_ignores_negotiation() {
+    let transitions = state_transitions();
+    let set_local_sdp = transitions
+        .iter()
+        .find(|t| t["name"] == "set_local_sdp")
+        .unwrap();
+
+    assert_eq!(set_local_sdp["from_checksum"], set_local_sdp["to_checksum"]);
+}
@@ -6,8 +6,17 @@
 //! [`Serialize`] implementation for [`Command`]s.
 //! - `medea`: Enables [`Deserialize`] implementation for [`Command`]s, and
 //! [`Serialize`] implementation for [`Event`]s.
+//! - `stats`: Enables RTC Stats DTOs, `PeerMetrics::RtcStats` and
+//! `PeerMetrics::SelectedCandidatePairChanged` variants.
 //! - `extended-stats`: Enables unused RTC Stats DTOs.
 //!
+//! Clients only needing [`Command`]s and [`Event`]s may disable default
+//! features to compile this crate faster. Without the `jason` feature,
+//! [`EventHandler`] is a plain (not [`async_trait`]) trait, so no
+//! [`async_trait`] crate is required.
+//!
+//! [`async_trait`]: https://docs.rs/async-trait
+//!
 //! # Contribution guide
 //!
 //! Avoid using 64 bit types. [`medea-jason`] uses [wasm-bindgen] to interop
@@ -28,15 +37,90 @@
 #![forbid(non_ascii_idents, unsafe_code)]
 
 pub mod state;
+#[cfg(feature = "stats")]
+#[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
 pub mod stats;
 
-use std::collections::HashMap;
+#[cfg(all(test, feature = "jason", feature = "medea"))]
+mod conformance;
+
+use std::{collections::HashMap, convert::TryFrom as _, time::Duration};
 
-use derive_more::{Constructor, Display, From};
+use derive_more::{Display, From};
 use medea_macro::dispatchable;
 use serde::{Deserialize, Serialize};
 
-use self::stats::RtcStat;
+#[cfg(feature = "stats")]
+use self::stats::{CandidateType, Protocol, RtcStat};
+
+/// Default maximum size (in bytes) of a single serialized [`ClientMsg`].
+///
+/// `Room` state of 5 `Member`s with a screen sharing, camera and audio will be
+/// around 100 Kb, so this value is multiplied by 5 and rounded just in case.
+pub const MAX_MESSAGE_SIZE: usize = 512 * 1024;
+
+/// Maximum length (in bytes) of an SDP in a [`Command`].
+///
+/// [`Command`]s with longer SDPs are rejected by
+/// [`Command::check_relayed_size()`].
+pub const MAX_SDP_LEN: usize = 64 * 1024;
+
+/// Maximum length (in bytes) of an [`IceCandidate::candidate`].
+///
+/// [`Command`]s with longer candidates are rejected by
+/// [`Command::check_relayed_size()`].
+pub const MAX_ICE_CANDIDATE_LEN: usize = 1024;
+
+/// Maximum length (in bytes) of a [`Command::SendAppData`] payload.
+///
+/// [`Command`]s with longer payloads are rejected by
+/// [`Command::check_relayed_size()`].
+pub const MAX_APP_DATA_LEN: usize = 64 * 1024;
+
+/// Maximum length (in bytes) of a [`ClientCapabilities::user_agent`].
+///
+/// Longer user agents are truncated by [`Command::truncate_oversized()`].
+pub const MAX_USER_AGENT_LEN: usize = 512;
+
+/// Maximum number of [`ClientCapabilities::codecs`].
+///
+/// Excessive codecs are dropped by [`Command::truncate_oversized()`].
+pub const MAX_CLIENT_CODECS: usize = 64;
+
+/// Maximum number of [`RtcStat`]s in a single
+/// [`Command::AddPeerConnectionMetrics`].
+///
+/// Excessive [`RtcStat`]s are dropped by [`Command::truncate_oversized()`].
+#[cfg(feature = "stats")]
+pub const MAX_RTC_STATS: usize = 1024;
+
+/// Error of a serialized message exceeding its maximum size.
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+#[display(fmt = "Message of {} bytes exceeds the limit of {} bytes", size, max)]
+pub struct MessageTooLarge {
+    /// Size of the message (in bytes).
+    pub size: usize,
+
+    /// Maximum allowed size of the message (in bytes).
+    pub max: usize,
+}
+
+impl MessageTooLarge {
+    /// Checks whether the provided message `size` doesn't exceed the provided
+    /// `max` one.
+    ///
+    /// # Errors
+    ///
+    /// With [`MessageTooLarge`] if it does.
+    #[inline]
+    pub fn check(size: usize, max: usize) -> Result<(), Self> {
+        if size > max {
+            Err(Self { size, max })
+        } else {
+            Ok(())
+        }
+    }
+}
 
 /// ID of `Room`.
 #[derive(
@@ -81,6 +165,8 @@ pub struct Credential(pub String);
 #[cfg(feature = "medea")]
 pub trait Incrementable {
     /// Returns current value + 1.
+    ///
+    /// Wraps around to zero on overflow.
     #[must_use]
     fn incr(&self) -> Self;
 }
@@ -91,7 +177,7 @@ macro_rules! impl_incrementable {
         impl Incrementable for $name {
             #[inline]
             fn incr(&self) -> Self {
-                Self(self.0 + 1)
+                Self(self.0.wrapping_add(1))
             }
         }
     };
@@ -120,6 +206,13 @@ pub enum ServerMsg {
 
         /// Actual [`Event`] sent to `Client`.
         event: Event,
+
+        /// Sequence number of this [`Event`] among all the [`Event`]s sent to
+        /// `Client` in the `Room`.
+        ///
+        /// [`None`] if this [`Event`] can't be replayed on connection restore.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        seq: Option<u64>,
     },
 
     /// `Media Server` notifies `Client` about necessity to update its RPC
@@ -159,6 +252,37 @@ pub struct RpcSettings {
     ///
     /// Unit: millisecond.
     pub ping_interval_ms: u32,
+
+    /// Key-value feature flags enabled for `Client` by `Media Server`.
+    ///
+    /// Allows to roll out new `Media Server` features without bumping the
+    /// protocol version, so unknown feature flags should be ignored.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub features: HashMap<String, String>,
+}
+
+/// Capabilities of `Client`, reported to `Media Server` when joining `Room`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ClientCapabilities {
+    /// User agent of `Client`, used by `Media Server` to recognize the dialect
+    /// of SDPs it produces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+
+    /// MIME types of the codecs supported by `Client` (e.g. `video/VP8`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub codecs: Vec<String>,
+
+    /// Indicator whether `Client` supports sending simulcast.
+    #[serde(default)]
+    pub simulcast: bool,
+
+    /// Indicator whether `Client` supports [insertable streams][1], required
+    /// for end-to-end encryption.
+    ///
+    /// [1]: https://w3.org/TR/webrtc-encoded-transform
+    #[serde(default)]
+    pub insertable_streams: bool,
 }
 
 /// WebSocket message from Web Client to Media Server.
@@ -175,6 +299,10 @@ pub enum Command {
 
         /// [`Credential`] of `Client`'s `Member`.
         credential: Credential,
+
+        /// [`ClientCapabilities`] of `Client`.
+        #[serde(default)]
+        capabilities: ClientCapabilities,
     },
 
     /// Request of `Client` to leave `Room`.
@@ -234,9 +362,63 @@ pub enum Command {
         tracks_patches: Vec<TrackPatchCommand>,
     },
 
+    /// Web Client acknowledges that it has released resources of the
+    /// [`Track`]s removed from the specified `Peer` by
+    /// [`TrackUpdate::Removed`].
+    TracksRemovedAck {
+        /// ID of the `Peer` the [`Track`]s were removed from.
+        peer_id: PeerId,
+
+        /// IDs of the removed [`Track`]s.
+        track_ids: Vec<TrackId>,
+    },
+
     /// Web Client asks Media Server to synchronize Client State with a Server
     /// State.
-    SynchronizeMe { state: state::Room },
+    SynchronizeMe {
+        /// Current Client State.
+        ///
+        /// If it's not empty, Media Server sends
+        /// [`Event::StateDeltaSynchronized`] relative to it instead of
+        /// [`Event::StateSynchronized`].
+        state: state::Room,
+
+        /// Sequence number of the last [`ServerMsg::Event`] received by Web
+        /// Client in the `Room`.
+        ///
+        /// If provided, Media Server may replay the [`Event`]s missed by Web
+        /// Client instead of synchronizing its State.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        last_event_seq: Option<u64>,
+    },
+
+    /// Web Client sends arbitrary application data to other `Member`s of the
+    /// `Room`.
+    ///
+    /// Media Server relays it as [`Event::AppDataReceived`] without any
+    /// interpretation.
+    SendAppData {
+        /// ID of the `Member` to send the data to.
+        ///
+        /// If `None` then the data is sent to all other `Member`s of the
+        /// `Room`.
+        to: Option<MemberId>,
+
+        /// Arbitrary JSON-encoded payload.
+        ///
+        /// Must not exceed [`MAX_APP_DATA_LEN`].
+        data: String,
+    },
+
+    /// Web Client provides a renewed [`Credential`] of its `Member`, so it can
+    /// be used for the further reconnections instead of the expiring one.
+    ///
+    /// Media Server verifies it and disconnects the `Member` with
+    /// [`CloseReason::Rejected`] if it's invalid.
+    RefreshCredentials {
+        /// Renewed [`Credential`] of the `Member`.
+        credential: Credential,
+    },
 }
 
 /// Web Client's Peer Connection metrics.
@@ -251,7 +433,25 @@ pub enum PeerMetrics {
     PeerConnectionState(PeerConnectionState),
 
     /// Peer Connection's RTC stats.
+    #[cfg(feature = "stats")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
     RtcStats(Vec<RtcStat>),
+
+    /// Peer Connection has switched to another ICE candidate pair for media
+    /// transmission.
+    #[cfg(feature = "stats")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "stats")))]
+    SelectedCandidatePairChanged {
+        /// Type of the local ICE candidate of the selected pair.
+        local_type: CandidateType,
+
+        /// Type of the remote ICE candidate of the selected pair.
+        remote_type: CandidateType,
+
+        /// Protocol used by the local ICE candidate to communicate with the
+        /// TURN server, if it's a `relay` one.
+        relay_protocol: Option<Protocol>,
+    },
 }
 
 /// Peer Connection's ICE connection state.
@@ -383,20 +583,203 @@ pub enum CloseReason {
 
     /// Client was evicted on the server side.
     Evicted,
+
+    /// Media Server is draining, so doesn't accept new clients.
+    ///
+    /// Client may retry connecting later, most likely to another Media
+    /// Server.
+    Draining,
+
+    /// Media Server admits too many clients at the moment (most likely,
+    /// because of its restart), so doesn't accept new ones.
+    ///
+    /// Client should retry connecting after the
+    /// [`CloseDescription::retry_after_ms`] hint.
+    Overloaded,
+
+    /// Media Server has been drained and is shutting down.
+    ///
+    /// Client may reconnect, most likely to another Media Server.
+    Drained,
+
+    /// Client has sent a message exceeding the maximum size allowed by Media
+    /// Server.
+    MessageTooLarge,
+}
+
+impl CloseReason {
+    /// Returns the [WebSocket close code][1] this [`CloseReason`] is sent
+    /// with.
+    ///
+    /// Non-retryable reasons are all sent with the `1000` close code, since
+    /// Web Clients consider any other close code as an abnormal closure and
+    /// reconnect, so they're distinguishable by a [`CloseDescription`] only.
+    ///
+    /// Retryable reasons are sent with their own close codes, so can be
+    /// recovered via [`CloseReason::from_close_code()`] even if a
+    /// [`CloseDescription`] is lost.
+    ///
+    /// [1]: https://tools.ietf.org/html/rfc6455#section-7.4
+    #[must_use]
+    pub fn close_code(self) -> u16 {
+        match self {
+            Self::Finished
+            | Self::Reconnected
+            | Self::Idle
+            | Self::Rejected
+            | Self::InternalError
+            | Self::Evicted
+            | Self::MessageTooLarge => 1000,
+            Self::Drained => 1001,
+            Self::Draining => 1013,
+            Self::Overloaded => 4004,
+        }
+    }
+
+    /// Returns the retryable [`CloseReason`] which is sent with the provided
+    /// [WebSocket close code][1], if any.
+    ///
+    /// Non-retryable [`CloseReason`]s cannot be recovered from a close code
+    /// (see [`CloseReason::close_code()`]).
+    ///
+    /// [1]: https://tools.ietf.org/html/rfc6455#section-7.4
+    #[must_use]
+    pub fn from_close_code(code: u16) -> Option<Self> {
+        Some(match code {
+            1001 => Self::Drained,
+            1013 => Self::Draining,
+            4004 => Self::Overloaded,
+            _ => return None,
+        })
+    }
+
+    /// Indicates whether Web Client is expected to reconnect after its
+    /// connection is closed with this [`CloseReason`].
+    #[inline]
+    #[must_use]
+    pub fn is_retryable(self) -> bool {
+        matches!(self, Self::Draining | Self::Overloaded | Self::Drained)
+    }
 }
 
 /// Description which is sent in [Close] WebSocket frame from Media Server
 /// to Web Client.
 ///
 /// [Close]: https://tools.ietf.org/html/rfc6455#section-5.5.1
-#[derive(Constructor, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(
+    Clone, Copy, Debug, Deserialize, Display, Eq, PartialEq, Serialize,
+)]
+#[display(fmt = "{}", reason)]
 pub struct CloseDescription {
     /// Reason of why WebSocket connection has been closed.
     pub reason: CloseReason,
+
+    /// Hint of the delay (in milliseconds) after which Web Client should try
+    /// to reconnect.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after_ms: Option<u32>,
+}
+
+impl CloseDescription {
+    /// Creates a new [`CloseDescription`] with the provided [`CloseReason`]
+    /// and no retry hint.
+    #[inline]
+    #[must_use]
+    pub fn new(reason: CloseReason) -> Self {
+        Self {
+            reason,
+            retry_after_ms: None,
+        }
+    }
+
+    /// Sets the hint of the delay after which Web Client should try to
+    /// reconnect.
+    #[inline]
+    #[must_use]
+    pub fn with_retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after_ms =
+            Some(u32::try_from(retry_after.as_millis()).unwrap_or(u32::MAX));
+        self
+    }
+}
+
+impl Command {
+    /// Checks whether fields of this [`Command`], which are relayed to other
+    /// `Member`s as is, don't exceed their limits (see [`MAX_SDP_LEN`],
+    /// [`MAX_ICE_CANDIDATE_LEN`] and [`MAX_APP_DATA_LEN`]).
+    ///
+    /// Such fields cannot be truncated, since truncating breaks them for the
+    /// receiving side.
+    ///
+    /// # Errors
+    ///
+    /// With [`MessageTooLarge`] if any of such fields exceeds its limit.
+    pub fn check_relayed_size(&self) -> Result<(), MessageTooLarge> {
+        match self {
+            Self::MakeSdpOffer { sdp_offer: sdp, .. }
+            | Self::MakeSdpAnswer {
+                sdp_answer: sdp, ..
+            } => MessageTooLarge::check(sdp.len(), MAX_SDP_LEN),
+            Self::SetIceCandidate { candidate, .. } => MessageTooLarge::check(
+                candidate.candidate.len(),
+                MAX_ICE_CANDIDATE_LEN,
+            ),
+            Self::SendAppData { data, .. } => {
+                MessageTooLarge::check(data.len(), MAX_APP_DATA_LEN)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Truncates informational fields of this [`Command`] exceeding their
+    /// limits (see [`MAX_USER_AGENT_LEN`], [`MAX_CLIENT_CODECS`] and
+    /// `MAX_RTC_STATS`).
+    ///
+    /// Returns `true` if any field has been truncated.
+    pub fn truncate_oversized(&mut self) -> bool {
+        match self {
+            Self::JoinRoom { capabilities, .. } => {
+                let truncated = capabilities.codecs.len() > MAX_CLIENT_CODECS;
+                capabilities.codecs.truncate(MAX_CLIENT_CODECS);
+                capabilities
+                    .user_agent
+                    .as_mut()
+                    .map_or(false, |ua| truncate_str(ua, MAX_USER_AGENT_LEN))
+                    || truncated
+            }
+            #[cfg(feature = "stats")]
+            Self::AddPeerConnectionMetrics {
+                metrics: PeerMetrics::RtcStats(stats),
+                ..
+            } => {
+                let truncated = stats.len() > MAX_RTC_STATS;
+                stats.truncate(MAX_RTC_STATS);
+                truncated
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Truncates the provided [`String`] to the provided `max` length (in bytes),
+/// respecting its `char` boundaries.
+///
+/// Returns `true` if the [`String`] has been truncated.
+fn truncate_str(s: &mut String, max: usize) -> bool {
+    if s.len() <= max {
+        return false;
+    }
+    let mut len = max;
+    while !s.is_char_boundary(len) {
+        len -= 1;
+    }
+    s.truncate(len);
+    true
 }
 
 /// WebSocket message from Medea to Jason.
-#[dispatchable(self: &Self, async_trait(?Send))]
+#[cfg_attr(feature = "jason", dispatchable(self: &Self, async_trait(?Send)))]
+#[cfg_attr(not(feature = "jason"), dispatchable(self: &Self))]
 #[cfg_attr(feature = "medea", derive(Clone, Debug, Eq, PartialEq, Serialize))]
 #[cfg_attr(feature = "jason", derive(Deserialize))]
 #[serde(tag = "event", content = "data")]
@@ -411,6 +794,11 @@ pub enum Event {
     RoomLeft {
         /// [`CloseReason`] with which `Client` was left.
         close_reason: CloseReason,
+
+        /// Hint of the delay (in milliseconds) after which `Client` should
+        /// try to rejoin `Room`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        retry_after_ms: Option<u32>,
     },
 
     /// Media Server notifies Web Client about necessity of RTCPeerConnection
@@ -466,8 +854,56 @@ pub enum Event {
         quality_score: ConnectionQualityScore,
     },
 
+    /// Media Server advises Web Client to limit the bitrate of its sent
+    /// [`Track`], since the receiving side of this [`Track`] is struggling.
+    TrackQualityAdvisory {
+        /// ID of the sent [`Track`] this advisory relates to.
+        track_id: TrackId,
+
+        /// Advised maximum bitrate (in bits per second) of the [`Track`].
+        ///
+        /// [`None`] means that the previously advised limit should be lifted.
+        advised_max_bitrate: Option<u32>,
+
+        /// Reason of this advisory.
+        reason: QualityAdvisoryReason,
+    },
+
     /// Media Server synchronizes Web Client about State synchronization.
     StateSynchronized { state: state::Room },
+
+    /// Media Server synchronizes Web Client State with the difference between
+    /// the Client State provided in [`Command::SynchronizeMe`] and the Server
+    /// State.
+    StateDeltaSynchronized { delta: state::RoomStateDelta },
+
+    /// Media Server notifies Web Client about key-value metadata of some other
+    /// `Member` of the `Room` being set or updated.
+    MemberMetadataUpdated {
+        /// [`MemberId`] of the `Member` which metadata was updated.
+        member_id: MemberId,
+
+        /// Actual key-value metadata of the `Member`.
+        metadata: HashMap<String, String>,
+    },
+
+    /// Media Server relays application data sent by some other `Member` of
+    /// the `Room` via [`Command::SendAppData`].
+    AppDataReceived {
+        /// [`MemberId`] of the `Member` which sent the data.
+        from: MemberId,
+
+        /// Arbitrary JSON-encoded payload.
+        data: String,
+    },
+
+    /// Media Server periodically notifies Web Client about a checksum of its
+    /// [`state::Room`], so Web Client can detect its state drift and ask for
+    /// [`Command::SynchronizeMe`].
+    StateChecksum {
+        /// [`state::Room::checksum()`] of the Media Server state.
+        checksum: u32,
+    },
 }
 
 /// `Peer`'s negotiation role.
@@ -508,6 +944,17 @@ pub enum PeerUpdate {
 
     /// `Peer` should start ICE restart process on the next renegotiation.
     IceRestart,
+
+    /// `Peer` should use the provided [`IceServer`]s and ICE transport policy
+    /// since the next renegotiation.
+    IcePolicyUpdated {
+        /// [`IceServer`]s which should be used by the `Peer`.
+        ice_servers: Vec<IceServer>,
+
+        /// Indicator whether the `Peer` should be forcibly connected through
+        /// TURN.
+        force_relay: bool,
+    },
 }
 
 /// Represents [RTCIceCandidateInit][1] object.
@@ -537,6 +984,40 @@ impl Track {
     }
 }
 
+/// Patch of a [`Track`], which can be merged with other patches of the same
+/// [`Track`].
+pub trait Merge {
+    /// Returns [`TrackId`] of the [`Track`] patched by this patch.
+    fn id(&self) -> TrackId;
+
+    /// Merges the provided patch into this one, if both patch the same
+    /// [`Track`].
+    fn merge(&mut self, another: &Self);
+}
+
+/// Merges the provided patches, collapsing the ones with the same [`TrackId`]
+/// into a single patch.
+///
+/// Later patches take precedence over the earlier ones, while the resulting
+/// patches keep the order in which their [`TrackId`]s occur first.
+#[must_use]
+pub fn merge_all<T, I>(patches: I) -> Vec<T>
+where
+    T: Merge,
+    I: IntoIterator<Item = T>,
+{
+    let mut merged: Vec<T> = Vec::new();
+    for patch in patches {
+        if let Some(target) = merged.iter_mut().find(|p| p.id() == patch.id())
+        {
+            target.merge(&patch);
+        } else {
+            merged.push(patch);
+        }
+    }
+    merged
+}
+
 /// Patch of the [`Track`] which Web Client can request with
 /// [`Command::UpdateTracks`].
 #[cfg_attr(feature = "medea", derive(Clone, Debug, Serialize))]
@@ -548,6 +1029,52 @@ pub struct TrackPatchCommand {
     pub muted: Option<bool>,
 }
 
+impl TrackPatchCommand {
+    /// Merges this [`TrackPatchCommand`] with a provided
+    /// [`TrackPatchCommand`].
+    ///
+    /// Does nothing if [`TrackId`] of this [`TrackPatchCommand`] and the
+    /// provided [`TrackPatchCommand`] are different.
+    pub fn merge(&mut self, another: &Self) {
+        if self.id != another.id {
+            return;
+        }
+
+        if let Some(enabled) = another.enabled {
+            self.enabled = Some(enabled);
+        }
+
+        if let Some(muted) = another.muted {
+            self.muted = Some(muted);
+        }
+    }
+
+    /// Merges the provided [`TrackPatchCommand`]s, collapsing the ones with
+    /// the same [`TrackId`] into a single [`TrackPatchCommand`].
+    ///
+    /// See [`merge_all()`](crate::merge_all) for details.
+    #[inline]
+    #[must_use]
+    pub fn merge_all<I>(patches: I) -> Vec<Self>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        merge_all(patches)
+    }
+}
+
+impl Merge for TrackPatchCommand {
+    #[inline]
+    fn id(&self) -> TrackId {
+        self.id
+    }
+
+    #[inline]
+    fn merge(&mut self, another: &Self) {
+        Self::merge(self, another);
+    }
+}
+
 /// Patch of the [`Track`] which Media Server can send with
 /// [`Event::PeerUpdated`].
 #[cfg_attr(feature = "medea", derive(Clone, Debug, Eq, PartialEq, Serialize))]
@@ -575,6 +1102,12 @@ pub struct TrackPatchEvent {
     /// / removing tracks from transceivers, hence renegotiation is not
     /// required.
     pub muted: Option<bool>,
+
+    /// [`ContentHint`] of the video `Track` being sent.
+    pub content_hint: Option<ContentHint>,
+
+    /// [`DegradationPreference`] of the video `Track` being sent.
+    pub degradation_preference: Option<DegradationPreference>,
 }
 
 impl From<TrackPatchCommand> for TrackPatchEvent {
@@ -584,6 +1117,8 @@ impl From<TrackPatchCommand> for TrackPatchEvent {
             enabled_individual: from.enabled,
             enabled_general: None,
             muted: from.muted,
+            content_hint: None,
+            degradation_preference: None,
         }
     }
 }
@@ -598,6 +1133,8 @@ impl TrackPatchEvent {
             enabled_general: None,
             enabled_individual: None,
             muted: None,
+            content_hint: None,
+            degradation_preference: None,
         }
     }
 
@@ -621,6 +1158,39 @@ impl TrackPatchEvent {
         if let Some(muted) = another.muted {
             self.muted = Some(muted);
         }
+
+        if let Some(content_hint) = another.content_hint {
+            self.content_hint = Some(content_hint);
+        }
+
+        if let Some(preference) = another.degradation_preference {
+            self.degradation_preference = Some(preference);
+        }
+    }
+
+    /// Merges the provided [`TrackPatchEvent`]s, collapsing the ones with the
+    /// same [`TrackId`] into a single [`TrackPatchEvent`].
+    ///
+    /// See [`merge_all()`](crate::merge_all) for details.
+    #[inline]
+    #[must_use]
+    pub fn merge_all<I>(patches: I) -> Vec<Self>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        merge_all(patches)
+    }
+}
+
+impl Merge for TrackPatchEvent {
+    #[inline]
+    fn id(&self) -> TrackId {
+        self.id
+    }
+
+    #[inline]
+    fn merge(&mut self, another: &Self) {
+        Self::merge(self, another);
     }
 }
 
@@ -684,6 +1254,38 @@ pub struct AudioSettings {
     ///
     /// If `false` then audio may be not published.
     pub required: bool,
+
+    /// [`OpusParameters`] to encode the audio with.
+    ///
+    /// [`None`] means the defaults of `Client`'s user agent.
+    pub opus: Option<OpusParameters>,
+}
+
+/// Parameters of the Opus codec negotiated for an audio track.
+///
+/// See [RFC 7587] for details.
+///
+/// [RFC 7587]: https://tools.ietf.org/html/rfc7587#section-6.1
+#[cfg_attr(feature = "medea", derive(Eq, PartialEq, Serialize))]
+#[cfg_attr(feature = "jason", derive(Deserialize))]
+#[derive(Clone, Copy, Debug)]
+pub struct OpusParameters {
+    /// Indicator whether discontinuous transmission (`usedtx`) should be used,
+    /// so nothing is sent during silence.
+    pub dtx: bool,
+
+    /// Indicator whether in-band forward error correction (`useinbandfec`)
+    /// should be used.
+    pub inband_fec: bool,
+
+    /// Indicator whether stereo audio (`stereo`) should be encoded.
+    pub stereo: bool,
+
+    /// Maximum average bitrate (`maxaveragebitrate`) of the encoded audio in
+    /// bits per second.
+    ///
+    /// [`None`] means the default of `Client`'s user agent.
+    pub max_average_bitrate: Option<u32>,
 }
 
 #[cfg_attr(feature = "medea", derive(Eq, PartialEq, Serialize))]
@@ -711,6 +1313,49 @@ pub enum MediaSourceKind {
     Display,
 }
 
+/// Hint of the video `Track` contents, letting a browser choose how to encode
+/// it.
+///
+/// See [`contentHint`][1] for details.
+///
+/// [1]: https://w3.org/TR/mst-content-hint#video-content-hints
+#[cfg_attr(feature = "medea", derive(Serialize))]
+#[cfg_attr(feature = "jason", derive(Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContentHint {
+    /// `Track` should be treated as containing video where motion is
+    /// important (webcam or a video playback).
+    Motion,
+
+    /// `Track` should be treated as containing video where details are
+    /// important (presentation slides or web pages).
+    Detail,
+
+    /// `Track` should be treated as containing video where details are
+    /// extra important, and which contains text content.
+    Text,
+}
+
+/// Preference of what a browser should sacrifice when sending a video `Track`
+/// under constrained bandwidth.
+///
+/// See [`degradationPreference`][1] for details.
+///
+/// [1]: https://w3.org/TR/webrtc/#dom-rtcdegradationpreference
+#[cfg_attr(feature = "medea", derive(Serialize))]
+#[cfg_attr(feature = "jason", derive(Deserialize))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DegradationPreference {
+    /// Degrade resolution in order to maintain framerate.
+    MaintainFramerate,
+
+    /// Degrade framerate in order to maintain resolution.
+    MaintainResolution,
+
+    /// Degrade both framerate and resolution in a balanced way.
+    Balanced,
+}
+
 /// Estimated connection quality.
 #[cfg_attr(
     feature = "medea",
@@ -732,10 +1377,140 @@ pub enum ConnectionQualityScore {
     High = 4,
 }
 
+/// Reason of an [`Event::TrackQualityAdvisory`].
+#[cfg_attr(feature = "medea", derive(Serialize, Eq, PartialEq))]
+#[cfg_attr(feature = "jason", derive(Deserialize))]
+#[derive(Clone, Copy, Debug)]
+pub enum QualityAdvisoryReason {
+    /// Receiving side loses too many packets.
+    PacketLoss,
+
+    /// Receiving side observes too high jitter.
+    Jitter,
+
+    /// Receiving side has recovered, so the previously advised limit can be
+    /// lifted.
+    Recovered,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    fn close_reason_round_trips_via_close_frame() {
+        for reason in &[
+            CloseReason::Finished,
+            CloseReason::Reconnected,
+            CloseReason::Idle,
+            CloseReason::Rejected,
+            CloseReason::InternalError,
+            CloseReason::Evicted,
+            CloseReason::Draining,
+            CloseReason::Overloaded,
+            CloseReason::Drained,
+            CloseReason::MessageTooLarge,
+        ] {
+            let reason = *reason;
+            if reason.is_retryable() {
+                assert_eq!(
+                    CloseReason::from_close_code(reason.close_code()),
+                    Some(reason),
+                );
+            } else {
+                assert_eq!(reason.close_code(), 1000);
+                assert_eq!(CloseReason::from_close_code(1000), None);
+            }
+
+            let description = CloseDescription::new(reason);
+            let serialized = serde_json::to_string(&description).unwrap();
+            assert_eq!(
+                serde_json::from_str::<CloseDescription>(&serialized).unwrap(),
+                description,
+            );
+        }
+        assert_eq!(CloseReason::from_close_code(1006), None);
+    }
+
+    #[test]
+    fn rejects_oversized_relayed_commands() {
+        let mut offer = Command::MakeSdpOffer {
+            peer_id: PeerId(1),
+            sdp_offer: "a".repeat(MAX_SDP_LEN + 1),
+            mids: HashMap::new(),
+            transceivers_statuses: HashMap::new(),
+        };
+        assert_eq!(
+            offer.check_relayed_size(),
+            Err(MessageTooLarge {
+                size: MAX_SDP_LEN + 1,
+                max: MAX_SDP_LEN,
+            }),
+        );
+        assert!(!offer.truncate_oversized());
+        if let Command::MakeSdpOffer { sdp_offer, .. } = &mut offer {
+            sdp_offer.truncate(MAX_SDP_LEN);
+        }
+        assert!(offer.check_relayed_size().is_ok());
+
+        let candidate = Command::SetIceCandidate {
+            peer_id: PeerId(1),
+            candidate: IceCandidate {
+                candidate: "a".repeat(MAX_ICE_CANDIDATE_LEN + 1),
+                sdp_m_line_index: None,
+                sdp_mid: None,
+            },
+        };
+        assert!(candidate.check_relayed_size().is_err());
+
+        let app_data = Command::SendAppData {
+            to: None,
+            data: "a".repeat(MAX_APP_DATA_LEN + 1),
+        };
+        assert!(app_data.check_relayed_size().is_err());
+
+        let leave = Command::LeaveRoom {
+            member_id: "alice".into(),
+        };
+        assert!(leave.check_relayed_size().is_ok());
+    }
+
+    #[test]
+    fn truncates_oversized_commands() {
+        let mut join = Command::JoinRoom {
+            member_id: "alice".into(),
+            credential: "token".into(),
+            capabilities: ClientCapabilities {
+                user_agent: Some("a".repeat(MAX_USER_AGENT_LEN + 1)),
+                codecs: vec!["video/VP8".into(); MAX_CLIENT_CODECS + 1],
+                ..ClientCapabilities::default()
+            },
+        };
+        assert!(join.truncate_oversized());
+        if let Command::JoinRoom { capabilities, .. } = &join {
+            assert_eq!(
+                capabilities.user_agent.as_ref().map(String::len),
+                Some(MAX_USER_AGENT_LEN),
+            );
+            assert_eq!(capabilities.codecs.len(), MAX_CLIENT_CODECS);
+        }
+        assert!(!join.truncate_oversized());
+
+        let mut leave = Command::LeaveRoom {
+            member_id: "alice".into(),
+        };
+        assert!(!leave.truncate_oversized());
+    }
+
+    #[test]
+    fn checks_message_size() {
+        assert!(MessageTooLarge::check(10, 10).is_ok());
+        assert_eq!(
+            MessageTooLarge::check(11, 10),
+            Err(MessageTooLarge { size: 11, max: 10 }),
+        );
+    }
+
     #[test]
     fn track_patch_merge() {
         for (track_patches, result) in vec![
@@ -746,30 +1521,40 @@ mod test {
                         enabled_general: Some(true),
                         enabled_individual: Some(true),
                         muted: None,
+                        content_hint: None,
+                        degradation_preference: None,
                     },
                     TrackPatchEvent {
                         id: TrackId(1),
                         enabled_general: Some(false),
                         enabled_individual: Some(false),
                         muted: None,
+                        content_hint: None,
+                        degradation_preference: None,
                     },
                     TrackPatchEvent {
                         id: TrackId(1),
                         enabled_general: None,
                         enabled_individual: None,
                         muted: None,
+                        content_hint: None,
+                        degradation_preference: None,
                     },
                     TrackPatchEvent {
                         id: TrackId(1),
                         enabled_general: Some(true),
                         enabled_individual: Some(true),
                         muted: None,
+                        content_hint: None,
+                        degradation_preference: None,
                     },
                     TrackPatchEvent {
                         id: TrackId(1),
                         enabled_general: Some(true),
                         enabled_individual: Some(true),
                         muted: None,
+                        content_hint: None,
+                        degradation_preference: None,
                     },
                 ],
                 TrackPatchEvent {
@@ -777,6 +1562,8 @@ mod test {
                     enabled_general: Some(true),
                     enabled_individual: Some(true),
                     muted: None,
+                    content_hint: None,
+                    degradation_preference: None,
                 },
             ),
             (
@@ -786,12 +1573,16 @@ mod test {
                         enabled_general: None,
                         enabled_individual: None,
                         muted: None,
+                        content_hint: None,
+                        degradation_preference: None,
                     },
                     TrackPatchEvent {
                         id: TrackId(1),
                         enabled_general: Some(true),
                         enabled_individual: Some(true),
                         muted: None,
+                        content_hint: None,
+                        degradation_preference: None,
                     },
                 ],
                 TrackPatchEvent {
@@ -799,6 +1590,8 @@ mod test {
                     enabled_general: Some(true),
                     enabled_individual: Some(true),
                     muted: None,
+                    content_hint: None,
+                    degradation_preference: None,
                 },
             ),
             (
@@ -808,12 +1601,16 @@ mod test {
                         enabled_general: Some(true),
                         enabled_individual: Some(true),
                         muted: None,
+                        content_hint: None,
+                        degradation_preference: None,
                     },
                     TrackPatchEvent {
                         id: TrackId(1),
                         enabled_general: None,
                         enabled_individual: None,
                         muted: None,
+                        content_hint: None,
+                        degradation_preference: None,
                     },
                 ],
                 TrackPatchEvent {
@@ -821,6 +1618,8 @@ mod test {
                     enabled_general: Some(true),
                     enabled_individual: Some(true),
                     muted: None,
+                    content_hint: None,
+                    degradation_preference: None,
                 },
             ),
             (
@@ -830,12 +1629,16 @@ mod test {
                         enabled_general: None,
                         enabled_individual: None,
                         muted: None,
+                        content_hint: None,
+                        degradation_preference: None,
                     },
                     TrackPatchEvent {
                         id: TrackId(2),
                         enabled_general: Some(true),
                         enabled_individual: Some(true),
                         muted: None,
+                        content_hint: None,
+                        degradation_preference: None,
                     },
                 ],
                 TrackPatchEvent {
@@ -843,6 +1646,8 @@ mod test {
                     enabled_general: None,
                     enabled_individual: None,
                     muted: None,
+                    content_hint: None,
+                    degradation_preference: None,
                 },
             ),
         ] {
@@ -858,4 +1663,54 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn track_patches_merge_all() {
+        let patch = |id, muted| TrackPatchEvent {
+            muted: Some(muted),
+            ..TrackPatchEvent::new(TrackId(id))
+        };
+
+        let merged = TrackPatchEvent::merge_all(vec![
+            patch(2, true),
+            patch(1, true),
+            TrackPatchEvent {
+                enabled_individual: Some(false),
+                ..TrackPatchEvent::new(TrackId(2))
+            },
+            patch(2, false),
+        ]);
+
+        assert_eq!(
+            merged,
+            vec![
+                TrackPatchEvent {
+                    enabled_individual: Some(false),
+                    ..patch(2, false)
+                },
+                patch(1, true),
+            ],
+        );
+    }
+
+    #[test]
+    fn track_patch_commands_merge_all() {
+        let patch = |id, enabled, muted| TrackPatchCommand {
+            id: TrackId(id),
+            enabled,
+            muted,
+        };
+
+        let merged = TrackPatchCommand::merge_all(vec![
+            patch(1, Some(false), None),
+            patch(3, None, Some(true)),
+            patch(1, None, Some(true)),
+            patch(1, Some(true), None),
+        ]);
+
+        assert_eq!(
+            merged,
+            vec![patch(1, Some(true), Some(true)), patch(3, None, Some(true))],
+        );
+    }
 }
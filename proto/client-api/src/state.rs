@@ -1,17 +1,24 @@
 //! State of the Media Server which will be used for Client and Server
 //! synchronization.
 
-use std::collections::{HashMap, HashSet};
+// `Option<Option<T>>` is used in deltas to distinguish an unchanged value from
+// the one being unset.
+#![allow(clippy::option_option)]
+
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    IceCandidate, IceServer, MediaType, MemberId, NegotiationRole, PeerId,
-    TrackId,
+    IceCandidate, IceServer, MediaSourceKind, MediaType, MemberId,
+    NegotiationRole, PeerId, TrackId,
 };
 
 /// State of a `Room` element.
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
 pub struct Room {
     /// All [`Peer`]s of this [`Room`].
     pub peers: HashMap<PeerId, Peer>,
@@ -61,6 +68,13 @@ pub struct Sender {
     /// Mid of this [`Sender`].
     pub mid: Option<String>,
 
+    /// Primary [SSRC] of the RTP stream sent by this [`Sender`], as it was
+    /// negotiated.
+    ///
+    /// [SSRC]: https://tools.ietf.org/html/rfc3550#section-3
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssrc: Option<u32>,
+
     /// [`MediaType`] of this [`Sender`].
     pub media_type: MediaType,
 
@@ -88,6 +102,13 @@ pub struct Receiver {
     /// Mid of this [`Receiver`].
     pub mid: Option<String>,
 
+    /// Primary [SSRC] of the RTP stream received by this [`Receiver`], as it
+    /// was negotiated.
+    ///
+    /// [SSRC]: https://tools.ietf.org/html/rfc3550#section-3
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssrc: Option<u32>,
+
     /// [`MediaType`] of this [`Receiver`].
     pub media_type: MediaType,
 
@@ -105,3 +126,487 @@ pub struct Receiver {
     /// Indicator whether this [`Receiver`] is muted.
     pub muted: bool,
 }
+
+impl Room {
+    /// Calculates a [`RoomStateDelta`] transforming this [`Room`] into the
+    /// `other` one.
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> RoomStateDelta {
+        let mut delta = RoomStateDelta::default();
+        for (id, peer) in &other.peers {
+            match self.peers.get(id) {
+                Some(current) => {
+                    let peer_delta = current.diff(peer);
+                    if !peer_delta.is_empty() {
+                        drop(delta.updated_peers.insert(*id, peer_delta));
+                    }
+                }
+                None => {
+                    drop(delta.added_peers.insert(*id, peer.clone()));
+                }
+            }
+        }
+        delta.removed_peers = self
+            .peers
+            .keys()
+            .filter(|id| !other.peers.contains_key(id))
+            .copied()
+            .collect();
+        delta
+    }
+
+    /// Applies the provided [`RoomStateDelta`] to this [`Room`].
+    ///
+    /// Updates of [`Peer`]s missing in this [`Room`] are ignored.
+    pub fn apply_delta(&mut self, delta: RoomStateDelta) {
+        for id in delta.removed_peers {
+            drop(self.peers.remove(&id));
+        }
+        self.peers.extend(delta.added_peers);
+        for (id, peer_delta) in delta.updated_peers {
+            if let Some(peer) = self.peers.get_mut(&id) {
+                peer.apply_delta(peer_delta);
+            }
+        }
+    }
+}
+
+impl Room {
+    /// Calculates a deterministic checksum of this [`Room`] state, which is the
+    /// same on all the platforms.
+    ///
+    /// Only the set of [`Peer`]s and their tracks are taken into account. SDP,
+    /// ICE and negotiation related fields are ignored, as they legitimately
+    /// differ between a Client and a Media Server while a negotiation is in
+    /// progress.
+    #[must_use]
+    pub fn checksum(&self) -> u32 {
+        let mut hasher = Checksum::default();
+        let mut peers: Vec<_> = self.peers.values().collect();
+        peers.sort_unstable_by_key(|p| p.id.0);
+        for peer in peers {
+            hasher.write_u32(peer.id.0);
+            hasher.write_bool(peer.force_relay);
+
+            let mut senders: Vec<_> = peer.senders.values().collect();
+            senders.sort_unstable_by_key(|s| s.id.0);
+            hasher.write_len(senders.len());
+            for sender in senders {
+                hasher.write_u32(sender.id.0);
+                hasher.write_media_type(&sender.media_type);
+                let mut receivers: Vec<_> =
+                    sender.receivers.iter().map(|m| m.0.as_str()).collect();
+                receivers.sort_unstable();
+                hasher.write_len(receivers.len());
+                for receiver in receivers {
+                    hasher.write_str(receiver);
+                }
+                hasher.write_bool(sender.enabled_individual);
+                hasher.write_bool(sender.enabled_general);
+                hasher.write_bool(sender.muted);
+            }
+
+            let mut receivers: Vec<_> = peer.receivers.values().collect();
+            receivers.sort_unstable_by_key(|r| r.id.0);
+            hasher.write_len(receivers.len());
+            for receiver in receivers {
+                hasher.write_u32(receiver.id.0);
+                hasher.write_media_type(&receiver.media_type);
+                hasher.write_str(&receiver.sender_id.0);
+                hasher.write_bool(receiver.enabled_individual);
+                hasher.write_bool(receiver.enabled_general);
+                hasher.write_bool(receiver.muted);
+            }
+        }
+        hasher.0
+    }
+}
+
+/// [FNV-1a] 32-bit hasher used for calculating [`Room::checksum()`].
+///
+/// [`std::hash::Hasher`] isn't used, as it hashes integers with platform
+/// dependent endianness and size.
+///
+/// [FNV-1a]: http://www.isthe.com/chongo/tech/comp/fnv
+struct Checksum(u32);
+
+impl Default for Checksum {
+    #[inline]
+    fn default() -> Self {
+        Self(0x811c_9dc5)
+    }
+}
+
+impl Checksum {
+    /// Feeds the provided bytes into this [`Checksum`].
+    fn write(&mut self, bytes: &[u8]) {
+        for b in bytes {
+            self.0 ^= u32::from(*b);
+            self.0 = self.0.wrapping_mul(0x0100_0193);
+        }
+    }
+
+    /// Feeds the provided `u32` into this [`Checksum`].
+    #[inline]
+    fn write_u32(&mut self, val: u32) {
+        self.write(&val.to_le_bytes());
+    }
+
+    /// Feeds the provided collection length into this [`Checksum`].
+    ///
+    /// Length is truncated to `u32` for being the same on all the platforms.
+    #[allow(clippy::cast_possible_truncation)]
+    #[inline]
+    fn write_len(&mut self, len: usize) {
+        self.write_u32(len as u32);
+    }
+
+    /// Feeds the provided `bool` into this [`Checksum`].
+    #[inline]
+    fn write_bool(&mut self, val: bool) {
+        self.write(&[u8::from(val)]);
+    }
+
+    /// Feeds the provided string (prefixed with its length) into this
+    /// [`Checksum`].
+    #[inline]
+    fn write_str(&mut self, val: &str) {
+        self.write_len(val.len());
+        self.write(val.as_bytes());
+    }
+
+    /// Feeds the provided [`MediaType`] into this [`Checksum`].
+    fn write_media_type(&mut self, media_type: &MediaType) {
+        match media_type {
+            MediaType::Audio(audio) => {
+                self.write(&[0]);
+                self.write_bool(audio.required);
+            }
+            MediaType::Video(video) => {
+                self.write(&[1]);
+                self.write_bool(video.required);
+                self.write(&[match video.source_kind {
+                    MediaSourceKind::Device => 0,
+                    MediaSourceKind::Display => 1,
+                }]);
+            }
+        }
+    }
+}
+
+impl Peer {
+    /// Calculates a [`PeerStateDelta`] transforming this [`Peer`] into the
+    /// `other` one.
+    #[must_use]
+    pub fn diff(&self, other: &Self) -> PeerStateDelta {
+        /// Returns the `new` value if it differs from the `old` one.
+        fn changed<T: Clone + PartialEq>(old: &T, new: &T) -> Option<T> {
+            (old != new).then(|| new.clone())
+        }
+
+        let (upserted_senders, removed_senders) =
+            diff_map(&self.senders, &other.senders);
+        let (upserted_receivers, removed_receivers) =
+            diff_map(&self.receivers, &other.receivers);
+
+        PeerStateDelta {
+            upserted_senders,
+            removed_senders,
+            upserted_receivers,
+            removed_receivers,
+            force_relay: changed(&self.force_relay, &other.force_relay),
+            ice_servers: changed(&self.ice_servers, &other.ice_servers),
+            negotiation_role: changed(
+                &self.negotiation_role,
+                &other.negotiation_role,
+            ),
+            local_sdp: changed(&self.local_sdp, &other.local_sdp),
+            remote_sdp: changed(&self.remote_sdp, &other.remote_sdp),
+            restart_ice: changed(&self.restart_ice, &other.restart_ice),
+            added_ice_candidates: other
+                .ice_candidates
+                .difference(&self.ice_candidates)
+                .cloned()
+                .collect(),
+            removed_ice_candidates: self
+                .ice_candidates
+                .difference(&other.ice_candidates)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Applies the provided [`PeerStateDelta`] to this [`Peer`].
+    pub fn apply_delta(&mut self, delta: PeerStateDelta) {
+        for id in delta.removed_senders {
+            drop(self.senders.remove(&id));
+        }
+        self.senders.extend(delta.upserted_senders);
+        for id in delta.removed_receivers {
+            drop(self.receivers.remove(&id));
+        }
+        self.receivers.extend(delta.upserted_receivers);
+
+        if let Some(force_relay) = delta.force_relay {
+            self.force_relay = force_relay;
+        }
+        if let Some(ice_servers) = delta.ice_servers {
+            self.ice_servers = ice_servers;
+        }
+        if let Some(negotiation_role) = delta.negotiation_role {
+            self.negotiation_role = negotiation_role;
+        }
+        if let Some(local_sdp) = delta.local_sdp {
+            self.local_sdp = local_sdp;
+        }
+        if let Some(remote_sdp) = delta.remote_sdp {
+            self.remote_sdp = remote_sdp;
+        }
+        if let Some(restart_ice) = delta.restart_ice {
+            self.restart_ice = restart_ice;
+        }
+
+        for candidate in &delta.removed_ice_candidates {
+            let _ = self.ice_candidates.remove(candidate);
+        }
+        self.ice_candidates.extend(delta.added_ice_candidates);
+    }
+}
+
+/// Returns entries of the `new` [`HashMap`] which are absent or differ in the
+/// `old` one, and keys of the `old` [`HashMap`] which are absent in the `new`
+/// one.
+fn diff_map<K, V>(
+    old: &HashMap<K, V>,
+    new: &HashMap<K, V>,
+) -> (HashMap<K, V>, HashSet<K>)
+where
+    K: Copy + Eq + Hash,
+    V: Clone + PartialEq,
+{
+    let upserted = new
+        .iter()
+        .filter(|(k, v)| old.get(k) != Some(v))
+        .map(|(k, v)| (*k, v.clone()))
+        .collect();
+    let removed = old
+        .keys()
+        .filter(|k| !new.contains_key(k))
+        .copied()
+        .collect();
+    (upserted, removed)
+}
+
+/// Difference between two [`Room`] states, allowing to synchronize them
+/// partially.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RoomStateDelta {
+    /// [`Peer`]s which should be added.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub added_peers: HashMap<PeerId, Peer>,
+
+    /// Changes of the already existing [`Peer`]s.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub updated_peers: HashMap<PeerId, PeerStateDelta>,
+
+    /// IDs of the [`Peer`]s which should be removed.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub removed_peers: HashSet<PeerId>,
+}
+
+impl RoomStateDelta {
+    /// Indicates whether this [`RoomStateDelta`] contains no changes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added_peers.is_empty()
+            && self.updated_peers.is_empty()
+            && self.removed_peers.is_empty()
+    }
+}
+
+/// Difference between two [`Peer`] states.
+///
+/// [`None`] fields mean that the appropriate [`Peer`] field is unchanged.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PeerStateDelta {
+    /// [`Sender`]s which should be added or replaced.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub upserted_senders: HashMap<TrackId, Sender>,
+
+    /// IDs of the [`Sender`]s which should be removed.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub removed_senders: HashSet<TrackId>,
+
+    /// [`Receiver`]s which should be added or replaced.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub upserted_receivers: HashMap<TrackId, Receiver>,
+
+    /// IDs of the [`Receiver`]s which should be removed.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub removed_receivers: HashSet<TrackId>,
+
+    /// New value of the [`Peer::force_relay`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub force_relay: Option<bool>,
+
+    /// New value of the [`Peer::ice_servers`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ice_servers: Option<Vec<IceServer>>,
+
+    /// New value of the [`Peer::negotiation_role`].
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "serde_with::rust::double_option"
+    )]
+    pub negotiation_role: Option<Option<NegotiationRole>>,
+
+    /// New value of the [`Peer::local_sdp`].
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "serde_with::rust::double_option"
+    )]
+    pub local_sdp: Option<Option<String>>,
+
+    /// New value of the [`Peer::remote_sdp`].
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "serde_with::rust::double_option"
+    )]
+    pub remote_sdp: Option<Option<String>>,
+
+    /// New value of the [`Peer::restart_ice`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart_ice: Option<bool>,
+
+    /// [`IceCandidate`]s which should be added.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub added_ice_candidates: HashSet<IceCandidate>,
+
+    /// [`IceCandidate`]s which should be removed.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub removed_ice_candidates: HashSet<IceCandidate>,
+}
+
+impl PeerStateDelta {
+    /// Indicates whether this [`PeerStateDelta`] contains no changes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::AudioSettings;
+
+    fn peer(id: u32) -> Peer {
+        Peer {
+            id: PeerId(id),
+            senders: HashMap::new(),
+            receivers: HashMap::new(),
+            force_relay: false,
+            ice_servers: Vec::new(),
+            negotiation_role: None,
+            local_sdp: Some(String::from("local")),
+            remote_sdp: None,
+            restart_ice: false,
+            ice_candidates: vec![IceCandidate {
+                candidate: String::from("candidate"),
+                sdp_m_line_index: None,
+                sdp_mid: None,
+            }]
+            .into_iter()
+            .collect(),
+        }
+    }
+
+    fn room(peers: Vec<Peer>) -> Room {
+        Room {
+            peers: peers.into_iter().map(|p| (p.id, p)).collect(),
+        }
+    }
+
+    #[test]
+    fn diff_of_same_states_is_empty() {
+        let state = room(vec![peer(1), peer(2)]);
+
+        assert!(state.diff(&state.clone()).is_empty());
+    }
+
+    #[test]
+    fn applied_diff_produces_target_state() {
+        let mut current = room(vec![peer(1), peer(2)]);
+
+        let mut updated = peer(1);
+        updated.local_sdp = None;
+        updated.restart_ice = true;
+        updated.ice_candidates.clear();
+        drop(updated.senders.insert(
+            TrackId(1),
+            Sender {
+                id: TrackId(1),
+                mid: None,
+                ssrc: None,
+                media_type: MediaType::Audio(AudioSettings {
+                    required: true,
+                    opus: None,
+                }),
+                receivers: vec![MemberId::from("bob")],
+                enabled_individual: true,
+                enabled_general: true,
+                muted: false,
+            },
+        ));
+        let target = room(vec![updated, peer(3)]);
+
+        let delta = current.diff(&target);
+        assert_eq!(delta.added_peers.keys().collect::<Vec<_>>(), [&PeerId(3)]);
+        assert_eq!(
+            delta.removed_peers.iter().collect::<Vec<_>>(),
+            [&PeerId(2)],
+        );
+        assert_eq!(delta.updated_peers[&PeerId(1)].local_sdp, Some(None));
+        assert_eq!(delta.updated_peers[&PeerId(1)].remote_sdp, None);
+
+        current.apply_delta(delta);
+        assert_eq!(current, target);
+    }
+
+    #[test]
+    fn checksum_ignores_negotiation_related_fields() {
+        let state = room(vec![peer(1), peer(2)]);
+
+        let mut negotiated = state.clone();
+        for peer in negotiated.peers.values_mut() {
+            peer.local_sdp = None;
+            peer.remote_sdp = Some(String::from("remote"));
+            peer.ice_candidates.clear();
+        }
+        assert_eq!(state.checksum(), negotiated.checksum());
+        assert_eq!(state.checksum(), room(vec![peer(2), peer(1)]).checksum());
+
+        let mut changed = state.clone();
+        drop(changed.peers.remove(&PeerId(2)));
+        assert_ne!(state.checksum(), changed.checksum());
+    }
+
+    #[test]
+    fn delta_serialization_preserves_unset_values() {
+        let delta = PeerStateDelta {
+            local_sdp: Some(None),
+            ..PeerStateDelta::default()
+        };
+
+        let json = serde_json::to_string(&delta).unwrap();
+        assert_eq!(json, r#"{"local_sdp":null}"#);
+        assert_eq!(
+            serde_json::from_str::<PeerStateDelta>(&json).unwrap(),
+            delta,
+        );
+    }
+}
@@ -821,6 +821,14 @@ pub struct RtcIceCandidatePairStats {
     /// State of the checklist for the local and remote candidates in a pair.
     pub state: IceCandidatePairState,
 
+    /// ID of the [`RtcStatsType::LocalCandidate`] stats of the local
+    /// candidate in this pair.
+    pub local_candidate_id: Option<StatId>,
+
+    /// ID of the [`RtcStatsType::RemoteCandidate`] stats of the remote
+    /// candidate in this pair.
+    pub remote_candidate_id: Option<StatId>,
+
     /// Related to updating the nominated flag described in
     /// [Section 7.1.3.2.4 of RFC 5245][1].
     ///
@@ -1154,6 +1162,16 @@ pub struct RtcInboundRtpStreamStats {
     ///
     /// [`jitterBufferDelay`]: https://tinyurl.com/qvoojt5
     pub jitter_buffer_emitted_count: Option<u64>,
+
+    /// Sum of the time (in seconds) each audio sample or video frame takes
+    /// from the time the first packet is received by the jitter buffer to the
+    /// time it exits the jitter buffer.
+    ///
+    /// The average jitter buffer delay can be calculated by dividing this
+    /// value with [`jitterBufferEmittedCount`].
+    ///
+    /// [`jitterBufferEmittedCount`]: https://tinyurl.com/qvoojt5
+    pub jitter_buffer_delay: Option<Float>,
 }
 
 /// Statistics related to a specific [MediaStreamTrack][1]'s attachment to an
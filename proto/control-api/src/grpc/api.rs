@@ -7,6 +7,17 @@ pub struct CreateRequest {
     /// Spec of the created Element.
     #[prost(oneof="create_request::El", tags="2, 3, 4, 5")]
     pub el: ::core::option::Option<create_request::El>,
+    /// URL of the Callback service to notify about the progress of the Room
+    /// creation.
+    ///
+    /// If specified, then the Room is created asynchronously: its Members are
+    /// provisioned in background after the CreateResponse is returned, firing
+    /// OnCreateProgress and OnCreateComplete callbacks to this URL. If
+    /// provisioning of any element fails, then the whole Room is removed.
+    ///
+    /// Only applicable to Room creation.
+    #[prost(string, tag="6")]
+    pub on_progress: ::prost::alloc::string::String,
 }
 /// Nested message and enum types in `CreateRequest`.
 pub mod create_request {
@@ -23,6 +34,37 @@ pub mod create_request {
         WebrtcPub(super::WebRtcPublishEndpoint),
     }
 }
+/// Request of applying many operations to Elements of a single Room
+/// transactionally.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchRequest {
+    /// Operations to be applied, in order.
+    #[prost(message, repeated, tag="1")]
+    pub operations: ::prost::alloc::vec::Vec<BatchOperation>,
+}
+/// Single operation of a BatchRequest.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatchOperation {
+    #[prost(oneof="batch_operation::Op", tags="1, 2")]
+    pub op: ::core::option::Option<batch_operation::Op>,
+}
+/// Nested message and enum types in `BatchOperation`.
+pub mod batch_operation {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Op {
+        /// Creates a new Element in the Element with the given FID (full ID).
+        ///
+        /// Rooms can't be created in a batch.
+        #[prost(message, tag="1")]
+        Create(super::CreateRequest),
+        /// Removes an Element by its FID (full ID).
+        ///
+        /// Rooms can't be removed in a batch. Errors if the Element doesn't
+        /// exist.
+        #[prost(string, tag="2")]
+        Delete(::prost::alloc::string::String),
+    }
+}
 /// Request with many FIDs (full IDs) of Elements.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct IdRequest {
@@ -81,6 +123,12 @@ pub struct CreateResponse {
     /// Error of the CreateResponse.
     #[prost(message, optional, tag="2")]
     pub error: ::core::option::Option<Error>,
+    /// ID of the asynchronous creation operation, referred by its
+    /// OnCreateProgress and OnCreateComplete callbacks.
+    ///
+    /// Returned only if CreateRequest.on_progress is specified.
+    #[prost(string, tag="3")]
+    pub operation_id: ::prost::alloc::string::String,
 }
 /// Response of Get RPC method.
 ///
@@ -97,6 +145,402 @@ pub struct GetResponse {
     #[prost(message, optional, tag="2")]
     pub error: ::core::option::Option<Error>,
 }
+/// Request of the Room journal events.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RoomJournalRequest {
+    /// FID (full ID) of the Room to return the journal of.
+    #[prost(string, tag="1")]
+    pub room_fid: ::prost::alloc::string::String,
+    /// Sequence number of the last event already known by the client.
+    ///
+    /// Only events with a greater sequence number will be returned, so 0 returns
+    /// all the events stored in the journal.
+    #[prost(uint64, tag="2")]
+    pub since: u64,
+}
+/// Response of GetRoomJournal RPC method.
+///
+/// If operation fails then an Error will be returned.
+/// The response is considered successful only if it does not contain Error.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RoomJournalResponse {
+    /// Requested events of the Room journal, ordered by their sequence numbers.
+    ///
+    /// Returned only if RoomJournalResponse is successful.
+    #[prost(message, repeated, tag="1")]
+    pub events: ::prost::alloc::vec::Vec<RoomJournalEvent>,
+    /// Sequence number of the oldest event stored in the Room journal.
+    ///
+    /// If it's greater than RoomJournalRequest.since + 1, then some events were
+    /// evicted from the journal and cannot be replayed.
+    ///
+    /// Returned only if RoomJournalResponse is successful.
+    #[prost(uint64, tag="2")]
+    pub oldest_seq: u64,
+    /// Error of the RoomJournalResponse.
+    #[prost(message, optional, tag="3")]
+    pub error: ::core::option::Option<Error>,
+}
+/// High-level event occurred in a Room.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RoomJournalEvent {
+    /// Sequence number of this event, unique in its Room.
+    #[prost(uint64, tag="1")]
+    pub seq: u64,
+    /// Time of event occurring.
+    #[prost(string, tag="2")]
+    pub at: ::prost::alloc::string::String,
+    /// FID (full ID) of the Element that event was occurred with.
+    #[prost(string, tag="3")]
+    pub fid: ::prost::alloc::string::String,
+    /// Kind of this event.
+    #[prost(enumeration="room_journal_event::Kind", tag="4")]
+    pub kind: i32,
+    /// ID of the Peer that event was occurred with.
+    ///
+    /// Set only for PEER_CONNECTED events.
+    #[prost(uint32, tag="5")]
+    pub peer_id: u32,
+}
+/// Nested message and enum types in `RoomJournalEvent`.
+pub mod room_journal_event {
+    /// Possible kinds of a RoomJournalEvent.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Kind {
+        /// Member connected to its Room.
+        MemberJoined = 0,
+        /// Member disconnected from its Room.
+        MemberLeft = 1,
+        /// Endpoint was created in a Room.
+        EndpointCreated = 2,
+        /// Peer of a Member connected to its partner Peer.
+        PeerConnected = 3,
+    }
+}
+/// Request of putting the media server into drain mode.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DrainRequest {
+    /// Indicator whether the media server should gracefully shut down once it's
+    /// drained.
+    #[prost(bool, tag="1")]
+    pub shutdown: bool,
+}
+/// Response of Drain RPC method.
+///
+/// If operation fails then an Error will be returned.
+/// The response is considered successful only if it does not contain Error.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DrainResponse {
+    /// Number of Rooms still having connected Members.
+    #[prost(uint32, tag="1")]
+    pub rooms: u32,
+    /// Number of Members still connected to the media server.
+    ///
+    /// The media server is drained once it's 0.
+    #[prost(uint32, tag="2")]
+    pub members: u32,
+    /// Error of the DrainResponse.
+    #[prost(message, optional, tag="3")]
+    pub error: ::core::option::Option<Error>,
+}
+/// Request of the connection quality history of a Member.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QualityHistoryRequest {
+    /// FID (full ID) of the Member to return the connection quality history of.
+    #[prost(string, tag="1")]
+    pub member_fid: ::prost::alloc::string::String,
+}
+/// Response of GetQualityHistory RPC method.
+///
+/// If operation fails then an Error will be returned.
+/// The response is considered successful only if it does not contain Error.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QualityHistoryResponse {
+    /// Connection quality histories of the Member with each of its partner
+    /// Members.
+    ///
+    /// Returned only if QualityHistoryResponse is successful.
+    #[prost(message, repeated, tag="1")]
+    pub histories: ::prost::alloc::vec::Vec<QualityHistory>,
+    /// Error of the QualityHistoryResponse.
+    #[prost(message, optional, tag="2")]
+    pub error: ::core::option::Option<Error>,
+}
+/// History of connection quality scores estimated between two Members.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QualityHistory {
+    /// ID of the partner Member.
+    #[prost(string, tag="1")]
+    pub partner_member_id: ::prost::alloc::string::String,
+    /// Estimated connection quality scores, ordered from the oldest to the
+    /// newest.
+    ///
+    /// A new sample is recorded each time the score changes.
+    #[prost(message, repeated, tag="2")]
+    pub samples: ::prost::alloc::vec::Vec<QualitySample>,
+}
+/// Connection quality score estimated at some moment.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QualitySample {
+    /// Time of the score estimation.
+    #[prost(string, tag="1")]
+    pub at: ::prost::alloc::string::String,
+    /// Estimated connection quality score.
+    #[prost(enumeration="quality_sample::Score", tag="2")]
+    pub score: i32,
+    /// Indicator whether the connection was relayed via TURN server at the
+    /// moment of the score estimation.
+    #[prost(bool, tag="3")]
+    pub relayed: bool,
+}
+/// Nested message and enum types in `QualitySample`.
+pub mod quality_sample {
+    /// Possible connection quality scores.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Score {
+        /// Nearly all users dissatisfied.
+        Poor = 0,
+        /// Many users dissatisfied.
+        Low = 1,
+        /// Some users dissatisfied.
+        Medium = 2,
+        /// Satisfied.
+        High = 3,
+    }
+}
+/// Request of exporting a spec of a Room.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExportSpecRequest {
+    /// FID (full ID) of the Room to export the spec of.
+    #[prost(string, tag="1")]
+    pub room_fid: ::prost::alloc::string::String,
+}
+/// Response of ExportSpec RPC method.
+///
+/// If operation fails then an Error will be returned.
+/// The response is considered successful only if it does not contain Error.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExportSpecResponse {
+    /// YAML spec of the Room.
+    ///
+    /// Returned only if ExportSpecResponse is successful.
+    #[prost(string, tag="1")]
+    pub spec: ::prost::alloc::string::String,
+    /// Error of the ExportSpecResponse.
+    #[prost(message, optional, tag="2")]
+    pub error: ::core::option::Option<Error>,
+}
+/// Request of importing a spec of a Room.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImportSpecRequest {
+    /// YAML spec of the Room to be applied.
+    ///
+    /// Spec without a version is considered to be of the latest one.
+    #[prost(string, tag="1")]
+    pub spec: ::prost::alloc::string::String,
+}
+/// Request of creating new Room from a template.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateFromTemplateRequest {
+    /// ID of the template to create the Room from.
+    #[prost(string, tag="1")]
+    pub template_id: ::prost::alloc::string::String,
+    /// ID of the created Room.
+    #[prost(string, tag="2")]
+    pub room_id: ::prost::alloc::string::String,
+    /// Values of the template parameters.
+    ///
+    /// Parameters which are not provided take their default values.
+    #[prost(map="string, string", tag="3")]
+    pub params: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+}
+/// Request of the memory usage of Rooms.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RoomsUsageRequest {
+    /// FIDs (full IDs) of the Rooms to return the memory usage of.
+    ///
+    /// Memory usage of all the Rooms is returned if empty.
+    #[prost(string, repeated, tag="1")]
+    pub room_fids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// Response of GetRoomsUsage RPC method.
+///
+/// If operation fails then an Error will be returned.
+/// The response is considered successful only if it does not contain Error.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RoomsUsageResponse {
+    /// Memory usage of the requested Rooms by their FIDs.
+    ///
+    /// Returned only if RoomsUsageResponse is successful.
+    #[prost(map="string, message", tag="1")]
+    pub rooms: ::std::collections::HashMap<::prost::alloc::string::String, RoomUsage>,
+    /// Error of the RoomsUsageResponse.
+    #[prost(message, optional, tag="2")]
+    pub error: ::core::option::Option<Error>,
+}
+/// Approximate amounts of entities kept in memory by a Room.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RoomUsage {
+    /// Number of Members in the Room.
+    #[prost(uint64, tag="1")]
+    pub members: u64,
+    /// Number of Peers in the Room.
+    #[prost(uint64, tag="2")]
+    pub peers: u64,
+    /// Number of Tracks of all the Peers in the Room.
+    #[prost(uint64, tag="3")]
+    pub tracks: u64,
+    /// Number of Peer changes scheduled, but not sent to Members yet.
+    #[prost(uint64, tag="4")]
+    pub pending_peer_changes: u64,
+    /// Number of ICE candidates stored for all the Peers in the Room.
+    #[prost(uint64, tag="5")]
+    pub ice_candidates: u64,
+    /// Number of events stored in the journal of the Room.
+    #[prost(uint64, tag="6")]
+    pub journal_events: u64,
+    /// Number of entries kept for calculating metrics of the Peers (stats of
+    /// Peers and their Tracks, connection quality samples).
+    #[prost(uint64, tag="7")]
+    pub metrics_entries: u64,
+    /// Approximate lower bound of the memory occupied by the entities above,
+    /// in bytes.
+    #[prost(uint64, tag="8")]
+    pub approx_bytes: u64,
+    /// Number of entries evicted from the ones kept for calculating metrics of
+    /// the Peers due to the configured retention.
+    #[prost(uint64, tag="9")]
+    pub metrics_evicted: u64,
+}
+/// Request of the capabilities reported by the Client of a Member.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClientCapabilitiesRequest {
+    /// FID (full ID) of the Member to return the Client capabilities of.
+    #[prost(string, tag="1")]
+    pub member_fid: ::prost::alloc::string::String,
+}
+/// Response of GetClientCapabilities RPC method.
+///
+/// If operation fails then an Error will be returned.
+/// The response is considered successful only if it does not contain Error.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClientCapabilitiesResponse {
+    /// Capabilities reported by the Client of the Member.
+    ///
+    /// Not returned if the Member has not joined its Room yet.
+    #[prost(message, optional, tag="1")]
+    pub capabilities: ::core::option::Option<ClientCapabilities>,
+    /// Error of the ClientCapabilitiesResponse.
+    #[prost(message, optional, tag="2")]
+    pub error: ::core::option::Option<Error>,
+}
+/// Capabilities reported by a Client when joining a Room.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClientCapabilities {
+    /// User agent of the Client.
+    ///
+    /// Empty if the Client has not reported it.
+    #[prost(string, tag="1")]
+    pub user_agent: ::prost::alloc::string::String,
+    /// MIME types of the codecs supported by the Client (e.g. "video/VP8").
+    #[prost(string, repeated, tag="2")]
+    pub codecs: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Indicator whether the Client supports sending simulcast.
+    #[prost(bool, tag="3")]
+    pub simulcast: bool,
+    /// Indicator whether the Client supports insertable streams, required for
+    /// end-to-end encryption.
+    #[prost(bool, tag="4")]
+    pub insertable_streams: bool,
+}
+/// Request of forcibly updating the tracks of an Endpoint.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PatchTrackRequest {
+    /// FID (full ID) of the Endpoint to update the tracks of.
+    #[prost(string, tag="1")]
+    pub endpoint_fid: ::prost::alloc::string::String,
+    /// Kind of the tracks to be updated.
+    #[prost(enumeration="patch_track_request::TrackKind", tag="2")]
+    pub kind: i32,
+    /// Change of the tracks being enabled.
+    #[prost(enumeration="patch_track_request::Switch", tag="3")]
+    pub enabled: i32,
+    /// Change of the tracks being muted.
+    #[prost(enumeration="patch_track_request::Switch", tag="4")]
+    pub muted: i32,
+    /// URL of the Callback service to notify once the patch is applied by all
+    /// the affected Clients.
+    ///
+    /// If specified, then OnPatchApplied callback is fired to this URL once
+    /// both the Endpoint's Client and the Clients of its partners have received
+    /// the patch and finished the renegotiation it requires (if any).
+    #[prost(string, tag="5")]
+    pub on_applied: ::prost::alloc::string::String,
+}
+/// Nested message and enum types in `PatchTrackRequest`.
+pub mod patch_track_request {
+    /// Kind of the tracks to be updated.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum TrackKind {
+        /// Both audio and video tracks.
+        All = 0,
+        /// Audio tracks only.
+        Audio = 1,
+        /// Video tracks only.
+        Video = 2,
+    }
+    /// Change of a track property.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+    #[repr(i32)]
+    pub enum Switch {
+        /// Property is left unchanged.
+        Keep = 0,
+        /// Property is turned on.
+        On = 1,
+        /// Property is turned off.
+        Off = 2,
+    }
+}
+/// Response of PatchTrack RPC method.
+///
+/// If operation fails then an Error will be returned.
+/// The response is considered successful only if it does not contain Error.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PatchTrackResponse {
+    /// Error of the PatchTrackResponse.
+    #[prost(message, optional, tag="1")]
+    pub error: ::core::option::Option<Error>,
+    /// ID of the patch operation, referred by its OnPatchApplied callback.
+    ///
+    /// Returned only if PatchTrackRequest.on_applied is specified.
+    #[prost(string, tag="2")]
+    pub operation_id: ::prost::alloc::string::String,
+}
+/// Request of dumping the live state of a Room.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DumpRoomRequest {
+    /// FID (full ID) of the Room to dump the state of.
+    #[prost(string, tag="1")]
+    pub room_fid: ::prost::alloc::string::String,
+}
+/// Response of DumpRoom RPC method.
+///
+/// If operation fails then an Error will be returned.
+/// The response is considered successful only if it does not contain Error.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DumpRoomResponse {
+    /// JSON snapshot of the Room state.
+    ///
+    /// Returned only if DumpRoomResponse is successful.
+    #[prost(string, tag="1")]
+    pub snapshot: ::prost::alloc::string::String,
+    /// Error of the DumpRoomResponse.
+    #[prost(message, optional, tag="2")]
+    pub error: ::core::option::Option<Error>,
+}
 /// Error of failed request.
 ///
 /// If the Error is not returned then request is considered as successful.
@@ -202,6 +646,18 @@ pub struct Member {
     /// Pipeline of this Member.
     #[prost(map="string, message", tag="9")]
     pub pipeline: ::std::collections::HashMap<::prost::alloc::string::String, member::Element>,
+    /// Arbitrary key-value metadata of this Member (display name, avatar URL,
+    /// etc.), which is delivered to other Members of the Room via Client API.
+    #[prost(map="string, string", tag="10")]
+    pub metadata: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
+    /// Callback which fires when a Peer of the Member doesn't finish its
+    /// negotiation in time, so is removed along with its partner Peer.
+    #[prost(string, tag="11")]
+    pub on_negotiation_timeout: ::prost::alloc::string::String,
+    /// Key-value feature flags of this Member, which are delivered to the Member
+    /// only via Client API and override the ones configured on a media server.
+    #[prost(map="string, string", tag="12")]
+    pub features: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
     /// Credentials of the Member to authorize via Client API with.
     ///
     /// Plain and hashed credentials are supported. If no credentials provided,
@@ -285,13 +741,48 @@ pub mod web_rtc_publish_endpoint {
         /// Policy to publish audio media type with.
         #[prost(enumeration="PublishPolicy", tag="1")]
         pub publish_policy: i32,
+        /// Parameters of the Opus codec to encode audio with.
+        ///
+        /// If not specified, then the defaults of client's user agent are used.
+        #[prost(message, optional, tag="2")]
+        pub opus: ::core::option::Option<OpusParameters>,
+    }
+    /// Parameters of the Opus codec negotiated for the audio of
+    /// WebRtcPublishEndpoint (see RFC 7587).
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct OpusParameters {
+        /// Option to use discontinuous transmission (DTX), so nothing is sent
+        /// during silence ("usedtx").
+        #[prost(bool, tag="1")]
+        pub dtx: bool,
+        /// Option to use Opus in-band forward error correction (FEC)
+        /// ("useinbandfec").
+        #[prost(bool, tag="2")]
+        pub inband_fec: bool,
+        /// Option to encode stereo audio ("stereo").
+        #[prost(bool, tag="3")]
+        pub stereo: bool,
+        /// Maximum average bitrate (in bits per second) of the encoded audio
+        /// ("maxaveragebitrate").
+        ///
+        /// 0 means the default of client's user agent.
+        #[prost(uint32, tag="4")]
+        pub max_average_bitrate: u32,
     }
     /// Video media type settings of WebRtcPublishEndpoint.
     #[derive(Clone, PartialEq, ::prost::Message)]
     pub struct VideoSettings {
         /// Policy to publish video media type with.
+        ///
+        /// Applies to the video captured from a device (camera).
         #[prost(enumeration="PublishPolicy", tag="1")]
         pub publish_policy: i32,
+        /// Policy to publish video captured from a display with.
+        ///
+        /// Display video is published alongside the device one, so it's never
+        /// published if the video media type is DISABLED.
+        #[prost(enumeration="PublishPolicy", tag="2")]
+        pub display_publish_policy: i32,
     }
     /// Policy of how the video or audio media type can be published in
     /// WebRtcPublishEndpoint.
@@ -336,6 +827,9 @@ pub struct WebRtcPlayEndpoint {
     #[prost(string, tag="1")]
     pub id: ::prost::alloc::string::String,
     /// The source to get media data from.
+    ///
+    /// `*` may be used instead of a Member ID (`local://room/*/publish`) to
+    /// receive media from all the Members having such WebRtcPublishEndpoint.
     #[prost(string, tag="2")]
     pub src: ::prost::alloc::string::String,
     /// Callback which fires when a client starts playing media data
@@ -350,4 +844,4 @@ pub struct WebRtcPlayEndpoint {
     #[prost(bool, tag="5")]
     pub force_relay: bool,
 }
-# [doc = r" Generated client implementations."] pub mod control_api_client { # ! [allow (unused_variables , dead_code , missing_docs)] use tonic :: codegen :: * ; # [doc = " Media server's Control API service."] pub struct ControlApiClient < T > { inner : tonic :: client :: Grpc < T > , } impl ControlApiClient < tonic :: transport :: Channel > { # [doc = r" Attempt to create a new client by connecting to a given endpoint."] pub async fn connect < D > (dst : D) -> Result < Self , tonic :: transport :: Error > where D : std :: convert :: TryInto < tonic :: transport :: Endpoint > , D :: Error : Into < StdError > , { let conn = tonic :: transport :: Endpoint :: new (dst) ? . connect () . await ? ; Ok (Self :: new (conn)) } } impl < T > ControlApiClient < T > where T : tonic :: client :: GrpcService < tonic :: body :: BoxBody > , T :: ResponseBody : Body + HttpBody + Send + 'static , T :: Error : Into < StdError > , < T :: ResponseBody as HttpBody > :: Error : Into < StdError > + Send , { pub fn new (inner : T) -> Self { let inner = tonic :: client :: Grpc :: new (inner) ; Self { inner } } pub fn with_interceptor (inner : T , interceptor : impl Into < tonic :: Interceptor >) -> Self { let inner = tonic :: client :: Grpc :: with_interceptor (inner , interceptor) ; Self { inner } } # [doc = " Creates new Element with a given ID."] # [doc = ""] # [doc = " Not idempotent. Errors if an Element with the same ID already exists."] pub async fn create (& mut self , request : impl tonic :: IntoRequest < super :: CreateRequest > ,) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/Create") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Removes Element by its ID."] # [doc = " Allows referring multiple Elements on the last two levels."] # [doc = ""] # [doc = " Idempotent. If no Elements with such IDs exist, then succeeds."] pub async fn delete (& mut self , request : impl tonic :: IntoRequest < super :: IdRequest > ,) -> Result < tonic :: Response < super :: Response > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/Delete") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Returns Element by its ID."] # [doc = " Allows referring multiple Elements."] # [doc = " If no ID specified, returns all Elements declared."] pub async fn get (& mut self , request : impl tonic :: IntoRequest < super :: IdRequest > ,) -> Result < tonic :: Response < super :: GetResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/Get") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Applies the given spec to Element by its ID."] # [doc = ""] # [doc = " Idempotent. If no Element with such ID exists, then it will be created,"] # [doc = " otherwise it will be reconfigured. Elements that exist, but are not"] # [doc = " specified in the provided spec will be removed."] pub async fn apply (& mut self , request : impl tonic :: IntoRequest < super :: ApplyRequest > ,) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/Apply") ; self . inner . unary (request . into_request () , path , codec) . await } } impl < T : Clone > Clone for ControlApiClient < T > { fn clone (& self) -> Self { Self { inner : self . inner . clone () , } } } impl < T > std :: fmt :: Debug for ControlApiClient < T > { fn fmt (& self , f : & mut std :: fmt :: Formatter < '_ >) -> std :: fmt :: Result { write ! (f , "ControlApiClient {{ ... }}") } } }# [doc = r" Generated server implementations."] pub mod control_api_server { # ! [allow (unused_variables , dead_code , missing_docs)] use tonic :: codegen :: * ; # [doc = "Generated trait containing gRPC methods that should be implemented for use with ControlApiServer."] # [async_trait] pub trait ControlApi : Send + Sync + 'static { # [doc = " Creates new Element with a given ID."] # [doc = ""] # [doc = " Not idempotent. Errors if an Element with the same ID already exists."] async fn create (& self , request : tonic :: Request < super :: CreateRequest >) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > ; # [doc = " Removes Element by its ID."] # [doc = " Allows referring multiple Elements on the last two levels."] # [doc = ""] # [doc = " Idempotent. If no Elements with such IDs exist, then succeeds."] async fn delete (& self , request : tonic :: Request < super :: IdRequest >) -> Result < tonic :: Response < super :: Response > , tonic :: Status > ; # [doc = " Returns Element by its ID."] # [doc = " Allows referring multiple Elements."] # [doc = " If no ID specified, returns all Elements declared."] async fn get (& self , request : tonic :: Request < super :: IdRequest >) -> Result < tonic :: Response < super :: GetResponse > , tonic :: Status > ; # [doc = " Applies the given spec to Element by its ID."] # [doc = ""] # [doc = " Idempotent. If no Element with such ID exists, then it will be created,"] # [doc = " otherwise it will be reconfigured. Elements that exist, but are not"] # [doc = " specified in the provided spec will be removed."] async fn apply (& self , request : tonic :: Request < super :: ApplyRequest >) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > ; } # [doc = " Media server's Control API service."] # [derive (Debug)] pub struct ControlApiServer < T : ControlApi > { inner : _Inner < T > , } struct _Inner < T > (Arc < T > , Option < tonic :: Interceptor >) ; impl < T : ControlApi > ControlApiServer < T > { pub fn new (inner : T) -> Self { let inner = Arc :: new (inner) ; let inner = _Inner (inner , None) ; Self { inner } } pub fn with_interceptor (inner : T , interceptor : impl Into < tonic :: Interceptor >) -> Self { let inner = Arc :: new (inner) ; let inner = _Inner (inner , Some (interceptor . into ())) ; Self { inner } } } impl < T , B > Service < http :: Request < B >> for ControlApiServer < T > where T : ControlApi , B : HttpBody + Send + Sync + 'static , B :: Error : Into < StdError > + Send + 'static , { type Response = http :: Response < tonic :: body :: BoxBody > ; type Error = Never ; type Future = BoxFuture < Self :: Response , Self :: Error > ; fn poll_ready (& mut self , _cx : & mut Context < '_ >) -> Poll < Result < () , Self :: Error >> { Poll :: Ready (Ok (())) } fn call (& mut self , req : http :: Request < B >) -> Self :: Future { let inner = self . inner . clone () ; match req . uri () . path () { "/api.ControlApi/Create" => { # [allow (non_camel_case_types)] struct CreateSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: CreateRequest > for CreateSvc < T > { type Response = super :: CreateResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: CreateRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . create (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = CreateSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/Delete" => { # [allow (non_camel_case_types)] struct DeleteSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: IdRequest > for DeleteSvc < T > { type Response = super :: Response ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: IdRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . delete (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = DeleteSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/Get" => { # [allow (non_camel_case_types)] struct GetSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: IdRequest > for GetSvc < T > { type Response = super :: GetResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: IdRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . get (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = GetSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/Apply" => { # [allow (non_camel_case_types)] struct ApplySvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: ApplyRequest > for ApplySvc < T > { type Response = super :: CreateResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: ApplyRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . apply (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = ApplySvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } _ => Box :: pin (async move { Ok (http :: Response :: builder () . status (200) . header ("grpc-status" , "12") . header ("content-type" , "application/grpc") . body (tonic :: body :: BoxBody :: empty ()) . unwrap ()) }) , } } } impl < T : ControlApi > Clone for ControlApiServer < T > { fn clone (& self) -> Self { let inner = self . inner . clone () ; Self { inner } } } impl < T : ControlApi > Clone for _Inner < T > { fn clone (& self) -> Self { Self (self . 0 . clone () , self . 1 . clone ()) } } impl < T : std :: fmt :: Debug > std :: fmt :: Debug for _Inner < T > { fn fmt (& self , f : & mut std :: fmt :: Formatter < '_ >) -> std :: fmt :: Result { write ! (f , "{:?}" , self . 0) } } impl < T : ControlApi > tonic :: transport :: NamedService for ControlApiServer < T > { const NAME : & 'static str = "api.ControlApi" ; } }
\ No newline at end of file
+# [doc = r" Generated client implementations."] pub mod control_api_client { # ! [allow (unused_variables , dead_code , missing_docs)] use tonic :: codegen :: * ; # [doc = " Media server's Control API service."] pub struct ControlApiClient < T > { inner : tonic :: client :: Grpc < T > , } impl ControlApiClient < tonic :: transport :: Channel > { # [doc = r" Attempt to create a new client by connecting to a given endpoint."] pub async fn connect < D > (dst : D) -> Result < Self , tonic :: transport :: Error > where D : std :: convert :: TryInto < tonic :: transport :: Endpoint > , D :: Error : Into < StdError > , { let conn = tonic :: transport :: Endpoint :: new (dst) ? . connect () . await ? ; Ok (Self :: new (conn)) } } impl < T > ControlApiClient < T > where T : tonic :: client :: GrpcService < tonic :: body :: BoxBody > , T :: ResponseBody : Body + HttpBody + Send + 'static , T :: Error : Into < StdError > , < T :: ResponseBody as HttpBody > :: Error : Into < StdError > + Send , { pub fn new (inner : T) -> Self { let inner = tonic :: client :: Grpc :: new (inner) ; Self { inner } } pub fn with_interceptor (inner : T , interceptor : impl Into < tonic :: Interceptor >) -> Self { let inner = tonic :: client :: Grpc :: with_interceptor (inner , interceptor) ; Self { inner } } # [doc = " Creates new Element with a given ID."] # [doc = ""] # [doc = " Not idempotent. Errors if an Element with the same ID already exists."] pub async fn create (& mut self , request : impl tonic :: IntoRequest < super :: CreateRequest > ,) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/Create") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Removes Element by its ID."] # [doc = " Allows referring multiple Elements on the last two levels."] # [doc = ""] # [doc = " Idempotent. If no Elements with such IDs exist, then succeeds."] pub async fn delete (& mut self , request : impl tonic :: IntoRequest < super :: IdRequest > ,) -> Result < tonic :: Response < super :: Response > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/Delete") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Returns Element by its ID."] # [doc = " Allows referring multiple Elements."] # [doc = " If no ID specified, returns all Elements declared."] pub async fn get (& mut self , request : impl tonic :: IntoRequest < super :: IdRequest > ,) -> Result < tonic :: Response < super :: GetResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/Get") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Applies the given spec to Element by its ID."] # [doc = ""] # [doc = " Idempotent. If no Element with such ID exists, then it will be created,"] # [doc = " otherwise it will be reconfigured. Elements that exist, but are not"] # [doc = " specified in the provided spec will be removed."] pub async fn apply (& mut self , request : impl tonic :: IntoRequest < super :: ApplyRequest > ,) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/Apply") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Returns events of the Room journal, which occurred after the provided"] # [doc = " sequence number."] # [doc = ""] # [doc = " Room journal is bounded, so the oldest events may be already evicted from"] # [doc = " it. Compare RoomJournalResponse.oldest_seq with the requested one to"] # [doc = " detect this."] pub async fn get_room_journal (& mut self , request : impl tonic :: IntoRequest < super :: RoomJournalRequest > ,) -> Result < tonic :: Response < super :: RoomJournalResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/GetRoomJournal") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Applies the given create/delete operations to Elements of a single Room"] # [doc = " transactionally."] # [doc = ""] # [doc = " Creations are applied in the given order, and removals are applied"] # [doc = " after all of them. If any of them fails, then none of them is applied."] # [doc = ""] # [doc = " Elements removed by a batch can't be created by it again."] pub async fn batch (& mut self , request : impl tonic :: IntoRequest < super :: BatchRequest > ,) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/Batch") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Puts the media server into drain mode: new Members are not allowed to"] # [doc = " join its Rooms anymore (being rejected with a retryable close code),"] # [doc = " while the already joined ones may finish their sessions."] # [doc = ""] # [doc = " Idempotent. Returns the current drain progress, so may be called"] # [doc = " repeatedly to track it."] pub async fn drain (& mut self , request : impl tonic :: IntoRequest < super :: DrainRequest > ,) -> Result < tonic :: Response < super :: DrainResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/Drain") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Returns the history of connection quality scores estimated between the"] # [doc = " given Member and each of its partner Members."] # [doc = ""] # [doc = " History is bounded, so only the latest scores are returned."] pub async fn get_quality_history (& mut self , request : impl tonic :: IntoRequest < super :: QualityHistoryRequest > ,) -> Result < tonic :: Response < super :: QualityHistoryResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/GetQualityHistory") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Exports the spec of the given Room as YAML document, marked with the"] # [doc = " version of its schema."] pub async fn export_spec (& mut self , request : impl tonic :: IntoRequest < super :: ExportSpecRequest > ,) -> Result < tonic :: Response < super :: ExportSpecResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/ExportSpec") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Applies the given YAML spec of a Room."] # [doc = ""] # [doc = " Idempotent. Has the same semantics as Apply of a Room, so the exported"] # [doc = " spec may be imported back as is."] pub async fn import_spec (& mut self , request : impl tonic :: IntoRequest < super :: ImportSpecRequest > ,) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/ImportSpec") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Creates new Room from the given template of the media server, substituting"] # [doc = " its placeholders with the given parameters."] # [doc = ""] # [doc = " Not idempotent. Errors if a Room with the same ID already exists."] pub async fn create_from_template (& mut self , request : impl tonic :: IntoRequest < super :: CreateFromTemplateRequest > ,) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/CreateFromTemplate") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Returns approximate amounts of entities kept in memory by the given Rooms"] # [doc = " (or all the Rooms, if none given), helping to find the leaking ones."] pub async fn get_rooms_usage (& mut self , request : impl tonic :: IntoRequest < super :: RoomsUsageRequest > ,) -> Result < tonic :: Response < super :: RoomsUsageResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/GetRoomsUsage") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Pauses media exchange in the Rooms with the given IDs, while keeping"] # [doc = " their Members' sessions and Peers alive."] # [doc = ""] # [doc = " Idempotent. If media exchange is already paused, then succeeds."] pub async fn pause_room (& mut self , request : impl tonic :: IntoRequest < super :: IdRequest > ,) -> Result < tonic :: Response < super :: Response > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/PauseRoom") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Resumes media exchange in the Rooms with the given IDs, previously paused"] # [doc = " with PauseRoom."] # [doc = ""] # [doc = " Idempotent. If media exchange is not paused, then succeeds."] pub async fn resume_room (& mut self , request : impl tonic :: IntoRequest < super :: IdRequest > ,) -> Result < tonic :: Response < super :: Response > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/ResumeRoom") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Returns capabilities reported by the Client of the given Member when it"] # [doc = " joined its Room."] pub async fn get_client_capabilities (& mut self , request : impl tonic :: IntoRequest < super :: ClientCapabilitiesRequest > ,) -> Result < tonic :: Response < super :: ClientCapabilitiesResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/GetClientCapabilities") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Forcibly updates the tracks of the given Endpoint (e.g. disables the"] # [doc = " camera of a specific Member), regardless of its Client's will."] # [doc = ""] # [doc = " Idempotent. Succeeds if the Endpoint has no matching tracks yet."] pub async fn patch_track (& mut self , request : impl tonic :: IntoRequest < super :: PatchTrackRequest > ,) -> Result < tonic :: Response < super :: PatchTrackResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/PatchTrack") ; self . inner . unary (request . into_request () , path , codec) . await } # [doc = " Dumps the complete live state of the given Room (Members, Peers with"] # [doc = " their negotiation states and scheduled changes, metrics summary) as JSON,"] # [doc = " to debug stuck negotiations offline."] # [doc = ""] # [doc = " Admin-only: requires one of the admin API keys to be provided."] pub async fn dump_room (& mut self , request : impl tonic :: IntoRequest < super :: DumpRoomRequest > ,) -> Result < tonic :: Response < super :: DumpRoomResponse > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/api.ControlApi/DumpRoom") ; self . inner . unary (request . into_request () , path , codec) . await } } impl < T : Clone > Clone for ControlApiClient < T > { fn clone (& self) -> Self { Self { inner : self . inner . clone () , } } } impl < T > std :: fmt :: Debug for ControlApiClient < T > { fn fmt (& self , f : & mut std :: fmt :: Formatter < '_ >) -> std :: fmt :: Result { write ! (f , "ControlApiClient {{ ... }}") } } }# [doc = r" Generated server implementations."] pub mod control_api_server { # ! [allow (unused_variables , dead_code , missing_docs)] use tonic :: codegen :: * ; # [doc = "Generated trait containing gRPC methods that should be implemented for use with ControlApiServer."] # [async_trait] pub trait ControlApi : Send + Sync + 'static { # [doc = " Creates new Element with a given ID."] # [doc = ""] # [doc = " Not idempotent. Errors if an Element with the same ID already exists."] async fn create (& self , request : tonic :: Request < super :: CreateRequest >) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > ; # [doc = " Removes Element by its ID."] # [doc = " Allows referring multiple Elements on the last two levels."] # [doc = ""] # [doc = " Idempotent. If no Elements with such IDs exist, then succeeds."] async fn delete (& self , request : tonic :: Request < super :: IdRequest >) -> Result < tonic :: Response < super :: Response > , tonic :: Status > ; # [doc = " Returns Element by its ID."] # [doc = " Allows referring multiple Elements."] # [doc = " If no ID specified, returns all Elements declared."] async fn get (& self , request : tonic :: Request < super :: IdRequest >) -> Result < tonic :: Response < super :: GetResponse > , tonic :: Status > ; # [doc = " Applies the given spec to Element by its ID."] # [doc = ""] # [doc = " Idempotent. If no Element with such ID exists, then it will be created,"] # [doc = " otherwise it will be reconfigured. Elements that exist, but are not"] # [doc = " specified in the provided spec will be removed."] async fn apply (& self , request : tonic :: Request < super :: ApplyRequest >) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > ; # [doc = " Returns events of the Room journal, which occurred after the provided"] # [doc = " sequence number."] # [doc = ""] # [doc = " Room journal is bounded, so the oldest events may be already evicted from"] # [doc = " it. Compare RoomJournalResponse.oldest_seq with the requested one to"] # [doc = " detect this."] async fn get_room_journal (& self , request : tonic :: Request < super :: RoomJournalRequest >) -> Result < tonic :: Response < super :: RoomJournalResponse > , tonic :: Status > ; # [doc = " Applies the given create/delete operations to Elements of a single Room"] # [doc = " transactionally."] # [doc = ""] # [doc = " Creations are applied in the given order, and removals are applied"] # [doc = " after all of them. If any of them fails, then none of them is applied."] # [doc = ""] # [doc = " Elements removed by a batch can't be created by it again."] async fn batch (& self , request : tonic :: Request < super :: BatchRequest >) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > ; # [doc = " Puts the media server into drain mode: new Members are not allowed to"] # [doc = " join its Rooms anymore (being rejected with a retryable close code),"] # [doc = " while the already joined ones may finish their sessions."] # [doc = ""] # [doc = " Idempotent. Returns the current drain progress, so may be called"] # [doc = " repeatedly to track it."] async fn drain (& self , request : tonic :: Request < super :: DrainRequest >) -> Result < tonic :: Response < super :: DrainResponse > , tonic :: Status > ; # [doc = " Returns the history of connection quality scores estimated between the"] # [doc = " given Member and each of its partner Members."] # [doc = ""] # [doc = " History is bounded, so only the latest scores are returned."] async fn get_quality_history (& self , request : tonic :: Request < super :: QualityHistoryRequest >) -> Result < tonic :: Response < super :: QualityHistoryResponse > , tonic :: Status > ; # [doc = " Exports the spec of the given Room as YAML document, marked with the"] # [doc = " version of its schema."] async fn export_spec (& self , request : tonic :: Request < super :: ExportSpecRequest >) -> Result < tonic :: Response < super :: ExportSpecResponse > , tonic :: Status > ; # [doc = " Applies the given YAML spec of a Room."] # [doc = ""] # [doc = " Idempotent. Has the same semantics as Apply of a Room, so the exported"] # [doc = " spec may be imported back as is."] async fn import_spec (& self , request : tonic :: Request < super :: ImportSpecRequest >) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > ; # [doc = " Creates new Room from the given template of the media server, substituting"] # [doc = " its placeholders with the given parameters."] # [doc = ""] # [doc = " Not idempotent. Errors if a Room with the same ID already exists."] async fn create_from_template (& self , request : tonic :: Request < super :: CreateFromTemplateRequest >) -> Result < tonic :: Response < super :: CreateResponse > , tonic :: Status > ; # [doc = " Returns approximate amounts of entities kept in memory by the given Rooms"] # [doc = " (or all the Rooms, if none given), helping to find the leaking ones."] async fn get_rooms_usage (& self , request : tonic :: Request < super :: RoomsUsageRequest >) -> Result < tonic :: Response < super :: RoomsUsageResponse > , tonic :: Status > ; # [doc = " Pauses media exchange in the Rooms with the given IDs, while keeping"] # [doc = " their Members' sessions and Peers alive."] # [doc = ""] # [doc = " Idempotent. If media exchange is already paused, then succeeds."] async fn pause_room (& self , request : tonic :: Request < super :: IdRequest >) -> Result < tonic :: Response < super :: Response > , tonic :: Status > ; # [doc = " Resumes media exchange in the Rooms with the given IDs, previously paused"] # [doc = " with PauseRoom."] # [doc = ""] # [doc = " Idempotent. If media exchange is not paused, then succeeds."] async fn resume_room (& self , request : tonic :: Request < super :: IdRequest >) -> Result < tonic :: Response < super :: Response > , tonic :: Status > ; # [doc = " Returns capabilities reported by the Client of the given Member when it"] # [doc = " joined its Room."] async fn get_client_capabilities (& self , request : tonic :: Request < super :: ClientCapabilitiesRequest >) -> Result < tonic :: Response < super :: ClientCapabilitiesResponse > , tonic :: Status > ; # [doc = " Forcibly updates the tracks of the given Endpoint (e.g. disables the"] # [doc = " camera of a specific Member), regardless of its Client's will."] # [doc = ""] # [doc = " Idempotent. Succeeds if the Endpoint has no matching tracks yet."] async fn patch_track (& self , request : tonic :: Request < super :: PatchTrackRequest >) -> Result < tonic :: Response < super :: PatchTrackResponse > , tonic :: Status > ; # [doc = " Dumps the complete live state of the given Room (Members, Peers with"] # [doc = " their negotiation states and scheduled changes, metrics summary) as JSON,"] # [doc = " to debug stuck negotiations offline."] # [doc = ""] # [doc = " Admin-only: requires one of the admin API keys to be provided."] async fn dump_room (& self , request : tonic :: Request < super :: DumpRoomRequest >) -> Result < tonic :: Response < super :: DumpRoomResponse > , tonic :: Status > ; } # [doc = " Media server's Control API service."] # [derive (Debug)] pub struct ControlApiServer < T : ControlApi > { inner : _Inner < T > , } struct _Inner < T > (Arc < T > , Option < tonic :: Interceptor >) ; impl < T : ControlApi > ControlApiServer < T > { pub fn new (inner : T) -> Self { let inner = Arc :: new (inner) ; let inner = _Inner (inner , None) ; Self { inner } } pub fn with_interceptor (inner : T , interceptor : impl Into < tonic :: Interceptor >) -> Self { let inner = Arc :: new (inner) ; let inner = _Inner (inner , Some (interceptor . into ())) ; Self { inner } } } impl < T , B > Service < http :: Request < B >> for ControlApiServer < T > where T : ControlApi , B : HttpBody + Send + Sync + 'static , B :: Error : Into < StdError > + Send + 'static , { type Response = http :: Response < tonic :: body :: BoxBody > ; type Error = Never ; type Future = BoxFuture < Self :: Response , Self :: Error > ; fn poll_ready (& mut self , _cx : & mut Context < '_ >) -> Poll < Result < () , Self :: Error >> { Poll :: Ready (Ok (())) } fn call (& mut self , req : http :: Request < B >) -> Self :: Future { let inner = self . inner . clone () ; match req . uri () . path () { "/api.ControlApi/Create" => { # [allow (non_camel_case_types)] struct CreateSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: CreateRequest > for CreateSvc < T > { type Response = super :: CreateResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: CreateRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . create (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = CreateSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/Delete" => { # [allow (non_camel_case_types)] struct DeleteSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: IdRequest > for DeleteSvc < T > { type Response = super :: Response ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: IdRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . delete (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = DeleteSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/Get" => { # [allow (non_camel_case_types)] struct GetSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: IdRequest > for GetSvc < T > { type Response = super :: GetResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: IdRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . get (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = GetSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/Apply" => { # [allow (non_camel_case_types)] struct ApplySvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: ApplyRequest > for ApplySvc < T > { type Response = super :: CreateResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: ApplyRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . apply (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = ApplySvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/GetRoomJournal" => { # [allow (non_camel_case_types)] struct GetRoomJournalSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: RoomJournalRequest > for GetRoomJournalSvc < T > { type Response = super :: RoomJournalResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: RoomJournalRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . get_room_journal (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = GetRoomJournalSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/Batch" => { # [allow (non_camel_case_types)] struct BatchSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: BatchRequest > for BatchSvc < T > { type Response = super :: CreateResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: BatchRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . batch (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = BatchSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/Drain" => { # [allow (non_camel_case_types)] struct DrainSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: DrainRequest > for DrainSvc < T > { type Response = super :: DrainResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: DrainRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . drain (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = DrainSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/GetQualityHistory" => { # [allow (non_camel_case_types)] struct GetQualityHistorySvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: QualityHistoryRequest > for GetQualityHistorySvc < T > { type Response = super :: QualityHistoryResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: QualityHistoryRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . get_quality_history (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = GetQualityHistorySvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/ExportSpec" => { # [allow (non_camel_case_types)] struct ExportSpecSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: ExportSpecRequest > for ExportSpecSvc < T > { type Response = super :: ExportSpecResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: ExportSpecRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . export_spec (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = ExportSpecSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/ImportSpec" => { # [allow (non_camel_case_types)] struct ImportSpecSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: ImportSpecRequest > for ImportSpecSvc < T > { type Response = super :: CreateResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: ImportSpecRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . import_spec (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = ImportSpecSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/CreateFromTemplate" => { # [allow (non_camel_case_types)] struct CreateFromTemplateSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: CreateFromTemplateRequest > for CreateFromTemplateSvc < T > { type Response = super :: CreateResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: CreateFromTemplateRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . create_from_template (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = CreateFromTemplateSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/GetRoomsUsage" => { # [allow (non_camel_case_types)] struct GetRoomsUsageSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: RoomsUsageRequest > for GetRoomsUsageSvc < T > { type Response = super :: RoomsUsageResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: RoomsUsageRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . get_rooms_usage (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = GetRoomsUsageSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/PauseRoom" => { # [allow (non_camel_case_types)] struct PauseRoomSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: IdRequest > for PauseRoomSvc < T > { type Response = super :: Response ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: IdRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . pause_room (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = PauseRoomSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/ResumeRoom" => { # [allow (non_camel_case_types)] struct ResumeRoomSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: IdRequest > for ResumeRoomSvc < T > { type Response = super :: Response ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: IdRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . resume_room (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = ResumeRoomSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/GetClientCapabilities" => { # [allow (non_camel_case_types)] struct GetClientCapabilitiesSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: ClientCapabilitiesRequest > for GetClientCapabilitiesSvc < T > { type Response = super :: ClientCapabilitiesResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: ClientCapabilitiesRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . get_client_capabilities (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = GetClientCapabilitiesSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/PatchTrack" => { # [allow (non_camel_case_types)] struct PatchTrackSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: PatchTrackRequest > for PatchTrackSvc < T > { type Response = super :: PatchTrackResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: PatchTrackRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . patch_track (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = PatchTrackSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } "/api.ControlApi/DumpRoom" => { # [allow (non_camel_case_types)] struct DumpRoomSvc < T : ControlApi > (pub Arc < T >) ; impl < T : ControlApi > tonic :: server :: UnaryService < super :: DumpRoomRequest > for DumpRoomSvc < T > { type Response = super :: DumpRoomResponse ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: DumpRoomRequest >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . dump_room (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = DumpRoomSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } _ => Box :: pin (async move { Ok (http :: Response :: builder () . status (200) . header ("grpc-status" , "12") . header ("content-type" , "application/grpc") . body (tonic :: body :: BoxBody :: empty ()) . unwrap ()) }) , } } } impl < T : ControlApi > Clone for ControlApiServer < T > { fn clone (& self) -> Self { let inner = self . inner . clone () ; Self { inner } } } impl < T : ControlApi > Clone for _Inner < T > { fn clone (& self) -> Self { Self (self . 0 . clone () , self . 1 . clone ()) } } impl < T : std :: fmt :: Debug > std :: fmt :: Debug for _Inner < T > { fn fmt (& self , f : & mut std :: fmt :: Formatter < '_ >) -> std :: fmt :: Result { write ! (f , "{:?}" , self . 0) } } impl < T : ControlApi > tonic :: transport :: NamedService for ControlApiServer < T > { const NAME : & 'static str = "api.ControlApi" ; } }
\ No newline at end of file
@@ -8,7 +8,7 @@ pub struct Request {
     #[prost(string, tag="2")]
     pub at: ::prost::alloc::string::String,
     /// Occurred callback event.
-    #[prost(oneof="request::Event", tags="3, 4")]
+    #[prost(oneof="request::Event", tags="3, 4, 5, 6, 7, 8, 9, 10")]
     pub event: ::core::option::Option<request::Event>,
 }
 /// Nested message and enum types in `Request`.
@@ -20,6 +20,18 @@ pub mod request {
         OnJoin(super::OnJoin),
         #[prost(message, tag="4")]
         OnLeave(super::OnLeave),
+        #[prost(message, tag="5")]
+        OnStart(super::OnStart),
+        #[prost(message, tag="6")]
+        OnStop(super::OnStop),
+        #[prost(message, tag="7")]
+        OnCreateProgress(super::OnCreateProgress),
+        #[prost(message, tag="8")]
+        OnCreateComplete(super::OnCreateComplete),
+        #[prost(message, tag="9")]
+        OnNegotiationTimeout(super::OnNegotiationTimeout),
+        #[prost(message, tag="10")]
+        OnPatchApplied(super::OnPatchApplied),
     }
 }
 /// Empty response of the Callback service.
@@ -32,6 +44,9 @@ pub struct Response {
 /// Event that fires when Member joins a Room.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct OnJoin {
+    /// Session of the Client the Member has joined with.
+    #[prost(message, optional, tag="1")]
+    pub session: ::core::option::Option<ClientSession>,
 }
 /// Event that fires when Member leaves its Room.
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -39,6 +54,20 @@ pub struct OnLeave {
     /// Reason of why Member leaves the Room.
     #[prost(enumeration="on_leave::Reason", tag="1")]
     pub reason: i32,
+    /// Session of the Client the Member has left with.
+    ///
+    /// Not set if the Member has never joined the Room.
+    #[prost(message, optional, tag="2")]
+    pub session: ::core::option::Option<ClientSession>,
+    /// Duration (in milliseconds) the Member has been in the Room for.
+    #[prost(uint64, tag="3")]
+    pub duration_ms: u64,
+    /// Reason which the Client's connection has been closed by Medea media
+    /// server with (e.g. "Evicted").
+    ///
+    /// Empty if the connection has been closed by the Client or lost.
+    #[prost(string, tag="4")]
+    pub close_reason: ::prost::alloc::string::String,
 }
 /// Nested message and enum types in `OnLeave`.
 pub mod on_leave {
@@ -55,4 +84,119 @@ pub mod on_leave {
         ServerShutdown = 3,
     }
 }
+/// Metadata of a Client session of a Member.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClientSession {
+    /// Remote address of the Client's connection.
+    ///
+    /// Empty if unknown.
+    #[prost(string, tag="1")]
+    pub remote_addr: ::prost::alloc::string::String,
+    /// User agent of the Client.
+    ///
+    /// Empty if the Client has not reported it.
+    #[prost(string, tag="2")]
+    pub user_agent: ::prost::alloc::string::String,
+    /// MIME types of the codecs supported by the Client (e.g. "video/VP8").
+    #[prost(string, repeated, tag="3")]
+    pub codecs: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Indicator whether the Client supports sending simulcast.
+    #[prost(bool, tag="4")]
+    pub simulcast: bool,
+    /// Indicator whether the Client supports insertable streams, required for
+    /// end-to-end encryption.
+    #[prost(bool, tag="5")]
+    pub insertable_streams: bool,
+}
+/// Event that fires when media traffic starts flowing in an Endpoint.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnStart {
+    /// Type of the media which traffic started flowing.
+    #[prost(enumeration="MediaType", tag="1")]
+    pub media_type: i32,
+    /// Direction of the Endpoint's media traffic.
+    #[prost(enumeration="MediaDirection", tag="2")]
+    pub media_direction: i32,
+}
+/// Event that fires when media traffic stops flowing in an Endpoint.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnStop {
+    /// Type of the media which traffic stopped flowing.
+    #[prost(enumeration="MediaType", tag="1")]
+    pub media_type: i32,
+    /// Direction of the Endpoint's media traffic.
+    #[prost(enumeration="MediaDirection", tag="2")]
+    pub media_direction: i32,
+}
+/// Event that fires when an element of an asynchronously created Room is
+/// provisioned.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnCreateProgress {
+    /// ID of the creation operation, returned in CreateResponse.
+    #[prost(string, tag="1")]
+    pub operation_id: ::prost::alloc::string::String,
+    /// Number of the already provisioned elements.
+    #[prost(uint32, tag="2")]
+    pub done: u32,
+    /// Total number of the elements to be provisioned.
+    #[prost(uint32, tag="3")]
+    pub total: u32,
+}
+/// Event that fires when an asynchronous creation of a Room is finished.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnCreateComplete {
+    /// ID of the creation operation, returned in CreateResponse.
+    #[prost(string, tag="1")]
+    pub operation_id: ::prost::alloc::string::String,
+    /// Error of the creation.
+    ///
+    /// Empty if the creation succeeded. Otherwise, the Room has been removed.
+    #[prost(string, tag="2")]
+    pub error: ::prost::alloc::string::String,
+}
+/// Event that fires when a Peer of a Member doesn't finish its negotiation in
+/// time, so is removed along with its partner Peer.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnNegotiationTimeout {
+    /// ID of the removed Peer.
+    #[prost(uint32, tag="1")]
+    pub peer_id: u32,
+    /// ID of the Member owning the removed partner Peer.
+    #[prost(string, tag="2")]
+    pub partner_member_id: ::prost::alloc::string::String,
+}
+/// Event that fires when a patch requested via PatchTrack is applied by all the
+/// affected Clients.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnPatchApplied {
+    /// ID of the patch operation, returned in PatchTrackResponse.
+    #[prost(string, tag="1")]
+    pub operation_id: ::prost::alloc::string::String,
+    /// Error of the patch operation.
+    ///
+    /// Empty if the patch is applied by all the affected Clients. Otherwise, some
+    /// of them have left before applying it.
+    #[prost(string, tag="2")]
+    pub error: ::prost::alloc::string::String,
+}
+/// Type of the media which traffic starts/stops flowing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MediaType {
+    /// Audio traffic.
+    Audio = 0,
+    /// Video traffic.
+    Video = 1,
+    /// Audio and video traffic.
+    Both = 2,
+}
+/// Direction of the media traffic of an Endpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum MediaDirection {
+    /// Endpoint publishes media.
+    Publish = 0,
+    /// Endpoint plays media.
+    Play = 1,
+}
 # [doc = r" Generated client implementations."] pub mod callback_client { # ! [allow (unused_variables , dead_code , missing_docs)] use tonic :: codegen :: * ; # [doc = " Service for receiving callbacks from Medea media server."] pub struct CallbackClient < T > { inner : tonic :: client :: Grpc < T > , } impl CallbackClient < tonic :: transport :: Channel > { # [doc = r" Attempt to create a new client by connecting to a given endpoint."] pub async fn connect < D > (dst : D) -> Result < Self , tonic :: transport :: Error > where D : std :: convert :: TryInto < tonic :: transport :: Endpoint > , D :: Error : Into < StdError > , { let conn = tonic :: transport :: Endpoint :: new (dst) ? . connect () . await ? ; Ok (Self :: new (conn)) } } impl < T > CallbackClient < T > where T : tonic :: client :: GrpcService < tonic :: body :: BoxBody > , T :: ResponseBody : Body + HttpBody + Send + 'static , T :: Error : Into < StdError > , < T :: ResponseBody as HttpBody > :: Error : Into < StdError > + Send , { pub fn new (inner : T) -> Self { let inner = tonic :: client :: Grpc :: new (inner) ; Self { inner } } pub fn with_interceptor (inner : T , interceptor : impl Into < tonic :: Interceptor >) -> Self { let inner = tonic :: client :: Grpc :: with_interceptor (inner , interceptor) ; Self { inner } } # [doc = "/ Fires when a certain callback event happens on Medea media server."] pub async fn on_event (& mut self , request : impl tonic :: IntoRequest < super :: Request > ,) -> Result < tonic :: Response < super :: Response > , tonic :: Status > { self . inner . ready () . await . map_err (| e | { tonic :: Status :: new (tonic :: Code :: Unknown , format ! ("Service was not ready: {}" , e . into ())) }) ? ; let codec = tonic :: codec :: ProstCodec :: default () ; let path = http :: uri :: PathAndQuery :: from_static ("/callback.Callback/OnEvent") ; self . inner . unary (request . into_request () , path , codec) . await } } impl < T : Clone > Clone for CallbackClient < T > { fn clone (& self) -> Self { Self { inner : self . inner . clone () , } } } impl < T > std :: fmt :: Debug for CallbackClient < T > { fn fmt (& self , f : & mut std :: fmt :: Formatter < '_ >) -> std :: fmt :: Result { write ! (f , "CallbackClient {{ ... }}") } } }# [doc = r" Generated server implementations."] pub mod callback_server { # ! [allow (unused_variables , dead_code , missing_docs)] use tonic :: codegen :: * ; # [doc = "Generated trait containing gRPC methods that should be implemented for use with CallbackServer."] # [async_trait] pub trait Callback : Send + Sync + 'static { # [doc = "/ Fires when a certain callback event happens on Medea media server."] async fn on_event (& self , request : tonic :: Request < super :: Request >) -> Result < tonic :: Response < super :: Response > , tonic :: Status > ; } # [doc = " Service for receiving callbacks from Medea media server."] # [derive (Debug)] pub struct CallbackServer < T : Callback > { inner : _Inner < T > , } struct _Inner < T > (Arc < T > , Option < tonic :: Interceptor >) ; impl < T : Callback > CallbackServer < T > { pub fn new (inner : T) -> Self { let inner = Arc :: new (inner) ; let inner = _Inner (inner , None) ; Self { inner } } pub fn with_interceptor (inner : T , interceptor : impl Into < tonic :: Interceptor >) -> Self { let inner = Arc :: new (inner) ; let inner = _Inner (inner , Some (interceptor . into ())) ; Self { inner } } } impl < T , B > Service < http :: Request < B >> for CallbackServer < T > where T : Callback , B : HttpBody + Send + Sync + 'static , B :: Error : Into < StdError > + Send + 'static , { type Response = http :: Response < tonic :: body :: BoxBody > ; type Error = Never ; type Future = BoxFuture < Self :: Response , Self :: Error > ; fn poll_ready (& mut self , _cx : & mut Context < '_ >) -> Poll < Result < () , Self :: Error >> { Poll :: Ready (Ok (())) } fn call (& mut self , req : http :: Request < B >) -> Self :: Future { let inner = self . inner . clone () ; match req . uri () . path () { "/callback.Callback/OnEvent" => { # [allow (non_camel_case_types)] struct OnEventSvc < T : Callback > (pub Arc < T >) ; impl < T : Callback > tonic :: server :: UnaryService < super :: Request > for OnEventSvc < T > { type Response = super :: Response ; type Future = BoxFuture < tonic :: Response < Self :: Response > , tonic :: Status > ; fn call (& mut self , request : tonic :: Request < super :: Request >) -> Self :: Future { let inner = self . 0 . clone () ; let fut = async move { (* inner) . on_event (request) . await } ; Box :: pin (fut) } } let inner = self . inner . clone () ; let fut = async move { let interceptor = inner . 1 . clone () ; let inner = inner . 0 ; let method = OnEventSvc (inner) ; let codec = tonic :: codec :: ProstCodec :: default () ; let mut grpc = if let Some (interceptor) = interceptor { tonic :: server :: Grpc :: with_interceptor (codec , interceptor) } else { tonic :: server :: Grpc :: new (codec) } ; let res = grpc . unary (method , req) . await ; Ok (res) } ; Box :: pin (fut) } _ => Box :: pin (async move { Ok (http :: Response :: builder () . status (200) . header ("grpc-status" , "12") . header ("content-type" , "application/grpc") . body (tonic :: body :: BoxBody :: empty ()) . unwrap ()) }) , } } } impl < T : Callback > Clone for CallbackServer < T > { fn clone (& self) -> Self { let inner = self . inner . clone () ; Self { inner } } } impl < T : Callback > Clone for _Inner < T > { fn clone (& self) -> Self { Self (self . 0 . clone () , self . 1 . clone ()) } } impl < T : std :: fmt :: Debug > std :: fmt :: Debug for _Inner < T > { fn fmt (& self , f : & mut std :: fmt :: Formatter < '_ >) -> std :: fmt :: Result { write ! (f , "{:?}" , self . 0) } } impl < T : Callback > tonic :: transport :: NamedService for CallbackServer < T > { const NAME : & 'static str = "callback.Callback" ; } }
\ No newline at end of file
@@ -0,0 +1,163 @@
+//! Admission pacing of clients joining `Room`s, protecting a freshly
+//! (re)started media server from a storm of simultaneous reconnections.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::conf::server::AdmissionPacing;
+
+/// [Token bucket] of admissions, which refill rate grows linearly from the
+/// `initial_rate` to the `rate` during the `ramp_up` period.
+///
+/// [Token bucket]: https://en.wikipedia.org/wiki/Token_bucket
+#[derive(Debug)]
+struct Bucket {
+    /// Settings of this [`Bucket`].
+    conf: AdmissionPacing,
+
+    /// Moment this [`Bucket`] was created at, starting the ramp-up period.
+    started_at: Instant,
+
+    /// Number of admissions available at the moment of the last refill.
+    tokens: f64,
+
+    /// Moment of the last refill.
+    refilled_at: Instant,
+}
+
+impl Bucket {
+    /// Creates a new [`Bucket`] holding tokens for one second of the
+    /// `initial_rate`.
+    fn new(conf: AdmissionPacing, now: Instant) -> Self {
+        let tokens = f64::from(conf.initial_rate.min(conf.burst));
+        Self {
+            conf,
+            started_at: now,
+            tokens,
+            refilled_at: now,
+        }
+    }
+
+    /// Returns the refill rate (tokens per second) at the provided moment.
+    fn rate_at(&self, now: Instant) -> f64 {
+        let initial = f64::from(self.conf.initial_rate.min(self.conf.rate));
+        let target = f64::from(self.conf.rate);
+        let elapsed = now.saturating_duration_since(self.started_at);
+        if elapsed >= self.conf.ramp_up {
+            return target;
+        }
+        let progress = elapsed.as_secs_f64() / self.conf.ramp_up.as_secs_f64();
+        initial + (target - initial) * progress
+    }
+
+    /// Takes a single token at the provided moment, if there is any.
+    ///
+    /// Returns the estimated [`Duration`] after which a token will be
+    /// available otherwise.
+    fn take(&mut self, now: Instant) -> Result<(), Duration> {
+        let rate = self.rate_at(now);
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * rate)
+            .min(f64::from(self.conf.burst.max(1)));
+        self.refilled_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else if rate > 0.0 {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / rate))
+        } else {
+            // Zero `initial_rate` at the very start of the ramp-up period.
+            Err(Duration::from_secs(1))
+        }
+    }
+}
+
+/// Per-node pacer of admissions to `Room`s.
+///
+/// Clones share the same [`Bucket`], so a single [`Admission`] should be
+/// created for the whole media server.
+#[derive(Clone, Debug, Default)]
+pub struct Admission(Option<Arc<Mutex<Bucket>>>);
+
+impl Admission {
+    /// Creates a new [`Admission`] with the provided settings.
+    ///
+    /// Admissions are not paced at all if the `rate` is zero.
+    #[must_use]
+    pub fn new(conf: &AdmissionPacing) -> Self {
+        if conf.rate == 0 {
+            return Self(None);
+        }
+        Self(Some(Arc::new(Mutex::new(Bucket::new(
+            conf.clone(),
+            Instant::now(),
+        )))))
+    }
+
+    /// Tries to admit a single client.
+    ///
+    /// # Errors
+    ///
+    /// Errors with a [`Duration`] after which the client should retry, if
+    /// the admission rate is exceeded at the moment.
+    pub fn try_admit(&self) -> Result<(), Duration> {
+        self.0.as_ref().map_or(Ok(()), |bucket| {
+            bucket.lock().unwrap().take(Instant::now())
+        })
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    fn conf() -> AdmissionPacing {
+        AdmissionPacing {
+            rate: 10,
+            burst: 10,
+            initial_rate: 2,
+            ramp_up: Duration::from_secs(8),
+        }
+    }
+
+    #[test]
+    fn ramps_up_rate() {
+        let now = Instant::now();
+        let mut bucket = Bucket::new(conf(), now);
+
+        assert!(bucket.take(now).is_ok());
+        assert!(bucket.take(now).is_ok());
+        let retry_after = bucket.take(now).unwrap_err();
+        assert_eq!(retry_after, Duration::from_millis(500));
+
+        let later = now + Duration::from_secs(4);
+        assert!((bucket.rate_at(later) - 6.0).abs() < f64::EPSILON);
+        assert!(
+            (bucket.rate_at(now + Duration::from_secs(60)) - 10.0).abs()
+                < f64::EPSILON
+        );
+    }
+
+    #[test]
+    fn never_exceeds_burst() {
+        let now = Instant::now();
+        let mut bucket = Bucket::new(conf(), now);
+
+        let later = now + Duration::from_secs(60);
+        for _ in 0..10 {
+            assert!(bucket.take(later).is_ok());
+        }
+        assert!(bucket.take(later).is_err());
+    }
+
+    #[test]
+    fn admits_everyone_if_disabled() {
+        let admission = Admission::new(&AdmissionPacing { rate: 0, ..conf() });
+        for _ in 0..100 {
+            assert!(admission.try_admit().is_ok());
+        }
+    }
+}
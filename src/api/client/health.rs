@@ -0,0 +1,251 @@
+//! Health and readiness probes of [Medea], exposed by the Client API HTTP
+//! server.
+//!
+//! [Medea]: https://github.com/instrumentisto/medea
+
+use std::{
+    collections::BTreeMap,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+use actix::Addr;
+use futures::future::{self, Future};
+use serde::Serialize;
+use tokio::{net::TcpStream, time};
+
+use crate::{
+    drain::Drain,
+    signalling::{room_service::Ping, RoomService},
+    turn::TurnAuthService,
+    AppContext,
+};
+
+/// Maximum duration of a single health check.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Result of a single health check.
+type CheckResult = Result<(), String>;
+
+/// Report of the performed health checks.
+#[derive(Debug, Default, Serialize)]
+pub struct HealthReport {
+    /// Results of the performed checks by their names: `ok` for the passed
+    /// ones and an error description for the failed ones.
+    pub checks: BTreeMap<&'static str, String>,
+
+    /// Indicator whether all the performed checks have passed.
+    pub healthy: bool,
+}
+
+impl HealthReport {
+    /// Builds a new [`HealthReport`] out of the provided named
+    /// [`CheckResult`]s.
+    fn new(results: Vec<(&'static str, CheckResult)>) -> Self {
+        let healthy = results.iter().all(|(_, res)| res.is_ok());
+        let checks = results
+            .into_iter()
+            .map(|(name, res)| (name, res.err().unwrap_or_else(|| "ok".into())))
+            .collect();
+        Self { checks, healthy }
+    }
+}
+
+/// Checker of [Medea] components health.
+///
+/// [Medea]: https://github.com/instrumentisto/medea
+#[derive(Clone, Debug)]
+pub struct HealthChecker {
+    /// [`RoomService`] to check liveness of.
+    room_service: Addr<RoomService>,
+
+    /// [`TurnAuthService`] to check connectivity of.
+    turn_service: Arc<dyn TurnAuthService>,
+
+    /// Address of the [Control API] gRPC server to check responsiveness of.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    control_api_addr: SocketAddr,
+
+    /// Switch of the drain mode, making [Medea] not ready once switched on.
+    ///
+    /// [Medea]: https://github.com/instrumentisto/medea
+    drain: Drain,
+}
+
+impl HealthChecker {
+    /// Creates a new [`HealthChecker`] of the provided components.
+    ///
+    /// Unspecified IP of the provided `control_api_addr` (`0.0.0.0`) is
+    /// checked via the localhost.
+    #[must_use]
+    pub fn new(
+        room_service: Addr<RoomService>,
+        app: &AppContext,
+        mut control_api_addr: SocketAddr,
+    ) -> Self {
+        if control_api_addr.ip().is_unspecified() {
+            control_api_addr.set_ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        }
+        Self {
+            room_service,
+            turn_service: Arc::clone(&app.turn_service),
+            control_api_addr,
+            drain: app.drain.clone(),
+        }
+    }
+
+    /// Checks whether [Medea] is alive, so doesn't need to be restarted.
+    ///
+    /// Checks [`RoomService`] liveness and [Control API] gRPC server
+    /// responsiveness.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    /// [Medea]: https://github.com/instrumentisto/medea
+    pub async fn liveness(&self) -> HealthReport {
+        let (room_service, control_api) =
+            future::join(self.check_room_service(), self.check_control_api())
+                .await;
+        HealthReport::new(vec![
+            ("room_service", room_service),
+            ("control_api", control_api),
+        ])
+    }
+
+    /// Checks whether [Medea] is ready to serve users.
+    ///
+    /// Additionally to the [`HealthChecker::liveness()`] checks, checks
+    /// [TURN] connectivity and whether [Medea] is not draining.
+    ///
+    /// [Medea]: https://github.com/instrumentisto/medea
+    /// [TURN]: https://webrtcglossary.com/turn
+    pub async fn readiness(&self) -> HealthReport {
+        let (room_service, control_api, turn) = future::join3(
+            self.check_room_service(),
+            self.check_control_api(),
+            self.check_turn(),
+        )
+        .await;
+        HealthReport::new(vec![
+            ("room_service", room_service),
+            ("control_api", control_api),
+            ("turn", turn),
+            ("drain", self.check_drain()),
+        ])
+    }
+
+    /// Checks whether [`RoomService`] processes its messages.
+    async fn check_room_service(&self) -> CheckResult {
+        with_timeout(self.room_service.send(Ping))
+            .await?
+            .map_err(|e| e.to_string())
+    }
+
+    /// Checks whether [Control API] gRPC server accepts connections.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    async fn check_control_api(&self) -> CheckResult {
+        with_timeout(TcpStream::connect(self.control_api_addr))
+            .await?
+            .map(drop)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Checks whether the drain mode is not switched on.
+    fn check_drain(&self) -> CheckResult {
+        if self.drain.is_draining() {
+            Err("Draining".into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Checks whether [`TurnAuthService`] is healthy.
+    async fn check_turn(&self) -> CheckResult {
+        with_timeout(self.turn_service.check_health())
+            .await?
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Runs the provided health check [`Future`], failing if it doesn't complete
+/// in [`CHECK_TIMEOUT`].
+async fn with_timeout<T>(check: impl Future<Output = T>) -> Result<T, String> {
+    time::timeout(CHECK_TIMEOUT, check)
+        .await
+        .map_err(|_| String::from("Timed out"))
+}
+
+#[cfg(test)]
+mod spec {
+    use std::net::TcpListener;
+
+    use actix::Actor as _;
+
+    use crate::{
+        conf::Conf, shutdown::GracefulShutdown,
+        signalling::room_repo::RoomRepository, turn::TurnAuthServiceMock,
+        AppContext,
+    };
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn reports_failed_checks() {
+        let turn = Arc::new(TurnAuthServiceMock::default());
+        let app = AppContext::new(Conf::default(), turn.clone());
+        let room_service = RoomService::new(
+            RoomRepository::new(),
+            app.clone(),
+            GracefulShutdown::new(Duration::from_secs(1)).start(),
+        )
+        .unwrap()
+        .start();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let checker = HealthChecker::new(
+            room_service,
+            &app,
+            listener.local_addr().unwrap(),
+        );
+
+        assert!(checker.readiness().await.healthy);
+
+        turn.set_failing(true);
+        let report = checker.readiness().await;
+        assert!(!report.healthy);
+        assert_eq!(report.checks["room_service"], "ok");
+        assert_ne!(report.checks["turn"], "ok");
+        assert!(checker.liveness().await.healthy);
+
+        drop(listener);
+        assert!(!checker.liveness().await.healthy);
+    }
+
+    #[actix_rt::test]
+    async fn not_ready_while_draining() {
+        let app = AppContext::new(
+            Conf::default(),
+            Arc::new(TurnAuthServiceMock::default()),
+        );
+        let room_service = RoomService::new(
+            RoomRepository::new(),
+            app.clone(),
+            GracefulShutdown::new(Duration::from_secs(1)).start(),
+        )
+        .unwrap()
+        .start();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let checker = HealthChecker::new(
+            room_service,
+            &app,
+            listener.local_addr().unwrap(),
+        );
+
+        app.drain.start();
+        let report = checker.readiness().await;
+        assert!(!report.healthy);
+        assert_eq!(report.checks["drain"], "Draining");
+        assert!(checker.liveness().await.healthy);
+    }
+}
@@ -0,0 +1,254 @@
+//! Heartbeat of a Client API WebSocket connection, shared by all the `Room`s
+//! attached to it.
+
+use std::time::{Duration, Instant};
+
+use crate::api::client::rpc_connection::{Liveness, RpcConnectionSettings};
+
+/// Minimal change of the measured round-trip time, which is worth reporting
+/// to the `Room`s attached to a connection.
+const RTT_REPORT_THRESHOLD: Duration = Duration::from_millis(20);
+
+/// Ping/pong and idle tracking of a single WebSocket connection.
+///
+/// Being kept per connection, it's never duplicated for the `Room`s attached
+/// to the connection, which are only notified when its [`Liveness`] changes
+/// noticeably.
+#[derive(Debug)]
+pub struct Heartbeat {
+    /// Timeout of receiving any messages from the client.
+    idle_timeout: Duration,
+
+    /// Interval to send `Ping`s to the client with.
+    ping_interval: Duration,
+
+    /// Additional [`Duration`], by which [`Heartbeat::idle_timeout`] may be
+    /// exceeded before the client is considered idle.
+    jitter_tolerance: Duration,
+
+    /// Number of consecutive `Ping`s, which the client may leave unanswered
+    /// before being considered idle.
+    ///
+    /// `0` disables missed `Pong`s tracking.
+    max_missed_pongs: u32,
+
+    /// Timestamp of the last message received from the client.
+    last_activity: Instant,
+
+    /// Number of the next `Ping` to be sent.
+    last_ping_num: u32,
+
+    /// Number of the last `Ping` answered by the client.
+    last_pong_num: Option<u32>,
+
+    /// Timestamp of the last `Ping` being sent.
+    last_ping_at: Instant,
+
+    /// [`Liveness`] last reported to the `Room`s attached to the connection.
+    reported: Option<Liveness>,
+}
+
+impl Heartbeat {
+    /// Creates a new [`Heartbeat`] with the provided `idle_timeout` and
+    /// `ping_interval`.
+    #[must_use]
+    pub fn new(idle_timeout: Duration, ping_interval: Duration) -> Self {
+        Self {
+            idle_timeout,
+            ping_interval,
+            jitter_tolerance: Duration::from_secs(0),
+            max_missed_pongs: 0,
+            last_activity: Instant::now(),
+            last_ping_num: 0,
+            last_pong_num: None,
+            last_ping_at: Instant::now(),
+            reported: None,
+        }
+    }
+
+    /// Sets the [`Duration`] by which `idle_timeout` may be exceeded, and the
+    /// number of consecutive `Ping`s which may be left unanswered, before the
+    /// client is considered idle.
+    #[inline]
+    pub fn set_tolerance(
+        &mut self,
+        jitter_tolerance: Duration,
+        max_missed_pongs: u32,
+    ) {
+        self.jitter_tolerance = jitter_tolerance;
+        self.max_missed_pongs = max_missed_pongs;
+    }
+
+    /// Returns timeout of receiving any messages from the client.
+    #[inline]
+    #[must_use]
+    pub fn idle_timeout(&self) -> Duration {
+        self.idle_timeout
+    }
+
+    /// Returns interval to send `Ping`s to the client with.
+    #[inline]
+    #[must_use]
+    pub fn ping_interval(&self) -> Duration {
+        self.ping_interval
+    }
+
+    /// Tightens settings of this [`Heartbeat`] with the provided
+    /// [`RpcConnectionSettings`] of a newly attached `Room`.
+    ///
+    /// Returns `true` if any setting was updated.
+    pub fn tighten(&mut self, settings: &RpcConnectionSettings) -> bool {
+        let mut updated = false;
+        if settings.idle_timeout < self.idle_timeout {
+            self.idle_timeout = settings.idle_timeout;
+            updated = true;
+        }
+        if settings.ping_interval < self.ping_interval {
+            self.ping_interval = settings.ping_interval;
+            updated = true;
+        }
+        updated
+    }
+
+    /// Renews activity of the client.
+    #[inline]
+    pub fn touch(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
+    /// Returns number of the next `Ping` to be sent, marking it as sent.
+    pub fn next_ping(&mut self) -> u32 {
+        let num = self.last_ping_num;
+        self.last_ping_num += 1;
+        self.last_ping_at = Instant::now();
+        num
+    }
+
+    /// Handles `Pong` with the provided number.
+    ///
+    /// Returns the updated [`Liveness`] if it should be reported to the
+    /// `Room`s attached to the connection.
+    pub fn handle_pong(&mut self, n: u32) -> Option<Liveness> {
+        if n >= self.last_ping_num {
+            return None;
+        }
+        if self.last_pong_num.map_or(false, |last| last >= n) {
+            return None;
+        }
+        self.last_pong_num = Some(n);
+
+        let liveness = Liveness {
+            missed_pongs: self.missed_pongs(),
+            rtt: (n + 1 == self.last_ping_num)
+                .then(|| Instant::now().duration_since(self.last_ping_at))
+                .or_else(|| self.reported.and_then(|l| l.rtt)),
+        };
+        if self.reported.map_or(false, |r| !is_noticeable(r, liveness)) {
+            return None;
+        }
+        self.reported = Some(liveness);
+        Some(liveness)
+    }
+
+    /// Returns [`Liveness`] last reported to the `Room`s attached to the
+    /// connection, if any.
+    #[inline]
+    #[must_use]
+    pub fn reported_liveness(&self) -> Option<Liveness> {
+        self.reported
+    }
+
+    /// Returns number of consecutive `Ping`s left unanswered by the client.
+    ///
+    /// The last sent `Ping` is not considered missed until the next one is
+    /// sent.
+    #[must_use]
+    pub fn missed_pongs(&self) -> u32 {
+        let answered = self.last_pong_num.map_or(0, |n| n + 1);
+        self.last_ping_num.saturating_sub(answered).saturating_sub(1)
+    }
+
+    /// Indicates whether the client should be considered idle.
+    ///
+    /// The client is idle once no messages were received from it during
+    /// [`Heartbeat::idle_timeout`] (with [`Heartbeat::jitter_tolerance`]),
+    /// and it has missed at least [`Heartbeat::max_missed_pongs`] `Ping`s.
+    #[must_use]
+    pub fn is_idle(&self) -> bool {
+        let silent = Instant::now().duration_since(self.last_activity)
+            > self.idle_timeout + self.jitter_tolerance;
+        silent
+            && (self.max_missed_pongs == 0
+                || self.missed_pongs() >= self.max_missed_pongs)
+    }
+}
+
+/// Indicates whether the `new` [`Liveness`] differs from the `old` one enough
+/// to be reported.
+fn is_noticeable(old: Liveness, new: Liveness) -> bool {
+    if old.missed_pongs != new.missed_pongs {
+        return true;
+    }
+    match (old.rtt, new.rtt) {
+        (Some(old), Some(new)) => {
+            let diff = if old > new { old - new } else { new - old };
+            diff >= RTT_REPORT_THRESHOLD
+        }
+        (old, new) => old.is_some() != new.is_some(),
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    // Client is considered idle only once both idle_timeout with
+    // jitter_tolerance and max_missed_pongs are exceeded.
+    #[test]
+    fn idle_with_jitter_tolerance_and_missed_pongs() {
+        let mut heartbeat =
+            Heartbeat::new(Duration::from_millis(100), Duration::from_secs(1));
+        heartbeat.set_tolerance(Duration::from_millis(100), 2);
+
+        heartbeat.last_activity = Instant::now() - Duration::from_millis(150);
+        heartbeat.last_ping_num = 5;
+        assert!(!heartbeat.is_idle());
+
+        heartbeat.last_activity = Instant::now() - Duration::from_millis(250);
+        heartbeat.last_pong_num = Some(2);
+        assert_eq!(heartbeat.missed_pongs(), 1);
+        assert!(!heartbeat.is_idle());
+
+        heartbeat.last_pong_num = Some(1);
+        assert_eq!(heartbeat.missed_pongs(), 2);
+        assert!(heartbeat.is_idle());
+    }
+
+    // Liveness is reported to the attached Rooms only when it changes
+    // noticeably.
+    #[test]
+    fn reports_only_noticeable_liveness_changes() {
+        let mut heartbeat =
+            Heartbeat::new(Duration::from_secs(10), Duration::from_secs(3));
+
+        assert_eq!(heartbeat.handle_pong(0), None);
+
+        assert_eq!(heartbeat.next_ping(), 0);
+        let reported = heartbeat.handle_pong(0).unwrap();
+        assert_eq!(reported.missed_pongs, 0);
+        assert!(reported.rtt.is_some());
+        assert_eq!(heartbeat.reported_liveness(), Some(reported));
+        let rtt = reported.rtt;
+
+        assert_eq!(heartbeat.next_ping(), 1);
+        assert_eq!(heartbeat.handle_pong(1), None);
+        assert_eq!(heartbeat.handle_pong(1), None);
+
+        heartbeat.next_ping();
+        heartbeat.next_ping();
+        heartbeat.next_ping();
+        let reported = heartbeat.handle_pong(2).unwrap();
+        assert_eq!(reported.missed_pongs, 1);
+        assert_eq!(reported.rtt, rtt);
+    }
+}
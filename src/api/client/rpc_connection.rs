@@ -2,12 +2,13 @@
 //!
 //! [`RpcConnection`]: crate::api::client::rpc_connection::RpcConnection
 
-use std::{fmt, time::Duration};
+use std::{collections::HashMap, fmt, net::SocketAddr, time::Duration};
 
 use actix::Message;
 use futures::future::LocalBoxFuture;
 use medea_client_api_proto::{
-    CloseDescription, Command, Credential, Event, MemberId, RoomId,
+    state, ClientCapabilities, CloseDescription, Command, Credential, Event,
+    MemberId, RoomId,
 };
 
 use crate::signalling::room::RoomError;
@@ -40,6 +41,7 @@ impl CommandMessage {
 pub struct EventMessage {
     pub room_id: RoomId,
     pub event: Event,
+    pub seq: Option<u64>,
 }
 
 /// Abstraction over RPC connection with some remote [`Member`].
@@ -60,14 +62,15 @@ pub trait RpcConnection: fmt::Debug + Send {
         close_description: CloseDescription,
     ) -> LocalBoxFuture<'static, ()>;
 
-    /// Sends [`Event`] to remote [`Member`].
+    /// Sends [`Event`] with the provided sequence number to remote
+    /// [`Member`].
     ///
     /// [`Member`]: crate::signalling::elements::Member
-    fn send_event(&self, room_id: RoomId, event: Event);
+    fn send_event(&self, room_id: RoomId, event: Event, seq: Option<u64>);
 }
 
 /// Settings of [`RpcConnection`].
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct RpcConnectionSettings {
     /// [`Duration`], after which [`RpcConnection`] will be considered idle if
     /// no heartbeat messages were received.
@@ -77,6 +80,11 @@ pub struct RpcConnectionSettings {
     ///
     /// [`Member`]: crate::signalling::elements::Member
     pub ping_interval: Duration,
+
+    /// Key-value feature flags of remote [`Member`].
+    ///
+    /// [`Member`]: crate::signalling::elements::Member
+    pub features: HashMap<String, String>,
 }
 
 /// Signal of new [`RpcConnection`] being established with specified [`Member`].
@@ -96,6 +104,14 @@ pub struct RpcConnectionEstablished {
     /// [`Member`]: crate::signalling::elements::Member
     pub credentials: Credential,
 
+    /// [`ClientCapabilities`] reported by the `Client` establishing
+    /// [`RpcConnection`].
+    pub capabilities: ClientCapabilities,
+
+    /// Remote address of the `Client` establishing [`RpcConnection`], if
+    /// known.
+    pub remote_addr: Option<SocketAddr>,
+
     /// Established [`RpcConnection`].
     pub connection: Box<dyn RpcConnection>,
 }
@@ -114,12 +130,48 @@ pub struct RpcConnectionClosed {
     pub reason: ClosedReason,
 }
 
+/// Liveness metrics of an [`RpcConnection`] with some remote [`Member`].
+///
+/// [`Member`]: crate::signalling::elements::Member
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Liveness {
+    /// Number of consecutive `Ping`s left unanswered by the remote [`Member`].
+    ///
+    /// [`Member`]: crate::signalling::elements::Member
+    pub missed_pongs: u32,
+
+    /// Round-trip time of the last answered `Ping`.
+    ///
+    /// [`None`] if it cannot be measured yet.
+    pub rtt: Option<Duration>,
+}
+
+/// Signal of [`Liveness`] metrics of the specified [`Member`]'s
+/// [`RpcConnection`] being updated.
+///
+/// [`Member`]: crate::signalling::elements::Member
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+pub struct LivenessUpdated {
+    /// ID of [`Member`] which [`Liveness`] is updated.
+    ///
+    /// [`Member`]: crate::signalling::elements::Member
+    pub member_id: MemberId,
+
+    /// Updated [`Liveness`] metrics.
+    pub liveness: Liveness,
+}
+
 /// Signal of a [`Member`] which state needs synchronization.
 ///
+/// Carries the sequence number of the last [`Event`] received by the
+/// [`Member`], if it has reported one, and the [`state::Room`] restored by
+/// the [`Member`].
+///
 /// [`Member`]: crate::signalling::elements::Member
 #[derive(Debug, Message)]
 #[rtype(result = "()")]
-pub struct Synchronize(pub MemberId);
+pub struct Synchronize(pub MemberId, pub Option<u64>, pub state::Room);
 
 /// Reasons of why [`RpcConnection`] may be closed.
 #[derive(Clone, Copy, Debug, PartialEq)]
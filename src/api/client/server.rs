@@ -13,15 +13,19 @@ use actix_web_actors::{ws, ws::WebsocketContext};
 use futures::FutureExt as _;
 
 use crate::{
-    api::client::session::WsSession,
+    api::{
+        client::{
+            health::{HealthChecker, HealthReport},
+            session::WsSession,
+        },
+        tls,
+    },
     conf::{Conf, Rpc},
     log::prelude::*,
     shutdown::ShutdownGracefully,
     signalling::room_repo::RoomRepository,
 };
 
-use super::MAX_WS_MSG_SIZE;
-
 /// Handles all HTTP requests, performs WebSocket handshake (upgrade) and starts
 /// new [`WsSession`] for WebSocket connection.
 async fn ws_index(
@@ -35,13 +39,41 @@ async fn ws_index(
                 Box::new(state.rooms.clone()),
                 state.config.idle_timeout,
                 state.config.ping_interval,
-            ),
+            )
+            .with_liveness_tolerance(
+                state.config.jitter_tolerance,
+                state.config.max_missed_pongs,
+            )
+            .with_max_message_size(state.config.max_message_size)
+            .with_remote_addr(request.peer_addr())
+            .with_features(state.config.features.clone()),
             payload,
-            actix_http::ws::Codec::new().max_size(MAX_WS_MSG_SIZE),
+            actix_http::ws::Codec::new()
+                .max_size(state.config.max_message_size),
         )),
     )
 }
 
+/// Responds with the provided [`HealthReport`], with `503 Service
+/// Unavailable` status if any of its checks has failed.
+fn health_response(report: HealthReport) -> HttpResponse {
+    if report.healthy {
+        HttpResponse::Ok().json(report)
+    } else {
+        HttpResponse::ServiceUnavailable().json(report)
+    }
+}
+
+/// Handles liveness probe HTTP requests.
+async fn healthz(state: Data<Context>) -> HttpResponse {
+    health_response(state.health.liveness().await)
+}
+
+/// Handles readiness probe HTTP requests.
+async fn readyz(state: Data<Context>) -> HttpResponse {
+    health_response(state.health.readiness().await)
+}
+
 /// Context for [`App`] which holds all the necessary dependencies.
 pub struct Context {
     /// Repository of all currently existing [`Room`]s in application.
@@ -51,6 +83,9 @@ pub struct Context {
 
     /// Settings of application.
     pub config: Rpc,
+
+    /// Checker of application components health.
+    pub health: HealthChecker,
 }
 
 /// HTTP server that handles WebSocket connections of Client API.
@@ -59,20 +94,36 @@ pub struct Server(ActixServer);
 impl Server {
     /// Starts Client API HTTP server.
     ///
+    /// Terminates TLS if it's configured.
+    ///
     /// # Errors
     ///
-    /// Errors if binding [`HttpServer`] to a listening address fails.
-    pub fn run(rooms: RoomRepository, config: Conf) -> io::Result<Addr<Self>> {
+    /// Errors if binding [`HttpServer`] to a listening address fails, or
+    /// configured TLS certificates cannot be loaded.
+    pub fn run(
+        rooms: RoomRepository,
+        config: Conf,
+        health: HealthChecker,
+    ) -> io::Result<Addr<Self>> {
         let server_addr = config.server.client.http.bind_addr();
+        let tls_config = tls::server_config(&config.server.client.http.tls)?;
 
         let server = HttpServer::new(move || {
             App::new()
-                .app_data(Self::app_data(rooms.clone(), config.rpc))
+                .app_data(Self::app_data(
+                    rooms.clone(),
+                    config.rpc.clone(),
+                    health.clone(),
+                ))
                 .configure(Self::configure)
                 .wrap(middleware::Logger::default())
         })
-        .disable_signals()
-        .bind(server_addr)?
+        .disable_signals();
+        let server = if let Some(tls_config) = tls_config {
+            server.bind_rustls(server_addr, tls_config)?
+        } else {
+            server.bind(server_addr)?
+        }
         .run();
 
         info!("Started Client API HTTP server on {}", server_addr);
@@ -81,14 +132,28 @@ impl Server {
     }
 
     /// Set application data.
-    fn app_data(rooms: RoomRepository, config: Rpc) -> Data<Context> {
-        Data::new(Context { rooms, config })
+    fn app_data(
+        rooms: RoomRepository,
+        config: Rpc,
+        health: HealthChecker,
+    ) -> Data<Context> {
+        Data::new(Context {
+            rooms,
+            config,
+            health,
+        })
     }
 
     /// Run external configuration as part of the application building
     /// process
     fn configure(cfg: &mut ServiceConfig) {
-        cfg.service(resource("/ws").route(actix_web::web::get().to(ws_index)));
+        cfg.service(resource("/ws").route(actix_web::web::get().to(ws_index)))
+            .service(
+                resource("/healthz").route(actix_web::web::get().to(healthz)),
+            )
+            .service(
+                resource("/readyz").route(actix_web::web::get().to(readyz)),
+            );
     }
 }
 
@@ -4,8 +4,9 @@ use std::{
     collections::HashMap,
     convert::TryInto as _,
     fmt::{Debug, Display, Error, Formatter},
+    net::SocketAddr,
     sync::atomic::{AtomicU64, Ordering},
-    time::{Duration, Instant},
+    time::Duration,
 };
 
 use actix::{
@@ -18,22 +19,25 @@ use actix_web_actors::ws::{self, CloseCode};
 use bytes::BytesMut;
 use futures::future::{self, FutureExt as _, LocalBoxFuture};
 use medea_client_api_proto::{
-    state, ClientMsg, CloseDescription, CloseReason, Command, Credential,
-    Event, MemberId, RoomId, RpcSettings, ServerMsg,
+    state, ClientCapabilities, ClientMsg, CloseDescription, CloseReason,
+    Command, Credential, Event, MemberId, MessageTooLarge, RoomId, RpcSettings,
+    ServerMsg, MAX_MESSAGE_SIZE,
 };
 
 use crate::{
     api::{
-        client::rpc_connection::{
-            ClosedReason, EventMessage, RpcConnection, RpcConnectionSettings,
+        client::{
+            heartbeat::Heartbeat,
+            rpc_connection::{
+                ClosedReason, EventMessage, RpcConnection,
+                RpcConnectionSettings,
+            },
         },
         RpcServer, RpcServerError,
     },
     log::prelude::*,
 };
 
-use super::MAX_WS_MSG_SIZE;
-
 /// Repository of the all [`RpcServer`]s registered on this Media Server.
 #[cfg_attr(test, mockall::automock)]
 pub trait RpcServerRepository: Debug {
@@ -78,25 +82,13 @@ pub struct WsSession {
     /// All sessions which this [`WsSession`] is serves.
     sessions: HashMap<RoomId, (MemberId, Box<dyn RpcServer>)>,
 
-    /// Timeout of receiving any messages from client.
-    idle_timeout: Duration,
-
-    /// Timestamp for watchdog which checks whether WebSocket client became
-    /// idle (no messages received during [`WsSession::idle_timeout`]).
-    ///
-    /// This one should be renewed on any received WebSocket message
-    /// from client.
-    last_activity: Instant,
+    /// Ping/pong and idle tracking of this [`WsSession`], shared by all the
+    /// sessions it serves.
+    heartbeat: Heartbeat,
 
     /// Buffer where continuation WebSocket frames are accumulated.
     fragmentation_buffer: BytesMut,
 
-    /// Last number of [`ServerMsg::Ping`].
-    last_ping_num: u32,
-
-    /// Interval to send [`ServerMsg::Ping`]s to a client with.
-    ping_interval: Duration,
-
     /// [`WsSession`] closed reason. Should be set by the moment
     /// `Actor::stopped()` for this [`WsSession`] is called.
     close_reason: Option<InnerCloseReason>,
@@ -106,6 +98,17 @@ pub struct WsSession {
 
     /// [`SpawnHandle`] for the heartbeat task.
     heartbeat_handle: Option<SpawnHandle>,
+
+    /// Maximum size (in bytes) of a single message received from the client,
+    /// including fragmented ones.
+    max_message_size: usize,
+
+    /// Remote address of the client of this [`WsSession`], if known.
+    remote_addr: Option<SocketAddr>,
+
+    /// Key-value feature flags delivered to the client of this [`WsSession`]
+    /// in [`RpcSettings`].
+    features: HashMap<String, String>,
 }
 
 impl WsSession {
@@ -121,37 +124,101 @@ impl WsSession {
             id: ID_COUNTER.fetch_add(1, Ordering::Relaxed),
             rpc_server_repo: rooms,
             sessions: HashMap::new(),
-            idle_timeout,
-            last_activity: Instant::now(),
+            heartbeat: Heartbeat::new(idle_timeout, ping_interval),
             fragmentation_buffer: BytesMut::new(),
-            last_ping_num: 0,
-            ping_interval,
             close_reason: None,
             auth_timeout_handle: None,
             heartbeat_handle: None,
+            max_message_size: MAX_MESSAGE_SIZE,
+            remote_addr: None,
+            features: HashMap::new(),
         }
     }
 
+    /// Sets the [`Duration`] by which `idle_timeout` may be exceeded, and the
+    /// number of consecutive [`ServerMsg::Ping`]s which may be left
+    /// unanswered, before the client of this [`WsSession`] is considered
+    /// idle.
+    #[inline]
+    #[must_use]
+    pub fn with_liveness_tolerance(
+        mut self,
+        jitter_tolerance: Duration,
+        max_missed_pongs: u32,
+    ) -> Self {
+        self.heartbeat
+            .set_tolerance(jitter_tolerance, max_missed_pongs);
+        self
+    }
+
+    /// Sets the maximum size (in bytes) of a single message received from the
+    /// client of this [`WsSession`], including fragmented ones.
+    #[inline]
+    #[must_use]
+    pub fn with_max_message_size(mut self, max_message_size: usize) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    /// Sets the remote address of the client of this [`WsSession`], reported
+    /// to the [`RpcServer`]s it joins.
+    #[inline]
+    #[must_use]
+    pub fn with_remote_addr(mut self, remote_addr: Option<SocketAddr>) -> Self {
+        self.remote_addr = remote_addr;
+        self
+    }
+
+    /// Sets the default key-value feature flags delivered to the client of
+    /// this [`WsSession`], which may be overridden by the [`RpcServer`]s it
+    /// joins.
+    #[inline]
+    #[must_use]
+    pub fn with_features(mut self, features: HashMap<String, String>) -> Self {
+        self.features = features;
+        self
+    }
+
     /// Handles text WebSocket messages.
     fn handle_text(
         &mut self,
         ctx: &mut ws::WebsocketContext<Self>,
         text: &str,
     ) {
-        self.last_activity = Instant::now();
+        self.heartbeat.touch();
         match serde_json::from_str::<ClientMsg>(&text) {
             Ok(ClientMsg::Pong(n)) => {
                 debug!("{}: Received Pong: {}", self, n);
+                self.handle_pong(n);
             }
-            Ok(ClientMsg::Command { room_id, command }) => {
+            Ok(ClientMsg::Command {
+                room_id,
+                mut command,
+            }) => {
+                if let Err(e) = command.check_relayed_size() {
+                    error!("{}: Rejected oversized Command: {}", self, e);
+                    self.close_in_place(
+                        ctx,
+                        &CloseDescription::new(CloseReason::MessageTooLarge),
+                    );
+                    return;
+                }
+                if command.truncate_oversized() {
+                    warn!("{}: Truncated oversized Command", self);
+                }
                 debug!("{}: Received Command: {:?}", self, command);
                 match command {
                     Command::JoinRoom {
                         member_id,
                         credential,
+                        capabilities,
                     } => {
                         self.handle_join_room(
-                            ctx, room_id, member_id, credential,
+                            ctx,
+                            room_id,
+                            member_id,
+                            credential,
+                            capabilities,
                         );
                     }
                     Command::LeaveRoom { member_id } => {
@@ -162,8 +229,16 @@ impl WsSession {
                             ClosedReason::Closed { normal: true },
                         );
                     }
-                    Command::SynchronizeMe { state } => {
-                        self.handle_synchronize_me(ctx, &room_id, &state);
+                    Command::SynchronizeMe {
+                        state,
+                        last_event_seq,
+                    } => {
+                        self.handle_synchronize_me(
+                            ctx,
+                            &room_id,
+                            &state,
+                            last_event_seq,
+                        );
                     }
                     _ => {
                         if let Some((member_id, room)) =
@@ -174,7 +249,7 @@ impl WsSession {
                             self.send_left_room(
                                 ctx,
                                 room_id,
-                                CloseReason::Finished,
+                                CloseDescription::new(CloseReason::Finished),
                             );
                             if self.sessions.is_empty() {
                                 self.close_in_place(
@@ -195,6 +270,19 @@ impl WsSession {
         }
     }
 
+    /// Handles [`ClientMsg::Pong`] reporting updated [`Liveness`] metrics of
+    /// this [`WsSession`] to all its [`RpcServer`]s, if they have changed
+    /// noticeably.
+    ///
+    /// [`Liveness`]: crate::api::client::rpc_connection::Liveness
+    fn handle_pong(&mut self, n: u32) {
+        if let Some(liveness) = self.heartbeat.handle_pong(n) {
+            for (member_id, room) in self.sessions.values() {
+                room.update_liveness(member_id.clone(), liveness);
+            }
+        }
+    }
+
     /// Updates [`RpcConnectionSettings`] of this [`WsSession`].
     ///
     /// Updates will be performed only if old settings are less then new one.
@@ -208,17 +296,18 @@ impl WsSession {
         new_settings: RpcConnectionSettings,
         ctx: &mut ws::WebsocketContext<Self>,
     ) {
-        let mut updated = false;
-        if new_settings.idle_timeout < self.idle_timeout {
-            self.idle_timeout = new_settings.idle_timeout;
-            updated = true;
-        }
-        if new_settings.ping_interval < self.ping_interval {
-            self.ping_interval = new_settings.ping_interval;
-            updated = true;
+        let tightened = self.heartbeat.tighten(&new_settings);
+        let mut features_changed = false;
+        for (name, value) in new_settings.features {
+            if self.features.get(&name) != Some(&value) {
+                self.features.insert(name, value);
+                features_changed = true;
+            }
         }
-        if updated {
+        if tightened || features_changed {
             self.send_current_rpc_settings(ctx);
+        }
+        if tightened {
             self.start_heartbeat(ctx);
         }
     }
@@ -236,17 +325,23 @@ impl WsSession {
         room_id: RoomId,
         member_id: MemberId,
         credential: Credential,
+        capabilities: ClientCapabilities,
     ) {
         if let Some(room) = self.rpc_server_repo.get(&room_id) {
             room.connection_established(
                 member_id.clone(),
                 credential,
+                capabilities,
+                self.remote_addr,
                 Box::new(ctx.address()),
             )
             .into_actor(self)
             .map(|result, this, ctx| match result {
                 Ok(settings) => {
                     this.update_rpc_settings(settings, ctx);
+                    if let Some(liveness) = this.heartbeat.reported_liveness() {
+                        room.update_liveness(member_id.clone(), liveness);
+                    }
                     this.sessions
                         .insert(room_id.clone(), (member_id.clone(), room));
                     if let Some(auth_timeout_handle) =
@@ -262,14 +357,30 @@ impl WsSession {
                          {:?}",
                         this, room_id, member_id, err
                     );
-                    let reason = match err {
-                        RpcServerError::Authorization => CloseReason::Rejected,
+                    let description = match err {
+                        RpcServerError::Authorization => {
+                            CloseDescription::new(CloseReason::Rejected)
+                        }
+                        RpcServerError::Draining => {
+                            CloseDescription::new(CloseReason::Draining)
+                        }
+                        RpcServerError::Overloaded(retry_after) => {
+                            CloseDescription::new(CloseReason::Overloaded)
+                                .with_retry_after(retry_after)
+                        }
                         RpcServerError::RoomError(_)
                         | RpcServerError::RoomMailbox(_) => {
-                            CloseReason::InternalError
+                            CloseDescription::new(CloseReason::InternalError)
                         }
                     };
-                    this.send_left_room(ctx, room_id, reason)
+                    this.send_left_room(ctx, room_id, description);
+                    if matches!(
+                        description.reason,
+                        CloseReason::Draining | CloseReason::Overloaded
+                    ) && this.sessions.is_empty()
+                    {
+                        this.close_in_place(ctx, &description);
+                    }
                 }
             })
             .wait(ctx);
@@ -278,7 +389,11 @@ impl WsSession {
                 "{}: Failed to authorize Rpc Session: Room `{}` does not exist",
                 self, room_id
             );
-            self.send_left_room(ctx, room_id, CloseReason::Rejected)
+            self.send_left_room(
+                ctx,
+                room_id,
+                CloseDescription::new(CloseReason::Rejected),
+            )
         }
     }
 
@@ -313,10 +428,21 @@ impl WsSession {
         ctx: &mut ws::WebsocketContext<Self>,
         room_id: &RoomId,
         state: &state::Room,
+        last_event_seq: Option<u64>,
     ) {
-        debug!("{}: Received synchronization request: {:?}", self, state);
+        debug!(
+            "{}: Received synchronization request since Event #{:?}: {:?}",
+            self, last_event_seq, state,
+        );
         if let Some((member_id, room)) = self.sessions.get(&room_id) {
-            ctx.wait(room.synchronize(member_id.clone()).into_actor(self));
+            ctx.wait(
+                room.synchronize(
+                    member_id.clone(),
+                    last_event_seq,
+                    state.clone(),
+                )
+                .into_actor(self),
+            );
         }
     }
 
@@ -352,12 +478,14 @@ impl WsSession {
         frame: Item,
     ) {
         if let Item::Continue(value) | Item::Last(value) = &frame {
-            if (self.fragmentation_buffer.len() + value.len()) > MAX_WS_MSG_SIZE
-            {
-                error!("{}: Fragmentation buffer overflow.", self);
+            if let Err(e) = MessageTooLarge::check(
+                self.fragmentation_buffer.len() + value.len(),
+                self.max_message_size,
+            ) {
+                error!("{}: Fragmentation buffer overflow: {}", self, e);
                 self.close_in_place(
                     ctx,
-                    &CloseDescription::new(CloseReason::Evicted),
+                    &CloseDescription::new(CloseReason::MessageTooLarge),
                 );
                 return;
             }
@@ -406,6 +534,8 @@ impl WsSession {
     }
 
     /// Sends close frame and stops connection [`Actor`].
+    ///
+    /// Close code of the frame is chosen via [`CloseReason::close_code()`].
     fn close_in_place(
         &mut self,
         ctx: &mut ws::WebsocketContext<Self>,
@@ -414,20 +544,22 @@ impl WsSession {
         debug!("{}: Closing WsSession", self);
         self.close_reason = Some(InnerCloseReason::ByServer);
         ctx.close(Some(ws::CloseReason {
-            code: ws::CloseCode::Normal,
+            code: CloseCode::from(reason.reason.close_code()),
             description: Some(serde_json::to_string(reason).unwrap()),
         }));
         ctx.stop();
     }
 
-    /// Starts watchdog which will drop connection if `now`-`last_activity` >
-    /// `idle_timeout`.
+    /// Starts watchdog which will drop connection once the client is
+    /// considered idle (see [`Heartbeat::is_idle()`]).
     fn start_idle_watchdog(ctx: &mut <Self as Actor>::Context) {
         ctx.run_interval(Duration::new(1, 0), |this, ctx| {
-            if Instant::now().duration_since(this.last_activity)
-                > this.idle_timeout
-            {
-                info!("{}: WsSession is idle", this);
+            if this.heartbeat.is_idle() {
+                info!(
+                    "{}: WsSession is idle, missed {} Pongs",
+                    this,
+                    this.heartbeat.missed_pongs(),
+                );
                 this.close_in_place(
                     ctx,
                     &CloseDescription::new(CloseReason::Idle),
@@ -444,34 +576,38 @@ impl WsSession {
         } else {
             self.send_ping(ctx);
         }
-        self.heartbeat_handle =
-            Some(ctx.run_interval(self.ping_interval, |this, ctx| {
+        self.heartbeat_handle = Some(ctx.run_interval(
+            self.heartbeat.ping_interval(),
+            |this, ctx| {
                 this.send_ping(ctx);
-            }));
+            },
+        ));
     }
 
     /// Sends [`ServerMsg::Ping`] increasing ping counter.
     fn send_ping(&mut self, ctx: &mut <Self as Actor>::Context) {
-        ctx.text(
-            serde_json::to_string(&ServerMsg::Ping(self.last_ping_num))
-                .unwrap(),
-        );
-        self.last_ping_num += 1;
+        let num = self.heartbeat.next_ping();
+        ctx.text(serde_json::to_string(&ServerMsg::Ping(num)).unwrap());
     }
 
-    /// Sends [`Event`] to Web Client.
+    /// Sends [`Event`] with the provided sequence number to Web Client.
     fn send_event(
         &self,
         ctx: &mut <Self as Actor>::Context,
         room_id: RoomId,
         event: Event,
+        seq: Option<u64>,
     ) {
         debug!(
-            "{}: Sending Event for Room [id = {}]: {:?}]",
-            self, room_id, event
+            "{}: Sending Event #{:?} for Room [id = {}]: {:?}]",
+            self, seq, room_id, event
         );
-        let event = serde_json::to_string(&ServerMsg::Event { room_id, event })
-            .unwrap();
+        let event = serde_json::to_string(&ServerMsg::Event {
+            room_id,
+            event,
+            seq,
+        })
+        .unwrap();
         ctx.text(event);
     }
 
@@ -482,32 +618,44 @@ impl WsSession {
         room_id: RoomId,
         member_id: MemberId,
     ) {
-        self.send_event(ctx, room_id, Event::RoomJoined { member_id });
+        self.send_event(ctx, room_id, Event::RoomJoined { member_id }, None);
     }
 
-    /// Sends [`Event::RoomLeft`] to the client.
+    /// Sends [`Event::RoomLeft`] with the provided [`CloseDescription`] to the
+    /// client.
     fn send_left_room(
         &self,
         ctx: &mut <Self as Actor>::Context,
         room_id: RoomId,
-        close_reason: CloseReason,
+        description: CloseDescription,
     ) {
-        self.send_event(ctx, room_id, Event::RoomLeft { close_reason });
+        self.send_event(
+            ctx,
+            room_id,
+            Event::RoomLeft {
+                close_reason: description.reason,
+                retry_after_ms: description.retry_after_ms,
+            },
+            None,
+        );
     }
 
     /// Sends current [`RpcSettings`] to the client.
     fn send_current_rpc_settings(&self, ctx: &mut <Self as Actor>::Context) {
         let rpc_settings = RpcSettings {
             idle_timeout_ms: self
-                .idle_timeout
+                .heartbeat
+                .idle_timeout()
                 .as_millis()
                 .try_into()
                 .expect("'idle_timeout' should fit into u64"),
             ping_interval_ms: self
-                .ping_interval
+                .heartbeat
+                .ping_interval()
                 .as_millis()
                 .try_into()
                 .expect("'ping_interval' should fit into u64"),
+            features: self.features.clone(),
         };
         ctx.text(
             serde_json::to_string(&ServerMsg::RpcSettings(rpc_settings))
@@ -595,11 +743,15 @@ impl RpcConnection for Addr<WsSession> {
         .boxed_local()
     }
 
-    /// Sends [`Event`] to Web Client.
+    /// Sends [`Event`] with the provided sequence number to Web Client.
     ///
     /// [`Event`]: medea_client_api_proto::Event
-    fn send_event(&self, room_id: RoomId, event: Event) {
-        self.do_send(EventMessage { room_id, event });
+    fn send_event(&self, room_id: RoomId, event: Event, seq: Option<u64>) {
+        self.do_send(EventMessage {
+            room_id,
+            event,
+            seq,
+        });
     }
 }
 
@@ -628,7 +780,7 @@ impl Handler<CloseRoom> for WsSession {
         ctx: &mut Self::Context,
     ) -> Self::Result {
         if self.sessions.remove(&msg.room_id).is_some() {
-            self.send_left_room(ctx, msg.room_id, msg.close_description.reason);
+            self.send_left_room(ctx, msg.room_id, msg.close_description);
             if self.sessions.is_empty() {
                 self.close_in_place(ctx, &msg.close_description);
             }
@@ -641,7 +793,7 @@ impl Handler<EventMessage> for WsSession {
 
     /// Sends [`Event`] to Web Client.
     fn handle(&mut self, msg: EventMessage, ctx: &mut Self::Context) {
-        self.send_event(ctx, msg.room_id, msg.event);
+        self.send_event(ctx, msg.room_id, msg.event, msg.seq);
     }
 }
 
@@ -667,13 +819,21 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WsSession {
                     // nothing to do here
                 }
             },
+            Err(ws::ProtocolError::Overflow) => {
+                error!(
+                    "{}: Received message exceeding {} bytes",
+                    self, self.max_message_size,
+                );
+                self.close_in_place(
+                    ctx,
+                    &CloseDescription::new(CloseReason::MessageTooLarge),
+                );
+            }
             Err(err) => {
                 error!("{}: StreamHandler Error: {:?}", self, err);
                 self.close_in_place(
                     ctx,
-                    &CloseDescription {
-                        reason: CloseReason::InternalError,
-                    },
+                    &CloseDescription::new(CloseReason::InternalError),
                 );
             }
         };
@@ -696,7 +856,8 @@ impl Display for WsSession {
 #[cfg(test)]
 mod test {
     use std::{
-        str,
+        collections::HashMap,
+        fmt, str,
         sync::Mutex,
         time::{Duration, Instant},
     };
@@ -712,11 +873,12 @@ mod test {
             mpsc::{self, UnboundedReceiver, UnboundedSender},
             oneshot::{self, Receiver, Sender},
         },
-        future, FutureExt as _, SinkExt as _, StreamExt as _,
+        future, FutureExt as _, SinkExt as _, Stream, StreamExt as _,
     };
     use medea_client_api_proto::{
-        ClientMsg, CloseDescription, CloseReason as ProtoCloseReason, Command,
-        Event, IceCandidate, MemberId, PeerId, RpcSettings, ServerMsg,
+        ClientCapabilities, ClientMsg, CloseDescription,
+        CloseReason as ProtoCloseReason, Command, Event, IceCandidate,
+        MemberId, PeerId, RpcSettings, ServerMsg, MAX_ICE_CANDIDATE_LEN,
     };
     use tokio::time::timeout;
 
@@ -748,6 +910,21 @@ mod test {
         )
     }
 
+    async fn next_rpc_settings<S, E>(client: &mut S) -> RpcSettings
+    where
+        S: Stream<Item = Result<Frame, E>> + Unpin,
+        E: fmt::Debug,
+    {
+        loop {
+            if let Frame::Text(text) = client.next().await.unwrap().unwrap() {
+                let msg: ServerMsg = serde_json::from_slice(&text).unwrap();
+                if let ServerMsg::RpcSettings(settings) = msg {
+                    break settings;
+                }
+            }
+        }
+    }
+
     async fn test_server(factory: fn() -> WsSession) -> TestServer {
         actix_http_test::test_server(move || {
             HttpService::new(map_config(
@@ -776,10 +953,10 @@ mod test {
                 let expected_member_id = member_id.clone();
                 rpc_server
                     .expect_connection_established()
-                    .withf(move |member_id, _, _| {
+                    .withf(move |member_id, _, _, _, _| {
                         *member_id == expected_member_id
                     })
-                    .return_once(|_, _, _| {
+                    .return_once(|_, _, _, _, _| {
                         future::err(RpcServerError::Authorization).boxed_local()
                     });
                 rpc_server
@@ -806,6 +983,7 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "member_id".into(),
                     credential: "token".into(),
+                    capabilities: ClientCapabilities::default(),
                 },
             }))
             .await
@@ -820,7 +998,9 @@ mod test {
                 room_id: "room_id".into(),
                 event: Event::RoomLeft {
                     close_reason: medea_client_api_proto::CloseReason::Rejected,
-                }
+                    retry_after_ms: None,
+                },
+                seq: None,
             })
         );
 
@@ -832,6 +1012,149 @@ mod test {
         assert_eq!(item, close_frame);
     }
 
+    // WebSocket connection is closed with a retryable close code when
+    // RpcServer rejects RpcConnectionEstablished due to draining.
+    #[actix_rt::test]
+    async fn close_with_again_code_if_draining() {
+        fn factory() -> WsSession {
+            let mut rpc_server_repo = MockRpcServerRepository::new();
+            rpc_server_repo.expect_get().returning(|_| {
+                let mut rpc_server = MockRpcServer::new();
+
+                rpc_server.expect_connection_established().return_once(
+                    |_, _, _, _, _| {
+                        future::err(RpcServerError::Draining).boxed_local()
+                    },
+                );
+
+                Some(Box::new(rpc_server))
+            });
+
+            WsSession::new(
+                Box::new(rpc_server_repo),
+                Duration::from_secs(5),
+                Duration::from_secs(5),
+            )
+        }
+
+        let mut serv = test_server(factory).await;
+
+        let mut client = serv.ws().await.unwrap();
+
+        client
+            .send(into_message(ClientMsg::Command {
+                room_id: "room_id".into(),
+                command: Command::JoinRoom {
+                    member_id: "member_id".into(),
+                    credential: "token".into(),
+                    capabilities: ClientCapabilities::default(),
+                },
+            }))
+            .await
+            .unwrap();
+
+        let mut client = client.skip(2);
+        let left_room_frame = client.next().await.unwrap().unwrap();
+
+        assert_eq!(
+            left_room_frame,
+            into_frame(ServerMsg::Event {
+                room_id: "room_id".into(),
+                event: Event::RoomLeft {
+                    close_reason: medea_client_api_proto::CloseReason::Draining,
+                    retry_after_ms: None,
+                },
+                seq: None,
+            })
+        );
+
+        let item = client.next().await.unwrap().unwrap();
+        let close_frame = Frame::Close(Some(CloseReason {
+            code: CloseCode::Again,
+            description: Some(String::from(r#"{"reason":"Draining"}"#)),
+        }));
+        assert_eq!(item, close_frame);
+    }
+
+    // WebSocket connection is closed with a retryable close code and a retry
+    // hint when RpcServer rejects RpcConnectionEstablished due to exceeded
+    // admission rate.
+    #[actix_rt::test]
+    async fn close_with_retry_hint_if_overloaded() {
+        fn factory() -> WsSession {
+            let mut rpc_server_repo = MockRpcServerRepository::new();
+            rpc_server_repo.expect_get().returning(|_| {
+                let mut rpc_server = MockRpcServer::new();
+
+                rpc_server.expect_connection_established().return_once(
+                    |_, _, _, _, _| {
+                        future::err(RpcServerError::Overloaded(
+                            Duration::from_millis(500),
+                        ))
+                        .boxed_local()
+                    },
+                );
+
+                Some(Box::new(rpc_server))
+            });
+
+            WsSession::new(
+                Box::new(rpc_server_repo),
+                Duration::from_secs(5),
+                Duration::from_secs(5),
+            )
+        }
+
+        let mut serv = test_server(factory).await;
+
+        let mut client = serv.ws().await.unwrap();
+
+        client
+            .send(into_message(ClientMsg::Command {
+                room_id: "room_id".into(),
+                command: Command::JoinRoom {
+                    member_id: "member_id".into(),
+                    credential: "token".into(),
+                    capabilities: ClientCapabilities::default(),
+                },
+            }))
+            .await
+            .unwrap();
+
+        let mut client = client.skip(2);
+        let left_room_frame = client.next().await.unwrap().unwrap();
+        if let Frame::Text(text) = left_room_frame {
+            let msg: ServerMsg = serde_json::from_slice(&text).unwrap();
+            if let ServerMsg::Event {
+                event:
+                    Event::RoomLeft {
+                        close_reason,
+                        retry_after_ms,
+                    },
+                ..
+            } = msg
+            {
+                assert_eq!(close_reason, ProtoCloseReason::Overloaded);
+                assert_eq!(retry_after_ms, Some(500));
+            } else {
+                unreachable!("Expected Event::RoomLeft, but got: {:?}", msg);
+            }
+        } else {
+            unreachable!("Expected Text frame, but got: {:?}", left_room_frame);
+        }
+
+        let item = client.next().await.unwrap().unwrap();
+        if let Frame::Close(Some(reason)) = item {
+            assert_eq!(reason.code, CloseCode::Other(4004));
+            let description: CloseDescription =
+                serde_json::from_str(&reason.description.unwrap()).unwrap();
+            assert_eq!(description.reason, ProtoCloseReason::Overloaded);
+            assert_eq!(description.retry_after_ms, Some(500));
+        } else {
+            unreachable!("Expected Close frame, but got: {:?}", item);
+        }
+    }
+
     #[actix_rt::test]
     async fn sends_rpc_settings_and_pings() {
         let mut serv = test_server(|| -> WsSession {
@@ -840,10 +1163,11 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, _| {
+                    |_, _, _, _, _| {
                         future::ok(RpcConnectionSettings {
                             ping_interval: Duration::from_secs(10),
                             idle_timeout: Duration::from_secs(10),
+                            features: HashMap::new(),
                         })
                         .boxed_local()
                     },
@@ -871,6 +1195,7 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "member_id".into(),
                     credential: "token".into(),
+                    capabilities: ClientCapabilities::default(),
                 },
             }))
             .await
@@ -881,6 +1206,7 @@ mod test {
         let expected_item = into_frame(ServerMsg::RpcSettings(RpcSettings {
             idle_timeout_ms: 5000,
             ping_interval_ms: 50,
+            features: HashMap::new(),
         }));
         assert_eq!(item, expected_item);
 
@@ -894,7 +1220,8 @@ mod test {
                 room_id: "room_id".into(),
                 event: Event::RoomJoined {
                     member_id: "member_id".into(),
-                }
+                },
+                seq: None,
             })
         );
 
@@ -902,6 +1229,68 @@ mod test {
         assert_eq!(item, into_frame(ServerMsg::Ping(1)));
     }
 
+    #[actix_rt::test]
+    async fn sends_rpc_settings_with_merged_features() {
+        let mut serv = test_server(|| -> WsSession {
+            let mut rpc_server_repo = MockRpcServerRepository::new();
+            rpc_server_repo.expect_get().returning(|_| {
+                let mut rpc_server = MockRpcServer::new();
+
+                rpc_server.expect_connection_established().return_once(
+                    |_, _, _, _, _| {
+                        let mut features = HashMap::new();
+                        features.insert("delta_sync".into(), "true".into());
+                        future::ok(RpcConnectionSettings {
+                            ping_interval: Duration::from_secs(10),
+                            idle_timeout: Duration::from_secs(10),
+                            features,
+                        })
+                        .boxed_local()
+                    },
+                );
+                rpc_server
+                    .expect_connection_closed()
+                    .returning(|_, _| future::ready(()).boxed_local());
+
+                Some(Box::new(rpc_server))
+            });
+
+            let mut features = HashMap::new();
+            features.insert("delta_sync".into(), "false".into());
+            features.insert("binary_proto".into(), "false".into());
+            WsSession::new(
+                Box::new(rpc_server_repo),
+                Duration::from_secs(5),
+                Duration::from_secs(5),
+            )
+            .with_features(features)
+        })
+        .await;
+
+        let mut client = serv.ws().await.unwrap();
+
+        let settings = next_rpc_settings(&mut client).await;
+        assert_eq!(settings.features["delta_sync"], "false");
+        assert_eq!(settings.features["binary_proto"], "false");
+
+        client
+            .send(into_message(ClientMsg::Command {
+                room_id: "room_id".into(),
+                command: Command::JoinRoom {
+                    member_id: "member_id".into(),
+                    credential: "token".into(),
+                    capabilities: ClientCapabilities::default(),
+                },
+            }))
+            .await
+            .unwrap();
+
+        let settings = next_rpc_settings(&mut client).await;
+        assert_eq!(settings.idle_timeout_ms, 5000);
+        assert_eq!(settings.features["delta_sync"], "true");
+        assert_eq!(settings.features["binary_proto"], "false");
+    }
+
     // WsSession is dropped and WebSocket connection is closed if no pongs
     // received for idle_timeout.
     #[actix_rt::test]
@@ -913,10 +1302,11 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, _| {
+                    |_, _, _, _, _| {
                         future::ok(RpcConnectionSettings {
                             ping_interval: Duration::from_secs(10),
                             idle_timeout: Duration::from_secs(10),
+                            features: HashMap::new(),
                         })
                         .boxed_local()
                     },
@@ -949,6 +1339,7 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "member_id".into(),
                     credential: "token".into(),
+                    capabilities: ClientCapabilities::default(),
                 },
             }))
             .await
@@ -986,10 +1377,11 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, _| {
+                    |_, _, _, _, _| {
                         future::ok(RpcConnectionSettings {
                             idle_timeout: Duration::from_secs(10),
                             ping_interval: Duration::from_secs(10),
+                            features: HashMap::new(),
                         })
                         .boxed_local()
                     },
@@ -1021,6 +1413,7 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "member_id".into(),
                     credential: "token".into(),
+                    capabilities: ClientCapabilities::default(),
                 },
             }))
             .await
@@ -1082,6 +1475,78 @@ mod test {
         }
     }
 
+    // WebSocket connection is closed when fragmented message exceeds the
+    // maximum message size.
+    #[actix_rt::test]
+    async fn close_on_too_large_message() {
+        let mut serv = test_server(|| -> WsSession {
+            WsSession::new(
+                Box::new(MockRpcServerRepository::new()),
+                Duration::from_secs(5),
+                Duration::from_secs(5),
+            )
+            .with_max_message_size(32)
+        })
+        .await;
+
+        let mut client = serv.ws().await.unwrap();
+
+        let payload = Bytes::from("a".repeat(40));
+        client
+            .send(Message::Continuation(Item::FirstText(payload.slice(0..20))))
+            .await
+            .unwrap();
+        client
+            .send(Message::Continuation(Item::Last(payload.slice(20..40))))
+            .await
+            .unwrap();
+
+        let item = client.next().await.unwrap().unwrap();
+        let close_frame = Frame::Close(Some(CloseReason {
+            code: CloseCode::Normal,
+            description: Some(String::from(r#"{"reason":"MessageTooLarge"}"#)),
+        }));
+        assert_eq!(item, close_frame);
+    }
+
+    // WebSocket connection is closed when Command with oversized fields,
+    // which are relayed to other Members, is received.
+    #[actix_rt::test]
+    async fn close_on_oversized_relayed_command() {
+        let mut serv = test_server(|| -> WsSession {
+            WsSession::new(
+                Box::new(MockRpcServerRepository::new()),
+                Duration::from_secs(5),
+                Duration::from_secs(5),
+            )
+        })
+        .await;
+
+        let mut client = serv.ws().await.unwrap();
+
+        client
+            .send(into_message(ClientMsg::Command {
+                room_id: "room_id".into(),
+                command: Command::SetIceCandidate {
+                    peer_id: PeerId(15),
+                    candidate: IceCandidate {
+                        candidate: "a".repeat(MAX_ICE_CANDIDATE_LEN + 1),
+                        sdp_m_line_index: Some(1),
+                        sdp_mid: Some("2".to_string()),
+                    },
+                },
+            }))
+            .await
+            .unwrap();
+
+        let item = client.next().await.unwrap().unwrap();
+        let close_frame = Frame::Close(Some(CloseReason {
+            code: CloseCode::Normal,
+            description: Some(String::from(r#"{"reason":"MessageTooLarge"}"#)),
+        }));
+        assert_eq!(item, close_frame);
+    }
+
     // WsSession is dropped and WebSocket connection is closed when
     // RpcConnection::close is called.
     #[actix_rt::test]
@@ -1099,7 +1564,7 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, connection| {
+                    |_, _, _, _, connection| {
                         let _ = CHAN
                             .0
                             .lock()
@@ -1110,6 +1575,7 @@ mod test {
                         future::ok(RpcConnectionSettings {
                             idle_timeout: Duration::from_secs(10),
                             ping_interval: Duration::from_secs(10),
+                            features: HashMap::new(),
                         })
                         .boxed_local()
                     },
@@ -1137,6 +1603,7 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "member_id".into(),
                     credential: "token".into(),
+                    capabilities: ClientCapabilities::default(),
                 },
             }))
             .await
@@ -1148,9 +1615,7 @@ mod test {
         rpc_connection
             .close(
                 "room_id".into(),
-                CloseDescription {
-                    reason: ProtoCloseReason::Evicted,
-                },
+                CloseDescription::new(ProtoCloseReason::Evicted),
             )
             .await;
         let mut client = client.skip(3);
@@ -1162,7 +1627,9 @@ mod test {
                 room_id: "room_id".into(),
                 event: Event::RoomLeft {
                     close_reason: medea_client_api_proto::CloseReason::Evicted,
-                }
+                    retry_after_ms: None,
+                },
+                seq: None,
             })
         );
         let item = client.next().await.unwrap().unwrap();
@@ -1192,7 +1659,7 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, connection| {
+                    |_, _, _, _, connection| {
                         let _ = CHAN
                             .0
                             .lock()
@@ -1203,6 +1670,7 @@ mod test {
                         future::ok(RpcConnectionSettings {
                             ping_interval: Duration::from_secs(10),
                             idle_timeout: Duration::from_secs(10),
+                            features: HashMap::new(),
                         })
                         .boxed_local()
                     },
@@ -1229,6 +1697,7 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "member_id".into(),
                     credential: "token".into(),
+                    capabilities: ClientCapabilities::default(),
                 },
             }))
             .await
@@ -1243,6 +1712,7 @@ mod test {
                 peer_id: PeerId(77),
                 sdp_answer: String::from("sdp_answer"),
             },
+            Some(1),
         );
 
         let item = client.skip(3).next().await.unwrap().unwrap();
@@ -1253,6 +1723,7 @@ mod test {
                 peer_id: PeerId(77),
                 sdp_answer: "sdp_answer".to_string(),
             },
+            seq: Some(1),
         })
         .unwrap();
 
@@ -1274,12 +1745,13 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().return_once(
-                    |_, _, connection| {
+                    |_, _, _, _, connection| {
                         let _ =
                             CHAN.0.lock().unwrap().unbounded_send(connection);
                         future::ok(RpcConnectionSettings {
                             ping_interval: Duration::from_secs(10),
                             idle_timeout: Duration::from_secs(10),
+                            features: HashMap::new(),
                         })
                         .boxed_local()
                     },
@@ -1306,6 +1778,7 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "alice".into(),
                     credential: "token".into(),
+                    capabilities: ClientCapabilities::default(),
                 },
             }))
             .await
@@ -1319,7 +1792,11 @@ mod test {
             peer_id: PeerId(0),
             sdp_answer: String::from("sdp_answer"),
         };
-        alice_connection.send_event("alice_room".into(), alice_event.clone());
+        alice_connection.send_event(
+            "alice_room".into(),
+            alice_event.clone(),
+            None,
+        );
 
         client
             .send(into_message(ClientMsg::Command {
@@ -1327,6 +1804,7 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "bob".into(),
                     credential: "token".into(),
+                    capabilities: ClientCapabilities::default(),
                 },
             }))
             .await
@@ -1338,7 +1816,7 @@ mod test {
             peer_id: PeerId(1),
             sdp_answer: String::from("sdp_answer"),
         };
-        bob_connection.send_event("bob_room".into(), bob_event.clone());
+        bob_connection.send_event("bob_room".into(), bob_event.clone(), None);
 
         let msgs: Vec<_> = client
             .filter_map(|f| async move {
@@ -1364,6 +1842,7 @@ mod test {
             ServerMsg::RpcSettings(RpcSettings {
                 idle_timeout_ms: 5000,
                 ping_interval_ms: 5000,
+                features: HashMap::new(),
             })
         );
         assert_eq!(
@@ -1372,7 +1851,8 @@ mod test {
                 room_id: "alice_room".into(),
                 event: Event::RoomJoined {
                     member_id: "alice".into(),
-                }
+                },
+                seq: None,
             }
         );
         assert_eq!(
@@ -1380,6 +1860,7 @@ mod test {
             ServerMsg::Event {
                 room_id: "alice_room".into(),
                 event: alice_event,
+                seq: None,
             }
         );
         assert_eq!(
@@ -1388,7 +1869,8 @@ mod test {
                 room_id: "bob_room".into(),
                 event: Event::RoomJoined {
                     member_id: "bob".into(),
-                }
+                },
+                seq: None,
             }
         );
         assert_eq!(
@@ -1396,6 +1878,7 @@ mod test {
             ServerMsg::Event {
                 room_id: "bob_room".into(),
                 event: bob_event,
+                seq: None,
             }
         );
         assert_eq!(msgs.len(), 5);
@@ -1409,10 +1892,11 @@ mod test {
                 let mut rpc_server = MockRpcServer::new();
 
                 rpc_server.expect_connection_established().returning(
-                    |_, _, _| {
+                    |_, _, _, _, _| {
                         future::ok(RpcConnectionSettings {
                             idle_timeout: Duration::from_secs(10),
                             ping_interval: Duration::from_secs(10),
+                            features: HashMap::new(),
                         })
                         .boxed_local()
                     },
@@ -1440,6 +1924,7 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "member1".into(),
                     credential: "token".into(),
+                    capabilities: ClientCapabilities::default(),
                 },
             }))
             .await
@@ -1450,6 +1935,7 @@ mod test {
                 command: Command::JoinRoom {
                     member_id: "member2".into(),
                     credential: "token".into(),
+                    capabilities: ClientCapabilities::default(),
                 },
             }))
             .await
@@ -1480,13 +1966,15 @@ mod test {
             room_id: "room1".into(),
             event: Event::RoomJoined {
                 member_id: "member1".into(),
-            }
+            },
+            seq: None,
         })));
         assert!(frames.contains(&into_frame(ServerMsg::Event {
             room_id: "room2".into(),
             event: Event::RoomJoined {
                 member_id: "member2".into(),
-            }
+            },
+            seq: None,
         })));
         assert_eq!(
             frames.pop().unwrap(),
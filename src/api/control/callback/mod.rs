@@ -4,9 +4,14 @@ pub mod clients;
 pub mod service;
 pub mod url;
 
+use std::{net::SocketAddr, time::Duration};
+
 use actix::Message;
 use chrono::{DateTime, Utc};
 use derive_more::{Display, From};
+use medea_client_api_proto::{
+    ClientCapabilities, CloseReason, MemberId, PeerId,
+};
 use medea_control_api_proto::grpc::callback as proto;
 
 use crate::api::control::refs::StatefulFid;
@@ -18,32 +23,101 @@ pub use self::{
     url::CallbackUrl,
 };
 
+/// Metadata of a `Client` session of a `Member`, reported in `on_join` and
+/// `on_leave` callbacks.
+#[derive(Clone, Debug, Default)]
+pub struct ClientSession {
+    /// Remote address of the `Client`'s connection, if known.
+    pub remote_addr: Option<SocketAddr>,
+
+    /// [`ClientCapabilities`] reported by the `Client`.
+    pub capabilities: ClientCapabilities,
+}
+
+impl From<ClientSession> for proto::ClientSession {
+    fn from(session: ClientSession) -> Self {
+        Self {
+            remote_addr: session
+                .remote_addr
+                .map(|addr| addr.to_string())
+                .unwrap_or_default(),
+            user_agent: session.capabilities.user_agent.unwrap_or_default(),
+            codecs: session.capabilities.codecs,
+            simulcast: session.capabilities.simulcast,
+            insertable_streams: session.capabilities.insertable_streams,
+        }
+    }
+}
+
 /// Event for `on_leave` `Member` callback.
 #[derive(Debug)]
 pub struct OnLeaveEvent {
     /// Reason of why `Member` was lost.
     reason: OnLeaveReason,
+
+    /// [`ClientSession`] the `Member` has left with, along with the
+    /// [`Duration`] it has been joined for.
+    session: Option<(ClientSession, Duration)>,
+
+    /// [`CloseReason`] the `Client`'s connection has been closed by server
+    /// with.
+    close_reason: Option<CloseReason>,
 }
 
 impl OnLeaveEvent {
     #[inline]
     #[must_use]
     pub fn new(reason: OnLeaveReason) -> Self {
-        Self { reason }
+        Self {
+            reason,
+            session: None,
+            close_reason: None,
+        }
+    }
+
+    /// Sets the [`ClientSession`] the `Member` has left with, and the
+    /// [`Duration`] it has been joined for.
+    #[inline]
+    #[must_use]
+    pub fn with_session(
+        mut self,
+        session: ClientSession,
+        duration: Duration,
+    ) -> Self {
+        self.session = Some((session, duration));
+        self
+    }
+
+    /// Sets the [`CloseReason`] the `Client`'s connection has been closed by
+    /// server with.
+    #[inline]
+    #[must_use]
+    pub fn with_close_reason(mut self, close_reason: CloseReason) -> Self {
+        self.close_reason = Some(close_reason);
+        self
     }
 }
 
 impl From<OnLeaveEvent> for proto::OnLeave {
-    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
     fn from(ev: OnLeaveEvent) -> Self {
+        let (session, duration_ms) = ev
+            .session
+            .map_or((None, 0), |(s, d)| (Some(s.into()), d.as_millis() as u64));
         Self {
             reason: proto::on_leave::Reason::from(ev.reason) as i32,
+            session,
+            duration_ms,
+            close_reason: ev
+                .close_reason
+                .map(|r| r.to_string())
+                .unwrap_or_default(),
         }
     }
 }
 
 /// Reason of why `Member` was lost.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum OnLeaveReason {
     /// `Member` was normally disconnected.
     Disconnected,
@@ -72,12 +146,220 @@ impl From<OnLeaveReason> for proto::on_leave::Reason {
 
 /// `on_join` `Member` callback for Control API.
 #[derive(Debug)]
-pub struct OnJoinEvent;
+pub struct OnJoinEvent {
+    /// [`ClientSession`] the `Member` has joined with.
+    session: ClientSession,
+}
+
+impl OnJoinEvent {
+    #[inline]
+    #[must_use]
+    pub fn new(session: ClientSession) -> Self {
+        Self { session }
+    }
+}
 
 impl From<OnJoinEvent> for proto::OnJoin {
     #[inline]
-    fn from(_: OnJoinEvent) -> Self {
-        Self {}
+    fn from(ev: OnJoinEvent) -> Self {
+        Self {
+            session: Some(ev.session.into()),
+        }
+    }
+}
+
+/// Event for `on_start` `Endpoint` callback.
+#[derive(Debug)]
+pub struct OnStartEvent {
+    /// [`MediaType`] of the traffic which started flowing.
+    media_type: MediaType,
+
+    /// [`MediaDirection`] of the `Endpoint` which traffic started flowing.
+    media_direction: MediaDirection,
+}
+
+impl OnStartEvent {
+    #[inline]
+    #[must_use]
+    pub fn new(media_type: MediaType, media_direction: MediaDirection) -> Self {
+        Self {
+            media_type,
+            media_direction,
+        }
+    }
+}
+
+impl From<OnStartEvent> for proto::OnStart {
+    #[inline]
+    fn from(ev: OnStartEvent) -> Self {
+        Self {
+            media_type: proto::MediaType::from(ev.media_type) as i32,
+            media_direction: proto::MediaDirection::from(ev.media_direction)
+                as i32,
+        }
+    }
+}
+
+/// Event for `on_stop` `Endpoint` callback.
+#[derive(Debug)]
+pub struct OnStopEvent {
+    /// [`MediaType`] of the traffic which stopped flowing.
+    media_type: MediaType,
+
+    /// [`MediaDirection`] of the `Endpoint` which traffic stopped flowing.
+    media_direction: MediaDirection,
+}
+
+impl OnStopEvent {
+    #[inline]
+    #[must_use]
+    pub fn new(media_type: MediaType, media_direction: MediaDirection) -> Self {
+        Self {
+            media_type,
+            media_direction,
+        }
+    }
+}
+
+impl From<OnStopEvent> for proto::OnStop {
+    #[inline]
+    fn from(ev: OnStopEvent) -> Self {
+        Self {
+            media_type: proto::MediaType::from(ev.media_type) as i32,
+            media_direction: proto::MediaDirection::from(ev.media_direction)
+                as i32,
+        }
+    }
+}
+
+/// Event for `on_progress` callback of an asynchronous `Room` creation.
+#[derive(Debug)]
+pub struct OnCreateProgressEvent {
+    /// ID of the creation operation.
+    operation_id: String,
+
+    /// Number of the already provisioned elements.
+    done: u32,
+
+    /// Total number of the elements to be provisioned.
+    total: u32,
+}
+
+impl OnCreateProgressEvent {
+    #[inline]
+    #[must_use]
+    pub fn new(operation_id: String, done: u32, total: u32) -> Self {
+        Self {
+            operation_id,
+            done,
+            total,
+        }
+    }
+}
+
+impl From<OnCreateProgressEvent> for proto::OnCreateProgress {
+    #[inline]
+    fn from(ev: OnCreateProgressEvent) -> Self {
+        Self {
+            operation_id: ev.operation_id,
+            done: ev.done,
+            total: ev.total,
+        }
+    }
+}
+
+/// Event for `on_progress` callback, fired once an asynchronous `Room`
+/// creation is finished.
+#[derive(Debug)]
+pub struct OnCreateCompleteEvent {
+    /// ID of the creation operation.
+    operation_id: String,
+
+    /// Error of the creation, if it has failed.
+    error: Option<String>,
+}
+
+impl OnCreateCompleteEvent {
+    #[inline]
+    #[must_use]
+    pub fn new(operation_id: String, error: Option<String>) -> Self {
+        Self {
+            operation_id,
+            error,
+        }
+    }
+}
+
+impl From<OnCreateCompleteEvent> for proto::OnCreateComplete {
+    #[inline]
+    fn from(ev: OnCreateCompleteEvent) -> Self {
+        Self {
+            operation_id: ev.operation_id,
+            error: ev.error.unwrap_or_default(),
+        }
+    }
+}
+
+/// Event for `on_negotiation_timeout` `Member` callback.
+#[derive(Debug)]
+pub struct OnNegotiationTimeoutEvent {
+    /// ID of the `Peer` which negotiation has timed out.
+    peer_id: PeerId,
+
+    /// ID of the `Member` owning the partner `Peer`.
+    partner_member_id: MemberId,
+}
+
+impl OnNegotiationTimeoutEvent {
+    #[inline]
+    #[must_use]
+    pub fn new(peer_id: PeerId, partner_member_id: MemberId) -> Self {
+        Self {
+            peer_id,
+            partner_member_id,
+        }
+    }
+}
+
+impl From<OnNegotiationTimeoutEvent> for proto::OnNegotiationTimeout {
+    #[inline]
+    fn from(ev: OnNegotiationTimeoutEvent) -> Self {
+        Self {
+            peer_id: ev.peer_id.0,
+            partner_member_id: ev.partner_member_id.to_string(),
+        }
+    }
+}
+
+/// Event for `on_applied` callback, fired once a patch requested via
+/// `PatchTrack` is applied by all the affected `Client`s.
+#[derive(Debug)]
+pub struct OnPatchAppliedEvent {
+    /// ID of the patch operation.
+    operation_id: String,
+
+    /// Error of the patch operation, if it has failed.
+    error: Option<String>,
+}
+
+impl OnPatchAppliedEvent {
+    #[inline]
+    #[must_use]
+    pub fn new(operation_id: String, error: Option<String>) -> Self {
+        Self {
+            operation_id,
+            error,
+        }
+    }
+}
+
+impl From<OnPatchAppliedEvent> for proto::OnPatchApplied {
+    #[inline]
+    fn from(ev: OnPatchAppliedEvent) -> Self {
+        Self {
+            operation_id: ev.operation_id,
+            error: ev.error.unwrap_or_default(),
+        }
     }
 }
 
@@ -86,6 +368,12 @@ impl From<OnJoinEvent> for proto::OnJoin {
 pub enum CallbackEvent {
     OnJoin(OnJoinEvent),
     OnLeave(OnLeaveEvent),
+    OnStart(OnStartEvent),
+    OnStop(OnStopEvent),
+    OnCreateProgress(OnCreateProgressEvent),
+    OnCreateComplete(OnCreateCompleteEvent),
+    OnNegotiationTimeout(OnNegotiationTimeoutEvent),
+    OnPatchApplied(OnPatchAppliedEvent),
 }
 
 impl From<CallbackEvent> for proto::request::Event {
@@ -94,6 +382,20 @@ impl From<CallbackEvent> for proto::request::Event {
         match ev {
             CallbackEvent::OnJoin(on_join) => Self::OnJoin(on_join.into()),
             CallbackEvent::OnLeave(on_leave) => Self::OnLeave(on_leave.into()),
+            CallbackEvent::OnStart(on_start) => Self::OnStart(on_start.into()),
+            CallbackEvent::OnStop(on_stop) => Self::OnStop(on_stop.into()),
+            CallbackEvent::OnCreateProgress(ev) => {
+                Self::OnCreateProgress(ev.into())
+            }
+            CallbackEvent::OnCreateComplete(ev) => {
+                Self::OnCreateComplete(ev.into())
+            }
+            CallbackEvent::OnNegotiationTimeout(ev) => {
+                Self::OnNegotiationTimeout(ev.into())
+            }
+            CallbackEvent::OnPatchApplied(ev) => {
+                Self::OnPatchApplied(ev.into())
+            }
         }
     }
 }
@@ -140,6 +442,17 @@ impl MediaType {
     }
 }
 
+impl From<MediaType> for proto::MediaType {
+    #[inline]
+    fn from(media_type: MediaType) -> Self {
+        match media_type {
+            MediaType::Audio => Self::Audio,
+            MediaType::Video => Self::Video,
+            MediaType::Both => Self::Both,
+        }
+    }
+}
+
 impl From<&medea_client_api_proto::MediaType> for MediaType {
     fn from(media_type: &medea_client_api_proto::MediaType) -> Self {
         use medea_client_api_proto::MediaType as MediaTypeProto;
@@ -162,6 +475,16 @@ pub enum MediaDirection {
     Play,
 }
 
+impl From<MediaDirection> for proto::MediaDirection {
+    #[inline]
+    fn from(direction: MediaDirection) -> Self {
+        match direction {
+            MediaDirection::Publish => Self::Publish,
+            MediaDirection::Play => Self::Play,
+        }
+    }
+}
+
 /// Control API callback.
 ///
 /// Used for sending callbacks with [`CallbackClient::send`].
@@ -187,10 +510,21 @@ impl CallbackRequest {
     #[inline]
     #[must_use]
     pub fn new(element: StatefulFid, event: CallbackEvent) -> Self {
+        Self::new_at(element, event, Utc::now())
+    }
+
+    /// Returns [`CallbackRequest`] with provided fields.
+    #[inline]
+    #[must_use]
+    pub fn new_at(
+        element: StatefulFid,
+        event: CallbackEvent,
+        at: DateTime<Utc>,
+    ) -> Self {
         Self {
             fid: element,
             event,
-            at: Utc::now(),
+            at,
         }
     }
 }
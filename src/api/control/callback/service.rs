@@ -4,6 +4,7 @@ use std::{
     collections::hash_map::HashMap, fmt::Debug, marker::PhantomData, sync::Arc,
 };
 
+use chrono::{DateTime, Utc};
 use tokio::sync::RwLock;
 
 use crate::{
@@ -100,15 +101,32 @@ impl<B: CallbackClientFactory + 'static> CallbackService<B> {
     /// [`CallbackClient`] ignoring any potential errors.
     ///
     /// Will use existing [`CallbackClient`] or create new.
+    #[inline]
     pub fn do_send<T: Into<CallbackEvent> + 'static>(
         &self,
         callback_url: CallbackUrl,
         fid: StatefulFid,
         event: T,
+    ) {
+        self.do_send_at(callback_url, fid, event, Utc::now());
+    }
+
+    /// Asynchronously sends [`CallbackEvent`] occurred at the provided
+    /// [`DateTime`] for provided [`StatefulFid`] to [`CallbackClient`]
+    /// ignoring any potential errors.
+    ///
+    /// Will use existing [`CallbackClient`] or create new.
+    pub fn do_send_at<T: Into<CallbackEvent> + 'static>(
+        &self,
+        callback_url: CallbackUrl,
+        fid: StatefulFid,
+        event: T,
+        at: DateTime<Utc>,
     ) {
         let this = self.clone();
         actix::spawn(async move {
-            if let Err(e) = this.send(callback_url, fid, event).await {
+            let request = CallbackRequest::new_at(fid, event.into(), at);
+            if let Err(e) = this.inner_send(request, callback_url).await {
                 error!("Failed to send callback because {:?}.", e);
             }
         });
@@ -125,7 +143,7 @@ mod tests {
 
     use crate::api::control::callback::{
         clients::{MockCallbackClient, MockCallbackClientFactory},
-        OnJoinEvent,
+        ClientSession, OnJoinEvent,
     };
 
     use super::*;
@@ -134,7 +152,7 @@ mod tests {
     fn callback_request() -> CallbackRequest {
         CallbackRequest::new(
             StatefulFid::try_from("foo".to_string()).unwrap(),
-            CallbackEvent::OnJoin(OnJoinEvent),
+            CallbackEvent::OnJoin(OnJoinEvent::new(ClientSession::default())),
         )
     }
 
@@ -3,7 +3,7 @@
 use std::{convert::TryFrom, fmt};
 
 use derive_more::{Display, From};
-use serde::{de::Visitor, Deserialize, Deserializer};
+use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
 use url::{ParseError, Url};
 
 /// Callback URL for gRPC client.
@@ -101,6 +101,16 @@ impl<'de> Deserialize<'de> for CallbackUrl {
     }
 }
 
+impl Serialize for CallbackUrl {
+    #[inline]
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
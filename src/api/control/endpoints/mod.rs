@@ -11,7 +11,9 @@ use derive_more::{Display, From, Into};
 use medea_control_api_proto::grpc::api as proto;
 use serde::Deserialize;
 
-use super::{member::MemberElement, TryFromProtobufError};
+use super::{
+    callback::url::CallbackUrl, member::MemberElement, TryFromProtobufError,
+};
 
 #[doc(inline)]
 pub use webrtc_play_endpoint::{WebRtcPlayEndpoint, WebRtcPlayId};
@@ -43,6 +45,22 @@ macro_rules! impl_from_into {
 impl_from_into!(WebRtcPublishId);
 impl_from_into!(WebRtcPlayId);
 
+/// Parses the provided [`CallbackUrl`] of an `Endpoint`, treating an empty
+/// string as an unset one.
+///
+/// # Errors
+///
+/// Errors if the provided URL is not a valid [`CallbackUrl`].
+fn parse_callback_url(
+    url: &str,
+) -> Result<Option<CallbackUrl>, TryFromProtobufError> {
+    if url.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(CallbackUrl::try_from(url.to_owned())?))
+    }
+}
+
 /// Media element that one or more media data streams flow through.
 #[derive(Debug, From)]
 pub enum EndpointSpec {
@@ -79,7 +97,7 @@ impl TryFrom<(Id, proto::member::element::El)> for EndpointSpec {
                 Ok(Self::WebRtcPlay(play))
             }
             El::WebrtcPub(elem) => {
-                let publish = WebRtcPublishEndpoint::from(&elem);
+                let publish = WebRtcPublishEndpoint::try_from(&elem)?;
                 Ok(Self::WebRtcPublish(publish))
             }
         }
@@ -100,7 +118,7 @@ impl TryFrom<(Id, proto::create_request::El)> for EndpointSpec {
                 Ok(Self::WebRtcPlay(play))
             }
             El::WebrtcPub(elem) => {
-                let publish = WebRtcPublishEndpoint::from(&elem);
+                let publish = WebRtcPublishEndpoint::try_from(&elem)?;
                 Ok(Self::WebRtcPublish(publish))
             }
             El::Member(_) | El::Room(_) => {
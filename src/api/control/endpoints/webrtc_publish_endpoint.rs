@@ -2,12 +2,19 @@
 //!
 //! [Control API]: https://tinyurl.com/yxsqplq7
 
+use std::convert::TryFrom;
+
 use derive_more::{Display, From, Into};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 
 use medea_control_api_proto::grpc::api as proto;
 
+use crate::api::control::{
+    callback::url::CallbackUrl, endpoints::parse_callback_url,
+    TryFromProtobufError,
+};
+
 /// ID of [`WebRtcPublishEndpoint`].
 #[derive(
     Clone, Debug, Deserialize, Display, Eq, Hash, PartialEq, From, Into,
@@ -15,7 +22,7 @@ use medea_control_api_proto::grpc::api as proto;
 pub struct WebRtcPublishId(String);
 
 /// Peer-to-peer mode of [`WebRtcPublishEndpoint`].
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum P2pMode {
     /// Always connect peer-to-peer.
     Always,
@@ -52,7 +59,7 @@ impl From<P2pMode> for proto::web_rtc_publish_endpoint::P2p {
 
 /// Media element which is able to publish media data for another client via
 /// WebRTC.
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug, Serialize)]
 pub struct WebRtcPublishEndpoint {
     /// Peer-to-peer mode of this [`WebRtcPublishEndpoint`].
     pub p2p: P2pMode,
@@ -68,11 +75,21 @@ pub struct WebRtcPublishEndpoint {
     /// Settings for the video media type of the [`WebRtcPublishEndpoint`].
     #[serde(default)]
     pub video_settings: VideoSettings,
+
+    /// URL to which `OnStart` Control API callback will be sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_start: Option<CallbackUrl>,
+
+    /// URL to which `OnStop` Control API callback will be sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_stop: Option<CallbackUrl>,
 }
 
 /// Publishing policy of the video or audio media type in the
 /// [`WebRtcPublishEndpoint`].
-#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, SmartDefault)]
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, SmartDefault,
+)]
 pub enum PublishPolicy {
     /// Specified media type __may__ be published.
     ///
@@ -133,12 +150,19 @@ impl From<PublishPolicy> for proto::web_rtc_publish_endpoint::PublishPolicy {
 }
 
 /// Settings for the audio media type of the [`WebRtcPublishEndpoint`].
-#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
 pub struct AudioSettings {
     /// Publishing policy of the audio media type in the
     /// [`WebRtcPublishEndpoint`].
     #[serde(default)]
     pub publish_policy: PublishPolicy,
+
+    /// [`OpusParameters`] to encode the audio of the [`WebRtcPublishEndpoint`]
+    /// with.
+    ///
+    /// [`None`] means the defaults of `Client`'s user agent.
+    #[serde(default)]
+    pub opus: Option<OpusParameters>,
 }
 
 impl From<&proto::web_rtc_publish_endpoint::AudioSettings> for AudioSettings {
@@ -150,6 +174,7 @@ impl From<&proto::web_rtc_publish_endpoint::AudioSettings> for AudioSettings {
                 )
                 .unwrap_or_default()
                 .into(),
+            opus: from.opus.as_ref().map(OpusParameters::from),
         }
     }
 }
@@ -160,28 +185,115 @@ impl From<AudioSettings> for proto::web_rtc_publish_endpoint::AudioSettings {
         use proto::web_rtc_publish_endpoint::PublishPolicy;
         Self {
             publish_policy: PublishPolicy::from(from.publish_policy).into(),
+            opus: from.opus.map(Into::into),
+        }
+    }
+}
+
+/// Parameters of the Opus codec negotiated for the audio of the
+/// [`WebRtcPublishEndpoint`].
+///
+/// See [RFC 7587] for details.
+///
+/// [RFC 7587]: https://tools.ietf.org/html/rfc7587#section-6.1
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize,
+)]
+pub struct OpusParameters {
+    /// Indicator whether discontinuous transmission (`usedtx`) should be used,
+    /// so nothing is sent during silence.
+    ///
+    /// Good for voice rooms, where most of the time only one `Member` talks.
+    #[serde(default)]
+    pub dtx: bool,
+
+    /// Indicator whether in-band forward error correction (`useinbandfec`)
+    /// should be used.
+    #[serde(default)]
+    pub inband_fec: bool,
+
+    /// Indicator whether stereo audio (`stereo`) should be encoded.
+    ///
+    /// Good for music rooms.
+    #[serde(default)]
+    pub stereo: bool,
+
+    /// Maximum average bitrate (`maxaveragebitrate`) of the encoded audio in
+    /// bits per second.
+    ///
+    /// [`None`] means the default of `Client`'s user agent.
+    #[serde(default)]
+    pub max_average_bitrate: Option<u32>,
+}
+
+impl From<&proto::web_rtc_publish_endpoint::OpusParameters> for OpusParameters {
+    #[inline]
+    fn from(from: &proto::web_rtc_publish_endpoint::OpusParameters) -> Self {
+        Self {
+            dtx: from.dtx,
+            inband_fec: from.inband_fec,
+            stereo: from.stereo,
+            max_average_bitrate: Some(from.max_average_bitrate)
+                .filter(|bitrate| *bitrate > 0),
+        }
+    }
+}
+
+impl From<OpusParameters> for proto::web_rtc_publish_endpoint::OpusParameters {
+    #[inline]
+    fn from(from: OpusParameters) -> Self {
+        Self {
+            dtx: from.dtx,
+            inband_fec: from.inband_fec,
+            stereo: from.stereo,
+            max_average_bitrate: from.max_average_bitrate.unwrap_or_default(),
+        }
+    }
+}
+
+impl From<OpusParameters> for medea_client_api_proto::OpusParameters {
+    #[inline]
+    fn from(from: OpusParameters) -> Self {
+        Self {
+            dtx: from.dtx,
+            inband_fec: from.inband_fec,
+            stereo: from.stereo,
+            max_average_bitrate: from.max_average_bitrate,
         }
     }
 }
 
 /// Settings for the video media type of the [`WebRtcPublishEndpoint`].
-#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
 pub struct VideoSettings {
     /// Publishing policy of the video media type in the
     /// [`WebRtcPublishEndpoint`].
+    ///
+    /// Applies to the video captured from a device (camera).
     #[serde(default)]
     pub publish_policy: PublishPolicy,
+
+    /// Publishing policy of the video captured from a display in the
+    /// [`WebRtcPublishEndpoint`].
+    ///
+    /// Display video is published alongside the device one, so it's never
+    /// published if the video media type is [`PublishPolicy::Disabled`].
+    #[serde(default)]
+    pub display_publish_policy: PublishPolicy,
 }
 
 impl From<&proto::web_rtc_publish_endpoint::VideoSettings> for VideoSettings {
     fn from(from: &proto::web_rtc_publish_endpoint::VideoSettings) -> Self {
+        use proto::web_rtc_publish_endpoint::PublishPolicy;
         Self {
-            publish_policy:
-                proto::web_rtc_publish_endpoint::PublishPolicy::from_i32(
-                    from.publish_policy,
-                )
+            publish_policy: PublishPolicy::from_i32(from.publish_policy)
                 .unwrap_or_default()
                 .into(),
+            display_publish_policy: PublishPolicy::from_i32(
+                from.display_publish_policy,
+            )
+            .unwrap_or_default()
+            .into(),
         }
     }
 }
@@ -192,13 +304,21 @@ impl From<VideoSettings> for proto::web_rtc_publish_endpoint::VideoSettings {
         use proto::web_rtc_publish_endpoint::PublishPolicy;
         Self {
             publish_policy: PublishPolicy::from(from.publish_policy).into(),
+            display_publish_policy: PublishPolicy::from(
+                from.display_publish_policy,
+            )
+            .into(),
         }
     }
 }
 
-impl From<&proto::WebRtcPublishEndpoint> for WebRtcPublishEndpoint {
-    fn from(value: &proto::WebRtcPublishEndpoint) -> Self {
-        Self {
+impl TryFrom<&proto::WebRtcPublishEndpoint> for WebRtcPublishEndpoint {
+    type Error = TryFromProtobufError;
+
+    fn try_from(
+        value: &proto::WebRtcPublishEndpoint,
+    ) -> Result<Self, Self::Error> {
+        Ok(Self {
             p2p: P2pMode::from(
                 proto::web_rtc_publish_endpoint::P2p::from_i32(value.p2p)
                     .unwrap_or_default(),
@@ -214,6 +334,8 @@ impl From<&proto::WebRtcPublishEndpoint> for WebRtcPublishEndpoint {
                 .map(VideoSettings::from)
                 .unwrap_or_default(),
             force_relay: value.force_relay,
-        }
+            on_start: parse_callback_url(&value.on_start)?,
+            on_stop: parse_callback_url(&value.on_stop)?,
+        })
     }
 }
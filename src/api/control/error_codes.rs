@@ -17,7 +17,7 @@ use crate::{
             fid::ParseFidError, local_uri::LocalUriParseError,
             src_uri::SrcParseError,
         },
-        TryFromElementError, TryFromProtobufError,
+        ParseYamlSpecError, TryFromElementError, TryFromProtobufError,
     },
     signalling::{
         elements::{member::MemberError, MembersLoadError},
@@ -288,6 +288,33 @@ pub enum ErrorCode {
     #[display(fmt = "Encountered negative duration")]
     NegativeDuration = 1023,
 
+    /// Provided spec is of an unsupported schema version.
+    ///
+    /// Code: __1024__.
+    #[display(fmt = "Unsupported spec version.")]
+    UnsupportedSpecVersion = 1024,
+
+    /// Provided YAML spec cannot be deserialized.
+    ///
+    /// Code: __1025__.
+    #[display(fmt = "Invalid YAML spec.")]
+    InvalidYamlSpec = 1025,
+
+    /// `Room` template with the provided ID is not found.
+    ///
+    /// Code: __1026__.
+    #[display(fmt = "Room template not found.")]
+    TemplateNotFound = 1026,
+
+    /// `Room` template cannot be instantiated with the provided parameters.
+    ///
+    /// This code should be with additional text which explains what exactly
+    /// is wrong.
+    ///
+    /// Code: __1027__.
+    #[display(fmt = "Failed to instantiate Room template.")]
+    TemplateInstantiationFailed = 1027,
+
     /// Unexpected server error.
     ///
     /// Use this [`ErrorCode`] only with [`ErrorResponse::unexpected`]
@@ -436,6 +463,7 @@ impl From<RoomError> for ErrorResponse {
             | E::BadRoomSpec(_)
             | E::PeerTrafficWatcherMailbox(_)
             | E::AuthorizationError
+            | E::Draining
             | E::TurnServiceErr(_) => Self::unexpected(&err),
         }
     }
@@ -446,12 +474,15 @@ impl From<MembersLoadError> for ErrorResponse {
         use MembersLoadError::{
             EndpointNotFound, MemberNotFound, TryFromError,
         };
-        use TryFromElementError::{NotMember, NotRoom};
+        use TryFromElementError::{NotMember, NotRoom, UnsupportedVersion};
 
         match err {
             TryFromError(e, id) => match e {
                 NotMember => Self::new(ErrorCode::NotMemberInSpec, &id),
                 NotRoom => Self::new(ErrorCode::NotRoomInSpec, &id),
+                UnsupportedVersion(_) => {
+                    Self::new(ErrorCode::UnsupportedSpecVersion, &id)
+                }
             },
             MemberNotFound(id) => Self::new(ErrorCode::MemberNotFound, &id),
             EndpointNotFound(id) => Self::new(ErrorCode::EndpointNotFound, &id),
@@ -459,6 +490,29 @@ impl From<MembersLoadError> for ErrorResponse {
     }
 }
 
+impl From<ParseYamlSpecError> for ErrorResponse {
+    fn from(err: ParseYamlSpecError) -> Self {
+        use TryFromElementError::{NotMember, NotRoom, UnsupportedVersion};
+
+        match err {
+            ParseYamlSpecError::Yaml(e) => Self::with_explanation(
+                ErrorCode::InvalidYamlSpec,
+                e.to_string(),
+                None,
+            ),
+            ParseYamlSpecError::TryFromElement(e) => match e {
+                NotRoom => Self::without_id(ErrorCode::NotRoomInSpec),
+                NotMember => Self::without_id(ErrorCode::NotMemberInSpec),
+                UnsupportedVersion(_) => Self::with_explanation(
+                    ErrorCode::UnsupportedSpecVersion,
+                    e.to_string(),
+                    None,
+                ),
+            },
+        }
+    }
+}
+
 impl From<MemberError> for ErrorResponse {
     fn from(err: MemberError) -> Self {
         match err {
@@ -500,6 +554,14 @@ impl From<RoomServiceError> for ErrorResponse {
                 ),
                 None,
             ),
+            E::TemplateNotFound(id) => {
+                Self::new(ErrorCode::TemplateNotFound, &id)
+            }
+            E::Template(e) => Self::with_explanation(
+                ErrorCode::TemplateInstantiationFailed,
+                e.to_string(),
+                None,
+            ),
             E::RoomMailboxErr(_)
             | E::FailedToLoadStaticSpecs(_)
             | E::TryFromElement(_) => Self::unexpected(&err),
@@ -0,0 +1,729 @@
+//! Audit log of [Control API] mutations.
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use std::{
+    cell::RefCell,
+    convert::TryInto as _,
+    error::Error as StdError,
+    mem,
+    net::SocketAddr,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use chrono::Local;
+use crypto::{digest::Digest as _, sha2::Sha256};
+use futures::future::BoxFuture;
+use hyper::{
+    body::{self, HttpBody},
+    header, Body, Client, HeaderMap, Request, Response, Uri,
+};
+use medea_control_api_proto::grpc::api as proto;
+use prost::Message as _;
+use serde::Serialize;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tonic::{body::BoxBody, transport::NamedService, Status};
+use tower::{Layer, Service};
+
+use crate::{conf::control::ControlApiAudit, log::prelude::*};
+
+/// Maximum number of [`AuditRecord`]s waiting to be sent to a webhook.
+///
+/// [`AuditRecord`]s exceeding it are dropped from the webhook (but still
+/// logged).
+const WEBHOOK_QUEUE_CAPACITY: usize = 1024;
+
+tokio::task_local! {
+    /// [`Principal`] of the [Control API] call being currently processed by
+    /// an [`Audited`] service.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    static PRINCIPAL: RefCell<Option<Principal>>;
+}
+
+/// Kind of an audited [Control API] mutation.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Operation {
+    /// `Create` method call.
+    Create,
+
+    /// `Delete` method call.
+    Delete,
+
+    /// `Apply` method call.
+    Apply,
+
+    /// `Batch` method call.
+    Batch,
+
+    /// `Drain` method call.
+    Drain,
+
+    /// `ImportSpec` method call.
+    Import,
+
+    /// `PauseRoom` method call.
+    Pause,
+
+    /// `ResumeRoom` method call.
+    Resume,
+}
+
+/// Caller of an audited [Control API] mutation.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Principal {
+    /// Remote address the call was made from.
+    pub remote_addr: Option<SocketAddr>,
+
+    /// Index of the configured API key the call was authenticated with.
+    pub api_key: Option<usize>,
+
+    /// SHA-256 fingerprint of the client certificate the call was
+    /// authenticated with.
+    pub client_cert: Option<String>,
+}
+
+impl Principal {
+    /// Returns a new [`Principal`] of the provided [`tonic::Request`],
+    /// authenticated with the API key by the provided index (if any).
+    #[must_use]
+    pub fn of<T>(request: &tonic::Request<T>, api_key: Option<usize>) -> Self {
+        Self {
+            remote_addr: request.remote_addr(),
+            api_key,
+            client_cert: request
+                .peer_certs()
+                .and_then(|certs| certs.first().map(|c| sha256(c.as_ref()))),
+        }
+    }
+
+    /// Makes this [`Principal`] the caller of the [Control API] call being
+    /// currently processed by an [`Audited`] service.
+    ///
+    /// Intended to be called from a [`tonic`] interceptor, as [`tonic`]
+    /// doesn't expose the caller's connection to outer services.
+    ///
+    /// # Panics
+    ///
+    /// If called outside of an [`Audited`] service.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub fn capture(self) {
+        PRINCIPAL.with(|p| *p.borrow_mut() = Some(self));
+    }
+}
+
+/// Single record of an [`AuditLog`].
+#[derive(Clone, Debug, Serialize)]
+pub struct AuditRecord {
+    /// Date and time of the mutation in [RFC 3339] format.
+    ///
+    /// [RFC 3339]: https://www.ietf.org/rfc/rfc3339.txt
+    pub time: String,
+
+    /// Caller of the mutation.
+    pub principal: Principal,
+
+    /// Kind of the mutation.
+    pub operation: Operation,
+
+    /// FIDs of the mutated elements.
+    pub elements: Vec<String>,
+
+    /// SHA-256 hash of the protobuf-encoded spec of the mutation.
+    pub spec_hash: Option<String>,
+
+    /// [`proto::Error`] the mutation resulted in, if any.
+    pub error: Option<AuditError>,
+}
+
+impl AuditRecord {
+    /// Returns a new [`AuditRecord`] of the provided mutation with the
+    /// provided protobuf-encoded `spec`.
+    #[must_use]
+    pub fn new(
+        principal: Principal,
+        operation: Operation,
+        elements: Vec<String>,
+        spec: Option<&[u8]>,
+        error: Option<&proto::Error>,
+    ) -> Self {
+        Self {
+            time: Local::now().to_rfc3339(),
+            principal,
+            operation,
+            elements,
+            spec_hash: spec.map(sha256),
+            error: error.map(|e| AuditError {
+                code: e.code,
+                text: e.text.clone(),
+            }),
+        }
+    }
+}
+
+/// Error of an audited [Control API] mutation.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Serialize)]
+pub struct AuditError {
+    /// Code of the [`proto::Error`].
+    pub code: u32,
+
+    /// Text description of the [`proto::Error`].
+    pub text: String,
+}
+
+/// Structured audit log of [Control API] mutations.
+///
+/// Writes [`AuditRecord`]s into the application log and, optionally, sends
+/// them to a webhook via HTTP `POST` request with a JSON body.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug)]
+pub struct AuditLog {
+    /// Indicator whether this [`AuditLog`] is enabled.
+    enabled: bool,
+
+    /// Queue of serialized [`AuditRecord`]s to be sent to a webhook.
+    webhook: Option<mpsc::Sender<String>>,
+}
+
+impl AuditLog {
+    /// Creates a new [`AuditLog`] out of the provided [`ControlApiAudit`]
+    /// settings, spawning a task sending [`AuditRecord`]s to the configured
+    /// webhook.
+    ///
+    /// Invalid webhook URL is logged and ignored.
+    ///
+    /// # Panics
+    ///
+    /// If called outside of [Tokio] runtime while a webhook is configured.
+    ///
+    /// [Tokio]: https://docs.rs/tokio
+    #[must_use]
+    pub fn new(conf: &ControlApiAudit) -> Self {
+        let webhook_url =
+            conf.webhook_url.as_ref().filter(|_| conf.enabled).and_then(
+                |url| {
+                    url.parse::<Uri>()
+                        .map_err(|e| {
+                            error!("Invalid audit webhook URL {}: {}", url, e)
+                        })
+                        .ok()
+                },
+            );
+        let webhook = webhook_url.map(|url| {
+            let (tx, rx) = mpsc::channel(WEBHOOK_QUEUE_CAPACITY);
+            tokio::spawn(send_to_webhook(url, rx));
+            tx
+        });
+        Self {
+            enabled: conf.enabled,
+            webhook,
+        }
+    }
+
+    /// Indicates whether this [`AuditLog`] is enabled.
+    #[inline]
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Records the provided [`AuditRecord`].
+    ///
+    /// Does nothing if this [`AuditLog`] is disabled.
+    pub fn record(&self, record: &AuditRecord) {
+        if !self.enabled {
+            return;
+        }
+        let json = match serde_json::to_string(record) {
+            Ok(json) => json,
+            Err(e) => {
+                error!("Failed to serialize AuditRecord: {}", e);
+                return;
+            }
+        };
+        info!("Control API audit"; "audit" => &json);
+
+        if let Some(webhook) = &self.webhook {
+            match webhook.try_send(json) {
+                Ok(_) => {}
+                Err(TrySendError::Full(_)) => warn!(
+                    "Audit webhook queue is full, AuditRecord is not sent",
+                ),
+                Err(TrySendError::Closed(_)) => {
+                    error!(
+                        "Audit webhook task is gone, AuditRecord is not sent"
+                    )
+                }
+            }
+        }
+    }
+}
+
+/// Sends the serialized [`AuditRecord`]s received from the provided queue to
+/// the webhook with the provided URL one by one, reusing a single [`Client`].
+async fn send_to_webhook(url: Uri, mut records: mpsc::Receiver<String>) {
+    let client = Client::new();
+    while let Some(json) = records.recv().await {
+        let req = Request::post(url.clone())
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(json))
+            .expect("AuditRecord webhook request is always valid");
+        match client.request(req).await {
+            Ok(resp) if resp.status().is_success() => {}
+            Ok(resp) => {
+                warn!("Audit webhook responded with status {}", resp.status())
+            }
+            Err(e) => warn!("Failed to send audit webhook: {}", e),
+        }
+    }
+}
+
+/// [`Layer`] recording the [Control API] mutations, passed through the
+/// services it wraps, into an [`AuditLog`].
+///
+/// [`Principal`]s of the mutations should be provided via
+/// [`Principal::capture()`].
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug)]
+pub struct AuditLayer(AuditLog);
+
+impl AuditLayer {
+    /// Creates a new [`AuditLayer`] recording into the provided [`AuditLog`].
+    #[inline]
+    #[must_use]
+    pub fn new(log: AuditLog) -> Self {
+        Self(log)
+    }
+}
+
+impl<S> Layer<S> for AuditLayer {
+    type Service = Audited<S>;
+
+    #[inline]
+    fn layer(&self, inner: S) -> Self::Service {
+        Audited {
+            inner,
+            log: self.0.clone(),
+        }
+    }
+}
+
+/// gRPC service recording the [Control API] mutations, passed through it, into
+/// an [`AuditLog`].
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug)]
+pub struct Audited<S> {
+    /// Wrapped gRPC service.
+    inner: S,
+
+    /// [`AuditLog`] to record mutations into.
+    log: AuditLog,
+}
+
+impl<S: NamedService> NamedService for Audited<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+impl<S> Service<Request<Body>> for Audited<S>
+where
+    S: Service<Request<Body>, Response = Response<BoxBody>>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+    S::Error: Into<Box<dyn StdError + Send + Sync>>,
+{
+    type Response = Response<BoxBody>;
+    type Error = Box<dyn StdError + Send + Sync>;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    #[inline]
+    fn poll_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        // Take the service being ready, leaving its clone instead.
+        let clone = self.inner.clone();
+        let mut inner = mem::replace(&mut self.inner, clone);
+        let log = self.log.clone();
+        let method = req
+            .uri()
+            .path()
+            .rsplit('/')
+            .next()
+            .and_then(AuditedMethod::parse)
+            .filter(|_| log.is_enabled());
+
+        Box::pin(PRINCIPAL.scope(RefCell::default(), async move {
+            let method = match method {
+                Some(method) => method,
+                None => return inner.call(req).await.map_err(Into::into),
+            };
+
+            let (parts, body) = req.into_parts();
+            let body = body::to_bytes(body).await?;
+            let request = grpc_message(&body).unwrap_or_default();
+            let elements = method.elements(request);
+            let spec = method.has_spec().then(|| request);
+
+            let resp = inner
+                .call(Request::from_parts(parts, Body::from(body.clone())))
+                .await
+                .map_err(Into::into)?;
+            let (parts, body) = resp.into_parts();
+            let body = BufferedBody::read(body).await;
+
+            // Calls failed with a gRPC status (unauthenticated, for example)
+            // don't reach the Control API implementation, so mutate nothing.
+            if let Some(message) = body.message() {
+                let principal = PRINCIPAL.with(|p| p.borrow_mut().take());
+                log.record(&AuditRecord::new(
+                    principal.unwrap_or_default(),
+                    method.operation(),
+                    elements,
+                    spec,
+                    method.error(message).as_ref(),
+                ));
+            }
+
+            Ok(Response::from_parts(parts, BoxBody::new(body)))
+        }))
+    }
+}
+
+/// [Control API] method recorded into an [`AuditLog`].
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum AuditedMethod {
+    /// `Create` method.
+    Create,
+
+    /// `Delete` method.
+    Delete,
+
+    /// `Apply` method.
+    Apply,
+
+    /// `Batch` method.
+    Batch,
+
+    /// `Drain` method.
+    Drain,
+
+    /// `ImportSpec` method.
+    ImportSpec,
+
+    /// `PauseRoom` method.
+    PauseRoom,
+
+    /// `ResumeRoom` method.
+    ResumeRoom,
+
+    /// `CreateFromTemplate` method.
+    CreateFromTemplate,
+}
+
+impl AuditedMethod {
+    /// Parses an [`AuditedMethod`] from the provided gRPC method name.
+    ///
+    /// Returns [`None`] if the method mutates nothing.
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "Create" => Self::Create,
+            "Delete" => Self::Delete,
+            "Apply" => Self::Apply,
+            "Batch" => Self::Batch,
+            "Drain" => Self::Drain,
+            "ImportSpec" => Self::ImportSpec,
+            "PauseRoom" => Self::PauseRoom,
+            "ResumeRoom" => Self::ResumeRoom,
+            "CreateFromTemplate" => Self::CreateFromTemplate,
+            _ => return None,
+        })
+    }
+
+    /// Returns [`Operation`] performed by this [`AuditedMethod`].
+    fn operation(self) -> Operation {
+        match self {
+            Self::Create | Self::CreateFromTemplate => Operation::Create,
+            Self::Delete => Operation::Delete,
+            Self::Apply => Operation::Apply,
+            Self::Batch => Operation::Batch,
+            Self::Drain => Operation::Drain,
+            Self::ImportSpec => Operation::Import,
+            Self::PauseRoom => Operation::Pause,
+            Self::ResumeRoom => Operation::Resume,
+        }
+    }
+
+    /// Indicates whether requests of this [`AuditedMethod`] contain a spec of
+    /// the mutation, rather than just FIDs of the mutated elements.
+    fn has_spec(self) -> bool {
+        !matches!(self, Self::Delete | Self::PauseRoom | Self::ResumeRoom)
+    }
+
+    /// Returns FIDs of the elements mutated by the provided protobuf-encoded
+    /// request of this [`AuditedMethod`].
+    fn elements(self, request: &[u8]) -> Vec<String> {
+        use proto::batch_operation::Op;
+
+        match self {
+            Self::Create => proto::CreateRequest::decode(request)
+                .map(|req| vec![created_fid(&req)])
+                .unwrap_or_default(),
+            Self::Delete | Self::PauseRoom | Self::ResumeRoom => {
+                proto::IdRequest::decode(request)
+                    .map(|req| req.fid)
+                    .unwrap_or_default()
+            }
+            Self::Apply => proto::ApplyRequest::decode(request)
+                .map(|req| vec![req.parent_fid])
+                .unwrap_or_default(),
+            Self::Batch => proto::BatchRequest::decode(request)
+                .map(|req| {
+                    req.operations
+                        .into_iter()
+                        .filter_map(|op| match op.op? {
+                            Op::Create(create) => Some(created_fid(&create)),
+                            Op::Delete(fid) => Some(fid),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default(),
+            Self::CreateFromTemplate => {
+                proto::CreateFromTemplateRequest::decode(request)
+                    .map(|req| vec![req.room_id])
+                    .unwrap_or_default()
+            }
+            Self::Drain | Self::ImportSpec => Vec::new(),
+        }
+    }
+
+    /// Returns [`proto::Error`] contained in the provided protobuf-encoded
+    /// response of this [`AuditedMethod`], if any.
+    fn error(self, response: &[u8]) -> Option<proto::Error> {
+        match self {
+            Self::Create
+            | Self::Apply
+            | Self::Batch
+            | Self::ImportSpec
+            | Self::CreateFromTemplate => {
+                proto::CreateResponse::decode(response).ok()?.error
+            }
+            Self::Delete | Self::PauseRoom | Self::ResumeRoom => {
+                proto::Response::decode(response).ok()?.error
+            }
+            Self::Drain => proto::DrainResponse::decode(response).ok()?.error,
+        }
+    }
+}
+
+/// Returns FID of the element created by the provided
+/// [`proto::CreateRequest`].
+fn created_fid(req: &proto::CreateRequest) -> String {
+    use proto::create_request::El;
+
+    let id = match &req.el {
+        Some(El::Room(room)) => &room.id,
+        Some(El::Member(member)) => &member.id,
+        Some(El::WebrtcPlay(play)) => &play.id,
+        Some(El::WebrtcPub(publish)) => &publish.id,
+        None => return req.parent_fid.clone(),
+    };
+    if req.parent_fid.is_empty() {
+        id.clone()
+    } else {
+        format!("{}/{}", req.parent_fid, id)
+    }
+}
+
+/// Returns protobuf-encoded message of the provided unary gRPC body, if it
+/// contains an uncompressed one.
+fn grpc_message(body: &[u8]) -> Option<&[u8]> {
+    let (&compressed, rest) = body.split_first()?;
+    if compressed != 0 || rest.len() < 4 {
+        return None;
+    }
+    let (len, rest) = rest.split_at(4);
+    let len = u32::from_be_bytes(len.try_into().ok()?) as usize;
+    rest.get(..len)
+}
+
+/// gRPC response body read into memory.
+#[derive(Debug)]
+struct BufferedBody {
+    /// Data of this [`BufferedBody`] not yet polled.
+    data: Option<Bytes>,
+
+    /// Error the original body failed with.
+    error: Option<Status>,
+
+    /// Trailers of this [`BufferedBody`] not yet polled.
+    trailers: Option<HeaderMap>,
+}
+
+impl BufferedBody {
+    /// Reads the provided [`BoxBody`] into a [`BufferedBody`].
+    async fn read(mut body: BoxBody) -> Self {
+        let mut data = Vec::new();
+        let mut error = None;
+        while let Some(chunk) = body.data().await {
+            match chunk {
+                Ok(chunk) => data.extend_from_slice(&chunk),
+                Err(e) => {
+                    error = Some(e);
+                    break;
+                }
+            }
+        }
+        let trailers = if error.is_none() {
+            body.trailers().await.unwrap_or_else(|e| {
+                error = Some(e);
+                None
+            })
+        } else {
+            None
+        };
+        Self {
+            data: Some(data.into()),
+            error,
+            trailers,
+        }
+    }
+
+    /// Returns protobuf-encoded message of this [`BufferedBody`], if it
+    /// contains any.
+    fn message(&self) -> Option<&[u8]> {
+        self.data.as_deref().and_then(grpc_message)
+    }
+}
+
+impl HttpBody for BufferedBody {
+    type Data = Bytes;
+    type Error = Status;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let data = self.data.take().filter(|d| !d.is_empty());
+        Poll::Ready(data.map(Ok).or_else(|| self.error.take().map(Err)))
+    }
+
+    fn poll_trailers(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        Poll::Ready(Ok(self.trailers.take()))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.data.is_none() && self.error.is_none() && self.trailers.is_none()
+    }
+}
+
+/// Returns hex-encoded SHA-256 hash of the provided bytes.
+fn sha256(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.input(bytes);
+    hasher.result_str()
+}
+
+#[cfg(test)]
+mod spec {
+    use prost::Message as _;
+
+    use super::*;
+
+    #[test]
+    fn serializes_record() {
+        let spec = proto::IdRequest {
+            fid: vec!["room".into()],
+        };
+        let mut encoded = Vec::new();
+        spec.encode(&mut encoded).unwrap();
+        let record = AuditRecord::new(
+            Principal {
+                remote_addr: Some("127.0.0.1:1234".parse().unwrap()),
+                api_key: Some(1),
+                client_cert: None,
+            },
+            Operation::Delete,
+            spec.fid.clone(),
+            Some(&encoded),
+            None,
+        );
+
+        let json: serde_json::Value = serde_json::to_value(&record).unwrap();
+        assert_eq!(json["operation"], "delete");
+        assert_eq!(json["elements"], serde_json::json!(["room"]));
+        assert_eq!(json["principal"]["api_key"], 1);
+        assert_eq!(json["principal"]["remote_addr"], "127.0.0.1:1234");
+        assert_eq!(json["spec_hash"].as_str().unwrap().len(), 64);
+        assert!(json["error"].is_null());
+    }
+
+    #[test]
+    fn records_created_elements() {
+        use proto::{batch_operation::Op, create_request::El};
+
+        let create = proto::CreateRequest {
+            parent_fid: "room".into(),
+            el: Some(El::Member(proto::Member {
+                id: "member".into(),
+                ..proto::Member::default()
+            })),
+            ..proto::CreateRequest::default()
+        };
+        let batch = proto::BatchRequest {
+            operations: vec![
+                proto::BatchOperation {
+                    op: Some(Op::Create(create.clone())),
+                },
+                proto::BatchOperation {
+                    op: Some(Op::Delete("room/other".into())),
+                },
+            ],
+        };
+
+        let mut encoded = Vec::new();
+        create.encode(&mut encoded).unwrap();
+        assert_eq!(
+            AuditedMethod::Create.elements(&encoded),
+            vec![String::from("room/member")],
+        );
+
+        let mut encoded = Vec::new();
+        batch.encode(&mut encoded).unwrap();
+        assert_eq!(
+            AuditedMethod::Batch.elements(&encoded),
+            vec![String::from("room/member"), String::from("room/other")],
+        );
+    }
+
+    #[test]
+    fn extracts_grpc_message() {
+        let body = [0, 0, 0, 0, 2, 7, 8, 9];
+        assert_eq!(grpc_message(&body), Some(&[7, 8][..]));
+        assert_eq!(grpc_message(&[1, 0, 0, 0, 0]), None);
+        assert_eq!(grpc_message(&[0, 0, 0, 0, 3, 7]), None);
+    }
+}
@@ -4,8 +4,10 @@
 
 use std::{
     collections::HashMap,
-    convert::{From, TryFrom},
+    convert::{From, TryFrom, TryInto as _},
+    io,
     net::SocketAddr,
+    sync::Arc,
 };
 
 use actix::{Actor, Addr, Arbiter, Context, Handler, MailboxError, System};
@@ -13,36 +15,53 @@ use async_trait::async_trait;
 use derive_more::{Display, From};
 use failure::Fail;
 use futures::channel::oneshot;
-use medea_client_api_proto::MemberId;
+use medea_client_api_proto::{MemberId, RoomId};
 use medea_control_api_proto::grpc::{
     api as proto,
     api::control_api_server::{
         ControlApi, ControlApiServer as TonicControlApiServer,
     },
 };
+use subtle::ConstantTimeEq as _;
 use tonic::{
-    transport::{self, Server},
+    metadata::MetadataMap,
+    transport::{self, Server, ServerTlsConfig},
     Status,
 };
+use tower::Layer as _;
 
 use crate::{
-    api::control::{
-        endpoints::{WebRtcPlayEndpoint, WebRtcPublishEndpoint},
-        error_codes::{
-            ErrorCode,
-            ErrorCode::{
-                ElementIdIsTooLong, ElementIdMismatch, UnimplementedCall,
+    api::{
+        control::{
+            callback::CallbackUrl,
+            endpoints::{WebRtcPlayEndpoint, WebRtcPublishEndpoint},
+            error_codes::{
+                ErrorCode,
+                ErrorCode::{
+                    ElementIdIsTooLong, ElementIdMismatch, RoomNotFound,
+                    UnimplementedCall,
+                },
+                ErrorResponse,
             },
-            ErrorResponse,
+            grpc::audit::{AuditLayer, AuditLog, Principal},
+            refs::{fid::ParseFidError, Fid, StatefulFid, ToMember, ToRoom},
+            EndpointId, EndpointSpec, MemberSpec, RoomSpec,
+            TryFromProtobufError,
         },
-        refs::{fid::ParseFidError, Fid, StatefulFid, ToMember, ToRoom},
-        EndpointId, EndpointSpec, MemberSpec, RoomSpec, TryFromProtobufError,
+        tls,
     },
     log::prelude::*,
     shutdown::ShutdownGracefully,
-    signalling::room_service::{
-        ApplyMember, ApplyRoom, CreateEndpointInRoom, CreateMemberInRoom,
-        CreateRoom, DeleteElements, Get, RoomService, RoomServiceError, Sids,
+    signalling::{
+        room::{BatchOperation, CreateEndpoint},
+        room_service::{
+            ApplyMember, ApplyRoom, BatchInRoom, CreateEndpointInRoom,
+            CreateMemberInRoom, CreateRoom, CreateRoomAsync,
+            CreateRoomFromTemplate, DeleteElements, DumpRoom, Get,
+            GetMemberClientCapabilities, GetMemberQualityHistory,
+            GetRoomJournal, GetRoomsUsage, PatchTrack, RoomService,
+            RoomServiceError, SetRoomsPaused, Sids, StartDrain,
+        },
     },
     AppContext,
 };
@@ -71,7 +90,13 @@ pub enum GrpcControlApiError {
 }
 
 /// Service which provides gRPC [Control API] implementation.
-struct ControlApiService(Addr<RoomService>);
+struct ControlApiService {
+    /// [`RoomService`] to propagate requests to.
+    room_service: Addr<RoomService>,
+
+    /// API keys, which admin-only calls are authorized with.
+    admin_api_keys: Arc<Vec<String>>,
+}
 
 impl ControlApiService {
     /// Implementation of `Create` method for [`Room`].
@@ -81,7 +106,25 @@ impl ControlApiService {
         &self,
         spec: RoomSpec,
     ) -> Result<Sids, GrpcControlApiError> {
-        Ok(self.0.send(CreateRoom { spec }).await??)
+        Ok(self.room_service.send(CreateRoom { spec }).await??)
+    }
+
+    /// Implementation of `Create` method for [`Room`] with the provided
+    /// `on_progress` [`CallbackUrl`].
+    ///
+    /// Returns [`Sids`] along with an ID of the creation operation, without
+    /// waiting for `Member`s to be provisioned.
+    ///
+    /// [`Room`]: crate::signalling::room::Room
+    async fn create_room_async(
+        &self,
+        spec: RoomSpec,
+        on_progress: CallbackUrl,
+    ) -> Result<(Sids, String), GrpcControlApiError> {
+        Ok(self
+            .room_service
+            .send(CreateRoomAsync { spec, on_progress })
+            .await??)
     }
 
     /// Implementation of `Create` method for [`Member`] element.
@@ -94,7 +137,7 @@ impl ControlApiService {
         spec: MemberSpec,
     ) -> Result<Sids, GrpcControlApiError> {
         Ok(self
-            .0
+            .room_service
             .send(CreateMemberInRoom {
                 id,
                 parent_fid,
@@ -111,7 +154,7 @@ impl ControlApiService {
         spec: EndpointSpec,
     ) -> Result<Sids, GrpcControlApiError> {
         Ok(self
-            .0
+            .room_service
             .send(CreateEndpointInRoom {
                 id,
                 parent_fid,
@@ -140,7 +183,7 @@ impl ControlApiService {
         match parent_fid {
             StatefulFid::Room(fid) => match elem {
                 proto::apply_request::El::Room(_) => Ok(self
-                    .0
+                    .room_service
                     .send(ApplyRoom {
                         id: fid.take_room_id(),
                         spec: RoomSpec::try_from(elem)?,
@@ -151,7 +194,7 @@ impl ControlApiService {
             },
             StatefulFid::Member(fid) => match elem {
                 proto::apply_request::El::Member(member) => Ok(self
-                    .0
+                    .room_service
                     .send(ApplyMember {
                         fid,
                         spec: MemberSpec::try_from(member)?,
@@ -172,10 +215,13 @@ impl ControlApiService {
     }
 
     /// Creates element based on provided [`proto::CreateRequest`].
+    ///
+    /// Returns [`Sids`] along with an ID of the asynchronous creation
+    /// operation, which is empty if the element is created synchronously.
     async fn create_element(
         &self,
         req: proto::CreateRequest,
-    ) -> Result<Sids, ErrorResponse> {
+    ) -> Result<(Sids, String), ErrorResponse> {
         let unparsed_parent_fid = req.parent_fid;
         let elem = if let Some(elem) = req.el {
             elem
@@ -187,7 +233,13 @@ impl ControlApiService {
         };
 
         if unparsed_parent_fid.is_empty() {
-            return Ok(self.create_room(RoomSpec::try_from(elem)?).await?);
+            let spec = RoomSpec::try_from(elem)?;
+            if req.on_progress.is_empty() {
+                return Ok((self.create_room(spec).await?, String::new()));
+            }
+            let on_progress = CallbackUrl::try_from(req.on_progress)
+                .map_err(TryFromProtobufError::from)?;
+            return Ok(self.create_room_async(spec, on_progress).await?);
         }
 
         let parent_fid = StatefulFid::try_from(unparsed_parent_fid)?;
@@ -196,7 +248,9 @@ impl ControlApiService {
                 proto::create_request::El::Member(member) => {
                     let id: MemberId = member.id.clone().into();
                     let member_spec = MemberSpec::try_from(member)?;
-                    Ok(self.create_member(id, parent_fid, member_spec).await?)
+                    let sids =
+                        self.create_member(id, parent_fid, member_spec).await?;
+                    Ok((sids, String::new()))
                 }
                 _ => Err(ErrorResponse::new(ElementIdMismatch, &parent_fid)),
             },
@@ -209,9 +263,9 @@ impl ControlApiService {
                         play.id.into(),
                     ),
                     proto::create_request::El::WebrtcPub(publish) => (
-                        EndpointSpec::from(WebRtcPublishEndpoint::from(
+                        EndpointSpec::from(WebRtcPublishEndpoint::try_from(
                             &publish,
-                        )),
+                        )?),
                         publish.id.into(),
                     ),
                     _ => {
@@ -222,7 +276,9 @@ impl ControlApiService {
                     }
                 };
 
-                Ok(self.create_endpoint(id, parent_fid, endpoint_spec).await?)
+                let sids =
+                    self.create_endpoint(id, parent_fid, endpoint_spec).await?;
+                Ok((sids, String::new()))
             }
             StatefulFid::Endpoint(_) => {
                 Err(ErrorResponse::new(ElementIdIsTooLong, &parent_fid))
@@ -240,10 +296,47 @@ impl ControlApiService {
             let fid = StatefulFid::try_from(id)?;
             delete_elements_msg.add_fid(fid);
         }
-        self.0.send(delete_elements_msg.validate()?).await??;
+        self.room_service
+            .send(delete_elements_msg.validate()?)
+            .await??;
         Ok(())
     }
 
+    /// Parses the provided [`proto::BatchRequest`] and sends [`BatchInRoom`]
+    /// message to [`RoomService`].
+    async fn apply_batch(
+        &self,
+        req: proto::BatchRequest,
+    ) -> Result<Sids, ErrorResponse> {
+        let mut room_id: Option<RoomId> = None;
+        let mut operations = Vec::with_capacity(req.operations.len());
+        for op in req.operations {
+            let (op_room_id, op) = batch_operation(op)?;
+            match &room_id {
+                Some(id) if *id != op_room_id => {
+                    return Err(RoomServiceError::NotSameRoomIds(
+                        id.clone(),
+                        op_room_id,
+                    )
+                    .into());
+                }
+                Some(_) => (),
+                None => room_id = Some(op_room_id),
+            }
+            operations.push(op);
+        }
+        let room_id = room_id.ok_or(RoomServiceError::EmptyUrisList)?;
+
+        Ok(self
+            .room_service
+            .send(BatchInRoom {
+                room_id,
+                operations,
+            })
+            .await
+            .map_err(GrpcControlApiError::RoomServiceMailboxError)??)
+    }
+
     /// Returns requested by [`proto::IdRequest`] [`proto::Element`]s serialized
     /// to protobuf.
     async fn get_element(
@@ -256,13 +349,347 @@ impl ControlApiService {
             fids.push(fid);
         }
 
-        let elements = self.0.send(Get(fids)).await??;
+        let elements = self.room_service.send(Get(fids)).await??;
 
         Ok(elements
             .into_iter()
             .map(|(id, value)| (id.to_string(), value))
             .collect())
     }
+    /// Returns events of the [`Room`]'s journal requested by
+    /// [`proto::RoomJournalRequest`].
+    ///
+    /// [`Room`]: crate::signalling::Room
+    async fn get_journal(
+        &self,
+        req: proto::RoomJournalRequest,
+    ) -> Result<proto::RoomJournalResponse, ErrorResponse> {
+        let room_fid = match StatefulFid::try_from(req.room_fid)? {
+            StatefulFid::Room(fid) => fid,
+            fid => return Err(ErrorResponse::new(ElementIdIsTooLong, &fid)),
+        };
+
+        Ok(self
+            .room_service
+            .send(GetRoomJournal {
+                room_fid,
+                since: req.since,
+            })
+            .await
+            .map_err(GrpcControlApiError::RoomServiceMailboxError)??)
+    }
+
+    /// Returns recent connection quality scores of the `Member` requested by
+    /// [`proto::QualityHistoryRequest`] with each of its partners.
+    async fn quality_history(
+        &self,
+        req: proto::QualityHistoryRequest,
+    ) -> Result<proto::QualityHistoryResponse, ErrorResponse> {
+        let member_fid = match StatefulFid::try_from(req.member_fid)? {
+            StatefulFid::Member(fid) => fid,
+            fid => return Err(ErrorResponse::new(ElementIdMismatch, &fid)),
+        };
+
+        Ok(self
+            .room_service
+            .send(GetMemberQualityHistory { member_fid })
+            .await
+            .map_err(GrpcControlApiError::RoomServiceMailboxError)??)
+    }
+
+    /// Returns capabilities reported by the `Client` of the `Member` requested
+    /// by [`proto::ClientCapabilitiesRequest`].
+    async fn client_capabilities(
+        &self,
+        req: proto::ClientCapabilitiesRequest,
+    ) -> Result<proto::ClientCapabilitiesResponse, ErrorResponse> {
+        let member_fid = match StatefulFid::try_from(req.member_fid)? {
+            StatefulFid::Member(fid) => fid,
+            fid => return Err(ErrorResponse::new(ElementIdMismatch, &fid)),
+        };
+
+        Ok(self
+            .room_service
+            .send(GetMemberClientCapabilities { member_fid })
+            .await
+            .map_err(GrpcControlApiError::RoomServiceMailboxError)??)
+    }
+
+    /// Forcibly patches the tracks of the `Endpoint` requested by
+    /// [`proto::PatchTrackRequest`].
+    ///
+    /// Returns ID of the started operation if `on_applied` [`CallbackUrl`] is
+    /// provided, or an empty [`String`] otherwise.
+    async fn patch_endpoint_tracks(
+        &self,
+        req: proto::PatchTrackRequest,
+    ) -> Result<String, ErrorResponse> {
+        use proto::patch_track_request::{Switch, TrackKind};
+
+        /// Converts the provided [`Switch`] into a new value of a track
+        /// property, if it should be changed.
+        fn switch(value: i32) -> Option<bool> {
+            match Switch::from_i32(value).unwrap_or(Switch::Keep) {
+                Switch::Keep => None,
+                Switch::On => Some(true),
+                Switch::Off => Some(false),
+            }
+        }
+
+        let endpoint_fid = match StatefulFid::try_from(req.endpoint_fid)? {
+            StatefulFid::Endpoint(fid) => fid,
+            fid => return Err(ErrorResponse::new(ElementIdMismatch, &fid)),
+        };
+        let on_applied = if req.on_applied.is_empty() {
+            None
+        } else {
+            Some(
+                CallbackUrl::try_from(req.on_applied)
+                    .map_err(TryFromProtobufError::from)?,
+            )
+        };
+
+        let operation_id = self
+            .room_service
+            .send(PatchTrack {
+                endpoint_fid,
+                kind: TrackKind::from_i32(req.kind).unwrap_or(TrackKind::All),
+                enabled: switch(req.enabled),
+                muted: switch(req.muted),
+                on_applied,
+            })
+            .await
+            .map_err(GrpcControlApiError::RoomServiceMailboxError)??;
+        Ok(operation_id.unwrap_or_default())
+    }
+
+    /// Returns JSON [`RoomSnapshot`] of the live state of the [`Room`]
+    /// requested by [`proto::DumpRoomRequest`].
+    ///
+    /// [`Room`]: crate::signalling::room::Room
+    /// [`RoomSnapshot`]: crate::signalling::room::RoomSnapshot
+    async fn dump_room_state(
+        &self,
+        req: proto::DumpRoomRequest,
+    ) -> Result<String, ErrorResponse> {
+        let room_fid = match StatefulFid::try_from(req.room_fid)? {
+            StatefulFid::Room(fid) => fid,
+            fid => return Err(ErrorResponse::new(ElementIdIsTooLong, &fid)),
+        };
+
+        let snapshot = self
+            .room_service
+            .send(DumpRoom(room_fid))
+            .await
+            .map_err(GrpcControlApiError::RoomServiceMailboxError)??;
+        serde_json::to_string_pretty(&snapshot)
+            .map_err(|e| ErrorResponse::unexpected(&e))
+    }
+
+    /// Returns approximate amounts of entities kept in memory by the [`Room`]s
+    /// requested by [`proto::RoomsUsageRequest`].
+    ///
+    /// [`Room`]: crate::signalling::room::Room
+    async fn rooms_usage(
+        &self,
+        req: proto::RoomsUsageRequest,
+    ) -> Result<HashMap<String, proto::RoomUsage>, ErrorResponse> {
+        let mut room_fids = Vec::with_capacity(req.room_fids.len());
+        for fid in req.room_fids {
+            match StatefulFid::try_from(fid)? {
+                StatefulFid::Room(fid) => room_fids.push(fid),
+                fid => {
+                    return Err(ErrorResponse::new(ElementIdIsTooLong, &fid))
+                }
+            }
+        }
+
+        let usage = self
+            .room_service
+            .send(GetRoomsUsage(room_fids))
+            .await
+            .map_err(GrpcControlApiError::RoomServiceMailboxError)??;
+
+        Ok(usage
+            .into_iter()
+            .map(|(id, usage)| (id.to_string(), usage))
+            .collect())
+    }
+
+    /// Returns YAML spec of the [`Room`] requested by
+    /// [`proto::ExportSpecRequest`].
+    ///
+    /// [`Room`]: crate::signalling::room::Room
+    async fn export_room_spec(
+        &self,
+        req: proto::ExportSpecRequest,
+    ) -> Result<String, ErrorResponse> {
+        let room_fid = StatefulFid::try_from(req.room_fid)?;
+        if !matches!(room_fid, StatefulFid::Room(_)) {
+            return Err(ErrorResponse::new(ElementIdIsTooLong, &room_fid));
+        }
+
+        let room = self
+            .room_service
+            .send(Get(vec![room_fid.clone()]))
+            .await
+            .map_err(GrpcControlApiError::RoomServiceMailboxError)??
+            .into_iter()
+            .find_map(|(_, el)| el.el)
+            .ok_or_else(|| ErrorResponse::new(RoomNotFound, &room_fid))?;
+        RoomSpec::try_from(room)?
+            .to_yaml()
+            .map_err(|e| ErrorResponse::unexpected(&e))
+    }
+
+    /// Parses YAML spec of a [`Room`] provided in [`proto::ImportSpecRequest`]
+    /// and sends [`ApplyRoom`] message to [`RoomService`].
+    ///
+    /// [`Room`]: crate::signalling::room::Room
+    async fn import_room_spec(
+        &self,
+        req: proto::ImportSpecRequest,
+    ) -> Result<Sids, ErrorResponse> {
+        let spec = RoomSpec::from_yaml(&req.spec)?;
+        Ok(self
+            .room_service
+            .send(ApplyRoom {
+                id: spec.id().clone(),
+                spec,
+            })
+            .await
+            .map_err(GrpcControlApiError::from)??)
+    }
+
+    /// Sends [`CreateRoomFromTemplate`] message to [`RoomService`] with the
+    /// parameters provided in [`proto::CreateFromTemplateRequest`].
+    async fn create_room_from_template(
+        &self,
+        req: proto::CreateFromTemplateRequest,
+    ) -> Result<Sids, ErrorResponse> {
+        let room_fid = StatefulFid::try_from(req.room_id)?;
+        let id = match room_fid {
+            StatefulFid::Room(fid) => fid.take_room_id(),
+            fid => return Err(ErrorResponse::new(ElementIdIsTooLong, &fid)),
+        };
+        Ok(self
+            .room_service
+            .send(CreateRoomFromTemplate {
+                template: req.template_id,
+                id,
+                params: req.params,
+            })
+            .await
+            .map_err(GrpcControlApiError::from)??)
+    }
+
+    /// Pauses/resumes media exchange in the [`Room`]s requested by
+    /// [`proto::IdRequest`].
+    ///
+    /// [`Room`]: crate::signalling::room::Room
+    async fn set_rooms_paused(
+        &self,
+        req: proto::IdRequest,
+        paused: bool,
+    ) -> Result<(), ErrorResponse> {
+        let mut room_fids = Vec::with_capacity(req.fid.len());
+        for fid in req.fid {
+            match StatefulFid::try_from(fid)? {
+                StatefulFid::Room(fid) => room_fids.push(fid),
+                fid => {
+                    return Err(ErrorResponse::new(ElementIdIsTooLong, &fid))
+                }
+            }
+        }
+
+        Ok(self
+            .room_service
+            .send(SetRoomsPaused { room_fids, paused })
+            .await
+            .map_err(GrpcControlApiError::RoomServiceMailboxError)??)
+    }
+
+    /// Switches the drain mode on, returning its current progress.
+    ///
+    /// Initiates graceful shutdown once draining is completed, if `shutdown`
+    /// is `true`.
+    async fn start_drain(
+        &self,
+        shutdown: bool,
+    ) -> Result<proto::DrainResponse, ErrorResponse> {
+        let progress =
+            self.room_service
+                .send(StartDrain { shutdown })
+                .await
+                .map_err(GrpcControlApiError::RoomServiceMailboxError)??;
+        Ok(proto::DrainResponse {
+            rooms: progress.rooms.try_into().unwrap_or(u32::MAX),
+            members: progress.members.try_into().unwrap_or(u32::MAX),
+            error: None,
+        })
+    }
+}
+
+/// Parses the provided [`proto::BatchOperation`] into a [`BatchOperation`]
+/// along with the [`RoomId`] of the [`Room`] it should be applied to.
+///
+/// [`Room`]: crate::signalling::room::Room
+fn batch_operation(
+    op: proto::BatchOperation,
+) -> Result<(RoomId, BatchOperation), ErrorResponse> {
+    use proto::{batch_operation::Op, create_request::El};
+
+    let req = match op.op {
+        Some(Op::Create(req)) => req,
+        Some(Op::Delete(fid)) => {
+            let fid = StatefulFid::try_from(fid)?;
+            return Ok((fid.room_id().clone(), BatchOperation::Delete(fid)));
+        }
+        None => return Err(ErrorResponse::without_id(ErrorCode::NoElement)),
+    };
+    let elem = if let Some(elem) = req.el {
+        elem
+    } else {
+        return Err(ErrorResponse::new(ErrorCode::NoElement, &req.parent_fid));
+    };
+
+    match StatefulFid::try_from(req.parent_fid)? {
+        StatefulFid::Room(fid) => match elem {
+            El::Member(member) => {
+                let id: MemberId = member.id.clone().into();
+                let spec = MemberSpec::try_from(member)?;
+                Ok((fid.take_room_id(), BatchOperation::CreateMember(id, spec)))
+            }
+            _ => Err(ErrorResponse::new(ElementIdMismatch, &fid)),
+        },
+        StatefulFid::Member(fid) => {
+            let (spec, endpoint_id) = match elem {
+                El::WebrtcPlay(play) => (
+                    EndpointSpec::from(WebRtcPlayEndpoint::try_from(&play)?),
+                    play.id.into(),
+                ),
+                El::WebrtcPub(publish) => (
+                    EndpointSpec::from(WebRtcPublishEndpoint::try_from(
+                        &publish,
+                    )?),
+                    publish.id.into(),
+                ),
+                _ => return Err(ErrorResponse::new(ElementIdMismatch, &fid)),
+            };
+            let (room_id, member_id) = fid.take_all();
+            Ok((
+                room_id,
+                BatchOperation::CreateEndpoint(CreateEndpoint {
+                    member_id,
+                    endpoint_id,
+                    spec,
+                }),
+            ))
+        }
+        fid @ StatefulFid::Endpoint(_) => {
+            Err(ErrorResponse::new(ElementIdIsTooLong, &fid))
+        }
+    }
 }
 
 /// Converts [`Sids`] to a [`HashMap`] of [`String`]s for gRPC Control API
@@ -290,13 +717,15 @@ impl ControlApi for ControlApiService {
         debug!("Create gRPC Request: [{:?}]", request);
         let create_response =
             match self.create_element(request.into_inner()).await {
-                Ok(sid) => proto::CreateResponse {
+                Ok((sid, operation_id)) => proto::CreateResponse {
                     sid: proto_sids(sid),
                     error: None,
+                    operation_id,
                 },
                 Err(e) => proto::CreateResponse {
                     sid: HashMap::new(),
                     error: Some(e.into()),
+                    operation_id: String::new(),
                 },
             };
         Ok(tonic::Response::new(create_response))
@@ -368,14 +797,318 @@ impl ControlApi for ControlApiService {
             Ok(sid) => proto::CreateResponse {
                 sid: proto_sids(sid),
                 error: None,
+                operation_id: String::new(),
             },
             Err(e) => proto::CreateResponse {
                 sid: HashMap::new(),
                 error: Some(e.into()),
+                operation_id: String::new(),
             },
         };
         Ok(tonic::Response::new(response))
     }
+
+    /// Applies the given create/delete operations to [`Element`]s of a single
+    /// [`Room`] transactionally.
+    ///
+    /// Operations are applied in the given order. If any of them fails, then
+    /// none of them is applied.
+    ///
+    /// Propagates request to [`ControlApiService::apply_batch`].
+    ///
+    /// [`Element`]: proto::Element
+    /// [`Room`]: proto::Room
+    async fn batch(
+        &self,
+        request: tonic::Request<proto::BatchRequest>,
+    ) -> Result<tonic::Response<proto::CreateResponse>, Status> {
+        debug!("Batch gRPC Request: [{:?}]", request);
+        let response = match self.apply_batch(request.into_inner()).await {
+            Ok(sid) => proto::CreateResponse {
+                sid: proto_sids(sid),
+                error: None,
+                operation_id: String::new(),
+            },
+            Err(e) => proto::CreateResponse {
+                sid: HashMap::new(),
+                error: Some(e.into()),
+                operation_id: String::new(),
+            },
+        };
+        Ok(tonic::Response::new(response))
+    }
+
+    /// Returns events of a [`Room`]'s journal, which occurred after the
+    /// provided sequence number.
+    ///
+    /// Propagates request to [`ControlApiService::get_journal`].
+    ///
+    /// [`Room`]: proto::Room
+    async fn get_room_journal(
+        &self,
+        request: tonic::Request<proto::RoomJournalRequest>,
+    ) -> Result<tonic::Response<proto::RoomJournalResponse>, Status> {
+        debug!("GetRoomJournal gRPC Request: [{:?}]", request);
+        let response = self
+            .get_journal(request.into_inner())
+            .await
+            .unwrap_or_else(|e| proto::RoomJournalResponse {
+                events: Vec::new(),
+                oldest_seq: 0,
+                error: Some(e.into()),
+            });
+        Ok(tonic::Response::new(response))
+    }
+
+    /// Puts [Medea] into drain mode, so no new `Member`s are allowed to join
+    /// its [`Room`]s, and returns the current drain progress.
+    ///
+    /// [Medea]: https://github.com/instrumentisto/medea
+    /// [`Room`]: crate::signalling::room::Room
+    async fn drain(
+        &self,
+        request: tonic::Request<proto::DrainRequest>,
+    ) -> Result<tonic::Response<proto::DrainResponse>, Status> {
+        debug!("Drain gRPC Request: [{:?}]", request);
+        let response = self
+            .start_drain(request.into_inner().shutdown)
+            .await
+            .unwrap_or_else(|e| proto::DrainResponse {
+                rooms: 0,
+                members: 0,
+                error: Some(e.into()),
+            });
+        Ok(tonic::Response::new(response))
+    }
+
+    /// Pauses media exchange in [`Room`]s, keeping their `Member`s' sessions
+    /// and `Peer`s alive.
+    ///
+    /// Propagates request to [`ControlApiService::set_rooms_paused`].
+    ///
+    /// [`Room`]: proto::Room
+    async fn pause_room(
+        &self,
+        request: tonic::Request<proto::IdRequest>,
+    ) -> Result<tonic::Response<proto::Response>, Status> {
+        debug!("PauseRoom gRPC Request: [{:?}]", request);
+        let response =
+            match self.set_rooms_paused(request.into_inner(), true).await {
+                Ok(_) => proto::Response { error: None },
+                Err(e) => proto::Response {
+                    error: Some(e.into()),
+                },
+            };
+        Ok(tonic::Response::new(response))
+    }
+
+    /// Resumes media exchange in [`Room`]s, previously paused with
+    /// [`ControlApi::pause_room`].
+    ///
+    /// Propagates request to [`ControlApiService::set_rooms_paused`].
+    ///
+    /// [`Room`]: proto::Room
+    async fn resume_room(
+        &self,
+        request: tonic::Request<proto::IdRequest>,
+    ) -> Result<tonic::Response<proto::Response>, Status> {
+        debug!("ResumeRoom gRPC Request: [{:?}]", request);
+        let response =
+            match self.set_rooms_paused(request.into_inner(), false).await {
+                Ok(_) => proto::Response { error: None },
+                Err(e) => proto::Response {
+                    error: Some(e.into()),
+                },
+            };
+        Ok(tonic::Response::new(response))
+    }
+
+    /// Returns connection quality history of a `Member` with each of its
+    /// partners.
+    ///
+    /// Propagates request to [`ControlApiService::quality_history`].
+    async fn get_quality_history(
+        &self,
+        request: tonic::Request<proto::QualityHistoryRequest>,
+    ) -> Result<tonic::Response<proto::QualityHistoryResponse>, Status> {
+        debug!("GetQualityHistory gRPC Request: [{:?}]", request);
+        let response = self
+            .quality_history(request.into_inner())
+            .await
+            .unwrap_or_else(|e| proto::QualityHistoryResponse {
+                histories: Vec::new(),
+                error: Some(e.into()),
+            });
+        Ok(tonic::Response::new(response))
+    }
+
+    /// Returns capabilities reported by the `Client` of a `Member` when it
+    /// joined its `Room`.
+    ///
+    /// Propagates request to [`ControlApiService::client_capabilities`].
+    async fn get_client_capabilities(
+        &self,
+        request: tonic::Request<proto::ClientCapabilitiesRequest>,
+    ) -> Result<tonic::Response<proto::ClientCapabilitiesResponse>, Status>
+    {
+        debug!("GetClientCapabilities gRPC Request: [{:?}]", request);
+        let response = self
+            .client_capabilities(request.into_inner())
+            .await
+            .unwrap_or_else(|e| proto::ClientCapabilitiesResponse {
+                capabilities: None,
+                error: Some(e.into()),
+            });
+        Ok(tonic::Response::new(response))
+    }
+
+    /// Forcibly updates the tracks of an `Endpoint`, regardless of its
+    /// `Client`'s will.
+    ///
+    /// Propagates request to [`ControlApiService::patch_endpoint_tracks`].
+    async fn patch_track(
+        &self,
+        request: tonic::Request<proto::PatchTrackRequest>,
+    ) -> Result<tonic::Response<proto::PatchTrackResponse>, Status> {
+        debug!("PatchTrack gRPC Request: [{:?}]", request);
+        let response =
+            match self.patch_endpoint_tracks(request.into_inner()).await {
+                Ok(operation_id) => proto::PatchTrackResponse {
+                    error: None,
+                    operation_id,
+                },
+                Err(e) => proto::PatchTrackResponse {
+                    error: Some(e.into()),
+                    operation_id: String::new(),
+                },
+            };
+        Ok(tonic::Response::new(response))
+    }
+
+    /// Dumps the complete live state of a [`Room`] as JSON.
+    ///
+    /// Admin-only, so fails with [`Status::permission_denied`] unless one of
+    /// the admin API keys is provided.
+    ///
+    /// Propagates request to [`ControlApiService::dump_room_state`].
+    ///
+    /// [`Room`]: proto::Room
+    async fn dump_room(
+        &self,
+        request: tonic::Request<proto::DumpRoomRequest>,
+    ) -> Result<tonic::Response<proto::DumpRoomResponse>, Status> {
+        debug!("DumpRoom gRPC Request: [{:?}]", request);
+        if find_api_key(&self.admin_api_keys, request.metadata()).is_none() {
+            return Err(Status::permission_denied(
+                "DumpRoom requires an admin API key",
+            ));
+        }
+
+        let response = match self.dump_room_state(request.into_inner()).await {
+            Ok(snapshot) => proto::DumpRoomResponse {
+                snapshot,
+                error: None,
+            },
+            Err(e) => proto::DumpRoomResponse {
+                snapshot: String::new(),
+                error: Some(e.into()),
+            },
+        };
+        Ok(tonic::Response::new(response))
+    }
+
+    /// Returns approximate amounts of entities kept in memory by [`Room`]s.
+    ///
+    /// Propagates request to [`ControlApiService::rooms_usage`].
+    ///
+    /// [`Room`]: proto::Room
+    async fn get_rooms_usage(
+        &self,
+        request: tonic::Request<proto::RoomsUsageRequest>,
+    ) -> Result<tonic::Response<proto::RoomsUsageResponse>, Status> {
+        debug!("GetRoomsUsage gRPC Request: [{:?}]", request);
+        let response = match self.rooms_usage(request.into_inner()).await {
+            Ok(rooms) => proto::RoomsUsageResponse { rooms, error: None },
+            Err(e) => proto::RoomsUsageResponse {
+                rooms: HashMap::new(),
+                error: Some(e.into()),
+            },
+        };
+        Ok(tonic::Response::new(response))
+    }
+
+    /// Exports spec of a [`Room`] as YAML document.
+    ///
+    /// Propagates request to [`ControlApiService::export_room_spec`].
+    ///
+    /// [`Room`]: proto::Room
+    async fn export_spec(
+        &self,
+        request: tonic::Request<proto::ExportSpecRequest>,
+    ) -> Result<tonic::Response<proto::ExportSpecResponse>, Status> {
+        debug!("ExportSpec gRPC Request: [{:?}]", request);
+        let response = match self.export_room_spec(request.into_inner()).await {
+            Ok(spec) => proto::ExportSpecResponse { spec, error: None },
+            Err(e) => proto::ExportSpecResponse {
+                spec: String::new(),
+                error: Some(e.into()),
+            },
+        };
+        Ok(tonic::Response::new(response))
+    }
+
+    /// Applies the given YAML spec of a [`Room`].
+    ///
+    /// Propagates request to [`ControlApiService::import_room_spec`].
+    ///
+    /// [`Room`]: proto::Room
+    async fn import_spec(
+        &self,
+        request: tonic::Request<proto::ImportSpecRequest>,
+    ) -> Result<tonic::Response<proto::CreateResponse>, Status> {
+        debug!("ImportSpec gRPC Request: [{:?}]", request);
+        let response = match self.import_room_spec(request.into_inner()).await {
+            Ok(sid) => proto::CreateResponse {
+                sid: proto_sids(sid),
+                error: None,
+                operation_id: String::new(),
+            },
+            Err(e) => proto::CreateResponse {
+                sid: HashMap::new(),
+                error: Some(e.into()),
+                operation_id: String::new(),
+            },
+        };
+        Ok(tonic::Response::new(response))
+    }
+
+    /// Creates a new [`Room`] from a template of [Medea].
+    ///
+    /// Propagates request to
+    /// [`ControlApiService::create_room_from_template`].
+    ///
+    /// [Medea]: https://github.com/instrumentisto/medea
+    /// [`Room`]: proto::Room
+    async fn create_from_template(
+        &self,
+        request: tonic::Request<proto::CreateFromTemplateRequest>,
+    ) -> Result<tonic::Response<proto::CreateResponse>, Status> {
+        debug!("CreateFromTemplate gRPC Request: [{:?}]", request);
+        let response =
+            match self.create_room_from_template(request.into_inner()).await {
+                Ok(sid) => proto::CreateResponse {
+                    sid: proto_sids(sid),
+                    error: None,
+                    operation_id: String::new(),
+                },
+                Err(e) => proto::CreateResponse {
+                    sid: HashMap::new(),
+                    error: Some(e.into()),
+                    operation_id: String::new(),
+                },
+            };
+        Ok(tonic::Response::new(response))
+    }
 }
 
 /// Actor wrapper for [`tonic`] gRPC server which provides dynamic [Control
@@ -410,20 +1143,68 @@ impl Handler<ShutdownGracefully> for GrpcServer {
     }
 }
 
+/// Returns index of the API key from the provided ones, which is contained in
+/// an `authorization: Bearer <key>` entry of the provided [`MetadataMap`] of
+/// a [Control API] call.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+fn find_api_key(api_keys: &[String], metadata: &MetadataMap) -> Option<usize> {
+    let key = metadata
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))?
+        .as_bytes();
+    api_keys.iter().enumerate().fold(None, |found, (i, k)| {
+        if bool::from(k.as_bytes().ct_eq(key)) {
+            Some(i)
+        } else {
+            found
+        }
+    })
+}
+
 /// Run gRPC [Control API] server in actix actor. Returns [`Addr`] of
 /// [`GrpcServer`] [`Actor`] and [`oneshot::Receiver`] for [`transport::Error`]
 /// that may fire when initializing [`GrpcServer`].
 ///
+/// Terminates TLS if it's configured.
+///
+/// # Errors
+///
+/// Errors if configured TLS certificates cannot be loaded.
+///
 /// [Control API]: https://tinyurl.com/yxsqplq7
 pub fn run(
     room_service: Addr<RoomService>,
     app: &AppContext,
-) -> (
+) -> io::Result<(
     Addr<GrpcServer>,
     oneshot::Receiver<Result<(), transport::Error>>,
-) {
+)> {
     let bind_ip = app.config.server.control.grpc.bind_ip;
     let bind_port = app.config.server.control.grpc.bind_port;
+    let auth = &app.config.control.auth;
+    let mut tls_config =
+        tls::server_config(&app.config.server.control.grpc.tls)?;
+    if let Some(ca) = &auth.client_ca {
+        let config = tls_config.as_mut().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Control API client certificates authentication requires \
+                 TLS being enabled",
+            )
+        })?;
+        tls::require_client_certs(config, ca)?;
+    }
+    let tls_config = tls_config.map(|mut config| {
+        config.set_protocols(&[b"h2".to_vec()]);
+        let mut tls_config = ServerTlsConfig::new();
+        tls_config.rustls_server_config(config);
+        tls_config
+    });
+    let api_keys = Arc::new(auth.api_keys.clone());
+    let admin_api_keys = Arc::new(auth.admin_api_keys.clone());
+    let audit_conf = app.config.control.audit.clone();
 
     info!("Starting gRPC server on {}:{}", bind_ip, bind_port);
 
@@ -433,10 +1214,37 @@ pub fn run(
     let grpc_actor_addr =
         GrpcServer::start_in_arbiter(&Arbiter::new().handle(), move |_| {
             Arbiter::current().spawn(async move {
-                let result = Server::builder()
-                    .add_service(TonicControlApiServer::new(ControlApiService(
-                        room_service,
-                    )))
+                let mut builder = Server::builder();
+                if let Some(tls_config) = tls_config {
+                    builder = builder.tls_config(tls_config);
+                }
+                let audit = AuditLog::new(&audit_conf);
+                let audited = audit.is_enabled();
+                let service = ControlApiService {
+                    room_service,
+                    admin_api_keys: Arc::clone(&admin_api_keys),
+                };
+                let service = TonicControlApiServer::with_interceptor(
+                    service,
+                    move |req: tonic::Request<()>| {
+                        let metadata = req.metadata();
+                        let api_key = find_api_key(&api_keys, metadata);
+                        if !api_keys.is_empty()
+                            && api_key.is_none()
+                            && find_api_key(&admin_api_keys, metadata).is_none()
+                        {
+                            return Err(Status::unauthenticated(
+                                "Invalid or missing API key",
+                            ));
+                        }
+                        if audited {
+                            Principal::of(&req, api_key).capture();
+                        }
+                        Ok(req)
+                    },
+                );
+                let result = builder
+                    .add_service(AuditLayer::new(audit).layer(service))
                     .serve_with_shutdown(bind_addr, async move {
                         let _ = grpc_shutdown_rx.await;
                     })
@@ -455,5 +1263,25 @@ pub fn run(
             GrpcServer(Some(grpc_shutdown_tx))
         });
 
-    (grpc_actor_addr, tonic_server_rx)
+    Ok((grpc_actor_addr, tonic_server_rx))
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    #[test]
+    fn authorizes_by_api_key() {
+        let keys = vec!["first".to_owned(), "second".to_owned()];
+        let metadata = |value: &str| {
+            let mut metadata = MetadataMap::new();
+            metadata.insert("authorization", value.parse().unwrap());
+            metadata
+        };
+
+        assert_eq!(find_api_key(&keys, &metadata("Bearer second")), Some(1));
+        assert_eq!(find_api_key(&keys, &metadata("Bearer third")), None);
+        assert_eq!(find_api_key(&keys, &metadata("second")), None);
+        assert_eq!(find_api_key(&keys, &MetadataMap::new()), None);
+    }
 }
@@ -11,7 +11,7 @@ use std::{
 
 use medea_client_api_proto::{self as client_proto, MemberId as Id, RoomId};
 use medea_control_api_proto::grpc::api as proto;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     api::control::{
@@ -75,7 +75,7 @@ impl fmt::Display for Sid {
 }
 
 /// Credentials of the `Member` element.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Credential {
     /// [Argon2] hash of the `Member` credential.
@@ -138,7 +138,7 @@ impl From<Credential> for proto::member::Credentials {
 /// Element of [`Member`]'s [`Pipeline`].
 ///
 /// [`Member`]: crate::signalling::elements::Member
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug, Serialize)]
 #[serde(tag = "kind")]
 pub enum MemberElement {
     /// Represent [`WebRtcPublishEndpoint`].
@@ -169,6 +169,9 @@ pub struct MemberSpec {
     /// URL to which `OnLeave` Control API callback will be sent.
     on_leave: Option<CallbackUrl>,
 
+    /// URL to which `OnNegotiationTimeout` Control API callback will be sent.
+    on_negotiation_timeout: Option<CallbackUrl>,
+
     /// Timeout of receiving heartbeat messages from the `Member` via Client
     /// API.
     ///
@@ -182,6 +185,14 @@ pub struct MemberSpec {
 
     /// Interval of sending `Ping`s to the `Member` via Client API.
     ping_interval: Option<Duration>,
+
+    /// Arbitrary key-value metadata of the `Member`, which is delivered to
+    /// other `Member`s of the `Room` via Client API.
+    metadata: HashMap<String, String>,
+
+    /// Key-value feature flags of the `Member`, which are delivered to the
+    /// `Member` only via Client API.
+    features: HashMap<String, String>,
 }
 
 impl From<MemberSpec> for RoomElement {
@@ -192,15 +203,19 @@ impl From<MemberSpec> for RoomElement {
             credentials: spec.credentials,
             on_join: spec.on_join,
             on_leave: spec.on_leave,
+            on_negotiation_timeout: spec.on_negotiation_timeout,
             idle_timeout: spec.idle_timeout,
             reconnect_timeout: spec.reconnect_timeout,
             ping_interval: spec.ping_interval,
+            metadata: spec.metadata,
+            features: spec.features,
         }
     }
 }
 
 impl MemberSpec {
-    /// Creates new [`MemberSpec`] with the given parameters.
+    /// Creates new [`MemberSpec`] with the given parameters, empty metadata
+    /// and feature flags, and no `on_negotiation_timeout` callback.
     #[inline]
     #[must_use]
     pub fn new(
@@ -217,9 +232,38 @@ impl MemberSpec {
             credentials,
             on_join,
             on_leave,
+            on_negotiation_timeout: None,
             idle_timeout,
             reconnect_timeout,
             ping_interval,
+            metadata: HashMap::new(),
+            features: HashMap::new(),
+        }
+    }
+
+    /// Returns a copy of this [`MemberSpec`] without its
+    /// [`WebRtcPlayEndpoint`]s.
+    #[must_use]
+    pub fn without_play_endpoints(&self) -> Self {
+        let pipeline = self
+            .pipeline
+            .iter()
+            .filter(|(_, e)| {
+                matches!(e, MemberElement::WebRtcPublishEndpoint { .. })
+            })
+            .map(|(id, e)| (id.clone(), e.clone()))
+            .collect();
+        Self {
+            pipeline: Pipeline::new(pipeline),
+            credentials: self.credentials.clone(),
+            on_join: self.on_join.clone(),
+            on_leave: self.on_leave.clone(),
+            on_negotiation_timeout: self.on_negotiation_timeout.clone(),
+            idle_timeout: self.idle_timeout,
+            reconnect_timeout: self.reconnect_timeout,
+            ping_interval: self.ping_interval,
+            metadata: self.metadata.clone(),
+            features: self.features.clone(),
         }
     }
 
@@ -296,6 +340,13 @@ impl MemberSpec {
         &self.on_leave
     }
 
+    /// Returns reference to `on_negotiation_timeout` [`CallbackUrl`].
+    #[inline]
+    #[must_use]
+    pub fn on_negotiation_timeout(&self) -> &Option<CallbackUrl> {
+        &self.on_negotiation_timeout
+    }
+
     /// Returns timeout of receiving heartbeat messages from the `Member` via
     /// Client API.
     ///
@@ -321,6 +372,20 @@ impl MemberSpec {
     pub fn ping_interval(&self) -> Option<Duration> {
         self.ping_interval
     }
+
+    /// Returns key-value metadata of the `Member`.
+    #[inline]
+    #[must_use]
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.metadata
+    }
+
+    /// Returns key-value feature flags of the `Member`.
+    #[inline]
+    #[must_use]
+    pub fn features(&self) -> &HashMap<String, String> {
+        &self.features
+    }
 }
 
 impl TryFrom<proto::Member> for MemberSpec {
@@ -369,6 +434,14 @@ impl TryFrom<proto::Member> for MemberSpec {
                 Some(CallbackUrl::try_from(on_join)?)
             }
         };
+        let on_negotiation_timeout = {
+            let on_negotiation_timeout = member.on_negotiation_timeout;
+            if on_negotiation_timeout.is_empty() {
+                None
+            } else {
+                Some(CallbackUrl::try_from(on_negotiation_timeout)?)
+            }
+        };
 
         let idle_timeout =
             parse_duration(member.idle_timeout, &member.id, "idle_timeout")?;
@@ -385,9 +458,12 @@ impl TryFrom<proto::Member> for MemberSpec {
             credentials,
             on_join,
             on_leave,
+            on_negotiation_timeout,
             idle_timeout,
             reconnect_timeout,
             ping_interval,
+            metadata: member.metadata,
+            features: member.features,
         })
     }
 }
@@ -428,17 +504,23 @@ impl TryFrom<&RoomElement> for MemberSpec {
                 credentials,
                 on_leave,
                 on_join,
+                on_negotiation_timeout,
                 idle_timeout,
                 reconnect_timeout,
                 ping_interval,
+                metadata,
+                features,
             } => Ok(Self {
                 pipeline: spec.clone(),
                 credentials: credentials.clone(),
                 on_leave: on_leave.clone(),
                 on_join: on_join.clone(),
+                on_negotiation_timeout: on_negotiation_timeout.clone(),
                 idle_timeout: *idle_timeout,
                 reconnect_timeout: *reconnect_timeout,
                 ping_interval: *ping_interval,
+                metadata: metadata.clone(),
+                features: features.clone(),
             }),
             _ => Err(TryFromElementError::NotMember),
         }
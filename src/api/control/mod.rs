@@ -10,14 +10,15 @@ pub mod member;
 pub mod pipeline;
 pub mod refs;
 pub mod room;
+pub mod template;
 
 use std::{convert::TryFrom as _, fs::File, io::Read as _, path::Path};
 
 use actix::Addr;
-use derive_more::Display;
+use derive_more::{Display, From};
 use failure::{Error, Fail};
 use medea_client_api_proto::{MemberId, RoomId};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     api::control::callback::url::CallbackUrlParseError,
@@ -38,6 +39,7 @@ pub use self::{
     },
     member::MemberSpec,
     room::{RoomElement, RoomSpec},
+    template::{load_templates_from_dir, RoomTemplate, TemplateError},
 };
 
 /// Errors which may occur while deserializing protobuf spec.
@@ -103,15 +105,31 @@ impl From<CallbackUrlParseError> for TryFromProtobufError {
     }
 }
 
+/// Current version of [Control API] specs schema.
+///
+/// Specs are exported being marked with this version, while the ones without
+/// any version are considered to be of this version.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+pub const SPEC_VERSION: u32 = 1;
+
+/// Returns [`SPEC_VERSION`] for [`serde`] defaults.
+#[inline]
+const fn spec_version() -> u32 {
+    SPEC_VERSION
+}
+
 /// Root elements of [Control API] spec.
 ///
 /// [Control API]: https://tinyurl.com/yxsqplq7
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug, Serialize)]
 #[serde(tag = "kind")]
 pub enum RootElement {
     /// Represents [`RoomSpec`].
     /// Can transform into [`RoomSpec`] by `RoomSpec::try_from`.
     Room {
+        #[serde(default = "spec_version")]
+        version: u32,
         id: RoomId,
         spec: Pipeline<MemberId, RoomElement>,
     },
@@ -132,6 +150,28 @@ pub enum TryFromElementError {
     /// Element is not `Member`.
     #[display(fmt = "Element is not Member")]
     NotMember,
+
+    /// Spec is of a schema version which is not supported.
+    #[display(
+        fmt = "Spec version {} is not supported, expected {}",
+        _0,
+        SPEC_VERSION
+    )]
+    UnsupportedVersion(u32),
+}
+
+/// Errors which can happen while parsing YAML [Control API] specs.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Debug, Display, Fail, From)]
+pub enum ParseYamlSpecError {
+    /// Error while deserializing YAML spec.
+    #[display(fmt = "Error while deserializing YAML spec: {}", _0)]
+    Yaml(serde_yaml::Error),
+
+    /// Deserialized spec is not a valid [`RoomSpec`].
+    #[display(fmt = "Invalid spec: {}", _0)]
+    TryFromElement(TryFromElementError),
 }
 
 /// Errors which can happen while loading static [Control API] specs.
@@ -171,6 +211,10 @@ pub enum LoadStaticControlSpecsError {
     /// [Control API]: https://tinyurl.com/yxsqplq7
     #[display(fmt = "Error while deserialization static spec. {:?}", _0)]
     YamlDeserializationError(serde_yaml::Error),
+
+    /// Malformed static [`RoomTemplate`].
+    #[display(fmt = "Invalid Room template. {}", _0)]
+    InvalidTemplate(TemplateError),
 }
 
 impl From<std::io::Error> for LoadStaticControlSpecsError {
@@ -191,6 +235,12 @@ impl From<serde_yaml::Error> for LoadStaticControlSpecsError {
     }
 }
 
+impl From<TemplateError> for LoadStaticControlSpecsError {
+    fn from(err: TemplateError) -> Self {
+        Self::InvalidTemplate(err)
+    }
+}
+
 /// Loads [`RoomSpec`] from file with YAML format.
 ///
 /// # Errors
@@ -3,12 +3,13 @@
 //! [Control API]: https://tinyurl.com/yxsqplq7
 
 use std::{
-    collections::{hash_map::Iter, HashMap},
+    collections::{hash_map::Iter, BTreeMap, HashMap},
+    fmt::Display,
     hash::Hash,
     iter::IntoIterator,
 };
 
-use serde::Deserialize;
+use serde::{ser::SerializeStruct as _, Deserialize, Serialize, Serializer};
 
 /// Entity that represents some pipeline of spec.
 #[derive(Clone, Deserialize, Debug)]
@@ -55,3 +56,25 @@ impl<'a, K: Eq + Hash, V> IntoIterator for &'a Pipeline<K, V> {
         self.pipeline.iter()
     }
 }
+
+/// Serializes [`Pipeline`] with its elements ordered by their IDs, so the
+/// serialized spec doesn't change from one export to another.
+impl<K, V> Serialize for Pipeline<K, V>
+where
+    K: Display + Eq + Hash,
+    V: Serialize,
+{
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let pipeline: BTreeMap<_, _> = self
+            .pipeline
+            .iter()
+            .map(|(id, el)| (id.to_string(), el))
+            .collect();
+        let mut s = serializer.serialize_struct("Pipeline", 1)?;
+        s.serialize_field("pipeline", &pipeline)?;
+        s.end()
+    }
+}
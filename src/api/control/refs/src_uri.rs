@@ -2,6 +2,9 @@
 //! `local://{room_id}/{member_id}/{endpoint_id}`. This URI can point only to
 //! [`WebRtcPublishEndpoint`].
 //!
+//! `*` may be used instead of a `{member_id}` for pointing to the
+//! [`WebRtcPublishEndpoint`]s with the same ID of all the `Member`s.
+//!
 //! [`WebRtcPublishEndpoint`]:
 //! crate::signalling::elements::endpoints::webrtc::WebRtcPublishEndpoint
 
@@ -12,7 +15,7 @@ use failure::Fail;
 use medea_client_api_proto::{MemberId, RoomId};
 use serde::{
     de::{self, Deserializer, Error, Visitor},
-    Deserialize,
+    Deserialize, Serialize, Serializer,
 };
 
 use crate::api::control::{
@@ -38,6 +41,10 @@ pub enum SrcParseError {
     LocalUriParseError(LocalUriParseError),
 }
 
+/// `{member_id}` of a wildcard [`SrcUri`], matching all the `Member`s of a
+/// `Room`.
+pub const WILDCARD_MEMBER_ID: &str = "*";
+
 /// Special URI with pattern `local://{room_id}/{member_id}/{endpoint_id}`.
 /// This uri can pointing only to [`WebRtcPublishEndpoint`].
 ///
@@ -72,6 +79,32 @@ pub struct SrcUri {
     pub endpoint_id: WebRtcPublishId,
 }
 
+impl SrcUri {
+    /// Indicates whether this [`SrcUri`] is a wildcard one
+    /// (`local://{room_id}/*/{endpoint_id}`), pointing to the
+    /// [`WebRtcPublishEndpoint`]s with the same ID of all the `Member`s.
+    ///
+    /// [`WebRtcPublishEndpoint`]:
+    /// crate::api::control::endpoints::WebRtcPublishEndpoint
+    #[inline]
+    #[must_use]
+    pub fn is_wildcard(&self) -> bool {
+        self.member_id.0 == WILDCARD_MEMBER_ID
+    }
+
+    /// Returns a copy of this [`SrcUri`] pointing to the `Member` with the
+    /// provided [`MemberId`].
+    #[inline]
+    #[must_use]
+    pub fn with_member_id(&self, member_id: MemberId) -> Self {
+        Self {
+            room_id: self.room_id.clone(),
+            member_id,
+            endpoint_id: self.endpoint_id.clone(),
+        }
+    }
+}
+
 impl TryFrom<String> for SrcUri {
     type Error = SrcParseError;
 
@@ -149,3 +182,19 @@ impl fmt::Display for SrcUri {
         )
     }
 }
+
+/// [Serde] serializer for [`SrcUri`].
+///
+/// Serializes into URIs with pattern:
+/// `local://room_id/member_id/publish_endpoint_id`.
+///
+/// [Serde]: serde
+impl Serialize for SrcUri {
+    #[inline]
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
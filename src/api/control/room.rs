@@ -2,11 +2,15 @@
 //!
 //! [Control API]: https://tinyurl.com/yxsqplq7
 
-use std::{collections::HashMap, convert::TryFrom, time::Duration};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+    time::Duration,
+};
 
 use medea_client_api_proto::{MemberId, RoomId as Id};
 use medea_control_api_proto::grpc::api as proto;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize, Serializer};
 
 use crate::api::control::{
     callback::url::CallbackUrl, member::Credential, EndpointId,
@@ -16,13 +20,13 @@ use crate::api::control::{
 use super::{
     member::{MemberElement, MemberSpec},
     pipeline::Pipeline,
-    RootElement, TryFromElementError,
+    ParseYamlSpecError, RootElement, TryFromElementError, SPEC_VERSION,
 };
 
 /// Element of [`Room`]'s [`Pipeline`].
 ///
 /// [`Room`]: crate::signalling::room::Room
-#[derive(Clone, Deserialize, Debug)]
+#[derive(Clone, Deserialize, Debug, Serialize)]
 #[serde(tag = "kind")]
 pub enum RoomElement {
     /// Represent [`MemberSpec`].
@@ -30,17 +34,54 @@ pub enum RoomElement {
     Member {
         spec: Pipeline<EndpointId, MemberElement>,
         credentials: Credential,
+        #[serde(skip_serializing_if = "Option::is_none")]
         on_leave: Option<CallbackUrl>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         on_join: Option<CallbackUrl>,
-        #[serde(default, with = "humantime_serde")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        on_negotiation_timeout: Option<CallbackUrl>,
+        #[serde(
+            default,
+            with = "humantime_serde",
+            skip_serializing_if = "Option::is_none"
+        )]
         idle_timeout: Option<Duration>,
-        #[serde(default, with = "humantime_serde")]
+        #[serde(
+            default,
+            with = "humantime_serde",
+            skip_serializing_if = "Option::is_none"
+        )]
         reconnect_timeout: Option<Duration>,
-        #[serde(default, with = "humantime_serde")]
+        #[serde(
+            default,
+            with = "humantime_serde",
+            skip_serializing_if = "Option::is_none"
+        )]
         ping_interval: Option<Duration>,
+        #[serde(
+            default,
+            serialize_with = "serialize_sorted",
+            skip_serializing_if = "HashMap::is_empty"
+        )]
+        metadata: HashMap<String, String>,
+        #[serde(
+            default,
+            serialize_with = "serialize_sorted",
+            skip_serializing_if = "HashMap::is_empty"
+        )]
+        features: HashMap<String, String>,
     },
 }
 
+/// Serializes the provided [`HashMap`] ordered by its keys, so the serialized
+/// spec doesn't change from one export to another.
+fn serialize_sorted<S: Serializer>(
+    map: &HashMap<String, String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(map.iter().collect::<BTreeMap<_, _>>())
+}
+
 /// [Control API]'s `Room` element specification.
 ///
 /// Newtype for [`RootElement::Room`].
@@ -78,6 +119,44 @@ impl RoomSpec {
     pub fn id(&self) -> &Id {
         &self.id
     }
+
+    /// Parses a [`RoomSpec`] from the provided YAML [Control API] spec.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`ParseYamlSpecError::Yaml`] if YAML deserialization fails.
+    ///
+    /// Errors with [`ParseYamlSpecError::TryFromElement`] if the provided spec
+    /// is not a `Room` spec, or is of an unsupported version.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub fn from_yaml(yaml: &str) -> Result<Self, ParseYamlSpecError> {
+        let parsed: RootElement = serde_yaml::from_str(yaml)?;
+        Ok(Self::try_from(&parsed)?)
+    }
+
+    /// Serializes this [`RoomSpec`] into a YAML [Control API] spec marked with
+    /// the current [`SPEC_VERSION`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if YAML serialization fails.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(&RootElement::from(self.clone()))
+    }
+}
+
+impl From<RoomSpec> for RootElement {
+    #[inline]
+    fn from(spec: RoomSpec) -> Self {
+        Self::Room {
+            version: SPEC_VERSION,
+            id: spec.id,
+            spec: spec.pipeline,
+        }
+    }
 }
 
 impl TryFrom<&RootElement> for RoomSpec {
@@ -87,7 +166,10 @@ impl TryFrom<&RootElement> for RoomSpec {
     #[allow(unreachable_patterns)]
     fn try_from(from: &RootElement) -> Result<Self, Self::Error> {
         match from {
-            RootElement::Room { id, spec } => Ok(Self {
+            RootElement::Room { version, .. } if *version != SPEC_VERSION => {
+                Err(TryFromElementError::UnsupportedVersion(*version))
+            }
+            RootElement::Room { id, spec, .. } => Ok(Self {
                 id: id.clone(),
                 pipeline: spec.clone(),
             }),
@@ -144,3 +226,80 @@ macro_rules! impl_from_el_for_room_spec {
 
 impl_from_el_for_room_spec!(proto::create_request::El);
 impl_from_el_for_room_spec!(proto::apply_request::El);
+impl_from_el_for_room_spec!(proto::element::El);
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    const ROOM_SPEC: &str = r#"
+        kind: Room
+        id: test-call
+        spec:
+          pipeline:
+            caller:
+              kind: Member
+              credentials:
+                plain: test
+              idle_timeout: 1m
+              metadata:
+                name: Alice
+              spec:
+                pipeline:
+                  publish:
+                    kind: WebRtcPublishEndpoint
+                    spec:
+                      p2p: Always
+            responder:
+              kind: Member
+              credentials:
+                plain: test
+              on_join: "grpc://127.0.0.1:9099"
+              spec:
+                pipeline:
+                  play:
+                    kind: WebRtcPlayEndpoint
+                    spec:
+                      src: "local://test-call/caller/publish"
+    "#;
+
+    #[test]
+    fn exported_yaml_spec_is_imported_back() {
+        let spec = RoomSpec::from_yaml(ROOM_SPEC).unwrap();
+
+        let yaml = spec.to_yaml().unwrap();
+        assert!(yaml.contains("version: 1"));
+        assert_eq!(
+            RoomSpec::from_yaml(&yaml).unwrap().to_yaml().unwrap(),
+            yaml
+        );
+
+        let members = RoomSpec::from_yaml(&yaml).unwrap().members().unwrap();
+        let caller = &members[&MemberId::from("caller")];
+        assert_eq!(caller.idle_timeout(), Some(Duration::from_secs(60)));
+        assert_eq!(caller.metadata()["name"], "Alice");
+        assert_eq!(caller.publish_endpoints().count(), 1);
+        let responder = &members[&MemberId::from("responder")];
+        assert_eq!(
+            responder.on_join().as_ref().map(ToString::to_string),
+            Some(String::from("grpc://127.0.0.1:9099")),
+        );
+        assert_eq!(responder.play_endpoints().count(), 1);
+    }
+
+    #[test]
+    fn rejects_unsupported_spec_version() {
+        let spec = ROOM_SPEC.replacen(
+            "id: test-call",
+            "version: 2\n        id: test-call",
+            1,
+        );
+
+        match RoomSpec::from_yaml(&spec) {
+            Err(ParseYamlSpecError::TryFromElement(
+                TryFromElementError::UnsupportedVersion(2),
+            )) => {}
+            _ => panic!("unsupported spec version is accepted"),
+        }
+    }
+}
@@ -0,0 +1,522 @@
+//! [Control API]'s `Room` templates, declaring placeholders to be substituted
+//! with parameters once a `Room` is created from them.
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use std::{collections::HashMap, convert::TryFrom as _, fs, path::Path};
+
+use derive_more::{Display, From};
+use failure::Fail;
+use medea_client_api_proto::RoomId;
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+
+use crate::utils::generate_token;
+
+use super::{
+    LoadStaticControlSpecsError, RoomSpec, RootElement, TryFromElementError,
+};
+
+/// Name of the placeholder substituted with an ID of the created `Room`.
+const ROOM_ID_PLACEHOLDER: &str = "room_id";
+
+/// Name of the placeholder substituted with a 1-based index of a repeated
+/// `Member`.
+const INDEX_PLACEHOLDER: &str = "n";
+
+/// Errors which can happen while instantiating a [`RoomTemplate`].
+#[derive(Debug, Display, Fail, From)]
+pub enum TemplateError {
+    /// Provided parameter is not declared by the [`RoomTemplate`].
+    #[display(fmt = "Parameter `{}` is not declared by the template", _0)]
+    #[from(ignore)]
+    UnknownParam(String),
+
+    /// Parameter declared by the [`RoomTemplate`] has neither been provided
+    /// nor has a default value.
+    #[display(fmt = "Parameter `{}` is required", _0)]
+    #[from(ignore)]
+    MissingParam(String),
+
+    /// Provided value of a [`TemplateParam::Count`] is not a number within
+    /// its range.
+    #[display(
+        fmt = "Parameter `{}` must be a number in [{}; {}] range",
+        _0,
+        _1,
+        _2
+    )]
+    #[from(ignore)]
+    InvalidCount(String, u32, u32),
+
+    /// `Member` is repeated by a parameter which is not a
+    /// [`TemplateParam::Count`].
+    #[display(fmt = "Parameter `{}` is not a count", _0)]
+    #[from(ignore)]
+    NotCountParam(String),
+
+    /// [`RoomTemplate`] contains a placeholder, which has no value.
+    #[display(fmt = "Unknown placeholder `{{{}}}`", _0)]
+    #[from(ignore)]
+    UnknownPlaceholder(String),
+
+    /// [`RoomTemplate`] has malformed structure.
+    #[display(fmt = "Malformed template: {}", _0)]
+    #[from(ignore)]
+    Malformed(&'static str),
+
+    /// Error while deserializing YAML of the [`RoomTemplate`] or its
+    /// instantiated spec.
+    #[display(fmt = "Invalid YAML: {}", _0)]
+    Yaml(serde_yaml::Error),
+
+    /// Instantiated spec is not a valid [`RoomSpec`].
+    #[display(fmt = "Instantiated spec is invalid: {}", _0)]
+    TryFromElement(TryFromElementError),
+}
+
+/// Parameter declared by a [`RoomTemplate`].
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "kind")]
+pub enum TemplateParam {
+    /// Number of the `Member`s repeated by this parameter.
+    Count {
+        /// Minimal allowed number of the `Member`s.
+        #[serde(default)]
+        min: u32,
+
+        /// Maximal allowed number of the `Member`s.
+        max: u32,
+
+        /// Number of the `Member`s if the parameter is not provided.
+        #[serde(default)]
+        default: Option<u32>,
+    },
+
+    /// Arbitrary string substituted into the spec as is.
+    String {
+        /// Value of the parameter if it's not provided.
+        #[serde(default)]
+        default: Option<String>,
+    },
+}
+
+impl TemplateParam {
+    /// Resolves a value of this [`TemplateParam`] with the provided `name`
+    /// from the provided one, falling back to its default value.
+    fn resolve(
+        &self,
+        name: &str,
+        value: Option<&String>,
+    ) -> Result<String, TemplateError> {
+        match self {
+            Self::Count { min, max, default } => {
+                let count = match value {
+                    Some(v) => v.parse().ok(),
+                    None => Some(default.ok_or_else(|| {
+                        TemplateError::MissingParam(name.to_owned())
+                    })?),
+                };
+                match count {
+                    Some(c) if (*min..=*max).contains(&c) => Ok(c.to_string()),
+                    _ => Err(TemplateError::InvalidCount(
+                        name.to_owned(),
+                        *min,
+                        *max,
+                    )),
+                }
+            }
+            Self::String { default } => value
+                .or_else(|| default.as_ref())
+                .cloned()
+                .ok_or_else(|| TemplateError::MissingParam(name.to_owned())),
+        }
+    }
+}
+
+/// Policy of generating `Member` credentials on a [`RoomTemplate`]
+/// instantiation.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum CredentialPolicy {
+    /// Plain text credentials of the specified length, randomly generated
+    /// for each `Member`.
+    Generate(usize),
+}
+
+/// Root element of [`RoomTemplate`] spec.
+#[derive(Deserialize)]
+#[serde(tag = "kind")]
+enum TemplateElement {
+    /// Represents [`RoomTemplate`].
+    RoomTemplate {
+        id: String,
+        #[serde(default)]
+        params: HashMap<String, TemplateParam>,
+        spec: Value,
+    },
+}
+
+/// Template of a [Control API]'s `Room` spec.
+///
+/// Any string of the spec (including IDs of its elements) may contain
+/// `{name}` placeholders, which are substituted with the values of the
+/// declared parameters, while the `{room_id}` one is substituted with an ID
+/// of the created `Room`.
+///
+/// Besides regular fields, a `Member` of the template may have:
+/// - `repeat: <param>` field, repeating it by the value of the specified
+///   [`TemplateParam::Count`] (its ID should contain the `{n}` placeholder,
+///   substituted with a 1-based index of each repetition);
+/// - `credentials: {generate: <length>}` field, generating random plain
+///   credentials of the specified length for it.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug)]
+pub struct RoomTemplate {
+    /// ID of this [`RoomTemplate`].
+    id: String,
+
+    /// Parameters declared by this [`RoomTemplate`].
+    params: HashMap<String, TemplateParam>,
+
+    /// Pipeline of the `Room` spec with the placeholders.
+    pipeline: Mapping,
+}
+
+impl RoomTemplate {
+    /// Parses a [`RoomTemplate`] from the provided YAML spec.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`TemplateError::Yaml`] if YAML deserialization fails.
+    ///
+    /// Errors with [`TemplateError::Malformed`] if the spec doesn't contain a
+    /// `Room` pipeline.
+    pub fn from_yaml(yaml: &str) -> Result<Self, TemplateError> {
+        let TemplateElement::RoomTemplate { id, params, spec } =
+            serde_yaml::from_str(yaml)?;
+        let pipeline = match spec.get("pipeline") {
+            Some(Value::Mapping(pipeline)) => pipeline.clone(),
+            _ => return Err(TemplateError::Malformed("no `spec.pipeline`")),
+        };
+        Ok(Self {
+            id,
+            params,
+            pipeline,
+        })
+    }
+
+    /// Returns ID of this [`RoomTemplate`].
+    #[inline]
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Instantiates a [`RoomSpec`] with the provided [`RoomId`] from this
+    /// [`RoomTemplate`], substituting its placeholders with the provided
+    /// parameters.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the provided parameters don't match the declared ones, or
+    /// the instantiated spec is invalid.
+    pub fn instantiate(
+        &self,
+        room_id: RoomId,
+        params: &HashMap<String, String>,
+    ) -> Result<RoomSpec, TemplateError> {
+        if let Some(name) =
+            params.keys().find(|n| !self.params.contains_key(*n))
+        {
+            return Err(TemplateError::UnknownParam(name.clone()));
+        }
+        let mut vars = HashMap::with_capacity(self.params.len() + 2);
+        for (name, param) in &self.params {
+            vars.insert(name.clone(), param.resolve(name, params.get(name))?);
+        }
+        vars.insert(ROOM_ID_PLACEHOLDER.to_owned(), room_id.to_string());
+
+        let mut pipeline = Mapping::new();
+        for (id, member) in self.pipeline.iter() {
+            let mut member = match member {
+                Value::Mapping(member) => member.clone(),
+                _ => return Err(TemplateError::Malformed("non-map Member")),
+            };
+            let repeat = member.remove(&Value::from("repeat"));
+            let count = match repeat {
+                None => None,
+                Some(Value::String(name)) => match self.params.get(&name) {
+                    Some(TemplateParam::Count { .. }) => {
+                        Some(vars[&name].parse().unwrap_or_default())
+                    }
+                    _ => return Err(TemplateError::NotCountParam(name)),
+                },
+                Some(_) => {
+                    return Err(TemplateError::Malformed("non-string `repeat`"))
+                }
+            };
+
+            if let Some(count) = count {
+                for n in 1..=count {
+                    let mut vars = vars.clone();
+                    vars.insert(INDEX_PLACEHOLDER.to_owned(), n.to_string());
+                    let (id, member) = instantiate_member(id, &member, &vars)?;
+                    pipeline.insert(id, member);
+                }
+            } else {
+                let (id, member) = instantiate_member(id, &member, &vars)?;
+                pipeline.insert(id, member);
+            }
+        }
+
+        let mut spec = Mapping::new();
+        spec.insert("pipeline".into(), Value::Mapping(pipeline));
+        let mut room = Mapping::new();
+        room.insert("kind".into(), "Room".into());
+        room.insert("id".into(), room_id.to_string().into());
+        room.insert("spec".into(), Value::Mapping(spec));
+
+        let room: RootElement = serde_yaml::from_value(Value::Mapping(room))?;
+        Ok(RoomSpec::try_from(&room)?)
+    }
+}
+
+/// Instantiates a single `Member` of a [`RoomTemplate`] with the provided ID,
+/// substituting its placeholders with the provided values and generating its
+/// credentials, if required.
+fn instantiate_member(
+    id: &Value,
+    member: &Mapping,
+    vars: &HashMap<String, String>,
+) -> Result<(Value, Value), TemplateError> {
+    let id = substitute_value(id, vars)?;
+    let mut member = substitute_mapping(member, vars)?;
+
+    let credentials = Value::from("credentials");
+    let policy = member
+        .get(&credentials)
+        .cloned()
+        .and_then(|c| serde_yaml::from_value(c).ok());
+    if let Some(CredentialPolicy::Generate(len)) = policy {
+        let mut plain = Mapping::new();
+        plain.insert("plain".into(), generate_token(len).into());
+        member.insert(credentials, Value::Mapping(plain));
+    }
+    Ok((id, Value::Mapping(member)))
+}
+
+/// Recursively substitutes placeholders in all the strings (including keys of
+/// mappings) of the provided YAML [`Value`].
+fn substitute_value(
+    value: &Value,
+    vars: &HashMap<String, String>,
+) -> Result<Value, TemplateError> {
+    Ok(match value {
+        Value::String(s) => Value::String(substitute(s, vars)?),
+        Value::Sequence(seq) => Value::Sequence(
+            seq.iter()
+                .map(|v| substitute_value(v, vars))
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Mapping(map) => Value::Mapping(substitute_mapping(map, vars)?),
+        Value::Null | Value::Bool(_) | Value::Number(_) => value.clone(),
+    })
+}
+
+/// Recursively substitutes placeholders in all the keys and values of the
+/// provided YAML [`Mapping`].
+fn substitute_mapping(
+    map: &Mapping,
+    vars: &HashMap<String, String>,
+) -> Result<Mapping, TemplateError> {
+    map.iter()
+        .map(|(k, v)| {
+            Ok((substitute_value(k, vars)?, substitute_value(v, vars)?))
+        })
+        .collect()
+}
+
+/// Substitutes all the `{name}` placeholders in the provided string with the
+/// provided values.
+fn substitute(
+    s: &str,
+    vars: &HashMap<String, String>,
+) -> Result<String, TemplateError> {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| TemplateError::Malformed("unclosed placeholder"))?
+            + start;
+        let name = &rest[start + 1..end];
+        let value = vars.get(name).ok_or_else(|| {
+            TemplateError::UnknownPlaceholder(name.to_owned())
+        })?;
+        out.push_str(&rest[..start]);
+        out.push_str(value);
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Loads all [`RoomTemplate`]s from YAML files of the provided directory.
+///
+/// # Errors
+///
+/// Errors with [`LoadStaticControlSpecsError::SpecDirReadError`] if reading
+/// the provided directory fails.
+///
+/// Errors with [`LoadStaticControlSpecsError::InvalidTemplate`] if any of
+/// the [`RoomTemplate`]s is malformed.
+pub fn load_templates_from_dir<P: AsRef<Path>>(
+    path: P,
+) -> Result<HashMap<String, RoomTemplate>, LoadStaticControlSpecsError> {
+    let mut templates = HashMap::new();
+    for entry in fs::read_dir(path)
+        .map_err(LoadStaticControlSpecsError::SpecDirReadError)?
+    {
+        let yaml = fs::read_to_string(entry?.path())?;
+        let template = RoomTemplate::from_yaml(&yaml)?;
+        templates.insert(template.id.clone(), template);
+    }
+    Ok(templates)
+}
+
+#[cfg(test)]
+mod spec {
+    use medea_client_api_proto::MemberId;
+
+    use crate::api::control::member::Credential;
+
+    use super::*;
+
+    const WEBINAR: &str = r#"
+kind: RoomTemplate
+id: webinar
+params:
+  viewers:
+    kind: Count
+    min: 1
+    max: 3
+    default: 2
+  title:
+    kind: String
+spec:
+  pipeline:
+    presenter:
+      kind: Member
+      credentials:
+        generate: 16
+      metadata:
+        title: "{title}"
+      spec:
+        pipeline:
+          publish:
+            kind: WebRtcPublishEndpoint
+            spec:
+              p2p: Never
+    viewer-{n}:
+      kind: Member
+      repeat: viewers
+      credentials:
+        plain: viewer-{n}
+      spec:
+        pipeline:
+          play:
+            kind: WebRtcPlayEndpoint
+            spec:
+              src: "local://{room_id}/presenter/publish"
+"#;
+
+    fn params(params: &[(&str, &str)]) -> HashMap<String, String> {
+        params
+            .iter()
+            .map(|(k, v)| ((*k).to_owned(), (*v).to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn instantiates_repeated_members() {
+        let template = RoomTemplate::from_yaml(WEBINAR).unwrap();
+        assert_eq!(template.id(), "webinar");
+
+        let spec = template
+            .instantiate(
+                "room".into(),
+                &params(&[("viewers", "3"), ("title", "Rust")]),
+            )
+            .unwrap();
+        assert_eq!(spec.id(), &RoomId::from("room"));
+        let members = spec.members().unwrap();
+        assert_eq!(members.len(), 4);
+
+        let presenter = &members[&MemberId::from("presenter")];
+        assert_eq!(presenter.metadata()["title"], "Rust");
+        match presenter.credentials() {
+            Credential::Plain(plain) => assert_eq!(plain.len(), 16),
+            Credential::Hash(_) => panic!("plain credentials expected"),
+        }
+        for n in 1..=3 {
+            let viewer = &members[&MemberId(format!("viewer-{}", n))];
+            assert_eq!(
+                viewer.credentials(),
+                &Credential::Plain(format!("viewer-{}", n)),
+            );
+            let (_, play) = viewer.play_endpoints().next().unwrap();
+            assert_eq!(play.src.to_string(), "local://room/presenter/publish");
+        }
+    }
+
+    #[test]
+    fn uses_default_params() {
+        let template = RoomTemplate::from_yaml(WEBINAR).unwrap();
+
+        let spec = template
+            .instantiate("room".into(), &params(&[("title", "Rust")]))
+            .unwrap();
+        assert_eq!(spec.members().unwrap().len(), 3);
+
+        assert!(matches!(
+            template.instantiate("room".into(), &HashMap::new()),
+            Err(TemplateError::MissingParam(name)) if name == "title",
+        ));
+    }
+
+    #[test]
+    fn validates_params() {
+        let template = RoomTemplate::from_yaml(WEBINAR).unwrap();
+
+        for viewers in &["0", "4", "many"] {
+            assert!(matches!(
+                template.instantiate(
+                    "room".into(),
+                    &params(&[("viewers", viewers), ("title", "Rust")]),
+                ),
+                Err(TemplateError::InvalidCount(_, 1, 3)),
+            ));
+        }
+        assert!(matches!(
+            template.instantiate(
+                "room".into(),
+                &params(&[("title", "Rust"), ("foo", "bar")]),
+            ),
+            Err(TemplateError::UnknownParam(name)) if name == "foo",
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_placeholders() {
+        let template =
+            RoomTemplate::from_yaml(&WEBINAR.replace("{title}", "{topic}"))
+                .unwrap();
+
+        assert!(matches!(
+            template.instantiate("room".into(), &params(&[("title", "Rust")])),
+            Err(TemplateError::UnknownPlaceholder(name)) if name == "topic",
+        ));
+    }
+}
@@ -2,16 +2,19 @@
 
 pub mod client;
 pub mod control;
+pub mod tls;
 
-use std::fmt::Debug;
+use std::{fmt::Debug, net::SocketAddr, time::Duration};
 
 use actix::MailboxError;
 use futures::future::LocalBoxFuture;
-use medea_client_api_proto::{Command, Credential, MemberId};
+use medea_client_api_proto::{
+    state, ClientCapabilities, Command, Credential, MemberId,
+};
 
 use crate::{
     api::client::rpc_connection::{
-        ClosedReason, RpcConnection, RpcConnectionSettings,
+        ClosedReason, Liveness, RpcConnection, RpcConnectionSettings,
     },
     signalling::room::RoomError,
 };
@@ -31,12 +34,23 @@ pub enum RpcServerError {
     ///
     /// [`Room`]: crate::signalling::room::Room
     RoomMailbox(MailboxError),
+
+    /// Media server is draining, so doesn't accept new connections.
+    Draining,
+
+    /// Media server admits too many connections at the moment, so the new
+    /// one should be retried after the provided [`Duration`].
+    Overloaded(Duration),
 }
 
 impl From<RoomError> for RpcServerError {
     fn from(err: RoomError) -> Self {
         match &err {
             RoomError::AuthorizationError => Self::Authorization,
+            RoomError::Draining => Self::Draining,
+            RoomError::Overloaded(retry_after) => {
+                Self::Overloaded(*retry_after)
+            }
             _ => Self::RoomError(err),
         }
     }
@@ -49,11 +63,16 @@ pub trait RpcServer: Debug + Send {
     /// [`Member`]. Transport should consider dropping connection if message
     /// result is err.
     ///
+    /// `remote_addr` is the address of the client the [`RpcConnection`] is
+    /// established with, if known.
+    ///
     /// [`Member`]: crate::signalling::elements::Member
     fn connection_established(
         &self,
         member_id: MemberId,
         credential: Credential,
+        capabilities: ClientCapabilities,
+        remote_addr: Option<SocketAddr>,
         connection: Box<dyn RpcConnection>,
     ) -> LocalBoxFuture<'static, Result<RpcConnectionSettings, RpcServerError>>;
 
@@ -70,8 +89,22 @@ pub trait RpcServer: Debug + Send {
     /// Sends [`Command`].
     fn send_command(&self, member_id: MemberId, msg: Command);
 
-    /// Sends [`Member`]'s request to synchronize its state.
+    /// Sends updated [`Liveness`] metrics of the specified [`Member`]'s
+    /// [`RpcConnection`].
+    ///
+    /// [`Member`]: crate::signalling::elements::Member
+    fn update_liveness(&self, member_id: MemberId, liveness: Liveness);
+
+    /// Sends [`Member`]'s request to synchronize its state, providing the
+    /// sequence number of the last [`Event`] it has received, if any, and the
+    /// [`state::Room`] it has restored.
     ///
+    /// [`Event`]: medea_client_api_proto::Event
     /// [`Member`]: crate::signalling::elements::Member
-    fn synchronize(&self, member_id: MemberId) -> LocalBoxFuture<'static, ()>;
+    fn synchronize(
+        &self,
+        member_id: MemberId,
+        last_event_seq: Option<u64>,
+        state: state::Room,
+    ) -> LocalBoxFuture<'static, ()>;
 }
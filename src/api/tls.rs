@@ -0,0 +1,266 @@
+//! TLS termination of API servers.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, BufReader},
+    path::Path,
+    sync::Arc,
+};
+
+use rustls::{
+    internal::pemfile,
+    sign::{self, CertifiedKey},
+    AllowAnyAuthenticatedClient, ClientHello, NoClientAuth, ResolvesServerCert,
+    RootCertStore, ServerConfig,
+};
+
+use crate::conf::server::{Tls, TlsCert};
+
+/// Builds a [`ServerConfig`] out of the provided [`Tls`] settings.
+///
+/// Returns [`None`] if TLS is disabled by the provided [`Tls`] settings.
+///
+/// # Errors
+///
+/// Errors if any of the configured certificate, private key or [OCSP]
+/// response files cannot be read or parsed.
+///
+/// [OCSP]: https://en.wikipedia.org/wiki/OCSP_stapling
+pub fn server_config(tls: &Tls) -> io::Result<Option<ServerConfig>> {
+    if !tls.is_enabled() {
+        return Ok(None);
+    }
+
+    let resolver = SniResolver {
+        default: tls.default.as_ref().map(load_cert).transpose()?,
+        by_name: tls
+            .sni
+            .iter()
+            .map(|(name, cert)| Ok((name.to_lowercase(), load_cert(cert)?)))
+            .collect::<io::Result<_>>()?,
+    };
+
+    let mut config = ServerConfig::new(NoClientAuth::new());
+    config.cert_resolver = Arc::new(resolver);
+    Ok(Some(config))
+}
+
+/// Makes the provided [`ServerConfig`] to require clients authenticating with
+/// certificates signed by the CA certificates from the provided PEM file.
+///
+/// # Errors
+///
+/// Errors if the provided CA certificates file cannot be read or parsed.
+pub fn require_client_certs(
+    config: &mut ServerConfig,
+    ca: &Path,
+) -> io::Result<()> {
+    let mut roots = RootCertStore::empty();
+    let (valid, _) = roots
+        .add_pem_file(&mut open(ca)?)
+        .map_err(|_| invalid_data(ca, "malformed CA certificate"))?;
+    if valid == 0 {
+        return Err(invalid_data(ca, "no CA certificates found"));
+    }
+    config.set_client_certificate_verifier(AllowAnyAuthenticatedClient::new(
+        roots,
+    ));
+    Ok(())
+}
+
+/// Loads the provided [`TlsCert`] into a [`CertifiedKey`].
+fn load_cert(cert: &TlsCert) -> io::Result<CertifiedKey> {
+    let chain = pemfile::certs(&mut open(&cert.cert)?)
+        .map_err(|_| invalid_data(&cert.cert, "malformed certificate"))?;
+    if chain.is_empty() {
+        return Err(invalid_data(&cert.cert, "no certificates found"));
+    }
+
+    let key = {
+        let mut keys = pemfile::pkcs8_private_keys(&mut open(&cert.key)?)
+            .map_err(|_| invalid_data(&cert.key, "malformed private key"))?;
+        if keys.is_empty() {
+            keys = pemfile::rsa_private_keys(&mut open(&cert.key)?).map_err(
+                |_| invalid_data(&cert.key, "malformed private key"),
+            )?;
+        }
+        keys.into_iter()
+            .next()
+            .ok_or_else(|| invalid_data(&cert.key, "no private key found"))?
+    };
+    let key = sign::any_supported_type(&key)
+        .map_err(|_| invalid_data(&cert.key, "unsupported private key"))?;
+
+    let mut certified = CertifiedKey::new(chain, Arc::new(key));
+    if let Some(ocsp) = &cert.ocsp {
+        certified.ocsp = Some(fs::read(ocsp)?);
+    }
+    Ok(certified)
+}
+
+/// Opens the file by the provided [`Path`] for buffered reading.
+fn open(path: &Path) -> io::Result<BufReader<File>> {
+    Ok(BufReader::new(File::open(path)?))
+}
+
+/// Returns an [`io::ErrorKind::InvalidData`] error about the file by the
+/// provided [`Path`].
+fn invalid_data(path: &Path, msg: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", path.display(), msg),
+    )
+}
+
+/// [`ResolvesServerCert`] selecting a [`CertifiedKey`] by the server name
+/// indicated by a client ([SNI]), falling back to the default one.
+///
+/// [SNI]: https://en.wikipedia.org/wiki/Server_Name_Indication
+struct SniResolver {
+    /// [`CertifiedKey`] used when no server name is indicated by a client, or
+    /// it's unknown.
+    default: Option<CertifiedKey>,
+
+    /// [`CertifiedKey`]s by their lowercase server names.
+    by_name: HashMap<String, CertifiedKey>,
+}
+
+impl ResolvesServerCert for SniResolver {
+    fn resolve(&self, client_hello: ClientHello<'_>) -> Option<CertifiedKey> {
+        client_hello
+            .server_name()
+            .and_then(|name| {
+                let name: &str = name.into();
+                self.by_name.get(&name.to_lowercase())
+            })
+            .or_else(|| self.default.as_ref())
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::{
+        io::{Read as _, Write as _},
+        path::PathBuf,
+    };
+
+    use rustls::{ClientConfig, ClientSession, ServerSession, Session};
+
+    use super::*;
+
+    /// Returns path to the test TLS fixture with the provided `name`.
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/tls")
+            .join(name)
+    }
+
+    /// Moves all the pending TLS data from one [`Session`] to another.
+    fn transfer(from: &mut dyn Session, to: &mut dyn Session) {
+        let mut buf = Vec::new();
+        while from.wants_write() {
+            let _ = from.write_tls(&mut buf).unwrap();
+        }
+        let mut buf = buf.as_slice();
+        while !buf.is_empty() {
+            let _ = to.read_tls(&mut buf).unwrap();
+        }
+    }
+
+    #[test]
+    fn disabled_without_certs() {
+        assert!(server_config(&Tls::default()).unwrap().is_none());
+    }
+
+    #[test]
+    fn errors_on_missing_files() {
+        let tls = Tls {
+            default: Some(TlsCert {
+                cert: PathBuf::from("/nonexistent/cert.pem"),
+                key: PathBuf::from("/nonexistent/key.pem"),
+                ocsp: None,
+            }),
+            sni: HashMap::new(),
+        };
+        assert!(server_config(&tls).is_err());
+    }
+
+    #[test]
+    fn handshakes_with_sni_cert() {
+        let tls = Tls {
+            default: None,
+            sni: vec![(
+                "Medea.Test".to_owned(),
+                TlsCert {
+                    cert: fixture("cert.pem"),
+                    key: fixture("key.pem"),
+                    ocsp: None,
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+        let mut server = ServerSession::new(&Arc::new(
+            server_config(&tls).unwrap().unwrap(),
+        ));
+
+        let mut client_config = ClientConfig::new();
+        let _ = client_config
+            .root_store
+            .add_pem_file(&mut open(&fixture("ca.pem")).unwrap())
+            .unwrap();
+        let mut client = ClientSession::new(
+            &Arc::new(client_config),
+            webpki::DNSNameRef::try_from_ascii_str("medea.test").unwrap(),
+        );
+
+        for _ in 0..10 {
+            if !client.is_handshaking() && !server.is_handshaking() {
+                break;
+            }
+            transfer(&mut client, &mut server);
+            server.process_new_packets().unwrap();
+            transfer(&mut server, &mut client);
+            client.process_new_packets().unwrap();
+        }
+        assert!(!client.is_handshaking());
+        assert!(!server.is_handshaking());
+        assert_eq!(server.get_sni_hostname(), Some("medea.test"));
+
+        client.write_all(b"ping").unwrap();
+        transfer(&mut client, &mut server);
+        server.process_new_packets().unwrap();
+        let mut received = [0; 4];
+        server.read_exact(&mut received).unwrap();
+        assert_eq!(&received, b"ping");
+    }
+
+    #[test]
+    fn handshake_fails_for_unknown_server_name() {
+        let tls = Tls {
+            default: None,
+            sni: vec![(
+                "medea.test".to_owned(),
+                TlsCert {
+                    cert: fixture("cert.pem"),
+                    key: fixture("key.pem"),
+                    ocsp: None,
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+        let mut server = ServerSession::new(&Arc::new(
+            server_config(&tls).unwrap().unwrap(),
+        ));
+        let mut client = ClientSession::new(
+            &Arc::new(ClientConfig::new()),
+            webpki::DNSNameRef::try_from_ascii_str("unknown.test").unwrap(),
+        );
+
+        transfer(&mut client, &mut server);
+        assert!(server.process_new_packets().is_err());
+    }
+}
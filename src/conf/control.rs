@@ -2,6 +2,8 @@
 //!
 //! [Control API]: https://tinyurl.com/yxsqplq7
 
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 
@@ -16,6 +18,95 @@ pub struct ControlApi {
     /// [Control API]: https://tinyurl.com/yxsqplq7
     #[default = "specs/"]
     pub static_specs_dir: String,
+
+    /// Path to directory with `Room` templates, which `Room`s may be created
+    /// from via [Control API] with the provided parameters.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[default = "templates/"]
+    pub templates_dir: String,
+
+    /// Maximum number of events stored in a journal of each `Room`, which may
+    /// be replayed via [Control API] by clients recovering from a downtime.
+    ///
+    /// Once exceeded, the oldest events are evicted from the journal. Zero
+    /// disables the journal.
+    ///
+    /// Defaults to `1000`.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[default = 1000]
+    pub journal_capacity: usize,
+
+    /// Authentication settings of [Control API] gRPC server.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub auth: ControlApiAuth,
+
+    /// Audit log settings of [Control API] mutations.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub audit: ControlApiAudit,
+}
+
+/// Audit log settings of [Control API] mutations.
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ControlApiAudit {
+    /// Indicator whether every [Control API] mutation should be recorded into
+    /// the audit log.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub enabled: bool,
+
+    /// URL of a webhook, which audit log records are sent to via HTTP `POST`
+    /// requests with a JSON body.
+    ///
+    /// Records are sent one by one, and ones not fitting into the queue of
+    /// 1024 pending records are not sent (but still logged).
+    ///
+    /// [`None`] disables sending audit log records to a webhook.
+    pub webhook_url: Option<String>,
+}
+
+/// Authentication settings of [Control API] gRPC server.
+///
+/// Any combination of the options may be enabled. If none is enabled, then
+/// any client is allowed to use [Control API].
+///
+/// [Control API]: https://tinyurl.com/yxsqplq7
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ControlApiAuth {
+    /// Static API keys, one of which must be provided by a client in an
+    /// `authorization: Bearer <key>` metadata of every call.
+    ///
+    /// Empty list disables API keys authentication.
+    pub api_keys: Vec<String>,
+
+    /// Static API keys, one of which must be provided by a client in an
+    /// `authorization: Bearer <key>` metadata of admin-only calls (like
+    /// `DumpRoom`).
+    ///
+    /// These keys authenticate any other call as well.
+    ///
+    /// Empty list disables admin-only calls.
+    pub admin_api_keys: Vec<String>,
+
+    /// Path to PEM-encoded CA certificates file, which client certificates
+    /// must be signed with ([mTLS]).
+    ///
+    /// Requires TLS of [Control API] gRPC server being enabled.
+    ///
+    /// [`None`] disables client certificates authentication.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    /// [mTLS]: https://en.wikipedia.org/wiki/Mutual_authentication
+    pub client_ca: Option<PathBuf>,
 }
 
 #[cfg(test)]
@@ -30,6 +121,11 @@ mod spec {
         let default_conf = Conf::default();
         let env_conf = overrided_by_env_conf!(
             "MEDEA_CONTROL__STATIC_SPECS_DIR" => "test/",
+            "MEDEA_CONTROL__TEMPLATES_DIR" => "tpl/",
+            "MEDEA_CONTROL__JOURNAL_CAPACITY" => "10",
+            "MEDEA_CONTROL__AUTH__CLIENT_CA" => "/tls/ca.pem",
+            "MEDEA_CONTROL__AUDIT__ENABLED" => "true",
+            "MEDEA_CONTROL__AUDIT__WEBHOOK_URL" => "http://audit/records",
         );
 
         assert_ne!(
@@ -38,5 +134,19 @@ mod spec {
         );
 
         assert_eq!(env_conf.control.static_specs_dir, "test/");
+        assert_eq!(env_conf.control.templates_dir, "tpl/");
+        assert_ne!(
+            default_conf.control.journal_capacity,
+            env_conf.control.journal_capacity,
+        );
+        assert_eq!(env_conf.control.journal_capacity, 10);
+        assert_eq!(env_conf.control.auth.client_ca, Some("/tls/ca.pem".into()));
+        assert!(env_conf.control.auth.api_keys.is_empty());
+        assert!(env_conf.control.auth.admin_api_keys.is_empty());
+        assert!(env_conf.control.audit.enabled);
+        assert_eq!(
+            env_conf.control.audit.webhook_url.as_deref(),
+            Some("http://audit/records"),
+        );
     }
 }
@@ -155,6 +155,63 @@ pub struct Coturn {
     #[default = "PASS"]
     pub pass: Cow<'static, str>,
 
+    /// Number of [Coturn] users pre-allocated for the upcoming `Peer`s of each
+    /// `Room`, so `Peer`s creation doesn't wait for [Coturn] database.
+    ///
+    /// Zero disables pre-allocation.
+    ///
+    /// Defaults to `4`.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    #[default = 4]
+    pub pool_size: u32,
+
+    /// ID of this Medea node, prefixing names of all the [Coturn] users
+    /// created by it.
+    ///
+    /// Each Medea node sharing the same [Coturn] database should have its own
+    /// unique ID, if [`Coturn::remove_stale_users`] is enabled.
+    ///
+    /// Must be non-empty and must not contain `_` symbol (separating parts of
+    /// [Coturn] users names) and [Redis] glob metacharacters (`*`, `?`, `[`,
+    /// `]`, `\`).
+    ///
+    /// Defaults to `medea`.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    #[default = "medea"]
+    #[serde(deserialize_with = "Coturn::parse_node_id")]
+    pub node_id: Cow<'static, str>,
+
+    /// Indicator whether all the [Coturn] users prefixed with
+    /// [`Coturn::node_id`] should be considered as stale ones (left after an
+    /// abnormal shutdown) and removed on startup.
+    ///
+    /// Enable only if [`Coturn::node_id`] is unique among all the Medea nodes
+    /// sharing the same [Coturn] database, otherwise live users of other nodes
+    /// will be removed.
+    ///
+    /// Note, that [Coturn] users created before [`Coturn::node_id`] was
+    /// introduced (named without any node prefix) are never removed on
+    /// startup, so should be removed manually, if left.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    pub remove_stale_users: bool,
+
+    /// Indicator whether allocation events of [Coturn] should be received
+    /// from its [Redis] database, detecting which `Peer`s are relayed.
+    ///
+    /// Requires [Coturn] to publish its statistics into [Redis] (see its
+    /// `--redis-statsdb` option).
+    ///
+    /// Defaults to `false`.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    /// [Redis]: https://redis.io
+    pub relay_detection: bool,
+
     /// Database settings
     pub db: Db,
 
@@ -170,6 +227,36 @@ impl Coturn {
     pub fn addr(&self) -> String {
         format!("{}:{}", self.host, self.port)
     }
+
+    /// Parses [`Coturn::node_id`] from the provided [`Deserializer`].
+    ///
+    /// # Errors
+    ///
+    /// If parsed [`Coturn::node_id`] is empty or contains `_` symbol, being
+    /// a separator of [Coturn] users names parts, or any of [Redis] glob
+    /// metacharacters, being used to find [Coturn] users of this node.
+    ///
+    /// [Coturn]: https://github.com/coturn/coturn
+    /// [Redis]: https://redis.io
+    fn parse_node_id<'de, D>(d: D) -> Result<Cow<'static, str>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let id = String::deserialize(d)?;
+        if id.is_empty() {
+            return Err(D::Error::custom("Empty value is not allowed"));
+        }
+        if id.contains('_') {
+            return Err(D::Error::custom("`_` symbol is not allowed"));
+        }
+        if let Some(c) = id.chars().find(|c| "*?[]\\".contains(*c)) {
+            return Err(D::Error::custom(format!(
+                "`{}` symbol is not allowed",
+                c,
+            )));
+        }
+        Ok(id.into())
+    }
 }
 
 /// Settings of databases which back [Coturn] storage.
@@ -382,16 +469,37 @@ mod spec {
             "MEDEA_ICE__COTURN__PORT" => "1234",
             "MEDEA_ICE__COTURN__USER" => "ferris",
             "MEDEA_ICE__COTURN__PASS" => "qwerty",
+            "MEDEA_ICE__COTURN__POOL_SIZE" => "0",
+            "MEDEA_ICE__COTURN__RELAY_DETECTION" => "true",
+            "MEDEA_ICE__COTURN__NODE_ID" => "node1",
+            "MEDEA_ICE__COTURN__REMOVE_STALE_USERS" => "true",
         );
 
         assert_ne!(default_conf.ice.coturn.host, env_conf.ice.coturn.host);
         assert_ne!(default_conf.ice.coturn.port, env_conf.ice.coturn.port);
         assert_ne!(default_conf.ice.coturn.user, env_conf.ice.coturn.user);
         assert_ne!(default_conf.ice.coturn.pass, env_conf.ice.coturn.pass);
+        assert_ne!(
+            default_conf.ice.coturn.pool_size,
+            env_conf.ice.coturn.pool_size,
+        );
+        assert_ne!(
+            default_conf.ice.coturn.relay_detection,
+            env_conf.ice.coturn.relay_detection,
+        );
+        assert_ne!(
+            default_conf.ice.coturn.node_id,
+            env_conf.ice.coturn.node_id,
+        );
 
         assert_eq!(env_conf.ice.coturn.host, "example.com");
         assert_eq!(env_conf.ice.coturn.port, 1234);
         assert_eq!(env_conf.ice.coturn.addr(), "example.com:1234");
+        assert_eq!(env_conf.ice.coturn.pool_size, 0);
+        assert!(env_conf.ice.coturn.relay_detection);
+        assert_eq!(env_conf.ice.coturn.node_id, "node1");
+        assert!(!default_conf.ice.coturn.remove_stale_users);
+        assert!(env_conf.ice.coturn.remove_stale_users);
     }
 
     #[test]
@@ -525,4 +633,28 @@ mod spec {
 
         assert!(conf.is_err());
     }
+
+    #[test]
+    #[serial]
+    fn disallows_invalid_coturn_node_id() {
+        let conf = try_overrided_by_env_conf!(
+            "MEDEA_ICE__COTURN__NODE_ID" => "node_1",
+        );
+
+        assert!(conf.is_err());
+
+        let conf = try_overrided_by_env_conf!(
+            "MEDEA_ICE__COTURN__NODE_ID" => "",
+        );
+
+        assert!(conf.is_err());
+
+        for id in &["node*", "node?", "node[1]", "node\\1"] {
+            let conf = try_overrided_by_env_conf!(
+                "MEDEA_ICE__COTURN__NODE_ID" => *id,
+            );
+
+            assert!(conf.is_err(), "`{}` node ID is allowed", id);
+        }
+    }
 }
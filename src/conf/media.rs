@@ -2,6 +2,8 @@
 
 use std::time::Duration;
 
+use derive_more::Display;
+use failure::Fail;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 
@@ -18,6 +20,153 @@ pub struct Media {
     #[default(Duration::from_secs(15))]
     #[serde(with = "humantime_serde")]
     pub init_timeout: Duration,
+
+    /// Maximum number of `Endpoint`s pairs connected concurrently (creating
+    /// their `Peer`s with TURN credentials) when a `Member` joins a `Room`.
+    ///
+    /// Defaults to `8`.
+    #[default = 8]
+    pub connect_concurrency: usize,
+
+    /// Number of consecutive connection quality estimations a new score must
+    /// persist for, before `Member`s are notified about it.
+    ///
+    /// Scores caused by `PeerConnection` state changes are notified about
+    /// immediately. `1` disables hysteresis.
+    ///
+    /// Defaults to `3`.
+    #[default = 3]
+    pub quality_hysteresis: u32,
+
+    /// Minimum interval between notifying `Member`s about their connection
+    /// quality score changes.
+    ///
+    /// Changes occurred during this interval are notified about once it
+    /// elapses.
+    ///
+    /// Defaults to `5s`.
+    #[default(Duration::from_secs(5))]
+    #[serde(with = "humantime_serde")]
+    pub quality_min_interval: Duration,
+
+    /// Timeout for a `Peer` to finish its negotiation (to be stuck in
+    /// `WaitLocalSdp` or `WaitRemoteSdp` state), after which it's removed
+    /// along with its partner `Peer`.
+    ///
+    /// Zero means that `Peer`s are never removed this way.
+    ///
+    /// Defaults to `30s`.
+    #[default(Duration::from_secs(30))]
+    #[serde(with = "humantime_serde")]
+    pub negotiation_timeout: Duration,
+
+    /// Maximum number of `Peer` pairs of a single `Room` (re)negotiating
+    /// simultaneously.
+    ///
+    /// Negotiations exceeding it are queued and started once the ongoing ones
+    /// finish, smoothing CPU usage spikes when a change touches every `Member`
+    /// of a `Room`.
+    ///
+    /// Zero means no limit.
+    ///
+    /// Requires non-zero [`Media::negotiation_timeout`], so `Peer`s which
+    /// never finish their negotiation don't hold the slots forever.
+    ///
+    /// Defaults to `0`.
+    #[default = 0]
+    pub max_concurrent_negotiations: usize,
+
+    /// Period, during which a `Peer` pair, left without any `MediaTrack`s
+    /// after removing the last `Endpoint` between its `Member`s, is kept alive
+    /// to be reused for new `Endpoint`s between them, avoiding full ICE/DTLS
+    /// setup.
+    ///
+    /// Zero means that such `Peer`s are removed immediately.
+    ///
+    /// Defaults to `0s`.
+    #[default(Duration::from_secs(0))]
+    #[serde(with = "humantime_serde")]
+    pub peer_linger_period: Duration,
+
+    /// Thresholds of received audio quality, lowering connection quality
+    /// scores of `Member`s.
+    pub audio_quality: AudioQuality,
+
+    /// Retention of the stats collected for calculating metrics of `Peer`s.
+    pub metrics_retention: MetricsRetention,
+}
+
+impl Media {
+    /// Validates this [`Media`] configuration.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`UnboundedNegotiationsError`] if
+    /// [`Media::max_concurrent_negotiations`] is limited, while
+    /// [`Media::negotiation_timeout`] is not.
+    pub fn validate(&self) -> Result<(), UnboundedNegotiationsError> {
+        if self.max_concurrent_negotiations != 0
+            && self.negotiation_timeout == Duration::from_secs(0)
+        {
+            return Err(UnboundedNegotiationsError);
+        }
+        Ok(())
+    }
+}
+
+/// Error of limiting [`Media::max_concurrent_negotiations`] without limiting
+/// [`Media::negotiation_timeout`], so a `Peer` never answering its
+/// negotiation would stall all the queued ones.
+#[derive(Debug, Display, Fail)]
+#[display(fmt = "`media.max_concurrent_negotiations` requires non-zero \
+           `media.negotiation_timeout`")]
+pub struct UnboundedNegotiationsError;
+
+/// Thresholds of received audio quality.
+///
+/// Audio quality sub-score is `High` while both thresholds are not exceeded,
+/// and goes down one level each time any of them is doubled (`Medium` above
+/// the threshold, `Low` above its double, `Poor` above its quadruple).
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct AudioQuality {
+    /// Average time audio samples spend in a jitter buffer.
+    ///
+    /// Defaults to `100ms`.
+    #[default(Duration::from_millis(100))]
+    #[serde(with = "humantime_serde")]
+    pub jitter_buffer_delay: Duration,
+
+    /// Percentage of concealed audio samples (synthesized instead of lost or
+    /// late ones) among all the received audio samples.
+    ///
+    /// Defaults to `3.0`.
+    #[default(3.0)]
+    pub concealed_samples_percent: f64,
+}
+
+/// Retention of the stats collected for calculating metrics of `Peer`s,
+/// bounding memory usage of long-lasting `Room`s.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct MetricsRetention {
+    /// Duration, after which stats of a `MediaTrack`, not being updated
+    /// anymore, are evicted.
+    ///
+    /// Should be greater than [`Media::max_lag`].
+    ///
+    /// Defaults to `1m`.
+    #[default(Duration::from_secs(60))]
+    #[serde(with = "humantime_serde")]
+    pub window: Duration,
+
+    /// Maximum number of stats samples stored for a single `MediaTrack`.
+    ///
+    /// The oldest samples are evicted once it's exceeded.
+    ///
+    /// Defaults to `100`.
+    #[default = 100]
+    pub length: usize,
 }
 
 #[cfg(test)]
@@ -26,7 +175,7 @@ mod spec {
 
     use serial_test::serial;
 
-    use crate::{conf::Conf, overrided_by_env_conf};
+    use crate::{conf::Conf, overrided_by_env_conf, try_overrided_by_env_conf};
 
     #[test]
     #[serial]
@@ -35,6 +184,16 @@ mod spec {
         let env_conf = overrided_by_env_conf!(
             "MEDEA_MEDIA__MAX_LAG" => "502ms",
             "MEDEA_MEDIA__INIT_TIMEOUT" => "503ms",
+            "MEDEA_MEDIA__CONNECT_CONCURRENCY" => "2",
+            "MEDEA_MEDIA__QUALITY_HYSTERESIS" => "5",
+            "MEDEA_MEDIA__QUALITY_MIN_INTERVAL" => "504ms",
+            "MEDEA_MEDIA__NEGOTIATION_TIMEOUT" => "505ms",
+            "MEDEA_MEDIA__MAX_CONCURRENT_NEGOTIATIONS" => "4",
+            "MEDEA_MEDIA__PEER_LINGER_PERIOD" => "508ms",
+            "MEDEA_MEDIA__AUDIO_QUALITY__JITTER_BUFFER_DELAY" => "506ms",
+            "MEDEA_MEDIA__AUDIO_QUALITY__CONCEALED_SAMPLES_PERCENT" => "5.5",
+            "MEDEA_MEDIA__METRICS_RETENTION__WINDOW" => "507ms",
+            "MEDEA_MEDIA__METRICS_RETENTION__LENGTH" => "7",
         );
 
         assert_ne!(default_conf.media.max_lag, env_conf.media.max_lag);
@@ -45,5 +204,94 @@ mod spec {
             env_conf.media.init_timeout,
         );
         assert_eq!(env_conf.media.init_timeout, Duration::from_millis(503));
+
+        assert_ne!(
+            default_conf.media.connect_concurrency,
+            env_conf.media.connect_concurrency,
+        );
+        assert_eq!(env_conf.media.connect_concurrency, 2);
+
+        assert_ne!(
+            default_conf.media.quality_hysteresis,
+            env_conf.media.quality_hysteresis,
+        );
+        assert_eq!(env_conf.media.quality_hysteresis, 5);
+
+        assert_ne!(
+            default_conf.media.quality_min_interval,
+            env_conf.media.quality_min_interval,
+        );
+        assert_eq!(
+            env_conf.media.quality_min_interval,
+            Duration::from_millis(504),
+        );
+
+        assert_ne!(
+            default_conf.media.negotiation_timeout,
+            env_conf.media.negotiation_timeout,
+        );
+        assert_eq!(
+            env_conf.media.negotiation_timeout,
+            Duration::from_millis(505),
+        );
+
+        assert_ne!(
+            default_conf.media.max_concurrent_negotiations,
+            env_conf.media.max_concurrent_negotiations,
+        );
+        assert_eq!(env_conf.media.max_concurrent_negotiations, 4);
+
+        assert_ne!(
+            default_conf.media.peer_linger_period,
+            env_conf.media.peer_linger_period,
+        );
+        assert_eq!(
+            env_conf.media.peer_linger_period,
+            Duration::from_millis(508),
+        );
+
+        assert_ne!(
+            default_conf.media.audio_quality.jitter_buffer_delay,
+            env_conf.media.audio_quality.jitter_buffer_delay,
+        );
+        assert_eq!(
+            env_conf.media.audio_quality.jitter_buffer_delay,
+            Duration::from_millis(506),
+        );
+        assert!(
+            (env_conf.media.audio_quality.concealed_samples_percent - 5.5)
+                .abs()
+                < f64::EPSILON
+        );
+
+        assert_ne!(
+            default_conf.media.metrics_retention.window,
+            env_conf.media.metrics_retention.window,
+        );
+        assert_eq!(
+            env_conf.media.metrics_retention.window,
+            Duration::from_millis(507),
+        );
+        assert_ne!(
+            default_conf.media.metrics_retention.length,
+            env_conf.media.metrics_retention.length,
+        );
+        assert_eq!(env_conf.media.metrics_retention.length, 7);
+    }
+
+    #[test]
+    #[serial]
+    fn requires_negotiation_timeout_for_limited_negotiations() {
+        assert!(try_overrided_by_env_conf!(
+            "MEDEA_MEDIA__MAX_CONCURRENT_NEGOTIATIONS" => "4",
+            "MEDEA_MEDIA__NEGOTIATION_TIMEOUT" => "0s",
+        )
+        .is_err());
+
+        assert!(try_overrided_by_env_conf!(
+            "MEDEA_MEDIA__MAX_CONCURRENT_NEGOTIATIONS" => "0",
+            "MEDEA_MEDIA__NEGOTIATION_TIMEOUT" => "0s",
+        )
+        .is_ok());
     }
 }
@@ -8,10 +8,14 @@ pub mod rpc;
 pub mod server;
 pub mod shutdown;
 
-use std::env;
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
 
-use config::{Config, Environment, File};
-use failure::Error;
+use config::{Config, ConfigError, Environment, File};
+use derive_more::Display;
+use failure::{Error, Fail};
 use serde::{Deserialize, Serialize};
 
 #[doc(inline)]
@@ -23,9 +27,25 @@ pub use self::{
 /// CLI argument that is responsible for holding application configuration
 /// file path.
 static APP_CONF_PATH_CMD_ARG_NAME: &str = "--conf";
+/// Alias of the [`APP_CONF_PATH_CMD_ARG_NAME`] CLI argument.
+static APP_CONF_PATH_CMD_ARG_ALIAS: &str = "--file";
 /// Environment variable that is responsible for holding application
 /// configuration file path.
 static APP_CONF_PATH_ENV_VAR_NAME: &str = "MEDEA_CONF";
+/// CLI argument that is responsible for holding application configuration
+/// profile name.
+static APP_CONF_PROFILE_CMD_ARG_NAME: &str = "--profile";
+/// Environment variable that is responsible for holding application
+/// configuration profile name.
+static APP_CONF_PROFILE_ENV_VAR_NAME: &str = "MEDEA_PROFILE";
+/// Name of the configuration file option listing the files it includes.
+static INCLUDE_OPTION_NAME: &str = "include";
+
+/// Error of a configuration file including itself, either directly or
+/// transitively.
+#[derive(Debug, Display, Fail)]
+#[display(fmt = "Configuration file `{}` includes itself", "_0.display()")]
+pub struct IncludeCycleError(PathBuf);
 
 /// Holds application config.
 #[derive(Clone, Debug, Deserialize, Serialize, Default)]
@@ -65,8 +85,14 @@ impl Conf {
     /// - default values;
     /// - configuration file, the name of which is given as a command line
     ///   parameter or environment variable;
+    /// - profile configuration file, if a profile name is given as a command
+    ///   line parameter or environment variable;
     /// - environment variables.
     ///
+    /// Each configuration file is preceded by the files listed in its
+    /// `include` option (in the listed order), so its own values override the
+    /// included ones.
+    ///
     /// # Errors
     ///
     /// Errors if parsing fails.
@@ -74,33 +100,168 @@ impl Conf {
         let mut cfg = Config::new();
 
         if let Some(path) = get_conf_file_name(env::args()) {
-            cfg.merge(File::with_name(&path))?;
+            merge_conf_files(
+                &mut cfg,
+                Path::new(&path),
+                get_conf_profile(env::args()).as_deref(),
+            )?;
         }
 
         cfg.merge(Environment::with_prefix("MEDEA").separator("__"))?;
 
-        Ok(cfg.try_into()?)
+        let conf: Self = cfg.try_into()?;
+        conf.media.validate()?;
+        Ok(conf)
+    }
+
+    /// Returns TOML representation of this [`Conf`] with values of all the
+    /// secret options replaced with [`REDACTED`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if this [`Conf`] cannot be represented as TOML.
+    pub fn to_redacted_toml(&self) -> Result<String, Error> {
+        let mut value = toml::Value::try_from(self)?;
+        redact_secrets(&mut value);
+        Ok(toml::to_string_pretty(&value)?)
+    }
+}
+
+/// Placeholder of the secret options values in the output of
+/// [`Conf::to_redacted_toml()`].
+pub const REDACTED: &str = "<redacted>";
+
+/// Names of the [`Conf`] options holding secrets.
+const SECRET_OPTIONS: &[&str] = &["admin_api_keys", "api_keys", "pass"];
+
+/// Recursively replaces values of all the [`SECRET_OPTIONS`] in the provided
+/// [`toml::Value`] with [`REDACTED`].
+fn redact_secrets(value: &mut toml::Value) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table.iter_mut() {
+                if SECRET_OPTIONS.contains(&key.as_str()) {
+                    *value = toml::Value::String(REDACTED.to_owned());
+                } else {
+                    redact_secrets(value);
+                }
+            }
+        }
+        toml::Value::Array(values) => {
+            values.iter_mut().for_each(redact_secrets)
+        }
+        _ => (),
+    }
+}
+
+/// Merges the configuration file by the provided `path` into the provided
+/// [`Config`], followed by the file of the provided `profile` (if any).
+///
+/// Profile file is looked up next to the configuration file and is named
+/// after it (`config.toml` -> `config.production.toml`).
+fn merge_conf_files(
+    cfg: &mut Config,
+    path: &Path,
+    profile: Option<&str>,
+) -> Result<(), Error> {
+    merge_conf_file(cfg, path, &mut Vec::new())?;
+    if let Some(profile) = profile {
+        merge_conf_file(
+            cfg,
+            &profile_file_path(path, profile),
+            &mut Vec::new(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Merges the configuration file by the provided `path` into the provided
+/// [`Config`], preceded by all the files listed in its `include` option.
+///
+/// Included paths are relative to the including file. `parents` are the
+/// files currently being included, used to detect include cycles.
+fn merge_conf_file(
+    cfg: &mut Config,
+    path: &Path,
+    parents: &mut Vec<PathBuf>,
+) -> Result<(), Error> {
+    if parents.iter().any(|p| p == path) {
+        return Err(IncludeCycleError(path.to_owned()).into());
+    }
+    let file = || File::with_name(&path.to_string_lossy());
+
+    let includes: Vec<String> =
+        match Config::new().merge(file())?.get(INCLUDE_OPTION_NAME) {
+            Ok(includes) => includes,
+            Err(ConfigError::NotFound(_)) => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+    if !includes.is_empty() {
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        parents.push(path.to_owned());
+        for include in includes {
+            merge_conf_file(cfg, &dir.join(include), parents)?;
+        }
+        parents.pop();
     }
+
+    cfg.merge(file())?;
+    Ok(())
+}
+
+/// Returns path to the file of the provided configuration `profile`, based on
+/// the provided path of the base configuration file.
+fn profile_file_path(path: &Path, profile: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = path.extension().map_or_else(
+        || String::from("toml"),
+        |ext| ext.to_string_lossy().into_owned(),
+    );
+    path.with_file_name(format!("{}.{}.{}", stem, profile, ext))
 }
 
 /// Returns the path to the configuration file, if it's set via CLI `args`
 /// or environment variables.
 fn get_conf_file_name<T>(args: T) -> Option<String>
+where
+    T: IntoIterator<Item = String>,
+{
+    get_arg_or_env(
+        args,
+        &[APP_CONF_PATH_CMD_ARG_NAME, APP_CONF_PATH_CMD_ARG_ALIAS],
+        APP_CONF_PATH_ENV_VAR_NAME,
+    )
+}
+
+/// Returns the name of the configuration profile, if it's set via CLI `args`
+/// or environment variables.
+fn get_conf_profile<T>(args: T) -> Option<String>
+where
+    T: IntoIterator<Item = String>,
+{
+    get_arg_or_env(
+        args,
+        &[APP_CONF_PROFILE_CMD_ARG_NAME],
+        APP_CONF_PROFILE_ENV_VAR_NAME,
+    )
+}
+
+/// Returns non-empty value of the CLI argument with one of the provided
+/// `names`, or of the provided environment variable, if any.
+fn get_arg_or_env<T>(args: T, names: &[&str], env_var: &str) -> Option<String>
 where
     T: IntoIterator<Item = String>,
 {
     // First, check CLI arguments as they have the highest priority.
     let mut args = args
         .into_iter()
-        .skip_while(|x| x != APP_CONF_PATH_CMD_ARG_NAME);
+        .skip_while(|x| !names.contains(&x.as_str()));
     if args.next().is_some() {
         return args.next().filter(|v| !v.is_empty());
     }
 
     // Then check env var.
-    env::var(APP_CONF_PATH_ENV_VAR_NAME)
-        .ok()
-        .filter(|v| !v.is_empty())
+    env::var(env_var).ok().filter(|v| !v.is_empty())
 }
 
 #[cfg(test)]
@@ -224,4 +385,101 @@ pub(crate) mod spec {
         );
         env::remove_var(APP_CONF_PATH_ENV_VAR_NAME);
     }
+
+    #[test]
+    #[serial]
+    fn get_conf_file_name_spec_arg_alias() {
+        env::remove_var(APP_CONF_PATH_ENV_VAR_NAME);
+        assert_eq!(
+            get_conf_file_name(vec![
+                "check-config".to_owned(),
+                APP_CONF_PATH_CMD_ARG_ALIAS.to_owned(),
+                "arg_path".to_owned(),
+            ]),
+            Some("arg_path".to_owned()),
+        );
+    }
+
+    #[test]
+    fn redacts_secrets() {
+        let mut conf = Conf::default();
+        conf.control.auth.api_keys = vec!["secret_key".to_owned()];
+
+        let toml = conf.to_redacted_toml().unwrap();
+        assert!(!toml.contains("secret_key"));
+        assert!(toml.contains(REDACTED));
+
+        let parsed: toml::Value = toml::from_str(&toml).unwrap();
+        assert_eq!(
+            parsed["control"]["auth"]["api_keys"].as_str(),
+            Some(REDACTED),
+        );
+        assert_eq!(parsed["ice"]["coturn"]["pass"].as_str(), Some(REDACTED));
+    }
+
+    /// Parses [`Conf`] from the configuration file by the provided `path`
+    /// and the file of the provided `profile`.
+    fn parse_files(path: &Path, profile: Option<&str>) -> Result<Conf, Error> {
+        let mut cfg = Config::new();
+        merge_conf_files(&mut cfg, path, profile)?;
+        Ok(cfg.try_into()?)
+    }
+
+    #[test]
+    fn profile_overrides_base_and_includes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("ice.toml"),
+            "[ice.coturn]\nhost = \"included\"\nport = 1234\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("config.toml"),
+            "include = [\"ice.toml\"]\n[ice.coturn]\nhost = \"base\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("config.production.toml"),
+            "[ice.coturn]\nport = 4321\n",
+        )
+        .unwrap();
+        let path = dir.path().join("config.toml");
+
+        let conf = parse_files(&path, None).unwrap();
+        assert_eq!(conf.ice.coturn.host, "base");
+        assert_eq!(conf.ice.coturn.port, 1234);
+
+        let conf = parse_files(&path, Some("production")).unwrap();
+        assert_eq!(conf.ice.coturn.host, "base");
+        assert_eq!(conf.ice.coturn.port, 4321);
+
+        assert!(parse_files(&path, Some("staging")).is_err());
+    }
+
+    #[test]
+    fn include_cycle_is_error() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.toml"), "include = [\"b.toml\"]\n")
+            .unwrap();
+        std::fs::write(dir.path().join("b.toml"), "include = [\"a.toml\"]\n")
+            .unwrap();
+
+        let err = parse_files(&dir.path().join("a.toml"), None).unwrap_err();
+        assert!(err.downcast_ref::<IncludeCycleError>().is_some());
+    }
+
+    #[test]
+    #[serial]
+    fn get_conf_profile_spec() {
+        env::set_var(APP_CONF_PROFILE_ENV_VAR_NAME, "staging");
+        assert_eq!(get_conf_profile(Vec::new()), Some("staging".to_owned()));
+        assert_eq!(
+            get_conf_profile(vec![
+                APP_CONF_PROFILE_CMD_ARG_NAME.to_owned(),
+                "production".to_owned(),
+            ]),
+            Some("production".to_owned()),
+        );
+        env::remove_var(APP_CONF_PROFILE_ENV_VAR_NAME);
+    }
 }
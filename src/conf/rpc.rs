@@ -1,12 +1,13 @@
 //! RPC connection settings.
 
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
+use medea_client_api_proto::MAX_MESSAGE_SIZE;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
 
 /// RPC connection settings.
-#[derive(Clone, Copy, Debug, Deserialize, Serialize, SmartDefault)]
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
 #[serde(default)]
 pub struct Rpc {
     /// Duration, after which remote RPC client will be considered idle
@@ -40,6 +41,89 @@ pub struct Rpc {
     #[default(Duration::from_secs(3))]
     #[serde(with = "humantime_serde")]
     pub ping_interval: Duration,
+
+    /// Additional duration, by which the remote RPC client is allowed to
+    /// exceed its `idle_timeout` before being considered idle.
+    ///
+    /// Tolerates network jitter of clients on flaky networks.
+    ///
+    /// Defaults to `0s`.
+    #[default(Duration::from_secs(0))]
+    #[serde(with = "humantime_serde")]
+    pub jitter_tolerance: Duration,
+
+    /// Number of consecutive `Ping`s, which the remote RPC client is allowed
+    /// to leave unanswered before being considered idle.
+    ///
+    /// The client is considered idle only once both this number and its
+    /// `idle_timeout` (with `jitter_tolerance`) are exceeded.
+    ///
+    /// `0` disables missed `Pong`s tracking.
+    ///
+    /// Defaults to `0`.
+    #[default(0)]
+    pub max_missed_pongs: u32,
+
+    /// Maximum number of application data messages, which a single remote
+    /// RPC client is allowed to relay to other clients of its room per
+    /// second.
+    ///
+    /// Messages exceeding this limit are dropped.
+    ///
+    /// Defaults to `10`.
+    #[default(10)]
+    pub app_data_rate_limit: u32,
+
+    /// Maximum number of `UpdateTracks` commands of a single remote RPC
+    /// client, which are applied immediately per second.
+    ///
+    /// Commands exceeding this limit are merged and applied at once when the
+    /// current second ends.
+    ///
+    /// `0` disables the limit.
+    ///
+    /// Defaults to `0`.
+    #[default(0)]
+    pub update_tracks_rate_limit: u32,
+
+    /// Interval of sending `Room` state checksums from the server to the
+    /// client, allowing the client to detect its state drift.
+    ///
+    /// `0s` disables sending state checksums.
+    ///
+    /// Defaults to `10s`.
+    #[default(Duration::from_secs(10))]
+    #[serde(with = "humantime_serde")]
+    pub state_checksum_interval: Duration,
+
+    /// Maximum size (in bytes) of a single message received from the remote
+    /// RPC client, including fragmented ones.
+    ///
+    /// The client sending a larger message is disconnected.
+    ///
+    /// Defaults to `524288` (512 KiB).
+    #[default(MAX_MESSAGE_SIZE)]
+    pub max_message_size: usize,
+
+    /// Number of the last `Event`s sent to each remote RPC client, which are
+    /// kept for replaying them to the client reconnecting within its
+    /// `reconnect_timeout`, instead of sending it the whole `Room` state.
+    ///
+    /// `0` disables `Event`s replaying.
+    ///
+    /// Defaults to `0`.
+    #[default(0)]
+    pub event_history_size: usize,
+
+    /// Key-value feature flags delivered to the remote RPC client along with
+    /// its RPC settings, allowing to roll out server-side features without
+    /// bumping the protocol version.
+    ///
+    /// It applies to all related pipelines as default value, but can be
+    /// overridden for each specific case via Control API.
+    ///
+    /// Defaults to no feature flags.
+    pub features: HashMap<String, String>,
 }
 
 #[cfg(test)]
@@ -60,6 +144,13 @@ mod spec {
         let env_conf = overrided_by_env_conf!(
             "MEDEA_RPC__IDLE_TIMEOUT" => "20s",
             "MEDEA_RPC__RECONNECT_TIMEOUT" => "30s",
+            "MEDEA_RPC__JITTER_TOLERANCE" => "2s",
+            "MEDEA_RPC__MAX_MISSED_PONGS" => "3",
+            "MEDEA_RPC__APP_DATA_RATE_LIMIT" => "5",
+            "MEDEA_RPC__UPDATE_TRACKS_RATE_LIMIT" => "10",
+            "MEDEA_RPC__STATE_CHECKSUM_INTERVAL" => "0s",
+            "MEDEA_RPC__MAX_MESSAGE_SIZE" => "1024",
+            "MEDEA_RPC__EVENT_HISTORY_SIZE" => "64",
         );
 
         assert_ne!(default_conf.rpc.idle_timeout, env_conf.rpc.idle_timeout);
@@ -70,6 +161,16 @@ mod spec {
 
         assert_eq!(env_conf.rpc.idle_timeout, Duration::from_secs(20));
         assert_eq!(env_conf.rpc.reconnect_timeout, Duration::from_secs(30));
+        assert_eq!(env_conf.rpc.jitter_tolerance, Duration::from_secs(2));
+        assert_eq!(env_conf.rpc.max_missed_pongs, 3);
+        assert_eq!(env_conf.rpc.app_data_rate_limit, 5);
+        assert_eq!(env_conf.rpc.update_tracks_rate_limit, 10);
+        assert_eq!(
+            env_conf.rpc.state_checksum_interval,
+            Duration::from_secs(0)
+        );
+        assert_eq!(env_conf.rpc.max_message_size, 1024);
+        assert_eq!(env_conf.rpc.event_history_size, 64);
     }
 
     #[test]
@@ -116,4 +217,30 @@ mod spec {
         assert_eq!(file_config.rpc.idle_timeout, Duration::from_secs(47));
         assert_eq!(file_env_config.rpc.idle_timeout, Duration::from_secs(48));
     }
+
+    #[test]
+    #[serial]
+    fn conf_parse_features_from_file() {
+        // Don't delete me! Otherwise temporary dir will be deleted.
+        let dir = tempfile::tempdir().unwrap();
+        let conf_path =
+            dir.path().join("test_config.toml").display().to_string();
+
+        let data = "[rpc.features]\ndelta_sync = \"true\"".to_owned();
+        fs::write(&conf_path, data).unwrap();
+
+        let new_config = overrided_by_env_conf!(
+            APP_CONF_PATH_ENV_VAR_NAME => &conf_path,
+        );
+
+        assert!(Conf::default().rpc.features.is_empty());
+        assert_eq!(
+            new_config
+                .rpc
+                .features
+                .get("delta_sync")
+                .map(String::as_str),
+            Some("true"),
+        );
+    }
 }
@@ -1,6 +1,12 @@
 //! Settings for application servers.
 
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::PathBuf,
+    time::Duration,
+};
 
 use derive_more::{Display, From};
 use serde::{Deserialize, Serialize};
@@ -16,6 +22,45 @@ pub struct ClientApiServer {
     ///
     /// [Client API]: https://tinyurl.com/yx9thsnr
     pub http: ClientApiHttpServer,
+
+    /// Pacing of clients joining `Room`s.
+    pub admission: AdmissionPacing,
+}
+
+/// Pacing of clients joining `Room`s, protecting a freshly (re)started media
+/// server from a storm of simultaneous reconnections.
+#[derive(Clone, Debug, Deserialize, Serialize, SmartDefault)]
+#[serde(default)]
+pub struct AdmissionPacing {
+    /// Maximum number of clients admitted per second, once the `ramp_up`
+    /// period is over.
+    ///
+    /// Zero disables pacing.
+    ///
+    /// Defaults to `0`.
+    #[default = 0]
+    pub rate: u32,
+
+    /// Maximum number of clients admitted at once.
+    ///
+    /// Defaults to `50`.
+    #[default = 50]
+    pub burst: u32,
+
+    /// Number of clients admitted per second right after the media server
+    /// start.
+    ///
+    /// Defaults to `5`.
+    #[default = 5]
+    pub initial_rate: u32,
+
+    /// Duration after the media server start, during which the admission rate
+    /// grows linearly from the `initial_rate` to the `rate`.
+    ///
+    /// Defaults to `30s`.
+    #[default(Duration::from_secs(30))]
+    #[serde(with = "humantime_serde")]
+    pub ramp_up: Duration,
 }
 
 /// [Client API] HTTP server settings.
@@ -49,6 +94,11 @@ pub struct ClientApiHttpServer {
     /// Defaults to `8080`.
     #[default = 8080]
     pub bind_port: u16,
+
+    /// TLS settings of HTTP server.
+    ///
+    /// TLS is disabled if no certificate is specified.
+    pub tls: Tls,
 }
 
 impl ClientApiHttpServer {
@@ -101,6 +151,115 @@ pub struct ControlApiGrpcServer {
     /// Defaults to `6565`.
     #[default = 6565]
     pub bind_port: u16,
+
+    /// TLS settings of gRPC server.
+    ///
+    /// TLS is disabled if no certificate is specified.
+    pub tls: Tls,
+}
+
+impl ControlApiGrpcServer {
+    /// Builds a [`SocketAddr`] from `bind_ip` and `bind_port`.
+    #[inline]
+    #[must_use]
+    pub fn bind_addr(&self) -> SocketAddr {
+        (self.bind_ip, self.bind_port).into()
+    }
+}
+
+/// TLS settings of a server.
+///
+/// Specifying only one of `cert` and `key` (or `ocsp` without them) is an
+/// error, rather than silently disabling TLS.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(try_from = "RawTls")]
+pub struct Tls {
+    /// Default certificate of a server.
+    ///
+    /// Is used for clients not indicating any server name, or indicating a
+    /// server name not listed in `sni`.
+    ///
+    /// TLS is disabled if [`None`] and `sni` is empty.
+    #[serde(flatten)]
+    pub default: Option<TlsCert>,
+
+    /// Certificates of a server selected by the server name indicated by
+    /// clients ([SNI]).
+    ///
+    /// [SNI]: https://en.wikipedia.org/wiki/Server_Name_Indication
+    pub sni: HashMap<String, TlsCert>,
+}
+
+impl Tls {
+    /// Indicates whether TLS is enabled with these [`Tls`] settings.
+    #[inline]
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        self.default.is_some() || !self.sni.is_empty()
+    }
+}
+
+/// [`Tls`] settings as they're specified in a config, before validation.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RawTls {
+    /// Path to PEM-encoded certificate chain file of the default certificate.
+    cert: Option<PathBuf>,
+
+    /// Path to PEM-encoded private key file of the default certificate.
+    key: Option<PathBuf>,
+
+    /// Path to DER-encoded [OCSP] response file of the default certificate.
+    ///
+    /// [OCSP]: https://en.wikipedia.org/wiki/OCSP_stapling
+    ocsp: Option<PathBuf>,
+
+    /// Certificates selected by the server name indicated by clients.
+    sni: HashMap<String, TlsCert>,
+}
+
+impl TryFrom<RawTls> for Tls {
+    type Error = &'static str;
+
+    fn try_from(raw: RawTls) -> Result<Self, Self::Error> {
+        let default = match (raw.cert, raw.key) {
+            (Some(cert), Some(key)) => Some(TlsCert {
+                cert,
+                key,
+                ocsp: raw.ocsp,
+            }),
+            (None, None) if raw.ocsp.is_none() => None,
+            (None, None) => {
+                return Err("TLS `ocsp` is specified without `cert` and `key`")
+            }
+            (Some(_), None) => {
+                return Err("TLS `cert` is specified without `key`")
+            }
+            (None, Some(_)) => {
+                return Err("TLS `key` is specified without `cert`")
+            }
+        };
+        Ok(Self {
+            default,
+            sni: raw.sni,
+        })
+    }
+}
+
+/// TLS certificate of a server.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TlsCert {
+    /// Path to PEM-encoded certificate chain file.
+    pub cert: PathBuf,
+
+    /// Path to PEM-encoded private key file (PKCS#8 or RSA).
+    pub key: PathBuf,
+
+    /// Path to DER-encoded [OCSP] response file, stapled to the certificate.
+    ///
+    /// [OCSP]: https://en.wikipedia.org/wiki/OCSP_stapling
+    #[serde(default)]
+    pub ocsp: Option<PathBuf>,
 }
 
 /// Settings for application servers.
@@ -150,6 +309,7 @@ mod client_http_spec {
             Ipv4Addr::new(5, 5, 5, 5)
         );
         assert_eq!(env_conf.server.client.http.bind_port, 1234);
+        assert!(!env_conf.server.client.http.tls.is_enabled());
         assert_eq!(
             env_conf.server.client.http.bind_addr(),
             "5.5.5.5:1234".parse().unwrap(),
@@ -157,6 +317,81 @@ mod client_http_spec {
     }
 }
 
+#[cfg(test)]
+mod admission_spec {
+    use std::time::Duration;
+
+    use serial_test::serial;
+
+    use crate::{conf::Conf, overrided_by_env_conf};
+
+    #[test]
+    #[serial]
+    fn overrides_defaults() {
+        let default_conf = Conf::default();
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_SERVER__CLIENT__ADMISSION__RATE" => "100",
+            "MEDEA_SERVER__CLIENT__ADMISSION__RAMP_UP" => "1m",
+        );
+
+        assert_eq!(default_conf.server.client.admission.rate, 0);
+        assert_eq!(env_conf.server.client.admission.rate, 100);
+        assert_eq!(
+            env_conf.server.client.admission.ramp_up,
+            Duration::from_secs(60),
+        );
+        assert_eq!(
+            env_conf.server.client.admission.burst,
+            default_conf.server.client.admission.burst,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tls_spec {
+    use std::path::PathBuf;
+
+    use serial_test::serial;
+
+    use crate::{conf::Conf, overrided_by_env_conf, try_overrided_by_env_conf};
+
+    #[test]
+    #[serial]
+    fn overrides_defaults() {
+        let env_conf = overrided_by_env_conf!(
+            "MEDEA_SERVER__CLIENT__HTTP__TLS__CERT" => "/tls/cert.pem",
+            "MEDEA_SERVER__CLIENT__HTTP__TLS__KEY" => "/tls/key.pem",
+        );
+
+        let tls = &env_conf.server.client.http.tls;
+        assert!(tls.is_enabled());
+        let default = tls.default.as_ref().unwrap();
+        assert_eq!(default.cert, PathBuf::from("/tls/cert.pem"));
+        assert_eq!(default.key, PathBuf::from("/tls/key.pem"));
+        assert_eq!(default.ocsp, None);
+        assert!(!env_conf.server.control.grpc.tls.is_enabled());
+    }
+
+    #[test]
+    #[serial]
+    fn disallows_half_configured_tls() {
+        let conf = try_overrided_by_env_conf!(
+            "MEDEA_SERVER__CLIENT__HTTP__TLS__CERT" => "/tls/cert.pem",
+        );
+        assert!(conf.is_err());
+
+        let conf = try_overrided_by_env_conf!(
+            "MEDEA_SERVER__CONTROL__GRPC__TLS__KEY" => "/tls/key.pem",
+        );
+        assert!(conf.is_err());
+
+        let conf = try_overrided_by_env_conf!(
+            "MEDEA_SERVER__CLIENT__HTTP__TLS__OCSP" => "/tls/ocsp.der",
+        );
+        assert!(conf.is_err());
+    }
+}
+
 #[cfg(test)]
 mod control_grpc_spec {
     use std::net::Ipv4Addr;
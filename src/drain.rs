@@ -0,0 +1,52 @@
+//! Drain mode, in which no new `Member`s are allowed to join `Room`s, while
+//! the already joined ones may finish their sessions.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Shared switch of the drain mode.
+///
+/// Once switched on, it cannot be switched off, as draining is meant to
+/// precede a graceful shutdown.
+#[derive(Clone, Debug, Default)]
+pub struct Drain(Arc<AtomicBool>);
+
+impl Drain {
+    /// Switches the drain mode on.
+    ///
+    /// Returns `false` if it has been switched on already.
+    #[inline]
+    pub fn start(&self) -> bool {
+        !self.0.swap(true, Ordering::SeqCst)
+    }
+
+    /// Indicates whether the drain mode is switched on.
+    #[inline]
+    #[must_use]
+    pub fn is_draining(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Progress of draining, reporting what still prevents it from being
+/// completed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DrainProgress {
+    /// Number of `Room`s still having connected `Member`s.
+    pub rooms: usize,
+
+    /// Number of `Member`s still connected.
+    pub members: usize,
+}
+
+impl DrainProgress {
+    /// Indicates whether draining is completed, so no `Member`s are connected
+    /// anymore.
+    #[inline]
+    #[must_use]
+    pub fn is_drained(&self) -> bool {
+        self.members == 0
+    }
+}
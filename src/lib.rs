@@ -6,21 +6,27 @@
 
 #[macro_use]
 pub mod utils;
+pub mod admission;
 pub mod api;
 pub mod conf;
+pub mod drain;
 pub mod log;
 pub mod media;
 pub mod shutdown;
 pub mod signalling;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod turn;
 
 use std::sync::Arc;
 
 use crate::{
+    admission::Admission,
     api::control::callback::{
         clients::CallbackClientFactoryImpl, service::CallbackService,
     },
     conf::Conf,
+    drain::Drain,
     turn::TurnAuthService,
 };
 
@@ -37,6 +43,18 @@ pub struct AppContext {
 
     /// Service for sending Control API Callbacks.
     pub callbacks: CallbackService<CallbackClientFactoryImpl>,
+
+    /// Switch of the drain mode.
+    pub drain: Drain,
+
+    /// Pacer of clients joining [`Room`]s, shared by all of them.
+    ///
+    /// [`Room`]: signalling::Room
+    pub admission: Admission,
+
+    /// Switch of the chaos mode of `Room`s.
+    #[cfg(any(test, feature = "testing"))]
+    pub chaos: signalling::room::Chaos,
 }
 
 impl AppContext {
@@ -44,10 +62,15 @@ impl AppContext {
     #[inline]
     #[must_use]
     pub fn new(config: Conf, turn: Arc<dyn TurnAuthService>) -> Self {
+        let admission = Admission::new(&config.server.client.admission);
         Self {
             config: Arc::new(config),
             turn_service: turn,
             callbacks: CallbackService::default(),
+            drain: Drain::default(),
+            admission,
+            #[cfg(any(test, feature = "testing"))]
+            chaos: signalling::room::Chaos::default(),
         }
     }
 }
@@ -2,10 +2,15 @@
 
 #![forbid(non_ascii_idents, unsafe_code)]
 
+use std::env;
+
 use actix::{Actor, System};
 use failure::Error;
 use medea::{
-    api::{client::server::Server, control::grpc},
+    api::{
+        client::{health::HealthChecker, server::Server},
+        control::grpc,
+    },
     conf::Conf,
     log::{self, prelude::*},
     shutdown::{self, GracefulShutdown},
@@ -16,6 +21,11 @@ use medea::{
 
 fn main() -> Result<(), Error> {
     dotenv::dotenv().ok();
+    match env::args().nth(1).as_deref() {
+        Some("check-config") => return check_config(),
+        Some("print-config") => return print_config(),
+        _ => (),
+    }
     let config = Conf::parse()?;
 
     if let Some(lvl) = config.log.level() {
@@ -45,9 +55,14 @@ fn main() -> Result<(), Error> {
 
         medea::api::control::start_static_rooms(&room_service).await?;
 
+        let health = HealthChecker::new(
+            room_service.clone(),
+            &app_context,
+            config.server.control.grpc.bind_addr(),
+        );
         let (grpc_server_addr, grpc_server_fut) =
-            grpc::server::run(room_service, &app_context);
-        let server = Server::run(room_repo, config)?;
+            grpc::server::run(room_service, &app_context)?;
+        let server = Server::run(room_repo, config, health)?;
 
         shutdown::subscribe(
             &graceful_shutdown,
@@ -69,3 +84,20 @@ fn main() -> Result<(), Error> {
     .map_err(|err: Error| err)?;
     sys.run().map_err(Into::into)
 }
+
+/// Validates the application configuration, failing if it's invalid.
+///
+/// Implements the `check-config` subcommand.
+fn check_config() -> Result<(), Error> {
+    Conf::parse()?;
+    println!("Configuration is valid");
+    Ok(())
+}
+
+/// Prints the effective application configuration with secrets redacted.
+///
+/// Implements the `print-config` subcommand.
+fn print_config() -> Result<(), Error> {
+    print!("{}", Conf::parse()?.to_redacted_toml()?);
+    Ok(())
+}
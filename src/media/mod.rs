@@ -1,6 +1,8 @@
 //! Representations of media and media connection establishment objects.
 
 pub mod peer;
+pub mod sdp_munging;
+pub mod sdp_quirks;
 pub mod track;
 
 #[doc(inline)]
@@ -8,5 +10,6 @@ pub use self::{
     peer::{
         Peer, PeerError, PeerStateMachine, Stable, WaitLocalSdp, WaitRemoteSdp,
     },
+    sdp_quirks::SdpQuirks,
     track::MediaTrack,
 };
@@ -53,25 +53,30 @@ use std::{
     convert::{TryFrom, TryInto as _},
     fmt,
     rc::Rc,
+    time::Instant,
 };
 
 use derive_more::Display;
 use failure::Fail;
 use medea_client_api_proto::{
-    state, AudioSettings, Direction, IceCandidate, MediaSourceKind, MediaType,
-    MemberId, NegotiationRole, PeerId as Id, PeerId, PeerUpdate, Track,
-    TrackId, TrackPatchCommand, TrackPatchEvent, VideoSettings,
+    state,
+    stats::{CandidateType, KnownCandidateType, NonExhaustive, Protocol},
+    AudioSettings, ContentHint, DegradationPreference, Direction, IceCandidate,
+    IceServer, MediaSourceKind, MediaType, MemberId, NegotiationRole,
+    OpusParameters, PeerId as Id, PeerId, PeerUpdate, Track, TrackId,
+    TrackPatchCommand, TrackPatchEvent, VideoSettings,
 };
 use medea_macro::{dispatchable, enum_delegate};
+use serde::Serialize;
 
 use crate::{
     api::control::endpoints::webrtc_publish_endpoint::PublishPolicy,
-    media::MediaTrack,
+    media::{sdp_munging::primary_ssrcs, MediaTrack},
     signalling::{
         elements::endpoints::{
             webrtc::WebRtcPublishEndpoint, Endpoint, WeakEndpoint,
         },
-        peers::Counter,
+        peers::{Counter, IdsExhausted},
     },
     turn::{IceUser, IceUsers},
 };
@@ -119,6 +124,32 @@ pub enum PeerError {
         _0
     )]
     MidsMismatch(TrackId),
+
+    /// [mid] is provided for a [`MediaTrack`] unknown to the [`Peer`].
+    ///
+    /// [mid]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    #[display(fmt = "Peer provided mid for unknown Track [{}]", _0)]
+    UnknownTrackMid(TrackId),
+
+    /// The same [mid] is provided for multiple [`MediaTrack`]s of the
+    /// [`Peer`].
+    ///
+    /// [mid]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    #[display(fmt = "Peer provided mid `{}` for multiple Tracks", _0)]
+    DuplicateMid(String),
+
+    /// [mid] is provided for a [`MediaTrack`] which is already negotiated with
+    /// another [mid].
+    ///
+    /// [mid]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    #[display(
+        fmt = "Peer provided mid `{}` for Track [{}] already negotiated with \
+               mid `{}`",
+        _1,
+        _0,
+        _2
+    )]
+    MidChanged(TrackId, String, String),
 }
 
 impl PeerError {
@@ -189,12 +220,27 @@ impl PeerError {
     pub fn add_ice_candidate(&mut self, ice_candidate: IceCandidate)
 )]
 #[enum_delegate(pub fn is_empty(&self) -> bool)]
+#[enum_delegate(pub fn pending_changes_count(&self) -> usize)]
+#[enum_delegate(pub fn pending_changes(&self) -> &[PeerChange])]
+#[enum_delegate(pub fn scheduled_changes(&self) -> &[PeerChange])]
+#[enum_delegate(pub fn is_renegotiation_scheduled(&self) -> bool)]
 #[enum_delegate(pub fn ice_candidates(&self) -> &HashSet<IceCandidate>)]
 #[enum_delegate(pub fn is_ice_restart(&self) -> bool)]
+#[enum_delegate(
+    pub fn unacked_removed_tracks(&self) -> &HashSet<TrackId>
+)]
+#[enum_delegate(pub fn ack_removed_tracks(&mut self, track_ids: &[TrackId]))]
 #[enum_delegate(pub fn negotiation_role(&self) -> Option<NegotiationRole>)]
 #[enum_delegate(pub fn is_known_to_remote(&self) -> bool)]
 #[enum_delegate(pub fn force_commit_partner_changes(&mut self))]
 #[enum_delegate(pub fn set_initialized(&mut self))]
+#[enum_delegate(pub fn negotiation_started_at(&self) -> Option<Instant>)]
+#[enum_delegate(
+    pub fn selected_candidate_pair(&self) -> Option<&SelectedCandidatePair>
+)]
+#[enum_delegate(
+    pub fn set_selected_candidate_pair(&mut self, pair: SelectedCandidatePair)
+)]
 #[derive(Debug)]
 pub enum PeerStateMachine {
     WaitLocalSdp(Peer<WaitLocalSdp>),
@@ -214,6 +260,7 @@ impl PeerStateMachine {
                     state::Sender {
                         id: sender.id(),
                         mid: sender.mid(),
+                        ssrc: sender.ssrc(),
                         media_type: sender.media_type().clone(),
                         receivers: vec![self.partner_member_id().clone()],
                         enabled_individual: sender
@@ -238,6 +285,7 @@ impl PeerStateMachine {
                     state::Receiver {
                         id: *id,
                         mid: receiver.mid(),
+                        ssrc: receiver.ssrc(),
                         media_type: receiver.media_type().clone(),
                         sender_id: self.partner_member_id().clone(),
                         enabled_individual: receiver
@@ -318,6 +366,26 @@ impl PeerStateMachine {
             }
         }
     }
+
+    /// Indicates whether the patches of the track with the provided
+    /// [`TrackId`] are applied by this [`PeerStateMachine`], meaning that it's
+    /// in a [`Stable`] state and has no such patches neither pending nor
+    /// scheduled.
+    #[must_use]
+    pub fn is_track_patch_applied(&self, track_id: TrackId) -> bool {
+        self.is_stable()
+            && !self
+                .pending_changes()
+                .iter()
+                .chain(self.scheduled_changes())
+                .any(|change| match change {
+                    PeerChange::TrackPatch(patch)
+                    | PeerChange::PartnerTrackPatch(patch) => {
+                        patch.id == track_id
+                    }
+                    _ => false,
+                })
+    }
 }
 
 impl fmt::Display for PeerStateMachine {
@@ -389,6 +457,32 @@ enum OnNegotiationFinish {
     Noop,
 }
 
+/// ICE candidate pair selected by a [`Peer`]'s `Client` for media
+/// transmission.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct SelectedCandidatePair {
+    /// Type of the local ICE candidate of this pair.
+    pub local_type: CandidateType,
+
+    /// Type of the remote ICE candidate of this pair.
+    pub remote_type: CandidateType,
+
+    /// Protocol used by the local ICE candidate of this pair to communicate
+    /// with the TURN server, if it's a `relay` one.
+    pub relay_protocol: Option<Protocol>,
+}
+
+impl SelectedCandidatePair {
+    /// Indicates whether media transmitted via this [`SelectedCandidatePair`]
+    /// is relayed through a TURN server.
+    #[inline]
+    #[must_use]
+    pub fn is_relayed(&self) -> bool {
+        let relay = NonExhaustive::Known(KnownCandidateType::Relay);
+        self.local_type == relay || self.remote_type == relay
+    }
+}
+
 /// State of a [`Peer`] initialization.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum InitializationState {
@@ -461,6 +555,10 @@ pub struct Context {
     /// Indicator whether an ICE restart should be performed for this [`Peer`].
     ice_restart: bool,
 
+    /// [`TrackId`]s of the removed [`MediaTrack`]s, which remote [`Peer`]
+    /// hasn't acknowledged releasing resources of yet.
+    unacked_removed_tracks: HashSet<TrackId>,
+
     /// Current [`NegotiationRole`] of this [`Peer`].
     negotiation_role: Option<NegotiationRole>,
 
@@ -469,6 +567,15 @@ pub struct Context {
 
     /// State of the [`Peer`] initialization.
     initialization_state: InitializationState,
+
+    /// Moment the ongoing negotiation of this [`Peer`] was started at.
+    ///
+    /// [`None`] if this [`Peer`] is in a [`Stable`] state.
+    negotiation_started_at: Option<Instant>,
+
+    /// ICE candidate pair currently used by this [`Peer`] for media
+    /// transmission, as reported by its `Client`.
+    selected_candidate_pair: Option<SelectedCandidatePair>,
 }
 
 /// [`Peer`] changes, that remote [`Peer`] is not aware of.
@@ -496,6 +603,17 @@ pub enum PeerChange {
 
     /// ICE restart request.
     IceRestart,
+
+    /// Update of the [`IceServer`]s and ICE transport policy of this [`Peer`],
+    /// that remote [`Peer`] is not aware of.
+    IcePolicyUpdated {
+        /// [`IceServer`]s of this [`Peer`].
+        ice_servers: Vec<IceServer>,
+
+        /// Indicator whether this [`Peer`] must be forcibly connected through
+        /// TURN.
+        force_relay: bool,
+    },
 }
 
 impl PeerChange {
@@ -506,11 +624,15 @@ impl PeerChange {
         matches!(
             self,
             Self::TrackPatch(TrackPatchEvent {
-                id: _,
                 enabled_individual: None,
                 enabled_general: None,
-                muted: Some(_),
-            })
+                muted,
+                content_hint,
+                degradation_preference,
+                ..
+            }) if muted.is_some()
+                || content_hint.is_some()
+                || degradation_preference.is_some()
         )
     }
 
@@ -523,6 +645,7 @@ impl PeerChange {
             PeerUpdate::Added(track) => Some(track),
             PeerUpdate::Updated(_)
             | PeerUpdate::IceRestart
+            | PeerUpdate::IcePolicyUpdated { .. }
             | PeerUpdate::Removed(_) => None,
         }
     }
@@ -552,6 +675,13 @@ impl PeerChange {
                 PeerUpdate::Updated(track_patch.clone())
             }
             Self::IceRestart => PeerUpdate::IceRestart,
+            Self::IcePolicyUpdated {
+                ice_servers,
+                force_relay,
+            } => PeerUpdate::IcePolicyUpdated {
+                ice_servers: ice_servers.clone(),
+                force_relay: *force_relay,
+            },
         }
     }
 
@@ -561,6 +691,7 @@ impl PeerChange {
             Self::AddSendTrack(_)
             | Self::AddRecvTrack(_)
             | Self::IceRestart
+            | Self::IcePolicyUpdated { .. }
             | Self::RemoveTrack(_) => false,
             Self::TrackPatch(_) | Self::PartnerTrackPatch(_) => true,
         }
@@ -647,6 +778,7 @@ impl<T> PeerChangeHandler for Peer<T> {
     fn on_remove_track(&mut self, track_id: TrackId) -> Self::Output {
         self.context.senders.remove(&track_id);
         self.context.receivers.remove(&track_id);
+        self.context.unacked_removed_tracks.insert(track_id);
         PeerChange::RemoveTrack(track_id)
     }
 
@@ -656,6 +788,21 @@ impl<T> PeerChangeHandler for Peer<T> {
         self.context.ice_restart = true;
         PeerChange::IceRestart
     }
+
+    /// Sets the `is_force_relayed` flag of this [`Context`] to the provided
+    /// value.
+    #[inline]
+    fn on_ice_policy_updated(
+        &mut self,
+        ice_servers: Vec<IceServer>,
+        force_relay: bool,
+    ) -> Self::Output {
+        self.context.is_force_relayed = force_relay;
+        PeerChange::IcePolicyUpdated {
+            ice_servers,
+            force_relay,
+        }
+    }
 }
 
 /// Deduper of the [`TrackPatchEvent`]s.
@@ -726,6 +873,35 @@ impl TrackPatchDeduper {
     }
 }
 
+/// Compacts the provided [`PeerUpdate`]s before sending them to a `Client`.
+///
+/// Successive [`PeerUpdate::Updated`]s are collapsed with
+/// [`TrackPatchEvent::merge_all()`], so each track is patched at most once
+/// between any other [`PeerUpdate`]s, which order is preserved.
+#[must_use]
+pub fn compact_updates(updates: Vec<PeerUpdate>) -> Vec<PeerUpdate> {
+    let mut compacted = Vec::with_capacity(updates.len());
+    let mut patches = Vec::new();
+    for update in updates {
+        if let PeerUpdate::Updated(patch) = update {
+            patches.push(patch);
+        } else {
+            compacted.extend(
+                TrackPatchEvent::merge_all(std::mem::take(&mut patches))
+                    .into_iter()
+                    .map(PeerUpdate::Updated),
+            );
+            compacted.push(update);
+        }
+    }
+    compacted.extend(
+        TrackPatchEvent::merge_all(patches)
+            .into_iter()
+            .map(PeerUpdate::Updated),
+    );
+    compacted
+}
+
 /// [RTCPeerConnection] representation.
 ///
 /// [RTCPeerConnection]: https://webrtcglossary.com/peerconnection/
@@ -783,11 +959,13 @@ impl<T> Peer<T> {
     ///
     /// [`Event::PeerUpdated`]: medea_client_api_proto::Event::PeerUpdated
     pub fn get_updates(&self) -> Vec<PeerUpdate> {
-        self.context
-            .pending_peer_changes
-            .iter()
-            .map(|c| c.as_peer_update(self.partner_member_id().clone()))
-            .collect()
+        compact_updates(
+            self.context
+                .pending_peer_changes
+                .iter()
+                .map(|c| c.as_peer_update(self.partner_member_id().clone()))
+                .collect(),
+        )
     }
 
     /// Returns [`Track`]s that remote [`Peer`] is not aware of.
@@ -860,6 +1038,24 @@ impl<T> Peer<T> {
         &self.context.senders
     }
 
+    /// Returns [`OpusParameters`] of the audio [`MediaTrack`]s sent by this
+    /// [`Peer`], mapped by their mids.
+    ///
+    /// [`MediaTrack`]s without a mid or [`OpusParameters`] are omitted.
+    #[must_use]
+    pub fn senders_opus_parameters(&self) -> HashMap<String, OpusParameters> {
+        self.context
+            .senders
+            .values()
+            .filter_map(|track| match track.media_type() {
+                MediaType::Audio(AudioSettings {
+                    opus: Some(opus), ..
+                }) => Some((track.mid()?, *opus)),
+                MediaType::Audio(_) | MediaType::Video(_) => None,
+            })
+            .collect()
+    }
+
     /// Forcibly commits all the [`PeerChange::PartnerTrackPatch`]es.
     pub fn force_commit_partner_changes(&mut self) {
         let mut partner_patches = Vec::new();
@@ -960,6 +1156,42 @@ impl<T> Peer<T> {
         self.context.is_known_to_remote
     }
 
+    /// Returns number of the [`PeerChange`]s of this [`Peer`], which are
+    /// either scheduled or not sent to remote [`Peer`] yet.
+    #[inline]
+    #[must_use]
+    pub fn pending_changes_count(&self) -> usize {
+        self.context.pending_peer_changes.len()
+            + self.context.peer_changes_queue.len()
+    }
+
+    /// Returns [`PeerChange`]s of this [`Peer`], which are applied, but remote
+    /// [`Peer`] is not aware of yet.
+    #[inline]
+    #[must_use]
+    pub fn pending_changes(&self) -> &[PeerChange] {
+        &self.context.pending_peer_changes
+    }
+
+    /// Returns [`PeerChange`]s of this [`Peer`], which are scheduled to be
+    /// applied once it's in a [`Stable`] state.
+    #[inline]
+    #[must_use]
+    pub fn scheduled_changes(&self) -> &[PeerChange] {
+        &self.context.peer_changes_queue
+    }
+
+    /// Indicates whether a new negotiation of this [`Peer`] should be started
+    /// once the ongoing one finishes.
+    #[inline]
+    #[must_use]
+    pub fn is_renegotiation_scheduled(&self) -> bool {
+        matches!(
+            self.context.on_negotiation_finish,
+            OnNegotiationFinish::Renegotiate
+        )
+    }
+
     /// Returns `true` if this [`Peer`] doesn't have any `Send` and `Recv`
     /// [`MediaTrack`]s.
     pub fn is_empty(&self) -> bool {
@@ -1053,6 +1285,24 @@ impl<T> Peer<T> {
         self.context.ice_restart
     }
 
+    /// Returns [`TrackId`]s of the removed [`MediaTrack`]s, which remote
+    /// [`Peer`] hasn't acknowledged releasing resources of yet.
+    #[inline]
+    #[must_use]
+    pub fn unacked_removed_tracks(&self) -> &HashSet<TrackId> {
+        &self.context.unacked_removed_tracks
+    }
+
+    /// Marks the removed [`MediaTrack`]s with the provided [`TrackId`]s as
+    /// released by remote [`Peer`].
+    ///
+    /// Unknown [`TrackId`]s are ignored.
+    pub fn ack_removed_tracks(&mut self, track_ids: &[TrackId]) {
+        for id in track_ids {
+            self.context.unacked_removed_tracks.remove(id);
+        }
+    }
+
     /// Returns the current [`NegotiationRole`] of this [`Peer`].
     #[inline]
     #[must_use]
@@ -1065,6 +1315,42 @@ impl<T> Peer<T> {
     pub fn set_initialized(&mut self) {
         self.context.initialization_state = InitializationState::Done;
     }
+
+    /// Discards [`PeerChange::IceRestart`]s scheduled for this [`Peer`], if
+    /// they are the only scheduled changes.
+    ///
+    /// Should be called when the partner [`Peer`] offers an ICE restart, as
+    /// answering it restarts ICE of this [`Peer`] as well, so renegotiating
+    /// these changes afterwards would be a no-op offer/answer cycle.
+    pub fn on_partner_ice_restart(&mut self) {
+        let queue = &mut self.context.peer_changes_queue;
+        if queue.iter().all(|c| matches!(c, PeerChange::IceRestart)) {
+            queue.clear();
+        }
+    }
+
+    /// Returns the moment the ongoing negotiation of this [`Peer`] was
+    /// started at, if there is any.
+    #[inline]
+    #[must_use]
+    pub fn negotiation_started_at(&self) -> Option<Instant> {
+        self.context.negotiation_started_at
+    }
+
+    /// Returns the [`SelectedCandidatePair`] of this [`Peer`], if its `Client`
+    /// has reported any.
+    #[inline]
+    #[must_use]
+    pub fn selected_candidate_pair(&self) -> Option<&SelectedCandidatePair> {
+        self.context.selected_candidate_pair.as_ref()
+    }
+
+    /// Sets the [`SelectedCandidatePair`] reported by this [`Peer`]'s
+    /// `Client`.
+    #[inline]
+    pub fn set_selected_candidate_pair(&mut self, pair: SelectedCandidatePair) {
+        self.context.selected_candidate_pair = Some(pair);
+    }
 }
 
 impl Peer<WaitLocalSdp> {
@@ -1098,31 +1384,68 @@ impl Peer<WaitLocalSdp> {
 
     /// Sets tracks [mid]s.
     ///
-    /// Provided [mid]s must have entries for all [`Peer`]s tracks.
+    /// Provided [mid]s must have entries for all [`Peer`]s tracks and only
+    /// for them. Nothing is set if the provided [mid]s fail validation.
     ///
     /// # Errors
     ///
     /// Errors with [`PeerError::MidsMismatch`] if [`Peer`] is sending
     /// [`MediaTrack`] without providing its [mid].
     ///
+    /// Errors with [`PeerError::UnknownTrackMid`] if [mid] is provided for a
+    /// [`MediaTrack`] unknown to this [`Peer`].
+    ///
+    /// Errors with [`PeerError::DuplicateMid`] if the same [mid] is provided
+    /// for multiple [`MediaTrack`]s.
+    ///
+    /// Errors with [`PeerError::MidChanged`] if the provided [mid] of a
+    /// [`MediaTrack`] differs from its already negotiated one, since [mid] of
+    /// a transceiver never changes once negotiated.
+    ///
     /// [mid]: https://developer.mozilla.org/docs/Web/API/RTCRtpTransceiver/mid
     pub fn set_mids(
         &mut self,
-        mut mids: HashMap<TrackId, String>,
+        mids: HashMap<TrackId, String>,
     ) -> Result<(), PeerError> {
-        let tracks = self
+        if let Some(id) = mids.keys().find(|id| {
+            !self.context.senders.contains_key(*id)
+                && !self.context.receivers.contains_key(*id)
+        }) {
+            return Err(PeerError::UnknownTrackMid(**id));
+        }
+        let tracks: Vec<_> = self
             .context
             .senders
-            .iter_mut()
-            .chain(self.context.receivers.iter_mut());
+            .values()
+            .chain(self.context.receivers.values())
+            .collect();
+        if let Some(track) = tracks.iter().find(|t| !mids.contains_key(&t.id()))
+        {
+            return Err(PeerError::MidsMismatch(track.id()));
+        }
+        let mut unique = HashSet::with_capacity(mids.len());
+        if let Some(mid) = mids.values().find(|mid| !unique.insert(*mid)) {
+            return Err(PeerError::DuplicateMid((*mid).clone()));
+        }
 
-        for (id, track) in tracks {
-            let mid = mids
-                .remove(&id)
-                .ok_or_else(|| PeerError::MidsMismatch(track.id()))?;
-            track.set_mid(mid)
+        for track in &tracks {
+            let mid = &mids[&track.id()];
+            if let Some(known) = track.mid() {
+                if known != *mid {
+                    return Err(PeerError::MidChanged(
+                        track.id(),
+                        mid.clone(),
+                        known,
+                    ));
+                }
+            }
         }
 
+        for track in tracks {
+            if track.mid().is_none() {
+                track.set_mid(mids[&track.id()].clone());
+            }
+        }
         Ok(())
     }
 
@@ -1137,6 +1460,23 @@ impl Peer<WaitLocalSdp> {
             }
         }
     }
+
+    /// Updates [SSRC]s of this [`Peer`]'s senders with the ones found in the
+    /// provided local SDP of this [`Peer`] by their [mid]s.
+    ///
+    /// As [`MediaTrack`]s are shared with the partner [`Peer`], its receivers
+    /// get the same [SSRC]s.
+    ///
+    /// [mid]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+    /// [SSRC]: https://tools.ietf.org/html/rfc3550#section-3
+    pub fn update_senders_ssrcs(&self, sdp: &str) {
+        let ssrcs = primary_ssrcs(sdp);
+        for sender in self.context.senders.values() {
+            if let Some(ssrc) = sender.mid().and_then(|mid| ssrcs.get(&mid)) {
+                sender.set_ssrc(*ssrc);
+            }
+        }
+    }
 }
 
 impl Peer<WaitRemoteSdp> {
@@ -1201,9 +1541,12 @@ impl Peer<Stable> {
             peer_updates_sub,
             ice_candidates: HashSet::new(),
             ice_restart: false,
+            unacked_removed_tracks: HashSet::new(),
             negotiation_role: None,
             on_negotiation_finish: OnNegotiationFinish::Noop,
             initialization_state: InitializationState::InProgress,
+            negotiation_started_at: None,
+            selected_candidate_pair: None,
         };
 
         Self {
@@ -1226,6 +1569,7 @@ impl Peer<Stable> {
         context.remote_sdp = None;
 
         context.negotiation_role = Some(NegotiationRole::Offerer);
+        context.negotiation_started_at = Some(Instant::now());
 
         Peer {
             context,
@@ -1245,6 +1589,7 @@ impl Peer<Stable> {
         let mut context = self.context;
         context.local_sdp = None;
         context.remote_sdp = None;
+        context.negotiation_started_at = Some(Instant::now());
 
         Peer {
             context,
@@ -1343,6 +1688,7 @@ impl Peer<Stable> {
         self.context.is_known_to_remote = true;
         self.context.pending_peer_changes.clear();
         self.context.negotiation_role = None;
+        self.context.negotiation_started_at = None;
         self.commit_scheduled_changes();
     }
 }
@@ -1373,29 +1719,129 @@ impl<'a> PeerChangesScheduler<'a> {
         }
     }
 
+    /// Schedules [`PeerChange::TrackPatch`] updating [`ContentHint`] and
+    /// [`DegradationPreference`] of the video [`Track`] with the provided
+    /// [`TrackId`].
+    ///
+    /// Doesn't require renegotiation.
+    pub fn patch_video_encoding(
+        &mut self,
+        track_id: TrackId,
+        content_hint: Option<ContentHint>,
+        degradation_preference: Option<DegradationPreference>,
+    ) {
+        let mut patch = TrackPatchEvent::new(track_id);
+        patch.content_hint = content_hint;
+        patch.degradation_preference = degradation_preference;
+        self.schedule_change(PeerChange::TrackPatch(patch));
+    }
+
+    /// Pauses/resumes media exchange of all the [`Track`]s of this [`Peer`]
+    /// (including the ones scheduled for addition), and schedules
+    /// [`PeerChange::TrackPatch`]es updating their general media exchange
+    /// state.
+    pub fn set_media_paused(&mut self, paused: bool) {
+        let scheduled_tracks: Vec<_> = self
+            .context
+            .peer_changes_queue
+            .iter()
+            .filter_map(|change| match change {
+                PeerChange::AddSendTrack(track)
+                | PeerChange::AddRecvTrack(track) => Some(Rc::clone(track)),
+                _ => None,
+            })
+            .collect();
+        let tracks: Vec<_> = self
+            .context
+            .senders
+            .values()
+            .chain(self.context.receivers.values())
+            .cloned()
+            .chain(scheduled_tracks)
+            .collect();
+
+        for track in tracks {
+            track.set_paused(paused);
+            let mut patch = TrackPatchEvent::new(track.id());
+            patch.enabled_general = Some(track.is_enabled_general());
+            self.schedule_change(PeerChange::TrackPatch(patch));
+        }
+    }
+
     /// Schedules [`PeerChange::IceRestart`].
     #[inline]
     pub fn restart_ice(&mut self) {
         self.schedule_change(PeerChange::IceRestart);
     }
 
+    /// Schedules [`PeerChange::IcePolicyUpdated`] switching forcible relaying
+    /// of this [`Peer`] and [`PeerChange::IceRestart`] to apply it.
+    pub fn update_ice_policy(&mut self, force_relay: bool) {
+        let ice_servers = Vec::<IceServer>::try_from(&self.context.ice_users)
+            .unwrap_or_default();
+        self.schedule_change(PeerChange::IcePolicyUpdated {
+            ice_servers,
+            force_relay,
+        });
+        self.restart_ice();
+    }
+
     /// Schedules `send` tracks adding to `self` and `recv` tracks for this
     /// `send` to `partner_peer`.
     ///
     /// Tracks will be added based on [`WebRtcPublishEndpoint::audio_settings`]
-    /// and [`WebRtcPublishEndpoint::video_settings`].
-    pub fn add_publisher(
+    /// and [`WebRtcPublishEndpoint::video_settings`], so a device video and a
+    /// display video are published simultaneously as separate tracks.
+    ///
+    /// Tracks of this [`Peer`] and the ones for which the provided
+    /// `is_in_use` function returns `true` are never reused by the provided
+    /// [`Counter`].
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`IdsExhausted`] if there are not enough free [`TrackId`]s
+    /// left. Nothing is scheduled in this case.
+    pub fn add_publisher<F>(
         &mut self,
         src: &WebRtcPublishEndpoint,
         partner_peer: &mut PeerStateMachine,
         tracks_counter: &Counter<TrackId>,
-    ) {
+        is_in_use: F,
+    ) -> Result<(), IdsExhausted>
+    where
+        F: Fn(TrackId) -> bool,
+    {
         let audio_settings = src.audio_settings();
-        if audio_settings.publish_policy != PublishPolicy::Disabled {
+        let video_settings = src.video_settings();
+        let publishes_audio =
+            audio_settings.publish_policy != PublishPolicy::Disabled;
+        let publishes_video =
+            video_settings.publish_policy != PublishPolicy::Disabled;
+        let publishes_display = publishes_video
+            && video_settings.display_publish_policy != PublishPolicy::Disabled;
+
+        let mut track_ids = Vec::new();
+        for _ in 0..[publishes_audio, publishes_video, publishes_display]
+            .iter()
+            .filter(|p| **p)
+            .count()
+        {
+            let id = tracks_counter.next_id(|id| {
+                track_ids.contains(&id)
+                    || self.context.senders.contains_key(&id)
+                    || self.context.receivers.contains_key(&id)
+                    || is_in_use(id)
+            })?;
+            track_ids.push(id);
+        }
+        let mut track_ids = track_ids.into_iter();
+
+        if publishes_audio {
             let track_audio = Rc::new(MediaTrack::new(
-                tracks_counter.next_id(),
+                track_ids.next().unwrap(),
                 MediaType::Audio(AudioSettings {
                     required: audio_settings.publish_policy.required(),
+                    opus: audio_settings.opus.map(Into::into),
                 }),
             ));
             self.add_sender(Rc::clone(&track_audio));
@@ -1405,10 +1851,9 @@ impl<'a> PeerChangesScheduler<'a> {
                 .add_receiver(track_audio);
         }
 
-        let video_settings = src.video_settings();
-        if video_settings.publish_policy != PublishPolicy::Disabled {
+        if publishes_video {
             let camera_video_track = Rc::new(MediaTrack::new(
-                tracks_counter.next_id(),
+                track_ids.next().unwrap(),
                 MediaType::Video(VideoSettings {
                     required: video_settings.publish_policy.required(),
                     source_kind: MediaSourceKind::Device,
@@ -1419,10 +1864,12 @@ impl<'a> PeerChangesScheduler<'a> {
             partner_peer
                 .as_changes_scheduler()
                 .add_receiver(camera_video_track);
+        }
+        if publishes_display {
             let display_video_track = Rc::new(MediaTrack::new(
-                tracks_counter.next_id(),
+                track_ids.next().unwrap(),
                 MediaType::Video(VideoSettings {
-                    required: false,
+                    required: video_settings.display_publish_policy.required(),
                     source_kind: MediaSourceKind::Display,
                 }),
             ));
@@ -1432,6 +1879,8 @@ impl<'a> PeerChangesScheduler<'a> {
                 .as_changes_scheduler()
                 .add_receiver(display_video_track);
         }
+
+        Ok(())
     }
 
     /// Adds provided [`PeerChange`] to scheduled changes queue.
@@ -1523,16 +1972,19 @@ pub mod tests {
         let track_id_counter = Counter::default();
 
         for _ in 0..send_audio {
-            let track_id = track_id_counter.next_id();
+            let track_id = track_id_counter.next_id(|_| false).unwrap();
             let track = MediaTrack::new(
                 track_id,
-                MediaType::Audio(AudioSettings { required: true }),
+                MediaType::Audio(AudioSettings {
+                    required: true,
+                    opus: None,
+                }),
             );
             peer.context.senders.insert(track_id, Rc::new(track));
         }
 
         for _ in 0..send_video {
-            let track_id = track_id_counter.next_id();
+            let track_id = track_id_counter.next_id(|_| false).unwrap();
             let track = MediaTrack::new(
                 track_id,
                 MediaType::Video(VideoSettings {
@@ -1544,16 +1996,19 @@ pub mod tests {
         }
 
         for _ in 0..recv_audio {
-            let track_id = track_id_counter.next_id();
+            let track_id = track_id_counter.next_id(|_| false).unwrap();
             let track = MediaTrack::new(
                 track_id,
-                MediaType::Audio(AudioSettings { required: true }),
+                MediaType::Audio(AudioSettings {
+                    required: true,
+                    opus: None,
+                }),
             );
             peer.context.receivers.insert(track_id, Rc::new(track));
         }
 
         for _ in 0..recv_video {
-            let track_id = track_id_counter.next_id();
+            let track_id = track_id_counter.next_id(|_| false).unwrap();
             let track = MediaTrack::new(
                 track_id,
                 MediaType::Video(VideoSettings {
@@ -1658,6 +2113,35 @@ pub mod tests {
         assert_eq!(rx.recv().unwrap(), PeerId(0));
     }
 
+    #[test]
+    fn removed_tracks_are_unacked_until_acknowledged() {
+        let mut peer = Peer::new(
+            PeerId(0),
+            MemberId::from("member-1"),
+            PeerId(1),
+            MemberId::from("member-2"),
+            false,
+            dummy_negotiation_sub_mock(),
+        );
+        peer.set_initialized();
+        peer.context.senders.insert(TrackId(0), media_track(0));
+        peer.context.receivers.insert(TrackId(1), media_track(1));
+
+        peer.as_changes_scheduler()
+            .remove_tracks(&[TrackId(0), TrackId(1)]);
+        peer.commit_scheduled_changes();
+
+        assert!(peer.context.senders.is_empty());
+        assert!(peer.context.receivers.is_empty());
+        assert_eq!(peer.unacked_removed_tracks().len(), 2);
+
+        peer.ack_removed_tracks(&[TrackId(1), TrackId(2)]);
+        assert_eq!(
+            peer.unacked_removed_tracks().iter().collect::<Vec<_>>(),
+            vec![&TrackId(0)],
+        );
+    }
+
     #[test]
     fn force_updates_works() {
         let (force_update_tx, force_update_rx) = std::sync::mpsc::channel();
@@ -1833,6 +2317,8 @@ pub mod tests {
                 enabled_individual: None,
                 enabled_general: None,
                 muted: None,
+                content_hint: None,
+                degradation_preference: None,
             }),
             PeerChange::IceRestart,
             PeerChange::TrackPatch(TrackPatchEvent {
@@ -1840,6 +2326,8 @@ pub mod tests {
                 enabled_individual: None,
                 enabled_general: None,
                 muted: None,
+                content_hint: None,
+                degradation_preference: None,
             }),
         ];
 
@@ -1868,6 +2356,68 @@ pub mod tests {
         assert!(matches!(deduped_track_updates[1], PeerChange::IceRestart));
     }
 
+    /// Checks that [`PeerChange::IceRestart`]s scheduled for an answerer
+    /// [`Peer`] are not renegotiated once its partner restarts ICE.
+    #[test]
+    fn partner_ice_restart_skips_redundant_renegotiation() {
+        let mut negotiation_sub = MockPeerUpdatesSubscriber::new();
+        negotiation_sub.expect_negotiation_needed().times(0);
+        let mut peer = Peer::new(
+            PeerId(1),
+            MemberId::from("member-2"),
+            PeerId(0),
+            MemberId::from("member-1"),
+            false,
+            Rc::new(negotiation_sub),
+        );
+        peer.set_initialized();
+        let mut peer = peer.start_as_answerer();
+
+        peer.as_changes_scheduler().restart_ice();
+        peer.as_changes_scheduler().restart_ice();
+        peer.on_partner_ice_restart();
+
+        let peer = peer
+            .set_remote_offer(String::from("offer"))
+            .set_local_answer(String::from("answer"));
+        assert!(peer.scheduled_changes().is_empty());
+        assert!(peer.pending_changes().is_empty());
+    }
+
+    /// Checks that [`PeerChange::IceRestart`]s scheduled along with other
+    /// [`PeerChange`]s are kept once the partner [`Peer`] restarts ICE.
+    #[test]
+    fn partner_ice_restart_keeps_effective_changes() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut negotiation_sub = MockPeerUpdatesSubscriber::new();
+        negotiation_sub
+            .expect_negotiation_needed()
+            .times(1)
+            .returning(move |peer_id| {
+                tx.send(peer_id).unwrap();
+            });
+        let mut peer = Peer::new(
+            PeerId(1),
+            MemberId::from("member-2"),
+            PeerId(0),
+            MemberId::from("member-1"),
+            false,
+            Rc::new(negotiation_sub),
+        );
+        peer.set_initialized();
+        let mut peer = peer.start_as_answerer();
+
+        peer.as_changes_scheduler().update_ice_policy(true);
+        peer.on_partner_ice_restart();
+        assert_eq!(peer.scheduled_changes().len(), 2);
+
+        let peer = peer
+            .set_remote_offer(String::from("offer"))
+            .set_local_answer(String::from("answer"));
+        assert_eq!(rx.recv().unwrap(), PeerId(1));
+        assert!(peer.is_ice_restart());
+    }
+
     /// Checks that [`Peer::inner_force_commit_scheduled_changes`] merges
     /// changes from the [`Context::pending_track_updates`] with a forcible
     /// changes from the [`Context::track_changes_queue`].
@@ -1901,18 +2451,24 @@ pub mod tests {
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
                 muted: None,
+                content_hint: None,
+                degradation_preference: None,
             }),
             PeerChange::TrackPatch(TrackPatchEvent {
                 id: TrackId(0),
                 enabled_general: Some(true),
                 enabled_individual: Some(true),
                 muted: None,
+                content_hint: None,
+                degradation_preference: None,
             }),
             PeerChange::TrackPatch(TrackPatchEvent {
                 id: TrackId(1),
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
                 muted: None,
+                content_hint: None,
+                degradation_preference: None,
             }),
         ];
         peer.as_changes_scheduler().patch_tracks(vec![
@@ -1961,12 +2517,16 @@ pub mod tests {
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
                 muted: None,
+                content_hint: None,
+                degradation_preference: None,
             });
             let whitelisted_patch = PeerChange::TrackPatch(TrackPatchEvent {
                 id: TrackId(1),
                 enabled_general: Some(false),
                 enabled_individual: Some(false),
                 muted: None,
+                content_hint: None,
+                degradation_preference: None,
             });
             let mut patches =
                 vec![whitelisted_patch.clone(), filtered_patch.clone()];
@@ -1990,30 +2550,40 @@ pub mod tests {
                     enabled_general: Some(true),
                     enabled_individual: Some(true),
                     muted: None,
+                    content_hint: None,
+                    degradation_preference: None,
                 },
                 TrackPatchEvent {
                     id: TrackId(2),
                     enabled_general: Some(false),
                     enabled_individual: Some(false),
                     muted: None,
+                    content_hint: None,
+                    degradation_preference: None,
                 },
                 TrackPatchEvent {
                     id: TrackId(1),
                     enabled_general: Some(false),
                     enabled_individual: Some(false),
                     muted: None,
+                    content_hint: None,
+                    degradation_preference: None,
                 },
                 TrackPatchEvent {
                     id: TrackId(1),
                     enabled_general: None,
                     enabled_individual: None,
                     muted: None,
+                    content_hint: None,
+                    degradation_preference: None,
                 },
                 TrackPatchEvent {
                     id: TrackId(2),
                     enabled_general: Some(true),
                     enabled_individual: Some(true),
                     muted: None,
+                    content_hint: None,
+                    degradation_preference: None,
                 },
             ]
             .into_iter()
@@ -2022,7 +2592,10 @@ pub mod tests {
             let unrelated_change =
                 PeerChange::AddSendTrack(Rc::new(MediaTrack::new(
                     TrackId(1),
-                    MediaType::Audio(AudioSettings { required: true }),
+                    MediaType::Audio(AudioSettings {
+                        required: true,
+                        opus: None,
+                    }),
                 )));
             changes.push(unrelated_change.clone());
             deduper.drain_merge(&mut changes);
@@ -2053,6 +2626,37 @@ pub mod tests {
         }
     }
 
+    /// Checks that [`compact_updates`] collapses successive
+    /// [`PeerUpdate::Updated`]s only.
+    #[test]
+    fn compacts_successive_track_patches() {
+        let patch = |id, muted| {
+            PeerUpdate::Updated(TrackPatchEvent {
+                muted: Some(muted),
+                ..TrackPatchEvent::new(TrackId(id))
+            })
+        };
+
+        let compacted = compact_updates(vec![
+            patch(1, true),
+            patch(2, true),
+            patch(1, false),
+            PeerUpdate::Removed(TrackId(2)),
+            patch(1, true),
+            patch(1, false),
+        ]);
+
+        assert_eq!(
+            compacted,
+            vec![
+                patch(1, false),
+                patch(2, true),
+                PeerUpdate::Removed(TrackId(2)),
+                patch(1, false),
+            ],
+        );
+    }
+
     mod negotiation_state_agnostic_patches {
         use super::*;
 
@@ -2063,6 +2667,8 @@ pub mod tests {
             let track_patch = TrackPatchEvent {
                 id: TrackId(0),
                 muted: Some(true),
+                content_hint: None,
+                degradation_preference: None,
                 enabled_individual: None,
                 enabled_general: None,
             };
@@ -2093,6 +2699,43 @@ pub mod tests {
             peer.commit_scheduled_changes();
         }
 
+        /// Checks that video encoding changes will not start renegotiation.
+        #[test]
+        fn video_encoding_change_dont_trigger_negotiation() {
+            let mut track_patch = TrackPatchEvent::new(TrackId(0));
+            track_patch.content_hint = Some(ContentHint::Text);
+            track_patch.degradation_preference =
+                Some(DegradationPreference::MaintainResolution);
+
+            let mut negotiation_sub = MockPeerUpdatesSubscriber::new();
+            negotiation_sub.expect_force_update().times(1).return_once(
+                move |peer_id: PeerId, updates: Vec<PeerUpdate>| {
+                    assert_eq!(peer_id, PeerId(0));
+                    assert_eq!(updates, vec![PeerUpdate::Updated(track_patch)]);
+                },
+            );
+            let mut peer = Peer::new(
+                PeerId(0),
+                MemberId::from("member-1"),
+                PeerId(1),
+                MemberId::from("member-2"),
+                false,
+                Rc::new(negotiation_sub),
+            );
+            peer.add_ice_users(vec![IceUser::new_coturn_static(
+                String::new(),
+                String::new(),
+                String::new(),
+            )]);
+            peer.set_initialized();
+            peer.as_changes_scheduler().patch_video_encoding(
+                TrackId(0),
+                Some(ContentHint::Text),
+                Some(DegradationPreference::MaintainResolution),
+            );
+            peer.commit_scheduled_changes();
+        }
+
         /// Checks that [`PeerChange`] which doesn't requires negotiation with
         /// [`PeerChange`] which requires negotiation will trigger negotiation.
         #[test]
@@ -2101,12 +2744,16 @@ pub mod tests {
                 PeerChange::TrackPatch(TrackPatchEvent {
                     id: TrackId(0),
                     muted: Some(true),
+                    content_hint: None,
+                    degradation_preference: None,
                     enabled_individual: None,
                     enabled_general: None,
                 }),
                 PeerChange::TrackPatch(TrackPatchEvent {
                     id: TrackId(1),
                     muted: None,
+                    content_hint: None,
+                    degradation_preference: None,
                     enabled_individual: Some(true),
                     enabled_general: Some(true),
                 }),
@@ -2153,6 +2800,8 @@ pub mod tests {
             let changes = vec![PeerChange::TrackPatch(TrackPatchEvent {
                 id: TrackId(0),
                 muted: Some(true),
+                content_hint: None,
+                degradation_preference: None,
                 enabled_individual: Some(true),
                 enabled_general: Some(true),
             })];
@@ -2280,6 +2929,20 @@ pub mod tests {
             assert!(state.restart_ice);
         }
 
+        #[test]
+        fn ice_policy_update() {
+            let mut peer = peer();
+            assert!(!peer.is_force_relayed());
+
+            peer.as_changes_scheduler().update_ice_policy(true);
+            peer.commit_scheduled_changes();
+
+            let peer = PeerStateMachine::from(peer);
+            let state = peer.get_state();
+            assert!(state.force_relay);
+            assert!(state.restart_ice);
+        }
+
         #[test]
         fn sender_patch() {
             let mut peer = peer();
@@ -2287,7 +2950,10 @@ pub mod tests {
                 TrackId(0),
                 Rc::new(MediaTrack::new(
                     TrackId(0),
-                    MediaType::Audio(AudioSettings { required: true }),
+                    MediaType::Audio(AudioSettings {
+                        required: true,
+                        opus: None,
+                    }),
                 )),
             );
 
@@ -2315,7 +2981,10 @@ pub mod tests {
                 TrackId(0),
                 Rc::new(MediaTrack::new(
                     TrackId(0),
-                    MediaType::Audio(AudioSettings { required: true }),
+                    MediaType::Audio(AudioSettings {
+                        required: true,
+                        opus: None,
+                    }),
                 )),
             );
 
@@ -2335,5 +3004,259 @@ pub mod tests {
             assert_eq!(track_state.enabled_general, false);
             assert_eq!(track_state.enabled_individual, false);
         }
+
+        #[test]
+        fn media_pause() {
+            let mut peer = peer();
+            peer.context.senders.insert(
+                TrackId(0),
+                Rc::new(MediaTrack::new(
+                    TrackId(0),
+                    MediaType::Audio(AudioSettings {
+                        required: true,
+                        opus: None,
+                    }),
+                )),
+            );
+
+            peer.as_changes_scheduler().set_media_paused(true);
+            peer.commit_scheduled_changes();
+
+            let mut peer = PeerStateMachine::from(peer);
+            let track_state =
+                peer.get_state().senders.remove(&TrackId(0)).unwrap();
+            assert!(!track_state.enabled_general);
+            assert!(track_state.enabled_individual);
+
+            peer.as_changes_scheduler().set_media_paused(false);
+            peer.commit_scheduled_changes();
+
+            let track_state =
+                peer.get_state().senders.remove(&TrackId(0)).unwrap();
+            assert!(track_state.enabled_general);
+            assert!(track_state.enabled_individual);
+        }
+    }
+
+    mod add_publisher {
+        use std::time::Duration;
+
+        use crate::{
+            api::control::{
+                endpoints::webrtc_publish_endpoint::{self as spec, P2pMode},
+                member::Credential,
+            },
+            signalling::elements::Member,
+        };
+
+        use super::*;
+
+        /// Returns [`MediaSourceKind`]s of the video [`MediaTrack`]s (along
+        /// with their `required` flags) sent by a [`Peer`] publishing with the
+        /// provided [`spec::VideoSettings`].
+        fn sent_video(
+            video_settings: spec::VideoSettings,
+        ) -> Vec<(MediaSourceKind, bool)> {
+            let member = Member::new(
+                "member".into(),
+                Credential::Plain("test".into()),
+                "test".into(),
+                Duration::from_secs(10),
+                Duration::from_secs(10),
+                Duration::from_secs(5),
+            );
+            let publish = WebRtcPublishEndpoint::new(
+                "publish".to_owned().into(),
+                P2pMode::Always,
+                member.downgrade(),
+                false,
+                spec::AudioSettings::default(),
+                video_settings,
+            );
+            let mut peer = test_peer_from_peer_tracks(0, 0, 0, 0);
+            let mut partner = test_peer_from_peer_tracks(0, 0, 0, 0);
+            peer.as_changes_scheduler()
+                .add_publisher(
+                    &publish,
+                    &mut partner,
+                    &Counter::default(),
+                    |_| false,
+                )
+                .unwrap();
+            peer.commit_scheduled_changes();
+
+            let mut tracks: Vec<_> = peer.senders().values().collect();
+            tracks.sort_by_key(|t| t.id().0);
+            tracks
+                .into_iter()
+                .filter_map(|t| match t.media_type() {
+                    MediaType::Video(v) => Some((v.source_kind, v.required)),
+                    MediaType::Audio(_) => None,
+                })
+                .collect()
+        }
+
+        #[test]
+        fn publishes_device_and_display_video() {
+            let video = sent_video(spec::VideoSettings {
+                publish_policy: PublishPolicy::Required,
+                display_publish_policy: PublishPolicy::Optional,
+            });
+
+            assert_eq!(
+                video,
+                vec![
+                    (MediaSourceKind::Device, true),
+                    (MediaSourceKind::Display, false),
+                ],
+            );
+        }
+
+        #[test]
+        fn respects_display_publish_policy() {
+            let video = sent_video(spec::VideoSettings {
+                publish_policy: PublishPolicy::Optional,
+                display_publish_policy: PublishPolicy::Disabled,
+            });
+            assert_eq!(video, vec![(MediaSourceKind::Device, false)]);
+
+            let video = sent_video(spec::VideoSettings {
+                publish_policy: PublishPolicy::Disabled,
+                display_publish_policy: PublishPolicy::Required,
+            });
+            assert!(video.is_empty());
+        }
+    }
+
+    mod set_mids {
+        use super::*;
+
+        /// Returns [`Peer`] waiting for a local SDP offer, with a sender
+        /// [`MediaTrack`] with [`TrackId`] `0` and a receiver [`MediaTrack`]
+        /// with [`TrackId`] `1`.
+        fn peer() -> Peer<WaitLocalSdp> {
+            let mut peer = Peer::new(
+                PeerId(0),
+                MemberId::from("member-1"),
+                PeerId(1),
+                MemberId::from("member-2"),
+                false,
+                dummy_negotiation_sub_mock(),
+            );
+            peer.context.senders.insert(TrackId(0), media_track(0));
+            peer.context.receivers.insert(TrackId(1), media_track(1));
+            peer.start_as_offerer()
+        }
+
+        fn mids(mids: &[(u32, &str)]) -> HashMap<TrackId, String> {
+            mids.iter()
+                .map(|(id, mid)| (TrackId(*id), (*mid).to_owned()))
+                .collect()
+        }
+
+        #[test]
+        fn sets_mids() {
+            let mut peer = peer();
+
+            peer.set_mids(mids(&[(0, "0"), (1, "1")])).unwrap();
+
+            assert_eq!(peer.context.senders[&TrackId(0)].mid().unwrap(), "0");
+            assert_eq!(peer.context.receivers[&TrackId(1)].mid().unwrap(), "1");
+        }
+
+        #[test]
+        fn rejects_invalid_mids() {
+            let mut peer = peer();
+
+            assert!(matches!(
+                peer.set_mids(mids(&[(0, "0")])),
+                Err(PeerError::MidsMismatch(TrackId(1))),
+            ));
+            assert!(matches!(
+                peer.set_mids(mids(&[(0, "0"), (1, "1"), (2, "2")])),
+                Err(PeerError::UnknownTrackMid(TrackId(2))),
+            ));
+            assert!(matches!(
+                peer.set_mids(mids(&[(0, "0"), (1, "0")])),
+                Err(PeerError::DuplicateMid(mid)) if mid == "0",
+            ));
+            assert!(peer.context.senders[&TrackId(0)].mid().is_none());
+        }
+
+        #[test]
+        fn rejects_changed_mids() {
+            let mut peer = peer();
+            peer.context.senders[&TrackId(0)].set_mid("0".to_owned());
+            peer.context.receivers[&TrackId(1)].set_mid("1".to_owned());
+
+            assert!(matches!(
+                peer.set_mids(mids(&[(0, "0"), (1, "5")])),
+                Err(PeerError::MidChanged(TrackId(1), provided, known))
+                    if provided == "5" && known == "1",
+            ));
+            assert_eq!(peer.context.receivers[&TrackId(1)].mid().unwrap(), "1");
+
+            peer.set_mids(mids(&[(0, "0"), (1, "1")])).unwrap();
+        }
+
+        #[test]
+        fn sets_nothing_on_changed_mid() {
+            let mut peer = peer();
+            peer.context.receivers[&TrackId(1)].set_mid("1".to_owned());
+
+            assert!(peer.set_mids(mids(&[(0, "0"), (1, "5")])).is_err());
+            assert!(peer.context.senders[&TrackId(0)].mid().is_none());
+        }
+
+        #[test]
+        fn updates_senders_ssrcs() {
+            let mut peer = peer();
+            peer.set_mids(mids(&[(0, "0"), (1, "1")])).unwrap();
+
+            peer.update_senders_ssrcs(
+                "v=0\r\n\
+                 m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+                 a=mid:0\r\n\
+                 a=ssrc:1001 cname:foo\r\n\
+                 m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+                 a=mid:1\r\n\
+                 a=ssrc:2002 cname:foo\r\n",
+            );
+
+            assert_eq!(peer.context.senders[&TrackId(0)].ssrc(), Some(1001));
+            assert_eq!(peer.context.receivers[&TrackId(1)].ssrc(), None);
+        }
+    }
+
+    mod selected_candidate_pair {
+        use super::*;
+
+        fn pair(local: KnownCandidateType) -> SelectedCandidatePair {
+            SelectedCandidatePair {
+                local_type: NonExhaustive::Known(local),
+                remote_type: NonExhaustive::Known(KnownCandidateType::Host),
+                relay_protocol: None,
+            }
+        }
+
+        #[test]
+        fn detects_relaying() {
+            assert!(pair(KnownCandidateType::Relay).is_relayed());
+            assert!(!pair(KnownCandidateType::Host).is_relayed());
+            assert!(!pair(KnownCandidateType::Srlfx).is_relayed());
+        }
+
+        #[test]
+        fn stored_in_peer() {
+            let mut peer = test_peer_from_peer_tracks(0, 0, 0, 0);
+            assert!(peer.selected_candidate_pair().is_none());
+
+            peer.set_selected_candidate_pair(pair(KnownCandidateType::Relay));
+
+            assert_eq!(
+                peer.selected_candidate_pair(),
+                Some(&pair(KnownCandidateType::Relay)),
+            );
+        }
     }
 }
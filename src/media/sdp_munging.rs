@@ -0,0 +1,309 @@
+//! Rewriting of SDPs relayed between `Client`s, so the media parameters
+//! specified via [Control API] are negotiated regardless of `Client`s' user
+//! agents defaults, and extracting the media parameters chosen by `Client`s
+//! from them.
+//!
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use std::{collections::HashMap, iter};
+
+use medea_client_api_proto::OpusParameters;
+
+/// Applies the provided [`OpusParameters`] to the Opus `a=fmtp` attributes of
+/// the audio media descriptions with the corresponding [mid]s in the provided
+/// `sdp`.
+///
+/// Parameters of an SDP describe what its author wants to receive, so the
+/// rewritten SDP must be the one applied as a remote description by the
+/// `Client` sending the audio.
+///
+/// Media descriptions without an Opus payload type are left untouched. If an
+/// Opus payload type has no `a=fmtp` attribute, then it's added right after
+/// its `a=rtpmap` one.
+///
+/// [mid]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+#[must_use]
+pub fn apply_opus_parameters(
+    sdp: &str,
+    params: &HashMap<String, OpusParameters>,
+) -> String {
+    if params.is_empty() {
+        return sdp.to_owned();
+    }
+
+    let eol = if sdp.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut lines: Vec<String> = sdp.lines().map(ToOwned::to_owned).collect();
+
+    let mut starts: Vec<_> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| l.starts_with("m=").then(|| i))
+        .collect();
+    // Going backwards, so insertions don't shift the unprocessed sections.
+    starts.reverse();
+    let mut end = lines.len();
+    for start in starts {
+        apply_to_media(&mut lines, start, end, params);
+        end = start;
+    }
+
+    let mut munged = lines.join(eol);
+    if sdp.ends_with('\n') {
+        munged.push_str(eol);
+    }
+    munged
+}
+
+/// Applies the [`OpusParameters`] matching the [mid] of the audio media
+/// description taking the provided `[start, end)` range of `lines`.
+///
+/// [mid]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+fn apply_to_media(
+    lines: &mut Vec<String>,
+    start: usize,
+    end: usize,
+    params: &HashMap<String, OpusParameters>,
+) {
+    if !lines[start].starts_with("m=audio ") {
+        return;
+    }
+    let section = &lines[start..end];
+    let opus = section
+        .iter()
+        .find_map(|l| l.strip_prefix("a=mid:"))
+        .and_then(|mid| params.get(mid.trim()));
+    let opus = if let Some(opus) = opus {
+        *opus
+    } else {
+        return;
+    };
+    let (rtpmap_idx, pt) = if let Some(found) =
+        section.iter().enumerate().find_map(|(i, l)| {
+            let mut rtpmap = l.strip_prefix("a=rtpmap:")?.splitn(2, ' ');
+            let (pt, codec) = (rtpmap.next()?, rtpmap.next()?);
+            codec
+                .to_ascii_lowercase()
+                .starts_with("opus/")
+                .then(|| (start + i, pt.to_owned()))
+        }) {
+        found
+    } else {
+        return;
+    };
+
+    let fmtp_prefix = format!("a=fmtp:{} ", pt);
+    let fmtp_idx = lines[start..end]
+        .iter()
+        .position(|l| l.starts_with(&fmtp_prefix))
+        .map(|i| start + i);
+    match fmtp_idx {
+        Some(idx) => {
+            let fmtp = munge_fmtp(&lines[idx][fmtp_prefix.len()..], opus);
+            lines[idx] = format!("{}{}", fmtp_prefix, fmtp);
+        }
+        None => {
+            let fmtp = munge_fmtp("", opus);
+            lines.insert(rtpmap_idx + 1, format!("{}{}", fmtp_prefix, fmtp));
+        }
+    }
+}
+
+/// Returns primary [SSRC]s of the media descriptions of the provided `sdp`,
+/// mapped by their [mid]s.
+///
+/// Primary [SSRC] is the first one of the first `a=ssrc-group` attribute (so
+/// RTX and FEC streams are skipped), or the first `a=ssrc` attribute if there
+/// are no groups. Media descriptions without a [mid] or `a=ssrc` attributes
+/// (the ones not sending anything) are omitted.
+///
+/// [mid]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
+/// [SSRC]: https://tools.ietf.org/html/rfc3550#section-3
+#[must_use]
+pub fn primary_ssrcs(sdp: &str) -> HashMap<String, u32> {
+    let mut ssrcs = HashMap::new();
+    let mut mid = None;
+    let mut ssrc = None;
+    let mut grouped = false;
+    for line in sdp.lines().chain(iter::once("m=")) {
+        if line.starts_with("m=") {
+            if let (Some(mid), Some(ssrc)) = (mid.take(), ssrc.take()) {
+                ssrcs.insert(mid, ssrc);
+            }
+            grouped = false;
+        } else if let Some(id) = line.strip_prefix("a=mid:") {
+            mid = Some(id.trim().to_owned());
+        } else if let Some(group) = line.strip_prefix("a=ssrc-group:") {
+            if !grouped {
+                if let Some(first) = group
+                    .split_whitespace()
+                    .nth(1)
+                    .and_then(|s| s.parse().ok())
+                {
+                    ssrc = Some(first);
+                    grouped = true;
+                }
+            }
+        } else if let Some(attr) = line.strip_prefix("a=ssrc:") {
+            if ssrc.is_none() {
+                ssrc = attr
+                    .split_whitespace()
+                    .next()
+                    .and_then(|s| s.parse().ok());
+            }
+        }
+    }
+    ssrcs
+}
+
+/// Overrides the provided `a=fmtp` attribute `params` of an Opus payload type
+/// with the provided [`OpusParameters`], keeping the unrelated ones.
+fn munge_fmtp(params: &str, opus: OpusParameters) -> String {
+    let mut pairs: Vec<(String, String)> = params
+        .split(';')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            let mut kv = p.splitn(2, '=');
+            let key = kv.next().unwrap_or_default().to_owned();
+            (key, kv.next().unwrap_or_default().to_owned())
+        })
+        .collect();
+    let mut set = |key: &str, value: String| {
+        if let Some(pair) = pairs.iter_mut().find(|(k, _)| k == key) {
+            pair.1 = value;
+        } else {
+            pairs.push((key.to_owned(), value));
+        }
+    };
+
+    set("usedtx", u8::from(opus.dtx).to_string());
+    set("useinbandfec", u8::from(opus.inband_fec).to_string());
+    set("stereo", u8::from(opus.stereo).to_string());
+    if let Some(bitrate) = opus.max_average_bitrate {
+        set("maxaveragebitrate", bitrate.to_string());
+    }
+
+    pairs
+        .into_iter()
+        .map(|(k, v)| {
+            if v.is_empty() {
+                k
+            } else {
+                format!("{}={}", k, v)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDP: &str = "v=0\r\n\
+                       m=audio 9 UDP/TLS/RTP/SAVPF 111 103\r\n\
+                       a=mid:0\r\n\
+                       a=rtpmap:111 opus/48000/2\r\n\
+                       a=fmtp:111 minptime=10;useinbandfec=1\r\n\
+                       a=rtpmap:103 ISAC/16000\r\n\
+                       m=audio 9 UDP/TLS/RTP/SAVPF 109\r\n\
+                       a=mid:1\r\n\
+                       a=rtpmap:109 opus/48000/2\r\n\
+                       m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+                       a=mid:2\r\n\
+                       a=rtpmap:96 VP8/90000\r\n";
+
+    fn params(
+        mid: &str,
+        opus: OpusParameters,
+    ) -> HashMap<String, OpusParameters> {
+        let mut params = HashMap::new();
+        params.insert(mid.to_owned(), opus);
+        params
+    }
+
+    #[test]
+    fn overrides_existing_fmtp() {
+        let sdp = apply_opus_parameters(
+            SDP,
+            &params(
+                "0",
+                OpusParameters {
+                    dtx: true,
+                    inband_fec: false,
+                    stereo: true,
+                    max_average_bitrate: Some(128_000),
+                },
+            ),
+        );
+
+        assert!(sdp.contains(
+            "a=fmtp:111 minptime=10;useinbandfec=0;usedtx=1;stereo=1;\
+             maxaveragebitrate=128000\r\n",
+        ));
+        assert!(!sdp.contains("a=fmtp:109"));
+        assert!(sdp.ends_with("a=rtpmap:96 VP8/90000\r\n"));
+    }
+
+    #[test]
+    fn adds_missing_fmtp() {
+        let sdp = apply_opus_parameters(
+            SDP,
+            &params(
+                "1",
+                OpusParameters {
+                    dtx: true,
+                    inband_fec: true,
+                    stereo: false,
+                    max_average_bitrate: None,
+                },
+            ),
+        );
+
+        assert!(sdp.contains(
+            "a=rtpmap:109 opus/48000/2\r\n\
+             a=fmtp:109 usedtx=1;useinbandfec=1;stereo=0\r\n\
+             m=video",
+        ));
+        assert!(sdp.contains("a=fmtp:111 minptime=10;useinbandfec=1\r\n"));
+    }
+
+    #[test]
+    fn ignores_unknown_mids_and_non_audio() {
+        let opus = OpusParameters {
+            dtx: true,
+            inband_fec: true,
+            stereo: true,
+            max_average_bitrate: None,
+        };
+
+        assert_eq!(apply_opus_parameters(SDP, &params("2", opus)), SDP);
+        assert_eq!(apply_opus_parameters(SDP, &params("3", opus)), SDP);
+        assert_eq!(apply_opus_parameters(SDP, &HashMap::new()), SDP);
+    }
+
+    #[test]
+    fn parses_primary_ssrcs() {
+        let sdp = "v=0\r\n\
+                   m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+                   a=mid:0\r\n\
+                   a=ssrc:1001 cname:foo\r\n\
+                   a=ssrc:1001 msid:stream track\r\n\
+                   m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+                   a=mid:1\r\n\
+                   a=ssrc-group:FID 2002 2003\r\n\
+                   a=ssrc:2003 cname:foo\r\n\
+                   a=ssrc:2002 cname:foo\r\n\
+                   m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+                   a=mid:2\r\n\
+                   a=recvonly\r\n\
+                   m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+                   a=ssrc:4004 cname:foo\r\n";
+
+        let ssrcs = primary_ssrcs(sdp);
+
+        assert_eq!(ssrcs.len(), 2);
+        assert_eq!(ssrcs["0"], 1001);
+        assert_eq!(ssrcs["1"], 2002);
+    }
+}
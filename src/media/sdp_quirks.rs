@@ -0,0 +1,261 @@
+//! Quirks of SDP dialects produced by different `Client` user agents.
+//!
+//! `Media Server` doesn't rewrite SDPs to fit dialects, but it has to know
+//! which patterns are normal for a `Client`, so unknown ones are reported
+//! instead of failing negotiation opaquely.
+
+use std::fmt;
+
+use derive_more::Display;
+
+/// Family of a `Client` user agent with a known SDP dialect.
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq)]
+pub enum Browser {
+    /// Chromium-based browser (Chrome, Edge, Opera, etc).
+    Chromium,
+
+    /// Mozilla Firefox.
+    Firefox,
+
+    /// Apple Safari.
+    Safari,
+
+    /// Unrecognized user agent.
+    Unknown,
+}
+
+/// Pattern found in an SDP, which presence depends on its dialect.
+#[derive(Clone, Debug, Display, Eq, PartialEq)]
+pub enum SdpPattern {
+    /// Plan B remnants: `a=ssrc:<ssrc> mslabel:` or `a=ssrc:<ssrc> label:`
+    /// attributes.
+    #[display(fmt = "Plan B ssrc attributes")]
+    PlanBRemnants,
+
+    /// `a=ssrc-group` attribute with the given semantics.
+    #[display(fmt = "ssrc-group:{}", _0)]
+    SsrcGroup(String),
+
+    /// `a=rtpmap:<pt> rtx/<rate>` attribute.
+    #[display(fmt = "rtx")]
+    Rtx,
+
+    /// Media description without an `a=mid` attribute.
+    #[display(fmt = "m-line without mid")]
+    MissingMid,
+}
+
+/// SDP expectations adjusted to the dialect of a `Client` user agent.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SdpQuirks {
+    /// [`Browser`] of the `Client`.
+    browser: Browser,
+
+    /// Major version of the [`Browser`], if recognized.
+    version: Option<u32>,
+
+    /// Indicator whether [`SdpPattern::PlanBRemnants`] are expected.
+    plan_b_remnants: bool,
+
+    /// Indicator whether `a=ssrc-group:FID` attributes are expected.
+    fid_groups: bool,
+
+    /// Indicator whether `a=ssrc-group:SIM` attributes are expected.
+    sim_groups: bool,
+
+    /// Indicator whether [`SdpPattern::Rtx`] is expected.
+    rtx: bool,
+}
+
+impl SdpQuirks {
+    /// Returns [`SdpQuirks`] of the provided `Client` user agent.
+    ///
+    /// SDPs of an absent or unrecognized user agent are expected to contain
+    /// any pattern known for the libwebrtc-based [`Browser`]s.
+    #[must_use]
+    pub fn new(user_agent: Option<&str>) -> Self {
+        let (browser, version) =
+            user_agent.map_or((Browser::Unknown, None), parse_user_agent);
+        match browser {
+            Browser::Firefox => Self {
+                browser,
+                version,
+                plan_b_remnants: false,
+                fid_groups: true,
+                sim_groups: false,
+                rtx: version.map_or(true, |v| v >= 64),
+            },
+            Browser::Chromium | Browser::Safari | Browser::Unknown => Self {
+                browser,
+                version,
+                plan_b_remnants: true,
+                fid_groups: true,
+                sim_groups: true,
+                rtx: true,
+            },
+        }
+    }
+
+    /// Returns [`Browser`] of the `Client` these [`SdpQuirks`] are for.
+    #[inline]
+    #[must_use]
+    pub fn browser(&self) -> Browser {
+        self.browser
+    }
+
+    /// Returns [`SdpPattern`]s of the provided `sdp` which are not expected
+    /// for the `Client` dialect.
+    ///
+    /// [`SdpPattern::MissingMid`] and `a=ssrc-group` with unknown semantics
+    /// are never expected, since Unified Plan requires the former and the
+    /// latter can't be interpreted.
+    #[must_use]
+    pub fn unexpected_patterns(&self, sdp: &str) -> Vec<SdpPattern> {
+        let mut patterns = Vec::new();
+        let mut push = |pattern: SdpPattern| {
+            if !patterns.contains(&pattern) {
+                patterns.push(pattern);
+            }
+        };
+
+        let mut in_media = false;
+        let mut media_has_mid = true;
+        for line in sdp.lines().map(str::trim) {
+            if line.starts_with("m=") {
+                if !media_has_mid {
+                    push(SdpPattern::MissingMid);
+                }
+                in_media = true;
+                media_has_mid = false;
+            } else if line.starts_with("a=mid:") {
+                media_has_mid = true;
+            } else if let Some(attr) = line.strip_prefix("a=ssrc-group:") {
+                let semantics = attr.split(' ').next().unwrap_or_default();
+                let expected = match semantics {
+                    "FID" => self.fid_groups,
+                    "SIM" => self.sim_groups,
+                    _ => false,
+                };
+                if !expected {
+                    push(SdpPattern::SsrcGroup(semantics.to_owned()));
+                }
+            } else if let Some(attr) = line.strip_prefix("a=ssrc:") {
+                let attr = attr.splitn(2, ' ').nth(1).unwrap_or_default();
+                if !self.plan_b_remnants
+                    && (attr.starts_with("mslabel:")
+                        || attr.starts_with("label:"))
+                {
+                    push(SdpPattern::PlanBRemnants);
+                }
+            } else if let Some(attr) = line.strip_prefix("a=rtpmap:") {
+                let codec = attr.splitn(2, ' ').nth(1).unwrap_or_default();
+                if !self.rtx && codec.starts_with("rtx/") {
+                    push(SdpPattern::Rtx);
+                }
+            }
+        }
+        if in_media && !media_has_mid {
+            push(SdpPattern::MissingMid);
+        }
+
+        patterns
+    }
+}
+
+impl fmt::Display for SdpQuirks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.version {
+            Some(version) => write!(f, "{} {}", self.browser, version),
+            None => write!(f, "{}", self.browser),
+        }
+    }
+}
+
+/// Recognizes [`Browser`] and its major version from the provided user agent.
+fn parse_user_agent(user_agent: &str) -> (Browser, Option<u32>) {
+    /// Parses major version following the provided `token` in the
+    /// `user_agent`.
+    fn version_of(user_agent: &str, token: &str) -> Option<u32> {
+        user_agent[user_agent.find(token)? + token.len()..]
+            .split(|c: char| !c.is_ascii_digit())
+            .next()?
+            .parse()
+            .ok()
+    }
+
+    if user_agent.contains("Firefox/") {
+        (Browser::Firefox, version_of(user_agent, "Firefox/"))
+    } else if user_agent.contains("Chrome/") {
+        (Browser::Chromium, version_of(user_agent, "Chrome/"))
+    } else if user_agent.contains("CriOS/") {
+        (Browser::Chromium, version_of(user_agent, "CriOS/"))
+    } else if user_agent.contains("Safari/") {
+        (Browser::Safari, version_of(user_agent, "Version/"))
+    } else {
+        (Browser::Unknown, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHROME: &str = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 \
+                          (KHTML, like Gecko) Chrome/89.0.4389.90 \
+                          Safari/537.36";
+    const FIREFOX: &str = "Mozilla/5.0 (X11; Linux x86_64; rv:86.0) \
+                           Gecko/20100101 Firefox/86.0";
+    const SAFARI: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 11_2_3) \
+                          AppleWebKit/605.1.15 (KHTML, like Gecko) \
+                          Version/14.0.3 Safari/605.1.15";
+
+    #[test]
+    fn recognizes_user_agents() {
+        assert_eq!(parse_user_agent(CHROME), (Browser::Chromium, Some(89)));
+        assert_eq!(parse_user_agent(FIREFOX), (Browser::Firefox, Some(86)));
+        assert_eq!(parse_user_agent(SAFARI), (Browser::Safari, Some(14)));
+        assert_eq!(parse_user_agent("curl/7.68.0"), (Browser::Unknown, None));
+        assert_eq!(SdpQuirks::new(None).browser(), Browser::Unknown);
+        assert_eq!(SdpQuirks::new(Some(CHROME)).to_string(), "Chromium 89");
+    }
+
+    #[test]
+    fn reports_unexpected_patterns() {
+        let sdp = "v=0\r\n\
+                   m=video 9 UDP/TLS/RTP/SAVPF 96 97\r\n\
+                   a=mid:0\r\n\
+                   a=rtpmap:96 VP8/90000\r\n\
+                   a=rtpmap:97 rtx/90000\r\n\
+                   a=ssrc-group:FID 1 2\r\n\
+                   a=ssrc-group:SIM 1 3\r\n\
+                   a=ssrc:1 mslabel:stream\r\n";
+
+        assert!(SdpQuirks::new(Some(CHROME))
+            .unexpected_patterns(sdp)
+            .is_empty());
+        assert_eq!(
+            SdpQuirks::new(Some(FIREFOX)).unexpected_patterns(sdp),
+            vec![
+                SdpPattern::SsrcGroup("SIM".into()),
+                SdpPattern::PlanBRemnants
+            ],
+        );
+    }
+
+    #[test]
+    fn never_expects_unknown_ssrc_groups_and_missing_mids() {
+        let sdp = "v=0\r\n\
+                   m=audio 9 UDP/TLS/RTP/SAVPF 111\r\n\
+                   a=mid:0\r\n\
+                   m=video 9 UDP/TLS/RTP/SAVPF 96\r\n\
+                   a=ssrc-group:FEC-FR 1 2\r\n";
+
+        assert_eq!(
+            SdpQuirks::new(None).unexpected_patterns(sdp),
+            vec![
+                SdpPattern::SsrcGroup("FEC-FR".into()),
+                SdpPattern::MissingMid,
+            ],
+        );
+    }
+}
@@ -13,8 +13,10 @@ use medea_client_api_proto::{MediaType, TrackId as Id};
 pub struct MediaTrack {
     id: Id,
     mid: RefCell<Option<String>>,
+    ssrc: Cell<Option<u32>>,
     media_type: MediaType,
     transceiver_enabled: Cell<bool>,
+    paused: Cell<bool>,
     send_media_state: MediaState,
     recv_media_state: MediaState,
 }
@@ -27,8 +29,10 @@ impl MediaTrack {
         Self {
             id,
             mid: RefCell::new(None),
+            ssrc: Cell::new(None),
             media_type,
             transceiver_enabled: Cell::new(true),
+            paused: Cell::new(false),
             send_media_state: MediaState::default(),
             recv_media_state: MediaState::default(),
         }
@@ -65,6 +69,24 @@ impl MediaTrack {
         self.mid.borrow_mut().as_ref().cloned()
     }
 
+    /// Sets primary [SSRC] of the RTP stream sending this [`MediaTrack`].
+    ///
+    /// [SSRC]: https://tools.ietf.org/html/rfc3550#section-3
+    #[inline]
+    pub fn set_ssrc(&self, ssrc: u32) {
+        self.ssrc.set(Some(ssrc));
+    }
+
+    /// Returns primary [SSRC] of the RTP stream sending this [`MediaTrack`],
+    /// if it's known already.
+    ///
+    /// [SSRC]: https://tools.ietf.org/html/rfc3550#section-3
+    #[inline]
+    #[must_use]
+    pub fn ssrc(&self) -> Option<u32> {
+        self.ssrc.get()
+    }
+
     /// Enables/disables transceiver publishing state.
     #[inline]
     pub fn set_transceiver_enabled(&self, enabled: bool) {
@@ -78,11 +100,28 @@ impl MediaTrack {
         self.transceiver_enabled.get()
     }
 
-    /// Indicates whether this [`MediaTrack`] is enabled for send and recv side.
+    /// Pauses/resumes media exchange of this [`MediaTrack`] regardless of its
+    /// send and recv side states.
+    #[inline]
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.set(paused);
+    }
+
+    /// Indicates whether media exchange of this [`MediaTrack`] is paused.
+    #[inline]
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused.get()
+    }
+
+    /// Indicates whether this [`MediaTrack`] is enabled for send and recv side,
+    /// and its media exchange is not paused.
     #[inline]
     #[must_use]
     pub fn is_enabled_general(&self) -> bool {
-        self.send_media_state.is_enabled() && self.recv_media_state.is_enabled()
+        !self.paused.get()
+            && self.send_media_state.is_enabled()
+            && self.recv_media_state.is_enabled()
     }
 
     /// Returns [`MediaState`] for the recv side.
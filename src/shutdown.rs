@@ -60,6 +60,61 @@ impl GracefulShutdown {
             state: State::Listening,
         }
     }
+
+    /// Performs graceful shutdown of all the [`Subscriber`]s, stopping the
+    /// [`System`] afterwards.
+    ///
+    /// Does nothing if graceful shutdown is being performed already.
+    fn shutdown(&mut self) -> ResponseFuture<()> {
+        match self.state {
+            State::ShuttingDown => {
+                return future::ready(()).boxed_local();
+            }
+            State::Listening => {
+                self.state = State::ShuttingDown;
+            }
+        }
+
+        info!("Initiating graceful shutdown...");
+
+        if self.subs.is_empty() {
+            System::current().stop();
+            return future::ready(()).boxed_local();
+        }
+
+        let subs = mem::take(&mut self.subs);
+        let ordered_subs: Vec<_> = subs
+            .into_iter()
+            .rev()
+            .map(|(_, addrs)| {
+                let addrs: Vec<_> = addrs
+                    .into_iter()
+                    .map(|addr| async move {
+                        if let Err(e) = addr.send(ShutdownGracefully).await {
+                            error!("Error requesting shutdown: {}", e);
+                        };
+                    })
+                    .collect();
+                future::join_all(addrs)
+            })
+            .collect();
+
+        let deadline = self.timeout;
+        async move {
+            let wait_finish = timeout(
+                deadline,
+                stream::iter(ordered_subs).for_each(|row| row.map(drop)),
+            )
+            .await;
+            if wait_finish.is_ok() {
+                info!("Graceful shutdown succeeded, stopping system");
+            } else {
+                error!("Graceful shutdown has timed out, stopping system");
+            }
+            System::current().stop()
+        }
+        .boxed_local()
+    }
 }
 
 impl Actor for GracefulShutdown {
@@ -111,55 +166,26 @@ impl Handler<OsSignal> for GracefulShutdown {
 
     fn handle(&mut self, sig: OsSignal, _: &mut Context<Self>) -> Self::Result {
         info!("OS signal '{}' received", sig.0);
+        self.shutdown()
+    }
+}
 
-        match self.state {
-            State::ShuttingDown => {
-                return future::ready(()).boxed_local();
-            }
-            State::Listening => {
-                self.state = State::ShuttingDown;
-            }
-        }
-
-        info!("Initiating graceful shutdown...");
-
-        if self.subs.is_empty() {
-            System::current().stop();
-            return future::ready(()).boxed_local();
-        }
+/// Message requesting [`GracefulShutdown`] service to perform graceful
+/// shutdown, as if an OS signal has been received.
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+pub struct InitiateShutdown;
 
-        let subs = mem::take(&mut self.subs);
-        let ordered_subs: Vec<_> = subs
-            .into_iter()
-            .rev()
-            .map(|(_, addrs)| {
-                let addrs: Vec<_> = addrs
-                    .into_iter()
-                    .map(|addr| async move {
-                        if let Err(e) = addr.send(ShutdownGracefully).await {
-                            error!("Error requesting shutdown: {}", e);
-                        };
-                    })
-                    .collect();
-                future::join_all(addrs)
-            })
-            .collect();
+impl Handler<InitiateShutdown> for GracefulShutdown {
+    type Result = ResponseFuture<()>;
 
-        let deadline = self.timeout;
-        async move {
-            let wait_finish = timeout(
-                deadline,
-                stream::iter(ordered_subs).for_each(|row| row.map(drop)),
-            )
-            .await;
-            if wait_finish.is_ok() {
-                info!("Graceful shutdown succeeded, stopping system");
-            } else {
-                error!("Graceful shutdown has timed out, stopping system");
-            }
-            System::current().stop()
-        }
-        .boxed_local()
+    fn handle(
+        &mut self,
+        _: InitiateShutdown,
+        _: &mut Context<Self>,
+    ) -> Self::Result {
+        info!("Graceful shutdown requested");
+        self.shutdown()
     }
 }
 
@@ -8,9 +8,12 @@ use derive_more::From;
 use medea_control_api_proto::grpc::api as proto;
 use medea_macro::enum_delegate;
 
-use crate::signalling::elements::endpoints::webrtc::{
-    play_endpoint::WeakWebRtcPlayEndpoint,
-    publish_endpoint::WeakWebRtcPublishEndpoint,
+use crate::{
+    api::control::callback::url::CallbackUrl,
+    signalling::elements::endpoints::webrtc::{
+        play_endpoint::WeakWebRtcPlayEndpoint,
+        publish_endpoint::WeakWebRtcPublishEndpoint,
+    },
 };
 
 /// Enum which can store all kinds of [Medea] endpoints.
@@ -18,6 +21,8 @@ use crate::signalling::elements::endpoints::webrtc::{
 /// [Medea]: https://github.com/instrumentisto/medea
 #[enum_delegate(pub fn is_force_relayed(&self) -> bool)]
 #[enum_delegate(pub fn has_traffic_callback(&self) -> bool)]
+#[enum_delegate(pub fn get_on_start(&self) -> Option<CallbackUrl>)]
+#[enum_delegate(pub fn get_on_stop(&self) -> Option<CallbackUrl>)]
 #[derive(Clone, Debug, From)]
 pub enum Endpoint {
     WebRtcPublishEndpoint(webrtc::WebRtcPublishEndpoint),
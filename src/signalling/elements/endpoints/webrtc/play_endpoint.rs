@@ -10,6 +10,7 @@ use medea_control_api_proto::grpc::api as proto;
 
 use crate::{
     api::control::{
+        callback::url::CallbackUrl,
         endpoints::webrtc_play_endpoint::WebRtcPlayId as Id, refs::SrcUri,
     },
     signalling::elements::{
@@ -55,6 +56,12 @@ struct WebRtcPlayEndpointInner {
     /// Indicator whether only `relay` ICE candidates are allowed for this
     /// [`WebRtcPlayEndpoint`].
     is_force_relayed: bool,
+
+    /// URL to which `OnStart` Control API callback will be sent.
+    on_start: Option<CallbackUrl>,
+
+    /// URL to which `OnStop` Control API callback will be sent.
+    on_stop: Option<CallbackUrl>,
 }
 
 impl WebRtcPlayEndpointInner {
@@ -128,6 +135,8 @@ impl WebRtcPlayEndpoint {
             peer_id: None,
             partner_peer_id: None,
             is_force_relayed,
+            on_start: None,
+            on_stop: None,
         })))
     }
 
@@ -210,13 +219,40 @@ impl WebRtcPlayEndpoint {
     }
 
     /// Returns `true` if `on_start` or `on_stop` callback is set.
-    #[allow(clippy::unused_self)]
     #[inline]
     #[must_use]
     pub fn has_traffic_callback(&self) -> bool {
-        // TODO: Must depend on on_start/on_stop endpoint callbacks, when those
-        //       will be added (#91).
-        true
+        let inner = self.0.borrow();
+        inner.on_start.is_some() || inner.on_stop.is_some()
+    }
+
+    /// Returns [`CallbackUrl`] to which `OnStart` Control API callback should
+    /// be sent.
+    #[inline]
+    #[must_use]
+    pub fn get_on_start(&self) -> Option<CallbackUrl> {
+        self.0.borrow().on_start.clone()
+    }
+
+    /// Returns [`CallbackUrl`] to which `OnStop` Control API callback should
+    /// be sent.
+    #[inline]
+    #[must_use]
+    pub fn get_on_stop(&self) -> Option<CallbackUrl> {
+        self.0.borrow().on_stop.clone()
+    }
+
+    /// Sets [`CallbackUrl`]s of `OnStart` and `OnStop` Control API callbacks
+    /// of this [`WebRtcPlayEndpoint`].
+    #[inline]
+    pub fn set_callback_urls(
+        &self,
+        on_start: Option<CallbackUrl>,
+        on_stop: Option<CallbackUrl>,
+    ) {
+        let mut inner = self.0.borrow_mut();
+        inner.on_start = on_start;
+        inner.on_stop = on_stop;
     }
 
     /// Downgrades [`WebRtcPlayEndpoint`] to [`WeakWebRtcPlayEndpoint`] weak
@@ -273,8 +309,14 @@ impl From<WebRtcPlayEndpoint> for proto::member::Element {
 impl From<WebRtcPlayEndpoint> for proto::WebRtcPlayEndpoint {
     fn from(endpoint: WebRtcPlayEndpoint) -> Self {
         Self {
-            on_start: String::new(),
-            on_stop: String::new(),
+            on_start: endpoint
+                .get_on_start()
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+            on_stop: endpoint
+                .get_on_stop()
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
             src: endpoint.src_uri().to_string(),
             id: endpoint.id().to_string(),
             force_relay: endpoint.is_force_relayed(),
@@ -10,8 +10,11 @@ use medea_client_api_proto::{PeerId, TrackId};
 use medea_control_api_proto::grpc::api as proto;
 
 use crate::{
-    api::control::endpoints::webrtc_publish_endpoint::{
-        AudioSettings, P2pMode, VideoSettings, WebRtcPublishId as Id,
+    api::control::{
+        callback::url::CallbackUrl,
+        endpoints::webrtc_publish_endpoint::{
+            AudioSettings, P2pMode, VideoSettings, WebRtcPublishId as Id,
+        },
     },
     signalling::elements::{
         endpoints::webrtc::play_endpoint::WeakWebRtcPlayEndpoint,
@@ -37,6 +40,12 @@ struct WebRtcPublishEndpointInner {
     /// [`WebRtcPublishEndpoint`].
     is_force_relayed: bool,
 
+    /// URL to which `OnStart` Control API callback will be sent.
+    on_start: Option<CallbackUrl>,
+
+    /// URL to which `OnStop` Control API callback will be sent.
+    on_stop: Option<CallbackUrl>,
+
     /// All sinks of this [`WebRtcPublishEndpoint`].
     sinks: Vec<WeakWebRtcPlayEndpoint>,
 
@@ -104,6 +113,7 @@ impl WebRtcPublishEndpointInner {
     #[allow(clippy::trivially_copy_pass_by_ref)]
     fn remove_peer_id(&mut self, peer_id: &PeerId) {
         self.peer_ids.remove(peer_id);
+        self.tracks_ids.remove(peer_id);
     }
 
     fn remove_peer_ids(&mut self, peer_ids: &[PeerId]) {
@@ -136,6 +146,8 @@ impl WebRtcPublishEndpoint {
             id,
             p2p,
             is_force_relayed,
+            on_start: None,
+            on_stop: None,
             sinks: Vec::new(),
             owner,
             audio_settings,
@@ -195,7 +207,11 @@ impl WebRtcPublishEndpoint {
         self.0.borrow_mut().reset()
     }
 
-    /// Removes all [`PeerId`]s related to this [`WebRtcPublishEndpoint`].
+    /// Removes the provided [`PeerId`]s from this [`WebRtcPublishEndpoint`]
+    /// along with [`TrackId`]s of its [`MediaTrack`]s in the related [`Peer`]s.
+    ///
+    /// [`MediaTrack`]: crate::media::track::MediaTrack
+    /// [`Peer`]: crate::media::peer::Peer
     #[inline]
     pub fn remove_peer_ids(&self, peer_ids: &[PeerId]) {
         self.0.borrow_mut().remove_peer_ids(peer_ids)
@@ -233,6 +249,17 @@ impl WebRtcPublishEndpoint {
         self.0.borrow().is_force_relayed
     }
 
+    /// Sets the indicator whether only `relay` ICE candidates are allowed for
+    /// this [`WebRtcPublishEndpoint`].
+    ///
+    /// Doesn't affect the already created [`Peer`]s.
+    ///
+    /// [`Peer`]: crate::media::peer::Peer
+    #[inline]
+    pub fn set_force_relayed(&self, is_force_relayed: bool) {
+        self.0.borrow_mut().is_force_relayed = is_force_relayed;
+    }
+
     /// Adds [`TrackId`] of the [`MediaTrack`] related to this
     /// [`WebRtcPublishEndpoint`].
     ///
@@ -256,13 +283,40 @@ impl WebRtcPublishEndpoint {
     }
 
     /// Returns `true` if `on_start` or `on_stop` callback is set.
-    #[allow(clippy::unused_self)]
     #[inline]
     #[must_use]
     pub fn has_traffic_callback(&self) -> bool {
-        // TODO: Must depend on on_start/on_stop endpoint callbacks, when those
-        //       will be added (#91).
-        true
+        let inner = self.0.borrow();
+        inner.on_start.is_some() || inner.on_stop.is_some()
+    }
+
+    /// Returns [`CallbackUrl`] to which `OnStart` Control API callback should
+    /// be sent.
+    #[inline]
+    #[must_use]
+    pub fn get_on_start(&self) -> Option<CallbackUrl> {
+        self.0.borrow().on_start.clone()
+    }
+
+    /// Returns [`CallbackUrl`] to which `OnStop` Control API callback should
+    /// be sent.
+    #[inline]
+    #[must_use]
+    pub fn get_on_stop(&self) -> Option<CallbackUrl> {
+        self.0.borrow().on_stop.clone()
+    }
+
+    /// Sets [`CallbackUrl`]s of `OnStart` and `OnStop` Control API callbacks
+    /// of this [`WebRtcPublishEndpoint`].
+    #[inline]
+    pub fn set_callback_urls(
+        &self,
+        on_start: Option<CallbackUrl>,
+        on_stop: Option<CallbackUrl>,
+    ) {
+        let mut inner = self.0.borrow_mut();
+        inner.on_start = on_start;
+        inner.on_stop = on_stop;
     }
 
     /// Returns [`AudioSettings`] of this [`WebRtcPublishEndpoint`].
@@ -331,8 +385,14 @@ impl From<WebRtcPublishEndpoint> for proto::WebRtcPublishEndpoint {
             force_relay: endpoint.is_force_relayed(),
             audio_settings: Some(endpoint.audio_settings().into()),
             video_settings: Some(endpoint.video_settings().into()),
-            on_stop: String::new(),
-            on_start: String::new(),
+            on_stop: endpoint
+                .get_on_stop()
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+            on_start: endpoint
+                .get_on_start()
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
         }
     }
 }
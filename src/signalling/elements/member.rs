@@ -16,12 +16,16 @@ use medea_client_api_proto::{self as client_proto, MemberId, PeerId, RoomId};
 use medea_control_api_proto::grpc::api as proto;
 
 use crate::{
-    api::control::{
-        callback::url::CallbackUrl,
-        member::Credential,
-        refs::{Fid, StatefulFid, ToEndpoint, ToMember, ToRoom},
-        EndpointId, MemberSpec, RoomSpec, TryFromElementError, WebRtcPlayId,
-        WebRtcPublishId,
+    api::{
+        client::rpc_connection::Liveness,
+        control::{
+            callback::url::CallbackUrl,
+            endpoints::WebRtcPlayEndpoint as WebRtcPlayEndpointSpec,
+            member::Credential,
+            refs::{Fid, StatefulFid, ToEndpoint, ToMember, ToRoom},
+            EndpointId, MemberSpec, RoomSpec, TryFromElementError,
+            WebRtcPlayId, WebRtcPublishId,
+        },
     },
     conf,
     log::prelude::*,
@@ -82,6 +86,11 @@ struct MemberInner {
     /// All [`WebRtcPlayEndpoint`]s of this [`Member`].
     sinks: HashMap<WebRtcPlayId, WebRtcPlayEndpoint>,
 
+    /// Specs of the wildcard [`WebRtcPlayEndpoint`]s of this [`Member`],
+    /// which are expanded into a [`WebRtcPlayEndpoint`] per each matching
+    /// [`WebRtcPublishEndpoint`] of the other [`Member`]s.
+    wildcard_sinks: HashMap<WebRtcPlayId, WebRtcPlayEndpointSpec>,
+
     /// Credentials for this [`Member`].
     credentials: Credential,
 
@@ -91,6 +100,10 @@ struct MemberInner {
     /// URL to which `on_leave` Control API callback will be sent.
     on_leave: Option<CallbackUrl>,
 
+    /// URL to which `on_negotiation_timeout` Control API callback will be
+    /// sent.
+    on_negotiation_timeout: Option<CallbackUrl>,
+
     /// Timeout of receiving heartbeat messages from the [`Member`] via Client
     /// API.
     ///
@@ -104,6 +117,20 @@ struct MemberInner {
 
     /// Interval of sending heartbeat `Ping`s to the [`Member`] via Client API.
     ping_interval: Duration,
+
+    /// Arbitrary key-value metadata of this [`Member`], which is delivered to
+    /// other [`Member`]s of the [`Room`].
+    ///
+    /// [`Room`]: crate::signalling::room::Room
+    metadata: HashMap<String, String>,
+
+    /// Key-value feature flags of this [`Member`], which are delivered to the
+    /// [`Member`] only via Client API, overriding the configured ones.
+    features: HashMap<String, String>,
+
+    /// Last known [`Liveness`] metrics of the [`Member`]'s Client API
+    /// connection.
+    liveness: Liveness,
 }
 
 impl Member {
@@ -125,13 +152,18 @@ impl Member {
             id,
             srcs: HashMap::new(),
             sinks: HashMap::new(),
+            wildcard_sinks: HashMap::new(),
             credentials,
             room_id,
             on_leave: None,
             on_join: None,
+            on_negotiation_timeout: None,
             idle_timeout,
             reconnect_timeout,
             ping_interval,
+            metadata: HashMap::new(),
+            features: HashMap::new(),
+            liveness: Liveness::default(),
         })))
     }
 
@@ -184,10 +216,20 @@ impl Member {
             .ok_or_else(|| MembersLoadError::MemberNotFound(self.get_fid()))?;
 
         this_member.set_callback_urls(&this_member_spec);
+        this_member.set_metadata(this_member_spec.metadata().clone());
+        this_member.set_features(this_member_spec.features().clone());
 
         for (spec_play_name, spec_play_endpoint) in
             this_member_spec.play_endpoints()
         {
+            if spec_play_endpoint.src.is_wildcard() {
+                self.insert_wildcard_sink(
+                    spec_play_name,
+                    spec_play_endpoint.clone(),
+                );
+                continue;
+            }
+
             let publisher_id =
                 MemberId(spec_play_endpoint.src.member_id.to_string());
             let publisher_member =
@@ -222,6 +264,10 @@ impl Member {
                     this_member.downgrade(),
                     spec_play_endpoint.force_relay,
                 );
+                new_play_endpoint.set_callback_urls(
+                    spec_play_endpoint.on_start.clone(),
+                    spec_play_endpoint.on_stop.clone(),
+                );
 
                 self.insert_sink(new_play_endpoint.clone());
 
@@ -236,6 +282,10 @@ impl Member {
                     publisher_endpoint.audio_settings,
                     publisher_endpoint.video_settings,
                 );
+                new_publish.set_callback_urls(
+                    publisher_endpoint.on_start.clone(),
+                    publisher_endpoint.on_stop.clone(),
+                );
 
                 let new_self_play = WebRtcPlayEndpoint::new(
                     spec_play_name,
@@ -244,6 +294,10 @@ impl Member {
                     this_member.downgrade(),
                     spec_play_endpoint.force_relay,
                 );
+                new_self_play.set_callback_urls(
+                    spec_play_endpoint.on_start.clone(),
+                    spec_play_endpoint.on_stop.clone(),
+                );
 
                 new_publish.add_sink(new_self_play.downgrade());
 
@@ -259,14 +313,17 @@ impl Member {
             .publish_endpoints()
             .filter(|(endpoint_id, _)| self.srcs().get(endpoint_id).is_none())
             .for_each(|(endpoint_id, e)| {
-                self.insert_src(WebRtcPublishEndpoint::new(
+                let publish = WebRtcPublishEndpoint::new(
                     endpoint_id,
                     e.p2p,
                     this_member.downgrade(),
                     e.force_relay,
                     e.audio_settings,
                     e.video_settings,
-                ));
+                );
+                publish
+                    .set_callback_urls(e.on_start.clone(), e.on_stop.clone());
+                self.insert_src(publish);
             });
 
         Ok(())
@@ -323,6 +380,17 @@ impl Member {
         self.0.borrow().credentials.clone()
     }
 
+    /// Replaces credentials of this [`Member`] with the provided ones.
+    ///
+    /// Already established [`RpcConnection`]s are not affected, while the
+    /// further ones are authorized with the new credentials.
+    ///
+    /// [`RpcConnection`]: crate::api::client::rpc_connection::RpcConnection
+    #[inline]
+    pub fn set_credentials(&self, credentials: Credential) {
+        self.0.borrow_mut().credentials = credentials;
+    }
+
     /// Verifies provided [`client_proto::Credential`].
     #[inline]
     #[must_use]
@@ -386,6 +454,119 @@ impl Member {
         self.0.borrow_mut().srcs.insert(endpoint.id(), endpoint);
     }
 
+    /// Inserts spec of the wildcard sink endpoint into this [`Member`].
+    ///
+    /// Doesn't expand it, so [`Member::expand_wildcard_sinks()`] should be
+    /// called for the already existing [`WebRtcPublishEndpoint`]s.
+    pub fn insert_wildcard_sink(
+        &self,
+        id: WebRtcPlayId,
+        spec: WebRtcPlayEndpointSpec,
+    ) {
+        self.0.borrow_mut().wildcard_sinks.insert(id, spec);
+    }
+
+    /// Returns specs of all the wildcard sinks of this [`Member`].
+    #[inline]
+    #[must_use]
+    pub fn wildcard_sinks(
+        &self,
+    ) -> HashMap<WebRtcPlayId, WebRtcPlayEndpointSpec> {
+        self.0.borrow().wildcard_sinks.clone()
+    }
+
+    /// Lookups spec of the wildcard sink by [`WebRtcPlayId`].
+    #[inline]
+    #[must_use]
+    pub fn get_wildcard_sink_by_id(
+        &self,
+        id: &WebRtcPlayId,
+    ) -> Option<WebRtcPlayEndpointSpec> {
+        self.0.borrow().wildcard_sinks.get(id).cloned()
+    }
+
+    /// Creates a [`WebRtcPlayEndpoint`] receiving from the provided
+    /// [`WebRtcPublishEndpoint`] for each wildcard sink of this [`Member`]
+    /// matching it.
+    ///
+    /// [`WebRtcPublishEndpoint`]s of this [`Member`] itself are not matched,
+    /// as well as the ones already received by the expanded sinks.
+    ///
+    /// Returns the created [`WebRtcPlayEndpoint`]s.
+    pub fn expand_wildcard_sinks(
+        &self,
+        src: &WebRtcPublishEndpoint,
+    ) -> Vec<WebRtcPlayEndpoint> {
+        let publisher_id = src.owner().id();
+        if publisher_id == self.id() {
+            return Vec::new();
+        }
+
+        let mut expanded = Vec::new();
+        for (wildcard_id, spec) in self.wildcard_sinks() {
+            if spec.src.endpoint_id != src.id() {
+                continue;
+            }
+            let id = expanded_sink_id(&wildcard_id, &publisher_id);
+            if self.get_sink_by_id(&id).is_some() {
+                continue;
+            }
+
+            let sink = WebRtcPlayEndpoint::new(
+                id,
+                spec.src.with_member_id(publisher_id.clone()),
+                src.downgrade(),
+                self.downgrade(),
+                spec.force_relay,
+            );
+            sink.set_callback_urls(spec.on_start, spec.on_stop);
+            src.add_sink(sink.downgrade());
+            self.insert_sink(sink.clone());
+            expanded.push(sink);
+        }
+        expanded
+    }
+
+    /// Returns ID of the wildcard sink which the [`WebRtcPlayEndpoint`] with
+    /// the provided [`WebRtcPlayId`] is expanded from, if any.
+    #[must_use]
+    pub fn wildcard_of(&self, id: &WebRtcPlayId) -> Option<WebRtcPlayId> {
+        let sink = self.get_sink_by_id(id)?;
+        let src_uri = sink.src_uri();
+        self.0
+            .borrow()
+            .wildcard_sinks
+            .iter()
+            .find(|(wildcard_id, spec)| {
+                spec.src.endpoint_id == src_uri.endpoint_id
+                    && &expanded_sink_id(wildcard_id, &src_uri.member_id) == id
+            })
+            .map(|(wildcard_id, _)| wildcard_id.clone())
+    }
+
+    /// Removes the wildcard sink with the provided [`WebRtcPlayId`] along with
+    /// all the [`WebRtcPlayEndpoint`]s expanded from it.
+    ///
+    /// Returns [`None`] if there is no such wildcard sink, or the removed
+    /// [`WebRtcPlayEndpoint`]s otherwise.
+    pub fn remove_wildcard_sink(
+        &self,
+        id: &WebRtcPlayId,
+    ) -> Option<Vec<WebRtcPlayEndpoint>> {
+        let expanded: Vec<_> = self
+            .sinks_ids()
+            .into_iter()
+            .filter(|sink_id| self.wildcard_of(sink_id).as_ref() == Some(id))
+            .collect();
+        self.0.borrow_mut().wildcard_sinks.remove(id)?;
+        Some(
+            expanded
+                .into_iter()
+                .filter_map(|sink_id| self.remove_sink(&sink_id))
+                .collect(),
+        )
+    }
+
     /// Lookups [`WebRtcPublishEndpoint`] source endpoint by
     /// [`WebRtcPublishId`].
     #[inline]
@@ -487,6 +668,14 @@ impl Member {
         self.0.borrow().on_leave.clone()
     }
 
+    /// Returns [`CallbackUrl`] to which Medea should send
+    /// `OnNegotiationTimeout` callback.
+    #[inline]
+    #[must_use]
+    pub fn get_on_negotiation_timeout(&self) -> Option<CallbackUrl> {
+        self.0.borrow().on_negotiation_timeout.clone()
+    }
+
     /// Returns timeout of receiving heartbeat messages from the [`Member`] via
     /// Client API.
     ///
@@ -514,10 +703,53 @@ impl Member {
         self.0.borrow().ping_interval
     }
 
+    /// Returns last known [`Liveness`] metrics of the [`Member`]'s Client API
+    /// connection.
+    #[inline]
+    #[must_use]
+    pub fn get_liveness(&self) -> Liveness {
+        self.0.borrow().liveness
+    }
+
+    /// Sets [`Liveness`] metrics of the [`Member`]'s Client API connection.
+    #[inline]
+    pub fn set_liveness(&self, liveness: Liveness) {
+        self.0.borrow_mut().liveness = liveness;
+    }
+
     /// Sets all [`CallbackUrl`]'s from [`MemberSpec`].
     pub fn set_callback_urls(&self, spec: &MemberSpec) {
         self.0.borrow_mut().on_leave = spec.on_leave().clone();
         self.0.borrow_mut().on_join = spec.on_join().clone();
+        self.0.borrow_mut().on_negotiation_timeout =
+            spec.on_negotiation_timeout().clone();
+    }
+
+    /// Returns key-value metadata of this [`Member`].
+    #[inline]
+    #[must_use]
+    pub fn get_metadata(&self) -> HashMap<String, String> {
+        self.0.borrow().metadata.clone()
+    }
+
+    /// Replaces key-value metadata of this [`Member`] with the provided one.
+    #[inline]
+    pub fn set_metadata(&self, metadata: HashMap<String, String>) {
+        self.0.borrow_mut().metadata = metadata;
+    }
+
+    /// Returns key-value feature flags of this [`Member`].
+    #[inline]
+    #[must_use]
+    pub fn get_features(&self) -> HashMap<String, String> {
+        self.0.borrow().features.clone()
+    }
+
+    /// Replaces key-value feature flags of this [`Member`] with the provided
+    /// ones.
+    #[inline]
+    pub fn set_features(&self, features: HashMap<String, String>) {
+        self.0.borrow_mut().features = features;
     }
 }
 
@@ -545,6 +777,17 @@ impl WeakMember {
     }
 }
 
+/// Returns [`WebRtcPlayId`] of the [`WebRtcPlayEndpoint`] expanded from the
+/// wildcard sink with the provided [`WebRtcPlayId`] for the publishing
+/// [`Member`] with the provided [`MemberId`].
+#[must_use]
+fn expanded_sink_id(
+    wildcard_id: &WebRtcPlayId,
+    publisher_id: &MemberId,
+) -> WebRtcPlayId {
+    format!("{}-{}", wildcard_id, publisher_id).into()
+}
+
 /// Creates all empty [`Member`]s from [`RoomSpec`] and then
 /// loads all related to this [`Member`]s sources and sinks endpoints.
 ///
@@ -558,7 +801,7 @@ impl WeakMember {
 /// Errors with [`MembersLoadError`] if loading [`Member`] fails.
 pub fn parse_members(
     room_spec: &RoomSpec,
-    rpc_conf: conf::Rpc,
+    rpc_conf: &conf::Rpc,
 ) -> Result<HashMap<MemberId, Member>, MembersLoadError> {
     let members_spec = room_spec.members().map_err(|e| {
         MembersLoadError::TryFromError(
@@ -587,6 +830,13 @@ pub fn parse_members(
     for member in members.values() {
         member.load(room_spec, &members)?;
     }
+    for member in members.values() {
+        for publisher in members.values() {
+            for src in publisher.srcs().values() {
+                member.expand_wildcard_sinks(src);
+            }
+        }
+    }
 
     debug!(
         "Created ParticipantService with participants: {:?}.",
@@ -617,7 +867,25 @@ impl From<Member> for proto::Member {
         let member_pipeline = m
             .sinks()
             .into_iter()
+            .filter(|(id, _)| m.wildcard_of(id).is_none())
             .map(|(id, play)| (id.to_string(), play.into()))
+            .chain(m.wildcard_sinks().into_iter().map(|(id, spec)| {
+                let play = proto::WebRtcPlayEndpoint {
+                    id: id.to_string(),
+                    src: spec.src.to_string(),
+                    force_relay: spec.force_relay,
+                    on_start: spec
+                        .on_start
+                        .map(|c| c.to_string())
+                        .unwrap_or_default(),
+                    on_stop: spec
+                        .on_stop
+                        .map(|c| c.to_string())
+                        .unwrap_or_default(),
+                };
+                let el = proto::member::element::El::WebrtcPlay(play);
+                (id.to_string(), proto::member::Element { el: Some(el) })
+            }))
             .chain(
                 m.srcs()
                     .into_iter()
@@ -633,10 +901,16 @@ impl From<Member> for proto::Member {
                 .map(|c| c.to_string())
                 .unwrap_or_default(),
             on_join: m.get_on_join().map(|c| c.to_string()).unwrap_or_default(),
+            on_negotiation_timeout: m
+                .get_on_negotiation_timeout()
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
             reconnect_timeout: Some(m.get_reconnect_timeout().into()),
             idle_timeout: Some(m.get_idle_timeout().into()),
             ping_interval: Some(m.get_ping_interval().into()),
             pipeline: member_pipeline,
+            metadata: m.get_metadata(),
+            features: m.get_features(),
         }
     }
 }
@@ -676,6 +950,10 @@ mod tests {
                   kind: Member
                   credentials:
                     plain: test
+                  metadata:
+                    display_name: Alice
+                  features:
+                    delta_sync: "true"
                   spec:
                     pipeline:
                       publish:
@@ -717,7 +995,7 @@ mod tests {
         let room_element: RootElement =
             serde_yaml::from_str(TEST_SPEC).unwrap();
         let room_spec = RoomSpec::try_from(&room_element).unwrap();
-        parse_members(&room_spec, conf::Rpc::default()).unwrap()
+        parse_members(&room_spec, &conf::Rpc::default()).unwrap()
     }
 
     #[test]
@@ -762,6 +1040,98 @@ mod tests {
         assert!(is_some_member_has_responder_in_sinks);
     }
 
+    #[test]
+    fn load_metadata() {
+        let store = get_test_store();
+
+        let caller = store.get(&id("caller")).unwrap();
+        assert_eq!(
+            caller
+                .get_metadata()
+                .get("display_name")
+                .map(String::as_str),
+            Some("Alice"),
+        );
+        let responder = store.get(&id("responder")).unwrap();
+        assert!(responder.get_metadata().is_empty());
+
+        caller.set_metadata(HashMap::new());
+        assert!(caller.get_metadata().is_empty());
+    }
+
+    #[test]
+    fn load_features() {
+        let store = get_test_store();
+
+        let caller = store.get(&id("caller")).unwrap();
+        assert_eq!(
+            caller.get_features().get("delta_sync").map(String::as_str),
+            Some("true"),
+        );
+        assert!(caller.get_metadata().get("delta_sync").is_none());
+        let responder = store.get(&id("responder")).unwrap();
+        assert!(responder.get_features().is_empty());
+    }
+
+    #[test]
+    fn expands_wildcard_sinks() {
+        const SPEC: &str = r#"
+            kind: Room
+            id: test-call
+            spec:
+              pipeline:
+                caller:
+                  kind: Member
+                  credentials:
+                    plain: test
+                  spec:
+                    pipeline:
+                      publish:
+                        kind: WebRtcPublishEndpoint
+                        spec:
+                          p2p: Always
+                viewer:
+                  kind: Member
+                  credentials:
+                    plain: test
+                  spec:
+                    pipeline:
+                      publish:
+                        kind: WebRtcPublishEndpoint
+                        spec:
+                          p2p: Always
+                      play:
+                        kind: WebRtcPlayEndpoint
+                        spec:
+                          src: "local://test-call/*/publish"
+        "#;
+        let room_element: RootElement = serde_yaml::from_str(SPEC).unwrap();
+        let room_spec = RoomSpec::try_from(&room_element).unwrap();
+        let store = parse_members(&room_spec, &conf::Rpc::default()).unwrap();
+
+        let caller = store.get(&id("caller")).unwrap();
+        let viewer = store.get(&id("viewer")).unwrap();
+        assert_eq!(viewer.sinks_ids(), vec![id("play-caller")]);
+        let sink = viewer.get_sink_by_id(&id("play-caller")).unwrap();
+        assert!(sink
+            .src()
+            .ptr_eq(&caller.get_src_by_id(&id("publish")).unwrap()));
+        assert_eq!(viewer.wildcard_of(&id("play-caller")), Some(id("play")));
+        drop(sink);
+
+        let member = proto::Member::from(viewer.clone());
+        assert_eq!(member.pipeline.len(), 2);
+        assert!(member.pipeline.contains_key("play"));
+
+        assert_eq!(viewer.remove_wildcard_sink(&id("play")).unwrap().len(), 1);
+        assert!(viewer.sinks().is_empty());
+        assert!(caller
+            .get_src_by_id(&id("publish"))
+            .unwrap()
+            .sinks()
+            .is_empty());
+    }
+
     #[test]
     fn publisher_delete_all_their_players() {
         let store = get_test_store();
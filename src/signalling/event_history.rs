@@ -0,0 +1,171 @@
+//! History of the [`Event`]s sent to a `Member`, allowing to replay the ones
+//! its `Client` has missed while restoring its connection.
+
+use std::collections::VecDeque;
+
+use medea_client_api_proto::Event;
+
+/// Bounded history of the [`Event`]s sent to a single `Member` of a `Room`.
+#[derive(Debug)]
+pub struct EventHistory {
+    /// Maximum number of the [`Event`]s kept for replaying.
+    ///
+    /// Zero means that [`Event`]s are only numbered, but never kept.
+    capacity: usize,
+
+    /// Sequence number to be assigned to the next [`Event`].
+    next_seq: u64,
+
+    /// Kept [`Event`]s along with their sequence numbers, in ascending order.
+    events: VecDeque<(u64, Event)>,
+
+    /// Sequence number of the first [`Event`] held from sending until the
+    /// `Client` is synchronized after restoring its connection.
+    ///
+    /// [`None`] if [`Event`]s are not held.
+    held_since: Option<u64>,
+}
+
+impl EventHistory {
+    /// Creates a new empty [`EventHistory`] keeping at most `capacity`
+    /// [`Event`]s.
+    #[inline]
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            next_seq: 1,
+            events: VecDeque::new(),
+            held_since: None,
+        }
+    }
+
+    /// Records the provided [`Event`] being sent, returning its sequence
+    /// number.
+    pub fn push(&mut self, event: &Event) -> u64 {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+        if self.capacity > 0 {
+            if self.events.len() == self.capacity {
+                self.events.pop_front();
+            }
+            self.events.push_back((seq, event.clone()));
+        }
+        seq
+    }
+
+    /// Returns all the kept [`Event`]s sent after the one with the provided
+    /// `last_seq`, along with their sequence numbers.
+    ///
+    /// Returns [`None`] if some of these [`Event`]s are not kept anymore, or
+    /// `last_seq` has never been assigned, so they cannot be replayed.
+    #[must_use]
+    pub fn since(&self, last_seq: u64) -> Option<Vec<(u64, Event)>> {
+        if last_seq >= self.next_seq || self.capacity == 0 {
+            return None;
+        }
+        let oldest = self.events.front().map_or(self.next_seq, |(s, _)| *s);
+        if last_seq + 1 < oldest {
+            return None;
+        }
+        Some(
+            self.events
+                .iter()
+                .filter(|(seq, _)| *seq > last_seq)
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Indicates whether sending [`Event`]s is held until the `Client` is
+    /// synchronized.
+    #[inline]
+    #[must_use]
+    pub fn is_held(&self) -> bool {
+        self.held_since.is_some()
+    }
+
+    /// Holds sending the next [`Event`]s until the `Client` is synchronized,
+    /// so the missed ones can be replayed before them.
+    ///
+    /// Does nothing if no [`Event`]s are kept.
+    pub fn hold(&mut self) {
+        if self.capacity > 0 && self.held_since.is_none() {
+            self.held_since = Some(self.next_seq);
+        }
+    }
+
+    /// Resumes sending [`Event`]s, returning the sequence number of the first
+    /// held one, if any.
+    #[inline]
+    pub fn resume(&mut self) -> Option<u64> {
+        self.held_since.take()
+    }
+
+    /// Forgets all the kept [`Event`]s and resumes sending them, preserving
+    /// the numbering.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.events.clear();
+        self.held_since = None;
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use medea_client_api_proto::MemberId;
+
+    use super::*;
+
+    fn event(n: u32) -> Event {
+        Event::RoomJoined {
+            member_id: MemberId(n.to_string()),
+        }
+    }
+
+    #[test]
+    fn replays_missed_events() {
+        let mut history = EventHistory::new(3);
+        for n in 0..4 {
+            assert_eq!(history.push(&event(n)), u64::from(n) + 1);
+        }
+
+        assert_eq!(history.since(4), Some(Vec::new()));
+        assert_eq!(history.since(2), Some(vec![(3, event(2)), (4, event(3))]));
+        assert_eq!(history.since(1).map(|e| e.len()), Some(3));
+        assert_eq!(history.since(0), None);
+        assert_eq!(history.since(5), None);
+    }
+
+    #[test]
+    fn holds_events_until_resumed() {
+        let mut history = EventHistory::new(4);
+        history.push(&event(0));
+        history.hold();
+        assert!(history.is_held());
+        history.push(&event(1));
+        history.hold();
+
+        assert_eq!(history.resume(), Some(2));
+        assert!(!history.is_held());
+        assert_eq!(history.resume(), None);
+    }
+
+    #[test]
+    fn never_replays_without_capacity() {
+        let mut history = EventHistory::new(0);
+        assert_eq!(history.push(&event(0)), 1);
+        assert_eq!(history.since(1), None);
+
+        history.hold();
+        assert!(!history.is_held());
+
+        let mut history = EventHistory::new(2);
+        history.push(&event(0));
+        history.hold();
+        history.clear();
+        assert!(!history.is_held());
+        assert_eq!(history.push(&event(1)), 2);
+        assert_eq!(history.since(1), Some(vec![(2, event(1))]));
+    }
+}
@@ -4,6 +4,7 @@
 //! [signalling]: https://webrtcglossary.com/signaling
 
 pub mod elements;
+pub mod event_history;
 pub mod participants;
 pub mod peers;
 pub mod room;
@@ -7,7 +7,12 @@
 //! [`RpcConnection`]: crate::api::client::rpc_connection::RpcConnection
 //! [`ParticipantService`]: crate::signalling::participants::ParticipantService
 
-use std::{collections::HashMap, time::Instant};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
 
 use actix::{
     fut::wrap_future, AsyncContext, Context, ContextFutureSpawner as _,
@@ -17,7 +22,8 @@ use derive_more::Display;
 use failure::Fail;
 use futures::future::{self, FutureExt as _, LocalBoxFuture};
 use medea_client_api_proto::{
-    CloseDescription, CloseReason, Credential, Event, MemberId, RoomId,
+    ClientCapabilities, CloseDescription, CloseReason, Credential, Event,
+    MemberId, RoomId,
 };
 
 use crate::{
@@ -26,18 +32,21 @@ use crate::{
             ClosedReason, RpcConnection, RpcConnectionClosed,
         },
         control::{
+            callback::ClientSession,
             refs::{Fid, ToEndpoint, ToMember},
             MemberSpec, RoomSpec,
         },
     },
     conf::Rpc as RpcConf,
     log::prelude::*,
+    media::SdpQuirks,
     signalling::{
         elements::{
             endpoints::webrtc::{WebRtcPlayEndpoint, WebRtcPublishEndpoint},
             member::MemberError,
             parse_members, Member, MembersLoadError,
         },
+        event_history::EventHistory,
         room::RoomError,
         Room,
     },
@@ -94,6 +103,27 @@ pub struct ParticipantService {
     /// If nothing provided into [`Member`] element spec then this values will
     /// be used.
     rpc_conf: RpcConf,
+
+    /// Start of the current rate limiting window and number of application
+    /// data messages relayed in it for each [`Member`].
+    app_data_windows: HashMap<MemberId, (Instant, u32)>,
+
+    /// [`ClientCapabilities`] reported by `Client`s of [`Member`]s when
+    /// joining this [`Room`].
+    capabilities: HashMap<MemberId, ClientCapabilities>,
+
+    /// [`SdpQuirks`] of the user agents [`Member`]s are connected with.
+    sdp_quirks: HashMap<MemberId, SdpQuirks>,
+
+    /// Remote addresses of the `Client`s [`Member`]s are connected from, along
+    /// with the moments they have joined this [`Room`] at.
+    sessions: HashMap<MemberId, (Option<SocketAddr>, Instant)>,
+
+    /// [`EventHistory`]s of the [`Event`]s sent to [`Member`]s.
+    ///
+    /// Wrapped into [`RefCell`], since [`Event`]s are sent via shared
+    /// references.
+    event_histories: RefCell<HashMap<MemberId, EventHistory>>,
 }
 
 impl ParticipantService {
@@ -108,10 +138,15 @@ impl ParticipantService {
     ) -> Result<Self, MembersLoadError> {
         Ok(Self {
             room_id: room_spec.id().clone(),
-            members: parse_members(room_spec, context.config.rpc)?,
+            members: parse_members(room_spec, &context.config.rpc)?,
             connections: HashMap::new(),
             drop_connection_tasks: HashMap::new(),
-            rpc_conf: context.config.rpc,
+            rpc_conf: context.config.rpc.clone(),
+            app_data_windows: HashMap::new(),
+            capabilities: HashMap::new(),
+            sdp_quirks: HashMap::new(),
+            sessions: HashMap::new(),
+            event_histories: RefCell::default(),
         })
     }
 
@@ -206,11 +241,38 @@ impl ParticipantService {
             && !self.drop_connection_tasks.contains_key(member_id)
     }
 
+    /// Checks if [`Member`] has either active or lost (but not dropped yet)
+    /// [`RpcConnection`].
+    #[inline]
+    #[must_use]
+    pub fn member_has_any_connection(&self, member_id: &MemberId) -> bool {
+        self.connections.contains_key(member_id)
+    }
+
+    /// Returns number of [`Member`]s having either active or lost (but not
+    /// dropped yet) [`RpcConnection`]s.
+    #[inline]
+    #[must_use]
+    pub fn connections_count(&self) -> usize {
+        self.connections.len()
+    }
+
     /// Sends the given [`Event`] to the specified remote [`Member`] if its
     /// [`RpcConnection`] exists.
+    ///
+    /// Records the [`Event`] in the [`EventHistory`] of the [`Member`], and
+    /// doesn't send it while the [`EventHistory`] is held.
     pub fn send_event_to_member(&self, member_id: &MemberId, event: Event) {
         if let Some(conn) = self.connections.get(&member_id) {
-            conn.send_event(self.room_id.clone(), event);
+            let mut histories = self.event_histories.borrow_mut();
+            let history =
+                histories.entry(member_id.clone()).or_insert_with(|| {
+                    EventHistory::new(self.rpc_conf.event_history_size)
+                });
+            let seq = history.push(&event);
+            if !history.is_held() {
+                conn.send_event(self.room_id.clone(), event, Some(seq));
+            }
         } else {
             debug!(
                 "Can't send event [{:?}] cause connection with Member [{}] \
@@ -220,6 +282,160 @@ impl ParticipantService {
         }
     }
 
+    /// Resumes sending [`Event`]s to the specified [`Member`], replaying the
+    /// ones it has missed since the provided `last_event_seq`, if it has
+    /// restored its [`RpcConnection`] and all of them are still kept.
+    ///
+    /// Returns `false` if the [`Member`] should be sent the whole state of the
+    /// [`Room`] instead. In this case, only the [`Event`]s held since the
+    /// [`RpcConnection`] restoring are sent, if all of them are still kept.
+    pub fn replay_missed_events(
+        &self,
+        member_id: &MemberId,
+        last_event_seq: Option<u64>,
+    ) -> bool {
+        let conn = if let Some(conn) = self.connections.get(member_id) {
+            conn
+        } else {
+            return false;
+        };
+        let mut histories = self.event_histories.borrow_mut();
+        let history = if let Some(history) = histories.get_mut(member_id) {
+            history
+        } else {
+            return false;
+        };
+        let held_since = if let Some(seq) = history.resume() {
+            seq
+        } else {
+            return false;
+        };
+
+        let missed = last_event_seq.and_then(|seq| history.since(seq));
+        let replayed = missed.is_some();
+        let events = missed
+            .or_else(|| history.since(held_since - 1))
+            .unwrap_or_default();
+        debug!(
+            "Sending {} Events to Member [id = {}] restored its connection \
+             since Event #{:?}",
+            events.len(),
+            member_id,
+            last_event_seq,
+        );
+        for (seq, event) in events {
+            conn.send_event(self.room_id.clone(), event, Some(seq));
+        }
+        replayed
+    }
+
+    /// Sends [`Event::MemberMetadataUpdated`] with the current metadata of the
+    /// specified [`Member`] to all other connected [`Member`]s.
+    pub fn broadcast_member_metadata(&self, member_id: &MemberId) {
+        let metadata = if let Some(member) = self.members.get(member_id) {
+            member.get_metadata()
+        } else {
+            return;
+        };
+        for id in self.connections.keys().filter(|id| *id != member_id) {
+            self.send_event_to_member(
+                id,
+                Event::MemberMetadataUpdated {
+                    member_id: member_id.clone(),
+                    metadata: metadata.clone(),
+                },
+            );
+        }
+    }
+
+    /// Sends [`Event::MemberMetadataUpdated`] for each other [`Member`] having
+    /// non-empty metadata to the specified [`Member`].
+    pub fn send_members_metadata(&self, member_id: &MemberId) {
+        for (id, member) in &self.members {
+            if id == member_id {
+                continue;
+            }
+            let metadata = member.get_metadata();
+            if !metadata.is_empty() {
+                self.send_event_to_member(
+                    member_id,
+                    Event::MemberMetadataUpdated {
+                        member_id: id.clone(),
+                        metadata,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Relays application data sent by the specified [`Member`] as
+    /// [`Event::AppDataReceived`] to the `to` [`Member`], or to all other
+    /// connected [`Member`]s if `to` is `None`.
+    ///
+    /// Drops the data if the sending [`Member`] exceeds
+    /// [`RpcConf::app_data_rate_limit`].
+    pub fn relay_app_data(
+        &mut self,
+        from: &MemberId,
+        to: Option<MemberId>,
+        data: String,
+    ) {
+        if !self.take_app_data_quota(from) {
+            warn!(
+                "Dropping application data from Member [id = {}] in Room \
+                 [id = {}] cause rate limit is exceeded",
+                from, self.room_id
+            );
+            return;
+        }
+
+        let event = Event::AppDataReceived {
+            from: from.clone(),
+            data,
+        };
+        if let Some(to) = to {
+            if &to != from {
+                self.send_event_to_member(&to, event);
+            }
+        } else {
+            for id in self.connections.keys().filter(|id| *id != from) {
+                self.send_event_to_member(id, event.clone());
+            }
+        }
+    }
+
+    /// Returns key-value feature flags of the specified [`Member`], overriding
+    /// the configured [`RpcConf::features`].
+    #[must_use]
+    pub fn get_features(&self, member: &Member) -> HashMap<String, String> {
+        let mut features = self.rpc_conf.features.clone();
+        features.extend(member.get_features());
+        features
+    }
+
+    /// Counts one more application data message of the specified [`Member`]
+    /// in its current one second window.
+    ///
+    /// Returns `false` if [`RpcConf::app_data_rate_limit`] is exhausted in
+    /// this window.
+    fn take_app_data_quota(&mut self, member_id: &MemberId) -> bool {
+        let limit = self.rpc_conf.app_data_rate_limit;
+        let now = Instant::now();
+        let (started_at, count) = self
+            .app_data_windows
+            .entry(member_id.clone())
+            .or_insert((now, 0));
+        if now.duration_since(*started_at) >= Duration::from_secs(1) {
+            *started_at = now;
+            *count = 0;
+        }
+        if *count >= limit {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+
     /// Saves provided [`RpcConnection`].
     /// If [`Member`] already has any other [`RpcConnection`],
     /// then it will be closed.
@@ -245,6 +461,13 @@ impl ParticipantService {
             if let Some(handler) = self.drop_connection_tasks.remove(&member_id)
             {
                 ctx.cancel_future(handler);
+                // hold events until the restored connection is synchronized,
+                // so the missed ones are replayed first
+                if let Some(history) =
+                    self.event_histories.get_mut().get_mut(&member_id)
+                {
+                    history.hold();
+                }
             }
             self.insert_connection(member_id, conn);
             Box::pin(
@@ -294,12 +517,13 @@ impl ParticipantService {
         }
     }
 
-    /// Cancels all connection close tasks, closes all [`RpcConnection`]s and
-    /// deletes all [`IceUser`]s.
+    /// Cancels all connection close tasks, closes all [`RpcConnection`]s with
+    /// the provided [`CloseReason`] and deletes all [`IceUser`]s.
     ///
     /// [`IceUser`]: crate::turn::IceUser
     pub fn drop_connections(
         &mut self,
+        close_reason: CloseReason,
         ctx: &mut Context<Room>,
     ) -> LocalBoxFuture<'static, ()> {
         // canceling all drop_connection_tasks
@@ -315,7 +539,7 @@ impl ParticipantService {
                 |mut futs, (_, mut connection)| {
                     futs.push(connection.close(
                         room_id.clone(),
-                        CloseDescription::new(CloseReason::Finished),
+                        CloseDescription::new(close_reason),
                     ));
                     futs
                 },
@@ -327,18 +551,110 @@ impl ParticipantService {
     /// Deletes a [`Member`] by its ID from this [`ParticipantService`].
     pub fn delete_member(&mut self, member_id: &MemberId) {
         self.members.remove(member_id);
+        self.app_data_windows.remove(member_id);
+        self.capabilities.remove(member_id);
+        self.sdp_quirks.remove(member_id);
+        self.sessions.remove(member_id);
+        self.event_histories.get_mut().remove(member_id);
+    }
+
+    /// Saves [`ClientCapabilities`] reported by `Client` of the [`Member`] with
+    /// the provided [`MemberId`], along with [`SdpQuirks`] of its user agent.
+    pub fn set_capabilities(
+        &mut self,
+        member_id: MemberId,
+        capabilities: ClientCapabilities,
+    ) {
+        self.sdp_quirks.insert(
+            member_id.clone(),
+            SdpQuirks::new(capabilities.user_agent.as_deref()),
+        );
+        self.capabilities.insert(member_id, capabilities);
+    }
+
+    /// Returns [`ClientCapabilities`] reported by `Client` of the [`Member`]
+    /// with the provided [`MemberId`], if it has joined this [`Room`].
+    #[inline]
+    #[must_use]
+    pub fn capabilities(
+        &self,
+        member_id: &MemberId,
+    ) -> Option<&ClientCapabilities> {
+        self.capabilities.get(member_id)
+    }
+
+    /// Saves the remote address of the `Client` of the [`Member`] with the
+    /// provided [`MemberId`].
+    ///
+    /// Remembers the moment the [`Member`] has joined this [`Room`] at, unless
+    /// it's reconnecting.
+    pub fn set_remote_addr(
+        &mut self,
+        member_id: MemberId,
+        remote_addr: Option<SocketAddr>,
+    ) {
+        self.sessions
+            .entry(member_id)
+            .and_modify(|(addr, _)| *addr = remote_addr)
+            .or_insert_with(|| (remote_addr, Instant::now()));
+    }
+
+    /// Returns [`ClientSession`] of the [`Member`] with the provided
+    /// [`MemberId`].
+    #[must_use]
+    pub fn client_session(&self, member_id: &MemberId) -> ClientSession {
+        ClientSession {
+            remote_addr: self.sessions.get(member_id).and_then(|(a, _)| *a),
+            capabilities: self
+                .capabilities
+                .get(member_id)
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Ends the session of the [`Member`] with the provided [`MemberId`] in
+    /// this [`Room`].
+    ///
+    /// Returns its [`ClientSession`] along with the [`Duration`] it has been
+    /// joined for, if it has joined this [`Room`].
+    pub fn end_session(
+        &mut self,
+        member_id: &MemberId,
+    ) -> Option<(ClientSession, Duration)> {
+        let (_, joined_at) = self.sessions.get(member_id).copied()?;
+        let session = self.client_session(member_id);
+        self.sessions.remove(member_id);
+        Some((session, joined_at.elapsed()))
+    }
+
+    /// Returns [`SdpQuirks`] of the user agent the [`Member`] with the
+    /// provided [`MemberId`] is connected with.
+    #[must_use]
+    pub fn sdp_quirks(&self, member_id: &MemberId) -> SdpQuirks {
+        self.sdp_quirks
+            .get(member_id)
+            .copied()
+            .unwrap_or_else(|| SdpQuirks::new(None))
     }
 
     /// Closes [`RpcConnection`] with [`Member`] with the provided [`MemberId`].
+    ///
+    /// Returns `true` if the [`Member`] had a [`RpcConnection`] (even a lost
+    /// one) before this call.
     pub fn close_member_connection(
         &mut self,
         member_id: &MemberId,
         close_reason: CloseReason,
         ctx: &mut Context<Room>,
-    ) {
+    ) -> bool {
         if let Some(drop) = self.drop_connection_tasks.remove(member_id) {
             ctx.cancel_future(drop);
         }
+        if let Some(history) = self.event_histories.get_mut().get_mut(member_id)
+        {
+            history.clear();
+        }
 
         if let Some(mut conn) = self.connections.remove(member_id) {
             wrap_future::<_, Room>(conn.close(
@@ -346,6 +662,9 @@ impl ParticipantService {
                 CloseDescription::new(close_reason),
             ))
             .spawn(ctx);
+            true
+        } else {
+            false
         }
     }
 
@@ -360,8 +679,25 @@ impl ParticipantService {
         self.members.iter()
     }
 
+    /// Expands wildcard sinks of all the [`Member`]s onto the provided
+    /// [`WebRtcPublishEndpoint`].
+    ///
+    /// Returns the created [`WebRtcPlayEndpoint`]s.
+    pub fn expand_wildcard_sinks(
+        &self,
+        src: &WebRtcPublishEndpoint,
+    ) -> Vec<WebRtcPlayEndpoint> {
+        self.members
+            .values()
+            .flat_map(|member| member.expand_wildcard_sinks(src))
+            .collect()
+    }
+
     /// Creates new [`Member`] in this [`ParticipantService`].
     ///
+    /// Wildcard sinks of the new and the existing [`Member`]s are expanded onto
+    /// the [`WebRtcPublishEndpoint`]s of each other.
+    ///
     /// This function will check that new [`Member`]'s ID is not present in
     /// [`ParticipantService`].
     ///
@@ -390,6 +726,8 @@ impl ParticipantService {
         );
 
         signalling_member.set_callback_urls(spec);
+        signalling_member.set_metadata(spec.metadata().clone());
+        signalling_member.set_features(spec.features().clone());
 
         for (id, publish) in spec.publish_endpoints() {
             let signalling_publish = WebRtcPublishEndpoint::new(
@@ -400,10 +738,19 @@ impl ParticipantService {
                 publish.audio_settings,
                 publish.video_settings,
             );
+            signalling_publish.set_callback_urls(
+                publish.on_start.clone(),
+                publish.on_stop.clone(),
+            );
             signalling_member.insert_src(signalling_publish);
         }
 
         for (id, play) in spec.play_endpoints() {
+            if play.src.is_wildcard() {
+                signalling_member.insert_wildcard_sink(id, play.clone());
+                continue;
+            }
+
             let partner_member = self.get_member(&play.src.member_id)?;
             let src = partner_member
                 .get_src_by_id(&play.src.endpoint_id)
@@ -422,6 +769,7 @@ impl ParticipantService {
                 signalling_member.downgrade(),
                 play.force_relay,
             );
+            sink.set_callback_urls(play.on_start.clone(), play.on_stop.clone());
 
             signalling_member.insert_sink(sink);
         }
@@ -432,6 +780,15 @@ impl ParticipantService {
             src.add_sink(sink.downgrade());
         }
 
+        for src in signalling_member.srcs().values() {
+            self.expand_wildcard_sinks(src);
+        }
+        for member in self.members.values() {
+            for src in member.srcs().values() {
+                signalling_member.expand_wildcard_sinks(src);
+            }
+        }
+
         self.insert_member(id, signalling_member);
 
         Ok(())
@@ -440,8 +797,6 @@ impl ParticipantService {
 
 #[cfg(test)]
 mod test {
-    use std::time::Duration;
-
     use crate::{
         api::control::{member::Credential, pipeline::Pipeline},
         conf::Conf,
@@ -530,4 +885,120 @@ mod test {
         assert_eq!(test_member.get_idle_timeout(), idle_timeout);
         assert_eq!(test_member.get_reconnect_timeout(), reconnect_timeout);
     }
+
+    /// Tests that application data messages exceeding the configured rate
+    /// limit are not allowed to be relayed.
+    #[test]
+    fn app_data_rate_limit_is_applied() {
+        let mut members = empty_participants_service();
+        let limit = Conf::default().rpc.app_data_rate_limit;
+        let member_id = MemberId::from("test-member");
+
+        for _ in 0..limit {
+            assert!(members.take_app_data_quota(&member_id));
+        }
+        assert!(!members.take_app_data_quota(&member_id));
+        assert!(members.take_app_data_quota(&MemberId::from("another")));
+    }
+
+    /// Tests that reported `Client` capabilities are stored per `Member`
+    /// along with SDP quirks of its user agent, and dropped with the `Member`.
+    #[test]
+    fn stores_client_capabilities() {
+        use crate::media::sdp_quirks::Browser;
+
+        let mut members = empty_participants_service();
+        let member_id = MemberId::from("test-member");
+        assert!(members.capabilities(&member_id).is_none());
+        assert_eq!(members.sdp_quirks(&member_id).browser(), Browser::Unknown);
+
+        let capabilities = ClientCapabilities {
+            user_agent: Some("Mozilla/5.0 Gecko/20100101 Firefox/86.0".into()),
+            codecs: vec!["audio/opus".into(), "video/VP8".into()],
+            simulcast: true,
+            insertable_streams: false,
+        };
+        members.set_capabilities(member_id.clone(), capabilities.clone());
+        assert_eq!(members.capabilities(&member_id), Some(&capabilities));
+        assert_eq!(members.sdp_quirks(&member_id).browser(), Browser::Firefox);
+
+        members.delete_member(&member_id);
+        assert!(members.capabilities(&member_id).is_none());
+    }
+
+    /// Tests that `Client` session metadata is kept across reconnections and
+    /// reported once the session ends.
+    #[test]
+    fn tracks_client_sessions() {
+        let mut members = empty_participants_service();
+        let member_id = MemberId::from("test-member");
+        assert!(members.end_session(&member_id).is_none());
+
+        let addr: SocketAddr = "127.0.0.1:1234".parse().unwrap();
+        members.set_capabilities(
+            member_id.clone(),
+            ClientCapabilities {
+                user_agent: Some("test".into()),
+                ..ClientCapabilities::default()
+            },
+        );
+        members.set_remote_addr(member_id.clone(), None);
+        let joined_at = members.sessions[&member_id].1;
+        members.set_remote_addr(member_id.clone(), Some(addr));
+        assert_eq!(members.sessions[&member_id].1, joined_at);
+
+        let session = members.client_session(&member_id);
+        assert_eq!(session.remote_addr, Some(addr));
+        assert_eq!(session.capabilities.user_agent.as_deref(), Some("test"));
+
+        let (session, _) = members.end_session(&member_id).unwrap();
+        assert_eq!(session.remote_addr, Some(addr));
+        assert!(members.end_session(&member_id).is_none());
+        assert_eq!(members.client_session(&member_id).remote_addr, None);
+    }
+
+    /// Tests that `Event`s held since restoring an `RpcConnection` are sent
+    /// only after the missed ones are replayed.
+    #[test]
+    fn replays_missed_events_before_held_ones() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::api::client::rpc_connection::MockRpcConnection;
+
+        let event = |checksum| Event::StateChecksum { checksum };
+        let mut members = empty_participants_service();
+        members.rpc_conf.event_history_size = 8;
+        let member_id = MemberId::from("test-member");
+
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let mut conn = MockRpcConnection::new();
+        conn.expect_send_event().returning({
+            let sent = Arc::clone(&sent);
+            move |_, event, seq| sent.lock().unwrap().push((event, seq))
+        });
+        members.insert_connection(member_id.clone(), Box::new(conn));
+
+        members.send_event_to_member(&member_id, event(1));
+        members.send_event_to_member(&member_id, event(2));
+        members
+            .event_histories
+            .get_mut()
+            .get_mut(&member_id)
+            .unwrap()
+            .hold();
+        members.send_event_to_member(&member_id, event(3));
+        assert_eq!(sent.lock().unwrap().len(), 2);
+
+        assert!(members.replay_missed_events(&member_id, Some(1)));
+        assert_eq!(
+            *sent.lock().unwrap(),
+            vec![
+                (event(1), Some(1)),
+                (event(2), Some(2)),
+                (event(2), Some(2)),
+                (event(3), Some(3)),
+            ],
+        );
+        assert!(!members.replay_missed_events(&member_id, Some(1)));
+    }
 }
@@ -188,4 +188,10 @@ impl RtcStatsHandler for ConnectionFailureDetector {
     fn subscribe(&mut self) -> LocalBoxStream<'static, PeersMetricsEvent> {
         self.event_tx.subscribe()
     }
+
+    /// Returns number of the registered [`PeerState`]s.
+    #[inline]
+    fn entries_count(&self) -> usize {
+        self.peers.len()
+    }
 }
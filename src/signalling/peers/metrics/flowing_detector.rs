@@ -35,7 +35,7 @@ use crate::{
         traffic_watcher::PeerTrafficWatcher,
         FlowMetricSource,
     },
-    utils::instant_into_utc,
+    utils::{deterministic, instant_into_utc},
 };
 
 use super::PeersMetricsEvent;
@@ -58,6 +58,13 @@ pub struct TrafficFlowDetector {
     /// Duration, after which [`Peer`]s stats will be considered as stale.
     stats_ttl: Duration,
 
+    /// Duration, after which [`TrackStat`]s not being updated anymore are
+    /// evicted.
+    retention_window: Duration,
+
+    /// Number of [`TrackStat`]s evicted due to the `retention_window`.
+    evicted: u64,
+
     /// Sender of [`PeersMetricsEvent`]s.
     ///
     /// Currently [`PeersMetricsEvent`] will receive [`Room`] to which this
@@ -72,12 +79,15 @@ impl TrafficFlowDetector {
         room_id: RoomId,
         peers_traffic_watcher: Arc<dyn PeerTrafficWatcher>,
         stats_ttl: Duration,
+        retention_window: Duration,
     ) -> Self {
         Self {
             room_id,
             peers_traffic_watcher,
             peers: HashMap::new(),
             stats_ttl,
+            retention_window,
+            evicted: 0,
             event_tx: EventSender::new(),
         }
     }
@@ -179,10 +189,16 @@ impl RtcStatsHandler for TrafficFlowDetector {
     ///
     /// Sends [`PeersMetricsEvent::NoTrafficFlow`] message if it determines that
     /// some track is not flowing.
+    ///
+    /// Evicts [`TrackStat`]s which haven't been updated within the retention
+    /// window.
     fn check(&mut self) {
-        for peer in self
-            .peers
-            .values()
+        for peer in self.peers.values() {
+            self.evicted +=
+                peer.borrow_mut().evict_stale(self.retention_window) as u64;
+        }
+
+        for peer in deterministic::ordered_values(&self.peers, |id| id.0)
             .filter(|peer| peer.borrow().state == PeerStatState::Connected)
         {
             let mut peer_ref = peer.borrow_mut();
@@ -354,6 +370,25 @@ impl RtcStatsHandler for TrafficFlowDetector {
     fn subscribe(&mut self) -> LocalBoxStream<'static, PeersMetricsEvent> {
         self.event_tx.subscribe()
     }
+
+    /// Returns number of the registered [`PeerStat`]s and [`TrackStat`]s of
+    /// their senders and receivers.
+    fn entries_count(&self) -> usize {
+        self.peers
+            .values()
+            .map(|peer| {
+                let peer = peer.borrow();
+                1 + peer.senders.len() + peer.receivers.len()
+            })
+            .sum()
+    }
+
+    /// Returns number of the [`TrackStat`]s evicted due to the retention
+    /// window.
+    #[inline]
+    fn evicted_count(&self) -> u64 {
+        self.evicted
+    }
 }
 
 /// Media type of a [`MediaTrack`].
@@ -646,6 +681,19 @@ impl PeerStat {
         receiver.update(upd);
     }
 
+    /// Removes [`TrackStat`]s which haven't been updated within the provided
+    /// `window`, so stats of the removed or renegotiated `MediaTrack`s don't
+    /// pile up during long-lasting calls.
+    ///
+    /// Returns number of the removed [`TrackStat`]s.
+    fn evict_stale(&mut self, window: Duration) -> usize {
+        let before = self.senders.len() + self.receivers.len();
+        self.senders.retain(|_, s| s.updated_at.elapsed() <= window);
+        self.receivers
+            .retain(|_, r| r.updated_at.elapsed() <= window);
+        before - self.senders.len() - self.receivers.len()
+    }
+
     /// Returns partner [`PeerStat`].
     ///
     /// Returns `None` if partner [`PeerStat`] weak reference can't be upgraded.
@@ -956,6 +1004,7 @@ mod tests {
             jitter: None,
             total_decode_time: None,
             jitter_buffer_emitted_count: None,
+            jitter_buffer_delay: None,
         }
     }
 
@@ -981,6 +1030,15 @@ mod tests {
         /// Returns new [`Helper`] with [`PeerMetricsService`] in which [`Room`]
         /// with `test` ID was registered.
         pub fn new(stats_ttl: Duration) -> Self {
+            Self::with_retention_window(stats_ttl, Duration::from_secs(60))
+        }
+
+        /// Returns new [`Helper`] with [`PeerMetricsService`] evicting stats
+        /// not being updated within the provided `retention_window`.
+        pub fn with_retention_window(
+            stats_ttl: Duration,
+            retention_window: Duration,
+        ) -> Self {
             let mut watcher = MockPeerTrafficWatcher::new();
             watcher
                 .expect_register_room()
@@ -1003,6 +1061,7 @@ mod tests {
                 "test".to_string().into(),
                 Arc::new(watcher),
                 stats_ttl,
+                retention_window,
             );
 
             Self {
@@ -1137,6 +1196,13 @@ mod tests {
             self.metrics.check();
         }
 
+        /// Returns [`RtcStatsHandler::entries_count`] and
+        /// [`RtcStatsHandler::evicted_count`] of the inner
+        /// [`TrafficFlowDetector`].
+        pub fn entries_and_evicted(&self) -> (usize, u64) {
+            (self.metrics.entries_count(), self.metrics.evicted_count())
+        }
+
         /// Calls [`PeerMetricsService::unregister_peers`] with provided
         /// [`PeerId`] as argument.
         pub fn unregister_peer(&mut self, peer_id: PeerId) {
@@ -1401,6 +1467,23 @@ mod tests {
             .unwrap_err();
     }
 
+    /// Checks that [`TrackStat`]s not being updated within the retention
+    /// window are evicted on [`PeerMetricsService::check_peers`].
+    #[actix_rt::test]
+    async fn evicts_stale_track_stats() {
+        let mut helper = Helper::with_retention_window(
+            Duration::from_millis(10),
+            Duration::from_millis(30),
+        );
+        helper.register_peer(1, 1, 1, 1);
+        helper.add_stats(1, 1, 1, 1, 100);
+        assert_eq!(helper.entries_and_evicted(), (5, 0));
+
+        sleep(Duration::from_millis(50)).await;
+        helper.check_peers();
+        assert_eq!(helper.entries_and_evicted(), (1, 4));
+    }
+
     /// Calling `check_peers` after adding new tracks via `update_peer_tracks`
     /// doesn't emits [`PeersMetricsEvent::NoTrafficFlow`].
     #[actix_rt::test]
@@ -13,9 +13,11 @@
 
 mod connection_failure_detector;
 mod flowing_detector;
+mod quality_advisor;
+mod quality_history;
 mod quality_meter;
 
-use std::{cell::RefCell, fmt::Debug, rc::Rc, sync::Arc, time::Duration};
+use std::{cell::RefCell, collections::HashMap, fmt::Debug, rc::Rc, sync::Arc};
 
 use chrono::{DateTime, Utc};
 use futures::{
@@ -24,23 +26,28 @@ use futures::{
 };
 use medea_client_api_proto::{
     stats::RtcStat, ConnectionQualityScore, MemberId, PeerConnectionState,
-    PeerId, RoomId,
+    PeerId, QualityAdvisoryReason, RoomId, TrackId,
 };
 use medea_macro::dispatchable;
 
 use crate::{
     api::control::callback::{MediaDirection, MediaType},
+    conf,
     media::PeerStateMachine,
     signalling::peers::{
         metrics::{
             connection_failure_detector::ConnectionFailureDetector,
             flowing_detector::TrafficFlowDetector,
+            quality_advisor::TrackQualityAdvisor,
+            quality_history::QualityHistory,
             quality_meter::QualityMeterStatsHandler,
         },
         PeerTrafficWatcher,
     },
 };
 
+pub use self::quality_history::QualitySample;
+
 /// WebRTC statistics analysis results.
 #[dispatchable]
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -84,6 +91,24 @@ pub enum PeersMetricsEvent {
         /// [`PeerId`] of `PeerConnection`.
         peer_id: PeerId,
     },
+
+    /// Receiving side of some `MediaTrack` is struggling, so its publisher is
+    /// advised to limit the bitrate of this `MediaTrack`.
+    TrackQualityAdvisory {
+        /// [`MemberId`] of the `MediaTrack` publisher.
+        member_id: MemberId,
+
+        /// [`TrackId`] of the advised `MediaTrack`.
+        track_id: TrackId,
+
+        /// Advised maximum bitrate (in bits per second) of the `MediaTrack`.
+        ///
+        /// [`None`] if the previously advised limit should be lifted.
+        advised_max_bitrate: Option<u32>,
+
+        /// [`QualityAdvisoryReason`] of this advisory.
+        reason: QualityAdvisoryReason,
+    },
 }
 
 /// [`RtcStatsHandler`] performs [`RtcStat`]s analysis.
@@ -123,6 +148,14 @@ pub trait RtcStatsHandler: Debug {
         state: PeerConnectionState,
     );
 
+    /// [`PeerMetricsService`] provides update of whether traffic of the
+    /// `Peer` is relayed via [TURN] server for the [`RtcStatsHandler`].
+    ///
+    /// Does nothing by default.
+    ///
+    /// [TURN]: https://webrtcglossary.com/turn
+    fn update_peer_relayed(&mut self, _peer_id: PeerId, _relayed: bool) {}
+
     /// Returns [`Stream`] of [`PeersMetricsEvent`]s.
     ///
     /// Creating new subscription will invalidate previous, so there may be only
@@ -131,6 +164,36 @@ pub trait RtcStatsHandler: Debug {
     ///
     /// [`Stream`]: futures::stream::Stream
     fn subscribe(&mut self) -> LocalBoxStream<'static, PeersMetricsEvent>;
+
+    /// Returns recent [`QualitySample`]s of the provided `Member` with each
+    /// of its partners, ordered from the oldest to the newest.
+    ///
+    /// Returns nothing by default, as only [`PeerMetricsService`] keeps the
+    /// [`ConnectionQualityScore`]s history.
+    fn quality_history(
+        &self,
+        _member_id: &MemberId,
+    ) -> HashMap<MemberId, Vec<QualitySample>> {
+        HashMap::new()
+    }
+
+    /// Returns number of entries (stats of `Peer`s and their `MediaTrack`s,
+    /// [`QualitySample`]s, etc.) kept in memory by this [`RtcStatsHandler`].
+    ///
+    /// Returns zero by default.
+    fn entries_count(&self) -> usize {
+        0
+    }
+
+    /// Returns number of entries evicted by this [`RtcStatsHandler`] due to
+    /// the configured [`MetricsRetention`].
+    ///
+    /// Returns zero by default.
+    ///
+    /// [`MetricsRetention`]: conf::media::MetricsRetention
+    fn evicted_count(&self) -> u64 {
+        0
+    }
 }
 
 /// Service which is responsible for processing [`Peer`]s [`RtcStat`] metrics.
@@ -143,28 +206,45 @@ pub struct PeerMetricsService {
 
     /// All [`RtcStatsHandler`]s registered in this [`PeerMetricsService`].
     handlers: Vec<Box<dyn RtcStatsHandler>>,
+
+    /// Rolling history of the [`ConnectionQualityScore`]s calculated between
+    /// `Member`s.
+    quality_history: Rc<RefCell<QualityHistory>>,
 }
 
 impl PeerMetricsService {
     /// Creates new [`PeerMetricsService`], registers all needed
-    /// [`RtcStatsHandler`]s.
+    /// [`RtcStatsHandler`]s configured with the provided [`conf::Media`].
     pub fn new(
         room_id: RoomId,
         peers_traffic_watcher: Arc<dyn PeerTrafficWatcher>,
-        stats_ttl: Duration,
+        media_conf: &conf::Media,
     ) -> Self {
         let event_tx = EventSender::new();
+        let quality_history = Rc::default();
         let handlers: Vec<Box<dyn RtcStatsHandler>> = vec![
             Box::new(TrafficFlowDetector::new(
                 room_id,
                 peers_traffic_watcher,
-                stats_ttl,
+                media_conf.max_lag,
+                media_conf.metrics_retention.window,
+            )),
+            Box::new(QualityMeterStatsHandler::new(
+                Rc::clone(&quality_history),
+                media_conf.quality_hysteresis,
+                media_conf.quality_min_interval,
+                media_conf.audio_quality,
+                media_conf.metrics_retention.length,
             )),
-            Box::new(QualityMeterStatsHandler::new()),
             Box::new(ConnectionFailureDetector::new()),
+            Box::new(TrackQualityAdvisor::new()),
         ];
 
-        Self { event_tx, handlers }
+        Self {
+            event_tx,
+            handlers,
+            quality_history,
+        }
     }
 }
 
@@ -221,6 +301,14 @@ impl RtcStatsHandler for PeerMetricsService {
         }
     }
 
+    /// Calls [`RtcStatsHandler::update_peer_relayed`] on the all registered
+    /// [`RtcStatsHandler`]s.
+    fn update_peer_relayed(&mut self, peer_id: PeerId, relayed: bool) {
+        for handler in &mut self.handlers {
+            handler.update_peer_relayed(peer_id, relayed);
+        }
+    }
+
     /// Calls [`RtcStatsHandler::subscribe`] on the all registered
     /// [`RtcStatsHandler`]s returning merged stream.
     ///
@@ -233,6 +321,35 @@ impl RtcStatsHandler for PeerMetricsService {
         )
         .boxed_local()
     }
+
+    /// Returns recent [`QualitySample`]s of the provided `Member` from the
+    /// [`QualityHistory`].
+    fn quality_history(
+        &self,
+        member_id: &MemberId,
+    ) -> HashMap<MemberId, Vec<QualitySample>> {
+        self.quality_history.borrow().of_member(member_id)
+    }
+
+    /// Sums [`RtcStatsHandler::entries_count`] of the all registered
+    /// [`RtcStatsHandler`]s with the number of [`QualitySample`]s in the
+    /// [`QualityHistory`].
+    fn entries_count(&self) -> usize {
+        self.handlers
+            .iter()
+            .map(|handler| handler.entries_count())
+            .sum::<usize>()
+            + self.quality_history.borrow().samples_count()
+    }
+
+    /// Sums [`RtcStatsHandler::evicted_count`] of the all registered
+    /// [`RtcStatsHandler`]s.
+    fn evicted_count(&self) -> u64 {
+        self.handlers
+            .iter()
+            .map(|handler| handler.evicted_count())
+            .sum()
+    }
 }
 
 /// [`PeersMetricsEvent`]s sender.
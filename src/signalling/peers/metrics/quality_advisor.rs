@@ -0,0 +1,443 @@
+//! [`RtcStatsHandler`] advising publishers to limit the bitrate of their sent
+//! `MediaTrack`s basing on the stats of their receivers.
+
+use std::collections::HashMap;
+
+use futures::stream::LocalBoxStream;
+use medea_client_api_proto::{
+    stats::{RtcInboundRtpStreamMediaType, RtcStat, RtcStatsType, StatId},
+    MediaType, MemberId, PeerConnectionState, PeerId, QualityAdvisoryReason,
+    TrackId,
+};
+
+use crate::{
+    media::PeerStateMachine,
+    signalling::peers::metrics::{
+        EventSender, PeersMetricsEvent, RtcStatsHandler,
+    },
+};
+
+/// Fraction of lost packets above which a receiving side is considered
+/// struggling.
+const LOSS_THRESHOLD: f64 = 0.1;
+
+/// Fraction of lost packets below which a struggling receiving side is
+/// considered recovered.
+const RECOVERY_LOSS_THRESHOLD: f64 = 0.02;
+
+/// Jitter (in seconds) above which a receiving side is considered struggling.
+const JITTER_THRESHOLD: f64 = 0.1;
+
+/// Factor the received bitrate is lowered by, when a receiving side is
+/// struggling because of jitter.
+const JITTER_BACKOFF: f64 = 0.85;
+
+/// Minimum bitrate (in bits per second) which may be advised.
+const MIN_ADVISED_BITRATE: u32 = 100_000;
+
+/// Minimum relative difference between the currently advised bitrate and a
+/// new one, required for a new advisory to be sent.
+const ADVISORY_HYSTERESIS: f64 = 0.1;
+
+/// [`RtcStatsHandler`] sending [`PeersMetricsEvent::TrackQualityAdvisory`]s to
+/// the publishers of video `MediaTrack`s, which receiving sides are
+/// struggling.
+#[derive(Debug)]
+pub(super) struct TrackQualityAdvisor {
+    /// [`PeerReception`]s of all the `Peer`s registered in this
+    /// [`TrackQualityAdvisor`].
+    peers: HashMap<PeerId, PeerReception>,
+
+    /// [`PeersMetricsEvent`]s sender.
+    event_tx: EventSender,
+}
+
+impl TrackQualityAdvisor {
+    /// Returns new empty [`TrackQualityAdvisor`].
+    pub(super) fn new() -> Self {
+        Self {
+            peers: HashMap::new(),
+            event_tx: EventSender::new(),
+        }
+    }
+}
+
+impl RtcStatsHandler for TrackQualityAdvisor {
+    /// Starts tracking reception of the provided [`PeerStateMachine`].
+    fn register_peer(&mut self, peer: &PeerStateMachine) {
+        self.peers.insert(peer.id(), PeerReception::new(peer));
+    }
+
+    /// Stops tracking reception of the provided `Peer`s.
+    fn unregister_peers(&mut self, peers_ids: &[PeerId]) {
+        for peer_id in peers_ids {
+            self.peers.remove(peer_id);
+        }
+    }
+
+    /// Updates the received video `MediaTrack`s of the provided
+    /// [`PeerStateMachine`].
+    fn update_peer(&mut self, peer: &PeerStateMachine) {
+        if let Some(reception) = self.peers.get_mut(&peer.id()) {
+            reception.video_tracks = PeerReception::video_tracks(peer);
+        }
+    }
+
+    /// Does nothing, since advisories are calculated on stats arrival.
+    #[inline]
+    fn check(&mut self) {}
+
+    /// Measures reception quality of the `Peer` with the provided [`PeerId`],
+    /// and sends [`PeersMetricsEvent::TrackQualityAdvisory`] for each of its
+    /// received video `MediaTrack`s, if the advised bitrate has changed.
+    fn add_stats(&mut self, peer_id: PeerId, stats: &[RtcStat]) {
+        let reception = if let Some(reception) = self.peers.get_mut(&peer_id) {
+            reception
+        } else {
+            return;
+        };
+        let advisory = reception
+            .update(stats)
+            .and_then(|measured| reception.advise(measured));
+        if let Some((advised_max_bitrate, reason)) = advisory {
+            for track_id in &reception.video_tracks {
+                self.event_tx.send_event(
+                    PeersMetricsEvent::TrackQualityAdvisory {
+                        member_id: reception.partner_member_id.clone(),
+                        track_id: *track_id,
+                        advised_max_bitrate,
+                        reason,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Does nothing.
+    #[inline]
+    fn update_peer_connection_state(
+        &mut self,
+        _: PeerId,
+        _: PeerConnectionState,
+    ) {
+    }
+
+    #[inline]
+    fn subscribe(&mut self) -> LocalBoxStream<'static, PeersMetricsEvent> {
+        self.event_tx.subscribe()
+    }
+
+    /// Returns number of the registered [`PeerReception`]s and the
+    /// [`InboundSample`]s stored in them.
+    fn entries_count(&self) -> usize {
+        self.peers
+            .values()
+            .map(|reception| 1 + reception.inbound.len())
+            .sum()
+    }
+}
+
+/// Reception state of a single `Peer`.
+#[derive(Debug)]
+struct PeerReception {
+    /// [`MemberId`] of the partner `Peer`, publishing the received
+    /// `MediaTrack`s.
+    partner_member_id: MemberId,
+
+    /// [`TrackId`]s of the received video `MediaTrack`s.
+    video_tracks: Vec<TrackId>,
+
+    /// Latest [`InboundSample`]s of the received video RTP streams.
+    inbound: HashMap<StatId, InboundSample>,
+
+    /// Currently advised maximum bitrate (in bits per second).
+    ///
+    /// [`None`] if no limit is advised.
+    advised_max_bitrate: Option<u32>,
+}
+
+impl PeerReception {
+    /// Returns new [`PeerReception`] of the provided [`PeerStateMachine`].
+    fn new(peer: &PeerStateMachine) -> Self {
+        Self {
+            partner_member_id: peer.partner_member_id().clone(),
+            video_tracks: Self::video_tracks(peer),
+            inbound: HashMap::new(),
+            advised_max_bitrate: None,
+        }
+    }
+
+    /// Returns [`TrackId`]s of the video `MediaTrack`s received by the
+    /// provided [`PeerStateMachine`].
+    fn video_tracks(peer: &PeerStateMachine) -> Vec<TrackId> {
+        let mut tracks: Vec<_> = peer
+            .receivers()
+            .values()
+            .filter(|track| matches!(track.media_type(), MediaType::Video(_)))
+            .map(|track| track.id())
+            .collect();
+        tracks.sort_unstable_by_key(|id| id.0);
+        tracks
+    }
+
+    /// Stores [`InboundSample`]s of the provided video [`RtcStat`]s and
+    /// returns the [`Reception`] measured since the previous ones.
+    ///
+    /// Returns [`None`] if there are no previous [`InboundSample`]s to measure
+    /// against.
+    fn update(&mut self, stats: &[RtcStat]) -> Option<Reception> {
+        let mut measured: Option<Reception> = None;
+        for stat in stats {
+            let inbound = match &stat.stats {
+                RtcStatsType::InboundRtp(inbound)
+                    if matches!(
+                        inbound.media_specific_stats,
+                        RtcInboundRtpStreamMediaType::Video { .. },
+                    ) =>
+                {
+                    inbound
+                }
+                _ => continue,
+            };
+            let sample = InboundSample {
+                timestamp: stat.timestamp.0,
+                bytes_received: inbound.bytes_received,
+                packets_received: inbound.packets_received,
+                packets_lost: inbound.packets_lost.unwrap_or_default(),
+            };
+            let prev = self.inbound.insert(stat.id.clone(), sample);
+            if let Some((loss, bitrate)) =
+                prev.and_then(|prev| sample.measure_since(&prev))
+            {
+                let reception = measured.get_or_insert_with(Reception::default);
+                reception.loss = reception.loss.max(loss);
+                reception.jitter = reception
+                    .jitter
+                    .max(inbound.jitter.as_ref().map_or(0., |j| j.0));
+                reception.bitrate += bitrate;
+            }
+        }
+        measured
+    }
+
+    /// Calculates new advised maximum bitrate basing on the provided
+    /// [`Reception`].
+    ///
+    /// Returns [`None`] if the advised maximum bitrate hasn't changed
+    /// significantly.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn advise(
+        &mut self,
+        reception: Reception,
+    ) -> Option<(Option<u32>, QualityAdvisoryReason)> {
+        let (reason, factor) = if reception.loss > LOSS_THRESHOLD {
+            (QualityAdvisoryReason::PacketLoss, 1. - reception.loss)
+        } else if reception.jitter > JITTER_THRESHOLD {
+            (QualityAdvisoryReason::Jitter, JITTER_BACKOFF)
+        } else if self.advised_max_bitrate.is_some()
+            && reception.loss < RECOVERY_LOSS_THRESHOLD
+        {
+            self.advised_max_bitrate = None;
+            return Some((None, QualityAdvisoryReason::Recovered));
+        } else {
+            return None;
+        };
+
+        let advised =
+            ((reception.bitrate * factor) as u32).max(MIN_ADVISED_BITRATE);
+        if let Some(current) = self.advised_max_bitrate {
+            let current = f64::from(current);
+            if (f64::from(advised) - current).abs() / current
+                < ADVISORY_HYSTERESIS
+            {
+                return None;
+            }
+        }
+        self.advised_max_bitrate = Some(advised);
+
+        Some((Some(advised), reason))
+    }
+}
+
+/// Sample of a received RTP stream stats.
+#[derive(Clone, Copy, Debug)]
+struct InboundSample {
+    /// Time (in milliseconds since the UNIX epoch) this [`InboundSample`] was
+    /// taken at.
+    timestamp: f64,
+
+    /// Total number of bytes received.
+    bytes_received: u64,
+
+    /// Total number of packets received.
+    packets_received: u64,
+
+    /// Total number of packets lost.
+    packets_lost: i64,
+}
+
+impl InboundSample {
+    /// Returns fraction of the lost packets and the received bitrate (in bits
+    /// per second) since the provided previous [`InboundSample`].
+    ///
+    /// Returns [`None`] if nothing has been received since the previous
+    /// [`InboundSample`].
+    #[allow(clippy::cast_precision_loss)]
+    fn measure_since(&self, prev: &Self) -> Option<(f64, f64)> {
+        let elapsed_ms = self.timestamp - prev.timestamp;
+        let received =
+            self.packets_received.saturating_sub(prev.packets_received);
+        let lost = (self.packets_lost - prev.packets_lost).max(0) as u64;
+        if elapsed_ms <= 0. || received + lost == 0 {
+            return None;
+        }
+
+        let loss = lost as f64 / (received + lost) as f64;
+        let bytes = self.bytes_received.saturating_sub(prev.bytes_received);
+        let bitrate = bytes as f64 * 8. * 1000. / elapsed_ms;
+
+        Some((loss, bitrate))
+    }
+}
+
+/// Reception quality of a `Peer` measured between two consecutive
+/// [`InboundSample`]s.
+#[derive(Clone, Copy, Debug, Default)]
+struct Reception {
+    /// Maximum fraction of lost packets among the received RTP streams.
+    loss: f64,
+
+    /// Maximum jitter (in seconds) among the received RTP streams.
+    jitter: f64,
+
+    /// Total received bitrate (in bits per second).
+    bitrate: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{stream::LocalBoxStream, StreamExt as _};
+    use medea_client_api_proto::{
+        stats::{
+            Float, HighResTimeStamp, RtcInboundRtpStreamMediaType,
+            RtcInboundRtpStreamStats, RtcStat, RtcStatsType, StatId,
+        },
+        MemberId, PeerId, QualityAdvisoryReason, TrackId,
+    };
+
+    use crate::{
+        media::peer::tests::test_peer_from_peer_tracks,
+        signalling::peers::{metrics::RtcStatsHandler, PeersMetricsEvent},
+    };
+
+    use super::TrackQualityAdvisor;
+
+    /// Returns video [`RtcStat`] taken at the provided `second` with the
+    /// provided totals of received bytes, received and lost packets.
+    fn video_inbound_stat(
+        second: u32,
+        bytes_received: u64,
+        packets_received: u64,
+        packets_lost: i64,
+    ) -> RtcStat {
+        RtcStat {
+            id: StatId::from("video-inbound"),
+            timestamp: HighResTimeStamp(f64::from(second) * 1000.),
+            stats: RtcStatsType::InboundRtp(Box::new(
+                RtcInboundRtpStreamStats {
+                    track_id: None,
+                    media_specific_stats: RtcInboundRtpStreamMediaType::Video {
+                        frames_decoded: None,
+                        key_frames_decoded: None,
+                        frame_width: None,
+                        frame_height: None,
+                        total_inter_frame_delay: None,
+                        frames_per_second: None,
+                        frame_bit_depth: None,
+                        fir_count: None,
+                        pli_count: None,
+                        sli_count: None,
+                        concealment_events: None,
+                        frames_received: None,
+                    },
+                    bytes_received,
+                    packets_received,
+                    packets_lost: Some(packets_lost),
+                    jitter: Some(Float(0.01)),
+                    total_decode_time: None,
+                    jitter_buffer_emitted_count: None,
+                    jitter_buffer_delay: None,
+                },
+            )),
+        }
+    }
+
+    /// Returns [`TrackQualityAdvisor`] with a registered `Peer` receiving a
+    /// single video `MediaTrack`, and a subscription to its
+    /// [`PeersMetricsEvent`]s.
+    fn advisor() -> (
+        TrackQualityAdvisor,
+        LocalBoxStream<'static, PeersMetricsEvent>,
+    ) {
+        let mut advisor = TrackQualityAdvisor::new();
+        let events = advisor.subscribe();
+        advisor.register_peer(&test_peer_from_peer_tracks(0, 0, 0, 1));
+        (advisor, events)
+    }
+
+    #[tokio::test]
+    async fn advises_lower_bitrate_on_packet_loss() {
+        let (mut advisor, mut events) = advisor();
+
+        advisor.add_stats(PeerId(1), &[video_inbound_stat(0, 0, 0, 0)]);
+        advisor
+            .add_stats(PeerId(1), &[video_inbound_stat(1, 250_000, 800, 200)]);
+        drop(advisor);
+
+        assert_eq!(
+            events.next().await.unwrap(),
+            PeersMetricsEvent::TrackQualityAdvisory {
+                member_id: MemberId::from("partner-member"),
+                track_id: TrackId(0),
+                advised_max_bitrate: Some(1_600_000),
+                reason: QualityAdvisoryReason::PacketLoss,
+            }
+        );
+        assert!(events.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn lifts_advised_limit_on_recovery() {
+        let (mut advisor, mut events) = advisor();
+
+        advisor.add_stats(PeerId(1), &[video_inbound_stat(0, 0, 0, 0)]);
+        advisor
+            .add_stats(PeerId(1), &[video_inbound_stat(1, 250_000, 800, 200)]);
+        advisor
+            .add_stats(PeerId(1), &[video_inbound_stat(2, 450_000, 1800, 200)]);
+        drop(advisor);
+
+        let last = events.collect::<Vec<_>>().await.pop().unwrap();
+        assert_eq!(
+            last,
+            PeersMetricsEvent::TrackQualityAdvisory {
+                member_id: MemberId::from("partner-member"),
+                track_id: TrackId(0),
+                advised_max_bitrate: None,
+                reason: QualityAdvisoryReason::Recovered,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn no_advisory_on_good_reception() {
+        let (mut advisor, events) = advisor();
+
+        advisor.add_stats(PeerId(1), &[video_inbound_stat(0, 0, 0, 0)]);
+        advisor
+            .add_stats(PeerId(1), &[video_inbound_stat(1, 250_000, 1000, 1)]);
+        drop(advisor);
+
+        assert!(events.collect::<Vec<_>>().await.is_empty());
+    }
+}
@@ -0,0 +1,147 @@
+//! Rolling history of [`ConnectionQualityScore`]s estimated between `Member`s.
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Utc};
+use medea_client_api_proto::{ConnectionQualityScore, MemberId};
+use medea_control_api_proto::grpc::api as proto;
+
+/// Maximum number of [`QualitySample`]s stored for a single pair of
+/// `Member`s.
+const HISTORY_SIZE: usize = 100;
+
+/// [`ConnectionQualityScore`] estimated at some moment.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct QualitySample {
+    /// Time of the [`ConnectionQualityScore`] estimation.
+    pub at: DateTime<Utc>,
+
+    /// Estimated [`ConnectionQualityScore`].
+    pub score: ConnectionQualityScore,
+
+    /// Indicator whether the connection was relayed via [TURN] server at the
+    /// moment of the [`ConnectionQualityScore`] estimation.
+    ///
+    /// [TURN]: https://webrtcglossary.com/turn
+    pub relayed: bool,
+}
+
+impl From<&QualitySample> for proto::QualitySample {
+    fn from(sample: &QualitySample) -> Self {
+        use proto::quality_sample::Score;
+
+        let score = match sample.score {
+            ConnectionQualityScore::Poor => Score::Poor,
+            ConnectionQualityScore::Low => Score::Low,
+            ConnectionQualityScore::Medium => Score::Medium,
+            ConnectionQualityScore::High => Score::High,
+        };
+        Self {
+            at: sample.at.to_rfc3339(),
+            score: score as i32,
+            relayed: sample.relayed,
+        }
+    }
+}
+
+/// Rolling window of [`QualitySample`]s for each pair of `Member`s.
+///
+/// A new [`QualitySample`] is recorded each time the estimated
+/// [`ConnectionQualityScore`] changes, so the history covers a long period
+/// with a bounded memory usage.
+#[derive(Debug, Default)]
+pub(super) struct QualityHistory {
+    /// [`QualitySample`]s of each `Member` with each of its partners, ordered
+    /// from the oldest to the newest.
+    samples: HashMap<(MemberId, MemberId), VecDeque<QualitySample>>,
+}
+
+impl QualityHistory {
+    /// Records the provided [`ConnectionQualityScore`] estimated between the
+    /// provided `Member`s over a relayed connection (if `relayed` is `true`),
+    /// evicting the oldest [`QualitySample`] if the history is full.
+    pub(super) fn record(
+        &mut self,
+        member_id: MemberId,
+        partner_member_id: MemberId,
+        score: ConnectionQualityScore,
+        relayed: bool,
+    ) {
+        let samples = self
+            .samples
+            .entry((member_id, partner_member_id))
+            .or_default();
+        if samples.len() == HISTORY_SIZE {
+            samples.pop_front();
+        }
+        samples.push_back(QualitySample {
+            at: Utc::now(),
+            score,
+            relayed,
+        });
+    }
+
+    /// Returns [`QualitySample`]s of the provided `Member` with each of its
+    /// partners, ordered from the oldest to the newest.
+    pub(super) fn of_member(
+        &self,
+        member_id: &MemberId,
+    ) -> HashMap<MemberId, Vec<QualitySample>> {
+        self.samples
+            .iter()
+            .filter(|((id, _), _)| id == member_id)
+            .map(|((_, partner_id), samples)| {
+                (partner_id.clone(), samples.iter().copied().collect())
+            })
+            .collect()
+    }
+
+    /// Returns total number of [`QualitySample`]s stored in this
+    /// [`QualityHistory`].
+    #[must_use]
+    pub(super) fn samples_count(&self) -> usize {
+        self.samples.values().map(VecDeque::len).sum()
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use ConnectionQualityScore::{High, Low, Poor};
+
+    use super::*;
+
+    #[test]
+    fn returns_samples_of_member_only() {
+        let mut history = QualityHistory::default();
+        history.record("alice".into(), "bob".into(), Poor, false);
+        history.record("alice".into(), "bob".into(), High, false);
+        history.record("alice".into(), "carol".into(), Low, true);
+        history.record("bob".into(), "alice".into(), Low, false);
+
+        let samples = history.of_member(&"alice".into());
+        assert_eq!(samples.len(), 2);
+        let scores: Vec<_> = samples[&MemberId::from("bob")]
+            .iter()
+            .map(|s| s.score)
+            .collect();
+        assert_eq!(scores, vec![Poor, High]);
+        assert_eq!(samples[&MemberId::from("carol")][0].score, Low);
+        assert!(samples[&MemberId::from("carol")][0].relayed);
+        assert!(!samples[&MemberId::from("bob")][0].relayed);
+        assert!(history.of_member(&"carol".into()).is_empty());
+    }
+
+    #[test]
+    fn evicts_oldest_samples() {
+        let mut history = QualityHistory::default();
+        history.record("alice".into(), "bob".into(), Poor, false);
+        for _ in 0..HISTORY_SIZE {
+            history.record("alice".into(), "bob".into(), High, false);
+        }
+
+        let samples =
+            &history.of_member(&"alice".into())[&MemberId::from("bob")];
+        assert_eq!(samples.len(), HISTORY_SIZE);
+        assert!(samples.iter().all(|s| s.score == High));
+    }
+}
@@ -2,24 +2,26 @@
 
 use std::{
     cell::RefCell,
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     rc::{Rc, Weak},
-    time::{Duration, SystemTime},
+    time::{Duration, Instant, SystemTime},
 };
 
 use futures::stream::LocalBoxStream;
 use medea_client_api_proto::{
     stats::{
-        RtcInboundRtpStreamStats, RtcRemoteInboundRtpStreamStats, RtcStat,
-        RtcStatsType, StatId,
+        RtcInboundRtpStreamMediaType, RtcInboundRtpStreamStats,
+        RtcRemoteInboundRtpStreamStats, RtcStat, RtcStatsType, StatId,
     },
     ConnectionQualityScore, MemberId, PeerConnectionState, PeerId,
 };
 
 use crate::{
+    conf::media::AudioQuality,
     media::PeerStateMachine,
     signalling::peers::metrics::{
-        EventSender, PeersMetricsEvent, RtcStatsHandler,
+        quality_history::QualityHistory, EventSender, PeersMetricsEvent,
+        RtcStatsHandler,
     },
 };
 
@@ -31,25 +33,79 @@ pub(super) struct QualityMeterStatsHandler {
 
     /// [`PeersMetricsEvent`]s sender.
     event_tx: EventSender,
+
+    /// [`QualityHistory`] to record the calculated
+    /// [`ConnectionQualityScore`]s into.
+    history: Rc<RefCell<QualityHistory>>,
+
+    /// Number of consecutive calculations a new [`ConnectionQualityScore`]
+    /// must persist for, before being reported.
+    hysteresis: u32,
+
+    /// Minimum interval between reporting [`ConnectionQualityScore`] changes
+    /// of a single `Peer`.
+    min_interval: Duration,
+
+    /// Thresholds of received audio quality lowering the calculated
+    /// [`ConnectionQualityScore`]s.
+    audio_quality: AudioQuality,
+
+    /// Maximum number of [`ExpiringStat`]s stored in a single series of a
+    /// [`QualityMeter`].
+    retention_length: usize,
+
+    /// Number of [`ExpiringStat`]s evicted from the [`QualityMeter`]s of the
+    /// already unregistered [`PeerMetric`]s.
+    evicted: u64,
 }
 
 impl QualityMeterStatsHandler {
-    /// Returns new empty [`QualityMeterStatsHandler`].
-    pub(super) fn new() -> Self {
+    /// Returns new empty [`QualityMeterStatsHandler`] recording the
+    /// calculated [`ConnectionQualityScore`]s into the provided
+    /// [`QualityHistory`].
+    ///
+    /// New [`ConnectionQualityScore`] is reported only once it persists for
+    /// `hysteresis` consecutive calculations, and not more often than once in
+    /// `min_interval`. Calculated scores are lowered according to the
+    /// provided [`AudioQuality`] thresholds. At most `retention_length` stats
+    /// are stored in a single series.
+    pub(super) fn new(
+        history: Rc<RefCell<QualityHistory>>,
+        hysteresis: u32,
+        min_interval: Duration,
+        audio_quality: AudioQuality,
+        retention_length: usize,
+    ) -> Self {
         Self {
             peers: HashMap::new(),
             event_tx: EventSender::new(),
+            history,
+            hysteresis: hysteresis.max(1),
+            min_interval,
+            audio_quality,
+            retention_length: retention_length.max(1),
+            evicted: 0,
         }
     }
 
     /// Recalculates [`ConnectionQualityScore`] for the provided
-    /// [`PeerMetric`], sends [`PeersMetricsEvent::QualityMeterUpdate`] if
-    /// new score is not equal to the previously calculated score.
+    /// [`PeerMetric`], records it into the [`QualityHistory`] and sends
+    /// [`PeersMetricsEvent::QualityMeterUpdate`] if new score is not equal to
+    /// the previously reported score.
+    ///
+    /// New score is reported only once it persists for the configured number
+    /// of consecutive calculations (unless it's caused by a connection state
+    /// change), and the configured minimum interval has elapsed since the
+    /// previous report. Otherwise, it's reconsidered on the next calculation.
     fn update_quality_score(&self, peer: &mut PeerMetric) {
-        let partner_score = peer
-            .partner_peer
-            .upgrade()
-            .and_then(|p| p.borrow_mut().calculate());
+        let partner = peer.partner_peer.upgrade();
+        let relayed = peer.relayed
+            || partner.as_ref().map_or(false, |p| p.borrow().relayed);
+        let is_state_caused = peer.calculate_from_connection_state().is_some()
+            || partner.as_ref().map_or(false, |p| {
+                p.borrow().calculate_from_connection_state().is_some()
+            });
+        let partner_score = partner.and_then(|p| p.borrow_mut().calculate());
         let score = peer
             .calculate()
             .and_then(|score| {
@@ -61,11 +117,34 @@ impl QualityMeterStatsHandler {
 
         if let Some(quality_score) = score {
             if quality_score == peer.last_quality_score {
+                peer.pending_score = None;
                 return;
             }
 
+            let persisted = match peer.pending_score {
+                Some((pending, count)) if pending == quality_score => count + 1,
+                _ => 1,
+            };
+            peer.pending_score = Some((quality_score, persisted));
+            if persisted < self.hysteresis && !is_state_caused {
+                return;
+            }
+            if let Some(reported_at) = peer.last_reported_at {
+                if reported_at.elapsed() < self.min_interval {
+                    return;
+                }
+            }
+
             peer.last_quality_score = quality_score;
+            peer.pending_score = None;
+            peer.last_reported_at = Some(Instant::now());
             if let Some(partner_member_id) = peer.get_partner_member_id() {
+                self.history.borrow_mut().record(
+                    peer.member_id.clone(),
+                    partner_member_id.clone(),
+                    quality_score,
+                    relayed,
+                );
                 self.event_tx.send_event(
                     PeersMetricsEvent::QualityMeterUpdate {
                         member_id: peer.member_id.clone(),
@@ -95,9 +174,16 @@ impl RtcStatsHandler for QualityMeterStatsHandler {
             id,
             member_id: peer.member_id().clone(),
             partner_peer,
-            quality_meter: QualityMeter::new(Duration::from_secs(5)),
+            quality_meter: QualityMeter::new(
+                Duration::from_secs(5),
+                self.audio_quality,
+                self.retention_length,
+            ),
             connection_state: PeerConnectionState::New,
             last_quality_score: ConnectionQualityScore::Poor,
+            pending_score: None,
+            last_reported_at: None,
+            relayed: false,
         }));
         self.peers.insert(peer.id(), peer_metric.clone());
 
@@ -110,7 +196,9 @@ impl RtcStatsHandler for QualityMeterStatsHandler {
     /// Removes [`PeerMetric`]s with the provided [`PeerId`]s.
     fn unregister_peers(&mut self, peers_ids: &[PeerId]) {
         for peer_id in peers_ids {
-            self.peers.remove(peer_id);
+            if let Some(peer) = self.peers.remove(peer_id) {
+                self.evicted += peer.borrow().quality_meter.evicted;
+            }
         }
     }
 
@@ -136,6 +224,7 @@ impl RtcStatsHandler for QualityMeterStatsHandler {
             for stat in stats {
                 match &stat.stats {
                     RtcStatsType::InboundRtp(inbound) => {
+                        peer_ref.add_inbound_audio(stat.id.clone(), inbound);
                         if let Some(partner_peer) =
                             peer_ref.partner_peer.upgrade()
                         {
@@ -172,9 +261,39 @@ impl RtcStatsHandler for QualityMeterStatsHandler {
         }
     }
 
+    /// Updates indicator whether traffic of the `Peer` is relayed.
+    ///
+    /// Does nothing if [`PeerMetric`] with a provided [`PeerId`] not exists.
+    #[inline]
+    fn update_peer_relayed(&mut self, peer_id: PeerId, relayed: bool) {
+        if let Some(peer) = self.peers.get(&peer_id) {
+            peer.borrow_mut().relayed = relayed;
+        }
+    }
+
     fn subscribe(&mut self) -> LocalBoxStream<'static, PeersMetricsEvent> {
         self.event_tx.subscribe()
     }
+
+    /// Returns number of the registered [`PeerMetric`]s and [`ExpiringStat`]s
+    /// stored in their [`QualityMeter`]s.
+    fn entries_count(&self) -> usize {
+        self.peers
+            .values()
+            .map(|peer| 1 + peer.borrow().quality_meter.stats_count())
+            .sum()
+    }
+
+    /// Returns number of the [`ExpiringStat`]s evicted from the
+    /// [`QualityMeter`]s due to the retention length.
+    fn evicted_count(&self) -> u64 {
+        self.evicted
+            + self
+                .peers
+                .values()
+                .map(|peer| peer.borrow().quality_meter.evicted)
+                .sum::<u64>()
+    }
 }
 
 /// [`PeerStateMachine`] representation for the [`QualityMeterStatsHandler`].
@@ -196,11 +315,24 @@ struct PeerMetric {
     /// [`ConnectionQualityScore`] score calculator for this [`PeerMetric`].
     quality_meter: QualityMeter,
 
-    /// Last calculated [`ConnectionQualityScore`].
+    /// Last reported [`ConnectionQualityScore`].
     last_quality_score: ConnectionQualityScore,
 
+    /// [`ConnectionQualityScore`] differing from the reported one, along with
+    /// the number of consecutive calculations it has persisted for.
+    pending_score: Option<(ConnectionQualityScore, u32)>,
+
+    /// Time of the last [`ConnectionQualityScore`] reporting.
+    last_reported_at: Option<Instant>,
+
     /// Current [`PeerConnectionState`].
     connection_state: PeerConnectionState,
+
+    /// Indicator whether traffic of this [`PeerMetric`] is relayed via
+    /// [TURN] server.
+    ///
+    /// [TURN]: https://webrtcglossary.com/turn
+    relayed: bool,
 }
 
 impl PeerMetric {
@@ -233,6 +365,38 @@ impl PeerMetric {
             .add_packets_sent(stat_id, upd.packets_received + packets_lost);
     }
 
+    /// Adds received and concealed samples, and jitter buffer stats from the
+    /// provided audio [`RtcInboundRtpStreamStats`] stats to the
+    /// [`QualityMeter`].
+    ///
+    /// Does nothing if the provided stats are not of audio.
+    fn add_inbound_audio(
+        &mut self,
+        stat_id: StatId,
+        upd: &RtcInboundRtpStreamStats,
+    ) {
+        if let RtcInboundRtpStreamMediaType::Audio {
+            total_samples_received: Some(received),
+            concealed_samples,
+            ..
+        } = &upd.media_specific_stats
+        {
+            self.quality_meter.add_audio_samples(
+                stat_id,
+                AudioSamples {
+                    received: *received,
+                    concealed: concealed_samples.unwrap_or(0),
+                    jitter_buffer_delay: upd
+                        .jitter_buffer_delay
+                        .map_or(0., |d| d.0),
+                    jitter_buffer_emitted: upd
+                        .jitter_buffer_emitted_count
+                        .unwrap_or(0),
+                },
+            );
+        }
+    }
+
     /// Returns [`MemberId`] of the partner [`Member`].
     ///
     /// [`Member`]: crate::signalling::elements::Member
@@ -270,22 +434,37 @@ struct QualityMeter {
     /// Round trip time stats.
     ///
     /// Expired values will be automatically removed.
-    rtt: Vec<ExpiringStat<Rtt>>,
+    rtt: VecDeque<ExpiringStat<Rtt>>,
 
     /// All jitter values added to this [`QualityMeter`].
     ///
     /// Expired values will be automatically removed.
-    jitter: Vec<ExpiringStat<Jitter>>,
+    jitter: VecDeque<ExpiringStat<Jitter>>,
 
     /// Packets lost stats by [`StatId`].
     ///
     /// Expired stats will be automatically removed.
-    packets_lost: HashMap<StatId, Vec<ExpiringStat<PacketLost>>>,
+    packets_lost: HashMap<StatId, VecDeque<ExpiringStat<PacketLost>>>,
 
     /// Packets sent stats by [`StatId`].
     ///
     /// Expired stats will be automatically removed.
-    packets_sent: HashMap<StatId, Vec<ExpiringStat<PacketsSent>>>,
+    packets_sent: HashMap<StatId, VecDeque<ExpiringStat<PacketsSent>>>,
+
+    /// Received audio samples stats by [`StatId`].
+    ///
+    /// Expired stats will be automatically removed.
+    audio_samples: HashMap<StatId, VecDeque<ExpiringStat<AudioSamples>>>,
+
+    /// Thresholds of the audio quality sub-score.
+    audio_quality: AudioQuality,
+
+    /// Maximum number of [`ExpiringStat`]s stored in a single series, after
+    /// which the oldest ones are evicted.
+    max_length: usize,
+
+    /// Number of [`ExpiringStat`]s evicted due to the `max_length`.
+    evicted: u64,
 }
 
 impl QualityMeter {
@@ -312,42 +491,72 @@ impl QualityMeter {
     /// Returns new empty [`QualityMeter`].
     ///
     /// Provided stats TTL will be used to decide when [`ExpiringStat`] should
-    /// expire.
-    fn new(stats_ttl: Duration) -> Self {
+    /// expire, provided [`AudioQuality`] thresholds to calculate the audio
+    /// quality sub-score, and provided `max_length` to bound each series of
+    /// [`ExpiringStat`]s.
+    fn new(
+        stats_ttl: Duration,
+        audio_quality: AudioQuality,
+        max_length: usize,
+    ) -> Self {
         Self {
             stats_ttl,
-            rtt: Vec::new(),
-            jitter: Vec::new(),
+            rtt: VecDeque::new(),
+            jitter: VecDeque::new(),
             packets_lost: HashMap::new(),
             packets_sent: HashMap::new(),
+            audio_samples: HashMap::new(),
+            audio_quality,
+            max_length,
+            evicted: 0,
         }
     }
 
+    /// Returns number of the [`ExpiringStat`]s stored in this
+    /// [`QualityMeter`].
+    fn stats_count(&self) -> usize {
+        self.rtt.len()
+            + self.jitter.len()
+            + self.packets_lost.values().map(VecDeque::len).sum::<usize>()
+            + self.packets_sent.values().map(VecDeque::len).sum::<usize>()
+            + self
+                .audio_samples
+                .values()
+                .map(VecDeque::len)
+                .sum::<usize>()
+    }
+
     /// Adds new round trip time measurement.
     fn add_rtt(&mut self, rtt: Duration) {
-        self.rtt.push(ExpiringStat::new(Rtt(rtt), self.stats_ttl));
+        let stat = ExpiringStat::new(Rtt(rtt), self.stats_ttl);
+        self.evicted += push_stat(&mut self.rtt, stat, self.max_length);
     }
 
     /// Adds new jitter measurement.
     fn add_jitter(&mut self, jitter: Duration) {
-        self.jitter
-            .push(ExpiringStat::new(Jitter(jitter), self.stats_ttl));
+        let stat = ExpiringStat::new(Jitter(jitter), self.stats_ttl);
+        self.evicted += push_stat(&mut self.jitter, stat, self.max_length);
     }
 
     /// Adds new packets sent measurement.
     fn add_packets_sent(&mut self, stat_id: StatId, packets_sent: u64) {
-        self.packets_sent
-            .entry(stat_id)
-            .or_default()
-            .push(ExpiringStat::new(PacketsSent(packets_sent), self.stats_ttl));
+        let stat = ExpiringStat::new(PacketsSent(packets_sent), self.stats_ttl);
+        let row = self.packets_sent.entry(stat_id).or_default();
+        self.evicted += push_stat(row, stat, self.max_length);
     }
 
     /// Adds new packets lost measurement.
     fn add_packets_lost(&mut self, stat_id: StatId, packets_lost: u64) {
-        self.packets_lost
-            .entry(stat_id)
-            .or_default()
-            .push(ExpiringStat::new(PacketLost(packets_lost), self.stats_ttl));
+        let stat = ExpiringStat::new(PacketLost(packets_lost), self.stats_ttl);
+        let row = self.packets_lost.entry(stat_id).or_default();
+        self.evicted += push_stat(row, stat, self.max_length);
+    }
+
+    /// Adds new received audio samples measurement.
+    fn add_audio_samples(&mut self, stat_id: StatId, samples: AudioSamples) {
+        let stat = ExpiringStat::new(samples, self.stats_ttl);
+        let row = self.audio_samples.entry(stat_id).or_default();
+        self.evicted += push_stat(row, stat, self.max_length);
     }
 
     /// Returns [`ConnectionQualityScore`] based on accumulated stats.
@@ -361,6 +570,7 @@ impl QualityMeter {
     /// [Algorithm-MOS]: https://tinyurl.com/y3nojmot
     #[allow(clippy::cast_precision_loss)]
     fn calculate(&mut self) -> Option<ConnectionQualityScore> {
+        let audio_score = self.audio_score();
         let latency = self.mean_rtt()?.as_millis() as f64;
         let jitter = self.mean_jitter()?.as_millis() as f64;
         let packet_loss = self.mean_packet_loss()?;
@@ -378,10 +588,10 @@ impl QualityMeter {
         };
 
         let r = r - (packet_loss * Self::P_LOSS_FACTOR);
-        {
+        let score = {
             use ConnectionQualityScore::{High, Low, Medium, Poor};
 
-            Some(if r < Self::R_LOWER_LIMIT_LOW {
+            if r < Self::R_LOWER_LIMIT_LOW {
                 Poor
             } else if r < Self::R_LOWER_LIMIT_MEDIUM {
                 Low
@@ -389,8 +599,62 @@ impl QualityMeter {
                 Medium
             } else {
                 High
-            })
+            }
+        };
+        Some(audio_score.map_or(score, |audio| score.min(audio)))
+    }
+
+    /// Returns audio quality sub-score based on accumulated [`AudioSamples`]
+    /// stats filtering out expired measurements.
+    ///
+    /// The sub-score is the worst of the ones estimated from the concealed
+    /// samples percentage and the average jitter buffer delay (see
+    /// [`degradation_score()`]).
+    ///
+    /// Returns `None` if there are not enough data to make calculations.
+    #[allow(clippy::cast_precision_loss)]
+    fn audio_score(&mut self) -> Option<ConnectionQualityScore> {
+        self.audio_samples.retain(|_, row| {
+            remove_expired_stats(row);
+            !row.is_empty()
+        });
+
+        let mut received = 0;
+        let mut concealed = 0;
+        let mut delay = 0.;
+        let mut emitted = 0;
+        for row in self.audio_samples.values() {
+            let first = &row.front()?.stat;
+            let last = &row.back()?.stat;
+
+            received += last.received.saturating_sub(first.received);
+            concealed += last.concealed.saturating_sub(first.concealed);
+            delay +=
+                (last.jitter_buffer_delay - first.jitter_buffer_delay).max(0.);
+            emitted += last
+                .jitter_buffer_emitted
+                .saturating_sub(first.jitter_buffer_emitted);
         }
+        if received == 0 {
+            return None;
+        }
+
+        let concealed_percent = concealed as f64 * 100. / received as f64;
+        let mean_delay = if emitted == 0 {
+            0.
+        } else {
+            delay / emitted as f64
+        };
+        Some(
+            degradation_score(
+                concealed_percent,
+                self.audio_quality.concealed_samples_percent,
+            )
+            .min(degradation_score(
+                mean_delay,
+                self.audio_quality.jitter_buffer_delay.as_secs_f64(),
+            )),
+        )
     }
 
     /// Returns average round trip time based on accumulated [`Rtt`] stats stats
@@ -466,13 +730,49 @@ impl QualityMeter {
     }
 }
 
-/// Retains expired [`ExpiringStat`]s from the `Vec<ExpiringStat<T>` storage.
+/// Returns [`ConnectionQualityScore`] degraded by one level each time the
+/// provided `threshold` is doubled by the provided `value`.
+fn degradation_score(value: f64, threshold: f64) -> ConnectionQualityScore {
+    use ConnectionQualityScore::{High, Low, Medium, Poor};
+
+    if value <= threshold {
+        High
+    } else if value <= threshold * 2. {
+        Medium
+    } else if value <= threshold * 4. {
+        Low
+    } else {
+        Poor
+    }
+}
+
+/// Retains expired [`ExpiringStat`]s from the `VecDeque<ExpiringStat<T>>`
+/// storage.
 ///
 /// Expiration will be considered by calling [`ExpiringStat::is_expired`].
-fn remove_expired_stats<T>(stats: &mut Vec<ExpiringStat<T>>) {
+fn remove_expired_stats<T>(stats: &mut VecDeque<ExpiringStat<T>>) {
     stats.retain(|s| !s.is_expired());
 }
 
+/// Pushes the provided [`ExpiringStat`] to the `VecDeque<ExpiringStat<T>>`
+/// storage, evicting the oldest [`ExpiringStat`]s if it contains `max_length`
+/// of them already.
+///
+/// Returns number of the evicted [`ExpiringStat`]s.
+fn push_stat<T>(
+    stats: &mut VecDeque<ExpiringStat<T>>,
+    stat: ExpiringStat<T>,
+    max_length: usize,
+) -> u64 {
+    let mut evicted = 0;
+    while !stats.is_empty() && stats.len() >= max_length {
+        stats.pop_front();
+        evicted += 1;
+    }
+    stats.push_back(stat);
+    evicted
+}
+
 /// Wrapper around stat which implements expiration logic.
 ///
 /// Periodically, storage of the [`ExpiringStat`]s should check all stored
@@ -528,9 +828,25 @@ struct PacketLost(u64);
 #[derive(Debug)]
 struct PacketsSent(u64);
 
+/// Accumulated audio samples stats for specific SSRC.
+#[derive(Debug)]
+struct AudioSamples {
+    /// Number of received samples, including the concealed ones.
+    received: u64,
+
+    /// Number of concealed samples.
+    concealed: u64,
+
+    /// Total time (in seconds) samples have spent in the jitter buffer.
+    jitter_buffer_delay: f64,
+
+    /// Number of samples that have come out of the jitter buffer.
+    jitter_buffer_emitted: u64,
+}
+
 #[cfg(test)]
 mod tests {
-    use futures::StreamExt as _;
+    use futures::{FutureExt as _, StreamExt as _};
     use medea_client_api_proto::stats::{
         Float, HighResTimeStamp, RtcInboundRtpStreamMediaType,
     };
@@ -540,10 +856,12 @@ mod tests {
     use super::*;
 
     const STATS_TTL: Duration = Duration::from_secs(5);
+    const STATS_LENGTH: usize = 100;
 
     #[test]
     fn packets_lost() {
-        let mut meter = QualityMeter::new(STATS_TTL);
+        let mut meter =
+            QualityMeter::new(STATS_TTL, AudioQuality::default(), STATS_LENGTH);
         meter.add_packets_sent(StatId::from("audio"), 100);
         assert_eq!(meter.mean_packet_loss(), Some(0.));
 
@@ -576,7 +894,8 @@ mod tests {
 
     #[test]
     fn very_good_call_quality() {
-        let mut meter = QualityMeter::new(STATS_TTL);
+        let mut meter =
+            QualityMeter::new(STATS_TTL, AudioQuality::default(), STATS_LENGTH);
         meter.add_packets_lost(StatId::from("111"), 0);
         meter.add_packets_sent(StatId::from("111"), 1000);
         meter.add_rtt(Duration::from_millis(0));
@@ -589,7 +908,8 @@ mod tests {
 
     #[test]
     fn regular_normal_call() {
-        let mut meter = QualityMeter::new(STATS_TTL);
+        let mut meter =
+            QualityMeter::new(STATS_TTL, AudioQuality::default(), STATS_LENGTH);
 
         for jitter in [0, 10, 12, 10] {
             meter.add_jitter(Duration::from_millis(jitter));
@@ -615,7 +935,8 @@ mod tests {
 
     #[test]
     fn bad_call() {
-        let mut meter = QualityMeter::new(STATS_TTL);
+        let mut meter =
+            QualityMeter::new(STATS_TTL, AudioQuality::default(), STATS_LENGTH);
 
         for jitter in [10, 20, 15, 16, 11] {
             meter.add_jitter(Duration::from_millis(jitter));
@@ -642,7 +963,8 @@ mod tests {
 
     #[test]
     fn extremely_bad_call() {
-        let mut meter = QualityMeter::new(STATS_TTL);
+        let mut meter =
+            QualityMeter::new(STATS_TTL, AudioQuality::default(), STATS_LENGTH);
         meter.add_packets_lost(StatId::from("a"), 100);
         meter.add_packets_sent(StatId::from("a"), 100);
         meter.add_rtt(Duration::from_millis(1000));
@@ -653,11 +975,68 @@ mod tests {
         assert_eq!(meter.calculate().unwrap(), ConnectionQualityScore::Poor);
     }
 
+    #[test]
+    fn audio_concealment_lowers_score() {
+        let mut meter =
+            QualityMeter::new(STATS_TTL, AudioQuality::default(), STATS_LENGTH);
+        meter.add_packets_lost(StatId::from("a"), 0);
+        meter.add_packets_sent(StatId::from("a"), 1000);
+        meter.add_rtt(Duration::from_millis(0));
+        meter.add_jitter(Duration::from_millis(0));
+
+        let samples = |received, concealed| AudioSamples {
+            received,
+            concealed,
+            jitter_buffer_delay: 0.,
+            jitter_buffer_emitted: 0,
+        };
+        meter.add_audio_samples(StatId::from("a"), samples(0, 0));
+        meter.add_audio_samples(StatId::from("a"), samples(1000, 20));
+        assert_eq!(meter.calculate().unwrap(), ConnectionQualityScore::High);
+
+        meter.add_audio_samples(StatId::from("a"), samples(2000, 100));
+        assert_eq!(meter.calculate().unwrap(), ConnectionQualityScore::Medium);
+
+        meter.add_audio_samples(StatId::from("a"), samples(3000, 400));
+        assert_eq!(meter.calculate().unwrap(), ConnectionQualityScore::Poor);
+    }
+
+    #[test]
+    fn jitter_buffer_delay_lowers_score() {
+        let mut meter =
+            QualityMeter::new(STATS_TTL, AudioQuality::default(), STATS_LENGTH);
+        meter.add_packets_lost(StatId::from("a"), 0);
+        meter.add_packets_sent(StatId::from("a"), 1000);
+        meter.add_rtt(Duration::from_millis(0));
+        meter.add_jitter(Duration::from_millis(0));
+
+        meter.add_audio_samples(
+            StatId::from("a"),
+            AudioSamples {
+                received: 0,
+                concealed: 0,
+                jitter_buffer_delay: 0.,
+                jitter_buffer_emitted: 0,
+            },
+        );
+        meter.add_audio_samples(
+            StatId::from("a"),
+            AudioSamples {
+                received: 1000,
+                concealed: 0,
+                jitter_buffer_delay: 300.,
+                jitter_buffer_emitted: 1000,
+            },
+        );
+        assert_eq!(meter.calculate().unwrap(), ConnectionQualityScore::Low);
+    }
+
     #[test]
     fn rtt_and_jitter_stats_expire() {
         let expired = SystemTime::now() - Duration::from_secs(6);
 
-        let mut meter = QualityMeter::new(STATS_TTL);
+        let mut meter =
+            QualityMeter::new(STATS_TTL, AudioQuality::default(), STATS_LENGTH);
 
         meter.add_rtt(Duration::from_millis(0));
         meter.add_jitter(Duration::from_millis(0));
@@ -677,11 +1056,32 @@ mod tests {
         assert_eq!(meter.jitter.len(), 1);
     }
 
+    #[test]
+    fn evicts_oldest_stats_above_max_length() {
+        let mut meter =
+            QualityMeter::new(STATS_TTL, AudioQuality::default(), 2);
+
+        for ms in 1..=5 {
+            meter.add_rtt(Duration::from_millis(ms));
+            meter.add_packets_sent(StatId::from("a"), ms);
+        }
+
+        assert_eq!(meter.rtt.len(), 2);
+        assert_eq!(meter.rtt.front().unwrap().stat.0.as_millis(), 4);
+        assert_eq!(
+            meter.packets_sent.get(&StatId::from("a")).unwrap().len(),
+            2
+        );
+        assert_eq!(meter.stats_count(), 4);
+        assert_eq!(meter.evicted, 6);
+    }
+
     #[test]
     fn psent_and_plost_stats_expire() {
         let expired = SystemTime::now() - Duration::from_secs(6);
 
-        let mut meter = QualityMeter::new(STATS_TTL);
+        let mut meter =
+            QualityMeter::new(STATS_TTL, AudioQuality::default(), STATS_LENGTH);
 
         meter.add_rtt(Duration::from_millis(0));
         meter.add_jitter(Duration::from_millis(0));
@@ -744,10 +1144,12 @@ mod tests {
         assert_eq!(meter.packets_lost.len(), 0);
     }
 
-    #[tokio::test]
-    async fn connection_state() {
-        let mut stats_handler = QualityMeterStatsHandler::new();
-        let metrics_events = stats_handler.subscribe();
+    /// Registers two partner `Peer`s in the provided
+    /// [`QualityMeterStatsHandler`] and adds stats of a good connection
+    /// quality for them.
+    fn register_peers_with_stats(
+        handler: &mut QualityMeterStatsHandler,
+    ) -> (MemberId, MemberId) {
         let member_id = MemberId::from("member-1");
         let partner_member_id = MemberId::from("member-1");
 
@@ -770,9 +1172,9 @@ mod tests {
         )
         .into();
 
-        stats_handler.register_peer(&peer1);
-        stats_handler.register_peer(&peer2);
-        stats_handler.add_stats(
+        handler.register_peer(&peer1);
+        handler.register_peer(&peer2);
+        handler.add_stats(
             PeerId(1),
             &[RtcStat {
                 id: StatId::from("InboundRtp"),
@@ -796,11 +1198,12 @@ mod tests {
                         jitter: None,
                         total_decode_time: None,
                         jitter_buffer_emitted_count: None,
+                        jitter_buffer_delay: None,
                     },
                 )),
             }],
         );
-        stats_handler.add_stats(
+        handler.add_stats(
             PeerId(0),
             &[RtcStat {
                 id: StatId::from("RemoteInboundRtp"),
@@ -817,6 +1220,22 @@ mod tests {
                 )),
             }],
         );
+
+        (member_id, partner_member_id)
+    }
+
+    #[tokio::test]
+    async fn connection_state() {
+        let mut stats_handler = QualityMeterStatsHandler::new(
+            Rc::default(),
+            1,
+            Duration::ZERO,
+            AudioQuality::default(),
+            STATS_LENGTH,
+        );
+        let metrics_events = stats_handler.subscribe();
+        let (member_id, partner_member_id) =
+            register_peers_with_stats(&mut stats_handler);
         stats_handler.check();
         stats_handler.update_peer_connection_state(
             PeerId(0),
@@ -869,4 +1288,93 @@ mod tests {
         let events: Vec<_> = metrics_events.collect().await;
         assert_eq!(events, &[high.clone(), poor.clone(), high, poor]);
     }
+
+    #[test]
+    fn records_relayed_scores() {
+        let history = Rc::default();
+        let mut stats_handler = QualityMeterStatsHandler::new(
+            Rc::clone(&history),
+            1,
+            Duration::ZERO,
+            AudioQuality::default(),
+            STATS_LENGTH,
+        );
+        let (member_id, partner_member_id) =
+            register_peers_with_stats(&mut stats_handler);
+        stats_handler.update_peer_relayed(PeerId(1), true);
+        stats_handler.check();
+
+        let samples = history.borrow().of_member(&member_id);
+        assert!(samples[&partner_member_id].iter().all(|s| s.relayed));
+        let samples = history.borrow().of_member(&partner_member_id);
+        assert!(samples[&member_id].iter().all(|s| s.relayed));
+        assert!(!samples[&member_id].is_empty());
+    }
+
+    #[tokio::test]
+    async fn reports_score_once_it_persists() {
+        let mut stats_handler = QualityMeterStatsHandler::new(
+            Rc::default(),
+            3,
+            Duration::ZERO,
+            AudioQuality::default(),
+            STATS_LENGTH,
+        );
+        let mut metrics_events = stats_handler.subscribe();
+        let (member_id, partner_member_id) =
+            register_peers_with_stats(&mut stats_handler);
+        for peer_id in &[PeerId(0), PeerId(1)] {
+            stats_handler.update_peer_connection_state(
+                *peer_id,
+                PeerConnectionState::Connected,
+            );
+        }
+        stats_handler.check();
+        assert!(metrics_events.next().now_or_never().is_none());
+
+        stats_handler.check();
+        stats_handler.check();
+        drop(stats_handler);
+
+        let high = PeersMetricsEvent::QualityMeterUpdate {
+            member_id: partner_member_id,
+            partner_member_id: member_id,
+            quality_score: ConnectionQualityScore::High,
+        };
+        let events: Vec<_> = metrics_events.collect().await;
+        assert_eq!(events, &[high.clone(), high]);
+    }
+
+    #[tokio::test]
+    async fn suppresses_changes_during_min_interval() {
+        let mut stats_handler = QualityMeterStatsHandler::new(
+            Rc::default(),
+            1,
+            Duration::from_secs(60),
+            AudioQuality::default(),
+            STATS_LENGTH,
+        );
+        let metrics_events = stats_handler.subscribe();
+        let (member_id, partner_member_id) =
+            register_peers_with_stats(&mut stats_handler);
+        for state in &[
+            PeerConnectionState::Connected,
+            PeerConnectionState::Disconnected,
+            PeerConnectionState::Connected,
+        ] {
+            stats_handler.update_peer_connection_state(PeerId(0), *state);
+            stats_handler.update_peer_connection_state(PeerId(1), *state);
+        }
+        drop(stats_handler);
+
+        let events: Vec<_> = metrics_events.collect().await;
+        assert_eq!(
+            events,
+            &[PeersMetricsEvent::QualityMeterUpdate {
+                member_id: partner_member_id,
+                partner_member_id: member_id,
+                quality_score: ConnectionQualityScore::High,
+            }],
+        );
+    }
 }
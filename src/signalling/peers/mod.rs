@@ -11,37 +11,47 @@ use std::{
     cell::{Cell, RefCell},
     collections::{HashMap, HashSet},
     convert::{TryFrom, TryInto},
+    fmt,
+    hash::Hash,
+    mem,
     rc::Rc,
     sync::Arc,
+    time::{Duration, Instant},
 };
 
 use derive_more::Display;
-use futures::{future, Stream};
+use failure::Fail;
+use futures::{future, stream, Stream, StreamExt as _, TryStreamExt as _};
 use medea_client_api_proto::{
-    state, stats::RtcStat, Incrementable, MemberId, PeerConnectionState,
-    PeerId, RoomId, TrackId,
+    state, stats::RtcStat, IceCandidate, Incrementable, MemberId,
+    NegotiationRole, PeerConnectionState, PeerId, RoomId, TrackId,
 };
 
 use crate::{
     conf,
     log::prelude::*,
-    media::{peer::PeerUpdatesSubscriber, Peer, PeerError, PeerStateMachine},
+    media::{
+        peer::{PeerChange as MediaPeerChange, PeerUpdatesSubscriber},
+        MediaTrack, Peer, PeerError, PeerStateMachine,
+    },
     signalling::{
         elements::endpoints::{
             webrtc::{WebRtcPlayEndpoint, WebRtcPublishEndpoint},
             Endpoint,
         },
         peers::metrics::{PeerMetricsService, RtcStatsHandler},
-        room::RoomError,
+        room::{PeerSnapshot, RoomError},
     },
     turn::{TurnAuthService, UnreachablePolicy},
+    utils::deterministic,
 };
 
 pub use self::{
-    metrics::{PeersMetricsEvent, PeersMetricsEventHandler},
+    metrics::{PeersMetricsEvent, PeersMetricsEventHandler, QualitySample},
     traffic_watcher::{
         build_peers_traffic_watcher, FlowMetricSource,
-        PeerConnectionStateEventsHandler, PeerTrafficWatcher,
+        FlowMetricSourceProvider, PeerConnectionStateEventsHandler,
+        PeerTrafficWatcher, Quorum, TrackedAlways, TrackedIfRelayed,
     },
 };
 
@@ -90,23 +100,113 @@ pub struct PeersService {
     /// Subscriber to the events which indicates that negotiation process
     /// should be started for a some [`Peer`].
     negotiation_sub: Rc<dyn PeerUpdatesSubscriber>,
+
+    /// Maximum number of `Endpoint`s pairs connected concurrently by the
+    /// [`PeersService::connect_endpoints_batch()`].
+    connect_concurrency: usize,
+
+    /// Indicator whether media exchange of all the [`Peer`]s in this [`Room`]
+    /// is paused.
+    ///
+    /// [`Peer`]: crate::media::peer::Peer
+    /// [`Room`]: crate::signalling::room::Room
+    media_paused: Cell<bool>,
+
+    /// Period, during which [`Peer`] pairs left without any [`MediaTrack`]s
+    /// are kept alive to be reused for new `Endpoint`s.
+    ///
+    /// Zero means that such [`Peer`] pairs are removed immediately.
+    ///
+    /// [`MediaTrack`]: crate::media::track::MediaTrack
+    /// [`Peer`]: crate::media::peer::Peer
+    peer_linger_period: Duration,
+
+    /// [`PeerId`]s of the lingering [`Peer`]s (one per pair) along with the
+    /// moments they've started lingering at.
+    ///
+    /// [`Peer`]: crate::media::peer::Peer
+    lingering_peers: RefCell<HashMap<PeerId, Instant>>,
 }
 
 /// Simple ID counter.
+///
+/// Once its IDs space is exhausted, starts over from zero with a new
+/// generation, skipping the IDs which are still in use.
 #[derive(Clone, Debug, Default, Display)]
+#[display(fmt = "{}", "count.get()")]
+#[display(bound = "T: fmt::Display")]
 pub struct Counter<T: Copy> {
     count: Cell<T>,
+
+    /// Number of times this [`Counter`] has exhausted its IDs space and
+    /// started over from zero.
+    generation: Cell<u32>,
+
+    /// IDs restored by clients, which this [`Counter`] doesn't know about.
+    ///
+    /// Each of them is skipped once, when reached by this [`Counter`].
+    reserved: RefCell<HashSet<T>>,
 }
 
-impl<T: Incrementable + Copy> Counter<T> {
+impl<T> Counter<T>
+where
+    T: Incrementable + Copy + Default + Eq + Hash,
+{
     /// Returns id and increase counter.
-    pub fn next_id(&self) -> T {
-        let id = self.count.get();
-        self.count.set(id.incr());
-        id
+    ///
+    /// Skips [reserved][1] IDs. Once this [`Counter`] has started over, also
+    /// skips the IDs for which the provided `is_in_use` function returns
+    /// `true`. Until then, `is_in_use` is not called at all.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`IdsExhausted`] if all the IDs are in use.
+    ///
+    /// [1]: Counter::reserve
+    pub fn next_id<F>(&self, mut is_in_use: F) -> Result<T, IdsExhausted>
+    where
+        F: FnMut(T) -> bool,
+    {
+        let start = self.count.get();
+        loop {
+            let id = self.count.get();
+            let next = id.incr();
+            self.count.set(next);
+            if next == T::default() {
+                self.generation.set(self.generation.get().wrapping_add(1));
+            }
+
+            let is_reserved = self.reserved.borrow_mut().remove(&id);
+            if !is_reserved && (self.generation.get() == 0 || !is_in_use(id)) {
+                break Ok(id);
+            }
+            if next == start {
+                break Err(IdsExhausted);
+            }
+        }
+    }
+
+    /// Returns the current generation of this [`Counter`], which is the
+    /// number of times it has exhausted its IDs space and started over.
+    #[inline]
+    #[must_use]
+    pub fn generation(&self) -> u32 {
+        self.generation.get()
+    }
+
+    /// Reserves the provided ID, so it will be skipped by the
+    /// [`Counter::next_id()`] next time it's reached.
+    #[inline]
+    pub fn reserve(&self, id: T) {
+        self.reserved.borrow_mut().insert(id);
     }
 }
 
+/// Error of a [`Counter`] which has no free IDs left.
+#[derive(Clone, Copy, Debug, Display, Eq, Fail, PartialEq)]
+#[display(fmt = "No free IDs left")]
+pub struct IdsExhausted;
+
 /// Result of the [`PeersService::get_or_create_peers`] function.
 #[derive(Clone, Copy, Debug)]
 enum GetOrCreatePeersResult {
@@ -151,10 +251,14 @@ impl PeersService {
                 PeerMetricsService::new(
                     room_id,
                     peers_traffic_watcher,
-                    media_conf.max_lag,
+                    media_conf,
                 ),
             )),
             negotiation_sub,
+            connect_concurrency: media_conf.connect_concurrency.max(1),
+            media_paused: Cell::new(false),
+            peer_linger_period: media_conf.peer_linger_period,
+            lingering_peers: RefCell::default(),
         })
     }
 
@@ -198,24 +302,52 @@ impl PeersService {
         self.peers.map_peer_by_id_mut(peer_id, f)
     }
 
+    /// Reserves [`PeerId`]s and [`TrackId`]s of the provided [`state::Room`]
+    /// restored by a client, which are unknown to this [`PeersService`], so
+    /// its [`Counter`]s won't reuse them.
+    pub fn reserve_restored_ids(&self, state: &state::Room) {
+        for (peer_id, peer) in &state.peers {
+            if !self.peers.contains(*peer_id) {
+                self.peers_count.reserve(*peer_id);
+            }
+            for track_id in peer.senders.keys().chain(peer.receivers.keys()) {
+                if !self.peers.has_track(*track_id) {
+                    self.tracks_count.reserve(*track_id);
+                }
+            }
+        }
+    }
+
     /// Creates interconnected [`Peer`]s for provided endpoints and saves them
     /// in [`PeersService`].
     ///
     /// Returns [`PeerId`]s of the created [`Peer`]s.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::IdsExhausted`] if there are no free
+    /// [`PeerId`]s or [`TrackId`]s left.
     fn create_peers(
         &self,
         src: &WebRtcPublishEndpoint,
         sink: &WebRtcPlayEndpoint,
-    ) -> (PeerId, PeerId) {
+    ) -> Result<(PeerId, PeerId), RoomError> {
         let src_member_id = src.owner().id();
         let sink_member_id = sink.owner().id();
 
-        let src_peer_id = self.peers_count.next_id();
-        let sink_peer_id = self.peers_count.next_id();
+        let src_peer_id =
+            self.peers_count.next_id(|id| self.peers.contains(id))?;
+        let sink_peer_id = self
+            .peers_count
+            .next_id(|id| id == src_peer_id || self.peers.contains(id))?;
 
         debug!(
-            "Created peers:[{}, {}] between {} and {}.",
-            src_peer_id, sink_peer_id, src_member_id, sink_member_id,
+            "Created peers:[{}, {}] (generation {}) between {} and {}.",
+            src_peer_id,
+            sink_peer_id,
+            self.peers_count.generation(),
+            src_member_id,
+            sink_member_id,
         );
 
         let mut src_peer = PeerStateMachine::from(Peer::new(
@@ -242,7 +374,12 @@ impl PeersService {
             &src,
             &mut sink_peer,
             &self.tracks_count,
-        );
+            |id| self.peers.has_track(id),
+        )?;
+        if self.media_paused.get() {
+            src_peer.as_changes_scheduler().set_media_paused(true);
+            sink_peer.as_changes_scheduler().set_media_paused(true);
+        }
 
         self.peer_metrics_service
             .borrow_mut()
@@ -254,7 +391,7 @@ impl PeersService {
         self.add_peer(src_peer);
         self.add_peer(sink_peer);
 
-        (src_peer_id, sink_peer_id)
+        Ok((src_peer_id, sink_peer_id))
     }
 
     /// Lookups [`Peer`] of [`Member`] with ID `member_id` which
@@ -338,16 +475,122 @@ impl PeersService {
         removed_peers
     }
 
+    /// Deletes [`PeerStateMachine`]s which haven't finished their negotiation
+    /// in the provided `timeout`, along with their partners.
+    ///
+    /// Returns removed [`PeerStateMachine`]s grouped by their owners'
+    /// [`MemberId`]s.
+    pub(super) fn remove_stuck_peers(
+        &self,
+        timeout: Duration,
+    ) -> HashMap<MemberId, Vec<PeerStateMachine>> {
+        let mut removed_peers: HashMap<_, Vec<_>> = HashMap::new();
+        let deadline = match Instant::now().checked_sub(timeout) {
+            Some(deadline) => deadline,
+            None => return removed_peers,
+        };
+        for (member_id, peer_id) in self.peers.negotiating_since(deadline) {
+            for (member_id, peers) in self.remove_peers(&member_id, &[peer_id])
+            {
+                removed_peers.entry(member_id).or_default().extend(peers);
+            }
+        }
+        removed_peers
+    }
+
+    /// Deletes [`PeerStateMachine`]s which have been lingering without any
+    /// [`MediaTrack`]s for longer than the `peer_linger_period`, along with
+    /// their partners.
+    ///
+    /// Returns removed [`PeerStateMachine`]s grouped by their owners'
+    /// [`MemberId`]s.
+    ///
+    /// [`MediaTrack`]: crate::media::track::MediaTrack
+    pub(super) fn remove_lingering_peers(
+        &self,
+    ) -> HashMap<MemberId, Vec<PeerStateMachine>> {
+        let mut removed_peers: HashMap<_, Vec<_>> = HashMap::new();
+        let deadline = match Instant::now().checked_sub(self.peer_linger_period)
+        {
+            Some(deadline) => deadline,
+            None => return removed_peers,
+        };
+
+        let expired: Vec<_> = {
+            let mut lingering = self.lingering_peers.borrow_mut();
+            let expired: Vec<_> = lingering
+                .iter()
+                .filter(|(_, since)| **since < deadline)
+                .map(|(id, _)| *id)
+                .collect();
+            for id in &expired {
+                lingering.remove(id);
+            }
+            expired
+        };
+        for peer_id in expired {
+            let member_id = self.peers.map_peer_by_id(peer_id, |peer| {
+                if peer.is_empty() {
+                    Some(peer.member_id().clone())
+                } else {
+                    None
+                }
+            });
+            if let Ok(Some(member_id)) = member_id {
+                for (member_id, peers) in
+                    self.remove_peers(&member_id, &[peer_id])
+                {
+                    removed_peers.entry(member_id).or_default().extend(peers);
+                }
+            }
+        }
+        removed_peers
+    }
+
+    /// Starts lingering of the [`Peer`] pairs updated by the provided
+    /// [`PeerChange`]s, which are left without any [`MediaTrack`]s.
+    ///
+    /// [`MediaTrack`]: crate::media::track::MediaTrack
+    /// [`Peer`]: crate::media::peer::Peer
+    fn start_lingering(&self, changes: &HashSet<PeerChange>) {
+        let now = Instant::now();
+        for change in changes {
+            if let PeerChange::Updated(peer_id) = change {
+                let is_empty = self
+                    .peers
+                    .map_peer_by_id(*peer_id, PeerStateMachine::is_empty)
+                    .unwrap_or_default();
+                if is_empty {
+                    debug!(
+                        "Peer [id = {}] in Room [id = {}] is left without \
+                         tracks, so lingers for {:?}",
+                        peer_id, self.room_id, self.peer_linger_period,
+                    );
+                    self.lingering_peers.borrow_mut().insert(*peer_id, now);
+                }
+            }
+        }
+    }
+
     /// Deletes the provided [`WebRtcPlayEndpoint`].
     ///
     /// Returns [`PeerChange`]s which were performed by this function.
-    #[inline]
+    ///
+    /// [`Peer`] pair left without any [`MediaTrack`]s is kept alive during
+    /// the `peer_linger_period` (if any).
+    ///
+    /// [`MediaTrack`]: crate::media::track::MediaTrack
+    /// [`Peer`]: crate::media::peer::Peer
     pub fn delete_sink_endpoint(
         &self,
         sink: &WebRtcPlayEndpoint,
     ) -> HashSet<PeerChange> {
-        if let Ok(change) = self.peers.delete_sink_endpoint(sink) {
-            change
+        let linger = !self.peer_linger_period.is_zero();
+        if let Ok(changes) = self.peers.delete_sink_endpoint(sink, linger) {
+            if linger {
+                self.start_lingering(&changes);
+            }
+            changes
         } else {
             // This can happen only if the provided endpoint contains peers that
             // don't exist anymore. Not a reason to propagate error, since we're
@@ -362,17 +605,25 @@ impl PeersService {
     ///
     /// Returns [`PeerChange`]s which were performed by this function.
     ///
+    /// [`Peer`] pairs left without any [`MediaTrack`]s are kept alive during
+    /// the `peer_linger_period` (if any).
+    ///
     /// # Errors
     ///
     /// If a [`Peer`] with the provided [`PeerId`] or a partner [`Peer`] hasn't
     /// been found.
-    #[inline]
+    ///
+    /// [`MediaTrack`]: crate::media::track::MediaTrack
     pub fn delete_src_endpoint(
         &self,
         src: &WebRtcPublishEndpoint,
     ) -> HashSet<PeerChange> {
-        if let Ok(change) = self.peers.delete_src_endpoint(src) {
-            change
+        let linger = !self.peer_linger_period.is_zero();
+        if let Ok(changes) = self.peers.delete_src_endpoint(src, linger) {
+            if linger {
+                self.start_lingering(&changes);
+            }
+            changes
         } else {
             // This can happen only if the provided endpoint contains peers that
             // don't exist anymore. Not a reason to propagate error, since we're
@@ -396,17 +647,28 @@ impl PeersService {
         if let Some((first_peer_id, second_peer_id)) = self
             .get_peers_between_members(&src.owner().id(), &sink.owner().id())
         {
+            let mut lingering = self.lingering_peers.borrow_mut();
+            if lingering.remove(&first_peer_id).is_some()
+                || lingering.remove(&second_peer_id).is_some()
+            {
+                debug!(
+                    "Reusing lingering Peers [{}, {}] in Room [id = {}]",
+                    first_peer_id, second_peer_id, self.room_id,
+                );
+            }
             Ok(GetOrCreatePeersResult::AlreadyExisted(
                 first_peer_id,
                 second_peer_id,
             ))
         } else {
-            let (src_peer_id, sink_peer_id) = self.create_peers(&src, &sink);
+            let (src_peer_id, sink_peer_id) =
+                self.create_peers(&src, &sink)?;
 
-            self.peer_post_construct(src_peer_id, &src.clone().into())
-                .await?;
-            self.peer_post_construct(sink_peer_id, &sink.clone().into())
-                .await?;
+            future::try_join(
+                self.peer_post_construct(src_peer_id, &src.clone().into()),
+                self.peer_post_construct(sink_peer_id, &sink.clone().into()),
+            )
+            .await?;
 
             Ok(GetOrCreatePeersResult::Created(src_peer_id, sink_peer_id))
         }
@@ -436,6 +698,20 @@ impl PeersService {
         Ok(())
     }
 
+    /// Pauses/resumes media exchange of all the [`Peer`]s in this
+    /// [`PeersService`], keeping them alive.
+    ///
+    /// [`Peer`]s created while media exchange is paused start paused too.
+    ///
+    /// Returns `false` if media exchange is already in the requested state.
+    pub fn set_media_paused(&self, paused: bool) -> bool {
+        if self.media_paused.replace(paused) == paused {
+            return false;
+        }
+        self.peers.set_media_paused(paused);
+        true
+    }
+
     /// Creates [`Peer`] for endpoints if [`Peer`] between endpoint's members
     /// doesn't exist.
     ///
@@ -477,11 +753,17 @@ impl PeersService {
                     let mut src_peer = self.peers.take(src_peer_id)?;
                     let mut sink_peer = self.peers.take(sink_peer_id)?;
 
-                    src_peer.as_changes_scheduler().add_publisher(
+                    let added = src_peer.as_changes_scheduler().add_publisher(
                         &src,
                         &mut sink_peer,
                         &self.tracks_count,
+                        |id| self.peers.has_track(id),
                     );
+                    if let Err(e) = added {
+                        self.peers.add_peer(src_peer);
+                        self.peers.add_peer(sink_peer);
+                        return Err(e.into());
+                    }
 
                     let mut register_peer_tasks = Vec::new();
                     if src.has_traffic_callback() {
@@ -519,6 +801,46 @@ impl PeersService {
         }
     }
 
+    /// Connects all the provided `Endpoint`s pairs with
+    /// [`PeersService::connect_endpoints()`], running at most
+    /// `connect_concurrency` of them concurrently.
+    ///
+    /// With the `deterministic` feature enabled, `Endpoint`s pairs are
+    /// connected one by one in the order of their IDs, so the created
+    /// [`Peer`]s always get the same [`PeerId`]s.
+    ///
+    /// Returns [`PeerId`]s of the [`Peer`] pairs which were created or
+    /// updated.
+    ///
+    /// # Errors
+    ///
+    /// Errors if connecting of any `Endpoint`s pair fails.
+    pub async fn connect_endpoints_batch(
+        self: Rc<Self>,
+        mut endpoints: Vec<(WebRtcPublishEndpoint, WebRtcPlayEndpoint)>,
+    ) -> Result<Vec<(PeerId, PeerId)>, RoomError> {
+        deterministic::order_by(&mut endpoints, |(src, sink)| {
+            (
+                src.owner().id().to_string(),
+                src.id().to_string(),
+                sink.owner().id().to_string(),
+                sink.id().to_string(),
+            )
+        });
+        // Connecting one by one keeps the order of created `Peer`s stable.
+        let concurrency = if cfg!(feature = "deterministic") {
+            1
+        } else {
+            self.connect_concurrency
+        };
+        stream::iter(endpoints)
+            .map(|(src, sink)| Rc::clone(&self).connect_endpoints(src, sink))
+            .buffer_unordered(concurrency)
+            .try_filter_map(future::ok)
+            .try_collect()
+            .await
+    }
+
     /// Creates and sets [`IceUser`], registers [`Peer`] in
     /// [`PeerTrafficWatcher`].
     ///
@@ -624,7 +946,7 @@ impl PeersService {
     }
 
     /// Propagates stats to [`RtcStatsHandler`].
-    pub(super) fn add_stats(&self, peer_id: PeerId, stats: &[RtcStat]) {
+    pub fn add_stats(&self, peer_id: PeerId, stats: &[RtcStat]) {
         self.peer_metrics_service
             .borrow_mut()
             .add_stats(peer_id, stats);
@@ -641,6 +963,23 @@ impl PeersService {
             .update_peer_connection_state(peer_id, state);
     }
 
+    /// Propagates indicator whether traffic of the [`Peer`] is relayed to
+    /// [`RtcStatsHandler`].
+    pub(super) fn update_peer_relayed(&self, peer_id: PeerId, relayed: bool) {
+        self.peer_metrics_service
+            .borrow_mut()
+            .update_peer_relayed(peer_id, relayed);
+    }
+
+    /// Returns recent [`QualitySample`]s of the provided `Member` with each of
+    /// its partners from the underlying [`RtcStatsHandler`].
+    pub(super) fn quality_history(
+        &self,
+        member_id: &MemberId,
+    ) -> HashMap<MemberId, Vec<QualitySample>> {
+        self.peer_metrics_service.borrow().quality_history(member_id)
+    }
+
     /// Runs [`Peer`]s stats checking in the underlying [`PeersMetricsEvent`]s.
     pub(super) fn check_peers(&self) {
         self.peer_metrics_service.borrow_mut().check();
@@ -656,6 +995,69 @@ impl PeersService {
     ) -> HashMap<PeerId, state::Peer> {
         self.peers.get_peers_states(member_id)
     }
+
+    /// Returns number of the [`Peer`] pairs being negotiated at the moment.
+    #[inline]
+    #[must_use]
+    pub(super) fn negotiations_count(&self) -> usize {
+        self.peers.negotiations_count()
+    }
+
+    /// Returns [`PeerSnapshot`]s of all the [`Peer`]s of this
+    /// [`PeersService`] taken at the provided moment, ordered by [`PeerId`]s.
+    #[must_use]
+    pub(super) fn snapshot(&self, now: Instant) -> Vec<PeerSnapshot> {
+        self.peers.snapshot(now)
+    }
+
+    /// Returns approximate [`PeersUsage`] of this [`PeersService`].
+    #[must_use]
+    pub(super) fn usage(&self) -> PeersUsage {
+        let metrics = self.peer_metrics_service.borrow();
+        let mut usage = self.peers.usage();
+        usage.metrics_entries = metrics.entries_count();
+        usage.metrics_evicted = metrics.evicted_count();
+        usage
+    }
+}
+
+impl Drop for PeersService {
+    /// Releases resources held by the [`TurnAuthService`] for the [`Room`] of
+    /// this [`PeersService`].
+    ///
+    /// [`Room`]: crate::signalling::room::Room
+    #[inline]
+    fn drop(&mut self) {
+        self.turn_service.release(&self.room_id);
+    }
+}
+
+/// Approximate amounts of entities kept in memory by a [`PeersService`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PeersUsage {
+    /// Number of [`Peer`]s.
+    pub peers: usize,
+
+    /// Number of [`MediaTrack`]s of all the [`Peer`]s.
+    pub tracks: usize,
+
+    /// Number of changes of all the [`Peer`]s, which are either scheduled or
+    /// not sent to remote [`Peer`]s yet.
+    pub pending_changes: usize,
+
+    /// Number of [`IceCandidate`]s stored for all the [`Peer`]s.
+    pub ice_candidates: usize,
+
+    /// Number of entries kept for calculating metrics of the [`Peer`]s.
+    pub metrics_entries: usize,
+
+    /// Number of entries evicted from the ones kept for calculating metrics
+    /// of the [`Peer`]s due to the configured retention.
+    pub metrics_evicted: u64,
+
+    /// Approximate lower bound of the memory occupied by the [`Peer`]s, in
+    /// bytes.
+    pub approx_bytes: usize,
 }
 
 /// Repository which stores all [`PeerStateMachine`]s of the [`PeersService`].
@@ -701,6 +1103,61 @@ impl PeerRepository {
             .ok_or(RoomError::PeerNotFound(peer_id))?))
     }
 
+    /// Indicates whether a [`PeerStateMachine`] with the provided [`PeerId`]
+    /// is stored.
+    #[inline]
+    fn contains(&self, peer_id: PeerId) -> bool {
+        self.0.borrow().contains_key(&peer_id)
+    }
+
+    /// Indicates whether any of the stored [`PeerStateMachine`]s has a track
+    /// with the provided [`TrackId`].
+    fn has_track(&self, track_id: TrackId) -> bool {
+        self.0.borrow().values().any(|peer| {
+            peer.senders().contains_key(&track_id)
+                || peer.receivers().contains_key(&track_id)
+        })
+    }
+
+    /// Pauses/resumes media exchange of all the stored [`PeerStateMachine`]s
+    /// and tries to commit it.
+    fn set_media_paused(&self, paused: bool) {
+        for peer in self.0.borrow_mut().values_mut() {
+            peer.as_changes_scheduler().set_media_paused(paused);
+            peer.commit_scheduled_changes();
+        }
+    }
+
+    /// Returns number of the [`PeerStateMachine`] pairs being negotiated
+    /// at the moment.
+    fn negotiations_count(&self) -> usize {
+        self.0
+            .borrow()
+            .values()
+            .filter(|peer| {
+                matches!(
+                    peer.negotiation_role(),
+                    Some(NegotiationRole::Offerer),
+                )
+            })
+            .count()
+    }
+
+    /// Returns [`PeerId`]s and owners' [`MemberId`]s of the
+    /// [`PeerStateMachine`]s which negotiation was started before the provided
+    /// `deadline`.
+    fn negotiating_since(&self, deadline: Instant) -> Vec<(MemberId, PeerId)> {
+        self.0
+            .borrow()
+            .values()
+            .filter(|peer| {
+                peer.negotiation_started_at()
+                    .map_or(false, |started_at| started_at < deadline)
+            })
+            .map(|peer| (peer.member_id().clone(), peer.id()))
+            .collect()
+    }
+
     /// Removes [`PeerStateMachine`] with a provided [`PeerId`].
     ///
     /// Returns removed [`PeerStateMachine`] if it existed.
@@ -778,17 +1235,24 @@ impl PeerRepository {
     ///
     /// Returns [`PeerChange`]s which were performed by this action.
     ///
+    /// Doesn't remove [`Peer`] pairs left without any [`MediaTrack`]s if
+    /// `linger` is `true`.
+    ///
     /// # Errors
     ///
     /// With [`RoomError::PeerNotFound`] if the requested [`PeerId`] doesn't
     /// exist in a [`PeerRepository`].
+    ///
+    /// [`MediaTrack`]: crate::media::track::MediaTrack
+    /// [`Peer`]: crate::media::peer::Peer
     pub fn delete_src_endpoint(
         &self,
         src: &WebRtcPublishEndpoint,
+        linger: bool,
     ) -> Result<HashSet<PeerChange>, RoomError> {
         let mut affected_peers = HashSet::new();
         for sink in src.sinks() {
-            affected_peers.extend(self.delete_sink_endpoint(&sink)?);
+            affected_peers.extend(self.delete_sink_endpoint(&sink, linger)?);
         }
 
         Ok(affected_peers)
@@ -798,13 +1262,20 @@ impl PeerRepository {
     ///
     /// Returns [`PeerChange`]s which were performed by this action.
     ///
+    /// Doesn't remove [`Peer`] pair left without any [`MediaTrack`]s if
+    /// `linger` is `true`.
+    ///
     /// # Errors
     ///
     /// With [`RoomError::PeerNotFound`] if the requested [`PeerId`] doesn't
     /// exist in a [`PeerRepository`].
+    ///
+    /// [`MediaTrack`]: crate::media::track::MediaTrack
+    /// [`Peer`]: crate::media::peer::Peer
     pub fn delete_sink_endpoint(
         &self,
         sink_endpoint: &WebRtcPlayEndpoint,
+        linger: bool,
     ) -> Result<HashSet<PeerChange>, RoomError> {
         let mut changes = HashSet::new();
 
@@ -832,7 +1303,8 @@ impl PeerRepository {
             let is_src_peer_empty =
                 self.map_peer_by_id(src_peer_id, PeerStateMachine::is_empty)?;
 
-            if is_sink_peer_empty && is_src_peer_empty {
+            let is_pair_empty = is_sink_peer_empty && is_src_peer_empty;
+            if is_pair_empty && !linger {
                 let member = sink_endpoint.owner();
                 member.peers_removed(&[sink_peer_id]);
 
@@ -845,6 +1317,11 @@ impl PeerRepository {
                     src_peer_id,
                 ));
             } else {
+                if is_pair_empty {
+                    // Lingering `Peer`s are not related to the source endpoint
+                    // anymore, so it could be connected to them again.
+                    sink_endpoint.src().remove_peer_ids(&[src_peer_id]);
+                }
                 changes.insert(PeerChange::Updated(sink_peer_id));
             }
         }
@@ -925,6 +1402,60 @@ impl PeerRepository {
             })
             .collect()
     }
+
+    /// Returns [`PeerSnapshot`]s of all the [`Peer`]s stored in this
+    /// [`PeerRepository`] taken at the provided moment, ordered by
+    /// [`PeerId`]s.
+    #[must_use]
+    pub fn snapshot(&self, now: Instant) -> Vec<PeerSnapshot> {
+        let mut peers: Vec<_> = self
+            .0
+            .borrow()
+            .values()
+            .map(|peer| PeerSnapshot::new(peer, now))
+            .collect();
+        peers.sort_by_key(|peer| peer.id);
+        peers
+    }
+
+    /// Returns [`PeersUsage`] of all the [`Peer`]s stored in this
+    /// [`PeerRepository`].
+    ///
+    /// [`PeersUsage::metrics_entries`] and [`PeersUsage::metrics_evicted`] are
+    /// not counted.
+    #[must_use]
+    pub fn usage(&self) -> PeersUsage {
+        self.0.borrow().values().fold(
+            PeersUsage::default(),
+            |mut usage, peer| {
+                let tracks = peer.senders().len() + peer.receivers().len();
+                let changes = peer.pending_changes_count();
+                let candidates = peer.ice_candidates();
+
+                usage.peers += 1;
+                usage.tracks += tracks;
+                usage.pending_changes += changes;
+                usage.ice_candidates += candidates.len();
+
+                // `MediaTrack`s are shared with the partner `Peer`, so only
+                // senders are accounted.
+                usage.approx_bytes += mem::size_of::<PeerStateMachine>()
+                    + peer.local_sdp().map_or(0, str::len)
+                    + peer.remote_sdp().map_or(0, str::len)
+                    + peer.senders().len() * mem::size_of::<MediaTrack>()
+                    + changes * mem::size_of::<MediaPeerChange>()
+                    + candidates
+                        .iter()
+                        .map(|c| {
+                            mem::size_of::<IceCandidate>()
+                                + c.candidate.len()
+                                + c.sdp_mid.as_ref().map_or(0, String::len)
+                        })
+                        .sum::<usize>();
+                usage
+            },
+        )
+    }
 }
 
 #[cfg(test)]
@@ -937,6 +1468,7 @@ mod tests {
 
     use crate::{
         api::control::{
+            callback::url::CallbackUrl,
             endpoints::webrtc_publish_endpoint::{
                 AudioSettings, P2pMode, VideoSettings,
             },
@@ -946,7 +1478,7 @@ mod tests {
         signalling::{
             elements::Member, peers::traffic_watcher::MockPeerTrafficWatcher,
         },
-        turn::test::new_turn_auth_service_mock,
+        turn::{new_turn_auth_service_mock, TurnAuthServiceMock},
     };
 
     use super::{metrics::MockRtcStatsHandler, *};
@@ -968,6 +1500,10 @@ mod tests {
                 peers_traffic_watcher,
                 peer_metrics_service: RefCell::new(peer_metrics_service),
                 negotiation_sub,
+                connect_concurrency: conf::Media::default().connect_concurrency,
+                media_paused: Cell::new(false),
+                peer_linger_period: Duration::default(),
+                lingering_peers: RefCell::default(),
             })
         }
     }
@@ -1026,6 +1562,15 @@ mod tests {
         }
     }
 
+    /// Returns `OnStart` [`CallbackUrl`] to a `grpc://127.0.0.1:6565`, making
+    /// `Endpoint`s registered in the [`PeerTrafficWatcher`].
+    fn on_start_url() -> Option<CallbackUrl> {
+        Some(
+            CallbackUrl::try_from("grpc://127.0.0.1:6565".to_string())
+                .unwrap(),
+        )
+    }
+
     /// Returns [`Fn`] which will return `true` if provided
     /// [`PeerStateMachine`]'s [`PeerId`] will be equal to the provided into
     /// [`peer_id_eq`] [`PeerId`].
@@ -1116,6 +1661,8 @@ mod tests {
             receiver.downgrade(),
             false,
         );
+        publish.set_callback_urls(on_start_url(), None);
+        play.set_callback_urls(on_start_url(), None);
 
         let (src_peer_id, sink_peer_id) = peers_service
             .clone()
@@ -1221,6 +1768,8 @@ mod tests {
             receiver.downgrade(),
             false,
         );
+        publish.set_callback_urls(on_start_url(), None);
+        play.set_callback_urls(on_start_url(), None);
 
         let (src_peer_id, sink_peer_id) = peers_service
             .clone()
@@ -1249,6 +1798,8 @@ mod tests {
             publisher.downgrade(),
             false,
         );
+        publish.set_callback_urls(on_start_url(), None);
+        play.set_callback_urls(on_start_url(), None);
 
         let (src_peer_id, sink_peer_id) = peers_service
             .clone()
@@ -1268,4 +1819,259 @@ mod tests {
         assert!(negotiate_peer_ids.contains(&PeerId(0)));
         assert!(negotiate_peer_ids.contains(&PeerId(1)));
     }
+
+    /// Checks that [`PeersService::connect_endpoints_batch()`] creates
+    /// [`Peer`]s for all the provided `Endpoint`s pairs.
+    #[actix_rt::test]
+    async fn connects_endpoints_batch() {
+        let mut mock = MockPeerTrafficWatcher::new();
+        mock.expect_register_peer()
+            .returning(|_, _, _| Box::pin(future::ok(())));
+        let mut metrics_service = MockRtcStatsHandler::new();
+        metrics_service.expect_register_peer().return_const(());
+
+        let turn_service = Arc::new(TurnAuthServiceMock::default());
+        let peers_service = PeersService::with_metrics_service(
+            "test".into(),
+            turn_service.clone(),
+            Arc::new(mock),
+            Rc::new(NegotiationSubMock::new()),
+            Box::new(metrics_service),
+        );
+
+        let new_member = |id: &str| {
+            Member::new(
+                id.into(),
+                Credential::Plain("test".into()),
+                "test".into(),
+                Duration::from_secs(10),
+                Duration::from_secs(10),
+                Duration::from_secs(5),
+            )
+        };
+        let publisher = new_member("publisher");
+        let publish = WebRtcPublishEndpoint::new(
+            "publish".to_string().into(),
+            P2pMode::Always,
+            publisher.downgrade(),
+            false,
+            AudioSettings::default(),
+            VideoSettings::default(),
+        );
+        let receivers: Vec<_> = (0..3)
+            .map(|i| new_member(&format!("receiver-{}", i)))
+            .collect();
+        let endpoints = receivers
+            .iter()
+            .map(|receiver| {
+                let play = WebRtcPlayEndpoint::new(
+                    "play-publisher".to_string().into(),
+                    SrcUri::try_from(
+                        "local://test/publisher/publish".to_string(),
+                    )
+                    .unwrap(),
+                    publish.downgrade(),
+                    receiver.downgrade(),
+                    false,
+                );
+                (publish.clone(), play)
+            })
+            .collect();
+
+        let connected = peers_service
+            .clone()
+            .connect_endpoints_batch(endpoints)
+            .await
+            .unwrap();
+
+        assert_eq!(connected.len(), 3);
+        assert_eq!(turn_service.created().len(), 6);
+        for receiver in &receivers {
+            assert!(peers_service
+                .get_peers_between_members(&publisher.id(), &receiver.id())
+                .is_some());
+        }
+    }
+
+    /// Checks that [`Peer`]s left without any [`MediaTrack`]s are kept alive
+    /// during the `peer_linger_period` and reused for new `Endpoint`s.
+    #[actix_rt::test]
+    async fn reuses_lingering_peers() {
+        let mut mock = MockPeerTrafficWatcher::new();
+        mock.expect_register_peer()
+            .returning(|_, _, _| Box::pin(future::ok(())));
+        mock.expect_unregister_peers().return_const(());
+        let mut metrics_service = MockRtcStatsHandler::new();
+        metrics_service.expect_register_peer().return_const(());
+        metrics_service.expect_unregister_peers().return_const(());
+
+        let mut peers_service = PeersService::with_metrics_service(
+            "test".into(),
+            new_turn_auth_service_mock(),
+            Arc::new(mock),
+            Rc::new(NegotiationSubMock::new()),
+            Box::new(metrics_service),
+        );
+        Rc::get_mut(&mut peers_service).unwrap().peer_linger_period =
+            Duration::from_secs(60);
+
+        let new_member = |id: &str| {
+            Member::new(
+                id.into(),
+                Credential::Plain("test".into()),
+                "test".into(),
+                Duration::from_secs(10),
+                Duration::from_secs(10),
+                Duration::from_secs(5),
+            )
+        };
+        let publisher = new_member("publisher");
+        let receiver = new_member("receiver");
+        let publish = WebRtcPublishEndpoint::new(
+            "publish".to_string().into(),
+            P2pMode::Always,
+            publisher.downgrade(),
+            false,
+            AudioSettings::default(),
+            VideoSettings::default(),
+        );
+        let new_play = || {
+            WebRtcPlayEndpoint::new(
+                "play-publisher".to_string().into(),
+                SrcUri::try_from("local://test/publisher/publish".to_string())
+                    .unwrap(),
+                publish.downgrade(),
+                receiver.downgrade(),
+                false,
+            )
+        };
+
+        let play = new_play();
+        let (src_peer_id, sink_peer_id) = peers_service
+            .clone()
+            .connect_endpoints(publish.clone(), play.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        peers_service.commit_scheduled_changes(src_peer_id).unwrap();
+
+        let changes = peers_service.delete_sink_endpoint(&play);
+        assert_eq!(
+            changes,
+            std::iter::once(PeerChange::Updated(sink_peer_id)).collect(),
+        );
+        assert!(peers_service.remove_lingering_peers().is_empty());
+
+        let play = new_play();
+        let reused = peers_service
+            .clone()
+            .connect_endpoints(publish, play.clone())
+            .await
+            .unwrap();
+        assert_eq!(reused, Some((src_peer_id, sink_peer_id)));
+        assert!(peers_service.lingering_peers.borrow().is_empty());
+        peers_service.commit_scheduled_changes(src_peer_id).unwrap();
+
+        peers_service.delete_sink_endpoint(&play);
+        peers_service
+            .lingering_peers
+            .borrow_mut()
+            .insert(sink_peer_id, Instant::now() - Duration::from_secs(61));
+
+        let removed = peers_service.remove_lingering_peers();
+        assert_eq!(removed.len(), 2);
+        assert!(peers_service
+            .get_peers_between_members(&publisher.id(), &receiver.id())
+            .is_none());
+    }
+
+    /// ID with a tiny IDs space, so [`Counter`] exhausts it quickly.
+    #[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+    struct TinyId(u8);
+
+    impl Incrementable for TinyId {
+        fn incr(&self) -> Self {
+            Self(self.0.wrapping_add(1))
+        }
+    }
+
+    #[test]
+    fn counter_checks_ids_in_use_only_once_started_over() {
+        let counter = Counter::default();
+
+        assert_eq!(counter.next_id(|_| unreachable!()), Ok(PeerId(0)));
+        assert_eq!(counter.next_id(|_| unreachable!()), Ok(PeerId(1)));
+        assert_eq!(counter.generation(), 0);
+    }
+
+    #[test]
+    fn counter_wraps_around_skipping_ids_in_use() {
+        let counter = Counter {
+            count: Cell::new(TrackId(u32::MAX)),
+            generation: Cell::default(),
+            reserved: RefCell::default(),
+        };
+
+        let in_use = |id| id == TrackId(0);
+        assert_eq!(counter.next_id(in_use), Ok(TrackId(u32::MAX)));
+        assert_eq!(counter.next_id(in_use), Ok(TrackId(1)));
+        assert_eq!(counter.generation(), 1);
+    }
+
+    #[test]
+    fn counter_skips_reserved_ids_once() {
+        let counter = Counter::default();
+        counter.reserve(PeerId(1));
+
+        assert_eq!(counter.next_id(|_| false), Ok(PeerId(0)));
+        assert_eq!(counter.next_id(|_| false), Ok(PeerId(2)));
+
+        counter.count.set(PeerId(1));
+        assert_eq!(counter.next_id(|_| false), Ok(PeerId(1)));
+    }
+
+    #[test]
+    fn counter_errors_when_all_ids_in_use() {
+        let counter = Counter::<TinyId>::default();
+        for i in 0..=u8::MAX {
+            assert_eq!(counter.next_id(|_| false), Ok(TinyId(i)));
+        }
+
+        assert_eq!(counter.next_id(|_| true), Err(IdsExhausted));
+        assert_eq!(counter.next_id(|id| id != TinyId(7)), Ok(TinyId(7)));
+    }
+
+    #[test]
+    fn finds_peers_stuck_in_negotiation() {
+        use crate::media::peer::tests::dummy_negotiation_sub_mock;
+
+        let new_peer = |id, partner_id, member_id: &str| {
+            Peer::new(
+                PeerId(id),
+                member_id.into(),
+                PeerId(partner_id),
+                "partner".into(),
+                false,
+                dummy_negotiation_sub_mock(),
+            )
+        };
+        let repo = PeerRepository::default();
+        repo.add_peer(new_peer(0, 1, "offerer").start_as_offerer());
+        repo.add_peer(new_peer(2, 3, "answerer").start_as_answerer());
+        repo.add_peer(new_peer(4, 5, "stable"));
+
+        let before = Instant::now() - Duration::from_secs(1);
+        assert!(repo.negotiating_since(before).is_empty());
+
+        let mut stuck =
+            repo.negotiating_since(Instant::now() + Duration::from_millis(1));
+        stuck.sort_by_key(|(_, id)| *id);
+        assert_eq!(
+            stuck,
+            vec![
+                ("offerer".into(), PeerId(0)),
+                ("answerer".into(), PeerId(2))
+            ],
+        );
+    }
 }
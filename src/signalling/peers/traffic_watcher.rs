@@ -11,6 +11,14 @@
 //!    that owns `Peer`, connected to target `Peer`.
 //! 3. [`FlowMetricSource::Coturn`] - Stats reported by Coturn TURN server, this
 //! source is only being tracked if target `Peer` traffic is being relayed.
+//! 4. [`FlowMetricSource::Custom`] - Stats reported by any other source (SFU
+//!    packet counters, for example), registered at runtime as a
+//!    [`FlowMetricSourceProvider`] via
+//!    `PeerTrafficWatcher.register_flow_source()`.
+//!
+//! By default, all the tracked sources should confirm that traffic is flowing.
+//! This can be relaxed for some sources combination with a [`Quorum`] via
+//! `PeerTrafficWatcher.set_quorum()`.
 //!
 //! At first you should register [`Room`] (`PeerTrafficWatcher.register_room()`)
 //! and `Peer` (`PeerTrafficWatcher.register_peer()`). When first source will
@@ -41,7 +49,11 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use medea_client_api_proto::{PeerId, RoomId};
 
-use crate::{conf, log::prelude::*, utils::instant_into_utc};
+use crate::{
+    conf,
+    log::prelude::*,
+    utils::{deterministic, instant_into_utc},
+};
 
 /// Subscriber of `Peer` traffic flowing changes.
 #[cfg_attr(test, mockall::automock)]
@@ -99,6 +111,10 @@ pub fn build_peers_traffic_watcher(
         fn traffic_flows(&self, _: RoomId, _: PeerId, _: FlowMetricSource) {}
 
         fn traffic_stopped(&self, _: RoomId, _: PeerId, _: Instant) {}
+
+        fn register_flow_source(&self, _: Box<dyn FlowMetricSourceProvider>) {}
+
+        fn set_quorum(&self, _: HashSet<FlowMetricSource>, _: Quorum) {}
     }
     Arc::new(DummyPeerTrafficWatcher)
 }
@@ -147,22 +163,108 @@ pub trait PeerTrafficWatcher: Debug + Send + Sync {
     /// Notifies [`PeerTrafficWatcher`] that some `Peer`s traffic flowing was
     /// stopped.
     fn traffic_stopped(&self, room_id: RoomId, peer_id: PeerId, at: Instant);
+
+    /// Registers the provided [`FlowMetricSourceProvider`], so its
+    /// [`FlowMetricSource`] will be tracked for the `Peer`s registered after
+    /// this call.
+    ///
+    /// Replaces the previously registered [`FlowMetricSourceProvider`] of the
+    /// same [`FlowMetricSource`], if any.
+    fn register_flow_source(&self, provider: Box<dyn FlowMetricSourceProvider>);
+
+    /// Sets [`Quorum`] for the `Peer`s tracking exactly the provided
+    /// combination of [`FlowMetricSource`]s.
+    fn set_quorum(&self, sources: HashSet<FlowMetricSource>, quorum: Quorum);
 }
 
-/// Returns [`FlowMetricSource`]s, which will be used to emit `Peer` state
-/// events.
-///
-/// [`FlowMetricSource::Peer`] and [`FlowMetricSource::PartnerPeer`] are
-/// always returned, [`FlowMetricSource::Coturn`] is optional (should be used
-/// only if media is forcibly relayed).
-fn build_flow_sources(should_watch_turn: bool) -> HashSet<FlowMetricSource> {
-    let mut sources =
-        hashset![FlowMetricSource::Peer, FlowMetricSource::PartnerPeer];
-    if should_watch_turn {
-        sources.insert(FlowMetricSource::Coturn);
+/// Provider of a [`FlowMetricSource`], deciding which `Peer`s it should be
+/// tracked for.
+pub trait FlowMetricSourceProvider: Debug + Send {
+    /// Returns [`FlowMetricSource`] provided by this
+    /// [`FlowMetricSourceProvider`].
+    fn source(&self) -> FlowMetricSource;
+
+    /// Indicates whether the [`FlowMetricSource`] should be tracked for a
+    /// `Peer`, which traffic is being relayed if `should_watch_turn` is
+    /// `true`.
+    fn is_tracked(&self, should_watch_turn: bool) -> bool;
+}
+
+/// [`FlowMetricSourceProvider`] tracked for all the `Peer`s.
+#[derive(Clone, Copy, Debug)]
+pub struct TrackedAlways(pub FlowMetricSource);
+
+impl FlowMetricSourceProvider for TrackedAlways {
+    #[inline]
+    fn source(&self) -> FlowMetricSource {
+        self.0
     }
 
-    sources
+    #[inline]
+    fn is_tracked(&self, _: bool) -> bool {
+        true
+    }
+}
+
+/// [`FlowMetricSourceProvider`] tracked only for the `Peer`s which traffic is
+/// being relayed.
+#[derive(Clone, Copy, Debug)]
+pub struct TrackedIfRelayed(pub FlowMetricSource);
+
+impl FlowMetricSourceProvider for TrackedIfRelayed {
+    #[inline]
+    fn source(&self) -> FlowMetricSource {
+        self.0
+    }
+
+    #[inline]
+    fn is_tracked(&self, should_watch_turn: bool) -> bool {
+        should_watch_turn
+    }
+}
+
+/// Rule deciding how many of the tracked [`FlowMetricSource`]s should confirm
+/// that `Peer` traffic is flowing.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Quorum {
+    /// All the tracked [`FlowMetricSource`]s should confirm.
+    All,
+
+    /// At least the provided number of the tracked [`FlowMetricSource`]s
+    /// should confirm.
+    AtLeast(usize),
+}
+
+impl Default for Quorum {
+    #[inline]
+    fn default() -> Self {
+        Self::All
+    }
+}
+
+/// Returns [`Quorum`] of the provided [`FlowMetricSource`]s combination from
+/// the provided list of [`Quorum`]s.
+fn quorum_of(
+    quorums: &[(HashSet<FlowMetricSource>, Quorum)],
+    sources: &HashSet<FlowMetricSource>,
+) -> Quorum {
+    quorums
+        .iter()
+        .find(|(combination, _)| combination == sources)
+        .map(|(_, quorum)| *quorum)
+        .unwrap_or_default()
+}
+
+impl Quorum {
+    /// Indicates whether this [`Quorum`] is reached with the provided number
+    /// of `confirmed` [`FlowMetricSource`]s out of `tracked` ones.
+    #[must_use]
+    fn is_reached(self, confirmed: usize, tracked: usize) -> bool {
+        match self {
+            Self::All => confirmed >= tracked,
+            Self::AtLeast(n) => confirmed >= n.min(tracked),
+        }
+    }
 }
 
 #[async_trait(?Send)]
@@ -188,7 +290,7 @@ impl PeerTrafficWatcher for Addr<PeersTrafficWatcherImpl> {
         self.send(RegisterPeer {
             room_id,
             peer_id,
-            flow_metrics_sources: build_flow_sources(should_watch_turn),
+            should_watch_turn,
         })
         .await
     }
@@ -219,11 +321,22 @@ impl PeerTrafficWatcher for Addr<PeersTrafficWatcherImpl> {
             at,
         })
     }
+
+    fn register_flow_source(
+        &self,
+        provider: Box<dyn FlowMetricSourceProvider>,
+    ) {
+        self.do_send(RegisterFlowSource(provider))
+    }
+
+    fn set_quorum(&self, sources: HashSet<FlowMetricSource>, quorum: Quorum) {
+        self.do_send(SetQuorum { sources, quorum })
+    }
 }
 
 /// Service which analyzes `Peer` traffic metrics and notifies about traffic
 /// flowing changes [`PeerConnectionStateEventsHandler`]s.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct PeersTrafficWatcherImpl {
     /// All `Room`s which exists on the Medea server.
     stats: HashMap<RoomId, RoomStats>,
@@ -237,18 +350,50 @@ pub struct PeersTrafficWatcherImpl {
     /// Duration after which [`PeersTrafficWatcherImpl`] will check that all
     /// tracked traffic sources have reported that traffic is flowing.
     init_timeout: Duration,
+
+    /// Registered [`FlowMetricSourceProvider`]s, deciding which
+    /// [`FlowMetricSource`]s are tracked for a `Peer`.
+    flow_sources: Vec<Box<dyn FlowMetricSourceProvider>>,
+
+    /// [`Quorum`]s of the [`FlowMetricSource`]s combinations.
+    ///
+    /// [`Quorum::All`] is used for the combinations not listed here.
+    quorums: Vec<(HashSet<FlowMetricSource>, Quorum)>,
 }
 
 impl PeersTrafficWatcherImpl {
     /// Returns new [`PeersTrafficWatcherImpl`].
+    ///
+    /// [`FlowMetricSource::Peer`] and [`FlowMetricSource::PartnerPeer`] are
+    /// always tracked, [`FlowMetricSource::Coturn`] is tracked only if media
+    /// is forcibly relayed.
     pub fn new(conf: &conf::Media) -> Self {
         Self {
             stats: HashMap::new(),
             traffic_report_ttl: conf.max_lag,
             init_timeout: conf.init_timeout,
+            flow_sources: vec![
+                Box::new(TrackedAlways(FlowMetricSource::Peer)),
+                Box::new(TrackedAlways(FlowMetricSource::PartnerPeer)),
+                Box::new(TrackedIfRelayed(FlowMetricSource::Coturn)),
+            ],
+            quorums: Vec::new(),
         }
     }
 
+    /// Returns [`FlowMetricSource`]s, which will be used to emit `Peer` state
+    /// events.
+    fn build_flow_sources(
+        &self,
+        should_watch_turn: bool,
+    ) -> HashSet<FlowMetricSource> {
+        self.flow_sources
+            .iter()
+            .filter(|provider| provider.is_tracked(should_watch_turn))
+            .map(|provider| provider.source())
+            .collect()
+    }
+
     /// Checks that all [`FlowMetricSource`] have reported that `Peer` traffic
     /// is flowing for `Peer` in `PeerState::Starting` state.
     ///
@@ -285,8 +430,16 @@ impl Actor for PeersTrafficWatcherImpl {
     /// flowing.
     fn started(&mut self, ctx: &mut Self::Context) {
         ctx.run_interval(Duration::from_secs(1), |this, _| {
-            for room in this.stats.values_mut() {
-                for peer in room.peers.values_mut() {
+            let rooms =
+                deterministic::ordered_values_mut(&mut this.stats, |id| {
+                    id.0.clone()
+                });
+            for room in rooms {
+                let peers =
+                    deterministic::ordered_values_mut(&mut room.peers, |id| {
+                        id.0
+                    });
+                for peer in peers {
                     if peer.state == PeerState::Started && !peer.is_flowing() {
                         peer.stop();
                         room.handler.peer_stopped(
@@ -445,6 +598,10 @@ pub enum FlowMetricSource {
 
     /// Metrics for this `Peer` from the Coturn TURN server.
     Coturn,
+
+    /// Metrics for this `Peer` from a source registered at runtime via a
+    /// [`FlowMetricSourceProvider`] (SFU packet counters, for example).
+    Custom(&'static str),
 }
 
 /// Current state of [`PeerStat`].
@@ -488,6 +645,9 @@ struct PeerStat {
     /// received for validation that traffic is really going.
     tracked_sources: HashSet<FlowMetricSource>,
 
+    /// [`Quorum`] of the [`PeerStat::tracked_sources`].
+    quorum: Quorum,
+
     /// [`DateTime`] when this [`PeerStat`] is started.
     ///
     /// If `None` then [`PeerStat`] not started.
@@ -507,20 +667,22 @@ struct PeerStat {
 impl PeerStat {
     /// Returns `true` if this [`PeerStat`] is considered valid.
     ///
-    /// Checks that all required [`FlowMetricSource`]s reported that traffic is
-    /// flowing within `now() - traffic_flowing_timeout`.
+    /// Checks that [`PeerStat::quorum`] of the tracked [`FlowMetricSource`]s
+    /// reported that traffic is flowing within
+    /// `now() - traffic_flowing_timeout`.
     fn is_flowing(&self) -> bool {
-        for tracked_source in &self.tracked_sources {
-            if let Some(at) = self.received_sources.get(tracked_source) {
-                if at.elapsed() > self.traffic_flowing_timeout {
-                    return false;
-                }
-            } else {
-                return false;
-            }
-        }
+        let confirmed = self
+            .tracked_sources
+            .iter()
+            .filter(|source| {
+                self.received_sources.get(source).map_or(false, |at| {
+                    at.elapsed() <= self.traffic_flowing_timeout
+                })
+            })
+            .count();
 
-        true
+        self.quorum
+            .is_reached(confirmed, self.tracked_sources.len())
     }
 
     /// Sets [`PeerStat`] state to the [`PeerState::Stopped`] and resets
@@ -639,9 +801,9 @@ struct RegisterPeer {
     /// [`PeerId`] of [`PeerStat`] for which subscription is requested.
     peer_id: PeerId,
 
-    /// List of [`FlowMetricSource`]s from which [`TrafficFlows`] should be
-    /// received for validation that traffic is really going.
-    flow_metrics_sources: HashSet<FlowMetricSource>,
+    /// Indicator whether [`FlowMetricSource`]s of relayed traffic should be
+    /// tracked for the `Peer`.
+    should_watch_turn: bool,
 }
 
 impl Handler<RegisterPeer> for PeersTrafficWatcherImpl {
@@ -652,14 +814,17 @@ impl Handler<RegisterPeer> for PeersTrafficWatcherImpl {
         msg: RegisterPeer,
         _: &mut Self::Context,
     ) -> Self::Result {
+        let flow_metrics_sources =
+            self.build_flow_sources(msg.should_watch_turn);
         if let Some(room) = self.stats.get_mut(&msg.room_id) {
             if let Some(peer) = room.peers.get_mut(&msg.peer_id) {
-                peer.tracked_sources.extend(msg.flow_metrics_sources);
+                peer.tracked_sources.extend(flow_metrics_sources);
+                peer.quorum = quorum_of(&self.quorums, &peer.tracked_sources);
             } else {
                 debug!(
                     "Peer [id = {}] from a Room [id = {}] was registered in \
                      the PeersTrafficWatcher with {:?} sources.",
-                    msg.peer_id, msg.room_id, msg.flow_metrics_sources
+                    msg.peer_id, msg.room_id, flow_metrics_sources
                 );
                 room.peers.insert(
                     msg.peer_id,
@@ -667,7 +832,8 @@ impl Handler<RegisterPeer> for PeersTrafficWatcherImpl {
                         peer_id: msg.peer_id,
                         state: PeerState::New,
                         init_task_handler: None,
-                        tracked_sources: msg.flow_metrics_sources,
+                        quorum: quorum_of(&self.quorums, &flow_metrics_sources),
+                        tracked_sources: flow_metrics_sources,
                         started_at: None,
                         received_sources: HashMap::new(),
                         traffic_flowing_timeout: self.traffic_report_ttl,
@@ -720,6 +886,66 @@ impl Handler<UnregisterPeers> for PeersTrafficWatcherImpl {
     }
 }
 
+/// Registers the provided [`FlowMetricSourceProvider`] in the
+/// [`PeersTrafficWatcherImpl`].
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+struct RegisterFlowSource(Box<dyn FlowMetricSourceProvider>);
+
+impl Handler<RegisterFlowSource> for PeersTrafficWatcherImpl {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: RegisterFlowSource,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let source = msg.0.source();
+        debug!(
+            "{:?} source was registered in the PeersTrafficWatcher.",
+            source,
+        );
+        self.flow_sources
+            .retain(|provider| provider.source() != source);
+        self.flow_sources.push(msg.0);
+    }
+}
+
+/// Sets [`Quorum`] for the provided combination of [`FlowMetricSource`]s.
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+struct SetQuorum {
+    /// Combination of [`FlowMetricSource`]s the [`Quorum`] is set for.
+    sources: HashSet<FlowMetricSource>,
+
+    /// [`Quorum`] to be set.
+    quorum: Quorum,
+}
+
+impl Handler<SetQuorum> for PeersTrafficWatcherImpl {
+    type Result = ();
+
+    /// Saves the provided [`Quorum`] and applies it to all the already
+    /// registered [`PeerStat`]s tracking the provided combination of
+    /// [`FlowMetricSource`]s.
+    fn handle(
+        &mut self,
+        msg: SetQuorum,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        for room in self.stats.values_mut() {
+            for peer in room.peers.values_mut() {
+                if peer.tracked_sources == msg.sources {
+                    peer.quorum = msg.quorum;
+                }
+            }
+        }
+        self.quorums
+            .retain(|(combination, _)| combination != &msg.sources);
+        self.quorums.push((msg.sources, msg.quorum));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use futures::{channel::mpsc, stream::LocalBoxStream, StreamExt};
@@ -981,6 +1207,83 @@ mod tests {
             .unwrap();
     }
 
+    /// Checks that `Peer` is considered flowing once its [`Quorum`] is
+    /// reached.
+    #[actix_rt::test]
+    async fn quorum_allows_partial_confirmation() {
+        let mut helper = Helper::new(&conf::Media {
+            init_timeout: Duration::from_millis(30),
+            max_lag: Duration::from_secs(999),
+        })
+        .await;
+        helper.watcher().set_quorum(
+            hashset![FlowMetricSource::Peer, FlowMetricSource::PartnerPeer],
+            Quorum::AtLeast(1),
+        );
+        helper
+            .watcher()
+            .register_peer(Helper::room_id(), PeerId(1), false)
+            .await
+            .unwrap();
+        helper.watcher().traffic_flows(
+            Helper::room_id(),
+            PeerId(1),
+            FlowMetricSource::Peer,
+        );
+
+        timeout(Duration::from_millis(50), helper.next_peer_started())
+            .await
+            .unwrap();
+        timeout(Duration::from_millis(50), helper.next_peer_stopped())
+            .await
+            .unwrap_err();
+    }
+
+    /// Checks that [`FlowMetricSource`] registered at runtime is tracked for
+    /// the `Peer`s registered afterwards.
+    #[actix_rt::test]
+    async fn registered_flow_source_is_tracked() {
+        let sfu = FlowMetricSource::Custom("sfu");
+        let mut helper = Helper::new(&conf::Media {
+            init_timeout: Duration::from_millis(30),
+            max_lag: Duration::from_secs(999),
+        })
+        .await;
+        helper
+            .watcher()
+            .register_flow_source(Box::new(TrackedAlways(sfu)));
+        for peer_id in &[PeerId(1), PeerId(2)] {
+            helper
+                .watcher()
+                .register_peer(Helper::room_id(), *peer_id, false)
+                .await
+                .unwrap();
+            for source in
+                &[FlowMetricSource::Peer, FlowMetricSource::PartnerPeer]
+            {
+                helper.watcher().traffic_flows(
+                    Helper::room_id(),
+                    *peer_id,
+                    *source,
+                );
+            }
+        }
+        helper
+            .watcher()
+            .traffic_flows(Helper::room_id(), PeerId(2), sfu);
+
+        assert_eq!(helper.next_peer_started().await, PeerId(1));
+        assert_eq!(helper.next_peer_started().await, PeerId(2));
+        let (stopped, _) =
+            timeout(Duration::from_millis(50), helper.next_peer_stopped())
+                .await
+                .unwrap();
+        assert_eq!(stopped, PeerId(1));
+        timeout(Duration::from_millis(50), helper.next_peer_stopped())
+            .await
+            .unwrap_err();
+    }
+
     #[actix_rt::test]
     async fn peer_stop_when_traffic_stop() {
         {
@@ -0,0 +1,99 @@
+//! Chaos mode of [`Room`]s, postponing processing of specific kinds of
+//! messages, so they're reordered with the other ones.
+//!
+//! Intended for hunting race conditions in tests only.
+
+use std::{
+    collections::HashMap,
+    mem,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use actix::{AsyncContext as _, Handler, Message};
+
+use super::Room;
+
+/// Kind of the messages processed by a [`Room`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MessageKind {
+    /// [`Command`]s received from `Member`s via Client API.
+    ///
+    /// [`Command`]: medea_client_api_proto::Command
+    Command,
+
+    /// Mutations requested via Control API, which don't await their results
+    /// (`Member`s and `Endpoint`s deletion).
+    Control,
+}
+
+/// Shared switch of the chaos mode, holding delays of processing each
+/// [`MessageKind`].
+///
+/// Clones share the same delays, so changing them affects all the [`Room`]s
+/// the [`Chaos`] was provided to.
+#[derive(Clone, Debug, Default)]
+pub struct Chaos(Arc<Mutex<HashMap<MessageKind, Duration>>>);
+
+impl Chaos {
+    /// Postpones processing of all the messages of the provided
+    /// [`MessageKind`] for the provided [`Duration`].
+    #[inline]
+    pub fn delay(&self, kind: MessageKind, delay: Duration) {
+        self.0.lock().unwrap().insert(kind, delay);
+    }
+
+    /// Stops postponing processing of any messages.
+    #[inline]
+    pub fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+
+    /// Postpones processing of the provided message by the provided [`Room`],
+    /// if the chaos mode is switched on for its [`MessageKind`].
+    ///
+    /// Returns the provided message back, if it should be processed right
+    /// away.
+    pub(super) fn postpone<M>(
+        &self,
+        kind: MessageKind,
+        msg: M,
+        ctx: &mut <Room as actix::Actor>::Context,
+    ) -> Option<M>
+    where
+        M: Message + 'static,
+        Room: Handler<M>,
+    {
+        let delay = self.0.lock().unwrap().get(&kind).copied();
+        if let Some(delay) = delay {
+            ctx.notify_later(Postponed(msg), delay);
+            None
+        } else {
+            Some(msg)
+        }
+    }
+}
+
+/// Message postponed by the [`Chaos`], which must be processed regardless of
+/// the chaos mode once received.
+#[derive(Message)]
+#[rtype(result = "()")]
+struct Postponed<M>(M);
+
+impl<M> Handler<Postponed<M>> for Room
+where
+    M: Message + 'static,
+    Room: Handler<M>,
+{
+    type Result = ();
+
+    /// Processes the postponed message, ignoring its result.
+    ///
+    /// [`Chaos`] of the [`Room`] is detached for the processing time, so the
+    /// message is not postponed again.
+    fn handle(&mut self, msg: Postponed<M>, ctx: &mut Self::Context) {
+        let chaos = mem::take(&mut self.chaos);
+        drop(<Self as Handler<M>>::handle(self, msg.0, ctx));
+        self.chaos = chaos;
+    }
+}
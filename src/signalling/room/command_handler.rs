@@ -6,21 +6,115 @@ use std::{collections::HashMap, convert::TryInto as _};
 use medea_client_api_proto as proto;
 use medea_client_api_proto::{
     CommandHandler, Credential, Event, IceCandidate, MemberId, NegotiationRole,
-    PeerId, PeerMetrics, TrackId, TrackPatchCommand,
+    PeerConnectionState, PeerId, PeerMetrics, TrackId, TrackPatchCommand,
 };
 
 use crate::{
     log::prelude::*,
-    media::{Peer, PeerStateMachine, WaitLocalSdp, WaitRemoteSdp},
+    media::{
+        peer::SelectedCandidatePair, sdp_munging::apply_opus_parameters, Peer,
+        PeerStateMachine, WaitLocalSdp, WaitRemoteSdp,
+    },
 };
 
 use super::{Room, RoomError};
 
+impl Room {
+    /// Checks the provided `sdp` of the [`Peer`] with the provided [`PeerId`]
+    /// against [`SdpQuirks`] of the provided [`Member`]'s user agent, logging
+    /// any unexpected patterns found.
+    ///
+    /// [`Member`]: crate::signalling::elements::Member
+    /// [`SdpQuirks`]: crate::media::SdpQuirks
+    fn check_sdp_dialect(
+        &self,
+        member_id: &MemberId,
+        peer_id: PeerId,
+        sdp: &str,
+    ) {
+        let quirks = self.members.sdp_quirks(member_id);
+        let unexpected = quirks.unexpected_patterns(sdp);
+        if !unexpected.is_empty() {
+            warn!(
+                "Member [id = {}] provided SDP of Peer [id = {}] with patterns \
+                 unknown for {} SDP dialect: {}",
+                member_id,
+                peer_id,
+                quirks,
+                unexpected
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            );
+        }
+    }
+
+    /// Applies the provided [`TrackPatchCommand`]s to the tracks of the
+    /// [`Peer`] with the provided [`PeerId`] and its partner [`Peer`].
+    ///
+    /// # Errors
+    ///
+    /// With [`RoomError::PeerNotFound`] if any of these [`Peer`]s doesn't
+    /// exist.
+    pub(super) fn patch_peer_tracks(
+        &mut self,
+        peer_id: PeerId,
+        tracks_patches: Vec<TrackPatchCommand>,
+    ) -> Result<(), RoomError> {
+        // Note, that we force committing changes to the `Member` owning the
+        // patched `Peer`, so it will be notified immediately, regardless of
+        // that `Peer` state, but non-forcibly committing changes to partner
+        // `Peer`, so it will be notified of changes only during next
+        // negotiation.
+        let partner_peer_id =
+            self.peers.map_peer_by_id_mut(peer_id, |peer| {
+                peer.as_changes_scheduler()
+                    .patch_tracks(tracks_patches.clone());
+                peer.force_commit_scheduled_changes();
+                peer.partner_peer_id()
+            })?;
+        self.peers.map_peer_by_id_mut(partner_peer_id, |peer| {
+            peer.as_changes_scheduler()
+                .partner_patch_tracks(tracks_patches);
+            if !peer.commit_scheduled_changes()
+                && peer.can_forcibly_commit_partner_patches()
+            {
+                peer.force_commit_partner_changes();
+            }
+        })?;
+
+        Ok(())
+    }
+
+    /// Applies the merged [`TrackPatchCommand`]s of the provided `Member`,
+    /// which were postponed due to its exceeded `UpdateTracks` rate limit.
+    ///
+    /// Skips the [`Peer`]s removed since the [`TrackPatchCommand`]s were
+    /// postponed.
+    pub(super) fn flush_track_patches(&mut self, member_id: &MemberId) {
+        for (peer_id, patches) in self.track_patches.flush(member_id) {
+            if let Err(e) = self.patch_peer_tracks(peer_id, patches) {
+                debug!(
+                    "Dropping postponed tracks patches of Member [id = {}] \
+                     for Peer [id = {}]: {}",
+                    member_id, peer_id, e,
+                );
+            }
+        }
+    }
+}
+
 impl CommandHandler for Room {
     type Output = Result<(), RoomError>;
 
     #[inline]
-    fn on_join_room(&mut self, _: MemberId, _: Credential) -> Self::Output {
+    fn on_join_room(
+        &mut self,
+        _: MemberId,
+        _: Credential,
+        _: Option<String>,
+    ) -> Self::Output {
         unreachable!("Room can't receive Command::JoinRoom")
     }
 
@@ -34,7 +128,20 @@ impl CommandHandler for Room {
     /// [`WaitRemoteSdp`], partners [`Peer`] state must be [`Stable`] and will
     /// be changed to [`WaitLocalSdp`].
     ///
+    /// Provided [mid]s are validated against [`Peer`]'s tracks. SDP Offer is
+    /// rejected if they're inconsistent with the already negotiated ones, as
+    /// the partner's tracks [mid]s wouldn't match the relayed SDP Offer.
+    ///
+    /// Provided SDP Offer is checked against the dialect of the `Member`'s user
+    /// agent, and its unexpected patterns are logged. SDP Offer sent to the
+    /// partner has `OpusParameters` of the partner's audio tracks applied.
+    ///
+    /// If the SDP Offer restarts ICE, then pure ICE restarts scheduled for the
+    /// partner [`Peer`] are discarded, as answering it restarts ICE on the
+    /// partner's side too.
+    ///
     /// [`Stable`]: crate::media::peer::Stable
+    /// [mid]: https://w3.org/TR/webrtc#dom-rtptransceiver-mid
     fn on_make_sdp_offer(
         &mut self,
         from_peer_id: PeerId,
@@ -44,14 +151,35 @@ impl CommandHandler for Room {
     ) -> Self::Output {
         let mut from_peer: Peer<WaitLocalSdp> =
             self.peers.take_inner_peer(from_peer_id)?;
-        let to_peer: Peer<WaitRemoteSdp> =
+        let mut to_peer: Peer<WaitRemoteSdp> =
             self.peers.take_inner_peer(from_peer.partner_peer_id())?;
+        self.check_sdp_dialect(from_peer.member_id(), from_peer_id, &sdp_offer);
 
-        from_peer.set_mids(mids)?;
+        if let Err(e) = from_peer.set_mids(mids) {
+            warn!(
+                "Member [id = {}] provided mids of Peer [id = {}] which can't \
+                 be applied with {} SDP dialect: {}",
+                from_peer.member_id(),
+                from_peer_id,
+                self.members.sdp_quirks(from_peer.member_id()),
+                e,
+            );
+            self.peers.add_peer(from_peer);
+            self.peers.add_peer(to_peer);
+            return Err(e.into());
+        }
         from_peer.update_senders_statuses(senders_statuses);
+        from_peer.update_senders_ssrcs(&sdp_offer);
+        if from_peer.is_ice_restart() {
+            to_peer.on_partner_ice_restart();
+        }
 
+        let remote_offer = apply_opus_parameters(
+            &sdp_offer,
+            &to_peer.senders_opus_parameters(),
+        );
         let from_peer = from_peer.set_local_offer(sdp_offer.clone());
-        let to_peer = to_peer.set_remote_offer(sdp_offer.clone());
+        let to_peer = to_peer.set_remote_offer(remote_offer.clone());
 
         let from_member_id = from_peer.member_id();
         let to_member_id = to_peer.member_id();
@@ -63,15 +191,13 @@ impl CommandHandler for Room {
         let event = if from_peer.is_known_to_remote() {
             Event::PeerUpdated {
                 peer_id: to_peer.id(),
-                negotiation_role: Some(NegotiationRole::Answerer(
-                    sdp_offer.clone(),
-                )),
+                negotiation_role: Some(NegotiationRole::Answerer(remote_offer)),
                 updates: to_peer.get_updates(),
             }
         } else {
             Event::PeerCreated {
                 peer_id: to_peer.id(),
-                negotiation_role: NegotiationRole::Answerer(sdp_offer.clone()),
+                negotiation_role: NegotiationRole::Answerer(remote_offer),
                 tracks: to_peer.new_tracks(),
                 ice_servers,
                 force_relay: to_peer.is_force_relayed(),
@@ -89,6 +215,7 @@ impl CommandHandler for Room {
 
         self.peers.add_peer(from_peer);
         self.peers.add_peer(to_peer);
+
         self.peers.sync_peer_spec(from_peer_id)
     }
 
@@ -97,6 +224,14 @@ impl CommandHandler for Room {
     /// [`Stable`], partners [`Peer`] state must be [`WaitRemoteSdp`] and will
     /// be changed to [`Stable`].
     ///
+    /// SDP Answer sent to the partner has `OpusParameters` of the partner's
+    /// audio tracks applied.
+    ///
+    /// Settles the `PatchTrack` operations awaiting the negotiated [`Peer`]s.
+    ///
+    /// Starts the negotiations postponed due to the limit of simultaneous
+    /// negotiations in the [`Room`], if any.
+    ///
     /// [`Stable`]: crate::media::peer::Stable
     fn on_make_sdp_answer(
         &mut self,
@@ -108,17 +243,27 @@ impl CommandHandler for Room {
             self.peers.take_inner_peer(from_peer_id)?;
         let to_peer: Peer<WaitRemoteSdp> =
             self.peers.take_inner_peer(from_peer.partner_peer_id())?;
+        self.check_sdp_dialect(
+            from_peer.member_id(),
+            from_peer_id,
+            &sdp_answer,
+        );
 
         from_peer.update_senders_statuses(senders_statuses);
+        from_peer.update_senders_ssrcs(&sdp_answer);
 
+        let remote_answer = apply_opus_parameters(
+            &sdp_answer,
+            &to_peer.senders_opus_parameters(),
+        );
         let from_peer = from_peer.set_local_answer(sdp_answer.clone());
-        let to_peer = to_peer.set_remote_answer(sdp_answer.clone());
+        let to_peer = to_peer.set_remote_answer(remote_answer.clone());
 
         let from_member_id = from_peer.member_id();
         let to_member_id = to_peer.member_id();
         let event = Event::SdpAnswerMade {
             peer_id: to_peer.id(),
-            sdp_answer: sdp_answer.clone(),
+            sdp_answer: remote_answer,
         };
 
         self.members.send_event_to_member(
@@ -130,8 +275,11 @@ impl CommandHandler for Room {
         );
         self.members.send_event_to_member(to_member_id, event);
 
+        let to_peer_id = to_peer.id();
         self.peers.add_peer(from_peer);
         self.peers.add_peer(to_peer);
+        self.settle_patch_operations(&[from_peer_id, to_peer_id]);
+        self.start_postponed_negotiations();
         self.peers.sync_peer_spec(from_peer_id)
     }
 
@@ -171,6 +319,12 @@ impl CommandHandler for Room {
     /// Passes [`PeerMetrics::RtcStats`] to [`PeersService`] for the further
     /// analysis.
     ///
+    /// Records the first [`PeerConnectionState::Connected`] of the [`Peer`]
+    /// into the [`Room`]'s journal.
+    ///
+    /// Stores [`PeerMetrics::SelectedCandidatePairChanged`] in the [`Peer`]
+    /// and updates its relaying in the connection quality estimation.
+    ///
     /// [`PeersService`]: crate::signalling::peers::PeersService
     fn on_add_peer_connection_metrics(
         &mut self,
@@ -183,10 +337,36 @@ impl CommandHandler for Room {
             }
             PeerMetrics::PeerConnectionState(state) => {
                 self.peers.update_peer_connection_state(peer_id, state);
+                if state == PeerConnectionState::Connected {
+                    self.record_peer_connected(peer_id)?;
+                }
             }
             PeerMetrics::IceConnectionState(state) => {
-                self.peers
-                    .update_peer_connection_state(peer_id, state.into());
+                let state = PeerConnectionState::from(state);
+                self.peers.update_peer_connection_state(peer_id, state);
+                if state == PeerConnectionState::Connected {
+                    self.record_peer_connected(peer_id)?;
+                }
+            }
+            PeerMetrics::SelectedCandidatePairChanged {
+                local_type,
+                remote_type,
+                relay_protocol,
+            } => {
+                let pair = SelectedCandidatePair {
+                    local_type,
+                    remote_type,
+                    relay_protocol,
+                };
+                info!(
+                    "Peer [id = {}] in Room [id = {}] switched to ICE \
+                     candidate pair: {:?}",
+                    peer_id, self.id, pair,
+                );
+                self.peers.update_peer_relayed(peer_id, pair.is_relayed());
+                self.peers.map_peer_by_id_mut(peer_id, |peer| {
+                    peer.set_selected_candidate_pair(pair);
+                })?;
             }
         }
         Ok(())
@@ -203,32 +383,42 @@ impl CommandHandler for Room {
         peer_id: PeerId,
         tracks_patches: Vec<TrackPatchCommand>,
     ) -> Self::Output {
-        // Note, that we force committing changes to `Member` that send
-        // `UpdateTracks` request, so response will be sent immediately,
-        // regardless of that `Peer` state, but non-forcibly committing
-        // changes to partner `Peer`, so it will be notified of changes only
-        // during next negotiation.
-        let partner_peer_id =
-            self.peers.map_peer_by_id_mut(peer_id, |peer| {
-                peer.as_changes_scheduler()
-                    .patch_tracks(tracks_patches.clone());
-                peer.force_commit_scheduled_changes();
-                peer.partner_peer_id()
-            })?;
-        self.peers.map_peer_by_id_mut(partner_peer_id, |peer| {
-            peer.as_changes_scheduler()
-                .partner_patch_tracks(tracks_patches);
-            if !peer.commit_scheduled_changes()
-                && peer.can_forcibly_commit_partner_patches()
-            {
-                peer.force_commit_partner_changes();
-            }
-        })?;
+        self.patch_peer_tracks(peer_id, tracks_patches)
+    }
 
+    /// Marks the [`Peer`]'s removed tracks as released by the remote side.
+    fn on_tracks_removed_ack(
+        &mut self,
+        peer_id: PeerId,
+        track_ids: Vec<TrackId>,
+    ) -> Self::Output {
+        self.peers.map_peer_by_id_mut(peer_id, |peer| {
+            peer.ack_removed_tracks(&track_ids);
+        })?;
         Ok(())
     }
 
-    fn on_synchronize_me(&mut self, _: proto::state::Room) -> Self::Output {
+    fn on_synchronize_me(
+        &mut self,
+        _: proto::state::Room,
+        _: Option<u64>,
+    ) -> Self::Output {
         unreachable!("Room can't receive Command::SynchronizeMe")
     }
+
+    #[inline]
+    fn on_send_app_data(
+        &mut self,
+        _: Option<MemberId>,
+        _: String,
+    ) -> Self::Output {
+        unreachable!("Room relays Command::SendAppData without dispatching")
+    }
+
+    #[inline]
+    fn on_refresh_credentials(&mut self, _: Credential) -> Self::Output {
+        unreachable!(
+            "Room handles Command::RefreshCredentials without dispatching"
+        )
+    }
 }
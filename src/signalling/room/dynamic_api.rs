@@ -6,37 +6,51 @@
 use std::{
     collections::{HashMap, HashSet},
     convert::TryFrom as _,
+    mem,
 };
 
 use actix::{
     ActorFutureExt as _, ActorTryFutureExt as _, AsyncContext, AtomicResponse,
-    Context, Handler, Message, WrapFuture as _,
+    Context, Handler, Message, MessageResult, WrapFuture as _,
+};
+use medea_client_api_proto::{
+    CloseReason, MediaType, MemberId, PeerId, TrackId, TrackPatchCommand,
 };
-use medea_client_api_proto::{CloseReason, MemberId};
 use medea_control_api_proto::grpc::api as proto;
 
 use crate::{
     api::control::{
-        callback::OnLeaveReason,
+        callback::{CallbackUrl, OnLeaveReason},
         endpoints::{
             WebRtcPlayEndpoint as WebRtcPlayEndpointSpec,
             WebRtcPublishEndpoint as WebRtcPublishEndpointSpec,
         },
-        refs::StatefulFid,
+        refs::{Fid, StatefulFid, ToEndpoint},
         EndpointId, EndpointSpec, MemberSpec, RoomSpec, WebRtcPlayId,
         WebRtcPublishId,
     },
     log::prelude::*,
     signalling::{
         elements::{
-            endpoints::webrtc::{WebRtcPlayEndpoint, WebRtcPublishEndpoint},
-            member::MemberError,
+            endpoints::{
+                webrtc::{WebRtcPlayEndpoint, WebRtcPublishEndpoint},
+                Endpoint,
+            },
+            member::{Member, MemberError},
         },
+        participants::ParticipantServiceErr,
         peers::PeerChange,
     },
+    utils::generate_token,
 };
 
-use super::{Room, RoomError};
+#[cfg(any(test, feature = "testing"))]
+use super::MessageKind;
+use super::{
+    journal::{JournalEvent, JournalEventKind},
+    patch_operations::PatchOperation,
+    Room, RoomError,
+};
 
 impl Room {
     /// Deletes [`Member`] from this [`Room`] by [`MemberId`].
@@ -79,6 +93,11 @@ impl Room {
             let play_id = endpoint_id.into();
             let changeset = if let Some(sink) = member.remove_sink(&play_id) {
                 self.peers.delete_sink_endpoint(&sink)
+            } else if let Some(sinks) = member.remove_wildcard_sink(&play_id) {
+                sinks
+                    .iter()
+                    .flat_map(|sink| self.peers.delete_sink_endpoint(sink))
+                    .collect()
             } else if let Some(src) =
                 member.remove_src(&String::from(play_id).into())
             {
@@ -112,9 +131,40 @@ impl Room {
             for (member_id, peer_ids) in removed_peers {
                 self.send_peers_removed(&member_id, peer_ids);
             }
+            self.start_postponed_negotiations();
         }
     }
 
+    /// Switches forcible relaying of the provided [`WebRtcPublishEndpoint`],
+    /// updating ICE transport policy of all its [`Peer`]s and restarting ICE
+    /// on them, so media isn't interrupted.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::PeerNotFound`] if some [`Peer`] of the
+    /// provided [`WebRtcPublishEndpoint`] doesn't exist.
+    fn update_src_force_relay(
+        &self,
+        src: &WebRtcPublishEndpoint,
+        force_relay: bool,
+    ) -> Result<(), RoomError> {
+        debug!(
+            "Switching force_relay of WebRtcPublishEndpoint [id = {}] to {} \
+             in Room [id = {}].",
+            src.id(),
+            force_relay,
+            self.id,
+        );
+        src.set_force_relayed(force_relay);
+        for peer_id in src.peer_ids() {
+            self.peers.map_peer_by_id_mut(peer_id, |peer| {
+                peer.as_changes_scheduler().update_ice_policy(force_relay);
+            })?;
+            self.peers.commit_scheduled_changes(peer_id)?;
+        }
+        Ok(())
+    }
+
     /// Creates new [`WebRtcPlayEndpoint`] in specified [`Member`].
     ///
     /// This function will check that new [`WebRtcPublishEndpoint`]'s ID is not
@@ -132,6 +182,7 @@ impl Room {
     /// [1]: crate::signalling::participants::ParticipantService
     fn create_src_endpoint(
         &mut self,
+        ctx: &mut Context<Self>,
         member_id: &MemberId,
         publish_id: WebRtcPublishId,
         spec: &WebRtcPublishEndpointSpec,
@@ -143,7 +194,8 @@ impl Room {
 
         let play_id = String::from(publish_id).into();
         let is_member_have_this_sink_id =
-            member.get_sink_by_id(&play_id).is_some();
+            member.get_sink_by_id(&play_id).is_some()
+                || member.get_wildcard_sink_by_id(&play_id).is_some();
 
         if is_member_have_this_sink_id || is_member_have_this_src_id {
             return Err(RoomError::EndpointAlreadyExists(
@@ -159,6 +211,7 @@ impl Room {
             spec.audio_settings,
             spec.video_settings,
         );
+        endpoint.set_callback_urls(spec.on_start, spec.on_stop);
 
         debug!(
             "Create WebRtcPublishEndpoint [id = {}] for Member [id = {}] in \
@@ -168,7 +221,30 @@ impl Room {
             self.id
         );
 
-        member.insert_src(endpoint);
+        self.journal.record(
+            JournalEventKind::EndpointCreated,
+            member.get_fid_to_endpoint(endpoint.id().into()).into(),
+            None,
+        );
+        member.insert_src(endpoint.clone());
+
+        let expanded = self.members.expand_wildcard_sinks(&endpoint);
+        for sink in &expanded {
+            debug!(
+                "Expanded wildcard WebRtcPlayEndpoint [id = {}] of Member \
+                 [id = {}] onto WebRtcPublishEndpoint [id = {}] of Member \
+                 [id = {}] in Room [id = {}]",
+                sink.id(),
+                sink.owner().id(),
+                endpoint.id(),
+                member_id,
+                self.id,
+            );
+        }
+        if !expanded.is_empty() && self.members.member_has_connection(member_id)
+        {
+            self.spawn_member_connections(ctx, &member);
+        }
 
         Ok(())
     }
@@ -198,7 +274,8 @@ impl Room {
         let member = self.members.get_member(&member_id)?;
 
         let is_member_have_this_sink_id =
-            member.get_sink_by_id(&endpoint_id).is_some();
+            member.get_sink_by_id(&endpoint_id).is_some()
+                || member.get_wildcard_sink_by_id(&endpoint_id).is_some();
 
         let publish_id = String::from(endpoint_id).into();
         let is_member_have_this_src_id =
@@ -209,58 +286,117 @@ impl Room {
             ));
         }
 
-        let partner_member = self.members.get_member(&spec.src.member_id)?;
-        let src = partner_member
-            .get_src_by_id(&spec.src.endpoint_id)
-            .ok_or_else(|| {
-                MemberError::EndpointNotFound(
-                    partner_member.get_fid_to_endpoint(
-                        spec.src.endpoint_id.clone().into(),
-                    ),
-                )
-            })?;
+        if spec.src.is_wildcard() {
+            self.create_wildcard_sink_endpoint(
+                &member,
+                String::from(publish_id).into(),
+                spec,
+            );
+        } else {
+            let partner_member =
+                self.members.get_member(&spec.src.member_id)?;
+            let src = partner_member
+                .get_src_by_id(&spec.src.endpoint_id)
+                .ok_or_else(|| {
+                    MemberError::EndpointNotFound(
+                        partner_member.get_fid_to_endpoint(
+                            spec.src.endpoint_id.clone().into(),
+                        ),
+                    )
+                })?;
 
-        let sink = WebRtcPlayEndpoint::new(
-            String::from(publish_id).into(),
-            spec.src,
-            src.downgrade(),
-            member.downgrade(),
-            spec.force_relay,
-        );
+            let sink = WebRtcPlayEndpoint::new(
+                String::from(publish_id).into(),
+                spec.src,
+                src.downgrade(),
+                member.downgrade(),
+                spec.force_relay,
+            );
+            sink.set_callback_urls(spec.on_start, spec.on_stop);
 
-        src.add_sink(sink.downgrade());
+            src.add_sink(sink.downgrade());
 
-        debug!(
-            "Created WebRtcPlayEndpoint [id = {}] for Member [id = {}] in \
-             Room [id = {}].",
-            sink.id(),
-            member_id,
-            self.id
-        );
+            debug!(
+                "Created WebRtcPlayEndpoint [id = {}] for Member [id = {}] in \
+                 Room [id = {}].",
+                sink.id(),
+                member_id,
+                self.id
+            );
 
-        member.insert_sink(sink);
+            self.journal.record(
+                JournalEventKind::EndpointCreated,
+                member.get_fid_to_endpoint(sink.id().into()).into(),
+                None,
+            );
+            member.insert_sink(sink);
+        }
 
         if self.members.member_has_connection(&member_id) {
-            ctx.spawn(
-                self.init_member_connections(&member)
-                    .map_err(move |err, this, ctx| {
-                        error!(
-                            "Failed to interconnect Members, because {}",
-                            err,
-                        );
-                        this.disconnect_member(
-                            &member_id,
-                            CloseReason::InternalError,
-                            Some(OnLeaveReason::Kicked),
-                            ctx,
-                        );
-                    })
-                    .map(|_, _, _| ()),
-            );
+            self.spawn_member_connections(ctx, &member);
         }
 
         Ok(())
     }
+
+    /// Creates new wildcard [`WebRtcPlayEndpoint`] in the provided [`Member`],
+    /// expanding it onto all the matching [`WebRtcPublishEndpoint`]s of the
+    /// other [`Member`]s.
+    fn create_wildcard_sink_endpoint(
+        &mut self,
+        member: &Member,
+        endpoint_id: WebRtcPlayId,
+        spec: WebRtcPlayEndpointSpec,
+    ) {
+        debug!(
+            "Created wildcard WebRtcPlayEndpoint [id = {}] for Member \
+             [id = {}] in Room [id = {}].",
+            endpoint_id,
+            member.id(),
+            self.id
+        );
+        self.journal.record(
+            JournalEventKind::EndpointCreated,
+            member
+                .get_fid_to_endpoint(endpoint_id.clone().into())
+                .into(),
+            None,
+        );
+        member.insert_wildcard_sink(endpoint_id, spec);
+
+        for (_, publisher) in self.members.iter_members() {
+            for src in publisher.srcs().values() {
+                member.expand_wildcard_sinks(src);
+            }
+        }
+    }
+
+    /// Spawns creation and interconnection of all the [`Peer`]s between the
+    /// provided connected [`Member`] and the other available [`Member`]s.
+    ///
+    /// Disconnects the provided [`Member`] if it fails.
+    ///
+    /// [`Peer`]: crate::media::peer::Peer
+    fn spawn_member_connections(
+        &mut self,
+        ctx: &mut Context<Self>,
+        member: &Member,
+    ) {
+        let member_id = member.id();
+        ctx.spawn(
+            self.init_member_connections(member)
+                .map_err(move |err, this, ctx| {
+                    error!("Failed to interconnect Members, because {}", err);
+                    this.disconnect_member(
+                        &member_id,
+                        CloseReason::InternalError,
+                        Some(OnLeaveReason::Kicked),
+                        ctx,
+                    );
+                })
+                .map(|_, _, _| ()),
+        );
+    }
 }
 
 impl From<&Room> for proto::Room {
@@ -343,6 +479,295 @@ impl Handler<SerializeProto> for Room {
     }
 }
 
+/// Message for retrieving events of this [`Room`]'s journal, which occurred
+/// after the provided sequence number, serialized to protobuf.
+#[derive(Message, Debug)]
+#[rtype(result = "proto::RoomJournalResponse")]
+pub struct GetJournal(pub u64);
+
+impl Handler<GetJournal> for Room {
+    type Result = MessageResult<GetJournal>;
+
+    fn handle(
+        &mut self,
+        msg: GetJournal,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        MessageResult(proto::RoomJournalResponse {
+            events: self.journal.since(msg.0).map(Into::into).collect(),
+            oldest_seq: self.journal.oldest_seq(),
+            error: None,
+        })
+    }
+}
+
+/// Message for retrieving recent connection quality scores of this [`Room`]'s
+/// `Member` with each of its partners, serialized to protobuf.
+#[derive(Message, Debug)]
+#[rtype(result = "Result<proto::QualityHistoryResponse, RoomError>")]
+pub struct GetQualityHistory(pub MemberId);
+
+impl Handler<GetQualityHistory> for Room {
+    type Result = Result<proto::QualityHistoryResponse, RoomError>;
+
+    fn handle(
+        &mut self,
+        msg: GetQualityHistory,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        self.members.get_member_by_id(&msg.0)?;
+
+        let mut histories: Vec<_> = self
+            .peers
+            .quality_history(&msg.0)
+            .into_iter()
+            .map(|(partner_id, samples)| proto::QualityHistory {
+                partner_member_id: partner_id.to_string(),
+                samples: samples.iter().map(Into::into).collect(),
+            })
+            .collect();
+        histories.sort_by(|a, b| a.partner_member_id.cmp(&b.partner_member_id));
+
+        Ok(proto::QualityHistoryResponse {
+            histories,
+            error: None,
+        })
+    }
+}
+
+/// Message for retrieving capabilities reported by the `Client` of this
+/// [`Room`]'s `Member`, serialized to protobuf.
+#[derive(Message, Debug)]
+#[rtype(result = "Result<proto::ClientCapabilitiesResponse, RoomError>")]
+pub struct GetClientCapabilities(pub MemberId);
+
+impl Handler<GetClientCapabilities> for Room {
+    type Result = Result<proto::ClientCapabilitiesResponse, RoomError>;
+
+    fn handle(
+        &mut self,
+        msg: GetClientCapabilities,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        self.members.get_member_by_id(&msg.0)?;
+
+        let capabilities = self.members.capabilities(&msg.0).map(|caps| {
+            proto::ClientCapabilities {
+                user_agent: caps.user_agent.clone().unwrap_or_default(),
+                codecs: caps.codecs.clone(),
+                simulcast: caps.simulcast,
+                insertable_streams: caps.insertable_streams,
+            }
+        });
+
+        Ok(proto::ClientCapabilitiesResponse {
+            capabilities,
+            error: None,
+        })
+    }
+}
+
+/// Message for forcibly patching the tracks of this [`Room`]'s `Endpoint`,
+/// regardless of the will of its `Member`.
+///
+/// Returns ID of the started `PatchTrack` operation, if `on_applied` callback
+/// is requested.
+#[derive(Message, Debug)]
+#[rtype(result = "Result<Option<String>, RoomError>")]
+pub struct PatchEndpointTracks {
+    /// [`MemberId`] of the `Member` owning the `Endpoint`.
+    pub member_id: MemberId,
+
+    /// [`EndpointId`] of the `Endpoint` to patch the tracks of.
+    pub endpoint_id: EndpointId,
+
+    /// Kind of the tracks to be patched.
+    pub kind: proto::patch_track_request::TrackKind,
+
+    /// New value of the tracks being enabled, if it should be changed.
+    pub enabled: Option<bool>,
+
+    /// New value of the tracks being muted, if it should be changed.
+    pub muted: Option<bool>,
+
+    /// [`CallbackUrl`] to send `OnPatchApplied` callback to, once the patches
+    /// are applied by all the affected `Member`s.
+    pub on_applied: Option<CallbackUrl>,
+}
+
+impl Handler<PatchEndpointTracks> for Room {
+    type Result = Result<Option<String>, RoomError>;
+
+    /// Applies [`TrackPatchCommand`]s to the `Endpoint`'s tracks of the
+    /// requested kind, the same way as if they were sent by its `Member`.
+    ///
+    /// Succeeds if the `Endpoint` has no such tracks yet.
+    ///
+    /// If `on_applied` callback is requested, starts a [`PatchOperation`]
+    /// awaiting the patched tracks of both the [`Peer`]s and their partners.
+    fn handle(
+        &mut self,
+        msg: PatchEndpointTracks,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        use proto::patch_track_request::TrackKind;
+
+        /// Checks whether the provided [`MediaType`] matches the provided
+        /// [`TrackKind`].
+        fn is_of_kind(media_type: &MediaType, kind: TrackKind) -> bool {
+            matches!(
+                (kind, media_type),
+                (TrackKind::All, _)
+                    | (TrackKind::Audio, MediaType::Audio(_))
+                    | (TrackKind::Video, MediaType::Video(_))
+            )
+        }
+
+        let member = self.members.get_member_by_id(&msg.member_id)?;
+        let tracks: Vec<(PeerId, Vec<TrackId>)> =
+            match member.get_endpoint_by_id(msg.endpoint_id.to_string())? {
+                Endpoint::WebRtcPublishEndpoint(publish) => publish
+                    .peer_ids()
+                    .into_iter()
+                    .map(|peer_id| {
+                        (peer_id, publish.get_tracks_ids_by_peer_id(peer_id))
+                    })
+                    .collect(),
+                Endpoint::WebRtcPlayEndpoint(play) => {
+                    if let Some(peer_id) = play.peer_id() {
+                        let src_peer_id = self
+                            .peers
+                            .map_peer_by_id(peer_id, |p| p.partner_peer_id())?;
+                        vec![(
+                            peer_id,
+                            play.src().get_tracks_ids_by_peer_id(src_peer_id),
+                        )]
+                    } else {
+                        Vec::new()
+                    }
+                }
+            };
+
+        let mut patched = HashSet::new();
+        for (peer_id, track_ids) in tracks {
+            let (patches, partner_peer_id) =
+                self.peers.map_peer_by_id(peer_id, |peer| {
+                    let patches = track_ids
+                        .into_iter()
+                        .filter(|id| {
+                            peer.senders()
+                                .get(id)
+                                .or_else(|| peer.receivers().get(id))
+                                .map_or(false, |track| {
+                                    is_of_kind(track.media_type(), msg.kind)
+                                })
+                        })
+                        .map(|id| TrackPatchCommand {
+                            id,
+                            enabled: msg.enabled,
+                            muted: msg.muted,
+                        })
+                        .collect::<Vec<_>>();
+                    (patches, peer.partner_peer_id())
+                })?;
+            if !patches.is_empty() {
+                for patch in &patches {
+                    patched.insert((peer_id, patch.id));
+                    patched.insert((partner_peer_id, patch.id));
+                }
+                self.patch_peer_tracks(peer_id, patches)?;
+            }
+        }
+
+        Ok(msg.on_applied.map(|url| {
+            let operation_id = generate_token(16);
+            self.register_patch_operation(PatchOperation::new(
+                operation_id.clone(),
+                url,
+                member.get_fid_to_endpoint(msg.endpoint_id).into(),
+                patched,
+            ));
+            operation_id
+        }))
+    }
+}
+
+/// Message for retrieving approximate amounts of entities kept in memory by
+/// this [`Room`], serialized to protobuf.
+#[derive(Message, Debug)]
+#[rtype(result = "proto::RoomUsage")]
+pub struct GetUsage;
+
+impl Handler<GetUsage> for Room {
+    type Result = MessageResult<GetUsage>;
+
+    fn handle(&mut self, _: GetUsage, _: &mut Self::Context) -> Self::Result {
+        let peers = self.peers.usage();
+        let journal_events = self.journal.len();
+        let approx_bytes = peers.approx_bytes
+            + journal_events * mem::size_of::<JournalEvent>();
+
+        MessageResult(proto::RoomUsage {
+            members: self.members.members_ids().len() as u64,
+            peers: peers.peers as u64,
+            tracks: peers.tracks as u64,
+            pending_peer_changes: peers.pending_changes as u64,
+            ice_candidates: peers.ice_candidates as u64,
+            journal_events: journal_events as u64,
+            metrics_entries: peers.metrics_entries as u64,
+            approx_bytes: approx_bytes as u64,
+            metrics_evicted: peers.metrics_evicted,
+        })
+    }
+}
+
+/// Message for pausing/resuming media exchange of all the `Member`s in this
+/// [`Room`], while keeping their sessions and [`Peer`]s alive.
+///
+/// [`Peer`]: crate::media::peer::Peer
+#[derive(Message, Debug)]
+#[rtype(result = "()")]
+pub struct SetMediaPaused(pub bool);
+
+impl Handler<SetMediaPaused> for Room {
+    type Result = ();
+
+    fn handle(
+        &mut self,
+        msg: SetMediaPaused,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        if self.peers.set_media_paused(msg.0) {
+            info!(
+                "Media exchange in Room [id = {}] is {}",
+                self.id,
+                if msg.0 { "paused" } else { "resumed" },
+            );
+        }
+    }
+}
+
+/// Message for counting established [`RpcConnection`]s of this [`Room`]'s
+/// `Member`s.
+///
+/// [`RpcConnection`]: crate::api::client::rpc_connection::RpcConnection
+#[derive(Message, Debug)]
+#[rtype(result = "usize")]
+pub struct CountConnections;
+
+impl Handler<CountConnections> for Room {
+    type Result = usize;
+
+    #[inline]
+    fn handle(
+        &mut self,
+        _: CountConnections,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        self.members.connections_count()
+    }
+}
+
 /// Signal for deleting elements from this [`Room`].
 #[derive(Message, Debug)]
 #[rtype(result = "()")]
@@ -354,9 +779,30 @@ impl Handler<Delete> for Room {
     /// Deletes elements from this [`Room`] by the IDs extracted from the
     /// provided [`Delete`] message.
     fn handle(&mut self, msg: Delete, ctx: &mut Self::Context) {
+        #[cfg(any(test, feature = "testing"))]
+        let msg = match self.chaos.postpone(MessageKind::Control, msg, ctx) {
+            Some(msg) => msg,
+            None => return,
+        };
+
+        self.delete_elements(msg.0, ctx);
+    }
+}
+
+impl Room {
+    /// Deletes elements with the provided [`StatefulFid`]s from this [`Room`].
+    ///
+    /// [`Member`]s are deleted before `Endpoint`s.
+    ///
+    /// [`Member`]: crate::signalling::elements::Member
+    fn delete_elements(
+        &mut self,
+        fids: Vec<StatefulFid>,
+        ctx: &mut Context<Self>,
+    ) {
         let mut member_ids = Vec::new();
         let mut endpoint_ids = Vec::new();
-        for id in msg.0 {
+        for id in fids {
             match id {
                 StatefulFid::Member(member_fid) => {
                     member_ids.push(member_fid);
@@ -409,17 +855,33 @@ impl Handler<ApplyMember> for Room {
                 }
             }
             for id in member.sinks_ids() {
+                if member.wildcard_of(&id).is_none()
+                    && member_spec.get_play_endpoint_by_id(id.clone()).is_none()
+                {
+                    self.delete_endpoint(&member_id, id.into());
+                }
+            }
+            for (id, _) in member.wildcard_sinks() {
                 if member_spec.get_play_endpoint_by_id(id.clone()).is_none() {
                     self.delete_endpoint(&member_id, id.into());
                 }
             }
             for (id, endpoint) in member_spec.publish_endpoints() {
-                if member.get_src_by_id(&id).is_none() {
-                    self.create_src_endpoint(&member_id, id, endpoint)?;
+                if let Some(src) = member.get_src_by_id(&id) {
+                    if src.is_force_relayed() != endpoint.force_relay {
+                        self.update_src_force_relay(
+                            &src,
+                            endpoint.force_relay,
+                        )?;
+                    }
+                } else {
+                    self.create_src_endpoint(ctx, &member_id, id, endpoint)?;
                 }
             }
             for (id, endpoint) in member_spec.play_endpoints() {
-                if member.get_sink_by_id(&id).is_none() {
+                if member.get_sink_by_id(&id).is_none()
+                    && member.get_wildcard_sink_by_id(&id).is_none()
+                {
                     self.create_sink_endpoint(
                         ctx,
                         member_id.clone(),
@@ -428,8 +890,21 @@ impl Handler<ApplyMember> for Room {
                     )?;
                 }
             }
+            if &member.get_metadata() != member_spec.metadata() {
+                member.set_metadata(member_spec.metadata().clone());
+                self.members.broadcast_member_metadata(&member_id);
+            }
+            // New feature flags are delivered on the next `Client` join.
+            member.set_features(member_spec.features().clone());
+            if &member.credentials() != member_spec.credentials() {
+                member.set_credentials(member_spec.credentials().clone());
+            }
         } else {
-            self.members.create_member(member_id, &member_spec)?;
+            self.members
+                .create_member(member_id.clone(), &member_spec)?;
+            if !member_spec.metadata().is_empty() {
+                self.members.broadcast_member_metadata(&member_id);
+            }
         }
         Ok(())
     }
@@ -463,12 +938,28 @@ impl Handler<Apply> for Room {
                     }
                 }
                 for (sink_id, _) in member.sinks() {
+                    if member.wildcard_of(&sink_id).is_none()
+                        && spec
+                            .get_play_endpoint_by_id(sink_id.clone())
+                            .is_none()
+                    {
+                        self.delete_endpoint(id, sink_id.into());
+                    }
+                }
+                for (sink_id, _) in member.wildcard_sinks() {
                     if spec.get_play_endpoint_by_id(sink_id.clone()).is_none() {
                         self.delete_endpoint(id, sink_id.into());
                     }
                 }
                 for (src_id, src) in spec.publish_endpoints() {
-                    if member.get_src_by_id(&src_id).is_none() {
+                    if let Some(endpoint) = member.get_src_by_id(&src_id) {
+                        if endpoint.is_force_relayed() != src.force_relay {
+                            self.update_src_force_relay(
+                                &endpoint,
+                                src.force_relay,
+                            )?;
+                        }
+                    } else {
                         create_src_endpoint.push((
                             id,
                             src_id.clone(),
@@ -477,7 +968,9 @@ impl Handler<Apply> for Room {
                     }
                 }
                 for (sink_id, sink) in spec.play_endpoints() {
-                    if member.get_sink_by_id(&sink_id).is_none() {
+                    if member.get_sink_by_id(&sink_id).is_none()
+                        && member.get_wildcard_sink_by_id(&sink_id).is_none()
+                    {
                         create_sink_endpoint.push((
                             id,
                             sink_id.clone(),
@@ -485,13 +978,25 @@ impl Handler<Apply> for Room {
                         ));
                     }
                 }
+                if &member.get_metadata() != spec.metadata() {
+                    member.set_metadata(spec.metadata().clone());
+                    self.members.broadcast_member_metadata(id);
+                }
+                // New feature flags are delivered on the next `Client` join.
+                member.set_features(spec.features().clone());
+                if &member.credentials() != spec.credentials() {
+                    member.set_credentials(spec.credentials().clone());
+                }
             } else {
                 self.members.create_member(id.clone(), &spec)?;
+                if !spec.metadata().is_empty() {
+                    self.members.broadcast_member_metadata(id);
+                }
             }
         }
 
         for (id, src_id, src) in create_src_endpoint {
-            self.create_src_endpoint(id, src_id, &src)?;
+            self.create_src_endpoint(ctx, id, src_id, &src)?;
         }
         for (id, sink_id, sink) in create_sink_endpoint {
             self.create_sink_endpoint(ctx, id.clone(), sink_id, sink)?;
@@ -519,6 +1024,9 @@ impl Handler<CreateMember> for Room {
         _: &mut Self::Context,
     ) -> Self::Result {
         self.members.create_member(msg.0.clone(), &msg.1)?;
+        if !msg.1.metadata().is_empty() {
+            self.members.broadcast_member_metadata(&msg.0);
+        }
         debug!(
             "Member [id = {}] created in Room [id = {}].",
             msg.0, self.id
@@ -561,6 +1069,7 @@ impl Handler<CreateEndpoint> for Room {
             }
             EndpointSpec::WebRtcPublish(endpoint) => {
                 self.create_src_endpoint(
+                    ctx,
                     &msg.member_id,
                     msg.endpoint_id.into(),
                     &endpoint,
@@ -571,6 +1080,251 @@ impl Handler<CreateEndpoint> for Room {
     }
 }
 
+/// Single operation of a [`Batch`].
+#[derive(Debug)]
+pub enum BatchOperation {
+    /// Creation of a new [`Member`] with the provided [`MemberId`] according
+    /// to the given [`MemberSpec`].
+    ///
+    /// [`Member`]: crate::signalling::elements::Member
+    CreateMember(MemberId, MemberSpec),
+
+    /// Creation of a new `Endpoint`.
+    CreateEndpoint(CreateEndpoint),
+
+    /// Deletion of an element with the provided [`StatefulFid`].
+    Delete(StatefulFid),
+}
+
+/// Signal for applying many [`BatchOperation`]s to this [`Room`]
+/// transactionally.
+#[derive(Message, Debug)]
+#[rtype(result = "Result<(), RoomError>")]
+pub struct Batch(pub Vec<BatchOperation>);
+
+impl Handler<Batch> for Room {
+    type Result = Result<(), RoomError>;
+
+    /// Applies the provided [`BatchOperation`]s in order, only if all of them
+    /// can be applied, so this [`Room`] remains unchanged if any of them
+    /// fails.
+    ///
+    /// Creations are applied first, and are undone if any of them fails.
+    /// Deletions can't be undone, so they are applied only once all the
+    /// creations have succeeded.
+    fn handle(&mut self, msg: Batch, ctx: &mut Self::Context) -> Self::Result {
+        self.validate_batch(&msg.0)?;
+
+        let mut created: Vec<StatefulFid> = Vec::new();
+        let mut deleted = Vec::new();
+        for op in msg.0 {
+            let res = match op {
+                BatchOperation::CreateMember(id, spec) => {
+                    let fid = self.members.get_fid_to_member(id.clone());
+                    self.handle(CreateMember(id, spec), ctx)
+                        .map(|_| created.push(fid.into()))
+                }
+                BatchOperation::CreateEndpoint(create) => {
+                    let fid = Fid::<ToEndpoint>::new(
+                        self.id.clone(),
+                        create.member_id.clone(),
+                        create.endpoint_id.clone(),
+                    );
+                    self.handle(create, ctx).map(|_| created.push(fid.into()))
+                }
+                BatchOperation::Delete(fid) => {
+                    deleted.push(fid);
+                    Ok(())
+                }
+            };
+            if let Err(e) = res {
+                warn!(
+                    "Rolling back Batch in Room [id = {}], because it has \
+                     failed: {}",
+                    self.id, e,
+                );
+                created.reverse();
+                self.delete_elements(created, ctx);
+                return Err(e);
+            }
+        }
+        self.delete_elements(deleted, ctx);
+
+        Ok(())
+    }
+}
+
+impl Room {
+    /// Checks whether the provided [`BatchOperation`]s can be applied to this
+    /// [`Room`] in order, without applying them.
+    ///
+    /// # Errors
+    ///
+    /// With [`RoomError::MemberAlreadyExists`] or
+    /// [`RoomError::EndpointAlreadyExists`] if an element being created
+    /// exists already.
+    ///
+    /// With [`RoomError::ParticipantServiceErr`] if an `Endpoint` is created
+    /// for a non-existent `Member`.
+    ///
+    /// With [`RoomError::MemberError`] if a `WebRtcPlayEndpoint` refers to a
+    /// non-existent `WebRtcPublishEndpoint`.
+    ///
+    /// With [`RoomError::ParticipantServiceErr`] or [`RoomError::MemberError`]
+    /// if a non-existent `Member` or `Endpoint` is deleted.
+    ///
+    /// With [`RoomError::BadRoomSpec`] if this [`Room`] itself is deleted, or
+    /// if an element deleted by the batch is created by it again.
+    fn validate_batch(&self, ops: &[BatchOperation]) -> Result<(), RoomError> {
+        // Endpoints of each `Member`, where `true` marks publishing ones.
+        let mut members: HashMap<MemberId, HashMap<EndpointId, bool>> = self
+            .members
+            .members()
+            .into_iter()
+            .map(|(id, member)| {
+                let srcs = member
+                    .srcs_ids()
+                    .into_iter()
+                    .map(|id| (EndpointId::from(id), true));
+                let sinks = member
+                    .sinks_ids()
+                    .into_iter()
+                    .chain(
+                        member.wildcard_sinks().into_iter().map(|(id, _)| id),
+                    )
+                    .map(|id| (EndpointId::from(id), false));
+                (id, srcs.chain(sinks).collect())
+            })
+            .collect();
+        // Elements deleted by the batch, which can't be created by it again,
+        // as deletions are applied only after all the creations.
+        let mut deleted_members = HashSet::new();
+        let mut deleted_endpoints = HashSet::new();
+        let fid_to_endpoint = |member_id: &MemberId, id: EndpointId| {
+            Fid::<ToEndpoint>::new(self.id.clone(), member_id.clone(), id)
+        };
+        let check_src =
+            |members: &HashMap<MemberId, HashMap<EndpointId, bool>>,
+             play: &WebRtcPlayEndpointSpec| {
+                if play.src.is_wildcard() {
+                    return Ok(());
+                }
+                let src_id = EndpointId::from(play.src.endpoint_id.clone());
+                let is_publish = members
+                    .get(&play.src.member_id)
+                    .and_then(|endpoints| endpoints.get(&src_id))
+                    .copied()
+                    .unwrap_or_default();
+                if is_publish {
+                    Ok(())
+                } else {
+                    Err(RoomError::from(MemberError::EndpointNotFound(
+                        fid_to_endpoint(&play.src.member_id, src_id),
+                    )))
+                }
+            };
+
+        for op in ops {
+            match op {
+                BatchOperation::CreateMember(id, spec) => {
+                    if deleted_members.contains(id) {
+                        return Err(RoomError::BadRoomSpec(format!(
+                            "Member [id = {}] can't be deleted and created \
+                             in a single batch",
+                            id,
+                        )));
+                    }
+                    if members.contains_key(id) {
+                        return Err(RoomError::MemberAlreadyExists(
+                            self.members.get_fid_to_member(id.clone()),
+                        ));
+                    }
+                    let srcs = spec
+                        .publish_endpoints()
+                        .map(|(id, _)| (EndpointId::from(id), true));
+                    let sinks = spec
+                        .play_endpoints()
+                        .map(|(id, _)| (EndpointId::from(id), false));
+                    members.insert(id.clone(), srcs.chain(sinks).collect());
+                    for (_, play) in spec.play_endpoints() {
+                        check_src(&members, play)?;
+                    }
+                }
+                BatchOperation::CreateEndpoint(create) => {
+                    let endpoints = members
+                        .get_mut(&create.member_id)
+                        .ok_or_else(|| {
+                            ParticipantServiceErr::ParticipantNotFound(
+                                self.members.get_fid_to_member(
+                                    create.member_id.clone(),
+                                ),
+                            )
+                        })?;
+                    if deleted_endpoints.contains(&(
+                        create.member_id.clone(),
+                        create.endpoint_id.clone(),
+                    )) {
+                        return Err(RoomError::BadRoomSpec(format!(
+                            "Endpoint [id = {}] can't be deleted and created \
+                             in a single batch",
+                            fid_to_endpoint(
+                                &create.member_id,
+                                create.endpoint_id.clone(),
+                            ),
+                        )));
+                    }
+                    if endpoints.contains_key(&create.endpoint_id) {
+                        return Err(RoomError::EndpointAlreadyExists(
+                            fid_to_endpoint(
+                                &create.member_id,
+                                create.endpoint_id.clone(),
+                            ),
+                        ));
+                    }
+                    let is_publish =
+                        matches!(create.spec, EndpointSpec::WebRtcPublish(_));
+                    endpoints.insert(create.endpoint_id.clone(), is_publish);
+                    if let EndpointSpec::WebRtcPlay(play) = &create.spec {
+                        check_src(&members, play)?;
+                    }
+                }
+                BatchOperation::Delete(StatefulFid::Member(fid)) => {
+                    if members.remove(fid.member_id()).is_none() {
+                        return Err(ParticipantServiceErr::ParticipantNotFound(
+                            fid.clone(),
+                        )
+                        .into());
+                    }
+                    deleted_members.insert(fid.member_id().clone());
+                }
+                BatchOperation::Delete(StatefulFid::Endpoint(fid)) => {
+                    let removed = members
+                        .get_mut(fid.member_id())
+                        .and_then(|endpoints| {
+                            endpoints.remove(fid.endpoint_id())
+                        });
+                    if removed.is_none() {
+                        return Err(
+                            MemberError::EndpointNotFound(fid.clone()).into()
+                        );
+                    }
+                    deleted_endpoints.insert((
+                        fid.member_id().clone(),
+                        fid.endpoint_id().clone(),
+                    ));
+                }
+                BatchOperation::Delete(StatefulFid::Room(fid)) => {
+                    return Err(RoomError::BadRoomSpec(format!(
+                        "Room [id = {}] can't be deleted in a batch",
+                        fid,
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Signal for closing this [`Room`].
 #[derive(Message, Debug)]
 #[rtype(result = "()")]
@@ -589,7 +1343,9 @@ impl Handler<Close> for Room {
             self.delete_member(id, ctx);
         }
         AtomicResponse::new(Box::pin(
-            self.members.drop_connections(ctx).into_actor(self),
+            self.members
+                .drop_connections(CloseReason::Finished, ctx)
+                .into_actor(self),
         ))
     }
 }
@@ -0,0 +1,235 @@
+//! Bounded journal of high-level [`Room`] events, allowing [Control API]
+//! clients to catch up with the [`Room`] after a downtime.
+//!
+//! [`Room`]: crate::signalling::Room
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+use medea_client_api_proto::PeerId;
+use medea_control_api_proto::grpc::api as proto;
+
+use crate::api::control::refs::StatefulFid;
+
+/// Kind of a [`JournalEvent`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum JournalEventKind {
+    /// `Member` established its [`RpcConnection`].
+    ///
+    /// [`RpcConnection`]: crate::api::client::rpc_connection::RpcConnection
+    MemberJoined,
+
+    /// `Member` lost or closed its [`RpcConnection`].
+    ///
+    /// [`RpcConnection`]: crate::api::client::rpc_connection::RpcConnection
+    MemberLeft,
+
+    /// `Endpoint` was created via [Control API].
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    EndpointCreated,
+
+    /// `Peer` of a `Member` connected to its partner `Peer`.
+    PeerConnected,
+}
+
+/// Single event recorded into a [`Journal`].
+#[derive(Clone, Debug)]
+pub struct JournalEvent {
+    /// Sequence number of this [`JournalEvent`], unique in its [`Journal`].
+    seq: u64,
+
+    /// [`DateTime`] when this [`JournalEvent`] occurred.
+    at: DateTime<Utc>,
+
+    /// [`StatefulFid`] of the element this [`JournalEvent`] occurred with.
+    fid: StatefulFid,
+
+    /// Kind of this [`JournalEvent`].
+    kind: JournalEventKind,
+
+    /// [`PeerId`] of the `Peer` this [`JournalEvent`] occurred with.
+    peer_id: Option<PeerId>,
+}
+
+impl From<&JournalEvent> for proto::RoomJournalEvent {
+    fn from(event: &JournalEvent) -> Self {
+        use proto::room_journal_event::Kind;
+
+        let kind = match event.kind {
+            JournalEventKind::MemberJoined => Kind::MemberJoined,
+            JournalEventKind::MemberLeft => Kind::MemberLeft,
+            JournalEventKind::EndpointCreated => Kind::EndpointCreated,
+            JournalEventKind::PeerConnected => Kind::PeerConnected,
+        };
+        Self {
+            seq: event.seq,
+            at: event.at.to_rfc3339(),
+            fid: event.fid.to_string(),
+            kind: kind as i32,
+            peer_id: event.peer_id.map_or(0, |id| id.0),
+        }
+    }
+}
+
+/// Bounded journal of [`JournalEvent`]s, evicting the oldest ones once its
+/// capacity is exceeded.
+#[derive(Debug)]
+pub struct Journal {
+    /// Recorded [`JournalEvent`]s, ordered by their sequence numbers.
+    events: VecDeque<JournalEvent>,
+
+    /// Maximum number of [`JournalEvent`]s stored in this [`Journal`].
+    capacity: usize,
+
+    /// Sequence number of the next recorded [`JournalEvent`].
+    next_seq: u64,
+}
+
+impl Journal {
+    /// Creates a new empty [`Journal`] storing at most `capacity`
+    /// [`JournalEvent`]s.
+    ///
+    /// Zero `capacity` disables recording.
+    #[inline]
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            events: VecDeque::with_capacity(capacity.min(64)),
+            capacity,
+            next_seq: 1,
+        }
+    }
+
+    /// Records a new [`JournalEvent`] of the provided `kind`, evicting the
+    /// oldest one if the capacity of this [`Journal`] is exceeded.
+    pub fn record(
+        &mut self,
+        kind: JournalEventKind,
+        fid: StatefulFid,
+        peer_id: Option<PeerId>,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+        }
+        self.events.push_back(JournalEvent {
+            seq: self.next_seq,
+            at: Utc::now(),
+            fid,
+            kind,
+            peer_id,
+        });
+        self.next_seq += 1;
+    }
+
+    /// Indicates whether a [`JournalEventKind::PeerConnected`] event of the
+    /// provided [`PeerId`] is stored in this [`Journal`].
+    #[must_use]
+    pub fn is_peer_connected(&self, peer_id: PeerId) -> bool {
+        self.events.iter().any(|e| {
+            e.kind == JournalEventKind::PeerConnected
+                && e.peer_id == Some(peer_id)
+        })
+    }
+
+    /// Returns sequence number of the oldest [`JournalEvent`] stored in this
+    /// [`Journal`].
+    ///
+    /// Returns sequence number of the next [`JournalEvent`] if this
+    /// [`Journal`] is empty.
+    #[must_use]
+    pub fn oldest_seq(&self) -> u64 {
+        self.events.front().map_or(self.next_seq, |e| e.seq)
+    }
+
+    /// Returns number of the [`JournalEvent`]s stored in this [`Journal`].
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns all the stored [`JournalEvent`]s with a sequence number greater
+    /// than the provided one.
+    pub fn since(&self, seq: u64) -> impl Iterator<Item = &JournalEvent> {
+        let skip = self.events.iter().take_while(|e| e.seq <= seq).count();
+        self.events.iter().skip(skip)
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::convert::TryFrom as _;
+
+    use super::*;
+
+    fn fid(member: &str) -> StatefulFid {
+        StatefulFid::try_from(format!("room/{}", member)).unwrap()
+    }
+
+    fn seqs(journal: &Journal, since: u64) -> Vec<u64> {
+        journal.since(since).map(|e| e.seq).collect()
+    }
+
+    #[test]
+    fn returns_events_after_seq() {
+        let mut journal = Journal::new(10);
+        assert_eq!(journal.oldest_seq(), 1);
+
+        journal.record(JournalEventKind::MemberJoined, fid("a"), None);
+        journal.record(JournalEventKind::MemberJoined, fid("b"), None);
+        journal.record(JournalEventKind::MemberLeft, fid("a"), None);
+
+        assert_eq!(seqs(&journal, 0), vec![1, 2, 3]);
+        assert_eq!(seqs(&journal, 2), vec![3]);
+        assert!(seqs(&journal, 3).is_empty());
+        assert_eq!(journal.oldest_seq(), 1);
+    }
+
+    #[test]
+    fn evicts_oldest_events() {
+        let mut journal = Journal::new(2);
+        for _ in 0..5 {
+            journal.record(JournalEventKind::MemberJoined, fid("a"), None);
+        }
+
+        assert_eq!(journal.oldest_seq(), 4);
+        assert_eq!(journal.len(), 2);
+        assert_eq!(seqs(&journal, 0), vec![4, 5]);
+    }
+
+    #[test]
+    fn zero_capacity_records_nothing() {
+        let mut journal = Journal::new(0);
+        journal.record(JournalEventKind::MemberJoined, fid("a"), None);
+
+        assert!(seqs(&journal, 0).is_empty());
+        assert_eq!(journal.oldest_seq(), 1);
+    }
+
+    #[test]
+    fn converts_to_proto() {
+        let mut journal = Journal::new(1);
+        journal.record(
+            JournalEventKind::PeerConnected,
+            fid("a"),
+            Some(PeerId(3)),
+        );
+        assert!(journal.is_peer_connected(PeerId(3)));
+        assert!(!journal.is_peer_connected(PeerId(4)));
+
+        let event: proto::RoomJournalEvent =
+            journal.since(0).next().unwrap().into();
+        assert_eq!(event.seq, 1);
+        assert_eq!(event.fid, "room/a");
+        assert_eq!(
+            event.kind,
+            proto::room_journal_event::Kind::PeerConnected as i32,
+        );
+        assert_eq!(event.peer_id, 3);
+    }
+}
@@ -1,51 +1,85 @@
 //! Room definitions and implementations. Room is responsible for media
 //! connection establishment between concrete [`Member`]s.
 
+#[cfg(any(test, feature = "testing"))]
+mod chaos;
 mod command_handler;
 mod dynamic_api;
+mod journal;
+mod negotiations;
+mod patch_operations;
 mod peer_events_handler;
 mod rpc_server;
+mod snapshot;
+mod track_patches;
 
-use std::{rc::Rc, sync::Arc, time::Duration};
+use std::{cell::RefCell, rc::Rc, sync::Arc, time::Duration};
 
 use actix::{
-    fut::LocalBoxActorFuture, Actor, ActorFutureExt as _,
-    ActorTryFutureExt as _, Addr, AsyncContext as _, AtomicResponse, Context,
-    Handler, MailboxError, WrapFuture as _,
+    fut::LocalBoxActorFuture, Actor, ActorFutureExt as _, Addr,
+    AsyncContext as _, AtomicResponse, Context, Handler, MailboxError,
+    WrapFuture as _,
 };
 use derive_more::{Display, From};
 use failure::Fail;
-use futures::future;
 use medea_client_api_proto::{
     state, CloseReason, Event, MemberId, NegotiationRole, PeerId, RoomId,
 };
 
 use crate::{
+    admission::Admission,
     api::control::{
         callback::{
             CallbackClientError, CallbackClientFactoryImpl, CallbackService,
-            OnLeaveEvent, OnLeaveReason,
+            OnLeaveEvent, OnLeaveReason, OnNegotiationTimeoutEvent,
+            OnPatchAppliedEvent,
         },
         refs::{Fid, StatefulFid, ToEndpoint, ToMember},
         room::RoomSpec,
         TryFromElementError,
     },
+    drain::Drain,
     log::prelude::*,
-    media::{peer::PeerUpdatesSubscriber, Peer, PeerError, Stable},
+    media::{
+        peer::PeerUpdatesSubscriber, Peer, PeerError, PeerStateMachine, Stable,
+    },
     shutdown::ShutdownGracefully,
     signalling::{
-        elements::{member::MemberError, Member, MembersLoadError},
+        elements::{
+            endpoints::webrtc::{WebRtcPlayEndpoint, WebRtcPublishEndpoint},
+            member::MemberError,
+            Member, MembersLoadError,
+        },
         participants::{ParticipantService, ParticipantServiceErr},
-        peers::{PeerTrafficWatcher, PeersService},
+        peers::{IdsExhausted, PeerTrafficWatcher, PeersService},
     },
     turn::TurnServiceErr,
-    utils::actix_try_join_all,
     AppContext,
 };
 
 pub use dynamic_api::{
-    Apply, ApplyMember, Close, CreateEndpoint, CreateMember, Delete,
-    SerializeProto,
+    Apply, ApplyMember, Batch, BatchOperation, Close, CountConnections,
+    CreateEndpoint, CreateMember, Delete, GetClientCapabilities, GetJournal,
+    GetQualityHistory, GetUsage, PatchEndpointTracks, SerializeProto,
+    SetMediaPaused,
+};
+
+pub use self::{
+    peer_events_handler::PeerRelayed,
+    snapshot::{
+        MemberSnapshot, MetricsSnapshot, PeerSnapshot, QualitySnapshot,
+        RoomSnapshot, TakeSnapshot,
+    },
+};
+
+#[cfg(any(test, feature = "testing"))]
+pub use self::chaos::{Chaos, MessageKind};
+
+use self::{
+    journal::{Journal, JournalEventKind},
+    negotiations::NegotiationsQueue,
+    patch_operations::{PatchOperation, PatchOperations},
+    track_patches::TrackPatchesThrottle,
 };
 
 /// Ergonomic type alias for using [`ActorFuture`] for [`Room`].
@@ -119,6 +153,23 @@ pub enum RoomError {
     /// [`TryFrom`]: std::convert::TryFrom
     #[display(fmt = "Failed to convert element: {:?}", _0)]
     TryFromElement(TryFromElementError),
+
+    /// Media server is draining, so no new [`Member`]s are allowed to join
+    /// this [`Room`].
+    #[display(fmt = "Media server is draining")]
+    Draining,
+
+    /// Media server admits too many [`Member`]s at the moment, so the
+    /// [`Member`] should retry joining this [`Room`] after the provided
+    /// [`Duration`].
+    #[display(fmt = "Media server is overloaded, retry after {:?}", _0)]
+    Overloaded(Duration),
+
+    /// There are no free [`PeerId`]s or [`TrackId`]s left in this [`Room`].
+    ///
+    /// [`TrackId`]: medea_client_api_proto::TrackId
+    #[display(fmt = "No free Peer or Track IDs left in Room")]
+    IdsExhausted(IdsExhausted),
 }
 
 /// Media server room with its [`Member`]s.
@@ -139,6 +190,53 @@ pub struct Room {
 
     /// [`Peer`]s of [`Member`]s in this [`Room`].
     peers: Rc<PeersService>,
+
+    /// Interval of sending [`Event::StateChecksum`]s to the connected
+    /// [`Member`]s.
+    ///
+    /// Zero means that no [`Event::StateChecksum`]s are sent.
+    state_checksum_interval: Duration,
+
+    /// Timeout for [`Peer`]s to finish their negotiation, after which they're
+    /// removed.
+    ///
+    /// Zero means that [`Peer`]s are never removed this way.
+    negotiation_timeout: Duration,
+
+    /// Queue of [`Peer`]s, which negotiations are postponed due to the limit
+    /// of simultaneous negotiations in this [`Room`].
+    negotiations: NegotiationsQueue,
+
+    /// Rate limiter of [`Command::UpdateTracks`]s of the [`Member`]s, merging
+    /// the excess ones.
+    ///
+    /// [`Command::UpdateTracks`]: medea_client_api_proto::Command::UpdateTracks
+    track_patches: TrackPatchesThrottle,
+
+    /// `PatchTrack` Control API operations, awaiting their patches to be
+    /// applied by the `Client`s.
+    patch_operations: RefCell<PatchOperations>,
+
+    /// Period, during which [`Peer`] pairs left without any tracks are kept
+    /// alive to be reused for new `Endpoint`s.
+    ///
+    /// Zero means that such [`Peer`]s are removed immediately.
+    peer_linger_period: Duration,
+
+    /// [`Journal`] of high-level events occurred in this [`Room`].
+    journal: Journal,
+
+    /// Switch of the drain mode, rejecting new [`Member`]s once switched on.
+    drain: Drain,
+
+    /// Pacer of [`Member`]s joining this [`Room`], shared by all the [`Room`]s
+    /// of this media server.
+    admission: Admission,
+
+    /// Switch of the chaos mode, postponing processing of specific kinds of
+    /// messages.
+    #[cfg(any(test, feature = "testing"))]
+    chaos: Chaos,
 }
 
 impl Room {
@@ -171,6 +269,21 @@ impl Room {
             ),
             members: ParticipantService::new(room_spec, context)?,
             callbacks: context.callbacks.clone(),
+            state_checksum_interval: context.config.rpc.state_checksum_interval,
+            negotiation_timeout: context.config.media.negotiation_timeout,
+            negotiations: NegotiationsQueue::new(
+                context.config.media.max_concurrent_negotiations,
+            ),
+            track_patches: TrackPatchesThrottle::new(
+                context.config.rpc.update_tracks_rate_limit,
+            ),
+            patch_operations: RefCell::default(),
+            peer_linger_period: context.config.media.peer_linger_period,
+            journal: Journal::new(context.config.control.journal_capacity),
+            drain: context.drain.clone(),
+            admission: context.admission.clone(),
+            #[cfg(any(test, feature = "testing"))]
+            chaos: context.chaos.clone(),
         };
 
         Ok(ctx.run(this))
@@ -185,6 +298,20 @@ impl Room {
         }
     }
 
+    /// Sends [`Event::StateChecksum`] with a checksum of its [`state::Room`]
+    /// to each connected [`Member`].
+    fn send_state_checksums(&self) {
+        for member_id in self.members.members_ids() {
+            if self.members.member_has_connection(&member_id) {
+                let checksum = self.get_state(&member_id).checksum();
+                self.members.send_event_to_member(
+                    &member_id,
+                    Event::StateChecksum { checksum },
+                );
+            }
+        }
+    }
+
     /// Returns [`RoomId`] of this [`Room`].
     #[inline]
     #[must_use]
@@ -192,13 +319,14 @@ impl Room {
         &self.id
     }
 
-    /// Sends [`Event::PeersRemoved`] to [`Member`].
-    #[inline]
+    /// Sends [`Event::PeersRemoved`] to [`Member`], failing the
+    /// [`PatchOperation`]s awaiting the removed [`Peer`]s.
     fn send_peers_removed(
         &self,
         member_id: &MemberId,
         removed_peers_ids: Vec<PeerId>,
     ) {
+        self.settle_patch_operations(&removed_peers_ids);
         self.members.send_event_to_member(
             member_id,
             Event::PeersRemoved {
@@ -207,21 +335,110 @@ impl Room {
         )
     }
 
-    /// Connects interconnected `Endpoint`s between provided [`Member`]s.
-    fn connect_members(
-        &mut self,
+    /// Starts tracking the provided [`PatchOperation`], emitting its
+    /// [`OnPatchAppliedEvent`] right away if it has nothing to await.
+    fn register_patch_operation(&self, op: PatchOperation) {
+        if let Some(op) = self.patch_operations.borrow_mut().register(op) {
+            self.callbacks.do_send(
+                op.url,
+                op.fid,
+                OnPatchAppliedEvent::new(op.id, None),
+            );
+        }
+    }
+
+    /// Checks whether the tracks patches awaited by the [`PatchOperation`]s
+    /// are applied by the [`Peer`]s with the provided [`PeerId`]s, emitting
+    /// [`OnPatchAppliedEvent`]s for the finished ones.
+    ///
+    /// [`PatchOperation`]s awaiting the removed [`Peer`]s are finished with an
+    /// error.
+    fn settle_patch_operations(&self, peer_ids: &[PeerId]) {
+        let finished =
+            self.patch_operations
+                .borrow_mut()
+                .settle(peer_ids, |peer_id, track_id| {
+                    self.peers
+                        .map_peer_by_id(peer_id, |peer| {
+                            peer.is_track_patch_applied(track_id)
+                        })
+                        .ok()
+                });
+        for (op, error) in finished {
+            self.callbacks.do_send(
+                op.url,
+                op.fid,
+                OnPatchAppliedEvent::new(op.id, error),
+            );
+        }
+    }
+
+    /// Removes [`Peer`]s which haven't finished their negotiation in the
+    /// `negotiation_timeout`, along with their partners, notifying their
+    /// owners with [`Event::PeersRemoved`] and emitting
+    /// [`OnNegotiationTimeoutEvent`] [`CallbackEvent`]s to the [`Member`]s
+    /// configured to receive them.
+    ///
+    /// [`CallbackEvent`]: crate::api::control::callback::CallbackEvent
+    fn remove_stuck_peers(&mut self) {
+        let removed_peers =
+            self.peers.remove_stuck_peers(self.negotiation_timeout);
+        for (member_id, peers) in removed_peers {
+            warn!(
+                "Peers {:?} of Member [id = {}] in Room [id = {}] haven't \
+                 finished negotiation in {:?}, so are removed",
+                peers.iter().map(PeerStateMachine::id).collect::<Vec<_>>(),
+                member_id,
+                self.id,
+                self.negotiation_timeout,
+            );
+            if let Ok(member) = self.members.get_member_by_id(&member_id) {
+                if let Some(url) = member.get_on_negotiation_timeout() {
+                    for peer in &peers {
+                        self.callbacks.do_send(
+                            url.clone(),
+                            member.get_fid().into(),
+                            OnNegotiationTimeoutEvent::new(
+                                peer.id(),
+                                peer.partner_member_id().clone(),
+                            ),
+                        );
+                    }
+                }
+            }
+            self.member_peers_removed(
+                peers.iter().map(PeerStateMachine::id).collect(),
+                &member_id,
+            );
+        }
+        self.start_postponed_negotiations();
+    }
+
+    /// Removes [`Peer`]s which have been left without any tracks for longer
+    /// than the `peer_linger_period`, along with their partners, notifying
+    /// their owners with [`Event::PeersRemoved`].
+    fn remove_lingering_peers(&self) {
+        for (member_id, peers) in self.peers.remove_lingering_peers() {
+            self.member_peers_removed(
+                peers.iter().map(PeerStateMachine::id).collect(),
+                &member_id,
+            );
+        }
+    }
+
+    /// Returns all the interconnected `Endpoint`s pairs between the provided
+    /// [`Member`]s.
+    fn endpoints_between(
         member1: &Member,
         member2: &Member,
-    ) -> ActFuture<Result<(), RoomError>> {
+    ) -> Vec<(WebRtcPublishEndpoint, WebRtcPlayEndpoint)> {
         let member2_id = member2.id();
-        let mut connect_endpoints_tasks = Vec::new();
+        let mut endpoints = Vec::new();
 
         for src in member1.srcs().values() {
             for sink in src.sinks() {
                 if sink.owner().id() == member2_id {
-                    connect_endpoints_tasks.push(
-                        self.peers.clone().connect_endpoints(src.clone(), sink),
-                    );
+                    endpoints.push((src.clone(), sink));
                 }
             }
         }
@@ -229,23 +446,11 @@ impl Room {
         for sink in member1.sinks().values() {
             let src = sink.src();
             if src.owner().id() == member2_id {
-                connect_endpoints_tasks.push(
-                    self.peers.clone().connect_endpoints(src, sink.clone()),
-                )
+                endpoints.push((src, sink.clone()));
             }
         }
 
-        Box::pin(
-            future::try_join_all(connect_endpoints_tasks)
-                .into_actor(self)
-                .map(move |result, room: &mut Room, _| {
-                    for (src_peer_id, _) in result?.into_iter().flatten() {
-                        room.peers.commit_scheduled_changes(src_peer_id)?;
-                    }
-
-                    Ok(())
-                }),
-        )
+        endpoints
     }
 
     /// Creates and interconnects all [`Peer`]s between connected [`Member`]
@@ -260,21 +465,32 @@ impl Room {
     /// interconnected `Endpoint`s will be found and if [`Peer`]s pair is
     /// already exists.
     ///
+    /// `Endpoint`s of all the available [`Member`]s are connected as a single
+    /// batch with bounded concurrency.
+    ///
     /// [`RpcConnection`]: crate::api::client::rpc_connection::RpcConnection
     fn init_member_connections(
         &mut self,
         member: &Member,
     ) -> ActFuture<Result<(), RoomError>> {
-        let connect_members_tasks =
-            member.partners().into_iter().filter_map(|partner| {
-                if self.members.member_has_connection(&partner.id()) {
-                    Some(self.connect_members(&partner, member))
-                } else {
-                    None
-                }
-            });
+        let endpoints = member
+            .partners()
+            .into_iter()
+            .filter(|partner| self.members.member_has_connection(&partner.id()))
+            .flat_map(|partner| Self::endpoints_between(&partner, member))
+            .collect();
 
-        Box::pin(actix_try_join_all(connect_members_tasks).map_ok(|_, _, _| ()))
+        Box::pin(
+            Rc::clone(&self.peers)
+                .connect_endpoints_batch(endpoints)
+                .into_actor(self)
+                .map(|result, room: &mut Room, _| {
+                    for (src_peer_id, _) in result? {
+                        room.peers.commit_scheduled_changes(src_peer_id)?;
+                    }
+                    Ok(())
+                }),
+        )
     }
 
     /// Signals about removing [`Member`]'s [`Peer`]s.
@@ -326,6 +542,32 @@ impl Room {
         Ok(())
     }
 
+    /// Records [`JournalEventKind::PeerConnected`] event of the provided
+    /// [`Peer`] into the [`Journal`], unless it's recorded already.
+    ///
+    /// # Errors
+    ///
+    /// Errors with [`RoomError::PeerNotFound`] if the provided [`Peer`] doesn't
+    /// exist.
+    fn record_peer_connected(
+        &mut self,
+        peer_id: PeerId,
+    ) -> Result<(), RoomError> {
+        if self.journal.is_peer_connected(peer_id) {
+            return Ok(());
+        }
+        let member_id = self
+            .peers
+            .map_peer_by_id(peer_id, |p| p.member_id().clone())?;
+        let member = self.members.get_member_by_id(&member_id)?;
+        self.journal.record(
+            JournalEventKind::PeerConnected,
+            member.get_fid().into(),
+            Some(peer_id),
+        );
+        Ok(())
+    }
+
     /// Closes [`Member`]s [`RpcConnection`] if `ws_close_reason` is provided,
     /// removes [`Member`]s [`Peer`], notifying connected [`Members`] and emits
     /// [`OnLeaveEvent`] [`CallbackEvent`] if `on_leave_reason` is provided and
@@ -344,19 +586,39 @@ impl Room {
         for (peer_member_id, peers_ids) in removed_peers {
             self.member_peers_removed(peers_ids, &peer_member_id);
         }
+        self.start_postponed_negotiations();
 
-        self.members
-            .close_member_connection(&member_id, ws_close_reason, ctx);
+        let had_connection = self.members.close_member_connection(
+            &member_id,
+            ws_close_reason,
+            ctx,
+        );
+        let session = self.members.end_session(member_id);
+        if let Some(flush) = self.track_patches.remove(member_id) {
+            ctx.cancel_future(flush);
+        }
 
         if let Ok(member) = self.members.get_member_by_id(member_id) {
+            if had_connection {
+                self.journal.record(
+                    JournalEventKind::MemberLeft,
+                    member.get_fid().into(),
+                    None,
+                );
+            }
             if let (Some(url), Some(reason)) =
                 (member.get_on_leave(), on_leave_reason)
             {
-                self.callbacks.do_send(
-                    url,
-                    member.get_fid().into(),
-                    OnLeaveEvent::new(reason),
-                );
+                let mut event = OnLeaveEvent::new(reason);
+                if let Some((session, duration)) = session {
+                    event = event.with_session(session, duration);
+                }
+                // otherwise, the connection has been closed by the client or
+                // lost, so no close reason has been sent to it
+                if reason == OnLeaveReason::Kicked {
+                    event = event.with_close_reason(ws_close_reason);
+                }
+                self.callbacks.do_send(url, member.get_fid().into(), event);
             }
         }
     }
@@ -372,6 +634,21 @@ impl Actor for Room {
         ctx.run_interval(Duration::from_secs(5), |this, _| {
             this.peers.check_peers();
         });
+        if !self.state_checksum_interval.is_zero() {
+            ctx.run_interval(self.state_checksum_interval, |this, _| {
+                this.send_state_checksums();
+            });
+        }
+        if !self.negotiation_timeout.is_zero() {
+            ctx.run_interval(self.negotiation_timeout / 2, |this, _| {
+                this.remove_stuck_peers();
+            });
+        }
+        if !self.peer_linger_period.is_zero() {
+            ctx.run_interval(self.peer_linger_period / 2, |this, _| {
+                this.remove_lingering_peers();
+            });
+        }
         ctx.add_stream(self.peers.subscribe_to_metrics_events());
     }
 }
@@ -390,26 +667,39 @@ impl Handler<ShutdownGracefully> for Room {
             self.id
         );
 
-        self.members
+        let close_reason = if self.drain.is_draining() {
+            CloseReason::Drained
+        } else {
+            CloseReason::Finished
+        };
+
+        let leaving: Vec<_> = self
+            .members
             .iter_members()
             .filter_map(|(_, member)| {
                 member
                     .get_on_leave()
-                    .map(move |on_leave| (member, on_leave))
+                    .map(move |on_leave| (member.clone(), on_leave))
             })
             .filter(|(member, _)| {
                 self.members.member_has_connection(&member.id())
             })
-            .for_each(|(member, on_leave)| {
-                self.callbacks.do_send(
-                    on_leave,
-                    member.get_fid().into(),
-                    OnLeaveEvent::new(OnLeaveReason::ServerShutdown),
-                );
-            });
-
+            .collect();
+        for (member, on_leave) in leaving {
+            let mut event = OnLeaveEvent::new(OnLeaveReason::ServerShutdown)
+                .with_close_reason(close_reason);
+            if let Some((session, duration)) =
+                self.members.end_session(&member.id())
+            {
+                event = event.with_session(session, duration);
+            }
+            self.callbacks
+                .do_send(on_leave, member.get_fid().into(), event);
+        }
         AtomicResponse::new(Box::pin(
-            self.members.drop_connections(ctx).into_actor(self),
+            self.members
+                .drop_connections(close_reason, ctx)
+                .into_actor(self),
         ))
     }
 }
@@ -0,0 +1,103 @@
+//! Scheduler of [`Room`] negotiations, bounding the number of `Peer` pairs
+//! (re)negotiating simultaneously.
+//!
+//! [`Room`]: crate::signalling::Room
+
+use std::collections::VecDeque;
+
+use medea_client_api_proto::PeerId;
+
+/// FIFO queue of `Peer`s waiting for a free negotiation slot of a [`Room`].
+///
+/// [`Room`]: crate::signalling::Room
+#[derive(Debug)]
+pub struct NegotiationsQueue {
+    /// Maximum number of `Peer` pairs negotiating simultaneously.
+    ///
+    /// Zero means no limit.
+    limit: usize,
+
+    /// [`PeerId`]s of the `Peer`s, which negotiations are postponed until a
+    /// free slot appears, in the order they were postponed in.
+    queue: VecDeque<PeerId>,
+}
+
+impl NegotiationsQueue {
+    /// Creates a new empty [`NegotiationsQueue`] with the provided `limit`.
+    #[inline]
+    #[must_use]
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Indicates whether a new negotiation can't be started while the
+    /// provided number of negotiations is ongoing.
+    #[inline]
+    #[must_use]
+    pub fn is_full(&self, negotiating: usize) -> bool {
+        self.limit != 0 && negotiating >= self.limit
+    }
+
+    /// Postpones negotiation of the `Peer` with the provided [`PeerId`]
+    /// until a free slot appears.
+    ///
+    /// Does nothing if this `Peer` is postponed already.
+    pub fn postpone(&mut self, peer_id: PeerId) {
+        if !self.queue.contains(&peer_id) {
+            self.queue.push_back(peer_id);
+        }
+    }
+
+    /// Pops the [`PeerId`] of the earliest postponed `Peer`, if a new
+    /// negotiation can be started while the provided number of negotiations
+    /// is ongoing.
+    pub fn pop_ready(&mut self, negotiating: usize) -> Option<PeerId> {
+        if self.is_full(negotiating) {
+            None
+        } else {
+            self.queue.pop_front()
+        }
+    }
+
+    /// Returns number of the postponed `Peer`s.
+    #[inline]
+    #[must_use]
+    pub fn postponed_count(&self) -> usize {
+        self.queue.len()
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    #[test]
+    fn unlimited_is_never_full() {
+        let mut queue = NegotiationsQueue::new(0);
+
+        assert!(!queue.is_full(usize::MAX));
+        queue.postpone(PeerId(1));
+        assert_eq!(queue.pop_ready(100), Some(PeerId(1)));
+    }
+
+    #[test]
+    fn pops_in_fifo_order_once_slots_free() {
+        let mut queue = NegotiationsQueue::new(2);
+
+        assert!(!queue.is_full(1));
+        assert!(queue.is_full(2));
+
+        queue.postpone(PeerId(3));
+        queue.postpone(PeerId(1));
+        queue.postpone(PeerId(3));
+        assert_eq!(queue.postponed_count(), 2);
+
+        assert_eq!(queue.pop_ready(2), None);
+        assert_eq!(queue.pop_ready(1), Some(PeerId(3)));
+        assert_eq!(queue.pop_ready(0), Some(PeerId(1)));
+        assert_eq!(queue.pop_ready(0), None);
+    }
+}
@@ -0,0 +1,180 @@
+//! Tracking of `PatchTrack` Control API operations, so their callers are
+//! notified once the requested patches are applied by all the affected
+//! `Client`s.
+
+use std::collections::HashSet;
+
+use medea_client_api_proto::{PeerId, TrackId};
+
+use crate::api::control::{callback::CallbackUrl, refs::StatefulFid};
+
+/// `PatchTrack` operation, awaiting its patches to be applied.
+#[derive(Debug)]
+pub struct PatchOperation {
+    /// ID of this [`PatchOperation`].
+    pub id: String,
+
+    /// [`CallbackUrl`] to send `OnPatchApplied` callback to.
+    pub url: CallbackUrl,
+
+    /// [`StatefulFid`] of the patched `Endpoint`.
+    pub fid: StatefulFid,
+
+    /// Tracks of `Peer`s, which `Client`s haven't applied the patch yet.
+    unapplied: HashSet<(PeerId, TrackId)>,
+}
+
+impl PatchOperation {
+    /// Creates a new [`PatchOperation`] awaiting the patches of the provided
+    /// tracks of `Peer`s to be applied.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        id: String,
+        url: CallbackUrl,
+        fid: StatefulFid,
+        unapplied: HashSet<(PeerId, TrackId)>,
+    ) -> Self {
+        Self {
+            id,
+            url,
+            fid,
+            unapplied,
+        }
+    }
+}
+
+/// Ongoing [`PatchOperation`]s of a `Room`.
+#[derive(Debug, Default)]
+pub struct PatchOperations(Vec<PatchOperation>);
+
+impl PatchOperations {
+    /// Starts tracking the provided [`PatchOperation`].
+    ///
+    /// Returns it back if it has nothing to await, so is finished already.
+    pub fn register(&mut self, op: PatchOperation) -> Option<PatchOperation> {
+        if op.unapplied.is_empty() {
+            Some(op)
+        } else {
+            self.0.push(op);
+            None
+        }
+    }
+
+    /// Checks the tracks of the `Peer`s with the provided [`PeerId`]s with
+    /// the provided `is_applied` function, returning `None` if the `Peer` is
+    /// removed.
+    ///
+    /// Returns the finished [`PatchOperation`]s along with their errors, if
+    /// they have failed due to the `Peer` removal.
+    pub fn settle<F>(
+        &mut self,
+        peer_ids: &[PeerId],
+        is_applied: F,
+    ) -> Vec<(PatchOperation, Option<String>)>
+    where
+        F: Fn(PeerId, TrackId) -> Option<bool>,
+    {
+        let mut finished = Vec::new();
+        let mut i = 0;
+        while i < self.0.len() {
+            let op = &mut self.0[i];
+            let mut error = None;
+            op.unapplied.retain(|&(peer_id, track_id)| {
+                if !peer_ids.contains(&peer_id) {
+                    return true;
+                }
+                match is_applied(peer_id, track_id) {
+                    Some(applied) => !applied,
+                    None => {
+                        error = Some(format!(
+                            "Peer [id = {}] was removed before applying the \
+                             patch",
+                            peer_id,
+                        ));
+                        false
+                    }
+                }
+            });
+
+            if error.is_some() || op.unapplied.is_empty() {
+                finished.push((self.0.swap_remove(i), error));
+            } else {
+                i += 1;
+            }
+        }
+        finished
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use std::convert::TryFrom as _;
+
+    use medea_client_api_proto::{MemberId, RoomId};
+
+    use crate::api::control::{
+        refs::{Fid, ToEndpoint},
+        EndpointId,
+    };
+
+    use super::*;
+
+    fn operation(id: &str, tracks: &[(u32, u32)]) -> PatchOperation {
+        PatchOperation::new(
+            id.to_owned(),
+            CallbackUrl::try_from("grpc://127.0.0.1:9099".to_owned())
+                .unwrap(),
+            Fid::<ToEndpoint>::new(
+                RoomId::from("room"),
+                MemberId::from("member"),
+                EndpointId::from(String::from("publish")),
+            )
+            .into(),
+            tracks
+                .iter()
+                .map(|&(peer, track)| (PeerId(peer), TrackId(track)))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn finishes_once_all_applied() {
+        let mut ops = PatchOperations::default();
+        assert!(ops.register(operation("op", &[(1, 0), (2, 0)])).is_none());
+
+        let finished = ops.settle(&[PeerId(1)], |_, _| Some(true));
+        assert!(finished.is_empty());
+        let finished = ops.settle(&[PeerId(2)], |_, _| Some(false));
+        assert!(finished.is_empty());
+
+        let finished = ops.settle(&[PeerId(1), PeerId(2)], |_, _| Some(true));
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].0.id, "op");
+        assert!(finished[0].1.is_none());
+        assert!(ops.settle(&[PeerId(2)], |_, _| Some(true)).is_empty());
+    }
+
+    #[test]
+    fn fails_on_peer_removal() {
+        let mut ops = PatchOperations::default();
+        assert!(ops.register(operation("first", &[(1, 0), (2, 0)])).is_none());
+        assert!(ops.register(operation("second", &[(3, 1)])).is_none());
+
+        let finished = ops.settle(&[PeerId(2)], |_, _| None);
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].0.id, "first");
+        assert!(finished[0].1.is_some());
+
+        let finished = ops.settle(&[PeerId(3)], |_, _| Some(true));
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].0.id, "second");
+    }
+
+    #[test]
+    fn returns_empty_operation() {
+        let mut ops = PatchOperations::default();
+        assert!(ops.register(operation("op", &[])).is_some());
+        assert!(ops.settle(&[PeerId(1)], |_, _| Some(true)).is_empty());
+    }
+}
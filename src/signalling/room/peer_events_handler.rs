@@ -1,19 +1,29 @@
 //! [`PeerConnectionStateEventsHandler`] implementation for [`Room`].
 
-use std::convert::TryInto as _;
+use std::{convert::TryInto as _, rc::Rc};
 
 use actix::{Handler, Message, StreamHandler, WeakAddr};
 use chrono::{DateTime, Utc};
 use medea_client_api_proto::{
     ConnectionQualityScore, Event, MemberId, NegotiationRole, PeerId,
-    PeerUpdate,
+    PeerUpdate, QualityAdvisoryReason, TrackId,
 };
 
 use crate::{
-    api::control::callback::{MediaDirection, MediaType},
+    api::control::{
+        callback::{
+            url::CallbackUrl, CallbackEvent, MediaDirection, MediaType,
+            OnStartEvent, OnStopEvent,
+        },
+        refs::StatefulFid,
+    },
     log::prelude::*,
-    media::{peer::PeerUpdatesSubscriber, Peer, PeerStateMachine, Stable},
+    media::{
+        peer::{compact_updates, PeerUpdatesSubscriber},
+        MediaTrack, Peer, PeerStateMachine, Stable,
+    },
     signalling::{
+        elements::endpoints::Endpoint,
         peers::{
             PeerConnectionStateEventsHandler, PeersMetricsEvent,
             PeersMetricsEventHandler,
@@ -67,6 +77,152 @@ impl Room {
 
         Ok(())
     }
+
+    /// Starts negotiation for the [`Peer`] with provided [`PeerId`].
+    ///
+    /// Sends [`Event::PeerCreated`] if this [`Peer`] unknown for the remote
+    /// side.
+    ///
+    /// Sends [`Event::PeerUpdated`] if this [`Peer`] known for the remote
+    /// side.
+    ///
+    /// If this [`Peer`] or it's partner not [`Stable`] then forcible
+    /// track changes will be committed.
+    ///
+    /// If the limit of simultaneous negotiations in this [`Room`] is reached,
+    /// then the negotiation is postponed until
+    /// [`Room::start_postponed_negotiations()`] finds a free slot for it.
+    ///
+    /// [`Event::PeerCreated`]: medea_client_api_proto::Event::PeerCreated
+    /// [`Event::PeerUpdated`]: medea_client_api_proto::Event::PeerUpdated
+    fn negotiate(&mut self, peer_id: PeerId) -> Result<(), RoomError> {
+        self.peers.update_peer_tracks(peer_id)?;
+
+        // Make sure that both peers are in stable state, if that is not the
+        // case then we just skip this iteration, and wait for next
+        // proc.
+        let peer: Peer<Stable> =
+            if let Ok(peer) = self.peers.take_inner_peer(peer_id) {
+                peer
+            } else {
+                return Ok(());
+            };
+        let is_partner_stable = match self
+            .peers
+            .map_peer_by_id(peer.partner_peer_id(), PeerStateMachine::is_stable)
+        {
+            Ok(r) => r,
+            Err(e) => {
+                self.peers.add_peer(peer);
+
+                return Err(e);
+            }
+        };
+        let is_known_to_remote = peer.is_known_to_remote();
+        self.peers.add_peer(peer);
+
+        if !is_partner_stable {
+            return Ok(());
+        }
+        if self.negotiations.is_full(self.peers.negotiations_count()) {
+            self.negotiations.postpone(peer_id);
+            debug!(
+                "Negotiation of Peer [id = {}] in Room [id = {}] is \
+                 postponed, {} negotiations are waiting for a free slot",
+                peer_id,
+                self.id,
+                self.negotiations.postponed_count(),
+            );
+            return Ok(());
+        }
+
+        if is_known_to_remote {
+            self.send_tracks_applied(peer_id)
+        } else {
+            self.send_peer_created(peer_id)
+        }
+    }
+
+    /// Starts the postponed negotiations of [`Peer`]s, while the limit of
+    /// simultaneous negotiations in this [`Room`] allows to.
+    ///
+    /// Should be called whenever negotiations finish or negotiating [`Peer`]s
+    /// are removed.
+    pub(super) fn start_postponed_negotiations(&mut self) {
+        while let Some(peer_id) =
+            self.negotiations.pop_ready(self.peers.negotiations_count())
+        {
+            if let Err(e) = self.negotiate(peer_id) {
+                debug!(
+                    "Postponed negotiation of Peer [id = {}] in Room [id = {}] \
+                     is discarded: {}",
+                    peer_id, self.id, e,
+                );
+            }
+        }
+    }
+
+    /// Sends traffic Control API callbacks of all the `Endpoint`s of the
+    /// [`Peer`] with the provided [`PeerId`].
+    ///
+    /// [`CallbackUrl`]s are taken from the `Endpoint`s with the provided `url`
+    /// function, and [`CallbackEvent`]s are built with the provided `event`
+    /// function.
+    fn send_traffic_callbacks(
+        &self,
+        peer_id: PeerId,
+        at: DateTime<Utc>,
+        url: fn(&Endpoint) -> Option<CallbackUrl>,
+        event: fn(MediaType, MediaDirection) -> CallbackEvent,
+    ) -> Result<(), RoomError> {
+        let callbacks = self.peers.map_peer_by_id(peer_id, |peer| {
+            let senders = tracks_media_type(peer.senders().values());
+            let receivers = tracks_media_type(peer.receivers().values());
+            peer.endpoints()
+                .into_iter()
+                .filter_map(|endpoint| endpoint.upgrade())
+                .filter_map(|endpoint| {
+                    let url = url(&endpoint)?;
+                    let (fid, media_type, direction) = match endpoint {
+                        Endpoint::WebRtcPublishEndpoint(publish) => (
+                            publish
+                                .owner()
+                                .get_fid_to_endpoint(publish.id().into()),
+                            senders?,
+                            MediaDirection::Publish,
+                        ),
+                        Endpoint::WebRtcPlayEndpoint(play) => (
+                            play.owner().get_fid_to_endpoint(play.id().into()),
+                            receivers?,
+                            MediaDirection::Play,
+                        ),
+                    };
+                    Some((url, StatefulFid::from(fid), media_type, direction))
+                })
+                .collect::<Vec<_>>()
+        })?;
+
+        for (url, fid, media_type, direction) in callbacks {
+            self.callbacks
+                .do_send_at(url, fid, event(media_type, direction), at);
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns [`MediaType`] of all the provided [`MediaTrack`]s.
+///
+/// Returns [`None`] if no [`MediaTrack`]s are provided.
+fn tracks_media_type<'a>(
+    tracks: impl Iterator<Item = &'a Rc<MediaTrack>>,
+) -> Option<MediaType> {
+    tracks
+        .map(|track| MediaType::from(track.media_type()))
+        .fold(None, |acc, kind| match acc {
+            Some(acc) if acc != kind => Some(MediaType::Both),
+            _ => Some(kind),
+        })
 }
 
 impl PeerConnectionStateEventsHandler for WeakAddr<Room> {
@@ -148,6 +304,26 @@ impl PeersMetricsEventHandler for Room {
 
         Ok(())
     }
+
+    /// Sends [`Event::TrackQualityAdvisory`] to the publisher of the advised
+    /// `MediaTrack`.
+    fn on_track_quality_advisory(
+        &mut self,
+        member_id: MemberId,
+        track_id: TrackId,
+        advised_max_bitrate: Option<u32>,
+        reason: QualityAdvisoryReason,
+    ) -> Self::Output {
+        self.members.send_event_to_member(
+            &member_id,
+            Event::TrackQualityAdvisory {
+                track_id,
+                advised_max_bitrate,
+                reason,
+            },
+        );
+        Ok(())
+    }
 }
 
 /// Message which indicates that `Peer` with provided [`PeerId`] has started.
@@ -166,27 +342,82 @@ struct PeerStopped {
     at: DateTime<Utc>,
 }
 
+/// Message which indicates whether traffic of the `Peer` with the provided
+/// [`PeerId`] is relayed via [TURN] server.
+///
+/// [TURN]: https://webrtcglossary.com/turn
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+pub struct PeerRelayed {
+    /// ID of the `Peer` which traffic relaying has changed.
+    pub peer_id: PeerId,
+
+    /// Indicator whether traffic of the `Peer` is relayed.
+    pub relayed: bool,
+}
+
 impl Handler<PeerStarted> for Room {
     type Result = ();
 
+    /// Sends `OnStart` Control API callbacks of the `Endpoint`s of the started
+    /// [`Peer`].
     fn handle(
         &mut self,
-        _: PeerStarted,
+        msg: PeerStarted,
         _: &mut Self::Context,
     ) -> Self::Result {
-        // TODO: Implement PeerStarted logic.
+        let res = self.send_traffic_callbacks(
+            msg.0,
+            Utc::now(),
+            Endpoint::get_on_start,
+            |media_type, direction| {
+                OnStartEvent::new(media_type, direction).into()
+            },
+        );
+        if let Err(e) = res {
+            error!("Failed to handle PeerStarted [id = {}]: {}", msg.0, e);
+        }
     }
 }
 
 impl Handler<PeerStopped> for Room {
     type Result = ();
 
+    /// Sends `OnStop` Control API callbacks of the `Endpoint`s of the stopped
+    /// [`Peer`].
+    fn handle(
+        &mut self,
+        msg: PeerStopped,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let res = self.send_traffic_callbacks(
+            msg.peer_id,
+            msg.at,
+            Endpoint::get_on_stop,
+            |media_type, direction| {
+                OnStopEvent::new(media_type, direction).into()
+            },
+        );
+        if let Err(e) = res {
+            error!(
+                "Failed to handle PeerStopped [id = {}]: {}",
+                msg.peer_id, e,
+            );
+        }
+    }
+}
+
+impl Handler<PeerRelayed> for Room {
+    type Result = ();
+
+    /// Updates relaying of the [`Peer`] in its connection quality estimation.
+    #[inline]
     fn handle(
         &mut self,
-        _: PeerStopped,
+        msg: PeerRelayed,
         _: &mut Self::Context,
     ) -> Self::Result {
-        // TODO: Implement PeerStopped logic.
+        self.peers.update_peer_relayed(msg.peer_id, msg.relayed);
     }
 }
 
@@ -227,7 +458,10 @@ impl Handler<ForceUpdate> for Room {
     type Result = Result<(), RoomError>;
 
     /// Gets [`MemberId`] of the provided [`Peer`] and sends all provided
-    /// [`PeerUpdate`]s to this [`MemberId`] with `negotiation_role: None`.
+    /// [`PeerUpdate`]s (compacted with [`compact_updates()`]) to this
+    /// [`MemberId`] with `negotiation_role: None`.
+    ///
+    /// Settles the `PatchTrack` operations awaiting the provided [`Peer`].
     fn handle(
         &mut self,
         msg: ForceUpdate,
@@ -238,11 +472,13 @@ impl Handler<ForceUpdate> for Room {
                 peer.member_id(),
                 Event::PeerUpdated {
                     peer_id: msg.0,
-                    updates: msg.1,
+                    updates: compact_updates(msg.1),
                     negotiation_role: None,
                 },
             );
-        })
+        })?;
+        self.settle_patch_operations(&[msg.0]);
+        Ok(())
     }
 }
 
@@ -258,58 +494,14 @@ pub struct NegotiationNeeded(pub PeerId);
 impl Handler<NegotiationNeeded> for Room {
     type Result = Result<(), RoomError>;
 
-    /// Starts negotiation for the [`Peer`] with provided [`PeerId`].
-    ///
-    /// Sends [`Event::PeerCreated`] if this [`Peer`] unknown for the remote
-    /// side.
-    ///
-    /// Sends [`Event::PeerUpdated`] if this [`Peer`] known for the remote
-    /// side.
-    ///
-    /// If this [`Peer`] or it's partner not [`Stable`] then forcible
-    /// track changes will be committed.
-    ///
-    /// [`Event::PeerCreated`]: medea_client_api_proto::Event::PeerCreated
-    /// [`Event::PeerUpdated`]: medea_client_api_proto::Event::PeerUpdated
+    /// Starts negotiation for the [`Peer`] with provided [`PeerId`], or
+    /// postpones it if too many [`Peer`]s of this [`Room`] are negotiating
+    /// already.
     fn handle(
         &mut self,
         msg: NegotiationNeeded,
         _: &mut Self::Context,
     ) -> Self::Result {
-        let peer_id = msg.0;
-        self.peers.update_peer_tracks(peer_id)?;
-
-        // Make sure that both peers are in stable state, if that is not the
-        // case then we just skip this iteration, and wait for next
-        // proc.
-        let peer: Peer<Stable> =
-            if let Ok(peer) = self.peers.take_inner_peer(msg.0) {
-                peer
-            } else {
-                return Ok(());
-            };
-        let is_partner_stable = match self
-            .peers
-            .map_peer_by_id(peer.partner_peer_id(), PeerStateMachine::is_stable)
-        {
-            Ok(r) => r,
-            Err(e) => {
-                self.peers.add_peer(peer);
-
-                return Err(e);
-            }
-        };
-        let is_known_to_remote = peer.is_known_to_remote();
-        self.peers.add_peer(peer);
-
-        if is_partner_stable {
-            if is_known_to_remote {
-                self.send_tracks_applied(peer_id)
-            } else {
-                self.send_peer_created(peer_id)
-            }
-        } else {
-            Ok(())
-        }
+        self.negotiate(msg.0)
     }
 }
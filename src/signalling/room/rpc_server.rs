@@ -1,6 +1,11 @@
 //! Implementation of the [`RpcServer`] and related [`Command`]s and functions.
 
-use actix::{ActorTryFutureExt as _, Addr, Handler, WrapFuture};
+use std::net::SocketAddr;
+
+use actix::{
+    Actor, ActorTryFutureExt as _, Addr, AsyncContext as _, Handler,
+    WrapFuture,
+};
 use derive_more::Display;
 use failure::Fail;
 use futures::{
@@ -8,23 +13,30 @@ use futures::{
     FutureExt as _,
 };
 use medea_client_api_proto::{
-    CloseReason, Command, Credential, Event, MemberId, PeerId,
+    state, ClientCapabilities, CloseReason, Command, Credential, Event,
+    MemberId, PeerId,
 };
 
 use crate::{
     api::{
         client::rpc_connection::{
-            ClosedReason, CommandMessage, RpcConnection, RpcConnectionClosed,
-            RpcConnectionEstablished, RpcConnectionSettings, Synchronize,
+            ClosedReason, CommandMessage, Liveness, LivenessUpdated,
+            RpcConnection, RpcConnectionClosed, RpcConnectionEstablished,
+            RpcConnectionSettings, Synchronize,
         },
         control::callback::{OnJoinEvent, OnLeaveReason},
         RpcServer, RpcServerError,
     },
     log::prelude::*,
+    media::sdp_quirks::Browser,
     signalling::room::RoomError,
 };
 
-use super::{ActFuture, Room};
+#[cfg(any(test, feature = "testing"))]
+use super::MessageKind;
+use super::{
+    journal::JournalEventKind, track_patches::Throttled, ActFuture, Room,
+};
 
 /// Error of validating received [`Command`].
 #[derive(Debug, Display, Fail)]
@@ -63,7 +75,11 @@ impl Room {
             | C::MakeSdpAnswer { peer_id, .. }
             | C::SetIceCandidate { peer_id, .. }
             | C::AddPeerConnectionMetrics { peer_id, .. }
-            | C::UpdateTracks { peer_id, .. } => peer_id,
+            | C::UpdateTracks { peer_id, .. }
+            | C::TracksRemovedAck { peer_id, .. } => peer_id,
+            C::SendAppData { .. } | C::RefreshCredentials { .. } => {
+                return Ok(())
+            }
             C::LeaveRoom { .. }
             | C::JoinRoom { .. }
             | C::SynchronizeMe { .. } => unreachable!(
@@ -85,6 +101,40 @@ impl Room {
     }
 }
 
+impl Room {
+    /// Verifies the renewed [`Credential`] provided by the `Member` in the
+    /// middle of its session.
+    ///
+    /// Disconnects the `Member` with [`CloseReason::Rejected`] if the
+    /// [`Credential`] is invalid.
+    fn refresh_credentials(
+        &mut self,
+        member_id: &MemberId,
+        credential: &Credential,
+        ctx: &mut <Self as Actor>::Context,
+    ) {
+        if self
+            .members
+            .get_member_by_id_and_credentials(member_id, credential)
+            .is_ok()
+        {
+            debug!("Member [id = {}] refreshed its credentials", member_id);
+        } else {
+            warn!(
+                "Member [id = {}] provided invalid credentials on refresh in \
+                 Room [id = {}]",
+                member_id, self.id,
+            );
+            self.disconnect_member(
+                member_id,
+                CloseReason::Rejected,
+                Some(OnLeaveReason::Kicked),
+                ctx,
+            );
+        }
+    }
+}
+
 impl RpcServer for Addr<Room> {
     /// Sends [`actix::Message`] to Room actor propagating errors.
     ///
@@ -98,12 +148,16 @@ impl RpcServer for Addr<Room> {
         &self,
         member_id: MemberId,
         credentials: Credential,
+        capabilities: ClientCapabilities,
+        remote_addr: Option<SocketAddr>,
         connection: Box<dyn RpcConnection>,
     ) -> LocalBoxFuture<'static, Result<RpcConnectionSettings, RpcServerError>>
     {
         self.send(RpcConnectionEstablished {
             member_id,
             credentials,
+            capabilities,
+            remote_addr,
             connection,
         })
         .map(|r| {
@@ -134,8 +188,21 @@ impl RpcServer for Addr<Room> {
     }
 
     /// Sends [`actix::Message`] to Room actor ignoring any errors.
-    fn synchronize(&self, member_id: MemberId) -> LocalBoxFuture<'static, ()> {
-        self.send(Synchronize(member_id))
+    fn update_liveness(&self, member_id: MemberId, liveness: Liveness) {
+        self.do_send(LivenessUpdated {
+            member_id,
+            liveness,
+        });
+    }
+
+    /// Sends [`actix::Message`] to Room actor ignoring any errors.
+    fn synchronize(
+        &self,
+        member_id: MemberId,
+        last_event_seq: Option<u64>,
+        state: state::Room,
+    ) -> LocalBoxFuture<'static, ()> {
+        self.send(Synchronize(member_id, last_event_seq, state))
             .map(|res| {
                 if let Err(e) = res {
                     error!("Failed to send Synchronize cause {:?}", e,);
@@ -155,6 +222,12 @@ impl Handler<CommandMessage> for Room {
         msg: CommandMessage,
         ctx: &mut Self::Context,
     ) -> Self::Result {
+        #[cfg(any(test, feature = "testing"))]
+        let msg = match self.chaos.postpone(MessageKind::Command, msg, ctx) {
+            Some(msg) => msg,
+            None => return,
+        };
+
         if let Err(err) = self.validate_command(&msg) {
             warn!(
                 "Ignoring Command from Member [{}] that failed validation \
@@ -165,7 +238,42 @@ impl Handler<CommandMessage> for Room {
         }
 
         let member_id = msg.member_id;
-        let command = msg.command;
+        let command = match msg.command {
+            Command::SendAppData { to, data } => {
+                self.members.relay_app_data(&member_id, to, data);
+                return;
+            }
+            Command::RefreshCredentials { credential } => {
+                self.refresh_credentials(&member_id, &credential, ctx);
+                return;
+            }
+            Command::UpdateTracks {
+                peer_id,
+                tracks_patches,
+            } => match self.track_patches.throttle(
+                &member_id,
+                peer_id,
+                tracks_patches,
+            ) {
+                Throttled::Apply(tracks_patches) => Command::UpdateTracks {
+                    peer_id,
+                    tracks_patches,
+                },
+                Throttled::Postponed(flush_in) => {
+                    if let Some(flush_in) = flush_in {
+                        let task = ctx.run_later(flush_in, {
+                            let member_id = member_id.clone();
+                            move |this, _| {
+                                this.flush_track_patches(&member_id);
+                            }
+                        });
+                        self.track_patches.flush_scheduled(member_id, task);
+                    }
+                    return;
+                }
+            },
+            command => command,
+        };
         if let Err(err) = command.dispatch_with(self) {
             error!(
                 "Error when handling command from Member [id = {}] in Room \
@@ -182,21 +290,53 @@ impl Handler<CommandMessage> for Room {
     }
 }
 
+impl Handler<LivenessUpdated> for Room {
+    type Result = ();
+
+    /// Stores the provided [`Liveness`] metrics in the `Member`.
+    fn handle(
+        &mut self,
+        msg: LivenessUpdated,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        if let Ok(member) = self.members.get_member_by_id(&msg.member_id) {
+            member.set_liveness(msg.liveness);
+        }
+    }
+}
+
 impl Handler<Synchronize> for Room {
     type Result = ();
 
-    /// Generates [`state::Room`] for the provided [`MemberId`] and sends
-    /// [`Event::StateSynchronized`].
+    /// Replays the [`Event`]s missed by the `Member` with the provided
+    /// [`MemberId`], if it has restored its connection and all of them are
+    /// still kept.
+    ///
+    /// Otherwise, generates [`state::Room`] for this `Member` and sends
+    /// [`Event::StateDeltaSynchronized`] relative to the [`state::Room`]
+    /// restored by this `Member`, or [`Event::StateSynchronized`] if the
+    /// restored [`state::Room`] is empty.
     ///
-    /// [`state::Room`]: medea_client_api_proto::state::Room
+    /// In both cases, reserves the `Peer` and `Track` IDs of the restored
+    /// [`state::Room`] unknown to this [`Room`], so they are not reused.
     fn handle(
         &mut self,
         msg: Synchronize,
         _: &mut Self::Context,
     ) -> Self::Result {
+        self.peers.reserve_restored_ids(&msg.2);
+        if self.members.replay_missed_events(&msg.0, msg.1) {
+            return;
+        }
         let state = self.get_state(&msg.0);
-        self.members
-            .send_event_to_member(&msg.0, Event::StateSynchronized { state });
+        let event = if msg.2.peers.is_empty() {
+            Event::StateSynchronized { state }
+        } else {
+            Event::StateDeltaSynchronized {
+                delta: msg.2.diff(&state),
+            }
+        };
+        self.members.send_event_to_member(&msg.0, event);
     }
 }
 
@@ -207,9 +347,15 @@ impl Handler<RpcConnectionEstablished> for Room {
     /// media establishment between members.
     /// Creates and interconnects all available `Member`'s `Peer`s.
     ///
+    /// Remembers [`ClientCapabilities`] reported by the `Member`, and
+    /// [`SdpQuirks`] of its user agent, so its SDPs are checked against its
+    /// dialect. Reports them in [`OnJoinEvent`] along with the remote address
+    /// of the `Member`'s `Client`.
+    ///
     /// Returns [`RpcConnectionSettings`] of the connected `Member`.
     ///
     /// [`RpcConnection`]: crate::api::client::rpc_connection::RpcConnection
+    /// [`SdpQuirks`]: crate::media::SdpQuirks
     /// [1]: crate::signalling::participants::ParticipantService
     fn handle(
         &mut self,
@@ -227,16 +373,50 @@ impl Handler<RpcConnectionEstablished> for Room {
             .get_member_by_id_and_credentials(&member_id, &credentials));
 
         let is_reconnect = self.members.member_has_connection(&member_id);
+        if self.drain.is_draining()
+            && !self.members.member_has_any_connection(&member_id)
+        {
+            info!(
+                "Rejecting Member [id = {}], since media server is draining",
+                member_id,
+            );
+            return Box::pin(future::err(RoomError::Draining));
+        }
+        if let Err(retry_after) = self.admission.try_admit() {
+            info!(
+                "Rejecting Member [id = {}], since admission rate is \
+                 exceeded, retry after {:?}",
+                member_id, retry_after,
+            );
+            return Box::pin(future::err(RoomError::Overloaded(retry_after)));
+        }
+
+        let user_agent = msg.capabilities.user_agent.clone();
+        self.members
+            .set_capabilities(member_id.clone(), msg.capabilities);
+        self.members
+            .set_remote_addr(member_id.clone(), msg.remote_addr);
+        let browser = self.members.sdp_quirks(&member_id).browser();
+        if let Some(user_agent) = user_agent {
+            if browser == Browser::Unknown {
+                info!(
+                    "Member [id = {}] connected with unknown user agent `{}`, \
+                     its SDPs are expected to be in libwebrtc dialect",
+                    member_id, user_agent,
+                );
+            }
+        }
 
         let maybe_send_on_join = match (member.get_on_join(), is_reconnect) {
             (Some(callback_url), false) => future::Either::Left({
                 let callback_service = self.callbacks.clone();
+                let session = self.members.client_session(&member_id);
                 async move {
                     callback_service
                         .send(
                             callback_url,
                             member.get_fid().into(),
-                            OnJoinEvent,
+                            OnJoinEvent::new(session),
                         )
                         .await
                 }
@@ -254,11 +434,21 @@ impl Handler<RpcConnectionEstablished> for Room {
                         .err_into()
                         .into_actor(this)
                 })
-                .and_then(|member, this, _| {
+                .and_then(move |member, this, _| {
+                    if !is_reconnect {
+                        this.journal.record(
+                            JournalEventKind::MemberJoined,
+                            member.get_fid().into(),
+                            None,
+                        );
+                    }
+                    this.members.send_members_metadata(&member.id());
+                    let features = this.members.get_features(&member);
                     this.init_member_connections(&member).map_ok(
                         move |_, _, _| RpcConnectionSettings {
                             idle_timeout: member.get_idle_timeout(),
                             ping_interval: member.get_ping_interval(),
+                            features,
                         },
                     )
                 }),
@@ -309,7 +499,7 @@ impl Handler<RpcConnectionClosed> for Room {
 
 #[cfg(test)]
 mod test {
-    use std::collections::HashMap;
+    use std::{cell::RefCell, collections::HashMap, time::Duration};
 
     use medea_client_api_proto::{IceCandidate, RoomId};
 
@@ -324,6 +514,10 @@ mod test {
         signalling::{
             participants::ParticipantService,
             peers::{build_peers_traffic_watcher, PeersService},
+            room::{
+                journal::Journal, negotiations::NegotiationsQueue,
+                track_patches::TrackPatchesThrottle, TakeSnapshot,
+            },
         },
         AppContext,
     };
@@ -349,6 +543,20 @@ mod test {
             ),
             members: ParticipantService::new(&room_spec, &context).unwrap(),
             callbacks: context.callbacks.clone(),
+            state_checksum_interval: context.config.rpc.state_checksum_interval,
+            negotiation_timeout: context.config.media.negotiation_timeout,
+            negotiations: NegotiationsQueue::new(
+                context.config.media.max_concurrent_negotiations,
+            ),
+            track_patches: TrackPatchesThrottle::new(
+                context.config.rpc.update_tracks_rate_limit,
+            ),
+            patch_operations: RefCell::default(),
+            peer_linger_period: context.config.media.peer_linger_period,
+            journal: Journal::new(0),
+            chaos: context.chaos.clone(),
+            drain: context.drain.clone(),
+            admission: context.admission.clone(),
         }
     }
 
@@ -434,6 +642,40 @@ mod test {
         ));
     }
 
+    #[actix_rt::test]
+    async fn liveness_is_exposed_in_snapshot() {
+        let mut room = empty_room();
+
+        let member1 = MemberSpec::new(
+            Pipeline::new(HashMap::new()),
+            Credential::Plain(String::from("w/e")),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        room.members
+            .create_member(MemberId::from("member1"), &member1)
+            .unwrap();
+        let room = room.start();
+
+        room.send(LivenessUpdated {
+            member_id: MemberId::from("member1"),
+            liveness: Liveness {
+                missed_pongs: 2,
+                rtt: Some(Duration::from_millis(150)),
+            },
+        })
+        .await
+        .unwrap();
+        let snapshot = room.send(TakeSnapshot).await.unwrap();
+
+        assert_eq!(snapshot.members[0].missed_pongs, 2);
+        assert_eq!(snapshot.members[0].rtt_ms, Some(150));
+    }
+
     mod callbacks {
         use std::convert::TryFrom;
 
@@ -481,9 +723,12 @@ mod test {
                 credentials: Credential::Plain(String::from("test")),
                 on_leave,
                 on_join,
+                on_negotiation_timeout: None,
                 idle_timeout: None,
                 reconnect_timeout: None,
                 ping_interval: None,
+                metadata: HashMap::new(),
+                features: HashMap::new(),
             };
             RoomSpec {
                 id: RoomId::from("test"),
@@ -530,6 +775,8 @@ mod test {
                 room.connection_established(
                     MemberId::from("member"),
                     client_proto::Credential::from("test"),
+                    client_proto::ClientCapabilities::default(),
+                    None,
                     Box::new(MockRpcConnection::new()),
                 )
                 .await
@@ -554,14 +801,14 @@ mod test {
                     .expect_close()
                     .with(
                         eq(RoomId::from("test")),
-                        eq(CloseDescription {
-                            reason: CloseReason::Reconnected,
-                        }),
+                        eq(CloseDescription::new(CloseReason::Reconnected)),
                     )
                     .return_once(|_, _| Box::pin(future::ready(())));
                 room.connection_established(
                     MemberId::from("member"),
                     client_proto::Credential::from("test"),
+                    client_proto::ClientCapabilities::default(),
+                    None,
                     Box::new(rpc_connection),
                 )
                 .await
@@ -569,6 +816,8 @@ mod test {
                 room.connection_established(
                     MemberId::from("member"),
                     client_proto::Credential::from("test"),
+                    client_proto::ClientCapabilities::default(),
+                    None,
                     Box::new(MockRpcConnection::new()),
                 )
                 .await
@@ -587,6 +836,8 @@ mod test {
                 room.connection_established(
                     MemberId::from("member"),
                     client_proto::Credential::from("test"),
+                    client_proto::ClientCapabilities::default(),
+                    None,
                     Box::new(MockRpcConnection::new()),
                 )
                 .await
@@ -609,6 +860,8 @@ mod test {
                 room.connection_established(
                     MemberId::from("member"),
                     client_proto::Credential::from("test"),
+                    client_proto::ClientCapabilities::default(),
+                    None,
                     Box::new(MockRpcConnection::new()),
                 )
                 .await
@@ -628,6 +881,8 @@ mod test {
                 room.connection_established(
                     MemberId::from("member"),
                     client_proto::Credential::from("test"),
+                    client_proto::ClientCapabilities::default(),
+                    None,
                     Box::new(MockRpcConnection::new()),
                 )
                 .await
@@ -635,4 +890,494 @@ mod test {
             }
         }
     }
+
+    mod refresh_credentials {
+        use medea_client_api_proto::{self as client_proto, CloseDescription};
+        use mockall::predicate::eq;
+
+        use crate::api::{
+            client::rpc_connection::MockRpcConnection,
+            control::{member::Credential, RoomElement},
+        };
+
+        use super::*;
+
+        async fn start_room(connection: MockRpcConnection) -> Addr<Room> {
+            let member = RoomElement::Member {
+                spec: Pipeline::new(HashMap::new()),
+                credentials: Credential::Plain(String::from("test")),
+                on_leave: None,
+                on_join: None,
+                on_negotiation_timeout: None,
+                idle_timeout: None,
+                reconnect_timeout: None,
+                ping_interval: None,
+                metadata: HashMap::new(),
+                features: HashMap::new(),
+            };
+            let room_spec = RoomSpec {
+                id: RoomId::from("test"),
+                pipeline: Pipeline::new(
+                    hashmap! {MemberId::from("member") => member},
+                ),
+            };
+            let app_ctx = AppContext::new(
+                Conf::default(),
+                crate::turn::new_turn_auth_service_mock(),
+            );
+            let room = Room::start(
+                &room_spec,
+                &app_ctx,
+                build_peers_traffic_watcher(&app_ctx.config.media),
+            )
+            .unwrap();
+
+            room.connection_established(
+                MemberId::from("member"),
+                client_proto::Credential::from("test"),
+                client_proto::ClientCapabilities::default(),
+                None,
+                Box::new(connection),
+            )
+            .await
+            .unwrap();
+            room
+        }
+
+        fn refresh(credential: &str) -> CommandMessage {
+            CommandMessage::new(
+                MemberId::from("member"),
+                Command::RefreshCredentials {
+                    credential: client_proto::Credential::from(credential),
+                },
+            )
+        }
+
+        #[actix_rt::test]
+        async fn keeps_member_with_valid_credential() {
+            let room = start_room(MockRpcConnection::new()).await;
+
+            room.send(refresh("test")).await.unwrap();
+        }
+
+        #[actix_rt::test]
+        async fn disconnects_member_with_invalid_credential() {
+            let mut connection = MockRpcConnection::new();
+            connection
+                .expect_close()
+                .with(
+                    eq(RoomId::from("test")),
+                    eq(CloseDescription::new(CloseReason::Rejected)),
+                )
+                .times(1)
+                .return_once(|_, _| Box::pin(future::ready(())));
+            let room = start_room(connection).await;
+
+            room.send(refresh("invalid")).await.unwrap();
+        }
+    }
+
+    mod app_data {
+        use std::sync::{Arc, Mutex};
+
+        use medea_client_api_proto as client_proto;
+
+        use crate::api::{
+            client::rpc_connection::MockRpcConnection,
+            control::{member::Credential, RoomElement},
+        };
+
+        use super::*;
+
+        /// Returns a [`MockRpcConnection`] recording all the [`Event`]s sent
+        /// via it into the provided list.
+        fn recording_connection(
+            events: Arc<Mutex<Vec<Event>>>,
+        ) -> MockRpcConnection {
+            let mut connection = MockRpcConnection::new();
+            connection.expect_send_event().returning(move |_, event, _| {
+                events.lock().unwrap().push(event);
+            });
+            connection
+        }
+
+        async fn start_room(
+            alice: MockRpcConnection,
+            bob: MockRpcConnection,
+        ) -> Addr<Room> {
+            let member = || RoomElement::Member {
+                spec: Pipeline::new(HashMap::new()),
+                credentials: Credential::Plain(String::from("test")),
+                on_leave: None,
+                on_join: None,
+                on_negotiation_timeout: None,
+                idle_timeout: None,
+                reconnect_timeout: None,
+                ping_interval: None,
+                metadata: HashMap::new(),
+                features: HashMap::new(),
+            };
+            let room_spec = RoomSpec {
+                id: RoomId::from("test"),
+                pipeline: Pipeline::new(hashmap! {
+                    MemberId::from("alice") => member(),
+                    MemberId::from("bob") => member(),
+                }),
+            };
+            let app_ctx = AppContext::new(
+                Conf::default(),
+                crate::turn::new_turn_auth_service_mock(),
+            );
+            let room = Room::start(
+                &room_spec,
+                &app_ctx,
+                build_peers_traffic_watcher(&app_ctx.config.media),
+            )
+            .unwrap();
+
+            let connect = |id: &str, connection: MockRpcConnection| {
+                room.connection_established(
+                    MemberId::from(id),
+                    client_proto::Credential::from("test"),
+                    client_proto::ClientCapabilities::default(),
+                    None,
+                    Box::new(connection),
+                )
+            };
+            connect("alice", alice).await.unwrap();
+            connect("bob", bob).await.unwrap();
+            room
+        }
+
+        fn received_app_data() -> Event {
+            Event::AppDataReceived {
+                from: MemberId::from("alice"),
+                data: r#"{"foo":"bar"}"#.into(),
+            }
+        }
+
+        #[actix_rt::test]
+        async fn relays_app_data_to_recipient() {
+            let alice_events = Arc::new(Mutex::new(Vec::new()));
+            let bob_events = Arc::new(Mutex::new(Vec::new()));
+            let room = start_room(
+                recording_connection(Arc::clone(&alice_events)),
+                recording_connection(Arc::clone(&bob_events)),
+            )
+            .await;
+
+            room.send(CommandMessage::new(
+                MemberId::from("alice"),
+                Command::SendAppData {
+                    to: Some(MemberId::from("bob")),
+                    data: r#"{"foo":"bar"}"#.into(),
+                },
+            ))
+            .await
+            .unwrap();
+
+            let received = received_app_data();
+            assert!(bob_events.lock().unwrap().contains(&received));
+            assert!(!alice_events.lock().unwrap().contains(&received));
+        }
+    }
+
+    mod negotiations {
+        use std::{
+            convert::TryFrom,
+            sync::{Arc, Mutex},
+        };
+
+        use medea_client_api_proto as client_proto;
+
+        use crate::api::{
+            client::rpc_connection::MockRpcConnection, control::RootElement,
+        };
+
+        use super::*;
+
+        /// [`RoomSpec`] with `alice` publishing to both `bob` and `carol`.
+        const ROOM_SPEC: &str = r#"
+kind: Room
+id: test
+spec:
+  pipeline:
+    alice:
+      kind: Member
+      credentials:
+        plain: test
+      spec:
+        pipeline:
+          publish:
+            kind: WebRtcPublishEndpoint
+            spec:
+              p2p: Always
+    bob:
+      kind: Member
+      credentials:
+        plain: test
+      spec:
+        pipeline:
+          play:
+            kind: WebRtcPlayEndpoint
+            spec:
+              src: "local://test/alice/publish"
+    carol:
+      kind: Member
+      credentials:
+        plain: test
+      spec:
+        pipeline:
+          play:
+            kind: WebRtcPlayEndpoint
+            spec:
+              src: "local://test/alice/publish"
+"#;
+
+        /// Returns a [`MockRpcConnection`] recording all the [`Event`]s sent
+        /// via it into the provided list.
+        fn recording_connection(
+            events: Arc<Mutex<Vec<Event>>>,
+        ) -> MockRpcConnection {
+            let mut connection = MockRpcConnection::new();
+            connection.expect_send_event().returning(move |_, event, _| {
+                events.lock().unwrap().push(event);
+            });
+            connection
+                .expect_close()
+                .returning(|_, _| Box::pin(future::ready(())));
+            connection
+        }
+
+        /// Returns number of [`Event::PeerCreated`]s in the provided list.
+        fn peers_created(events: &Mutex<Vec<Event>>) -> usize {
+            events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|e| matches!(e, Event::PeerCreated { .. }))
+                .count()
+        }
+
+        #[actix_rt::test]
+        async fn postpones_negotiations_over_limit() {
+            let mut conf = Conf::default();
+            conf.media.max_concurrent_negotiations = 1;
+            let app_ctx = AppContext::new(
+                conf,
+                crate::turn::new_turn_auth_service_mock(),
+            );
+            let parsed: RootElement = serde_yaml::from_str(ROOM_SPEC).unwrap();
+            let room = Room::start(
+                &RoomSpec::try_from(&parsed).unwrap(),
+                &app_ctx,
+                build_peers_traffic_watcher(&app_ctx.config.media),
+            )
+            .unwrap();
+
+            let alice_events = Arc::new(Mutex::new(Vec::new()));
+            for id in &["alice", "bob", "carol"] {
+                let events = if *id == "alice" {
+                    Arc::clone(&alice_events)
+                } else {
+                    Arc::default()
+                };
+                room.connection_established(
+                    MemberId::from(*id),
+                    client_proto::Credential::from("test"),
+                    client_proto::ClientCapabilities::default(),
+                    None,
+                    Box::new(recording_connection(events)),
+                )
+                .await
+                .unwrap();
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+
+            assert_eq!(peers_created(&alice_events), 1);
+
+            room.send(RpcConnectionClosed {
+                member_id: MemberId::from("bob"),
+                reason: ClosedReason::Closed { normal: true },
+            })
+            .await
+            .unwrap();
+            tokio::time::sleep(Duration::from_millis(50)).await;
+
+            assert_eq!(peers_created(&alice_events), 2);
+        }
+    }
+
+    mod track_patches {
+        use std::{
+            convert::TryFrom,
+            sync::{Arc, Mutex},
+        };
+
+        use medea_client_api_proto::{
+            self as client_proto, TrackId, TrackPatchCommand,
+        };
+
+        use crate::api::{
+            client::rpc_connection::MockRpcConnection, control::RootElement,
+        };
+
+        use super::*;
+
+        /// [`RoomSpec`] with `alice` publishing to `bob`.
+        const ROOM_SPEC: &str = r#"
+kind: Room
+id: test
+spec:
+  pipeline:
+    alice:
+      kind: Member
+      credentials:
+        plain: test
+      spec:
+        pipeline:
+          publish:
+            kind: WebRtcPublishEndpoint
+            spec:
+              p2p: Always
+    bob:
+      kind: Member
+      credentials:
+        plain: test
+      spec:
+        pipeline:
+          play:
+            kind: WebRtcPlayEndpoint
+            spec:
+              src: "local://test/alice/publish"
+"#;
+
+        /// Returns a [`MockRpcConnection`] recording all the [`Event`]s sent
+        /// via it into the provided list.
+        fn recording_connection(
+            events: Arc<Mutex<Vec<Event>>>,
+        ) -> MockRpcConnection {
+            let mut connection = MockRpcConnection::new();
+            connection.expect_send_event().returning(move |_, event, _| {
+                events.lock().unwrap().push(event);
+            });
+            connection
+                .expect_close()
+                .returning(|_, _| Box::pin(future::ready(())));
+            connection
+        }
+
+        /// Connects `Member` with the provided ID to the provided [`Room`],
+        /// returning the list of [`Event`]s it receives.
+        async fn connect(
+            room: &Addr<Room>,
+            id: &str,
+        ) -> Arc<Mutex<Vec<Event>>> {
+            let events = Arc::new(Mutex::new(Vec::new()));
+            room.connection_established(
+                MemberId::from(id),
+                client_proto::Credential::from("test"),
+                client_proto::ClientCapabilities::default(),
+                None,
+                Box::new(recording_connection(Arc::clone(&events))),
+            )
+            .await
+            .unwrap();
+            events
+        }
+
+        /// Returns [`PeerId`] and [`TrackId`] of some `Track` of the `Peer`
+        /// created in the provided list of [`Event`]s.
+        fn created_track(events: &Mutex<Vec<Event>>) -> (PeerId, TrackId) {
+            events
+                .lock()
+                .unwrap()
+                .iter()
+                .find_map(|e| match e {
+                    Event::PeerCreated {
+                        peer_id, tracks, ..
+                    } => Some((*peer_id, tracks[0].id)),
+                    _ => None,
+                })
+                .unwrap()
+        }
+
+        /// Returns number of [`Event::PeerUpdated`]s in the provided list.
+        fn peers_updated(events: &Mutex<Vec<Event>>) -> usize {
+            events
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|e| matches!(e, Event::PeerUpdated { .. }))
+                .count()
+        }
+
+        /// Returns `UpdateTracks` command of `alice`, enabling or disabling
+        /// the `Track` with the provided [`TrackId`].
+        fn set_enabled(
+            peer_id: PeerId,
+            track_id: TrackId,
+            enabled: bool,
+        ) -> CommandMessage {
+            CommandMessage::new(
+                MemberId::from("alice"),
+                Command::UpdateTracks {
+                    peer_id,
+                    tracks_patches: vec![TrackPatchCommand {
+                        id: track_id,
+                        enabled: Some(enabled),
+                        muted: None,
+                    }],
+                },
+            )
+        }
+
+        #[actix_rt::test]
+        async fn drops_postponed_patches_on_reconnect() {
+            let mut conf = Conf::default();
+            conf.rpc.update_tracks_rate_limit = 1;
+            let app_ctx = AppContext::new(
+                conf,
+                crate::turn::new_turn_auth_service_mock(),
+            );
+            let parsed: RootElement = serde_yaml::from_str(ROOM_SPEC).unwrap();
+            let room = Room::start(
+                &RoomSpec::try_from(&parsed).unwrap(),
+                &app_ctx,
+                build_peers_traffic_watcher(&app_ctx.config.media),
+            )
+            .unwrap();
+
+            let _bob_events = connect(&room, "bob").await;
+            let alice_events = connect(&room, "alice").await;
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let (peer_id, track_id) = created_track(&alice_events);
+
+            // The second patch is postponed till the end of the window.
+            room.send(set_enabled(peer_id, track_id, false)).await.unwrap();
+            room.send(set_enabled(peer_id, track_id, true)).await.unwrap();
+            room.send(RpcConnectionClosed {
+                member_id: MemberId::from("alice"),
+                reason: ClosedReason::Closed { normal: true },
+            })
+            .await
+            .unwrap();
+            tokio::time::sleep(Duration::from_millis(500)).await;
+
+            let alice_events = connect(&room, "alice").await;
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let (peer_id, track_id) = created_track(&alice_events);
+            room.send(set_enabled(peer_id, track_id, false)).await.unwrap();
+            room.send(set_enabled(peer_id, track_id, true)).await.unwrap();
+            assert_eq!(peers_updated(&alice_events), 1);
+
+            // The flush scheduled in the previous session must not apply the
+            // patch postponed in the new one before its window ends.
+            tokio::time::sleep(Duration::from_millis(700)).await;
+            assert_eq!(peers_updated(&alice_events), 1);
+
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            assert_eq!(peers_updated(&alice_events), 2);
+        }
+    }
 }
@@ -0,0 +1,305 @@
+//! Snapshot of a live [`Room`] state, dumped via [Control API] to debug stuck
+//! negotiations offline.
+//!
+//! [`Room`]: crate::signalling::Room
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+use std::time::Instant;
+
+use actix::{Handler, Message, MessageResult};
+use chrono::Utc;
+use medea_client_api_proto::NegotiationRole;
+use serde::Serialize;
+
+use crate::{
+    media::{
+        peer::{PeerChange, SelectedCandidatePair},
+        PeerStateMachine,
+    },
+    signalling::elements::Member,
+};
+
+use super::Room;
+
+/// Snapshot of a whole [`Room`] state.
+#[derive(Clone, Debug, Serialize)]
+pub struct RoomSnapshot {
+    /// ID of the snapshotted [`Room`].
+    pub room_id: String,
+
+    /// [RFC 3339] date and time this [`RoomSnapshot`] was taken at.
+    ///
+    /// [RFC 3339]: https://tools.ietf.org/html/rfc3339
+    pub taken_at: String,
+
+    /// [`MemberSnapshot`]s of all the `Member`s of the [`Room`], ordered by
+    /// their IDs.
+    pub members: Vec<MemberSnapshot>,
+
+    /// [`PeerSnapshot`]s of all the `Peer`s of the [`Room`], ordered by their
+    /// IDs.
+    pub peers: Vec<PeerSnapshot>,
+
+    /// Summary of the metrics collected for the `Peer`s of the [`Room`].
+    pub metrics: MetricsSnapshot,
+}
+
+/// Snapshot of a `Member` state.
+#[derive(Clone, Debug, Serialize)]
+pub struct MemberSnapshot {
+    /// ID of the `Member`.
+    pub id: String,
+
+    /// Indicator whether the `Member` has an active `RpcConnection`.
+    pub connected: bool,
+
+    /// IDs of the `WebRtcPublishEndpoint`s of the `Member`.
+    pub publish_endpoints: Vec<String>,
+
+    /// IDs of the `WebRtcPlayEndpoint`s of the `Member`.
+    pub play_endpoints: Vec<String>,
+
+    /// Number of consecutive `Ping`s left unanswered by the `Member`, as last
+    /// reported by its `RpcConnection`.
+    pub missed_pongs: u32,
+
+    /// Round-trip time (in milliseconds) of the last `Ping` answered by the
+    /// `Member`, if measured.
+    pub rtt_ms: Option<u64>,
+}
+
+impl MemberSnapshot {
+    /// Takes a [`MemberSnapshot`] of the provided [`Member`].
+    fn new(member: &Member, connected: bool) -> Self {
+        let mut publish_endpoints: Vec<_> =
+            member.srcs_ids().iter().map(ToString::to_string).collect();
+        publish_endpoints.sort();
+        let mut play_endpoints: Vec<_> =
+            member.sinks_ids().iter().map(ToString::to_string).collect();
+        play_endpoints.sort();
+        let liveness = member.get_liveness();
+
+        Self {
+            id: member.id().to_string(),
+            connected,
+            publish_endpoints,
+            play_endpoints,
+            missed_pongs: liveness.missed_pongs,
+            rtt_ms: liveness.rtt.map(|rtt| rtt.as_millis() as u64),
+        }
+    }
+}
+
+/// Snapshot of a `Peer` state.
+#[derive(Clone, Debug, Serialize)]
+pub struct PeerSnapshot {
+    /// ID of the `Peer`.
+    pub id: u32,
+
+    /// ID of the `Member` owning the `Peer`.
+    pub member_id: String,
+
+    /// ID of the partner `Peer`.
+    pub partner_peer_id: u32,
+
+    /// ID of the `Member` owning the partner `Peer`.
+    pub partner_member_id: String,
+
+    /// State of the `Peer`'s state machine (`Stable`, `WaitLocalSdp` or
+    /// `WaitRemoteSdp`).
+    pub state: String,
+
+    /// Role of the `Peer` in its ongoing negotiation (`Offerer` or
+    /// `Answerer`), if any.
+    pub negotiation_role: Option<String>,
+
+    /// Duration (in milliseconds) of the `Peer`'s ongoing negotiation, if
+    /// any.
+    pub negotiating_for_ms: Option<u64>,
+
+    /// Indicator whether the `Peer` was created on its `Client`.
+    pub known_to_remote: bool,
+
+    /// Indicator whether a new negotiation of the `Peer` will be started once
+    /// the ongoing one finishes.
+    pub renegotiation_scheduled: bool,
+
+    /// Indicator whether an ICE restart is requested for the `Peer`.
+    pub ice_restart: bool,
+
+    /// Indicator whether all media of the `Peer` is forcibly relayed through
+    /// a TURN server.
+    pub force_relayed: bool,
+
+    /// ICE candidate pair currently used by the `Peer` for media
+    /// transmission, if its `Client` has reported any.
+    pub selected_candidate_pair: Option<SelectedCandidatePair>,
+
+    /// Local SDP of the `Peer`, if any.
+    pub local_sdp: Option<String>,
+
+    /// Remote SDP of the `Peer`, if any.
+    pub remote_sdp: Option<String>,
+
+    /// IDs of the tracks sent by the `Peer`, in ascending order.
+    pub senders: Vec<u32>,
+
+    /// IDs of the tracks received by the `Peer`, in ascending order.
+    pub receivers: Vec<u32>,
+
+    /// Changes applied to the `Peer`, which its `Client` is not aware of yet.
+    pub pending_changes: Vec<String>,
+
+    /// Changes scheduled to be applied once the `Peer` is `Stable`.
+    pub scheduled_changes: Vec<String>,
+
+    /// IDs of the removed tracks, which `Client` hasn't acknowledged
+    /// releasing resources of yet, in ascending order.
+    pub unacked_removed_tracks: Vec<u32>,
+
+    /// Number of ICE candidates received for the `Peer`.
+    pub ice_candidates: usize,
+}
+
+impl PeerSnapshot {
+    /// Takes a [`PeerSnapshot`] of the provided [`PeerStateMachine`] at the
+    /// provided moment.
+    #[must_use]
+    pub fn new(peer: &PeerStateMachine, now: Instant) -> Self {
+        /// Collects the provided IDs in ascending order.
+        fn sorted(ids: impl Iterator<Item = u32>) -> Vec<u32> {
+            let mut ids: Vec<_> = ids.collect();
+            ids.sort_unstable();
+            ids
+        }
+
+        /// Returns [`Debug`] representations of the provided [`PeerChange`]s.
+        fn describe(changes: &[PeerChange]) -> Vec<String> {
+            changes.iter().map(|c| format!("{:?}", c)).collect()
+        }
+
+        Self {
+            id: peer.id().0,
+            member_id: peer.member_id().to_string(),
+            partner_peer_id: peer.partner_peer_id().0,
+            partner_member_id: peer.partner_member_id().to_string(),
+            state: peer.to_string(),
+            negotiation_role: peer.negotiation_role().map(|role| {
+                match role {
+                    NegotiationRole::Offerer => "Offerer",
+                    NegotiationRole::Answerer(_) => "Answerer",
+                }
+                .to_owned()
+            }),
+            negotiating_for_ms: peer
+                .negotiation_started_at()
+                .map(|at| now.saturating_duration_since(at).as_millis() as u64),
+            known_to_remote: peer.is_known_to_remote(),
+            renegotiation_scheduled: peer.is_renegotiation_scheduled(),
+            ice_restart: peer.is_ice_restart(),
+            force_relayed: peer.is_force_relayed(),
+            selected_candidate_pair: peer.selected_candidate_pair().cloned(),
+            local_sdp: peer.local_sdp().map(ToOwned::to_owned),
+            remote_sdp: peer.remote_sdp().map(ToOwned::to_owned),
+            senders: sorted(peer.senders().keys().map(|id| id.0)),
+            receivers: sorted(peer.receivers().keys().map(|id| id.0)),
+            pending_changes: describe(peer.pending_changes()),
+            scheduled_changes: describe(peer.scheduled_changes()),
+            unacked_removed_tracks: sorted(
+                peer.unacked_removed_tracks().iter().map(|id| id.0),
+            ),
+            ice_candidates: peer.ice_candidates().len(),
+        }
+    }
+}
+
+/// Summary of the metrics collected for the `Peer`s of a [`Room`].
+#[derive(Clone, Debug, Serialize)]
+pub struct MetricsSnapshot {
+    /// Number of entries kept for calculating metrics of the `Peer`s.
+    pub entries: usize,
+
+    /// Number of entries evicted from the ones kept for calculating metrics
+    /// of the `Peer`s due to the configured retention.
+    pub evicted: u64,
+
+    /// Latest connection quality scores estimated between `Member`s, ordered
+    /// by IDs of the `Member`s.
+    pub quality: Vec<QualitySnapshot>,
+}
+
+/// Latest connection quality score estimated between two `Member`s.
+#[derive(Clone, Debug, Serialize)]
+pub struct QualitySnapshot {
+    /// ID of the `Member` the score is estimated for.
+    pub member_id: String,
+
+    /// ID of the partner `Member` the score is estimated with.
+    pub partner_member_id: String,
+
+    /// Estimated connection quality score.
+    pub score: String,
+
+    /// Indicator whether the connection was relayed via TURN server at the
+    /// moment of the score estimation.
+    pub relayed: bool,
+
+    /// [RFC 3339] date and time the score was estimated at.
+    ///
+    /// [RFC 3339]: https://tools.ietf.org/html/rfc3339
+    pub at: String,
+}
+
+/// Message for taking a [`RoomSnapshot`] of this [`Room`].
+#[derive(Message, Debug)]
+#[rtype(result = "RoomSnapshot")]
+pub struct TakeSnapshot;
+
+impl Handler<TakeSnapshot> for Room {
+    type Result = MessageResult<TakeSnapshot>;
+
+    fn handle(
+        &mut self,
+        _: TakeSnapshot,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let mut members = Vec::new();
+        let mut quality = Vec::new();
+        for (member_id, member) in self.members.members() {
+            members.push(MemberSnapshot::new(
+                &member,
+                self.members.member_has_connection(&member_id),
+            ));
+            for (partner_id, samples) in self.peers.quality_history(&member_id)
+            {
+                if let Some(sample) = samples.last() {
+                    quality.push(QualitySnapshot {
+                        member_id: member_id.to_string(),
+                        partner_member_id: partner_id.to_string(),
+                        score: sample.score.to_string(),
+                        relayed: sample.relayed,
+                        at: sample.at.to_rfc3339(),
+                    });
+                }
+            }
+        }
+        members.sort_by(|a, b| a.id.cmp(&b.id));
+        quality.sort_by(|a, b| {
+            (&a.member_id, &a.partner_member_id)
+                .cmp(&(&b.member_id, &b.partner_member_id))
+        });
+
+        let usage = self.peers.usage();
+        MessageResult(RoomSnapshot {
+            room_id: self.id.to_string(),
+            taken_at: Utc::now().to_rfc3339(),
+            members,
+            peers: self.peers.snapshot(Instant::now()),
+            metrics: MetricsSnapshot {
+                entries: usage.metrics_entries,
+                evicted: usage.metrics_evicted,
+                quality,
+            },
+        })
+    }
+}
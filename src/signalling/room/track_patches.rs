@@ -0,0 +1,239 @@
+//! Throttling of [`Room`] tracks updates, so a `Member` rapidly patching its
+//! tracks can't cause renegotiation storms affecting its partners.
+//!
+//! [`Room`]: crate::signalling::Room
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use actix::SpawnHandle;
+use medea_client_api_proto::{MemberId, PeerId, TrackPatchCommand};
+
+/// Duration of a window, in which a limited number of `UpdateTracks`
+/// commands is applied immediately.
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Outcome of throttling `UpdateTracks` command.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Throttled {
+    /// [`TrackPatchCommand`]s should be applied immediately.
+    Apply(Vec<TrackPatchCommand>),
+
+    /// [`TrackPatchCommand`]s are merged into the postponed ones.
+    ///
+    /// Postponed [`TrackPatchCommand`]s should be flushed via
+    /// [`TrackPatchesThrottle::flush()`] in the provided [`Duration`], if
+    /// any, otherwise their flushing is scheduled already.
+    Postponed(Option<Duration>),
+}
+
+/// Per-`Member` rate limiter of `UpdateTracks` commands, merging the excess
+/// [`TrackPatchCommand`]s to apply them at once when the current window ends.
+#[derive(Debug)]
+pub struct TrackPatchesThrottle {
+    /// Maximum number of `UpdateTracks` commands of a single `Member`
+    /// applied immediately per window.
+    ///
+    /// Zero means no limit.
+    limit: u32,
+
+    /// Start of the current window and number of `UpdateTracks` commands
+    /// applied in it for each `Member`.
+    windows: HashMap<MemberId, (Instant, u32)>,
+
+    /// Postponed [`TrackPatchCommand`]s of each `Member`, grouped by `Peer`s
+    /// in the order they were postponed in.
+    postponed: HashMap<MemberId, Vec<(PeerId, Vec<TrackPatchCommand>)>>,
+
+    /// [`SpawnHandle`]s of the scheduled flushes of the postponed
+    /// [`TrackPatchCommand`]s of each `Member`.
+    flush_tasks: HashMap<MemberId, SpawnHandle>,
+}
+
+impl TrackPatchesThrottle {
+    /// Creates a new [`TrackPatchesThrottle`] with the provided `limit`.
+    #[inline]
+    #[must_use]
+    pub fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            windows: HashMap::new(),
+            postponed: HashMap::new(),
+            flush_tasks: HashMap::new(),
+        }
+    }
+
+    /// Throttles `UpdateTracks` command of the provided `Member`, patching
+    /// tracks of the `Peer` with the provided [`PeerId`].
+    pub fn throttle(
+        &mut self,
+        member_id: &MemberId,
+        peer_id: PeerId,
+        patches: Vec<TrackPatchCommand>,
+    ) -> Throttled {
+        if self.limit == 0 {
+            return Throttled::Apply(patches);
+        }
+
+        if let Some(postponed) = self.postponed.get_mut(member_id) {
+            Self::postpone(postponed, peer_id, patches);
+            return Throttled::Postponed(None);
+        }
+
+        let now = Instant::now();
+        let (started_at, count) =
+            self.windows.entry(member_id.clone()).or_insert((now, 0));
+        if now.duration_since(*started_at) >= WINDOW {
+            *started_at = now;
+            *count = 0;
+        }
+        if *count < self.limit {
+            *count += 1;
+            return Throttled::Apply(patches);
+        }
+
+        let flush_in = WINDOW - now.duration_since(*started_at);
+        Self::postpone(
+            self.postponed.entry(member_id.clone()).or_default(),
+            peer_id,
+            patches,
+        );
+        Throttled::Postponed(Some(flush_in))
+    }
+
+    /// Stores [`SpawnHandle`] of the scheduled flush of the postponed
+    /// [`TrackPatchCommand`]s of the provided `Member`.
+    #[inline]
+    pub fn flush_scheduled(&mut self, member_id: MemberId, task: SpawnHandle) {
+        self.flush_tasks.insert(member_id, task);
+    }
+
+    /// Takes the merged postponed [`TrackPatchCommand`]s of the provided
+    /// `Member`, starting its new window.
+    pub fn flush(
+        &mut self,
+        member_id: &MemberId,
+    ) -> Vec<(PeerId, Vec<TrackPatchCommand>)> {
+        self.flush_tasks.remove(member_id);
+        let postponed = self.postponed.remove(member_id).unwrap_or_default();
+        if !postponed.is_empty() {
+            // Flushing counts as a single command applied in the new window.
+            self.windows.insert(member_id.clone(), (Instant::now(), 1));
+        }
+        postponed
+            .into_iter()
+            .map(|(peer_id, patches)| {
+                (peer_id, TrackPatchCommand::merge_all(patches))
+            })
+            .collect()
+    }
+
+    /// Forgets the window and postponed [`TrackPatchCommand`]s of the
+    /// provided `Member`.
+    ///
+    /// Returns [`SpawnHandle`] of the scheduled flush of the forgotten
+    /// [`TrackPatchCommand`]s, which should be cancelled.
+    #[must_use]
+    pub fn remove(&mut self, member_id: &MemberId) -> Option<SpawnHandle> {
+        self.windows.remove(member_id);
+        self.postponed.remove(member_id);
+        self.flush_tasks.remove(member_id)
+    }
+
+    /// Adds the provided [`TrackPatchCommand`]s of the `Peer` with the
+    /// provided [`PeerId`] to the `postponed` ones.
+    fn postpone(
+        postponed: &mut Vec<(PeerId, Vec<TrackPatchCommand>)>,
+        peer_id: PeerId,
+        patches: Vec<TrackPatchCommand>,
+    ) {
+        if let Some((_, p)) =
+            postponed.iter_mut().find(|(id, _)| *id == peer_id)
+        {
+            p.extend(patches);
+        } else {
+            postponed.push((peer_id, patches));
+        }
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use medea_client_api_proto::TrackId;
+
+    use super::*;
+
+    fn mute(id: u32, muted: bool) -> Vec<TrackPatchCommand> {
+        vec![TrackPatchCommand {
+            id: TrackId(id),
+            enabled: None,
+            muted: Some(muted),
+        }]
+    }
+
+    #[test]
+    fn unlimited_always_applies() {
+        let mut throttle = TrackPatchesThrottle::new(0);
+        let member_id = MemberId::from("member");
+
+        for _ in 0..100 {
+            assert_eq!(
+                throttle.throttle(&member_id, PeerId(1), mute(1, true)),
+                Throttled::Apply(mute(1, true)),
+            );
+        }
+        assert!(throttle.flush(&member_id).is_empty());
+    }
+
+    #[test]
+    fn merges_patches_exceeding_limit() {
+        let mut throttle = TrackPatchesThrottle::new(2);
+        let member_id = MemberId::from("member");
+
+        for _ in 0..2 {
+            assert_eq!(
+                throttle.throttle(&member_id, PeerId(1), mute(1, true)),
+                Throttled::Apply(mute(1, true)),
+            );
+        }
+        assert!(matches!(
+            throttle.throttle(&member_id, PeerId(1), mute(1, false)),
+            Throttled::Postponed(Some(_)),
+        ));
+        assert_eq!(
+            throttle.throttle(&member_id, PeerId(2), mute(2, true)),
+            Throttled::Postponed(None),
+        );
+        assert_eq!(
+            throttle.throttle(&member_id, PeerId(1), mute(1, true)),
+            Throttled::Postponed(None),
+        );
+        assert_eq!(
+            throttle.throttle(&MemberId::from("another"), PeerId(3), vec![]),
+            Throttled::Apply(vec![]),
+        );
+
+        assert_eq!(
+            throttle.flush(&member_id),
+            vec![(PeerId(1), mute(1, true)), (PeerId(2), mute(2, true))],
+        );
+        assert!(throttle.flush(&member_id).is_empty());
+        assert_eq!(
+            throttle.throttle(&member_id, PeerId(1), mute(1, false)),
+            Throttled::Apply(mute(1, false)),
+        );
+        assert!(matches!(
+            throttle.throttle(&member_id, PeerId(1), mute(1, false)),
+            Throttled::Postponed(Some(_)),
+        ));
+
+        assert!(throttle.remove(&member_id).is_none());
+        assert!(throttle.flush(&member_id).is_empty());
+        assert_eq!(
+            throttle.throttle(&member_id, PeerId(1), mute(1, false)),
+            Throttled::Apply(mute(1, false)),
+        );
+    }
+}
@@ -63,6 +63,26 @@ impl RoomRepository {
         self.rooms.lock().unwrap().insert(id, room);
     }
 
+    /// Returns all the [`Room`]s stored in this [`RoomRepository`].
+    #[allow(clippy::missing_panics_doc)]
+    #[must_use]
+    pub fn all(&self) -> Vec<Addr<Room>> {
+        self.rooms.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Returns all the [`Room`]s stored in this [`RoomRepository`] along with
+    /// their [`RoomId`]s.
+    #[allow(clippy::missing_panics_doc)]
+    #[must_use]
+    pub fn all_with_ids(&self) -> Vec<(RoomId, Addr<Room>)> {
+        self.rooms
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, room)| (id.clone(), room.clone()))
+            .collect()
+    }
+
     /// Checks existence of [`Room`] in [`RoomRepository`] by provided
     /// [`RoomId`].
     #[allow(clippy::missing_panics_doc)]
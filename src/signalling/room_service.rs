@@ -1,9 +1,13 @@
 //! Service which provides CRUD actions for [`Room`].
 
-use std::{collections::HashMap, marker::PhantomData, sync::Arc};
+use std::{
+    collections::HashMap, convert::TryFrom as _, marker::PhantomData,
+    sync::Arc, time::Duration,
+};
 
 use actix::{
-    Actor, Addr, Context, Handler, MailboxError, Message, ResponseFuture,
+    Actor, ActorFutureExt as _, Addr, AsyncContext as _, Context, Handler,
+    MailboxError, Message, ResponseFuture, SpawnHandle, WrapFuture as _,
 };
 use derive_more::Display;
 use failure::Fail;
@@ -16,28 +20,43 @@ use redis::RedisError;
 
 use crate::{
     api::control::{
+        callback::{
+            CallbackClientFactoryImpl, CallbackService, CallbackUrl,
+            OnCreateCompleteEvent, OnCreateProgressEvent,
+        },
         endpoints::EndpointSpec,
-        load_static_specs_from_dir,
+        load_static_specs_from_dir, load_templates_from_dir,
         member::Sid,
-        refs::{Fid, StatefulFid, ToMember, ToRoom},
+        pipeline::Pipeline,
+        refs::{Fid, StatefulFid, ToEndpoint, ToMember, ToRoom},
         EndpointId, LoadStaticControlSpecsError, MemberSpec, RoomSpec,
-        TryFromElementError,
+        RoomTemplate, TemplateError, TryFromElementError,
     },
-    conf::server::PublicUrl,
+    conf::{self, server::PublicUrl},
+    drain::DrainProgress,
     log::prelude::*,
-    shutdown::{self, GracefulShutdown},
+    shutdown::{self, GracefulShutdown, InitiateShutdown},
     signalling::{
         peers::{build_peers_traffic_watcher, PeerTrafficWatcher},
         room::{
-            Apply, Close, CreateEndpoint, CreateMember, Delete, RoomError,
-            SerializeProto,
+            Apply, Batch, BatchOperation, Close, CountConnections,
+            CreateEndpoint, CreateMember, Delete, GetClientCapabilities,
+            GetJournal, GetQualityHistory, GetUsage, PatchEndpointTracks,
+            RoomError, RoomSnapshot, SerializeProto, SetMediaPaused,
+            TakeSnapshot,
         },
         room_repo::RoomRepository,
         Room,
     },
+    turn::CoturnMetricsService,
+    utils::{deterministic, generate_token},
     AppContext,
 };
 
+/// Interval of checking whether draining is completed, once graceful shutdown
+/// is requested to be initiated after it.
+const DRAIN_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Errors of [`RoomService`].
 #[derive(Debug, Fail, Display)]
 pub enum RoomServiceError {
@@ -85,6 +104,14 @@ pub enum RoomServiceError {
         _1
     )]
     NotSameRoomIds(RoomId, RoomId),
+
+    /// [`RoomTemplate`] with the provided ID is not found.
+    #[display(fmt = "Room template [id = {}] not found.", _0)]
+    TemplateNotFound(String),
+
+    /// Error while instantiating a [`RoomTemplate`].
+    #[display(fmt = "Failed to instantiate Room template. {}", _0)]
+    Template(TemplateError),
 }
 
 impl From<RoomError> for RoomServiceError {
@@ -120,6 +147,9 @@ pub struct RoomService {
     /// [Control API]: https://tinyurl.com/yxsqplq7
     static_specs_dir: String,
 
+    /// [`RoomTemplate`]s which [`Room`]s may be created from, by their IDs.
+    templates: HashMap<String, RoomTemplate>,
+
     /// Public URL of server. Address for exposed [Client API].
     ///
     /// [Client API]: https://tinyurl.com/yx9thsnr
@@ -127,12 +157,18 @@ pub struct RoomService {
 
     /// [`PeerTrafficWatcher`] for all [`Room`]s of this [`RoomService`].
     peer_traffic_watcher: Arc<dyn PeerTrafficWatcher>,
-    /* TODO: Enable in https://github.com/instrumentisto/medea/pull/91
-     * /// Service which is responsible for processing [`Peer`]'s metrics
-     * received /// from Coturn.
-     * ///
-     * /// [`Peer`]: crate::media::peer::Peer
-     * _coturn_metrics: Addr<CoturnMetricsService>, */
+
+    /// [`SpawnHandle`] of the task initiating graceful shutdown once draining
+    /// is completed.
+    drain_watcher: Option<SpawnHandle>,
+
+    /// Service which is responsible for processing [`Peer`]'s metrics
+    /// received from Coturn.
+    ///
+    /// [`None`] if relay detection is disabled.
+    ///
+    /// [`Peer`]: crate::media::peer::Peer
+    _coturn_metrics: Option<Addr<CoturnMetricsService>>,
 }
 
 impl RoomService {
@@ -148,19 +184,37 @@ impl RoomService {
     ) -> Result<Self, RedisError> {
         let peer_traffic_watcher =
             build_peers_traffic_watcher(&app.config.media);
+        let ice = &app.config.ice;
+        let coturn_metrics = if matches!(ice.default, conf::ice::Kind::Coturn)
+            && ice.coturn.relay_detection
+        {
+            Some(
+                CoturnMetricsService::new(
+                    &ice.coturn,
+                    Arc::clone(&peer_traffic_watcher),
+                    room_repo.clone(),
+                )?
+                .start(),
+            )
+        } else {
+            None
+        };
+        let templates =
+            load_templates_from_dir(&app.config.control.templates_dir)
+                .unwrap_or_else(|e| {
+                    warn!("Room templates are not loaded: {}", e);
+                    HashMap::new()
+                });
         Ok(Self {
-            // TODO: Enable in https://github.com/instrumentisto/medea/pull/91
-            // _coturn_metrics: CoturnMetricsService::new(
-            //     &app.config.turn,
-            //     peer_traffic_watcher.clone(),
-            // )?
-            // .start(),
+            _coturn_metrics: coturn_metrics,
             static_specs_dir: app.config.control.static_specs_dir.clone(),
+            templates,
             public_url: app.config.server.client.http.public_url.clone(),
             peer_traffic_watcher,
             room_repo,
             app,
             graceful_shutdown,
+            drain_watcher: None,
         })
     }
 
@@ -187,6 +241,31 @@ impl RoomService {
             })
     }
 
+    /// Returns the current [`DrainProgress`] of all the [`Room`]s.
+    fn drain_progress(
+        &self,
+    ) -> LocalBoxFuture<'static, Result<DrainProgress, RoomServiceError>> {
+        let counts = future::try_join_all(
+            self.room_repo
+                .all()
+                .into_iter()
+                .map(|room| room.send(CountConnections)),
+        );
+        async move {
+            let counts =
+                counts.await.map_err(RoomServiceError::RoomMailboxErr)?;
+            Ok(counts.into_iter().filter(|count| *count > 0).fold(
+                DrainProgress::default(),
+                |mut progress, count| {
+                    progress.rooms += 1;
+                    progress.members += count;
+                    progress
+                },
+            ))
+        }
+        .boxed_local()
+    }
+
     /// Returns [Control API] sids for `Members` in provided [`RoomSpec`] and
     /// based on `MEDEA_SERVER__CLIENT__HTTP__PUBLIC_URL` config value.
     fn get_sids_from_spec(
@@ -219,7 +298,10 @@ impl RoomService {
     ///
     /// With [`RoomServiceError::RoomAlreadyExists`] if a [`Room`] with the
     /// provided ID already exists.
-    fn create_room(&self, room_spec: RoomSpec) -> Result<(), RoomServiceError> {
+    fn create_room(
+        &self,
+        room_spec: RoomSpec,
+    ) -> Result<Addr<Room>, RoomServiceError> {
         if self.room_repo.get(&room_spec.id).is_some() {
             return Err(RoomServiceError::RoomAlreadyExists(
                 Fid::<ToRoom>::new(room_spec.id),
@@ -239,9 +321,9 @@ impl RoomService {
         );
 
         debug!("New Room [id = {}] started.", room_spec.id);
-        self.room_repo.add(room_spec.id, room_addr);
+        self.room_repo.add(room_spec.id, room_addr.clone());
 
-        Ok(())
+        Ok(room_addr)
     }
 }
 
@@ -249,6 +331,19 @@ impl Actor for RoomService {
     type Context = Context<Self>;
 }
 
+/// Signal for checking whether [`RoomService`] is alive and processes its
+/// messages.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Ping;
+
+impl Handler<Ping> for RoomService {
+    type Result = ();
+
+    #[inline]
+    fn handle(&mut self, _: Ping, _: &mut Self::Context) -> Self::Result {}
+}
+
 /// Signal for load all static specs and start [`Room`]s.
 #[derive(Message)]
 #[rtype(result = "Result<(), RoomServiceError>")]
@@ -332,7 +427,7 @@ impl Handler<ApplyRoom> for RoomService {
                 Ok(sids)
             })
         } else {
-            let res = self.create_room(msg.spec);
+            let res = self.create_room(msg.spec).map(drop);
 
             Box::pin(async move {
                 res?;
@@ -366,6 +461,212 @@ impl Handler<CreateRoom> for RoomService {
     }
 }
 
+/// Signal for creating a new [`Room`] from a [`RoomTemplate`].
+#[derive(Message)]
+#[rtype(result = "Result<Sids, RoomServiceError>")]
+pub struct CreateRoomFromTemplate {
+    /// ID of the [`RoomTemplate`] to instantiate.
+    pub template: String,
+
+    /// [`RoomId`] of the created [`Room`].
+    pub id: RoomId,
+
+    /// Values of the [`RoomTemplate`] parameters.
+    pub params: HashMap<String, String>,
+}
+
+impl Handler<CreateRoomFromTemplate> for RoomService {
+    type Result = Result<Sids, RoomServiceError>;
+
+    fn handle(
+        &mut self,
+        msg: CreateRoomFromTemplate,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let spec = self
+            .templates
+            .get(&msg.template)
+            .ok_or(RoomServiceError::TemplateNotFound(msg.template))?
+            .instantiate(msg.id, &msg.params)
+            .map_err(RoomServiceError::Template)?;
+        let sids = self.get_sids_from_spec(&spec)?;
+        self.create_room(spec)?;
+        Ok(sids)
+    }
+}
+
+/// Signal for creating a new [`Room`] asynchronously.
+///
+/// The [`Room`] is started without `Member`s, which are provisioned in
+/// background afterwards, notifying the `on_progress` [`CallbackUrl`] about
+/// each provisioned element. If provisioning of any element fails, then the
+/// whole [`Room`] is removed.
+#[derive(Message)]
+#[rtype(result = "Result<(Sids, String), RoomServiceError>")]
+pub struct CreateRoomAsync {
+    /// [Control API] spec for [`Room`].
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub spec: RoomSpec,
+
+    /// [`CallbackUrl`] to send `OnCreateProgress` and `OnCreateComplete`
+    /// callbacks to.
+    pub on_progress: CallbackUrl,
+}
+
+impl Handler<CreateRoomAsync> for RoomService {
+    type Result = Result<(Sids, String), RoomServiceError>;
+
+    /// Starts an empty [`Room`] and spawns provisioning of its `Member`s.
+    ///
+    /// Returns [`Sids`] of the `Member`s to be provisioned along with an ID
+    /// of the creation operation.
+    fn handle(
+        &mut self,
+        msg: CreateRoomAsync,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let sids = self.get_sids_from_spec(&msg.spec)?;
+        let mut members: Vec<_> = msg
+            .spec
+            .members()
+            .map_err(RoomServiceError::TryFromElement)?
+            .into_iter()
+            .collect();
+        deterministic::order_by(&mut members, |(id, _)| id.0.clone());
+
+        let room_id = msg.spec.id;
+        let room = self.create_room(RoomSpec {
+            id: room_id.clone(),
+            pipeline: Pipeline::new(HashMap::new()),
+        })?;
+
+        let operation_id = generate_token(16);
+        let total = members.len()
+            + members
+                .iter()
+                .map(|(_, spec)| spec.play_endpoints().count())
+                .sum::<usize>();
+        let progress = CreationProgress {
+            callbacks: self.app.callbacks.clone(),
+            url: msg.on_progress.clone(),
+            operation_id: operation_id.clone(),
+            done: 0,
+            total: u32::try_from(total).unwrap_or(u32::MAX),
+        };
+
+        let fid = StatefulFid::from(Fid::<ToRoom>::new(room_id.clone()));
+        let url = msg.on_progress;
+        let id = operation_id.clone();
+        ctx.spawn(
+            provision_members(room, room_id.clone(), members, progress)
+                .into_actor(self)
+                .map(move |res, this, ctx| {
+                    let error = res.err().map(|e| {
+                        warn!(
+                            "Failed to provision Room [id = {}], removing it: \
+                             {}",
+                            room_id, e,
+                        );
+                        ctx.spawn(
+                            this.close_room(room_id).map(drop).into_actor(this),
+                        );
+                        e.to_string()
+                    });
+                    this.app.callbacks.do_send(
+                        url,
+                        fid,
+                        OnCreateCompleteEvent::new(id, error),
+                    );
+                }),
+        );
+
+        Ok((sids, operation_id))
+    }
+}
+
+/// Notifier about the progress of an asynchronous [`Room`] creation.
+struct CreationProgress {
+    /// [`CallbackService`] to send `OnCreateProgress` callbacks with.
+    callbacks: CallbackService<CallbackClientFactoryImpl>,
+
+    /// [`CallbackUrl`] to send `OnCreateProgress` callbacks to.
+    url: CallbackUrl,
+
+    /// ID of the creation operation.
+    operation_id: String,
+
+    /// Number of the already provisioned elements.
+    done: u32,
+
+    /// Total number of the elements to be provisioned.
+    total: u32,
+}
+
+impl CreationProgress {
+    /// Notifies about the provisioned element with the provided
+    /// [`StatefulFid`].
+    fn advance(&mut self, fid: StatefulFid) {
+        self.done += 1;
+        self.callbacks.do_send(
+            self.url.clone(),
+            fid,
+            OnCreateProgressEvent::new(
+                self.operation_id.clone(),
+                self.done,
+                self.total,
+            ),
+        );
+    }
+}
+
+/// Provisions the provided `Member`s in the [`Room`] one by one, notifying
+/// the [`CreationProgress`] about each provisioned element.
+///
+/// `Member`s are created without their `WebRtcPlayEndpoint`s at first, so
+/// the latter may refer to `WebRtcPublishEndpoint`s of any `Member`.
+///
+/// # Errors
+///
+/// Errors if the [`Room`] fails to create any of the elements.
+async fn provision_members(
+    room: Addr<Room>,
+    room_id: RoomId,
+    members: Vec<(MemberId, MemberSpec)>,
+    mut progress: CreationProgress,
+) -> Result<(), RoomServiceError> {
+    for (id, spec) in &members {
+        room.send(CreateMember(id.clone(), spec.without_play_endpoints()))
+            .await
+            .map_err(RoomServiceError::RoomMailboxErr)??;
+        progress
+            .advance(Fid::<ToMember>::new(room_id.clone(), id.clone()).into());
+    }
+
+    for (member_id, spec) in &members {
+        for (id, play) in spec.play_endpoints() {
+            let endpoint_id = EndpointId::from(id);
+            room.send(CreateEndpoint {
+                member_id: member_id.clone(),
+                endpoint_id: endpoint_id.clone(),
+                spec: EndpointSpec::WebRtcPlay(play.clone()),
+            })
+            .await
+            .map_err(RoomServiceError::RoomMailboxErr)??;
+            progress.advance(
+                Fid::<ToEndpoint>::new(
+                    room_id.clone(),
+                    member_id.clone(),
+                    endpoint_id,
+                )
+                .into(),
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// Signal for applying the given [`MemberSpec`] to a [`Member`] in the given
 /// [`Room`].
 ///
@@ -513,6 +814,64 @@ impl Handler<CreateEndpointInRoom> for RoomService {
     }
 }
 
+/// Signal for applying many [`BatchOperation`]s to a single [`Room`]
+/// transactionally.
+#[derive(Message)]
+#[rtype(result = "Result<Sids, RoomServiceError>")]
+pub struct BatchInRoom {
+    pub room_id: RoomId,
+    pub operations: Vec<BatchOperation>,
+}
+
+impl Handler<BatchInRoom> for RoomService {
+    type Result = ResponseFuture<Result<Sids, RoomServiceError>>;
+
+    /// Applies the provided [`BatchOperation`]s to the [`Room`], returning
+    /// [`Sid`]s of all the created `Member`s.
+    fn handle(
+        &mut self,
+        msg: BatchInRoom,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let room_id = msg.room_id;
+        let sids: Sids = msg
+            .operations
+            .iter()
+            .filter_map(|op| {
+                if let BatchOperation::CreateMember(id, spec) = op {
+                    let sid = Sid::new(
+                        self.public_url.clone(),
+                        room_id.clone(),
+                        id.clone(),
+                        spec.credentials().clone(),
+                    );
+                    Some((id.clone(), sid))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        self.room_repo.get(&room_id).map_or_else(
+            || {
+                future::err(RoomServiceError::RoomNotFound(Fid::<ToRoom>::new(
+                    room_id,
+                )))
+                .boxed_local()
+            },
+            |room| {
+                async move {
+                    room.send(Batch(msg.operations))
+                        .await
+                        .map_err(RoomServiceError::RoomMailboxErr)??;
+                    Ok(sids)
+                }
+                .boxed_local()
+            },
+        )
+    }
+}
+
 /// State which indicates that [`DeleteElements`] message was validated and can
 /// be send to [`RoomService`].
 pub struct Validated;
@@ -697,6 +1056,341 @@ impl Handler<Get> for RoomService {
     }
 }
 
+/// Message which returns events of the [`Room`]'s journal, which occurred
+/// after the provided sequence number, serialized to protobuf.
+#[derive(Message)]
+#[rtype(result = "Result<proto::RoomJournalResponse, RoomServiceError>")]
+pub struct GetRoomJournal {
+    /// [`Fid`] of the [`Room`] to return the journal of.
+    pub room_fid: Fid<ToRoom>,
+
+    /// Sequence number of the last journal event known by the requester.
+    pub since: u64,
+}
+
+impl Handler<GetRoomJournal> for RoomService {
+    type Result =
+        ResponseFuture<Result<proto::RoomJournalResponse, RoomServiceError>>;
+
+    fn handle(
+        &mut self,
+        msg: GetRoomJournal,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let room = if let Some(room) =
+            self.room_repo.get(msg.room_fid.room_id())
+        {
+            room
+        } else {
+            return future::err(RoomServiceError::RoomNotFound(msg.room_fid))
+                .boxed_local();
+        };
+
+        room.send(GetJournal(msg.since))
+            .map_err(RoomServiceError::RoomMailboxErr)
+            .boxed_local()
+    }
+}
+
+/// Message which returns recent connection quality scores of a `Member` with
+/// each of its partners, serialized to protobuf.
+#[derive(Message)]
+#[rtype(result = "Result<proto::QualityHistoryResponse, RoomServiceError>")]
+pub struct GetMemberQualityHistory {
+    /// [`Fid`] of the `Member` to return the connection quality history of.
+    pub member_fid: Fid<ToMember>,
+}
+
+impl Handler<GetMemberQualityHistory> for RoomService {
+    type Result =
+        ResponseFuture<Result<proto::QualityHistoryResponse, RoomServiceError>>;
+
+    fn handle(
+        &mut self,
+        msg: GetMemberQualityHistory,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let room_id = msg.member_fid.room_id();
+        let room = if let Some(room) = self.room_repo.get(room_id) {
+            room
+        } else {
+            return future::err(RoomServiceError::RoomNotFound(
+                Fid::<ToRoom>::new(room_id.clone()),
+            ))
+            .boxed_local();
+        };
+
+        let member_id = msg.member_fid.member_id().clone();
+        async move {
+            Ok(room
+                .send(GetQualityHistory(member_id))
+                .await
+                .map_err(RoomServiceError::RoomMailboxErr)??)
+        }
+        .boxed_local()
+    }
+}
+
+/// Message which returns capabilities reported by the `Client` of a `Member`,
+/// serialized to protobuf.
+#[derive(Message)]
+#[rtype(result = "Result<proto::ClientCapabilitiesResponse, RoomServiceError>")]
+pub struct GetMemberClientCapabilities {
+    /// [`Fid`] of the `Member` to return the `Client` capabilities of.
+    pub member_fid: Fid<ToMember>,
+}
+
+impl Handler<GetMemberClientCapabilities> for RoomService {
+    type Result = ResponseFuture<
+        Result<proto::ClientCapabilitiesResponse, RoomServiceError>,
+    >;
+
+    fn handle(
+        &mut self,
+        msg: GetMemberClientCapabilities,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let room_id = msg.member_fid.room_id();
+        let room = if let Some(room) = self.room_repo.get(room_id) {
+            room
+        } else {
+            return future::err(RoomServiceError::RoomNotFound(
+                Fid::<ToRoom>::new(room_id.clone()),
+            ))
+            .boxed_local();
+        };
+
+        let member_id = msg.member_fid.member_id().clone();
+        async move {
+            Ok(room
+                .send(GetClientCapabilities(member_id))
+                .await
+                .map_err(RoomServiceError::RoomMailboxErr)??)
+        }
+        .boxed_local()
+    }
+}
+
+/// Message which forcibly patches the tracks of an `Endpoint`.
+///
+/// Returns ID of the started `PatchTrack` operation, if `on_applied` callback
+/// is requested.
+#[derive(Message)]
+#[rtype(result = "Result<Option<String>, RoomServiceError>")]
+pub struct PatchTrack {
+    /// [`Fid`] of the `Endpoint` to patch the tracks of.
+    pub endpoint_fid: Fid<ToEndpoint>,
+
+    /// Kind of the tracks to be patched.
+    pub kind: proto::patch_track_request::TrackKind,
+
+    /// New value of the tracks being enabled, if it should be changed.
+    pub enabled: Option<bool>,
+
+    /// New value of the tracks being muted, if it should be changed.
+    pub muted: Option<bool>,
+
+    /// [`CallbackUrl`] to send `OnPatchApplied` callback to, once the patches
+    /// are applied by all the affected `Member`s.
+    pub on_applied: Option<CallbackUrl>,
+}
+
+impl Handler<PatchTrack> for RoomService {
+    type Result = ResponseFuture<Result<Option<String>, RoomServiceError>>;
+
+    fn handle(
+        &mut self,
+        msg: PatchTrack,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let room_id = msg.endpoint_fid.room_id();
+        let room = if let Some(room) = self.room_repo.get(room_id) {
+            room
+        } else {
+            return future::err(RoomServiceError::RoomNotFound(
+                Fid::<ToRoom>::new(room_id.clone()),
+            ))
+            .boxed_local();
+        };
+
+        let (_, member_id, endpoint_id) = msg.endpoint_fid.take_all();
+        async move {
+            Ok(room
+                .send(PatchEndpointTracks {
+                    member_id,
+                    endpoint_id,
+                    kind: msg.kind,
+                    enabled: msg.enabled,
+                    muted: msg.muted,
+                    on_applied: msg.on_applied,
+                })
+                .await
+                .map_err(RoomServiceError::RoomMailboxErr)??)
+        }
+        .boxed_local()
+    }
+}
+
+/// Message which returns approximate amounts of entities kept in memory by the
+/// provided [`Room`]s (or all the [`Room`]s, if none provided), serialized to
+/// protobuf.
+#[derive(Message)]
+#[rtype(result = "Result<HashMap<RoomId, proto::RoomUsage>, RoomServiceError>")]
+pub struct GetRoomsUsage(pub Vec<Fid<ToRoom>>);
+
+impl Handler<GetRoomsUsage> for RoomService {
+    type Result = ResponseFuture<
+        Result<HashMap<RoomId, proto::RoomUsage>, RoomServiceError>,
+    >;
+
+    fn handle(
+        &mut self,
+        msg: GetRoomsUsage,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let rooms = if msg.0.is_empty() {
+            self.room_repo.all_with_ids()
+        } else {
+            let mut rooms = Vec::with_capacity(msg.0.len());
+            for fid in msg.0 {
+                if let Some(room) = self.room_repo.get(fid.room_id()) {
+                    rooms.push((fid.room_id().clone(), room));
+                } else {
+                    return future::err(RoomServiceError::RoomNotFound(fid))
+                        .boxed_local();
+                }
+            }
+            rooms
+        };
+
+        async move {
+            let (ids, usages): (Vec<_>, Vec<_>) = rooms
+                .into_iter()
+                .map(|(id, room)| (id, room.send(GetUsage)))
+                .unzip();
+            let usages = future::try_join_all(usages)
+                .await
+                .map_err(RoomServiceError::RoomMailboxErr)?;
+            Ok(ids.into_iter().zip(usages).collect())
+        }
+        .boxed_local()
+    }
+}
+
+/// Message which returns a [`RoomSnapshot`] of the live state of a [`Room`].
+#[derive(Message)]
+#[rtype(result = "Result<RoomSnapshot, RoomServiceError>")]
+pub struct DumpRoom(pub Fid<ToRoom>);
+
+impl Handler<DumpRoom> for RoomService {
+    type Result = ResponseFuture<Result<RoomSnapshot, RoomServiceError>>;
+
+    fn handle(&mut self, msg: DumpRoom, _: &mut Self::Context) -> Self::Result {
+        let room = if let Some(room) = self.room_repo.get(msg.0.room_id()) {
+            room
+        } else {
+            return future::err(RoomServiceError::RoomNotFound(msg.0))
+                .boxed_local();
+        };
+
+        async move {
+            room.send(TakeSnapshot)
+                .await
+                .map_err(RoomServiceError::RoomMailboxErr)
+        }
+        .boxed_local()
+    }
+}
+
+/// Message which pauses/resumes media exchange in the provided [`Room`]s,
+/// keeping their `Member`s' sessions and `Peer`s alive.
+#[derive(Message)]
+#[rtype(result = "Result<(), RoomServiceError>")]
+pub struct SetRoomsPaused {
+    /// [`Fid`]s of the [`Room`]s to pause/resume media exchange in.
+    pub room_fids: Vec<Fid<ToRoom>>,
+
+    /// Indicator whether media exchange should be paused or resumed.
+    pub paused: bool,
+}
+
+impl Handler<SetRoomsPaused> for RoomService {
+    type Result = ResponseFuture<Result<(), RoomServiceError>>;
+
+    fn handle(
+        &mut self,
+        msg: SetRoomsPaused,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        let mut rooms = Vec::with_capacity(msg.room_fids.len());
+        for fid in msg.room_fids {
+            if let Some(room) = self.room_repo.get(fid.room_id()) {
+                rooms.push(room);
+            } else {
+                return future::err(RoomServiceError::RoomNotFound(fid))
+                    .boxed_local();
+            }
+        }
+
+        let paused = msg.paused;
+        async move {
+            future::try_join_all(
+                rooms
+                    .into_iter()
+                    .map(|room| room.send(SetMediaPaused(paused))),
+            )
+            .await
+            .map_err(RoomServiceError::RoomMailboxErr)?;
+            Ok(())
+        }
+        .boxed_local()
+    }
+}
+
+/// Signal for switching the drain mode on, so no new `Member`s are allowed to
+/// join [`Room`]s, while the already joined ones may finish their sessions.
+///
+/// Returns the current [`DrainProgress`].
+#[derive(Message)]
+#[rtype(result = "Result<DrainProgress, RoomServiceError>")]
+pub struct StartDrain {
+    /// Indicator whether graceful shutdown should be initiated once draining
+    /// is completed.
+    pub shutdown: bool,
+}
+
+impl Handler<StartDrain> for RoomService {
+    type Result = ResponseFuture<Result<DrainProgress, RoomServiceError>>;
+
+    fn handle(
+        &mut self,
+        msg: StartDrain,
+        ctx: &mut Self::Context,
+    ) -> Self::Result {
+        if self.app.drain.start() {
+            info!("Drain mode is switched on");
+        }
+        if msg.shutdown && self.drain_watcher.is_none() {
+            self.drain_watcher =
+                Some(ctx.run_interval(DRAIN_CHECK_INTERVAL, |this, ctx| {
+                    ctx.spawn(this.drain_progress().into_actor(this).map(
+                        |progress, this, ctx| {
+                            if !matches!(progress, Ok(p) if p.is_drained()) {
+                                return;
+                            }
+                            info!("Draining is completed");
+                            if let Some(handle) = this.drain_watcher {
+                                ctx.cancel_future(handle);
+                            }
+                            this.graceful_shutdown.do_send(InitiateShutdown);
+                        },
+                    ));
+                }));
+        }
+        self.drain_progress()
+    }
+}
+
 #[cfg(test)]
 mod delete_elements_validation_specs {
     use std::convert::TryFrom as _;
@@ -874,6 +1568,45 @@ mod room_service_specs {
         .await;
     }
 
+    #[actix_rt::test]
+    async fn create_room_async() {
+        let room_service = room_service(RoomRepository::new());
+        let play_fid = StatefulFid::try_from(
+            "pub-sub-video-call/responder/play".to_string(),
+        )
+        .unwrap();
+
+        let (sids, operation_id) = room_service
+            .send(CreateRoomAsync {
+                spec: room_spec(),
+                on_progress: CallbackUrl::try_from(
+                    "grpc://127.0.0.1:9099".to_string(),
+                )
+                .unwrap(),
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(sids.len(), 2);
+        assert!(!operation_id.is_empty());
+
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        let mut resp = room_service
+            .send(Get(vec![play_fid.clone()]))
+            .await
+            .unwrap()
+            .unwrap();
+        match resp.remove(&play_fid).unwrap().el.unwrap() {
+            proto::element::El::WebrtcPlay(play) => {
+                assert_eq!(
+                    play.src,
+                    "local://pub-sub-video-call/caller/publish",
+                );
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[actix_rt::test]
     async fn create_member() {
         let spec = room_spec();
@@ -969,7 +1702,8 @@ mod room_service_specs {
             |endpoint_el| {
                 match endpoint_el {
                     proto::element::El::WebrtcPub(publish) => {
-                        let endpoint = WebRtcPublishEndpoint::from(&publish);
+                        let endpoint =
+                            WebRtcPublishEndpoint::try_from(&publish).unwrap();
                         assert_eq!(endpoint.p2p, P2pMode::Never);
                     }
                     _ => unreachable!(),
@@ -979,6 +1713,166 @@ mod room_service_specs {
         .await;
     }
 
+    #[actix_rt::test]
+    async fn batch_is_applied_atomically() {
+        let spec = room_spec();
+        let member_spec = spec
+            .members()
+            .unwrap()
+            .get(&MemberId::from("caller"))
+            .unwrap()
+            .clone();
+
+        let room_id = RoomId::from("pub-sub-video-call");
+        let room = Room::start(
+            &spec,
+            &app_ctx(),
+            build_peers_traffic_watcher(&conf::Media::default()),
+        )
+        .unwrap();
+        let room_service = room_service(RoomRepository::from(hashmap!(
+            room_id.clone() => room,
+        )));
+        let member_id = MemberId::from("test-member");
+        let member_fid: StatefulFid = Fid::<ToRoom>::new(room_id.clone())
+            .push_member_id(member_id.clone())
+            .into();
+
+        let res = room_service
+            .send(BatchInRoom {
+                room_id: room_id.clone(),
+                operations: vec![
+                    BatchOperation::CreateMember(
+                        member_id.clone(),
+                        member_spec.clone(),
+                    ),
+                    BatchOperation::CreateMember(
+                        MemberId::from("caller"),
+                        member_spec.clone(),
+                    ),
+                ],
+            })
+            .await
+            .unwrap();
+        assert!(res.is_err());
+        assert!(room_service
+            .send(Get(vec![member_fid.clone()]))
+            .await
+            .unwrap()
+            .is_err());
+
+        let sids = room_service
+            .send(BatchInRoom {
+                room_id,
+                operations: vec![BatchOperation::CreateMember(
+                    member_id.clone(),
+                    member_spec,
+                )],
+            })
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(sids.contains_key(&member_id));
+        assert!(room_service
+            .send(Get(vec![member_fid]))
+            .await
+            .unwrap()
+            .is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn batch_is_rolled_back_if_operation_fails() {
+        const LOOPED_MEMBER_SPEC: &str = r#"
+            kind: Member
+            credentials:
+              plain: test
+            spec:
+              pipeline:
+                publish:
+                  kind: WebRtcPublishEndpoint
+                  spec:
+                    p2p: Always
+                play:
+                  kind: WebRtcPlayEndpoint
+                  spec:
+                    src: "local://pub-sub-video-call/looped/publish"
+        "#;
+
+        let spec = room_spec();
+        let member_spec = spec
+            .members()
+            .unwrap()
+            .get(&MemberId::from("caller"))
+            .unwrap()
+            .clone();
+        // Passes the batch validation, but fails to be created, as its
+        // `WebRtcPlayEndpoint` refers to the `Member` itself.
+        let looped_spec = MemberSpec::try_from(
+            &serde_yaml::from_str::<RoomElement>(LOOPED_MEMBER_SPEC).unwrap(),
+        )
+        .unwrap();
+
+        let room_id = RoomId::from("pub-sub-video-call");
+        let room = Room::start(
+            &spec,
+            &app_ctx(),
+            build_peers_traffic_watcher(&conf::Media::default()),
+        )
+        .unwrap();
+        let room_service = room_service(RoomRepository::from(hashmap!(
+            room_id.clone() => room,
+        )));
+        let member_id = MemberId::from("test-member");
+        let member_fid: StatefulFid = Fid::<ToRoom>::new(room_id.clone())
+            .push_member_id(member_id.clone())
+            .into();
+        let responder_fid: StatefulFid = Fid::<ToRoom>::new(room_id.clone())
+            .push_member_id(MemberId::from("responder"))
+            .into();
+
+        let res = room_service
+            .send(BatchInRoom {
+                room_id: room_id.clone(),
+                operations: vec![
+                    BatchOperation::CreateMember(
+                        member_id.clone(),
+                        member_spec.clone(),
+                    ),
+                    BatchOperation::Delete(responder_fid.clone()),
+                    BatchOperation::CreateMember(
+                        MemberId::from("looped"),
+                        looped_spec,
+                    ),
+                ],
+            })
+            .await
+            .unwrap();
+        assert!(res.is_err());
+        assert!(room_service
+            .send(Get(vec![member_fid]))
+            .await
+            .unwrap()
+            .is_err());
+        assert!(room_service
+            .send(Get(vec![responder_fid]))
+            .await
+            .unwrap()
+            .is_ok());
+
+        let res = room_service
+            .send(BatchInRoom {
+                room_id: room_id.clone(),
+                operations: vec![BatchOperation::Delete(
+                    Fid::<ToRoom>::new(room_id)
+                        .push_member_id(MemberId::from("unknown"))
+                        .into(),
+                )],
+            })
+            .await
+            .unwrap();
+        assert!(res.is_err());
+    }
+
     /// Returns [`Future`] used for testing of all delete/get methods of
     /// [`RoomService`].
     ///
@@ -1096,25 +1990,33 @@ mod room_service_specs {
                                             audio_settings:
                                                 AudioSettings::default(),
                                             video_settings:
-                                                VideoSettings::default()
+                                                VideoSettings::default(),
+                                            on_start: None,
+                                            on_stop: None,
                                         }
                                     }
                             }),
                             credentials: Credential::Plain(String::from("1")),
                             on_leave: None,
                             on_join: None,
+                            on_negotiation_timeout: None,
                             idle_timeout: None,
                             reconnect_timeout: None,
                             ping_interval: None,
+                            metadata: HashMap::new(),
+                            features: HashMap::new(),
                         },
                         MemberId::from("member2") => RoomElement::Member {
                             spec: Pipeline::new(HashMap::new()),
                             credentials: Credential::Plain(String::from("2")),
                             on_leave: None,
                             on_join: None,
+                            on_negotiation_timeout: None,
                             idle_timeout: None,
                             reconnect_timeout: None,
                             ping_interval: None,
+                            metadata: HashMap::new(),
+                            features: HashMap::new(),
                         }
                     }),
                 },
@@ -1236,7 +2138,9 @@ mod room_service_specs {
                                 p2p: P2pMode::Always,
                                 force_relay: false,
                                 audio_settings: AudioSettings::default(),
-                                video_settings: VideoSettings::default()
+                                video_settings: VideoSettings::default(),
+                                on_start: None,
+                                on_stop: None,
                             }
                         },
                         EndpointId::from(String::from("pub2")) =>
@@ -1245,7 +2149,9 @@ mod room_service_specs {
                                 p2p: P2pMode::Always,
                                 force_relay: false,
                                 audio_settings: AudioSettings::default(),
-                                video_settings: VideoSettings::default()
+                                video_settings: VideoSettings::default(),
+                                on_start: None,
+                                on_stop: None,
                             }
                         },
                     }),
@@ -1347,9 +2253,12 @@ mod room_service_specs {
                                 Credential::Plain(String::from("test")),
                             on_leave: None,
                             on_join: None,
+                            on_negotiation_timeout: None,
                             idle_timeout: None,
                             reconnect_timeout: None,
                             ping_interval: None,
+                            metadata: HashMap::new(),
+                            features: HashMap::new(),
                         },
                         MemberId::from("responder") => RoomElement::Member {
                             spec: Pipeline::new(HashMap::new()),
@@ -1357,9 +2266,12 @@ mod room_service_specs {
                                 Credential::Plain(String::from("test")),
                             on_leave: None,
                             on_join: None,
+                            on_negotiation_timeout: None,
                             idle_timeout: None,
                             reconnect_timeout: None,
                             ping_interval: None,
+                            metadata: HashMap::new(),
+                            features: HashMap::new(),
                         }
                     }),
                 },
@@ -1417,4 +2329,87 @@ mod room_service_specs {
             _ => unreachable!(),
         };
     }
+
+    #[actix_rt::test]
+    async fn start_drain_reports_progress() {
+        let room_service = room_service(RoomRepository::new());
+        room_service
+            .send(CreateRoom { spec: room_spec() })
+            .await
+            .unwrap()
+            .unwrap();
+
+        let progress = room_service
+            .send(StartDrain { shutdown: false })
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(progress.is_drained());
+        assert_eq!(progress, DrainProgress::default());
+    }
+
+    #[actix_rt::test]
+    async fn returns_rooms_usage() {
+        let room_service = room_service(RoomRepository::new());
+        room_service
+            .send(CreateRoom { spec: room_spec() })
+            .await
+            .unwrap()
+            .unwrap();
+
+        let usage = room_service
+            .send(GetRoomsUsage(Vec::new()))
+            .await
+            .unwrap()
+            .unwrap();
+        let room = &usage[&RoomId::from("pub-sub-video-call")];
+        assert_eq!(usage.len(), 1);
+        assert_eq!(room.members, 2);
+        assert_eq!(room.peers, 0);
+        assert_eq!(room.tracks, 0);
+
+        let err = room_service
+            .send(GetRoomsUsage(vec![Fid::<ToRoom>::new(RoomId::from(
+                "unknown",
+            ))]))
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(err, RoomServiceError::RoomNotFound(_)));
+    }
+
+    #[actix_rt::test]
+    async fn pauses_and_resumes_rooms() {
+        let room_service = room_service(RoomRepository::new());
+        room_service
+            .send(CreateRoom { spec: room_spec() })
+            .await
+            .unwrap()
+            .unwrap();
+        let room_fid = Fid::<ToRoom>::new(RoomId::from("pub-sub-video-call"));
+
+        for paused in &[true, true, false] {
+            room_service
+                .send(SetRoomsPaused {
+                    room_fids: vec![room_fid.clone()],
+                    paused: *paused,
+                })
+                .await
+                .unwrap()
+                .unwrap();
+        }
+
+        let err = room_service
+            .send(SetRoomsPaused {
+                room_fids: vec![
+                    room_fid,
+                    Fid::<ToRoom>::new(RoomId::from("unknown")),
+                ],
+                paused: true,
+            })
+            .await
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(err, RoomServiceError::RoomNotFound(_)));
+    }
 }
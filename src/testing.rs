@@ -0,0 +1,493 @@
+//! Facilities for testing against a real [Medea] server logic without any
+//! external infrastructure.
+//!
+//! [`TestServer`] starts a full in-process [Medea] server ([Client API] and
+//! [Control API] on ephemeral ports, with a mocked [TURN] service), and
+//! [`TestClient`] drives its [Client API] programmatically.
+//!
+//! [Client API]: https://tinyurl.com/yx9thsnr
+//! [Control API]: https://tinyurl.com/yxsqplq7
+//! [Medea]: https://github.com/instrumentisto/medea
+//! [TURN]: https://webrtcglossary.com/turn
+
+use std::{
+    convert::TryFrom as _,
+    io,
+    net::{IpAddr, Ipv4Addr, SocketAddr, TcpListener},
+};
+
+use actix::{Actor as _, Addr, MailboxError};
+use actix_codec::Framed;
+use awc::{
+    error::WsProtocolError,
+    ws::{self, Frame},
+    BoxedSocket,
+};
+use derive_more::{Display, From};
+use failure::Fail;
+use futures::{channel::oneshot, SinkExt as _, StreamExt as _};
+use medea_client_api_proto::{
+    ClientCapabilities, ClientMsg, Command, Credential, Event, MemberId,
+    RoomId, RpcSettings, ServerMsg,
+};
+use tonic::transport;
+use url::Url;
+
+use crate::{
+    api::{
+        client::{health::HealthChecker, server::Server},
+        control::{
+            self, grpc::server::GrpcServer, RoomSpec, RootElement,
+            TryFromElementError,
+        },
+    },
+    conf::{server::PublicUrl, Conf},
+    shutdown::{GracefulShutdown, ShutdownGracefully},
+    signalling::{
+        room::Chaos,
+        room_service::{CreateRoom, RoomServiceError, Sids},
+        RoomRepository, RoomService,
+    },
+    turn::new_turn_auth_service_mock,
+    AppContext,
+};
+
+/// Errors of [`TestServer`] and [`TestClient`].
+#[derive(Debug, Display, Fail, From)]
+pub enum TestingError {
+    /// Failed to bind or to connect to some socket.
+    #[display(fmt = "I/O error: {}", _0)]
+    Io(io::Error),
+
+    /// Failed to start [`TestServer`].
+    #[display(fmt = "Failed to start server: {}", _0)]
+    #[from(ignore)]
+    Start(failure::Error),
+
+    /// Failed to communicate with some server actor.
+    #[display(fmt = "Mailbox error: {}", _0)]
+    Mailbox(MailboxError),
+
+    /// Provided [Control API] spec is not valid YAML.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[display(fmt = "Failed to parse spec: {}", _0)]
+    Yaml(serde_yaml::Error),
+
+    /// Provided [Control API] spec cannot be converted into [`RoomSpec`].
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[display(fmt = "Invalid spec: {}", _0)]
+    Spec(TryFromElementError),
+
+    /// [`RoomService`] failed to create a `Room`.
+    #[display(fmt = "RoomService error: {}", _0)]
+    RoomService(RoomServiceError),
+
+    /// Provided `Member` SID cannot be used to connect to [`TestServer`].
+    #[display(fmt = "Invalid SID: {}", _0)]
+    #[from(ignore)]
+    InvalidSid(String),
+
+    /// Failed to establish WebSocket connection.
+    #[display(fmt = "Failed to connect: {}", _0)]
+    #[from(ignore)]
+    Connect(String),
+
+    /// WebSocket protocol error.
+    #[display(fmt = "WebSocket error: {}", _0)]
+    Ws(WsProtocolError),
+
+    /// Failed to (de)serialize a [Client API] message.
+    ///
+    /// [Client API]: https://tinyurl.com/yx9thsnr
+    #[display(fmt = "Malformed message: {}", _0)]
+    Json(serde_json::Error),
+}
+
+/// Full [Medea] server running in the current [`actix::System`].
+///
+/// Both [Client API] and [Control API] servers are bound to ephemeral ports
+/// on `127.0.0.1`, and a mocked [TURN] service is used.
+///
+/// [Client API]: https://tinyurl.com/yx9thsnr
+/// [Control API]: https://tinyurl.com/yxsqplq7
+/// [Medea]: https://github.com/instrumentisto/medea
+/// [TURN]: https://webrtcglossary.com/turn
+pub struct TestServer {
+    /// Address the [Client API] HTTP server is bound to.
+    ///
+    /// [Client API]: https://tinyurl.com/yx9thsnr
+    client_api_addr: SocketAddr,
+
+    /// Address the [Control API] gRPC server is bound to.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    control_api_addr: SocketAddr,
+
+    /// [`RoomService`] of this [`TestServer`].
+    room_service: Addr<RoomService>,
+
+    /// Switch of the chaos mode of the `Room`s of this [`TestServer`].
+    chaos: Chaos,
+
+    /// [Client API] HTTP server.
+    ///
+    /// [Client API]: https://tinyurl.com/yx9thsnr
+    client_api_server: Addr<Server>,
+
+    /// [Control API] gRPC server.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    control_api_server: Addr<GrpcServer>,
+
+    /// Result of the [Control API] gRPC server run.
+    ///
+    /// Is held to not stop the [`actix::System`] once the gRPC server
+    /// finishes.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    _control_api_result: oneshot::Receiver<Result<(), transport::Error>>,
+}
+
+impl TestServer {
+    /// Starts a new [`TestServer`] with the provided [`Conf`].
+    ///
+    /// Bind addresses and public URL of the provided [`Conf`] are overridden.
+    ///
+    /// Must be called inside a running [`actix::System`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if no free port can be found, or any of the servers fails to
+    /// start.
+    pub async fn start(mut conf: Conf) -> Result<Self, TestingError> {
+        let localhost = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let client_api_addr = SocketAddr::new(localhost, free_port()?);
+        let control_api_addr = SocketAddr::new(localhost, free_port()?);
+
+        conf.server.client.http.bind_ip = client_api_addr.ip();
+        conf.server.client.http.bind_port = client_api_addr.port();
+        conf.server.client.http.public_url =
+            PublicUrl(format!("ws://{}/ws", client_api_addr));
+        conf.server.control.grpc.bind_ip = control_api_addr.ip();
+        conf.server.control.grpc.bind_port = control_api_addr.port();
+
+        let graceful_shutdown =
+            GracefulShutdown::new(conf.shutdown.timeout).start();
+        let app_context =
+            AppContext::new(conf.clone(), new_turn_auth_service_mock());
+
+        let room_repo = RoomRepository::new();
+        let room_service = RoomService::new(
+            room_repo.clone(),
+            app_context.clone(),
+            graceful_shutdown,
+        )
+        .map_err(|e| TestingError::Start(e.into()))?
+        .start();
+        control::start_static_rooms(&room_service)
+            .await
+            .map_err(TestingError::Start)?;
+
+        let (control_api_server, control_api_result) =
+            control::grpc::server::run(room_service.clone(), &app_context)?;
+        let health = HealthChecker::new(
+            room_service.clone(),
+            &app_context,
+            control_api_addr,
+        );
+        let client_api_server = Server::run(room_repo, conf, health)?;
+
+        Ok(Self {
+            client_api_addr,
+            control_api_addr,
+            room_service,
+            chaos: app_context.chaos,
+            client_api_server,
+            control_api_server,
+            _control_api_result: control_api_result,
+        })
+    }
+
+    /// Returns URL of the [Client API] WebSocket endpoint of this
+    /// [`TestServer`].
+    ///
+    /// [Client API]: https://tinyurl.com/yx9thsnr
+    #[inline]
+    #[must_use]
+    pub fn client_api_url(&self) -> String {
+        format!("ws://{}/ws", self.client_api_addr)
+    }
+
+    /// Returns address of the [Control API] gRPC server of this
+    /// [`TestServer`].
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[inline]
+    #[must_use]
+    pub fn control_api_addr(&self) -> SocketAddr {
+        self.control_api_addr
+    }
+
+    /// Returns [`RoomService`] of this [`TestServer`], which can be used to
+    /// manage its `Room`s directly, omitting [Control API].
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    #[inline]
+    #[must_use]
+    pub fn room_service(&self) -> &Addr<RoomService> {
+        &self.room_service
+    }
+
+    /// Returns switch of the chaos mode of the `Room`s of this [`TestServer`],
+    /// which can be used to reorder messages processed by them.
+    #[inline]
+    #[must_use]
+    pub fn chaos(&self) -> &Chaos {
+        &self.chaos
+    }
+
+    /// Creates a new `Room` from the provided [`RoomSpec`].
+    ///
+    /// Returns SIDs of the created `Member`s, which can be passed to
+    /// [`TestClient::connect()`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if [`RoomService`] fails to create the `Room`.
+    pub async fn create_room(
+        &self,
+        spec: RoomSpec,
+    ) -> Result<Sids, TestingError> {
+        Ok(self.room_service.send(CreateRoom { spec }).await??)
+    }
+
+    /// Creates a new `Room` from the provided YAML [Control API] spec.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the provided spec is invalid, or [`RoomService`] fails to
+    /// create the `Room`.
+    ///
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub async fn create_room_from_yaml(
+        &self,
+        yaml: &str,
+    ) -> Result<Sids, TestingError> {
+        let parsed: RootElement = serde_yaml::from_str(yaml)?;
+        self.create_room(RoomSpec::try_from(&parsed)?).await
+    }
+
+    /// Stops [Client API] and [Control API] servers of this [`TestServer`].
+    ///
+    /// [Client API]: https://tinyurl.com/yx9thsnr
+    /// [Control API]: https://tinyurl.com/yxsqplq7
+    pub async fn stop(self) {
+        let _ = self.client_api_server.send(ShutdownGracefully).await;
+        let _ = self.control_api_server.send(ShutdownGracefully).await;
+    }
+}
+
+/// Programmatic [Client API] client, connected as some `Member` to a `Room`.
+///
+/// Answers [`ServerMsg::Ping`]s automatically while awaiting [`Event`]s.
+///
+/// [Client API]: https://tinyurl.com/yx9thsnr
+pub struct TestClient {
+    /// [`RoomId`] of the `Room` this [`TestClient`] is connected to.
+    room_id: RoomId,
+
+    /// WebSocket connection with a server.
+    conn: Framed<BoxedSocket, ws::Codec>,
+
+    /// Last [`RpcSettings`] received from a server.
+    rpc_settings: Option<RpcSettings>,
+}
+
+impl TestClient {
+    /// Connects to a server by the provided `Member` SID (as returned by
+    /// [`TestServer::create_room()`]) and sends [`Command::JoinRoom`].
+    ///
+    /// # Errors
+    ///
+    /// Errors if the provided SID is malformed, or connection fails.
+    pub async fn connect(sid: &str) -> Result<Self, TestingError> {
+        let (url, room_id, member_id, credential) = parse_sid(sid)?;
+        let (_, conn) = awc::Client::new()
+            .ws(url.as_str())
+            .connect()
+            .await
+            .map_err(|e| TestingError::Connect(e.to_string()))?;
+
+        let mut this = Self {
+            room_id,
+            conn,
+            rpc_settings: None,
+        };
+        this.send(Command::JoinRoom {
+            member_id,
+            credential,
+            capabilities: ClientCapabilities::default(),
+        })
+        .await?;
+        Ok(this)
+    }
+
+    /// Returns [`RoomId`] of the `Room` this [`TestClient`] is connected to.
+    #[inline]
+    #[must_use]
+    pub fn room_id(&self) -> &RoomId {
+        &self.room_id
+    }
+
+    /// Returns last [`RpcSettings`] received from a server, if any.
+    #[inline]
+    #[must_use]
+    pub fn rpc_settings(&self) -> Option<&RpcSettings> {
+        self.rpc_settings.as_ref()
+    }
+
+    /// Sends the provided [`Command`] to a server.
+    ///
+    /// # Errors
+    ///
+    /// Errors if sending fails.
+    pub async fn send(&mut self, command: Command) -> Result<(), TestingError> {
+        self.send_msg(&ClientMsg::Command {
+            room_id: self.room_id.clone(),
+            command,
+        })
+        .await
+    }
+
+    /// Awaits the next [`Event`] from a server.
+    ///
+    /// Returns [`None`] once connection is closed.
+    ///
+    /// # Errors
+    ///
+    /// Errors if a malformed message is received, or connection fails.
+    pub async fn next_event(&mut self) -> Result<Option<Event>, TestingError> {
+        while let Some(frame) = self.conn.next().await {
+            match frame? {
+                Frame::Text(text) => match serde_json::from_slice(&text)? {
+                    ServerMsg::Event { event, .. } => return Ok(Some(event)),
+                    ServerMsg::Ping(n) => {
+                        self.send_msg(&ClientMsg::Pong(n)).await?;
+                    }
+                    ServerMsg::RpcSettings(settings) => {
+                        self.rpc_settings = Some(settings);
+                    }
+                },
+                Frame::Ping(payload) => {
+                    self.conn.send(ws::Message::Pong(payload)).await?;
+                }
+                Frame::Close(_) => return Ok(None),
+                Frame::Binary(_) | Frame::Continuation(_) | Frame::Pong(_) => {}
+            }
+        }
+        Ok(None)
+    }
+
+    /// Closes connection with a server normally.
+    ///
+    /// # Errors
+    ///
+    /// Errors if sending a close frame fails.
+    pub async fn close(mut self) -> Result<(), TestingError> {
+        self.conn
+            .send(ws::Message::Close(Some(ws::CloseCode::Normal.into())))
+            .await?;
+        Ok(())
+    }
+
+    /// Sends the provided [`ClientMsg`] to a server.
+    async fn send_msg(&mut self, msg: &ClientMsg) -> Result<(), TestingError> {
+        let json = serde_json::to_string(msg)?;
+        self.conn.send(ws::Message::Text(json.into())).await?;
+        Ok(())
+    }
+}
+
+/// Returns a currently free TCP port on `127.0.0.1`.
+fn free_port() -> io::Result<u16> {
+    Ok(TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?
+        .local_addr()?
+        .port())
+}
+
+/// Splits the provided `Member` SID into a [Client API] WebSocket URL,
+/// [`RoomId`], [`MemberId`] and [`Credential`].
+///
+/// [Client API]: https://tinyurl.com/yx9thsnr
+fn parse_sid(
+    sid: &str,
+) -> Result<(Url, RoomId, MemberId, Credential), TestingError> {
+    let invalid = || TestingError::InvalidSid(sid.to_owned());
+
+    let mut url = Url::parse(sid).map_err(|_| invalid())?;
+    let credential = url
+        .query_pairs()
+        .find(|(key, _)| key == "token")
+        .map(|(_, token)| token.into_owned())
+        .ok_or_else(invalid)?;
+    url.set_query(None);
+    url.set_fragment(None);
+
+    let mut segments = url.path_segments().ok_or_else(invalid)?.rev();
+    let member_id = segments.next().ok_or_else(invalid)?.to_owned();
+    let room_id = segments.next().ok_or_else(invalid)?.to_owned();
+    let path = segments.rev().collect::<Vec<_>>().join("/");
+    url.set_path(&path);
+
+    Ok((url, room_id.into(), member_id.into(), credential.into()))
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    #[actix_rt::test]
+    async fn members_join_room_on_test_server() {
+        let server = TestServer::start(Conf::default()).await.unwrap();
+        let sids = server
+            .create_room_from_yaml(include_str!(
+                "../tests/specs/pub-sub-video-call.yml"
+            ))
+            .await
+            .unwrap();
+        assert!(sids[&MemberId::from("caller")]
+            .to_string()
+            .starts_with(&server.client_api_url()));
+
+        let mut caller =
+            TestClient::connect(&sids[&MemberId::from("caller")].to_string())
+                .await
+                .unwrap();
+        assert_eq!(
+            caller.next_event().await.unwrap(),
+            Some(Event::RoomJoined {
+                member_id: "caller".into(),
+            }),
+        );
+        assert!(caller.rpc_settings().is_some());
+
+        caller.close().await.unwrap();
+        server.stop().await;
+    }
+
+    #[test]
+    fn parses_sid() {
+        let (url, room_id, member_id, credential) =
+            parse_sid("ws://127.0.0.1:8080/ws/room/member?token=secret")
+                .unwrap();
+
+        assert_eq!(url.as_str(), "ws://127.0.0.1:8080/ws");
+        assert_eq!(room_id, "room".into());
+        assert_eq!(member_id, "member".into());
+        assert_eq!(credential, "secret".into());
+
+        assert!(parse_sid("ws://127.0.0.1:8080/ws/room").is_err());
+    }
+}
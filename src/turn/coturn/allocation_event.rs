@@ -124,7 +124,8 @@ impl CoturnEvent {
 
         let (room_id, peer_id) = {
             let user = channel_splitted.next().ok_or(E::NoUserInfo)?;
-            let mut user_splitted = user.split('_');
+            // Skips ID of Medea node prefixing the username.
+            let mut user_splitted = user.split('_').skip(1);
             let room_id = RoomId::from(
                 user_splitted.next().ok_or(E::NoMemberId)?.to_string(),
             );
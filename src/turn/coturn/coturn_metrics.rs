@@ -3,7 +3,7 @@
 //! [`Peer`]: crate::media::peer::Peer
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -18,7 +18,11 @@ use redis::{ConnectionInfo, RedisError};
 use crate::{
     conf,
     log::prelude::*,
-    signalling::peers::{FlowMetricSource, PeerTrafficWatcher},
+    signalling::{
+        peers::{FlowMetricSource, PeerTrafficWatcher},
+        room::PeerRelayed,
+        room_repo::RoomRepository,
+    },
 };
 
 use super::{
@@ -49,6 +53,13 @@ pub struct CoturnMetricsService {
 
     /// Count of allocations for each [`CoturnUsername`] (which acts as a key).
     allocations_count: HashMap<CoturnUsername, u64>,
+
+    /// [`CoturnUsername`]s of the `Peer`s which traffic is relayed via
+    /// Coturn.
+    relayed: HashSet<CoturnUsername>,
+
+    /// [`RoomRepository`] to notify `Room`s about their relayed `Peer`s via.
+    room_repo: RoomRepository,
 }
 
 impl CoturnMetricsService {
@@ -57,10 +68,10 @@ impl CoturnMetricsService {
     /// # Errors
     ///
     /// [`RedisError`] can be returned if some basic check on the URL is failed.
-    #[allow(dead_code)]
     pub fn new(
         cf: &conf::ice::Coturn,
         peer_traffic_watcher: Arc<dyn PeerTrafficWatcher>,
+        room_repo: RoomRepository,
     ) -> Result<Self, RedisError> {
         let client = redis::Client::open(ConnectionInfo::from(&cf.db.redis))?;
 
@@ -68,9 +79,30 @@ impl CoturnMetricsService {
             client,
             allocations_count: HashMap::new(),
             peer_traffic_watcher,
+            relayed: HashSet::new(),
+            room_repo,
         })
     }
 
+    /// Notifies the `Room` of the provided [`CoturnUsername`] whether traffic
+    /// of its `Peer` is relayed, if it has changed.
+    fn set_relayed(&mut self, username: CoturnUsername, relayed: bool) {
+        let changed = if relayed {
+            self.relayed.insert(username.clone())
+        } else {
+            self.relayed.remove(&username)
+        };
+        if !changed {
+            return;
+        }
+        if let Some(room) = self.room_repo.get(&username.room_id) {
+            room.do_send(PeerRelayed {
+                peer_id: username.peer_id,
+                relayed,
+            });
+        }
+    }
+
     /// Opens new Redis connection, subscribes to Coturn events and injects
     /// [`Stream`] with these events into the [`CoturnMetricsService`]'s
     /// context.
@@ -151,14 +183,15 @@ impl StreamHandler<redis::Msg> for CoturnMetricsService {
             peer_id: event.peer_id,
         };
 
-        let allocations_count =
-            self.allocations_count.entry(username).or_insert(0);
         match event.event {
+            CoturnAllocationEvent::New { .. } => {
+                *self.allocations_count.entry(username).or_insert(0) += 1;
+            }
             CoturnAllocationEvent::Traffic { traffic } => {
-                *allocations_count += 1;
                 let is_traffic_really_going =
                     traffic.sent_packets + traffic.received_packets > 10;
                 if is_traffic_really_going {
+                    self.set_relayed(username, true);
                     self.peer_traffic_watcher.traffic_flows(
                         event.room_id,
                         event.peer_id,
@@ -167,8 +200,12 @@ impl StreamHandler<redis::Msg> for CoturnMetricsService {
                 }
             }
             CoturnAllocationEvent::Deleted => {
-                *allocations_count -= 1;
+                let allocations_count =
+                    self.allocations_count.entry(username.clone()).or_insert(0);
+                *allocations_count = allocations_count.saturating_sub(1);
                 if *allocations_count == 0 {
+                    self.allocations_count.remove(&username);
+                    self.set_relayed(username, false);
                     self.peer_traffic_watcher.traffic_stopped(
                         event.room_id,
                         event.peer_id,
@@ -21,10 +21,26 @@ pub struct IceUsername(String);
 
 impl IceUsername {
     /// Returns a new [`IceUsername`] for the provided [`RoomId`] and
-    /// [`PeerId`].
+    /// [`PeerId`], created by the Medea node with the provided `node_id`.
     #[must_use]
-    fn new(room_id: &RoomId, peer_id: PeerId) -> Self {
-        Self(format!("{}_{}", room_id, peer_id))
+    fn new(node_id: &str, room_id: &RoomId, peer_id: PeerId) -> Self {
+        Self(format!("{}_{}_{}", node_id, room_id, peer_id))
+    }
+
+    /// Returns a prefix of all the [`IceUsername`]s created by the Medea node
+    /// with the provided `node_id`.
+    #[inline]
+    #[must_use]
+    pub fn node_prefix(node_id: &str) -> String {
+        format!("{}_", node_id)
+    }
+
+    /// Indicates whether this [`IceUsername`] is created by the Medea node
+    /// with the provided `node_id`.
+    #[inline]
+    #[must_use]
+    pub fn is_created_by(&self, node_id: &str) -> bool {
+        self.0.starts_with(&Self::node_prefix(node_id))
     }
 }
 
@@ -74,6 +90,7 @@ impl CoturnIceUser {
     #[must_use]
     pub fn new_non_static(
         address: String,
+        node_id: &str,
         room_id: &RoomId,
         peer_id: PeerId,
         pass: IcePassword,
@@ -81,7 +98,7 @@ impl CoturnIceUser {
     ) -> Self {
         Self {
             address,
-            user: IceUsername::new(&room_id, peer_id),
+            user: IceUsername::new(node_id, &room_id, peer_id),
             pass,
             on_drop: Some(on_drop),
         }
@@ -158,6 +175,7 @@ mod spec {
 
         let user = CoturnIceUser::new_non_static(
             String::new(),
+            "medea",
             &RoomId::from("foobar"),
             PeerId(0),
             IcePassword::generate(),
@@ -170,4 +188,13 @@ mod spec {
         assert_eq!(rx.next().await.unwrap(), user_name);
         assert!(rx.next().await.is_none());
     }
+
+    #[test]
+    fn prefixes_username_with_node_id() {
+        let prefix = IceUsername::node_prefix("node1");
+        let user = IceUsername::new("node1", &RoomId::from("foo"), PeerId(3));
+
+        assert_eq!(user.to_string(), "node1_foo_3");
+        assert!(user.to_string().starts_with(&prefix));
+    }
 }
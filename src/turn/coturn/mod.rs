@@ -13,7 +13,11 @@ use std::slice;
 
 use async_trait::async_trait;
 use derive_more::Display;
-use futures::{channel::mpsc, StreamExt as _};
+use futures::{
+    channel::{mpsc, oneshot},
+    future::{FutureExt as _, Shared},
+    StreamExt as _,
+};
 use medea_client_api_proto::{PeerId, RoomId};
 use redis::ConnectionInfo;
 use tokio::task::JoinHandle;
@@ -29,7 +33,8 @@ use self::{
 };
 
 pub use self::{
-    cli::CoturnCliError, ice_user::CoturnIceUser, repo::TurnDatabaseErr,
+    cli::CoturnCliError, coturn_metrics::CoturnMetricsService,
+    ice_user::CoturnIceUser, repo::TurnDatabaseErr,
 };
 
 /// Username of the [Coturn] user.
@@ -72,6 +77,14 @@ pub struct Service {
     /// Turn server static user password.
     turn_password: String,
 
+    /// ID of this Medea node, prefixing all the created [`IceUsername`]s.
+    node_id: String,
+
+    /// Future resolving once stale [`IceUser`]s of this Medea node are removed
+    /// on startup (if enabled), so no new [`IceUser`]s are created before
+    /// that.
+    stale_users_removed: Shared<oneshot::Receiver<()>>,
+
     /// Channel sender signalling about an [`IseUser`] no longer being used and
     /// that it should be removed from [Coturn].
     ///
@@ -104,11 +117,19 @@ impl Service {
         );
 
         let (tx, mut rx) = mpsc::unbounded();
+        let (removed_tx, removed_rx) = oneshot::channel();
 
         let users_cleanup_task = {
             let db = turn_db.clone();
             let cli = coturn_cli.clone();
+            let node_id = cf.node_id.to_string();
+            let remove_stale = cf.remove_stale_users;
             tokio::spawn(async move {
+                if remove_stale {
+                    remove_stale_users(&db, &cli, &node_id).await;
+                }
+                let _ = removed_tx.send(());
+
                 while let Some(user) = rx.next().await {
                     let users = slice::from_ref(&user);
                     if let Err(e) = db.remove(users).await {
@@ -137,6 +158,8 @@ impl Service {
             turn_address: cf.addr(),
             turn_username: cf.user.to_string(),
             turn_password: cf.pass.to_string(),
+            node_id: cf.node_id.to_string(),
+            stale_users_removed: removed_rx.shared(),
             drop_tx: MpscOneshotSender::from(tx),
             users_cleanup_task,
         })
@@ -165,8 +188,12 @@ impl TurnAuthService for Service {
         peer_id: PeerId,
         policy: UnreachablePolicy,
     ) -> Result<Vec<IceUser>, TurnServiceErr> {
+        // Cleanup task being aborted means no stale users to wait for.
+        let _ = self.stale_users_removed.clone().await;
+
         let ice_user = CoturnIceUser::new_non_static(
             self.turn_address.clone(),
+            &self.node_id,
             &room_id,
             peer_id,
             IcePassword::generate(),
@@ -183,6 +210,44 @@ impl TurnAuthService for Service {
             },
         }
     }
+
+    /// Checks whether [`TurnDatabase`] is reachable.
+    #[inline]
+    async fn check_health(&self) -> Result<(), TurnServiceErr> {
+        Ok(self.turn_db.ping().await?)
+    }
+}
+
+/// Removes all the [`IceUser`]s of the Medea node with the provided `node_id`
+/// from the [`TurnDatabase`] and [Coturn], being left there after its abnormal
+/// shutdown.
+///
+/// Failures are only logged, as they don't prevent [`Service`] from working.
+///
+/// [Coturn]: https://github.com/coturn/coturn
+async fn remove_stale_users(
+    db: &TurnDatabase,
+    cli: &CoturnTelnetClient,
+    node_id: &str,
+) {
+    let users = match db.find_by_node(node_id).await {
+        Ok(users) => users,
+        Err(e) => {
+            log::warn!("Failed to find stale IceUsers in Redis: {}", e);
+            return;
+        }
+    };
+    if users.is_empty() {
+        return;
+    }
+
+    log::info!("Removing {} stale IceUsers: {:?}", users.len(), users);
+    if let Err(e) = db.remove(&users).await {
+        log::warn!("Failed to remove stale IceUsers from Redis: {}", e);
+    }
+    if let Err(e) = cli.delete_sessions(&users).await {
+        log::warn!("Failed to remove stale IceUsers from Coturn: {}", e);
+    }
 }
 
 impl Drop for Service {
@@ -102,6 +102,50 @@ impl TurnDatabase {
         let mut conn = self.0.get().await?;
         Ok(cmd("DEL").arg(keys).query_async(&mut conn).await?)
     }
+
+    /// Returns [`IceUsername`]s of all the [`IceUser`]s created by the Medea
+    /// node with the provided `node_id`, which are stored in remote Redis
+    /// database.
+    ///
+    /// # Errors
+    ///
+    /// Errors if unable to establish connection with database, or database
+    /// request fails.
+    pub async fn find_by_node(
+        &self,
+        node_id: &str,
+    ) -> Result<Vec<IceUsername>, TurnDatabaseErr> {
+        let pattern = IceUsername::node_redis_keys_pattern(node_id);
+
+        let mut conn = self.0.get().await?;
+        let mut users = Vec::new();
+        let mut cursor = 0_u64;
+        loop {
+            let (next, keys): (u64, Vec<String>) = cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .query_async(&mut conn)
+                .await?;
+            users.extend(parse_node_users(&keys, node_id));
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+        Ok(users)
+    }
+
+    /// Checks whether remote Redis database is reachable.
+    ///
+    /// # Errors
+    ///
+    /// Errors if unable to establish connection with database, or database
+    /// request fails.
+    pub async fn ping(&self) -> Result<(), TurnDatabaseErr> {
+        let mut conn = self.0.get().await?;
+        Ok(cmd("PING").query_async(&mut conn).await?)
+    }
 }
 
 impl IceUsername {
@@ -110,6 +154,39 @@ impl IceUsername {
     fn redis_key(&self) -> String {
         format!("turn/realm/{}/user/{}/key", COTURN_REALM, self)
     }
+
+    /// Returns a Redis glob pattern matching keys of all the [`IceUsername`]s
+    /// created by the Medea node with the provided `node_id`.
+    #[must_use]
+    fn node_redis_keys_pattern(node_id: &str) -> String {
+        format!(
+            "turn/realm/{}/user/{}*/key",
+            COTURN_REALM,
+            Self::node_prefix(node_id),
+        )
+    }
+
+    /// Parses an [`IceUsername`] from the provided Redis `key`.
+    ///
+    /// Returns [`None`] if the provided `key` isn't a key of an
+    /// [`IceUsername`].
+    #[must_use]
+    fn from_redis_key(key: &str) -> Option<Self> {
+        key.strip_prefix(&format!("turn/realm/{}/user/", COTURN_REALM))?
+            .strip_suffix("/key")
+            .map(|name| Self::from(name.to_owned()))
+    }
+}
+
+/// Parses [`IceUsername`]s created by the Medea node with the provided
+/// `node_id` out of the provided Redis `keys`, skipping all the other ones.
+fn parse_node_users<'a>(
+    keys: &'a [String],
+    node_id: &'a str,
+) -> impl Iterator<Item = IceUsername> + 'a {
+    keys.iter()
+        .filter_map(|k| IceUsername::from_redis_key(k))
+        .filter(move |u| u.is_created_by(node_id))
 }
 
 impl CoturnIceUser {
@@ -137,3 +214,35 @@ impl fmt::Debug for TurnDatabase {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    #[test]
+    fn finds_only_own_node_users() {
+        assert_eq!(
+            IceUsername::node_redis_keys_pattern("node1"),
+            "turn/realm/medea/user/node1_*/key",
+        );
+
+        let keys: Vec<_> = [
+            "node1_room_1",
+            "node10_room_2",
+            "node2_room_1",
+            "node1-b_room_1",
+            "static",
+            "node1_room_3",
+        ]
+        .iter()
+        .map(|name| IceUsername::from((*name).to_owned()).redis_key())
+        .chain(vec!["turn/realm/medea/user/node1_room_4".to_owned()])
+        .collect();
+
+        let users: Vec<_> = parse_node_users(&keys, "node1")
+            .map(|u| u.to_string())
+            .collect();
+
+        assert_eq!(users, vec!["node1_room_1", "node1_room_3"]);
+    }
+}
@@ -9,7 +9,7 @@ use medea_client_api_proto::IceServer;
 
 use crate::turn::static_service::StaticIceUser;
 
-use super::coturn::CoturnIceUser;
+use super::{coturn::CoturnIceUser, pool::IssuedGuard};
 
 /// Error indicating that [`IceUsers`] is empty.
 #[derive(Debug)]
@@ -72,6 +72,13 @@ pub enum IceUser {
     /// [STUN]: https://webrtcglossary.com/stun
     /// [TURN]: https://webrtcglossary.com/turn
     Static(StaticIceUser),
+
+    /// [ICE] user issued from a pool of pre-allocated ones, returning its
+    /// `Peer` to the pool once dropped.
+    ///
+    /// [ICE]: https://webrtcglossary.com/ice
+    #[from(ignore)]
+    Pooled(Box<IceUser>, IssuedGuard),
 }
 
 impl IceUser {
@@ -83,11 +90,12 @@ impl IceUser {
             Self::Static(user) => {
                 vec![user.ice_server()]
             }
+            Self::Pooled(user, _) => user.servers_list(),
         }
     }
 }
 
-#[cfg(test)]
+#[cfg(any(test, feature = "testing"))]
 impl IceUser {
     /// Returns a new [Coturn] static [`IceUser`] with the provided credentials.
     ///
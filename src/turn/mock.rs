@@ -0,0 +1,321 @@
+//! Mock [`TurnAuthService`] and minimal [STUN] responder, used for testing
+//! [Medea] and custom deployments without a real [TURN] server.
+//!
+//! [Medea]: https://github.com/instrumentisto/medea
+//! [STUN]: https://webrtcglossary.com/stun
+//! [TURN]: https://webrtcglossary.com/turn
+
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use async_trait::async_trait;
+use medea_client_api_proto::{PeerId, RoomId};
+use tokio::{net::UdpSocket, task::JoinHandle};
+
+use crate::turn::{IceUser, TurnAuthService, TurnServiceErr, UnreachablePolicy};
+
+/// [`TurnAuthService`] returning static [Coturn] credentials without
+/// accessing any external service.
+///
+/// Records all the [`TurnAuthService::create()`] calls, so they can be
+/// inspected in tests.
+///
+/// [Coturn]: https://github.com/coturn/coturn
+#[derive(Debug)]
+pub struct TurnAuthServiceMock {
+    /// Address of the [TURN] server returned in [`IceUser`]s.
+    ///
+    /// [TURN]: https://webrtcglossary.com/turn
+    address: String,
+
+    /// Username returned in [`IceUser`]s.
+    username: String,
+
+    /// Password returned in [`IceUser`]s.
+    password: String,
+
+    /// Indicator whether [`TurnAuthService::create()`] should fail with
+    /// [`TurnServiceErr::TimedOut`].
+    failing: AtomicBool,
+
+    /// [`RoomId`]s and [`PeerId`]s of all the [`TurnAuthService::create()`]
+    /// calls made on this [`TurnAuthServiceMock`].
+    created: Mutex<Vec<(RoomId, PeerId)>>,
+}
+
+impl TurnAuthServiceMock {
+    /// Returns a new [`TurnAuthServiceMock`] with the provided [TURN] server
+    /// address and credentials.
+    ///
+    /// [TURN]: https://webrtcglossary.com/turn
+    #[must_use]
+    pub fn new(address: String, username: String, password: String) -> Self {
+        Self {
+            address,
+            username,
+            password,
+            failing: AtomicBool::new(false),
+            created: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Makes all the subsequent [`TurnAuthService::create()`] calls fail (or
+    /// succeed again) as if the [TURN] database was unreachable.
+    ///
+    /// [TURN]: https://webrtcglossary.com/turn
+    #[inline]
+    pub fn set_failing(&self, failing: bool) {
+        self.failing.store(failing, Ordering::SeqCst);
+    }
+
+    /// Returns [`RoomId`]s and [`PeerId`]s of all the
+    /// [`TurnAuthService::create()`] calls made so far.
+    #[must_use]
+    pub fn created(&self) -> Vec<(RoomId, PeerId)> {
+        self.created.lock().unwrap().clone()
+    }
+}
+
+impl Default for TurnAuthServiceMock {
+    #[inline]
+    fn default() -> Self {
+        Self::new("5.5.5.5:1234".into(), "username".into(), "password".into())
+    }
+}
+
+#[async_trait]
+impl TurnAuthService for TurnAuthServiceMock {
+    /// Returns a single static [Coturn] [`IceUser`] of this
+    /// [`TurnAuthServiceMock`].
+    ///
+    /// # Errors
+    ///
+    /// With [`TurnServiceErr::TimedOut`] if this [`TurnAuthServiceMock`] is
+    /// [set failing][1], regardless of the provided [`UnreachablePolicy`].
+    ///
+    /// [1]: TurnAuthServiceMock::set_failing
+    /// [Coturn]: https://github.com/coturn/coturn
+    async fn create(
+        &self,
+        room_id: RoomId,
+        peer_id: PeerId,
+        _: UnreachablePolicy,
+    ) -> Result<Vec<IceUser>, TurnServiceErr> {
+        self.created.lock().unwrap().push((room_id, peer_id));
+        if self.failing.load(Ordering::SeqCst) {
+            return Err(TurnServiceErr::TimedOut);
+        }
+        Ok(vec![IceUser::new_coturn_static(
+            self.address.clone(),
+            self.username.clone(),
+            self.password.clone(),
+        )])
+    }
+
+    /// Checks whether this [`TurnAuthServiceMock`] is [set failing][1].
+    ///
+    /// # Errors
+    ///
+    /// With [`TurnServiceErr::TimedOut`] if this [`TurnAuthServiceMock`] is
+    /// [set failing][1].
+    ///
+    /// [1]: TurnAuthServiceMock::set_failing
+    async fn check_health(&self) -> Result<(), TurnServiceErr> {
+        if self.failing.load(Ordering::SeqCst) {
+            return Err(TurnServiceErr::TimedOut);
+        }
+        Ok(())
+    }
+}
+
+/// Returns a new [`TurnAuthServiceMock`] with default credentials.
+#[inline]
+#[must_use]
+pub fn new_turn_auth_service_mock() -> Arc<dyn TurnAuthService> {
+    Arc::new(TurnAuthServiceMock::default())
+}
+
+/// Minimal [STUN] server answering Binding Requests over UDP.
+///
+/// Is enough for ICE candidates gathering of `srflx` type, so no real
+/// [TURN] server is required. Stops once dropped.
+///
+/// [STUN]: https://webrtcglossary.com/stun
+/// [TURN]: https://webrtcglossary.com/turn
+#[derive(Debug)]
+pub struct StunResponder {
+    /// Address this [`StunResponder`] is bound to.
+    addr: SocketAddr,
+
+    /// Task serving incoming [STUN] requests.
+    ///
+    /// [STUN]: https://webrtcglossary.com/stun
+    task: JoinHandle<()>,
+}
+
+impl StunResponder {
+    /// [STUN] magic cookie as defined in [RFC 5389 Section 6][1].
+    ///
+    /// [STUN]: https://webrtcglossary.com/stun
+    /// [1]: https://tools.ietf.org/html/rfc5389#section-6
+    const MAGIC_COOKIE: u32 = 0x2112_A442;
+
+    /// Binds a new [`StunResponder`] on an ephemeral UDP port of
+    /// `127.0.0.1`.
+    ///
+    /// Must be called inside a running [Tokio] runtime.
+    ///
+    /// # Errors
+    ///
+    /// Errors if binding the UDP socket fails.
+    ///
+    /// [Tokio]: https://docs.rs/tokio
+    pub async fn start() -> io::Result<Self> {
+        let socket =
+            UdpSocket::bind((IpAddr::V4(Ipv4Addr::LOCALHOST), 0)).await?;
+        let addr = socket.local_addr()?;
+        let task = tokio::spawn(async move {
+            let mut buf = [0_u8; 1500];
+            while let Ok((len, from)) = socket.recv_from(&mut buf).await {
+                if let Some(resp) = Self::binding_response(&buf[..len], from)
+                {
+                    let _ = socket.send_to(&resp, from).await;
+                }
+            }
+        });
+        Ok(Self { addr, task })
+    }
+
+    /// Returns address this [`StunResponder`] is bound to.
+    #[inline]
+    #[must_use]
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns a new [`TurnAuthServiceMock`] pointing to this
+    /// [`StunResponder`].
+    #[must_use]
+    pub fn turn_auth_service(&self) -> TurnAuthServiceMock {
+        TurnAuthServiceMock::new(
+            self.addr.to_string(),
+            "username".into(),
+            "password".into(),
+        )
+    }
+
+    /// Builds a Binding Success Response with `XOR-MAPPED-ADDRESS` of the
+    /// provided `from` address for the provided Binding Request.
+    ///
+    /// Returns [`None`] if the provided `request` is not a valid Binding
+    /// Request.
+    fn binding_response(request: &[u8], from: SocketAddr) -> Option<Vec<u8>> {
+        if request.len() < 20
+            || request[0..2] != [0x00, 0x01]
+            || request[4..8] != Self::MAGIC_COOKIE.to_be_bytes()
+        {
+            return None;
+        }
+        let transaction_id = &request[8..20];
+
+        let port = from.port() ^ (Self::MAGIC_COOKIE >> 16) as u16;
+        let mut attr = vec![0x00];
+        match from.ip() {
+            IpAddr::V4(ip) => {
+                attr.push(0x01);
+                attr.extend_from_slice(&port.to_be_bytes());
+                let ip = u32::from(ip) ^ Self::MAGIC_COOKIE;
+                attr.extend_from_slice(&ip.to_be_bytes());
+            }
+            IpAddr::V6(ip) => {
+                attr.push(0x02);
+                attr.extend_from_slice(&port.to_be_bytes());
+                let mask = Self::MAGIC_COOKIE
+                    .to_be_bytes()
+                    .iter()
+                    .chain(transaction_id)
+                    .copied()
+                    .collect::<Vec<_>>();
+                attr.extend(ip.octets().iter().zip(mask).map(|(b, m)| b ^ m));
+            }
+        }
+
+        #[allow(clippy::cast_possible_truncation)]
+        let attr_len = attr.len() as u16;
+        let mut resp = Vec::with_capacity(24 + attr.len());
+        resp.extend_from_slice(&[0x01, 0x01]);
+        resp.extend_from_slice(&(attr_len + 4).to_be_bytes());
+        resp.extend_from_slice(&Self::MAGIC_COOKIE.to_be_bytes());
+        resp.extend_from_slice(transaction_id);
+        resp.extend_from_slice(&[0x00, 0x20]);
+        resp.extend_from_slice(&attr_len.to_be_bytes());
+        resp.extend(attr);
+        Some(resp)
+    }
+}
+
+impl Drop for StunResponder {
+    #[inline]
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use super::*;
+
+    #[actix_rt::test]
+    async fn records_created_and_fails_on_demand() {
+        let service = TurnAuthServiceMock::default();
+
+        let users = service
+            .create("room".into(), PeerId(1), UnreachablePolicy::Error)
+            .await
+            .unwrap();
+        assert_eq!(users.len(), 1);
+
+        service.set_failing(true);
+        assert!(service
+            .create("room".into(), PeerId(2), UnreachablePolicy::Static)
+            .await
+            .is_err());
+
+        assert_eq!(
+            service.created(),
+            vec![("room".into(), PeerId(1)), ("room".into(), PeerId(2))],
+        );
+    }
+
+    #[actix_rt::test]
+    async fn answers_binding_request() {
+        let stun = StunResponder::start().await.unwrap();
+        let client = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let client_addr = client.local_addr().unwrap();
+
+        let mut request = vec![0x00, 0x01, 0x00, 0x00];
+        request.extend_from_slice(&StunResponder::MAGIC_COOKIE.to_be_bytes());
+        request.extend_from_slice(&[7; 12]);
+        client.send_to(&request, stun.addr()).await.unwrap();
+
+        let mut buf = [0_u8; 64];
+        let (len, _) = client.recv_from(&mut buf).await.unwrap();
+        let resp = &buf[..len];
+
+        assert_eq!(&resp[0..2], &[0x01, 0x01]);
+        assert_eq!(&resp[8..20], &[7; 12]);
+        assert_eq!(&resp[20..22], &[0x00, 0x20]);
+        let port = u16::from_be_bytes([resp[26], resp[27]])
+            ^ (StunResponder::MAGIC_COOKIE >> 16) as u16;
+        assert_eq!(port, client_addr.port());
+        let ip = u32::from_be_bytes([resp[28], resp[29], resp[30], resp[31]])
+            ^ StunResponder::MAGIC_COOKIE;
+        assert_eq!(Ipv4Addr::from(ip), Ipv4Addr::LOCALHOST);
+    }
+}
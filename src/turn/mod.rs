@@ -4,6 +4,9 @@
 
 mod coturn;
 mod ice_user;
+#[cfg(any(test, feature = "testing"))]
+pub mod mock;
+mod pool;
 mod static_service;
 
 use std::sync::Arc;
@@ -16,13 +19,22 @@ use medea_client_api_proto::{PeerId, RoomId};
 
 use crate::{conf, turn::static_service::StaticService};
 
-use self::coturn::{CoturnCliError, Service as CoturnService, TurnDatabaseErr};
+use self::{
+    coturn::{CoturnCliError, Service as CoturnService, TurnDatabaseErr},
+    pool::PooledService,
+};
 
 #[doc(inline)]
-pub use self::ice_user::{EmptyIceServersListErr, IceUser, IceUsers};
+pub use self::{
+    coturn::CoturnMetricsService,
+    ice_user::{EmptyIceServersListErr, IceUser, IceUsers},
+};
 
-#[cfg(test)]
-pub use self::test::new_turn_auth_service_mock;
+#[cfg(any(test, feature = "testing"))]
+#[doc(inline)]
+pub use self::mock::{
+    new_turn_auth_service_mock, StunResponder, TurnAuthServiceMock,
+};
 
 /// Errors happening in [`TurnAuthService`].
 #[derive(Display, Debug, Fail, From)]
@@ -39,7 +51,7 @@ pub enum TurnServiceErr {
 }
 
 /// [`TurnAuthService`] behavior when remote database is unreachable.
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum UnreachablePolicy {
     /// Error will be propagated if request to database fails.
     Error,
@@ -63,6 +75,20 @@ pub trait TurnAuthService: fmt::Debug + Send + Sync {
         peer_id: PeerId,
         policy: UnreachablePolicy,
     ) -> Result<Vec<IceUser>, TurnServiceErr>;
+
+    /// Releases all the resources held for the provided [`RoomId`], once the
+    /// `Room` is closed.
+    #[inline]
+    fn release(&self, _: &RoomId) {}
+
+    /// Checks whether external services required for [TURN] credentials
+    /// generation are reachable.
+    ///
+    /// [TURN]: https://webrtcglossary.com/turn
+    #[inline]
+    async fn check_health(&self) -> Result<(), TurnServiceErr> {
+        Ok(())
+    }
 }
 
 /// Create a new instance of [`TurnAuthService`].
@@ -81,38 +107,14 @@ pub fn new_turn_auth_service<'a>(
             let static_servers = cf.r#static.values().cloned().collect();
             Arc::new(StaticService::new(static_servers))
         }
-        conf::ice::Kind::Coturn => Arc::new(CoturnService::new(&cf.coturn)?),
-    })
-}
-
-#[cfg(test)]
-pub mod test {
-    use std::sync::Arc;
-
-    use crate::turn::IceUser;
-
-    use super::*;
-
-    #[derive(Clone, Copy, Debug)]
-    struct TurnAuthServiceMock;
-
-    #[async_trait]
-    impl TurnAuthService for TurnAuthServiceMock {
-        async fn create(
-            &self,
-            _: RoomId,
-            _: PeerId,
-            _: UnreachablePolicy,
-        ) -> Result<Vec<IceUser>, TurnServiceErr> {
-            Ok(vec![IceUser::new_coturn_static(
-                "5.5.5.5:1234".parse().unwrap(),
-                "username".into(),
-                "password".into(),
-            )])
+        conf::ice::Kind::Coturn => {
+            let service: Arc<dyn TurnAuthService> =
+                Arc::new(CoturnService::new(&cf.coturn)?);
+            if cf.coturn.pool_size == 0 {
+                service
+            } else {
+                Arc::new(PooledService::new(service, cf.coturn.pool_size))
+            }
         }
-    }
-
-    pub fn new_turn_auth_service_mock() -> Arc<dyn TurnAuthService> {
-        Arc::new(TurnAuthServiceMock)
-    }
+    })
 }
@@ -0,0 +1,500 @@
+//! Pool of pre-allocated [`IceUser`]s, hiding latency of a slow [TURN]
+//! credentials backend from `Peer`s creation.
+//!
+//! [TURN]: https://webrtcglossary.com/turn
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use futures::channel::{mpsc, oneshot};
+use medea_client_api_proto::{PeerId, RoomId};
+use tokio::sync::Notify;
+
+use crate::{log::prelude::*, utils::MpscOneshotSender};
+
+use super::{IceUser, TurnAuthService, TurnServiceErr, UnreachablePolicy};
+
+/// [`IceUser`]s pre-allocated for a single `Peer` with some
+/// [`UnreachablePolicy`].
+#[derive(Debug)]
+enum Slot {
+    /// [`IceUser`]s are being created at the moment.
+    ///
+    /// Contains a [`oneshot::Sender`] to pass them to once created, if they
+    /// are requested already. [`None`] is passed if their creation fails.
+    Pending(
+        UnreachablePolicy,
+        Option<oneshot::Sender<Option<Vec<IceUser>>>>,
+    ),
+
+    /// [`IceUser`]s are created and ready to be used.
+    Ready(UnreachablePolicy, Vec<IceUser>),
+}
+
+/// Pre-allocated [`IceUser`]s of a single `Room`.
+#[derive(Debug, Default)]
+struct RoomPool {
+    /// [`Slot`]s of the `Peer`s which [`IceUser`]s are pre-allocated for.
+    slots: HashMap<PeerId, Slot>,
+
+    /// [`PeerId`]s which [`IceUser`]s were already requested for and are not
+    /// dropped yet, so must not be pre-allocated again.
+    issued: HashSet<PeerId>,
+}
+
+/// Result of taking a [`Slot`] from a [`RoomPool`].
+enum Taken {
+    /// No [`IceUser`]s were pre-allocated.
+    Nothing,
+
+    /// [`IceUser`]s are being created and will be sent via the
+    /// [`oneshot::Receiver`].
+    Pending(oneshot::Receiver<Option<Vec<IceUser>>>),
+
+    /// [`IceUser`]s are ready to be used.
+    Ready(Vec<IceUser>),
+}
+
+/// Guard of the [`IceUser`]s issued by a [`PooledService`] for some `Peer`,
+/// notifying it once they're dropped.
+#[derive(Debug)]
+pub struct IssuedGuard {
+    /// [`RoomId`] of the `Room` the [`IceUser`]s are issued in.
+    room_id: RoomId,
+
+    /// [`PeerId`] of the `Peer` the [`IceUser`]s are issued for.
+    peer_id: PeerId,
+
+    /// Sender to notify the [`PooledService`] with on [`Drop`].
+    on_drop: Option<MpscOneshotSender<(RoomId, PeerId)>>,
+}
+
+impl Drop for IssuedGuard {
+    fn drop(&mut self) {
+        if let Some(tx) = self.on_drop.take() {
+            let ids = (self.room_id.clone(), self.peer_id);
+            // Receiver is dropped along with the `PooledService`, so there
+            // is nobody to notify anymore.
+            drop(tx.send(ids));
+        }
+    }
+}
+
+/// State of a [`PooledService`] shared with its refilling tasks.
+#[derive(Debug)]
+struct Inner {
+    /// [`TurnAuthService`] actually creating [`IceUser`]s.
+    service: Arc<dyn TurnAuthService>,
+
+    /// Number of [`IceUser`]s kept ready to be used for each `Room`.
+    size: u32,
+
+    /// [`RoomPool`]s of all the `Room`s this [`PooledService`] was used for.
+    rooms: Mutex<HashMap<RoomId, RoomPool>>,
+
+    /// Sender of the `Peer`s which issued [`IceUser`]s are dropped.
+    dropped_tx: mpsc::UnboundedSender<(RoomId, PeerId)>,
+
+    /// Receiver of the `Peer`s which issued [`IceUser`]s are dropped.
+    dropped_rx: Mutex<mpsc::UnboundedReceiver<(RoomId, PeerId)>>,
+
+    /// [`Notify`] waking up the ones waiting for pre-allocated [`IceUser`]s
+    /// to be created.
+    filled: Notify,
+}
+
+impl Inner {
+    /// Forgets the `Peer`s which issued [`IceUser`]s are dropped already, so
+    /// they may be pre-allocated again.
+    fn forget_dropped(&self, rooms: &mut HashMap<RoomId, RoomPool>) {
+        let mut dropped = self.dropped_rx.lock().unwrap();
+        while let Ok(Some((room_id, peer_id))) = dropped.try_next() {
+            if let Some(room) = rooms.get_mut(&room_id) {
+                room.issued.remove(&peer_id);
+            }
+        }
+    }
+
+    /// Takes the [`IceUser`]s pre-allocated for the provided `Peer` with the
+    /// provided [`UnreachablePolicy`], marking them as issued.
+    ///
+    /// [`IceUser`]s pre-allocated with another [`UnreachablePolicy`] are
+    /// discarded.
+    fn take(
+        &self,
+        room_id: &RoomId,
+        peer_id: PeerId,
+        policy: UnreachablePolicy,
+    ) -> Taken {
+        let mut rooms = self.rooms.lock().unwrap();
+        self.forget_dropped(&mut rooms);
+
+        let room = rooms.entry(room_id.clone()).or_default();
+        room.issued.insert(peer_id);
+        match room.slots.remove(&peer_id) {
+            Some(Slot::Ready(p, users)) if p == policy => Taken::Ready(users),
+            Some(Slot::Pending(p, _)) if p == policy => {
+                let (tx, rx) = oneshot::channel();
+                room.slots.insert(peer_id, Slot::Pending(p, Some(tx)));
+                Taken::Pending(rx)
+            }
+            Some(_) | None => Taken::Nothing,
+        }
+    }
+
+    /// Unmarks the provided `Peer` as issued, if no [`IceUser`]s were issued
+    /// for it eventually.
+    fn untake(&self, room_id: &RoomId, peer_id: PeerId) {
+        if let Some(room) = self.rooms.lock().unwrap().get_mut(room_id) {
+            room.issued.remove(&peer_id);
+        }
+    }
+
+    /// Wraps the provided [`IceUser`]s issued for the provided `Peer` with an
+    /// [`IssuedGuard`], so it's unmarked as issued once they're dropped.
+    fn issue(
+        &self,
+        room_id: RoomId,
+        peer_id: PeerId,
+        mut users: Vec<IceUser>,
+    ) -> Vec<IceUser> {
+        if !users.is_empty() {
+            let user = users.remove(0);
+            let guard = IssuedGuard {
+                room_id,
+                peer_id,
+                on_drop: Some(self.dropped_tx.clone().into()),
+            };
+            users.insert(0, IceUser::Pooled(Box::new(user), guard));
+        }
+        users
+    }
+
+    /// Spawns creation of [`IceUser`]s with the provided
+    /// [`UnreachablePolicy`] for the `Peer`s following the provided one,
+    /// which are neither pre-allocated nor issued yet.
+    fn refill(
+        self: &Arc<Self>,
+        room_id: &RoomId,
+        after: PeerId,
+        policy: UnreachablePolicy,
+    ) {
+        let mut ids = Vec::new();
+        {
+            let mut rooms = self.rooms.lock().unwrap();
+            let room = rooms.entry(room_id.clone()).or_default();
+            for id in (1..=self.size).filter_map(|i| after.0.checked_add(i)) {
+                let id = PeerId(id);
+                if !room.issued.contains(&id) && !room.slots.contains_key(&id) {
+                    room.slots.insert(id, Slot::Pending(policy, None));
+                    ids.push(id);
+                }
+            }
+        }
+
+        for peer_id in ids {
+            let this = Arc::clone(self);
+            let room_id = room_id.clone();
+            tokio::spawn(async move {
+                let users = this
+                    .service
+                    .create(room_id.clone(), peer_id, policy)
+                    .await;
+                this.fill(&room_id, peer_id, users);
+            });
+        }
+    }
+
+    /// Puts the created [`IceUser`]s into the [`Slot`] of the provided
+    /// `Peer`, or passes them to the awaiting requester.
+    ///
+    /// Created [`IceUser`]s are dropped if the `Room` is released already, or
+    /// if they were requested with another [`UnreachablePolicy`] meanwhile.
+    fn fill(
+        &self,
+        room_id: &RoomId,
+        peer_id: PeerId,
+        users: Result<Vec<IceUser>, TurnServiceErr>,
+    ) {
+        let users = users
+            .map_err(|e| {
+                warn!(
+                    "Failed to pre-allocate IceUser for Peer [id = {}] in \
+                     Room [id = {}]: {}",
+                    peer_id, room_id, e,
+                );
+            })
+            .ok();
+
+        {
+            let mut rooms = self.rooms.lock().unwrap();
+            if let Some(room) = rooms.get_mut(room_id) {
+                match room.slots.remove(&peer_id) {
+                    Some(Slot::Pending(_, Some(tx))) => {
+                        drop(tx.send(users));
+                    }
+                    Some(Slot::Pending(policy, None)) => {
+                        let is_issued = room.issued.contains(&peer_id);
+                        if let Some(users) = users.filter(|_| !is_issued) {
+                            let slot = Slot::Ready(policy, users);
+                            room.slots.insert(peer_id, slot);
+                        }
+                    }
+                    Some(slot @ Slot::Ready(..)) => {
+                        room.slots.insert(peer_id, slot);
+                    }
+                    None => (),
+                }
+            }
+        }
+        self.filled.notify_waiters();
+    }
+}
+
+/// [`TurnAuthService`] keeping a number of [`IceUser`]s for the upcoming
+/// `Peer`s of each `Room` ready to be used, and refilling them asynchronously
+/// once used.
+///
+/// Pre-allocation for a `Room` starts only after its first successful
+/// allocation, with the [`UnreachablePolicy`] of the last one.
+///
+/// Falls back to the wrapped [`TurnAuthService`] if no [`IceUser`]s are
+/// pre-allocated for a `Peer`.
+#[derive(Debug)]
+pub struct PooledService(Arc<Inner>);
+
+impl PooledService {
+    /// Wraps the provided [`TurnAuthService`] into a [`PooledService`] keeping
+    /// `size` ready-to-use [`IceUser`]s for each `Room`.
+    #[must_use]
+    pub fn new(service: Arc<dyn TurnAuthService>, size: u32) -> Self {
+        let (dropped_tx, dropped_rx) = mpsc::unbounded();
+        Self(Arc::new(Inner {
+            service,
+            size,
+            rooms: Mutex::default(),
+            dropped_tx,
+            dropped_rx: Mutex::new(dropped_rx),
+            filled: Notify::new(),
+        }))
+    }
+}
+
+#[async_trait]
+impl TurnAuthService for PooledService {
+    /// Returns [`IceUser`]s pre-allocated for the provided `Peer` with the
+    /// provided [`UnreachablePolicy`], or creates them with the wrapped
+    /// [`TurnAuthService`] if there are none.
+    ///
+    /// Schedules pre-allocation of [`IceUser`]s for the following `Peer`s of
+    /// the `Room` once succeeded.
+    async fn create(
+        &self,
+        room_id: RoomId,
+        peer_id: PeerId,
+        policy: UnreachablePolicy,
+    ) -> Result<Vec<IceUser>, TurnServiceErr> {
+        let taken = match self.0.take(&room_id, peer_id, policy) {
+            Taken::Ready(users) => Some(users),
+            Taken::Pending(rx) => rx.await.ok().flatten(),
+            Taken::Nothing => None,
+        };
+        let users = if let Some(users) = taken {
+            users
+        } else {
+            match self.0.service.create(room_id.clone(), peer_id, policy).await
+            {
+                Ok(users) => users,
+                Err(e) => {
+                    self.0.untake(&room_id, peer_id);
+                    return Err(e);
+                }
+            }
+        };
+
+        self.0.refill(&room_id, peer_id, policy);
+        Ok(self.0.issue(room_id, peer_id, users))
+    }
+
+    /// Drops all the [`IceUser`]s pre-allocated for the provided `Room`.
+    fn release(&self, room_id: &RoomId) {
+        self.0.rooms.lock().unwrap().remove(room_id);
+        self.0.service.release(room_id);
+    }
+
+    /// Checks health of the wrapped [`TurnAuthService`].
+    #[inline]
+    async fn check_health(&self) -> Result<(), TurnServiceErr> {
+        self.0.service.check_health().await
+    }
+}
+
+#[cfg(test)]
+mod spec {
+    use crate::turn::TurnAuthServiceMock;
+
+    use super::*;
+
+    /// Waits for all the spawned refilling tasks of the provided
+    /// [`PooledService`] to complete.
+    async fn refilled(pool: &PooledService) {
+        loop {
+            let filled = pool.0.filled.notified();
+            let is_pending = pool.0.rooms.lock().unwrap().values().any(|r| {
+                r.slots.values().any(|s| matches!(s, Slot::Pending(..)))
+            });
+            if !is_pending {
+                break;
+            }
+            filled.await;
+        }
+    }
+
+    fn created_for(mock: &TurnAuthServiceMock, peer_id: u32) -> usize {
+        mock.created()
+            .into_iter()
+            .filter(|(_, id)| *id == PeerId(peer_id))
+            .count()
+    }
+
+    fn is_issued(pool: &PooledService, room_id: &RoomId, peer_id: u32) -> bool {
+        pool.0.rooms.lock().unwrap()[room_id]
+            .issued
+            .contains(&PeerId(peer_id))
+    }
+
+    #[actix_rt::test]
+    async fn preallocates_users_for_following_peers() {
+        let mock = Arc::new(TurnAuthServiceMock::default());
+        let pool = PooledService::new(mock.clone(), 2);
+        let room_id = RoomId::from("room");
+
+        pool.create(room_id.clone(), PeerId(0), UnreachablePolicy::Error)
+            .await
+            .unwrap();
+        refilled(&pool).await;
+        assert_eq!(mock.created().len(), 3);
+
+        pool.create(room_id.clone(), PeerId(1), UnreachablePolicy::Error)
+            .await
+            .unwrap();
+        refilled(&pool).await;
+        assert_eq!(created_for(&mock, 1), 1);
+        assert_eq!(created_for(&mock, 3), 1);
+    }
+
+    #[actix_rt::test]
+    async fn never_preallocates_issued_peers() {
+        let mock = Arc::new(TurnAuthServiceMock::default());
+        let pool = PooledService::new(mock.clone(), 2);
+        let room_id = RoomId::from("room");
+
+        let _users = pool
+            .create(room_id.clone(), PeerId(2), UnreachablePolicy::Error)
+            .await
+            .unwrap();
+        pool.create(room_id.clone(), PeerId(1), UnreachablePolicy::Error)
+            .await
+            .unwrap();
+        refilled(&pool).await;
+
+        assert_eq!(created_for(&mock, 2), 1);
+        assert_eq!(created_for(&mock, 3), 1);
+    }
+
+    #[actix_rt::test]
+    async fn forgets_issued_peers_once_users_dropped() {
+        let mock = Arc::new(TurnAuthServiceMock::default());
+        let pool = PooledService::new(mock.clone(), 1);
+        let room_id = RoomId::from("room");
+
+        let users = pool
+            .create(room_id.clone(), PeerId(0), UnreachablePolicy::Error)
+            .await
+            .unwrap();
+        refilled(&pool).await;
+        assert!(is_issued(&pool, &room_id, 0));
+
+        drop(users);
+        pool.create(room_id.clone(), PeerId(1), UnreachablePolicy::Error)
+            .await
+            .unwrap();
+        assert!(!is_issued(&pool, &room_id, 0));
+    }
+
+    #[actix_rt::test]
+    async fn preallocates_users_with_requested_policy() {
+        let mock = Arc::new(TurnAuthServiceMock::default());
+        let pool = PooledService::new(mock.clone(), 1);
+        let room_id = RoomId::from("room");
+
+        pool.create(room_id.clone(), PeerId(0), UnreachablePolicy::Static)
+            .await
+            .unwrap();
+        refilled(&pool).await;
+        assert_eq!(created_for(&mock, 1), 1);
+
+        pool.create(room_id.clone(), PeerId(1), UnreachablePolicy::Error)
+            .await
+            .unwrap();
+        assert_eq!(created_for(&mock, 1), 2);
+    }
+
+    #[actix_rt::test]
+    async fn does_not_preallocate_until_allocation_succeeds() {
+        let mock = Arc::new(TurnAuthServiceMock::default());
+        let pool = PooledService::new(mock.clone(), 2);
+        let room_id = RoomId::from("room");
+
+        mock.set_failing(true);
+        assert!(pool
+            .create(room_id.clone(), PeerId(0), UnreachablePolicy::Error)
+            .await
+            .is_err());
+        refilled(&pool).await;
+
+        assert_eq!(mock.created().len(), 1);
+        assert!(!is_issued(&pool, &room_id, 0));
+    }
+
+    #[actix_rt::test]
+    async fn falls_back_to_service_if_preallocation_fails() {
+        let mock = Arc::new(TurnAuthServiceMock::default());
+        let pool = PooledService::new(mock.clone(), 1);
+        let room_id = RoomId::from("room");
+
+        pool.create(room_id.clone(), PeerId(0), UnreachablePolicy::Error)
+            .await
+            .unwrap();
+        mock.set_failing(true);
+        refilled(&pool).await;
+
+        mock.set_failing(false);
+        pool.create(room_id.clone(), PeerId(1), UnreachablePolicy::Error)
+            .await
+            .unwrap();
+        assert_eq!(created_for(&mock, 1), 2);
+    }
+
+    #[actix_rt::test]
+    async fn drops_users_of_released_room() {
+        let mock = Arc::new(TurnAuthServiceMock::default());
+        let pool = PooledService::new(mock.clone(), 1);
+        let room_id = RoomId::from("room");
+
+        pool.create(room_id.clone(), PeerId(0), UnreachablePolicy::Error)
+            .await
+            .unwrap();
+        refilled(&pool).await;
+        pool.release(&room_id);
+        assert!(pool.0.rooms.lock().unwrap().is_empty());
+
+        pool.create(room_id.clone(), PeerId(1), UnreachablePolicy::Error)
+            .await
+            .unwrap();
+        assert_eq!(created_for(&mock, 1), 2);
+    }
+}
@@ -0,0 +1,143 @@
+//! Helpers making [Medea] behaviour reproducible with the `deterministic`
+//! feature enabled.
+//!
+//! With the feature enabled, random values are produced by a seeded
+//! generator, and collections iterated on timers are processed in the order of
+//! their keys. Without the feature, these helpers have no overhead.
+//!
+//! [Medea]: https://github.com/instrumentisto/medea
+
+use std::collections::HashMap;
+
+use rand::RngCore;
+
+#[cfg(feature = "deterministic")]
+use std::cell::RefCell;
+
+#[cfg(feature = "deterministic")]
+use rand::{rngs::StdRng, SeedableRng as _};
+
+/// Name of the environment variable the seed of the random values generator
+/// is read from.
+#[cfg(feature = "deterministic")]
+pub const SEED_ENV: &str = "MEDEA_SEED";
+
+#[cfg(feature = "deterministic")]
+thread_local! {
+    /// Seeded random values generator of the current thread.
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::seed_from_u64(
+        std::env::var(SEED_ENV)
+            .ok()
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or_default(),
+    ));
+}
+
+/// Reseeds the random values generator of the current thread, so the values
+/// produced after this call are reproducible.
+#[cfg(feature = "deterministic")]
+pub fn set_seed(seed: u64) {
+    RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+}
+
+/// Calls the provided function with a random values generator.
+///
+/// With the `deterministic` feature enabled, the generator is seeded with the
+/// [`SEED_ENV`] environment variable or with the [`set_seed()`].
+#[cfg(feature = "deterministic")]
+pub fn with_rng<T>(f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+    RNG.with(|rng| f(&mut *rng.borrow_mut()))
+}
+
+/// Calls the provided function with a random values generator.
+#[cfg(not(feature = "deterministic"))]
+#[inline]
+pub fn with_rng<T>(f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+    f(&mut rand::thread_rng())
+}
+
+/// Sorts the provided items by the provided `key` if the `deterministic`
+/// feature is enabled, leaving them untouched otherwise.
+#[cfg(feature = "deterministic")]
+pub fn order_by<T, O: Ord>(items: &mut [T], key: impl FnMut(&T) -> O) {
+    items.sort_by_cached_key(key);
+}
+
+/// Sorts the provided items by the provided `key` if the `deterministic`
+/// feature is enabled, leaving them untouched otherwise.
+#[cfg(not(feature = "deterministic"))]
+#[inline]
+pub fn order_by<T, O: Ord>(_: &mut [T], _: impl FnMut(&T) -> O) {}
+
+/// Returns values of the provided [`HashMap`], ordered by the provided `key`
+/// of their entries if the `deterministic` feature is enabled.
+#[cfg(feature = "deterministic")]
+pub fn ordered_values<K, V, O: Ord>(
+    map: &HashMap<K, V>,
+    mut key: impl FnMut(&K) -> O,
+) -> impl Iterator<Item = &V> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by_cached_key(|(k, _)| key(k));
+    entries.into_iter().map(|(_, v)| v)
+}
+
+/// Returns values of the provided [`HashMap`], ordered by the provided `key`
+/// of their entries if the `deterministic` feature is enabled.
+#[cfg(not(feature = "deterministic"))]
+#[inline]
+pub fn ordered_values<K, V, O: Ord>(
+    map: &HashMap<K, V>,
+    _: impl FnMut(&K) -> O,
+) -> impl Iterator<Item = &V> {
+    map.values()
+}
+
+/// Returns mutable values of the provided [`HashMap`], ordered by the
+/// provided `key` of their entries if the `deterministic` feature is enabled.
+#[cfg(feature = "deterministic")]
+pub fn ordered_values_mut<K, V, O: Ord>(
+    map: &mut HashMap<K, V>,
+    mut key: impl FnMut(&K) -> O,
+) -> impl Iterator<Item = &mut V> {
+    let mut entries: Vec<_> = map.iter_mut().collect();
+    entries.sort_by_cached_key(|(k, _)| key(k));
+    entries.into_iter().map(|(_, v)| v)
+}
+
+/// Returns mutable values of the provided [`HashMap`], ordered by the
+/// provided `key` of their entries if the `deterministic` feature is enabled.
+#[cfg(not(feature = "deterministic"))]
+#[inline]
+pub fn ordered_values_mut<K, V, O: Ord>(
+    map: &mut HashMap<K, V>,
+    _: impl FnMut(&K) -> O,
+) -> impl Iterator<Item = &mut V> {
+    map.values_mut()
+}
+
+#[cfg(all(test, feature = "deterministic"))]
+mod spec {
+    use rand::Rng as _;
+
+    use super::*;
+
+    #[test]
+    fn reseeding_reproduces_values() {
+        set_seed(42);
+        let first: Vec<u32> =
+            with_rng(|rng| (0..5).map(|_| rng.gen()).collect());
+        set_seed(42);
+        let second: Vec<u32> =
+            with_rng(|rng| (0..5).map(|_| rng.gen()).collect());
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn orders_values_by_keys() {
+        let map: HashMap<_, _> = (0..32).map(|i| (i, i * 2)).collect();
+
+        let values: Vec<_> = ordered_values(&map, |k| *k).copied().collect();
+        assert_eq!(values, (0..32).map(|i| i * 2).collect::<Vec<_>>());
+    }
+}
@@ -1,15 +1,13 @@
 //! Helper utils used in project.
 
-mod actix_try_join_all;
+pub mod deterministic;
 
 use std::time::Instant;
 
 use chrono::{DateTime, Utc};
 use derive_more::From;
 use futures::channel::mpsc::{TrySendError, UnboundedSender};
-use rand::{distributions::Alphanumeric, Rng};
-
-pub use self::actix_try_join_all::actix_try_join_all;
+use rand::{distributions::Alphanumeric, Rng as _};
 
 /// Creates new [`HashMap`] from a list of key-value pairs.
 ///
@@ -101,11 +99,12 @@ pub fn instant_into_utc(instant: Instant) -> DateTime<Utc> {
 /// Generates random alphanumeric string of the specified `length`.
 #[must_use]
 pub fn generate_token(length: usize) -> String {
-    rand::thread_rng()
-        .sample_iter(&Alphanumeric)
-        .take(length)
-        .map(char::from)
-        .collect()
+    deterministic::with_rng(|rng| {
+        rng.sample_iter(&Alphanumeric)
+            .take(length)
+            .map(char::from)
+            .collect()
+    })
 }
 
 /// Cloneable oneshot sender backed by [`UnboundedSender`].
@@ -75,24 +75,28 @@ async fn callback_test(name: &str, port: u16) -> CallbackTestItem {
 ///
 /// 2. Wait `500ms`.
 ///
-/// 3. Check that test callback server receives `on_join` callback.
+/// 3. Check that test callback server receives `on_join` callback with the
+///    session of the [`TestMember`]'s client.
 #[actix_rt::test]
 #[named]
 async fn on_join() {
     let (_, callback_server) = callback_test(test_name!(), 9096).await;
     sleep(Duration::from_millis(500)).await;
     let callbacks = callback_server.send(GetCallbacks).await.unwrap().unwrap();
-    let on_joins_count = callbacks
+    let on_joins: Vec<_> = callbacks
         .into_iter()
-        .filter(|r| {
-            if let Some(Event::OnJoin(_)) = &r.event {
-                true
+        .filter_map(|r| {
+            if let Some(Event::OnJoin(on_join)) = r.event {
+                Some(on_join)
             } else {
-                false
+                None
             }
         })
-        .count();
-    assert_eq!(on_joins_count, 1);
+        .collect();
+    assert_eq!(on_joins.len(), 1);
+
+    let session = on_joins[0].session.as_ref().unwrap();
+    assert!(session.remote_addr.starts_with("127.0.0.1:"));
 }
 
 /// Checks that `on_leave` callback works on normal client disconnect.
@@ -116,20 +120,23 @@ async fn on_leave_normally_disconnected() {
 
     let callbacks = callback_server.send(GetCallbacks).await.unwrap().unwrap();
 
-    let on_leaves_count = callbacks
+    let on_leaves: Vec<_> = callbacks
         .into_iter()
         .filter_map(|req| {
             if let Some(Event::OnLeave(on_leave)) = req.event {
-                Some(on_leave.reason)
+                Some(on_leave)
             } else {
                 None
             }
         })
-        .filter(|reason| {
-            reason == &(proto::on_leave::Reason::Disconnected as i32)
+        .filter(|on_leave| {
+            on_leave.reason == proto::on_leave::Reason::Disconnected as i32
         })
-        .count();
-    assert_eq!(on_leaves_count, 1);
+        .collect();
+    assert_eq!(on_leaves.len(), 1);
+
+    assert!(on_leaves[0].session.is_some());
+    assert!(on_leaves[0].close_reason.is_empty());
 }
 
 /// Checks that `on_leave` callback works when connection with client was lost.
@@ -6,7 +6,7 @@
 //! [Control API]: https://tinyurl.com/yxsqplq7
 
 use function_name::named;
-use medea::api::control::error_codes::ErrorCode;
+use medea::{api::control::error_codes::ErrorCode, hashmap};
 use medea_control_api_proto::grpc::api::{self as proto, member::Credentials};
 
 use crate::{
@@ -164,6 +164,55 @@ mod member {
         );
     }
 
+    #[actix_rt::test]
+    #[named]
+    async fn member_with_metadata() {
+        let mut client = ControlClient::new().await;
+        client.create(pub_sub_room_req(test_name!())).await;
+
+        let add_member = MemberBuilder::default()
+            .id("test-member")
+            .metadata(hashmap! {
+                String::from("display_name") => String::from("Alice"),
+            })
+            .build()
+            .unwrap()
+            .build_request(test_name!());
+        client.create(add_member).await;
+
+        let member = client.get(&format!("{}/test-member", test_name!())).await;
+        let member = take_member(member);
+        assert_eq!(
+            member.metadata.get("display_name").map(String::as_str),
+            Some("Alice"),
+        );
+    }
+
+    #[actix_rt::test]
+    #[named]
+    async fn member_with_features() {
+        let mut client = ControlClient::new().await;
+        client.create(pub_sub_room_req(test_name!())).await;
+
+        let add_member = MemberBuilder::default()
+            .id("test-member")
+            .features(hashmap! {
+                String::from("delta_sync") => String::from("true"),
+            })
+            .build()
+            .unwrap()
+            .build_request(test_name!());
+        client.create(add_member).await;
+
+        let member = client.get(&format!("{}/test-member", test_name!())).await;
+        let member = take_member(member);
+        assert_eq!(
+            member.features.get("delta_sync").map(String::as_str),
+            Some("true"),
+        );
+        assert!(member.metadata.is_empty());
+    }
+
     #[actix_rt::test]
     #[named]
     async fn cant_create_member_in_non_existent_room() {
@@ -95,7 +95,8 @@ async fn invalid_plain_credentials() {
         .await
         .unwrap(),
         Event::RoomLeft {
-            close_reason: CloseReason::Rejected
+            close_reason: CloseReason::Rejected,
+            retry_after_ms: None,
         }
     );
 }
@@ -118,7 +119,8 @@ async fn invalid_hash_credentials() {
         .await
         .unwrap(),
         Event::RoomLeft {
-            close_reason: CloseReason::Rejected
+            close_reason: CloseReason::Rejected,
+            retry_after_ms: None,
         }
     );
 }
@@ -180,6 +180,7 @@ impl Room {
         proto::CreateRequest {
             parent_fid: uri.into(),
             el: Some(proto::create_request::El::Room(grpc_room)),
+            on_progress: String::new(),
         }
     }
 }
@@ -212,11 +213,18 @@ pub struct Member {
     #[builder(setter(strip_option))]
     on_leave: Option<String>,
     #[builder(default = "None")]
+    #[builder(setter(strip_option))]
+    on_negotiation_timeout: Option<String>,
+    #[builder(default = "None")]
     ping_interval: Option<Duration>,
     #[builder(default = "None")]
     idle_timeout: Option<Duration>,
     #[builder(default = "None")]
     reconnect_timeout: Option<Duration>,
+    #[builder(default = "HashMap::new()")]
+    metadata: HashMap<String, String>,
+    #[builder(default = "HashMap::new()")]
+    features: HashMap<String, String>,
 }
 
 impl Into<proto::Member> for Member {
@@ -232,10 +240,15 @@ impl Into<proto::Member> for Member {
             pipeline,
             on_leave: self.on_leave.unwrap_or_default(),
             on_join: self.on_join.unwrap_or_default(),
+            on_negotiation_timeout: self
+                .on_negotiation_timeout
+                .unwrap_or_default(),
             credentials: self.credentials,
             ping_interval: self.ping_interval.map(Into::into),
             idle_timeout: self.idle_timeout.map(Into::into),
             reconnect_timeout: self.reconnect_timeout.map(Into::into),
+            metadata: self.metadata,
+            features: self.features,
         }
     }
 }
@@ -245,6 +258,7 @@ impl Member {
         proto::CreateRequest {
             parent_fid: url.into(),
             el: Some(proto::create_request::El::Member(self.into())),
+            on_progress: String::new(),
         }
     }
 }
@@ -307,6 +321,7 @@ impl WebRtcPlayEndpoint {
         proto::CreateRequest {
             el: Some(proto::create_request::El::WebrtcPlay(self.into())),
             parent_fid: url.into(),
+            on_progress: String::new(),
         }
     }
 }
@@ -344,6 +359,7 @@ impl WebRtcPublishEndpoint {
         proto::CreateRequest {
             el: Some(proto::create_request::El::WebrtcPub(self.into())),
             parent_fid: url.into(),
+            on_progress: String::new(),
         }
     }
 }
@@ -361,9 +377,11 @@ impl Into<proto::WebRtcPublishEndpoint> for WebRtcPublishEndpoint {
             force_relay: bool::default(),
             audio_settings: Some(AudioSettings {
                 publish_policy: PublishPolicy::Optional as i32,
+                opus: None,
             }),
             video_settings: Some(VideoSettings {
                 publish_policy: PublishPolicy::Optional as i32,
+                display_publish_policy: PublishPolicy::Optional as i32,
             }),
         }
     }
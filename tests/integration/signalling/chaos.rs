@@ -0,0 +1,205 @@
+//! Tests of [`Room`] invariants holding while processing of its messages is
+//! reordered by the chaos mode.
+//!
+//! [`Room`]: medea::signalling::Room
+
+use std::{convert::TryFrom as _, time::Duration};
+
+use medea::{
+    api::control::refs::StatefulFid,
+    conf::Conf,
+    signalling::{room::MessageKind, room_service::DeleteElements},
+    testing::{TestClient, TestServer},
+};
+use medea_client_api_proto::{
+    state, Command, Event, MemberId, NegotiationRole, PeerId, Track,
+};
+use tokio::time::timeout;
+
+/// Spec of a `Room` with `publisher` publishing to `subscriber`.
+const PUB_SUB_SPEC: &str = r#"
+kind: Room
+id: chaos
+spec:
+  pipeline:
+    publisher:
+      kind: Member
+      credentials:
+        plain: test
+      spec:
+        pipeline:
+          publish:
+            kind: WebRtcPublishEndpoint
+            spec:
+              p2p: Always
+    subscriber:
+      kind: Member
+      credentials:
+        plain: test
+      spec:
+        pipeline:
+          play:
+            kind: WebRtcPlayEndpoint
+            spec:
+              src: "local://chaos/publisher/publish"
+"#;
+
+/// Starts a [`TestServer`] with the [`PUB_SUB_SPEC`] `Room` and connects
+/// `publisher` and `subscriber` [`TestClient`]s to it.
+async fn start() -> (TestServer, TestClient, TestClient) {
+    let server = TestServer::start(Conf::default()).await.unwrap();
+    let sids = server.create_room_from_yaml(PUB_SUB_SPEC).await.unwrap();
+    let publisher =
+        TestClient::connect(&sids[&MemberId::from("publisher")].to_string())
+            .await
+            .unwrap();
+    let subscriber =
+        TestClient::connect(&sids[&MemberId::from("subscriber")].to_string())
+            .await
+            .unwrap();
+    (server, publisher, subscriber)
+}
+
+/// Awaits the first [`Event`] received by the provided [`TestClient`], for
+/// which the provided function returns [`Some`], skipping all the other ones.
+///
+/// Returns [`None`] if connection is closed.
+async fn wait_for<T>(
+    client: &mut TestClient,
+    mut f: impl FnMut(Event) -> Option<T>,
+) -> Option<T> {
+    timeout(Duration::from_secs(5), async {
+        while let Some(event) = client.next_event().await.unwrap() {
+            if let Some(found) = f(event) {
+                return Some(found);
+            }
+        }
+        None
+    })
+    .await
+    .unwrap()
+}
+
+/// Awaits [`Event::PeerCreated`] making the provided [`TestClient`] an
+/// offerer, returning [`Command::MakeSdpOffer`] answering to it.
+async fn wait_for_offer(client: &mut TestClient) -> (PeerId, Command) {
+    wait_for(client, |event| match event {
+        Event::PeerCreated {
+            peer_id,
+            negotiation_role: NegotiationRole::Offerer,
+            tracks,
+            ..
+        } => Some((peer_id, make_sdp_offer(peer_id, &tracks))),
+        _ => None,
+    })
+    .await
+    .unwrap()
+}
+
+/// Returns [`Command::MakeSdpOffer`] for the provided `Peer`.
+fn make_sdp_offer(peer_id: PeerId, tracks: &[Track]) -> Command {
+    Command::MakeSdpOffer {
+        peer_id,
+        sdp_offer: "caller_offer".into(),
+        mids: tracks
+            .iter()
+            .enumerate()
+            .map(|(mid, track)| (track.id, mid.to_string()))
+            .collect(),
+        transceivers_statuses: tracks.iter().map(|t| (t.id, true)).collect(),
+    }
+}
+
+/// Deletes the element with the provided FID from the provided
+/// [`TestServer`].
+async fn delete(server: &TestServer, fid: &str) {
+    let mut delete = DeleteElements::new();
+    delete.add_fid(StatefulFid::try_from(fid.to_owned()).unwrap());
+    server
+        .room_service()
+        .send(delete.validate().unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+/// Asks the server for the state of the provided [`TestClient`]'s `Room`.
+async fn synchronize(client: &mut TestClient) -> state::Room {
+    client
+        .send(Command::SynchronizeMe {
+            state: state::Room::default(),
+            last_event_seq: None,
+        })
+        .await
+        .unwrap();
+    wait_for(client, |event| match event {
+        Event::StateSynchronized { state } => Some(state),
+        _ => None,
+    })
+    .await
+    .unwrap()
+}
+
+/// Checks that [`Command`] postponed until its `Peer` is removed by Control
+/// API is ignored, keeping session of the `Member` consistent.
+#[actix_rt::test]
+async fn postponed_command_for_removed_peer_is_ignored() {
+    let (server, mut publisher, _subscriber) = start().await;
+    let (peer_id, offer) = wait_for_offer(&mut publisher).await;
+
+    server
+        .chaos()
+        .delay(MessageKind::Command, Duration::from_millis(300));
+    publisher.send(offer).await.unwrap();
+    delete(&server, "chaos/subscriber").await;
+
+    let removed = wait_for(&mut publisher, |event| match event {
+        Event::PeersRemoved { peer_ids } => Some(peer_ids),
+        _ => None,
+    })
+    .await
+    .unwrap();
+    assert_eq!(removed, vec![peer_id]);
+
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    server.chaos().clear();
+
+    assert!(synchronize(&mut publisher).await.peers.is_empty());
+    server.stop().await;
+}
+
+/// Checks that `Member` deletion postponed until its `Peer`s are negotiating
+/// removes them from both sides.
+#[actix_rt::test]
+async fn postponed_deletion_removes_negotiating_peers() {
+    let (server, mut publisher, mut subscriber) = start().await;
+    let (peer_id, offer) = wait_for_offer(&mut publisher).await;
+
+    server
+        .chaos()
+        .delay(MessageKind::Control, Duration::from_millis(300));
+    delete(&server, "chaos/subscriber").await;
+    publisher.send(offer).await.unwrap();
+
+    let answerer_created = wait_for(&mut subscriber, |event| match event {
+        Event::PeerCreated {
+            negotiation_role: NegotiationRole::Answerer(_),
+            ..
+        } => Some(()),
+        _ => None,
+    })
+    .await;
+    assert!(answerer_created.is_some());
+    let removed = wait_for(&mut publisher, |event| match event {
+        Event::PeersRemoved { peer_ids } => Some(peer_ids),
+        _ => None,
+    })
+    .await
+    .unwrap();
+    assert_eq!(removed, vec![peer_id]);
+    assert!(wait_for(&mut subscriber, |_| None::<()>).await.is_none());
+
+    server.chaos().clear();
+    assert!(synchronize(&mut publisher).await.peers.is_empty());
+    server.stop().await;
+}
@@ -1,11 +1,15 @@
 //! Signalling API E2E tests.
 
 mod add_endpoints_synchronization;
+#[cfg(feature = "testing")]
+mod chaos;
 mod command_validation;
 mod dynamic_endpoints_removal;
 mod ice_restart;
 mod pub_sub_signallng;
 mod rpc_settings;
+#[cfg(feature = "testing")]
+mod state_synchronization;
 mod three_pubs;
 mod track_disable;
 mod track_mute;
@@ -27,9 +31,9 @@ use awc::{
 };
 use futures::{executor, stream::SplitSink, SinkExt as _, StreamExt as _};
 use medea_client_api_proto::{
-    ClientMsg, Command, Credential, Event, IceCandidate, MemberId,
-    NegotiationRole, PeerId, PeerUpdate, RoomId, RpcSettings, ServerMsg, Track,
-    TrackId,
+    ClientCapabilities, ClientMsg, Command, Credential, Event, IceCandidate,
+    MemberId, NegotiationRole, PeerId, PeerUpdate, RoomId, RpcSettings,
+    ServerMsg, Track, TrackId,
 };
 use url::Url;
 
@@ -140,6 +144,7 @@ impl TestMember {
         self.send_command(Command::JoinRoom {
             member_id,
             credential,
+            capabilities: ClientCapabilities::default(),
         });
     }
 
@@ -315,7 +320,7 @@ impl StreamHandler<Result<Frame, WsProtocolError>> for TestMember {
 
             match server_msg {
                 ServerMsg::Ping(id) => self.send_pong(id),
-                ServerMsg::Event { room_id, event } => {
+                ServerMsg::Event { room_id, event, .. } => {
                     assert_eq!(self.room_id, room_id);
                     if self.auto_room_management {
                         if matches!(
@@ -454,6 +459,11 @@ impl StreamHandler<Result<Frame, WsProtocolError>> for TestMember {
                             | Event::ConnectionQualityUpdated { .. }
                             | Event::RoomJoined { .. }
                             | Event::StateSynchronized { .. }
+                            | Event::StateDeltaSynchronized { .. }
+                            | Event::MemberMetadataUpdated { .. }
+                            | Event::AppDataReceived { .. }
+                            | Event::StateChecksum { .. }
+                            | Event::TrackQualityAdvisory { .. }
                             | Event::RoomLeft { .. } => (),
                         }
                     }
@@ -0,0 +1,127 @@
+//! Tests of synchronizing Client State with a Server State via
+//! [`Command::SynchronizeMe`].
+
+use std::time::Duration;
+
+use medea::{
+    conf::Conf,
+    testing::{TestClient, TestServer},
+};
+use medea_client_api_proto::{
+    state, Command, Event, MemberId, NegotiationRole, PeerId,
+};
+use tokio::time::timeout;
+
+/// Spec of a `Room` with `publisher` publishing to `subscriber`.
+const PUB_SUB_SPEC: &str = r#"
+kind: Room
+id: state-sync
+spec:
+  pipeline:
+    publisher:
+      kind: Member
+      credentials:
+        plain: test
+      spec:
+        pipeline:
+          publish:
+            kind: WebRtcPublishEndpoint
+            spec:
+              p2p: Always
+    subscriber:
+      kind: Member
+      credentials:
+        plain: test
+      spec:
+        pipeline:
+          play:
+            kind: WebRtcPlayEndpoint
+            spec:
+              src: "local://state-sync/publisher/publish"
+"#;
+
+/// Awaits the first [`Event`] received by the provided [`TestClient`], for
+/// which the provided function returns [`Some`], skipping all the other ones.
+async fn wait_for<T>(
+    client: &mut TestClient,
+    mut f: impl FnMut(Event) -> Option<T>,
+) -> T {
+    timeout(Duration::from_secs(5), async {
+        while let Some(event) = client.next_event().await.unwrap() {
+            if let Some(found) = f(event) {
+                return found;
+            }
+        }
+        panic!("connection closed unexpectedly")
+    })
+    .await
+    .unwrap()
+}
+
+/// Checks that the full [`state::Room`] is sent for an empty Client State,
+/// and only a delta is sent for a non-empty one.
+#[actix_rt::test]
+async fn sends_delta_relative_to_client_state() {
+    let server = TestServer::start(Conf::default()).await.unwrap();
+    let sids = server.create_room_from_yaml(PUB_SUB_SPEC).await.unwrap();
+    let mut publisher =
+        TestClient::connect(&sids[&MemberId::from("publisher")].to_string())
+            .await
+            .unwrap();
+    let _subscriber =
+        TestClient::connect(&sids[&MemberId::from("subscriber")].to_string())
+            .await
+            .unwrap();
+    let peer_id: PeerId = wait_for(&mut publisher, |event| match event {
+        Event::PeerCreated {
+            peer_id,
+            negotiation_role: NegotiationRole::Offerer,
+            ..
+        } => Some(peer_id),
+        _ => None,
+    })
+    .await;
+
+    publisher
+        .send(Command::SynchronizeMe {
+            state: state::Room::default(),
+            last_event_seq: None,
+        })
+        .await
+        .unwrap();
+    let server_state = wait_for(&mut publisher, |event| match event {
+        Event::StateSynchronized { state } => Some(state),
+        Event::StateDeltaSynchronized { .. } => {
+            panic!("delta is sent for an empty Client State")
+        }
+        _ => None,
+    })
+    .await;
+    assert!(server_state.peers.contains_key(&peer_id));
+
+    let mut client_state = server_state.clone();
+    let peer = client_state.peers.get_mut(&peer_id).unwrap();
+    peer.senders.clear();
+    peer.restart_ice = !peer.restart_ice;
+    publisher
+        .send(Command::SynchronizeMe {
+            state: client_state.clone(),
+            last_event_seq: None,
+        })
+        .await
+        .unwrap();
+    let delta = wait_for(&mut publisher, |event| match event {
+        Event::StateDeltaSynchronized { delta } => Some(delta),
+        Event::StateSynchronized { .. } => {
+            panic!("full state is sent for a non-empty Client State")
+        }
+        _ => None,
+    })
+    .await;
+
+    assert!(delta.added_peers.is_empty());
+    assert!(delta.removed_peers.is_empty());
+    assert_eq!(delta.updated_peers.len(), 1);
+    client_state.apply_delta(delta);
+    assert_eq!(client_state, server_state);
+}
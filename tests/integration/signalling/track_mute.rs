@@ -78,6 +78,8 @@ async fn track_mute_doesnt_renegotiates() {
                 updates[0],
                 PeerUpdate::Updated(TrackPatchEvent {
                     muted: Some(true),
+                    content_hint: None,
+                    degradation_preference: None,
                     id: TrackId(0),
                     enabled_general: None,
                     enabled_individual: None
@@ -103,6 +105,8 @@ async fn track_mute_doesnt_renegotiates() {
                 updates[0],
                 PeerUpdate::Updated(TrackPatchEvent {
                     muted: Some(true),
+                    content_hint: None,
+                    degradation_preference: None,
                     id: TrackId(0),
                     enabled_general: None,
                     enabled_individual: None
@@ -179,6 +183,8 @@ async fn track_mute_with_disable_will_start_renegotiation() {
                 updates[0],
                 PeerUpdate::Updated(TrackPatchEvent {
                     muted: Some(true),
+                    content_hint: None,
+                    degradation_preference: None,
                     id: TrackId(0),
                     enabled_general: Some(false),
                     enabled_individual: Some(false),
@@ -204,6 +210,8 @@ async fn track_mute_with_disable_will_start_renegotiation() {
                 updates[0],
                 PeerUpdate::Updated(TrackPatchEvent {
                     muted: Some(true),
+                    content_hint: None,
+                    degradation_preference: None,
                     id: TrackId(0),
                     enabled_general: Some(false),
                     enabled_individual: None
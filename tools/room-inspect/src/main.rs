@@ -0,0 +1,482 @@
+//! Offline analyzer of [Medea] `Room` state snapshots, dumped via `DumpRoom`
+//! call of its gRPC [Control API].
+//!
+//! [Medea]: https://github.com/instrumentisto/medea
+//! [Control API]: https://tinyurl.com/yxsqplq7
+
+#![forbid(non_ascii_idents, unsafe_code)]
+
+use std::{
+    collections::HashMap, error::Error as StdError, fmt, fs, io, io::Read as _,
+    time::Duration,
+};
+
+use clap::{
+    app_from_crate, crate_authors, crate_description, crate_name,
+    crate_version, Arg,
+};
+use serde::Deserialize;
+
+/// Result of running the analyzer.
+type Result<T = ()> = std::result::Result<T, Box<dyn StdError>>;
+
+/// State of a `Peer`, which is not negotiating at the moment.
+const STABLE: &str = "Stable";
+
+/// Snapshot of a whole `Room` state.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct RoomSnapshot {
+    /// ID of the snapshotted `Room`.
+    room_id: String,
+
+    /// [RFC 3339] date and time the snapshot was taken at.
+    ///
+    /// [RFC 3339]: https://tools.ietf.org/html/rfc3339
+    taken_at: String,
+
+    /// Snapshots of all the `Member`s of the `Room`.
+    members: Vec<MemberSnapshot>,
+
+    /// Snapshots of all the `Peer`s of the `Room`.
+    peers: Vec<PeerSnapshot>,
+
+    /// Summary of the metrics collected for the `Peer`s of the `Room`.
+    metrics: MetricsSnapshot,
+}
+
+/// Snapshot of a `Member` state.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct MemberSnapshot {
+    /// ID of the `Member`.
+    id: String,
+
+    /// Indicator whether the `Member` has an active `RpcConnection`.
+    connected: bool,
+
+    /// IDs of the `WebRtcPublishEndpoint`s of the `Member`.
+    publish_endpoints: Vec<String>,
+
+    /// IDs of the `WebRtcPlayEndpoint`s of the `Member`.
+    play_endpoints: Vec<String>,
+}
+
+/// Snapshot of a `Peer` state.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct PeerSnapshot {
+    /// ID of the `Peer`.
+    id: u32,
+
+    /// ID of the `Member` owning the `Peer`.
+    member_id: String,
+
+    /// ID of the partner `Peer`.
+    partner_peer_id: u32,
+
+    /// ID of the `Member` owning the partner `Peer`.
+    partner_member_id: String,
+
+    /// State of the `Peer`'s state machine.
+    state: String,
+
+    /// Role of the `Peer` in its ongoing negotiation, if any.
+    negotiation_role: Option<String>,
+
+    /// Duration (in milliseconds) of the `Peer`'s ongoing negotiation, if
+    /// any.
+    negotiating_for_ms: Option<u64>,
+
+    /// Indicator whether the `Peer` was created on its `Client`.
+    known_to_remote: bool,
+
+    /// Indicator whether a new negotiation of the `Peer` will be started once
+    /// the ongoing one finishes.
+    renegotiation_scheduled: bool,
+
+    /// Indicator whether an ICE restart is requested for the `Peer`.
+    ice_restart: bool,
+
+    /// Indicator whether all media of the `Peer` is forcibly relayed.
+    force_relayed: bool,
+
+    /// IDs of the tracks sent by the `Peer`.
+    senders: Vec<u32>,
+
+    /// IDs of the tracks received by the `Peer`.
+    receivers: Vec<u32>,
+
+    /// Changes applied to the `Peer`, which its `Client` is not aware of yet.
+    pending_changes: Vec<String>,
+
+    /// Changes scheduled to be applied once the `Peer` is `Stable`.
+    scheduled_changes: Vec<String>,
+
+    /// IDs of the removed tracks, which `Client` hasn't acknowledged
+    /// releasing resources of yet.
+    unacked_removed_tracks: Vec<u32>,
+
+    /// Number of ICE candidates received for the `Peer`.
+    ice_candidates: usize,
+}
+
+/// Summary of the metrics collected for the `Peer`s of a `Room`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct MetricsSnapshot {
+    /// Number of entries kept for calculating metrics of the `Peer`s.
+    entries: usize,
+
+    /// Number of entries evicted due to the configured retention.
+    evicted: u64,
+
+    /// Latest connection quality scores estimated between `Member`s.
+    quality: Vec<QualitySnapshot>,
+}
+
+/// Latest connection quality score estimated between two `Member`s.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct QualitySnapshot {
+    /// ID of the `Member` the score is estimated for.
+    member_id: String,
+
+    /// ID of the partner `Member` the score is estimated with.
+    partner_member_id: String,
+
+    /// Estimated connection quality score.
+    score: String,
+
+    /// Indicator whether the connection was relayed via TURN server.
+    relayed: bool,
+}
+
+/// Problem of a `Room` state found in its snapshot.
+#[derive(Debug)]
+enum Issue {
+    /// `Peer` is negotiating for too long.
+    StuckNegotiation {
+        /// ID of the `Peer`.
+        peer_id: u32,
+
+        /// State of the `Peer`.
+        state: String,
+
+        /// Duration (in milliseconds) of the negotiation.
+        for_ms: u64,
+    },
+
+    /// Partner of a `Peer` is absent in the snapshot.
+    MissingPartner {
+        /// ID of the `Peer`.
+        peer_id: u32,
+
+        /// ID of the absent partner `Peer`.
+        partner_peer_id: u32,
+    },
+
+    /// Both partner `Peer`s wait for the same SDP, so neither of them can
+    /// proceed.
+    Deadlock {
+        /// ID of the first `Peer`.
+        peer_id: u32,
+
+        /// ID of the partner `Peer`.
+        partner_peer_id: u32,
+
+        /// State both the `Peer`s are in.
+        state: String,
+    },
+
+    /// `Stable` `Peer` has changes, which are neither negotiated nor
+    /// scheduled to be.
+    UnnegotiatedChanges {
+        /// ID of the `Peer`.
+        peer_id: u32,
+
+        /// Number of the not negotiated changes.
+        count: usize,
+    },
+
+    /// Disconnected `Member` owns a negotiating `Peer`.
+    DisconnectedNegotiator {
+        /// ID of the `Member`.
+        member_id: String,
+
+        /// ID of the `Peer`.
+        peer_id: u32,
+    },
+
+    /// `Client` hasn't acknowledged releasing resources of removed tracks.
+    UnackedRemovedTracks {
+        /// ID of the `Peer`.
+        peer_id: u32,
+
+        /// IDs of the removed tracks.
+        track_ids: Vec<u32>,
+    },
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StuckNegotiation {
+                peer_id,
+                state,
+                for_ms,
+            } => write!(
+                f,
+                "Peer {} is negotiating for {:.1}s in {} state",
+                peer_id,
+                Duration::from_millis(*for_ms).as_secs_f64(),
+                state,
+            ),
+            Self::MissingPartner {
+                peer_id,
+                partner_peer_id,
+            } => write!(
+                f,
+                "Peer {} refers to absent partner Peer {}",
+                peer_id, partner_peer_id,
+            ),
+            Self::Deadlock {
+                peer_id,
+                partner_peer_id,
+                state,
+            } => write!(
+                f,
+                "Peers {} and {} are both in {} state, so neither can proceed",
+                peer_id, partner_peer_id, state,
+            ),
+            Self::UnnegotiatedChanges { peer_id, count } => write!(
+                f,
+                "Stable Peer {} has {} change(s), but no negotiation is \
+                 scheduled",
+                peer_id, count,
+            ),
+            Self::DisconnectedNegotiator { member_id, peer_id } => write!(
+                f,
+                "Member {} is disconnected, but its Peer {} is negotiating",
+                member_id, peer_id,
+            ),
+            Self::UnackedRemovedTracks { peer_id, track_ids } => write!(
+                f,
+                "Peer {} has unacknowledged removed tracks {:?}",
+                peer_id, track_ids,
+            ),
+        }
+    }
+}
+
+fn main() {
+    let opts = app_from_crate!()
+        .arg(
+            Arg::with_name("file")
+                .help("Room snapshot JSON file to read, or `-` for stdin.")
+                .default_value("-"),
+        )
+        .arg(
+            Arg::with_name("stuck_after")
+                .help("Seconds after which a negotiation is considered stuck.")
+                .default_value("10")
+                .long("stuck-after")
+                .short("s"),
+        )
+        .get_matches();
+
+    let run = || -> Result<bool> {
+        let stuck_after_ms = opts
+            .value_of("stuck_after")
+            .unwrap()
+            .parse::<u64>()?
+            .saturating_mul(1000);
+        let snapshot = read_snapshot(opts.value_of("file").unwrap())?;
+        print_summary(&snapshot);
+        let issues = diagnose(&snapshot, stuck_after_ms);
+        print_issues(&issues);
+        Ok(issues.is_empty())
+    };
+    match run() {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(2),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Reads [`RoomSnapshot`] from the provided file (or stdin if `-`).
+fn read_snapshot(file: &str) -> Result<RoomSnapshot> {
+    let json = if file == "-" {
+        let mut json = String::new();
+        let _ = io::stdin().read_to_string(&mut json)?;
+        json
+    } else {
+        fs::read_to_string(file)?
+    };
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Prints human-readable summary of the provided [`RoomSnapshot`].
+fn print_summary(snapshot: &RoomSnapshot) {
+    println!("Room {} (taken at {})", snapshot.room_id, snapshot.taken_at);
+
+    println!("\nMembers ({}):", snapshot.members.len());
+    for member in &snapshot.members {
+        println!(
+            "  {} [{}] publish: {:?}, play: {:?}",
+            member.id,
+            if member.connected {
+                "connected"
+            } else {
+                "disconnected"
+            },
+            member.publish_endpoints,
+            member.play_endpoints,
+        );
+    }
+
+    println!("\nPeers ({}):", snapshot.peers.len());
+    for peer in &snapshot.peers {
+        print!(
+            "  {} ({}) <-> {} ({}): {}",
+            peer.id,
+            peer.member_id,
+            peer.partner_peer_id,
+            peer.partner_member_id,
+            peer.state,
+        );
+        if let Some(role) = &peer.negotiation_role {
+            print!(" as {}", role);
+        }
+        if let Some(ms) = peer.negotiating_for_ms {
+            print!(" for {}ms", ms);
+        }
+        println!();
+        println!(
+            "    senders: {:?}, receivers: {:?}, ice candidates: {}",
+            peer.senders, peer.receivers, peer.ice_candidates,
+        );
+        let mut flags = Vec::new();
+        if !peer.known_to_remote {
+            flags.push("unknown to remote");
+        }
+        if peer.renegotiation_scheduled {
+            flags.push("renegotiation scheduled");
+        }
+        if peer.ice_restart {
+            flags.push("ICE restart");
+        }
+        if peer.force_relayed {
+            flags.push("force relayed");
+        }
+        if !flags.is_empty() {
+            println!("    flags: {}", flags.join(", "));
+        }
+        for change in &peer.pending_changes {
+            println!("    pending: {}", change);
+        }
+        for change in &peer.scheduled_changes {
+            println!("    scheduled: {}", change);
+        }
+    }
+
+    let metrics = &snapshot.metrics;
+    println!(
+        "\nMetrics: {} entries kept, {} evicted",
+        metrics.entries, metrics.evicted,
+    );
+    for q in &metrics.quality {
+        println!(
+            "  {} -> {}: {}{}",
+            q.member_id,
+            q.partner_member_id,
+            q.score,
+            if q.relayed { " (relayed)" } else { "" },
+        );
+    }
+}
+
+/// Prints the provided [`Issue`]s.
+fn print_issues(issues: &[Issue]) {
+    if issues.is_empty() {
+        println!("\nNo issues found.");
+        return;
+    }
+    println!("\nIssues ({}):", issues.len());
+    for issue in issues {
+        println!("  - {}", issue);
+    }
+}
+
+/// Finds [`Issue`]s of the provided [`RoomSnapshot`], considering
+/// negotiations lasting longer than `stuck_after_ms` as stuck.
+fn diagnose(snapshot: &RoomSnapshot, stuck_after_ms: u64) -> Vec<Issue> {
+    let peers: HashMap<_, _> =
+        snapshot.peers.iter().map(|p| (p.id, p)).collect();
+    let connected: HashMap<_, _> = snapshot
+        .members
+        .iter()
+        .map(|m| (m.id.as_str(), m.connected))
+        .collect();
+
+    let mut issues = Vec::new();
+    for peer in &snapshot.peers {
+        let negotiating = peer.state != STABLE;
+
+        if let Some(for_ms) = peer.negotiating_for_ms {
+            if for_ms > stuck_after_ms {
+                issues.push(Issue::StuckNegotiation {
+                    peer_id: peer.id,
+                    state: peer.state.clone(),
+                    for_ms,
+                });
+            }
+        }
+
+        match peers.get(&peer.partner_peer_id) {
+            None => issues.push(Issue::MissingPartner {
+                peer_id: peer.id,
+                partner_peer_id: peer.partner_peer_id,
+            }),
+            Some(partner) => {
+                if negotiating
+                    && peer.id < partner.id
+                    && peer.state == partner.state
+                {
+                    issues.push(Issue::Deadlock {
+                        peer_id: peer.id,
+                        partner_peer_id: partner.id,
+                        state: peer.state.clone(),
+                    });
+                }
+            }
+        }
+
+        let changes = peer.pending_changes.len() + peer.scheduled_changes.len();
+        if !negotiating && changes > 0 && !peer.renegotiation_scheduled {
+            issues.push(Issue::UnnegotiatedChanges {
+                peer_id: peer.id,
+                count: changes,
+            });
+        }
+
+        if negotiating && connected.get(peer.member_id.as_str()) == Some(&false)
+        {
+            issues.push(Issue::DisconnectedNegotiator {
+                member_id: peer.member_id.clone(),
+                peer_id: peer.id,
+            });
+        }
+
+        if !peer.unacked_removed_tracks.is_empty() {
+            issues.push(Issue::UnackedRemovedTracks {
+                peer_id: peer.id,
+                track_ids: peer.unacked_removed_tracks.clone(),
+            });
+        }
+    }
+    issues
+}